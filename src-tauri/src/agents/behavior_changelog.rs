@@ -0,0 +1,260 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistence of the behavior changelog.
+//!
+//! Changes are appended by the agent and MCP server commands; workflow runs
+//! read the current position at start (see
+//! [`crate::models::behavior_changelog`]).
+
+use crate::db::DBClient;
+use crate::models::behavior_changelog::{BehaviorChange, BehaviorChangeCreate, BehaviorChangeKind};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+/// Serializes appends so that positions are allocated without gaps or duplicates
+static APPEND_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Stored form of a change (`changes` is a JSON string)
+#[derive(Deserialize)]
+struct BehaviorChangeRow {
+    id: String,
+    position: u64,
+    kind: BehaviorChangeKind,
+    #[serde(default)]
+    agent_id: Option<String>,
+    subject: String,
+    summary: String,
+    #[serde(default)]
+    changes: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<BehaviorChangeRow> for BehaviorChange {
+    fn from(row: BehaviorChangeRow) -> Self {
+        Self {
+            id: row.id,
+            position: row.position,
+            kind: row.kind,
+            agent_id: row.agent_id,
+            subject: row.subject,
+            summary: row.summary,
+            changes: serde_json::from_str(&row.changes).unwrap_or_default(),
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// Position of the last recorded change (0 when the changelog is empty).
+pub async fn current_behavior_position(db: &DBClient) -> anyhow::Result<u64> {
+    let rows = db
+        .query_json("SELECT position FROM behavior_change ORDER BY position DESC LIMIT 1")
+        .await?;
+    Ok(rows
+        .first()
+        .and_then(|row| row.get("position"))
+        .and_then(|p| p.as_u64())
+        .unwrap_or(0))
+}
+
+/// Position recorded with a run starting now (None if the changelog cannot be read).
+pub async fn run_behavior_position(db: &DBClient) -> Option<u64> {
+    current_behavior_position(db)
+        .await
+        .map_err(|e| warn!(error = %e, "Failed to read behavior changelog position"))
+        .ok()
+}
+
+/// Appends changes to the changelog, returning the position of the last one.
+pub async fn record_behavior_changes(
+    db: &DBClient,
+    changes: Vec<BehaviorChangeCreate>,
+) -> anyhow::Result<u64> {
+    let _guard = APPEND_LOCK.lock().await;
+    let mut position = current_behavior_position(db).await?;
+
+    for change in changes {
+        position += 1;
+        let id = uuid::Uuid::new_v4().to_string();
+        db.execute_with_params(
+            "CREATE type::thing('behavior_change', $id) CONTENT { id: $id, position: $position, \
+             kind: $kind, agent_id: $agent_id, subject: $subject, summary: $summary, \
+             changes: $changes }",
+            vec![
+                ("id".to_string(), json!(id)),
+                ("position".to_string(), json!(position)),
+                ("kind".to_string(), json!(change.kind)),
+                ("agent_id".to_string(), json!(change.agent_id)),
+                ("subject".to_string(), json!(change.subject)),
+                ("summary".to_string(), json!(change.summary)),
+                (
+                    "changes".to_string(),
+                    json!(serde_json::to_string(&change.changes)?),
+                ),
+            ],
+        )
+        .await?;
+        debug!(position, kind = ?change.kind, subject = %change.subject, "Behavior change recorded");
+    }
+
+    Ok(position)
+}
+
+/// Appends changes, logging instead of failing (the triggering edit is already saved).
+pub async fn record_behavior_changes_best_effort(
+    db: &DBClient,
+    changes: Vec<BehaviorChangeCreate>,
+) {
+    if changes.is_empty() {
+        return;
+    }
+    if let Err(e) = record_behavior_changes(db, changes).await {
+        warn!(error = %e, "Failed to record behavior changes");
+    }
+}
+
+/// Lists changes, newest first.
+///
+/// # Arguments
+/// * `agent_id` - Only changes of this agent and changes affecting every agent (MCP servers)
+/// * `after_position` - Only changes after this position (exclusive)
+/// * `until_position` - Only changes up to this position (inclusive)
+pub async fn list_behavior_changes(
+    db: &DBClient,
+    agent_id: Option<&str>,
+    after_position: Option<u64>,
+    until_position: Option<u64>,
+    limit: usize,
+) -> anyhow::Result<Vec<BehaviorChange>> {
+    let mut conditions = Vec::new();
+    let mut params = vec![("limit".to_string(), json!(limit))];
+    if let Some(agent_id) = agent_id {
+        conditions.push("(agent_id = $agent_id OR agent_id IS NONE)");
+        params.push(("agent_id".to_string(), json!(agent_id)));
+    }
+    if let Some(after) = after_position {
+        conditions.push("position > $after");
+        params.push(("after".to_string(), json!(after)));
+    }
+    if let Some(until) = until_position {
+        conditions.push("position <= $until");
+        params.push(("until".to_string(), json!(until)));
+    }
+    let filter = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT meta::id(id) AS id, position, kind, agent_id, subject, summary, changes, \
+                 created_at FROM behavior_change{} ORDER BY position DESC LIMIT $limit",
+                filter
+            ),
+            params,
+        )
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(
+            |row| match serde_json::from_value::<BehaviorChangeRow>(row) {
+                Ok(row) => Some(row.into()),
+                Err(e) => {
+                    warn!(error = %e, "Skipping malformed behavior change record");
+                    None
+                }
+            },
+        )
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::behavior_changelog::BehaviorFieldChange;
+    use tempfile::tempdir;
+
+    fn change(kind: BehaviorChangeKind, agent_id: Option<&str>) -> BehaviorChangeCreate {
+        BehaviorChangeCreate {
+            kind,
+            agent_id: agent_id.map(str::to_string),
+            subject: "subject".to_string(),
+            summary: "summary".to_string(),
+            changes: vec![BehaviorFieldChange {
+                field: "system_prompt".to_string(),
+                before: Some("old".to_string()),
+                after: Some("new".to_string()),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_list_behavior_changes() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("behavior_changelog_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        assert_eq!(current_behavior_position(&db).await.unwrap(), 0);
+        let last = record_behavior_changes(
+            &db,
+            vec![
+                change(BehaviorChangeKind::PromptChanged, Some("agent1")),
+                change(BehaviorChangeKind::McpServerUpdated, None),
+            ],
+        )
+        .await
+        .unwrap();
+        assert_eq!(last, 2);
+        record_behavior_changes(
+            &db,
+            vec![change(BehaviorChangeKind::ToolsChanged, Some("agent2"))],
+        )
+        .await
+        .unwrap();
+        assert_eq!(current_behavior_position(&db).await.unwrap(), 3);
+
+        let all = list_behavior_changes(&db, None, None, None, 10)
+            .await
+            .unwrap();
+        let positions: Vec<u64> = all.iter().map(|c| c.position).collect();
+        assert_eq!(positions, vec![3, 2, 1]);
+        assert_eq!(all[2].changes[0].after.as_deref(), Some("new"));
+
+        // Agent filter keeps the global MCP server change
+        let agent1 = list_behavior_changes(&db, Some("agent1"), None, None, 10)
+            .await
+            .unwrap();
+        let kinds: Vec<BehaviorChangeKind> = agent1.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                BehaviorChangeKind::McpServerUpdated,
+                BehaviorChangeKind::PromptChanged
+            ]
+        );
+
+        let window = list_behavior_changes(&db, None, Some(1), Some(2), 10)
+            .await
+            .unwrap();
+        assert_eq!(window.len(), 1);
+        assert_eq!(window[0].position, 2);
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistence of the tool loop checkpoints of workflow executions
+//! (see [`crate::models::checkpoint`]).
+//!
+//! The loop state is stored as a JSON string, encrypted for a sensitive
+//! workflow; the summary fields used to list resumable executions stay plain.
+
+use crate::db::DBClient;
+use crate::models::checkpoint::{CheckpointState, ResumableExecution, WorkflowCheckpoint};
+use crate::security::WORKFLOW_VAULT;
+use serde::Deserialize;
+use serde_json::json;
+use tracing::{debug, warn};
+
+/// Stored checkpoint record
+#[derive(Debug, Deserialize)]
+struct CheckpointRecord {
+    workflow_id: String,
+    agent_id: String,
+    iteration: usize,
+    state: String,
+}
+
+/// Saves the checkpoint of a workflow, replacing the previous one.
+pub async fn save_checkpoint(db: &DBClient, checkpoint: &WorkflowCheckpoint) -> Result<(), String> {
+    let state = serde_json::to_string(&checkpoint.state)
+        .map_err(|e| format!("Failed to serialize checkpoint: {}", e))?;
+    let state = WORKFLOW_VAULT
+        .seal(db, &checkpoint.workflow_id, &state)
+        .await?;
+    let pending_tools: Vec<&str> = checkpoint
+        .state
+        .pending_tool_calls
+        .iter()
+        .map(|call| call.name.as_str())
+        .collect();
+
+    db.execute_with_params(
+        "UPSERT type::thing('workflow_checkpoint', $workflow_id) SET workflow_id = $workflow_id, \
+         agent_id = $agent_id, iteration = $iteration, pending_tools = $pending_tools, \
+         state = $state, updated_at = time::now()",
+        vec![
+            ("workflow_id".to_string(), json!(checkpoint.workflow_id)),
+            ("agent_id".to_string(), json!(checkpoint.agent_id)),
+            ("iteration".to_string(), json!(checkpoint.iteration)),
+            ("pending_tools".to_string(), json!(pending_tools)),
+            ("state".to_string(), json!(state)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to save checkpoint: {}", e))?;
+
+    debug!(
+        workflow_id = %checkpoint.workflow_id,
+        iteration = checkpoint.iteration,
+        pending_tools = pending_tools.len(),
+        "Checkpoint saved"
+    );
+    Ok(())
+}
+
+/// Loads the checkpoint of a workflow.
+///
+/// # Errors
+/// The workflow is sensitive and locked, or the checkpoint is corrupted.
+pub async fn load_checkpoint(
+    db: &DBClient,
+    workflow_id: &str,
+) -> Result<Option<WorkflowCheckpoint>, String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT workflow_id, agent_id, iteration, state \
+             FROM type::thing('workflow_checkpoint', $workflow_id)",
+            vec![("workflow_id".to_string(), json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load checkpoint: {}", e))?;
+    let Some(row) = rows.into_iter().next() else {
+        return Ok(None);
+    };
+
+    let record: CheckpointRecord =
+        serde_json::from_value(row).map_err(|e| format!("Invalid checkpoint: {}", e))?;
+    let state = WORKFLOW_VAULT.decrypt(&record.workflow_id, &record.state)?;
+    let state: CheckpointState =
+        serde_json::from_str(&state).map_err(|e| format!("Invalid checkpoint state: {}", e))?;
+    Ok(Some(WorkflowCheckpoint {
+        workflow_id: record.workflow_id,
+        agent_id: record.agent_id,
+        iteration: record.iteration,
+        state,
+    }))
+}
+
+/// Lists the interrupted executions, most recent first.
+pub async fn list_checkpoints(db: &DBClient) -> Result<Vec<ResumableExecution>, String> {
+    let rows = db
+        .query_json(
+            "SELECT workflow_id, agent_id, iteration, pending_tools, updated_at \
+             FROM workflow_checkpoint ORDER BY updated_at DESC",
+        )
+        .await
+        .map_err(|e| format!("Failed to list checkpoints: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match serde_json::from_value(row) {
+            Ok(execution) => Some(execution),
+            Err(e) => {
+                warn!(error = %e, "Skipping malformed checkpoint record");
+                None
+            }
+        })
+        .collect())
+}
+
+/// Deletes the checkpoint of a workflow (the execution ended or was discarded).
+pub async fn clear_checkpoint(db: &DBClient, workflow_id: &str) -> Result<(), String> {
+    db.delete(&format!("workflow_checkpoint:{}", workflow_id))
+        .await
+        .map_err(|e| format!("Failed to delete checkpoint: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::function_calling::FunctionCall;
+    use tempfile::tempdir;
+
+    fn checkpoint(workflow_id: &str, iteration: usize) -> WorkflowCheckpoint {
+        WorkflowCheckpoint {
+            workflow_id: workflow_id.to_string(),
+            agent_id: "agent1".to_string(),
+            iteration,
+            state: CheckpointState {
+                task: "List the files".to_string(),
+                context: json!({ "locale": "en" }),
+                messages: vec![
+                    json!({ "role": "system", "content": "You are helpful" }),
+                    json!({ "role": "user", "content": "List the files" }),
+                ],
+                pending_tool_calls: vec![FunctionCall {
+                    id: "call_1".to_string(),
+                    name: "mcp__fs__list".to_string(),
+                    arguments: json!({ "path": "/" }),
+                }],
+                first_message: true,
+                tokens_input: 120,
+                tokens_output: 30,
+                tools_used: vec![],
+                mcp_calls: vec![],
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_load_and_clear_checkpoint() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("checkpoint_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        assert_eq!(load_checkpoint(&db, "wf1").await.unwrap(), None);
+
+        save_checkpoint(&db, &checkpoint("wf1", 1)).await.unwrap();
+        // A later step replaces the checkpoint
+        let latest = checkpoint("wf1", 2);
+        save_checkpoint(&db, &latest).await.unwrap();
+        save_checkpoint(&db, &checkpoint("wf2", 1)).await.unwrap();
+
+        assert_eq!(load_checkpoint(&db, "wf1").await.unwrap(), Some(latest));
+
+        let executions = list_checkpoints(&db).await.unwrap();
+        assert_eq!(executions.len(), 2);
+        let wf1 = executions.iter().find(|e| e.workflow_id == "wf1").unwrap();
+        assert_eq!(wf1.iteration, 2);
+        assert_eq!(wf1.pending_tools, vec!["mcp__fs__list".to_string()]);
+
+        clear_checkpoint(&db, "wf1").await.unwrap();
+        assert_eq!(load_checkpoint(&db, "wf1").await.unwrap(), None);
+        assert_eq!(list_checkpoints(&db).await.unwrap().len(), 1);
+    }
+}
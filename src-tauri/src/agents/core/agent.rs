@@ -70,6 +70,23 @@ pub struct ReasoningStepData {
     pub duration_ms: u64,
 }
 
+/// Single LLM call of the tool loop, for execution trace persistence
+#[derive(Debug, Clone)]
+pub struct LlmTurnData {
+    /// Iteration number in the tool loop (matches `ToolExecutionData::iteration`)
+    pub iteration: u32,
+    /// Input tokens of this call
+    pub tokens_input: usize,
+    /// Output tokens of this call
+    pub tokens_output: usize,
+    /// LLM call duration in milliseconds
+    pub duration_ms: u64,
+    /// Number of tool calls requested by this call
+    pub tool_calls: usize,
+    /// Unix time (milliseconds) at which the call started
+    pub started_at_ms: u64,
+}
+
 /// Detailed tool execution data for persistence
 #[derive(Debug, Clone)]
 pub struct ToolExecutionData {
@@ -91,10 +108,12 @@ pub struct ToolExecutionData {
     pub duration_ms: u64,
     /// Iteration number in the tool loop
     pub iteration: u32,
+    /// Unix time (milliseconds) at which the execution started
+    pub started_at_ms: u64,
 }
 
 /// Metrics collected during task execution
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ReportMetrics {
     /// Duration in milliseconds
     pub duration_ms: u64,
@@ -110,6 +129,8 @@ pub struct ReportMetrics {
     pub tool_executions: Vec<ToolExecutionData>,
     /// Intermediate reasoning steps collected during execution
     pub reasoning_steps: Vec<ReasoningStepData>,
+    /// LLM calls of the tool loop, in order
+    pub llm_turns: Vec<LlmTurnData>,
 }
 
 /// Agent trait - unified interface for all agents
@@ -195,6 +195,10 @@ mod tests {
                     system_prompt: "Test prompt".to_string(),
                     max_tool_iterations: 50,
                     enable_thinking: true,
+                    tool_settings: Default::default(),
+                    mcp_tool_policy: Default::default(),
+                    regressions: Default::default(),
+                    moderation: Default::default(),
                 },
                 delay_ms,
             }
@@ -219,6 +223,7 @@ mod tests {
                     mcp_calls: vec![],
                     tool_executions: vec![],
                     reasoning_steps: vec![],
+                    llm_turns: vec![],
                 },
                 system_prompt: None,
                 tools_json: None,
@@ -273,6 +278,10 @@ mod tests {
                     system_prompt: "Test prompt".to_string(),
                     max_tool_iterations: 50,
                     enable_thinking: true,
+                    tool_settings: Default::default(),
+                    mcp_tool_policy: Default::default(),
+                    regressions: Default::default(),
+                    moderation: Default::default(),
                 },
             }
         }
@@ -72,6 +72,32 @@ impl AgentRegistry {
         ids
     }
 
+    /// Replaces a registered agent in a single write operation (hot-reload).
+    ///
+    /// Used when an agent configuration is updated: the new instance is visible
+    /// to the next `get()` call, while executions already holding the previous
+    /// instance finish with the old configuration. Unlike `unregister_any` +
+    /// `register`, there is no window where the agent is missing.
+    ///
+    /// # Returns
+    /// The previously registered agent, or None if the ID was not registered
+    #[instrument(name = "registry_replace", skip(self, agent), fields(agent_id = %id))]
+    pub async fn replace(&self, id: &str, agent: Arc<dyn Agent>) -> Option<Arc<dyn Agent>> {
+        let mut agents = self.agents.write().await;
+        let previous = agents.insert(id.to_string(), agent);
+
+        if previous.is_some() {
+            info!("Agent reloaded");
+        } else {
+            info!(
+                total_agents = agents.len(),
+                "Agent registered (no previous instance)"
+            );
+        }
+
+        previous
+    }
+
     /// Unregisters an agent (temporary only).
     ///
     /// This method is used by SpawnAgentTool to cleanup sub-agents after execution.
@@ -175,6 +201,10 @@ mod tests {
                     system_prompt: "Test prompt".to_string(),
                     max_tool_iterations: 50,
                     enable_thinking: true,
+                    tool_settings: Default::default(),
+                    mcp_tool_policy: Default::default(),
+                    regressions: Default::default(),
+                    moderation: Default::default(),
                 },
             }
         }
@@ -196,6 +226,7 @@ mod tests {
                     mcp_calls: vec![],
                     tool_executions: vec![],
                     reasoning_steps: vec![],
+                    llm_turns: vec![],
                 },
                 system_prompt: None,
                 tools_json: None,
@@ -325,6 +356,32 @@ mod tests {
         assert_eq!(remaining[0], "perm");
     }
 
+    #[tokio::test]
+    async fn test_registry_replace() {
+        let registry = AgentRegistry::new();
+        let original = Arc::new(TestAgent::new("agent", Lifecycle::Permanent));
+        registry.register("agent".to_string(), original).await;
+
+        // Held instance keeps the old config after replacement
+        let held = registry.get("agent").await.unwrap();
+
+        let mut updated = TestAgent::new("agent", Lifecycle::Permanent);
+        updated.config.system_prompt = "Updated prompt".to_string();
+        let previous = registry.replace("agent", Arc::new(updated)).await;
+
+        assert!(previous.is_some());
+        assert_eq!(
+            registry.get("agent").await.unwrap().config().system_prompt,
+            "Updated prompt"
+        );
+        assert_ne!(held.config().system_prompt, "Updated prompt");
+        assert_eq!(registry.list().await.len(), 1);
+
+        let fresh = Arc::new(TestAgent::new("new_agent", Lifecycle::Permanent));
+        assert!(registry.replace("new_agent", fresh).await.is_none());
+        assert!(registry.get("new_agent").await.is_some());
+    }
+
     #[tokio::test]
     async fn test_registry_default() {
         let registry = AgentRegistry::default();
@@ -0,0 +1,401 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Agent health probing.
+//!
+//! Verifies the runtime dependencies of an agent configuration without
+//! calling the LLM: provider configuration, model availability, local tools
+//! and MCP server status. The resulting [`AgentHealthReport`] lets the UI
+//! surface problems before a workflow is executed.
+
+use crate::db::DBClient;
+use crate::llm::{ProviderManager, ProviderType};
+use crate::mcp::MCPManager;
+use crate::models::health::{AgentHealthReport, HealthCheck, HealthCheckKind, HealthStatus};
+use crate::models::mcp::MCPServerStatus;
+use crate::models::AgentConfig;
+use crate::plugins::{self, PLUGIN_HOST};
+use crate::tools::macros;
+use crate::tools::registry::TOOL_REGISTRY;
+use crate::tools::script;
+use serde_json::json;
+use tracing::{info, warn};
+
+/// Probes every dependency of an agent configuration.
+///
+/// # Arguments
+/// * `config` - Agent configuration to probe
+/// * `provider_manager` - Provider manager holding provider configuration
+/// * `db` - Database client (model catalog, custom and macro tools)
+/// * `mcp_manager` - MCP manager (None = MCP servers reported as unavailable)
+pub async fn probe_agent_health(
+    config: &AgentConfig,
+    provider_manager: &ProviderManager,
+    db: &DBClient,
+    mcp_manager: Option<&MCPManager>,
+) -> AgentHealthReport {
+    let mut checks = Vec::new();
+
+    match config.llm.provider.parse::<ProviderType>() {
+        Ok(provider) => {
+            checks.push(check_provider(
+                &config.llm.provider,
+                provider.clone(),
+                provider_manager,
+            ));
+            checks.push(check_model(&config.llm.model, provider, provider_manager, db).await);
+        }
+        Err(e) => checks.push(HealthCheck::new(
+            HealthCheckKind::Provider,
+            &config.llm.provider,
+            HealthStatus::Error,
+            format!("Invalid provider: {}", e),
+        )),
+    }
+
+    for tool in &config.tools {
+        checks.push(check_tool(tool, db).await);
+    }
+    checks.extend(check_mcp_servers(&config.mcp_servers, mcp_manager).await);
+
+    let report = AgentHealthReport::new(config.id.clone(), checks);
+    info!(
+        agent_id = %config.id,
+        status = ?report.status,
+        checks = report.checks.len(),
+        "Agent health probed"
+    );
+    report
+}
+
+/// Checks that the provider is configured (API key or server URL set).
+fn check_provider(
+    name: &str,
+    provider: ProviderType,
+    provider_manager: &ProviderManager,
+) -> HealthCheck {
+    if provider_manager.is_provider_configured(provider) {
+        HealthCheck::new(
+            HealthCheckKind::Provider,
+            name,
+            HealthStatus::Ok,
+            "Provider configured",
+        )
+    } else {
+        HealthCheck::new(
+            HealthCheckKind::Provider,
+            name,
+            HealthStatus::Error,
+            "Provider is not configured",
+        )
+    }
+}
+
+/// Checks that the model exists in the model catalog.
+///
+/// Falls back to the provider's built-in model list when the catalog
+/// does not contain the model (reported as a warning).
+async fn check_model(
+    model: &str,
+    provider: ProviderType,
+    provider_manager: &ProviderManager,
+    db: &DBClient,
+) -> HealthCheck {
+    let query = "SELECT meta::id(id) AS id FROM llm_model \
+                 WHERE api_name = $api_name AND provider = $provider LIMIT 1";
+    let params = vec![
+        ("api_name".to_string(), json!(model)),
+        (
+            "provider".to_string(),
+            json!(provider.to_string().to_lowercase()),
+        ),
+    ];
+
+    let in_catalog = match db.query_json_with_params(query, params).await {
+        Ok(rows) => !rows.is_empty(),
+        Err(e) => {
+            warn!(error = %e, model = %model, "Failed to query model catalog");
+            false
+        }
+    };
+
+    if in_catalog {
+        HealthCheck::new(
+            HealthCheckKind::Model,
+            model,
+            HealthStatus::Ok,
+            "Model found in catalog",
+        )
+    } else if provider_manager
+        .get_available_models(provider.clone())
+        .iter()
+        .any(|m| m == model)
+    {
+        HealthCheck::new(
+            HealthCheckKind::Model,
+            model,
+            HealthStatus::Warning,
+            "Model not in catalog but supported by the provider",
+        )
+    } else {
+        HealthCheck::new(
+            HealthCheckKind::Model,
+            model,
+            HealthStatus::Error,
+            format!("Model not found for provider '{}'", provider),
+        )
+    }
+}
+
+/// Checks that a local tool is known to the tool registry, that plugin
+/// tools are installed, that custom and macro tools exist and are enabled,
+/// and that the tools of macro steps are available.
+async fn check_tool(tool: &str, db: &DBClient) -> HealthCheck {
+    if plugins::plugin_id(tool).is_some_and(|id| !PLUGIN_HOST.is_installed(id)) {
+        return HealthCheck::new(
+            HealthCheckKind::Tool,
+            tool,
+            HealthStatus::Error,
+            "Plugin not installed",
+        );
+    }
+    if let Some(name) = script::custom_name(tool) {
+        let message = match script::store::find_custom_tool(db, name).await {
+            Ok(Some(custom_tool)) if custom_tool.enabled => None,
+            Ok(Some(_)) => Some("Custom tool disabled".to_string()),
+            Ok(None) => Some("Custom tool not found".to_string()),
+            Err(e) => Some(e),
+        };
+        if let Some(message) = message {
+            return HealthCheck::new(HealthCheckKind::Tool, tool, HealthStatus::Error, message);
+        }
+    }
+    if let Some(name) = macros::macro_name(tool) {
+        let steps = match macros::store::find_tool_macro(db, name).await {
+            Ok(Some(tool_macro)) if tool_macro.enabled => Ok(tool_macro.steps),
+            Ok(Some(_)) => Err("Macro tool disabled".to_string()),
+            Ok(None) => Err("Macro tool not found".to_string()),
+            Err(e) => Err(e),
+        };
+        let steps = match steps {
+            Ok(steps) => steps,
+            Err(message) => {
+                return HealthCheck::new(HealthCheckKind::Tool, tool, HealthStatus::Error, message)
+            }
+        };
+        for step in &steps {
+            // Macros cannot call macros, which bounds the recursion
+            let message = if macros::is_step_tool(&step.tool) {
+                let check = Box::pin(check_tool(&step.tool, db)).await;
+                (check.status == HealthStatus::Error).then_some(check.message)
+            } else {
+                Some("Tool cannot be used in a macro".to_string())
+            };
+            if let Some(message) = message {
+                return HealthCheck::new(
+                    HealthCheckKind::Tool,
+                    tool,
+                    HealthStatus::Error,
+                    format!("Step '{}' ({}): {}", step.id, step.tool, message),
+                );
+            }
+        }
+    }
+
+    if TOOL_REGISTRY.has_tool(tool) {
+        HealthCheck::new(
+            HealthCheckKind::Tool,
+            tool,
+            HealthStatus::Ok,
+            "Tool available",
+        )
+    } else {
+        HealthCheck::new(
+            HealthCheckKind::Tool,
+            tool,
+            HealthStatus::Error,
+            "Unknown tool",
+        )
+    }
+}
+
+/// Checks that every MCP server used by the agent is running.
+async fn check_mcp_servers(
+    server_names: &[String],
+    mcp_manager: Option<&MCPManager>,
+) -> Vec<HealthCheck> {
+    if server_names.is_empty() {
+        return Vec::new();
+    }
+
+    let servers = match mcp_manager {
+        Some(manager) => match manager.list_servers().await {
+            Ok(servers) => servers,
+            Err(e) => {
+                warn!(error = %e, "Failed to list MCP servers");
+                return server_names
+                    .iter()
+                    .map(|name| {
+                        HealthCheck::new(
+                            HealthCheckKind::McpServer,
+                            name,
+                            HealthStatus::Warning,
+                            format!("Could not verify server status: {}", e),
+                        )
+                    })
+                    .collect();
+            }
+        },
+        None => Vec::new(),
+    };
+
+    server_names
+        .iter()
+        .map(
+            |name| match servers.iter().find(|s| &s.config.name == name) {
+                Some(server) if server.status == MCPServerStatus::Running => HealthCheck::new(
+                    HealthCheckKind::McpServer,
+                    name,
+                    HealthStatus::Ok,
+                    "Server running",
+                ),
+                Some(server) => HealthCheck::new(
+                    HealthCheckKind::McpServer,
+                    name,
+                    HealthStatus::Error,
+                    format!("Server is not running (status: {})", server.status),
+                ),
+                None => HealthCheck::new(
+                    HealthCheckKind::McpServer,
+                    name,
+                    HealthStatus::Error,
+                    "Server is not configured",
+                ),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{LLMConfig, Lifecycle};
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn test_config() -> AgentConfig {
+        AgentConfig {
+            id: "health_agent".to_string(),
+            name: "Health Agent".to_string(),
+            lifecycle: Lifecycle::Permanent,
+            llm: LLMConfig {
+                provider: "Mistral".to_string(),
+                model: "mistral-large-latest".to_string(),
+                temperature: 0.7,
+                max_tokens: 1000,
+            },
+            tools: vec!["MemoryTool".to_string(), "UnknownTool".to_string()],
+            mcp_servers: vec!["missing_server".to_string()],
+            system_prompt: "You are a helpful assistant.".to_string(),
+            max_tool_iterations: 10,
+            enable_thinking: false,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_probe_reports_each_dependency() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("health_db");
+        let db = Arc::new(
+            DBClient::new(db_path.to_str().unwrap())
+                .await
+                .expect("Failed to create DB"),
+        );
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        let report = probe_agent_health(&test_config(), &ProviderManager::new(), &db, None).await;
+
+        assert_eq!(report.agent_id, "health_agent");
+        assert!(!report.healthy);
+        assert_eq!(report.checks.len(), 5);
+
+        let status_of = |kind: HealthCheckKind, target: &str| {
+            report
+                .checks
+                .iter()
+                .find(|c| c.kind == kind && c.target == target)
+                .map(|c| c.status)
+        };
+        assert_eq!(
+            status_of(HealthCheckKind::Provider, "Mistral"),
+            Some(HealthStatus::Error)
+        );
+        // Empty catalog, but the model is in Mistral's built-in list
+        assert_eq!(
+            status_of(HealthCheckKind::Model, "mistral-large-latest"),
+            Some(HealthStatus::Warning)
+        );
+        assert_eq!(
+            status_of(HealthCheckKind::Tool, "MemoryTool"),
+            Some(HealthStatus::Ok)
+        );
+        assert_eq!(
+            status_of(HealthCheckKind::Tool, "UnknownTool"),
+            Some(HealthStatus::Error)
+        );
+        assert_eq!(
+            status_of(HealthCheckKind::McpServer, "missing_server"),
+            Some(HealthStatus::Error)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_probe_model_found_in_catalog() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("health_catalog_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        db.execute(
+            "CREATE llm_model:custom_model CONTENT { id: 'custom_model', provider: 'mistral', name: 'Custom', \
+             api_name: 'my-custom-model', context_window: 32000, max_output_tokens: 4096, \
+             temperature_default: 0.7, is_builtin: false, is_reasoning: false }",
+        )
+        .await
+        .unwrap();
+
+        let check = check_model(
+            "my-custom-model",
+            ProviderType::Mistral,
+            &ProviderManager::new(),
+            &db,
+        )
+        .await;
+        assert_eq!(check.status, HealthStatus::Ok);
+
+        let check = check_model(
+            "does-not-exist",
+            ProviderType::Mistral,
+            &ProviderManager::new(),
+            &db,
+        )
+        .await;
+        assert_eq!(check.status, HealthStatus::Error);
+    }
+}
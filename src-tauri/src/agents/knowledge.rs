@@ -0,0 +1,481 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Knowledge pack retrieval.
+//!
+//! Documents added to a knowledge pack are split into chunks (embedded when an
+//! embedding service is configured). When an agent with attached packs runs,
+//! the chunks and curated memories of those packs only are ranked against the
+//! task and the best ones are added to its system prompt. Chunks without
+//! embedding, or all chunks without embedding service, are ranked by keyword
+//! overlap.
+
+use crate::db::DBClient;
+use crate::llm::embedding::EmbeddingService;
+use crate::models::knowledge_pack::{KnowledgeChunk, KnowledgePack};
+use crate::security::workflow_vault::ENCRYPTED_PREFIX;
+use crate::tools::constants::knowledge as knowledge_const;
+use crate::tools::memory::expiration_filter;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Fields selected when loading packs (documents are loaded separately)
+pub const PACK_SELECT_FIELDS: &str =
+    "meta::id(id) AS id, name, description, agent_ids, memory_ids, created_at, updated_at";
+
+/// Splits a document into chunks of about `max_chars` characters.
+///
+/// Paragraphs are kept together when they fit; longer paragraphs are split
+/// at whitespace.
+pub fn chunk_document(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    let flush = |current: &mut String, chunks: &mut Vec<String>| {
+        let chunk = current.trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
+        current.clear();
+    };
+
+    for paragraph in text.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let paragraph_len = paragraph.chars().count();
+        if !current.is_empty() && current.chars().count() + paragraph_len + 2 > max_chars {
+            flush(&mut current, &mut chunks);
+        }
+        if paragraph_len <= max_chars {
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(paragraph);
+            continue;
+        }
+
+        // Paragraph longer than a chunk: split it word by word
+        for word in paragraph.split_whitespace() {
+            if !current.is_empty() && current.chars().count() + word.chars().count() + 1 > max_chars
+            {
+                flush(&mut current, &mut chunks);
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        flush(&mut current, &mut chunks);
+    }
+    flush(&mut current, &mut chunks);
+    chunks
+}
+
+/// Loads the knowledge packs attached to an agent.
+pub async fn attached_packs(db: &DBClient, agent_id: &str) -> Result<Vec<KnowledgePack>, String> {
+    let query = format!(
+        "SELECT {} FROM knowledge_pack WHERE agent_ids CONTAINS $agent_id ORDER BY name",
+        PACK_SELECT_FIELDS
+    );
+    let rows = db
+        .query_json_with_params(
+            &query,
+            vec![("agent_id".to_string(), serde_json::json!(agent_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load knowledge packs: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect())
+}
+
+/// Detaches an agent from all knowledge packs (agent deletion).
+pub async fn detach_agent(db: &DBClient, agent_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE knowledge_pack SET agent_ids -= $agent_id WHERE agent_ids CONTAINS $agent_id",
+        vec![("agent_id".to_string(), serde_json::json!(agent_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to detach knowledge packs: {}", e))
+}
+
+/// Retrieves the chunks of the given packs most relevant to a query.
+///
+/// Embedded chunks and memories are ranked by cosine similarity; the
+/// remaining slots are filled by keyword matches among the others.
+pub async fn retrieve(
+    db: &DBClient,
+    embedding_service: Option<&Arc<EmbeddingService>>,
+    packs: &[KnowledgePack],
+    query_text: &str,
+    limit: usize,
+) -> Result<Vec<KnowledgeChunk>, String> {
+    if packs.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    let query_embedding = match embedding_service {
+        Some(service) => match service.embed(query_text).await {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                warn!(error = %e, "Query embedding failed, ranking knowledge by keywords");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let mut results = match &query_embedding {
+        Some(embedding) => vector_matches(db, packs, embedding, limit).await?,
+        None => Vec::new(),
+    };
+    if results.len() < limit {
+        let keyword = keyword_matches(
+            db,
+            packs,
+            query_text,
+            query_embedding.is_some(),
+            limit - results.len(),
+        )
+        .await?;
+        results.extend(keyword);
+    }
+
+    debug!(
+        packs = packs.len(),
+        chunks = results.len(),
+        "Knowledge pack chunks retrieved"
+    );
+    Ok(results)
+}
+
+/// Ranks the embedded chunks and memories of the packs by cosine similarity.
+async fn vector_matches(
+    db: &DBClient,
+    packs: &[KnowledgePack],
+    embedding: &[f32],
+    limit: usize,
+) -> Result<Vec<KnowledgeChunk>, String> {
+    let search_params = [
+        ("embedding".to_string(), serde_json::json!(embedding)),
+        (
+            "threshold".to_string(),
+            serde_json::json!(knowledge_const::SIMILARITY_THRESHOLD),
+        ),
+        ("limit".to_string(), serde_json::json!(limit)),
+    ];
+    let similarity = "vector::similarity::cosine(embedding, $embedding)";
+
+    let chunk_query = format!(
+        "SELECT pack_id, document, content, {similarity} AS score FROM knowledge_chunk \
+         WHERE pack_id IN $pack_ids AND embedding IS NOT NONE \
+         AND {similarity} > $threshold ORDER BY score DESC LIMIT $limit",
+        similarity = similarity
+    );
+    let mut params = vec![pack_ids_param(packs)];
+    params.extend(search_params.iter().cloned());
+    let rows = db
+        .query_json_with_params(&chunk_query, params)
+        .await
+        .map_err(|e| format!("Failed to search knowledge chunks: {}", e))?;
+    let mut results: Vec<KnowledgeChunk> = rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect();
+
+    let memory_query = format!(
+        "SELECT meta::id(id) AS id, content, {similarity} AS score FROM memory \
+         WHERE meta::id(id) IN $memory_ids AND embedding IS NOT NONE \
+         AND !string::starts_with(content, $encrypted_prefix) \
+         AND {expiration} AND {similarity} > $threshold ORDER BY score DESC LIMIT $limit",
+        similarity = similarity,
+        expiration = expiration_filter()
+    );
+    let mut params = memory_params(packs);
+    params.extend(search_params);
+    let rows = db
+        .query_json_with_params(&memory_query, params)
+        .await
+        .map_err(|e| format!("Failed to search knowledge memories: {}", e))?;
+    results.extend(rows.iter().filter_map(|row| memory_chunk(packs, row)));
+
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(limit);
+    Ok(results)
+}
+
+/// Ranks chunks and memories of the packs by keyword overlap with the query.
+///
+/// With `unembedded_only`, only the items without embedding are considered
+/// (the others were already ranked by similarity).
+async fn keyword_matches(
+    db: &DBClient,
+    packs: &[KnowledgePack],
+    query_text: &str,
+    unembedded_only: bool,
+    limit: usize,
+) -> Result<Vec<KnowledgeChunk>, String> {
+    let terms = query_terms(query_text);
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    let embedding_filter = if unembedded_only {
+        " AND embedding IS NONE"
+    } else {
+        ""
+    };
+
+    let chunk_query = format!(
+        "SELECT pack_id, document, content, 0.0 AS score FROM knowledge_chunk \
+         WHERE pack_id IN $pack_ids{}",
+        embedding_filter
+    );
+    let rows = db
+        .query_json_with_params(&chunk_query, vec![pack_ids_param(packs)])
+        .await
+        .map_err(|e| format!("Failed to load knowledge chunks: {}", e))?;
+    let mut candidates: Vec<KnowledgeChunk> = rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect();
+
+    let memory_query = format!(
+        "SELECT meta::id(id) AS id, content, 0.0 AS score FROM memory \
+         WHERE meta::id(id) IN $memory_ids \
+         AND !string::starts_with(content, $encrypted_prefix) AND {}{}",
+        expiration_filter(),
+        embedding_filter
+    );
+    let rows = db
+        .query_json_with_params(&memory_query, memory_params(packs))
+        .await
+        .map_err(|e| format!("Failed to load knowledge memories: {}", e))?;
+    candidates.extend(rows.iter().filter_map(|row| memory_chunk(packs, row)));
+
+    let mut results: Vec<KnowledgeChunk> = candidates
+        .into_iter()
+        .filter_map(|mut chunk| {
+            chunk.score = keyword_score(&terms, &chunk.content);
+            (chunk.score > 0.0).then_some(chunk)
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(limit);
+    Ok(results)
+}
+
+/// `$pack_ids` query parameter
+fn pack_ids_param(packs: &[KnowledgePack]) -> (String, serde_json::Value) {
+    let ids: Vec<&str> = packs.iter().map(|pack| pack.id.as_str()).collect();
+    ("pack_ids".to_string(), serde_json::json!(ids))
+}
+
+/// `$memory_ids` and `$encrypted_prefix` query parameters
+fn memory_params(packs: &[KnowledgePack]) -> Vec<(String, serde_json::Value)> {
+    let ids: HashSet<&str> = packs
+        .iter()
+        .flat_map(|pack| pack.memory_ids.iter().map(String::as_str))
+        .collect();
+    vec![
+        ("memory_ids".to_string(), serde_json::json!(ids)),
+        (
+            "encrypted_prefix".to_string(),
+            serde_json::json!(ENCRYPTED_PREFIX),
+        ),
+    ]
+}
+
+/// Converts a memory row to a chunk of the first pack that curates it.
+fn memory_chunk(packs: &[KnowledgePack], row: &serde_json::Value) -> Option<KnowledgeChunk> {
+    let id = row["id"].as_str()?;
+    let pack = packs
+        .iter()
+        .find(|pack| pack.memory_ids.iter().any(|memory_id| memory_id == id))?;
+    Some(KnowledgeChunk {
+        pack_id: pack.id.clone(),
+        document: None,
+        content: row["content"].as_str()?.to_string(),
+        score: row["score"].as_f64().unwrap_or(0.0),
+    })
+}
+
+/// Distinct lowercase words of at least 3 characters.
+fn query_terms(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+/// Share of the query terms found in the content (0.0 to 1.0).
+fn keyword_score(terms: &[String], content: &str) -> f64 {
+    let words: HashSet<String> = content
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .collect();
+    let matched = terms.iter().filter(|term| words.contains(*term)).count();
+    matched as f64 / terms.len() as f64
+}
+
+/// Formats retrieved chunks as a system prompt section.
+///
+/// # Returns
+/// The section, or None if no chunk was retrieved
+pub fn format_context(packs: &[KnowledgePack], chunks: &[KnowledgeChunk]) -> Option<String> {
+    if chunks.is_empty() {
+        return None;
+    }
+
+    let mut section = String::from(
+        "## Knowledge Pack Context\n\n\
+         The following excerpts come from the knowledge packs attached to you. \
+         Ground your answer in them when they are relevant, and say so when they \
+         do not cover the question.\n",
+    );
+    for chunk in chunks {
+        let pack_name = packs
+            .iter()
+            .find(|pack| pack.id == chunk.pack_id)
+            .map_or(chunk.pack_id.as_str(), |pack| pack.name.as_str());
+        let source = match &chunk.document {
+            Some(document) => format!("{} / {}", pack_name, document),
+            None => format!("{} / memory", pack_name),
+        };
+        let excerpt = format!("\n### {}\n{}\n", source, chunk.content);
+        if section.chars().count() + excerpt.chars().count() > knowledge_const::MAX_CONTEXT_CHARS {
+            break;
+        }
+        section.push_str(&excerpt);
+    }
+    Some(section)
+}
+
+/// Builds the knowledge context of an agent for a task.
+///
+/// Errors are logged and result in no context: retrieval never fails the
+/// execution.
+pub async fn knowledge_context(
+    db: &DBClient,
+    embedding_service: Option<&Arc<EmbeddingService>>,
+    agent_id: &str,
+    query_text: &str,
+) -> Option<String> {
+    let packs = match attached_packs(db, agent_id).await {
+        Ok(packs) if !packs.is_empty() => packs,
+        Ok(_) => return None,
+        Err(e) => {
+            warn!(agent_id = %agent_id, error = %e, "Failed to load attached knowledge packs");
+            return None;
+        }
+    };
+
+    match retrieve(
+        db,
+        embedding_service,
+        &packs,
+        query_text,
+        knowledge_const::TOP_K,
+    )
+    .await
+    {
+        Ok(chunks) => format_context(&packs, &chunks),
+        Err(e) => {
+            warn!(agent_id = %agent_id, error = %e, "Knowledge pack retrieval failed");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn pack(id: &str, name: &str) -> KnowledgePack {
+        KnowledgePack {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: String::new(),
+            agent_ids: vec!["agent-1".to_string()],
+            memory_ids: Vec::new(),
+            documents: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_chunk_document() {
+        let text = "First paragraph.\n\nSecond paragraph.\n\n\n\nThird one.";
+        assert_eq!(
+            chunk_document(text, 1000),
+            vec!["First paragraph.\n\nSecond paragraph.\n\nThird one.".to_string()]
+        );
+        assert_eq!(
+            chunk_document(text, 20),
+            vec!["First paragraph.", "Second paragraph.", "Third one."]
+        );
+
+        let long = "word ".repeat(50);
+        let chunks = chunk_document(&long, 24);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 24));
+        assert_eq!(chunks.concat().matches("word").count(), 50);
+
+        assert!(chunk_document("  \n\n ", 100).is_empty());
+    }
+
+    #[test]
+    fn test_keyword_score() {
+        let terms = query_terms("How do I rotate the API keys? the keys");
+        assert_eq!(terms, vec!["how", "rotate", "the", "api", "keys"]);
+        assert_eq!(keyword_score(&terms, "Rotate API keys monthly."), 0.6);
+        assert_eq!(keyword_score(&terms, "Unrelated text"), 0.0);
+        assert!(query_terms("a b ?").is_empty());
+    }
+
+    #[test]
+    fn test_format_context() {
+        let packs = vec![pack("p1", "Security")];
+        assert!(format_context(&packs, &[]).is_none());
+
+        let chunks = vec![
+            KnowledgeChunk {
+                pack_id: "p1".to_string(),
+                document: Some("Key policy".to_string()),
+                content: "Rotate keys monthly.".to_string(),
+                score: 0.9,
+            },
+            KnowledgeChunk {
+                pack_id: "p1".to_string(),
+                document: None,
+                content: "The user prefers short answers.".to_string(),
+                score: 0.6,
+            },
+            KnowledgeChunk {
+                pack_id: "p1".to_string(),
+                document: Some("Huge".to_string()),
+                content: "x".repeat(knowledge_const::MAX_CONTEXT_CHARS),
+                score: 0.5,
+            },
+        ];
+        let context = format_context(&packs, &chunks).unwrap();
+        assert!(context.starts_with("## Knowledge Pack Context"));
+        assert!(context.contains("### Security / Key policy\nRotate keys monthly."));
+        assert!(context.contains("### Security / memory\nThe user prefers short answers."));
+        // Excerpts beyond the context budget are dropped
+        assert!(!context.contains("Huge"));
+    }
+}
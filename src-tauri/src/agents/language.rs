@@ -0,0 +1,281 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Response language detection and pinning.
+//!
+//! Each workflow pins the language of its responses (`workflow.response_language`):
+//! detected from the first user message written in a recognizable language, or
+//! set by the user with `set_workflow_language`. The agent states the pinned
+//! language in its system prompt and checks its final response, asking the
+//! model to rewrite it when it was written in another language.
+//!
+//! Detection counts common function words of each supported language, so it
+//! needs a few words of prose; short messages ("ok", "merci") are not pinned.
+
+use crate::db::DBClient;
+use tracing::{debug, info};
+
+/// Supported response languages (ISO 639-1 codes)
+pub const SUPPORTED_LANGUAGES: &[&str] = &["en", "fr", "es", "de", "it", "pt"];
+
+/// Minimum number of function words for a detection
+const MIN_MATCHES: usize = 2;
+
+/// Ratio between the best and the second best language for a detection
+const MIN_MARGIN: f64 = 1.5;
+
+/// Common function words of each supported language
+const FUNCTION_WORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "you", "what", "how",
+            "this", "with", "for", "was", "have", "can", "please", "not", "my", "do", "does", "be",
+            "i",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "des", "est", "et", "une", "du", "qui", "pour", "dans", "pas",
+            "vous", "je", "ce", "cette", "avec", "sur", "mon", "comment", "quoi", "sont", "au",
+            "aux", "nous", "tu",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "es", "y", "una", "por", "para", "del", "como", "qué", "cómo",
+            "está", "son", "yo", "pero", "al", "se", "lo", "muy", "usted", "puedes", "esto",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "ich", "sie", "zu", "mit",
+            "den", "dem", "von", "wie", "was", "auf", "für", "sind", "bitte", "auch", "kann",
+            "mein", "du",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "gli", "della", "di", "che", "è", "per", "non", "sono", "con", "cosa", "questo",
+            "ho", "nel", "anche", "ma", "perché", "mio", "puoi", "come",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "as", "é", "um", "uma", "não", "com", "do", "da", "dos", "em", "no", "na",
+            "você", "eu", "mas", "meu", "são", "isso", "como", "para",
+        ],
+    ),
+];
+
+/// Returns true if `code` is a supported response language.
+pub fn is_supported(code: &str) -> bool {
+    SUPPORTED_LANGUAGES.contains(&code)
+}
+
+/// Returns the display name of a supported language.
+pub fn language_name(code: &str) -> &str {
+    match code {
+        "en" => "English",
+        "fr" => "French (Français)",
+        "es" => "Spanish (Español)",
+        "de" => "German (Deutsch)",
+        "it" => "Italian (Italiano)",
+        "pt" => "Portuguese (Português)",
+        other => other,
+    }
+}
+
+/// Removes code blocks and inline code, which are language-neutral.
+fn strip_code(text: &str) -> String {
+    let mut prose = String::with_capacity(text.len());
+    let mut in_block = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+        if !in_block {
+            // Drop inline code spans (odd segments between backticks)
+            for (i, segment) in line.split('`').enumerate() {
+                if i % 2 == 0 {
+                    prose.push_str(segment);
+                    prose.push(' ');
+                }
+            }
+            prose.push('\n');
+        }
+    }
+    prose
+}
+
+/// Detects the language of a text.
+///
+/// Returns `None` when the text is too short or ambiguous.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let prose = strip_code(text).to_lowercase();
+    let words: Vec<&str> = prose
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut scores: Vec<(&'static str, usize)> = FUNCTION_WORDS
+        .iter()
+        .map(|(code, list)| (*code, words.iter().filter(|w| list.contains(w)).count()))
+        .collect();
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    let (best, best_score) = scores[0];
+    let second_score = scores[1].1;
+    if best_score >= MIN_MATCHES && best_score as f64 >= second_score as f64 * MIN_MARGIN {
+        Some(best)
+    } else {
+        None
+    }
+}
+
+/// Returns the language a response was written in when it differs from the
+/// pinned language (`None` if it matches or cannot be detected).
+pub fn response_mismatch(response: &str, pinned: &str) -> Option<&'static str> {
+    detect_language(response).filter(|detected| *detected != pinned)
+}
+
+/// System prompt section pinning the response language.
+pub fn prompt_constraint(code: &str) -> String {
+    let name = language_name(code);
+    format!(
+        "## Response Language\n\n\
+         This conversation is pinned to **{}**. Always write your responses in {}, \
+         even when documents, tool results or quoted text use another language. \
+         Only switch language if the user explicitly asks for it.",
+        name, name
+    )
+}
+
+/// Prompt asking the model to rewrite a response in the pinned language.
+pub fn rewrite_prompt(code: &str, response: &str) -> String {
+    format!(
+        "Rewrite the following answer in {}. Keep its meaning and formatting; \
+         leave code blocks, identifiers and quoted names unchanged. \
+         Reply with the rewritten answer only.\n\n{}",
+        language_name(code),
+        response
+    )
+}
+
+/// Resolves the response language of a workflow for a new user message.
+///
+/// Returns the pinned language, or pins the language detected in `message`
+/// when the workflow has none yet. Returns `None` when nothing is pinned and
+/// the message language cannot be detected.
+pub async fn pin_workflow_language(
+    db: &DBClient,
+    workflow_id: &str,
+    pinned: Option<&str>,
+    message: &str,
+) -> anyhow::Result<Option<String>> {
+    if let Some(code) = pinned {
+        return Ok(Some(code.to_string()));
+    }
+
+    let Some(detected) = detect_language(message) else {
+        debug!(workflow_id = %workflow_id, "Message language not detected, not pinning");
+        return Ok(None);
+    };
+
+    set_workflow_language(db, workflow_id, Some(detected)).await?;
+    info!(workflow_id = %workflow_id, language = %detected, "Pinned workflow response language");
+    Ok(Some(detected.to_string()))
+}
+
+/// Stores the response language of a workflow (`None` clears it, so the
+/// language of the next message is detected again).
+pub async fn set_workflow_language(
+    db: &DBClient,
+    workflow_id: &str,
+    language: Option<&str>,
+) -> anyhow::Result<()> {
+    let mut params = vec![("id".to_string(), serde_json::json!(workflow_id))];
+    let query = match language {
+        Some(code) => {
+            params.push(("language".to_string(), serde_json::json!(code)));
+            "UPDATE type::thing('workflow', $id) SET response_language = $language"
+        }
+        None => "UPDATE type::thing('workflow', $id) SET response_language = NONE",
+    };
+    db.execute_with_params(query, params).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("How do I configure the MCP server for this project?"),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("Comment est-ce que je configure le serveur pour ce projet ?"),
+            Some("fr")
+        );
+        assert_eq!(
+            detect_language("¿Cómo puedo configurar el servidor para este proyecto?"),
+            Some("es")
+        );
+        assert_eq!(
+            detect_language("Wie kann ich den Server für das Projekt einrichten?"),
+            Some("de")
+        );
+        assert_eq!(
+            detect_language("Come posso configurare il server per questo progetto?"),
+            Some("it")
+        );
+        assert_eq!(
+            detect_language("Como eu configuro o servidor do projeto? Não sei."),
+            Some("pt")
+        );
+
+        // Too short or language-neutral
+        assert_eq!(detect_language("ok"), None);
+        assert_eq!(detect_language("merci"), None);
+        assert_eq!(detect_language("```rust\nfn main() {}\n```"), None);
+    }
+
+    #[test]
+    fn test_code_is_ignored() {
+        let response = "Voici la fonction demandée, elle est dans le module :\n\n\
+                        ```rust\n// Returns the value of the thing and the rest\nfn get() {}\n```\n\n\
+                        Appelez `get_the_value_of_it` pour la tester.";
+        assert_eq!(detect_language(response), Some("fr"));
+        assert_eq!(response_mismatch(response, "fr"), None);
+        assert_eq!(response_mismatch(response, "en"), Some("fr"));
+    }
+
+    #[test]
+    fn test_prompts() {
+        assert!(is_supported("de"));
+        assert!(!is_supported("xx"));
+        assert!(prompt_constraint("fr").contains("French"));
+        let rewrite = rewrite_prompt("en", "Bonjour");
+        assert!(rewrite.contains("English"));
+        assert!(rewrite.ends_with("Bonjour"));
+    }
+}
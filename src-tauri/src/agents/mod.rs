@@ -41,10 +41,32 @@
 //!
 //! - [`SimpleAgent`] - Base implementation for demonstration (no LLM calls)
 //! - [`LLMAgent`] - Agent that uses real LLM calls via ProviderManager
+//!
+//! Regression suites bundled with agents are executed by [`regression`].
+//! Agent dependencies are verified before execution by [`health`].
+//! The response language of a workflow is detected and pinned by [`language`].
+//! Responses are checked against the agent's moderation policy by [`moderation`].
+//! Tasks are grounded in the knowledge packs attached to the agent by [`knowledge`].
+//! System prompt references to prompt library blocks are resolved by [`prompt_blocks`].
+//! First uses of powerful tools are acknowledged per agent through [`tool_acknowledgment`].
+//! The tool loop state is checkpointed by [`checkpoint`] to resume executions after a crash.
 
+pub mod behavior_changelog;
+pub mod checkpoint;
 pub mod core;
+pub mod health;
+pub mod knowledge;
+pub mod language;
 pub mod llm_agent;
+pub mod moderation;
+pub mod prompt_blocks;
+pub mod regression;
 pub mod simple_agent;
+pub mod token_monitor;
+pub mod tool_acknowledgment;
+pub mod tool_result_diff;
+pub mod tool_usage;
+pub mod watchdog;
 
 pub use llm_agent::LLMAgent;
 // SimpleAgent is used in tests only
@@ -0,0 +1,246 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pre-persist output moderation.
+//!
+//! When an agent's moderation policy is enabled, its report is checked before
+//! the response is streamed and returned for persistence. A flagged response
+//! is replaced by a notice (`block`), returned unchanged with a warning for
+//! the user (`warn`), or kept with a moderation note appended (`annotate`).
+//! Every check is recorded in the audit log (`moderation` action) without
+//! the checked content.
+
+use crate::agents::core::agent::Report;
+use crate::db::DBClient;
+use crate::llm::moderation::moderate;
+use crate::llm::ProviderManager;
+use crate::models::audit::{AuditAction, AuditLogCreate};
+use crate::models::moderation::{ModerationAction, ModerationOutcome};
+use crate::models::AgentConfig;
+use crate::security::audit;
+use tracing::info;
+
+/// Identifies the response being moderated
+pub struct ModerationTarget<'a> {
+    /// Associated workflow ID
+    pub workflow_id: &'a str,
+    /// Message ID of the response
+    pub message_id: &'a str,
+}
+
+/// Checks a report against the agent's moderation policy and applies the action.
+///
+/// # Returns
+/// The moderation outcome, or None if the agent has no enabled policy
+pub async fn moderate_report(
+    db: &DBClient,
+    llm_manager: &ProviderManager,
+    config: &AgentConfig,
+    target: ModerationTarget<'_>,
+    report: &mut Report,
+) -> Option<ModerationOutcome> {
+    if !config.moderation.enabled {
+        return None;
+    }
+
+    let outcome = moderate(
+        llm_manager,
+        &config.llm.provider,
+        &config.moderation,
+        &report.response,
+    )
+    .await;
+
+    if outcome.flagged {
+        info!(
+            agent_id = %config.id,
+            action = %outcome.action,
+            categories = ?outcome.categories,
+            "Agent response flagged by moderation"
+        );
+        apply_action(&outcome, report);
+    }
+
+    audit::record(
+        db,
+        AuditLogCreate {
+            workflow_id: Some(target.workflow_id.to_string()),
+            details: serde_json::json!({
+                "flagged": outcome.flagged,
+                "categories": outcome.categories,
+                "action": outcome.action,
+                "source": outcome.source,
+            }),
+            ..AuditLogCreate::new(AuditAction::Moderation, &config.id, target.message_id)
+        },
+    )
+    .await;
+
+    Some(outcome)
+}
+
+/// Applies the action of a flagged outcome to the report.
+fn apply_action(outcome: &ModerationOutcome, report: &mut Report) {
+    let categories = outcome.categories.join(", ");
+    match outcome.action {
+        ModerationAction::Block => {
+            let notice = format!(
+                "This response was blocked by the agent's moderation policy (flagged: {}).",
+                categories
+            );
+            report.response = notice.clone();
+            report.content = notice;
+        }
+        ModerationAction::Annotate => {
+            let note = format!("\n\n> Moderation: flagged ({})", categories);
+            report.response.push_str(&note);
+            report.content.push_str(&note);
+        }
+        ModerationAction::Warn => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::core::agent::{ReportMetrics, ReportStatus};
+    use crate::models::audit::AuditLogFilter;
+    use crate::models::moderation::{ModerationPolicy, ModerationSource};
+    use crate::models::{LLMConfig, Lifecycle};
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    fn report(response: &str) -> Report {
+        Report {
+            task_id: "task".to_string(),
+            status: ReportStatus::Success,
+            content: response.to_string(),
+            response: response.to_string(),
+            metrics: ReportMetrics::default(),
+            system_prompt: None,
+            tools_json: None,
+        }
+    }
+
+    fn config(action: ModerationAction) -> AgentConfig {
+        AgentConfig {
+            id: "agent-1".to_string(),
+            name: "Moderated".to_string(),
+            lifecycle: Lifecycle::Permanent,
+            llm: LLMConfig {
+                provider: "Ollama".to_string(),
+                model: "llama3".to_string(),
+                temperature: 0.7,
+                max_tokens: 1000,
+            },
+            tools: vec![],
+            mcp_servers: vec![],
+            system_prompt: "You are a helpful assistant.".to_string(),
+            max_tool_iterations: 10,
+            enable_thinking: false,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: ModerationPolicy {
+                enabled: true,
+                action,
+                source: ModerationSource::Local,
+                blocked_terms: vec!["codename".to_string()],
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_moderate_report_applies_action_and_records_check() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test_db");
+        let db = Arc::new(DBClient::new(db_path.to_str().unwrap()).await.unwrap());
+        db.initialize_schema().await.unwrap();
+        let llm = ProviderManager::new();
+        let target = || ModerationTarget {
+            workflow_id: "wf-1",
+            message_id: "msg-1",
+        };
+
+        let mut blocked = report("The codename is Falcon.");
+        let outcome = moderate_report(
+            &db,
+            &llm,
+            &config(ModerationAction::Block),
+            target(),
+            &mut blocked,
+        )
+        .await
+        .unwrap();
+        assert!(outcome.flagged);
+        assert!(blocked.response.contains("blocked"));
+        assert!(!blocked.content.contains("Falcon"));
+
+        let mut annotated = report("The codename is Falcon.");
+        moderate_report(
+            &db,
+            &llm,
+            &config(ModerationAction::Annotate),
+            target(),
+            &mut annotated,
+        )
+        .await;
+        assert!(annotated.response.starts_with("The codename is Falcon."));
+        assert!(annotated
+            .response
+            .contains("Moderation: flagged (blocked_term)"));
+
+        let mut clean = report("All good.");
+        let outcome = moderate_report(
+            &db,
+            &llm,
+            &config(ModerationAction::Block),
+            target(),
+            &mut clean,
+        )
+        .await
+        .unwrap();
+        assert!(!outcome.flagged);
+        assert_eq!(clean.response, "All good.");
+
+        let mut disabled = config(ModerationAction::Block);
+        disabled.moderation.enabled = false;
+        let mut unchecked = report("The codename is Falcon.");
+        assert!(
+            moderate_report(&db, &llm, &disabled, target(), &mut unchecked)
+                .await
+                .is_none()
+        );
+
+        let log = audit::query(
+            &db,
+            &AuditLogFilter {
+                action: Some(AuditAction::Moderation),
+                workflow_id: Some("wf-1".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(
+            log.iter().filter(|e| e.details["flagged"] == true).count(),
+            2
+        );
+        assert!(log
+            .iter()
+            .all(|e| e.actor == "agent-1" && e.target == "msg-1"));
+        assert!(log.iter().any(|e| e.details["action"] == "annotate"));
+    }
+}
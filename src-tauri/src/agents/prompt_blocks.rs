@@ -0,0 +1,149 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reusable system prompt blocks.
+//!
+//! An agent system prompt can reference a prompt of the library by name with
+//! `{{block:name}}`. References are replaced with the content of that prompt
+//! each time the agent runs, so a shared block updated once applies to every
+//! agent using it. Blocks are resolved one level deep (references inside a
+//! block are left as-is) and references to unknown prompts are kept unchanged.
+
+use crate::db::DBClient;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use tracing::{debug, warn};
+
+/// Marker starting a block reference
+const BLOCK_MARKER: &str = "{{block:";
+
+fn block_pattern() -> Regex {
+    Regex::new(r"\{\{block:([^{}\n]+)\}\}").expect("Invalid regex pattern")
+}
+
+/// Names of the blocks referenced by a system prompt, in order of first use.
+pub fn block_names(prompt: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    block_pattern()
+        .captures_iter(prompt)
+        .map(|cap| cap[1].trim().to_string())
+        .filter(|name| !name.is_empty() && seen.insert(name.clone()))
+        .collect()
+}
+
+/// Replaces the block references with the given block contents.
+pub fn substitute(prompt: &str, blocks: &HashMap<String, String>) -> String {
+    block_pattern()
+        .replace_all(prompt, |caps: &regex::Captures| {
+            blocks
+                .get(caps[1].trim())
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// Resolves the block references of a system prompt from the prompt library.
+///
+/// Returns the prompt unchanged when it has no references or the library
+/// cannot be read.
+pub async fn resolve_blocks(db: &DBClient, prompt: &str) -> String {
+    if !prompt.contains(BLOCK_MARKER) {
+        return prompt.to_string();
+    }
+    let names = block_names(prompt);
+    if names.is_empty() {
+        return prompt.to_string();
+    }
+
+    let rows = match db
+        .query_json_with_params(
+            "SELECT name, content FROM prompt WHERE name IN $names",
+            vec![("names".to_string(), serde_json::json!(names))],
+        )
+        .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            warn!(error = %e, "Failed to load system prompt blocks");
+            return prompt.to_string();
+        }
+    };
+
+    let blocks: HashMap<String, String> = rows
+        .into_iter()
+        .filter_map(|row| {
+            Some((
+                row.get("name")?.as_str()?.to_string(),
+                row.get("content")?.as_str()?.to_string(),
+            ))
+        })
+        .collect();
+    let unknown: Vec<&String> = names.iter().filter(|n| !blocks.contains_key(*n)).collect();
+    if !unknown.is_empty() {
+        warn!(blocks = ?unknown, "System prompt references unknown prompt blocks");
+    }
+    debug!(resolved = blocks.len(), "Resolved system prompt blocks");
+
+    substitute(prompt, &blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_block_names_and_substitute() {
+        let prompt = "Intro\n{{block:coding_guidelines}}\n{{ block:x }}{{block: tone }}\n\
+                      {{block:coding_guidelines}} {{name}}";
+        assert_eq!(block_names(prompt), vec!["coding_guidelines", "tone"]);
+
+        let blocks = HashMap::from([("tone".to_string(), "Be concise.".to_string())]);
+        assert_eq!(
+            substitute(prompt, &blocks),
+            "Intro\n{{block:coding_guidelines}}\n{{ block:x }}Be concise.\n\
+             {{block:coding_guidelines}} {{name}}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_blocks() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("blocks_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        db.execute(
+            "CREATE prompt:guidelines CONTENT { name: 'coding_guidelines', description: '', \
+             category: 'system', content: 'Write tests. {{block:other}}', variables: [], \
+             created_at: time::now(), updated_at: time::now() }",
+        )
+        .await
+        .expect("Failed to create prompt");
+
+        let resolved = resolve_blocks(
+            &db,
+            "You are a coder.\n\n{{block:coding_guidelines}}\n{{block:missing}}",
+        )
+        .await;
+        assert_eq!(
+            resolved,
+            "You are a coder.\n\nWrite tests. {{block:other}}\n{{block:missing}}"
+        );
+        assert_eq!(resolve_blocks(&db, "Plain prompt").await, "Plain prompt");
+    }
+}
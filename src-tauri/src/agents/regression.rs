@@ -0,0 +1,216 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Agent regression runner.
+//!
+//! Executes the [`RegressionSuite`] bundled with an agent configuration.
+//! Each case runs on a fresh [`LLMAgent`] (no sub-agent context) with the
+//! case's tool mocks, then the case assertions are evaluated against the
+//! final response and the tools called.
+
+use crate::agents::core::agent::{Agent, ReportStatus, Task};
+use crate::agents::LLMAgent;
+use crate::llm::ProviderManager;
+use crate::mcp::MCPManager;
+use crate::models::regression::{RegressionCase, RegressionCaseResult, RegressionReport};
+use crate::models::AgentConfig;
+use crate::tools::constants::workflow as wf_const;
+use crate::tools::ToolFactory;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::timeout;
+use tracing::{info, warn};
+
+/// Runs every regression case of the agent configuration.
+///
+/// # Arguments
+/// * `config` - Agent configuration under test (may be an unsaved candidate)
+/// * `provider_manager` - Provider manager for LLM calls
+/// * `tool_factory` - Factory for local tools
+/// * `mcp_manager` - Optional MCP manager for MCP tools
+pub async fn run_regression_suite(
+    config: &AgentConfig,
+    provider_manager: Arc<ProviderManager>,
+    tool_factory: Arc<ToolFactory>,
+    mcp_manager: Option<Arc<MCPManager>>,
+) -> RegressionReport {
+    let mut results = Vec::with_capacity(config.regressions.cases.len());
+
+    for case in &config.regressions.cases {
+        let result = run_case(
+            config,
+            case,
+            provider_manager.clone(),
+            tool_factory.clone(),
+            mcp_manager.clone(),
+        )
+        .await;
+        results.push(result);
+    }
+
+    let report = RegressionReport::new(config.id.clone(), results);
+    info!(
+        agent_id = %config.id,
+        total = report.total,
+        failed = report.failed,
+        "Agent regressions completed"
+    );
+    report
+}
+
+/// Runs a single regression case and evaluates its assertions.
+async fn run_case(
+    config: &AgentConfig,
+    case: &RegressionCase,
+    provider_manager: Arc<ProviderManager>,
+    tool_factory: Arc<ToolFactory>,
+    mcp_manager: Option<Arc<MCPManager>>,
+) -> RegressionCaseResult {
+    let start = std::time::Instant::now();
+    let agent = LLMAgent::with_factory(config.clone(), provider_manager, tool_factory)
+        .with_tool_mocks(case.mock_tools.clone());
+
+    let task = Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: case.input.clone(),
+        context: serde_json::json!({}),
+    };
+
+    let execution = timeout(
+        Duration::from_secs(wf_const::LLM_EXECUTION_TIMEOUT_SECS),
+        agent.execute_with_mcp(task, mcp_manager),
+    )
+    .await;
+
+    let report = match execution {
+        Ok(Ok(report)) => report,
+        Ok(Err(e)) => return failed_case(case, format!("Execution failed: {}", e), start),
+        Err(_) => {
+            return failed_case(
+                case,
+                format!(
+                    "Execution timed out after {} seconds",
+                    wf_const::LLM_EXECUTION_TIMEOUT_SECS
+                ),
+                start,
+            )
+        }
+    };
+
+    if matches!(report.status, ReportStatus::Failed) {
+        return failed_case(case, report.response, start);
+    }
+
+    let mut tools_called = report.metrics.tools_used;
+    tools_called.extend(report.metrics.mcp_calls);
+
+    let failures: Vec<String> = case
+        .assertions
+        .iter()
+        .filter_map(|a| a.evaluate(&report.response, &tools_called))
+        .collect();
+
+    if !failures.is_empty() {
+        warn!(case = %case.name, failures = ?failures, "Regression case failed");
+    }
+
+    RegressionCaseResult {
+        name: case.name.clone(),
+        passed: failures.is_empty(),
+        failures,
+        response: report.response,
+        tools_called,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+/// Builds the result of a case that could not be executed.
+fn failed_case(
+    case: &RegressionCase,
+    error: String,
+    start: std::time::Instant,
+) -> RegressionCaseResult {
+    warn!(case = %case.name, error = %error, "Regression case execution failed");
+    RegressionCaseResult {
+        name: case.name.clone(),
+        passed: false,
+        failures: vec![error],
+        response: String::new(),
+        tools_called: vec![],
+        duration_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DBClient;
+    use crate::models::regression::{RegressionAssertion, RegressionSuite};
+    use crate::models::{LLMConfig, Lifecycle};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_unconfigured_provider_fails_cases() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("regression_db");
+        let db = Arc::new(
+            DBClient::new(db_path.to_str().unwrap())
+                .await
+                .expect("Failed to create DB"),
+        );
+        let factory = Arc::new(ToolFactory::new(
+            db,
+            Arc::new(tokio::sync::RwLock::new(None)),
+        ));
+
+        let config = AgentConfig {
+            id: "regression_agent".to_string(),
+            name: "Regression Agent".to_string(),
+            lifecycle: Lifecycle::Permanent,
+            llm: LLMConfig {
+                provider: "Mistral".to_string(),
+                model: "mistral-large-latest".to_string(),
+                temperature: 0.7,
+                max_tokens: 1000,
+            },
+            tools: vec!["MemoryTool".to_string()],
+            mcp_servers: vec![],
+            system_prompt: "You are a helpful assistant.".to_string(),
+            max_tool_iterations: 10,
+            enable_thinking: false,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: RegressionSuite {
+                strict: true,
+                cases: vec![RegressionCase {
+                    name: "greeting".to_string(),
+                    input: "Hello".to_string(),
+                    assertions: vec![RegressionAssertion::Contains {
+                        value: "hello".to_string(),
+                    }],
+                    mock_tools: Default::default(),
+                }],
+            },
+            moderation: Default::default(),
+        };
+
+        let report =
+            run_regression_suite(&config, Arc::new(ProviderManager::new()), factory, None).await;
+
+        assert!(!report.passed);
+        assert_eq!(report.total, 1);
+        assert_eq!(report.failed, 1);
+        assert!(report.results[0].failures[0].contains("not configured"));
+    }
+}
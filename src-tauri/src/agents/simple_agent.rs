@@ -81,6 +81,7 @@ impl Agent for SimpleAgent {
                 mcp_calls: vec![],
                 tool_executions: vec![],
                 reasoning_steps: vec![],
+                llm_turns: vec![],
             },
             system_prompt: None,
             tools_json: None,
@@ -142,6 +143,10 @@ mod tests {
             system_prompt: "You are a test agent.".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         }
     }
 
@@ -0,0 +1,216 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-run token usage monitor.
+//!
+//! Loaded once at the start of an agent run, [`TokenMonitor`] compares the
+//! run's token usage after each LLM call with the agent's rolling baseline
+//! and reports the first time the configured threshold is exceeded.
+
+use crate::db::{queries::settings, DBClient};
+use crate::models::token_anomaly::{compute_baseline, TokenAnomalySettings};
+use serde_json::json;
+use tracing::{debug, warn};
+
+/// Storage key of the token anomaly settings
+pub const TOKEN_ANOMALY_SETTINGS_KEY: &str = "settings:token_anomaly";
+
+/// Loads token anomaly settings from the database, falling back to defaults.
+pub async fn load_token_anomaly_settings(db: &DBClient) -> TokenAnomalySettings {
+    match settings::load_config(db, TOKEN_ANOMALY_SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No token anomaly settings found, using defaults");
+            TokenAnomalySettings::default()
+        }
+    }
+}
+
+/// Token usage monitor for a single agent run
+#[derive(Debug)]
+pub struct TokenMonitor {
+    /// Detection settings
+    settings: TokenAnomalySettings,
+    /// Baseline tokens per run (None = not enough history)
+    baseline: Option<usize>,
+    /// Whether an alert was already raised for this run
+    alerted: bool,
+}
+
+impl TokenMonitor {
+    /// Creates a monitor with an explicit baseline.
+    pub fn new(settings: TokenAnomalySettings, baseline: Option<usize>) -> Self {
+        Self {
+            settings,
+            baseline,
+            alerted: false,
+        }
+    }
+
+    /// Creates a monitor that never raises alerts.
+    pub fn disabled() -> Self {
+        Self::new(
+            TokenAnomalySettings {
+                enabled: false,
+                ..Default::default()
+            },
+            None,
+        )
+    }
+
+    /// Loads settings and computes the agent's baseline from recent runs.
+    ///
+    /// A run is an assistant message; its usage is `tokens_input + tokens_output`,
+    /// the same measure reported by [`TokenMonitor::check`] callers.
+    pub async fn load(db: &DBClient, agent_id: &str) -> Self {
+        let settings = load_token_anomaly_settings(db).await;
+        if !settings.enabled {
+            return Self::new(settings, None);
+        }
+
+        let query = "SELECT tokens_input, tokens_output, timestamp FROM message \
+                     WHERE role = 'assistant' AND workflow_id IN \
+                     (SELECT VALUE meta::id(id) FROM workflow WHERE agent_id = $agent_id) \
+                     ORDER BY timestamp DESC LIMIT $limit";
+        let params = vec![
+            ("agent_id".to_string(), json!(agent_id)),
+            ("limit".to_string(), json!(settings.baseline_window)),
+        ];
+
+        let samples: Vec<usize> = match db.query_json_with_params(query, params).await {
+            Ok(rows) => rows
+                .iter()
+                .map(|row| {
+                    let input = row["tokens_input"].as_u64().unwrap_or(0);
+                    let output = row["tokens_output"].as_u64().unwrap_or(0);
+                    (input + output) as usize
+                })
+                .collect(),
+            Err(e) => {
+                warn!(error = %e, "Failed to load token baseline, anomaly detection skipped");
+                Vec::new()
+            }
+        };
+
+        let baseline = compute_baseline(&samples, settings.min_baseline_runs.max(1));
+        debug!(
+            agent_id = %agent_id,
+            samples = samples.len(),
+            baseline = ?baseline,
+            "Token anomaly baseline loaded"
+        );
+        Self::new(settings, baseline)
+    }
+
+    /// Returns the settings used by this monitor.
+    pub fn settings(&self) -> &TokenAnomalySettings {
+        &self.settings
+    }
+
+    /// Checks the run's current token usage against the baseline.
+    ///
+    /// # Returns
+    /// `Some((baseline, ratio))` the first time the threshold is exceeded, None otherwise
+    pub fn check(&mut self, tokens_used: usize) -> Option<(usize, f64)> {
+        if !self.settings.enabled || self.alerted {
+            return None;
+        }
+        let baseline = self.baseline?;
+        let ratio = tokens_used as f64 / baseline as f64;
+        if ratio > self.settings.threshold_multiplier {
+            self.alerted = true;
+            Some((baseline, ratio))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_check_alerts_once() {
+        let mut monitor = TokenMonitor::new(TokenAnomalySettings::default(), Some(1000));
+
+        assert!(monitor.check(4_000).is_none());
+        let (baseline, ratio) = monitor.check(6_000).expect("threshold exceeded");
+        assert_eq!(baseline, 1000);
+        assert!((ratio - 6.0).abs() < f64::EPSILON);
+        assert!(monitor.check(10_000).is_none());
+    }
+
+    #[test]
+    fn test_check_without_baseline_or_disabled() {
+        let mut monitor = TokenMonitor::new(TokenAnomalySettings::default(), None);
+        assert!(monitor.check(1_000_000).is_none());
+
+        let mut monitor = TokenMonitor::disabled();
+        assert!(monitor.check(1_000_000).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_baseline_from_history() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("token_monitor_db");
+        let db = Arc::new(
+            DBClient::new(db_path.to_str().unwrap())
+                .await
+                .expect("Failed to create DB"),
+        );
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        db.execute(
+            "CREATE workflow:wf_a CONTENT { id: 'wf_a', name: 'A', agent_id: 'agent_a', status: 'completed' }",
+        )
+        .await
+        .unwrap();
+        db.execute(
+            "CREATE workflow:wf_b CONTENT { id: 'wf_b', name: 'B', agent_id: 'agent_b', status: 'completed' }",
+        )
+        .await
+        .unwrap();
+        for (i, tokens) in [100, 200, 300, 400, 500].iter().enumerate() {
+            db.execute_with_params(
+                "CREATE type::thing('message', $id) CONTENT { workflow_id: 'wf_a', \
+                 role: 'assistant', content: 'ok', tokens: $tokens, tokens_input: $tokens, \
+                 tokens_output: 0 }",
+                vec![
+                    ("id".to_string(), json!(format!("m{}", i))),
+                    ("tokens".to_string(), json!(tokens)),
+                ],
+            )
+            .await
+            .unwrap();
+        }
+        db.execute(
+            "CREATE message:other CONTENT { id: 'other', workflow_id: 'wf_b', role: 'assistant', \
+             content: 'ok', tokens: 99999, tokens_input: 99999, tokens_output: 0 }",
+        )
+        .await
+        .unwrap();
+
+        let mut monitor = TokenMonitor::load(&db, "agent_a").await;
+        assert_eq!(monitor.baseline, Some(300));
+        assert!(monitor.check(1_600).is_some());
+
+        let monitor = TokenMonitor::load(&db, "agent_without_history").await;
+        assert_eq!(monitor.baseline, None);
+    }
+}
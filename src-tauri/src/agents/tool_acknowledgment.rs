@@ -0,0 +1,204 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistence of first-use acknowledgments of powerful tools
+//! (see [`crate::models::tool_acknowledgment`]).
+
+use crate::db::DBClient;
+use crate::models::tool_acknowledgment::{PowerfulToolKind, ToolAcknowledgment};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+/// Record ID of the acknowledgment of one tool for an agent.
+fn acknowledgment_record_id(agent_id: &str, tool_name: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", agent_id, tool_name).as_bytes());
+    digest[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Loads the acknowledged tools of an agent, sorted by tool name.
+pub async fn load_tool_acknowledgments(
+    db: &DBClient,
+    agent_id: &str,
+) -> anyhow::Result<Vec<ToolAcknowledgment>> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT agent_id, tool_name, kind, acknowledged_at FROM tool_acknowledgment \
+             WHERE agent_id = $agent_id ORDER BY tool_name ASC",
+            vec![("agent_id".to_string(), json!(agent_id))],
+        )
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match serde_json::from_value(row) {
+            Ok(acknowledgment) => Some(acknowledgment),
+            Err(e) => {
+                warn!(error = %e, "Skipping malformed tool acknowledgment record");
+                None
+            }
+        })
+        .collect())
+}
+
+/// Returns true if the user acknowledged the tool for the agent.
+pub async fn is_tool_acknowledged(
+    db: &DBClient,
+    agent_id: &str,
+    tool_name: &str,
+) -> anyhow::Result<bool> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id FROM type::thing('tool_acknowledgment', $id)",
+            vec![(
+                "id".to_string(),
+                json!(acknowledgment_record_id(agent_id, tool_name)),
+            )],
+        )
+        .await?;
+    Ok(!rows.is_empty())
+}
+
+/// Records the user's acknowledgment of a tool for an agent.
+pub async fn record_tool_acknowledgment(
+    db: &DBClient,
+    agent_id: &str,
+    tool_name: &str,
+    kind: PowerfulToolKind,
+) -> anyhow::Result<()> {
+    db.execute_with_params(
+        "UPSERT type::thing('tool_acknowledgment', $id) SET agent_id = $agent_id, \
+         tool_name = $tool_name, kind = $kind, acknowledged_at = time::now()",
+        vec![
+            (
+                "id".to_string(),
+                json!(acknowledgment_record_id(agent_id, tool_name)),
+            ),
+            ("agent_id".to_string(), json!(agent_id)),
+            ("tool_name".to_string(), json!(tool_name)),
+            ("kind".to_string(), json!(kind)),
+        ],
+    )
+    .await?;
+
+    debug!(agent_id = %agent_id, tool_name = %tool_name, kind = %kind, "Tool acknowledged");
+    Ok(())
+}
+
+/// Revokes the acknowledgment of one tool for an agent.
+pub async fn revoke_tool_acknowledgment(
+    db: &DBClient,
+    agent_id: &str,
+    tool_name: &str,
+) -> anyhow::Result<()> {
+    db.delete(&format!(
+        "tool_acknowledgment:{}",
+        acknowledgment_record_id(agent_id, tool_name)
+    ))
+    .await
+}
+
+/// Revokes the acknowledgments of an agent.
+///
+/// # Arguments
+/// * `tool_prefix` - Only revoke tools whose name starts with this prefix
+///   (e.g. `mcp__server__` for the tools of a removed MCP server); None revokes all
+pub async fn revoke_tool_acknowledgments(
+    db: &DBClient,
+    agent_id: &str,
+    tool_prefix: Option<&str>,
+) -> anyhow::Result<()> {
+    let (query, mut params) = (
+        "DELETE tool_acknowledgment WHERE agent_id = $agent_id",
+        vec![("agent_id".to_string(), json!(agent_id))],
+    );
+    match tool_prefix {
+        Some(prefix) => {
+            params.push(("prefix".to_string(), json!(prefix)));
+            db.execute_with_params(
+                &format!("{} AND string::starts_with(tool_name, $prefix)", query),
+                params,
+            )
+            .await
+        }
+        None => db.execute_with_params(query, params).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_record_and_revoke_tool_acknowledgments() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("tool_ack_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let shell = "mcp__shell__run_command";
+        assert!(!is_tool_acknowledged(&db, "agent1", shell).await.unwrap());
+
+        record_tool_acknowledgment(&db, "agent1", shell, PowerfulToolKind::Shell)
+            .await
+            .unwrap();
+        record_tool_acknowledgment(
+            &db,
+            "agent1",
+            "mcp__fs__write_file",
+            PowerfulToolKind::FileSystemWrite,
+        )
+        .await
+        .unwrap();
+        record_tool_acknowledgment(
+            &db,
+            "agent1",
+            "mcp__db__execute_sql",
+            PowerfulToolKind::ExternalSql,
+        )
+        .await
+        .unwrap();
+        revoke_tool_acknowledgment(&db, "agent1", "mcp__db__execute_sql")
+            .await
+            .unwrap();
+        assert!(is_tool_acknowledged(&db, "agent1", shell).await.unwrap());
+        assert!(!is_tool_acknowledged(&db, "agent2", shell).await.unwrap());
+
+        let acknowledgments = load_tool_acknowledgments(&db, "agent1").await.unwrap();
+        assert_eq!(acknowledgments.len(), 2);
+        assert_eq!(acknowledgments[0].tool_name, "mcp__fs__write_file");
+        assert_eq!(acknowledgments[1].kind, PowerfulToolKind::Shell);
+
+        revoke_tool_acknowledgments(&db, "agent1", Some("mcp__shell__"))
+            .await
+            .unwrap();
+        assert!(!is_tool_acknowledged(&db, "agent1", shell).await.unwrap());
+        assert_eq!(
+            load_tool_acknowledgments(&db, "agent1")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        revoke_tool_acknowledgments(&db, "agent1", None)
+            .await
+            .unwrap();
+        assert!(load_tool_acknowledgments(&db, "agent1")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,328 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diffs between the results of repeated tool calls.
+//!
+//! Agents often call the same tool with the same input several times in a
+//! workflow (e.g. a `list` operation after each change). When such a call is
+//! persisted, its result is compared to the previous result of the same call
+//! and the difference is stored with the execution, so the UI can show what
+//! changed instead of the full repeated output.
+//!
+//! A diff is a JSON array of operations in the style of RFC 6902:
+//! `{"op": "add" | "remove" | "replace", "path": "/items/2", "value": .., "old": ..}`.
+//! Arrays are compared by index. An empty array means the result is unchanged.
+
+use crate::db::DBClient;
+use crate::security::WORKFLOW_VAULT;
+use crate::tools::constants::tool_execution::MAX_DIFF_LOOKBACK;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Previous result of a call, as stored for the diff
+struct PreviousResult {
+    /// Execution ID of the previous call
+    execution_id: String,
+    /// Output of the previous call
+    output: Value,
+}
+
+/// Computes the result diffs of the tool executions persisted for a workflow.
+///
+/// Results recorded during the run are kept in memory; the first repeated call
+/// of a run is compared to the latest matching execution in the database.
+pub struct ResultDiffs<'a> {
+    db: &'a DBClient,
+    workflow_id: &'a str,
+    /// Latest result per call (server, tool and input)
+    latest: HashMap<String, PreviousResult>,
+}
+
+impl<'a> ResultDiffs<'a> {
+    /// Creates a diff tracker for a workflow.
+    pub fn new(db: &'a DBClient, workflow_id: &'a str) -> Self {
+        Self {
+            db,
+            workflow_id,
+            latest: HashMap::new(),
+        }
+    }
+
+    /// Compares a call result to the previous result of the same call.
+    ///
+    /// Successful calls are recorded as the latest result of their call.
+    ///
+    /// # Arguments
+    /// * `execution_id` - ID under which the call is persisted
+    /// * `tool_name` - Tool name (without the MCP server prefix)
+    /// * `server_name` - MCP server name (None for local tools)
+    /// * `input` - Call input
+    /// * `output` - Call result
+    /// * `success` - Whether the call succeeded (failed calls are not diffed)
+    ///
+    /// # Returns
+    /// The previous execution ID and the diff, or None if there is no previous
+    /// result or the diff would not be smaller than the result itself
+    pub async fn diff(
+        &mut self,
+        execution_id: &str,
+        tool_name: &str,
+        server_name: Option<&str>,
+        input: &Value,
+        output: &Value,
+        success: bool,
+    ) -> Option<(String, Value)> {
+        if !success {
+            return None;
+        }
+
+        let key = call_key(tool_name, server_name, input);
+        let previous = match self.latest.remove(&key) {
+            Some(previous) => Some(previous),
+            None => self.load_previous(tool_name, server_name, input).await,
+        };
+
+        let diff = previous.and_then(|previous| {
+            compact_diff(&previous.output, output).map(|diff| (previous.execution_id, diff))
+        });
+
+        self.latest.insert(
+            key,
+            PreviousResult {
+                execution_id: execution_id.to_string(),
+                output: output.clone(),
+            },
+        );
+        diff
+    }
+
+    /// Loads the latest persisted result of a call in the workflow.
+    async fn load_previous(
+        &self,
+        tool_name: &str,
+        server_name: Option<&str>,
+        input: &Value,
+    ) -> Option<PreviousResult> {
+        let query = format!(
+            "SELECT meta::id(id) AS id, server_name, input_params, output_result, created_at \
+             FROM tool_execution WHERE workflow_id = $workflow_id AND tool_name = $tool_name \
+             AND success = true ORDER BY created_at DESC LIMIT {}",
+            MAX_DIFF_LOOKBACK
+        );
+        let params = vec![
+            ("workflow_id".to_string(), json!(self.workflow_id)),
+            ("tool_name".to_string(), json!(tool_name)),
+        ];
+        let rows = match self.db.query_json_with_params(&query, params).await {
+            Ok(rows) => rows,
+            Err(e) => {
+                warn!(error = %e, tool_name = %tool_name, "Failed to load previous tool result");
+                return None;
+            }
+        };
+
+        rows.into_iter().find_map(|row| {
+            if row.get("server_name").and_then(Value::as_str) != server_name {
+                return None;
+            }
+            let stored_input = self.stored_json(row.get("input_params"))?;
+            if &stored_input != input {
+                return None;
+            }
+            Some(PreviousResult {
+                execution_id: row.get("id")?.as_str()?.to_string(),
+                output: self.stored_json(row.get("output_result"))?,
+            })
+        })
+    }
+
+    /// Decodes a JSON string column, decrypting it for sensitive workflows.
+    fn stored_json(&self, value: Option<&Value>) -> Option<Value> {
+        let value = match value? {
+            Value::String(s) => serde_json::from_str(s).ok()?,
+            other => other.clone(),
+        };
+        WORKFLOW_VAULT.decrypt_json(self.workflow_id, &value).ok()
+    }
+}
+
+/// Key identifying repeated calls (same server, tool and input).
+fn call_key(tool_name: &str, server_name: Option<&str>, input: &Value) -> String {
+    format!(
+        "{}\n{}\n{}",
+        server_name.unwrap_or_default(),
+        tool_name,
+        input
+    )
+}
+
+/// Returns the diff between two results if it is smaller than the new result.
+pub fn compact_diff(previous: &Value, current: &Value) -> Option<Value> {
+    let diff = Value::Array(diff_results(previous, current));
+    (diff.to_string().len() < current.to_string().len()).then_some(diff)
+}
+
+/// Computes the operations turning `previous` into `current`.
+pub fn diff_results(previous: &Value, current: &Value) -> Vec<Value> {
+    let mut ops = Vec::new();
+    diff_into(previous, current, "", &mut ops);
+    ops
+}
+
+fn diff_into(previous: &Value, current: &Value, path: &str, ops: &mut Vec<Value>) {
+    match (previous, current) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let child = format!("{}/{}", path, escape_pointer(key));
+                match new.get(key) {
+                    Some(new_value) => diff_into(old_value, new_value, &child, ops),
+                    None => ops.push(json!({"op": "remove", "path": child, "old": old_value})),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    let child = format!("{}/{}", path, escape_pointer(key));
+                    ops.push(json!({"op": "add", "path": child, "value": new_value}));
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (index, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                diff_into(old_value, new_value, &format!("{}/{}", path, index), ops);
+            }
+            // Removals from the end, so each path is valid when applied in order
+            for index in (new.len()..old.len()).rev() {
+                let child = format!("{}/{}", path, index);
+                ops.push(json!({"op": "remove", "path": child, "old": old[index]}));
+            }
+            for (index, new_value) in new.iter().enumerate().skip(old.len()) {
+                let child = format!("{}/{}", path, index);
+                ops.push(json!({"op": "add", "path": child, "value": new_value}));
+            }
+        }
+        (old, new) if old != new => {
+            ops.push(json!({"op": "replace", "path": path, "old": old, "value": new}));
+        }
+        _ => {}
+    }
+}
+
+/// Escapes a key for use in a JSON pointer (RFC 6901).
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ToolExecutionCreate;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_diff_results() {
+        let previous = json!({"count": 2, "items": [{"id": 1}, {"id": 2}], "a/b": true});
+        let current = json!({"count": 3, "items": [{"id": 1}, {"id": 2}, {"id": 3}]});
+
+        assert_eq!(
+            diff_results(&previous, &current),
+            vec![
+                json!({"op": "replace", "path": "/count", "old": 2, "value": 3}),
+                json!({"op": "add", "path": "/items/2", "value": {"id": 3}}),
+                json!({"op": "remove", "path": "/a~1b", "old": true}),
+            ]
+        );
+        assert!(diff_results(&current, &current).is_empty());
+        assert_eq!(
+            diff_results(&json!([1, 2, 3]), &json!([1])),
+            vec![
+                json!({"op": "remove", "path": "/2", "old": 3}),
+                json!({"op": "remove", "path": "/1", "old": 2}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compact_diff_skips_larger_diffs() {
+        assert_eq!(
+            compact_diff(&json!({"a": 1, "b": "x"}), &json!({"a": 1, "b": "x"})),
+            Some(json!([]))
+        );
+        assert!(compact_diff(&json!("old value"), &json!("new")).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_result_diffs_across_runs() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test_db");
+        let db = Arc::new(DBClient::new(db_path.to_str().unwrap()).await.unwrap());
+        db.initialize_schema().await.unwrap();
+
+        let input = json!({"operation": "list"});
+        let tasks = |count: usize| -> Value {
+            let tasks: Vec<Value> = (0..count)
+                .map(|i| json!({"id": i, "name": format!("Task {}", i), "status": "pending"}))
+                .collect();
+            json!({"tasks": tasks, "total": count})
+        };
+        let first = tasks(10);
+        db.create(
+            "tool_execution",
+            "exec1",
+            ToolExecutionCreate::local(
+                "wf-1".to_string(),
+                "msg-1".to_string(),
+                "agent-1".to_string(),
+                "TodoTool".to_string(),
+                input.clone(),
+                first,
+                true,
+                None,
+                5,
+                0,
+            ),
+        )
+        .await
+        .unwrap();
+
+        let mut diffs = ResultDiffs::new(&db, "wf-1");
+        let second = tasks(11);
+        let (previous_id, diff) = diffs
+            .diff("exec2", "TodoTool", None, &input, &second, true)
+            .await
+            .unwrap();
+        assert_eq!(previous_id, "exec1");
+        assert_eq!(diff.as_array().unwrap().len(), 2);
+
+        // Later calls of the run are compared to the in-memory result
+        let (previous_id, diff) = diffs
+            .diff("exec3", "TodoTool", None, &input, &second, true)
+            .await
+            .unwrap();
+        assert_eq!(previous_id, "exec2");
+        assert_eq!(diff, json!([]));
+
+        // Other inputs and failed calls are not diffed
+        let other = json!({"operation": "get", "task_id": "a"});
+        assert!(diffs
+            .diff("exec4", "TodoTool", None, &other, &second, true)
+            .await
+            .is_none());
+        assert!(diffs
+            .diff("exec5", "TodoTool", None, &input, &json!({}), false)
+            .await
+            .is_none());
+    }
+}
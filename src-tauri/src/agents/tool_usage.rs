@@ -0,0 +1,193 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistence of per-agent tool usage statistics.
+//!
+//! Statistics are updated after each workflow run from the run's tool
+//! executions and loaded at the start of a run to build the "past tool usage"
+//! hint of the system prompt (see [`crate::models::agent_tool_usage`]).
+
+use crate::db::DBClient;
+use crate::models::agent_tool_usage::{extract_task_keywords, merge_task_keywords, AgentToolUsage};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tracing::{debug, warn};
+
+/// Record ID of the statistics of one tool of an agent.
+fn usage_record_id(agent_id: &str, tool_name: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", agent_id, tool_name).as_bytes());
+    digest[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Loads the tool usage statistics of an agent, sorted by tool name.
+pub async fn load_agent_tool_usage(
+    db: &DBClient,
+    agent_id: &str,
+) -> anyhow::Result<Vec<AgentToolUsage>> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT agent_id, tool_name, success_count, failure_count, task_keywords, last_used_at \
+             FROM agent_tool_usage WHERE agent_id = $agent_id ORDER BY tool_name ASC",
+            vec![("agent_id".to_string(), json!(agent_id))],
+        )
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| match serde_json::from_value(row) {
+            Ok(usage) => Some(usage),
+            Err(e) => {
+                warn!(error = %e, "Skipping malformed tool usage record");
+                None
+            }
+        })
+        .collect())
+}
+
+/// Adds the tool calls of a run to the agent's statistics.
+///
+/// # Arguments
+/// * `calls` - Tool name (as called by the agent) and success of each call
+/// * `task` - Task description of the run, source of the task keywords
+pub async fn record_agent_tool_usage(
+    db: &DBClient,
+    agent_id: &str,
+    task: &str,
+    calls: &[(String, bool)],
+) -> anyhow::Result<()> {
+    if calls.is_empty() {
+        return Ok(());
+    }
+
+    // (successes, failures) per tool
+    let mut counts: HashMap<&str, (u64, u64)> = HashMap::new();
+    for (tool_name, success) in calls {
+        let entry = counts.entry(tool_name.as_str()).or_default();
+        if *success {
+            entry.0 += 1;
+        } else {
+            entry.1 += 1;
+        }
+    }
+
+    let existing: HashMap<String, AgentToolUsage> = load_agent_tool_usage(db, agent_id)
+        .await?
+        .into_iter()
+        .map(|u| (u.tool_name.clone(), u))
+        .collect();
+    let keywords = extract_task_keywords(task);
+
+    for (tool_name, (successes, failures)) in counts {
+        let previous = existing.get(tool_name);
+        let previous_keywords = previous.map(|u| u.task_keywords.as_slice()).unwrap_or(&[]);
+        // Only tasks where the tool worked describe what it is good for
+        let task_keywords = if successes > 0 {
+            merge_task_keywords(previous_keywords, &keywords)
+        } else {
+            previous_keywords.to_vec()
+        };
+
+        db.execute_with_params(
+            "UPSERT type::thing('agent_tool_usage', $id) SET agent_id = $agent_id, \
+             tool_name = $tool_name, success_count = $success_count, \
+             failure_count = $failure_count, task_keywords = $task_keywords, \
+             last_used_at = time::now()",
+            vec![
+                (
+                    "id".to_string(),
+                    json!(usage_record_id(agent_id, tool_name)),
+                ),
+                ("agent_id".to_string(), json!(agent_id)),
+                ("tool_name".to_string(), json!(tool_name)),
+                (
+                    "success_count".to_string(),
+                    json!(previous.map_or(0, |u| u.success_count) + successes),
+                ),
+                (
+                    "failure_count".to_string(),
+                    json!(previous.map_or(0, |u| u.failure_count) + failures),
+                ),
+                ("task_keywords".to_string(), json!(task_keywords)),
+            ],
+        )
+        .await?;
+    }
+
+    debug!(agent_id = %agent_id, calls = calls.len(), "Tool usage statistics updated");
+    Ok(())
+}
+
+/// Deletes the tool usage statistics of an agent.
+pub async fn reset_agent_tool_usage(db: &DBClient, agent_id: &str) -> anyhow::Result<()> {
+    db.execute_with_params(
+        "DELETE agent_tool_usage WHERE agent_id = $agent_id",
+        vec![("agent_id".to_string(), json!(agent_id))],
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_record_and_reset_agent_tool_usage() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("tool_usage_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let calls = vec![
+            ("MemoryTool".to_string(), true),
+            ("MemoryTool".to_string(), false),
+            ("mcp__search__query".to_string(), false),
+        ];
+        record_agent_tool_usage(&db, "agent1", "Summarize the invoices", &calls)
+            .await
+            .unwrap();
+        record_agent_tool_usage(
+            &db,
+            "agent1",
+            "Archive contracts",
+            &[("MemoryTool".to_string(), true)],
+        )
+        .await
+        .unwrap();
+        record_agent_tool_usage(&db, "agent2", "Other", &[("TodoTool".to_string(), true)])
+            .await
+            .unwrap();
+
+        let usage = load_agent_tool_usage(&db, "agent1").await.unwrap();
+        assert_eq!(usage.len(), 2);
+        let memory = &usage[0];
+        assert_eq!(memory.tool_name, "MemoryTool");
+        assert_eq!((memory.success_count, memory.failure_count), (2, 1));
+        assert_eq!(
+            memory.task_keywords,
+            vec!["archive", "contracts", "summarize", "invoices"]
+        );
+        let search = &usage[1];
+        assert_eq!((search.success_count, search.failure_count), (0, 1));
+        assert!(search.task_keywords.is_empty());
+
+        reset_agent_tool_usage(&db, "agent1").await.unwrap();
+        assert!(load_agent_tool_usage(&db, "agent1")
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(load_agent_tool_usage(&db, "agent2").await.unwrap().len(), 1);
+    }
+}
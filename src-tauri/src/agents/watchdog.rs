@@ -0,0 +1,249 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Heartbeat watchdog for stuck tool loops.
+//!
+//! Started at the beginning of an agent run, [`Watchdog`] tracks the phase of
+//! the tool loop and the time elapsed since the last stream event of the
+//! workflow (emitted by the agent, its tools or its sub-agents). After
+//! `stall_threshold_secs` of silence it emits a single `possibly_stuck` chunk
+//! with the current phase, so the frontend can offer to cancel the run
+//! instead of showing a silent spinner. The next event re-arms it.
+
+use crate::db::{queries::settings, DBClient};
+use crate::models::streaming::{events, ChunkType, StreamChunk};
+use crate::models::watchdog::{RunPhase, WatchdogSettings};
+use serde::Deserialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, EventId, Listener};
+use tracing::{debug, warn};
+
+/// Storage key of the watchdog settings
+pub const WATCHDOG_SETTINGS_KEY: &str = "settings:watchdog";
+
+/// Loads watchdog settings from the database, falling back to defaults.
+pub async fn load_watchdog_settings(db: &DBClient) -> WatchdogSettings {
+    match settings::load_config(db, WATCHDOG_SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No watchdog settings found, using defaults");
+            WatchdogSettings::default()
+        }
+    }
+}
+
+/// Fields of a stream chunk read by the watchdog
+#[derive(Deserialize)]
+struct ChunkHeader {
+    workflow_id: String,
+    chunk_type: ChunkType,
+}
+
+/// State shared with the check task and the event listener
+struct Heartbeat {
+    /// Time of the last event (or phase change)
+    last_event: Instant,
+    /// Current phase of the tool loop
+    phase: Option<RunPhase>,
+    /// Whether the current silence was already reported
+    reported: bool,
+}
+
+/// Watchdog of a single agent run (stops when dropped)
+pub struct Watchdog {
+    /// Heartbeat shared with the check task
+    heartbeat: Arc<Mutex<Heartbeat>>,
+    /// Periodic silence check
+    task: tokio::task::JoinHandle<()>,
+    /// Stream event listener (removed on drop)
+    listener: Option<(AppHandle, EventId)>,
+}
+
+impl Watchdog {
+    /// Starts a watchdog calling `on_stall` once per silence longer than
+    /// `threshold`, with the current phase and the silence duration.
+    pub fn start<F>(threshold: Duration, on_stall: F) -> Self
+    where
+        F: Fn(&RunPhase, Duration) + Send + 'static,
+    {
+        let heartbeat = Arc::new(Mutex::new(Heartbeat {
+            last_event: Instant::now(),
+            phase: None,
+            reported: false,
+        }));
+        let shared = heartbeat.clone();
+        let check_every = (threshold / 10).max(Duration::from_millis(10));
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(check_every);
+            loop {
+                interval.tick().await;
+                let stall = match shared.lock() {
+                    Ok(mut heartbeat) => {
+                        let silence = heartbeat.last_event.elapsed();
+                        match &heartbeat.phase {
+                            Some(phase) if !heartbeat.reported && silence >= threshold => {
+                                let phase = phase.clone();
+                                heartbeat.reported = true;
+                                Some((phase, silence))
+                            }
+                            _ => None,
+                        }
+                    }
+                    Err(_) => break,
+                };
+                if let Some((phase, silence)) = stall {
+                    on_stall(&phase, silence);
+                }
+            }
+        });
+
+        Self {
+            heartbeat,
+            task,
+            listener: None,
+        }
+    }
+
+    /// Starts the watchdog of a workflow run.
+    ///
+    /// Any `workflow_stream` event of the workflow counts as a heartbeat; a
+    /// stall is reported as a `possibly_stuck` chunk of the workflow.
+    pub fn for_workflow(app_handle: AppHandle, workflow_id: String, threshold: Duration) -> Self {
+        let emitter = app_handle.clone();
+        let stalled_workflow = workflow_id.clone();
+        let mut watchdog = Self::start(threshold, move |phase, silence| {
+            warn!(
+                workflow_id = %stalled_workflow,
+                phase = %phase.describe(),
+                silence_ms = silence.as_millis() as u64,
+                "Run possibly stuck"
+            );
+            let chunk = StreamChunk::possibly_stuck(
+                stalled_workflow.clone(),
+                phase.describe(),
+                phase.tool(),
+                silence.as_millis() as u64,
+            );
+            if let Err(e) = emitter.emit(events::WORKFLOW_STREAM, &chunk) {
+                warn!(error = %e, "Failed to emit possibly stuck event");
+            }
+        });
+
+        let heartbeat = watchdog.heartbeat.clone();
+        let id = app_handle.listen(events::WORKFLOW_STREAM, move |event| {
+            let Ok(header) = serde_json::from_str::<ChunkHeader>(event.payload()) else {
+                return;
+            };
+            if header.workflow_id == workflow_id && header.chunk_type != ChunkType::PossiblyStuck {
+                Self::beat_shared(&heartbeat);
+            }
+        });
+        watchdog.listener = Some((app_handle, id));
+        watchdog
+    }
+
+    /// Records activity, re-arming the stall report.
+    #[allow(dead_code)]
+    pub fn beat(&self) {
+        Self::beat_shared(&self.heartbeat);
+    }
+
+    /// Enters a new phase of the tool loop (counts as activity).
+    pub fn set_phase(&self, phase: RunPhase) {
+        if let Ok(mut heartbeat) = self.heartbeat.lock() {
+            heartbeat.phase = Some(phase);
+            heartbeat.last_event = Instant::now();
+            heartbeat.reported = false;
+        }
+    }
+
+    fn beat_shared(heartbeat: &Mutex<Heartbeat>) {
+        if let Ok(mut heartbeat) = heartbeat.lock() {
+            heartbeat.last_event = Instant::now();
+            heartbeat.reported = false;
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.task.abort();
+        if let Some((app_handle, id)) = self.listener.take() {
+            app_handle.unlisten(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Stalls = Arc<Mutex<Vec<(RunPhase, Duration)>>>;
+
+    fn recording_watchdog(threshold: Duration) -> (Watchdog, Stalls) {
+        let stalls: Stalls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = stalls.clone();
+        let watchdog = Watchdog::start(threshold, move |phase, silence| {
+            recorded.lock().unwrap().push((phase.clone(), silence));
+        });
+        (watchdog, stalls)
+    }
+
+    #[tokio::test]
+    async fn test_reports_stall_once_per_silence() {
+        let threshold = Duration::from_millis(100);
+        let (watchdog, stalls) = recording_watchdog(threshold);
+        let phase = RunPhase::Tools {
+            iteration: 2,
+            tools: vec!["web_fetch".to_string()],
+        };
+        watchdog.set_phase(phase.clone());
+
+        tokio::time::sleep(Duration::from_millis(350)).await;
+        {
+            let stalls = stalls.lock().unwrap();
+            assert_eq!(stalls.len(), 1);
+            assert_eq!(stalls[0].0, phase);
+            assert!(stalls[0].1 >= threshold);
+        }
+
+        // An event re-arms the report
+        watchdog.beat();
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(stalls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeats_prevent_stall() {
+        let (watchdog, stalls) = recording_watchdog(Duration::from_millis(300));
+        watchdog.set_phase(RunPhase::LlmCall { iteration: 1 });
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            watchdog.beat();
+        }
+        assert!(stalls.lock().unwrap().is_empty());
+
+        // No report before the first phase, nor after the watchdog is dropped
+        let (watchdog, stalls) = recording_watchdog(Duration::from_millis(50));
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(stalls.lock().unwrap().is_empty());
+        watchdog.set_phase(RunPhase::LlmCall { iteration: 1 });
+        drop(watchdog);
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(stalls.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command palette commands.
+//!
+//! Builds the action registry from the static features of the application
+//! plus the user's prompt templates and agents, and searches it.
+
+use crate::models::action::{rank_actions, ActionCategory, ActionMatch, PaletteAction};
+use crate::state::AppState;
+use serde::Deserialize;
+use tauri::State;
+use tracing::{info, instrument, warn};
+
+/// Default number of results returned by `search_actions`
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+/// Maximum number of results returned by `search_actions`
+const MAX_SEARCH_LIMIT: usize = 100;
+/// Maximum query length accepted by `search_actions`
+const MAX_QUERY_LEN: usize = 256;
+
+/// Settings sections: (id, title, i18n key, keywords)
+const SETTINGS_SECTIONS: &[(&str, &str, &str, &[&str])] = &[
+    (
+        "providers",
+        "Providers",
+        "settings_providers",
+        &["llm", "api key", "models", "mistral", "ollama", "gemini"],
+    ),
+    (
+        "agents",
+        "Agents",
+        "settings_agents",
+        &["tools", "system prompt"],
+    ),
+    (
+        "mcp",
+        "MCP Servers",
+        "settings_mcp_servers",
+        &["tools", "servers"],
+    ),
+    (
+        "memory",
+        "Memory",
+        "settings_memory",
+        &["embeddings", "knowledge"],
+    ),
+    (
+        "validation",
+        "Validation",
+        "settings_validation",
+        &["approval", "security", "risk"],
+    ),
+    (
+        "prompts",
+        "Prompts",
+        "settings_prompts",
+        &["templates", "library"],
+    ),
+    (
+        "import-export",
+        "Import / Export",
+        "settings_import_export",
+        &["backup", "restore"],
+    ),
+    (
+        "theme",
+        "Theme",
+        "settings_theme",
+        &["dark", "light", "appearance"],
+    ),
+];
+
+/// Prompt template row used to build workflow actions
+#[derive(Debug, Deserialize)]
+struct PromptRow {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Actions that are always available.
+fn static_actions() -> Vec<PaletteAction> {
+    let create =
+        |id: &str, title: &str, description: &str, keywords: &[&str], route: &str| PaletteAction {
+            id: id.to_string(),
+            category: ActionCategory::Create,
+            title: title.to_string(),
+            label_key: None,
+            description: description.to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            route: route.to_string(),
+            params: Some(serde_json::json!({ "open": "create" })),
+        };
+
+    let mut actions = vec![
+        PaletteAction {
+            id: "workflow.create".to_string(),
+            category: ActionCategory::Workflow,
+            title: "New workflow".to_string(),
+            label_key: None,
+            description: "Start a new conversation with an agent".to_string(),
+            keywords: vec!["chat".to_string(), "conversation".to_string()],
+            route: "/agent".to_string(),
+            params: Some(serde_json::json!({ "open": "create" })),
+        },
+        create(
+            "agent.create",
+            "Create agent",
+            "Configure a new agent",
+            &["new"],
+            "/settings/agents",
+        ),
+        create(
+            "prompt.create",
+            "Create prompt template",
+            "Add a reusable prompt to the library",
+            &["new", "template"],
+            "/settings/prompts",
+        ),
+        create(
+            "mcp.create",
+            "Add MCP server",
+            "Connect a new MCP server",
+            &["new", "tools"],
+            "/settings/mcp",
+        ),
+        create(
+            "provider.create",
+            "Add custom provider",
+            "Connect an OpenAI-compatible provider",
+            &["new", "llm", "openai"],
+            "/settings/providers",
+        ),
+    ];
+
+    actions.extend(
+        SETTINGS_SECTIONS
+            .iter()
+            .map(|(id, title, label_key, keywords)| PaletteAction {
+                id: format!("settings.open:{}", id),
+                category: ActionCategory::Navigate,
+                title: format!("Open {} settings", title),
+                label_key: Some(label_key.to_string()),
+                description: format!("Go to the {} section of the settings", title),
+                keywords: keywords.iter().map(|k| k.to_string()).collect(),
+                route: format!("/settings/{}", id),
+                params: None,
+            }),
+    );
+
+    actions
+}
+
+/// Builds the full action registry (static actions, prompt templates, agents).
+async fn build_action_registry(state: &AppState) -> Vec<PaletteAction> {
+    let mut actions = static_actions();
+
+    let query = "SELECT meta::id(id) AS id, name, description FROM prompt ORDER BY name";
+    match state.db.query_json(query).await {
+        Ok(rows) => {
+            actions.extend(
+                rows.into_iter()
+                    .filter_map(|v| serde_json::from_value::<PromptRow>(v).ok())
+                    .map(|prompt| PaletteAction {
+                        id: format!("workflow.start_with_prompt:{}", prompt.id),
+                        category: ActionCategory::Workflow,
+                        title: format!("Start workflow with template: {}", prompt.name),
+                        label_key: None,
+                        description: prompt.description,
+                        keywords: vec!["prompt".to_string(), "template".to_string()],
+                        route: "/agent".to_string(),
+                        params: Some(serde_json::json!({ "prompt_id": prompt.id })),
+                    }),
+            );
+        }
+        Err(e) => warn!(error = %e, "Failed to load prompts for action registry"),
+    }
+
+    for agent_id in state.registry.list().await {
+        if let Some(agent) = state.registry.get(&agent_id).await {
+            let config = agent.config();
+            actions.push(PaletteAction {
+                id: format!("workflow.start_with_agent:{}", config.id),
+                category: ActionCategory::Workflow,
+                title: format!("Start workflow with agent: {}", config.name),
+                label_key: None,
+                description: format!("{} / {}", config.llm.provider, config.llm.model),
+                keywords: vec!["agent".to_string(), "chat".to_string()],
+                route: "/agent".to_string(),
+                params: Some(serde_json::json!({ "agent_id": config.id })),
+            });
+        }
+    }
+
+    actions
+}
+
+/// Searches the command palette actions with a fuzzy query.
+///
+/// An empty query returns the whole registry in its default order.
+#[tauri::command]
+#[instrument(name = "search_actions", skip(state), fields(query_len = query.len()))]
+pub async fn search_actions(
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ActionMatch>, String> {
+    if query.len() > MAX_QUERY_LEN {
+        return Err(format!(
+            "Query exceeds maximum length of {} characters",
+            MAX_QUERY_LEN
+        ));
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    let actions = build_action_registry(&state).await;
+    let matches = rank_actions(actions, &query, limit);
+
+    info!(count = matches.len(), "Searched palette actions");
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_static_actions() {
+        let actions = static_actions();
+
+        let ids: HashSet<&str> = actions.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids.len(), actions.len(), "action IDs must be unique");
+        assert!(ids.contains("agent.create"));
+        assert!(ids.contains("settings.open:mcp"));
+
+        let ranked = rank_actions(actions, "mcp", 3);
+        assert!(ranked.iter().any(|m| m.action.id == "mcp.create"));
+        assert!(ranked
+            .iter()
+            .any(|m| m.action.route == "/settings/mcp" && m.action.label_key.is_some()));
+    }
+}
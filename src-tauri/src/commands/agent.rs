@@ -23,20 +23,47 @@
 //! - [`create_agent`] - Create a new agent
 //! - [`update_agent`] - Update an existing agent
 //! - [`delete_agent`] - Delete an agent
-
+//! - [`run_agent_regressions`] - Run the regression cases bundled with an agent
+//! - [`probe_agent`] - Check the agent's provider, model, tools and MCP servers
+//! - [`get_agent_tool_usage`] - Get the agent's tool usage statistics
+//! - [`reset_agent_tool_usage`] - Clear the agent's tool usage statistics
+//! - [`get_agent_tool_acknowledgments`] - Get the powerful tools acknowledged for the agent
+//! - [`revoke_agent_tool_acknowledgment`] - Revoke acknowledged tools (asked again on next use)
+
+use crate::agents::behavior_changelog;
+use crate::agents::health::probe_agent_health;
+use crate::agents::knowledge;
+use crate::agents::regression::run_regression_suite;
+use crate::agents::tool_acknowledgment;
+use crate::agents::tool_usage;
 use crate::agents::LLMAgent;
+use crate::commands::workspace_defaults::load_workspace_defaults;
+use crate::models::agent::{parse_mcp_tool_policy_json, parse_tool_settings_json};
+use crate::models::agent_tool_usage::AgentToolUsage;
+use crate::models::audit::{AuditAction, AuditLogCreate};
+use crate::models::behavior_changelog as behavior_change;
+use crate::models::health::AgentHealthReport;
 use crate::models::llm_models::ProviderType;
+use crate::models::moderation::{parse_moderation_json, ModerationPolicy};
+use crate::models::regression::parse_regressions_json;
+use crate::models::tool_acknowledgment::ToolAcknowledgment;
 use crate::models::{
     AgentConfig, AgentConfigCreate, AgentConfigUpdate, AgentSummary, LLMConfig, Lifecycle,
+    MCPToolPolicy, RegressionReport, RegressionSuite, ToolSettings,
 };
-use crate::security::Validator;
+use crate::security::{audit, Validator};
 use crate::state::AppState;
 use crate::tools::constants::commands as cmd_const;
+use crate::tools::constants::tool_execution::{
+    MAX_CALLS_PER_MINUTE, MAX_CALLS_PER_WORKFLOW, MAX_TIMEOUT_SECS,
+};
 use crate::tools::context::AgentToolContext;
-use crate::tools::registry::TOOL_REGISTRY;
+use crate::tools::registry::{KNOWN_TOOLS, TOOL_REGISTRY};
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::State;
-use tracing::{error, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 /// Validates agent name
 fn validate_agent_name(name: &str) -> Result<String, String> {
@@ -122,7 +149,7 @@ fn validate_llm_config(llm: &LLMConfig) -> Result<LLMConfig, String> {
 }
 
 /// Validates tools list
-fn validate_tools(tools: &[String]) -> Result<Vec<String>, String> {
+pub(crate) fn validate_tools(tools: &[String]) -> Result<Vec<String>, String> {
     let mut validated = Vec::new();
 
     for tool in tools {
@@ -134,8 +161,7 @@ fn validate_tools(tools: &[String]) -> Result<Vec<String>, String> {
         if !TOOL_REGISTRY.has_tool(trimmed) {
             return Err(format!(
                 "Unknown tool '{}'. Available tools: {:?}",
-                trimmed,
-                TOOL_REGISTRY.available_tools()
+                trimmed, *KNOWN_TOOLS
             ));
         }
 
@@ -146,7 +172,7 @@ fn validate_tools(tools: &[String]) -> Result<Vec<String>, String> {
 }
 
 /// Validates MCP servers list
-fn validate_mcp_servers(servers: &[String]) -> Result<Vec<String>, String> {
+pub(crate) fn validate_mcp_servers(servers: &[String]) -> Result<Vec<String>, String> {
     let mut validated = Vec::new();
 
     for server in servers {
@@ -172,17 +198,268 @@ fn validate_mcp_servers(servers: &[String]) -> Result<Vec<String>, String> {
     Ok(validated)
 }
 
+/// Validates per-tool settings
+///
+/// Settings may only target known tools, and the `operation` parameter must be
+/// restricted through `allowed_operations` rather than defaults or fixed values.
+/// Timeouts are bounded by `MAX_TIMEOUT_SECS` and call quotas by
+/// `MAX_CALLS_PER_WORKFLOW` / `MAX_CALLS_PER_MINUTE`.
+fn validate_tool_settings(
+    settings: &HashMap<String, ToolSettings>,
+) -> Result<HashMap<String, ToolSettings>, String> {
+    let mut validated = HashMap::with_capacity(settings.len());
+
+    for (tool, tool_settings) in settings {
+        let trimmed = tool.trim();
+
+        if !TOOL_REGISTRY.has_tool(trimmed) {
+            return Err(format!(
+                "Tool settings reference unknown tool '{}'",
+                trimmed
+            ));
+        }
+
+        if tool_settings.defaults.contains_key("operation")
+            || tool_settings.fixed.contains_key("operation")
+        {
+            return Err(format!(
+                "Tool settings for '{}' cannot set 'operation', use allowed_operations instead",
+                trimmed
+            ));
+        }
+
+        if let Some(ops) = &tool_settings.allowed_operations {
+            if ops.is_empty() || ops.iter().any(|op| op.trim().is_empty()) {
+                return Err(format!(
+                    "allowed_operations for '{}' must list at least one non-empty operation",
+                    trimmed
+                ));
+            }
+        }
+
+        if let Some(secs) = tool_settings.timeout_secs {
+            if secs == 0 || secs > MAX_TIMEOUT_SECS {
+                return Err(format!(
+                    "timeout_secs for '{}' must be between 1 and {}",
+                    trimmed, MAX_TIMEOUT_SECS
+                ));
+            }
+        }
+
+        if let Some(max) = tool_settings.max_calls_per_workflow {
+            if max == 0 || max > MAX_CALLS_PER_WORKFLOW {
+                return Err(format!(
+                    "max_calls_per_workflow for '{}' must be between 1 and {}",
+                    trimmed, MAX_CALLS_PER_WORKFLOW
+                ));
+            }
+        }
+
+        if let Some(max) = tool_settings.max_calls_per_minute {
+            if max == 0 || max > MAX_CALLS_PER_MINUTE {
+                return Err(format!(
+                    "max_calls_per_minute for '{}' must be between 1 and {}",
+                    trimmed, MAX_CALLS_PER_MINUTE
+                ));
+            }
+        }
+
+        if !tool_settings.is_empty() {
+            validated.insert(trimmed.to_string(), tool_settings.clone());
+        }
+    }
+
+    let size = serde_json::to_string(&validated)
+        .map(|s| s.len())
+        .unwrap_or(usize::MAX);
+    if size > cmd_const::MAX_TOOL_SETTINGS_SIZE {
+        return Err(format!(
+            "Tool settings exceed maximum size of {} bytes",
+            cmd_const::MAX_TOOL_SETTINGS_SIZE
+        ));
+    }
+
+    Ok(validated)
+}
+
+/// Validates per-server MCP tool policies
+///
+/// Policies may only target MCP servers assigned to the agent, and tool names
+/// must be non-empty. Policies permitting every tool are dropped.
+fn validate_mcp_tool_policy(
+    policy: &HashMap<String, MCPToolPolicy>,
+    mcp_servers: &[String],
+) -> Result<HashMap<String, MCPToolPolicy>, String> {
+    let mut validated = HashMap::with_capacity(policy.len());
+
+    for (server, server_policy) in policy {
+        let trimmed = server.trim();
+
+        if !mcp_servers.iter().any(|s| s == trimmed) {
+            return Err(format!(
+                "MCP tool policy references MCP server '{}' not assigned to the agent",
+                trimmed
+            ));
+        }
+
+        let trim_names = |names: &[String]| -> Result<Vec<String>, String> {
+            names
+                .iter()
+                .map(|name| {
+                    let name = name.trim();
+                    if name.is_empty() {
+                        Err(format!(
+                            "MCP tool policy for '{}' contains an empty tool name",
+                            trimmed
+                        ))
+                    } else {
+                        Ok(name.to_string())
+                    }
+                })
+                .collect()
+        };
+
+        let allow = match &server_policy.allow {
+            Some(allow) if allow.is_empty() => {
+                return Err(format!(
+                    "MCP tool policy allow list for '{}' must list at least one tool \
+                     (remove the server to deny all its tools)",
+                    trimmed
+                ));
+            }
+            Some(allow) => Some(trim_names(allow)?),
+            None => None,
+        };
+
+        let server_policy = MCPToolPolicy {
+            allow,
+            deny: trim_names(&server_policy.deny)?,
+        };
+
+        if !server_policy.is_empty() {
+            validated.insert(trimmed.to_string(), server_policy);
+        }
+    }
+
+    let size = serde_json::to_string(&validated)
+        .map(|s| s.len())
+        .unwrap_or(usize::MAX);
+    if size > cmd_const::MAX_MCP_TOOL_POLICY_SIZE {
+        return Err(format!(
+            "MCP tool policy exceeds maximum size of {} bytes",
+            cmd_const::MAX_MCP_TOOL_POLICY_SIZE
+        ));
+    }
+
+    Ok(validated)
+}
+
+/// Validates an agent regression suite
+///
+/// Case names must be unique and every assertion must be well-formed.
+fn validate_regressions(suite: &RegressionSuite) -> Result<RegressionSuite, String> {
+    if suite.cases.len() > cmd_const::MAX_REGRESSION_CASES {
+        return Err(format!(
+            "Regression suite cannot have more than {} cases",
+            cmd_const::MAX_REGRESSION_CASES
+        ));
+    }
+
+    let mut validated = suite.clone();
+    let mut names = std::collections::HashSet::with_capacity(suite.cases.len());
+
+    for case in &mut validated.cases {
+        case.name = case.name.trim().to_string();
+        if case.name.is_empty() {
+            return Err("Regression case name cannot be empty".to_string());
+        }
+        if !names.insert(case.name.clone()) {
+            return Err(format!("Duplicate regression case name '{}'", case.name));
+        }
+        if case.input.trim().is_empty() {
+            return Err(format!(
+                "Regression case '{}' has an empty input",
+                case.name
+            ));
+        }
+        if case.assertions.is_empty() {
+            return Err(format!(
+                "Regression case '{}' must have at least one assertion",
+                case.name
+            ));
+        }
+        for assertion in &case.assertions {
+            assertion
+                .validate()
+                .map_err(|e| format!("Regression case '{}': {}", case.name, e))?;
+        }
+    }
+
+    let size = serde_json::to_string(&validated)
+        .map(|s| s.len())
+        .unwrap_or(usize::MAX);
+    if size > cmd_const::MAX_REGRESSIONS_SIZE {
+        return Err(format!(
+            "Regression suite exceeds maximum size of {} bytes",
+            cmd_const::MAX_REGRESSIONS_SIZE
+        ));
+    }
+
+    Ok(validated)
+}
+
+/// Validates an agent moderation policy
+///
+/// Blocked terms are trimmed and deduplicated (case-insensitive).
+fn validate_moderation(policy: &ModerationPolicy) -> Result<ModerationPolicy, String> {
+    let mut blocked_terms: Vec<String> = Vec::with_capacity(policy.blocked_terms.len());
+
+    for term in &policy.blocked_terms {
+        let term = term.trim();
+        if term.is_empty() {
+            return Err("Moderation blocked term cannot be empty".to_string());
+        }
+        if term.chars().count() > cmd_const::MAX_MODERATION_TERM_LEN {
+            return Err(format!(
+                "Moderation blocked term exceeds {} characters",
+                cmd_const::MAX_MODERATION_TERM_LEN
+            ));
+        }
+        if !blocked_terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+            blocked_terms.push(term.to_string());
+        }
+    }
+
+    if blocked_terms.len() > cmd_const::MAX_MODERATION_BLOCKED_TERMS {
+        return Err(format!(
+            "Moderation policy cannot have more than {} blocked terms",
+            cmd_const::MAX_MODERATION_BLOCKED_TERMS
+        ));
+    }
+
+    Ok(ModerationPolicy {
+        blocked_terms,
+        ..policy.clone()
+    })
+}
+
 /// Validates full agent creation config
 fn validate_agent_create(config: &AgentConfigCreate) -> Result<AgentConfigCreate, String> {
+    let mcp_servers = validate_mcp_servers(&config.mcp_servers)?;
     Ok(AgentConfigCreate {
         name: validate_agent_name(&config.name)?,
         lifecycle: config.lifecycle.clone(),
         llm: validate_llm_config(&config.llm)?,
         tools: validate_tools(&config.tools)?,
-        mcp_servers: validate_mcp_servers(&config.mcp_servers)?,
+        mcp_tool_policy: validate_mcp_tool_policy(&config.mcp_tool_policy, &mcp_servers)?,
+        mcp_servers,
         system_prompt: validate_system_prompt(&config.system_prompt)?,
         max_tool_iterations: config.max_tool_iterations.clamp(1, 200),
         enable_thinking: config.enable_thinking,
+        tool_settings: validate_tool_settings(&config.tool_settings)?,
+        regressions: validate_regressions(&config.regressions)?,
+        moderation: validate_moderation(&config.moderation)?,
+        inherit_workspace_defaults: config.inherit_workspace_defaults,
     })
 }
 
@@ -190,66 +467,95 @@ fn validate_agent_create(config: &AgentConfigCreate) -> Result<AgentConfigCreate
 // Database Serialization Helpers (OPT-5)
 // ============================================================================
 
-/// Serialized agent configuration fields for database operations
-struct SerializedAgentFields {
-    name_json: String,
-    llm_json: String,
-    tools_json: String,
-    mcp_json: String,
-    prompt_json: String,
-}
-
-/// Serializes agent configuration fields for database storage
-fn serialize_agent_fields(config: &AgentConfig) -> Result<SerializedAgentFields, String> {
-    let name_json = serde_json::to_string(&config.name).map_err(|e| {
-        error!(error = %e, "Failed to serialize name");
-        format!("Failed to serialize name: {}", e)
-    })?;
-
-    let llm_json = serde_json::to_string(&config.llm).map_err(|e| {
+/// Fields of an agent record, as SET clause (each field bound to its parameter)
+const AGENT_FIELDS_SET: &str = "name = $name, llm = $llm, tools = $tools, \
+     mcp_servers = $mcp_servers, system_prompt = $system_prompt, \
+     max_tool_iterations = $max_tool_iterations, enable_thinking = $enable_thinking, \
+     tool_settings = $tool_settings, mcp_tool_policy = $mcp_tool_policy, \
+     regressions = $regressions, moderation = $moderation, updated_at = time::now()";
+
+/// Serializes agent configuration fields as query parameters for [`AGENT_FIELDS_SET`]
+fn agent_field_params(config: &AgentConfig) -> Result<Vec<(String, Value)>, String> {
+    // Stored as JSON strings (SCHEMAFULL nested object workaround)
+    let encode = |name: &str, value: Result<String, serde_json::Error>| {
+        value.map(Value::String).map_err(|e| {
+            error!(error = %e, field = name, "Failed to serialize agent field");
+            format!("Failed to serialize {}: {}", name, e)
+        })
+    };
+    let llm = serde_json::to_value(&config.llm).map_err(|e| {
         error!(error = %e, "Failed to serialize LLM config");
         format!("Failed to serialize LLM config: {}", e)
     })?;
 
-    let tools_json = serde_json::to_string(&config.tools).map_err(|e| {
-        error!(error = %e, "Failed to serialize tools");
-        format!("Failed to serialize tools: {}", e)
-    })?;
-
-    let mcp_json = serde_json::to_string(&config.mcp_servers).map_err(|e| {
-        error!(error = %e, "Failed to serialize MCP servers");
-        format!("Failed to serialize MCP servers: {}", e)
-    })?;
-
-    let prompt_json = serde_json::to_string(&config.system_prompt).map_err(|e| {
-        error!(error = %e, "Failed to serialize system prompt");
-        format!("Failed to serialize system prompt: {}", e)
-    })?;
-
-    Ok(SerializedAgentFields {
-        name_json,
-        llm_json,
-        tools_json,
-        mcp_json,
-        prompt_json,
-    })
+    Ok(vec![
+        ("name".to_string(), json!(config.name)),
+        ("llm".to_string(), llm),
+        ("tools".to_string(), json!(config.tools)),
+        ("mcp_servers".to_string(), json!(config.mcp_servers)),
+        ("system_prompt".to_string(), json!(config.system_prompt)),
+        (
+            "max_tool_iterations".to_string(),
+            json!(config.max_tool_iterations),
+        ),
+        ("enable_thinking".to_string(), json!(config.enable_thinking)),
+        (
+            "tool_settings".to_string(),
+            encode(
+                "tool settings",
+                serde_json::to_string(&config.tool_settings),
+            )?,
+        ),
+        (
+            "mcp_tool_policy".to_string(),
+            encode(
+                "MCP tool policy",
+                serde_json::to_string(&config.mcp_tool_policy),
+            )?,
+        ),
+        (
+            "regressions".to_string(),
+            encode("regressions", serde_json::to_string(&config.regressions))?,
+        ),
+        (
+            "moderation".to_string(),
+            encode(
+                "moderation policy",
+                serde_json::to_string(&config.moderation),
+            )?,
+        ),
+    ])
 }
 
-/// Registers an LLMAgent in the registry with proper context
-async fn register_agent_runtime(state: &AppState, agent_id: &str, config: AgentConfig) {
+/// Builds an LLMAgent runtime instance with proper context
+fn build_agent_runtime(state: &AppState, config: AgentConfig) -> Arc<LLMAgent> {
     let agent_context = AgentToolContext::from_app_state_full(state);
-    let llm_agent = LLMAgent::with_context(
+    Arc::new(LLMAgent::with_context(
         config,
         state.llm_manager.clone(),
         state.tool_factory.clone(),
         agent_context,
-    );
+    ))
+}
+
+/// Registers an LLMAgent in the registry with proper context
+async fn register_agent_runtime(state: &AppState, agent_id: &str, config: AgentConfig) {
+    let llm_agent = build_agent_runtime(state, config);
     state
         .registry
-        .register(agent_id.to_string(), Arc::new(llm_agent))
+        .register(agent_id.to_string(), llm_agent)
         .await;
 }
 
+/// Hot-reloads an agent in the registry with a new configuration
+///
+/// The next workflow execution uses the new configuration; executions already
+/// running keep the instance they started with.
+async fn reload_agent_runtime(state: &AppState, agent_id: &str, config: AgentConfig) {
+    let llm_agent = build_agent_runtime(state, config);
+    state.registry.replace(agent_id, llm_agent).await;
+}
+
 /// Lists all agents with summary information
 #[tauri::command]
 #[instrument(name = "list_agents", skip(state))]
@@ -329,13 +635,28 @@ pub async fn create_agent(
         name,
         lifecycle,
         llm,
-        tools,
-        mcp_servers,
+        mut tools,
+        mut mcp_servers,
         system_prompt,
         max_tool_iterations,
         enable_thinking,
+        tool_settings,
+        mcp_tool_policy,
+        regressions,
+        moderation,
+        inherit_workspace_defaults,
     } = validated;
 
+    if inherit_workspace_defaults {
+        let defaults = load_workspace_defaults(&state.db).await;
+        defaults.apply(&mut tools, &mut mcp_servers);
+        debug!(
+            default_tools = defaults.tools.len(),
+            default_mcp_servers = defaults.mcp_servers.len(),
+            "Applied workspace defaults"
+        );
+    }
+
     // Persist to database - get lifecycle string before moving into AgentConfig
     let lifecycle_str = match lifecycle {
         Lifecycle::Permanent => "permanent",
@@ -352,41 +673,45 @@ pub async fn create_agent(
         system_prompt,
         max_tool_iterations,
         enable_thinking,
+        tool_settings,
+        mcp_tool_policy,
+        regressions,
+        moderation,
     };
 
     // Serialize fields for database (OPT-5 refactoring)
-    let fields = serialize_agent_fields(&agent_config)?;
+    let mut params = agent_field_params(&agent_config)?;
+    params.push(("id".to_string(), json!(agent_id)));
+    params.push(("lifecycle".to_string(), json!(lifecycle_str)));
 
     let query = format!(
-        "CREATE agent:`{}` CONTENT {{
-            id: '{}',
-            name: {},
-            lifecycle: '{}',
-            llm: {},
-            tools: {},
-            mcp_servers: {},
-            system_prompt: {},
-            max_tool_iterations: {},
-            enable_thinking: {},
-            created_at: time::now(),
-            updated_at: time::now()
-        }}",
-        agent_id,
-        agent_id,
-        fields.name_json,
-        lifecycle_str,
-        fields.llm_json,
-        fields.tools_json,
-        fields.mcp_json,
-        fields.prompt_json,
-        validated.max_tool_iterations,
-        validated.enable_thinking
+        "CREATE type::thing('agent', $id) SET lifecycle = $lifecycle, \
+         created_at = time::now(), {}",
+        AGENT_FIELDS_SET
     );
 
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to persist agent to database");
-        format!("Failed to persist agent: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to persist agent to database");
+            format!("Failed to persist agent: {}", e)
+        })?;
+
+    behavior_changelog::record_behavior_changes_best_effort(
+        &state.db,
+        vec![behavior_change::agent_created(&agent_config)],
+    )
+    .await;
+    audit::record(
+        &state.db,
+        AuditLogCreate {
+            details: json!({ "operation": "created", "name": agent_config.name }),
+            ..AuditLogCreate::new(AuditAction::AgentConfigChange, "user", &agent_id)
+        },
+    )
+    .await;
 
     // Register agent in runtime (OPT-5 refactoring)
     register_agent_runtime(state.inner(), &agent_id, agent_config).await;
@@ -397,7 +722,7 @@ pub async fn create_agent(
 
 /// Updates an existing agent
 ///
-/// Validates the configuration, updates database, and re-registers in memory.
+/// Validates the configuration, updates database, and hot-reloads the registered agent.
 #[tauri::command]
 #[instrument(name = "update_agent", skip(state, config), fields(agent_id = %agent_id))]
 pub async fn update_agent(
@@ -457,6 +782,32 @@ pub async fn update_agent(
         None => existing_config.enable_thinking,
     };
 
+    let new_tool_settings = match &config.tool_settings {
+        Some(t) => validate_tool_settings(t)?,
+        None => existing_config.tool_settings.clone(),
+    };
+
+    // Policies of servers no longer assigned to the agent are dropped
+    let new_mcp_tool_policy = match &config.mcp_tool_policy {
+        Some(p) => validate_mcp_tool_policy(p, &new_mcp)?,
+        None => existing_config
+            .mcp_tool_policy
+            .iter()
+            .filter(|(server, _)| new_mcp.contains(server))
+            .map(|(server, policy)| (server.clone(), policy.clone()))
+            .collect(),
+    };
+
+    let new_regressions = match &config.regressions {
+        Some(r) => validate_regressions(r)?,
+        None => existing_config.regressions.clone(),
+    };
+
+    let new_moderation = match &config.moderation {
+        Some(m) => validate_moderation(m)?,
+        None => existing_config.moderation.clone(),
+    };
+
     let updated_config = AgentConfig {
         id: validated_id.clone(),
         name: new_name,
@@ -467,39 +818,93 @@ pub async fn update_agent(
         system_prompt: new_prompt,
         max_tool_iterations: new_max_iterations,
         enable_thinking: new_enable_thinking,
+        tool_settings: new_tool_settings,
+        mcp_tool_policy: new_mcp_tool_policy,
+        regressions: new_regressions,
+        moderation: new_moderation,
     };
 
+    // Strict regression suites gate system prompt changes
+    if updated_config.system_prompt != existing_config.system_prompt
+        && updated_config.regressions.strict
+        && !updated_config.regressions.is_empty()
+    {
+        let report = run_regression_suite(
+            &updated_config,
+            state.llm_manager.clone(),
+            state.tool_factory.clone(),
+            Some(state.mcp_manager.clone()),
+        )
+        .await;
+
+        if !report.passed {
+            let failed: Vec<&str> = report
+                .results
+                .iter()
+                .filter(|r| !r.passed)
+                .map(|r| r.name.as_str())
+                .collect();
+            warn!(failed = ?failed, "System prompt update blocked by failing regressions");
+            return Err(format!(
+                "System prompt update blocked: {} of {} regression case(s) failed ({})",
+                report.failed,
+                report.total,
+                failed.join(", ")
+            ));
+        }
+    }
+
     // Serialize fields for database (OPT-5 refactoring)
-    let fields = serialize_agent_fields(&updated_config)?;
+    let mut params = agent_field_params(&updated_config)?;
+    params.push(("id".to_string(), json!(validated_id)));
 
-    let query = format!(
-        "UPDATE agent:`{}` SET
-            name = {},
-            llm = {},
-            tools = {},
-            mcp_servers = {},
-            system_prompt = {},
-            max_tool_iterations = {},
-            enable_thinking = {},
-            updated_at = time::now()",
-        validated_id,
-        fields.name_json,
-        fields.llm_json,
-        fields.tools_json,
-        fields.mcp_json,
-        fields.prompt_json,
-        new_max_iterations,
-        new_enable_thinking
-    );
+    let query = format!("UPDATE type::thing('agent', $id) SET {}", AGENT_FIELDS_SET);
 
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to update agent in database");
-        format!("Failed to update agent: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update agent in database");
+            format!("Failed to update agent: {}", e)
+        })?;
 
-    // Unregister old and register new agent (OPT-5 refactoring)
-    state.registry.unregister_any(&validated_id).await;
-    register_agent_runtime(state.inner(), &validated_id, updated_config.clone()).await;
+    // Tools of removed MCP servers are acknowledged again if granted back
+    for server in existing_config
+        .mcp_servers
+        .iter()
+        .filter(|s| !updated_config.mcp_servers.contains(s))
+    {
+        let prefix = format!("mcp__{}__", server);
+        if let Err(e) = tool_acknowledgment::revoke_tool_acknowledgments(
+            &state.db,
+            &validated_id,
+            Some(&prefix),
+        )
+        .await
+        {
+            warn!(error = %e, server = %server, "Failed to revoke tool acknowledgments");
+        }
+    }
+
+    let changes = behavior_change::diff_agent_configs(&existing_config, &updated_config);
+    let summaries: Vec<&str> = changes.iter().map(|c| c.summary.as_str()).collect();
+    audit::record(
+        &state.db,
+        AuditLogCreate {
+            details: json!({
+                "operation": "updated",
+                "name": updated_config.name,
+                "changes": summaries
+            }),
+            ..AuditLogCreate::new(AuditAction::AgentConfigChange, "user", &validated_id)
+        },
+    )
+    .await;
+    behavior_changelog::record_behavior_changes_best_effort(&state.db, changes).await;
+
+    // Hot-reload the runtime agent so the next execution uses the new config
+    reload_agent_runtime(state.inner(), &validated_id, updated_config.clone()).await;
 
     info!(agent_id = %validated_id, "Agent updated successfully");
     Ok(updated_config)
@@ -520,17 +925,50 @@ pub async fn delete_agent(agent_id: String, state: State<'_, AppState>) -> Resul
     })?;
 
     // Check agent exists
-    if state.registry.get(&validated_id).await.is_none() {
+    let existing = state.registry.get(&validated_id).await.ok_or_else(|| {
         warn!(agent_id = %validated_id, "Agent not found");
-        return Err("Agent not found".to_string());
-    }
+        "Agent not found".to_string()
+    })?;
+    let existing_config = existing.config().clone();
 
     // Delete from database
-    let query = format!("DELETE agent:`{}`", validated_id);
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to delete agent from database");
-        format!("Failed to delete agent: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(
+            "DELETE type::thing('agent', $id)",
+            vec![("id".to_string(), json!(validated_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete agent from database");
+            format!("Failed to delete agent: {}", e)
+        })?;
+
+    // Statistics are only meaningful for this agent
+    if let Err(e) = tool_usage::reset_agent_tool_usage(&state.db, &validated_id).await {
+        warn!(error = %e, "Failed to delete agent tool usage statistics");
+    }
+    if let Err(e) =
+        tool_acknowledgment::revoke_tool_acknowledgments(&state.db, &validated_id, None).await
+    {
+        warn!(error = %e, "Failed to delete agent tool acknowledgments");
+    }
+    if let Err(e) = knowledge::detach_agent(&state.db, &validated_id).await {
+        warn!(error = %e, "Failed to detach agent knowledge packs");
+    }
+    behavior_changelog::record_behavior_changes_best_effort(
+        &state.db,
+        vec![behavior_change::agent_deleted(&existing_config)],
+    )
+    .await;
+    audit::record(
+        &state.db,
+        AuditLogCreate {
+            details: json!({ "operation": "deleted", "name": existing_config.name }),
+            ..AuditLogCreate::new(AuditAction::AgentConfigChange, "user", &validated_id)
+        },
+    )
+    .await;
 
     // Unregister from memory
     state.registry.unregister_any(&validated_id).await;
@@ -539,20 +977,202 @@ pub async fn delete_agent(agent_id: String, state: State<'_, AppState>) -> Resul
     Ok(())
 }
 
+/// Runs the regression cases bundled with an agent
+///
+/// Each case is executed against the agent's current configuration, with the
+/// case's mocked tools replacing real tool execution.
+#[tauri::command]
+#[instrument(name = "run_agent_regressions", skip(state), fields(agent_id = %agent_id))]
+pub async fn run_agent_regressions(
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<RegressionReport, String> {
+    info!("Running agent regressions");
+
+    let validated_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    let agent = state.registry.get(&validated_id).await.ok_or_else(|| {
+        warn!(agent_id = %validated_id, "Agent not found");
+        "Agent not found".to_string()
+    })?;
+
+    let config = agent.config().clone();
+    if config.regressions.is_empty() {
+        return Err("Agent has no regression cases".to_string());
+    }
+
+    let report = run_regression_suite(
+        &config,
+        state.llm_manager.clone(),
+        state.tool_factory.clone(),
+        Some(state.mcp_manager.clone()),
+    )
+    .await;
+
+    info!(
+        passed = report.passed,
+        failed = report.failed,
+        total = report.total,
+        "Agent regressions finished"
+    );
+    Ok(report)
+}
+
+/// Probes the health of an agent before execution
+///
+/// Verifies that the agent's provider is configured, its model exists, all
+/// listed tools are known and its MCP servers are running. No LLM call is made.
+#[tauri::command]
+#[instrument(name = "probe_agent", skip(state), fields(agent_id = %agent_id))]
+pub async fn probe_agent(
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<AgentHealthReport, String> {
+    info!("Probing agent health");
+
+    let validated_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    let agent = state.registry.get(&validated_id).await.ok_or_else(|| {
+        warn!(agent_id = %validated_id, "Agent not found");
+        "Agent not found".to_string()
+    })?;
+
+    Ok(probe_agent_health(
+        agent.config(),
+        &state.llm_manager,
+        &state.db,
+        Some(&state.mcp_manager),
+    )
+    .await)
+}
+
+/// Gets the tool usage statistics of an agent
+///
+/// These statistics feed the "past tool usage" section of the agent's system prompt.
+#[tauri::command]
+#[instrument(name = "get_agent_tool_usage", skip(state), fields(agent_id = %agent_id))]
+pub async fn get_agent_tool_usage(
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<AgentToolUsage>, String> {
+    info!("Loading agent tool usage statistics");
+
+    let validated_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    tool_usage::load_agent_tool_usage(&state.db, &validated_id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load agent tool usage statistics");
+            format!("Failed to load tool usage statistics: {}", e)
+        })
+}
+
+/// Clears the tool usage statistics of an agent
+#[tauri::command]
+#[instrument(name = "reset_agent_tool_usage", skip(state), fields(agent_id = %agent_id))]
+pub async fn reset_agent_tool_usage(
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!("Resetting agent tool usage statistics");
+
+    let validated_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    tool_usage::reset_agent_tool_usage(&state.db, &validated_id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to reset agent tool usage statistics");
+            format!("Failed to reset tool usage statistics: {}", e)
+        })?;
+
+    info!("Agent tool usage statistics reset");
+    Ok(())
+}
+
+/// Gets the powerful tools (shell, file system writes, SQL) acknowledged for an agent
+#[tauri::command]
+#[instrument(name = "get_agent_tool_acknowledgments", skip(state), fields(agent_id = %agent_id))]
+pub async fn get_agent_tool_acknowledgments(
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ToolAcknowledgment>, String> {
+    info!("Loading agent tool acknowledgments");
+
+    let validated_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    tool_acknowledgment::load_tool_acknowledgments(&state.db, &validated_id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load agent tool acknowledgments");
+            format!("Failed to load tool acknowledgments: {}", e)
+        })
+}
+
+/// Revokes acknowledged tools of an agent, so their next use is acknowledged again
+///
+/// # Arguments
+/// * `agent_id` - Agent ID
+/// * `tool_name` - Tool to revoke (`mcp__server__tool`); None revokes all tools
+#[tauri::command]
+#[instrument(name = "revoke_agent_tool_acknowledgment", skip(state), fields(agent_id = %agent_id))]
+pub async fn revoke_agent_tool_acknowledgment(
+    agent_id: String,
+    tool_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!(tool_name = ?tool_name, "Revoking agent tool acknowledgment");
+
+    let validated_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    let revoked = match tool_name.as_deref() {
+        Some(name) => {
+            tool_acknowledgment::revoke_tool_acknowledgment(&state.db, &validated_id, name).await
+        }
+        None => {
+            tool_acknowledgment::revoke_tool_acknowledgments(&state.db, &validated_id, None).await
+        }
+    };
+
+    revoked.map_err(|e| {
+        error!(error = %e, "Failed to revoke tool acknowledgment");
+        format!("Failed to revoke tool acknowledgment: {}", e)
+    })?;
+
+    info!("Agent tool acknowledgment revoked");
+    Ok(())
+}
+
 /// Loads all agents from database and registers them in memory
 ///
-/// Note: This function is no longer called directly. Agent loading is now
-/// done inline in the setup hook in main.rs to ensure app_handle is available.
-#[allow(dead_code)]
+/// Called by the deferred startup once the app handle is set, so that the
+/// agent contexts can emit validation events.
 pub async fn load_agents_from_db(state: &AppState) -> Result<usize, String> {
     info!("Loading agents from database");
 
     // Query all agents from database
-    let query = "SELECT meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers, system_prompt FROM agent";
+    let query = "SELECT meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers, system_prompt, max_tool_iterations, enable_thinking, tool_settings, mcp_tool_policy, regressions, moderation FROM agent";
 
     let results: Vec<serde_json::Value> = state
         .db
-        .db
+        .conn()
         .query(query)
         .await
         .map(|mut r| r.take(0).unwrap_or_default())
@@ -627,6 +1247,14 @@ pub async fn load_agents_from_db(state: &AppState) -> Result<usize, String> {
 
         let enable_thinking = row["enable_thinking"].as_bool().unwrap_or(true);
 
+        let tool_settings = parse_tool_settings_json(row.get("tool_settings"));
+
+        let mcp_tool_policy = parse_mcp_tool_policy_json(row.get("mcp_tool_policy"));
+
+        let regressions = parse_regressions_json(row.get("regressions"));
+
+        let moderation = parse_moderation_json(row.get("moderation"));
+
         let config = AgentConfig {
             id: id.clone(),
             name,
@@ -637,6 +1265,10 @@ pub async fn load_agents_from_db(state: &AppState) -> Result<usize, String> {
             system_prompt,
             max_tool_iterations,
             enable_thinking,
+            tool_settings,
+            mcp_tool_policy,
+            regressions,
+            moderation,
         };
 
         // Create LLMAgent with AgentToolContext for sub-agent operations
@@ -658,11 +1290,13 @@ pub async fn load_agents_from_db(state: &AppState) -> Result<usize, String> {
 
 #[cfg(test)]
 mod tests {
+    use super::validate_mcp_tool_policy;
     use crate::agents::core::{AgentOrchestrator, AgentRegistry};
     use crate::agents::SimpleAgent;
     use crate::db::DBClient;
-    use crate::models::{AgentConfig, LLMConfig, Lifecycle};
+    use crate::models::{AgentConfig, LLMConfig, Lifecycle, MCPToolPolicy};
     use crate::state::AppState;
+    use std::collections::HashMap;
     use std::sync::Arc;
     use tempfile::tempdir;
 
@@ -698,6 +1332,13 @@ mod tests {
 
         AppState {
             db: db.clone(),
+            outbox: Arc::new(crate::db::outbox::PersistenceOutbox::new(
+                db_path.with_file_name("outbox.jsonl"),
+                Default::default(),
+            )),
+            attachments: Arc::new(crate::db::attachments::AttachmentStore::new(
+                db_path.with_file_name("attachments"),
+            )),
             registry,
             orchestrator,
             llm_manager,
@@ -711,6 +1352,14 @@ mod tests {
                 std::collections::HashMap::new(),
             )),
             app_handle: Arc::new(std::sync::RwLock::new(None)),
+            startup_status: Default::default(),
+            scheduler: Default::default(),
+            job_queue: Default::default(),
+            profiles: Arc::new(crate::profiles::ProfileStore::new(
+                db_path.parent().unwrap(),
+                &db_path,
+            )),
+            config: Default::default(),
         }
     }
 
@@ -741,6 +1390,10 @@ mod tests {
             system_prompt: "Test".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         let agent = SimpleAgent::new(config);
@@ -773,6 +1426,10 @@ mod tests {
             system_prompt: "You are a test agent".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         let agent = SimpleAgent::new(config.clone());
@@ -817,6 +1474,10 @@ mod tests {
             system_prompt: "Test prompt".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         // Verify JSON serialization
@@ -863,6 +1524,10 @@ mod tests {
                 system_prompt: format!("Agent {} prompt", i),
                 max_tool_iterations: 50,
                 enable_thinking: true,
+                tool_settings: Default::default(),
+                mcp_tool_policy: Default::default(),
+                regressions: Default::default(),
+                moderation: Default::default(),
             };
 
             let agent = SimpleAgent::new(config);
@@ -875,4 +1540,45 @@ mod tests {
         let agents = state.registry.list().await;
         assert_eq!(agents.len(), 5);
     }
+
+    #[test]
+    fn test_validate_mcp_tool_policy() {
+        let servers = vec!["serena".to_string()];
+        let policy = |value: serde_json::Value| -> HashMap<String, MCPToolPolicy> {
+            serde_json::from_value(value).unwrap()
+        };
+
+        let validated = validate_mcp_tool_policy(
+            &policy(serde_json::json!({
+                " serena ": {"allow": [" find_symbol "]}
+            })),
+            &servers,
+        )
+        .unwrap();
+        assert_eq!(
+            validated["serena"].allow,
+            Some(vec!["find_symbol".to_string()])
+        );
+
+        // Policies permitting every tool are dropped
+        let validated =
+            validate_mcp_tool_policy(&policy(serde_json::json!({"serena": {}})), &servers).unwrap();
+        assert!(validated.is_empty());
+
+        assert!(validate_mcp_tool_policy(
+            &policy(serde_json::json!({"context7": {"deny": ["resolve"]}})),
+            &servers
+        )
+        .is_err());
+        assert!(validate_mcp_tool_policy(
+            &policy(serde_json::json!({"serena": {"allow": []}})),
+            &servers
+        )
+        .is_err());
+        assert!(validate_mcp_tool_policy(
+            &policy(serde_json::json!({"serena": {"deny": [" "]}})),
+            &servers
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,433 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message attachment commands.
+//!
+//! Image attachments are sent with `save_message` (base64 data or a local
+//! file path), validated from their content, stored as blobs by the
+//! [`AttachmentStore`](crate::db::attachments::AttachmentStore) and recorded
+//! in the `attachment` table. Loaded messages carry their attachments'
+//! metadata; `get_attachment_data` returns a blob as a data URL for display.
+
+use crate::db::DBClient;
+use crate::models::attachment::{
+    detect_image_mime, Attachment, AttachmentCreate, AttachmentInput, VISION_IMAGE_TYPES,
+};
+use crate::models::Message;
+use crate::security::Validator;
+use crate::state::AppState;
+use crate::tools::chart;
+use crate::tools::constants::attachments as attachments_const;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Fields selected when loading attachments
+const ATTACHMENT_SELECT: &str = "SELECT meta::id(id) AS id, workflow_id, message_id, mime_type, \
+     file_name, size_bytes, created_at FROM attachment";
+
+/// Decoded attachment ready to be stored
+struct DecodedAttachment {
+    bytes: Vec<u8>,
+    mime_type: &'static str,
+    file_name: Option<String>,
+}
+
+/// Decodes and validates an attachment input (size, image content, file name).
+///
+/// # Arguments
+/// * `input` - Attachment input
+/// * `generated` - Whether the attachment comes with an assistant response:
+///   SVG charts rendered by the ChartTool are then accepted
+async fn decode_attachment(
+    input: &AttachmentInput,
+    generated: bool,
+) -> Result<DecodedAttachment, String> {
+    let (bytes, default_name) = match (&input.data, &input.path) {
+        (Some(data), None) => {
+            let encoded = match data.split_once(";base64,") {
+                Some((header, encoded)) if header.starts_with("data:") => encoded,
+                _ => data.as_str(),
+            };
+            // Base64 is 4/3 of the decoded size: reject oversized input before decoding
+            if encoded.len() / 4 * 3 > attachments_const::MAX_SIZE_BYTES + 3 {
+                return Err(size_error());
+            }
+            let bytes = STANDARD
+                .decode(encoded.trim())
+                .map_err(|e| format!("Invalid attachment data: {}", e))?;
+            (bytes, None)
+        }
+        (None, Some(path)) => {
+            let path = std::path::Path::new(path);
+            let metadata = tokio::fs::metadata(path)
+                .await
+                .map_err(|e| format!("Cannot read attachment file: {}", e))?;
+            if !metadata.is_file() {
+                return Err("Attachment path is not a file".to_string());
+            }
+            if metadata.len() > attachments_const::MAX_SIZE_BYTES as u64 {
+                return Err(size_error());
+            }
+            let bytes = tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Cannot read attachment file: {}", e))?;
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned());
+            (bytes, name)
+        }
+        _ => return Err("An attachment needs either data or a path".to_string()),
+    };
+
+    if bytes.is_empty() {
+        return Err("Attachment is empty".to_string());
+    }
+    if bytes.len() > attachments_const::MAX_SIZE_BYTES {
+        return Err(size_error());
+    }
+    let mime_type = match detect_image_mime(&bytes) {
+        Some(mime) if VISION_IMAGE_TYPES.contains(&mime) => mime,
+        Some(mime) if generated && is_rendered_chart(input).await => mime,
+        _ => return Err("Unsupported attachment: only PNG, JPEG, GIF and WebP images".to_string()),
+    };
+
+    let file_name = input
+        .file_name
+        .clone()
+        .or(default_name)
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    if let Some(name) = &file_name {
+        if name.len() > attachments_const::MAX_FILE_NAME_LEN || name.chars().any(char::is_control) {
+            return Err("Invalid attachment file name".to_string());
+        }
+    }
+
+    Ok(DecodedAttachment {
+        bytes,
+        mime_type,
+        file_name,
+    })
+}
+
+/// Whether an attachment input is a file rendered by the ChartTool.
+async fn is_rendered_chart(input: &AttachmentInput) -> bool {
+    let Some(path) = &input.path else {
+        return false;
+    };
+    let chart_dir = chart::output_dir();
+    match (
+        tokio::fs::canonicalize(path).await,
+        tokio::fs::canonicalize(chart_dir).await,
+    ) {
+        (Ok(path), Ok(chart_dir)) => path.starts_with(chart_dir),
+        _ => false,
+    }
+}
+
+fn size_error() -> String {
+    format!(
+        "Attachment exceeds maximum size of {} MB",
+        attachments_const::MAX_SIZE_BYTES / (1024 * 1024)
+    )
+}
+
+/// Stores the attachments of a new message (blobs and records).
+///
+/// All inputs are validated before anything is written. `generated` is set
+/// for the images of assistant responses (see [`decode_attachment`]).
+pub(crate) async fn store_attachments(
+    state: &AppState,
+    workflow_id: &str,
+    message_id: &str,
+    inputs: &[AttachmentInput],
+    generated: bool,
+) -> Result<Vec<Attachment>, String> {
+    if inputs.len() > attachments_const::MAX_PER_MESSAGE {
+        return Err(format!(
+            "A message can have at most {} attachments",
+            attachments_const::MAX_PER_MESSAGE
+        ));
+    }
+
+    let mut decoded = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        decoded.push(decode_attachment(input, generated).await?);
+    }
+
+    let mut attachments = Vec::with_capacity(decoded.len());
+    for attachment in decoded {
+        let attachment_id = Uuid::new_v4().to_string();
+        state
+            .attachments
+            .write(
+                &state.db,
+                &attachment_id,
+                workflow_id,
+                attachment.mime_type,
+                &attachment.bytes,
+            )
+            .await?;
+
+        let record = AttachmentCreate {
+            workflow_id: workflow_id.to_string(),
+            message_id: message_id.to_string(),
+            mime_type: attachment.mime_type.to_string(),
+            file_name: attachment.file_name.clone(),
+            size_bytes: attachment.bytes.len() as u64,
+        };
+        state
+            .outbox
+            .create(&state.db, "attachment", &attachment_id, record)
+            .await?;
+
+        attachments.push(Attachment {
+            id: attachment_id,
+            workflow_id: workflow_id.to_string(),
+            message_id: message_id.to_string(),
+            mime_type: attachment.mime_type.to_string(),
+            file_name: attachment.file_name,
+            size_bytes: attachment.bytes.len() as u64,
+            created_at: chrono::Utc::now(),
+        });
+    }
+
+    Ok(attachments)
+}
+
+/// Loads the attachments of a workflow (oldest first).
+pub(crate) async fn load_workflow_attachments(
+    db: &DBClient,
+    workflow_id: &str,
+) -> Result<Vec<Attachment>, String> {
+    let query = format!(
+        "{} WHERE workflow_id = $workflow_id ORDER BY created_at ASC",
+        ATTACHMENT_SELECT
+    );
+    let rows = db
+        .query_json_with_params(
+            &query,
+            vec![("workflow_id".to_string(), serde_json::json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load attachments: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect())
+}
+
+/// Sets the attachments of loaded messages.
+pub(crate) fn assign_attachments(messages: &mut [Message], attachments: Vec<Attachment>) {
+    for attachment in attachments {
+        if let Some(message) = messages.iter_mut().find(|m| m.id == attachment.message_id) {
+            message.attachments.push(attachment);
+        }
+    }
+}
+
+/// Loads and sets the attachments of messages of a workflow.
+///
+/// Failures are logged: messages are still usable without their attachments.
+pub(crate) async fn load_message_attachments(
+    db: &DBClient,
+    workflow_id: &str,
+    messages: &mut [Message],
+) {
+    match load_workflow_attachments(db, workflow_id).await {
+        Ok(attachments) => assign_attachments(messages, attachments),
+        Err(e) => warn!(error = %e, "Failed to load message attachments"),
+    }
+}
+
+/// Loads the attachments of a message.
+pub(crate) async fn load_attachments_of_message(
+    db: &DBClient,
+    message_id: &str,
+) -> Result<Vec<Attachment>, String> {
+    let query = format!("{} WHERE message_id = $message_id", ATTACHMENT_SELECT);
+    let rows = db
+        .query_json_with_params(
+            &query,
+            vec![("message_id".to_string(), serde_json::json!(message_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load attachments: {}", e))?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect())
+}
+
+/// Reads attachments as data URLs for vision models, skipping (and logging)
+/// unreadable blobs. Formats models cannot read (SVG) are left out.
+pub(crate) async fn read_image_urls(state: &AppState, attachments: &[Attachment]) -> Vec<String> {
+    let mut urls = Vec::with_capacity(attachments.len());
+    for attachment in attachments {
+        if !VISION_IMAGE_TYPES.contains(&attachment.mime_type.as_str()) {
+            continue;
+        }
+        match state.attachments.read_data_url(attachment).await {
+            Ok(url) => urls.push(url),
+            Err(e) => warn!(attachment_id = %attachment.id, error = %e, "Skipping attachment"),
+        }
+    }
+    urls
+}
+
+/// Gets the content of an attachment as a data URL (`data:<mime>;base64,...`).
+///
+/// # Arguments
+/// * `attachment_id` - The attachment ID
+///
+/// # Returns
+/// The data URL, for display
+#[tauri::command]
+#[instrument(name = "get_attachment_data", skip(state), fields(attachment_id = %attachment_id))]
+pub async fn get_attachment_data(
+    attachment_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let validated_id = Validator::validate_uuid(&attachment_id).map_err(|e| {
+        warn!(error = %e, "Invalid attachment_id");
+        format!("Invalid attachment_id: {}", e)
+    })?;
+
+    let query = format!("{} WHERE meta::id(id) = $id", ATTACHMENT_SELECT);
+    let attachment: Attachment = state
+        .db
+        .query_json_with_params(
+            &query,
+            vec![("id".to_string(), serde_json::json!(validated_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load attachment");
+            format!("Failed to load attachment: {}", e)
+        })?
+        .into_iter()
+        .find_map(|row| serde_json::from_value(row).ok())
+        .ok_or_else(|| format!("Attachment not found: {}", validated_id))?;
+
+    let data_url = state.attachments.read_data_url(&attachment).await?;
+    info!(size_bytes = attachment.size_bytes, "Attachment data loaded");
+    Ok(data_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    fn input(data: Option<String>, path: Option<String>) -> AttachmentInput {
+        AttachmentInput {
+            data,
+            path,
+            file_name: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_attachment() {
+        let encoded = STANDARD.encode(PNG);
+
+        let decoded = decode_attachment(&input(Some(encoded.clone()), None), false)
+            .await
+            .unwrap();
+        assert_eq!(decoded.mime_type, "image/png");
+        assert_eq!(decoded.bytes, PNG);
+
+        // Data URL prefix is accepted
+        let url = format!("data:image/png;base64,{}", encoded);
+        assert!(decode_attachment(&input(Some(url), None), false)
+            .await
+            .is_ok());
+
+        // Not an image
+        let pdf = STANDARD.encode(b"%PDF-1.7");
+        assert!(decode_attachment(&input(Some(pdf), None), false)
+            .await
+            .is_err());
+
+        // Exactly one of data and path
+        assert!(decode_attachment(&input(None, None), false).await.is_err());
+        assert!(
+            decode_attachment(&input(Some(encoded), Some("/tmp/a.png".to_string())), false)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_attachment_from_path() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("screenshot.png");
+        std::fs::write(&path, PNG).unwrap();
+
+        let decoded = decode_attachment(&input(None, Some(path.display().to_string())), false)
+            .await
+            .unwrap();
+        assert_eq!(decoded.file_name.as_deref(), Some("screenshot.png"));
+
+        let missing = temp_dir.path().join("missing.png");
+        assert!(
+            decode_attachment(&input(None, Some(missing.display().to_string())), false)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_svg_attachment() {
+        const SVG: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>";
+
+        // Users cannot upload SVG images, even with an assistant message
+        let encoded = STANDARD.encode(SVG);
+        assert!(
+            decode_attachment(&input(Some(encoded.clone()), None), false)
+                .await
+                .is_err()
+        );
+        assert!(decode_attachment(&input(Some(encoded), None), true)
+            .await
+            .is_err());
+        let temp_dir = tempfile::tempdir().unwrap();
+        let uploaded = temp_dir.path().join("drawing.svg");
+        std::fs::write(&uploaded, SVG).unwrap();
+        assert!(
+            decode_attachment(&input(None, Some(uploaded.display().to_string())), true)
+                .await
+                .is_err()
+        );
+
+        // Charts rendered by the ChartTool are attached to responses
+        let chart_dir = chart::output_dir().join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&chart_dir).unwrap();
+        let chart = chart_dir.join("chart.svg").display().to_string();
+        std::fs::write(&chart, SVG).unwrap();
+        let decoded = decode_attachment(&input(None, Some(chart.clone())), true)
+            .await
+            .unwrap();
+        assert_eq!(decoded.mime_type, "image/svg+xml");
+        assert!(decode_attachment(&input(None, Some(chart)), false)
+            .await
+            .is_err());
+        std::fs::remove_dir_all(&chart_dir).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit log commands.
+//!
+//! Sensitive actions are recorded in the `audit_log` table (see
+//! [`crate::security::audit`]); these commands query and export it.
+
+use crate::{
+    models::audit::{AuditExportFormat, AuditLogEntry, AuditLogFilter},
+    security::{audit, Validator},
+    tools::constants::audit::MAX_QUERY_LIMIT,
+    AppState,
+};
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Validates the workflow ID of a filter.
+fn validate_filter(mut filter: AuditLogFilter) -> Result<AuditLogFilter, String> {
+    if let Some(ref workflow_id) = filter.workflow_id {
+        filter.workflow_id = Some(Validator::validate_uuid(workflow_id).map_err(|e| {
+            warn!(error = %e, "Invalid workflow_id");
+            format!("Invalid workflow_id: {}", e)
+        })?);
+    }
+    Ok(filter)
+}
+
+/// Loads audit log entries, newest first.
+///
+/// # Arguments
+/// * `filter` - Action, actor, workflow and date range criteria, and the
+///   maximum number of entries (500 if omitted)
+#[tauri::command]
+#[instrument(name = "query_audit_log", skip(state, filter))]
+pub async fn query_audit_log(
+    filter: Option<AuditLogFilter>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AuditLogEntry>, String> {
+    let filter = validate_filter(filter.unwrap_or_default())?;
+
+    let entries = audit::query(&state.db, &filter).await.map_err(|e| {
+        error!(error = %e, "Failed to load audit log");
+        format!("Failed to load audit log: {}", e)
+    })?;
+
+    info!(count = entries.len(), "Audit log loaded");
+    Ok(entries)
+}
+
+/// Exports audit log entries to a file.
+///
+/// # Arguments
+/// * `path` - Full path of the output file
+/// * `filter` - Entries to export (all entries if omitted)
+/// * `format` - JSON Lines (default) or CSV
+///
+/// # Returns
+/// Number of exported entries
+#[tauri::command]
+#[instrument(name = "export_audit_log", skip(state, filter), fields(format = ?format))]
+pub async fn export_audit_log(
+    path: String,
+    filter: Option<AuditLogFilter>,
+    format: Option<AuditExportFormat>,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let mut filter = validate_filter(filter.unwrap_or_default())?;
+    filter.limit = Some(filter.limit.unwrap_or(MAX_QUERY_LIMIT));
+
+    let entries = audit::query(&state.db, &filter).await.map_err(|e| {
+        error!(error = %e, "Failed to load audit log");
+        format!("Failed to load audit log: {}", e)
+    })?;
+
+    std::fs::write(
+        &path,
+        audit::render_export(&entries, format.unwrap_or_default()),
+    )
+    .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    info!(count = entries.len(), path = %path, "Audit log exported");
+    Ok(entries.len())
+}
@@ -0,0 +1,79 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commands reading the behavior changelog.
+//!
+//! Changes are recorded by the agent and MCP server commands (see
+//! [`crate::models::behavior_changelog`]). Assistant messages carry the
+//! `behavior_position` of their run: the changes between the positions of two
+//! runs are the ones that may explain a difference in their responses.
+
+use crate::{
+    agents::behavior_changelog,
+    models::behavior_changelog::BehaviorChange,
+    security::Validator,
+    tools::constants::behavior_changelog::{DEFAULT_LIST_LIMIT, MAX_LIST_LIMIT},
+    AppState,
+};
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Lists behavior changes, newest first.
+///
+/// # Arguments
+/// * `agent_id` - Only changes of this agent and MCP server changes (optional)
+/// * `after_position` - Only changes after this position, exclusive (optional)
+/// * `until_position` - Only changes up to this position, inclusive (optional)
+/// * `limit` - Maximum number of changes (default: 100, max: 1000)
+///
+/// # Returns
+/// The matching changes, highest position first
+#[tauri::command]
+#[instrument(name = "list_behavior_changes", skip(state))]
+pub async fn list_behavior_changes(
+    agent_id: Option<String>,
+    after_position: Option<u64>,
+    until_position: Option<u64>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<BehaviorChange>, String> {
+    let agent_id = agent_id
+        .map(|id| {
+            Validator::validate_agent_id(&id).map_err(|e| {
+                warn!(error = %e, "Invalid agent_id");
+                format!("Invalid agent_id: {}", e)
+            })
+        })
+        .transpose()?;
+    let limit = limit
+        .map(|l| l as usize)
+        .unwrap_or(DEFAULT_LIST_LIMIT)
+        .clamp(1, MAX_LIST_LIMIT);
+
+    let changes = behavior_changelog::list_behavior_changes(
+        &state.db,
+        agent_id.as_deref(),
+        after_position,
+        until_position,
+        limit,
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to list behavior changes");
+        format!("Failed to list behavior changes: {}", e)
+    })?;
+
+    info!(count = changes.len(), "Behavior changes listed");
+    Ok(changes)
+}
@@ -0,0 +1,199 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commands resuming workflow executions interrupted by a crash.
+//!
+//! The primary agent of a streaming execution checkpoints its tool loop after
+//! each step (see [`crate::agents::checkpoint`]). The checkpoint is deleted when
+//! the execution ends; one left behind means the app stopped mid-execution,
+//! and `resume_workflow_execution` continues from it instead of starting over.
+
+use crate::{
+    agents::{
+        checkpoint::{clear_checkpoint, list_checkpoints, load_checkpoint},
+        core::agent::Task,
+    },
+    commands::streaming::wind_down,
+    db::DBClient,
+    inbox,
+    models::{
+        checkpoint::ResumableExecution,
+        pending_item::PendingItemKind,
+        streaming::{events, WorkflowComplete},
+        MessageCreate,
+    },
+    scheduler::{execute_task, save_message},
+    security::{Validator, WORKFLOW_VAULT},
+    AppState,
+};
+use tauri::{Emitter, State};
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Deletes the checkpoint of an ended execution (failures are only logged).
+pub(crate) async fn discard_checkpoint(db: &DBClient, workflow_id: &str) {
+    if let Err(e) = clear_checkpoint(db, workflow_id).await {
+        warn!(error = %e, workflow_id = %workflow_id, "Failed to delete execution checkpoint");
+    }
+    inbox::resolve_pending_item(db, PendingItemKind::InterruptedExecution, workflow_id).await;
+}
+
+/// Lists the workflow executions interrupted by a crash, most recent first.
+#[tauri::command]
+#[instrument(name = "list_resumable_executions", skip(state))]
+pub async fn list_resumable_executions(
+    state: State<'_, AppState>,
+) -> Result<Vec<ResumableExecution>, String> {
+    let running = state.streaming_cancellations.lock().await;
+    Ok(list_checkpoints(&state.db)
+        .await?
+        .into_iter()
+        .filter(|execution| !running.contains_key(&execution.workflow_id))
+        .collect())
+}
+
+/// Resumes an interrupted workflow execution from its last checkpoint.
+///
+/// Tool calls requested before the interruption are executed first, then the
+/// agent loop goes on. The response is moderated and stored as an assistant
+/// message; agent events are emitted on `workflow_stream` and the end of the
+/// execution on `workflow_complete`. The execution can be cancelled with
+/// `cancel_workflow_streaming`.
+///
+/// # Arguments
+/// * `workflow_id` - Workflow of the interrupted execution
+///
+/// # Returns
+/// The ID of the assistant message
+#[tauri::command]
+#[instrument(name = "resume_workflow_execution", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn resume_workflow_execution(
+    workflow_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    info!("Resuming interrupted workflow execution");
+
+    let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+    if WORKFLOW_VAULT.is_sensitive(&state.db, &workflow_id).await?
+        && !WORKFLOW_VAULT.is_unlocked(&workflow_id)
+    {
+        return Err("This workflow is sensitive and locked, open it first".to_string());
+    }
+    if state
+        .streaming_cancellations
+        .lock()
+        .await
+        .contains_key(&workflow_id)
+    {
+        return Err("This workflow is already running".to_string());
+    }
+
+    let mut checkpoint = load_checkpoint(&state.db, &workflow_id)
+        .await?
+        .ok_or_else(|| "No interrupted execution to resume for this workflow".to_string())?;
+
+    // The system prompt of an interrupted first message was never stored
+    if checkpoint.state.first_message {
+        let system_prompt = checkpoint
+            .state
+            .messages
+            .iter()
+            .find(|m| m.get("role").and_then(|r| r.as_str()) == Some("system"))
+            .and_then(|m| m.get("content").and_then(|c| c.as_str()));
+        if let Some(prompt) = system_prompt {
+            save_message(
+                &state.db,
+                MessageCreate::system(workflow_id.clone(), prompt.to_string()),
+            )
+            .await?;
+        }
+        checkpoint.state.first_message = false;
+    }
+
+    let agent_id = checkpoint.agent_id.clone();
+    let mut context = checkpoint.state.context.clone();
+    if !context.is_object() {
+        context = serde_json::json!({});
+    }
+    context["workflow_id"] = serde_json::json!(workflow_id);
+    context["is_primary_agent"] = serde_json::json!(true);
+    context["checkpoint"] = serde_json::to_value(&checkpoint)
+        .map_err(|e| format!("Failed to serialize checkpoint: {}", e))?;
+    let task = Task {
+        id: Uuid::new_v4().to_string(),
+        description: checkpoint.state.task.clone(),
+        context,
+    };
+
+    let cancellation_token = state.create_cancellation_token(&workflow_id).await;
+    let run_context = state.run_context();
+    let execution = execute_task(&run_context, &workflow_id, &agent_id, task);
+    tokio::pin!(execution);
+    let result = tokio::select! {
+        result = &mut execution => result,
+        _ = cancellation_token.cancelled() => {
+            wind_down(&mut execution).await;
+            Err("Workflow cancelled by user".to_string())
+        }
+    };
+    state.clear_cancellation(&workflow_id).await;
+    discard_checkpoint(&state.db, &workflow_id).await;
+
+    let complete = match &result {
+        Ok(_) => WorkflowComplete::success(workflow_id.clone()),
+        Err(e) if cancellation_token.is_cancelled() => {
+            warn!(error = %e, "Resumed execution cancelled");
+            WorkflowComplete::cancelled(workflow_id.clone())
+        }
+        Err(e) => {
+            error!(error = %e, "Resumed execution failed");
+            WorkflowComplete::failed(workflow_id.clone(), e.clone())
+        }
+    };
+    crate::metrics::record_workflow_execution("resume", &complete.status);
+    if let Some(handle) = state.get_app_handle() {
+        if let Err(e) = handle.emit(events::WORKFLOW_COMPLETE, &complete) {
+            warn!(error = %e, "Failed to emit workflow_complete event");
+        }
+    }
+    result
+}
+
+/// Discards the checkpoint of an interrupted execution (it will not be resumed).
+///
+/// # Arguments
+/// * `workflow_id` - Workflow of the interrupted execution
+#[tauri::command]
+#[instrument(name = "discard_workflow_checkpoint", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn discard_workflow_checkpoint(
+    workflow_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+    clear_checkpoint(&state.db, &workflow_id).await?;
+    inbox::resolve_pending_item(
+        &state.db,
+        PendingItemKind::InterruptedExecution,
+        &workflow_id,
+    )
+    .await;
+    info!("Execution checkpoint discarded");
+    Ok(())
+}
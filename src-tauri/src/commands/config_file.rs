@@ -0,0 +1,33 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config file commands.
+//!
+//! `~/.zileo/config.toml` is read once at startup (see [`crate::config_file`]);
+//! [`get_effective_config`] lets the settings page show which settings it
+//! overrides.
+
+use crate::models::config_file::EffectiveConfig;
+use crate::tools::constants::profiles::DEFAULT_PROFILE_ID;
+use crate::AppState;
+use tauri::State;
+use tracing::instrument;
+
+/// Gets the settings in effect and whether they come from the config file.
+#[tauri::command]
+#[instrument(name = "get_effective_config", skip(state))]
+pub async fn get_effective_config(state: State<'_, AppState>) -> Result<EffectiveConfig, String> {
+    let db_path = state.profiles.db_path(DEFAULT_PROFILE_ID);
+    Ok(state.config.effective(&db_path))
+}
@@ -14,10 +14,13 @@
 
 //! Custom provider CRUD commands.
 //!
-//! Manages user-created OpenAI-compatible providers (RouterLab, OpenRouter, etc.).
+//! Manages user-created OpenAI-compatible providers (RouterLab, OpenRouter, Groq,
+//! LM Studio, vLLM, llama.cpp server, etc.).
 
 use crate::commands::SecureKeyStore;
+use crate::llm::fake::FAKE_PROVIDER_NAME;
 use crate::llm::openai_compatible::OpenAiCompatibleProvider;
+use crate::llm::ProviderManager;
 use crate::models::custom_provider::{CustomProvider, ProviderInfo};
 use crate::state::AppState;
 use std::sync::Arc;
@@ -37,7 +40,7 @@ fn validate_provider_name(name: &str) -> Result<(), String> {
             "Provider name must contain only lowercase letters, numbers, and hyphens".into(),
         );
     }
-    if name == "mistral" || name == "ollama" {
+    if name == "mistral" || name == "ollama" || name == "gemini" || name == FAKE_PROVIDER_NAME {
         return Err(format!("'{}' is a builtin provider name", name));
     }
     Ok(())
@@ -73,8 +76,32 @@ pub async fn list_providers(state: State<'_, AppState>) -> Result<Vec<ProviderIn
             base_url: Some("http://localhost:11434".to_string()),
             enabled: true,
         },
+        ProviderInfo {
+            id: "gemini".to_string(),
+            display_name: "Gemini".to_string(),
+            is_builtin: true,
+            is_cloud: true,
+            requires_api_key: true,
+            has_base_url: false,
+            base_url: None,
+            enabled: true,
+        },
     ];
 
+    // Fake provider, only listed when enabled with ZILEO_FAKE_LLM
+    if state.llm_manager.is_fake_provider_enabled() {
+        providers.push(ProviderInfo {
+            id: FAKE_PROVIDER_NAME.to_string(),
+            display_name: "Fake (scripted)".to_string(),
+            is_builtin: true,
+            is_cloud: false,
+            requires_api_key: false,
+            has_base_url: false,
+            base_url: None,
+            enabled: true,
+        });
+    }
+
     // Custom providers from DB
     let query = "SELECT name, display_name, base_url, enabled, \
                  time::format(created_at, '%Y-%m-%dT%H:%M:%SZ') AS created_at \
@@ -149,26 +176,31 @@ pub async fn create_custom_provider(
     let normalized_url = base_url.trim_end_matches('/').to_string();
 
     // Insert into DB
-    let insert_query = format!("CREATE custom_provider:`{}` CONTENT $data", name);
     let data = serde_json::json!({
         "name": name,
         "display_name": display_name,
         "base_url": normalized_url,
         "enabled": true
     });
-    db.execute_with_params(&insert_query, vec![("data".to_string(), data)])
-        .await
-        .map_err(|e| format!("Failed to create custom provider: {}", e))?;
+    db.execute_with_params(
+        "CREATE type::thing('custom_provider', $name) CONTENT $data",
+        vec![
+            ("name".to_string(), serde_json::json!(name)),
+            ("data".to_string(), data),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create custom provider: {}", e))?;
 
     // Store API key in SecureKeyStore
     keystore
-        .set_key(&name, &api_key)
+        .save_api_key(&name, &api_key, None)
         .map_err(|e| format!("Failed to store API key: {}", e))?;
 
     // Create and register provider in manager
     let provider = Arc::new(OpenAiCompatibleProvider::new(
         &name,
-        state.llm_manager.http_client().clone(),
+        ProviderManager::provider_http_client(&name),
     ));
     if let Err(e) = provider.configure(&api_key, &normalized_url).await {
         warn!(name = %name, error = %e, "Failed to configure new custom provider");
@@ -209,39 +241,36 @@ pub async fn update_custom_provider(
     let db = &state.db;
 
     // Build SET clauses dynamically
-    let mut set_parts: Vec<String> = Vec::new();
+    let mut set_parts: Vec<&str> = Vec::new();
+    let mut params = vec![("name".to_string(), serde_json::json!(name))];
 
     if let Some(ref dn) = display_name {
         if dn.trim().is_empty() || dn.len() > 128 {
             return Err("Display name must be 1-128 characters".into());
         }
-        set_parts.push(format!(
-            "display_name = {}",
-            serde_json::to_string(dn).map_err(|e| e.to_string())?
-        ));
+        set_parts.push("display_name = $display_name");
+        params.push(("display_name".to_string(), serde_json::json!(dn)));
     }
     if let Some(ref url) = base_url {
         if url.trim().is_empty() || url.len() > 512 {
             return Err("Base URL must be 1-512 characters".into());
         }
         let normalized = url.trim_end_matches('/');
-        set_parts.push(format!(
-            "base_url = {}",
-            serde_json::to_string(normalized).map_err(|e| e.to_string())?
-        ));
+        set_parts.push("base_url = $base_url");
+        params.push(("base_url".to_string(), serde_json::json!(normalized)));
     }
     if let Some(en) = enabled {
-        set_parts.push(format!("enabled = {}", en));
+        set_parts.push("enabled = $enabled");
+        params.push(("enabled".to_string(), serde_json::json!(en)));
     }
 
     if !set_parts.is_empty() {
-        set_parts.push("updated_at = time::now()".to_string());
+        set_parts.push("updated_at = time::now()");
         let update_query = format!(
-            "UPDATE custom_provider:`{}` SET {}",
-            name,
+            "UPDATE type::thing('custom_provider', $name) SET {}",
             set_parts.join(", ")
         );
-        db.execute(&update_query)
+        db.execute_with_params(&update_query, params)
             .await
             .map_err(|e| format!("Failed to update custom provider: {}", e))?;
     }
@@ -252,7 +281,7 @@ pub async fn update_custom_provider(
             return Err("API key cannot be empty".into());
         }
         keystore
-            .set_key(&name, key)
+            .save_api_key(&name, key, None)
             .map_err(|e| format!("Failed to update API key: {}", e))?;
     }
 
@@ -269,19 +298,21 @@ pub async fn update_custom_provider(
             } else {
                 provider.get_base_url().await.unwrap_or_default()
             };
-            if let Err(e) = provider.configure(&current_key, &current_url).await {
+            if current_key.is_empty() {
+                // Without API key, models can still be listed
+                provider.set_base_url(&current_url).await;
+            } else if let Err(e) = provider.configure(&current_key, &current_url).await {
                 warn!(name = %name, error = %e, "Failed to reconfigure custom provider");
             }
         }
     }
 
     // Read back updated provider
-    let read_query = format!(
-        "SELECT name, display_name, base_url, enabled FROM custom_provider:`{}`",
-        name
-    );
     let results: Vec<serde_json::Value> = db
-        .query_json(&read_query)
+        .query_json_with_params(
+            "SELECT name, display_name, base_url, enabled FROM type::thing('custom_provider', $name)",
+            vec![("name".to_string(), serde_json::json!(name))],
+        )
         .await
         .map_err(|e| format!("Failed to read updated provider: {}", e))?;
     let cp: CustomProvider = results
@@ -317,13 +348,12 @@ pub async fn delete_custom_provider(
     let db = &state.db;
 
     // Delete from DB
-    let delete_query = format!("DELETE custom_provider:`{}`", name);
-    db.execute(&delete_query)
+    db.delete(&format!("custom_provider:{}", name))
         .await
         .map_err(|e| format!("Failed to delete custom provider: {}", e))?;
 
     // Remove API key from SecureKeyStore
-    if let Err(e) = keystore.delete_key(&name) {
+    if let Err(e) = keystore.delete_api_key(&name) {
         warn!(name = %name, error = %e, "Failed to delete API key (may not exist)");
     }
 
@@ -333,3 +363,27 @@ pub async fn delete_custom_provider(
     info!(name = %name, "Custom provider deleted");
     Ok(())
 }
+
+/// Lists the models a custom provider serves at `{base_url}/models`.
+///
+/// Lets the frontend offer discovered model IDs instead of requiring users to
+/// type them by hand. Works without stored API key (local servers).
+#[tauri::command]
+#[instrument(name = "list_custom_provider_models", skip(state))]
+pub async fn list_custom_provider_models(
+    name: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    validate_provider_name(&name)?;
+
+    let provider = state
+        .llm_manager
+        .get_custom_provider(&name)
+        .await
+        .ok_or_else(|| format!("Provider '{}' not found", name))?;
+
+    provider
+        .list_models()
+        .await
+        .map_err(|e| format!("Failed to list models for '{}': {}", name, e))
+}
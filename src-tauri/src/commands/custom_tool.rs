@@ -0,0 +1,272 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Custom tool commands.
+//!
+//! Manages the tools defined by the user as Rhai scripts (see
+//! [`crate::tools::script`]) and runs a script on a sample input while it is
+//! written.
+
+use crate::models::custom_tool::{CustomTool, CustomToolCreate, CustomToolUpdate};
+use crate::security::Validator;
+use crate::state::AppState;
+use crate::tools::constants::script as script_const;
+use crate::tools::script::{self, store, ScriptToolRunner};
+use serde_json::Value;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Validates the description of a custom tool, which the LLM reads to pick
+/// the tool.
+fn validate_description(description: &str) -> Result<String, String> {
+    let trimmed = description.trim();
+    if trimmed.is_empty() {
+        return Err(
+            "The description cannot be empty: the LLM reads it to use the tool".to_string(),
+        );
+    }
+    if trimmed.chars().count() > script_const::MAX_DESCRIPTION_LEN {
+        return Err(format!(
+            "The description exceeds {} characters",
+            script_const::MAX_DESCRIPTION_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a custom tool ID and checks that the tool exists.
+async fn existing_tool(state: &AppState, tool_id: &str) -> Result<CustomTool, String> {
+    let tool_id = Validator::validate_uuid(tool_id).map_err(|e| {
+        warn!(error = %e, "Invalid tool_id");
+        format!("Invalid tool_id: {}", e)
+    })?;
+    store::load_custom_tool(&state.db, &tool_id)
+        .await?
+        .ok_or_else(|| "Custom tool not found".to_string())
+}
+
+/// Lists the custom tools.
+///
+/// # Returns
+/// Custom tools sorted by name
+#[tauri::command]
+#[instrument(name = "list_custom_tools", skip(state))]
+pub async fn list_custom_tools(state: State<'_, AppState>) -> Result<Vec<CustomTool>, String> {
+    info!("Listing custom tools");
+
+    let tools = store::list_custom_tools(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to list custom tools");
+        e
+    })?;
+
+    info!(count = tools.len(), "Custom tools loaded");
+    Ok(tools)
+}
+
+/// Creates a custom tool, enabled.
+///
+/// # Arguments
+/// * `config` - Name (lowercase letters, digits and `_`), description, input
+///   schema and Rhai script; the script must compile
+///
+/// # Returns
+/// The created custom tool
+#[tauri::command]
+#[instrument(name = "create_custom_tool", skip(state, config), fields(name = %config.name))]
+pub async fn create_custom_tool(
+    config: CustomToolCreate,
+    state: State<'_, AppState>,
+) -> Result<CustomTool, String> {
+    info!("Creating custom tool");
+
+    let name = config.name.trim().to_string();
+    if !script::is_valid_name(&name) {
+        return Err(format!(
+            "Invalid name '{}': use lowercase letters, digits and '_', starting with a letter (at most {} characters)",
+            name,
+            script_const::MAX_NAME_LEN
+        ));
+    }
+    let tool = CustomToolCreate {
+        description: validate_description(&config.description)?,
+        input_schema: script::validate_schema(&config.input_schema)?,
+        script: config.script,
+        name,
+    };
+    ScriptToolRunner::compile(&tool.name, &tool.script)?;
+
+    let tools = store::list_custom_tools(&state.db).await?;
+    if tools.len() >= script_const::MAX_CUSTOM_TOOLS {
+        return Err(format!(
+            "Maximum number of custom tools reached ({})",
+            script_const::MAX_CUSTOM_TOOLS
+        ));
+    }
+    if tools.iter().any(|existing| existing.name == tool.name) {
+        return Err(format!("A custom tool is already named '{}'", tool.name));
+    }
+
+    let tool_id = Uuid::new_v4().to_string();
+    store::create_custom_tool(&state.db, &tool_id, &tool)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create custom tool");
+            e
+        })?;
+
+    info!(tool_id = %tool_id, "Custom tool created");
+    store::load_custom_tool(&state.db, &tool_id)
+        .await?
+        .ok_or_else(|| "Custom tool not found".to_string())
+}
+
+/// Updates a custom tool. Its name cannot change: agents refer to it as
+/// `custom__<name>`.
+///
+/// # Arguments
+/// * `tool_id` - Custom tool ID
+/// * `config` - Fields to update
+///
+/// # Returns
+/// The updated custom tool
+#[tauri::command]
+#[instrument(name = "update_custom_tool", skip(state, config))]
+pub async fn update_custom_tool(
+    tool_id: String,
+    config: CustomToolUpdate,
+    state: State<'_, AppState>,
+) -> Result<CustomTool, String> {
+    info!("Updating custom tool");
+
+    let existing = existing_tool(&state, &tool_id).await?;
+    let update = CustomToolUpdate {
+        description: config
+            .description
+            .as_deref()
+            .map(validate_description)
+            .transpose()?,
+        input_schema: config
+            .input_schema
+            .as_ref()
+            .map(script::validate_schema)
+            .transpose()?,
+        script: config.script,
+        enabled: config.enabled,
+    };
+    if let Some(script) = &update.script {
+        ScriptToolRunner::compile(&existing.name, script)?;
+    }
+
+    store::update_custom_tool(&state.db, &existing.id, &update)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update custom tool");
+            e
+        })?;
+
+    info!(tool_id = %existing.id, "Custom tool updated");
+    store::load_custom_tool(&state.db, &existing.id)
+        .await?
+        .ok_or_else(|| "Custom tool not found".to_string())
+}
+
+/// Deletes a custom tool. Agents still listing it report it as missing in
+/// their health check.
+///
+/// # Arguments
+/// * `tool_id` - Custom tool ID
+#[tauri::command]
+#[instrument(name = "delete_custom_tool", skip(state))]
+pub async fn delete_custom_tool(tool_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    info!("Deleting custom tool");
+
+    let existing = existing_tool(&state, &tool_id).await?;
+    store::delete_custom_tool(&state.db, &existing.id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete custom tool");
+            e
+        })?;
+
+    info!(tool_id = %existing.id, "Custom tool deleted");
+    Ok(())
+}
+
+/// Runs a script on a sample input, as an agent call would.
+///
+/// # Arguments
+/// * `script` - Rhai script
+/// * `input_schema` - JSON Schema the input is checked against
+/// * `input` - Sample input
+///
+/// # Returns
+/// The value returned by the script
+#[tauri::command]
+#[instrument(name = "test_custom_tool", skip_all)]
+pub async fn test_custom_tool(
+    script: String,
+    input_schema: Value,
+    input: Value,
+) -> Result<Value, String> {
+    let schema = script::validate_schema(&input_schema)?;
+    script::check_input(&schema, &input)?;
+    let runner = ScriptToolRunner::compile("test", &script)?;
+
+    tokio::task::spawn_blocking(move || runner.run(&input))
+        .await
+        .map_err(|e| format!("Script task failed: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_description() {
+        assert_eq!(
+            validate_description("  Counts words ").unwrap(),
+            "Counts words"
+        );
+        assert!(validate_description(" ").is_err());
+        assert!(validate_description(&"a".repeat(script_const::MAX_DESCRIPTION_LEN + 1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_test_custom_tool() {
+        let schema = serde_json::json!({
+            "properties": { "a": { "type": "integer" }, "b": { "type": "integer" } },
+            "required": ["a", "b"]
+        });
+        assert_eq!(
+            test_custom_tool(
+                "input.a + input.b".to_string(),
+                schema.clone(),
+                serde_json::json!({ "a": 2, "b": 3 })
+            )
+            .await
+            .unwrap(),
+            serde_json::json!(5)
+        );
+        assert!(test_custom_tool(
+            "input.a + input.b".to_string(),
+            schema,
+            serde_json::json!({ "a": 2 })
+        )
+        .await
+        .unwrap_err()
+        .contains("'b'"));
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Desktop access permission commands.
+//!
+//! Permissions are stored in the `settings:desktop_access` record and read by
+//! the ClipboardTool, ScreenshotTool, PdfTool and OcrTool on each call, so a
+//! change applies to running workflows immediately.
+
+use crate::db::{queries, DBClient};
+use crate::models::desktop_access::DesktopAccessSettings;
+use crate::tools::constants::desktop::SETTINGS_KEY;
+use crate::AppState;
+use tauri::State;
+use tracing::{debug, error, info, instrument};
+
+/// Loads the desktop access permissions, all denied when unset.
+pub async fn load_desktop_access_settings(db: &DBClient) -> DesktopAccessSettings {
+    match queries::settings::load_config(db, SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No desktop access settings found, using defaults");
+            DesktopAccessSettings::default()
+        }
+    }
+}
+
+/// Gets the desktop access permissions of the agents.
+#[tauri::command]
+#[instrument(name = "get_desktop_access_settings", skip(state))]
+pub async fn get_desktop_access_settings(
+    state: State<'_, AppState>,
+) -> Result<DesktopAccessSettings, String> {
+    info!("Loading desktop access settings");
+    Ok(load_desktop_access_settings(&state.db).await)
+}
+
+/// Updates the desktop access permissions of the agents.
+///
+/// # Arguments
+/// * `settings` - The complete permissions to store
+///
+/// # Returns
+/// The stored permissions
+#[tauri::command]
+#[instrument(name = "update_desktop_access_settings", skip(state))]
+pub async fn update_desktop_access_settings(
+    settings: DesktopAccessSettings,
+    state: State<'_, AppState>,
+) -> Result<DesktopAccessSettings, String> {
+    let json_config = serde_json::to_value(settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize desktop access settings");
+        format!("Failed to serialize desktop access settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save desktop access settings");
+            format!("Failed to save desktop access settings: {}", e)
+        })?;
+
+    info!(
+        clipboard = settings.clipboard,
+        screenshot = settings.screenshot,
+        documents = settings.documents,
+        "Desktop access settings updated"
+    );
+    Ok(settings)
+}
@@ -0,0 +1,68 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diagnostics bundle commands.
+//!
+//! [`generate_diagnostics`] writes the zip archive attached to bug reports
+//! (see [`crate::diagnostics`] for its content and redaction).
+
+use crate::diagnostics;
+use crate::models::diagnostics::DiagnosticsBundle;
+use crate::AppState;
+use std::path::PathBuf;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Generates the diagnostics bundle (zip archive) at `path`.
+///
+/// # Arguments
+/// * `path` - Destination file chosen by the user
+///
+/// # Returns
+/// The written bundle (path, size and files)
+#[tauri::command]
+#[instrument(name = "generate_diagnostics", skip(state))]
+pub async fn generate_diagnostics(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<DiagnosticsBundle, String> {
+    if path.trim().is_empty() {
+        warn!("Empty diagnostics path");
+        return Err("Path cannot be empty".to_string());
+    }
+
+    let files = diagnostics::collect(&state).await;
+    let archive = diagnostics::write_bundle(&files).map_err(|e| {
+        error!(error = %e, "Failed to build diagnostics bundle");
+        format!("Failed to build bundle: {}", e)
+    })?;
+
+    let output = PathBuf::from(&path);
+    std::fs::write(&output, &archive).map_err(|e| {
+        error!(error = %e, path = %output.display(), "Failed to write diagnostics bundle");
+        format!("Failed to write file: {}", e)
+    })?;
+
+    info!(
+        path = %output.display(),
+        files = files.len(),
+        size_bytes = archive.len(),
+        "Diagnostics bundle generated"
+    );
+    Ok(DiagnosticsBundle {
+        path,
+        size_bytes: archive.len() as u64,
+        files: files.iter().map(|file| file.name.to_string()).collect(),
+    })
+}
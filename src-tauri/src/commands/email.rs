@@ -0,0 +1,194 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Email account commands.
+//!
+//! Manages the email accounts agents read and send from with the EmailTool.
+//! Metadata is stored in the database and passwords in SecureKeyStore.
+
+use crate::commands::SecureKeyStore;
+use crate::models::email::{EmailAccount, EmailAccountInput};
+use crate::state::AppState;
+use crate::tools::constants::email as email_const;
+use crate::tools::email::config::{self, password_key, validate_account_name};
+use crate::tools::email::{imap, smtp};
+use tauri::State;
+use tracing::{info, instrument, warn};
+
+/// Trims a required server or address field.
+fn required_field(value: &str, field: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("{} is required", field));
+    }
+    Ok(value.to_string())
+}
+
+/// Builds the account to save from the user input.
+fn build_account(
+    input: EmailAccountInput,
+    existing: Option<&EmailAccount>,
+) -> Result<EmailAccount, String> {
+    let name = validate_account_name(&input.name)?;
+    let address = required_field(&input.address, "Address")?;
+    if address
+        .split_once('@')
+        .is_none_or(|(local, domain)| local.is_empty() || !domain.contains('.'))
+    {
+        return Err(format!("Invalid email address '{}'", address));
+    }
+    let username = input
+        .username
+        .map(|username| username.trim().to_string())
+        .filter(|username| !username.is_empty())
+        .unwrap_or_else(|| address.clone());
+
+    Ok(EmailAccount {
+        name,
+        display_name: input
+            .display_name
+            .map(|display_name| display_name.trim().to_string())
+            .filter(|display_name| !display_name.is_empty()),
+        imap_host: required_field(&input.imap_host, "IMAP host")?,
+        imap_port: input.imap_port.unwrap_or(email_const::DEFAULT_IMAP_PORT),
+        smtp_host: required_field(&input.smtp_host, "SMTP host")?,
+        smtp_port: input.smtp_port.unwrap_or(email_const::DEFAULT_SMTP_PORT),
+        address,
+        username,
+        read_only: input.read_only,
+        created_at: existing.and_then(|account| account.created_at.clone()),
+    })
+}
+
+/// Lists the email accounts.
+#[tauri::command]
+#[instrument(name = "list_email_accounts", skip(state))]
+pub async fn list_email_accounts(state: State<'_, AppState>) -> Result<Vec<EmailAccount>, String> {
+    config::list_accounts(&state.db).await
+}
+
+/// Creates or updates an email account.
+///
+/// # Arguments
+/// * `account` - Address, servers, login and read-only flag
+/// * `password` - Password (required on creation, kept when omitted on update)
+#[tauri::command]
+#[instrument(name = "save_email_account", skip(state, keystore, password))]
+pub async fn save_email_account(
+    account: EmailAccountInput,
+    password: Option<String>,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<EmailAccount, String> {
+    let name = validate_account_name(&account.name)?;
+    let password = password.filter(|value| !value.is_empty());
+    let existing = config::load_account(&state.db, &name).await?;
+    if existing.is_none() && password.is_none() {
+        return Err("A password is required".to_string());
+    }
+    let account = build_account(account, existing.as_ref())?;
+
+    if let Some(password) = &password {
+        keystore
+            .set_key(&password_key(&name), password)
+            .map_err(|e| format!("Failed to store password: {}", e))?;
+    }
+    config::save_account(&state.db, &account).await?;
+
+    info!(name = %name, read_only = account.read_only, "Email account saved");
+    config::load_account(&state.db, &name)
+        .await?
+        .ok_or_else(|| "Email account not found after save".to_string())
+}
+
+/// Deletes an email account and its password.
+#[tauri::command]
+#[instrument(name = "delete_email_account", skip(state, keystore))]
+pub async fn delete_email_account(
+    name: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<(), String> {
+    let name = validate_account_name(&name)?;
+    config::delete_account(&state.db, &name).await?;
+
+    if let Err(e) = keystore.delete_key(&password_key(&name)) {
+        warn!(name = %name, error = %e, "Failed to delete email password (may not exist)");
+    }
+
+    info!(name = %name, "Email account deleted");
+    Ok(())
+}
+
+/// Checks that an email account can log in to its IMAP server and, unless
+/// read-only, connect to its SMTP server.
+#[tauri::command]
+#[instrument(name = "test_email_account", skip(state, keystore))]
+pub async fn test_email_account(
+    name: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<(), String> {
+    let name = validate_account_name(&name)?;
+    let account = config::load_account(&state.db, &name)
+        .await?
+        .ok_or_else(|| format!("Email account '{}' not found", name))?;
+    let password = keystore
+        .get_key(&password_key(&name))
+        .ok_or_else(|| "No password stored".to_string())?;
+
+    imap::test_login(&account, &password).await?;
+    if !account.read_only {
+        smtp::test_connection(&account, &password).await?;
+    }
+    info!(name = %name, "Email account connection succeeded");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(address: &str) -> EmailAccountInput {
+        EmailAccountInput {
+            name: "work".to_string(),
+            address: address.to_string(),
+            display_name: Some(" ".to_string()),
+            imap_host: " imap.example.com ".to_string(),
+            imap_port: None,
+            smtp_host: "smtp.example.com".to_string(),
+            smtp_port: Some(587),
+            username: None,
+            read_only: true,
+        }
+    }
+
+    #[test]
+    fn test_build_account() {
+        let account = build_account(input("me@example.com"), None).unwrap();
+        assert_eq!(account.imap_host, "imap.example.com");
+        assert_eq!(account.imap_port, email_const::DEFAULT_IMAP_PORT);
+        assert_eq!(account.smtp_port, 587);
+        assert_eq!(account.username, "me@example.com");
+        assert_eq!(account.display_name, None);
+
+        assert!(build_account(input("me"), None).is_err());
+        assert!(build_account(input("me@localhost"), None).is_err());
+        let no_host = EmailAccountInput {
+            smtp_host: " ".to_string(),
+            ..input("me@example.com")
+        };
+        assert!(build_account(no_host, None).is_err());
+    }
+}
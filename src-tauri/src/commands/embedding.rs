@@ -19,22 +19,26 @@
 
 use crate::{
     commands::SecureKeyStore,
+    db::{queries::settings, sanitize_for_surrealdb},
     llm::embedding::{EmbeddingProvider, EmbeddingService},
     models::{
-        CategoryTokenStats, EmbeddingConfigSettings, EmbeddingTestResult, ExportFormat,
-        ImportResult, Memory, MemoryStats, MemoryTokenStats, RegenerateResult,
+        streaming::events, CategoryTokenStats, EmbeddingConfigSettings, EmbeddingTestResult,
+        ExportFormat, ImportResult, Memory, MemoryStats, MemoryTokenStats, RegenerateProgress,
+        RegenerateResult,
     },
+    security::{workflow_vault::is_encrypted, WORKFLOW_VAULT},
+    tools::memory::{self, enqueue_embedding, reveal_memory},
     AppState,
 };
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use tracing::{error, info, instrument, warn};
 
 /// Storage key for embedding configuration in the database
-const EMBEDDING_CONFIG_KEY: &str = "settings:embedding_config";
+pub(crate) const EMBEDDING_CONFIG_KEY: &str = "settings:embedding_config";
 
 /// Gets the current embedding configuration.
 ///
@@ -46,26 +50,17 @@ pub async fn get_embedding_config(
 ) -> Result<EmbeddingConfigSettings, String> {
     info!("Getting embedding configuration");
 
-    // Try to load from database using direct record access
-    // Note: Using backtick-escaped ID for direct access instead of WHERE clause
-    // to ensure correct record ID matching in SurrealDB
-    // Note: Using query_json and SELECT config (not SELECT *) to avoid
-    // SurrealDB SDK 2.x serialization issues with Thing enum type in id field
-    let query = format!("SELECT config FROM settings:`{}`", EMBEDDING_CONFIG_KEY);
-
-    let results: Vec<serde_json::Value> = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to query embedding config");
-        format!("Failed to load embedding config: {}", e)
-    })?;
-
-    if let Some(row) = results.first() {
-        if let Some(config_value) = row.get("config") {
-            if let Ok(config) =
-                serde_json::from_value::<EmbeddingConfigSettings>(config_value.clone())
-            {
-                info!("Loaded embedding config from database");
-                return Ok(config);
-            }
+    let stored = settings::load_config(&state.db, EMBEDDING_CONFIG_KEY)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to query embedding config");
+            format!("Failed to load embedding config: {}", e)
+        })?;
+
+    if let Some(config_value) = stored {
+        if let Ok(config) = serde_json::from_value::<EmbeddingConfigSettings>(config_value) {
+            info!("Loaded embedding config from database");
+            return Ok(config);
         }
     }
 
@@ -106,25 +101,17 @@ pub async fn save_embedding_config(
         return Err("Chunk overlap must be less than chunk size".to_string());
     }
 
-    // Serialize config to JSON string for embedding in query
-    // Note: Using raw query instead of query_with_params due to SurrealDB SDK 2.x
-    // serialization issues with complex types (see CLAUDE.md SurrealDB patterns)
-    let config_json_str = serde_json::to_string(&config).map_err(|e| {
+    let config_value = serde_json::to_value(&config).map_err(|e| {
         error!(error = %e, "Failed to serialize embedding config");
         format!("Failed to serialize config: {}", e)
     })?;
 
-    // Upsert configuration using raw query with embedded JSON
-    // Use execute() to avoid SurrealDB SDK serialization issues with UPSERT return type
-    let upsert_query = format!(
-        "UPSERT settings:`{}` CONTENT {{ id: '{}', config: {} }}",
-        EMBEDDING_CONFIG_KEY, EMBEDDING_CONFIG_KEY, config_json_str
-    );
-
-    state.db.execute(&upsert_query).await.map_err(|e| {
-        error!(error = %e, "Failed to save embedding config");
-        format!("Failed to save config: {}", e)
-    })?;
+    settings::save_config(&state.db, EMBEDDING_CONFIG_KEY, &config_value)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save embedding config");
+            format!("Failed to save config: {}", e)
+        })?;
 
     // Update the EmbeddingService in AppState
     // Note: For Mistral, the API key is retrieved from SecureKeyStore (OS keychain)
@@ -281,6 +268,10 @@ pub async fn update_memory(
 
     // Build update fields
     let mut updates = Vec::new();
+    let mut params = Vec::new();
+
+    // Memories of a sensitive workflow are stored encrypted and never embedded
+    let mut sensitive_workflow = None;
 
     if let Some(ref c) = content {
         let trimmed = c.trim();
@@ -290,45 +281,96 @@ pub async fn update_memory(
         if trimmed.len() > 50_000 {
             return Err("Content exceeds maximum length".to_string());
         }
-        // Use JSON string encoding to properly escape all special characters
-        let content_json =
-            serde_json::to_string(trimmed).map_err(|e| format!("Invalid content: {}", e))?;
-        updates.push(format!("content = {}", content_json));
+
+        let owner: Vec<serde_json::Value> = state
+            .db
+            .query_json_with_params(
+                "SELECT workflow_id FROM type::thing('memory', $id)",
+                vec![("id".to_string(), json!(memory_id))],
+            )
+            .await
+            .map_err(|e| format!("Failed to load memory: {}", e))?;
+        let workflow_id = owner
+            .first()
+            .and_then(|row| row.get("workflow_id"))
+            .and_then(|w| w.as_str());
+        let mut stored = trimmed.to_string();
+        if let Some(workflow_id) = workflow_id {
+            if WORKFLOW_VAULT.is_sensitive(&state.db, workflow_id).await? {
+                stored = WORKFLOW_VAULT.encrypt(workflow_id, trimmed)?;
+                sensitive_workflow = Some(workflow_id.to_string());
+            }
+        }
+
+        updates.push("content = $content");
+        params.push(("content".to_string(), json!(stored)));
     }
 
     if let Some(ref m) = metadata {
-        let meta_str = serde_json::to_string(m).map_err(|e| format!("Invalid metadata: {}", e))?;
-        updates.push(format!("metadata = {}", meta_str));
+        updates.push("metadata = $metadata");
+        params.push(("metadata".to_string(), m.clone()));
     }
 
     if updates.is_empty() {
         return Err("No updates provided".to_string());
     }
 
+    // New content invalidates the embedding: queue a fresh one
+    let embedding_service = state.embedding_service.read().await.clone();
+    let reembed = content
+        .as_deref()
+        .map(str::trim)
+        .filter(|_| sensitive_workflow.is_none())
+        .zip(embedding_service.as_ref());
+    if reembed.is_some() {
+        updates.push("embedding = NONE, embedding_status = 'pending'");
+    }
+
     // Use execute() for UPDATE to avoid SurrealDB SDK serialization issues
-    let update_query = format!("UPDATE memory:`{}` SET {}", memory_id, updates.join(", "));
+    let update_query = format!(
+        "UPDATE type::thing('memory', $id) SET {}",
+        updates.join(", ")
+    );
+    params.push(("id".to_string(), json!(memory_id)));
 
-    state.db.execute(&update_query).await.map_err(|e| {
-        error!(error = %e, "Failed to update memory");
-        format!("Failed to update memory: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(&update_query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update memory");
+            format!("Failed to update memory: {}", e)
+        })?;
+
+    if let Some((new_content, service)) = reembed {
+        enqueue_embedding(
+            state.db.clone(),
+            service.clone(),
+            memory_id.clone(),
+            new_content.to_string(),
+        );
+    }
 
     // Fetch the updated record with explicit field selection
-    let select_query = format!(
-        "SELECT meta::id(id) AS id, type, content, workflow_id, metadata, created_at \
-         FROM memory WHERE meta::id(id) = '{}'",
-        memory_id
-    );
+    let select_query = "SELECT meta::id(id) AS id, type, content, workflow_id, metadata, \
+                        embedding_status, created_at FROM type::thing('memory', $id)";
+    let params = vec![("id".to_string(), json!(memory_id))];
 
-    let results: Vec<Memory> = state.db.query(&select_query).await.map_err(|e| {
-        error!(error = %e, "Failed to fetch updated memory");
-        format!("Failed to fetch updated memory: {}", e)
-    })?;
+    let results: Vec<Memory> = state
+        .db
+        .query_with_params(select_query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to fetch updated memory");
+            format!("Failed to fetch updated memory: {}", e)
+        })?;
 
-    results.into_iter().next().ok_or_else(|| {
+    let mut memory = results.into_iter().next().ok_or_else(|| {
         warn!(memory_id = %memory_id, "Memory not found");
         "Memory not found".to_string()
-    })
+    })?;
+    reveal_memory(&mut memory);
+    Ok(memory)
 }
 
 /// Exports memories to JSON or CSV format.
@@ -373,6 +415,18 @@ pub async fn export_memories(
         }
     };
 
+    // Memories of sensitive workflows are only exported while the workflow is open
+    let memories: Vec<Memory> = memories
+        .into_iter()
+        .filter_map(|mut mem| {
+            if is_encrypted(&mem.content) {
+                let workflow_id = mem.workflow_id.as_deref()?;
+                mem.content = WORKFLOW_VAULT.decrypt(workflow_id, &mem.content).ok()?;
+            }
+            Some(mem)
+        })
+        .collect();
+
     let export_data = match format {
         ExportFormat::Json => serde_json::to_string_pretty(&memories).map_err(|e| {
             error!(error = %e, "Failed to serialize memories to JSON");
@@ -441,18 +495,25 @@ pub async fn import_memories(
         let metadata = mem.get("metadata").cloned().unwrap_or_else(|| json!({}));
 
         // Create memory
-        // Sanitize content: remove null chars (SurrealDB panics on \0) and escape quotes
+        // Sanitize content: remove null chars (SurrealDB panics on \0)
         let memory_id = uuid::Uuid::new_v4().to_string();
-        let sanitized_content = content.replace('\0', "").replace('\'', "''");
-        let create_query = format!(
-            "CREATE memory:`{}` CONTENT {{ type: '{}', content: '{}', metadata: {} }}",
-            memory_id,
-            memory_type,
-            sanitized_content,
-            serde_json::to_string(&metadata).unwrap_or_else(|_| "{}".to_string())
-        );
-
-        match state.db.query::<serde_json::Value>(&create_query).await {
+        let data = sanitize_for_surrealdb(json!({
+            "type": memory_type,
+            "content": content,
+            "metadata": metadata,
+        }));
+
+        match state
+            .db
+            .execute_with_params(
+                "CREATE type::thing('memory', $id) CONTENT $data",
+                vec![
+                    ("id".to_string(), json!(memory_id)),
+                    ("data".to_string(), data),
+                ],
+            )
+            .await
+        {
             Ok(_) => imported += 1,
             Err(e) => {
                 failed += 1;
@@ -472,15 +533,22 @@ pub async fn import_memories(
 
 /// Regenerates embeddings for existing memories.
 ///
+/// Memories are embedded in provider batches with bounded concurrency; the
+/// progress is emitted as `embedding_regeneration_progress` events.
+///
 /// # Arguments
 /// * `type_filter` - Optional filter to only regenerate for specific type
+/// * `resume` - Continue the interrupted run instead of starting over
+///   (its type filter is kept)
 #[tauri::command]
-#[instrument(name = "regenerate_embeddings", skip(state))]
+#[instrument(name = "regenerate_embeddings", skip(app, state))]
 pub async fn regenerate_embeddings(
     type_filter: Option<String>,
+    resume: Option<bool>,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<RegenerateResult, String> {
-    info!(type_filter = ?type_filter, "Regenerating embeddings");
+    info!(type_filter = ?type_filter, resume = ?resume, "Regenerating embeddings");
 
     // Get embedding service
     let service_guard = state.embedding_service.read().await;
@@ -495,95 +563,60 @@ pub async fn regenerate_embeddings(
     };
     drop(service_guard);
 
-    // Load memories (no ORDER BY needed for regeneration, just need id and content)
-    let query = match type_filter {
-        Some(ref mtype) => format!(
-            "SELECT meta::id(id) AS id, content FROM memory WHERE type = '{}'",
-            mtype
-        ),
-        None => "SELECT meta::id(id) AS id, content FROM memory".to_string(),
+    let checkpoint = if resume.unwrap_or(false) {
+        memory::load_regeneration_checkpoint(&state.db).await?
+    } else {
+        None
+    };
+    let progress = match checkpoint {
+        Some(checkpoint) => {
+            info!(
+                processed = checkpoint.processed,
+                total = checkpoint.total,
+                "Resuming embedding regeneration"
+            );
+            checkpoint
+        }
+        None => memory::start_regeneration(&state.db, type_filter, false).await?,
     };
 
-    let memories: Vec<serde_json::Value> = state.db.query(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load memories for regeneration");
-        format!("Failed to load memories: {}", e)
-    })?;
-
-    let mut processed = 0;
-    let mut success = 0;
-    let mut failed = 0;
-
-    for mem in &memories {
-        processed += 1;
-
-        let id = match mem.get("id").and_then(|i| i.as_str()) {
-            Some(i) => i,
-            None => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        let content = match mem.get("content").and_then(|c| c.as_str()) {
-            Some(c) => c,
-            None => {
-                failed += 1;
-                continue;
-            }
-        };
-
-        // Generate embedding
-        match service.embed(content).await {
-            Ok(embedding) => {
-                // Update memory with new embedding using execute() to avoid serialization issues
-                let embedding_json = serde_json::to_string(&embedding).unwrap_or_default();
-                let update_query =
-                    format!("UPDATE memory:`{}` SET embedding = {}", id, embedding_json);
-
-                match state.db.execute(&update_query).await {
-                    Ok(_) => success += 1,
-                    Err(e) => {
-                        warn!(memory_id = %id, error = %e, "Failed to update embedding");
-                        failed += 1;
-                    }
-                }
-            }
-            Err(e) => {
-                warn!(memory_id = %id, error = %e, "Failed to generate embedding");
-                failed += 1;
-            }
+    let result = memory::regenerate_embeddings(&state.db, &service, progress, |progress| {
+        if let Err(e) = app.emit(events::EMBEDDING_REGENERATION_PROGRESS, progress) {
+            warn!(error = %e, "Failed to emit regeneration progress");
         }
-    }
+    })
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Embedding regeneration interrupted");
+        e
+    })?;
 
-    info!(
-        processed,
-        success, failed, "Embedding regeneration completed"
-    );
+    Ok(result.into())
+}
 
-    Ok(RegenerateResult {
-        processed,
-        success,
-        failed,
-    })
+/// Gets the progress of an interrupted embedding regeneration.
+///
+/// Returns None when no run is pending; otherwise the run can be continued
+/// with `regenerate_embeddings(resume = true)`.
+#[tauri::command]
+#[instrument(name = "get_regeneration_checkpoint", skip(state))]
+pub async fn get_regeneration_checkpoint(
+    state: State<'_, AppState>,
+) -> Result<Option<RegenerateProgress>, String> {
+    memory::load_regeneration_checkpoint(&state.db).await
 }
 
 /// Helper function to get config internally
 async fn get_embedding_config_internal(
     state: &State<'_, AppState>,
 ) -> Result<EmbeddingConfigSettings, String> {
-    let query = format!("SELECT config FROM settings:`{}`", EMBEDDING_CONFIG_KEY);
-
-    let results: Vec<serde_json::Value> = state
-        .db
-        .query_json(&query)
+    let stored = settings::load_config(&state.db, EMBEDDING_CONFIG_KEY)
         .await
         .map_err(|e| format!("Failed to query config: {}", e))?;
 
-    if let Some(row) = results.first() {
-        if let Some(config) = row.get("config") {
-            return serde_json::from_value(config.clone())
-                .map_err(|e| format!("Failed to parse config: {}", e));
-        }
+    if let Some(config) = stored {
+        return serde_json::from_value(config)
+            .map_err(|e| format!("Failed to parse config: {}", e));
     }
 
     Ok(EmbeddingConfigSettings::default())
@@ -689,18 +722,16 @@ pub async fn get_memory_token_stats(
     info!(type_filter = ?type_filter, "Getting memory token statistics");
 
     // Build query with optional filter
-    let query = if let Some(ref mem_type) = type_filter {
-        format!(
-            r#"SELECT
-                type,
-                count() AS count,
-                math::sum(string::len(content)) AS total_chars,
-                count(embedding != NONE) AS with_embeddings
-            FROM memory
-            WHERE type = '{}'
-            GROUP BY type"#,
-            mem_type
-        )
+    let query = if type_filter.is_some() {
+        r#"SELECT
+            type,
+            count() AS count,
+            math::sum(string::len(content)) AS total_chars,
+            count(embedding != NONE) AS with_embeddings
+        FROM memory
+        WHERE type = $type
+        GROUP BY type"#
+            .to_string()
     } else {
         r#"SELECT
             type,
@@ -712,10 +743,15 @@ pub async fn get_memory_token_stats(
             .to_string()
     };
 
-    let results: Vec<serde_json::Value> = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to get token stats");
-        format!("Failed to get token statistics: {}", e)
-    })?;
+    let params = vec![("type".to_string(), json!(type_filter))];
+    let results: Vec<serde_json::Value> = state
+        .db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to get token stats");
+            format!("Failed to get token statistics: {}", e)
+        })?;
 
     let mut categories = Vec::new();
     let mut total_chars: usize = 0;
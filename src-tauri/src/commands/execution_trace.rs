@@ -0,0 +1,386 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution trace commands.
+//!
+//! Assembles the persisted LLM turns and tool executions of an assistant
+//! message into an [`ExecutionTrace`] graph for the execution flow diagram,
+//! and those of a whole workflow into a timeline file for trace viewers
+//! ([`export_workflow_trace`]).
+
+use crate::{
+    commands::transcript::load_rows,
+    db::DBClient,
+    models::{
+        execution_trace::{ExecutionTrace, LlmTurn},
+        sub_agent::SubAgentExecution,
+        workflow_trace::WorkflowTraceExport,
+        ToolExecution,
+    },
+    security::{Validator, WORKFLOW_VAULT},
+    AppState,
+};
+use serde_json::json;
+use std::path::PathBuf;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Gets the execution trace of an assistant message.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow the message belongs to
+/// * `message_id` - The assistant message ID
+///
+/// # Returns
+/// Graph of the tool loop (LLM turn -> tool calls -> next turn -> response)
+/// with timings and token counts per node. Empty if nothing was recorded.
+#[tauri::command]
+#[instrument(
+    name = "get_execution_trace",
+    skip(state),
+    fields(workflow_id = %workflow_id, message_id = %message_id)
+)]
+pub async fn get_execution_trace(
+    workflow_id: String,
+    message_id: String,
+    state: State<'_, AppState>,
+) -> Result<ExecutionTrace, String> {
+    info!("Loading execution trace");
+
+    let validated_workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let validated_message_id = Validator::validate_uuid(&message_id).map_err(|e| {
+        warn!(error = %e, "Invalid message_id");
+        format!("Invalid message_id: {}", e)
+    })?;
+
+    let trace = load_execution_trace(&state.db, &validated_workflow_id, &validated_message_id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load execution trace");
+            format!("Failed to load execution trace: {}", e)
+        })?;
+
+    info!(
+        nodes = trace.nodes.len(),
+        iterations = trace.iterations,
+        "Execution trace loaded"
+    );
+    Ok(trace)
+}
+
+/// Loads the turns and tool executions of a message and builds its trace.
+async fn load_execution_trace(
+    db: &DBClient,
+    workflow_id: &str,
+    message_id: &str,
+) -> Result<ExecutionTrace, String> {
+    let turns_query = r#"SELECT
+            meta::id(id) AS id,
+            workflow_id,
+            message_id,
+            agent_id,
+            iteration,
+            tokens_input,
+            tokens_output,
+            duration_ms,
+            tool_calls,
+            created_at
+        FROM llm_turn
+        WHERE workflow_id = $workflow_id AND message_id = $message_id
+        ORDER BY iteration ASC"#;
+
+    let tools_query = r#"SELECT
+            meta::id(id) AS id,
+            workflow_id,
+            message_id,
+            agent_id,
+            tool_type,
+            tool_name,
+            server_name,
+            input_params,
+            output_result,
+            success,
+            error_message,
+            duration_ms,
+            iteration,
+            created_at
+        FROM tool_execution
+        WHERE workflow_id = $workflow_id AND message_id = $message_id
+        ORDER BY created_at ASC"#;
+    let params = vec![
+        ("workflow_id".to_string(), serde_json::json!(workflow_id)),
+        ("message_id".to_string(), serde_json::json!(message_id)),
+    ];
+
+    let turns: Vec<LlmTurn> = db
+        .query_json_with_params(turns_query, params.clone())
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|e| format!("Failed to deserialize LLM turns: {}", e))?;
+
+    let mut tool_executions: Vec<ToolExecution> = db
+        .query_json_with_params(tools_query, params)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|e| format!("Failed to deserialize tool executions: {}", e))?;
+    tool_executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+
+    Ok(ExecutionTrace::build(
+        workflow_id.to_string(),
+        message_id.to_string(),
+        &turns,
+        &tool_executions,
+    ))
+}
+
+/// Exports the timeline of a workflow as a trace file.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow to export
+/// * `path` - Destination file (JSON)
+///
+/// # Returns
+/// Number of exported spans. The file uses the Jaeger JSON format (one span
+/// per agent run, iteration, LLM call, tool call and sub-agent run) with
+/// OpenTelemetry GenAI attributes.
+#[tauri::command]
+#[instrument(name = "export_workflow_trace", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn export_workflow_trace(
+    workflow_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<usize, String> {
+    let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let export = load_workflow_trace(&state.db, &workflow_id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow trace");
+            e
+        })?;
+    let content = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to serialize trace: {}", e))?;
+
+    let output = PathBuf::from(&path);
+    std::fs::write(&output, content).map_err(|e| format!("Failed to write file: {}", e))?;
+
+    let spans = export.span_count();
+    info!(path = %output.display(), spans, "Workflow trace exported");
+    Ok(spans)
+}
+
+/// Loads the turns, tool executions and sub-agent runs of a workflow as a trace.
+async fn load_workflow_trace(
+    db: &DBClient,
+    workflow_id: &str,
+) -> Result<WorkflowTraceExport, String> {
+    let workflow_name = db
+        .query_json_with_params(
+            "SELECT name FROM type::thing('workflow', $workflow_id)",
+            vec![("workflow_id".to_string(), json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Workflow '{}' not found", workflow_id))?["name"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+
+    let turns: Vec<LlmTurn> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, iteration, tokens_input, \
+         tokens_output, duration_ms, tool_calls, started_at_ms, created_at \
+         FROM llm_turn WHERE workflow_id = $workflow_id ORDER BY created_at ASC",
+        workflow_id,
+        "LLM turns",
+    )
+    .await?;
+    let mut tool_executions: Vec<ToolExecution> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, tool_type, tool_name, \
+         server_name, input_params, output_result, success, error_message, duration_ms, \
+         iteration, started_at_ms, created_at \
+         FROM tool_execution WHERE workflow_id = $workflow_id ORDER BY created_at ASC",
+        workflow_id,
+        "tool executions",
+    )
+    .await?;
+    let sub_agents: Vec<SubAgentExecution> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, parent_agent_id, sub_agent_id, sub_agent_name, \
+         task_description, status, duration_ms, tokens_input, tokens_output, result_summary, \
+         error_message, created_at, completed_at \
+         FROM sub_agent_execution WHERE workflow_id = $workflow_id ORDER BY created_at ASC",
+        workflow_id,
+        "sub-agent executions",
+    )
+    .await?;
+    tool_executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+
+    Ok(WorkflowTraceExport::build(
+        workflow_id,
+        &workflow_name,
+        &turns,
+        &tool_executions,
+        &sub_agents,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::execution_trace::{LlmTurnCreate, TraceNodeKind};
+    use crate::models::ToolExecutionCreate;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_load_execution_trace() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("trace_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        for (iteration, tool_calls) in [(1, 1), (2, 0)] {
+            let turn = LlmTurnCreate {
+                workflow_id: "wf".to_string(),
+                message_id: "msg".to_string(),
+                agent_id: "agent".to_string(),
+                iteration,
+                tokens_input: 100 * iteration as u64,
+                tokens_output: 20,
+                duration_ms: 300,
+                tool_calls,
+                started_at_ms: None,
+            };
+            db.create("llm_turn", &format!("turn{}", iteration), turn)
+                .await
+                .expect("Failed to create turn");
+        }
+        let execution = ToolExecutionCreate {
+            workflow_id: "wf".to_string(),
+            message_id: "msg".to_string(),
+            agent_id: "agent".to_string(),
+            tool_type: "local".to_string(),
+            tool_name: "MemoryTool".to_string(),
+            server_name: None,
+            input_params: serde_json::json!({"operation": "list"}),
+            output_result: serde_json::json!({"success": true}),
+            success: true,
+            error_message: None,
+            duration_ms: 15,
+            iteration: 1,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
+        };
+        db.create("tool_execution", "exec1", execution)
+            .await
+            .expect("Failed to create tool execution");
+
+        let trace = load_execution_trace(&db, "wf", "msg").await.unwrap();
+
+        let kinds: Vec<TraceNodeKind> = trace.nodes.iter().map(|n| n.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TraceNodeKind::LlmTurn,
+                TraceNodeKind::ToolCall,
+                TraceNodeKind::LlmTurn,
+                TraceNodeKind::Response
+            ]
+        );
+        assert_eq!(trace.tokens_input, 300);
+        assert_eq!(trace.total_duration_ms, 615);
+
+        let other = load_execution_trace(&db, "wf", "other_msg").await.unwrap();
+        assert!(other.nodes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_load_workflow_trace() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("workflow_trace_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        assert!(load_workflow_trace(&db, "wf").await.is_err());
+
+        db.create(
+            "workflow",
+            "wf",
+            json!({ "id": "wf", "name": "Release", "agent_id": "agent", "status": "idle" }),
+        )
+        .await
+        .unwrap();
+        let turn = LlmTurnCreate {
+            workflow_id: "wf".to_string(),
+            message_id: "msg".to_string(),
+            agent_id: "agent".to_string(),
+            iteration: 1,
+            tokens_input: 100,
+            tokens_output: 20,
+            duration_ms: 300,
+            tool_calls: 1,
+            started_at_ms: Some(1_790_000_000_000),
+        };
+        db.create("llm_turn", "turn1", turn).await.unwrap();
+        let mut execution = ToolExecutionCreate::local(
+            "wf".to_string(),
+            "msg".to_string(),
+            "agent".to_string(),
+            "MemoryTool".to_string(),
+            json!({}),
+            json!({}),
+            true,
+            None,
+            15,
+            1,
+        );
+        execution.started_at_ms = Some(1_790_000_000_300);
+        db.create("tool_execution", "exec1", execution)
+            .await
+            .unwrap();
+
+        let export = load_workflow_trace(&db, "wf").await.unwrap();
+        let spans = &export.data[0].spans;
+        // Workflow, agent run, iteration, LLM call, tool call
+        assert_eq!(spans.len(), 5);
+        assert_eq!(spans[0].operation_name, "workflow Release");
+        assert_eq!(spans[0].start_time, 1_790_000_000_000_000);
+        assert_eq!(spans[0].duration, 315_000);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External database commands.
+//!
+//! Manages the external databases (PostgreSQL, MySQL, SQLite) agents can
+//! query with the SqlTool. Metadata is stored in the database and connection
+//! strings in SecureKeyStore.
+
+use crate::commands::SecureKeyStore;
+use crate::models::external_db::{ExternalDatabase, ExternalDatabaseInput, ExternalDbKind};
+use crate::state::AppState;
+use crate::tools::constants::external_db as sql_const;
+use crate::tools::external_db::config::{self, connection_key, validate_database_name};
+use crate::tools::external_db::connection;
+use tauri::State;
+use tracing::{info, instrument, warn};
+
+/// Builds the database to save from the user input.
+///
+/// The engine is detected from the connection string, which is required when
+/// creating a database and optional when updating one.
+fn build_database(
+    input: ExternalDatabaseInput,
+    existing: Option<&ExternalDatabase>,
+    connection_string: Option<&str>,
+) -> Result<ExternalDatabase, String> {
+    let name = validate_database_name(&input.name)?;
+    let kind = match connection_string {
+        Some(connection_string) => ExternalDbKind::from_connection_string(connection_string)
+            .ok_or_else(|| {
+                "Unsupported connection string: use a postgres://, mysql:// or sqlite: URL"
+                    .to_string()
+            })?,
+        None => {
+            existing
+                .ok_or_else(|| "A connection string is required".to_string())?
+                .kind
+        }
+    };
+
+    let description = input
+        .description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+    if description
+        .as_ref()
+        .is_some_and(|d| d.chars().count() > sql_const::MAX_DESCRIPTION_LENGTH)
+    {
+        return Err(format!(
+            "Description must be at most {} characters",
+            sql_const::MAX_DESCRIPTION_LENGTH
+        ));
+    }
+
+    let max_rows = input.max_rows.unwrap_or(sql_const::DEFAULT_MAX_ROWS);
+    if !(1..=sql_const::MAX_ROWS).contains(&max_rows) {
+        return Err(format!("Max rows must be 1-{}", sql_const::MAX_ROWS));
+    }
+
+    Ok(ExternalDatabase {
+        name,
+        kind,
+        description,
+        read_only: input.read_only,
+        max_rows,
+        created_at: existing.and_then(|database| database.created_at.clone()),
+    })
+}
+
+/// Lists the external databases.
+#[tauri::command]
+#[instrument(name = "list_external_databases", skip(state))]
+pub async fn list_external_databases(
+    state: State<'_, AppState>,
+) -> Result<Vec<ExternalDatabase>, String> {
+    config::list_databases(&state.db).await
+}
+
+/// Creates or updates an external database.
+///
+/// # Arguments
+/// * `database` - Name, description, read-only flag and row limit
+/// * `connection_string` - Connection URL (required on creation, kept when omitted on update)
+#[tauri::command]
+#[instrument(
+    name = "save_external_database",
+    skip(state, keystore, connection_string)
+)]
+pub async fn save_external_database(
+    database: ExternalDatabaseInput,
+    connection_string: Option<String>,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<ExternalDatabase, String> {
+    let name = validate_database_name(&database.name)?;
+    let connection_string = connection_string
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let existing = config::load_database(&state.db, &name).await?;
+    let database = build_database(database, existing.as_ref(), connection_string.as_deref())?;
+
+    if let Some(connection_string) = &connection_string {
+        keystore
+            .set_key(&connection_key(&name), connection_string)
+            .map_err(|e| format!("Failed to store connection string: {}", e))?;
+    }
+    config::save_database(&state.db, &database).await?;
+
+    info!(name = %name, kind = %database.kind, "External database saved");
+    config::load_database(&state.db, &name)
+        .await?
+        .ok_or_else(|| "External database not found after save".to_string())
+}
+
+/// Deletes an external database and its connection string.
+#[tauri::command]
+#[instrument(name = "delete_external_database", skip(state, keystore))]
+pub async fn delete_external_database(
+    name: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<(), String> {
+    let name = validate_database_name(&name)?;
+    config::delete_database(&state.db, &name).await?;
+
+    if let Err(e) = keystore.delete_key(&connection_key(&name)) {
+        warn!(name = %name, error = %e, "Failed to delete connection string (may not exist)");
+    }
+
+    info!(name = %name, "External database deleted");
+    Ok(())
+}
+
+/// Checks that an external database accepts connections.
+#[tauri::command]
+#[instrument(name = "test_external_database", skip(state, keystore))]
+pub async fn test_external_database(
+    name: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<(), String> {
+    let name = validate_database_name(&name)?;
+    let database = config::load_database(&state.db, &name)
+        .await?
+        .ok_or_else(|| format!("External database '{}' not found", name))?;
+    let connection_string = keystore
+        .get_key(&connection_key(&name))
+        .ok_or_else(|| "No connection string stored".to_string())?;
+
+    connection::test_connection(database.kind, &connection_string).await?;
+    info!(name = %name, "External database connection succeeded");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(name: &str) -> ExternalDatabaseInput {
+        ExternalDatabaseInput {
+            name: name.to_string(),
+            description: Some("  ".to_string()),
+            read_only: true,
+            max_rows: None,
+        }
+    }
+
+    #[test]
+    fn test_build_database() {
+        let created = build_database(input("crm"), None, Some("postgres://u:p@host/crm")).unwrap();
+        assert_eq!(created.kind, ExternalDbKind::Postgres);
+        assert_eq!(created.max_rows, sql_const::DEFAULT_MAX_ROWS);
+        assert_eq!(created.description, None);
+
+        // Update without a new connection string keeps the engine
+        let updated = build_database(input("crm"), Some(&created), None).unwrap();
+        assert_eq!(updated.kind, ExternalDbKind::Postgres);
+
+        assert!(build_database(input("crm"), None, None).is_err());
+        assert!(build_database(input("crm"), None, Some("redis://host")).is_err());
+        let too_many = ExternalDatabaseInput {
+            max_rows: Some(sql_const::MAX_ROWS + 1),
+            ..input("crm")
+        };
+        assert!(build_database(too_many, None, Some("sqlite::memory:")).is_err());
+    }
+}
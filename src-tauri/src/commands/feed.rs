@@ -0,0 +1,179 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! News feed commands.
+//!
+//! Manages the RSS/Atom feeds read by the RssTool and creates the scheduled
+//! digests: a daily schedule running an agent with the RssTool, whose
+//! summaries are posted into a workflow dedicated to the digest.
+
+use crate::commands::schedule::create_schedule;
+use crate::models::feed::{Feed, FeedCreate};
+use crate::models::schedule::Schedule;
+use crate::security::Validator;
+use crate::state::AppState;
+use crate::tools::constants::rss as rss_const;
+use crate::tools::rss::fetch::{fetch_feed, http_client, validate_feed_url};
+use crate::tools::rss::store;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Lists the news feeds.
+///
+/// # Returns
+/// Feeds sorted by title, with their last fetch outcome
+#[tauri::command]
+#[instrument(name = "list_feeds", skip(state))]
+pub async fn list_feeds(state: State<'_, AppState>) -> Result<Vec<Feed>, String> {
+    info!("Listing feeds");
+
+    let feeds = store::list_feeds(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to list feeds");
+        e
+    })?;
+
+    info!(count = feeds.len(), "Feeds loaded");
+    Ok(feeds)
+}
+
+/// Adds a news feed.
+///
+/// The feed is downloaded once to check that it is an RSS or Atom feed and
+/// to read its title. Its items are reported by the next `fetch_new`.
+///
+/// # Arguments
+/// * `url` - Feed URL (http or https)
+///
+/// # Returns
+/// The added feed
+#[tauri::command]
+#[instrument(name = "add_feed", skip(state))]
+pub async fn add_feed(url: String, state: State<'_, AppState>) -> Result<Feed, String> {
+    info!("Adding feed");
+
+    let url = validate_feed_url(&url)?;
+    let feeds = store::list_feeds(&state.db).await?;
+    if feeds.len() >= rss_const::MAX_FEEDS {
+        return Err(format!(
+            "Maximum number of feeds reached ({})",
+            rss_const::MAX_FEEDS
+        ));
+    }
+    if feeds.iter().any(|feed| feed.url == url) {
+        return Err("This feed is already added".to_string());
+    }
+
+    let parsed = fetch_feed(&http_client()?, &url).await.map_err(|e| {
+        warn!(error = %e, "Feed check failed");
+        e
+    })?;
+
+    let feed_id = Uuid::new_v4().to_string();
+    let record = FeedCreate {
+        url,
+        title: parsed.title,
+        site_url: parsed.site_url,
+    };
+    state
+        .db
+        .create("feed", &feed_id, record)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create feed");
+            format!("Failed to create feed: {}", e)
+        })?;
+
+    info!(feed_id = %feed_id, items = parsed.items.len(), "Feed added");
+    store::load_feed(&state.db, &feed_id)
+        .await?
+        .ok_or_else(|| "Feed not found".to_string())
+}
+
+/// Deletes a news feed and its recorded items.
+///
+/// # Arguments
+/// * `feed_id` - Feed ID
+#[tauri::command]
+#[instrument(name = "delete_feed", skip(state))]
+pub async fn delete_feed(feed_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    info!("Deleting feed");
+
+    let feed_id = Validator::validate_uuid(&feed_id).map_err(|e| {
+        warn!(error = %e, "Invalid feed_id");
+        format!("Invalid feed_id: {}", e)
+    })?;
+    if store::load_feed(&state.db, &feed_id).await?.is_none() {
+        return Err("Feed not found".to_string());
+    }
+    store::delete_feed(&state.db, &feed_id).await.map_err(|e| {
+        error!(error = %e, "Failed to delete feed");
+        e
+    })?;
+
+    info!(feed_id = %feed_id, "Feed deleted");
+    Ok(())
+}
+
+/// Creates a scheduled news digest.
+///
+/// The schedule runs the digest prompt with the agent, which must have the
+/// RssTool enabled; the digests are posted into a new workflow named after
+/// the schedule. The schedule is then managed like any other.
+///
+/// # Arguments
+/// * `agent_id` - Agent writing the digests
+/// * `name` - Schedule and workflow name (default: "News digest")
+/// * `cron` - Cron expression in local time (default: every day at 8:00)
+///
+/// # Returns
+/// The created schedule
+#[tauri::command]
+#[instrument(name = "create_feed_digest", skip(state))]
+pub async fn create_feed_digest(
+    agent_id: String,
+    name: Option<String>,
+    cron: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Schedule, String> {
+    info!("Creating feed digest");
+
+    let agent = state
+        .registry
+        .get(agent_id.trim())
+        .await
+        .ok_or_else(|| "Agent not found".to_string())?;
+    if !agent.config().tools.iter().any(|tool| tool == "RssTool") {
+        return Err("The digest agent must have the RssTool enabled".to_string());
+    }
+
+    let name = name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "News digest".to_string());
+    let cron = cron
+        .map(|cron| cron.trim().to_string())
+        .filter(|cron| !cron.is_empty())
+        .unwrap_or_else(|| rss_const::DEFAULT_DIGEST_CRON.to_string());
+
+    create_schedule(
+        name,
+        cron,
+        agent_id,
+        rss_const::DIGEST_PROMPT.to_string(),
+        None,
+        state,
+    )
+    .await
+}
@@ -24,14 +24,87 @@
 //! - `validate_import` - Validate import file and detect conflicts
 //! - `execute_import` - Execute import with conflict resolutions
 
+use crate::db::DBClient;
+use crate::llm::embedding::EmbeddingService;
+use crate::models::chat_history::{
+    detect_source, parse_conversations, ChatHistorySource, ImportedConversation,
+};
 use crate::models::import_export::*;
+use crate::models::message::MessageRole;
 use crate::models::prompt::Prompt;
+use crate::models::MemoryType;
+use crate::security::validation::MAX_WORKFLOW_NAME_LEN;
+use crate::security::Validator;
 use crate::state::AppState;
-use std::collections::HashMap;
+use crate::tools::constants::{commands as cmd_const, memory as memory_const};
+use crate::tools::memory::{add_memory_core, AddMemoryParams};
+use chrono::Utc;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
+use std::sync::Arc;
 use tauri::State;
 use tracing::instrument;
 
+// ============================================================================
+// Queries
+// ============================================================================
+
+/// Selects `fields` of the `table` record with the given ID (empty if missing).
+async fn select_record(
+    db: &DBClient,
+    fields: &str,
+    table: &str,
+    id: &str,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    db.query_json_with_params(
+        &format!("SELECT {} FROM type::thing($table, $id)", fields),
+        vec![
+            ("table".to_string(), json!(table)),
+            ("id".to_string(), json!(id)),
+        ],
+    )
+    .await
+}
+
+/// Returns the ID of the `table` record with the given name, if any.
+async fn find_id_by_name(db: &DBClient, table: &str, name: &str) -> Option<String> {
+    db.query_json_with_params(
+        &format!(
+            "SELECT meta::id(id) AS id FROM {} WHERE name = $name",
+            table
+        ),
+        vec![("name".to_string(), json!(name))],
+    )
+    .await
+    .unwrap_or_default()
+    .first()
+    .and_then(|row| row["id"].as_str())
+    .map(String::from)
+}
+
+/// Writes an imported record: `fields` (a SET clause bound to `params`, which
+/// include `$id`) overwrite the existing record, or create a new one.
+async fn upsert_record(
+    db: &DBClient,
+    table: &str,
+    fields: &str,
+    resolution: Option<ConflictResolution>,
+    mut params: Vec<(String, serde_json::Value)>,
+) -> anyhow::Result<()> {
+    params.push(("table".to_string(), json!(table)));
+    let query = if resolution == Some(ConflictResolution::Overwrite) {
+        format!("UPDATE type::thing($table, $id) SET {}", fields)
+    } else {
+        format!(
+            "CREATE type::thing($table, $id) SET {}, created_at = time::now()",
+            fields
+        )
+    };
+    db.execute_with_params(&query, params).await
+}
+
 // ============================================================================
 // Export Commands
 // ============================================================================
@@ -75,17 +148,14 @@ pub async fn prepare_export_preview(
 
     // Load agent summaries
     for agent_id in &selection.agents {
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers FROM agent WHERE meta::id(id) = '{}'",
-            agent_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query agent: {}", e))?;
+        let results = select_record(
+            &state.db,
+            "meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers",
+            "agent",
+            agent_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query agent: {}", e))?;
 
         if let Some(row) = results.first() {
             let llm = &row["llm"];
@@ -103,17 +173,14 @@ pub async fn prepare_export_preview(
 
     // Load MCP server summaries and env keys
     for server_id in &selection.mcp_servers {
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, enabled, command, env FROM mcp_server WHERE meta::id(id) = '{}'",
-            server_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query MCP server: {}", e))?;
+        let results = select_record(
+            &state.db,
+            "meta::id(id) AS id, name, enabled, command, env",
+            "mcp_server",
+            server_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query MCP server: {}", e))?;
 
         if let Some(row) = results.first() {
             let id = row["id"].as_str().unwrap_or("").to_string();
@@ -139,17 +206,14 @@ pub async fn prepare_export_preview(
 
     // Load model summaries
     for model_id in &selection.models {
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, provider, api_name, is_builtin FROM llm_model WHERE meta::id(id) = '{}'",
-            model_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query model: {}", e))?;
+        let results = select_record(
+            &state.db,
+            "meta::id(id) AS id, name, provider, api_name, is_builtin",
+            "llm_model",
+            model_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query model: {}", e))?;
 
         if let Some(row) = results.first() {
             preview.models.push(LLMModelExportSummary {
@@ -164,17 +228,14 @@ pub async fn prepare_export_preview(
 
     // Load prompt summaries
     for prompt_id in &selection.prompts {
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, description, category, content FROM prompt WHERE meta::id(id) = '{}'",
-            prompt_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query prompt: {}", e))?;
+        let results = select_record(
+            &state.db,
+            "meta::id(id) AS id, name, description, category, content",
+            "prompt",
+            prompt_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query prompt: {}", e))?;
 
         if let Some(row) = results.first() {
             let content = row["content"].as_str().unwrap_or("");
@@ -228,6 +289,33 @@ pub async fn generate_export_file(
         return Err("At least one entity must be selected for export".to_string());
     }
 
+    let package = build_export_package(&state.db, &selection, &options, &sanitization).await?;
+
+    // Serialize to JSON
+    let json = serde_json::to_string_pretty(&package)
+        .map_err(|e| format!("Failed to serialize export: {}", e))?;
+
+    tracing::info!(
+        agents = package.manifest.counts.agents,
+        mcp_servers = package.manifest.counts.mcp_servers,
+        models = package.manifest.counts.models,
+        prompts = package.manifest.counts.prompts,
+        size_bytes = json.len(),
+        "Export file generated"
+    );
+
+    Ok(json)
+}
+
+/// Builds the export package of the selected entities (shared with settings sync).
+///
+/// MCP servers are sanitized with `sanitization` (keyed by server ID).
+pub(crate) async fn build_export_package(
+    db: &DBClient,
+    selection: &ExportSelection,
+    options: &ExportOptions,
+    sanitization: &HashMap<String, MCPSanitizationConfig>,
+) -> Result<ExportPackage, String> {
     let mut agents = Vec::new();
     let mut mcp_servers = Vec::new();
     let mut models = Vec::new();
@@ -235,17 +323,14 @@ pub async fn generate_export_file(
 
     // Export agents
     for agent_id in &selection.agents {
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers, system_prompt, max_tool_iterations, enable_thinking, created_at, updated_at FROM agent WHERE meta::id(id) = '{}'",
-            agent_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query agent: {}", e))?;
+        let results = select_record(
+            db,
+            "meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers, system_prompt, max_tool_iterations, enable_thinking, created_at, updated_at",
+            "agent",
+            agent_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query agent: {}", e))?;
 
         if let Some(row) = results.first() {
             let llm = &row["llm"];
@@ -301,17 +386,14 @@ pub async fn generate_export_file(
             }
         }
 
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, enabled, command, args, env, description, created_at, updated_at FROM mcp_server WHERE meta::id(id) = '{}'",
-            server_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query MCP server: {}", e))?;
+        let results = select_record(
+            db,
+            "meta::id(id) AS id, name, enabled, command, args, env, description, created_at, updated_at",
+            "mcp_server",
+            server_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query MCP server: {}", e))?;
 
         if let Some(row) = results.first() {
             // Parse env from JSON string
@@ -381,17 +463,14 @@ pub async fn generate_export_file(
 
     // Export models
     for model_id in &selection.models {
-        let query = format!(
-            "SELECT meta::id(id) AS id, provider, name, api_name, context_window, max_output_tokens, temperature_default, is_builtin, is_reasoning, input_price_per_mtok, output_price_per_mtok, created_at, updated_at FROM llm_model WHERE meta::id(id) = '{}'",
-            model_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query model: {}", e))?;
+        let results = select_record(
+            db,
+            "meta::id(id) AS id, provider, name, api_name, context_window, max_output_tokens, temperature_default, is_builtin, is_reasoning, input_price_per_mtok, output_price_per_mtok, created_at, updated_at",
+            "llm_model",
+            model_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query model: {}", e))?;
 
         if let Some(row) = results.first() {
             // Note: ID is NOT exported - entities are identified by name
@@ -422,17 +501,14 @@ pub async fn generate_export_file(
 
     // Export prompts
     for prompt_id in &selection.prompts {
-        let query = format!(
-            "SELECT meta::id(id) AS id, name, description, category, content, created_at, updated_at FROM prompt WHERE meta::id(id) = '{}'",
-            prompt_id
-        );
-        let results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .map_err(|e| format!("Failed to query prompt: {}", e))?;
+        let results = select_record(
+            db,
+            "meta::id(id) AS id, name, description, category, content, created_at, updated_at",
+            "prompt",
+            prompt_id,
+        )
+        .await
+        .map_err(|e| format!("Failed to query prompt: {}", e))?;
 
         if let Some(row) = results.first() {
             // Note: ID is NOT exported - entities are identified by name
@@ -455,23 +531,13 @@ pub async fn generate_export_file(
         }
     }
 
-    // Build export package
-    let package = ExportPackage::new(agents, mcp_servers, models, prompts, None);
-
-    // Serialize to JSON
-    let json = serde_json::to_string_pretty(&package)
-        .map_err(|e| format!("Failed to serialize export: {}", e))?;
-
-    tracing::info!(
-        agents = package.manifest.counts.agents,
-        mcp_servers = package.manifest.counts.mcp_servers,
-        models = package.manifest.counts.models,
-        prompts = package.manifest.counts.prompts,
-        size_bytes = json.len(),
-        "Export file generated"
-    );
-
-    Ok(json)
+    Ok(ExportPackage::new(
+        agents,
+        mcp_servers,
+        models,
+        prompts,
+        None,
+    ))
 }
 
 // ============================================================================
@@ -545,24 +611,11 @@ pub async fn validate_import(
         });
 
         // Check for name conflict - this is the ONLY conflict check
-        let name_query = format!(
-            "SELECT meta::id(id) AS id FROM agent WHERE name = {}",
-            serde_json::to_string(&agent.name).unwrap_or_default()
-        );
-        let name_results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&name_query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .unwrap_or_default();
-
-        if !name_results.is_empty() {
-            let existing = &name_results[0];
+        if let Some(existing_id) = find_id_by_name(&state.db, "agent", &agent.name).await {
             conflicts.push(ImportConflict {
                 entity_type: "agent".to_string(),
                 entity_name: agent.name.clone(),
-                existing_id: existing["id"].as_str().unwrap_or("").to_string(),
+                existing_id,
             });
         }
     }
@@ -590,24 +643,11 @@ pub async fn validate_import(
         }
 
         // Check for name conflict - this is the ONLY conflict check
-        let name_query = format!(
-            "SELECT meta::id(id) AS id FROM mcp_server WHERE name = {}",
-            serde_json::to_string(&server.name).unwrap_or_default()
-        );
-        let name_results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&name_query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .unwrap_or_default();
-
-        if !name_results.is_empty() {
-            let existing = &name_results[0];
+        if let Some(existing_id) = find_id_by_name(&state.db, "mcp_server", &server.name).await {
             conflicts.push(ImportConflict {
                 entity_type: "mcp".to_string(),
                 entity_name: server.name.clone(),
-                existing_id: existing["id"].as_str().unwrap_or("").to_string(),
+                existing_id,
             });
         }
     }
@@ -631,24 +671,11 @@ pub async fn validate_import(
         }
 
         // Check for name conflict - this is the ONLY conflict check
-        let name_query = format!(
-            "SELECT meta::id(id) AS id FROM llm_model WHERE name = {}",
-            serde_json::to_string(&model.name).unwrap_or_default()
-        );
-        let name_results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&name_query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .unwrap_or_default();
-
-        if !name_results.is_empty() {
-            let existing = &name_results[0];
+        if let Some(existing_id) = find_id_by_name(&state.db, "llm_model", &model.name).await {
             conflicts.push(ImportConflict {
                 entity_type: "model".to_string(),
                 entity_name: model.name.clone(),
-                existing_id: existing["id"].as_str().unwrap_or("").to_string(),
+                existing_id,
             });
         }
     }
@@ -666,24 +693,11 @@ pub async fn validate_import(
         });
 
         // Check for name conflict - this is the ONLY conflict check
-        let name_query = format!(
-            "SELECT meta::id(id) AS id FROM prompt WHERE name = {}",
-            serde_json::to_string(&prompt.name).unwrap_or_default()
-        );
-        let name_results: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&name_query)
-            .await
-            .map(|mut r| r.take(0).unwrap_or_default())
-            .unwrap_or_default();
-
-        if !name_results.is_empty() {
-            let existing = &name_results[0];
+        if let Some(existing_id) = find_id_by_name(&state.db, "prompt", &prompt.name).await {
             conflicts.push(ImportConflict {
                 entity_type: "prompt".to_string(),
                 entity_name: prompt.name.clone(),
-                existing_id: existing["id"].as_str().unwrap_or("").to_string(),
+                existing_id,
             });
         }
     }
@@ -746,6 +760,27 @@ pub async fn execute_import(
     let package: ExportPackage =
         serde_json::from_str(&data).map_err(|e| format!("Invalid JSON: {}", e))?;
 
+    Ok(import_package(
+        &state.db,
+        &package,
+        &selection,
+        &resolutions,
+        &mcp_additions,
+    )
+    .await)
+}
+
+/// Imports the selected entities of a package with conflict resolutions
+/// applied (shared with settings sync).
+///
+/// Entity failures are reported in the result, not as an error.
+pub(crate) async fn import_package(
+    db: &DBClient,
+    package: &ExportPackage,
+    selection: &ImportSelection,
+    resolutions: &HashMap<String, ConflictResolution>,
+    mcp_additions: &HashMap<String, MCPAdditions>,
+) -> ImportResult {
     let mut imported = ImportCounts::default();
     let mut skipped = ImportCounts::default();
     let mut errors = Vec::new();
@@ -767,21 +802,7 @@ pub async fn execute_import(
 
         // For Overwrite, we need to find the existing ID by name
         let existing_id = if resolution == Some(ConflictResolution::Overwrite) {
-            let query = format!(
-                "SELECT meta::id(id) AS id FROM agent WHERE name = {}",
-                serde_json::to_string(&agent.name).unwrap_or_default()
-            );
-            let results: Vec<serde_json::Value> = state
-                .db
-                .db
-                .query(&query)
-                .await
-                .map(|mut r| r.take(0).unwrap_or_default())
-                .unwrap_or_default();
-            results
-                .first()
-                .and_then(|r| r["id"].as_str())
-                .map(String::from)
+            find_id_by_name(db, "agent", &agent.name).await
         } else {
             None
         };
@@ -796,71 +817,34 @@ pub async fn execute_import(
             agent.name.clone()
         };
 
-        // Build insert/upsert query
-        let tools_json = serde_json::to_string(&agent.tools).unwrap_or("[]".to_string());
-        let mcp_servers_json =
-            serde_json::to_string(&agent.mcp_servers).unwrap_or("[]".to_string());
-        let system_prompt_json =
-            serde_json::to_string(&agent.system_prompt).unwrap_or("\"\"".to_string());
-        let name_json = serde_json::to_string(&name).unwrap_or("\"\"".to_string());
-
-        let query = if resolution == Some(ConflictResolution::Overwrite) {
-            format!(
-                "UPDATE agent:`{}` SET \
-                    name = {}, \
-                    lifecycle = '{}', \
-                    llm = {{ provider: '{}', model: '{}', temperature: {}, max_tokens: {} }}, \
-                    tools = {}, \
-                    mcp_servers = {}, \
-                    system_prompt = {}, \
-                    max_tool_iterations = {}, \
-                    enable_thinking = {}, \
-                    updated_at = time::now()",
-                agent_id,
-                name_json,
-                agent.lifecycle,
-                agent.llm.provider,
-                agent.llm.model,
-                agent.llm.temperature,
-                agent.llm.max_tokens,
-                tools_json,
-                mcp_servers_json,
-                system_prompt_json,
-                agent.max_tool_iterations,
-                agent.enable_thinking
-            )
-        } else {
-            format!(
-                "CREATE agent:`{}` CONTENT {{ \
-                    id: '{}', \
-                    name: {}, \
-                    lifecycle: '{}', \
-                    llm: {{ provider: '{}', model: '{}', temperature: {}, max_tokens: {} }}, \
-                    tools: {}, \
-                    mcp_servers: {}, \
-                    system_prompt: {}, \
-                    max_tool_iterations: {}, \
-                    enable_thinking: {}, \
-                    created_at: time::now(), \
-                    updated_at: time::now() \
-                }}",
-                agent_id,
-                agent_id,
-                name_json,
-                agent.lifecycle,
-                agent.llm.provider,
-                agent.llm.model,
-                agent.llm.temperature,
-                agent.llm.max_tokens,
-                tools_json,
-                mcp_servers_json,
-                system_prompt_json,
-                agent.max_tool_iterations,
-                agent.enable_thinking
-            )
-        };
-
-        match state.db.execute(&query).await {
+        let fields = "name = $name, lifecycle = $lifecycle, llm = $llm, tools = $tools, \
+                      mcp_servers = $mcp_servers, system_prompt = $system_prompt, \
+                      max_tool_iterations = $max_tool_iterations, \
+                      enable_thinking = $enable_thinking, updated_at = time::now()";
+        let params = vec![
+            ("id".to_string(), json!(agent_id)),
+            ("name".to_string(), json!(name)),
+            ("lifecycle".to_string(), json!(agent.lifecycle)),
+            (
+                "llm".to_string(),
+                json!({
+                    "provider": agent.llm.provider,
+                    "model": agent.llm.model,
+                    "temperature": agent.llm.temperature,
+                    "max_tokens": agent.llm.max_tokens,
+                }),
+            ),
+            ("tools".to_string(), json!(agent.tools)),
+            ("mcp_servers".to_string(), json!(agent.mcp_servers)),
+            ("system_prompt".to_string(), json!(agent.system_prompt)),
+            (
+                "max_tool_iterations".to_string(),
+                json!(agent.max_tool_iterations),
+            ),
+            ("enable_thinking".to_string(), json!(agent.enable_thinking)),
+        ];
+
+        match upsert_record(db, "agent", fields, resolution, params).await {
             Ok(_) => imported.agents += 1,
             Err(e) => {
                 errors.push(ImportError {
@@ -889,21 +873,7 @@ pub async fn execute_import(
 
         // For Overwrite, we need to find the existing ID by name
         let existing_id = if resolution == Some(ConflictResolution::Overwrite) {
-            let query = format!(
-                "SELECT meta::id(id) AS id FROM mcp_server WHERE name = {}",
-                serde_json::to_string(&server.name).unwrap_or_default()
-            );
-            let results: Vec<serde_json::Value> = state
-                .db
-                .db
-                .query(&query)
-                .await
-                .map(|mut r| r.take(0).unwrap_or_default())
-                .unwrap_or_default();
-            results
-                .first()
-                .and_then(|r| r["id"].as_str())
-                .map(String::from)
+            find_id_by_name(db, "mcp_server", &server.name).await
         } else {
             None
         };
@@ -925,59 +895,22 @@ pub async fn execute_import(
                 env.insert(key.clone(), value.clone());
             }
         }
-        // Double JSON encoding required: env is stored as STRING in SurrealDB
-        // Pattern from mcp/manager.rs: serialize HashMap to JSON, then encode as JSON string
+        // env is stored as JSON string in SurrealDB (pattern from mcp/manager.rs)
         let env_str = serde_json::to_string(&env).unwrap_or("{}".to_string());
-        let env_json = serde_json::to_string(&env_str).unwrap_or("\"{}\"".to_string());
-
-        let args_json = serde_json::to_string(&server.args).unwrap_or("[]".to_string());
-        let name_json = serde_json::to_string(&name).unwrap_or("\"\"".to_string());
-        let description_json =
-            serde_json::to_string(&server.description).unwrap_or("null".to_string());
-
-        let query = if resolution == Some(ConflictResolution::Overwrite) {
-            format!(
-                "UPDATE mcp_server:`{}` SET \
-                    name = {}, \
-                    enabled = {}, \
-                    command = '{}', \
-                    args = {}, \
-                    env = {}, \
-                    description = {}, \
-                    updated_at = time::now()",
-                server_id,
-                name_json,
-                server.enabled,
-                server.command,
-                args_json,
-                env_json,
-                description_json
-            )
-        } else {
-            format!(
-                "CREATE mcp_server:`{}` CONTENT {{ \
-                    id: '{}', \
-                    name: {}, \
-                    enabled: {}, \
-                    command: '{}', \
-                    args: {}, \
-                    env: {}, \
-                    description: {}, \
-                    created_at: time::now(), \
-                    updated_at: time::now() \
-                }}",
-                server_id,
-                server_id,
-                name_json,
-                server.enabled,
-                server.command,
-                args_json,
-                env_json,
-                description_json
-            )
-        };
 
-        match state.db.execute(&query).await {
+        let fields = "name = $name, enabled = $enabled, command = $command, args = $args, \
+                      env = $env, description = $description, updated_at = time::now()";
+        let params = vec![
+            ("id".to_string(), json!(server_id)),
+            ("name".to_string(), json!(name)),
+            ("enabled".to_string(), json!(server.enabled)),
+            ("command".to_string(), json!(server.command)),
+            ("args".to_string(), json!(server.args)),
+            ("env".to_string(), json!(env_str)),
+            ("description".to_string(), json!(server.description)),
+        ];
+
+        match upsert_record(db, "mcp_server", fields, resolution, params).await {
             Ok(_) => imported.mcp_servers += 1,
             Err(e) => {
                 errors.push(ImportError {
@@ -1006,21 +939,7 @@ pub async fn execute_import(
 
         // For Overwrite, we need to find the existing ID by name
         let existing_id = if resolution == Some(ConflictResolution::Overwrite) {
-            let query = format!(
-                "SELECT meta::id(id) AS id FROM llm_model WHERE name = {}",
-                serde_json::to_string(&model.name).unwrap_or_default()
-            );
-            let results: Vec<serde_json::Value> = state
-                .db
-                .db
-                .query(&query)
-                .await
-                .map(|mut r| r.take(0).unwrap_or_default())
-                .unwrap_or_default();
-            results
-                .first()
-                .and_then(|r| r["id"].as_str())
-                .map(String::from)
+            find_id_by_name(db, "llm_model", &model.name).await
         } else {
             None
         };
@@ -1035,68 +954,38 @@ pub async fn execute_import(
             model.name.clone()
         };
 
-        let name_json = serde_json::to_string(&name).unwrap_or("\"\"".to_string());
-        let api_name_json = serde_json::to_string(&model.api_name).unwrap_or("\"\"".to_string());
-
-        let query = if resolution == Some(ConflictResolution::Overwrite) {
-            format!(
-                "UPDATE llm_model:`{}` SET \
-                    provider = '{}', \
-                    name = {}, \
-                    api_name = {}, \
-                    context_window = {}, \
-                    max_output_tokens = {}, \
-                    temperature_default = {}, \
-                    is_builtin = {}, \
-                    is_reasoning = {}, \
-                    input_price_per_mtok = {}, \
-                    output_price_per_mtok = {}, \
-                    updated_at = time::now()",
-                model_id,
-                model.provider,
-                name_json,
-                api_name_json,
-                model.context_window,
-                model.max_output_tokens,
-                model.temperature_default,
-                model.is_builtin,
-                model.is_reasoning,
-                model.input_price_per_mtok,
-                model.output_price_per_mtok
-            )
-        } else {
-            format!(
-                "CREATE llm_model:`{}` CONTENT {{ \
-                    id: '{}', \
-                    provider: '{}', \
-                    name: {}, \
-                    api_name: {}, \
-                    context_window: {}, \
-                    max_output_tokens: {}, \
-                    temperature_default: {}, \
-                    is_builtin: {}, \
-                    is_reasoning: {}, \
-                    input_price_per_mtok: {}, \
-                    output_price_per_mtok: {}, \
-                    created_at: time::now(), \
-                    updated_at: time::now() \
-                }}",
-                model_id,
-                model_id,
-                model.provider,
-                name_json,
-                api_name_json,
-                model.context_window,
-                model.max_output_tokens,
-                model.temperature_default,
-                model.is_builtin,
-                model.is_reasoning,
-                model.input_price_per_mtok,
-                model.output_price_per_mtok
-            )
-        };
-
-        match state.db.execute(&query).await {
+        let fields = "provider = $provider, name = $name, api_name = $api_name, \
+                      context_window = $context_window, max_output_tokens = $max_output_tokens, \
+                      temperature_default = $temperature_default, is_builtin = $is_builtin, \
+                      is_reasoning = $is_reasoning, input_price_per_mtok = $input_price_per_mtok, \
+                      output_price_per_mtok = $output_price_per_mtok, updated_at = time::now()";
+        let params = vec![
+            ("id".to_string(), json!(model_id)),
+            ("provider".to_string(), json!(model.provider)),
+            ("name".to_string(), json!(name)),
+            ("api_name".to_string(), json!(model.api_name)),
+            ("context_window".to_string(), json!(model.context_window)),
+            (
+                "max_output_tokens".to_string(),
+                json!(model.max_output_tokens),
+            ),
+            (
+                "temperature_default".to_string(),
+                json!(model.temperature_default),
+            ),
+            ("is_builtin".to_string(), json!(model.is_builtin)),
+            ("is_reasoning".to_string(), json!(model.is_reasoning)),
+            (
+                "input_price_per_mtok".to_string(),
+                json!(model.input_price_per_mtok),
+            ),
+            (
+                "output_price_per_mtok".to_string(),
+                json!(model.output_price_per_mtok),
+            ),
+        ];
+
+        match upsert_record(db, "llm_model", fields, resolution, params).await {
             Ok(_) => imported.models += 1,
             Err(e) => {
                 errors.push(ImportError {
@@ -1125,21 +1014,7 @@ pub async fn execute_import(
 
         // For Overwrite, we need to find the existing ID by name
         let existing_id = if resolution == Some(ConflictResolution::Overwrite) {
-            let query = format!(
-                "SELECT meta::id(id) AS id FROM prompt WHERE name = {}",
-                serde_json::to_string(&prompt.name).unwrap_or_default()
-            );
-            let results: Vec<serde_json::Value> = state
-                .db
-                .db
-                .query(&query)
-                .await
-                .map(|mut r| r.take(0).unwrap_or_default())
-                .unwrap_or_default();
-            results
-                .first()
-                .and_then(|r| r["id"].as_str())
-                .map(String::from)
+            find_id_by_name(db, "prompt", &prompt.name).await
         } else {
             None
         };
@@ -1154,54 +1029,21 @@ pub async fn execute_import(
             prompt.name.clone()
         };
 
-        let name_json = serde_json::to_string(&name).unwrap_or("\"\"".to_string());
-        let description_json =
-            serde_json::to_string(&prompt.description).unwrap_or("\"\"".to_string());
-        let content_json = serde_json::to_string(&prompt.content).unwrap_or("\"\"".to_string());
-
         // Extract variables from content using the same pattern as create_prompt
         let variables = Prompt::detect_variables(&prompt.content);
-        let variables_json = serde_json::to_string(&variables).unwrap_or("[]".to_string());
-
-        let query = if resolution == Some(ConflictResolution::Overwrite) {
-            format!(
-                "UPDATE prompt:`{}` SET \
-                    name = {}, \
-                    description = {}, \
-                    category = '{}', \
-                    content = {}, \
-                    variables = {}, \
-                    updated_at = time::now()",
-                prompt_id,
-                name_json,
-                description_json,
-                prompt.category,
-                content_json,
-                variables_json
-            )
-        } else {
-            format!(
-                "CREATE prompt:`{}` CONTENT {{ \
-                    id: '{}', \
-                    name: {}, \
-                    description: {}, \
-                    category: '{}', \
-                    content: {}, \
-                    variables: {}, \
-                    created_at: time::now(), \
-                    updated_at: time::now() \
-                }}",
-                prompt_id,
-                prompt_id,
-                name_json,
-                description_json,
-                prompt.category,
-                content_json,
-                variables_json
-            )
-        };
 
-        match state.db.execute(&query).await {
+        let fields = "name = $name, description = $description, category = $category, \
+                      content = $content, variables = $variables, updated_at = time::now()";
+        let params = vec![
+            ("id".to_string(), json!(prompt_id)),
+            ("name".to_string(), json!(name)),
+            ("description".to_string(), json!(prompt.description)),
+            ("category".to_string(), json!(prompt.category)),
+            ("content".to_string(), json!(prompt.content)),
+            ("variables".to_string(), json!(variables)),
+        ];
+
+        match upsert_record(db, "prompt", fields, resolution, params).await {
             Ok(_) => imported.prompts += 1,
             Err(e) => {
                 errors.push(ImportError {
@@ -1226,12 +1068,12 @@ pub async fn execute_import(
         "Import execution complete"
     );
 
-    Ok(ImportResult {
+    ImportResult {
         success,
         imported,
         skipped,
         errors,
-    })
+    }
 }
 
 // ============================================================================
@@ -1289,3 +1131,1040 @@ pub async fn read_import_file(path: String) -> Result<String, String> {
 
     Ok(content)
 }
+
+// ============================================================================
+// Workspace Archive
+// ============================================================================
+
+/// Table stored in a workspace archive, with the fields it carries.
+struct ArchiveTable {
+    name: &'static str,
+    fields: &'static [&'static str],
+    /// Fields stored as datetimes (cast back on import)
+    datetime_fields: &'static [&'static str],
+}
+
+const WORKFLOW_TABLE: ArchiveTable = ArchiveTable {
+    name: "workflow",
+    fields: &[
+        "name",
+        "agent_id",
+        "status",
+        "created_at",
+        "updated_at",
+        "completed_at",
+        "total_tokens_input",
+        "total_tokens_output",
+        "total_cost_usd",
+        "model_id",
+        "current_context_tokens",
+        "response_language",
+        "tags",
+        "voice",
+    ],
+    datetime_fields: &["created_at", "updated_at", "completed_at"],
+};
+
+const MESSAGE_TABLE: ArchiveTable = ArchiveTable {
+    name: "message",
+    fields: &[
+        "workflow_id",
+        "role",
+        "content",
+        "tokens",
+        "tokens_input",
+        "tokens_output",
+        "model",
+        "provider",
+        "cost_usd",
+        "duration_ms",
+        "context_usage",
+        "read_aloud",
+        "behavior_position",
+        "generation",
+        "timestamp",
+    ],
+    datetime_fields: &["timestamp"],
+};
+
+const TOOL_EXECUTION_TABLE: ArchiveTable = ArchiveTable {
+    name: "tool_execution",
+    fields: &[
+        "workflow_id",
+        "message_id",
+        "agent_id",
+        "tool_type",
+        "tool_name",
+        "server_name",
+        "input_params",
+        "output_result",
+        "success",
+        "error_message",
+        "duration_ms",
+        "iteration",
+        "previous_execution_id",
+        "result_diff",
+        "created_at",
+    ],
+    datetime_fields: &["created_at"],
+};
+
+const THINKING_STEP_TABLE: ArchiveTable = ArchiveTable {
+    name: "thinking_step",
+    fields: &[
+        "workflow_id",
+        "message_id",
+        "agent_id",
+        "step_number",
+        "content",
+        "duration_ms",
+        "tokens",
+        "created_at",
+    ],
+    datetime_fields: &["created_at"],
+};
+
+const MEMORY_TABLE: ArchiveTable = ArchiveTable {
+    name: "memory",
+    fields: &[
+        "type",
+        "content",
+        "embedding",
+        "workflow_id",
+        "metadata",
+        "importance",
+        "expires_at",
+        "embedding_status",
+        "created_at",
+    ],
+    datetime_fields: &["expires_at", "created_at"],
+};
+
+impl ArchiveTable {
+    /// Projection of the archived fields, datetimes as strings.
+    fn projection(&self) -> String {
+        let mut columns = vec!["meta::id(id) AS id".to_string()];
+        columns.extend(self.fields.iter().map(|field| {
+            if self.datetime_fields.contains(field) {
+                format!("IF {0} = NONE THEN NONE ELSE <string>{0} END AS {0}", field)
+            } else {
+                field.to_string()
+            }
+        }));
+        columns.join(", ")
+    }
+
+    /// CREATE query writing an archived record bound as `$data`.
+    fn create_query(&self) -> String {
+        let assignments: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| {
+                if self.datetime_fields.contains(field) {
+                    format!(
+                        "{0} = IF $data.{0} = NONE THEN NONE ELSE <datetime>$data.{0} END",
+                        field
+                    )
+                } else {
+                    format!("{0} = $data.{0}", field)
+                }
+            })
+            .collect();
+        format!(
+            "CREATE type::thing($table, $id) SET id = $id, {} RETURN NONE",
+            assignments.join(", ")
+        )
+    }
+}
+
+/// Selects the archived records of `table` matching `condition`.
+async fn select_archive_records(
+    db: &DBClient,
+    table: &ArchiveTable,
+    condition: &str,
+    workflow_ids: &[String],
+) -> Result<Vec<serde_json::Value>, String> {
+    db.query_json_with_params(
+        &format!(
+            "SELECT {} FROM {} WHERE {}",
+            table.projection(),
+            table.name,
+            condition
+        ),
+        vec![("workflow_ids".to_string(), json!(workflow_ids))],
+    )
+    .await
+    .map_err(|e| format!("Failed to export {} records: {}", table.name, e))
+}
+
+/// Removes null values, which the schema only accepts as absent fields.
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_nulls),
+        _ => {}
+    }
+}
+
+/// Builds a workspace archive of the non-sensitive workflows and memories.
+pub(crate) async fn build_workspace_archive(
+    db: &DBClient,
+    options: &WorkspaceExportOptions,
+) -> Result<WorkspaceArchive, String> {
+    let selection = if options.workflow_ids.is_some() {
+        "meta::id(id) IN $workflow_ids"
+    } else {
+        "true"
+    };
+    let selected = db
+        .query_json_with_params(
+            &format!(
+                "SELECT meta::id(id) AS id, (sensitive ?? false) AS sensitive FROM workflow WHERE {}",
+                selection
+            ),
+            vec![(
+                "workflow_ids".to_string(),
+                json!(options.workflow_ids.clone().unwrap_or_default()),
+            )],
+        )
+        .await
+        .map_err(|e| format!("Failed to list workflows: {}", e))?;
+
+    // Sensitive conversations are encrypted at rest: they never leave the vault
+    let skipped_sensitive = selected
+        .iter()
+        .filter(|row| row["sensitive"].as_bool() == Some(true))
+        .count();
+    let workflow_ids: Vec<String> = selected
+        .iter()
+        .filter(|row| row["sensitive"].as_bool() != Some(true))
+        .filter_map(|row| row["id"].as_str().map(String::from))
+        .collect();
+
+    let workflows = select_archive_records(
+        db,
+        &WORKFLOW_TABLE,
+        "meta::id(id) IN $workflow_ids",
+        &workflow_ids,
+    )
+    .await?;
+    let by_workflow = "workflow_id IN $workflow_ids";
+    let messages = select_archive_records(db, &MESSAGE_TABLE, by_workflow, &workflow_ids).await?;
+    let tool_executions =
+        select_archive_records(db, &TOOL_EXECUTION_TABLE, by_workflow, &workflow_ids).await?;
+    let thinking_steps =
+        select_archive_records(db, &THINKING_STEP_TABLE, by_workflow, &workflow_ids).await?;
+    let mut memories = select_archive_records(
+        db,
+        &MEMORY_TABLE,
+        &format!(
+            "(workflow_id IS NONE OR workflow_id IN $workflow_ids) AND !string::starts_with(content, '{}')",
+            crate::security::workflow_vault::ENCRYPTED_PREFIX
+        ),
+        &workflow_ids,
+    )
+    .await?;
+
+    if !options.include_embeddings {
+        for memory in memories.iter_mut().filter_map(|m| m.as_object_mut()) {
+            memory.remove("embedding");
+            if memory.get("embedding_status").and_then(|s| s.as_str()) == Some("ready") {
+                memory.insert("embedding_status".to_string(), json!("pending"));
+            }
+        }
+    }
+
+    let counts = WorkspaceCounts {
+        workflows: workflows.len(),
+        messages: messages.len(),
+        tool_executions: tool_executions.len(),
+        thinking_steps: thinking_steps.len(),
+        memories: memories.len(),
+    };
+
+    Ok(WorkspaceArchive {
+        manifest: WorkspaceArchiveManifest {
+            version: WORKSPACE_ARCHIVE_VERSION.to_string(),
+            app_version: APP_VERSION.to_string(),
+            exported_at: Utc::now(),
+            counts,
+            skipped_sensitive,
+            include_embeddings: options.include_embeddings,
+        },
+        workflows,
+        messages,
+        tool_executions,
+        thinking_steps,
+        memories,
+    })
+}
+
+/// Whether a record with the given ID exists in `table`.
+async fn record_exists(db: &DBClient, table: &str, id: &str) -> Result<bool, String> {
+    select_record(db, "meta::id(id) AS id", table, id)
+        .await
+        .map(|rows| !rows.is_empty())
+        .map_err(|e| format!("Failed to check {} '{}': {}", table, id, e))
+}
+
+/// Imports archived records of `table`, skipping IDs already present.
+///
+/// Returns the (imported, skipped) counts.
+async fn import_archive_records(
+    db: &DBClient,
+    table: &ArchiveTable,
+    records: Vec<serde_json::Value>,
+    errors: &mut Vec<ImportError>,
+) -> (usize, usize) {
+    let query = table.create_query();
+    let (mut imported, mut skipped) = (0, 0);
+
+    for mut record in records {
+        let Some(id) = record["id"].as_str().map(String::from) else {
+            errors.push(ImportError {
+                entity_type: table.name.to_string(),
+                entity_id: String::new(),
+                error: "Record without ID".to_string(),
+            });
+            continue;
+        };
+
+        match record_exists(db, table.name, &id).await {
+            Ok(true) => {
+                skipped += 1;
+                continue;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                errors.push(ImportError {
+                    entity_type: table.name.to_string(),
+                    entity_id: id,
+                    error: e,
+                });
+                continue;
+            }
+        }
+
+        strip_nulls(&mut record);
+        let params = vec![
+            ("table".to_string(), json!(table.name)),
+            ("id".to_string(), json!(id)),
+            ("data".to_string(), record),
+        ];
+        // Results are read back so that schema violations surface as errors
+        match db.query_json_with_params(&query, params).await {
+            Ok(_) => imported += 1,
+            Err(e) => errors.push(ImportError {
+                entity_type: table.name.to_string(),
+                entity_id: id,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    (imported, skipped)
+}
+
+/// Imports archived records attached to workflows, skipping the records of
+/// workflows that were already present (`existing`).
+async fn import_workflow_records(
+    db: &DBClient,
+    table: &ArchiveTable,
+    records: Vec<serde_json::Value>,
+    existing: &HashSet<String>,
+    errors: &mut Vec<ImportError>,
+) -> (usize, usize) {
+    let (records, preserved): (Vec<_>, Vec<_>) = records.into_iter().partition(|record| {
+        record["workflow_id"]
+            .as_str()
+            .is_none_or(|workflow_id| !existing.contains(workflow_id))
+    });
+    let (imported, skipped) = import_archive_records(db, table, records, errors).await;
+    (imported, skipped + preserved.len())
+}
+
+/// Imports a workspace archive.
+///
+/// Workflows already present are left untouched, and so are their records.
+pub(crate) async fn import_workspace_archive(
+    db: &DBClient,
+    archive: WorkspaceArchive,
+) -> Result<WorkspaceImportResult, String> {
+    if archive.manifest.version != WORKSPACE_ARCHIVE_VERSION {
+        return Err(format!(
+            "Unsupported workspace archive version: {} (expected {})",
+            archive.manifest.version, WORKSPACE_ARCHIVE_VERSION
+        ));
+    }
+
+    let mut result = WorkspaceImportResult::default();
+
+    // Workflows already present keep their conversation: their records are skipped
+    let mut existing = HashSet::new();
+    for workflow in &archive.workflows {
+        let id = workflow["id"].as_str().unwrap_or_default();
+        if record_exists(db, WORKFLOW_TABLE.name, id).await? {
+            existing.insert(id.to_string());
+        }
+    }
+    let (workflows, skipped): (Vec<_>, Vec<_>) = archive
+        .workflows
+        .into_iter()
+        .partition(|w| !existing.contains(w["id"].as_str().unwrap_or_default()));
+    result.skipped.workflows = skipped.len();
+
+    let mut agents: Vec<String> = workflows
+        .iter()
+        .filter_map(|w| w["agent_id"].as_str().map(String::from))
+        .collect();
+    agents.sort();
+    agents.dedup();
+    for agent_id in agents {
+        if !record_exists(db, "agent", &agent_id).await? {
+            result.warnings.push(format!(
+                "Agent '{}' does not exist: select another agent to continue its workflows",
+                agent_id
+            ));
+        }
+    }
+
+    let (imported, _) =
+        import_archive_records(db, &WORKFLOW_TABLE, workflows, &mut result.errors).await;
+    result.imported.workflows = imported;
+
+    (result.imported.messages, result.skipped.messages) = import_workflow_records(
+        db,
+        &MESSAGE_TABLE,
+        archive.messages,
+        &existing,
+        &mut result.errors,
+    )
+    .await;
+    (
+        result.imported.tool_executions,
+        result.skipped.tool_executions,
+    ) = import_workflow_records(
+        db,
+        &TOOL_EXECUTION_TABLE,
+        archive.tool_executions,
+        &existing,
+        &mut result.errors,
+    )
+    .await;
+    (
+        result.imported.thinking_steps,
+        result.skipped.thinking_steps,
+    ) = import_workflow_records(
+        db,
+        &THINKING_STEP_TABLE,
+        archive.thinking_steps,
+        &existing,
+        &mut result.errors,
+    )
+    .await;
+    (result.imported.memories, result.skipped.memories) = import_workflow_records(
+        db,
+        &MEMORY_TABLE,
+        archive.memories,
+        &existing,
+        &mut result.errors,
+    )
+    .await;
+
+    Ok(result)
+}
+
+/// Exports workflows, messages, tool executions, thinking steps and memories
+/// to a single archive file.
+///
+/// Sensitive workflows are left out (counted in the manifest).
+///
+/// # Arguments
+/// * `path` - Full path of the archive file
+/// * `options` - Workflows to export and whether embeddings are included
+///
+/// # Returns
+/// Manifest of the written archive
+#[tauri::command]
+#[instrument(name = "export_workspace", skip(state))]
+pub async fn export_workspace(
+    path: String,
+    options: WorkspaceExportOptions,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceArchiveManifest, String> {
+    let archive = build_workspace_archive(&state.db, &options).await?;
+    let content = serde_json::to_string(&archive)
+        .map_err(|e| format!("Failed to serialize workspace archive: {}", e))?;
+
+    std::fs::write(PathBuf::from(&path), &content)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+
+    tracing::info!(
+        path = %path,
+        size_bytes = content.len(),
+        workflows = archive.manifest.counts.workflows,
+        skipped_sensitive = archive.manifest.skipped_sensitive,
+        "Workspace exported"
+    );
+
+    Ok(archive.manifest)
+}
+
+/// Imports a workspace archive file.
+///
+/// Records whose ID already exists are skipped, never overwritten.
+///
+/// # Arguments
+/// * `path` - Full path of the archive file
+///
+/// # Returns
+/// Import result with counts, warnings and per-record errors
+#[tauri::command]
+#[instrument(name = "import_workspace", skip(state))]
+pub async fn import_workspace(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceImportResult, String> {
+    let content = std::fs::read_to_string(PathBuf::from(&path))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let archive: WorkspaceArchive =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid workspace archive: {}", e))?;
+
+    let result = import_workspace_archive(&state.db, archive).await?;
+
+    tracing::info!(
+        path = %path,
+        workflows = result.imported.workflows,
+        messages = result.imported.messages,
+        errors = result.errors.len(),
+        "Workspace imported"
+    );
+
+    Ok(result)
+}
+
+// ============================================================================
+// Chat History Import
+// ============================================================================
+
+/// Stable UUID of an imported record, so that importing a file twice does not
+/// duplicate its conversations.
+fn stable_import_id(source: ChatHistorySource, external_id: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", source.label(), external_id).as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_random_bytes(bytes)
+        .into_uuid()
+        .to_string()
+}
+
+/// Keeps the first `max_chars` characters of a text.
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let end = text
+        .char_indices()
+        .nth(max_chars.saturating_sub(1))
+        .map_or(0, |(index, _)| index);
+    format!("{}…", &text[..end])
+}
+
+/// Writes an imported conversation as a workflow and its messages.
+async fn create_imported_workflow(
+    db: &DBClient,
+    source: ChatHistorySource,
+    conversation: &ImportedConversation,
+    workflow_id: &str,
+    agent_id: &str,
+) -> anyhow::Result<()> {
+    let name = truncate_chars(&conversation.title, MAX_WORKFLOW_NAME_LEN);
+    let messages: Vec<serde_json::Value> = conversation
+        .messages
+        .iter()
+        .map(|message| {
+            json!({
+                "id": stable_import_id(
+                    source,
+                    &format!("{}/{}", conversation.external_id, message.external_id)
+                ),
+                "role": message.role.to_string(),
+                "content": truncate_chars(&message.content, cmd_const::MAX_MESSAGE_CONTENT_LEN),
+                "timestamp": message.timestamp.to_rfc3339(),
+            })
+        })
+        .collect();
+
+    // One statement list: statement errors surface through the result
+    db.query_json_with_params(
+        "BEGIN TRANSACTION; \
+         CREATE type::thing('workflow', $workflow_id) SET id = $workflow_id, name = $name, \
+         agent_id = $agent_id, status = 'idle', tags = $tags, \
+         created_at = <datetime>$created_at, updated_at = <datetime>$updated_at RETURN NONE; \
+         FOR $message IN $messages { \
+         CREATE type::thing('message', $message.id) SET id = $message.id, \
+         workflow_id = $workflow_id, role = $message.role, content = $message.content, \
+         tokens = 0, timestamp = <datetime>$message.timestamp RETURN NONE; \
+         }; \
+         COMMIT TRANSACTION;",
+        vec![
+            ("workflow_id".to_string(), json!(workflow_id)),
+            ("name".to_string(), json!(name)),
+            ("agent_id".to_string(), json!(agent_id)),
+            ("tags".to_string(), json!([source.label().to_lowercase()])),
+            (
+                "created_at".to_string(),
+                json!(conversation.created_at.to_rfc3339()),
+            ),
+            (
+                "updated_at".to_string(),
+                json!(conversation.updated_at.to_rfc3339()),
+            ),
+            ("messages".to_string(), json!(messages)),
+        ],
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Stores each user question and its answer as a general memory.
+///
+/// Returns the number of memories created.
+async fn create_imported_memories(
+    db: &Arc<DBClient>,
+    embedding_service: Option<&Arc<EmbeddingService>>,
+    source: ChatHistorySource,
+    conversation: &ImportedConversation,
+) -> Result<usize, String> {
+    let mut created = 0;
+    for pair in conversation.messages.windows(2) {
+        let [question, answer] = pair else { continue };
+        if question.role != MessageRole::User || answer.role != MessageRole::Assistant {
+            continue;
+        }
+        let content = truncate_chars(
+            &format!(
+                "{} ({}): {}\n\n{}",
+                conversation.title,
+                source.label(),
+                question.content,
+                answer.content
+            ),
+            memory_const::MAX_CONTENT_LENGTH,
+        );
+        let params = AddMemoryParams {
+            memory_type: MemoryType::Context,
+            content,
+            metadata: json!({
+                "tags": ["imported", source.label().to_lowercase()],
+                "agent_source": "import",
+            }),
+            workflow_id: None,
+            importance: 0.3,
+            expires_at: None,
+        };
+        add_memory_core(params, db, embedding_service).await?;
+        created += 1;
+    }
+    Ok(created)
+}
+
+/// Imports parsed conversations as workflows (and optionally memories).
+///
+/// Conversations already imported (same stable ID) or without text are skipped.
+pub(crate) async fn import_chat_conversations(
+    db: &Arc<DBClient>,
+    embedding_service: Option<&Arc<EmbeddingService>>,
+    source: ChatHistorySource,
+    conversations: Vec<ImportedConversation>,
+    options: &ChatHistoryImportOptions,
+) -> Result<ChatHistoryImportResult, String> {
+    let mut result = ChatHistoryImportResult {
+        source,
+        workflows: 0,
+        messages: 0,
+        memories: 0,
+        skipped: 0,
+        errors: Vec::new(),
+    };
+
+    for conversation in conversations {
+        let workflow_id = stable_import_id(source, &conversation.external_id);
+        if conversation.messages.is_empty() || record_exists(db, "workflow", &workflow_id).await? {
+            result.skipped += 1;
+            continue;
+        }
+
+        if let Err(e) =
+            create_imported_workflow(db, source, &conversation, &workflow_id, &options.agent_id)
+                .await
+        {
+            result.errors.push(ImportError {
+                entity_type: "workflow".to_string(),
+                entity_id: conversation.external_id.clone(),
+                error: e.to_string(),
+            });
+            continue;
+        }
+        result.workflows += 1;
+        result.messages += conversation.messages.len();
+
+        if options.create_memories {
+            match create_imported_memories(db, embedding_service, source, &conversation).await {
+                Ok(created) => result.memories += created,
+                Err(e) => result.errors.push(ImportError {
+                    entity_type: "memory".to_string(),
+                    entity_id: conversation.external_id.clone(),
+                    error: e,
+                }),
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Imports the chat history exported by ChatGPT or Claude.
+///
+/// Each conversation becomes an idle workflow of the given agent (tagged with
+/// the source), keeping its title, dates and visible messages, so it can be
+/// searched and continued. Importing the same file again skips the
+/// conversations already present.
+///
+/// # Arguments
+/// * `path` - Full path of the `conversations.json` file
+/// * `options` - Source (detected if omitted), agent and memory creation
+///
+/// # Returns
+/// Import counts and per-conversation errors
+#[tauri::command]
+#[instrument(name = "import_chat_history", skip(state, options))]
+pub async fn import_chat_history(
+    path: String,
+    options: ChatHistoryImportOptions,
+    state: State<'_, AppState>,
+) -> Result<ChatHistoryImportResult, String> {
+    let agent_id = Validator::validate_agent_id(&options.agent_id)
+        .map_err(|e| format!("Invalid agent ID: {}", e))?;
+    if !record_exists(&state.db, "agent", &agent_id).await? {
+        return Err(format!("Agent '{}' not found", agent_id));
+    }
+
+    let path = PathBuf::from(&path);
+    let size = std::fs::metadata(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?
+        .len();
+    if size > MAX_CHAT_HISTORY_FILE_SIZE {
+        return Err(format!(
+            "File too large: {} bytes (max {} bytes)",
+            size, MAX_CHAT_HISTORY_FILE_SIZE
+        ));
+    }
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let export: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid JSON: {}", e))?;
+
+    let source = options
+        .source
+        .or_else(|| detect_source(&export))
+        .ok_or_else(|| {
+            "Unrecognized chat history export (expected conversations.json from ChatGPT or Claude)"
+                .to_string()
+        })?;
+    let conversations = parse_conversations(source, &export)?;
+
+    let embedding_service = state.embedding_service.read().await.clone();
+    let options = ChatHistoryImportOptions {
+        agent_id,
+        ..options
+    };
+    let result = import_chat_conversations(
+        &state.db,
+        embedding_service.as_ref(),
+        source,
+        conversations,
+        &options,
+    )
+    .await?;
+
+    tracing::info!(
+        source = source.label(),
+        workflows = result.workflows,
+        messages = result.messages,
+        memories = result.memories,
+        skipped = result.skipped,
+        errors = result.errors.len(),
+        "Chat history imported"
+    );
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    async fn setup_db(name: &str) -> (tempfile::TempDir, DBClient) {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join(name);
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+        (temp_dir, db)
+    }
+
+    async fn create(db: &DBClient, table: &str, id: &str, mut data: serde_json::Value) {
+        data["id"] = json!(id);
+        db.create(table, id, data).await.unwrap();
+    }
+
+    async fn seed_workspace(db: &DBClient) {
+        create(
+            db,
+            "workflow",
+            "wf1",
+            json!({ "name": "Release notes", "agent_id": "agent_a", "status": "idle" }),
+        )
+        .await;
+        create(
+            db,
+            "workflow",
+            "wf_secret",
+            json!({ "name": "Secret", "agent_id": "agent_a", "status": "idle", "sensitive": true }),
+        )
+        .await;
+        for (id, workflow_id) in [("msg1", "wf1"), ("msg2", "wf1"), ("msg3", "wf_secret")] {
+            create(
+                db,
+                "message",
+                id,
+                json!({ "workflow_id": workflow_id, "role": "user", "content": "Hello", "tokens": 3 }),
+            )
+            .await;
+        }
+        create(
+            db,
+            "tool_execution",
+            "exec1",
+            json!({
+                "workflow_id": "wf1",
+                "message_id": "msg2",
+                "agent_id": "agent_a",
+                "tool_type": "local",
+                "tool_name": "MemoryTool",
+                "input_params": "{}",
+                "success": true,
+                "duration_ms": 12,
+                "iteration": 1
+            }),
+        )
+        .await;
+        create(
+            db,
+            "thinking_step",
+            "step1",
+            json!({
+                "workflow_id": "wf1",
+                "message_id": "msg2",
+                "agent_id": "agent_a",
+                "step_number": 0,
+                "content": "Reading the changelog"
+            }),
+        )
+        .await;
+        create(
+            db,
+            "memory",
+            "mem_general",
+            json!({
+                "type": "user_pref",
+                "content": "Prefers short answers",
+                "metadata": {},
+                "embedding": vec![0.5; 1024],
+                "embedding_status": "ready"
+            }),
+        )
+        .await;
+        create(
+            db,
+            "memory",
+            "mem_secret",
+            json!({
+                "type": "context",
+                "content": "Secret context",
+                "workflow_id": "wf_secret",
+                "metadata": {}
+            }),
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_workspace_archive_round_trip() {
+        let (_source_dir, source) = setup_db("source_db").await;
+        seed_workspace(&source).await;
+
+        let archive = build_workspace_archive(&source, &WorkspaceExportOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(archive.manifest.skipped_sensitive, 1);
+        assert_eq!(
+            archive.manifest.counts,
+            WorkspaceCounts {
+                workflows: 1,
+                messages: 2,
+                tool_executions: 1,
+                thinking_steps: 1,
+                memories: 1,
+            }
+        );
+        assert!(archive.memories[0].get("embedding").is_none());
+        assert_eq!(archive.memories[0]["embedding_status"], "pending");
+
+        let content = serde_json::to_string(&archive).unwrap();
+        let archive: WorkspaceArchive = serde_json::from_str(&content).unwrap();
+        let original_timestamp = archive.messages[0]["timestamp"].clone();
+
+        let (_target_dir, target) = setup_db("target_db").await;
+        let result = import_workspace_archive(&target, archive.clone())
+            .await
+            .unwrap();
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.imported, archive.manifest.counts);
+        assert_eq!(result.warnings.len(), 1, "agent_a is missing");
+
+        let imported = target
+            .query_json_with_params(
+                "SELECT <string>timestamp AS timestamp FROM type::thing('message', $id)",
+                vec![("id".to_string(), archive.messages[0]["id"].clone())],
+            )
+            .await
+            .unwrap();
+        assert_eq!(imported[0]["timestamp"], original_timestamp);
+
+        // Records rejected by the schema are reported, not counted
+        let mut invalid = WorkspaceArchive {
+            workflows: vec![
+                json!({ "id": "wf_invalid", "name": "Invalid", "agent_id": "agent_a", "status": "archived" }),
+            ],
+            messages: vec![],
+            tool_executions: vec![],
+            thinking_steps: vec![],
+            memories: vec![],
+            manifest: archive.manifest.clone(),
+        };
+        invalid.manifest.counts = WorkspaceCounts::default();
+        let result = import_workspace_archive(&target, invalid).await.unwrap();
+        assert_eq!(result.imported.workflows, 0);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].entity_id, "wf_invalid");
+
+        // Importing again leaves everything in place
+        let result = import_workspace_archive(&target, archive).await.unwrap();
+        assert_eq!(result.imported, WorkspaceCounts::default());
+        assert_eq!(result.skipped.workflows, 1);
+        assert_eq!(result.skipped.messages, 2);
+        assert_eq!(result.skipped.memories, 1);
+    }
+
+    #[tokio::test]
+    async fn test_workspace_archive_selection_and_embeddings() {
+        let (_dir, db) = setup_db("selection_db").await;
+        seed_workspace(&db).await;
+
+        let options = WorkspaceExportOptions {
+            workflow_ids: Some(vec!["wf_secret".to_string()]),
+            include_embeddings: true,
+        };
+        let archive = build_workspace_archive(&db, &options).await.unwrap();
+        assert!(archive.workflows.is_empty());
+        assert!(archive.messages.is_empty());
+        assert_eq!(archive.manifest.skipped_sensitive, 1);
+        // General memories are always part of the workspace
+        assert_eq!(archive.memories.len(), 1);
+        assert_eq!(archive.memories[0]["embedding"], json!(vec![0.5; 1024]));
+        assert_eq!(archive.memories[0]["embedding_status"], "ready");
+    }
+
+    #[tokio::test]
+    async fn test_import_chat_conversations() {
+        let (_dir, db) = setup_db("chat_history_db").await;
+        let db = Arc::new(db);
+        let export = json!([
+            {
+                "uuid": "c-1",
+                "name": "Borrow checker",
+                "created_at": "2024-05-01T10:00:00Z",
+                "updated_at": "2024-05-01T10:05:00Z",
+                "chat_messages": [
+                    { "uuid": "m-1", "sender": "human", "text": "Explain borrowing", "created_at": "2024-05-01T10:00:01Z" },
+                    { "uuid": "m-2", "sender": "assistant", "text": "References borrow a value.", "created_at": "2024-05-01T10:00:09Z" }
+                ]
+            },
+            { "uuid": "c-2", "name": "Empty", "chat_messages": [] }
+        ]);
+        let source = detect_source(&export).unwrap();
+        let options = ChatHistoryImportOptions {
+            source: None,
+            agent_id: "agent_a".to_string(),
+            create_memories: true,
+        };
+
+        let conversations = parse_conversations(source, &export).unwrap();
+        let result = import_chat_conversations(&db, None, source, conversations, &options)
+            .await
+            .unwrap();
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            (
+                result.workflows,
+                result.messages,
+                result.memories,
+                result.skipped
+            ),
+            (1, 2, 1, 1)
+        );
+
+        let workflow_id = stable_import_id(source, "c-1");
+        let workflows = db
+            .query_json_with_params(
+                "SELECT name, tags, <string>created_at AS created_at FROM type::thing('workflow', $id)",
+                vec![("id".to_string(), json!(workflow_id))],
+            )
+            .await
+            .unwrap();
+        assert_eq!(workflows[0]["name"], "Borrow checker");
+        assert_eq!(workflows[0]["tags"], json!(["claude"]));
+        assert!(workflows[0]["created_at"]
+            .as_str()
+            .unwrap()
+            .starts_with("2024-05-01T10:00:00"));
+
+        let messages = db
+            .query_json_with_params(
+                "SELECT role, content, timestamp FROM message WHERE workflow_id = $workflow_id ORDER BY timestamp ASC",
+                vec![("workflow_id".to_string(), json!(workflow_id))],
+            )
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["role"], "user");
+
+        // Importing the same file again duplicates nothing
+        let conversations = parse_conversations(source, &export).unwrap();
+        let result = import_chat_conversations(&db, None, source, conversations, &options)
+            .await
+            .unwrap();
+        assert_eq!((result.workflows, result.skipped), (0, 2));
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("héllo", 3), "hé…");
+        assert_eq!(truncate_chars("héllo", 5), "héllo");
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Priority inbox command.
+//!
+//! Items are recorded and resolved by the subsystems concerned (see
+//! [`crate::inbox`]); this command only reads them.
+
+use crate::{inbox, models::pending_item::PendingItem, AppState};
+use chrono::Utc;
+use tauri::State;
+use tracing::{error, info, instrument};
+
+/// Lists everything awaiting the user, most urgent first.
+///
+/// # Returns
+/// Pending validations, agent questions, paused and interrupted runs, failed
+/// scheduled runs and expiring MCP authorizations, each with the link to the
+/// screen where it is handled
+#[tauri::command]
+#[instrument(name = "get_pending_items", skip(state))]
+pub async fn get_pending_items(state: State<'_, AppState>) -> Result<Vec<PendingItem>, String> {
+    let items = inbox::list_pending_items(&state.db, Utc::now())
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to list pending items");
+            format!("Failed to list pending items: {}", e)
+        })?;
+
+    info!(count = items.len(), "Pending items listed");
+    Ok(items)
+}
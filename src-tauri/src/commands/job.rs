@@ -0,0 +1,174 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Background job commands.
+//!
+//! `enqueue_workflow` stores the user message and returns immediately; the
+//! execution is done by the job queue of the application state (see
+//! [`crate::jobs`]) and its progress is polled with `get_job_status` or
+//! followed with the `job_status` event.
+
+use crate::{
+    db::DBClient,
+    jobs::JOB_SELECT_FIELDS,
+    models::{
+        job::{Job, JobCreate, JobStatus},
+        MessageCreate,
+    },
+    scheduler::save_message,
+    security::{Validator, WORKFLOW_VAULT},
+    tools::constants::jobs as jobs_const,
+    AppState,
+};
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Loads a job, failing if it does not exist.
+pub(crate) async fn load_job(db: &DBClient, job_id: &str) -> Result<Job, String> {
+    let query = format!(
+        "SELECT {} FROM job WHERE meta::id(id) = $id",
+        JOB_SELECT_FIELDS
+    );
+    let rows = db
+        .query_json_with_params(&query, vec![("id".to_string(), serde_json::json!(job_id))])
+        .await
+        .map_err(|e| format!("Failed to load job: {}", e))?;
+    rows.into_iter()
+        .next()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| format!("Failed to deserialize job: {}", e))?
+        .ok_or_else(|| "Job not found".to_string())
+}
+
+/// Enqueues the execution of a workflow with a message.
+///
+/// The message is stored as a user message of the workflow right away; the
+/// response is stored as an assistant message when the job is done, even if
+/// the app was restarted in between. Sensitive workflows cannot run in the
+/// background (their contents can only be written while they are open).
+///
+/// # Arguments
+/// * `workflow_id` - Workflow receiving the messages
+/// * `message` - User message
+/// * `agent_id` - Agent executing the message
+///
+/// # Returns
+/// The queued job
+#[tauri::command]
+#[instrument(
+    name = "enqueue_workflow",
+    skip(state, message),
+    fields(workflow_id = %workflow_id, agent_id = %agent_id, message_len = message.len())
+)]
+pub async fn enqueue_workflow(
+    workflow_id: String,
+    message: String,
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<Job, String> {
+    info!("Enqueuing workflow execution");
+    enqueue_job(&state, &workflow_id, &message, &agent_id).await
+}
+
+/// Validates and queues a job (see [`enqueue_workflow`]).
+pub(crate) async fn enqueue_job(
+    state: &AppState,
+    workflow_id: &str,
+    message: &str,
+    agent_id: &str,
+) -> Result<Job, String> {
+    let workflow_id = Validator::validate_uuid(workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+    let message = Validator::validate_message(message).map_err(|e| {
+        warn!(error = %e, "Invalid message");
+        format!("Invalid message: {}", e)
+    })?;
+    let agent_id = Validator::validate_agent_id(agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    let rows = state
+        .db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id FROM workflow WHERE meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?;
+    if rows.is_empty() {
+        return Err("Workflow not found".to_string());
+    }
+    if WORKFLOW_VAULT.is_sensitive(&state.db, &workflow_id).await? {
+        return Err("Sensitive workflows cannot run in the background".to_string());
+    }
+    if state.registry.get(&agent_id).await.is_none() {
+        return Err("Agent not found".to_string());
+    }
+
+    let queued = state
+        .db
+        .query_json("SELECT meta::id(id) AS id FROM job WHERE status = 'queued'")
+        .await
+        .map_err(|e| format!("Failed to count queued jobs: {}", e))?;
+    if queued.len() >= jobs_const::MAX_QUEUED_JOBS {
+        return Err(format!(
+            "Maximum number of queued jobs reached ({})",
+            jobs_const::MAX_QUEUED_JOBS
+        ));
+    }
+
+    save_message(
+        &state.db,
+        MessageCreate::user(workflow_id.clone(), message.clone()),
+    )
+    .await?;
+
+    let job_id = Uuid::new_v4().to_string();
+    let record = JobCreate {
+        workflow_id,
+        agent_id,
+        message,
+        status: JobStatus::Queued,
+    };
+    state.db.create("job", &job_id, record).await.map_err(|e| {
+        error!(error = %e, "Failed to create job");
+        format!("Failed to create job: {}", e)
+    })?;
+    state.job_queue.notify();
+
+    info!(job_id = %job_id, "Job queued");
+    load_job(&state.db, &job_id).await
+}
+
+/// Gets the state of a job.
+///
+/// # Arguments
+/// * `job_id` - Job ID
+///
+/// # Returns
+/// The job with its status, and its response message or error once finished
+#[tauri::command]
+#[instrument(name = "get_job_status", skip(state))]
+pub async fn get_job_status(job_id: String, state: State<'_, AppState>) -> Result<Job, String> {
+    let job_id = Validator::validate_uuid(&job_id).map_err(|e| {
+        warn!(error = %e, "Invalid job_id");
+        format!("Invalid job_id: {}", e)
+    })?;
+    load_job(&state.db, &job_id).await
+}
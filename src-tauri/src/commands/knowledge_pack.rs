@@ -0,0 +1,1133 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Knowledge pack commands.
+//!
+//! Knowledge packs bundle documents and curated memories; agents only
+//! retrieve chunks from the packs attached to them (see
+//! [`crate::agents::knowledge`]). Packs are the collections of the memory
+//! tool: its searches cover the attached packs plus the workflow memory. Documents are split into chunks, embedded
+//! when an embedding service is configured, and stored in the
+//! `knowledge_chunk` table. PDF, Markdown and text files are imported through
+//! the document extraction shared with the PdfTool ([`crate::documents`]).
+
+use crate::{
+    agents::knowledge::{attached_packs, chunk_document, retrieve, PACK_SELECT_FIELDS},
+    db::DBClient,
+    documents,
+    llm::embedding::EmbeddingService,
+    models::knowledge_pack::{
+        KnowledgeChunk, KnowledgeChunkCreate, KnowledgeDocument, KnowledgePack, KnowledgePackCreate,
+    },
+    security::Validator,
+    tools::constants::knowledge as knowledge_const,
+    AppState,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Chunks embedded per embedding request
+const EMBEDDING_BATCH_SIZE: usize = 16;
+
+/// Validates a pack name (trimmed, non-empty, bounded).
+fn validate_pack_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Knowledge pack name cannot be empty".to_string());
+    }
+    if trimmed.chars().count() > knowledge_const::MAX_PACK_NAME_LEN {
+        return Err(format!(
+            "Knowledge pack name exceeds {} characters",
+            knowledge_const::MAX_PACK_NAME_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a pack description (trimmed, bounded).
+fn validate_description(description: &str) -> Result<String, String> {
+    let trimmed = description.trim();
+    if trimmed.chars().count() > knowledge_const::MAX_PACK_DESCRIPTION_LEN {
+        return Err(format!(
+            "Knowledge pack description exceeds {} characters",
+            knowledge_const::MAX_PACK_DESCRIPTION_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a document title (trimmed, non-empty, bounded).
+fn validate_document_title(title: &str) -> Result<String, String> {
+    let trimmed = title.trim();
+    if trimmed.is_empty() {
+        return Err("Document title cannot be empty".to_string());
+    }
+    if trimmed.chars().count() > knowledge_const::MAX_DOCUMENT_TITLE_LEN {
+        return Err(format!(
+            "Document title exceeds {} characters",
+            knowledge_const::MAX_DOCUMENT_TITLE_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a pack ID (UUID).
+fn validate_pack_id(pack_id: &str) -> Result<String, String> {
+    Validator::validate_uuid(pack_id).map_err(|e| {
+        warn!(error = %e, "Invalid pack_id");
+        format!("Invalid pack_id: {}", e)
+    })
+}
+
+/// Loads the documents of the given packs (all packs if None).
+async fn load_documents(
+    db: &DBClient,
+    pack_id: Option<&str>,
+) -> Result<Vec<(String, KnowledgeDocument)>, String> {
+    let (filter, params) = match pack_id {
+        Some(id) => (
+            "WHERE pack_id = $pack_id ",
+            vec![("pack_id".to_string(), serde_json::json!(id))],
+        ),
+        None => ("", Vec::new()),
+    };
+    let query = format!(
+        "SELECT pack_id, document, count() AS chunk_count, \
+         count(embedding IS NOT NONE) AS embedded_count \
+         FROM knowledge_chunk {}GROUP BY pack_id, document",
+        filter
+    );
+    let rows = db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| format!("Failed to load knowledge documents: {}", e))?;
+
+    let mut documents: Vec<(String, KnowledgeDocument)> = rows
+        .iter()
+        .filter_map(|row| {
+            Some((
+                row["pack_id"].as_str()?.to_string(),
+                KnowledgeDocument {
+                    title: row["document"].as_str()?.to_string(),
+                    chunk_count: row["chunk_count"].as_u64().unwrap_or(0) as usize,
+                    embedded_count: row["embedded_count"].as_u64().unwrap_or(0) as usize,
+                },
+            ))
+        })
+        .collect();
+    documents.sort_by(|a, b| a.1.title.cmp(&b.1.title));
+    Ok(documents)
+}
+
+/// Loads all packs with their documents, sorted by name.
+pub(crate) async fn load_packs(db: &DBClient) -> Result<Vec<KnowledgePack>, String> {
+    let query = format!(
+        "SELECT {} FROM knowledge_pack ORDER BY name",
+        PACK_SELECT_FIELDS
+    );
+    let rows = db
+        .query_json(&query)
+        .await
+        .map_err(|e| format!("Failed to load knowledge packs: {}", e))?;
+    let mut packs: Vec<KnowledgePack> = rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect();
+
+    for (pack_id, document) in load_documents(db, None).await? {
+        if let Some(pack) = packs.iter_mut().find(|pack| pack.id == pack_id) {
+            pack.documents.push(document);
+        }
+    }
+    Ok(packs)
+}
+
+/// Loads a pack with its documents.
+pub(crate) async fn load_pack(
+    db: &DBClient,
+    pack_id: &str,
+) -> Result<Option<KnowledgePack>, String> {
+    let query = format!(
+        "SELECT {} FROM knowledge_pack WHERE meta::id(id) = $id",
+        PACK_SELECT_FIELDS
+    );
+    let rows = db
+        .query_json_with_params(&query, vec![("id".to_string(), serde_json::json!(pack_id))])
+        .await
+        .map_err(|e| format!("Failed to load knowledge pack: {}", e))?;
+    let Some(mut pack) = rows
+        .into_iter()
+        .find_map(|row| serde_json::from_value::<KnowledgePack>(row).ok())
+    else {
+        return Ok(None);
+    };
+
+    pack.documents = load_documents(db, Some(pack_id))
+        .await?
+        .into_iter()
+        .map(|(_, document)| document)
+        .collect();
+    Ok(Some(pack))
+}
+
+/// Loads a pack, failing if it does not exist.
+async fn require_pack(db: &DBClient, pack_id: &str) -> Result<KnowledgePack, String> {
+    load_pack(db, pack_id)
+        .await?
+        .ok_or_else(|| "Knowledge pack not found".to_string())
+}
+
+/// Fails if another pack already uses the name.
+async fn ensure_name_available(
+    db: &DBClient,
+    name: &str,
+    pack_id: Option<&str>,
+) -> Result<(), String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id FROM knowledge_pack WHERE name = $name",
+            vec![("name".to_string(), serde_json::json!(name))],
+        )
+        .await
+        .map_err(|e| format!("Failed to check knowledge pack name: {}", e))?;
+    if rows.iter().any(|row| row["id"].as_str() != pack_id) {
+        return Err(format!("A knowledge pack named '{}' already exists", name));
+    }
+    Ok(())
+}
+
+/// Marks a pack as updated.
+async fn touch_pack(db: &DBClient, pack_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('knowledge_pack', $id) SET updated_at = time::now()",
+        vec![("id".to_string(), serde_json::json!(pack_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to update knowledge pack: {}", e))
+}
+
+/// Adds a document to a pack, replacing the document with the same title.
+///
+/// Chunks are embedded in batches; a failed batch is stored without
+/// embedding (ranked by keywords at retrieval).
+pub(crate) async fn store_document(
+    db: &DBClient,
+    embedding_service: Option<&Arc<EmbeddingService>>,
+    pack: &KnowledgePack,
+    title: &str,
+    content: &str,
+) -> Result<KnowledgeDocument, String> {
+    let chunks = chunk_document(content, knowledge_const::CHUNK_CHARS);
+    if chunks.is_empty() {
+        return Err("Document content cannot be empty".to_string());
+    }
+    let other_chunks: usize = pack
+        .documents
+        .iter()
+        .filter(|document| document.title != title)
+        .map(|document| document.chunk_count)
+        .sum();
+    if other_chunks + chunks.len() > knowledge_const::MAX_PACK_CHUNKS {
+        return Err(format!(
+            "Knowledge pack exceeds the maximum of {} chunks",
+            knowledge_const::MAX_PACK_CHUNKS
+        ));
+    }
+
+    delete_document_chunks(db, &pack.id, title).await?;
+
+    let mut embedded_count = 0;
+    for (batch_index, batch) in chunks.chunks(EMBEDDING_BATCH_SIZE).enumerate() {
+        let embeddings = match embedding_service {
+            Some(service) => {
+                let texts: Vec<&str> = batch.iter().map(String::as_str).collect();
+                match service.embed_batch(&texts).await {
+                    Ok(embeddings) if embeddings.len() == batch.len() => Some(embeddings),
+                    Ok(_) => None,
+                    Err(e) => {
+                        warn!(error = %e, "Knowledge chunk embedding failed, stored without embedding");
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        for (offset, chunk) in batch.iter().enumerate() {
+            let chunk_id = Uuid::new_v4().to_string();
+            let record = KnowledgeChunkCreate {
+                pack_id: pack.id.clone(),
+                document: title.to_string(),
+                position: batch_index * EMBEDDING_BATCH_SIZE + offset,
+                content: chunk.clone(),
+            };
+            db.create("knowledge_chunk", &chunk_id, record)
+                .await
+                .map_err(|e| format!("Failed to store knowledge chunk: {}", e))?;
+
+            if let Some(embedding) = embeddings.as_ref().map(|e| &e[offset]) {
+                db.execute_with_params(
+                    "UPDATE type::thing('knowledge_chunk', $id) SET embedding = $embedding",
+                    vec![
+                        ("id".to_string(), serde_json::json!(chunk_id)),
+                        ("embedding".to_string(), serde_json::json!(embedding)),
+                    ],
+                )
+                .await
+                .map_err(|e| format!("Failed to store knowledge chunk embedding: {}", e))?;
+                embedded_count += 1;
+            }
+        }
+    }
+    touch_pack(db, &pack.id).await?;
+
+    Ok(KnowledgeDocument {
+        title: title.to_string(),
+        chunk_count: chunks.len(),
+        embedded_count,
+    })
+}
+
+/// Deletes the chunks of a document.
+async fn delete_document_chunks(db: &DBClient, pack_id: &str, title: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "DELETE knowledge_chunk WHERE pack_id = $pack_id AND document = $document",
+        vec![
+            ("pack_id".to_string(), serde_json::json!(pack_id)),
+            ("document".to_string(), serde_json::json!(title)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete knowledge document: {}", e))
+}
+
+/// Lists the knowledge packs with their documents.
+///
+/// # Returns
+/// Packs sorted by name
+#[tauri::command]
+#[instrument(name = "list_knowledge_packs", skip(state))]
+pub async fn list_knowledge_packs(
+    state: State<'_, AppState>,
+) -> Result<Vec<KnowledgePack>, String> {
+    info!("Listing knowledge packs");
+
+    let packs = load_packs(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to list knowledge packs");
+        e
+    })?;
+
+    info!(count = packs.len(), "Knowledge packs loaded");
+    Ok(packs)
+}
+
+/// Creates an empty knowledge pack.
+///
+/// # Arguments
+/// * `name` - Pack name (unique)
+/// * `description` - Description of the pack content
+///
+/// # Returns
+/// The created pack
+#[tauri::command]
+#[instrument(name = "create_knowledge_pack", skip(state, description))]
+pub async fn create_knowledge_pack(
+    name: String,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<KnowledgePack, String> {
+    info!("Creating knowledge pack");
+
+    let name = validate_pack_name(&name)?;
+    let description = validate_description(description.as_deref().unwrap_or(""))?;
+    ensure_name_available(&state.db, &name, None).await?;
+
+    let pack_id = Uuid::new_v4().to_string();
+    let record = KnowledgePackCreate {
+        name,
+        description,
+        agent_ids: Vec::new(),
+        memory_ids: Vec::new(),
+    };
+    state
+        .db
+        .create("knowledge_pack", &pack_id, record)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create knowledge pack");
+            format!("Failed to create knowledge pack: {}", e)
+        })?;
+
+    info!(pack_id = %pack_id, "Knowledge pack created");
+    require_pack(&state.db, &pack_id).await
+}
+
+/// Renames a knowledge pack or changes its description.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `name` - New name (unchanged if omitted)
+/// * `description` - New description (unchanged if omitted)
+///
+/// # Returns
+/// The updated pack
+#[tauri::command]
+#[instrument(name = "update_knowledge_pack", skip(state, description))]
+pub async fn update_knowledge_pack(
+    pack_id: String,
+    name: Option<String>,
+    description: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<KnowledgePack, String> {
+    info!("Updating knowledge pack");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    let pack = require_pack(&state.db, &pack_id).await?;
+    let name = match name {
+        Some(name) => validate_pack_name(&name)?,
+        None => pack.name,
+    };
+    let description = match description {
+        Some(description) => validate_description(&description)?,
+        None => pack.description,
+    };
+    ensure_name_available(&state.db, &name, Some(&pack_id)).await?;
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('knowledge_pack', $id) SET name = $name, \
+             description = $description, updated_at = time::now()",
+            vec![
+                ("id".to_string(), serde_json::json!(pack_id)),
+                ("name".to_string(), serde_json::json!(name)),
+                ("description".to_string(), serde_json::json!(description)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update knowledge pack");
+            format!("Failed to update knowledge pack: {}", e)
+        })?;
+
+    info!(pack_id = %pack_id, "Knowledge pack updated");
+    require_pack(&state.db, &pack_id).await
+}
+
+/// Deletes a knowledge pack and its documents (curated memories are kept).
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+#[tauri::command]
+#[instrument(name = "delete_knowledge_pack", skip(state))]
+pub async fn delete_knowledge_pack(
+    pack_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!("Deleting knowledge pack");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    require_pack(&state.db, &pack_id).await?;
+
+    state
+        .db
+        .execute_with_params(
+            "DELETE knowledge_chunk WHERE pack_id = $pack_id; \
+             DELETE type::thing('knowledge_pack', $pack_id)",
+            vec![("pack_id".to_string(), serde_json::json!(pack_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete knowledge pack");
+            format!("Failed to delete knowledge pack: {}", e)
+        })?;
+
+    info!(pack_id = %pack_id, "Knowledge pack deleted");
+    Ok(())
+}
+
+/// Adds a document to a knowledge pack.
+///
+/// The document is split into chunks, embedded when an embedding service is
+/// configured. A document with the same title is replaced.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `title` - Document title (unique within the pack)
+/// * `content` - Document text
+///
+/// # Returns
+/// The stored document (chunk counts)
+#[tauri::command]
+#[instrument(
+    name = "add_knowledge_document",
+    skip(state, content),
+    fields(content_len = content.len())
+)]
+pub async fn add_knowledge_document(
+    pack_id: String,
+    title: String,
+    content: String,
+    state: State<'_, AppState>,
+) -> Result<KnowledgeDocument, String> {
+    info!("Adding knowledge document");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    let title = validate_document_title(&title)?;
+    if content.chars().count() > knowledge_const::MAX_DOCUMENT_LEN {
+        return Err(format!(
+            "Document exceeds the maximum length of {} characters",
+            knowledge_const::MAX_DOCUMENT_LEN
+        ));
+    }
+    let pack = require_pack(&state.db, &pack_id).await?;
+    let embedding_service = state.get_embedding_service().await;
+
+    let document = store_document(
+        &state.db,
+        embedding_service.as_ref(),
+        &pack,
+        &title,
+        &content,
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to add knowledge document");
+        e
+    })?;
+
+    info!(
+        pack_id = %pack_id,
+        chunks = document.chunk_count,
+        embedded = document.embedded_count,
+        "Knowledge document added"
+    );
+    Ok(document)
+}
+
+/// Imports a PDF, Markdown or text file into a knowledge pack.
+///
+/// The file is read by the document extraction shared with the PdfTool; the
+/// title defaults to the PDF title or the file name.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `path` - Absolute path of the file
+/// * `title` - Document title (optional)
+///
+/// # Returns
+/// The stored document (chunk counts)
+#[tauri::command]
+#[instrument(name = "import_knowledge_document", skip(state))]
+pub async fn import_knowledge_document(
+    pack_id: String,
+    path: String,
+    title: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<KnowledgeDocument, String> {
+    info!("Importing knowledge document");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    let file_path = PathBuf::from(path.trim());
+    if !file_path.is_absolute() {
+        return Err("Document path must be absolute".to_string());
+    }
+    let extracted = tokio::task::spawn_blocking(move || documents::extract_file(&file_path))
+        .await
+        .map_err(|e| format!("Document extraction failed: {}", e))?
+        .map_err(|e| {
+            warn!(error = %e, "Failed to extract knowledge document");
+            e
+        })?;
+
+    let title = match title.filter(|title| !title.trim().is_empty()) {
+        Some(title) => validate_document_title(&title)?,
+        None => {
+            let title: String = extracted
+                .title
+                .chars()
+                .take(knowledge_const::MAX_DOCUMENT_TITLE_LEN)
+                .collect();
+            validate_document_title(&title)?
+        }
+    };
+    let content = extracted.text();
+    if content.chars().count() > knowledge_const::MAX_DOCUMENT_LEN {
+        return Err(format!(
+            "Document exceeds the maximum length of {} characters",
+            knowledge_const::MAX_DOCUMENT_LEN
+        ));
+    }
+    if content.trim().is_empty() {
+        return Err("No text found in the document (scanned PDF?)".to_string());
+    }
+    let pack = require_pack(&state.db, &pack_id).await?;
+    let embedding_service = state.get_embedding_service().await;
+
+    let document = store_document(
+        &state.db,
+        embedding_service.as_ref(),
+        &pack,
+        &title,
+        &content,
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to import knowledge document");
+        e
+    })?;
+
+    info!(
+        pack_id = %pack_id,
+        pages = extracted.pages.len(),
+        chunks = document.chunk_count,
+        embedded = document.embedded_count,
+        "Knowledge document imported"
+    );
+    Ok(document)
+}
+
+/// Removes a document from a knowledge pack.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `title` - Document title
+#[tauri::command]
+#[instrument(name = "remove_knowledge_document", skip(state))]
+pub async fn remove_knowledge_document(
+    pack_id: String,
+    title: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!("Removing knowledge document");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    let pack = require_pack(&state.db, &pack_id).await?;
+    if !pack
+        .documents
+        .iter()
+        .any(|document| document.title == title)
+    {
+        return Err("Document not found".to_string());
+    }
+
+    delete_document_chunks(&state.db, &pack_id, &title).await?;
+    touch_pack(&state.db, &pack_id).await?;
+
+    info!(pack_id = %pack_id, "Knowledge document removed");
+    Ok(())
+}
+
+/// Sets the memories curated into a knowledge pack.
+///
+/// Memories of sensitive workflows (encrypted) cannot be added.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `memory_ids` - IDs of the memories (replaces the current list)
+///
+/// # Returns
+/// The updated pack
+#[tauri::command]
+#[instrument(name = "set_knowledge_pack_memories", skip(state, memory_ids), fields(count = memory_ids.len()))]
+pub async fn set_knowledge_pack_memories(
+    pack_id: String,
+    memory_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<KnowledgePack, String> {
+    info!("Setting knowledge pack memories");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    if memory_ids.len() > knowledge_const::MAX_PACK_MEMORIES {
+        return Err(format!(
+            "Knowledge pack exceeds the maximum of {} memories",
+            knowledge_const::MAX_PACK_MEMORIES
+        ));
+    }
+    let mut validated_ids: Vec<String> = Vec::with_capacity(memory_ids.len());
+    for memory_id in &memory_ids {
+        let id =
+            Validator::validate_uuid(memory_id).map_err(|e| format!("Invalid memory_id: {}", e))?;
+        if !validated_ids.contains(&id) {
+            validated_ids.push(id);
+        }
+    }
+    require_pack(&state.db, &pack_id).await?;
+
+    let rows = state
+        .db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id, content FROM memory WHERE meta::id(id) IN $memory_ids",
+            vec![("memory_ids".to_string(), serde_json::json!(validated_ids))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load memories: {}", e))?;
+    for memory_id in &validated_ids {
+        let row = rows
+            .iter()
+            .find(|row| row["id"].as_str() == Some(memory_id.as_str()))
+            .ok_or_else(|| format!("Memory not found: {}", memory_id))?;
+        if row["content"]
+            .as_str()
+            .is_some_and(crate::security::workflow_vault::is_encrypted)
+        {
+            return Err(format!(
+                "Memory {} belongs to a sensitive workflow and cannot be added to a knowledge pack",
+                memory_id
+            ));
+        }
+    }
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('knowledge_pack', $id) SET memory_ids = $memory_ids, \
+             updated_at = time::now()",
+            vec![
+                ("id".to_string(), serde_json::json!(pack_id)),
+                ("memory_ids".to_string(), serde_json::json!(validated_ids)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to set knowledge pack memories");
+            format!("Failed to update knowledge pack: {}", e)
+        })?;
+
+    info!(pack_id = %pack_id, count = validated_ids.len(), "Knowledge pack memories set");
+    require_pack(&state.db, &pack_id).await
+}
+
+/// Attaches a knowledge pack to an agent.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `agent_id` - Agent ID
+///
+/// # Returns
+/// The updated pack
+#[tauri::command]
+#[instrument(name = "attach_knowledge_pack", skip(state))]
+pub async fn attach_knowledge_pack(
+    pack_id: String,
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<KnowledgePack, String> {
+    info!("Attaching knowledge pack");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    let agent_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+    if state.registry.get(&agent_id).await.is_none() {
+        return Err("Agent not found".to_string());
+    }
+    require_pack(&state.db, &pack_id).await?;
+
+    set_attachment(&state.db, &pack_id, &agent_id, true).await?;
+
+    info!(pack_id = %pack_id, agent_id = %agent_id, "Knowledge pack attached");
+    require_pack(&state.db, &pack_id).await
+}
+
+/// Detaches a knowledge pack from an agent.
+///
+/// # Arguments
+/// * `pack_id` - Pack ID
+/// * `agent_id` - Agent ID
+///
+/// # Returns
+/// The updated pack
+#[tauri::command]
+#[instrument(name = "detach_knowledge_pack", skip(state))]
+pub async fn detach_knowledge_pack(
+    pack_id: String,
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<KnowledgePack, String> {
+    info!("Detaching knowledge pack");
+
+    let pack_id = validate_pack_id(&pack_id)?;
+    let agent_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+    require_pack(&state.db, &pack_id).await?;
+
+    set_attachment(&state.db, &pack_id, &agent_id, false).await?;
+
+    info!(pack_id = %pack_id, agent_id = %agent_id, "Knowledge pack detached");
+    require_pack(&state.db, &pack_id).await
+}
+
+/// Lists the knowledge packs attached to an agent.
+///
+/// # Arguments
+/// * `agent_id` - Agent ID
+///
+/// # Returns
+/// Attached packs with their documents, sorted by name
+#[tauri::command]
+#[instrument(name = "list_agent_knowledge_packs", skip(state))]
+pub async fn list_agent_knowledge_packs(
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<KnowledgePack>, String> {
+    let agent_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+
+    load_agent_packs(&state.db, &agent_id).await
+}
+
+/// Replaces the knowledge packs attached to an agent.
+///
+/// Packs missing from `pack_ids` are detached from the agent.
+///
+/// # Arguments
+/// * `agent_id` - Agent ID
+/// * `pack_ids` - IDs of the packs to attach
+///
+/// # Returns
+/// The attached packs, sorted by name
+#[tauri::command]
+#[instrument(name = "set_agent_knowledge_packs", skip(state))]
+pub async fn set_agent_knowledge_packs(
+    agent_id: String,
+    pack_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<KnowledgePack>, String> {
+    info!("Setting agent knowledge packs");
+
+    let agent_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+    if state.registry.get(&agent_id).await.is_none() {
+        return Err("Agent not found".to_string());
+    }
+    let mut validated = Vec::with_capacity(pack_ids.len());
+    for pack_id in &pack_ids {
+        let pack_id = validate_pack_id(pack_id)?;
+        require_pack(&state.db, &pack_id).await?;
+        if !validated.contains(&pack_id) {
+            validated.push(pack_id);
+        }
+    }
+
+    set_agent_packs(&state.db, &agent_id, &validated).await?;
+
+    info!(agent_id = %agent_id, count = validated.len(), "Agent knowledge packs set");
+    load_agent_packs(&state.db, &agent_id).await
+}
+
+/// Loads the packs attached to an agent, with their documents.
+async fn load_agent_packs(db: &DBClient, agent_id: &str) -> Result<Vec<KnowledgePack>, String> {
+    let packs = load_packs(db).await?;
+    Ok(packs
+        .into_iter()
+        .filter(|pack| pack.agent_ids.iter().any(|id| id == agent_id))
+        .collect())
+}
+
+/// Attaches exactly the given packs to an agent.
+async fn set_agent_packs(db: &DBClient, agent_id: &str, pack_ids: &[String]) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE knowledge_pack SET agent_ids -= $agent_id, updated_at = time::now() \
+         WHERE agent_ids CONTAINS $agent_id AND meta::id(id) NOTINSIDE $pack_ids;\n\
+         UPDATE knowledge_pack SET agent_ids = array::union(agent_ids, [$agent_id]), \
+         updated_at = time::now() \
+         WHERE meta::id(id) INSIDE $pack_ids AND agent_ids CONTAINSNOT $agent_id;",
+        vec![
+            ("agent_id".to_string(), serde_json::json!(agent_id)),
+            ("pack_ids".to_string(), serde_json::json!(pack_ids)),
+        ],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to update agent knowledge packs");
+        format!("Failed to update agent knowledge packs: {}", e)
+    })
+}
+
+/// Adds (or removes) an agent to the agents of a pack.
+async fn set_attachment(
+    db: &DBClient,
+    pack_id: &str,
+    agent_id: &str,
+    attached: bool,
+) -> Result<(), String> {
+    let operation = if attached {
+        "agent_ids = array::union(agent_ids, [$agent_id])"
+    } else {
+        "agent_ids -= $agent_id"
+    };
+    db.execute_with_params(
+        &format!(
+            "UPDATE type::thing('knowledge_pack', $id) SET {}, updated_at = time::now()",
+            operation
+        ),
+        vec![
+            ("id".to_string(), serde_json::json!(pack_id)),
+            ("agent_id".to_string(), serde_json::json!(agent_id)),
+        ],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to update knowledge pack agents");
+        format!("Failed to update knowledge pack: {}", e)
+    })
+}
+
+/// Previews the chunks an agent retrieves from its attached packs for a query.
+///
+/// # Arguments
+/// * `agent_id` - Agent ID
+/// * `query` - Query text (e.g. a user message)
+/// * `limit` - Maximum number of chunks (defaults to the execution limit)
+///
+/// # Returns
+/// Retrieved chunks, most relevant first
+#[tauri::command]
+#[instrument(name = "search_agent_knowledge", skip(state, query))]
+pub async fn search_agent_knowledge(
+    agent_id: String,
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<KnowledgeChunk>, String> {
+    info!("Searching agent knowledge");
+
+    let agent_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+    if query.trim().is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+    let limit = limit
+        .unwrap_or(knowledge_const::TOP_K)
+        .min(crate::tools::constants::memory::MAX_LIMIT);
+
+    let packs = attached_packs(&state.db, &agent_id).await?;
+    let embedding_service = state.get_embedding_service().await;
+    let chunks = retrieve(&state.db, embedding_service.as_ref(), &packs, &query, limit)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Knowledge search failed");
+            e
+        })?;
+
+    info!(count = chunks.len(), "Agent knowledge searched");
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::knowledge::{attached_packs, detach_agent};
+    use crate::llm::embedding::EmbeddingProvider;
+    use crate::mcp::test_server::{json_reply, spawn_mock_server};
+    use tempfile::tempdir;
+
+    async fn setup_db() -> (DBClient, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("knowledge_pack_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        (db, temp_dir)
+    }
+
+    async fn create_pack(db: &DBClient, name: &str) -> KnowledgePack {
+        let pack_id = Uuid::new_v4().to_string();
+        let record = KnowledgePackCreate {
+            name: name.to_string(),
+            description: String::new(),
+            agent_ids: Vec::new(),
+            memory_ids: Vec::new(),
+        };
+        db.create("knowledge_pack", &pack_id, record).await.unwrap();
+        load_pack(db, &pack_id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_documents_stored_as_chunks() {
+        let (db, _dir) = setup_db().await;
+        let pack = create_pack(&db, "Handbook").await;
+        assert!(ensure_name_available(&db, "Handbook", None).await.is_err());
+        assert!(ensure_name_available(&db, "Handbook", Some(&pack.id))
+            .await
+            .is_ok());
+
+        let content = "Paragraph one.\n\n".repeat(200);
+        let document = store_document(&db, None, &pack, "Onboarding", &content)
+            .await
+            .unwrap();
+        assert!(document.chunk_count > 1);
+        assert_eq!(document.embedded_count, 0);
+
+        // Same title replaces the document
+        let pack = load_pack(&db, &pack.id).await.unwrap().unwrap();
+        let replaced = store_document(&db, None, &pack, "Onboarding", "Short text.")
+            .await
+            .unwrap();
+        assert_eq!(replaced.chunk_count, 1);
+        store_document(&db, None, &pack, "Security", "Rotate keys.")
+            .await
+            .unwrap();
+
+        let packs = load_packs(&db).await.unwrap();
+        assert_eq!(packs.len(), 1);
+        let titles: Vec<&str> = packs[0]
+            .documents
+            .iter()
+            .map(|d| d.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Onboarding", "Security"]);
+        assert!(packs[0].documents.iter().all(|d| d.chunk_count == 1));
+    }
+
+    #[tokio::test]
+    async fn test_set_agent_packs_replaces_attachments() {
+        let (db, _dir) = setup_db().await;
+        let first = create_pack(&db, "First").await;
+        let second = create_pack(&db, "Second").await;
+        set_attachment(&db, &first.id, "agent-1", true)
+            .await
+            .unwrap();
+        set_attachment(&db, &first.id, "agent-2", true)
+            .await
+            .unwrap();
+
+        set_agent_packs(&db, "agent-1", std::slice::from_ref(&second.id))
+            .await
+            .unwrap();
+        let names: Vec<String> = attached_packs(&db, "agent-1")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|pack| pack.name)
+            .collect();
+        assert_eq!(names, vec!["Second"]);
+        // Other agents keep their attachments
+        assert_eq!(attached_packs(&db, "agent-2").await.unwrap().len(), 1);
+
+        set_agent_packs(&db, "agent-1", &[]).await.unwrap();
+        assert!(attached_packs(&db, "agent-1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retrieval_limited_to_attached_packs() {
+        let (db, _dir) = setup_db().await;
+        let attached = create_pack(&db, "Attached").await;
+        let other = create_pack(&db, "Other").await;
+        store_document(
+            &db,
+            None,
+            &attached,
+            "Keys",
+            "Rotate the API keys every month.",
+        )
+        .await
+        .unwrap();
+        store_document(&db, None, &other, "Keys", "Never rotate the API keys.")
+            .await
+            .unwrap();
+
+        set_attachment(&db, &attached.id, "agent-1", true)
+            .await
+            .unwrap();
+        set_attachment(&db, &attached.id, "agent-1", true)
+            .await
+            .unwrap();
+        let packs = attached_packs(&db, "agent-1").await.unwrap();
+        assert_eq!(packs.len(), 1);
+        assert_eq!(packs[0].agent_ids, vec!["agent-1".to_string()]);
+
+        // Keyword ranking without embedding service
+        let chunks = retrieve(&db, None, &packs, "how to rotate API keys", 5)
+            .await
+            .unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].pack_id, attached.id);
+        assert_eq!(chunks[0].document.as_deref(), Some("Keys"));
+
+        detach_agent(&db, "agent-1").await.unwrap();
+        assert!(attached_packs(&db, "agent-1").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_retrieval_with_embeddings_and_memories() {
+        let (db, _dir) = setup_db().await;
+        let base = spawn_mock_server(|_| {
+            json_reply(&[], serde_json::json!({"embedding": vec![0.1_f32; 1024]}))
+        })
+        .await;
+        let service = Arc::new(EmbeddingService::with_provider(
+            EmbeddingProvider::ollama_with_config(&base, "mxbai-embed-large"),
+        ));
+
+        let pack = create_pack(&db, "Embedded").await;
+        let document = store_document(&db, Some(&service), &pack, "Doc", "Some content.")
+            .await
+            .unwrap();
+        assert_eq!(document.embedded_count, 1);
+
+        let memory_id = Uuid::new_v4().to_string();
+        db.create(
+            "memory",
+            &memory_id,
+            crate::models::MemoryCreate::new(
+                crate::models::MemoryType::Knowledge,
+                "Curated fact.".to_string(),
+                serde_json::json!({}),
+            ),
+        )
+        .await
+        .unwrap();
+        db.execute_with_params(
+            "UPDATE type::thing('knowledge_pack', $id) SET memory_ids = [$memory_id]",
+            vec![
+                ("id".to_string(), serde_json::json!(pack.id)),
+                ("memory_id".to_string(), serde_json::json!(memory_id)),
+            ],
+        )
+        .await
+        .unwrap();
+        let pack = load_pack(&db, &pack.id).await.unwrap().unwrap();
+
+        // The unembedded memory is matched by keywords after the embedded chunk
+        let chunks = retrieve(
+            &db,
+            Some(&service),
+            std::slice::from_ref(&pack),
+            "curated fact",
+            5,
+        )
+        .await
+        .unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].document.as_deref(), Some("Doc"));
+        assert!(chunks[0].score > 0.99);
+        assert_eq!(chunks[1].document, None);
+        assert_eq!(chunks[1].content, "Curated fact.");
+    }
+}
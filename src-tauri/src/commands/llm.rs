@@ -13,12 +13,32 @@
 // limitations under the License.
 
 //! LLM configuration and execution commands
-
-use crate::llm::ProviderType;
+//!
+//! The retry policy applied to transient LLM failures is stored in the
+//! `settings:llm_retry` record and restored at startup, as are the Gemini
+//! safety settings (`settings:gemini_safety`) and the utility model of
+//! internal summarization passes (`settings:utility_model`).
+
+use crate::commands::models::{validate_model_id, validate_provider_string};
+use crate::db::{queries, DBClient};
+use crate::llm::gemini::GeminiSafetySetting;
+use crate::llm::local_status;
+use crate::llm::utility::UtilityModelConfig;
+use crate::llm::{ProviderType, RetryConfig};
+use crate::models::local_llm::{LocalLlmStatus, ModelFitCheck};
 use crate::state::AppState;
+use crate::tools::constants::commands as cmd_const;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use tauri::State;
-use tracing::{info, instrument};
+use tracing::{debug, error, info, instrument, warn};
+
+/// Storage key of the LLM retry policy
+const LLM_RETRY_SETTINGS_KEY: &str = "settings:llm_retry";
+/// Storage key of the Gemini safety settings
+const GEMINI_SAFETY_SETTINGS_KEY: &str = "settings:gemini_safety";
+/// Storage key of the utility model
+const UTILITY_MODEL_SETTINGS_KEY: &str = "settings:utility_model";
 
 /// LLM provider status response
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +62,8 @@ pub struct LLMConfigResponse {
     pub mistral: ProviderStatus,
     /// Ollama configuration status
     pub ollama: ProviderStatus,
+    /// Gemini configuration status
+    pub gemini: ProviderStatus,
     /// Ollama server URL
     pub ollama_url: String,
 }
@@ -72,10 +94,20 @@ pub async fn get_llm_config(state: State<'_, AppState>) -> Result<LLMConfigRespo
         available_models: state.llm_manager.get_available_models(ProviderType::Ollama),
     };
 
+    let gemini_status = ProviderStatus {
+        provider: "Gemini".to_string(),
+        configured: state
+            .llm_manager
+            .is_provider_configured(ProviderType::Gemini),
+        default_model: config.gemini_model.clone(),
+        available_models: state.llm_manager.get_available_models(ProviderType::Gemini),
+    };
+
     Ok(LLMConfigResponse {
         active_provider: config.active_provider.to_string(),
         mistral: mistral_status,
         ollama: ollama_status,
+        gemini: gemini_status,
         ollama_url: config.ollama_url,
     })
 }
@@ -99,6 +131,24 @@ pub async fn configure_mistral(api_key: String, state: State<'_, AppState>) -> R
     Ok(())
 }
 
+/// Configures the Gemini provider with an API key
+#[tauri::command]
+#[instrument(name = "configure_gemini", skip(state, api_key))]
+pub async fn configure_gemini(api_key: String, state: State<'_, AppState>) -> Result<(), String> {
+    if api_key.is_empty() {
+        return Err("API key cannot be empty".to_string());
+    }
+
+    state
+        .llm_manager
+        .configure_gemini(&api_key)
+        .await
+        .map_err(|e| format!("Failed to configure Gemini: {}", e))?;
+
+    info!("Gemini provider configured successfully");
+    Ok(())
+}
+
 /// Configures the Ollama provider
 #[tauri::command]
 #[instrument(name = "configure_ollama", skip(state))]
@@ -123,9 +173,12 @@ pub async fn set_active_provider(
     provider: String,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
-    let provider_type: ProviderType = provider
-        .parse()
-        .map_err(|_| format!("Invalid provider: {}. Use 'Mistral' or 'Ollama'", provider))?;
+    let provider_type: ProviderType = provider.parse().map_err(|_| {
+        format!(
+            "Invalid provider: {}. Use 'Mistral', 'Ollama' or 'Gemini'",
+            provider
+        )
+    })?;
 
     state
         .llm_manager
@@ -194,6 +247,61 @@ pub async fn test_ollama_connection(state: State<'_, AppState>) -> Result<bool,
         .map_err(|e| format!("Connection test failed: {}", e))
 }
 
+/// Gets the loaded Ollama models, their VRAM usage and the host memory.
+///
+/// An unreachable server is reported in the status instead of failing, so
+/// the settings screen still shows the host memory.
+#[tauri::command]
+#[instrument(name = "get_local_llm_status", skip(state))]
+pub async fn get_local_llm_status(state: State<'_, AppState>) -> Result<LocalLlmStatus, String> {
+    let ollama = state.llm_manager.ollama();
+    let (loaded, system_memory) =
+        tokio::join!(ollama.loaded_models(), local_status::read_system_memory());
+
+    let (loaded_models, error) = match loaded {
+        Ok(models) => (models, None),
+        Err(e) => {
+            debug!(error = %e, "Ollama status unavailable");
+            (Vec::new(), Some(e.to_string()))
+        }
+    };
+    Ok(LocalLlmStatus {
+        server_url: ollama.get_server_url().await,
+        reachable: error.is_none(),
+        vram_used_bytes: loaded_models.iter().map(|m| m.vram_bytes).sum(),
+        loaded_models,
+        system_memory,
+        error,
+    })
+}
+
+/// Checks whether an Ollama model fits in the available memory.
+///
+/// Called before selecting a local model; the result carries the warning to
+/// show when the model is too large or not installed.
+#[tauri::command]
+#[instrument(name = "check_local_model_fit", skip(state))]
+pub async fn check_local_model_fit(
+    model: String,
+    state: State<'_, AppState>,
+) -> Result<ModelFitCheck, String> {
+    validate_model_id(&model)?;
+    let ollama = state.llm_manager.ollama();
+    let (size, loaded, system_memory) = tokio::join!(
+        ollama.model_size(&model),
+        ollama.loaded_models(),
+        local_status::read_system_memory()
+    );
+    let size = size.map_err(|e| format!("Failed to read Ollama models: {}", e))?;
+    let loaded = loaded.map_err(|e| format!("Failed to read loaded models: {}", e))?;
+
+    let check = ModelFitCheck::evaluate(&model, size, &loaded, system_memory);
+    if let Some(warning) = &check.warning {
+        warn!(model = %model, warning = %warning, "Local model may not fit");
+    }
+    Ok(check)
+}
+
 /// Tests the Mistral connection by querying the models endpoint
 #[tauri::command]
 #[instrument(name = "test_mistral_connection", skip(state))]
@@ -205,7 +313,7 @@ pub async fn test_mistral_connection(state: State<'_, AppState>) -> Result<bool,
     };
 
     // Test by calling the models endpoint
-    let client = reqwest::Client::builder()
+    let client = crate::proxy::client_builder("mistral")
         .timeout(std::time::Duration::from_secs(10))
         .build()
         .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -254,6 +362,7 @@ pub async fn test_llm_completion(
                 model.as_deref(),
                 0.7,
                 1000,
+                None,
             )
             .await
     } else {
@@ -274,10 +383,346 @@ pub async fn test_llm_completion(
         .map_err(|e| format!("LLM completion failed: {}", e))
 }
 
+/// Loads the LLM retry policy from the database, falling back to defaults.
+pub async fn load_llm_retry_settings(db: &DBClient) -> RetryConfig {
+    match queries::settings::load_config(db, LLM_RETRY_SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No LLM retry settings found, using defaults");
+            RetryConfig::default()
+        }
+    }
+}
+
+/// Gets the retry policy applied to transient LLM failures
+#[tauri::command]
+#[instrument(name = "get_llm_retry_settings", skip(state))]
+pub async fn get_llm_retry_settings(state: State<'_, AppState>) -> Result<RetryConfig, String> {
+    Ok(state.llm_manager.get_retry_config().await)
+}
+
+/// Updates the retry policy applied to transient LLM failures.
+///
+/// # Arguments
+/// * `settings` - The complete retry policy to store
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_llm_retry_settings", skip(state, settings))]
+pub async fn update_llm_retry_settings(
+    settings: RetryConfig,
+    state: State<'_, AppState>,
+) -> Result<RetryConfig, String> {
+    info!("Updating LLM retry settings");
+
+    validate_llm_retry_settings(&settings).map_err(|e| {
+        warn!(error = %e, "Invalid LLM retry settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize LLM retry settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, LLM_RETRY_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save LLM retry settings");
+            format!("Failed to save LLM retry settings: {}", e)
+        })?;
+
+    state.llm_manager.set_retry_config(settings.clone()).await;
+    info!("LLM retry settings updated successfully");
+    Ok(settings)
+}
+
+/// Loads the Gemini safety settings from the database, empty when unset.
+pub async fn load_gemini_safety_settings(db: &DBClient) -> Vec<GeminiSafetySetting> {
+    let results = match db
+        .query_json_with_params(
+            "SELECT settings FROM type::thing('settings', $key)",
+            vec![("key".to_string(), json!(GEMINI_SAFETY_SETTINGS_KEY))],
+        )
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            debug!(error = %e, "No Gemini safety settings found, using API defaults");
+            return Vec::new();
+        }
+    };
+
+    results
+        .first()
+        .and_then(|row| row.get("settings"))
+        .filter(|settings| !settings.is_null())
+        .and_then(|settings| serde_json::from_value(settings.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Gets the safety settings sent with every Gemini request
+#[tauri::command]
+#[instrument(name = "get_gemini_safety_settings", skip(state))]
+pub async fn get_gemini_safety_settings(
+    state: State<'_, AppState>,
+) -> Result<Vec<GeminiSafetySetting>, String> {
+    Ok(state.llm_manager.gemini().get_safety_settings().await)
+}
+
+/// Updates the safety settings sent with every Gemini request.
+///
+/// # Arguments
+/// * `settings` - One threshold per harm category; omitted categories use the API default
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_gemini_safety_settings", skip(state, settings))]
+pub async fn update_gemini_safety_settings(
+    settings: Vec<GeminiSafetySetting>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GeminiSafetySetting>, String> {
+    info!(count = settings.len(), "Updating Gemini safety settings");
+
+    validate_gemini_safety_settings(&settings).map_err(|e| {
+        warn!(error = %e, "Invalid Gemini safety settings");
+        e
+    })?;
+
+    state
+        .db
+        .execute_with_params(
+            "UPSERT type::thing('settings', $key) CONTENT { settings: $settings }",
+            vec![
+                ("key".to_string(), json!(GEMINI_SAFETY_SETTINGS_KEY)),
+                ("settings".to_string(), json!(settings)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save Gemini safety settings");
+            format!("Failed to save Gemini safety settings: {}", e)
+        })?;
+
+    state
+        .llm_manager
+        .set_gemini_safety_settings(settings.clone())
+        .await;
+    info!("Gemini safety settings updated successfully");
+    Ok(settings)
+}
+
+/// Loads the utility model from the database, None (agent model) when unset.
+pub async fn load_utility_model_settings(db: &DBClient) -> Option<UtilityModelConfig> {
+    match queries::settings::load_config(db, UTILITY_MODEL_SETTINGS_KEY).await {
+        Ok(stored) => stored.and_then(|config| serde_json::from_value(config).ok()),
+        Err(e) => {
+            debug!(error = %e, "No utility model settings found, using agent models");
+            None
+        }
+    }
+}
+
+/// Gets the model used by internal summarization and extraction passes
+#[tauri::command]
+#[instrument(name = "get_utility_model_settings", skip(state))]
+pub async fn get_utility_model_settings(
+    state: State<'_, AppState>,
+) -> Result<Option<UtilityModelConfig>, String> {
+    Ok(state.llm_manager.get_utility_model().await)
+}
+
+/// Updates the model used by internal summarization and extraction passes.
+///
+/// # Arguments
+/// * `settings` - Utility model, or None to use the model of the calling agent
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_utility_model_settings", skip(state, settings))]
+pub async fn update_utility_model_settings(
+    settings: Option<UtilityModelConfig>,
+    state: State<'_, AppState>,
+) -> Result<Option<UtilityModelConfig>, String> {
+    info!(
+        enabled = settings.is_some(),
+        "Updating utility model settings"
+    );
+
+    let settings = settings
+        .map(|config| {
+            validate_utility_model_settings(&config).map(|_| UtilityModelConfig {
+                provider: config.provider.trim().to_lowercase(),
+                model: config.model.trim().to_string(),
+                ..config
+            })
+        })
+        .transpose()
+        .map_err(|e| {
+            warn!(error = %e, "Invalid utility model settings");
+            e
+        })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize utility model settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, UTILITY_MODEL_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save utility model settings");
+            format!("Failed to save utility model settings: {}", e)
+        })?;
+
+    state.llm_manager.set_utility_model(settings.clone()).await;
+    info!("Utility model settings updated successfully");
+    Ok(settings)
+}
+
+/// Validates the utility model (known provider name format, model, sampling ranges)
+fn validate_utility_model_settings(config: &UtilityModelConfig) -> Result<(), String> {
+    validate_provider_string(config.provider.trim())?;
+    validate_model_id(&config.model)?;
+    if !(cmd_const::MIN_TEMPERATURE..=cmd_const::MAX_TEMPERATURE).contains(&config.temperature) {
+        return Err(format!(
+            "Temperature must be between {} and {}",
+            cmd_const::MIN_TEMPERATURE,
+            cmd_const::MAX_TEMPERATURE
+        ));
+    }
+    if !(cmd_const::MIN_MAX_TOKENS..=cmd_const::MAX_MAX_TOKENS).contains(&config.max_tokens) {
+        return Err(format!(
+            "Max tokens must be between {} and {}",
+            cmd_const::MIN_MAX_TOKENS,
+            cmd_const::MAX_MAX_TOKENS
+        ));
+    }
+    Ok(())
+}
+
+/// Validates Gemini safety settings (known values, one setting per category)
+fn validate_gemini_safety_settings(settings: &[GeminiSafetySetting]) -> Result<(), String> {
+    let mut categories = std::collections::HashSet::new();
+    for setting in settings {
+        setting.validate()?;
+        if !categories.insert(setting.category.as_str()) {
+            return Err(format!("Duplicate harm category '{}'", setting.category));
+        }
+    }
+    Ok(())
+}
+
+/// Validates LLM retry settings ranges
+fn validate_llm_retry_settings(settings: &RetryConfig) -> Result<(), String> {
+    if settings.max_retries > 10 {
+        return Err("Maximum retries must be between 0 and 10".to_string());
+    }
+    if !(100..=60_000).contains(&settings.initial_delay_ms) {
+        return Err("Initial delay must be between 100 and 60000 ms".to_string());
+    }
+    if settings.max_delay_ms < settings.initial_delay_ms || settings.max_delay_ms > 300_000 {
+        return Err("Maximum delay must be between the initial delay and 300000 ms".to_string());
+    }
+    if !(1.0..=10.0).contains(&settings.backoff_multiplier) {
+        return Err("Backoff multiplier must be between 1 and 10".to_string());
+    }
+    if !(0.0..=1.0).contains(&settings.jitter) {
+        return Err("Jitter must be between 0 and 1".to_string());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_validate_llm_retry_settings() {
+        assert!(validate_llm_retry_settings(&RetryConfig::default()).is_ok());
+
+        let settings = RetryConfig {
+            max_retries: 11,
+            ..Default::default()
+        };
+        assert!(validate_llm_retry_settings(&settings).is_err());
+
+        let settings = RetryConfig {
+            initial_delay_ms: 5000,
+            max_delay_ms: 1000,
+            ..Default::default()
+        };
+        assert!(validate_llm_retry_settings(&settings).is_err());
+
+        let settings = RetryConfig {
+            jitter: 1.5,
+            ..Default::default()
+        };
+        assert!(validate_llm_retry_settings(&settings).is_err());
+    }
+
+    #[test]
+    fn test_validate_gemini_safety_settings() {
+        let setting = |category: &str, threshold: &str| GeminiSafetySetting {
+            category: category.to_string(),
+            threshold: threshold.to_string(),
+        };
+
+        assert!(validate_gemini_safety_settings(&[]).is_ok());
+        assert!(validate_gemini_safety_settings(&[
+            setting("HARM_CATEGORY_HARASSMENT", "BLOCK_ONLY_HIGH"),
+            setting("HARM_CATEGORY_DANGEROUS_CONTENT", "BLOCK_NONE"),
+        ])
+        .is_ok());
+        assert!(validate_gemini_safety_settings(&[
+            setting("HARM_CATEGORY_HARASSMENT", "BLOCK_ONLY_HIGH"),
+            setting("HARM_CATEGORY_HARASSMENT", "BLOCK_NONE"),
+        ])
+        .is_err());
+        assert!(
+            validate_gemini_safety_settings(&[setting("HARM_CATEGORY_UNKNOWN", "BLOCK_NONE")])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_utility_model_settings() {
+        let config = UtilityModelConfig {
+            provider: "ollama".to_string(),
+            model: "llama3.2:3b".to_string(),
+            temperature: 0.2,
+            max_tokens: 1024,
+        };
+        assert!(validate_utility_model_settings(&config).is_ok());
+
+        let invalid = [
+            UtilityModelConfig {
+                provider: " ".to_string(),
+                ..config.clone()
+            },
+            UtilityModelConfig {
+                model: String::new(),
+                ..config.clone()
+            },
+            UtilityModelConfig {
+                temperature: 3.0,
+                ..config.clone()
+            },
+            UtilityModelConfig {
+                max_tokens: 10,
+                ..config.clone()
+            },
+        ];
+        for config in invalid {
+            assert!(validate_utility_model_settings(&config).is_err());
+        }
+    }
+
     #[test]
     fn test_provider_status_serialization() {
         let status = ProviderStatus {
@@ -310,6 +755,12 @@ mod tests {
                 default_model: "llama3.2".to_string(),
                 available_models: vec!["llama3.2".to_string()],
             },
+            gemini: ProviderStatus {
+                provider: "Gemini".to_string(),
+                configured: false,
+                default_model: "gemini-2.5-flash".to_string(),
+                available_models: vec!["gemini-2.5-flash".to_string()],
+            },
             ollama_url: "http://localhost:11434".to_string(),
         };
 
@@ -0,0 +1,96 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Format locale settings commands.
+//!
+//! Settings are stored in the `settings:locale` record and read through
+//! [`load_locale_settings`] when system prompts are built and when
+//! locale-aware tools are created.
+
+use crate::db::queries;
+use crate::models::locale::LocaleSettings;
+use crate::tools::utils::{load_locale_settings, LOCALE_SETTINGS_KEY};
+use crate::AppState;
+use tauri::State;
+use tracing::{error, info, instrument};
+
+/// Gets the format locale settings.
+/// Returns default settings (English) if none are configured.
+#[tauri::command]
+#[instrument(name = "get_locale_settings", skip(state))]
+pub async fn get_locale_settings(state: State<'_, AppState>) -> Result<LocaleSettings, String> {
+    info!("Loading locale settings");
+    Ok(load_locale_settings(&state.db).await)
+}
+
+/// Updates the format locale settings.
+///
+/// # Arguments
+/// * `settings` - The complete settings to store
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_locale_settings", skip(state), fields(locale = %settings.locale))]
+pub async fn update_locale_settings(
+    settings: LocaleSettings,
+    state: State<'_, AppState>,
+) -> Result<LocaleSettings, String> {
+    info!("Updating locale settings");
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize locale settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, LOCALE_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save locale settings");
+            format!("Failed to save locale settings: {}", e)
+        })?;
+
+    info!("Locale settings updated successfully");
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DBClient;
+    use crate::models::locale::Locale;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_load_locale_settings() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("locale_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        assert_eq!(load_locale_settings(&db).await.locale, Locale::En);
+
+        queries::settings::save_config(
+            &db,
+            LOCALE_SETTINGS_KEY,
+            &serde_json::json!({ "locale": "fr" }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(load_locale_settings(&db).await.locale, Locale::Fr);
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Macro tool commands.
+//!
+//! Manages the sequences of tool calls defined by the user as macros (see
+//! [`crate::tools::macros`]).
+
+use crate::models::macro_tool::{ToolMacro, ToolMacroCreate, ToolMacroUpdate};
+use crate::security::Validator;
+use crate::state::AppState;
+use crate::tools::constants::macros as macro_const;
+use crate::tools::macros::{self, store};
+use crate::tools::script;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Validates the description of a macro, which the LLM reads to pick the
+/// tool.
+fn validate_description(description: &str) -> Result<String, String> {
+    let trimmed = description.trim();
+    if trimmed.is_empty() {
+        return Err(
+            "The description cannot be empty: the LLM reads it to use the macro".to_string(),
+        );
+    }
+    if trimmed.chars().count() > macro_const::MAX_DESCRIPTION_LEN {
+        return Err(format!(
+            "The description exceeds {} characters",
+            macro_const::MAX_DESCRIPTION_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a macro ID and checks that the macro exists.
+async fn existing_macro(state: &AppState, macro_id: &str) -> Result<ToolMacro, String> {
+    let macro_id = Validator::validate_uuid(macro_id).map_err(|e| {
+        warn!(error = %e, "Invalid macro_id");
+        format!("Invalid macro_id: {}", e)
+    })?;
+    store::load_tool_macro(&state.db, &macro_id)
+        .await?
+        .ok_or_else(|| "Macro tool not found".to_string())
+}
+
+/// Lists the macro tools.
+///
+/// # Returns
+/// Macros sorted by name
+#[tauri::command]
+#[instrument(name = "list_macro_tools", skip(state))]
+pub async fn list_macro_tools(state: State<'_, AppState>) -> Result<Vec<ToolMacro>, String> {
+    info!("Listing macro tools");
+
+    let macros = store::list_tool_macros(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to list macro tools");
+        e
+    })?;
+
+    info!(count = macros.len(), "Macro tools loaded");
+    Ok(macros)
+}
+
+/// Creates a macro tool, enabled.
+///
+/// # Arguments
+/// * `config` - Name (lowercase letters, digits and `_`), description, input
+///   schema, steps and optional output template
+///
+/// # Returns
+/// The created macro
+#[tauri::command]
+#[instrument(name = "create_macro_tool", skip(state, config), fields(name = %config.name))]
+pub async fn create_macro_tool(
+    config: ToolMacroCreate,
+    state: State<'_, AppState>,
+) -> Result<ToolMacro, String> {
+    info!("Creating macro tool");
+
+    let name = config.name.trim().to_string();
+    if !macros::is_valid_name(&name) {
+        return Err(format!(
+            "Invalid name '{}': use lowercase letters, digits and '_', starting with a letter (at most {} characters)",
+            name,
+            macro_const::MAX_NAME_LEN
+        ));
+    }
+    macros::validate_steps(&config.steps, config.output.as_ref())?;
+    let tool_macro = ToolMacroCreate {
+        description: validate_description(&config.description)?,
+        input_schema: script::validate_schema(&config.input_schema)?,
+        name,
+        ..config
+    };
+
+    let existing = store::list_tool_macros(&state.db).await?;
+    if existing.len() >= macro_const::MAX_MACRO_TOOLS {
+        return Err(format!(
+            "Maximum number of macro tools reached ({})",
+            macro_const::MAX_MACRO_TOOLS
+        ));
+    }
+    if existing.iter().any(|other| other.name == tool_macro.name) {
+        return Err(format!("A macro is already named '{}'", tool_macro.name));
+    }
+
+    let macro_id = Uuid::new_v4().to_string();
+    store::create_tool_macro(&state.db, &macro_id, &tool_macro)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create macro tool");
+            e
+        })?;
+
+    info!(macro_id = %macro_id, "Macro tool created");
+    store::load_tool_macro(&state.db, &macro_id)
+        .await?
+        .ok_or_else(|| "Macro tool not found".to_string())
+}
+
+/// Updates a macro tool. Its name cannot change: agents refer to it as
+/// `macro__<name>`.
+///
+/// # Arguments
+/// * `macro_id` - Macro ID
+/// * `config` - Fields to update; the output template is replaced along
+///   with the steps
+///
+/// # Returns
+/// The updated macro
+#[tauri::command]
+#[instrument(name = "update_macro_tool", skip(state, config))]
+pub async fn update_macro_tool(
+    macro_id: String,
+    config: ToolMacroUpdate,
+    state: State<'_, AppState>,
+) -> Result<ToolMacro, String> {
+    info!("Updating macro tool");
+
+    let existing = existing_macro(&state, &macro_id).await?;
+    if let Some(steps) = &config.steps {
+        macros::validate_steps(steps, config.output.as_ref())?;
+    }
+    let update = ToolMacroUpdate {
+        description: config
+            .description
+            .as_deref()
+            .map(validate_description)
+            .transpose()?,
+        input_schema: config
+            .input_schema
+            .as_ref()
+            .map(script::validate_schema)
+            .transpose()?,
+        ..config
+    };
+
+    store::update_tool_macro(&state.db, &existing.id, &update)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update macro tool");
+            e
+        })?;
+
+    info!(macro_id = %existing.id, "Macro tool updated");
+    store::load_tool_macro(&state.db, &existing.id)
+        .await?
+        .ok_or_else(|| "Macro tool not found".to_string())
+}
+
+/// Deletes a macro tool. Agents still listing it report it as missing in
+/// their health check.
+///
+/// # Arguments
+/// * `macro_id` - Macro ID
+#[tauri::command]
+#[instrument(name = "delete_macro_tool", skip(state))]
+pub async fn delete_macro_tool(macro_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    info!("Deleting macro tool");
+
+    let existing = existing_macro(&state, &macro_id).await?;
+    store::delete_tool_macro(&state.db, &existing.id)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete macro tool");
+            e
+        })?;
+
+    info!(macro_id = %existing.id, "Macro tool deleted");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_description() {
+        assert_eq!(
+            validate_description("  Researches a topic ").unwrap(),
+            "Researches a topic"
+        );
+        assert!(validate_description(" ").is_err());
+        assert!(validate_description(&"a".repeat(macro_const::MAX_DESCRIPTION_LEN + 1)).is_err());
+    }
+}
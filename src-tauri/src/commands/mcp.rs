@@ -25,6 +25,7 @@
 //! - [`create_mcp_server`] - Create a new MCP server configuration
 //! - [`update_mcp_server`] - Update an existing MCP server
 //! - [`delete_mcp_server`] - Delete an MCP server configuration
+//! - [`import_mcp_servers_from_json`] - Import servers from another client's `mcpServers` JSON
 //!
 //! ### Lifecycle
 //! - [`start_mcp_server`] - Start an MCP server
@@ -34,13 +35,35 @@
 //! ### Tools
 //! - [`list_mcp_tools`] - List available tools from a server
 //! - [`call_mcp_tool`] - Execute a tool on an MCP server
-
+//!
+//! ### Resources
+//! - [`list_mcp_resources`] - List resources exposed by a server
+//! - [`read_mcp_resource`] - Read the contents of a resource
+//!
+//! ### Prompts
+//! - [`list_mcp_prompts`] - List prompts exposed by servers as library prompts
+//! - [`get_mcp_prompt`] - Render a prompt with argument values
+//!
+//! ### Supervision
+//! - [`get_mcp_server_status_history`] - Status transitions of a server
+//!
+//! ### OAuth
+//! - [`begin_mcp_oauth`] - Start the OAuth authorization of an HTTP server
+//! - [`complete_mcp_oauth`] - Exchange the authorization code for tokens
+
+use crate::mcp::config_import::parse_mcp_servers_json;
+use crate::mcp::oauth::OAuthAuthorizationStart;
+use crate::mcp::MCPManager;
+use crate::models::import_export::ConflictResolution;
 use crate::models::mcp::{
-    MCPLatencyMetrics, MCPServer, MCPServerConfig, MCPTestResult, MCPTool, MCPToolCallRequest,
-    MCPToolCallResult,
+    MCPLatencyMetrics, MCPPrompt, MCPResource, MCPResourceContents, MCPServer, MCPServerConfig,
+    MCPServerImportAction, MCPServerImportReport, MCPServerImportResult, MCPServerStatus,
+    MCPServerStatusChange, MCPTestResult, MCPTool, MCPToolCallRequest, MCPToolCallResult,
 };
+use crate::models::prompt::Prompt;
 use crate::state::AppState;
 use crate::tools::constants::commands as cmd_const;
+use std::collections::HashMap;
 use tauri::State;
 use tracing::{error, info, instrument, warn};
 
@@ -286,6 +309,128 @@ fn validate_tool_name(name: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
+/// Validates an MCP resource URI.
+///
+/// Rules:
+/// - Cannot be empty
+/// - Maximum 2048 characters
+/// - No control characters
+pub(crate) fn validate_mcp_resource_uri(uri: &str) -> Result<String, String> {
+    let trimmed = uri.trim();
+
+    if trimmed.is_empty() {
+        return Err("Resource URI cannot be empty".to_string());
+    }
+
+    if trimmed.len() > cmd_const::MAX_MCP_RESOURCE_URI_LEN {
+        return Err(format!(
+            "Resource URI exceeds maximum length of {} characters",
+            cmd_const::MAX_MCP_RESOURCE_URI_LEN
+        ));
+    }
+
+    if trimmed.chars().any(char::is_control) {
+        return Err("Resource URI contains invalid control characters".to_string());
+    }
+
+    Ok(trimmed.to_string())
+}
+
+/// Validates an MCP prompt or prompt argument name.
+///
+/// Rules:
+/// - Cannot be empty
+/// - Maximum 128 characters
+/// - No control characters
+fn validate_mcp_prompt_name(name: &str, field: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+
+    if trimmed.is_empty() {
+        return Err(format!("{} cannot be empty", field));
+    }
+
+    if trimmed.len() > cmd_const::MAX_MCP_PROMPT_NAME_LEN {
+        return Err(format!(
+            "{} exceeds maximum length of {} characters",
+            field,
+            cmd_const::MAX_MCP_PROMPT_NAME_LEN
+        ));
+    }
+
+    if trimmed.chars().any(char::is_control) {
+        return Err(format!("{} contains invalid control characters", field));
+    }
+
+    Ok(trimmed.to_string())
+}
+
+/// Validates MCP prompt argument values.
+///
+/// Rules:
+/// - Maximum 50 arguments
+/// - Argument names follow [`validate_mcp_prompt_name`]
+/// - Values maximum 10000 characters
+fn validate_mcp_prompt_arguments(
+    arguments: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    if arguments.len() > cmd_const::MAX_MCP_PROMPT_ARGS_COUNT {
+        return Err(format!(
+            "Too many prompt arguments (max {})",
+            cmd_const::MAX_MCP_PROMPT_ARGS_COUNT
+        ));
+    }
+
+    arguments
+        .iter()
+        .map(|(name, value)| {
+            let validated_name = validate_mcp_prompt_name(name, "Prompt argument name")?;
+            if value.chars().count() > cmd_const::MAX_MCP_PROMPT_ARG_VALUE_LEN {
+                return Err(format!(
+                    "Prompt argument '{}' exceeds maximum length of {} characters",
+                    validated_name,
+                    cmd_const::MAX_MCP_PROMPT_ARG_VALUE_LEN
+                ));
+            }
+            Ok((validated_name, value.clone()))
+        })
+        .collect()
+}
+
+/// Validates an OAuth redirect URI.
+///
+/// Rules:
+/// - Must be a valid `http://` URL
+/// - Host must be a loopback address (`localhost`, `127.0.0.1`, `[::1]`)
+fn validate_oauth_redirect_uri(redirect_uri: &str) -> Result<String, String> {
+    let trimmed = redirect_uri.trim();
+    let url = reqwest::Url::parse(trimmed).map_err(|e| format!("Invalid redirect URI: {}", e))?;
+
+    if url.scheme() != "http" {
+        return Err("Redirect URI must use http:// on a loopback address".to_string());
+    }
+
+    match url.host_str() {
+        Some("localhost") | Some("127.0.0.1") | Some("[::1]") => Ok(trimmed.to_string()),
+        _ => Err("Redirect URI host must be localhost, 127.0.0.1 or [::1]".to_string()),
+    }
+}
+
+/// Validates an OAuth state or authorization code.
+fn validate_oauth_param(field: &str, value: &str) -> Result<String, String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(format!("OAuth {} cannot be empty", field));
+    }
+    if trimmed.len() > cmd_const::MAX_OAUTH_PARAM_LEN {
+        return Err(format!(
+            "OAuth {} exceeds maximum length of {} characters",
+            field,
+            cmd_const::MAX_OAUTH_PARAM_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
 // =============================================================================
 // Tauri Commands
 // =============================================================================
@@ -536,6 +681,198 @@ pub async fn delete_mcp_server(id: String, state: State<'_, AppState>) -> Result
     Ok(())
 }
 
+/// Imports MCP servers from another client's configuration file.
+///
+/// Accepts the `mcpServers` JSON of Claude Desktop, Cursor, Windsurf or
+/// VS Code (see [`crate::mcp::config_import`]). Servers are created disabled,
+/// so their arguments and environment can be reviewed before starting them.
+///
+/// # Arguments
+///
+/// * `json` - Content of the configuration file
+/// * `conflict` - What to do when a server name is already taken (default: skip)
+/// * `dry_run` - If true, only reports what would be imported
+///
+/// # Returns
+///
+/// An [`MCPServerImportReport`] with the outcome of each server entry.
+///
+/// # Errors
+///
+/// Returns an error if the JSON is invalid, too large, or has no server map.
+#[tauri::command]
+#[instrument(name = "import_mcp_servers_from_json", skip(state, json))]
+pub async fn import_mcp_servers_from_json(
+    json: String,
+    conflict: Option<ConflictResolution>,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<MCPServerImportReport, String> {
+    import_mcp_servers(
+        &state.mcp_manager,
+        &json,
+        conflict.unwrap_or_default(),
+        dry_run.unwrap_or(false),
+    )
+    .await
+}
+
+/// Imports the servers of an `mcpServers` JSON document.
+///
+/// Conflicts are detected by name, against the configured servers and the
+/// servers imported earlier in the same document.
+pub(crate) async fn import_mcp_servers(
+    manager: &MCPManager,
+    json: &str,
+    conflict: ConflictResolution,
+    dry_run: bool,
+) -> Result<MCPServerImportReport, String> {
+    if json.len() > cmd_const::MAX_MCP_IMPORT_JSON_SIZE {
+        return Err(format!(
+            "Configuration exceeds maximum size of {} bytes",
+            cmd_const::MAX_MCP_IMPORT_JSON_SIZE
+        ));
+    }
+    let entries = parse_mcp_servers_json(json)?;
+    if entries.len() > cmd_const::MAX_MCP_IMPORT_SERVERS {
+        return Err(format!(
+            "Configuration exceeds maximum of {} servers",
+            cmd_const::MAX_MCP_IMPORT_SERVERS
+        ));
+    }
+
+    let existing: HashMap<String, MCPServer> = manager
+        .list_servers()
+        .await
+        .map_err(|e| format!("Failed to list MCP servers: {}", e))?
+        .into_iter()
+        .map(|server| (server.config.name.clone(), server))
+        .collect();
+    let mut taken: std::collections::HashSet<String> = existing.keys().cloned().collect();
+
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let mut result = MCPServerImportResult {
+            source_name: entry.name.clone(),
+            name: entry.name.trim().to_string(),
+            server_id: None,
+            command: None,
+            action: MCPServerImportAction::Failed,
+            message: None,
+        };
+
+        let mut config = match entry
+            .config
+            .and_then(|config| validate_mcp_server_config(&config))
+        {
+            Ok(config) => config,
+            Err(e) => {
+                result.message = Some(e);
+                results.push(result);
+                continue;
+            }
+        };
+        result.command = Some(config.command.clone());
+
+        let action = if !taken.contains(&config.name) {
+            MCPServerImportAction::Created
+        } else {
+            match (&conflict, existing.get(&config.name)) {
+                (ConflictResolution::Overwrite, Some(current)) => {
+                    config.id = current.config.id.clone();
+                    config.enabled = current.config.enabled;
+                    config.description = current.config.description.clone();
+                    MCPServerImportAction::Overwritten
+                }
+                (ConflictResolution::Rename, _) => match unique_import_name(&config.name, &taken) {
+                    Ok(name) => {
+                        config.name = name;
+                        MCPServerImportAction::Renamed
+                    }
+                    Err(e) => {
+                        result.message = Some(e);
+                        results.push(result);
+                        continue;
+                    }
+                },
+                // Skip, or a duplicate name within the imported document
+                _ => MCPServerImportAction::Skipped,
+            }
+        };
+        result.name = config.name.clone();
+
+        if action == MCPServerImportAction::Skipped {
+            result.action = action;
+            result.message = Some(format!("MCP server '{}' already exists", config.name));
+            results.push(result);
+            continue;
+        }
+
+        if !dry_run {
+            let saved = if action == MCPServerImportAction::Overwritten {
+                manager.update_server_config(&config).await
+            } else {
+                manager.save_server_config(&config).await
+            };
+            if let Err(e) = saved {
+                error!(name = %config.name, error = %e, "Failed to import MCP server");
+                result.message = Some(format!("Failed to save server: {}", e));
+                results.push(result);
+                continue;
+            }
+            // Running servers pick up the overwritten configuration
+            if action == MCPServerImportAction::Overwritten
+                && existing[&config.name].status == MCPServerStatus::Running
+            {
+                if let Err(e) = manager.restart_server(&config.id).await {
+                    warn!(name = %config.name, error = %e, "Failed to restart imported MCP server");
+                }
+            }
+        }
+
+        taken.insert(config.name.clone());
+        result.server_id = Some(config.id);
+        result.action = action;
+        results.push(result);
+    }
+
+    let count =
+        |action: MCPServerImportAction| results.iter().filter(|r| r.action == action).count();
+    let report = MCPServerImportReport {
+        imported: results.len()
+            - count(MCPServerImportAction::Skipped)
+            - count(MCPServerImportAction::Failed),
+        skipped: count(MCPServerImportAction::Skipped),
+        failed: count(MCPServerImportAction::Failed),
+        results,
+        dry_run,
+    };
+
+    info!(
+        imported = report.imported,
+        skipped = report.skipped,
+        failed = report.failed,
+        dry_run = dry_run,
+        "MCP servers imported from JSON"
+    );
+    Ok(report)
+}
+
+/// Returns a free name for a renamed import ("name (imported)", "name (imported 2)"...).
+fn unique_import_name(
+    name: &str,
+    taken: &std::collections::HashSet<String>,
+) -> Result<String, String> {
+    (1..=cmd_const::MAX_MCP_IMPORT_SERVERS + 1)
+        .map(|n| match n {
+            1 => format!("{} (imported)", name),
+            n => format!("{} (imported {})", name, n),
+        })
+        .find(|candidate| !taken.contains(candidate))
+        .ok_or_else(|| format!("No free name for MCP server '{}'", name))
+        .and_then(|candidate| validate_mcp_server_display_name(&candidate))
+}
+
 /// Tests an MCP server connection.
 ///
 /// Spawns a temporary server process, initializes the MCP protocol,
@@ -742,6 +1079,243 @@ pub async fn list_mcp_tools(
     Ok(tools)
 }
 
+/// Lists resources exposed by an MCP server.
+///
+/// Resources are read-only data sources (files, documents, schemas)
+/// identified by URI.
+///
+/// # Arguments
+///
+/// * `server_name` - The name of the MCP server
+/// * `refresh` - Re-fetch the list from the server instead of using the
+///   list discovered at initialization
+///
+/// # Returns
+///
+/// A vector of [`MCPResource`] objects.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The server name is invalid
+/// - The server is not running (when refreshing)
+#[tauri::command]
+#[instrument(name = "list_mcp_resources", skip(state), fields(server = %server_name))]
+pub async fn list_mcp_resources(
+    server_name: String,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<MCPResource>, String> {
+    let validated_name = validate_mcp_server_id(&server_name)?;
+    info!(server = %validated_name, "Listing MCP resources");
+
+    let resources = if refresh.unwrap_or(false) {
+        state
+            .mcp_manager
+            .refresh_server_resources(&validated_name)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to refresh MCP resources");
+                format!("Failed to refresh MCP resources: {}", e)
+            })?
+    } else {
+        state
+            .mcp_manager
+            .list_server_resources(&validated_name)
+            .await
+    };
+
+    info!(
+        server = %validated_name,
+        count = resources.len(),
+        "MCP resources listed"
+    );
+    Ok(resources)
+}
+
+/// Reads a resource from an MCP server.
+///
+/// # Arguments
+///
+/// * `server_name` - The name of the MCP server
+/// * `uri` - The resource URI
+///
+/// # Returns
+///
+/// The [`MCPResourceContents`] items of the resource (text or base64 blob).
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The server name or URI is invalid
+/// - The server is not running
+/// - The server rejects the URI
+#[tauri::command]
+#[instrument(name = "read_mcp_resource", skip(state), fields(server = %server_name))]
+pub async fn read_mcp_resource(
+    server_name: String,
+    uri: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MCPResourceContents>, String> {
+    let validated_name = validate_mcp_server_id(&server_name)?;
+    let validated_uri = validate_mcp_resource_uri(&uri)?;
+    info!(server = %validated_name, uri = %validated_uri, "Reading MCP resource");
+
+    let contents = state
+        .mcp_manager
+        .read_resource(&validated_name, &validated_uri)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to read MCP resource");
+            format!("Failed to read MCP resource: {}", e)
+        })?;
+
+    info!(
+        server = %validated_name,
+        items = contents.len(),
+        "MCP resource read"
+    );
+    Ok(contents)
+}
+
+/// Lists prompts exposed by MCP servers as library prompts.
+///
+/// Each prompt is converted with [`Prompt::from_mcp`]: its arguments become
+/// variables and `mcp_server` names the providing server. The content is
+/// empty; it is rendered by the server with [`get_mcp_prompt`].
+///
+/// # Arguments
+///
+/// * `server_name` - Only list prompts of this server (all running servers if None)
+/// * `refresh` - Re-fetch the list from the server instead of using the
+///   list discovered at initialization (requires `server_name`)
+///
+/// # Returns
+///
+/// Prompts sorted by server name, then prompt name.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The server name is invalid
+/// - The server is not running (when refreshing)
+#[tauri::command]
+#[instrument(name = "list_mcp_prompts", skip(state))]
+pub async fn list_mcp_prompts(
+    server_name: Option<String>,
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Prompt>, String> {
+    info!("Listing MCP prompts");
+
+    let prompts_by_server = match server_name {
+        Some(ref name) => {
+            let validated_name = validate_mcp_server_id(name)?;
+            let prompts = if refresh.unwrap_or(false) {
+                state
+                    .mcp_manager
+                    .refresh_server_prompts(&validated_name)
+                    .await
+                    .map_err(|e| {
+                        error!(error = %e, "Failed to refresh MCP prompts");
+                        format!("Failed to refresh MCP prompts: {}", e)
+                    })?
+            } else {
+                state.mcp_manager.list_server_prompts(&validated_name).await
+            };
+            HashMap::from([(validated_name, prompts)])
+        }
+        None => state.mcp_manager.list_all_prompts().await,
+    };
+
+    let mut prompts: Vec<Prompt> = prompts_by_server
+        .iter()
+        .flat_map(|(server, prompts)| {
+            prompts
+                .iter()
+                .map(|prompt| Prompt::from_mcp(server, prompt, String::new()))
+        })
+        .collect();
+    prompts.sort_by(|a, b| (&a.mcp_server, &a.name).cmp(&(&b.mcp_server, &b.name)));
+
+    info!(count = prompts.len(), "MCP prompts listed");
+    Ok(prompts)
+}
+
+/// Renders an MCP prompt with argument values.
+///
+/// The rendered messages are joined into the prompt `content`, ready to be
+/// used as the first message of a workflow.
+///
+/// # Arguments
+///
+/// * `server_name` - The name of the MCP server
+/// * `prompt_name` - The prompt name
+/// * `arguments` - Argument values (by argument name)
+///
+/// # Returns
+///
+/// The library [`Prompt`] with the rendered content.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The server name, prompt name or arguments are invalid
+/// - The server is not running
+/// - The server rejects the prompt (unknown prompt, missing argument)
+#[tauri::command]
+#[instrument(
+    name = "get_mcp_prompt",
+    skip(state, arguments),
+    fields(server = %server_name, prompt = %prompt_name)
+)]
+pub async fn get_mcp_prompt(
+    server_name: String,
+    prompt_name: String,
+    arguments: Option<HashMap<String, String>>,
+    state: State<'_, AppState>,
+) -> Result<Prompt, String> {
+    let validated_server = validate_mcp_server_id(&server_name)?;
+    let validated_prompt = validate_mcp_prompt_name(&prompt_name, "Prompt name")?;
+    let validated_arguments = validate_mcp_prompt_arguments(&arguments.unwrap_or_default())?;
+    info!("Rendering MCP prompt");
+
+    let result = state
+        .mcp_manager
+        .get_prompt(&validated_server, &validated_prompt, validated_arguments)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to get MCP prompt");
+            format!("Failed to get MCP prompt: {}", e)
+        })?;
+
+    let definition = state
+        .mcp_manager
+        .list_server_prompts(&validated_server)
+        .await
+        .into_iter()
+        .find(|p| p.name == validated_prompt)
+        .unwrap_or_else(|| MCPPrompt {
+            name: validated_prompt.clone(),
+            description: None,
+            arguments: Vec::new(),
+        });
+
+    let content = result
+        .messages
+        .iter()
+        .map(|m| m.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let mut prompt = Prompt::from_mcp(&validated_server, &definition, content);
+    if let Some(description) = result.description {
+        prompt.description = description;
+    }
+
+    info!(messages = result.messages.len(), "MCP prompt rendered");
+    Ok(prompt)
+}
+
 /// Calls a tool on an MCP server.
 ///
 /// Executes the specified tool with the provided arguments.
@@ -838,39 +1412,34 @@ pub async fn get_mcp_latency_metrics(
     info!(server = ?filter, "Fetching MCP latency metrics");
 
     // Build query using SurrealDB percentile function
-    let query = match &filter {
-        Some(name) => {
-            // Use JSON string encoding for proper escaping
-            let json_name = serde_json::to_string(name).map_err(|e| e.to_string())?;
-            format!(
-                r#"SELECT
-                    server_name,
-                    math::percentile(duration_ms, 0.50) AS p50_ms,
-                    math::percentile(duration_ms, 0.95) AS p95_ms,
-                    math::percentile(duration_ms, 0.99) AS p99_ms,
-                    count() AS total_calls
-                FROM mcp_call_log
-                WHERE timestamp > time::now() - 1h AND server_name = {}
-                GROUP BY server_name"#,
-                json_name
-            )
-        }
-        None => r#"SELECT
+    let server_condition = if filter.is_some() {
+        " AND server_name = $server_name"
+    } else {
+        ""
+    };
+    let query = format!(
+        r#"SELECT
             server_name,
             math::percentile(duration_ms, 0.50) AS p50_ms,
             math::percentile(duration_ms, 0.95) AS p95_ms,
             math::percentile(duration_ms, 0.99) AS p99_ms,
             count() AS total_calls
         FROM mcp_call_log
-        WHERE timestamp > time::now() - 1h
-        GROUP BY server_name"#
-            .to_string(),
-    };
+        WHERE timestamp > time::now() - 1h{}
+        GROUP BY server_name"#,
+        server_condition
+    );
 
-    let mut response = state.db.db.query(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to query MCP latency metrics");
-        format!("Failed to query MCP latency metrics: {}", e)
-    })?;
+    let mut response = state
+        .db
+        .conn()
+        .query(&query)
+        .bind(("server_name", filter.clone()))
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to query MCP latency metrics");
+            format!("Failed to query MCP latency metrics: {}", e)
+        })?;
 
     let metrics: Vec<MCPLatencyMetrics> = response.take(0).map_err(|e| {
         error!(error = %e, "Failed to parse MCP latency metrics");
@@ -886,6 +1455,128 @@ pub async fn get_mcp_latency_metrics(
     Ok(metrics)
 }
 
+/// Gets the status transitions of an MCP server.
+///
+/// Transitions are recorded by the server supervision (start, stop, failed
+/// health checks, crashes and automatic restarts) and also emitted live as
+/// `mcp_server_status_changed` events.
+///
+/// # Arguments
+///
+/// * `server_name` - The name of the MCP server
+///
+/// # Returns
+///
+/// The recorded [`MCPServerStatusChange`] items, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the server name is invalid.
+#[tauri::command]
+#[instrument(name = "get_mcp_server_status_history", skip(state), fields(server = %server_name))]
+pub async fn get_mcp_server_status_history(
+    server_name: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MCPServerStatusChange>, String> {
+    let validated_name = validate_mcp_server_id(&server_name)?;
+
+    let history = state.mcp_manager.get_status_history(&validated_name).await;
+
+    info!(
+        server = %validated_name,
+        count = history.len(),
+        "MCP server status history retrieved"
+    );
+    Ok(history)
+}
+
+/// Starts the OAuth 2.1 authorization of an HTTP MCP server.
+///
+/// Discovers the authorization server, registers the application as a
+/// public client and returns the authorization URL (PKCE) to open in the
+/// browser. The browser is redirected to `redirect_uri` with `code` and
+/// `state`, which are passed to [`complete_mcp_oauth`].
+///
+/// # Arguments
+///
+/// * `id` - The unique identifier of the HTTP server
+/// * `redirect_uri` - Loopback redirect URI receiving the authorization code
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The ID or redirect URI is invalid
+/// - The server is not an HTTP server
+/// - Discovery or client registration fails
+#[tauri::command]
+#[instrument(name = "begin_mcp_oauth", skip(state), fields(server_id = %id))]
+pub async fn begin_mcp_oauth(
+    id: String,
+    redirect_uri: String,
+    state: State<'_, AppState>,
+) -> Result<OAuthAuthorizationStart, String> {
+    let validated_id = validate_mcp_server_id(&id)?;
+    let validated_redirect_uri = validate_oauth_redirect_uri(&redirect_uri)?;
+    info!(id = %validated_id, "Starting MCP OAuth authorization");
+
+    state
+        .mcp_manager
+        .begin_oauth(&validated_id, &validated_redirect_uri)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to start MCP OAuth authorization");
+            format!("Failed to start OAuth authorization: {}", e)
+        })
+}
+
+/// Completes the OAuth authorization of an HTTP MCP server.
+///
+/// Exchanges the authorization code for tokens, stores them in the secure
+/// keystore and restarts the server (if enabled). Access tokens are then
+/// refreshed automatically.
+///
+/// # Arguments
+///
+/// * `oauth_state` - State returned by [`begin_mcp_oauth`]
+/// * `code` - Authorization code received on the redirect URI
+///
+/// # Returns
+///
+/// The [`MCPServer`] with its updated status.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The state is unknown or expired
+/// - The token exchange fails
+#[tauri::command]
+#[instrument(name = "complete_mcp_oauth", skip(oauth_state, code, state))]
+pub async fn complete_mcp_oauth(
+    oauth_state: String,
+    code: String,
+    state: State<'_, AppState>,
+) -> Result<MCPServer, String> {
+    let validated_state = validate_oauth_param("state", &oauth_state)?;
+    let validated_code = validate_oauth_param("code", &code)?;
+
+    let server_id = state
+        .mcp_manager
+        .complete_oauth(&validated_state, &validated_code)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to complete MCP OAuth authorization");
+            format!("Failed to complete OAuth authorization: {}", e)
+        })?;
+
+    info!(id = %server_id, "MCP OAuth authorization completed");
+
+    state
+        .mcp_manager
+        .get_server(&server_id)
+        .await
+        .ok_or_else(|| format!("MCP server '{}' not found", server_id))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1040,6 +1731,58 @@ mod tests {
         assert!(validate_mcp_env(&env).is_ok());
     }
 
+    #[test]
+    fn test_validate_mcp_resource_uri() {
+        assert_eq!(
+            validate_mcp_resource_uri("  file:///docs/README.md ").unwrap(),
+            "file:///docs/README.md"
+        );
+        assert!(validate_mcp_resource_uri("postgres://db/users/schema").is_ok());
+        assert!(validate_mcp_resource_uri("").is_err());
+        assert!(validate_mcp_resource_uri("file:///a\u{0}b").is_err());
+        assert!(validate_mcp_resource_uri(&"a".repeat(3000)).is_err());
+    }
+
+    #[test]
+    fn test_validate_mcp_prompt_arguments() {
+        assert_eq!(
+            validate_mcp_prompt_name(" code_review ", "Prompt name").unwrap(),
+            "code_review"
+        );
+        assert!(validate_mcp_prompt_name("", "Prompt name").is_err());
+        assert!(validate_mcp_prompt_name(&"p".repeat(200), "Prompt name").is_err());
+
+        let arguments = HashMap::from([(" code ".to_string(), "fn main() {}".to_string())]);
+        let validated = validate_mcp_prompt_arguments(&arguments).unwrap();
+        assert_eq!(validated["code"], "fn main() {}");
+
+        let too_long = HashMap::from([("code".to_string(), "x".repeat(10_001))]);
+        assert!(validate_mcp_prompt_arguments(&too_long).is_err());
+
+        let too_many: HashMap<String, String> = (0..51)
+            .map(|i| (format!("arg{}", i), String::new()))
+            .collect();
+        assert!(validate_mcp_prompt_arguments(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_validate_oauth_redirect_uri() {
+        assert!(validate_oauth_redirect_uri("http://127.0.0.1:8765/callback").is_ok());
+        assert!(validate_oauth_redirect_uri("http://localhost:1234/cb").is_ok());
+        assert!(validate_oauth_redirect_uri("http://[::1]:1234/cb").is_ok());
+        assert!(validate_oauth_redirect_uri("https://example.com/callback").is_err());
+        assert!(validate_oauth_redirect_uri("http://example.com/callback").is_err());
+        assert!(validate_oauth_redirect_uri("zileo://callback").is_err());
+        assert!(validate_oauth_redirect_uri("not a url").is_err());
+    }
+
+    #[test]
+    fn test_validate_oauth_param() {
+        assert_eq!(validate_oauth_param("code", " abc ").unwrap(), "abc");
+        assert!(validate_oauth_param("code", "  ").is_err());
+        assert!(validate_oauth_param("state", &"a".repeat(5000)).is_err());
+    }
+
     #[test]
     fn test_validate_tool_name_valid() {
         assert!(validate_tool_name("find_symbol").is_ok());
@@ -1096,4 +1839,79 @@ mod tests {
         let result = validate_mcp_server_config(&config);
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_import_mcp_servers() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test_db");
+        let db = std::sync::Arc::new(
+            crate::db::DBClient::new(db_path.to_str().unwrap())
+                .await
+                .expect("Failed to create DB"),
+        );
+        db.initialize_schema().await.expect("Failed to init schema");
+        let manager = MCPManager::new(db).await.expect("Failed to create manager");
+
+        let json = r#"{ "mcpServers": {
+            "fetch": { "command": "uvx", "args": ["mcp-server-fetch"] },
+            "memory": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-memory"] },
+            "script": { "command": "python", "args": ["server.py"] }
+        } }"#;
+
+        // Dry run writes nothing
+        let report = import_mcp_servers(&manager, json, ConflictResolution::Skip, true)
+            .await
+            .unwrap();
+        assert!(report.dry_run);
+        assert_eq!((report.imported, report.skipped, report.failed), (2, 0, 1));
+        assert!(manager.list_servers().await.unwrap().is_empty());
+
+        let report = import_mcp_servers(&manager, json, ConflictResolution::Skip, false)
+            .await
+            .unwrap();
+        assert_eq!((report.imported, report.skipped, report.failed), (2, 0, 1));
+        let servers = manager.list_servers().await.unwrap();
+        assert_eq!(servers.len(), 2);
+        assert!(servers.iter().all(|s| !s.config.enabled));
+        let script = report
+            .results
+            .iter()
+            .find(|r| r.source_name == "script")
+            .unwrap();
+        assert_eq!(script.action, MCPServerImportAction::Failed);
+        assert!(script.message.as_deref().unwrap().contains("python"));
+
+        // Same document again: conflicts
+        let report = import_mcp_servers(&manager, json, ConflictResolution::Skip, false)
+            .await
+            .unwrap();
+        assert_eq!((report.imported, report.skipped), (0, 2));
+
+        let report = import_mcp_servers(&manager, json, ConflictResolution::Rename, false)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 2);
+        assert!(report
+            .results
+            .iter()
+            .any(|r| r.action == MCPServerImportAction::Renamed && r.name == "fetch (imported)"));
+
+        let fetch_id = servers
+            .iter()
+            .find(|s| s.config.name == "fetch")
+            .map(|s| s.config.id.clone())
+            .unwrap();
+        let updated = r#"{ "mcpServers": { "fetch": { "command": "uvx", "args": ["mcp-server-fetch", "--ignore-robots-txt"] } } }"#;
+        let report = import_mcp_servers(&manager, updated, ConflictResolution::Overwrite, false)
+            .await
+            .unwrap();
+        assert_eq!(report.results[0].action, MCPServerImportAction::Overwritten);
+        assert_eq!(
+            report.results[0].server_id.as_deref(),
+            Some(fetch_id.as_str())
+        );
+        let config = manager.get_server_config(&fetch_id).await.unwrap().unwrap();
+        assert_eq!(config.args.len(), 2);
+        assert_eq!(manager.list_servers().await.unwrap().len(), 4);
+    }
 }
@@ -24,7 +24,9 @@ use crate::{
     models::{Memory, MemorySearchResult, MemoryType},
     security::Validator,
     tools::constants::{memory as memory_constants, query_limits},
-    tools::memory::{add_memory_core, search_memories_core, AddMemoryParams, SearchParams},
+    tools::memory::{
+        add_memory_core, reveal_memory, search_memories_core, AddMemoryParams, SearchParams,
+    },
     AppState,
 };
 use tauri::State;
@@ -98,7 +100,7 @@ pub async fn add_memory(
 
     info!(
         memory_id = %result.memory_id,
-        embedding_generated = result.embedding_generated,
+        embedding_status = ?result.embedding_status,
         workflow_id = ?workflow_id,
         "Memory entry created successfully"
     );
@@ -155,14 +157,14 @@ pub async fn list_memories(
     // serialization issues with internal Thing type (see CLAUDE.md)
     // Add LIMIT to prevent memory explosion (OPT-DB-8)
     let query = format!(
-        "SELECT meta::id(id) AS id, type, content, workflow_id, metadata, created_at \
+        "SELECT meta::id(id) AS id, type, content, workflow_id, metadata, embedding_status, created_at \
          FROM memory{} ORDER BY created_at DESC LIMIT {}",
         where_clause,
         query_limits::DEFAULT_LIST_LIMIT
     );
 
     // Use parameterized query if we have parameters, otherwise standard query
-    let memories: Vec<Memory> = if params.is_empty() {
+    let mut memories: Vec<Memory> = if params.is_empty() {
         state.db.query(&query).await.map_err(|e| {
             error!(error = %e, "Failed to load memories");
             format!("Failed to load memories: {}", e)
@@ -177,6 +179,7 @@ pub async fn list_memories(
                 format!("Failed to load memories: {}", e)
             })?
     };
+    memories.iter_mut().for_each(reveal_memory);
 
     debug!(
         count = memories.len(),
@@ -209,21 +212,23 @@ pub async fn get_memory(memory_id: String, state: State<'_, AppState>) -> Result
     // serialization issues with internal Thing type (see CLAUDE.md)
     let memories: Vec<Memory> = state
         .db
-        .query(&format!(
-            "SELECT meta::id(id) AS id, type, content, workflow_id, metadata, created_at \
-             FROM memory WHERE meta::id(id) = '{}'",
-            validated_id
-        ))
+        .query_with_params(
+            "SELECT meta::id(id) AS id, type, content, workflow_id, metadata, embedding_status, created_at \
+             FROM memory WHERE meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(validated_id))],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to get memory");
             format!("Failed to get memory: {}", e)
         })?;
 
-    memories.into_iter().next().ok_or_else(|| {
+    let mut memory = memories.into_iter().next().ok_or_else(|| {
         warn!(memory_id = %validated_id, "Memory not found");
         "Memory not found".to_string()
-    })
+    })?;
+    reveal_memory(&mut memory);
+    Ok(memory)
 }
 
 /// Deletes a memory entry.
@@ -241,9 +246,8 @@ pub async fn delete_memory(memory_id: String, state: State<'_, AppState>) -> Res
         format!("Invalid memory_id: {}", e)
     })?;
 
-    // Use execute() with DELETE query to avoid SurrealDB SDK issues with delete() method
-    let delete_query = format!("DELETE memory:`{}`", validated_id);
-    state.db.execute(&delete_query).await.map_err(|e| {
+    let record_id = format!("memory:{}", validated_id);
+    state.db.delete(&record_id).await.map_err(|e| {
         error!(error = %e, "Failed to delete memory");
         format!("Failed to delete memory: {}", e)
     })?;
@@ -352,6 +356,9 @@ pub async fn search_memories(
                     .and_then(|e| e.as_str())
                     .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
                     .map(|dt| dt.with_timezone(&chrono::Utc)),
+                embedding_status: v
+                    .get("embedding_status")
+                    .and_then(|s| serde_json::from_value(s.clone()).ok()),
                 created_at: v
                     .get("created_at")
                     .and_then(|c| c.as_str())
@@ -467,6 +474,13 @@ mod tests {
 
         AppState {
             db: db.clone(),
+            outbox: Arc::new(crate::db::outbox::PersistenceOutbox::new(
+                db_path.with_file_name("outbox.jsonl"),
+                Default::default(),
+            )),
+            attachments: Arc::new(crate::db::attachments::AttachmentStore::new(
+                db_path.with_file_name("attachments"),
+            )),
             registry,
             orchestrator,
             llm_manager,
@@ -480,6 +494,14 @@ mod tests {
                 std::collections::HashMap::new(),
             )),
             app_handle: Arc::new(std::sync::RwLock::new(None)),
+            startup_status: Default::default(),
+            scheduler: Default::default(),
+            job_queue: Default::default(),
+            profiles: Arc::new(crate::profiles::ProfileStore::new(
+                db_path.parent().unwrap(),
+                &db_path,
+            )),
+            config: Default::default(),
         }
     }
 
@@ -504,6 +526,7 @@ mod tests {
             metadata: serde_json::json!({"source": "settings"}),
             importance: 0.3,
             expires_at: None,
+            embedding_status: None,
             created_at: Utc::now(),
         };
 
@@ -522,6 +545,7 @@ mod tests {
             metadata: serde_json::json!({}),
             importance: 0.7,
             expires_at: None,
+            embedding_status: None,
             created_at: Utc::now(),
         };
 
@@ -21,9 +21,19 @@
 //! after application restart by persisting all messages to SurrealDB.
 
 use crate::{
-    models::{Message, MessageCreate, PaginatedMessages},
-    security::Validator,
-    tools::constants::commands as cmd_const,
+    commands::{
+        attachment::{load_attachments_of_message, load_message_attachments, store_attachments},
+        speech::{load_speech_settings, seal_read_aloud},
+    },
+    db::DBClient,
+    models::{
+        attachment::AttachmentInput,
+        message::{format_pinned_context, ContextUsage},
+        GenerationParams, Message, MessageCreate, PaginatedMessages,
+    },
+    security::{Validator, WORKFLOW_VAULT},
+    speech::read_aloud,
+    tools::constants::{commands as cmd_const, workflow as wf_const},
     AppState,
 };
 use tauri::State;
@@ -32,6 +42,9 @@ use uuid::Uuid;
 
 /// Saves a new message to the database.
 ///
+/// If the write keeps failing, the message is queued in the persistence
+/// outbox and written later; its ID is returned either way.
+///
 /// # Arguments
 /// * `workflow_id` - Associated workflow ID
 /// * `role` - Message role (user, assistant, system)
@@ -41,6 +54,10 @@ use uuid::Uuid;
 /// * `model` - Model used for generation (optional)
 /// * `provider` - Provider used (optional)
 /// * `duration_ms` - Generation duration in milliseconds (optional)
+/// * `context_usage` - Context window utilization of the run (optional, for assistant messages)
+/// * `behavior_position` - Behavior changelog position of the run (optional, for assistant messages)
+/// * `generation` - Generation parameters used for the response (optional, for assistant messages)
+/// * `attachments` - Images attached to the message (optional: user images, response charts)
 ///
 /// # Returns
 /// The ID of the created message
@@ -64,6 +81,10 @@ pub async fn save_message(
     model: Option<String>,
     provider: Option<String>,
     duration_ms: Option<u64>,
+    context_usage: Option<ContextUsage>,
+    behavior_position: Option<u64>,
+    generation: Option<GenerationParams>,
+    attachments: Option<Vec<AttachmentInput>>,
     state: State<'_, AppState>,
 ) -> Result<String, String> {
     info!("Saving message");
@@ -97,8 +118,45 @@ pub async fn save_message(
         ));
     }
 
+    let attachments = attachments.unwrap_or_default();
+    if !attachments.is_empty() && validated_role == "system" {
+        return Err("System messages cannot have attachments".to_string());
+    }
+
     let message_id = Uuid::new_v4().to_string();
 
+    // Attachments are validated and stored first: an invalid one rejects the message
+    let stored_attachments = store_attachments(
+        &state,
+        &validated_workflow_id,
+        &message_id,
+        &attachments,
+        validated_role == "assistant",
+    )
+    .await
+    .map_err(|e| {
+        warn!(error = %e, "Failed to store message attachments");
+        e
+    })?;
+
+    // Read-aloud version of responses, when enabled in the speech settings
+    let read_aloud = if validated_role == "assistant" {
+        let speech = load_speech_settings(&state.db).await;
+        if speech.read_aloud {
+            let prepared = read_aloud::prepare(&content, speech.language.as_deref());
+            Some(seal_read_aloud(&state.db, &validated_workflow_id, prepared).await?)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    // Messages of a sensitive workflow are stored encrypted
+    let content = WORKFLOW_VAULT
+        .seal(&state.db, &validated_workflow_id, &content)
+        .await?;
+
     // Build MessageCreate payload
     let message = MessageCreate {
         workflow_id: validated_workflow_id,
@@ -111,19 +169,27 @@ pub async fn save_message(
         provider,
         cost_usd: None, // Cost calculation is provider-specific (future enhancement)
         duration_ms,
+        context_usage,
+        read_aloud,
+        behavior_position,
+        generation,
     };
 
-    // Insert into database
-    let id = state
-        .db
-        .create("message", &message_id, message)
+    // Insert into database (queued in the outbox if the write keeps failing)
+    state
+        .outbox
+        .create(&state.db, "message", &message_id, message)
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to save message");
             format!("Failed to save message: {}", e)
         })?;
 
-    info!(message_id = %id, "Message saved successfully");
+    info!(
+        message_id = %message_id,
+        attachments = stored_attachments.len(),
+        "Message saved successfully"
+    );
     Ok(message_id)
 }
 
@@ -151,8 +217,7 @@ pub async fn load_workflow_messages(
     // Use explicit field selection with meta::id(id) to avoid SurrealDB SDK
     // serialization issues with internal Thing type (see CLAUDE.md)
     // ORDER BY timestamp ASC for chronological order
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             role,
@@ -164,20 +229,32 @@ pub async fn load_workflow_messages(
             provider,
             cost_usd,
             duration_ms,
+            context_usage,
+            behavior_position,
+            generation,
+            pinned,
+            bookmarked,
+            reactions,
             timestamp
         FROM message
-        WHERE workflow_id = '{}'
-        ORDER BY timestamp ASC"#,
-        validated_workflow_id
-    );
-
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load workflow messages");
-        format!("Failed to load workflow messages: {}", e)
-    })?;
+        WHERE workflow_id = $workflow_id
+        ORDER BY timestamp ASC"#;
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
+
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow messages");
+            format!("Failed to load workflow messages: {}", e)
+        })?;
 
     // Deserialize using serde_json which respects our custom deserializers
-    let messages: Vec<Message> = json_results
+    let mut messages: Vec<Message> = json_results
         .into_iter()
         .map(serde_json::from_value)
         .collect::<std::result::Result<Vec<Message>, _>>()
@@ -185,6 +262,10 @@ pub async fn load_workflow_messages(
             error!(error = %e, "Failed to deserialize messages");
             format!("Failed to deserialize messages: {}", e)
         })?;
+    messages
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    load_message_attachments(&state.db, &validated_workflow_id, &mut messages).await;
 
     info!(count = messages.len(), "Workflow messages loaded");
     Ok(messages)
@@ -224,12 +305,17 @@ pub async fn load_workflow_messages_paginated(
     let offset = offset.unwrap_or(0);
 
     // Get total count
-    let count_query = format!(
-        "SELECT count() FROM message WHERE workflow_id = '{}' GROUP ALL",
-        validated_workflow_id
-    );
-    let count_result: Vec<serde_json::Value> =
-        state.db.query(&count_query).await.unwrap_or_default();
+    let count_result: Vec<serde_json::Value> = state
+        .db
+        .query_with_params(
+            "SELECT count() FROM message WHERE workflow_id = $workflow_id GROUP ALL",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
+        .await
+        .unwrap_or_default();
 
     let total = count_result
         .first()
@@ -238,8 +324,7 @@ pub async fn load_workflow_messages_paginated(
         .unwrap_or(0) as u32;
 
     // Load paginated messages
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             role,
@@ -251,20 +336,36 @@ pub async fn load_workflow_messages_paginated(
             provider,
             cost_usd,
             duration_ms,
+            context_usage,
+            behavior_position,
+            generation,
+            pinned,
+            bookmarked,
+            reactions,
             timestamp
         FROM message
-        WHERE workflow_id = '{}'
+        WHERE workflow_id = $workflow_id
         ORDER BY timestamp ASC
-        LIMIT {} START {}"#,
-        validated_workflow_id, limit, offset
-    );
-
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load paginated messages");
-        format!("Failed to load paginated messages: {}", e)
-    })?;
+        LIMIT $limit START $offset"#;
+    let params = vec![
+        (
+            "workflow_id".to_string(),
+            serde_json::json!(validated_workflow_id),
+        ),
+        ("limit".to_string(), serde_json::json!(limit)),
+        ("offset".to_string(), serde_json::json!(offset)),
+    ];
+
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load paginated messages");
+            format!("Failed to load paginated messages: {}", e)
+        })?;
 
-    let messages: Vec<Message> = json_results
+    let mut messages: Vec<Message> = json_results
         .into_iter()
         .map(serde_json::from_value)
         .collect::<std::result::Result<Vec<Message>, _>>()
@@ -272,6 +373,10 @@ pub async fn load_workflow_messages_paginated(
             error!(error = %e, "Failed to deserialize messages");
             format!("Failed to deserialize messages: {}", e)
         })?;
+    messages
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    load_message_attachments(&state.db, &validated_workflow_id, &mut messages).await;
 
     let has_more = offset + (messages.len() as u32) < total;
 
@@ -309,11 +414,33 @@ pub async fn delete_message(message_id: String, state: State<'_, AppState>) -> R
         format!("Invalid message_id: {}", e)
     })?;
 
-    // Use execute() with DELETE query to avoid SurrealDB SDK serialization issues
+    // Attachment blobs and records go with the message
+    let attachments = load_attachments_of_message(&state.db, &validated_id)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to load message attachments");
+            Vec::new()
+        });
+    state.attachments.delete(&attachments).await;
+    if let Err(e) = state
+        .db
+        .execute_with_params(
+            "DELETE attachment WHERE message_id = $message_id",
+            vec![("message_id".to_string(), serde_json::json!(validated_id))],
+        )
+        .await
+    {
+        warn!(error = %e, "Failed to delete message attachments");
+    }
+
+    // Use a DELETE query to avoid SurrealDB SDK serialization issues
     // (see CLAUDE.md - db.delete() has issues with table:id format)
     state
         .db
-        .execute(&format!("DELETE message:`{}`", validated_id))
+        .execute_with_params(
+            "DELETE type::thing('message', $id)",
+            vec![("id".to_string(), serde_json::json!(validated_id))],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to delete message");
@@ -346,12 +473,17 @@ pub async fn clear_workflow_messages(
     })?;
 
     // First count existing messages
-    let count_query = format!(
-        "SELECT count() FROM message WHERE workflow_id = '{}' GROUP ALL",
-        validated_workflow_id
-    );
-    let count_result: Vec<serde_json::Value> =
-        state.db.query(&count_query).await.unwrap_or_default();
+    let count_result: Vec<serde_json::Value> = state
+        .db
+        .query_with_params(
+            "SELECT count() FROM message WHERE workflow_id = $workflow_id GROUP ALL",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
+        .await
+        .unwrap_or_default();
 
     let count = count_result
         .first()
@@ -359,13 +491,21 @@ pub async fn clear_workflow_messages(
         .and_then(|c| c.as_u64())
         .unwrap_or(0);
 
-    // Delete all messages for the workflow
+    // Delete all messages of the workflow, with their attachments
+    state
+        .attachments
+        .delete_workflow(&validated_workflow_id)
+        .await;
     state
         .db
-        .execute(&format!(
-            "DELETE message WHERE workflow_id = '{}'",
-            validated_workflow_id
-        ))
+        .execute_with_params(
+            "DELETE attachment WHERE workflow_id = $workflow_id; \
+             DELETE message WHERE workflow_id = $workflow_id",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to clear workflow messages");
@@ -376,6 +516,264 @@ pub async fn clear_workflow_messages(
     Ok(count)
 }
 
+/// Fields selected when loading pinned and bookmarked messages
+const MESSAGE_SELECT_FIELDS: &str = "meta::id(id) AS id, workflow_id, role, content, tokens, \
+     tokens_input, tokens_output, model, provider, cost_usd, duration_ms, context_usage, \
+     behavior_position, generation, pinned, bookmarked, reactions, timestamp";
+
+/// Loads messages matching a condition, oldest first, with their contents revealed.
+async fn load_messages_where(
+    db: &DBClient,
+    condition: &str,
+    params: Vec<(String, serde_json::Value)>,
+) -> Result<Vec<Message>, String> {
+    let query = format!(
+        "SELECT {} FROM message WHERE {} ORDER BY timestamp ASC",
+        MESSAGE_SELECT_FIELDS, condition
+    );
+    let mut messages: Vec<Message> = db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load messages");
+            format!("Failed to load messages: {}", e)
+        })?
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<Vec<Message>, _>>()
+        .map_err(|e| format!("Failed to deserialize messages: {}", e))?;
+    messages
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    Ok(messages)
+}
+
+/// Loads the stored reactions of a message (error if it does not exist).
+async fn load_message_reactions(db: &DBClient, message_id: &str) -> Result<Vec<String>, String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT reactions FROM type::thing('message', $id)",
+            vec![("id".to_string(), serde_json::json!(message_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load message: {}", e))?;
+    let row = rows.first().ok_or("Message not found")?;
+    Ok(serde_json::from_value(row["reactions"].clone()).unwrap_or_default())
+}
+
+/// Sets the pinned or bookmarked flag of a message.
+async fn set_message_flag(
+    db: &DBClient,
+    message_id: &str,
+    flag: MessageFlag,
+    value: bool,
+) -> Result<(), String> {
+    let message_id = Validator::validate_uuid(message_id).map_err(|e| {
+        warn!(error = %e, "Invalid message_id");
+        format!("Invalid message_id: {}", e)
+    })?;
+    load_message_reactions(db, &message_id).await?;
+
+    let query = match flag {
+        MessageFlag::Pinned => "UPDATE type::thing('message', $id) SET pinned = $value",
+        MessageFlag::Bookmarked => "UPDATE type::thing('message', $id) SET bookmarked = $value",
+    };
+    db.execute_with_params(
+        query,
+        vec![
+            ("id".to_string(), serde_json::json!(message_id)),
+            ("value".to_string(), serde_json::json!(value)),
+        ],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to update message");
+        format!("Failed to update message: {}", e)
+    })
+}
+
+/// User marks stored as a boolean field of a message
+enum MessageFlag {
+    Pinned,
+    Bookmarked,
+}
+
+/// Pins or unpins a message.
+///
+/// Pinned messages are sent to the agent with every request of their
+/// workflow, even once they are older than the history sent to the model.
+///
+/// # Arguments
+/// * `message_id` - The message ID
+/// * `pinned` - Whether the message is pinned
+#[tauri::command]
+#[instrument(name = "pin_message", skip(state), fields(message_id = %message_id))]
+pub async fn pin_message(
+    message_id: String,
+    pinned: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    set_message_flag(&state.db, &message_id, MessageFlag::Pinned, pinned).await?;
+    info!(pinned, "Message pin updated");
+    Ok(())
+}
+
+/// Lists the pinned messages of a workflow, oldest first.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow ID
+#[tauri::command]
+#[instrument(name = "list_pinned_messages", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn list_pinned_messages(
+    workflow_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Message>, String> {
+    let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+    load_messages_where(
+        &state.db,
+        "workflow_id = $workflow_id AND pinned = true",
+        vec![("workflow_id".to_string(), serde_json::json!(workflow_id))],
+    )
+    .await
+}
+
+/// Bookmarks or unbookmarks a message.
+///
+/// # Arguments
+/// * `message_id` - The message ID
+/// * `bookmarked` - Whether the message is bookmarked
+#[tauri::command]
+#[instrument(name = "bookmark_message", skip(state), fields(message_id = %message_id))]
+pub async fn bookmark_message(
+    message_id: String,
+    bookmarked: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    set_message_flag(&state.db, &message_id, MessageFlag::Bookmarked, bookmarked).await?;
+    info!(bookmarked, "Message bookmark updated");
+    Ok(())
+}
+
+/// Lists the bookmarked messages, oldest first.
+///
+/// # Arguments
+/// * `workflow_id` - Only the bookmarks of this workflow (all workflows if None)
+#[tauri::command]
+#[instrument(name = "list_bookmarked_messages", skip(state))]
+pub async fn list_bookmarked_messages(
+    workflow_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Message>, String> {
+    match workflow_id {
+        Some(workflow_id) => {
+            let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+                warn!(error = %e, "Invalid workflow_id");
+                format!("Invalid workflow_id: {}", e)
+            })?;
+            load_messages_where(
+                &state.db,
+                "workflow_id = $workflow_id AND bookmarked = true",
+                vec![("workflow_id".to_string(), serde_json::json!(workflow_id))],
+            )
+            .await
+        }
+        None => load_messages_where(&state.db, "bookmarked = true", vec![]).await,
+    }
+}
+
+/// Adds a reaction to a message, or removes it if already present.
+///
+/// # Arguments
+/// * `message_id` - The message ID
+/// * `reaction` - Reaction (emoji)
+///
+/// # Returns
+/// The reactions of the message
+#[tauri::command]
+#[instrument(name = "toggle_message_reaction", skip(state), fields(message_id = %message_id))]
+pub async fn toggle_message_reaction(
+    message_id: String,
+    reaction: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let message_id = Validator::validate_uuid(&message_id).map_err(|e| {
+        warn!(error = %e, "Invalid message_id");
+        format!("Invalid message_id: {}", e)
+    })?;
+    let reaction = reaction.trim().to_string();
+    if reaction.is_empty() || reaction.len() > cmd_const::MAX_REACTION_LEN {
+        return Err(format!(
+            "Reaction must be 1 to {} bytes long",
+            cmd_const::MAX_REACTION_LEN
+        ));
+    }
+
+    let mut reactions = load_message_reactions(&state.db, &message_id).await?;
+    reactions = toggle_reaction(reactions, &reaction)?;
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('message', $id) SET reactions = $reactions",
+            vec![
+                ("id".to_string(), serde_json::json!(message_id)),
+                ("reactions".to_string(), serde_json::json!(reactions)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update message reactions");
+            format!("Failed to update message reactions: {}", e)
+        })?;
+
+    Ok(reactions)
+}
+
+/// Adds the reaction, or removes it if present.
+fn toggle_reaction(mut reactions: Vec<String>, reaction: &str) -> Result<Vec<String>, String> {
+    if let Some(index) = reactions.iter().position(|r| r == reaction) {
+        reactions.remove(index);
+    } else if reactions.len() >= cmd_const::MAX_MESSAGE_REACTIONS {
+        return Err(format!(
+            "A message has at most {} reactions",
+            cmd_const::MAX_MESSAGE_REACTIONS
+        ));
+    } else {
+        reactions.push(reaction.to_string());
+    }
+    Ok(reactions)
+}
+
+/// Pinned messages of a workflow that are not part of the history sent to
+/// the agent, formatted for its system prompt.
+pub(crate) async fn pinned_context(
+    db: &DBClient,
+    workflow_id: &str,
+    history: &[Message],
+) -> Option<String> {
+    let pinned = load_messages_where(
+        db,
+        "workflow_id = $workflow_id AND pinned = true",
+        vec![("workflow_id".to_string(), serde_json::json!(workflow_id))],
+    )
+    .await
+    .map_err(|e| warn!(error = %e, "Failed to load pinned messages"))
+    .ok()?;
+
+    // The most recent pins are kept when there are too many
+    let left_out: Vec<Message> = pinned
+        .into_iter()
+        .filter(|m| !history.iter().any(|h| h.id == m.id))
+        .collect();
+    let skip = left_out
+        .len()
+        .saturating_sub(wf_const::PINNED_CONTEXT_LIMIT);
+    format_pinned_context(&left_out[skip..], wf_const::MAX_PINNED_MESSAGE_CHARS)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -401,4 +799,121 @@ mod tests {
             assert!(!matches!(role, "user" | "assistant" | "system"));
         }
     }
+
+    #[tokio::test]
+    async fn test_context_usage_roundtrip() {
+        use crate::db::DBClient;
+        use chrono::Utc;
+        use tempfile::tempdir;
+
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("context_usage_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let usage = ContextUsage::new(Some(32000), 30000, 60, 50, Some(Utc::now()));
+        let mut assistant = MessageCreate::assistant(
+            "wf1".to_string(),
+            "Answer".to_string(),
+            Some(30000),
+            Some(200),
+            None,
+            None,
+            None,
+        );
+        assistant.context_usage = Some(usage.clone());
+        db.create("message", "m1", assistant).await.unwrap();
+        db.create(
+            "message",
+            "m2",
+            MessageCreate::user("wf1".to_string(), "Question".to_string()),
+        )
+        .await
+        .unwrap();
+
+        let rows = db
+            .query_json(
+                "SELECT meta::id(id) AS id, workflow_id, role, content, tokens, tokens_input, \
+                 tokens_output, model, provider, cost_usd, duration_ms, context_usage, timestamp \
+                 FROM message ORDER BY id",
+            )
+            .await
+            .unwrap();
+        let messages: Vec<Message> = rows
+            .into_iter()
+            .map(|v| serde_json::from_value(v).unwrap())
+            .collect();
+
+        assert_eq!(messages[0].context_usage, Some(usage));
+        assert!(messages[1].context_usage.is_none());
+    }
+
+    #[test]
+    fn test_toggle_reaction() {
+        let reactions = toggle_reaction(vec![], "👍").unwrap();
+        assert_eq!(reactions, vec!["👍".to_string()]);
+        let reactions = toggle_reaction(reactions, "🎉").unwrap();
+        assert_eq!(reactions.len(), 2);
+        let reactions = toggle_reaction(reactions, "👍").unwrap();
+        assert_eq!(reactions, vec!["🎉".to_string()]);
+
+        let full: Vec<String> = (0..cmd_const::MAX_MESSAGE_REACTIONS)
+            .map(|i| i.to_string())
+            .collect();
+        assert!(toggle_reaction(full.clone(), "new").is_err());
+        assert_eq!(
+            toggle_reaction(full, "0").unwrap().len(),
+            cmd_const::MAX_MESSAGE_REACTIONS - 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pinned_context() {
+        use crate::db::DBClient;
+        use tempfile::tempdir;
+
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("pinned_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let old_id = Uuid::new_v4().to_string();
+        let recent_id = Uuid::new_v4().to_string();
+        for (id, content) in [
+            (&old_id, "Use metric units"),
+            (&recent_id, "Recent question"),
+        ] {
+            db.create(
+                "message",
+                id,
+                MessageCreate::user("wf1".to_string(), content.to_string()),
+            )
+            .await
+            .unwrap();
+        }
+        assert!(pinned_context(&db, "wf1", &[]).await.is_none());
+        assert!(
+            set_message_flag(&db, &Uuid::new_v4().to_string(), MessageFlag::Pinned, true)
+                .await
+                .is_err()
+        );
+
+        for id in [&old_id, &recent_id] {
+            set_message_flag(&db, id, MessageFlag::Pinned, true)
+                .await
+                .unwrap();
+        }
+        let history = load_messages_where(
+            &db,
+            "meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(recent_id))],
+        )
+        .await
+        .unwrap();
+        assert!(history[0].pinned);
+
+        let context = pinned_context(&db, "wf1", &history).await.unwrap();
+        assert!(context.contains("Use metric units"));
+        assert!(!context.contains("Recent question"));
+    }
 }
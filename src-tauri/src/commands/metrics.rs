@@ -0,0 +1,95 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics export commands.
+//!
+//! The export settings are stored in the `settings:metrics` record and
+//! applied at startup (see [`crate::metrics::export`]).
+
+use crate::db::{queries, DBClient};
+use crate::metrics::export;
+use crate::models::metrics::{MetricsExportStatus, MetricsSettings};
+use crate::tools::constants::metrics::SETTINGS_KEY;
+use crate::AppState;
+use tauri::State;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Loads the metrics export settings, default (no export) when unset.
+pub async fn load_metrics_settings(db: &DBClient) -> MetricsSettings {
+    match queries::settings::load_config(db, SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No metrics settings found, using defaults");
+            MetricsSettings::default()
+        }
+    }
+}
+
+/// Gets the metrics export settings
+#[tauri::command]
+#[instrument(name = "get_metrics_settings", skip(state))]
+pub async fn get_metrics_settings(state: State<'_, AppState>) -> Result<MetricsSettings, String> {
+    Ok(load_metrics_settings(&state.db).await)
+}
+
+/// Updates the metrics export settings and (re)starts the exporters.
+///
+/// # Arguments
+/// * `settings` - The complete export settings to store
+///
+/// # Returns
+/// The running exporters
+#[tauri::command]
+#[instrument(name = "update_metrics_settings", skip(state, settings))]
+pub async fn update_metrics_settings(
+    settings: MetricsSettings,
+    state: State<'_, AppState>,
+) -> Result<MetricsExportStatus, String> {
+    info!("Updating metrics settings");
+
+    export::validate_settings(&settings).map_err(|e| {
+        warn!(error = %e, "Invalid metrics settings");
+        e
+    })?;
+
+    // Started before saving, so that an unavailable port is not stored
+    let status = export::apply_settings(&settings).await.map_err(|e| {
+        warn!(error = %e, "Failed to start metrics exporters");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize metrics settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save metrics settings");
+            format!("Failed to save metrics settings: {}", e)
+        })?;
+
+    info!("Metrics settings updated successfully");
+    Ok(status)
+}
+
+/// Gets the running metrics exporters (Prometheus endpoint URL, OTLP push)
+#[tauri::command]
+#[instrument(name = "get_metrics_export_status")]
+pub async fn get_metrics_export_status() -> Result<MetricsExportStatus, String> {
+    Ok(export::export_status().await)
+}
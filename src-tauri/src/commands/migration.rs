@@ -16,12 +16,24 @@
 //!
 //! Provides Tauri commands for running database migrations,
 //! particularly for the Memory Tool vector search schema.
-
+//!
+//! The HNSW index only accepts vectors of its dimension: after switching the
+//! embedding provider, `migrate_embedding_dimension` rebuilds the index with
+//! the new dimension and re-embeds the affected memories in the background.
+
+use crate::db::DBClient;
+use crate::models::streaming::events;
+use crate::tools::constants::memory as mem_constants;
+use crate::tools::memory;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use serde_json::json;
+use tauri::{AppHandle, Emitter, State};
 use tracing::{error, info, instrument, warn};
 
+/// Name of the memory vector index
+const MEMORY_VECTOR_INDEX: &str = "memory_vec_idx";
+
 /// Result of a migration operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MigrationResult {
@@ -198,7 +210,7 @@ pub async fn get_memory_schema_status(
         with_embeddings,
         without_embeddings: total_memories.saturating_sub(with_embeddings),
         with_workflow_id,
-        hnsw_dimension: 1024, // Current schema dimension
+        hnsw_dimension: memory_index_dimension(&state.db).await?.unwrap_or_default(),
     })
 }
 
@@ -318,6 +330,185 @@ pub async fn migrate_mcp_http_schema(
     })
 }
 
+/// Reads the dimension of an HNSW index definition.
+fn parse_index_dimension(definition: &str) -> Option<usize> {
+    let (_, rest) = definition.split_once("DIMENSION ")?;
+    rest.split_whitespace().next()?.parse().ok()
+}
+
+/// Gets the dimension of the memory vector index (None if it does not exist).
+pub(crate) async fn memory_index_dimension(db: &DBClient) -> Result<Option<usize>, String> {
+    let info: Vec<serde_json::Value> = db
+        .query_json("INFO FOR TABLE memory")
+        .await
+        .map_err(|e| format!("Failed to read memory table info: {}", e))?;
+    Ok(info
+        .first()
+        .and_then(|table| table["indexes"][MEMORY_VECTOR_INDEX].as_str())
+        .and_then(parse_index_dimension))
+}
+
+/// Counts the stored embeddings whose length differs from `dimension`.
+async fn count_mismatched_embeddings(db: &DBClient, dimension: usize) -> Result<usize, String> {
+    let rows: Vec<serde_json::Value> = db
+        .query_json_with_params(
+            "SELECT count() FROM memory WHERE embedding IS NOT NONE \
+             AND array::len(embedding) != $dimension GROUP ALL",
+            vec![("dimension".to_string(), json!(dimension))],
+        )
+        .await
+        .map_err(|e| format!("Failed to count mismatched embeddings: {}", e))?;
+    Ok(rows
+        .first()
+        .and_then(|row| row["count"].as_u64())
+        .unwrap_or(0) as usize)
+}
+
+/// Rebuilds the memory vector index with a new dimension.
+///
+/// Embeddings of another length are cleared and their memories marked
+/// `pending`, as are the memories whose embedding failed (vectors rejected by
+/// the old index). Returns the number of memories to embed again.
+pub(crate) async fn rebuild_memory_index(db: &DBClient, dimension: usize) -> Result<usize, String> {
+    if dimension == 0 || dimension > mem_constants::MAX_EMBEDDING_DIMENSION {
+        return Err(format!(
+            "Invalid embedding dimension {} (1-{})",
+            dimension,
+            mem_constants::MAX_EMBEDDING_DIMENSION
+        ));
+    }
+    let affected = count_mismatched_embeddings(db, dimension).await?;
+
+    // DIMENSION cannot be bound as a parameter; the value is a validated integer
+    let query = format!(
+        "REMOVE INDEX IF EXISTS {index} ON TABLE memory;\n\
+         UPDATE memory SET embedding = NONE, embedding_status = 'pending' \
+         WHERE embedding IS NOT NONE AND array::len(embedding) != $dimension;\n\
+         UPDATE memory SET embedding_status = 'pending' \
+         WHERE embedding IS NONE AND embedding_status = 'failed';\n\
+         DEFINE INDEX {index} ON memory FIELDS embedding HNSW DIMENSION {dimension} DIST COSINE;",
+        index = MEMORY_VECTOR_INDEX,
+        dimension = dimension
+    );
+    db.execute_with_params(&query, vec![("dimension".to_string(), json!(dimension))])
+        .await
+        .map_err(|e| format!("Failed to rebuild memory vector index: {}", e))?;
+
+    let rebuilt = memory_index_dimension(db).await?;
+    if rebuilt != Some(dimension) {
+        return Err(format!(
+            "Memory vector index was not rebuilt (dimension {:?}, expected {})",
+            rebuilt, dimension
+        ));
+    }
+    info!(dimension, affected, "Memory vector index rebuilt");
+    Ok(affected)
+}
+
+/// Compares the memory vector index with the embedding service dimension.
+///
+/// A mismatch makes every new embedding fail: the settings screen then offers
+/// `migrate_embedding_dimension`.
+#[tauri::command]
+#[instrument(name = "get_embedding_dimension_status", skip(state))]
+pub async fn get_embedding_dimension_status(
+    state: State<'_, AppState>,
+) -> Result<EmbeddingDimensionStatus, String> {
+    let index_dimension = memory_index_dimension(&state.db).await?;
+    let service_dimension = match state.embedding_service.read().await.as_ref() {
+        Some(service) => Some(service.dimension().await),
+        None => None,
+    };
+    let mismatched_embeddings = match service_dimension {
+        Some(dimension) => count_mismatched_embeddings(&state.db, dimension).await?,
+        None => 0,
+    };
+    let needs_migration = service_dimension
+        .is_some_and(|dimension| index_dimension != Some(dimension) || mismatched_embeddings > 0);
+    if needs_migration {
+        warn!(
+            ?index_dimension,
+            ?service_dimension,
+            mismatched_embeddings,
+            "Embedding dimension mismatch"
+        );
+    }
+
+    Ok(EmbeddingDimensionStatus {
+        index_dimension,
+        service_dimension,
+        mismatched_embeddings,
+        needs_migration,
+    })
+}
+
+/// Migrates the memory vector index to the dimension of the embedding service.
+///
+/// The dimension is measured with a test embedding, the index is rebuilt,
+/// then the affected memories are embedded again in the background with
+/// `embedding_migration_progress` events. An interrupted re-embedding can be
+/// continued with `regenerate_embeddings(resume = true)`.
+#[tauri::command]
+#[instrument(name = "migrate_embedding_dimension", skip(app, state))]
+pub async fn migrate_embedding_dimension(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<MigrationResult, String> {
+    let service = state
+        .embedding_service
+        .read()
+        .await
+        .clone()
+        .ok_or_else(|| {
+            "Embedding service not configured. Please save embedding settings first.".to_string()
+        })?;
+    let dimension = service
+        .test_connection()
+        .await
+        .map_err(|e| format!("Failed to measure embedding dimension: {}", e))?;
+
+    info!(dimension, "Starting embedding dimension migration");
+    let cleared = rebuild_memory_index(&state.db, dimension).await?;
+    let progress = memory::start_regeneration(&state.db, None, true).await?;
+    let to_embed = progress.total;
+    info!(cleared, to_embed, "Re-embedding memories without embedding");
+
+    let db = state.db.clone();
+    tokio::spawn(async move {
+        let result = memory::regenerate_embeddings(&db, &service, progress, |progress| {
+            if let Err(e) = app.emit(events::EMBEDDING_MIGRATION_PROGRESS, progress) {
+                warn!(error = %e, "Failed to emit migration progress");
+            }
+        })
+        .await;
+        if let Err(e) = result {
+            error!(error = %e, "Embedding migration re-embedding interrupted");
+        }
+    });
+
+    Ok(MigrationResult {
+        success: true,
+        message: format!(
+            "Vector index rebuilt with {} dimensions. {} memories are being embedded again.",
+            dimension, to_embed
+        ),
+        records_affected: to_embed,
+    })
+}
+
+/// Embedding dimension status of the memory vector index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingDimensionStatus {
+    /// Dimension of the HNSW index (None if missing)
+    pub index_dimension: Option<usize>,
+    /// Dimension of the configured embedding service
+    pub service_dimension: Option<usize>,
+    /// Stored embeddings whose length differs from the service dimension
+    pub mismatched_embeddings: usize,
+    /// Whether the index must be rebuilt
+    pub needs_migration: bool,
+}
+
 /// Memory schema status information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemorySchemaStatus {
@@ -369,6 +560,75 @@ mod tests {
         assert!(json.contains("\"hnsw_dimension\":1024"));
     }
 
+    #[test]
+    fn test_parse_index_dimension() {
+        assert_eq!(
+            parse_index_dimension(
+                "DEFINE INDEX memory_vec_idx ON memory FIELDS embedding HNSW DIMENSION 768 DIST COSINE TYPE F64"
+            ),
+            Some(768)
+        );
+        assert_eq!(
+            parse_index_dimension("DEFINE INDEX memory_workflow_idx ON memory FIELDS workflow_id"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_memory_index() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("migration_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        assert_eq!(memory_index_dimension(&db).await.unwrap(), Some(1024));
+
+        for (id, embedding, status) in [
+            ("embedded", json!(vec![0.1_f32; 1024]), "ready"),
+            ("rejected", serde_json::Value::Null, "failed"),
+        ] {
+            db.execute_with_params(
+                "CREATE type::thing('memory', $id) CONTENT { type: 'knowledge', content: $id, \
+                 metadata: {}, embedding: $embedding, embedding_status: $status }",
+                vec![
+                    ("id".to_string(), json!(id)),
+                    ("embedding".to_string(), embedding),
+                    ("status".to_string(), json!(status)),
+                ],
+            )
+            .await
+            .unwrap();
+        }
+
+        assert!(rebuild_memory_index(&db, 0).await.is_err());
+        assert_eq!(rebuild_memory_index(&db, 768).await.unwrap(), 1);
+        assert_eq!(memory_index_dimension(&db).await.unwrap(), Some(768));
+
+        let rows = db
+            .query_json(
+                "SELECT meta::id(id) AS id, embedding IS NOT NONE AS embedded, embedding_status \
+                 FROM memory ORDER BY id",
+            )
+            .await
+            .unwrap();
+        for row in &rows {
+            assert_eq!(row["embedded"], false);
+            assert_eq!(row["embedding_status"], "pending");
+        }
+
+        // Vectors of the new dimension are accepted, and a schema
+        // initialization keeps the migrated index
+        db.execute_with_params(
+            "UPDATE type::thing('memory', 'embedded') SET embedding = $embedding",
+            vec![("embedding".to_string(), json!(vec![0.1_f32; 768]))],
+        )
+        .await
+        .unwrap();
+        db.initialize_schema().await.unwrap();
+        assert_eq!(memory_index_dimension(&db).await.unwrap(), Some(768));
+    }
+
     #[test]
     fn test_migration_sql_contains_required_changes() {
         // Verify migration SQL contains all required changes
@@ -25,26 +25,62 @@
 //! - `execute_workflow` - Execute workflow with message
 //! - `load_workflows` - List all workflows
 //! - `delete_workflow` - Delete workflow by ID
+//! - `set_workflow_language` - Pin or clear the response language of a workflow
+//! - `load_workflows_paginated` - Page of workflows sorted and filtered by status, tag, agent or creation date
+//! - `set_workflow_tags` - Set the tags of a workflow
+//! - `set_workflow_sensitive` - Mark a workflow as sensitive (encrypted contents) or regular
+//! - `unlock_workflow` / `lock_workflow` - Open or close a sensitive workflow
+//!
+//! ### Workflow Title Commands ([`workflow_title`])
+//! - `regenerate_title` - Generate the workflow name again from its first exchange
+//!
+//! ### Workflow Tag Commands ([`workflow_tag`])
+//! - `list_workflow_tags` - List the tag catalog with the number of workflows per tag
+//! - `create_workflow_tag` / `rename_workflow_tag` / `delete_workflow_tag` - Manage catalog tags
+//! - `set_workflow_tag_color` - Set or clear the display color of a tag
+//! - `add_workflow_tags` / `remove_workflow_tags` - Tag or untag several workflows at once
+//!
+//! ### Workflow Snapshot Commands ([`workflow_snapshot`])
+//! - `create_workflow_snapshot` - Render a read-only HTML snapshot, optionally served on localhost
+//! - `stop_workflow_snapshot_server` - Stop the temporary snapshot server of a workflow
 //!
 //! ### Agent Commands ([`agent`])
 //! - `list_agents` - List registered agent IDs
 //! - `get_agent_config` - Get agent configuration
+//! - `get_agent_tool_usage` - Get per-agent tool usage statistics
+//! - `reset_agent_tool_usage` - Clear per-agent tool usage statistics
+//! - `get_agent_tool_acknowledgments` - Get the powerful tools acknowledged for an agent
+//! - `revoke_agent_tool_acknowledgment` - Revoke acknowledged tools of an agent
+//!
+//! ### Behavior Changelog Commands ([`behavior_changelog`])
+//! - `list_behavior_changes` - List the changes affecting agent behavior (prompt, model, tools, MCP)
 //!
 //! ### Security Commands ([`security`])
 //! - `save_api_key` - Securely store API key
 //! - `get_api_key` - Retrieve stored API key
 //! - `delete_api_key` - Remove stored API key
+//! - `rotate_api_key` - Replace a stored API key and re-initialize providers
+//! - `list_api_key_metadata` - List stored API keys with age and expiry status
 //!
 //! ### LLM Commands ([`llm`])
 //! - `get_llm_config` - Get current LLM configuration
 //! - `configure_mistral` - Configure Mistral provider
+//! - `configure_gemini` - Configure Gemini provider
 //! - `configure_ollama` - Configure Ollama provider
 //! - `set_active_provider` - Set active LLM provider
 //! - `set_default_model` - Set default model for provider
 //! - `get_available_models` - Get available models
 //! - `test_ollama_connection` - Test Ollama connectivity
+//! - `get_local_llm_status` - Loaded Ollama models, VRAM usage and host memory
+//! - `check_local_model_fit` - Warn when a local model exceeds the available memory
 //! - `test_mistral_connection` - Test Mistral connectivity
 //! - `test_llm_completion` - Test LLM completion
+//! - `get_llm_retry_settings` - Get the retry policy for transient LLM failures
+//! - `update_llm_retry_settings` - Update the retry policy for transient LLM failures
+//! - `get_gemini_safety_settings` - Get the safety settings sent to Gemini
+//! - `update_gemini_safety_settings` - Update the safety settings sent to Gemini
+//! - `get_utility_model_settings` - Get the model of internal summarization passes
+//! - `update_utility_model_settings` - Update the model of internal summarization passes
 //!
 //! ### Model Commands ([`models`])
 //! - `list_models` - List all LLM models (builtin + custom)
@@ -55,6 +91,7 @@
 //! - `get_provider_settings` - Get provider settings
 //! - `update_provider_settings` - Update provider settings
 //! - `test_provider_connection` - Test provider connection
+//! - `refresh_provider_models` - Upsert models from the provider model lists
 //! - `seed_builtin_models` - Seed database with builtin models
 //!
 //! ### Validation Commands ([`validation`])
@@ -65,6 +102,32 @@
 //! - `reject_validation` - Reject a validation request
 //! - `delete_validation` - Delete a validation request
 //!
+//! ### Token Anomaly Commands ([`token_anomaly`])
+//! - `get_token_anomaly_settings` - Get token usage anomaly detection settings
+//! - `update_token_anomaly_settings` - Update token usage anomaly detection settings
+//!
+//! ### Watchdog Commands ([`watchdog`])
+//! - `get_watchdog_settings` - Get the stuck tool loop watchdog settings
+//! - `update_watchdog_settings` - Update the stuck tool loop watchdog settings
+//!
+//! ### Inbox Commands ([`inbox`])
+//! - `get_pending_items` - List everything awaiting the user, most urgent first
+//!
+//! ### Search Commands ([`search`])
+//! - `search_messages` - Full-text search of message contents, with highlighted excerpts
+//! - `search_workflows` - Full-text search of workflow names
+//!
+//! ### Transcript Commands ([`transcript`])
+//! - `export_workflow_transcript` - Render a workflow to Markdown, HTML or PDF
+//!
+//! ### Locale Commands ([`locale`])
+//! - `get_locale_settings` - Get the date/number format locale
+//! - `update_locale_settings` - Update the date/number format locale
+//!
+//! ### Workspace Defaults Commands ([`workspace_defaults`])
+//! - `get_workspace_defaults` - Get default tools and MCP servers for new agents
+//! - `update_workspace_defaults` - Update default tools and MCP servers for new agents
+//!
 //! ### Memory Commands ([`memory`])
 //! - `add_memory` - Add memory entry
 //! - `list_memories` - List memories with optional filter
@@ -73,27 +136,99 @@
 //! - `search_memories` - Search memories by text
 //! - `clear_memories_by_type` - Clear all memories of a type
 //!
+//! ### Knowledge Pack Commands ([`knowledge_pack`])
+//! - `list_knowledge_packs` - List knowledge packs with their documents
+//! - `create_knowledge_pack` / `update_knowledge_pack` / `delete_knowledge_pack` - Manage packs
+//! - `add_knowledge_document` - Add (or replace) a document, split into embedded chunks
+//! - `import_knowledge_document` - Import a PDF, Markdown or text file as a document
+//! - `remove_knowledge_document` - Remove a document from a pack
+//! - `set_knowledge_pack_memories` - Set the memories curated into a pack
+//! - `attach_knowledge_pack` / `detach_knowledge_pack` - Attach a pack to an agent or detach it
+//! - `list_agent_knowledge_packs` / `set_agent_knowledge_packs` - Packs (collections) attached to an agent
+//! - `search_agent_knowledge` - Preview the chunks retrieved for an agent and a query
+//!
 //! ### Streaming Commands ([`streaming`])
 //! - `execute_workflow_streaming` - Execute workflow with real-time events
 //! - `cancel_workflow_streaming` - Cancel streaming execution
 //!
+//! ### Checkpoint Commands ([`checkpoint`])
+//! - `list_resumable_executions` - List executions interrupted by a crash
+//! - `resume_workflow_execution` - Resume an interrupted execution from its last checkpoint
+//! - `discard_workflow_checkpoint` - Discard an interrupted execution
+//!
 //! ### Message Commands ([`message`]) - Phase 6
-//! - `save_message` - Persist a message to the database
+//! - `save_message` - Persist a message (with image attachments) to the database
 //! - `load_workflow_messages` - Load all messages for a workflow
 //! - `load_workflow_messages_paginated` - Load messages with pagination
 //! - `delete_message` - Delete a single message
 //! - `clear_workflow_messages` - Delete all messages for a workflow
+//! - `pin_message` / `list_pinned_messages` - Pin a message (always sent to the agent) and list the pins of a workflow
+//! - `bookmark_message` / `list_bookmarked_messages` - Bookmark a message and list the bookmarks
+//! - `toggle_message_reaction` - Add or remove a reaction on a message
+//!
+//! ### Attachment Commands ([`attachment`])
+//! - `get_attachment_data` - Get a message attachment as a data URL
+//!
+//! ### Settings Sync Commands ([`settings_sync`])
+//! - `create_sync_pairing` - Create a pairing code (text and QR code) for another machine
+//! - `accept_sync_pairing` - Pair with the code of another machine
+//! - `list_sync_pairings` - List the pairings of this machine
+//! - `sync_settings_now` - Exchange agents, prompts and MCP servers with the paired machine
+//! - `delete_sync_pairing` - Delete a pairing and its key
+//!
+//! ### Schedule Commands ([`schedule`])
+//! - `create_schedule` - Schedule a prompt with an agent in a workflow on a cron expression
+//! - `list_schedules` - List schedules with their next run and last outcome
+//! - `pause_schedule` / `resume_schedule` - Pause a schedule or resume it from now
+//! - `delete_schedule` - Delete a schedule (its workflow is kept)
+//!
+//! ### Job Commands ([`job`])
+//! - `enqueue_workflow` - Execute a workflow in the background (resumed after a restart)
+//! - `get_job_status` - Get the state of a background job
+//!
+//! ### Speech Commands ([`speech`])
+//! - `transcribe_audio` - Transcribe a voice message with the local Whisper server
+//! - `get_read_aloud` - Get the plain text and SSML version of a message for text-to-speech
+//! - `get_speech_settings` - Get the transcription server settings
+//! - `update_speech_settings` - Update the transcription server settings
+//!
+//! ### Text-to-Speech Commands ([`tts`])
+//! - `synthesize_speech` - Speak a reply, streaming audio as `speech_audio` events
+//! - `stop_speech` - Stop a running synthesis
+//! - `get_tts_settings` - Get the text-to-speech settings
+//! - `update_tts_settings` - Update the text-to-speech backend, voice and speaking rate
+//! - `get_workflow_voice` - Get the voice settings of a workflow
+//! - `set_workflow_voice` - Set (or clear) the voice settings of a workflow
+//!
+//! ### Team Usage Commands ([`usage_stats`])
+//! - `export_team_usage` - Generate noised weekly usage and tool statistics for a team
 //!
 //! ### Tool Execution Commands ([`tool_execution`]) - Phase 3
 //! - `save_tool_execution` - Persist a tool execution log
 //! - `load_workflow_tool_executions` - Load all tool executions for a workflow
+//! - `load_workflow_tool_executions_page` - Load a page of tool executions (cursor, summary mode)
+//! - `get_tool_execution` - Load a single tool execution with its payloads
 //! - `load_message_tool_executions` - Load tool executions for a message
 //! - `delete_tool_execution` - Delete a single tool execution
 //! - `clear_workflow_tool_executions` - Delete all tool executions for a workflow
 //!
+//! ### Execution Trace Commands ([`execution_trace`])
+//! - `get_execution_trace` - Get the tool loop graph of an assistant message
+//! - `export_workflow_trace` - Export the timeline of a workflow (LLM calls, tool calls, iterations) as a Jaeger JSON trace
+//!
+//! ### Audit Log Commands ([`audit`])
+//! - `query_audit_log` - Load audit log entries of sensitive actions
+//! - `export_audit_log` - Export audit log entries to JSON Lines or CSV
+//!
+//! ### Persistence Outbox Commands ([`outbox`])
+//! - `flush_outbox` - Write the records queued after failed persistence writes
+//! - `get_outbox_pending` - Count the records waiting in the outbox
+//!
 //! ### Thinking Step Commands ([`thinking`]) - Phase 4
 //! - `save_thinking_step` - Persist a thinking/reasoning step
 //! - `load_workflow_thinking_steps` - Load all thinking steps for a workflow
+//! - `load_workflow_thinking_steps_page` - Load a page of thinking steps (cursor, summary mode)
+//! - `get_thinking_step` - Load a single thinking step with its full content
 //! - `load_message_thinking_steps` - Load thinking steps for a message
 //! - `delete_thinking_step` - Delete a single thinking step
 //! - `clear_workflow_thinking_steps` - Delete all thinking steps for a workflow
@@ -123,10 +258,60 @@
 //! - `stop_mcp_server` - Stop a running MCP server
 //! - `list_mcp_tools` - List available tools from a server
 //! - `call_mcp_tool` - Execute a tool on an MCP server
+//! - `begin_mcp_oauth` - Start the OAuth authorization of an HTTP server
+//! - `complete_mcp_oauth` - Complete the OAuth authorization with the code
+//! - `list_mcp_resources` - List resources exposed by a server
+//! - `read_mcp_resource` - Read the contents of a resource
+//! - `list_mcp_prompts` - List prompts exposed by servers as library prompts
+//! - `get_mcp_prompt` - Render an MCP prompt with argument values
+//! - `get_mcp_server_status_history` - Status transitions of an MCP server
+//!
+//! ### External Database Commands ([`external_db`])
+//! - `list_external_databases` - List the databases queried by the SqlTool
+//! - `save_external_database` / `delete_external_database` - Manage a database and its connection string
+//! - `test_external_database` - Check that a database accepts connections
+//!
+//! ### Email Account Commands ([`email`])
+//! - `list_email_accounts` - List the accounts used by the EmailTool
+//! - `save_email_account` / `delete_email_account` - Manage an account and its password
+//! - `test_email_account` - Check that an account can log in to its servers
+//!
+//! ### News Feed Commands ([`feed`])
+//! - `list_feeds` - List the feeds read by the RssTool
+//! - `add_feed` / `delete_feed` - Manage a feed (checked by downloading it)
+//! - `create_feed_digest` - Schedule a daily digest posted into a dedicated workflow
+//!
+//! ### Desktop Access Commands ([`desktop_access`])
+//! - `get_desktop_access_settings` / `update_desktop_access_settings` - Clipboard, screen capture and local document permissions of agents
+//!
+//! ### OCR Commands ([`ocr`])
+//! - `get_ocr_settings` / `update_ocr_settings` - OCR engine and default language packs of the OcrTool
+//! - `list_ocr_languages` - List the language packs installed for tesseract
+//!
+//! ### Plugin Commands ([`plugin`])
+//! - `list_plugins` - List the WASM tool plugins installed in `~/.zileo/plugins`
+//! - `install_plugin` - Install a plugin and grant it capabilities
+//! - `set_plugin_permissions` - Enable, disable or re-approve a plugin and set its capabilities
+//! - `uninstall_plugin` - Remove a plugin
+//!
+//! ### Custom Tool Commands ([`custom_tool`])
+//! - `list_custom_tools` - List the tools defined as Rhai scripts
+//! - `create_custom_tool` - Create a custom tool from a script and an input schema
+//! - `update_custom_tool` - Update the description, schema or script of a custom tool, or disable it
+//! - `delete_custom_tool` - Delete a custom tool
+//! - `test_custom_tool` - Run a script on a sample input
+//!
+//! ### Macro Tool Commands ([`macro_tool`])
+//! - `list_macro_tools` - List the tool sequences defined as macros
+//! - `create_macro_tool` - Create a macro from its steps and an input schema
+//! - `update_macro_tool` - Update the description, schema or steps of a macro, or disable it
+//! - `delete_macro_tool` - Delete a macro
 //!
 //! ### Migration Commands ([`migration`])
 //! - `migrate_memory_schema` - Migrate memory table for vector search
 //! - `get_memory_schema_status` - Get memory schema status
+//! - `get_embedding_dimension_status` - Detect a vector index dimension mismatch
+//! - `migrate_embedding_dimension` - Rebuild the vector index and re-embed memories
 //!
 //! ### Embedding Commands ([`embedding`])
 //! - `get_embedding_config` - Get current embedding configuration
@@ -135,7 +320,8 @@
 //! - `update_memory` - Update an existing memory entry
 //! - `export_memories` - Export memories to JSON/CSV
 //! - `import_memories` - Import memories from JSON
-//! - `regenerate_embeddings` - Regenerate embeddings for existing memories
+//! - `regenerate_embeddings` - Regenerate embeddings in batches (resumable)
+//! - `get_regeneration_checkpoint` - Progress of an interrupted regeneration
 //!
 //! ### Prompt Commands ([`prompt`])
 //! - `list_prompts` - List all prompt templates
@@ -144,36 +330,104 @@
 //! - `update_prompt` - Update an existing prompt
 //! - `delete_prompt` - Delete a prompt template
 //! - `search_prompts` - Search prompts by query and/or category
+//! - `render_prompt` - Render a prompt with variable values
+//! - `launch_prompt` - Render a prompt and queue it as a workflow job
+//! - `list_prompt_versions` - List the version history of a prompt
+//! - `compare_prompt_versions` - Run two prompt versions side by side with an agent
 //!
 //! ### User Question Commands ([`user_question`])
 //! - `submit_user_response` - Submit user's answer to a pending question
 //! - `get_pending_questions` - Get all pending questions for a workflow
 //! - `skip_question` - Skip a question (user chooses not to answer)
 //!
+//! ### Startup Commands ([`startup`])
+//! - `get_startup_status` - Progress of the deferred startup phases
+//!
+//! ### Profile Commands ([`profile`])
+//! - `list_profiles` - List the profiles and the active one
+//! - `create_profile` - Create a profile with an isolated database
+//! - `switch_profile` - Reopen the database of another profile and reload its data
+//!
+//! ### Config File Commands ([`config_file`])
+//! - `get_effective_config` - Settings in effect and those overridden by `~/.zileo/config.toml`
+//!
+//! ### Metrics Commands ([`metrics`])
+//! - `get_metrics_settings` - Get the Prometheus endpoint and OTLP push settings
+//! - `update_metrics_settings` - Update the export settings and (re)start the exporters
+//! - `get_metrics_export_status` - Get the running exporters
+//!
+//! ### Proxy Commands ([`proxy`])
+//! - `get_proxy_settings` - Get the proxy of the provider, embedding and MCP requests
+//! - `update_proxy_settings` - Update the global proxy, its `no_proxy` hosts and per-target overrides
+//!
+//! ### Diagnostics Commands ([`diagnostics`])
+//! - `generate_diagnostics` - Write a zip of recent logs, schema version, provider/MCP health, DB stats and sanitized config
+//!
+//! ### Command Palette Commands ([`action`])
+//! - `search_actions` - Fuzzy search of the invocable actions registry
+//!
 //! ## Input Validation
 //!
 //! All commands validate inputs using the [`crate::security::Validator`]
 //! to prevent injection attacks and ensure data integrity.
 
+pub mod action;
 pub mod agent;
+pub mod attachment;
+pub mod audit;
+pub mod behavior_changelog;
+pub mod checkpoint;
+pub mod config_file;
 pub mod custom_provider;
+pub mod custom_tool;
+pub mod desktop_access;
+pub mod diagnostics;
+pub mod email;
 pub mod embedding;
+pub mod execution_trace;
+pub mod external_db;
+pub mod feed;
 pub mod import_export;
+pub mod inbox;
+pub mod job;
+pub mod knowledge_pack;
 pub mod llm;
+pub mod locale;
+pub mod macro_tool;
 pub mod mcp;
 pub mod memory;
 pub mod message;
+pub mod metrics;
 pub mod migration;
 pub mod models;
+pub mod ocr;
+pub mod outbox;
+pub mod plugin;
+pub mod profile;
 pub mod prompt;
+pub mod proxy;
+pub mod schedule;
+pub mod search;
 pub mod security;
+pub mod settings_sync;
+pub mod speech;
+pub mod startup;
 pub mod streaming;
 pub mod sub_agent_execution;
 pub mod task;
 pub mod thinking;
+pub mod token_anomaly;
 pub mod tool_execution;
+pub mod transcript;
+pub mod tts;
+pub mod usage_stats;
 pub mod user_question;
 pub mod validation;
+pub mod watchdog;
 pub mod workflow;
+pub mod workflow_snapshot;
+pub mod workflow_tag;
+pub mod workflow_title;
+pub mod workspace_defaults;
 
 pub use security::SecureKeyStore;
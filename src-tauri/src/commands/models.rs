@@ -29,30 +29,117 @@
 //!
 //! ## Connection Commands
 //! - `test_provider_connection` - Test connection to a provider
+//! - `refresh_provider_models` - Upsert models from the provider model lists
 //!
 //! ## Seed Commands
 //! - `seed_builtin_models` - Seed the database with builtin models
 
 use chrono::Utc;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use tauri::State;
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
 use crate::commands::security::SecureKeyStore;
+use crate::db::DBClient;
 use crate::models::llm_models::{
-    get_all_builtin_models, ConnectionTestResult, CreateModelRequest, LLMModel, ProviderSettings,
-    ProviderType, UpdateModelRequest,
+    get_all_builtin_models, ConnectionTestResult, CreateModelRequest, LLMModel, ModelMetadata,
+    ModelMetadataDrift, ModelRefreshReport, ProviderSettings, ProviderType, UpdateModelRequest,
 };
 use crate::state::AppState;
 use crate::tools::constants::{commands as cmd_const, query_limits};
 
+/// Fields selected for an [`LLMModel`] (prices default to 0 on older records)
+pub(crate) const MODEL_SELECT_FIELDS: &str =
+    "meta::id(id) AS id, provider, name, api_name, context_window, \
+     max_output_tokens, temperature_default, is_builtin, is_reasoning, \
+     (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
+     (output_price_per_mtok ?? 0.0) AS output_price_per_mtok, \
+     supports_vision, supports_tools, (metadata_drift ?? []) AS metadata_drift, \
+     metadata_refreshed_at, created_at, updated_at";
+
+/// Inserts a model record.
+async fn insert_model(db: &DBClient, model: &LLMModel) -> anyhow::Result<()> {
+    db.execute_with_params(
+        "CREATE type::thing('llm_model', $id) SET provider = $provider, name = $name, \
+         api_name = $api_name, context_window = $context_window, \
+         max_output_tokens = $max_output_tokens, temperature_default = $temperature_default, \
+         is_builtin = $is_builtin, is_reasoning = $is_reasoning, \
+         input_price_per_mtok = $input_price_per_mtok, \
+         output_price_per_mtok = $output_price_per_mtok, \
+         created_at = time::now(), updated_at = time::now()",
+        vec![
+            ("id".to_string(), json!(model.id)),
+            ("provider".to_string(), json!(model.provider.to_string())),
+            ("name".to_string(), json!(model.name)),
+            ("api_name".to_string(), json!(model.api_name)),
+            ("context_window".to_string(), json!(model.context_window)),
+            (
+                "max_output_tokens".to_string(),
+                json!(model.max_output_tokens),
+            ),
+            (
+                "temperature_default".to_string(),
+                json!(model.temperature_default),
+            ),
+            ("is_builtin".to_string(), json!(model.is_builtin)),
+            ("is_reasoning".to_string(), json!(model.is_reasoning)),
+            (
+                "input_price_per_mtok".to_string(),
+                json!(model.input_price_per_mtok),
+            ),
+            (
+                "output_price_per_mtok".to_string(),
+                json!(model.output_price_per_mtok),
+            ),
+        ],
+    )
+    .await
+}
+
+/// Returns whether another model of the provider already uses `api_name`.
+async fn api_name_taken(
+    db: &DBClient,
+    provider: &impl std::fmt::Display,
+    api_name: &str,
+    exclude_id: Option<&str>,
+) -> Result<bool, String> {
+    let filter = if exclude_id.is_some() {
+        " AND id != type::thing('llm_model', $exclude_id)"
+    } else {
+        ""
+    };
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT count() AS count FROM llm_model \
+                 WHERE provider = $provider AND api_name = $api_name{} GROUP ALL",
+                filter
+            ),
+            vec![
+                ("provider".to_string(), json!(provider.to_string())),
+                ("api_name".to_string(), json!(api_name)),
+                ("exclude_id".to_string(), json!(exclude_id)),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to check duplicate: {}", e))?;
+    Ok(rows
+        .first()
+        .and_then(|row| row.get("count"))
+        .and_then(|count| count.as_i64())
+        .unwrap_or(0)
+        > 0)
+}
+
 // ============================================================================
 // Validation Helpers
 // ============================================================================
 
 /// Validates a model ID string.
-fn validate_model_id(id: &str) -> Result<(), String> {
+pub(crate) fn validate_model_id(id: &str) -> Result<(), String> {
     if id.trim().is_empty() {
         return Err("Model ID is required".into());
     }
@@ -66,7 +153,7 @@ fn validate_model_id(id: &str) -> Result<(), String> {
 }
 
 /// Validates a provider string.
-fn validate_provider_string(provider: &str) -> Result<ProviderType, String> {
+pub(crate) fn validate_provider_string(provider: &str) -> Result<ProviderType, String> {
     provider
         .parse::<ProviderType>()
         .map_err(|_| format!("Invalid provider '{}'", provider))
@@ -113,42 +200,33 @@ pub async fn list_models(
     // Build query based on filter
     // Use ?? (null coalescing) for pricing fields to handle existing records without these fields
     // Add LIMIT to prevent memory explosion (OPT-DB-8)
-    let query = if let Some(ref pt) = provider_filter {
-        format!(
-            "SELECT meta::id(id) AS id, provider, name, api_name, context_window, \
-             max_output_tokens, temperature_default, is_builtin, is_reasoning, \
-             (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
-             (output_price_per_mtok ?? 0.0) AS output_price_per_mtok, \
-             created_at, updated_at \
-             FROM llm_model WHERE provider = '{}' LIMIT {}",
-            pt,
-            query_limits::DEFAULT_MODELS_LIMIT
-        )
+    let filter = if provider_filter.is_some() {
+        "WHERE provider = $provider "
     } else {
-        format!(
-            "SELECT meta::id(id) AS id, provider, name, api_name, context_window, \
-             max_output_tokens, temperature_default, is_builtin, is_reasoning, \
-             (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
-             (output_price_per_mtok ?? 0.0) AS output_price_per_mtok, \
-             created_at, updated_at \
-             FROM llm_model LIMIT {}",
-            query_limits::DEFAULT_MODELS_LIMIT
-        )
+        ""
     };
+    let query = format!(
+        "SELECT {} FROM llm_model {}LIMIT $limit",
+        MODEL_SELECT_FIELDS, filter
+    );
+    let params = vec![
+        (
+            "provider".to_string(),
+            json!(provider_filter.map(|pt| pt.to_string())),
+        ),
+        (
+            "limit".to_string(),
+            json!(query_limits::DEFAULT_MODELS_LIMIT),
+        ),
+    ];
 
     let result: Vec<LLMModel> = state
         .db
-        .db
-        .query(&query)
+        .query_with_params(&query, params)
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to query models");
             format!("Database error: {}", e)
-        })?
-        .take(0)
-        .map_err(|e| {
-            error!(error = %e, "Failed to deserialize models");
-            format!("Failed to deserialize models: {}", e)
         })?;
 
     info!(count = result.len(), "Models retrieved");
@@ -181,29 +259,20 @@ pub async fn get_model(id: String, state: State<'_, AppState>) -> Result<LLMMode
     // Query by record ID directly (llm_model:uuid)
     // Use ?? (null coalescing) for pricing fields to handle existing records without these fields
     let query = format!(
-        "SELECT meta::id(id) AS id, provider, name, api_name, context_window, \
-         max_output_tokens, temperature_default, is_builtin, is_reasoning, \
-         (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
-         (output_price_per_mtok ?? 0.0) AS output_price_per_mtok, \
-         created_at, updated_at \
-         FROM llm_model:`{}`",
-        id
+        "SELECT {} FROM type::thing('llm_model', $id)",
+        MODEL_SELECT_FIELDS
     );
+    let params = vec![("id".to_string(), json!(id))];
 
-    let mut result: Vec<LLMModel> = state
-        .db
-        .db
-        .query(&query)
-        .await
-        .map_err(|e| {
-            error!(error = %e, "Failed to query model");
-            format!("Database error: {}", e)
-        })?
-        .take(0)
-        .map_err(|e| {
-            error!(error = %e, "Failed to deserialize model");
-            format!("Failed to deserialize model: {}", e)
-        })?;
+    let mut result: Vec<LLMModel> =
+        state
+            .db
+            .query_with_params(&query, params)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to query model");
+                format!("Database error: {}", e)
+            })?;
 
     result.pop().ok_or_else(|| {
         warn!(model_id = %id, "Model not found");
@@ -243,29 +312,23 @@ pub async fn get_model_by_api_name(
     let provider_lower = provider.to_lowercase();
 
     let query = format!(
-        "SELECT meta::id(id) AS id, provider, name, api_name, context_window, \
-         max_output_tokens, temperature_default, is_builtin, is_reasoning, \
-         (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
-         (output_price_per_mtok ?? 0.0) AS output_price_per_mtok, \
-         created_at, updated_at \
-         FROM llm_model WHERE api_name = '{}' AND provider = '{}'",
-        api_name, provider_lower
+        "SELECT {} FROM llm_model WHERE api_name = $api_name AND provider = $provider",
+        MODEL_SELECT_FIELDS
     );
+    let params = vec![
+        ("api_name".to_string(), json!(api_name)),
+        ("provider".to_string(), json!(provider_lower)),
+    ];
 
-    let mut result: Vec<LLMModel> = state
-        .db
-        .db
-        .query(&query)
-        .await
-        .map_err(|e| {
-            error!(error = %e, "Failed to query model by api_name");
-            format!("Database error: {}", e)
-        })?
-        .take(0)
-        .map_err(|e| {
-            error!(error = %e, "Failed to deserialize model");
-            format!("Failed to deserialize model: {}", e)
-        })?;
+    let mut result: Vec<LLMModel> =
+        state
+            .db
+            .query_with_params(&query, params)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to query model by api_name");
+                format!("Database error: {}", e)
+            })?;
 
     result.pop().ok_or_else(|| {
         warn!(api_name = %api_name, provider = %provider, "Model not found");
@@ -308,29 +371,11 @@ pub async fn create_model(
     );
 
     // Check for duplicate api_name
-    let check_query = format!(
-        "SELECT count() FROM llm_model WHERE provider = '{}' AND api_name = '{}' GROUP ALL",
-        data.provider, data.api_name
-    );
-
-    let count_result: Option<serde_json::Value> = state
-        .db
-        .db
-        .query(&check_query)
-        .await
-        .map_err(|e| format!("Database error: {}", e))?
-        .take(0)
-        .map_err(|e| format!("Failed to check duplicate: {}", e))?;
-
-    if let Some(val) = count_result {
-        if let Some(count) = val.get("count").and_then(|v| v.as_i64()) {
-            if count > 0 {
-                return Err(format!(
-                    "Model with api_name '{}' already exists for provider {}",
-                    data.api_name, data.provider
-                ));
-            }
-        }
+    if api_name_taken(&state.db, &data.provider, &data.api_name, None).await? {
+        return Err(format!(
+            "Model with api_name '{}' already exists for provider {}",
+            data.api_name, data.provider
+        ));
     }
 
     // Generate ID and timestamps
@@ -340,37 +385,7 @@ pub async fn create_model(
     // Create the model from request
     let model = LLMModel::from_create_request(model_id.clone(), &data);
 
-    // Insert into database using raw query (SurrealDB SDK 2.x workaround)
-    let insert_query = format!(
-        "CREATE llm_model:`{}` CONTENT {{ \
-            id: '{}', \
-            provider: '{}', \
-            name: '{}', \
-            api_name: '{}', \
-            context_window: {}, \
-            max_output_tokens: {}, \
-            temperature_default: {}, \
-            is_builtin: false, \
-            is_reasoning: {}, \
-            input_price_per_mtok: {}, \
-            output_price_per_mtok: {}, \
-            created_at: time::now(), \
-            updated_at: time::now() \
-        }}",
-        model_id,
-        model_id,
-        model.provider,
-        model.name.replace('\'', "''"),
-        model.api_name.replace('\'', "''"),
-        model.context_window,
-        model.max_output_tokens,
-        model.temperature_default,
-        model.is_reasoning,
-        model.input_price_per_mtok,
-        model.output_price_per_mtok
-    );
-
-    state.db.execute(&insert_query).await.map_err(|e| {
+    insert_model(&state.db, &model).await.map_err(|e| {
         error!(error = %e, "Failed to create model");
         format!("Failed to create model: {}", e)
     })?;
@@ -390,6 +405,10 @@ pub async fn create_model(
         is_reasoning: model.is_reasoning,
         input_price_per_mtok: model.input_price_per_mtok,
         output_price_per_mtok: model.output_price_per_mtok,
+        supports_vision: None,
+        supports_tools: None,
+        metadata_drift: Vec::new(),
+        metadata_refreshed_at: None,
         created_at: now,
         updated_at: now,
     })
@@ -437,64 +456,83 @@ pub async fn update_model(
 
     info!(is_builtin = existing.is_builtin, "Updating model");
 
-    // Build SET clause dynamically
-    let mut set_parts: Vec<String> = vec!["updated_at = time::now()".to_string()];
+    // Build SET clause dynamically (values bound as parameters)
+    let mut set_parts = vec!["updated_at = time::now()"];
+    let mut params = vec![("id".to_string(), json!(id))];
+    let mut set = |field: &'static str, clause: &'static str, value: serde_json::Value| {
+        set_parts.push(clause);
+        params.push((field.to_string(), value));
+    };
 
     if let Some(ref name) = data.name {
-        set_parts.push(format!("name = '{}'", name.replace('\'', "''")));
+        set("name", "name = $name", json!(name));
     }
     if let Some(ref api_name) = data.api_name {
         // Check for duplicate api_name
-        let check_query = format!(
-            "SELECT count() FROM llm_model WHERE provider = '{}' AND api_name = '{}' AND id != '{}' GROUP ALL",
-            existing.provider, api_name, id
-        );
-        let count_result: Option<serde_json::Value> = state
-            .db
-            .db
-            .query(&check_query)
-            .await
-            .map_err(|e| format!("Database error: {}", e))?
-            .take(0)
-            .map_err(|e| format!("Failed to check duplicate: {}", e))?;
-
-        if let Some(val) = count_result {
-            if let Some(count) = val.get("count").and_then(|v| v.as_i64()) {
-                if count > 0 {
-                    return Err(format!(
-                        "Model with api_name '{}' already exists for provider {}",
-                        api_name, existing.provider
-                    ));
-                }
-            }
+        if api_name_taken(&state.db, &existing.provider, api_name, Some(&id)).await? {
+            return Err(format!(
+                "Model with api_name '{}' already exists for provider {}",
+                api_name, existing.provider
+            ));
         }
-        set_parts.push(format!("api_name = '{}'", api_name.replace('\'', "''")));
+        set("api_name", "api_name = $api_name", json!(api_name));
     }
     if let Some(ctx) = data.context_window {
-        set_parts.push(format!("context_window = {}", ctx));
+        set(
+            "context_window",
+            "context_window = $context_window",
+            json!(ctx),
+        );
     }
     if let Some(max_out) = data.max_output_tokens {
-        set_parts.push(format!("max_output_tokens = {}", max_out));
+        set(
+            "max_output_tokens",
+            "max_output_tokens = $max_output_tokens",
+            json!(max_out),
+        );
     }
     if let Some(temp) = data.temperature_default {
-        set_parts.push(format!("temperature_default = {}", temp));
+        set(
+            "temperature_default",
+            "temperature_default = $temperature_default",
+            json!(temp),
+        );
     }
     if let Some(is_reasoning) = data.is_reasoning {
-        set_parts.push(format!("is_reasoning = {}", is_reasoning));
+        set(
+            "is_reasoning",
+            "is_reasoning = $is_reasoning",
+            json!(is_reasoning),
+        );
     }
     if let Some(input_price) = data.input_price_per_mtok {
-        set_parts.push(format!("input_price_per_mtok = {}", input_price));
+        set(
+            "input_price_per_mtok",
+            "input_price_per_mtok = $input_price_per_mtok",
+            json!(input_price),
+        );
     }
     if let Some(output_price) = data.output_price_per_mtok {
-        set_parts.push(format!("output_price_per_mtok = {}", output_price));
+        set(
+            "output_price_per_mtok",
+            "output_price_per_mtok = $output_price_per_mtok",
+            json!(output_price),
+        );
     }
 
-    let update_query = format!("UPDATE llm_model:`{}` SET {}", id, set_parts.join(", "));
+    let update_query = format!(
+        "UPDATE type::thing('llm_model', $id) SET {}",
+        set_parts.join(", ")
+    );
 
-    state.db.execute(&update_query).await.map_err(|e| {
-        error!(error = %e, "Failed to update model");
-        format!("Failed to update model: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(&update_query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update model");
+            format!("Failed to update model: {}", e)
+        })?;
 
     info!("Model updated");
 
@@ -535,12 +573,14 @@ pub async fn delete_model(id: String, state: State<'_, AppState>) -> Result<bool
 
     info!("Deleting model");
 
-    let delete_query = format!("DELETE llm_model:`{}`", id);
-
-    state.db.execute(&delete_query).await.map_err(|e| {
-        error!(error = %e, "Failed to delete model");
-        format!("Failed to delete model: {}", e)
-    })?;
+    state
+        .db
+        .delete(&format!("llm_model:{}", id))
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete model");
+            format!("Failed to delete model: {}", e)
+        })?;
 
     info!("Model deleted");
     Ok(true)
@@ -579,32 +619,27 @@ pub async fn get_provider_settings(
     info!("Getting provider settings");
 
     // Query by record ID (provider_settings:mistral or provider_settings:ollama)
-    let query = format!(
-        "SELECT provider, enabled, default_model_id, base_url, updated_at \
-         FROM provider_settings:`{}`",
-        provider_type
-    );
-
     let result: Option<ProviderSettings> = state
         .db
-        .db
-        .query(&query)
+        .query_with_params(
+            "SELECT provider, enabled, default_model_id, base_url, updated_at \
+             FROM type::thing('provider_settings', $provider)",
+            vec![("provider".to_string(), json!(provider_type.to_string()))],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to query provider settings");
             format!("Database error: {}", e)
         })?
-        .take(0)
-        .map_err(|e| {
-            error!(error = %e, "Failed to deserialize settings");
-            format!("Failed to deserialize settings: {}", e)
-        })?;
+        .into_iter()
+        .next();
 
     info!(found = result.is_some(), "Provider settings query result");
 
     // Check if API key is configured (using the secure keystore)
     let api_key_configured = match &provider_type {
         ProviderType::Mistral => keystore.has_key("Mistral"),
+        ProviderType::Gemini => keystore.has_key("Gemini"),
         ProviderType::Custom(name) => keystore.has_key(name),
         ProviderType::Ollama => false, // Ollama doesn't need API key
     };
@@ -676,45 +711,29 @@ pub async fn update_provider_settings(
 
     // Build SET clause with null coalescing (??) to preserve existing values
     // or use defaults for new records
-    let mut set_parts: Vec<String> = vec![
-        format!("provider = '{}'", provider_type),
-        "updated_at = time::now()".to_string(),
+    // Provided values are bound; missing ones keep the stored value
+    let upsert_query = "UPSERT type::thing('provider_settings', $provider) SET \
+                        provider = $provider, updated_at = time::now(), \
+                        enabled = $enabled ?? enabled ?? true, \
+                        default_model_id = $default_model_id ?? default_model_id, \
+                        base_url = $base_url ?? base_url";
+    let params = vec![
+        ("provider".to_string(), json!(provider_type.to_string())),
+        ("enabled".to_string(), json!(enabled)),
+        ("default_model_id".to_string(), json!(default_model_id)),
+        ("base_url".to_string(), json!(base_url)),
     ];
 
-    // For enabled: use provided value, keep existing, or default to true
-    if let Some(en) = enabled {
-        set_parts.push(format!("enabled = {}", en));
-    } else {
-        set_parts.push("enabled = enabled ?? true".to_string());
-    }
-
-    // For default_model_id: use provided value or keep existing
-    if let Some(ref model_id) = default_model_id {
-        set_parts.push(format!("default_model_id = '{}'", model_id));
-    } else {
-        set_parts.push("default_model_id = default_model_id".to_string());
-    }
-
-    // For base_url: use provided value or keep existing
-    if let Some(ref url) = base_url {
-        set_parts.push(format!("base_url = '{}'", url.replace('\'', "''")));
-    } else {
-        set_parts.push("base_url = base_url".to_string());
-    }
-
-    // Upsert: create if not exists, update if exists
-    let upsert_query = format!(
-        "UPSERT provider_settings:`{}` SET {}",
-        provider_type,
-        set_parts.join(", ")
-    );
-
     info!(query = %upsert_query, "Executing UPSERT query");
 
-    state.db.execute(&upsert_query).await.map_err(|e| {
-        error!(error = %e, "Failed to update provider settings");
-        format!("Failed to update settings: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(upsert_query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update provider settings");
+            format!("Failed to update settings: {}", e)
+        })?;
 
     info!("Provider settings updated successfully");
 
@@ -729,6 +748,7 @@ pub async fn update_provider_settings(
 ///
 /// For Ollama: Tests the `/api/version` endpoint.
 /// For Mistral: Tests by making a simple API request.
+/// For Gemini: Configures the stored API key and lists the models.
 ///
 /// # Arguments
 ///
@@ -791,7 +811,7 @@ pub async fn test_provider_connection(
             };
 
             // Test Mistral by making a models list request
-            let client = reqwest::Client::builder()
+            let client = crate::proxy::client_builder("mistral")
                 .timeout(std::time::Duration::from_secs(10))
                 .build()
                 .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -828,6 +848,47 @@ pub async fn test_provider_connection(
                 }
             }
         }
+        ProviderType::Gemini => {
+            let api_key = match keystore.get_key("Gemini") {
+                Some(key) => key,
+                None => {
+                    return Ok(ConnectionTestResult::failure(
+                        provider_type,
+                        "API key not configured".into(),
+                    ));
+                }
+            };
+
+            // Pick up a key saved since startup
+            if let Err(e) = state.llm_manager.configure_gemini(&api_key).await {
+                return Ok(ConnectionTestResult::failure(
+                    provider_type,
+                    format!("Configuration failed: {}", e),
+                ));
+            }
+
+            match state.llm_manager.gemini().test_connection().await {
+                Ok(true) => {
+                    let latency = start.elapsed().as_millis() as u64;
+                    info!(latency_ms = latency, "Gemini connection successful");
+                    Ok(ConnectionTestResult::success(provider_type, latency, None))
+                }
+                Ok(false) => {
+                    warn!("Gemini API rejected the connection test");
+                    Ok(ConnectionTestResult::failure(
+                        provider_type,
+                        "Gemini API rejected the API key".into(),
+                    ))
+                }
+                Err(e) => {
+                    warn!(error = %e, "Gemini connection failed");
+                    Ok(ConnectionTestResult::failure(
+                        provider_type,
+                        format!("Connection failed: {}", e),
+                    ))
+                }
+            }
+        }
         ProviderType::Custom(ref name) => {
             let name = name.clone();
             match state.llm_manager.get_custom_provider(&name).await {
@@ -862,6 +923,255 @@ pub async fn test_provider_connection(
     }
 }
 
+// ============================================================================
+// Model Refresh Commands
+// ============================================================================
+
+/// Builtin values that differ from the ones reported by the provider API.
+fn metadata_drift(model: &LLMModel, metadata: &ModelMetadata) -> Vec<ModelMetadataDrift> {
+    [
+        (
+            "context_window",
+            model.context_window,
+            metadata.context_window,
+        ),
+        (
+            "max_output_tokens",
+            model.max_output_tokens,
+            metadata.max_output_tokens,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(field, stored, reported)| {
+        reported
+            .filter(|reported| *reported != stored)
+            .map(|reported| ModelMetadataDrift {
+                field: field.to_string(),
+                stored,
+                reported,
+            })
+    })
+    .collect()
+}
+
+/// Custom model created for a model discovered on the provider API.
+///
+/// Unreported limits get defaults; reported ones are clamped to the accepted bounds.
+fn discovered_model(provider: &ProviderType, metadata: &ModelMetadata) -> LLMModel {
+    let name: String = metadata
+        .name
+        .as_deref()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or(&metadata.api_name)
+        .chars()
+        .take(cmd_const::MAX_MODEL_NAME_LEN)
+        .collect();
+    let request = CreateModelRequest {
+        provider: provider.clone(),
+        name,
+        api_name: metadata.api_name.clone(),
+        context_window: metadata
+            .context_window
+            .unwrap_or(cmd_const::DISCOVERED_MODEL_CONTEXT_WINDOW)
+            .clamp(
+                cmd_const::MIN_MODEL_CONTEXT_WINDOW,
+                cmd_const::MAX_MODEL_CONTEXT_WINDOW,
+            ),
+        max_output_tokens: metadata
+            .max_output_tokens
+            .unwrap_or(cmd_const::DISCOVERED_MODEL_MAX_OUTPUT)
+            .clamp(cmd_const::MIN_MAX_TOKENS, cmd_const::MAX_MAX_TOKENS),
+        temperature_default: 0.7,
+        is_reasoning: false,
+        input_price_per_mtok: 0.0,
+        output_price_per_mtok: 0.0,
+    };
+    LLMModel::from_create_request(Uuid::new_v4().to_string(), &request)
+}
+
+/// Writes the refreshed metadata of a model.
+///
+/// `updated_at` only moves when a value changed.
+async fn store_model_metadata(
+    db: &DBClient,
+    model: &LLMModel,
+    changed: bool,
+) -> anyhow::Result<()> {
+    db.execute_with_params(
+        "UPDATE type::thing('llm_model', $id) SET context_window = $context_window, \
+         max_output_tokens = $max_output_tokens, supports_vision = $supports_vision, \
+         supports_tools = $supports_tools, metadata_drift = $metadata_drift, \
+         metadata_refreshed_at = time::now(), \
+         updated_at = IF $changed THEN time::now() ELSE updated_at END",
+        vec![
+            ("id".to_string(), json!(model.id)),
+            ("context_window".to_string(), json!(model.context_window)),
+            (
+                "max_output_tokens".to_string(),
+                json!(model.max_output_tokens),
+            ),
+            ("supports_vision".to_string(), json!(model.supports_vision)),
+            ("supports_tools".to_string(), json!(model.supports_tools)),
+            ("metadata_drift".to_string(), json!(model.metadata_drift)),
+            ("changed".to_string(), json!(changed)),
+        ],
+    )
+    .await
+}
+
+/// Upserts the models reported by a provider API.
+///
+/// - Unknown models are created as custom models.
+/// - Custom models take the reported limits and capabilities.
+/// - Builtin models keep their limits: differing values are recorded in
+///   `metadata_drift` and the model is listed in the report's `drifted`.
+pub(crate) async fn apply_model_metadata(
+    db: &DBClient,
+    provider: &ProviderType,
+    metadata: &[ModelMetadata],
+) -> Result<ModelRefreshReport, String> {
+    let existing: Vec<LLMModel> = db
+        .query_with_params(
+            &format!(
+                "SELECT {} FROM llm_model WHERE provider = $provider",
+                MODEL_SELECT_FIELDS
+            ),
+            vec![("provider".to_string(), json!(provider.to_string()))],
+        )
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    let by_api_name: HashMap<&str, &LLMModel> = existing
+        .iter()
+        .map(|model| (model.api_name.as_str(), model))
+        .collect();
+
+    let mut report = ModelRefreshReport {
+        provider: provider.clone(),
+        discovered: metadata.len(),
+        created: 0,
+        updated: 0,
+        drifted: Vec::new(),
+        error: None,
+    };
+
+    for reported in metadata {
+        if reported.api_name.len() > cmd_const::MAX_MODEL_ID_LEN {
+            warn!(api_name = %reported.api_name, "Skipping model with an overlong API name");
+            continue;
+        }
+
+        let (mut model, created) = match by_api_name.get(reported.api_name.as_str()) {
+            Some(model) => ((*model).clone(), false),
+            None => {
+                let model = discovered_model(provider, reported);
+                insert_model(db, &model).await.map_err(|e| {
+                    error!(error = %e, api_name = %model.api_name, "Failed to create discovered model");
+                    format!("Failed to create model {}: {}", model.api_name, e)
+                })?;
+                (model, true)
+            }
+        };
+
+        let before = model.clone();
+        model.supports_vision = reported.supports_vision.or(model.supports_vision);
+        model.supports_tools = reported.supports_tools.or(model.supports_tools);
+        if model.is_builtin {
+            model.metadata_drift = metadata_drift(&model, reported);
+            if !model.metadata_drift.is_empty() {
+                report.drifted.push(model.id.clone());
+            }
+        } else if !created {
+            let bounded = discovered_model(provider, reported);
+            if reported.context_window.is_some() {
+                model.context_window = bounded.context_window;
+            }
+            if reported.max_output_tokens.is_some() {
+                model.max_output_tokens = bounded.max_output_tokens;
+            }
+        }
+
+        let changed = model.context_window != before.context_window
+            || model.max_output_tokens != before.max_output_tokens
+            || model.supports_vision != before.supports_vision
+            || model.supports_tools != before.supports_tools
+            || model.metadata_drift != before.metadata_drift;
+        store_model_metadata(db, &model, changed)
+            .await
+            .map_err(|e| format!("Failed to update model {}: {}", model.id, e))?;
+
+        if created {
+            report.created += 1;
+        } else if changed {
+            report.updated += 1;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Refreshes the models of the configured providers from their model list APIs.
+///
+/// Queries each provider for its chat models and their metadata (context
+/// window, output limit, vision and tool support) and upserts the `llm_model`
+/// records. Builtin models whose metadata drifted from the API are flagged
+/// instead of being overwritten.
+///
+/// # Arguments
+///
+/// * `provider` - Provider to refresh (all configured providers if None)
+///
+/// # Returns
+///
+/// One [`ModelRefreshReport`] per provider. A provider whose model list cannot
+/// be fetched reports the error without failing the others.
+#[tauri::command]
+#[instrument(name = "refresh_provider_models", skip(state), fields(provider))]
+pub async fn refresh_provider_models(
+    provider: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ModelRefreshReport>, String> {
+    let providers = match provider {
+        Some(ref p) => {
+            tracing::Span::current().record("provider", p.as_str());
+            vec![validate_provider_string(p)?]
+        }
+        None => state
+            .llm_manager
+            .get_configured_providers()
+            .iter()
+            .filter_map(|p| p.to_string().parse::<ProviderType>().ok())
+            .collect(),
+    };
+
+    info!(count = providers.len(), "Refreshing provider models");
+
+    let mut reports = Vec::with_capacity(providers.len());
+    for provider in providers {
+        let llm_provider = provider
+            .to_string()
+            .parse::<crate::llm::ProviderType>()
+            .map_err(|e| e.to_string())?;
+        let report = match state.llm_manager.list_model_metadata(&llm_provider).await {
+            Ok(metadata) => apply_model_metadata(&state.db, &provider, &metadata).await?,
+            Err(e) => {
+                warn!(provider = %provider, error = %e, "Failed to list provider models");
+                ModelRefreshReport::failure(provider, e.to_string())
+            }
+        };
+        info!(
+            provider = %report.provider,
+            discovered = report.discovered,
+            created = report.created,
+            updated = report.updated,
+            drifted = report.drifted.len(),
+            "Provider models refreshed"
+        );
+        reports.push(report);
+    }
+
+    Ok(reports)
+}
+
 // ============================================================================
 // Seed Commands
 // ============================================================================
@@ -882,68 +1192,34 @@ pub async fn test_provider_connection(
 #[instrument(name = "seed_builtin_models", skip(state))]
 pub async fn seed_builtin_models(state: State<'_, AppState>) -> Result<usize, String> {
     info!("Seeding builtin models");
+    insert_missing_builtin_models(&state.db).await
+}
 
+/// Inserts the builtin models missing from the database.
+///
+/// Shared by the `seed_builtin_models` command and the deferred startup.
+/// Returns the number of models inserted.
+pub async fn insert_missing_builtin_models(db: &DBClient) -> Result<usize, String> {
     let models = get_all_builtin_models();
+
+    // One query for all existing builtin models instead of one per model
+    let existing: HashSet<String> = db
+        .query_json("SELECT meta::id(id) AS id FROM llm_model WHERE is_builtin = true")
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .iter()
+        .filter_map(|row| row.get("id").and_then(|v| v.as_str()).map(String::from))
+        .collect();
+
     let mut inserted = 0;
 
-    for model in &models {
-        // Check if model already exists
-        let check_query = format!(
-            "SELECT count() FROM llm_model WHERE id = '{}' GROUP ALL",
-            model.id
-        );
-        let count_result: Vec<serde_json::Value> = state
-            .db
-            .db
-            .query(&check_query)
-            .await
-            .map_err(|e| format!("Database error: {}", e))?
-            .take(0)
-            .unwrap_or_default();
-
-        let exists = count_result
-            .first()
-            .and_then(|v| v.get("count").and_then(|c| c.as_i64()))
-            .unwrap_or(0)
-            > 0;
-
-        if !exists {
-            let insert_query = format!(
-                "CREATE llm_model:`{}` CONTENT {{ \
-                    id: '{}', \
-                    provider: '{}', \
-                    name: '{}', \
-                    api_name: '{}', \
-                    context_window: {}, \
-                    max_output_tokens: {}, \
-                    temperature_default: {}, \
-                    is_builtin: true, \
-                    is_reasoning: {}, \
-                    input_price_per_mtok: {}, \
-                    output_price_per_mtok: {}, \
-                    created_at: time::now(), \
-                    updated_at: time::now() \
-                }}",
-                model.id,
-                model.id,
-                model.provider,
-                model.name.replace('\'', "''"),
-                model.api_name.replace('\'', "''"),
-                model.context_window,
-                model.max_output_tokens,
-                model.temperature_default,
-                model.is_reasoning,
-                model.input_price_per_mtok,
-                model.output_price_per_mtok
-            );
-
-            state.db.execute(&insert_query).await.map_err(|e| {
-                error!(error = %e, model_id = %model.id, "Failed to insert builtin model");
-                format!("Failed to insert model {}: {}", model.id, e)
-            })?;
+    for model in models.iter().filter(|m| !existing.contains(&m.id)) {
+        insert_model(db, model).await.map_err(|e| {
+            error!(error = %e, model_id = %model.id, "Failed to insert builtin model");
+            format!("Failed to insert model {}: {}", model.id, e)
+        })?;
 
-            inserted += 1;
-        }
+        inserted += 1;
     }
 
     info!(
@@ -1053,4 +1329,84 @@ mod tests {
         assert!(validate_provider_string("routerlab").is_ok()); // Custom providers accepted
         assert!(validate_provider_string("").is_err()); // Empty rejected
     }
+
+    // ========================================================================
+    // Model Refresh Tests
+    // ========================================================================
+
+    #[tokio::test]
+    async fn test_apply_model_metadata() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("models_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        insert_missing_builtin_models(&db).await.unwrap();
+
+        let metadata = vec![
+            // Builtin with a larger context window than stored
+            ModelMetadata {
+                api_name: "gemini-2.5-pro".to_string(),
+                name: Some("Gemini 2.5 Pro".to_string()),
+                context_window: Some(2_097_152),
+                max_output_tokens: Some(65_536),
+                supports_vision: None,
+                supports_tools: Some(true),
+            },
+            // New model with out of bounds limits
+            ModelMetadata {
+                api_name: "gemini-3-flash".to_string(),
+                name: None,
+                context_window: Some(4_000_000),
+                max_output_tokens: Some(100),
+                supports_vision: Some(true),
+                supports_tools: Some(true),
+            },
+        ];
+        let report = apply_model_metadata(&db, &ProviderType::Gemini, &metadata)
+            .await
+            .unwrap();
+        assert_eq!(report.discovered, 2);
+        assert_eq!(report.created, 1);
+        assert_eq!(report.updated, 1);
+        assert_eq!(report.drifted, vec!["gemini-2.5-pro".to_string()]);
+
+        let models: Vec<LLMModel> = db
+            .query(&format!(
+                "SELECT {} FROM llm_model WHERE provider = 'gemini'",
+                MODEL_SELECT_FIELDS
+            ))
+            .await
+            .unwrap();
+        let model = |api_name: &str| models.iter().find(|m| m.api_name == api_name).unwrap();
+
+        let pro = model("gemini-2.5-pro");
+        assert_eq!(pro.context_window, 1_048_576);
+        assert_eq!(pro.supports_tools, Some(true));
+        assert_eq!(
+            pro.metadata_drift,
+            vec![ModelMetadataDrift {
+                field: "context_window".to_string(),
+                stored: 1_048_576,
+                reported: 2_097_152,
+            }]
+        );
+        assert!(pro.metadata_refreshed_at.is_some());
+
+        let flash = model("gemini-3-flash");
+        assert!(!flash.is_builtin);
+        assert_eq!(flash.name, "gemini-3-flash");
+        assert_eq!(flash.context_window, 2_000_000);
+        assert_eq!(flash.max_output_tokens, 256);
+        assert_eq!(flash.supports_vision, Some(true));
+
+        // Unreported models untouched, and a second refresh changes nothing
+        assert!(model("gemini-2.5-flash").metadata_refreshed_at.is_none());
+        let again = apply_model_metadata(&db, &ProviderType::Gemini, &metadata)
+            .await
+            .unwrap();
+        assert_eq!((again.created, again.updated), (0, 0));
+        assert_eq!(again.drifted, vec!["gemini-2.5-pro".to_string()]);
+    }
 }
@@ -0,0 +1,203 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OCR settings commands.
+//!
+//! The engine and the default language packs are stored in the `settings:ocr`
+//! record and read by the OcrTool on each call (see [`crate::documents::ocr`]).
+
+use crate::commands::models::validate_model_id;
+use crate::db::{queries, DBClient};
+use crate::documents::ocr;
+use crate::models::ocr::OcrSettings;
+use crate::state::AppState;
+use crate::tools::constants::ocr as ocr_const;
+use tauri::State;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Loads the OCR settings, defaults when unset.
+pub async fn load_ocr_settings(db: &DBClient) -> OcrSettings {
+    match queries::settings::load_config(db, ocr_const::SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No OCR settings found, using defaults");
+            OcrSettings::default()
+        }
+    }
+}
+
+/// Gets the OCR settings.
+#[tauri::command]
+#[instrument(name = "get_ocr_settings", skip(state))]
+pub async fn get_ocr_settings(state: State<'_, AppState>) -> Result<OcrSettings, String> {
+    Ok(load_ocr_settings(&state.db).await)
+}
+
+/// Updates the OCR settings.
+///
+/// # Arguments
+/// * `settings` - Engine, default language packs, tesseract path and Ollama model
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_ocr_settings", skip(state, settings))]
+pub async fn update_ocr_settings(
+    settings: OcrSettings,
+    state: State<'_, AppState>,
+) -> Result<OcrSettings, String> {
+    info!(engine = ?settings.engine, "Updating OCR settings");
+
+    let settings = normalize_ocr_settings(settings).map_err(|e| {
+        warn!(error = %e, "Invalid OCR settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize OCR settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, ocr_const::SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save OCR settings");
+            format!("Failed to save OCR settings: {}", e)
+        })?;
+
+    info!(languages = ?settings.languages, "OCR settings updated successfully");
+    Ok(settings)
+}
+
+/// Lists the language packs installed for tesseract.
+///
+/// Uses the stored tesseract path; fails when tesseract cannot be run.
+#[tauri::command]
+#[instrument(name = "list_ocr_languages", skip(state))]
+pub async fn list_ocr_languages(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let settings = load_ocr_settings(&state.db).await;
+    ocr::list_languages(&settings).await.map_err(|e| {
+        warn!(error = %e, "Failed to list tesseract languages");
+        e
+    })
+}
+
+/// Trims and validates the OCR settings.
+fn normalize_ocr_settings(settings: OcrSettings) -> Result<OcrSettings, String> {
+    let mut languages: Vec<String> = Vec::new();
+    for language in &settings.languages {
+        let language = language.trim();
+        if !ocr::is_valid_language(language) {
+            return Err(format!(
+                "Invalid OCR language '{}': use tesseract codes like 'eng' or 'chi_sim'",
+                language
+            ));
+        }
+        if !languages.iter().any(|known| known == language) {
+            languages.push(language.to_string());
+        }
+    }
+    if languages.is_empty() {
+        languages.push(ocr_const::DEFAULT_LANGUAGE.to_string());
+    }
+    if languages.len() > ocr_const::MAX_LANGUAGES {
+        return Err(format!(
+            "At most {} OCR languages can be combined",
+            ocr_const::MAX_LANGUAGES
+        ));
+    }
+
+    let tesseract_path = settings
+        .tesseract_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+
+    let ollama_url = settings.ollama_url.trim().trim_end_matches('/').to_string();
+    let url = reqwest::Url::parse(&ollama_url).map_err(|e| format!("Invalid Ollama URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Ollama URL must be an http(s) URL".to_string());
+    }
+
+    let ollama_model = settings.ollama_model.trim().to_string();
+    validate_model_id(&ollama_model)?;
+
+    Ok(OcrSettings {
+        engine: settings.engine,
+        languages,
+        tesseract_path,
+        ollama_url,
+        ollama_model,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ocr::OcrEngine;
+
+    #[test]
+    fn test_normalize_ocr_settings() {
+        let settings = normalize_ocr_settings(OcrSettings {
+            engine: OcrEngine::Ollama,
+            languages: vec![" fra ".to_string(), "eng".to_string(), "fra".to_string()],
+            tesseract_path: Some("  ".to_string()),
+            ollama_url: " http://localhost:11434/ ".to_string(),
+            ollama_model: " llava ".to_string(),
+        })
+        .unwrap();
+        assert_eq!(settings.languages, vec!["fra", "eng"]);
+        assert_eq!(settings.tesseract_path, None);
+        assert_eq!(settings.ollama_url, "http://localhost:11434");
+        assert_eq!(settings.ollama_model, "llava");
+
+        // No language falls back to English
+        let settings = normalize_ocr_settings(OcrSettings {
+            languages: Vec::new(),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(settings.languages, vec!["eng"]);
+    }
+
+    #[test]
+    fn test_normalize_rejects_invalid_settings() {
+        let invalid = [
+            OcrSettings {
+                languages: vec!["eng+fra".to_string()],
+                ..Default::default()
+            },
+            OcrSettings {
+                ollama_url: "ftp://localhost".to_string(),
+                ..Default::default()
+            },
+            OcrSettings {
+                ollama_model: " ".to_string(),
+                ..Default::default()
+            },
+        ];
+        for settings in invalid {
+            assert!(normalize_ocr_settings(settings).is_err());
+        }
+    }
+
+    #[test]
+    fn test_settings_defaults() {
+        let settings: OcrSettings = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(settings, OcrSettings::default());
+        assert_eq!(settings.engine, OcrEngine::Tesseract);
+        assert_eq!(settings.languages, vec!["eng"]);
+    }
+}
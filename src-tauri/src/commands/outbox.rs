@@ -0,0 +1,45 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Persistence outbox commands.
+//!
+//! Records whose database write failed during a workflow execution are queued
+//! in a durable outbox (see [`crate::db::outbox`]). They are retried
+//! automatically after each execution and at startup; these commands let the
+//! user retry them and check how many are waiting.
+
+use crate::{models::outbox::OutboxFlushResult, AppState};
+use tauri::State;
+use tracing::{error, info, instrument};
+
+/// Writes the records queued in the persistence outbox to the database.
+///
+/// # Returns
+/// Number of records written and still waiting
+#[tauri::command]
+#[instrument(name = "flush_outbox", skip(state))]
+pub async fn flush_outbox(state: State<'_, AppState>) -> Result<OutboxFlushResult, String> {
+    info!("Flushing persistence outbox");
+
+    state.outbox.flush(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to flush persistence outbox");
+        e
+    })
+}
+
+/// Returns the number of records waiting in the persistence outbox.
+#[tauri::command]
+#[instrument(name = "get_outbox_pending", skip(state))]
+pub async fn get_outbox_pending(state: State<'_, AppState>) -> Result<usize, String> {
+    state.outbox.pending().await
+}
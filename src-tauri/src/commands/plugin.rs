@@ -0,0 +1,233 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tool plugin commands.
+//!
+//! Plugins are installed once for all profiles in `~/.zileo/plugins`; the
+//! capabilities granted to each one are stored per profile in the
+//! `settings:plugins` record (see [`crate::plugins`]).
+
+use crate::db::{queries, DBClient};
+use crate::models::plugin::{PluginCapability, PluginGrant, PluginInfo, PluginSettings};
+use crate::plugins::manifest::{self, PluginManifest};
+use crate::plugins::PLUGIN_HOST;
+use crate::state::AppState;
+use crate::tools::constants::plugins as plugin_const;
+use crate::tools::utils::resolve_path;
+use tauri::State;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Loads the plugin capability grants, empty when unset.
+pub async fn load_plugin_settings(db: &DBClient) -> PluginSettings {
+    match queries::settings::load_config(db, plugin_const::SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No plugin settings found, using defaults");
+            PluginSettings::default()
+        }
+    }
+}
+
+/// Saves the plugin capability grants.
+async fn save_plugin_settings(db: &DBClient, settings: &PluginSettings) -> Result<(), String> {
+    let json_config = serde_json::to_value(settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize plugin settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(db, plugin_const::SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save plugin settings");
+            format!("Failed to save plugin settings: {}", e)
+        })
+}
+
+/// Keeps each granted capability once, rejecting those the manifest does
+/// not request.
+fn check_capabilities(
+    requested: &[PluginCapability],
+    granted: &[PluginCapability],
+) -> Result<Vec<PluginCapability>, String> {
+    let mut capabilities = Vec::new();
+    for capability in granted {
+        if !requested.contains(capability) {
+            return Err(format!(
+                "The plugin does not request the '{}' capability",
+                capability.as_str()
+            ));
+        }
+        if !capabilities.contains(capability) {
+            capabilities.push(*capability);
+        }
+    }
+    Ok(capabilities)
+}
+
+/// Lists the installed plugins with their grants in the active profile.
+#[tauri::command]
+#[instrument(name = "list_plugins", skip(state))]
+pub async fn list_plugins(state: State<'_, AppState>) -> Result<Vec<PluginInfo>, String> {
+    let settings = load_plugin_settings(&state.db).await;
+    tokio::task::spawn_blocking(move || PLUGIN_HOST.list(&settings))
+        .await
+        .map_err(|e| format!("Failed to list plugins: {}", e))
+}
+
+/// Installs a plugin and enables it in the active profile.
+///
+/// # Arguments
+/// * `path` - Directory holding the `plugin.toml` manifest and the
+///   `plugin.wasm` component (or the manifest itself)
+/// * `capabilities` - Capabilities granted, among those the manifest requests
+///
+/// # Returns
+/// The installed plugin
+#[tauri::command]
+#[instrument(name = "install_plugin", skip(state, capabilities))]
+pub async fn install_plugin(
+    path: String,
+    capabilities: Vec<PluginCapability>,
+    state: State<'_, AppState>,
+) -> Result<PluginInfo, String> {
+    let mut source = resolve_path(&path).map_err(|e| e.to_string())?;
+    if source.is_file() {
+        source.pop();
+    }
+
+    // The grant is checked against the manifest before anything is copied
+    let (plugin, capabilities) = tokio::task::spawn_blocking(move || {
+        let manifest_path = source.join(plugin_const::MANIFEST_FILE);
+        let text = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("Cannot read '{}': {}", manifest_path.display(), e))?;
+        let requested = PluginManifest::parse(&text)?.requested_capabilities();
+        let capabilities = check_capabilities(&requested, &capabilities)?;
+        PLUGIN_HOST
+            .install(&source)
+            .map(|plugin| (plugin, capabilities))
+    })
+    .await
+    .map_err(|e| format!("Failed to install plugin: {}", e))?
+    .map_err(|e| {
+        warn!(error = %e, "Plugin installation failed");
+        e
+    })?;
+    let id = plugin.manifest.id.clone();
+
+    let mut settings = load_plugin_settings(&state.db).await;
+    settings.plugins.insert(
+        id.clone(),
+        PluginGrant {
+            enabled: true,
+            capabilities,
+            sha256: plugin.sha256.clone(),
+        },
+    );
+    save_plugin_settings(&state.db, &settings).await?;
+
+    info!(plugin = %id, "Plugin installed and enabled");
+    Ok(PLUGIN_HOST.info(&id, &settings))
+}
+
+/// Enables or disables a plugin in the active profile and sets its granted
+/// capabilities. Approves the plugin as currently installed.
+///
+/// # Arguments
+/// * `id` - Plugin ID
+/// * `enabled` - Whether agents can use the plugin
+/// * `capabilities` - Capabilities granted, among those the manifest requests
+#[tauri::command]
+#[instrument(name = "set_plugin_permissions", skip(state, capabilities))]
+pub async fn set_plugin_permissions(
+    id: String,
+    enabled: bool,
+    capabilities: Vec<PluginCapability>,
+    state: State<'_, AppState>,
+) -> Result<PluginInfo, String> {
+    if !manifest::is_valid_id(&id) {
+        return Err(format!("Invalid plugin ID '{}'", id));
+    }
+    let plugin_id = id.clone();
+    let plugin = tokio::task::spawn_blocking(move || PLUGIN_HOST.load(&plugin_id))
+        .await
+        .map_err(|e| format!("Failed to load plugin: {}", e))??;
+    let capabilities =
+        check_capabilities(&plugin.manifest.requested_capabilities(), &capabilities)?;
+
+    let mut settings = load_plugin_settings(&state.db).await;
+    settings.plugins.insert(
+        id.clone(),
+        PluginGrant {
+            enabled,
+            capabilities,
+            sha256: plugin.sha256.clone(),
+        },
+    );
+    save_plugin_settings(&state.db, &settings).await?;
+
+    info!(plugin = %id, enabled = enabled, "Plugin permissions updated");
+    Ok(PLUGIN_HOST.info(&id, &settings))
+}
+
+/// Removes a plugin, with its grants in the active profile.
+///
+/// Grants of other profiles are kept, but only match the same plugin if it is
+/// installed again.
+#[tauri::command]
+#[instrument(name = "uninstall_plugin", skip(state))]
+pub async fn uninstall_plugin(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    PLUGIN_HOST.uninstall(&id)?;
+
+    let mut settings = load_plugin_settings(&state.db).await;
+    if settings.plugins.remove(&id).is_some() {
+        save_plugin_settings(&state.db, &settings).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_capabilities() {
+        let requested = [PluginCapability::Network, PluginCapability::ReadFiles];
+        assert_eq!(
+            check_capabilities(
+                &requested,
+                &[PluginCapability::ReadFiles, PluginCapability::ReadFiles]
+            )
+            .unwrap(),
+            vec![PluginCapability::ReadFiles]
+        );
+        assert!(check_capabilities(&requested, &[]).unwrap().is_empty());
+        assert!(
+            check_capabilities(&[PluginCapability::ReadFiles], &[PluginCapability::Network])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_settings_defaults() {
+        let settings: PluginSettings = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(settings.plugins.is_empty());
+
+        let grant: PluginGrant =
+            serde_json::from_value(serde_json::json!({ "sha256": "abc" })).unwrap();
+        assert!(!grant.enabled);
+        assert!(grant.capabilities.is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Profile commands.
+//!
+//! Profiles are isolated data sets (see [`crate::profiles`]). Switching
+//! profile reopens the database at the path of the new profile and reloads
+//! everything read from it: MCP servers, agents, custom providers, embedding
+//! configuration. The frontend reloads its stores on `profile_switched`.
+
+use crate::commands::SecureKeyStore;
+use crate::models::profile::{Profile, ProfileRegistry, ProfileSwitchedEvent};
+use crate::models::streaming::events;
+use crate::profiles::{attachments_dir, outbox_path};
+use crate::startup::restore_profile_data;
+use crate::AppState;
+use std::path::Path;
+use tauri::{Emitter, State};
+use tracing::{error, info, instrument, warn};
+
+/// Lists the profiles and the active one.
+#[tauri::command]
+#[instrument(name = "list_profiles", skip(state))]
+pub async fn list_profiles(state: State<'_, AppState>) -> Result<ProfileRegistry, String> {
+    state.profiles.load().map_err(|e| {
+        error!(error = %e, "Failed to load profiles");
+        format!("Failed to load profiles: {}", e)
+    })
+}
+
+/// Creates a profile, with an empty database.
+///
+/// # Arguments
+/// * `name` - Display name (unique, the profile ID is derived from it)
+#[tauri::command]
+#[instrument(name = "create_profile", skip(state))]
+pub async fn create_profile(name: String, state: State<'_, AppState>) -> Result<Profile, String> {
+    let profile = state.profiles.create(&name).map_err(|e| {
+        warn!(error = %e, "Failed to create profile");
+        format!("Failed to create profile: {}", e)
+    })?;
+    info!(profile_id = %profile.id, "Profile created");
+    Ok(profile)
+}
+
+/// Switches to another profile.
+///
+/// Refused while a workflow, scheduled run or job is running. MCP servers and
+/// agents of the current profile are stopped, the database of the new profile
+/// is opened (schema initialized) and its data restored. The profile stays
+/// active at next startup.
+///
+/// # Arguments
+/// * `profile_id` - ID of the profile to activate
+#[tauri::command]
+#[instrument(name = "switch_profile", skip(state, keystore))]
+pub async fn switch_profile(
+    profile_id: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<ProfileRegistry, String> {
+    let registry = state
+        .profiles
+        .load()
+        .map_err(|e| format!("Failed to load profiles: {}", e))?;
+    let profile = registry
+        .get(&profile_id)
+        .cloned()
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+    if registry.active == profile.id {
+        return Ok(registry);
+    }
+    if !state.streaming_cancellations.lock().await.is_empty()
+        || state.scheduler.is_busy()
+        || state.job_queue.is_busy()
+    {
+        return Err(
+            "Cannot switch profile while a workflow, scheduled run or job is running".to_string(),
+        );
+    }
+
+    info!(from = %registry.active, to = %profile.id, "Switching profile");
+    unload_profile_data(&state).await;
+
+    let previous_db = state.profiles.db_path(&registry.active);
+    let db_path = state.profiles.db_path(&profile.id);
+    if let Err(e) = open_database(&state, &db_path).await {
+        error!(error = %e, "Failed to open profile database, restoring previous profile");
+        if let Err(e) = open_database(&state, &previous_db).await {
+            error!(error = %e, "Failed to reopen previous profile database");
+        }
+        restore_profile_data(&state, &keystore).await;
+        return Err(format!("Failed to open profile database: {}", e));
+    }
+
+    let registry = state.profiles.set_active(&profile.id).map_err(|e| {
+        error!(error = %e, "Failed to store active profile");
+        format!("Failed to store active profile: {}", e)
+    })?;
+    restore_profile_data(&state, &keystore).await;
+
+    // Jobs left running when this profile was last active
+    if let Err(e) = crate::jobs::resume_interrupted(&state.db).await {
+        warn!(error = %e, "Failed to resume interrupted jobs");
+    }
+    state.job_queue.notify();
+
+    if let Some(handle) = state.get_app_handle() {
+        let event = ProfileSwitchedEvent {
+            profile: profile.clone(),
+        };
+        if let Err(e) = handle.emit(events::PROFILE_SWITCHED, &event) {
+            warn!(error = %e, "Failed to emit profile_switched event");
+        }
+    }
+
+    info!(profile_id = %profile.id, "Profile switched");
+    Ok(registry)
+}
+
+/// Stops everything loaded from the current profile database.
+async fn unload_profile_data(state: &AppState) {
+    // Records queued for the current database are written before it closes
+    if let Err(e) = state.outbox.flush(&state.db).await {
+        warn!(error = %e, "Failed to flush persistence outbox");
+    }
+    if let Err(e) = state.mcp_manager.shutdown().await {
+        warn!(error = %e, "Failed to stop MCP servers");
+    }
+    for agent_id in state.registry.list().await {
+        state.registry.unregister_any(&agent_id).await;
+    }
+    state.llm_manager.clear_custom_providers().await;
+    state.set_embedding_service(None).await;
+}
+
+/// Opens the database at `db_path`, with the outbox and attachments next to it.
+async fn open_database(state: &AppState, db_path: &Path) -> anyhow::Result<()> {
+    state.db.reopen(&db_path.to_string_lossy()).await?;
+    state.db.initialize_schema().await?;
+    state.outbox.set_path(outbox_path(db_path)).await;
+    state.attachments.set_dir(attachments_dir(db_path));
+    Ok(())
+}
@@ -16,12 +16,25 @@
 //!
 //! Tauri IPC commands for managing prompt templates with variable interpolation.
 
+use crate::agents::core::agent::{Agent, ReportStatus, Task};
+use crate::agents::LLMAgent;
+use crate::commands::job::enqueue_job;
+use crate::commands::workflow::create_workflow;
+use crate::db::DBClient;
+use crate::llm::pricing::calculate_cost;
+use crate::models::job::Job;
 use crate::models::prompt::{
-    Prompt, PromptCreate, PromptSummary, PromptUpdate, MAX_PROMPT_CONTENT_LEN,
-    MAX_PROMPT_DESCRIPTION_LEN, MAX_PROMPT_NAME_LEN,
+    Prompt, PromptComparison, PromptCreate, PromptSummary, PromptUpdate, PromptVersion,
+    PromptVersionRun, RenderedPrompt, MAX_PROMPT_CONTENT_LEN, MAX_PROMPT_DESCRIPTION_LEN,
+    MAX_PROMPT_NAME_LEN,
 };
+use crate::models::AgentConfig;
+use crate::tools::constants::workflow as wf_const;
 use crate::AppState;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use tauri::State;
+use tokio::time::timeout;
 use tracing::{error, info, instrument, warn};
 
 // ===== Validation Helpers =====
@@ -79,6 +92,7 @@ pub async fn list_prompts(state: State<'_, AppState>) -> Result<Vec<PromptSummar
             description,
             category,
             array::len(variables ?? []) AS variables_count,
+            (usage_count ?? 0) AS usage_count,
             updated_at
         FROM prompt
         ORDER BY updated_at DESC
@@ -104,8 +118,15 @@ pub async fn list_prompts(state: State<'_, AppState>) -> Result<Vec<PromptSummar
 pub async fn get_prompt(prompt_id: String, state: State<'_, AppState>) -> Result<Prompt, String> {
     info!("Getting prompt");
 
-    let query = format!(
-        r#"
+    let prompt = load_prompt(&state.db, &prompt_id).await?;
+
+    info!("Retrieved prompt");
+    Ok(prompt)
+}
+
+/// Loads a full prompt from the database.
+async fn load_prompt(db: &DBClient, prompt_id: &str) -> Result<Prompt, String> {
+    let query = r#"
         SELECT
             meta::id(id) AS id,
             name,
@@ -113,28 +134,377 @@ pub async fn get_prompt(prompt_id: String, state: State<'_, AppState>) -> Result
             category,
             content,
             variables,
+            (usage_count ?? 0) AS usage_count,
+            last_used_at,
             created_at,
             updated_at
-        FROM prompt:`{}`
-        "#,
-        prompt_id
-    );
-
-    let results: Vec<serde_json::Value> = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to get prompt");
-        format!("Failed to get prompt: {}", e)
-    })?;
-
-    let prompt: Prompt = results
+        FROM type::thing('prompt', $id)
+        "#;
+    let params = vec![("id".to_string(), serde_json::json!(prompt_id))];
+
+    let results: Vec<serde_json::Value> =
+        db.query_json_with_params(query, params)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to get prompt");
+                format!("Failed to get prompt: {}", e)
+            })?;
+
+    results
         .into_iter()
         .next()
         .ok_or_else(|| format!("Prompt not found: {}", prompt_id))
         .and_then(|v| {
             serde_json::from_value(v).map_err(|e| format!("Failed to deserialize prompt: {}", e))
+        })
+}
+
+/// Increments the usage counter of a prompt and stamps its last use.
+async fn record_prompt_usage(db: &DBClient, prompt_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('prompt', $id) SET usage_count = (usage_count ?? 0) + 1, last_used_at = time::now()",
+        vec![("id".to_string(), serde_json::json!(prompt_id))],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to record prompt usage");
+        format!("Failed to record prompt usage: {}", e)
+    })
+}
+
+const PROMPT_VERSION_FIELDS: &str =
+    "prompt_id, version, name, description, category, content, created_at";
+
+/// Saves the current state of a prompt as its next version.
+async fn save_prompt_version(db: &DBClient, prompt_id: &str) -> Result<u32, String> {
+    let prompt = load_prompt(db, prompt_id).await?;
+    let latest = db
+        .query_json_with_params(
+            "SELECT version FROM prompt_version WHERE prompt_id = $id ORDER BY version DESC LIMIT 1",
+            vec![("id".to_string(), serde_json::json!(prompt_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load prompt versions: {}", e))?;
+    let version = latest
+        .first()
+        .and_then(|row| row["version"].as_u64())
+        .unwrap_or(0) as u32
+        + 1;
+
+    let query = r#"
+        CREATE type::thing('prompt_version', $version_id) CONTENT {
+            prompt_id: $prompt_id,
+            version: $version,
+            name: $name,
+            description: $description,
+            category: $category,
+            content: $content,
+            created_at: time::now()
+        }
+        "#;
+    let params = vec![
+        (
+            "version_id".to_string(),
+            serde_json::json!(uuid::Uuid::new_v4().to_string()),
+        ),
+        ("prompt_id".to_string(), serde_json::json!(prompt_id)),
+        ("version".to_string(), serde_json::json!(version)),
+        ("name".to_string(), serde_json::json!(prompt.name)),
+        (
+            "description".to_string(),
+            serde_json::json!(prompt.description),
+        ),
+        ("category".to_string(), serde_json::json!(prompt.category)),
+        ("content".to_string(), serde_json::json!(prompt.content)),
+    ];
+    db.execute_with_params(query, params).await.map_err(|e| {
+        error!(error = %e, "Failed to save prompt version");
+        format!("Failed to save prompt version: {}", e)
+    })?;
+
+    Ok(version)
+}
+
+/// Loads one version of a prompt.
+async fn load_prompt_version(
+    db: &DBClient,
+    prompt_id: &str,
+    version: u32,
+) -> Result<PromptVersion, String> {
+    let query = format!(
+        "SELECT {} FROM prompt_version WHERE prompt_id = $id AND version = $version",
+        PROMPT_VERSION_FIELDS
+    );
+    let params = vec![
+        ("id".to_string(), serde_json::json!(prompt_id)),
+        ("version".to_string(), serde_json::json!(version)),
+    ];
+    db.query_with_params::<PromptVersion>(&query, params)
+        .await
+        .map_err(|e| format!("Failed to load prompt version: {}", e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Prompt version not found: {} v{}", prompt_id, version))
+}
+
+/// Lists the versions of a prompt, newest first
+#[tauri::command]
+#[instrument(name = "list_prompt_versions", skip(state), fields(prompt_id = %prompt_id))]
+pub async fn list_prompt_versions(
+    prompt_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<PromptVersion>, String> {
+    let query = format!(
+        "SELECT {} FROM prompt_version WHERE prompt_id = $id ORDER BY version DESC",
+        PROMPT_VERSION_FIELDS
+    );
+    let versions = state
+        .db
+        .query_with_params::<PromptVersion>(
+            &query,
+            vec![("id".to_string(), serde_json::json!(prompt_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to list prompt versions");
+            format!("Failed to list prompt versions: {}", e)
         })?;
 
-    info!("Retrieved prompt");
-    Ok(prompt)
+    info!(count = versions.len(), "Listed prompt versions");
+    Ok(versions)
+}
+
+/// Runs two versions of a prompt with the same variable values and agent
+///
+/// Both rendered prompts are executed concurrently on fresh agent instances
+/// (no workflow, no persisted messages). Tools called by the agent run for
+/// real, as in a workflow.
+#[tauri::command]
+#[instrument(
+    name = "compare_prompt_versions",
+    skip(state, values),
+    fields(prompt_id = %prompt_id, agent_id = %agent_id)
+)]
+pub async fn compare_prompt_versions(
+    prompt_id: String,
+    version_a: u32,
+    version_b: u32,
+    values: HashMap<String, String>,
+    agent_id: String,
+    state: State<'_, AppState>,
+) -> Result<PromptComparison, String> {
+    if version_a == version_b {
+        return Err("Select two different versions to compare".to_string());
+    }
+
+    let mut rendered = Vec::with_capacity(2);
+    for version in [version_a, version_b] {
+        let prompt = load_prompt_version(&state.db, &prompt_id, version)
+            .await?
+            .to_prompt();
+        let render = prompt.render(&values);
+        if !render.missing_variables.is_empty() {
+            return Err(format!(
+                "Missing values for variables of version {}: {}",
+                version,
+                render.missing_variables.join(", ")
+            ));
+        }
+        rendered.push(render.content);
+    }
+
+    let agent = state.registry.get(&agent_id).await.ok_or_else(|| {
+        warn!("Agent not found");
+        "Agent not found".to_string()
+    })?;
+    let config = agent.config().clone();
+    let (input_price, output_price) =
+        model_pricing(&state.db, &config.llm.provider, &config.llm.model).await;
+
+    let (a, b) = tokio::join!(
+        run_prompt_version(&config, version_a, &rendered[0], &state),
+        run_prompt_version(&config, version_b, &rendered[1], &state),
+    );
+    let [a, b] = [a, b].map(|mut run| {
+        run.cost_usd = calculate_cost(
+            run.tokens_input as usize,
+            run.tokens_output as usize,
+            input_price,
+            output_price,
+        );
+        run
+    });
+
+    info!(
+        success_a = a.success,
+        success_b = b.success,
+        "Prompt versions compared"
+    );
+    Ok(PromptComparison {
+        prompt_id,
+        agent_id,
+        a,
+        b,
+    })
+}
+
+/// Executes a rendered prompt version on a fresh agent (cost filled by the caller).
+async fn run_prompt_version(
+    config: &AgentConfig,
+    version: u32,
+    input: &str,
+    state: &AppState,
+) -> PromptVersionRun {
+    let start = Instant::now();
+    let agent = LLMAgent::with_factory(
+        config.clone(),
+        state.llm_manager.clone(),
+        state.tool_factory.clone(),
+    );
+    let task = Task {
+        id: uuid::Uuid::new_v4().to_string(),
+        description: input.to_string(),
+        context: serde_json::json!({}),
+    };
+
+    let mut run = PromptVersionRun {
+        version,
+        input: input.to_string(),
+        response: String::new(),
+        success: false,
+        error: None,
+        tokens_input: 0,
+        tokens_output: 0,
+        cost_usd: 0.0,
+        duration_ms: 0,
+    };
+    match timeout(
+        Duration::from_secs(wf_const::LLM_EXECUTION_TIMEOUT_SECS),
+        agent.execute_with_mcp(task, Some(state.mcp_manager.clone())),
+    )
+    .await
+    {
+        Ok(Ok(report)) => {
+            run.tokens_input = report.metrics.tokens_input as u64;
+            run.tokens_output = report.metrics.tokens_output as u64;
+            if matches!(report.status, ReportStatus::Failed) {
+                run.error = Some(report.response);
+            } else {
+                run.success = true;
+                run.response = report.response;
+            }
+        }
+        Ok(Err(e)) => run.error = Some(format!("Execution failed: {}", e)),
+        Err(_) => {
+            run.error = Some(format!(
+                "Execution timed out after {} seconds",
+                wf_const::LLM_EXECUTION_TIMEOUT_SECS
+            ))
+        }
+    }
+    if let Some(error) = &run.error {
+        warn!(version, error = %error, "Prompt version run failed");
+    }
+    run.duration_ms = start.elapsed().as_millis() as u64;
+    run
+}
+
+/// Input and output prices (USD per million tokens) of a model; 0 if unknown.
+async fn model_pricing(db: &DBClient, provider: &str, model: &str) -> (f64, f64) {
+    let params = vec![
+        ("api_name".to_string(), serde_json::json!(model)),
+        (
+            "provider".to_string(),
+            serde_json::json!(provider.to_lowercase()),
+        ),
+    ];
+    match db
+        .query_json_with_params(
+            "SELECT input_price_per_mtok, output_price_per_mtok FROM llm_model \
+             WHERE api_name = $api_name AND provider = $provider",
+            params,
+        )
+        .await
+    {
+        Ok(rows) => rows
+            .first()
+            .map(|row| {
+                (
+                    row["input_price_per_mtok"].as_f64().unwrap_or(0.0),
+                    row["output_price_per_mtok"].as_f64().unwrap_or(0.0),
+                )
+            })
+            .unwrap_or((0.0, 0.0)),
+        Err(e) => {
+            warn!(error = %e, "Failed to load model pricing");
+            (0.0, 0.0)
+        }
+    }
+}
+
+/// Renders a prompt with the given variable values.
+///
+/// Defaults declared on the variables are applied to missing or blank values.
+/// Placeholders that still have no value are left as-is and reported in
+/// `missing_variables`.
+#[tauri::command]
+#[instrument(name = "render_prompt", skip(state, values), fields(prompt_id = %prompt_id))]
+pub async fn render_prompt(
+    prompt_id: String,
+    values: HashMap<String, String>,
+    state: State<'_, AppState>,
+) -> Result<RenderedPrompt, String> {
+    let prompt = load_prompt(&state.db, &prompt_id).await?;
+    let rendered = prompt.render(&values);
+
+    info!(
+        missing = rendered.missing_variables.len(),
+        "Rendered prompt"
+    );
+    Ok(rendered)
+}
+
+/// Renders a prompt and queues it as a background job.
+///
+/// Every variable must have a value (or a default). When `workflow_id` is
+/// omitted, a new workflow named after the prompt is created for `agent_id`.
+/// The prompt usage counter is incremented once the job is queued.
+#[tauri::command]
+#[instrument(
+    name = "launch_prompt",
+    skip(state, values),
+    fields(prompt_id = %prompt_id, agent_id = %agent_id)
+)]
+pub async fn launch_prompt(
+    prompt_id: String,
+    values: HashMap<String, String>,
+    agent_id: String,
+    workflow_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Job, String> {
+    let prompt = load_prompt(&state.db, &prompt_id).await?;
+    let rendered = prompt.render(&values);
+    if !rendered.missing_variables.is_empty() {
+        warn!(missing = ?rendered.missing_variables, "Prompt has missing variables");
+        return Err(format!(
+            "Missing values for variables: {}",
+            rendered.missing_variables.join(", ")
+        ));
+    }
+    if state.registry.get(&agent_id).await.is_none() {
+        return Err("Agent not found".to_string());
+    }
+
+    let workflow_id = match workflow_id {
+        Some(id) => id,
+        None => create_workflow(prompt.name.clone(), agent_id.clone(), state.clone()).await?,
+    };
+
+    let job = enqueue_job(&state, &workflow_id, &rendered.content, &agent_id).await?;
+    record_prompt_usage(&state.db, &prompt_id).await?;
+
+    info!(job_id = %job.id, workflow_id = %workflow_id, "Prompt launched");
+    Ok(job)
 }
 
 /// Create new prompt
@@ -156,46 +526,36 @@ pub async fn create_prompt(
 
     // Detect variables from content
     let variables = Prompt::detect_variables(&content);
-    let variables_json = serde_json::to_string(&variables).map_err(|e| {
-        error!(error = %e, "Failed to serialize variables");
-        format!("Failed to serialize variables: {}", e)
-    })?;
-
-    // Serialize strings for SurrealDB
-    let name_json =
-        serde_json::to_string(&name).map_err(|e| format!("Failed to serialize name: {}", e))?;
-    let description_json = serde_json::to_string(&description)
-        .map_err(|e| format!("Failed to serialize description: {}", e))?;
-    let content_json = serde_json::to_string(&content)
-        .map_err(|e| format!("Failed to serialize content: {}", e))?;
-    let category_str = config.category.to_string();
 
-    let query = format!(
-        r#"
-        CREATE prompt:`{}` CONTENT {{
-            id: '{}',
-            name: {},
-            description: {},
-            category: '{}',
-            content: {},
-            variables: {},
+    let query = r#"
+        CREATE type::thing('prompt', $id) CONTENT {
+            name: $name,
+            description: $description,
+            category: $category,
+            content: $content,
+            variables: $variables,
             created_at: time::now(),
             updated_at: time::now()
-        }}
-        "#,
-        prompt_id,
-        prompt_id,
-        name_json,
-        description_json,
-        category_str,
-        content_json,
-        variables_json
-    );
-
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to create prompt in database");
-        format!("Failed to create prompt: {}", e)
-    })?;
+        }
+        "#;
+    let params = vec![
+        ("id".to_string(), serde_json::json!(prompt_id)),
+        ("name".to_string(), serde_json::json!(name)),
+        ("description".to_string(), serde_json::json!(description)),
+        ("category".to_string(), serde_json::json!(config.category)),
+        ("content".to_string(), serde_json::json!(content)),
+        ("variables".to_string(), serde_json::json!(variables)),
+    ];
+
+    state
+        .db
+        .execute_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create prompt in database");
+            format!("Failed to create prompt: {}", e)
+        })?;
+    save_prompt_version(&state.db, &prompt_id).await?;
 
     info!(prompt_id = %prompt_id, "Prompt created successfully");
     Ok(prompt_id)
@@ -213,37 +573,35 @@ pub async fn update_prompt(
 
     // Build SET clauses for non-None fields
     let mut set_clauses = Vec::new();
+    let mut params = vec![("id".to_string(), serde_json::json!(prompt_id))];
 
     if let Some(ref name) = config.name {
         let validated = validate_prompt_name(name)?;
-        let name_json = serde_json::to_string(&validated)
-            .map_err(|e| format!("Failed to serialize name: {}", e))?;
-        set_clauses.push(format!("name = {}", name_json));
+        set_clauses.push("name = $name");
+        params.push(("name".to_string(), serde_json::json!(validated)));
     }
 
     if let Some(ref description) = config.description {
         let validated = validate_prompt_description(description)?;
-        let desc_json = serde_json::to_string(&validated)
-            .map_err(|e| format!("Failed to serialize description: {}", e))?;
-        set_clauses.push(format!("description = {}", desc_json));
+        set_clauses.push("description = $description");
+        params.push(("description".to_string(), serde_json::json!(validated)));
     }
 
     if let Some(ref category) = config.category {
-        set_clauses.push(format!("category = '{}'", category));
+        set_clauses.push("category = $category");
+        params.push(("category".to_string(), serde_json::json!(category)));
     }
 
     if let Some(ref content) = config.content {
         let validated = validate_prompt_content(content)?;
-        let content_json = serde_json::to_string(&validated)
-            .map_err(|e| format!("Failed to serialize content: {}", e))?;
 
         // Re-detect variables when content changes
         let variables = Prompt::detect_variables(&validated);
-        let variables_json = serde_json::to_string(&variables)
-            .map_err(|e| format!("Failed to serialize variables: {}", e))?;
 
-        set_clauses.push(format!("content = {}", content_json));
-        set_clauses.push(format!("variables = {}", variables_json));
+        set_clauses.push("content = $content");
+        set_clauses.push("variables = $variables");
+        params.push(("content".to_string(), serde_json::json!(validated)));
+        params.push(("variables".to_string(), serde_json::json!(variables)));
     }
 
     if set_clauses.is_empty() {
@@ -252,18 +610,22 @@ pub async fn update_prompt(
     }
 
     // Always update timestamp
-    set_clauses.push("updated_at = time::now()".to_string());
+    set_clauses.push("updated_at = time::now()");
 
     let query = format!(
-        "UPDATE prompt:`{}` SET {}",
-        prompt_id,
+        "UPDATE type::thing('prompt', $id) SET {}",
         set_clauses.join(", ")
     );
 
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to update prompt in database");
-        format!("Failed to update prompt: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update prompt in database");
+            format!("Failed to update prompt: {}", e)
+        })?;
+    save_prompt_version(&state.db, &prompt_id).await?;
 
     info!("Prompt updated successfully");
     get_prompt(prompt_id, state).await
@@ -275,12 +637,17 @@ pub async fn update_prompt(
 pub async fn delete_prompt(prompt_id: String, state: State<'_, AppState>) -> Result<(), String> {
     info!("Deleting prompt");
 
-    let query = format!("DELETE prompt:`{}`", prompt_id);
-
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to delete prompt from database");
-        format!("Failed to delete prompt: {}", e)
-    })?;
+    state
+        .db
+        .execute_with_params(
+            "DELETE type::thing('prompt', $id); DELETE prompt_version WHERE prompt_id = $id",
+            vec![("id".to_string(), serde_json::json!(prompt_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete prompt from database");
+            format!("Failed to delete prompt: {}", e)
+        })?;
 
     info!("Prompt deleted successfully");
     Ok(())
@@ -297,20 +664,24 @@ pub async fn search_prompts(
     info!(query = ?query, category = ?category, "Searching prompts");
 
     let mut conditions = Vec::new();
+    let mut params = Vec::new();
 
     if let Some(ref q) = query {
         if !q.trim().is_empty() {
-            let search_term = q.trim().to_lowercase();
-            conditions.push(format!(
-                "(string::lowercase(name) CONTAINS '{}' OR string::lowercase(description) CONTAINS '{}')",
-                search_term, search_term
+            conditions.push(
+                "(string::lowercase(name) CONTAINS $search OR string::lowercase(description) CONTAINS $search)",
+            );
+            params.push((
+                "search".to_string(),
+                serde_json::json!(q.trim().to_lowercase()),
             ));
         }
     }
 
     if let Some(ref cat) = category {
         if !cat.trim().is_empty() {
-            conditions.push(format!("category = '{}'", cat.trim()));
+            conditions.push("category = $category");
+            params.push(("category".to_string(), serde_json::json!(cat.trim())));
         }
     }
 
@@ -328,6 +699,7 @@ pub async fn search_prompts(
             description,
             category,
             array::len(variables ?? []) AS variables_count,
+            (usage_count ?? 0) AS usage_count,
             updated_at
         FROM prompt
         {}
@@ -336,10 +708,14 @@ pub async fn search_prompts(
         where_clause
     );
 
-    let results: Vec<serde_json::Value> = state.db.query_json(&db_query).await.map_err(|e| {
-        error!(error = %e, "Failed to search prompts");
-        format!("Failed to search prompts: {}", e)
-    })?;
+    let results: Vec<serde_json::Value> = state
+        .db
+        .query_json_with_params(&db_query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to search prompts");
+            format!("Failed to search prompts: {}", e)
+        })?;
 
     let prompts: Vec<PromptSummary> = results
         .into_iter()
@@ -349,3 +725,86 @@ pub async fn search_prompts(
     info!(count = prompts.len(), "Search completed");
     Ok(prompts)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_record_prompt_usage() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("prompt_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        db.execute(
+            "CREATE prompt:greeting CONTENT { name: 'Greeting', description: '', category: 'custom', \
+             content: 'Hello {{name}}', variables: [{ name: 'name' }], \
+             created_at: time::now(), updated_at: time::now() }",
+        )
+        .await
+        .expect("Failed to create prompt");
+
+        let prompt = load_prompt(&db, "greeting").await.unwrap();
+        assert_eq!(prompt.usage_count, 0);
+        assert!(prompt.last_used_at.is_none());
+        assert_eq!(
+            prompt.render(&HashMap::new()).missing_variables,
+            vec!["name".to_string()]
+        );
+
+        record_prompt_usage(&db, "greeting").await.unwrap();
+        record_prompt_usage(&db, "greeting").await.unwrap();
+
+        let prompt = load_prompt(&db, "greeting").await.unwrap();
+        assert_eq!(prompt.usage_count, 2);
+        assert!(prompt.last_used_at.is_some());
+
+        assert!(load_prompt(&db, "missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prompt_versions() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("prompt_version_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        db.execute(
+            "CREATE prompt:summary CONTENT { name: 'Summary', description: '', category: 'custom', \
+             content: 'Summarize {{text}}', variables: [{ name: 'text' }], \
+             created_at: time::now(), updated_at: time::now() }",
+        )
+        .await
+        .expect("Failed to create prompt");
+        assert_eq!(save_prompt_version(&db, "summary").await.unwrap(), 1);
+
+        db.execute("UPDATE prompt:summary SET content = 'Summarize {{text}} in {{lang}}'")
+            .await
+            .expect("Failed to update prompt");
+        assert_eq!(save_prompt_version(&db, "summary").await.unwrap(), 2);
+
+        let v1 = load_prompt_version(&db, "summary", 1).await.unwrap();
+        assert_eq!(v1.content, "Summarize {{text}}");
+        let v2 = load_prompt_version(&db, "summary", 2)
+            .await
+            .unwrap()
+            .to_prompt();
+        assert_eq!(v2.id, "summary");
+        assert_eq!(v2.variables.len(), 2);
+
+        let values = HashMap::from([("text".to_string(), "the report".to_string())]);
+        assert!(v1.to_prompt().render(&values).missing_variables.is_empty());
+        assert_eq!(
+            v2.render(&values).missing_variables,
+            vec!["lang".to_string()]
+        );
+
+        assert!(load_prompt_version(&db, "summary", 3).await.is_err());
+    }
+}
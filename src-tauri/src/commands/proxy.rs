@@ -0,0 +1,86 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proxy settings commands.
+//!
+//! The saved settings apply to the next requests of the LLM providers, the
+//! embedding service and the MCP HTTP transports (see [`crate::proxy`]); the
+//! `[proxy]` table of the config file replaces them while present.
+
+use crate::db::{queries, DBClient};
+use crate::models::proxy::ProxySettings;
+use crate::tools::constants::proxy::SETTINGS_KEY;
+use crate::AppState;
+use tauri::State;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Loads the saved proxy settings, default (direct connection) when unset.
+pub async fn load_proxy_settings(db: &DBClient) -> ProxySettings {
+    match queries::settings::load_config(db, SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No proxy settings found, using direct connections");
+            ProxySettings::default()
+        }
+    }
+}
+
+/// Gets the saved proxy settings
+#[tauri::command]
+#[instrument(name = "get_proxy_settings", skip(state))]
+pub async fn get_proxy_settings(state: State<'_, AppState>) -> Result<ProxySettings, String> {
+    Ok(load_proxy_settings(&state.db).await)
+}
+
+/// Updates the proxy settings (global proxy, `no_proxy` hosts and per-target overrides).
+///
+/// # Arguments
+/// * `settings` - The complete proxy settings to store
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_proxy_settings", skip(state, settings))]
+pub async fn update_proxy_settings(
+    settings: ProxySettings,
+    state: State<'_, AppState>,
+) -> Result<ProxySettings, String> {
+    info!("Updating proxy settings");
+
+    crate::proxy::validate_settings(&settings).map_err(|e| {
+        warn!(error = %e, "Invalid proxy settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize proxy settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save proxy settings");
+            format!("Failed to save proxy settings: {}", e)
+        })?;
+
+    crate::proxy::set_saved(settings.clone());
+    if crate::proxy::overridden_by_config_file() {
+        warn!("Proxy settings saved but replaced by the config file until it is removed");
+    }
+    info!("Proxy settings updated successfully");
+    Ok(settings)
+}
@@ -0,0 +1,334 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduled workflow run commands.
+//!
+//! Schedules run a prompt with an agent in a workflow on a cron expression;
+//! the runs are executed by the scheduler of the application state (see
+//! [`crate::scheduler`]) and stored as regular messages.
+
+use crate::{
+    db::DBClient,
+    inbox,
+    models::{
+        pending_item::PendingItemKind,
+        schedule::{Schedule, ScheduleCreate},
+        WorkflowCreate, WorkflowStatus,
+    },
+    scheduler::{next_run, set_run_times, SCHEDULE_SELECT_FIELDS},
+    security::{Validator, WORKFLOW_VAULT},
+    tools::constants::scheduler as scheduler_const,
+    AppState,
+};
+use chrono::Utc;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+/// Loads all schedules, sorted by name.
+pub(crate) async fn load_schedules(db: &DBClient) -> Result<Vec<Schedule>, String> {
+    let query = format!(
+        "SELECT {} FROM schedule ORDER BY name",
+        SCHEDULE_SELECT_FIELDS
+    );
+    let rows = db
+        .query_json(&query)
+        .await
+        .map_err(|e| format!("Failed to load schedules: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect())
+}
+
+/// Loads a schedule, failing if it does not exist.
+pub(crate) async fn load_schedule(db: &DBClient, schedule_id: &str) -> Result<Schedule, String> {
+    let query = format!(
+        "SELECT {} FROM schedule WHERE meta::id(id) = $id",
+        SCHEDULE_SELECT_FIELDS
+    );
+    let rows = db
+        .query_json_with_params(
+            &query,
+            vec![("id".to_string(), serde_json::json!(schedule_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load schedule: {}", e))?;
+    rows.into_iter()
+        .find_map(|row| serde_json::from_value(row).ok())
+        .ok_or_else(|| "Schedule not found".to_string())
+}
+
+/// Validates a schedule name (trimmed, non-empty, bounded).
+fn validate_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Schedule name cannot be empty".to_string());
+    }
+    if trimmed.chars().count() > scheduler_const::MAX_NAME_LEN {
+        return Err(format!(
+            "Schedule name exceeds {} characters",
+            scheduler_const::MAX_NAME_LEN
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a cron expression and returns it trimmed with its next run.
+fn validate_cron(cron: &str) -> Result<(String, chrono::DateTime<Utc>), String> {
+    let trimmed = cron.trim();
+    if trimmed.len() > scheduler_const::MAX_CRON_LEN {
+        return Err(format!(
+            "Cron expression exceeds {} characters",
+            scheduler_const::MAX_CRON_LEN
+        ));
+    }
+    let next = next_run(trimmed, Utc::now())?
+        .ok_or_else(|| "Cron expression never matches a date".to_string())?;
+    Ok((trimmed.to_string(), next))
+}
+
+/// Validates a schedule ID (UUID).
+fn validate_schedule_id(schedule_id: &str) -> Result<String, String> {
+    Validator::validate_uuid(schedule_id).map_err(|e| {
+        warn!(error = %e, "Invalid schedule_id");
+        format!("Invalid schedule_id: {}", e)
+    })
+}
+
+/// Creates a schedule.
+///
+/// Without `workflow_id`, a workflow named after the schedule is created to
+/// receive the runs. Sensitive workflows cannot be scheduled (their contents
+/// can only be written while they are open).
+///
+/// # Arguments
+/// * `name` - Schedule name
+/// * `cron` - Cron expression in local time (e.g. `0 9 * * mon-fri`, `@daily`)
+/// * `agent_id` - Agent executing the prompt
+/// * `prompt` - Prompt sent at each run
+/// * `workflow_id` - Workflow receiving the runs (created if omitted)
+///
+/// # Returns
+/// The created schedule with its next run
+#[tauri::command]
+#[instrument(name = "create_schedule", skip(state, prompt))]
+pub async fn create_schedule(
+    name: String,
+    cron: String,
+    agent_id: String,
+    prompt: String,
+    workflow_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Schedule, String> {
+    info!("Creating schedule");
+
+    let name = validate_name(&name)?;
+    let (cron, next_run_at) = validate_cron(&cron)?;
+    let agent_id = Validator::validate_agent_id(&agent_id).map_err(|e| {
+        warn!(error = %e, "Invalid agent_id");
+        format!("Invalid agent_id: {}", e)
+    })?;
+    if state.registry.get(&agent_id).await.is_none() {
+        return Err("Agent not found".to_string());
+    }
+    let prompt = Validator::validate_message(&prompt).map_err(|e| {
+        warn!(error = %e, "Invalid prompt");
+        format!("Invalid prompt: {}", e)
+    })?;
+    if load_schedules(&state.db).await?.len() >= scheduler_const::MAX_SCHEDULES {
+        return Err(format!(
+            "Maximum number of schedules reached ({})",
+            scheduler_const::MAX_SCHEDULES
+        ));
+    }
+
+    let workflow_id = match workflow_id {
+        Some(workflow_id) => {
+            let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+                warn!(error = %e, "Invalid workflow_id");
+                format!("Invalid workflow_id: {}", e)
+            })?;
+            let rows = state
+                .db
+                .query_json_with_params(
+                    "SELECT meta::id(id) AS id FROM workflow WHERE meta::id(id) = $id",
+                    vec![("id".to_string(), serde_json::json!(workflow_id))],
+                )
+                .await
+                .map_err(|e| format!("Failed to load workflow: {}", e))?;
+            if rows.is_empty() {
+                return Err("Workflow not found".to_string());
+            }
+            if WORKFLOW_VAULT.is_sensitive(&state.db, &workflow_id).await? {
+                return Err("Sensitive workflows cannot be scheduled".to_string());
+            }
+            workflow_id
+        }
+        None => {
+            let workflow_id = Uuid::new_v4().to_string();
+            let workflow =
+                WorkflowCreate::new(name.clone(), agent_id.clone(), WorkflowStatus::Idle);
+            state
+                .db
+                .create("workflow", &workflow_id, workflow)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to create schedule workflow");
+                    format!("Failed to create workflow: {}", e)
+                })?;
+            workflow_id
+        }
+    };
+
+    let schedule_id = Uuid::new_v4().to_string();
+    let record = ScheduleCreate {
+        name,
+        cron,
+        agent_id,
+        workflow_id,
+        prompt,
+        paused: false,
+    };
+    state
+        .db
+        .create("schedule", &schedule_id, record)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create schedule");
+            format!("Failed to create schedule: {}", e)
+        })?;
+    set_run_times(&state.db, &schedule_id, Some(next_run_at), None).await?;
+
+    info!(schedule_id = %schedule_id, next_run_at = %next_run_at, "Schedule created");
+    load_schedule(&state.db, &schedule_id).await
+}
+
+/// Lists the schedules.
+///
+/// # Returns
+/// Schedules sorted by name, with their next run and last outcome
+#[tauri::command]
+#[instrument(name = "list_schedules", skip(state))]
+pub async fn list_schedules(state: State<'_, AppState>) -> Result<Vec<Schedule>, String> {
+    info!("Listing schedules");
+
+    let schedules = load_schedules(&state.db).await.map_err(|e| {
+        error!(error = %e, "Failed to list schedules");
+        e
+    })?;
+
+    info!(count = schedules.len(), "Schedules loaded");
+    Ok(schedules)
+}
+
+/// Pauses a schedule (a run in progress completes).
+///
+/// # Arguments
+/// * `schedule_id` - Schedule ID
+///
+/// # Returns
+/// The paused schedule
+#[tauri::command]
+#[instrument(name = "pause_schedule", skip(state))]
+pub async fn pause_schedule(
+    schedule_id: String,
+    state: State<'_, AppState>,
+) -> Result<Schedule, String> {
+    info!("Pausing schedule");
+
+    let schedule_id = validate_schedule_id(&schedule_id)?;
+    load_schedule(&state.db, &schedule_id).await?;
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('schedule', $id) SET paused = true, next_run_at = NONE",
+            vec![("id".to_string(), serde_json::json!(schedule_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to pause schedule");
+            format!("Failed to pause schedule: {}", e)
+        })?;
+
+    info!(schedule_id = %schedule_id, "Schedule paused");
+    load_schedule(&state.db, &schedule_id).await
+}
+
+/// Resumes a paused schedule from now (missed runs are not caught up).
+///
+/// # Arguments
+/// * `schedule_id` - Schedule ID
+///
+/// # Returns
+/// The resumed schedule with its next run
+#[tauri::command]
+#[instrument(name = "resume_schedule", skip(state))]
+pub async fn resume_schedule(
+    schedule_id: String,
+    state: State<'_, AppState>,
+) -> Result<Schedule, String> {
+    info!("Resuming schedule");
+
+    let schedule_id = validate_schedule_id(&schedule_id)?;
+    let schedule = load_schedule(&state.db, &schedule_id).await?;
+    let (_, next_run_at) = validate_cron(&schedule.cron)?;
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('schedule', $id) SET paused = false",
+            vec![("id".to_string(), serde_json::json!(schedule_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to resume schedule");
+            format!("Failed to resume schedule: {}", e)
+        })?;
+    set_run_times(&state.db, &schedule_id, Some(next_run_at), None).await?;
+
+    info!(schedule_id = %schedule_id, next_run_at = %next_run_at, "Schedule resumed");
+    load_schedule(&state.db, &schedule_id).await
+}
+
+/// Deletes a schedule (its workflow and past runs are kept).
+///
+/// # Arguments
+/// * `schedule_id` - Schedule ID
+#[tauri::command]
+#[instrument(name = "delete_schedule", skip(state))]
+pub async fn delete_schedule(
+    schedule_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!("Deleting schedule");
+
+    let schedule_id = validate_schedule_id(&schedule_id)?;
+    load_schedule(&state.db, &schedule_id).await?;
+
+    state
+        .db
+        .delete(&format!("schedule:{}", schedule_id))
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete schedule");
+            format!("Failed to delete schedule: {}", e)
+        })?;
+
+    inbox::resolve_pending_item(&state.db, PendingItemKind::FailedSchedule, &schedule_id).await;
+
+    info!(schedule_id = %schedule_id, "Schedule deleted");
+    Ok(())
+}
@@ -0,0 +1,463 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Full-text search commands over messages and workflow names.
+//!
+//! Both commands rely on the `SEARCH` indexes of the schema: keywords are
+//! matched case and accent insensitively, hits are ranked by BM25 relevance.
+//! Contents of sensitive workflows are stored encrypted and never match.
+
+use crate::{
+    db::DBClient,
+    models::search::{
+        highlight_snippet, parse_highlighted, MessageSearchHit, SearchQuery, SearchResults,
+        WorkflowSearchHit, MATCH_END, MATCH_START,
+    },
+    security::{workflow_vault::ENCRYPTED_PREFIX, Validator},
+    tools::constants::search as search_const,
+    AppState,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Message row returned by the search query
+#[derive(Deserialize)]
+struct MessageRow {
+    id: String,
+    workflow_id: String,
+    role: String,
+    timestamp: DateTime<Utc>,
+    score: Option<f64>,
+    highlighted: String,
+}
+
+/// Workflow row returned by the search query
+#[derive(Deserialize)]
+struct WorkflowRow {
+    id: String,
+    agent_id: String,
+    updated_at: DateTime<Utc>,
+    score: Option<f64>,
+    highlighted: String,
+}
+
+/// Searches message contents by keywords.
+///
+/// # Arguments
+/// * `query` - Keywords and pagination
+/// * `workflow_id` - Only search the messages of this workflow
+///
+/// # Returns
+/// Page of matching messages, most relevant first, each with an excerpt
+/// around its first match
+#[tauri::command]
+#[instrument(name = "search_messages", skip(state, query))]
+pub async fn search_messages(
+    query: SearchQuery,
+    workflow_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SearchResults<MessageSearchHit>, String> {
+    let workflow_id = workflow_id
+        .map(|id| Validator::validate_uuid(&id))
+        .transpose()
+        .map_err(|e| {
+            warn!(error = %e, "Invalid workflow_id");
+            format!("Invalid workflow_id: {}", e)
+        })?;
+    let results = search_message_contents(&state.db, &query, workflow_id.as_deref()).await?;
+
+    info!(
+        count = results.hits.len(),
+        total = results.total,
+        "Messages searched"
+    );
+    Ok(results)
+}
+
+/// Searches workflow names by keywords.
+///
+/// # Arguments
+/// * `query` - Keywords and pagination
+///
+/// # Returns
+/// Page of matching workflows, most relevant first, names highlighted
+#[tauri::command]
+#[instrument(name = "search_workflows", skip(state, query))]
+pub async fn search_workflows(
+    query: SearchQuery,
+    state: State<'_, AppState>,
+) -> Result<SearchResults<WorkflowSearchHit>, String> {
+    let results = search_workflow_names(&state.db, &query).await?;
+
+    info!(
+        count = results.hits.len(),
+        total = results.total,
+        "Workflows searched"
+    );
+    Ok(results)
+}
+
+/// Bound parameters and bounds of a search page
+struct SearchPage {
+    params: Vec<(String, Value)>,
+    limit: u32,
+    offset: u32,
+}
+
+/// Validates the search text and resolves the page bounds.
+fn search_page(query: &SearchQuery) -> Result<SearchPage, String> {
+    let text = query.text.trim();
+    if text.is_empty() {
+        return Err("Search text cannot be empty".to_string());
+    }
+    if text.chars().count() > search_const::MAX_QUERY_CHARS {
+        return Err(format!(
+            "Search text exceeds {} characters",
+            search_const::MAX_QUERY_CHARS
+        ));
+    }
+
+    let limit = query
+        .limit
+        .map(|l| l as usize)
+        .unwrap_or(search_const::DEFAULT_PAGE_SIZE)
+        .clamp(1, search_const::MAX_PAGE_SIZE) as u32;
+    let offset = query.offset.unwrap_or(0);
+
+    let params = vec![
+        ("text".to_string(), json!(text)),
+        ("open".to_string(), json!(MATCH_START.to_string())),
+        ("close".to_string(), json!(MATCH_END.to_string())),
+        ("encrypted_prefix".to_string(), json!(ENCRYPTED_PREFIX)),
+        ("limit".to_string(), json!(limit)),
+        ("offset".to_string(), json!(offset)),
+    ];
+    Ok(SearchPage {
+        params,
+        limit,
+        offset,
+    })
+}
+
+/// Counts the rows matching a search condition.
+async fn count_matches(
+    db: &DBClient,
+    table: &str,
+    condition: &str,
+    params: Vec<(String, Value)>,
+) -> Result<u32, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT count() FROM {} WHERE {} GROUP ALL",
+                table, condition
+            ),
+            params,
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, table = %table, "Failed to count search hits");
+            format!("Failed to search: {}", e)
+        })?;
+
+    Ok(rows
+        .first()
+        .and_then(|v| v.get("count"))
+        .and_then(|c| c.as_u64())
+        .unwrap_or(0) as u32)
+}
+
+/// Runs a message search (shared by the command and tests).
+pub(crate) async fn search_message_contents(
+    db: &DBClient,
+    query: &SearchQuery,
+    workflow_id: Option<&str>,
+) -> Result<SearchResults<MessageSearchHit>, String> {
+    let SearchPage {
+        mut params,
+        limit,
+        offset,
+    } = search_page(query)?;
+    let mut condition =
+        "content @1@ $text AND !string::starts_with(content, $encrypted_prefix)".to_string();
+    if let Some(workflow_id) = workflow_id {
+        condition.push_str(" AND workflow_id = $workflow_id");
+        params.push(("workflow_id".to_string(), json!(workflow_id)));
+    }
+
+    let total = count_matches(db, "message", &condition, params.clone()).await?;
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT meta::id(id) AS id, workflow_id, role, timestamp, \
+                 search::score(1) AS score, search::highlight($open, $close, 1) AS highlighted \
+                 FROM message WHERE {} ORDER BY score DESC, timestamp DESC \
+                 LIMIT $limit START $offset",
+                condition
+            ),
+            params,
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to search messages");
+            format!("Failed to search messages: {}", e)
+        })?;
+    let rows: Vec<MessageRow> = rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect();
+
+    let names = load_workflow_names(db, &rows).await?;
+    let hits: Vec<MessageSearchHit> = rows
+        .into_iter()
+        .map(|row| MessageSearchHit {
+            workflow_name: names.get(&row.workflow_id).cloned(),
+            message_id: row.id,
+            workflow_id: row.workflow_id,
+            role: row.role,
+            timestamp: row.timestamp,
+            score: row.score.unwrap_or(0.0),
+            snippet: highlight_snippet(&row.highlighted, search_const::SNIPPET_CONTEXT_CHARS),
+        })
+        .collect();
+
+    let has_more = offset + (hits.len() as u32) < total;
+    Ok(SearchResults {
+        hits,
+        total,
+        offset,
+        limit,
+        has_more,
+    })
+}
+
+/// Loads the names of the workflows of the message hits.
+async fn load_workflow_names(
+    db: &DBClient,
+    rows: &[MessageRow],
+) -> Result<HashMap<String, String>, String> {
+    let mut workflow_ids: Vec<&str> = rows.iter().map(|r| r.workflow_id.as_str()).collect();
+    workflow_ids.sort_unstable();
+    workflow_ids.dedup();
+    if workflow_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id, name FROM workflow WHERE meta::id(id) IN $ids",
+            vec![("ids".to_string(), json!(workflow_ids))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow names: {}", e))?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            Some((
+                row.get("id")?.as_str()?.to_string(),
+                row.get("name")?.as_str()?.to_string(),
+            ))
+        })
+        .collect())
+}
+
+/// Runs a workflow search (shared by the command and tests).
+pub(crate) async fn search_workflow_names(
+    db: &DBClient,
+    query: &SearchQuery,
+) -> Result<SearchResults<WorkflowSearchHit>, String> {
+    let SearchPage {
+        params,
+        limit,
+        offset,
+    } = search_page(query)?;
+    let condition = "name @1@ $text";
+
+    let total = count_matches(db, "workflow", condition, params.clone()).await?;
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT meta::id(id) AS id, agent_id, updated_at, search::score(1) AS score, \
+                 search::highlight($open, $close, 1) AS highlighted \
+                 FROM workflow WHERE {} ORDER BY score DESC, updated_at DESC \
+                 LIMIT $limit START $offset",
+                condition
+            ),
+            params,
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to search workflows");
+            format!("Failed to search workflows: {}", e)
+        })?;
+
+    let hits: Vec<WorkflowSearchHit> = rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value::<WorkflowRow>(row).ok())
+        .map(|row| WorkflowSearchHit {
+            workflow_id: row.id,
+            name: parse_highlighted(&row.highlighted),
+            agent_id: row.agent_id,
+            updated_at: row.updated_at,
+            score: row.score.unwrap_or(0.0),
+        })
+        .collect();
+
+    let has_more = offset + (hits.len() as u32) < total;
+    Ok(SearchResults {
+        hits,
+        total,
+        offset,
+        limit,
+        has_more,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    async fn setup_db() -> (tempfile::TempDir, DBClient) {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("search_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+        (temp_dir, db)
+    }
+
+    async fn create_workflow(db: &DBClient, id: &str, name: &str) {
+        db.create(
+            "workflow",
+            id,
+            json!({ "id": id, "name": name, "agent_id": "agent_a", "status": "idle" }),
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn create_message(db: &DBClient, id: &str, workflow_id: &str, content: &str) {
+        db.create(
+            "message",
+            id,
+            json!({
+                "id": id,
+                "workflow_id": workflow_id,
+                "role": "assistant",
+                "content": content,
+                "tokens": 0
+            }),
+        )
+        .await
+        .unwrap();
+    }
+
+    fn query(text: &str, limit: Option<u32>, offset: Option<u32>) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            limit,
+            offset,
+        }
+    }
+
+    fn matched(segments: &[crate::models::search::HighlightSegment]) -> Vec<String> {
+        segments
+            .iter()
+            .filter(|s| s.matched)
+            .map(|s| s.text.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_messages() {
+        let (_dir, db) = setup_db().await;
+        create_workflow(&db, "wf1", "Release planning").await;
+        create_workflow(&db, "wf2", "Holidays").await;
+        create_message(
+            &db,
+            "m1",
+            "wf1",
+            "The Déploiement of the release is on Friday",
+        )
+        .await;
+        create_message(&db, "m2", "wf1", "Nothing to see here").await;
+        create_message(&db, "m3", "wf2", "Book the hotel before the deploiement").await;
+        create_message(&db, "m4", "wf2", "enc:v1:ZGVwbG9pZW1lbnQ=").await;
+
+        // Case and accent insensitive, encrypted contents excluded
+        let results = search_message_contents(&db, &query("DEPLOIEMENT", None, None), None)
+            .await
+            .unwrap();
+        assert_eq!(results.total, 2);
+        let mut ids: Vec<&str> = results.hits.iter().map(|h| h.message_id.as_str()).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["m1", "m3"]);
+        let hit = results.hits.iter().find(|h| h.message_id == "m1").unwrap();
+        assert_eq!(hit.workflow_name.as_deref(), Some("Release planning"));
+        assert_eq!(matched(&hit.snippet), vec!["Déploiement"]);
+
+        // Workflow filter and pagination
+        let results =
+            search_message_contents(&db, &query("deploiement", Some(1), Some(0)), Some("wf2"))
+                .await
+                .unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(results.hits[0].message_id, "m3");
+        assert!(!results.has_more);
+
+        let page = search_message_contents(&db, &query("deploiement", Some(1), None), None)
+            .await
+            .unwrap();
+        assert_eq!(page.hits.len(), 1);
+        assert!(page.has_more);
+
+        assert!(search_message_contents(&db, &query("  ", None, None), None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_workflows() {
+        let (_dir, db) = setup_db().await;
+        create_workflow(&db, "wf1", "Release planning").await;
+        create_workflow(&db, "wf2", "Release notes review").await;
+        create_workflow(&db, "wf3", "Holidays").await;
+
+        let results = search_workflow_names(&db, &query("release", None, None))
+            .await
+            .unwrap();
+        assert_eq!(results.total, 2);
+        assert!(results
+            .hits
+            .iter()
+            .all(|h| matched(&h.name) == vec!["Release"]));
+
+        let results = search_workflow_names(&db, &query("release notes", None, None))
+            .await
+            .unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(results.hits[0].workflow_id, "wf2");
+
+        // Quotes are bound, not interpolated
+        let results = search_workflow_names(&db, &query("it's", None, None))
+            .await
+            .unwrap();
+        assert_eq!(results.total, 0);
+    }
+}
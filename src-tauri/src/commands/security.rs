@@ -17,29 +17,47 @@
 //! Provides secure storage and retrieval of API keys for LLM providers
 //! using OS keychain + AES-256-GCM encryption.
 
+use crate::db::DBClient;
+use crate::inbox;
+use crate::mcp::oauth::{OAuthCredentialStore, OAuthCredentials};
+use crate::models::api_key::{ApiKeyInfo, ApiKeyMetadata, ApiKeyStatus};
+use crate::models::audit::{AuditAction, AuditLogCreate};
+use crate::models::pending_item::{PendingItemCreate, PendingItemKind};
 #[cfg(test)]
 use crate::security::ValidationError;
-use crate::security::{KeyStore, KeyStoreError, Validator};
+use crate::security::{audit, KeyStore, KeyStoreError, Validator};
+use crate::tools::constants::api_keys::LAST_USED_RESOLUTION_SECS;
+use crate::AppState;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tauri::State;
-use tracing::{error, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 
 /// Thread-safe wrapper for KeyStore
+///
+/// Cloning is cheap and shares the same underlying keystore.
+#[derive(Clone)]
 pub struct SecureKeyStore {
-    inner: KeyStore,
+    inner: Arc<KeyStore>,
+    /// Last time the use of each entry was recorded (throttles metadata writes)
+    last_used_recorded: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
 }
 
 impl SecureKeyStore {
     /// Creates a new SecureKeyStore instance.
     pub fn new() -> Result<Self, KeyStoreError> {
         Ok(Self {
-            inner: KeyStore::new()?,
+            inner: Arc::new(KeyStore::new()?),
+            last_used_recorded: Arc::default(),
         })
     }
 
     /// Creates a SecureKeyStore without encryption (for testing).
     pub fn new_without_encryption() -> Self {
         Self {
-            inner: KeyStore::new_without_encryption(),
+            inner: Arc::new(KeyStore::new_without_encryption()),
+            last_used_recorded: Arc::default(),
         }
     }
 
@@ -49,8 +67,13 @@ impl SecureKeyStore {
     }
 
     /// Gets the API key for a provider, if it exists.
+    ///
+    /// The use is recorded in the key metadata, if any, at most once per
+    /// `LAST_USED_RESOLUTION_SECS`.
     pub fn get_key(&self, provider: &str) -> Option<String> {
-        self.inner.get(provider).ok()
+        let api_key = self.inner.get(provider).ok()?;
+        self.record_use(provider);
+        Some(api_key)
     }
 
     /// Saves an API key for a provider.
@@ -62,6 +85,119 @@ impl SecureKeyStore {
     pub fn delete_key(&self, provider: &str) -> Result<(), KeyStoreError> {
         self.inner.delete(provider)
     }
+
+    /// Saves a provider API key with fresh metadata.
+    pub fn save_api_key(
+        &self,
+        provider: &str,
+        api_key: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKeyMetadata, KeyStoreError> {
+        self.inner.save(provider, api_key)?;
+        let metadata = ApiKeyMetadata::new(provider, Utc::now(), expires_at);
+        self.write_metadata(&metadata);
+        Ok(metadata)
+    }
+
+    /// Replaces the stored API key of a provider.
+    ///
+    /// The new key is read back after being written; if writing or reading it
+    /// fails, the previous key is restored, so the provider never ends up
+    /// without a key.
+    pub fn rotate_api_key(
+        &self,
+        provider: &str,
+        api_key: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKeyMetadata, KeyStoreError> {
+        let previous = self.inner.get(provider)?;
+
+        let swapped = self.inner.save(provider, api_key).and_then(|_| {
+            if self.inner.get(provider)? == api_key {
+                Ok(())
+            } else {
+                Err(KeyStoreError::KeychainError(
+                    "Stored key does not match the new key".to_string(),
+                ))
+            }
+        });
+        if let Err(e) = swapped {
+            if let Err(restore_error) = self.inner.save(provider, &previous) {
+                error!(error = %restore_error, "Failed to restore the previous API key");
+            }
+            return Err(e);
+        }
+
+        let now = Utc::now();
+        let metadata = ApiKeyMetadata {
+            rotated_at: Some(now),
+            ..ApiKeyMetadata::new(provider, now, expires_at)
+        };
+        self.write_metadata(&metadata);
+        Ok(metadata)
+    }
+
+    /// Deletes a provider API key and its metadata.
+    pub fn delete_api_key(&self, provider: &str) -> Result<(), KeyStoreError> {
+        self.inner.delete(provider)?;
+        if let Err(e) = self.inner.delete_metadata(provider) {
+            warn!(error = %e, "Failed to delete API key metadata");
+        }
+        Ok(())
+    }
+
+    /// Metadata of a provider API key, if the key exists.
+    ///
+    /// Keys saved before metadata was tracked get metadata created now.
+    pub fn api_key_metadata(&self, provider: &str) -> Option<ApiKeyMetadata> {
+        if !self.inner.exists(provider) {
+            return None;
+        }
+        match self.inner.get_metadata(provider) {
+            Ok(Some(metadata)) => Some(metadata),
+            Ok(None) => {
+                let metadata = ApiKeyMetadata::new(provider, Utc::now(), None);
+                self.write_metadata(&metadata);
+                Some(metadata)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to read API key metadata");
+                None
+            }
+        }
+    }
+
+    /// Writes key metadata; a failure is logged and never fails the key operation.
+    fn write_metadata(&self, metadata: &ApiKeyMetadata) {
+        if let Err(e) = self.inner.save_metadata(metadata) {
+            warn!(error = %e, "Failed to store API key metadata");
+        }
+    }
+
+    /// Records the use of a key in its metadata (throttled).
+    fn record_use(&self, provider: &str) {
+        let now = Utc::now();
+        {
+            let mut recorded = self
+                .last_used_recorded
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if recorded
+                .get(provider)
+                .is_some_and(|at| now - *at < Duration::seconds(LAST_USED_RESOLUTION_SECS))
+            {
+                return;
+            }
+            recorded.insert(provider.to_string(), now);
+        }
+
+        // Only provider API keys have metadata (not workflow or pairing secrets)
+        if let Ok(Some(mut metadata)) = self.inner.get_metadata(provider) {
+            metadata.last_used = Some(now);
+            self.write_metadata(&metadata);
+            debug!(provider = %provider, "API key use recorded");
+        }
+    }
 }
 
 impl Default for SecureKeyStore {
@@ -70,15 +206,130 @@ impl Default for SecureKeyStore {
     }
 }
 
+/// Keystore entry name for the OAuth credentials of an MCP server
+fn mcp_oauth_key(server_id: &str) -> String {
+    format!("mcp_oauth_{}", server_id)
+}
+
+/// Records an API key access in the audit log.
+async fn audit_key_access(state: &AppState, provider: &str, operation: &str, success: bool) {
+    audit::record(
+        &state.db,
+        AuditLogCreate {
+            success,
+            details: serde_json::json!({ "operation": operation }),
+            ..AuditLogCreate::new(AuditAction::ApiKeyAccess, "user", provider)
+        },
+    )
+    .await;
+}
+
+impl OAuthCredentialStore for SecureKeyStore {
+    fn load_credentials(&self, server_id: &str) -> Option<OAuthCredentials> {
+        let stored = self.inner.get(&mcp_oauth_key(server_id)).ok()?;
+        match serde_json::from_str(&stored) {
+            Ok(credentials) => Some(credentials),
+            Err(e) => {
+                warn!(server_id = %server_id, error = %e, "Invalid stored MCP OAuth credentials");
+                None
+            }
+        }
+    }
+
+    fn save_credentials(
+        &self,
+        server_id: &str,
+        credentials: &OAuthCredentials,
+    ) -> Result<(), String> {
+        let json = serde_json::to_string(credentials).map_err(|e| e.to_string())?;
+        self.inner
+            .save(&mcp_oauth_key(server_id), &json)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Providers with a stored API key (known providers and custom providers).
+async fn stored_api_key_providers(db: &DBClient, keystore: &SecureKeyStore) -> Vec<String> {
+    let mut providers = keystore.inner.list_providers();
+    match db
+        .query_json("SELECT name FROM custom_provider ORDER BY name")
+        .await
+    {
+        Ok(rows) => providers.extend(
+            rows.iter()
+                .filter_map(|row| row["name"].as_str())
+                .filter(|name| keystore.inner.exists(name))
+                .map(str::to_string),
+        ),
+        Err(e) => debug!(error = %e, "Failed to load custom providers"),
+    }
+    providers.sort();
+    providers.dedup();
+    providers
+}
+
+/// Metadata and status of the stored provider API keys.
+async fn api_key_infos(db: &DBClient, keystore: &SecureKeyStore) -> Vec<ApiKeyInfo> {
+    let now = Utc::now();
+    stored_api_key_providers(db, keystore)
+        .await
+        .iter()
+        .filter_map(|provider| keystore.api_key_metadata(provider))
+        .map(|metadata| ApiKeyInfo {
+            status: metadata.status(now),
+            metadata,
+        })
+        .collect()
+}
+
+/// Checks the stored API keys at startup.
+///
+/// Stale, expiring and expired keys are logged and listed in the priority
+/// inbox; the inbox item of a key that no longer needs attention is resolved.
+pub async fn check_api_key_expiry(db: &DBClient, keystore: &SecureKeyStore) {
+    for info in api_key_infos(db, keystore).await {
+        let provider = &info.metadata.provider;
+        match info.status {
+            ApiKeyStatus::Ok => {
+                inbox::resolve_pending_item(db, PendingItemKind::ExpiringApiKey, provider).await;
+                continue;
+            }
+            ApiKeyStatus::Stale => warn!(
+                provider = %provider,
+                created_at = %info.metadata.created_at,
+                "API key is stale, rotation recommended"
+            ),
+            ApiKeyStatus::ExpiringSoon | ApiKeyStatus::Expired => warn!(
+                provider = %provider,
+                expires_at = ?info.metadata.expires_at,
+                status = ?info.status,
+                "API key expires soon or has expired"
+            ),
+        }
+        inbox::track_pending_item(
+            db,
+            PendingItemCreate::expiring_api_key(provider, info.status, info.metadata.expires_at),
+        )
+        .await;
+    }
+}
+
 /// Saves an API key for a provider.
 ///
 /// The key is validated, encrypted with AES-256-GCM, and stored in the OS keychain.
+///
+/// # Arguments
+/// * `provider` - Provider name
+/// * `api_key` - API key
+/// * `expires_at` - Expiry date of the key, if known (reminders before it)
 #[tauri::command]
-#[instrument(name = "save_api_key", skip(api_key, keystore), fields(provider = %provider))]
+#[instrument(name = "save_api_key", skip(api_key, keystore, state), fields(provider = %provider))]
 pub async fn save_api_key(
     provider: String,
     api_key: String,
+    expires_at: Option<DateTime<Utc>>,
     keystore: State<'_, SecureKeyStore>,
+    state: State<'_, AppState>,
 ) -> Result<(), String> {
     info!("Saving API key");
 
@@ -95,13 +346,18 @@ pub async fn save_api_key(
     })?;
 
     // Save to keystore
-    keystore
-        .inner
-        .save(&validated_provider, &api_key)
-        .map_err(|e| {
-            error!(error = %e, "Failed to save API key");
-            format!("Failed to save API key: {}", e)
-        })?;
+    let saved = keystore.save_api_key(&validated_provider, &api_key, expires_at);
+    audit_key_access(&state, &validated_provider, "save", saved.is_ok()).await;
+    saved.map_err(|e| {
+        error!(error = %e, "Failed to save API key");
+        format!("Failed to save API key: {}", e)
+    })?;
+    inbox::resolve_pending_item(
+        &state.db,
+        PendingItemKind::ExpiringApiKey,
+        &validated_provider,
+    )
+    .await;
 
     info!("API key saved successfully");
     Ok(())
@@ -111,10 +367,11 @@ pub async fn save_api_key(
 ///
 /// Returns the decrypted API key if found.
 #[tauri::command]
-#[instrument(name = "get_api_key", skip(keystore), fields(provider = %provider))]
+#[instrument(name = "get_api_key", skip(keystore, state), fields(provider = %provider))]
 pub async fn get_api_key(
     provider: String,
     keystore: State<'_, SecureKeyStore>,
+    state: State<'_, AppState>,
 ) -> Result<String, String> {
     info!("Retrieving API key");
 
@@ -125,7 +382,9 @@ pub async fn get_api_key(
     })?;
 
     // Get from keystore
-    let api_key = keystore.inner.get(&validated_provider).map_err(|e| {
+    let api_key = keystore.inner.get(&validated_provider);
+    audit_key_access(&state, &validated_provider, "read", api_key.is_ok()).await;
+    let api_key = api_key.map_err(|e| {
         // Normalized error message to prevent provider enumeration
         warn!("API key operation failed for provider");
         match &e {
@@ -140,10 +399,11 @@ pub async fn get_api_key(
 
 /// Deletes an API key for a provider.
 #[tauri::command]
-#[instrument(name = "delete_api_key", skip(keystore), fields(provider = %provider))]
+#[instrument(name = "delete_api_key", skip(keystore, state), fields(provider = %provider))]
 pub async fn delete_api_key(
     provider: String,
     keystore: State<'_, SecureKeyStore>,
+    state: State<'_, AppState>,
 ) -> Result<(), String> {
     info!("Deleting API key");
 
@@ -154,7 +414,9 @@ pub async fn delete_api_key(
     })?;
 
     // Delete from keystore
-    keystore.inner.delete(&validated_provider).map_err(|e| {
+    let deleted = keystore.delete_api_key(&validated_provider);
+    audit_key_access(&state, &validated_provider, "delete", deleted.is_ok()).await;
+    deleted.map_err(|e| {
         // Normalized error message to prevent provider enumeration
         warn!("API key operation failed for provider");
         match &e {
@@ -163,10 +425,89 @@ pub async fn delete_api_key(
         }
     })?;
 
+    inbox::resolve_pending_item(
+        &state.db,
+        PendingItemKind::ExpiringApiKey,
+        &validated_provider,
+    )
+    .await;
+
     info!("API key deleted successfully");
     Ok(())
 }
 
+/// Replaces the API key of a provider and re-initializes the providers.
+///
+/// The previous key is restored if the new one cannot be stored. LLM
+/// providers and the embedding service are then reconfigured with the new key.
+///
+/// # Arguments
+/// * `provider` - Provider name (must already have a key)
+/// * `api_key` - New API key
+/// * `expires_at` - Expiry date of the new key, if known
+///
+/// # Returns
+/// Metadata and status of the new key
+#[tauri::command]
+#[instrument(name = "rotate_api_key", skip(api_key, keystore, state), fields(provider = %provider))]
+pub async fn rotate_api_key(
+    provider: String,
+    api_key: String,
+    expires_at: Option<DateTime<Utc>>,
+    keystore: State<'_, SecureKeyStore>,
+    state: State<'_, AppState>,
+) -> Result<ApiKeyInfo, String> {
+    info!("Rotating API key");
+
+    let validated_provider = Validator::validate_provider(&provider).map_err(|e| {
+        warn!(error = %e, "Invalid provider name");
+        format!("Invalid provider: {}", e)
+    })?;
+    Validator::validate_api_key(&api_key).map_err(|e| {
+        warn!(error = %e, "Invalid API key format");
+        format!("Invalid API key: {}", e)
+    })?;
+
+    let rotated = keystore.rotate_api_key(&validated_provider, &api_key, expires_at);
+    audit_key_access(&state, &validated_provider, "rotate", rotated.is_ok()).await;
+    let metadata = rotated.map_err(|e| {
+        warn!("API key operation failed for provider");
+        match &e {
+            KeyStoreError::NotFound(_) => "API key not found".to_string(),
+            _ => "API key operation failed".to_string(),
+        }
+    })?;
+
+    // Providers and the embedding service hold the previous key
+    state.initialize_providers_from_config(&keystore).await;
+    state.initialize_embedding_from_config(&keystore).await;
+
+    inbox::resolve_pending_item(
+        &state.db,
+        PendingItemKind::ExpiringApiKey,
+        &validated_provider,
+    )
+    .await;
+
+    info!("API key rotated successfully");
+    Ok(ApiKeyInfo {
+        status: metadata.status(Utc::now()),
+        metadata,
+    })
+}
+
+/// Lists the metadata and status of the stored provider API keys.
+#[tauri::command]
+#[instrument(name = "list_api_key_metadata", skip(keystore, state))]
+pub async fn list_api_key_metadata(
+    keystore: State<'_, SecureKeyStore>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ApiKeyInfo>, String> {
+    let infos = api_key_infos(&state.db, &keystore).await;
+    info!(count = infos.len(), "API key metadata listed");
+    Ok(infos)
+}
+
 /// Checks if an API key exists for a provider.
 #[tauri::command]
 #[instrument(name = "has_api_key", skip(keystore), fields(provider = %provider))]
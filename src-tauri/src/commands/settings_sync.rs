@@ -0,0 +1,653 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Settings sync commands.
+//!
+//! Pairs this machine with another one of the user (`create_sync_pairing`
+//! on one, `accept_sync_pairing` with its code on the other) and exchanges
+//! agents, prompts and sanitized MCP server configurations with
+//! `sync_settings_now`.
+//! See [`crate::settings_sync`] for the protocol.
+
+use crate::commands::import_export::{build_export_package, import_package};
+use crate::commands::SecureKeyStore;
+use crate::db::DBClient;
+use crate::models::import_export::{ExportOptions, ExportPackage, ExportSelection, ImportCounts};
+use crate::models::settings_sync::{
+    SyncPairing, SyncPairingCreate, SyncPairingOffer, SyncResult, SyncRole, SyncSnapshot,
+    SyncTransport,
+};
+use crate::security::Validator;
+use crate::settings_sync::crypto::{generate_pairing_key, SyncCipher};
+use crate::settings_sync::mailbox::{
+    ensure_lan_mailbox, get_mailbox, local_network_ip, put_mailbox, stop_lan_mailbox,
+};
+use crate::settings_sync::merge::{plan_merge, sanitize_for_sync};
+use crate::settings_sync::pairing::{render_qr_svg, PairingCode};
+use crate::state::AppState;
+use crate::tools::constants::sync as sync_const;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use std::collections::HashMap;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Fields selected when loading pairings
+const PAIRING_SELECT: &str = "SELECT meta::id(id) AS id, device_name, peer_name, role, transport, \
+     mailbox_url, created_at, last_synced_at, last_received_at FROM sync_pairing";
+
+/// Keystore entry name of the key of a pairing
+fn pairing_key_name(pairing_id: &str) -> String {
+    format!("sync_pairing_key_{}", pairing_id)
+}
+
+/// Validates a device name (non-empty, bounded, no control characters).
+fn validate_device_name(name: &str) -> Result<String, String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err("Device name is required".to_string());
+    }
+    if trimmed.chars().count() > sync_const::MAX_DEVICE_NAME_LEN {
+        return Err(format!(
+            "Device name must be {} characters or less",
+            sync_const::MAX_DEVICE_NAME_LEN
+        ));
+    }
+    if trimmed.chars().any(char::is_control) {
+        return Err("Device name cannot contain control characters".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Validates a mailbox URL (http or https, with a host).
+fn validate_mailbox_url(url: &str) -> Result<String, String> {
+    let trimmed = url.trim().trim_end_matches('/');
+    let parsed = reqwest::Url::parse(trimmed).map_err(|e| format!("Invalid mailbox URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Mailbox URL must be an http(s) URL".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Loads all pairings (oldest first).
+async fn load_pairings(db: &DBClient) -> Result<Vec<SyncPairing>, String> {
+    let query = format!("{} ORDER BY created_at ASC", PAIRING_SELECT);
+    let rows = db
+        .query_json(&query)
+        .await
+        .map_err(|e| format!("Failed to load sync pairings: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value(row).ok())
+        .collect())
+}
+
+/// Loads a pairing.
+async fn load_pairing(db: &DBClient, pairing_id: &str) -> Result<Option<SyncPairing>, String> {
+    let query = format!("{} WHERE meta::id(id) = $id", PAIRING_SELECT);
+    let rows = db
+        .query_json_with_params(
+            &query,
+            vec![("id".to_string(), serde_json::json!(pairing_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load sync pairing: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .find_map(|row| serde_json::from_value(row).ok()))
+}
+
+/// Stores a new pairing (record and key).
+async fn store_pairing(
+    db: &DBClient,
+    keystore: &SecureKeyStore,
+    pairing_id: &str,
+    key: &[u8],
+    record: SyncPairingCreate,
+) -> Result<SyncPairing, String> {
+    keystore
+        .set_key(&pairing_key_name(pairing_id), &STANDARD.encode(key))
+        .map_err(|e| format!("Failed to store pairing key: {}", e))?;
+
+    if let Err(e) = db.create("sync_pairing", pairing_id, record).await {
+        let _ = keystore.delete_key(&pairing_key_name(pairing_id));
+        return Err(format!("Failed to save sync pairing: {}", e));
+    }
+
+    load_pairing(db, pairing_id)
+        .await?
+        .ok_or_else(|| "Sync pairing was not saved".to_string())
+}
+
+/// Exports the synced settings: permanent agents, prompts and MCP servers,
+/// with timestamps and without sanitization.
+async fn export_synced_settings(db: &DBClient) -> Result<ExportPackage, String> {
+    async fn ids(db: &DBClient, query: &str) -> Result<Vec<String>, String> {
+        let rows = db
+            .query_json(query)
+            .await
+            .map_err(|e| format!("Failed to list settings: {}", e))?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| row["id"].as_str().map(String::from))
+            .collect())
+    }
+
+    let selection = ExportSelection {
+        agents: ids(
+            db,
+            "SELECT meta::id(id) AS id FROM agent WHERE lifecycle = 'permanent'",
+        )
+        .await?,
+        mcp_servers: ids(db, "SELECT meta::id(id) AS id FROM mcp_server").await?,
+        models: Vec::new(),
+        prompts: ids(db, "SELECT meta::id(id) AS id FROM prompt").await?,
+    };
+    let options = ExportOptions {
+        sanitize_mcp: false,
+        ..ExportOptions::default()
+    };
+    build_export_package(db, &selection, &options, &HashMap::new()).await
+}
+
+/// Base URL this machine uses to reach the mailboxes of a pairing.
+///
+/// The initiator of a local network pairing hosts the mailbox server: it
+/// (re)starts it on the port of the pairing code and uses it on loopback.
+async fn mailbox_base_url(pairing: &SyncPairing) -> Result<String, String> {
+    if pairing.transport != SyncTransport::Lan || pairing.role != SyncRole::Initiator {
+        return Ok(pairing.mailbox_url.clone());
+    }
+    let port = reqwest::Url::parse(&pairing.mailbox_url)
+        .ok()
+        .and_then(|url| url.port())
+        .ok_or_else(|| "Invalid local mailbox URL".to_string())?;
+    let port = ensure_lan_mailbox(port).await?;
+    Ok(format!("http://127.0.0.1:{}", port))
+}
+
+/// Restarts the local network mailbox server at startup if a pairing created
+/// on this machine uses it, so the peer can sync while the app runs.
+pub async fn restore_lan_mailbox(db: &DBClient) -> Result<(), String> {
+    let pairings = load_pairings(db).await?;
+    if let Some(pairing) = pairings.iter().find(|pairing| {
+        pairing.transport == SyncTransport::Lan && pairing.role == SyncRole::Initiator
+    }) {
+        mailbox_base_url(pairing).await?;
+    }
+    Ok(())
+}
+
+/// Syncs with the peer of a pairing: applies its latest payload, then sends
+/// the local settings.
+pub(crate) async fn run_sync(
+    db: &DBClient,
+    client: &reqwest::Client,
+    pairing: &SyncPairing,
+    key: &[u8],
+) -> Result<SyncResult, String> {
+    let cipher = SyncCipher::new(&pairing.id, key)?;
+    let base_url = mailbox_base_url(pairing).await?;
+    let mut local = export_synced_settings(db).await?;
+
+    let mut result = SyncResult {
+        pairing_id: pairing.id.clone(),
+        pushed: ImportCounts::default(),
+        received: false,
+        imported: ImportCounts::default(),
+        skipped: ImportCounts::default(),
+        errors: Vec::new(),
+        synced_at: Utc::now(),
+    };
+
+    // Pull: apply the peer payload if it is newer than the last applied one
+    let peer = pairing.role.peer();
+    if let Some(sealed) = get_mailbox(client, &base_url, &cipher.mailbox_id(peer)).await? {
+        let plaintext = cipher.open(peer, &sealed)?;
+        let snapshot: SyncSnapshot = serde_json::from_slice(&plaintext)
+            .map_err(|e| format!("Invalid sync payload: {}", e))?;
+
+        if pairing
+            .last_received_at
+            .is_none_or(|last| snapshot.created_at > last)
+        {
+            let plan = plan_merge(&snapshot.package, &local);
+            let imported = import_package(
+                db,
+                &snapshot.package,
+                &plan.selection,
+                &plan.resolutions,
+                &plan.mcp_additions,
+            )
+            .await;
+            result.received = true;
+            result.imported = imported.imported;
+            result.skipped = plan.unchanged;
+            result.errors = imported.errors;
+
+            let peer_name = validate_device_name(&snapshot.device_name).ok();
+            db.execute_with_params(
+                "UPDATE type::thing('sync_pairing', $id) SET last_received_at = <datetime>$received_at, \
+                 peer_name = $peer_name ?? peer_name",
+                vec![
+                    ("id".to_string(), serde_json::json!(pairing.id)),
+                    (
+                        "received_at".to_string(),
+                        serde_json::json!(snapshot.created_at.to_rfc3339()),
+                    ),
+                    ("peer_name".to_string(), serde_json::json!(peer_name)),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to update sync pairing: {}", e))?;
+
+            if result.imported.agents + result.imported.mcp_servers + result.imported.prompts > 0 {
+                local = export_synced_settings(db).await?;
+            }
+        }
+    }
+
+    // Push: seal the sanitized local settings into this machine's mailbox
+    sanitize_for_sync(&mut local);
+    result.pushed = ImportCounts {
+        agents: local.agents.len(),
+        mcp_servers: local.mcp_servers.len(),
+        models: 0,
+        prompts: local.prompts.len(),
+    };
+    let snapshot = SyncSnapshot {
+        device_name: pairing.device_name.clone(),
+        created_at: result.synced_at,
+        package: local,
+    };
+    let plaintext = serde_json::to_vec(&snapshot)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    let sealed = cipher.seal(pairing.role, &plaintext)?;
+    if sealed.len() > sync_const::MAX_PAYLOAD_BYTES {
+        return Err("Settings exceed the maximum sync payload size".to_string());
+    }
+    put_mailbox(client, &base_url, &cipher.mailbox_id(pairing.role), sealed).await?;
+
+    db.execute_with_params(
+        "UPDATE type::thing('sync_pairing', $id) SET last_synced_at = <datetime>$synced_at",
+        vec![
+            ("id".to_string(), serde_json::json!(pairing.id)),
+            (
+                "synced_at".to_string(),
+                serde_json::json!(result.synced_at.to_rfc3339()),
+            ),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update sync pairing: {}", e))?;
+
+    Ok(result)
+}
+
+/// Creates a pairing and returns its code, to enter on the other machine.
+///
+/// # Arguments
+/// * `device_name` - Name of this machine, shown on the other one
+/// * `transport` - Relay server, or mailbox hosted by this machine on the local network
+/// * `relay_url` - Base URL of the relay (relay transport only)
+///
+/// # Returns
+/// The pairing with its code as text and SVG QR code
+#[tauri::command]
+#[instrument(name = "create_sync_pairing", skip(state, keystore, relay_url))]
+pub async fn create_sync_pairing(
+    device_name: String,
+    transport: SyncTransport,
+    relay_url: Option<String>,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<SyncPairingOffer, String> {
+    let device_name = validate_device_name(&device_name).map_err(|e| {
+        warn!(error = %e, "Invalid device name");
+        e
+    })?;
+
+    let mailbox_url = match transport {
+        SyncTransport::Relay => {
+            let url = relay_url.ok_or_else(|| "A relay URL is required".to_string())?;
+            validate_mailbox_url(&url)?
+        }
+        SyncTransport::Lan => {
+            let ip = local_network_ip()
+                .ok_or_else(|| "No local network address found: use a relay instead".to_string())?;
+            let port = ensure_lan_mailbox(0).await?;
+            format!("http://{}", std::net::SocketAddr::new(ip, port))
+        }
+    };
+
+    let pairing_id = uuid::Uuid::new_v4().to_string();
+    let key = generate_pairing_key();
+    let pairing = store_pairing(
+        &state.db,
+        &keystore,
+        &pairing_id,
+        &key,
+        SyncPairingCreate {
+            device_name: device_name.clone(),
+            peer_name: None,
+            role: SyncRole::Initiator,
+            transport,
+            mailbox_url: mailbox_url.clone(),
+        },
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to create sync pairing");
+        e
+    })?;
+
+    let code = PairingCode {
+        pairing_id,
+        key,
+        transport,
+        mailbox_url,
+        device_name,
+    }
+    .encode();
+    let qr_svg = render_qr_svg(&code)?;
+
+    info!(pairing_id = %pairing.id, transport = transport.as_str(), "Sync pairing created");
+    Ok(SyncPairingOffer {
+        pairing,
+        code,
+        qr_svg,
+    })
+}
+
+/// Accepts the pairing code shown by the other machine.
+///
+/// # Arguments
+/// * `code` - Pairing code (scanned from the QR code or pasted)
+/// * `device_name` - Name of this machine, shown on the other one
+///
+/// # Returns
+/// The stored pairing
+#[tauri::command]
+#[instrument(name = "accept_sync_pairing", skip(state, keystore, code))]
+pub async fn accept_sync_pairing(
+    code: String,
+    device_name: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<SyncPairing, String> {
+    let device_name = validate_device_name(&device_name)?;
+    let code = PairingCode::decode(&code).map_err(|e| {
+        warn!(error = %e, "Invalid pairing code");
+        e
+    })?;
+    let pairing_id = Validator::validate_uuid(&code.pairing_id)
+        .map_err(|e| format!("Invalid pairing code: {}", e))?;
+    let mailbox_url = validate_mailbox_url(&code.mailbox_url)?;
+    let peer_name = validate_device_name(&code.device_name)?;
+
+    if load_pairing(&state.db, &pairing_id).await?.is_some() {
+        return Err("This pairing code was already used on this machine".to_string());
+    }
+
+    let pairing = store_pairing(
+        &state.db,
+        &keystore,
+        &pairing_id,
+        &code.key,
+        SyncPairingCreate {
+            device_name,
+            peer_name: Some(peer_name),
+            role: SyncRole::Responder,
+            transport: code.transport,
+            mailbox_url,
+        },
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to accept sync pairing");
+        e
+    })?;
+
+    info!(pairing_id = %pairing.id, "Sync pairing accepted");
+    Ok(pairing)
+}
+
+/// Lists the pairings of this machine
+#[tauri::command]
+#[instrument(name = "list_sync_pairings", skip(state))]
+pub async fn list_sync_pairings(state: State<'_, AppState>) -> Result<Vec<SyncPairing>, String> {
+    load_pairings(&state.db).await
+}
+
+/// Syncs settings with the peer of a pairing.
+///
+/// # Arguments
+/// * `pairing_id` - The pairing ID
+///
+/// # Returns
+/// Counts of the entities sent and applied
+#[tauri::command]
+#[instrument(name = "sync_settings_now", skip(state, keystore), fields(pairing_id = %pairing_id))]
+pub async fn sync_settings_now(
+    pairing_id: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<SyncResult, String> {
+    let validated_id = Validator::validate_uuid(&pairing_id).map_err(|e| {
+        warn!(error = %e, "Invalid pairing_id");
+        format!("Invalid pairing_id: {}", e)
+    })?;
+    let pairing = load_pairing(&state.db, &validated_id)
+        .await?
+        .ok_or_else(|| format!("Sync pairing not found: {}", validated_id))?;
+    let key = keystore
+        .get_key(&pairing_key_name(&validated_id))
+        .and_then(|key| STANDARD.decode(key).ok())
+        .ok_or_else(|| {
+            error!("Pairing key not found in the keystore");
+            "The key of this pairing was not found: pair the machines again".to_string()
+        })?;
+
+    let result = run_sync(&state.db, state.llm_manager.http_client(), &pairing, &key)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Settings sync failed");
+            e
+        })?;
+
+    info!(
+        received = result.received,
+        imported = result.imported.agents + result.imported.mcp_servers + result.imported.prompts,
+        errors = result.errors.len(),
+        "Settings synced"
+    );
+    Ok(result)
+}
+
+/// Deletes a pairing (record and key).
+///
+/// The local network mailbox server stops when no pairing uses it anymore.
+///
+/// # Arguments
+/// * `pairing_id` - The pairing ID
+#[tauri::command]
+#[instrument(name = "delete_sync_pairing", skip(state, keystore), fields(pairing_id = %pairing_id))]
+pub async fn delete_sync_pairing(
+    pairing_id: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<(), String> {
+    let validated_id = Validator::validate_uuid(&pairing_id).map_err(|e| {
+        warn!(error = %e, "Invalid pairing_id");
+        format!("Invalid pairing_id: {}", e)
+    })?;
+
+    state
+        .db
+        .delete(&format!("sync_pairing:{}", validated_id))
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to delete sync pairing");
+            format!("Failed to delete sync pairing: {}", e)
+        })?;
+    if let Err(e) = keystore.delete_key(&pairing_key_name(&validated_id)) {
+        warn!(error = %e, "Failed to delete pairing key");
+    }
+
+    let serves_lan = load_pairings(&state.db).await?.iter().any(|pairing| {
+        pairing.transport == SyncTransport::Lan && pairing.role == SyncRole::Initiator
+    });
+    if !serves_lan {
+        stop_lan_mailbox().await;
+    }
+
+    info!("Sync pairing deleted");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings_sync::mailbox::MailboxServer;
+    use tempfile::tempdir;
+
+    async fn db(dir: &std::path::Path, name: &str) -> DBClient {
+        let db = DBClient::new(dir.join(name).to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        db
+    }
+
+    async fn create_prompt(db: &DBClient, name: &str, content: &str) {
+        db.execute_with_params(
+            "CREATE type::thing('prompt', $name) CONTENT { name: $name, description: '', \
+             category: 'custom', content: $content, variables: [], created_at: time::now(), \
+             updated_at: time::now() }",
+            vec![
+                ("name".to_string(), serde_json::json!(name)),
+                ("content".to_string(), serde_json::json!(content)),
+            ],
+        )
+        .await
+        .expect("Failed to create prompt");
+    }
+
+    async fn prompt_contents(db: &DBClient) -> Vec<(String, String)> {
+        db.query_json("SELECT name, content FROM prompt ORDER BY name")
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| {
+                (
+                    row["name"].as_str().unwrap().to_string(),
+                    row["content"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    async fn pair(db: &DBClient, id: &str, name: &str, role: SyncRole, url: &str) -> SyncPairing {
+        db.create(
+            "sync_pairing",
+            id,
+            SyncPairingCreate {
+                device_name: name.to_string(),
+                peer_name: None,
+                role,
+                transport: SyncTransport::Relay,
+                mailbox_url: url.to_string(),
+            },
+        )
+        .await
+        .expect("Failed to create pairing");
+        load_pairing(db, id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_run_sync_between_two_machines() {
+        let temp_dir = tempdir().unwrap();
+        let desktop = db(temp_dir.path(), "desktop").await;
+        let laptop = db(temp_dir.path(), "laptop").await;
+        let relay = MailboxServer::start(std::net::SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let url = format!("http://127.0.0.1:{}", relay.port());
+        let client = reqwest::Client::new();
+        let key = generate_pairing_key();
+        let pairing_id = uuid::Uuid::new_v4().to_string();
+
+        create_prompt(&desktop, "review", "Review this code").await;
+        create_prompt(&laptop, "translate", "Translate to French").await;
+
+        let on_desktop = pair(&desktop, &pairing_id, "Desktop", SyncRole::Initiator, &url).await;
+        let on_laptop = pair(&laptop, &pairing_id, "Laptop", SyncRole::Responder, &url).await;
+
+        // Desktop sends first: nothing to receive yet
+        let result = run_sync(&desktop, &client, &on_desktop, &key)
+            .await
+            .unwrap();
+        assert!(!result.received);
+        assert_eq!(result.pushed.prompts, 1);
+
+        // Laptop applies the desktop prompt and sends both
+        let result = run_sync(&laptop, &client, &on_laptop, &key).await.unwrap();
+        assert!(result.received);
+        assert_eq!(result.imported.prompts, 1);
+        assert_eq!(result.pushed.prompts, 2);
+        let on_laptop = load_pairing(&laptop, &pairing_id).await.unwrap().unwrap();
+        assert!(on_laptop.last_received_at.is_some());
+        assert_eq!(on_laptop.peer_name.as_deref(), Some("Desktop"));
+
+        // Desktop gets the laptop prompt; its own is identical and left alone
+        let result = run_sync(&desktop, &client, &on_desktop, &key)
+            .await
+            .unwrap();
+        assert_eq!(result.imported.prompts, 1);
+        assert_eq!(result.skipped.prompts, 1);
+        assert_eq!(
+            prompt_contents(&desktop).await,
+            prompt_contents(&laptop).await
+        );
+        let on_desktop = load_pairing(&desktop, &pairing_id).await.unwrap().unwrap();
+        assert_eq!(on_desktop.peer_name.as_deref(), Some("Laptop"));
+
+        // An already applied payload is not applied again
+        let result = run_sync(&desktop, &client, &on_desktop, &key)
+            .await
+            .unwrap();
+        assert!(!result.received);
+
+        // Mailbox IDs derive from the key: another key finds no payload
+        let result = run_sync(&laptop, &client, &on_laptop, &generate_pairing_key())
+            .await
+            .unwrap();
+        assert!(!result.received);
+    }
+
+    #[test]
+    fn test_validate_inputs() {
+        assert_eq!(validate_device_name("  Laptop ").unwrap(), "Laptop");
+        assert!(validate_device_name("").is_err());
+        assert!(validate_device_name(&"x".repeat(65)).is_err());
+        assert!(validate_device_name("bad\nname").is_err());
+
+        assert_eq!(
+            validate_mailbox_url("https://relay.example.com/").unwrap(),
+            "https://relay.example.com"
+        );
+        assert!(validate_mailbox_url("ftp://relay.example.com").is_err());
+        assert!(validate_mailbox_url("relay.example.com").is_err());
+    }
+}
@@ -0,0 +1,408 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Speech transcription commands.
+//!
+//! The frontend records a voice message, sends it to `transcribe_audio` and
+//! submits the returned text as a user message. The transcription server is
+//! configured in the `settings:speech` record (see [`crate::speech`]).
+//!
+//! `get_read_aloud` returns the text-to-speech version of a message, stored
+//! in its `read_aloud` field (prepared when the message is saved if enabled in
+//! the speech settings, otherwise on first request).
+
+use crate::commands::models::validate_model_id;
+use crate::db::{queries, DBClient};
+use crate::models::message::ReadAloud;
+use crate::security::workflow_vault::is_encrypted;
+use crate::security::{Validator, WORKFLOW_VAULT};
+use crate::speech::{self, detect_audio_mime, read_aloud, SpeechConfig, Transcription};
+use crate::state::AppState;
+use crate::tools::constants::speech as speech_const;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tauri::State;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Storage key of the transcription server settings
+const SPEECH_SETTINGS_KEY: &str = "settings:speech";
+
+/// Loads the transcription server settings, defaults when unset.
+pub async fn load_speech_settings(db: &DBClient) -> SpeechConfig {
+    match queries::settings::load_config(db, SPEECH_SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No speech settings found, using defaults");
+            SpeechConfig::default()
+        }
+    }
+}
+
+/// Decodes a recording (base64, optionally as a data URL) and detects its type.
+fn decode_audio(audio: &str) -> Result<(Vec<u8>, &'static str), String> {
+    let encoded = match audio.split_once(";base64,") {
+        Some((header, encoded)) if header.starts_with("data:") => encoded,
+        _ => audio,
+    };
+    // Base64 is 4/3 of the decoded size: reject oversized input before decoding
+    if encoded.len() / 4 * 3 > speech_const::MAX_AUDIO_SIZE_BYTES + 3 {
+        return Err(size_error());
+    }
+    let bytes = STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| format!("Invalid audio data: {}", e))?;
+
+    if bytes.is_empty() {
+        return Err("Recording is empty".to_string());
+    }
+    if bytes.len() > speech_const::MAX_AUDIO_SIZE_BYTES {
+        return Err(size_error());
+    }
+    let mime_type = detect_audio_mime(&bytes).ok_or_else(|| {
+        "Unsupported audio: only WAV, WebM, Ogg, MP3, M4A and FLAC recordings".to_string()
+    })?;
+    Ok((bytes, mime_type))
+}
+
+fn size_error() -> String {
+    format!(
+        "Recording exceeds maximum size of {} MB",
+        speech_const::MAX_AUDIO_SIZE_BYTES / (1024 * 1024)
+    )
+}
+
+/// Transcribes a voice message with the configured local Whisper server.
+///
+/// # Arguments
+/// * `audio` - Recording as base64 (or a `data:audio/...;base64,` URL)
+/// * `language` - Spoken language (ISO 639-1 code or "auto"), overrides the settings
+///
+/// # Returns
+/// The transcription, to be sent as a user message
+#[tauri::command]
+#[instrument(name = "transcribe_audio", skip(state, audio), fields(audio_len = audio.len()))]
+pub async fn transcribe_audio(
+    audio: String,
+    language: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Transcription, String> {
+    let language = language
+        .map(|language| language.trim().to_lowercase())
+        .filter(|language| !language.is_empty());
+    if let Some(language) = &language {
+        speech::validate_language(language).map_err(|e| {
+            warn!(error = %e, "Invalid language");
+            e
+        })?;
+    }
+
+    let (bytes, mime_type) = decode_audio(&audio).map_err(|e| {
+        warn!(error = %e, "Invalid recording");
+        e
+    })?;
+    let size_bytes = bytes.len();
+
+    let config = load_speech_settings(&state.db).await;
+    let transcription = speech::transcribe(
+        state.llm_manager.http_client(),
+        &config,
+        bytes,
+        mime_type,
+        language.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, endpoint = %config.endpoint, "Transcription failed");
+        e
+    })?;
+
+    info!(
+        size_bytes,
+        mime_type,
+        text_len = transcription.text.len(),
+        "Voice message transcribed"
+    );
+    Ok(transcription)
+}
+
+/// Gets the transcription server settings
+#[tauri::command]
+#[instrument(name = "get_speech_settings", skip(state))]
+pub async fn get_speech_settings(state: State<'_, AppState>) -> Result<SpeechConfig, String> {
+    Ok(load_speech_settings(&state.db).await)
+}
+
+/// Updates the transcription server settings.
+///
+/// # Arguments
+/// * `settings` - Server API, endpoint, model and default language
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_speech_settings", skip(state, settings))]
+pub async fn update_speech_settings(
+    settings: SpeechConfig,
+    state: State<'_, AppState>,
+) -> Result<SpeechConfig, String> {
+    info!(backend = ?settings.backend, "Updating speech settings");
+
+    let settings = normalize_speech_settings(settings).map_err(|e| {
+        warn!(error = %e, "Invalid speech settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize speech settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, SPEECH_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save speech settings");
+            format!("Failed to save speech settings: {}", e)
+        })?;
+
+    info!("Speech settings updated successfully");
+    Ok(settings)
+}
+
+/// Prepares a read-aloud version for storage (encrypted in sensitive workflows).
+pub(crate) async fn seal_read_aloud(
+    db: &DBClient,
+    workflow_id: &str,
+    read_aloud: ReadAloud,
+) -> Result<ReadAloud, String> {
+    Ok(ReadAloud {
+        text: WORKFLOW_VAULT
+            .seal(db, workflow_id, &read_aloud.text)
+            .await?,
+        ssml: WORKFLOW_VAULT
+            .seal(db, workflow_id, &read_aloud.ssml)
+            .await?,
+    })
+}
+
+/// Decrypts a stored value of a sensitive workflow (error when locked).
+fn open_stored(workflow_id: &str, value: &str) -> Result<String, String> {
+    if is_encrypted(value) {
+        WORKFLOW_VAULT.decrypt(workflow_id, value)
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// Gets the read-aloud version of a message (plain text and SSML).
+///
+/// Prepared from the Markdown content and stored on first request when it
+/// was not prepared at save time.
+///
+/// # Arguments
+/// * `message_id` - The message ID
+///
+/// # Returns
+/// The read-aloud text, the message content is left unchanged
+#[tauri::command]
+#[instrument(name = "get_read_aloud", skip(state), fields(message_id = %message_id))]
+pub async fn get_read_aloud(
+    message_id: String,
+    state: State<'_, AppState>,
+) -> Result<ReadAloud, String> {
+    let validated_id = Validator::validate_uuid(&message_id).map_err(|e| {
+        warn!(error = %e, "Invalid message_id");
+        format!("Invalid message_id: {}", e)
+    })?;
+
+    let rows = state
+        .db
+        .query_json_with_params(
+            "SELECT workflow_id, content, read_aloud FROM message WHERE meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(validated_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load message");
+            format!("Failed to load message: {}", e)
+        })?;
+    let row = rows
+        .first()
+        .ok_or_else(|| format!("Message {} not found", validated_id))?;
+    let field = |name: &str| row.get(name).and_then(|v| v.as_str()).unwrap_or_default();
+    let workflow_id = field("workflow_id");
+
+    let stored = row
+        .get("read_aloud")
+        .and_then(|v| serde_json::from_value::<ReadAloud>(v.clone()).ok());
+    if let Some(stored) = stored {
+        return Ok(ReadAloud {
+            text: open_stored(workflow_id, &stored.text)?,
+            ssml: open_stored(workflow_id, &stored.ssml)?,
+        });
+    }
+
+    let content = open_stored(workflow_id, field("content"))?;
+    let config = load_speech_settings(&state.db).await;
+    let prepared = read_aloud::prepare(&content, config.language.as_deref());
+
+    let sealed = seal_read_aloud(&state.db, workflow_id, prepared.clone()).await?;
+    if let Err(e) = state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('message', $id) SET read_aloud = $read_aloud",
+            vec![
+                ("id".to_string(), serde_json::json!(validated_id)),
+                ("read_aloud".to_string(), serde_json::json!(sealed)),
+            ],
+        )
+        .await
+    {
+        // The text is returned anyway, it is prepared again next time
+        warn!(error = %e, "Failed to store read-aloud text");
+    }
+
+    info!(text_len = prepared.text.len(), "Read-aloud text prepared");
+    Ok(prepared)
+}
+
+/// Validates and normalizes speech settings (http(s) endpoint, model, language)
+fn normalize_speech_settings(settings: SpeechConfig) -> Result<SpeechConfig, String> {
+    let endpoint = settings.endpoint.trim().trim_end_matches('/').to_string();
+    let url =
+        reqwest::Url::parse(&endpoint).map_err(|e| format!("Invalid speech endpoint: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Speech endpoint must be an http(s) URL".to_string());
+    }
+
+    let model = settings
+        .model
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty());
+    if let Some(model) = &model {
+        validate_model_id(model)?;
+    }
+
+    let language = settings
+        .language
+        .map(|language| language.trim().to_lowercase())
+        .filter(|language| !language.is_empty() && language != "auto");
+    if let Some(language) = &language {
+        speech::validate_language(language)?;
+    }
+
+    Ok(SpeechConfig {
+        backend: settings.backend,
+        endpoint,
+        model,
+        language,
+        read_aloud: settings.read_aloud,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::MessageCreate;
+    use crate::speech::SpeechBackend;
+
+    const WAV: &[u8] = b"RIFF\x24\0\0\0WAVEfmt ";
+
+    #[test]
+    fn test_decode_audio() {
+        let encoded = STANDARD.encode(WAV);
+
+        let (bytes, mime_type) = decode_audio(&encoded).unwrap();
+        assert_eq!(bytes, WAV);
+        assert_eq!(mime_type, "audio/wav");
+
+        // Data URL prefix is accepted
+        let url = format!("data:audio/wav;base64,{}", encoded);
+        assert!(decode_audio(&url).is_ok());
+
+        // Not audio, empty, not base64
+        assert!(decode_audio(&STANDARD.encode(b"\x89PNG\r\n\x1a\n")).is_err());
+        assert!(decode_audio("").is_err());
+        assert!(decode_audio("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_normalize_speech_settings() {
+        let settings = normalize_speech_settings(SpeechConfig {
+            backend: SpeechBackend::OpenAiCompatible,
+            endpoint: " http://localhost:8000/ ".to_string(),
+            model: Some("  ".to_string()),
+            language: Some("FR".to_string()),
+            read_aloud: true,
+        })
+        .unwrap();
+        assert_eq!(settings.endpoint, "http://localhost:8000");
+        assert_eq!(settings.model, None);
+        assert_eq!(settings.language.as_deref(), Some("fr"));
+        assert!(settings.read_aloud);
+
+        // "auto" is stored as no default language
+        let settings = normalize_speech_settings(SpeechConfig {
+            language: Some("auto".to_string()),
+            ..SpeechConfig::default()
+        })
+        .unwrap();
+        assert_eq!(settings.language, None);
+
+        for endpoint in ["localhost:8080", "file:///tmp/whisper", ""] {
+            let settings = SpeechConfig {
+                endpoint: endpoint.to_string(),
+                ..SpeechConfig::default()
+            };
+            assert!(normalize_speech_settings(settings).is_err(), "{}", endpoint);
+        }
+
+        let settings = SpeechConfig {
+            language: Some("english".to_string()),
+            ..SpeechConfig::default()
+        };
+        assert!(normalize_speech_settings(settings).is_err());
+    }
+
+    #[test]
+    fn test_speech_config_defaults() {
+        let config: SpeechConfig =
+            serde_json::from_str(r#"{"endpoint": "http://127.0.0.1:8080"}"#).unwrap();
+        assert_eq!(config.backend, SpeechBackend::WhisperCpp);
+        assert_eq!(config.model, None);
+    }
+
+    #[tokio::test]
+    async fn test_read_aloud_stored_next_to_content() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("read_aloud_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let content = "**Done**, see `main.rs`";
+        let prepared = read_aloud::prepare(content, None);
+        let mut message = MessageCreate::system("wf1".to_string(), content.to_string());
+        message.read_aloud = Some(seal_read_aloud(&db, "wf1", prepared.clone()).await.unwrap());
+        db.create("message", "m1", message).await.unwrap();
+
+        let rows = db
+            .query_json("SELECT content, read_aloud FROM message")
+            .await
+            .unwrap();
+        assert_eq!(rows[0]["content"], content);
+        let stored: ReadAloud = serde_json::from_value(rows[0]["read_aloud"].clone()).unwrap();
+        assert_eq!(stored, prepared);
+        assert_eq!(stored.text, "Done, see main.rs.");
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Startup progress commands.
+//!
+//! The frontend may subscribe to the `startup_progress` event after some
+//! phases finished; [`get_startup_status`] returns the current status so the
+//! splash screen starts from the right state.
+
+use crate::models::startup::StartupStatus;
+use crate::AppState;
+use tauri::State;
+use tracing::instrument;
+
+/// Gets the progress of the deferred startup phases.
+#[tauri::command]
+#[instrument(name = "get_startup_status", skip(state))]
+pub async fn get_startup_status(state: State<'_, AppState>) -> Result<StartupStatus, String> {
+    state
+        .startup_status
+        .read()
+        .map(|guard| guard.clone())
+        .map_err(|_| "Startup status lock poisoned".to_string())
+}
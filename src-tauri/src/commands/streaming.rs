@@ -18,18 +18,37 @@
 //! responses via Tauri events.
 
 use crate::{
-    agents::core::agent::Task,
-    db::queries::workflow as wf_queries,
-    llm::pricing::calculate_cost,
+    agents::{
+        behavior_changelog::run_behavior_position,
+        core::agent::{ReasoningStepData, Task},
+        language,
+        moderation::{moderate_report, ModerationTarget},
+        tool_result_diff::ResultDiffs,
+        tool_usage::record_agent_tool_usage,
+    },
+    commands::attachment::{load_message_attachments, read_image_urls},
+    commands::checkpoint::discard_checkpoint,
+    commands::message::pinned_context,
+    commands::models::MODEL_SELECT_FIELDS,
+    commands::workflow_title::spawn_title_generation,
+    db::{outbox::PersistenceOutbox, queries::workflow as wf_queries, DBClient},
+    llm::{pricing::calculate_cost, utils::multimodal_content},
     models::{
-        llm_models::LLMModel, streaming::events, Message, StreamChunk, ThinkingStepCreate,
-        ToolExecutionCreate, Workflow, WorkflowComplete, WorkflowMetrics, WorkflowResult,
-        WorkflowToolExecution,
+        audit::{AuditAction, AuditLogCreate},
+        execution_trace::LlmTurnCreate,
+        llm_models::LLMModel,
+        message::ContextUsage,
+        streaming::events,
+        GenerationParams, Message, StreamChunk, ThinkingStepCreate, ToolExecutionCreate, Workflow,
+        WorkflowComplete, WorkflowMetrics, WorkflowResult, WorkflowToolExecution,
     },
-    security::Validator,
-    tools::constants::workflow as wf_const,
+    security::{audit, Validator, WORKFLOW_VAULT},
+    tools::{constants::workflow as wf_const, utils::tool_image_paths},
     AppState,
 };
+use serde_json::json;
+use std::future::Future;
+use std::time::Duration;
 use tauri::{Emitter, State, Window};
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
@@ -46,6 +65,7 @@ use uuid::Uuid;
 /// * `message` - User message to process
 /// * `agent_id` - Agent to execute with
 /// * `locale` - User's selected language (e.g., "en", "fr")
+/// * `generation` - Generation parameters overriding the agent's for this message (optional)
 ///
 /// # Returns
 /// Final workflow result after streaming completes
@@ -66,6 +86,7 @@ pub async fn execute_workflow_streaming(
     message: String,
     agent_id: String,
     locale: String,
+    generation: Option<GenerationParams>,
     state: State<'_, AppState>,
 ) -> Result<WorkflowResult, String> {
     info!("Starting streaming workflow execution");
@@ -86,6 +107,21 @@ pub async fn execute_workflow_streaming(
         format!("Invalid agent_id: {}", e)
     })?;
 
+    let generation = generation.unwrap_or_default();
+    generation.validate().map_err(|e| {
+        warn!(error = %e, "Invalid generation parameters");
+        format!("Invalid generation parameters: {}", e)
+    })?;
+
+    // A sensitive workflow must be open: its contents are written encrypted
+    if WORKFLOW_VAULT
+        .is_sensitive(&state.db, &validated_workflow_id)
+        .await?
+        && !WORKFLOW_VAULT.is_unlocked(&validated_workflow_id)
+    {
+        return Err("This workflow is sensitive and locked, open it first".to_string());
+    }
+
     // Safety net: enforce concurrent workflow limit
     // Frontend enforces this too, but backend provides race condition protection
     let running_count = state.streaming_cancellations.lock().await.len();
@@ -104,20 +140,24 @@ pub async fn execute_workflow_streaming(
 
     // OPT-WF-1: Use centralized query constant
     let query = format!(
-        "{} WHERE meta::id(id) = '{}'",
-        wf_queries::SELECT_BASIC,
-        validated_workflow_id
+        "{} WHERE id = type::thing('workflow', $id)",
+        wf_queries::SELECT_BASIC
     );
+    let params = vec![("id".to_string(), json!(validated_workflow_id))];
 
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load workflow");
-        emit_error(
-            &window,
-            &validated_workflow_id,
-            &format!("Failed to load workflow: {}", e),
-        );
-        format!("Failed to load workflow: {}", e)
-    })?;
+    let json_results = state
+        .db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow");
+            emit_error(
+                &window,
+                &validated_workflow_id,
+                &format!("Failed to load workflow: {}", e),
+            );
+            format!("Failed to load workflow: {}", e)
+        })?;
 
     let workflows: Vec<Workflow> = json_results
         .into_iter()
@@ -133,12 +173,26 @@ pub async fn execute_workflow_streaming(
             format!("Failed to deserialize workflow: {}", e)
         })?;
 
-    let _workflow = workflows.first().ok_or_else(|| {
+    let workflow = workflows.first().ok_or_else(|| {
         warn!(workflow_id = %validated_workflow_id, "Workflow not found");
         emit_error(&window, &validated_workflow_id, "Workflow not found");
         "Workflow not found".to_string()
     })?;
 
+    // Response language: pinned from the first message written in a detectable
+    // language, so later messages don't switch it
+    let response_language = language::pin_workflow_language(
+        &state.db,
+        &validated_workflow_id,
+        workflow.response_language.as_deref(),
+        &validated_message,
+    )
+    .await
+    .unwrap_or_else(|e| {
+        warn!(error = %e, "Failed to pin workflow response language");
+        None
+    });
+
     // Generate a message ID for this execution (the assistant response)
     // This is generated early so thinking steps can reference it
     let message_id = Uuid::new_v4().to_string();
@@ -174,8 +228,8 @@ pub async fn execute_workflow_streaming(
     };
     let step_id = Uuid::new_v4().to_string();
     if let Err(e) = state
-        .db
-        .create("thinking_step", &step_id, initial_step)
+        .outbox
+        .create(&state.db, "thinking_step", &step_id, initial_step)
         .await
     {
         warn!(error = %e, "Failed to persist initial thinking step");
@@ -184,8 +238,8 @@ pub async fn execute_workflow_streaming(
 
     // Load conversation history for context (API-native format for continuation)
     // Messages are stored with role: system|user|assistant
-    let history_query = format!(
-        r#"SELECT
+    // The most recent messages are kept; the system prompt is always included
+    let history_select = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             role,
@@ -198,23 +252,89 @@ pub async fn execute_workflow_streaming(
             cost_usd,
             duration_ms,
             timestamp
-        FROM message
-        WHERE workflow_id = '{}'
-        ORDER BY timestamp ASC
-        LIMIT {}"#, // OPT-WF-3: Use centralized constant
-        validated_workflow_id,
-        wf_const::MESSAGE_HISTORY_LIMIT
+        FROM message"#;
+    let history_query = format!(
+        "{} WHERE workflow_id = $workflow_id ORDER BY timestamp DESC LIMIT $limit",
+        history_select
     );
+    let history_params = vec![
+        ("workflow_id".to_string(), json!(validated_workflow_id)),
+        // OPT-WF-3: Use centralized constant
+        ("limit".to_string(), json!(wf_const::MESSAGE_HISTORY_LIMIT)),
+    ];
 
     let history_json = state
         .db
-        .query_json(&history_query)
+        .query_json_with_params(&history_query, history_params)
         .await
         .unwrap_or_default();
-    let conversation_history: Vec<Message> = history_json
+    let mut conversation_history: Vec<Message> = history_json
         .into_iter()
         .filter_map(|v| serde_json::from_value(v).ok())
         .collect();
+    conversation_history.reverse();
+
+    // Total history size, to report the messages left out of the prompt
+    let history_total = state
+        .db
+        .query_json_with_params(
+            "SELECT count() FROM message WHERE workflow_id = $workflow_id GROUP ALL",
+            vec![("workflow_id".to_string(), json!(validated_workflow_id))],
+        )
+        .await
+        .unwrap_or_default()
+        .first()
+        .and_then(|v| v.get("count"))
+        .and_then(|c| c.as_u64())
+        .unwrap_or(conversation_history.len() as u64) as u32;
+
+    if history_total as usize > conversation_history.len()
+        && !conversation_history
+            .iter()
+            .any(|msg| matches!(msg.role, crate::models::MessageRole::System))
+    {
+        let system_query = format!(
+            "{} WHERE workflow_id = $workflow_id AND role = 'system' ORDER BY timestamp ASC LIMIT 1",
+            history_select
+        );
+        if let Some(system_message) = state
+            .db
+            .query_json_with_params(
+                &system_query,
+                vec![("workflow_id".to_string(), json!(validated_workflow_id))],
+            )
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|v| serde_json::from_value::<Message>(v).ok())
+        {
+            conversation_history.insert(0, system_message);
+        }
+    }
+    conversation_history
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    load_message_attachments(&state.db, &validated_workflow_id, &mut conversation_history).await;
+
+    // Pinned messages older than the history are sent in the system prompt
+    let pinned_context =
+        pinned_context(&state.db, &validated_workflow_id, &conversation_history).await;
+
+    // Images of the message being processed (saved just before execution),
+    // sent with the new user turn
+    let current_message_id = conversation_history
+        .iter()
+        .rev()
+        .find(|msg| {
+            matches!(msg.role, crate::models::MessageRole::User) && msg.content == validated_message
+        })
+        .map(|msg| msg.id.clone());
+    let current_attachments = conversation_history
+        .iter()
+        .find(|msg| Some(&msg.id) == current_message_id.as_ref())
+        .map(|msg| msg.attachments.clone())
+        .unwrap_or_default();
+    let image_urls = read_image_urls(&state, &current_attachments).await;
 
     // Check if we have a system message (indicates existing context)
     let has_system_message = conversation_history
@@ -223,45 +343,79 @@ pub async fn execute_workflow_streaming(
 
     // Build conversation context for the LLM
     // If we have existing messages with system prompt, pass them as conversation_messages
-    // for direct reuse (no reconstruction needed)
+    // for direct reuse (the agent refreshes the system prompt from its current config)
     // Note: locale is always passed for system prompt injection (first message only uses it)
     let history_context = if has_system_message && !conversation_history.is_empty() {
         // Continuation: format messages for API-native reuse
-        let api_messages: Vec<serde_json::Value> = conversation_history
-            .iter()
-            .map(|msg| {
-                serde_json::json!({
-                    "role": msg.role,
-                    "content": msg.content
-                })
-            })
-            .collect();
+        // Earlier user messages keep their images (charts of responses are not resent)
+        let mut api_messages: Vec<serde_json::Value> = Vec::new();
+        for msg in &conversation_history {
+            let content = if msg.attachments.is_empty()
+                || !matches!(msg.role, crate::models::MessageRole::User)
+                || Some(&msg.id) == current_message_id.as_ref()
+            {
+                serde_json::Value::String(msg.content.clone())
+            } else {
+                multimodal_content(
+                    &msg.content,
+                    &read_image_urls(&state, &msg.attachments).await,
+                )
+            };
+            api_messages.push(serde_json::json!({
+                "role": msg.role,
+                "content": content
+            }));
+        }
         serde_json::json!({
             "conversation_messages": api_messages,
             "is_primary_agent": true,
             "workflow_id": validated_workflow_id.clone(),
-            "locale": locale.clone()
+            "locale": locale.clone(),
+            "response_language": response_language,
+            "pinned_context": pinned_context,
+            "image_urls": image_urls,
+            "generation": generation
         })
     } else {
         // First message or no system prompt: let agent build the context
         serde_json::json!({
             "is_primary_agent": true,
             "workflow_id": validated_workflow_id.clone(),
-            "locale": locale.clone()
+            "locale": locale.clone(),
+            "response_language": response_language,
+            "pinned_context": pinned_context,
+            "image_urls": image_urls,
+            "generation": generation
         })
     };
 
     info!(
         history_count = conversation_history.len(),
         has_system_message = has_system_message,
+        history_total = history_total,
         is_continuation = has_system_message && !conversation_history.is_empty(),
         "Loaded conversation history for context"
     );
 
+    // Messages the model sees as history (the first message only sends the task)
+    let is_continuation = has_system_message && !conversation_history.is_empty();
+    let history_sent = if is_continuation {
+        conversation_history.len() as u32
+    } else {
+        history_total.min(1)
+    };
+    let oldest_sent = conversation_history
+        .iter()
+        .find(|msg| !matches!(msg.role, crate::models::MessageRole::System))
+        .filter(|_| is_continuation)
+        .map(|msg| msg.timestamp);
+
     // Create task with conversation history
     let task_id = Uuid::new_v4().to_string();
     info!(task_id = %task_id, "Creating task for streaming workflow");
 
+    // Kept to update the agent's tool usage statistics after the run
+    let task_description = validated_message.clone();
     let task = Task {
         id: task_id.clone(),
         description: validated_message,
@@ -274,6 +428,7 @@ pub async fn execute_workflow_streaming(
         StreamChunk::tool_start(validated_workflow_id.clone(), validated_agent_id.clone()),
     );
 
+    let behavior_position = run_behavior_position(&state.db).await;
     let start_time = std::time::Instant::now();
 
     // Execute via orchestrator with MCP support, racing against cancellation token
@@ -283,10 +438,11 @@ pub async fn execute_workflow_streaming(
         task,
         Some(state.mcp_manager.clone()),
     );
+    tokio::pin!(execution_future);
 
-    let report = tokio::select! {
+    let mut report = tokio::select! {
         // Execution branch - runs the actual LLM call
-        result = execution_future => {
+        result = &mut execution_future => {
             match result {
                 Ok(report) => report,
                 Err(e) => {
@@ -300,6 +456,7 @@ pub async fn execute_workflow_streaming(
                         WorkflowComplete::failed(validated_workflow_id.clone(), e.to_string()),
                     );
                     state.clear_cancellation(&validated_workflow_id).await;
+                    discard_checkpoint(&state.db, &validated_workflow_id).await;
                     return Err(format!("Execution failed: {}", e));
                 }
             }
@@ -307,6 +464,7 @@ pub async fn execute_workflow_streaming(
         // Cancellation branch - triggers when user clicks Cancel button
         _ = cancellation_token.cancelled() => {
             warn!(workflow_id = %validated_workflow_id, "Workflow cancelled by user during execution");
+            wind_down(&mut execution_future).await;
             emit_chunk(
                 &window,
                 StreamChunk::error(validated_workflow_id.clone(), "Cancelled by user".to_string()),
@@ -316,10 +474,14 @@ pub async fn execute_workflow_streaming(
                 WorkflowComplete::cancelled(validated_workflow_id.clone()),
             );
             state.clear_cancellation(&validated_workflow_id).await;
+            discard_checkpoint(&state.db, &validated_workflow_id).await;
             return Err("Workflow cancelled by user".to_string());
         }
     };
 
+    // The execution ended: its loop checkpoint is only needed after a crash
+    discard_checkpoint(&state.db, &validated_workflow_id).await;
+
     // Execution completed successfully - process the report
     let duration = start_time.elapsed().as_millis() as u64;
 
@@ -335,27 +497,35 @@ pub async fn execute_workflow_streaming(
 
     // If this is the first message, save the system prompt for future conversations
     // This enables context reuse without reconstruction
-    if let Some(ref system_prompt) = report.system_prompt {
+    let first_exchange = report.system_prompt.is_some();
+    let system_prompt = match &report.system_prompt {
+        Some(prompt) => WORKFLOW_VAULT
+            .seal(&state.db, &validated_workflow_id, prompt)
+            .await
+            .map_err(|e| warn!(error = %e, "Failed to encrypt system prompt"))
+            .ok(),
+        None => None,
+    };
+    if let Some(system_content) = system_prompt {
         let system_message_id = Uuid::new_v4().to_string();
-        let system_content = system_prompt.clone();
 
         // Save system prompt as a system message (will be loaded in future conversations)
-        let insert_query = format!(
-            "CREATE message:`{}` CONTENT {{ \
-                workflow_id: '{}', \
+        let insert_query = "CREATE type::thing('message', $id) CONTENT { \
+                workflow_id: $workflow_id, \
                 role: 'system', \
-                content: {}, \
+                content: $content, \
                 tokens: 0, \
                 tokens_input: 0, \
                 tokens_output: 0, \
                 timestamp: time::now() \
-            }}",
-            system_message_id,
-            validated_workflow_id,
-            serde_json::to_string(&system_content).unwrap_or_else(|_| "\"\"".to_string())
-        );
-
-        if let Err(e) = state.db.execute(&insert_query).await {
+            }";
+        let params = vec![
+            ("id".to_string(), json!(system_message_id)),
+            ("workflow_id".to_string(), json!(validated_workflow_id)),
+            ("content".to_string(), json!(system_content)),
+        ];
+
+        if let Err(e) = state.db.execute_with_params(insert_query, params).await {
             warn!(error = %e, "Failed to persist system prompt as message");
         } else {
             info!(
@@ -389,14 +559,37 @@ pub async fn execute_workflow_streaming(
     };
     let completion_step_id = Uuid::new_v4().to_string();
     if let Err(e) = state
-        .db
-        .create("thinking_step", &completion_step_id, completion_step)
+        .outbox
+        .create(
+            &state.db,
+            "thinking_step",
+            &completion_step_id,
+            completion_step,
+        )
         .await
     {
         warn!(error = %e, "Failed to persist completion thinking step");
     }
     thinking_step_number += 1;
 
+    // Check the response against the agent's moderation policy before it is
+    // displayed and returned for persistence
+    let agent_config = state
+        .registry
+        .get(&validated_agent_id)
+        .await
+        .map(|agent| agent.config().clone());
+    let moderation = match &agent_config {
+        Some(config) => {
+            let target = ModerationTarget {
+                workflow_id: &validated_workflow_id,
+                message_id: &message_id,
+            };
+            moderate_report(&state.db, &state.llm_manager, config, target, &mut report).await
+        }
+        None => None,
+    };
+
     // Clear the placeholder and stream the actual response content
     // First, emit a newline to visually separate from placeholder
     emit_chunk(
@@ -449,12 +642,14 @@ pub async fn execute_workflow_streaming(
         return Err("Workflow cancelled by user".to_string());
     }
 
-    // Get agent config for accurate provider/model info (OPT-7: avoid cloning by using &str temporarily)
-    let (provider, model) = match state.registry.get(&validated_agent_id).await {
-        Some(agent) => {
-            let config = agent.config();
-            (config.llm.provider.clone(), config.llm.model.clone())
-        }
+    // Generation parameters actually used (agent values where not overridden)
+    let generation = agent_config
+        .as_ref()
+        .map(|config| generation.effective(&config.llm));
+
+    // Get agent config for accurate provider/model info
+    let (provider, model) = match agent_config {
+        Some(config) => (config.llm.provider, config.llm.model),
         None => {
             // Fallback if agent not found (shouldn't happen after successful execution)
             ("Unknown".to_string(), validated_agent_id.clone())
@@ -466,51 +661,73 @@ pub async fn execute_workflow_streaming(
     // Load model to get pricing info for cost calculation
     // Note: model is the api_name (e.g. "mistral-large-latest"), not the UUID
     // We need to search by api_name + provider to find the correct model
-    let (input_price, output_price, model_id) = {
+    let (input_price, output_price, model_id, context_window) = {
         // Convert provider string to lowercase for matching (DB stores lowercase)
         let provider_lower = provider.to_lowercase();
         let model_query = format!(
-            "SELECT meta::id(id) AS id, provider, name, api_name, context_window, \
-             max_output_tokens, temperature_default, is_builtin, is_reasoning, \
-             (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
-             (output_price_per_mtok ?? 0.0) AS output_price_per_mtok, \
-             created_at, updated_at \
-             FROM llm_model WHERE api_name = '{}' AND provider = '{}'",
-            model, provider_lower
+            "SELECT {} FROM llm_model WHERE api_name = $api_name AND provider = $provider",
+            MODEL_SELECT_FIELDS
         );
+        let params = vec![
+            ("api_name".to_string(), json!(model)),
+            ("provider".to_string(), json!(provider_lower)),
+        ];
 
-        match state.db.db.query(&model_query).await {
-            Ok(mut response) => {
-                let models: Result<Vec<LLMModel>, _> = response.take(0);
-                match models {
-                    Ok(mut m) if !m.is_empty() => {
-                        let loaded_model = m.remove(0);
-                        info!(
-                            model_api_name = %model,
-                            model_id = %loaded_model.id,
-                            input_price = loaded_model.input_price_per_mtok,
-                            output_price = loaded_model.output_price_per_mtok,
-                            "Loaded model for pricing"
-                        );
-                        (
-                            loaded_model.input_price_per_mtok,
-                            loaded_model.output_price_per_mtok,
-                            loaded_model.id,
-                        )
-                    }
-                    _ => {
-                        warn!(model_api_name = %model, provider = %provider, "Model not found for pricing, using defaults");
-                        (0.0, 0.0, model.clone())
-                    }
-                }
+        match state
+            .db
+            .query_with_params::<LLMModel>(&model_query, params)
+            .await
+        {
+            Ok(mut m) if !m.is_empty() => {
+                let loaded_model = m.remove(0);
+                info!(
+                    model_api_name = %model,
+                    model_id = %loaded_model.id,
+                    input_price = loaded_model.input_price_per_mtok,
+                    output_price = loaded_model.output_price_per_mtok,
+                    "Loaded model for pricing"
+                );
+                (
+                    loaded_model.input_price_per_mtok,
+                    loaded_model.output_price_per_mtok,
+                    loaded_model.id,
+                    Some(loaded_model.context_window as u64),
+                )
+            }
+            Ok(_) => {
+                warn!(model_api_name = %model, provider = %provider, "Model not found for pricing, using defaults");
+                (0.0, 0.0, model.clone(), None)
             }
             Err(e) => {
                 warn!(error = %e, "Failed to load model for pricing, using defaults");
-                (0.0, 0.0, model.clone())
+                (0.0, 0.0, model.clone(), None)
             }
         }
     };
 
+    // Context window utilization: the largest prompt of the tool loop
+    let peak_context_tokens = report
+        .metrics
+        .llm_turns
+        .iter()
+        .map(|turn| turn.tokens_input)
+        .max()
+        .unwrap_or(report.metrics.tokens_input) as u64;
+    let context_usage = ContextUsage::new(
+        context_window,
+        peak_context_tokens,
+        history_total,
+        history_sent,
+        oldest_sent,
+    );
+    if context_usage.history_truncated > 0 {
+        info!(
+            history_truncated = context_usage.history_truncated,
+            peak_context_tokens = peak_context_tokens,
+            "Earlier messages were left out of the prompt"
+        );
+    }
+
     // Calculate cost using model pricing
     let cost_usd = calculate_cost(
         report.metrics.tokens_input,
@@ -530,28 +747,33 @@ pub async fn execute_workflow_streaming(
 
     // Update workflow with cumulative tokens, cost, model_id, and current context size
     // OPT-WF-2: Use ?? (null coalescing) instead of IF/THEN/ELSE to eliminate param duplication
-    // Use explicit float formatting to avoid scientific notation (e.g., 1.6e-5)
     // current_context_tokens = tokens_input (actual context size at last API call)
-    let update_query = format!(
-        "UPDATE workflow:`{}` SET \
-            total_tokens_input = (total_tokens_input ?? 0) + {}, \
-            total_tokens_output = (total_tokens_output ?? 0) + {}, \
-            total_cost_usd = (total_cost_usd ?? 0.0) + {:.10}, \
-            model_id = '{}', \
-            current_context_tokens = {}, \
-            updated_at = time::now()",
-        validated_workflow_id,
-        report.metrics.tokens_input,
-        report.metrics.tokens_output,
-        cost_usd,
-        model_id,                    // Use real model UUID, not api_name
-        report.metrics.tokens_input  // Current context size (last API call)
-    );
+    let update_query = "UPDATE type::thing('workflow', $id) SET \
+            total_tokens_input = (total_tokens_input ?? 0) + $tokens_input, \
+            total_tokens_output = (total_tokens_output ?? 0) + $tokens_output, \
+            total_cost_usd = (total_cost_usd ?? 0.0) + $cost_usd, \
+            model_id = $model_id, \
+            current_context_tokens = $tokens_input, \
+            updated_at = time::now()";
+    let params = vec![
+        ("id".to_string(), json!(validated_workflow_id)),
+        (
+            "tokens_input".to_string(),
+            json!(report.metrics.tokens_input),
+        ),
+        (
+            "tokens_output".to_string(),
+            json!(report.metrics.tokens_output),
+        ),
+        ("cost_usd".to_string(), json!(cost_usd)),
+        // Use real model UUID, not api_name
+        ("model_id".to_string(), json!(model_id)),
+    ];
 
     // Log the query for debugging
     info!(query = %update_query, "Executing workflow token update");
 
-    if let Err(e) = state.db.db.query(&update_query).await {
+    if let Err(e) = state.db.execute_with_params(update_query, params).await {
         error!(error = %e, query = %update_query, "Failed to update workflow cumulative tokens");
     } else {
         info!(
@@ -584,9 +806,70 @@ pub async fn execute_workflow_streaming(
         .collect();
     // Note: Clones here are necessary as WorkflowToolExecution needs owned data for Tauri IPC
 
-    // Persist tool executions to database (message_id was generated earlier)
+    // Persist tool executions to database (message_id was generated earlier),
+    // with the output changes of calls repeating an earlier call
+    let mut result_diffs = ResultDiffs::new(&state.db, &validated_workflow_id);
     for (idx, te) in tool_executions.iter().enumerate() {
         let execution_id = Uuid::new_v4().to_string();
+        audit::record(
+            &state.db,
+            AuditLogCreate {
+                workflow_id: Some(validated_workflow_id.clone()),
+                success: te.success,
+                details: json!({
+                    "execution_id": execution_id,
+                    "server_name": te.server_name,
+                    "duration_ms": te.duration_ms
+                }),
+                ..AuditLogCreate::new(
+                    AuditAction::ToolExecution,
+                    &validated_agent_id,
+                    &te.tool_name,
+                )
+            },
+        )
+        .await;
+        let (previous_execution_id, result_diff) = match result_diffs
+            .diff(
+                &execution_id,
+                &te.tool_name,
+                te.server_name.as_deref(),
+                &te.input_params,
+                &te.output_result,
+                te.success,
+            )
+            .await
+        {
+            Some((previous_id, diff)) => (Some(previous_id), Some(diff)),
+            None => (None, None),
+        };
+        let (input_params, output_result) = match (
+            WORKFLOW_VAULT
+                .seal_json(&state.db, &validated_workflow_id, te.input_params.clone())
+                .await,
+            WORKFLOW_VAULT
+                .seal_json(&state.db, &validated_workflow_id, te.output_result.clone())
+                .await,
+        ) {
+            (Ok(input), Ok(output)) => (input, output),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!(error = %e, tool_name = %te.tool_name, "Failed to encrypt tool execution");
+                continue;
+            }
+        };
+        let result_diff = match result_diff {
+            Some(diff) => match WORKFLOW_VAULT
+                .seal_json(&state.db, &validated_workflow_id, diff)
+                .await
+            {
+                Ok(diff) => Some(diff),
+                Err(e) => {
+                    warn!(error = %e, tool_name = %te.tool_name, "Failed to encrypt result diff");
+                    continue;
+                }
+            },
+            None => None,
+        };
         let execution = ToolExecutionCreate {
             workflow_id: validated_workflow_id.clone(),
             message_id: message_id.clone(),
@@ -594,17 +877,25 @@ pub async fn execute_workflow_streaming(
             tool_type: te.tool_type.clone(),
             tool_name: te.tool_name.clone(),
             server_name: te.server_name.clone(),
-            input_params: te.input_params.clone(),
-            output_result: te.output_result.clone(),
+            input_params,
+            output_result,
             success: te.success,
             error_message: te.error_message.clone(),
             duration_ms: te.duration_ms,
             iteration: te.iteration,
+            previous_execution_id,
+            result_diff,
+            // Start time is not part of the IPC format (same order as the report)
+            started_at_ms: report
+                .metrics
+                .tool_executions
+                .get(idx)
+                .map(|data| data.started_at_ms),
         };
 
         if let Err(e) = state
-            .db
-            .create("tool_execution", &execution_id, execution)
+            .outbox
+            .create(&state.db, "tool_execution", &execution_id, execution)
             .await
         {
             warn!(
@@ -616,30 +907,74 @@ pub async fn execute_workflow_streaming(
         }
     }
 
-    // Persist intermediate reasoning steps from agent execution
-    for rs in &report.metrics.reasoning_steps {
-        let step_id = Uuid::new_v4().to_string();
-        let step = ThinkingStepCreate {
+    // Update the agent's tool usage statistics (names as called by the agent)
+    let tool_calls: Vec<(String, bool)> = tool_executions
+        .iter()
+        .map(|te| {
+            let name = match te.server_name {
+                Some(ref server) => format!("mcp__{}__{}", server, te.tool_name),
+                None => te.tool_name.clone(),
+            };
+            (name, te.success)
+        })
+        .collect();
+    if let Err(e) = record_agent_tool_usage(
+        &state.db,
+        &validated_agent_id,
+        &task_description,
+        &tool_calls,
+    )
+    .await
+    {
+        warn!(error = %e, "Failed to update tool usage statistics");
+    }
+
+    // Persist LLM turns of the tool loop (execution trace)
+    for turn in &report.metrics.llm_turns {
+        let turn_id = Uuid::new_v4().to_string();
+        let llm_turn = LlmTurnCreate {
             workflow_id: validated_workflow_id.clone(),
             message_id: message_id.clone(),
             agent_id: validated_agent_id.clone(),
-            step_number: thinking_step_number,
-            content: rs.content.clone(),
-            duration_ms: Some(rs.duration_ms),
-            tokens: None,
+            iteration: turn.iteration,
+            tokens_input: turn.tokens_input as u64,
+            tokens_output: turn.tokens_output as u64,
+            duration_ms: turn.duration_ms,
+            tool_calls: turn.tool_calls as u32,
+            started_at_ms: Some(turn.started_at_ms),
         };
-        if let Err(e) = state.db.create("thinking_step", &step_id, step).await {
-            warn!(error = %e, step_number = thinking_step_number, "Failed to persist intermediate reasoning step");
+        if let Err(e) = state
+            .outbox
+            .create(&state.db, "llm_turn", &turn_id, llm_turn)
+            .await
+        {
+            warn!(error = %e, iteration = turn.iteration, "Failed to persist LLM turn");
         }
-        thinking_step_number += 1;
     }
 
+    // Persist intermediate reasoning steps from agent execution
+    thinking_step_number = persist_reasoning_steps(
+        &state.db,
+        &state.outbox,
+        &validated_workflow_id,
+        &message_id,
+        &validated_agent_id,
+        &report.metrics.reasoning_steps,
+        thinking_step_number,
+    )
+    .await;
+
     info!(
         tool_executions_count = tool_executions.len(),
         thinking_steps_count = thinking_step_number,
         "Persisted tool executions and thinking steps to database"
     );
 
+    // Retry records queued in the outbox by this or earlier executions
+    if let Err(e) = state.outbox.flush(&state.db).await {
+        warn!(error = %e, "Failed to flush persistence outbox");
+    }
+
     // Build result with calculated cost
     let result = WorkflowResult {
         report: report.content,
@@ -654,9 +989,25 @@ pub async fn execute_workflow_streaming(
         },
         tools_used: report.metrics.tools_used.clone(),
         mcp_calls: report.metrics.mcp_calls.clone(),
+        images: tool_image_paths(&tool_executions),
         tool_executions,
+        moderation,
+        context_usage: Some(context_usage),
+        behavior_position,
+        generation,
     };
 
+    // First exchange: name the workflow in the background
+    if first_exchange {
+        spawn_title_generation(
+            &state,
+            validated_workflow_id.clone(),
+            validated_agent_id.clone(),
+            task_description,
+            result.response.clone(),
+        );
+    }
+
     // Emit completion
     emit_complete(
         &window,
@@ -686,6 +1037,7 @@ fn emit_chunk(window: &Window, chunk: StreamChunk) {
 
 /// Helper function to emit a completion event.
 fn emit_complete(window: &Window, complete: WorkflowComplete) {
+    crate::metrics::record_workflow_execution("interactive", &complete.status);
     if let Err(e) = window.emit(events::WORKFLOW_COMPLETE, &complete) {
         warn!(error = %e, "Failed to emit completion event");
     }
@@ -703,11 +1055,28 @@ fn emit_error(window: &Window, workflow_id: &str, error: &str) {
     );
 }
 
+/// Gives a cancelled execution `CANCEL_GRACE_PERIOD_MS` to stop on its own.
+///
+/// The agent observes the workflow cancellation token: it drops its LLM
+/// request and cancels its running MCP tool calls on their servers (sub-agents
+/// do the same). The execution is dropped after the grace period if it has
+/// not returned.
+pub(crate) async fn wind_down<F: Future + Unpin>(execution: &mut F) {
+    let grace = Duration::from_millis(wf_const::CANCEL_GRACE_PERIOD_MS);
+    if tokio::time::timeout(grace, execution).await.is_err() {
+        warn!(
+            grace_ms = wf_const::CANCEL_GRACE_PERIOD_MS,
+            "Cancelled execution did not stop in time, dropping it"
+        );
+    }
+}
+
 /// Cancels a streaming workflow execution immediately.
 ///
 /// Triggers the cancellation token associated with the workflow, causing the
 /// execute_workflow_streaming function to abort via tokio::select!.
-/// This provides immediate cancellation, even during LLM execution.
+/// The running agent stops its LLM iterations, aborts the in-flight provider
+/// request and cancels running MCP tool calls on their servers.
 ///
 /// # Arguments
 /// * `workflow_id` - The workflow ID to cancel
@@ -733,6 +1102,47 @@ pub async fn cancel_workflow_streaming(
     Ok(())
 }
 
+/// Persists the intermediate reasoning steps of an execution, encrypted when
+/// the workflow is sensitive.
+///
+/// # Returns
+/// Number of the next thinking step
+async fn persist_reasoning_steps(
+    db: &DBClient,
+    outbox: &PersistenceOutbox,
+    workflow_id: &str,
+    message_id: &str,
+    agent_id: &str,
+    steps: &[ReasoningStepData],
+    mut step_number: u32,
+) -> u32 {
+    for rs in steps {
+        let content = match WORKFLOW_VAULT.seal(db, workflow_id, &rs.content).await {
+            Ok(content) => content,
+            Err(e) => {
+                warn!(error = %e, step_number, "Failed to encrypt intermediate reasoning step");
+                step_number += 1;
+                continue;
+            }
+        };
+        let step_id = Uuid::new_v4().to_string();
+        let step = ThinkingStepCreate {
+            workflow_id: workflow_id.to_string(),
+            message_id: message_id.to_string(),
+            agent_id: agent_id.to_string(),
+            step_number,
+            content,
+            duration_ms: Some(rs.duration_ms),
+            tokens: None,
+        };
+        if let Err(e) = outbox.create(db, "thinking_step", &step_id, step).await {
+            warn!(error = %e, step_number, "Failed to persist intermediate reasoning step");
+        }
+        step_number += 1;
+    }
+    step_number
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -765,4 +1175,66 @@ mod tests {
         assert_eq!(events::WORKFLOW_STREAM, "workflow_stream");
         assert_eq!(events::WORKFLOW_COMPLETE, "workflow_complete");
     }
+
+    #[tokio::test]
+    async fn test_reasoning_steps_of_sensitive_workflow_are_encrypted() {
+        use crate::models::{WorkflowCreate, WorkflowStatus};
+        use crate::security::workflow_vault::{generate_key, is_encrypted};
+        use std::sync::{Arc, RwLock as StdRwLock};
+
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let outbox = PersistenceOutbox::new(
+            temp_dir.path().join("outbox.jsonl"),
+            Arc::new(StdRwLock::new(None)),
+        );
+
+        let wf_id = Uuid::new_v4().to_string();
+        db.create(
+            "workflow",
+            &wf_id,
+            WorkflowCreate::new("Secret".into(), "agent".into(), WorkflowStatus::Idle),
+        )
+        .await
+        .unwrap();
+        db.execute(&format!(
+            "UPDATE type::thing('workflow', '{}') SET sensitive = true",
+            wf_id
+        ))
+        .await
+        .unwrap();
+        WORKFLOW_VAULT.unlock(&wf_id, &generate_key()).unwrap();
+
+        let steps = vec![ReasoningStepData {
+            content: "the salary is 90k".to_string(),
+            duration_ms: 12,
+        }];
+        let next = persist_reasoning_steps(&db, &outbox, &wf_id, "m1", "agent", &steps, 2).await;
+        assert_eq!(next, 3);
+
+        let rows = db
+            .query_json("SELECT content, step_number FROM thinking_step")
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["step_number"], 2);
+        let content = rows[0]["content"].as_str().unwrap();
+        assert!(is_encrypted(content));
+        assert!(!content.contains("salary"));
+        assert_eq!(WORKFLOW_VAULT.reveal(&wf_id, content), "the salary is 90k");
+
+        // Closed: nothing is written in clear
+        WORKFLOW_VAULT.lock(&wf_id);
+        persist_reasoning_steps(&db, &outbox, &wf_id, "m1", "agent", &steps, 3).await;
+        let rows = db
+            .query_json("SELECT content FROM thinking_step")
+            .await
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+
+        WORKFLOW_VAULT.forget(&wf_id);
+    }
 }
@@ -44,8 +44,7 @@ pub async fn load_workflow_sub_agent_executions(
 
     // Use explicit field selection with meta::id(id) to avoid SurrealDB SDK
     // serialization issues with internal Thing type (see CLAUDE.md)
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             parent_agent_id,
@@ -61,15 +60,21 @@ pub async fn load_workflow_sub_agent_executions(
             created_at,
             completed_at
         FROM sub_agent_execution
-        WHERE workflow_id = '{}'
-        ORDER BY created_at ASC"#,
-        validated_workflow_id
-    );
+        WHERE workflow_id = $workflow_id
+        ORDER BY created_at ASC"#;
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
 
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load workflow sub-agent executions");
-        format!("Failed to load workflow sub-agent executions: {}", e)
-    })?;
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow sub-agent executions");
+            format!("Failed to load workflow sub-agent executions: {}", e)
+        })?;
 
     // Deserialize using serde_json
     let executions: Vec<SubAgentExecution> = json_results
@@ -110,12 +115,17 @@ pub async fn clear_workflow_sub_agent_executions(
     })?;
 
     // First count existing executions
-    let count_query = format!(
-        "SELECT count() FROM sub_agent_execution WHERE workflow_id = '{}' GROUP ALL",
-        validated_workflow_id
-    );
-    let count_result: Vec<serde_json::Value> =
-        state.db.query(&count_query).await.unwrap_or_default();
+    let count_query =
+        "SELECT count() FROM sub_agent_execution WHERE workflow_id = $workflow_id GROUP ALL";
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
+    let count_result: Vec<serde_json::Value> = state
+        .db
+        .query_with_params(count_query, params)
+        .await
+        .unwrap_or_default();
 
     let count = count_result
         .first()
@@ -126,10 +136,13 @@ pub async fn clear_workflow_sub_agent_executions(
     // Delete all executions for the workflow
     state
         .db
-        .execute(&format!(
-            "DELETE sub_agent_execution WHERE workflow_id = '{}'",
-            validated_workflow_id
-        ))
+        .execute_with_params(
+            "DELETE sub_agent_execution WHERE workflow_id = $workflow_id",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to clear workflow sub-agent executions");
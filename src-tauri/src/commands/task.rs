@@ -43,6 +43,7 @@ use crate::{
     tools::constants::query_limits,
     AppState,
 };
+use serde_json::json;
 use tauri::State;
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
@@ -166,8 +167,7 @@ pub async fn get_task(task_id: String, state: State<'_, AppState>) -> Result<Tas
     })?;
 
     // Use meta::id(id) to extract clean UUID from SurrealDB Thing type
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             name,
@@ -180,14 +180,16 @@ pub async fn get_task(task_id: String, state: State<'_, AppState>) -> Result<Tas
             created_at,
             completed_at
         FROM task
-        WHERE meta::id(id) = '{}'"#,
-        validated_id
-    );
+        WHERE id = type::thing('task', $id)"#;
 
-    let results: Vec<Task> = state.db.query(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to query task");
-        format!("Database error: {}", e)
-    })?;
+    let results: Vec<Task> = state
+        .db
+        .query_with_params(query, vec![("id".to_string(), json!(validated_id))])
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to query task");
+            format!("Database error: {}", e)
+        })?;
 
     results
         .into_iter()
@@ -230,17 +232,21 @@ pub async fn list_workflow_tasks(
             created_at,
             completed_at
         FROM task
-        WHERE workflow_id = '{}'
+        WHERE workflow_id = $workflow_id
         ORDER BY priority ASC, created_at ASC
         LIMIT {}"#,
-        validated_workflow_id,
         query_limits::DEFAULT_LIST_LIMIT
     );
+    let params = vec![("workflow_id".to_string(), json!(validated_workflow_id))];
 
-    let tasks: Vec<Task> = state.db.query(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to list tasks");
-        format!("Database error: {}", e)
-    })?;
+    let tasks: Vec<Task> = state
+        .db
+        .query_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to list tasks");
+            format!("Database error: {}", e)
+        })?;
 
     info!(count = tasks.len(), "Workflow tasks loaded");
     Ok(tasks)
@@ -273,9 +279,11 @@ pub async fn list_tasks_by_status(
     }
 
     // Add LIMIT to prevent memory explosion (OPT-DB-8)
+    let mut params = vec![("status".to_string(), json!(status))];
     let query = if let Some(wf_id) = workflow_id {
         let validated_wf_id =
             Validator::validate_uuid(&wf_id).map_err(|e| format!("Invalid workflow_id: {}", e))?;
+        params.push(("workflow_id".to_string(), json!(validated_wf_id)));
         format!(
             r#"SELECT
                 meta::id(id) AS id,
@@ -290,11 +298,9 @@ pub async fn list_tasks_by_status(
                 created_at,
                 completed_at
             FROM task
-            WHERE status = '{}' AND workflow_id = '{}'
+            WHERE status = $status AND workflow_id = $workflow_id
             ORDER BY priority ASC, created_at ASC
             LIMIT {}"#,
-            status,
-            validated_wf_id,
             query_limits::DEFAULT_LIST_LIMIT
         )
     } else {
@@ -312,18 +318,21 @@ pub async fn list_tasks_by_status(
                 created_at,
                 completed_at
             FROM task
-            WHERE status = '{}'
+            WHERE status = $status
             ORDER BY priority ASC, created_at ASC
             LIMIT {}"#,
-            status,
             query_limits::DEFAULT_LIST_LIMIT
         )
     };
 
-    let tasks: Vec<Task> = state.db.query(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to list tasks by status");
-        format!("Database error: {}", e)
-    })?;
+    let tasks: Vec<Task> = state
+        .db
+        .query_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to list tasks by status");
+            format!("Database error: {}", e)
+        })?;
 
     info!(count = tasks.len(), status = %status, "Tasks by status loaded");
     Ok(tasks)
@@ -355,31 +364,36 @@ pub async fn update_task(
     })?;
 
     // Build SET clause dynamically
-    let mut set_parts: Vec<String> = Vec::new();
+    let mut set_parts: Vec<&str> = Vec::new();
+    let mut params = vec![("id".to_string(), json!(validated_id))];
 
     if let Some(name) = &updates.name {
         if name.len() > 128 {
             return Err("Task name must be 128 characters or less".to_string());
         }
-        set_parts.push(format!("name = '{}'", name.replace('\'', "''")));
+        set_parts.push("name = $name");
+        params.push(("name".to_string(), json!(name)));
     }
 
     if let Some(desc) = &updates.description {
         if desc.len() > 1000 {
             return Err("Task description must be 1000 characters or less".to_string());
         }
-        set_parts.push(format!("description = '{}'", desc.replace('\'', "''")));
+        set_parts.push("description = $description");
+        params.push(("description".to_string(), json!(desc)));
     }
 
     if let Some(agent) = &updates.agent_assigned {
-        set_parts.push(format!("agent_assigned = '{}'", agent.replace('\'', "''")));
+        set_parts.push("agent_assigned = $agent_assigned");
+        params.push(("agent_assigned".to_string(), json!(agent)));
     }
 
     if let Some(priority) = updates.priority {
         if !(1..=5).contains(&priority) {
             return Err("Priority must be between 1 and 5".to_string());
         }
-        set_parts.push(format!("priority = {}", priority));
+        set_parts.push("priority = $priority");
+        params.push(("priority".to_string(), json!(priority)));
     }
 
     if let Some(status) = &updates.status {
@@ -387,17 +401,18 @@ pub async fn update_task(
         if !valid_statuses.contains(&status.as_str()) {
             return Err(format!("Invalid status '{}'", status));
         }
-        set_parts.push(format!("status = '{}'", status));
+        set_parts.push("status = $status");
+        params.push(("status".to_string(), json!(status)));
     }
 
     if let Some(deps) = &updates.dependencies {
-        let deps_json = serde_json::to_string(deps)
-            .map_err(|e| format!("Failed to serialize dependencies: {}", e))?;
-        set_parts.push(format!("dependencies = {}", deps_json));
+        set_parts.push("dependencies = $dependencies");
+        params.push(("dependencies".to_string(), json!(deps)));
     }
 
     if let Some(duration) = updates.duration_ms {
-        set_parts.push(format!("duration_ms = {}", duration));
+        set_parts.push("duration_ms = $duration_ms");
+        params.push(("duration_ms".to_string(), json!(duration)));
     }
 
     if set_parts.is_empty() {
@@ -405,16 +420,19 @@ pub async fn update_task(
     }
 
     let query = format!(
-        "UPDATE task:`{}` SET {}",
-        validated_id,
+        "UPDATE type::thing('task', $id) SET {}",
         set_parts.join(", ")
     );
 
-    // Use execute() for UPDATE to avoid SurrealDB SDK serialization issues
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to update task");
-        format!("Database error: {}", e)
-    })?;
+    // Use a raw UPDATE query to avoid SurrealDB SDK serialization issues
+    state
+        .db
+        .execute_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update task");
+            format!("Database error: {}", e)
+        })?;
 
     info!(task_id = %validated_id, "Task updated successfully");
 
@@ -453,12 +471,14 @@ pub async fn update_task_status(
     }
 
     // Use parameterized query for UPDATE to prevent injection
-    // Note: validated_id is a UUID (safe), but status is validated above
     state
         .db
         .execute_with_params(
-            &format!("UPDATE task:`{}` SET status = $status", validated_id),
-            vec![("status".to_string(), serde_json::json!(status))],
+            "UPDATE type::thing('task', $id) SET status = $status",
+            vec![
+                ("id".to_string(), json!(validated_id)),
+                ("status".to_string(), json!(status)),
+            ],
         )
         .await
         .map_err(|e| {
@@ -493,22 +513,28 @@ pub async fn complete_task(
     let validated_id =
         Validator::validate_uuid(&task_id).map_err(|e| format!("Invalid task_id: {}", e))?;
 
+    let mut params = vec![("id".to_string(), json!(validated_id))];
     let duration_part = if let Some(d) = duration_ms {
-        format!(", duration_ms = {}", d)
+        params.push(("duration_ms".to_string(), json!(d)));
+        ", duration_ms = $duration_ms"
     } else {
-        String::new()
+        ""
     };
 
     let query = format!(
-        "UPDATE task:`{}` SET status = 'completed', completed_at = time::now(){}",
-        validated_id, duration_part
+        "UPDATE type::thing('task', $id) SET status = 'completed', completed_at = time::now(){}",
+        duration_part
     );
 
-    // Use execute() for UPDATE to avoid SurrealDB SDK serialization issues
-    state.db.execute(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to complete task");
-        format!("Database error: {}", e)
-    })?;
+    // Use a raw UPDATE query to avoid SurrealDB SDK serialization issues
+    state
+        .db
+        .execute_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to complete task");
+            format!("Database error: {}", e)
+        })?;
 
     info!(task_id = %validated_id, "Task marked as completed");
     get_task(task_id, state).await
@@ -580,6 +606,13 @@ mod tests {
 
         AppState {
             db: db.clone(),
+            outbox: Arc::new(crate::db::outbox::PersistenceOutbox::new(
+                db_path.with_file_name("outbox.jsonl"),
+                Default::default(),
+            )),
+            attachments: Arc::new(crate::db::attachments::AttachmentStore::new(
+                db_path.with_file_name("attachments"),
+            )),
             registry,
             orchestrator,
             llm_manager,
@@ -593,6 +626,14 @@ mod tests {
                 std::collections::HashMap::new(),
             )),
             app_handle: Arc::new(std::sync::RwLock::new(None)),
+            startup_status: Default::default(),
+            scheduler: Default::default(),
+            job_queue: Default::default(),
+            profiles: Arc::new(crate::profiles::ProfileStore::new(
+                db_path.parent().unwrap(),
+                &db_path,
+            )),
+            config: Default::default(),
         }
     }
 
@@ -21,9 +21,13 @@
 //! recovery with full reasoning history.
 
 use crate::{
-    models::{ThinkingStep, ThinkingStepCreate},
-    security::Validator,
-    tools::constants::commands as cmd_const,
+    db::{
+        utils::{decode_cursor, encode_cursor},
+        DBClient,
+    },
+    models::{ThinkingStep, ThinkingStepCreate, ThinkingStepPage},
+    security::{workflow_vault::ENCRYPTED_PREFIX, Validator, WORKFLOW_VAULT},
+    tools::constants::{commands as cmd_const, query_limits},
     AppState,
 };
 use tauri::State;
@@ -95,6 +99,10 @@ pub async fn save_thinking_step(
         ));
     }
 
+    let content = WORKFLOW_VAULT
+        .seal(&state.db, &validated_workflow_id, &content)
+        .await?;
+
     let step_id = Uuid::new_v4().to_string();
 
     // Build ThinkingStepCreate payload
@@ -145,8 +153,7 @@ pub async fn load_workflow_thinking_steps(
 
     // Use explicit field selection with meta::id(id) to avoid SurrealDB SDK
     // serialization issues with internal Thing type (see CLAUDE.md)
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             message_id,
@@ -157,18 +164,24 @@ pub async fn load_workflow_thinking_steps(
             tokens,
             created_at
         FROM thinking_step
-        WHERE workflow_id = '{}'
-        ORDER BY created_at ASC, step_number ASC"#,
-        validated_workflow_id
-    );
-
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load workflow thinking steps");
-        format!("Failed to load workflow thinking steps: {}", e)
-    })?;
+        WHERE workflow_id = $workflow_id
+        ORDER BY created_at ASC, step_number ASC"#;
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
+
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow thinking steps");
+            format!("Failed to load workflow thinking steps: {}", e)
+        })?;
 
     // Deserialize using serde_json
-    let steps: Vec<ThinkingStep> = json_results
+    let mut steps: Vec<ThinkingStep> = json_results
         .into_iter()
         .map(serde_json::from_value)
         .collect::<std::result::Result<Vec<ThinkingStep>, _>>()
@@ -177,10 +190,202 @@ pub async fn load_workflow_thinking_steps(
             format!("Failed to deserialize thinking steps: {}", e)
         })?;
 
+    steps
+        .iter_mut()
+        .for_each(|s| WORKFLOW_VAULT.reveal_thinking_step(s));
+
     info!(count = steps.len(), "Workflow thinking steps loaded");
     Ok(steps)
 }
 
+/// Loads a page of the thinking steps of a workflow (oldest first).
+///
+/// Pages are loaded on demand from the cursor of the previous page. The
+/// summary mode shortens each content to a preview; the full step is loaded
+/// with `get_thinking_step` when expanded.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow ID to load thinking steps for
+/// * `cursor` - `next_cursor` of the previous page (`None` for the first page)
+/// * `limit` - Page size (default 50, capped at 500)
+/// * `summary` - Shorten the contents to a preview
+///
+/// # Returns
+/// Page of thinking steps with the cursor of the next page
+#[tauri::command]
+#[instrument(
+    name = "load_workflow_thinking_steps_page",
+    skip(state, cursor),
+    fields(workflow_id = %workflow_id, limit = ?limit, summary = ?summary)
+)]
+pub async fn load_workflow_thinking_steps_page(
+    workflow_id: String,
+    cursor: Option<String>,
+    limit: Option<u32>,
+    summary: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ThinkingStepPage, String> {
+    let validated_workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let page = query_thinking_step_page(
+        &state.db,
+        &validated_workflow_id,
+        cursor.as_deref(),
+        limit,
+        summary.unwrap_or(false),
+    )
+    .await?;
+    info!(
+        count = page.steps.len(),
+        has_more = page.has_more,
+        "Thinking step page loaded"
+    );
+    Ok(page)
+}
+
+/// Runs a thinking step page query (shared by the command and tests)
+pub(crate) async fn query_thinking_step_page(
+    db: &DBClient,
+    workflow_id: &str,
+    cursor: Option<&str>,
+    limit: Option<u32>,
+    summary: bool,
+) -> Result<ThinkingStepPage, String> {
+    let limit = limit
+        .map(|l| l as usize)
+        .unwrap_or(query_limits::DEFAULT_TRACE_PAGE_SIZE)
+        .clamp(1, query_limits::MAX_TRACE_PAGE_SIZE);
+
+    let mut conditions = "workflow_id = $workflow_id".to_string();
+    let mut params = vec![("workflow_id".to_string(), serde_json::json!(workflow_id))];
+    if let Some(cursor) = cursor {
+        let (after, after_id) = decode_cursor(cursor)?;
+        conditions.push_str(
+            " AND (created_at > <datetime>$after \
+             OR (created_at = <datetime>$after AND meta::id(id) > $after_id))",
+        );
+        params.push(("after".to_string(), serde_json::json!(after)));
+        params.push(("after_id".to_string(), serde_json::json!(after_id)));
+    }
+    let content_field = if summary {
+        params.push((
+            "preview".to_string(),
+            serde_json::json!(cmd_const::THINKING_PREVIEW_CHARS),
+        ));
+        params.push((
+            "encrypted_prefix".to_string(),
+            serde_json::json!(ENCRYPTED_PREFIX),
+        ));
+        // Encrypted contents are shortened once decrypted
+        "IF string::starts_with(content, $encrypted_prefix) THEN content \
+         ELSE string::slice(content, 0, $preview) END AS content"
+    } else {
+        "content"
+    };
+
+    // One extra row tells whether another page follows
+    let query = format!(
+        "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, step_number, {}, \
+             duration_ms, tokens, created_at \
+         FROM thinking_step WHERE {} ORDER BY created_at ASC, id ASC LIMIT {}",
+        content_field,
+        conditions,
+        limit + 1
+    );
+    let json_results = db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load thinking step page");
+            format!("Failed to load thinking steps: {}", e)
+        })?;
+
+    let mut steps: Vec<ThinkingStep> = json_results
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<Vec<ThinkingStep>, _>>()
+        .map_err(|e| {
+            error!(error = %e, "Failed to deserialize thinking steps");
+            format!("Failed to deserialize thinking steps: {}", e)
+        })?;
+    let has_more = steps.len() > limit;
+    steps.truncate(limit);
+    for step in steps.iter_mut() {
+        WORKFLOW_VAULT.reveal_thinking_step(step);
+        if summary {
+            if let Some((end, _)) = step
+                .content
+                .char_indices()
+                .nth(cmd_const::THINKING_PREVIEW_CHARS)
+            {
+                step.content.truncate(end);
+            }
+        }
+    }
+
+    let next_cursor = if has_more {
+        steps
+            .last()
+            .map(|step| encode_cursor(&step.created_at, &step.id))
+    } else {
+        None
+    };
+    Ok(ThinkingStepPage {
+        steps,
+        next_cursor,
+        has_more,
+        summary,
+    })
+}
+
+/// Loads a single thinking step by ID, with its full content.
+///
+/// # Arguments
+/// * `step_id` - The thinking step UUID
+#[tauri::command]
+#[instrument(name = "get_thinking_step", skip(state), fields(step_id = %step_id))]
+pub async fn get_thinking_step(
+    step_id: String,
+    state: State<'_, AppState>,
+) -> Result<ThinkingStep, String> {
+    let validated_id = Validator::validate_uuid(&step_id).map_err(|e| {
+        warn!(error = %e, "Invalid step ID");
+        format!("Invalid step ID: {}", e)
+    })?;
+
+    let json_results = state
+        .db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, step_number, \
+                 content, duration_ms, tokens, created_at \
+             FROM thinking_step WHERE meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(validated_id))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to get thinking step");
+            format!("Failed to get thinking step: {}", e)
+        })?;
+
+    json_results
+        .into_iter()
+        .next()
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|e| {
+            error!(error = %e, "Failed to deserialize thinking step");
+            format!("Failed to deserialize thinking step: {}", e)
+        })?
+        .ok_or_else(|| format!("Thinking step not found: {}", step_id))
+        .map(|mut step| {
+            WORKFLOW_VAULT.reveal_thinking_step(&mut step);
+            step
+        })
+}
+
 /// Loads thinking steps for a specific message.
 ///
 /// Useful for displaying reasoning associated with a particular assistant response.
@@ -204,8 +409,7 @@ pub async fn load_message_thinking_steps(
         format!("Invalid message_id: {}", e)
     })?;
 
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             message_id,
@@ -216,17 +420,23 @@ pub async fn load_message_thinking_steps(
             tokens,
             created_at
         FROM thinking_step
-        WHERE message_id = '{}'
-        ORDER BY step_number ASC"#,
-        validated_message_id
-    );
-
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load message thinking steps");
-        format!("Failed to load message thinking steps: {}", e)
-    })?;
+        WHERE message_id = $message_id
+        ORDER BY step_number ASC"#;
+    let params = vec![(
+        "message_id".to_string(),
+        serde_json::json!(validated_message_id),
+    )];
+
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load message thinking steps");
+            format!("Failed to load message thinking steps: {}", e)
+        })?;
 
-    let steps: Vec<ThinkingStep> = json_results
+    let mut steps: Vec<ThinkingStep> = json_results
         .into_iter()
         .map(serde_json::from_value)
         .collect::<std::result::Result<Vec<ThinkingStep>, _>>()
@@ -235,6 +445,10 @@ pub async fn load_message_thinking_steps(
             format!("Failed to deserialize thinking steps: {}", e)
         })?;
 
+    steps
+        .iter_mut()
+        .for_each(|s| WORKFLOW_VAULT.reveal_thinking_step(s));
+
     info!(count = steps.len(), "Message thinking steps loaded");
     Ok(steps)
 }
@@ -260,10 +474,9 @@ pub async fn delete_thinking_step(
         format!("Invalid step ID: {}", e)
     })?;
 
-    // Use execute() with DELETE query to avoid SurrealDB SDK serialization issues
     state
         .db
-        .execute(&format!("DELETE thinking_step:`{}`", validated_id))
+        .delete(&format!("thinking_step:{}", validated_id))
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to delete thinking step");
@@ -296,12 +509,17 @@ pub async fn clear_workflow_thinking_steps(
     })?;
 
     // First count existing steps
-    let count_query = format!(
-        "SELECT count() FROM thinking_step WHERE workflow_id = '{}' GROUP ALL",
-        validated_workflow_id
-    );
-    let count_result: Vec<serde_json::Value> =
-        state.db.query(&count_query).await.unwrap_or_default();
+    let count_query =
+        "SELECT count() FROM thinking_step WHERE workflow_id = $workflow_id GROUP ALL";
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
+    let count_result: Vec<serde_json::Value> = state
+        .db
+        .query_with_params(count_query, params)
+        .await
+        .unwrap_or_default();
 
     let count = count_result
         .first()
@@ -312,10 +530,13 @@ pub async fn clear_workflow_thinking_steps(
     // Delete all thinking steps for the workflow
     state
         .db
-        .execute(&format!(
-            "DELETE thinking_step WHERE workflow_id = '{}'",
-            validated_workflow_id
-        ))
+        .execute_with_params(
+            "DELETE thinking_step WHERE workflow_id = $workflow_id",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to clear workflow thinking steps");
@@ -350,4 +571,57 @@ mod tests {
         let long_content = "x".repeat(cmd_const::MAX_THINKING_CONTENT_LEN + 1);
         assert!(long_content.len() > cmd_const::MAX_THINKING_CONTENT_LEN);
     }
+
+    #[tokio::test]
+    async fn test_thinking_step_pages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let workflow_id = Uuid::new_v4().to_string();
+        for i in 0..3 {
+            db.create(
+                "thinking_step",
+                &Uuid::new_v4().to_string(),
+                ThinkingStepCreate::new(
+                    workflow_id.clone(),
+                    "m1".to_string(),
+                    "agent".to_string(),
+                    i,
+                    "r".repeat(cmd_const::THINKING_PREVIEW_CHARS * 2),
+                ),
+            )
+            .await
+            .unwrap();
+        }
+
+        let first = query_thinking_step_page(&db, &workflow_id, None, Some(2), true)
+            .await
+            .unwrap();
+        assert_eq!(first.steps.len(), 2);
+        assert!(first.has_more);
+        assert_eq!(
+            first.steps[0].content.chars().count(),
+            cmd_const::THINKING_PREVIEW_CHARS
+        );
+
+        let second = query_thinking_step_page(
+            &db,
+            &workflow_id,
+            first.next_cursor.as_deref(),
+            Some(2),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.steps.len(), 1);
+        assert_eq!(second.steps[0].step_number, 2);
+        assert_eq!(
+            second.steps[0].content.len(),
+            cmd_const::THINKING_PREVIEW_CHARS * 2
+        );
+        assert!(!second.has_more && second.next_cursor.is_none());
+    }
 }
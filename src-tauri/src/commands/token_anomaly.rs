@@ -0,0 +1,115 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token usage anomaly settings commands.
+//!
+//! Settings are stored in the `settings:token_anomaly` record and read by
+//! [`crate::agents::token_monitor::TokenMonitor`] at the start of each run.
+
+use crate::agents::token_monitor::{load_token_anomaly_settings, TOKEN_ANOMALY_SETTINGS_KEY};
+use crate::db::queries;
+use crate::models::token_anomaly::TokenAnomalySettings;
+use crate::AppState;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Gets the token anomaly detection settings.
+/// Returns default settings if none are configured.
+#[tauri::command]
+#[instrument(name = "get_token_anomaly_settings", skip(state))]
+pub async fn get_token_anomaly_settings(
+    state: State<'_, AppState>,
+) -> Result<TokenAnomalySettings, String> {
+    info!("Loading token anomaly settings");
+    Ok(load_token_anomaly_settings(&state.db).await)
+}
+
+/// Updates the token anomaly detection settings.
+///
+/// # Arguments
+/// * `settings` - The complete settings to store
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_token_anomaly_settings", skip(state, settings))]
+pub async fn update_token_anomaly_settings(
+    settings: TokenAnomalySettings,
+    state: State<'_, AppState>,
+) -> Result<TokenAnomalySettings, String> {
+    info!("Updating token anomaly settings");
+
+    validate_token_anomaly_settings(&settings).map_err(|e| {
+        warn!(error = %e, "Invalid token anomaly settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize token anomaly settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, TOKEN_ANOMALY_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save token anomaly settings");
+            format!("Failed to save token anomaly settings: {}", e)
+        })?;
+
+    info!("Token anomaly settings updated successfully");
+    Ok(settings)
+}
+
+/// Validates token anomaly settings ranges
+fn validate_token_anomaly_settings(settings: &TokenAnomalySettings) -> Result<(), String> {
+    if !(1.5..=100.0).contains(&settings.threshold_multiplier) {
+        return Err("Threshold multiplier must be between 1.5 and 100".to_string());
+    }
+    if !(1..=200).contains(&settings.baseline_window) {
+        return Err("Baseline window must be between 1 and 200 runs".to_string());
+    }
+    if settings.min_baseline_runs == 0 || settings.min_baseline_runs > settings.baseline_window {
+        return Err("Minimum baseline runs must be between 1 and the baseline window".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_token_anomaly_settings() {
+        assert!(validate_token_anomaly_settings(&TokenAnomalySettings::default()).is_ok());
+
+        let settings = TokenAnomalySettings {
+            threshold_multiplier: 1.0,
+            ..Default::default()
+        };
+        assert!(validate_token_anomaly_settings(&settings).is_err());
+
+        let settings = TokenAnomalySettings {
+            baseline_window: 0,
+            ..Default::default()
+        };
+        assert!(validate_token_anomaly_settings(&settings).is_err());
+
+        let settings = TokenAnomalySettings {
+            baseline_window: 10,
+            min_baseline_runs: 11,
+            ..Default::default()
+        };
+        assert!(validate_token_anomaly_settings(&settings).is_err());
+    }
+}
@@ -21,9 +21,14 @@
 //! recovery with full tool call history.
 
 use crate::{
-    models::{ToolExecution, ToolExecutionCreate},
-    security::Validator,
-    tools::constants::commands as cmd_const,
+    agents::tool_result_diff::ResultDiffs,
+    db::{
+        utils::{decode_cursor, encode_cursor},
+        DBClient,
+    },
+    models::{ToolExecution, ToolExecutionCreate, ToolExecutionPage},
+    security::{Validator, WORKFLOW_VAULT},
+    tools::constants::{commands as cmd_const, query_limits},
     AppState,
 };
 use tauri::State;
@@ -32,6 +37,9 @@ use uuid::Uuid;
 
 /// Saves a new tool execution to the database.
 ///
+/// If the call repeats an earlier successful call of the workflow (same tool
+/// and input), the changes of its output are stored with it.
+///
 /// # Arguments
 /// * `workflow_id` - Associated workflow ID
 /// * `message_id` - Associated message ID
@@ -145,6 +153,34 @@ pub async fn save_tool_execution(
 
     let execution_id = Uuid::new_v4().to_string();
 
+    let (previous_execution_id, result_diff) = ResultDiffs::new(&state.db, &validated_workflow_id)
+        .diff(
+            &execution_id,
+            &tool_name,
+            server_name.as_deref(),
+            &input_params,
+            &output_result,
+            success,
+        )
+        .await
+        .unzip();
+
+    // Tool logs of a sensitive workflow are stored encrypted
+    let input_params = WORKFLOW_VAULT
+        .seal_json(&state.db, &validated_workflow_id, input_params)
+        .await?;
+    let output_result = WORKFLOW_VAULT
+        .seal_json(&state.db, &validated_workflow_id, output_result)
+        .await?;
+    let result_diff = match result_diff {
+        Some(diff) => Some(
+            WORKFLOW_VAULT
+                .seal_json(&state.db, &validated_workflow_id, diff)
+                .await?,
+        ),
+        None => None,
+    };
+
     // Build ToolExecutionCreate payload
     let execution = ToolExecutionCreate {
         workflow_id: validated_workflow_id,
@@ -159,6 +195,9 @@ pub async fn save_tool_execution(
         error_message,
         duration_ms,
         iteration,
+        previous_execution_id,
+        result_diff,
+        started_at_ms: None,
     };
 
     // Insert into database
@@ -179,6 +218,8 @@ pub async fn save_tool_execution(
 ///
 /// # Arguments
 /// * `workflow_id` - The workflow ID to load executions for
+/// * `compact` - Omit the output of executions that have a result diff (the
+///   full output remains available through `get_tool_execution`)
 ///
 /// # Returns
 /// Vector of tool executions in chronological order
@@ -186,6 +227,7 @@ pub async fn save_tool_execution(
 #[instrument(name = "load_workflow_tool_executions", skip(state), fields(workflow_id = %workflow_id))]
 pub async fn load_workflow_tool_executions(
     workflow_id: String,
+    compact: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<Vec<ToolExecution>, String> {
     info!("Loading workflow tool executions");
@@ -198,8 +240,7 @@ pub async fn load_workflow_tool_executions(
 
     // Use explicit field selection with meta::id(id) to avoid SurrealDB SDK
     // serialization issues with internal Thing type (see CLAUDE.md)
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             message_id,
@@ -213,20 +254,28 @@ pub async fn load_workflow_tool_executions(
             error_message,
             duration_ms,
             iteration,
+            previous_execution_id,
+            result_diff,
             created_at
         FROM tool_execution
-        WHERE workflow_id = '{}'
-        ORDER BY created_at ASC"#,
-        validated_workflow_id
-    );
-
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load workflow tool executions");
-        format!("Failed to load workflow tool executions: {}", e)
-    })?;
+        WHERE workflow_id = $workflow_id
+        ORDER BY created_at ASC"#;
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
+
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow tool executions");
+            format!("Failed to load workflow tool executions: {}", e)
+        })?;
 
     // Deserialize using serde_json
-    let executions: Vec<ToolExecution> = json_results
+    let mut executions: Vec<ToolExecution> = json_results
         .into_iter()
         .map(serde_json::from_value)
         .collect::<std::result::Result<Vec<ToolExecution>, _>>()
@@ -234,11 +283,146 @@ pub async fn load_workflow_tool_executions(
             error!(error = %e, "Failed to deserialize tool executions");
             format!("Failed to deserialize tool executions: {}", e)
         })?;
+    executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+    if compact.unwrap_or(false) {
+        executions
+            .iter_mut()
+            .filter(|te| te.result_diff.is_some())
+            .for_each(|te| te.output_result = serde_json::Value::Null);
+    }
 
     info!(count = executions.len(), "Workflow tool executions loaded");
     Ok(executions)
 }
 
+/// Loads a page of the tool executions of a workflow (oldest first).
+///
+/// Long agentic sessions accumulate megabytes of tool payloads: pages are
+/// loaded on demand from the cursor of the previous page, and the summary
+/// mode leaves the payloads out until an execution is expanded with
+/// `get_tool_execution`.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow ID to load executions for
+/// * `cursor` - `next_cursor` of the previous page (`None` for the first page)
+/// * `limit` - Page size (default 50, capped at 500)
+/// * `summary` - Omit the input, output and result diff of the executions
+///
+/// # Returns
+/// Page of executions with the cursor of the next page
+#[tauri::command]
+#[instrument(
+    name = "load_workflow_tool_executions_page",
+    skip(state, cursor),
+    fields(workflow_id = %workflow_id, limit = ?limit, summary = ?summary)
+)]
+pub async fn load_workflow_tool_executions_page(
+    workflow_id: String,
+    cursor: Option<String>,
+    limit: Option<u32>,
+    summary: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ToolExecutionPage, String> {
+    let validated_workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let page = query_tool_execution_page(
+        &state.db,
+        &validated_workflow_id,
+        cursor.as_deref(),
+        limit,
+        summary.unwrap_or(false),
+    )
+    .await?;
+    info!(
+        count = page.executions.len(),
+        has_more = page.has_more,
+        "Tool execution page loaded"
+    );
+    Ok(page)
+}
+
+/// Runs a tool execution page query (shared by the command and tests)
+pub(crate) async fn query_tool_execution_page(
+    db: &DBClient,
+    workflow_id: &str,
+    cursor: Option<&str>,
+    limit: Option<u32>,
+    summary: bool,
+) -> Result<ToolExecutionPage, String> {
+    let limit = limit
+        .map(|l| l as usize)
+        .unwrap_or(query_limits::DEFAULT_TRACE_PAGE_SIZE)
+        .clamp(1, query_limits::MAX_TRACE_PAGE_SIZE);
+
+    let mut conditions = "workflow_id = $workflow_id".to_string();
+    let mut params = vec![("workflow_id".to_string(), serde_json::json!(workflow_id))];
+    if let Some(cursor) = cursor {
+        let (after, after_id) = decode_cursor(cursor)?;
+        conditions.push_str(
+            " AND (created_at > <datetime>$after \
+             OR (created_at = <datetime>$after AND meta::id(id) > $after_id))",
+        );
+        params.push(("after".to_string(), serde_json::json!(after)));
+        params.push(("after_id".to_string(), serde_json::json!(after_id)));
+    }
+    let payload_fields = if summary {
+        "NULL AS input_params, NULL AS output_result"
+    } else {
+        "input_params, output_result, result_diff"
+    };
+
+    // One extra row tells whether another page follows
+    let query = format!(
+        "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, tool_type, tool_name, \
+             server_name, {}, success, error_message, duration_ms, iteration, \
+             previous_execution_id, created_at \
+         FROM tool_execution WHERE {} ORDER BY created_at ASC, id ASC LIMIT {}",
+        payload_fields,
+        conditions,
+        limit + 1
+    );
+    let json_results = db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load tool execution page");
+            format!("Failed to load tool executions: {}", e)
+        })?;
+
+    let mut executions: Vec<ToolExecution> = json_results
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<Vec<ToolExecution>, _>>()
+        .map_err(|e| {
+            error!(error = %e, "Failed to deserialize tool executions");
+            format!("Failed to deserialize tool executions: {}", e)
+        })?;
+    let has_more = executions.len() > limit;
+    executions.truncate(limit);
+    executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+
+    let next_cursor = if has_more {
+        executions
+            .last()
+            .map(|te| encode_cursor(&te.created_at, &te.id))
+    } else {
+        None
+    };
+    Ok(ToolExecutionPage {
+        executions,
+        next_cursor,
+        has_more,
+        summary,
+    })
+}
+
 /// Loads tool executions for a specific message.
 ///
 /// Useful for displaying tool calls associated with a particular assistant response.
@@ -262,8 +446,7 @@ pub async fn load_message_tool_executions(
         format!("Invalid message_id: {}", e)
     })?;
 
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id,
             message_id,
@@ -277,19 +460,27 @@ pub async fn load_message_tool_executions(
             error_message,
             duration_ms,
             iteration,
+            previous_execution_id,
+            result_diff,
             created_at
         FROM tool_execution
-        WHERE message_id = '{}'
-        ORDER BY created_at ASC"#,
-        validated_message_id
-    );
-
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load message tool executions");
-        format!("Failed to load message tool executions: {}", e)
-    })?;
+        WHERE message_id = $message_id
+        ORDER BY created_at ASC"#;
+    let params = vec![(
+        "message_id".to_string(),
+        serde_json::json!(validated_message_id),
+    )];
+
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load message tool executions");
+            format!("Failed to load message tool executions: {}", e)
+        })?;
 
-    let executions: Vec<ToolExecution> = json_results
+    let mut executions: Vec<ToolExecution> = json_results
         .into_iter()
         .map(serde_json::from_value)
         .collect::<std::result::Result<Vec<ToolExecution>, _>>()
@@ -297,6 +488,9 @@ pub async fn load_message_tool_executions(
             error!(error = %e, "Failed to deserialize tool executions");
             format!("Failed to deserialize tool executions: {}", e)
         })?;
+    executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
 
     info!(count = executions.len(), "Message tool executions loaded");
     Ok(executions)
@@ -304,6 +498,8 @@ pub async fn load_message_tool_executions(
 
 /// Loads a single tool execution by ID.
 ///
+/// Also loads the payloads left out of summary pages.
+///
 /// # Arguments
 /// * `execution_id` - The tool execution UUID
 ///
@@ -322,21 +518,24 @@ pub async fn get_tool_execution(
         format!("Invalid execution_id: {}", e)
     })?;
 
-    let query = format!(
-        r#"SELECT
+    let query = r#"SELECT
             meta::id(id) AS id,
             workflow_id, message_id, agent_id, tool_type, tool_name,
             server_name, input_params, output_result, success,
-            error_message, duration_ms, iteration, created_at
+            error_message, duration_ms, iteration,
+            previous_execution_id, result_diff, created_at
         FROM tool_execution
-        WHERE meta::id(id) = '{}'"#,
-        validated_id
-    );
+        WHERE meta::id(id) = $id"#;
+    let params = vec![("id".to_string(), serde_json::json!(validated_id))];
 
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to get tool execution");
-        format!("Failed to get tool execution: {}", e)
-    })?;
+    let json_results = state
+        .db
+        .query_json_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to get tool execution");
+            format!("Failed to get tool execution: {}", e)
+        })?;
 
     let execution: Option<ToolExecution> = json_results
         .into_iter()
@@ -348,7 +547,10 @@ pub async fn get_tool_execution(
             format!("Failed to deserialize tool execution: {}", e)
         })?;
 
-    execution.ok_or_else(|| format!("Tool execution not found: {}", execution_id))
+    let mut execution =
+        execution.ok_or_else(|| format!("Tool execution not found: {}", execution_id))?;
+    WORKFLOW_VAULT.reveal_tool_execution(&mut execution);
+    Ok(execution)
 }
 
 /// Deletes a single tool execution by ID.
@@ -372,10 +574,9 @@ pub async fn delete_tool_execution(
         format!("Invalid execution ID: {}", e)
     })?;
 
-    // Use execute() with DELETE query to avoid SurrealDB SDK serialization issues
     state
         .db
-        .execute(&format!("DELETE tool_execution:`{}`", validated_id))
+        .delete(&format!("tool_execution:{}", validated_id))
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to delete tool execution");
@@ -408,12 +609,17 @@ pub async fn clear_workflow_tool_executions(
     })?;
 
     // First count existing executions
-    let count_query = format!(
-        "SELECT count() FROM tool_execution WHERE workflow_id = '{}' GROUP ALL",
-        validated_workflow_id
-    );
-    let count_result: Vec<serde_json::Value> =
-        state.db.query(&count_query).await.unwrap_or_default();
+    let count_query =
+        "SELECT count() FROM tool_execution WHERE workflow_id = $workflow_id GROUP ALL";
+    let params = vec![(
+        "workflow_id".to_string(),
+        serde_json::json!(validated_workflow_id),
+    )];
+    let count_result: Vec<serde_json::Value> = state
+        .db
+        .query_with_params(count_query, params)
+        .await
+        .unwrap_or_default();
 
     let count = count_result
         .first()
@@ -424,10 +630,13 @@ pub async fn clear_workflow_tool_executions(
     // Delete all executions for the workflow
     state
         .db
-        .execute(&format!(
-            "DELETE tool_execution WHERE workflow_id = '{}'",
-            validated_workflow_id
-        ))
+        .execute_with_params(
+            "DELETE tool_execution WHERE workflow_id = $workflow_id",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to clear workflow tool executions");
@@ -469,4 +678,63 @@ mod tests {
         // 50KB should be enough for most tool params
         assert_eq!(cmd_const::MAX_PARAMS_SIZE, 50 * 1024);
     }
+
+    #[tokio::test]
+    async fn test_tool_execution_pages() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let workflow_id = Uuid::new_v4().to_string();
+        for i in 0..3 {
+            db.create(
+                "tool_execution",
+                &Uuid::new_v4().to_string(),
+                ToolExecutionCreate::local(
+                    workflow_id.clone(),
+                    "m1".to_string(),
+                    "agent".to_string(),
+                    "MemoryTool".to_string(),
+                    serde_json::json!({"step": i}),
+                    serde_json::json!({"result": "x".repeat(100)}),
+                    true,
+                    None,
+                    1,
+                    i,
+                ),
+            )
+            .await
+            .unwrap();
+        }
+
+        let first = query_tool_execution_page(&db, &workflow_id, None, Some(2), false)
+            .await
+            .unwrap();
+        assert_eq!(first.executions.len(), 2);
+        assert!(first.has_more);
+        assert_eq!(
+            first.executions[0].input_params,
+            serde_json::json!({"step": 0})
+        );
+        let cursor = first.next_cursor.unwrap();
+
+        let second = query_tool_execution_page(&db, &workflow_id, Some(&cursor), Some(2), true)
+            .await
+            .unwrap();
+        assert_eq!(second.executions.len(), 1);
+        assert!(!second.has_more);
+        assert!(second.next_cursor.is_none());
+        assert!(second.summary);
+        assert_eq!(second.executions[0].iteration, 2);
+        assert!(second.executions[0].input_params.is_null());
+        assert!(second.executions[0].output_result.is_null());
+
+        assert!(
+            query_tool_execution_page(&db, &workflow_id, Some("bogus"), None, false)
+                .await
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,331 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow transcript export.
+//!
+//! Renders a workflow to Markdown or HTML (see [`Transcript`]). PDF files are
+//! printed from the HTML page by a headless Chromium-based browser found in
+//! the `PATH`; the export fails with an explicit error when none is installed.
+
+use crate::{
+    db::DBClient,
+    models::{
+        message::Message,
+        sub_agent::SubAgentExecution,
+        thinking_step::ThinkingStep,
+        tool_execution::ToolExecution,
+        transcript::{Transcript, TranscriptFormat, TranscriptOptions},
+    },
+    security::{workflow_vault::WORKFLOW_VAULT, Validator},
+    tools::constants::transcript as transcript_const,
+    AppState,
+};
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tauri::State;
+use tracing::{info, instrument, warn};
+
+/// Loads the rows of `query` (bound to `$workflow_id`) as `T`.
+pub(crate) async fn load_rows<T: DeserializeOwned>(
+    db: &DBClient,
+    query: &str,
+    workflow_id: &str,
+    what: &str,
+) -> Result<Vec<T>, String> {
+    db.query_json_with_params(query, vec![("workflow_id".to_string(), json!(workflow_id))])
+        .await
+        .map_err(|e| format!("Failed to load {}: {}", what, e))?
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to deserialize {}: {}", what, e))
+}
+
+/// Loads everything a transcript shows, decrypted for sensitive workflows.
+///
+/// # Errors
+/// The workflow does not exist, or is sensitive and locked.
+pub(crate) async fn load_transcript(
+    db: &DBClient,
+    workflow_id: &str,
+) -> Result<Transcript, String> {
+    let workflow = db
+        .query_json_with_params(
+            "SELECT name, agent_id, (sensitive ?? false) AS sensitive \
+             FROM type::thing('workflow', $workflow_id)",
+            vec![("workflow_id".to_string(), json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Workflow '{}' not found", workflow_id))?;
+
+    if workflow["sensitive"].as_bool() == Some(true) && !WORKFLOW_VAULT.is_unlocked(workflow_id) {
+        return Err("This workflow is sensitive and locked, open it first".to_string());
+    }
+
+    let mut messages: Vec<Message> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, role, content, tokens, model, provider, timestamp \
+         FROM message WHERE workflow_id = $workflow_id ORDER BY timestamp ASC",
+        workflow_id,
+        "messages",
+    )
+    .await?;
+    let mut tool_executions: Vec<ToolExecution> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, tool_type, tool_name, \
+         server_name, input_params, output_result, success, error_message, duration_ms, \
+         iteration, created_at \
+         FROM tool_execution WHERE workflow_id = $workflow_id ORDER BY created_at ASC",
+        workflow_id,
+        "tool executions",
+    )
+    .await?;
+    let mut thinking_steps: Vec<ThinkingStep> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, message_id, agent_id, step_number, content, \
+         duration_ms, tokens, created_at \
+         FROM thinking_step WHERE workflow_id = $workflow_id \
+         ORDER BY created_at ASC, step_number ASC",
+        workflow_id,
+        "thinking steps",
+    )
+    .await?;
+    let sub_agents: Vec<SubAgentExecution> = load_rows(
+        db,
+        "SELECT meta::id(id) AS id, workflow_id, parent_agent_id, sub_agent_id, sub_agent_name, \
+         task_description, status, duration_ms, tokens_input, tokens_output, result_summary, \
+         error_message, created_at, completed_at \
+         FROM sub_agent_execution WHERE workflow_id = $workflow_id ORDER BY created_at ASC",
+        workflow_id,
+        "sub-agent executions",
+    )
+    .await?;
+
+    messages
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    tool_executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+    thinking_steps
+        .iter_mut()
+        .for_each(|s| WORKFLOW_VAULT.reveal_thinking_step(s));
+
+    Ok(Transcript {
+        workflow_name: workflow["name"].as_str().unwrap_or_default().to_string(),
+        agent_id: workflow["agent_id"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        exported_at: Utc::now(),
+        messages,
+        tool_executions,
+        thinking_steps,
+        sub_agents,
+    })
+}
+
+/// Returns the first headless-capable browser of the `PATH`.
+fn find_pdf_renderer() -> Option<PathBuf> {
+    let paths = std::env::var_os("PATH")?;
+    transcript_const::PDF_RENDERERS.iter().find_map(|name| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Prints an HTML page to a PDF file with a headless browser (blocking).
+fn print_pdf(html: &str, output: &Path) -> Result<(), String> {
+    let renderer = find_pdf_renderer().ok_or_else(|| {
+        format!(
+            "PDF export requires a Chromium-based browser in the PATH ({})",
+            transcript_const::PDF_RENDERERS.join(", ")
+        )
+    })?;
+
+    // Readable by the current user only, deleted when dropped
+    let mut source = tempfile::Builder::new()
+        .prefix("transcript-")
+        .suffix(".html")
+        .tempfile()
+        .map_err(|e| format!("Failed to create temporary file: {}", e))?;
+    source
+        .write_all(html.as_bytes())
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    run_renderer(&renderer, source.path(), output)
+}
+
+fn run_renderer(renderer: &Path, source: &Path, output: &Path) -> Result<(), String> {
+    let mut child = Command::new(renderer)
+        .arg("--headless")
+        .arg("--disable-gpu")
+        .arg("--no-pdf-header-footer")
+        .arg(format!("--print-to-pdf={}", output.display()))
+        .arg(format!("file://{}", source.display()))
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start {}: {}", renderer.display(), e))?;
+
+    let deadline = Instant::now() + Duration::from_secs(transcript_const::PDF_RENDER_TIMEOUT_SECS);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(100)),
+            Ok(None) => {
+                let _ = child.kill();
+                return Err(format!(
+                    "PDF rendering timed out after {}s",
+                    transcript_const::PDF_RENDER_TIMEOUT_SECS
+                ));
+            }
+            Err(e) => return Err(format!("PDF rendering failed: {}", e)),
+        }
+    };
+
+    if !status.success() || !output.is_file() {
+        return Err(format!("PDF rendering failed ({})", status));
+    }
+    Ok(())
+}
+
+/// Exports the transcript of a workflow to a file.
+///
+/// Messages are listed in order; each assistant message is preceded by its
+/// tool calls and sub-agent reports, and optionally its thinking steps and
+/// tool payloads.
+///
+/// # Arguments
+/// * `workflow_id` - Workflow to export
+/// * `path` - Full path of the output file
+/// * `options` - Format and optional sections
+///
+/// # Returns
+/// Number of bytes written
+#[tauri::command]
+#[instrument(name = "export_workflow_transcript", skip(state, options), fields(workflow_id = %workflow_id, format = ?options.format))]
+pub async fn export_workflow_transcript(
+    workflow_id: String,
+    path: String,
+    options: TranscriptOptions,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let transcript = load_transcript(&state.db, &workflow_id).await?;
+    let output = PathBuf::from(&path);
+
+    match options.format {
+        TranscriptFormat::Markdown => std::fs::write(&output, transcript.to_markdown(&options))
+            .map_err(|e| format!("Failed to write file: {}", e))?,
+        TranscriptFormat::Html => std::fs::write(&output, transcript.to_html(&options))
+            .map_err(|e| format!("Failed to write file: {}", e))?,
+        TranscriptFormat::Pdf => {
+            let html = transcript.to_html(&options);
+            let target = output.clone();
+            tokio::task::spawn_blocking(move || print_pdf(&html, &target))
+                .await
+                .map_err(|e| format!("PDF rendering failed: {}", e))??
+        }
+    }
+
+    let size = std::fs::metadata(&output)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    info!(
+        path = %output.display(),
+        messages = transcript.messages.len(),
+        size_bytes = size,
+        "Workflow transcript exported"
+    );
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_load_transcript() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("transcript_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        assert!(load_transcript(&db, "wf1").await.is_err());
+
+        db.create(
+            "workflow",
+            "wf1",
+            json!({ "id": "wf1", "name": "Release", "agent_id": "agent_a", "status": "idle" }),
+        )
+        .await
+        .unwrap();
+        for (id, role, content) in [("m1", "user", "Summarize"), ("m2", "assistant", "Done")] {
+            db.create(
+                "message",
+                id,
+                json!({ "id": id, "workflow_id": "wf1", "role": role, "content": content, "tokens": 0 }),
+            )
+            .await
+            .unwrap();
+        }
+        db.create(
+            "tool_execution",
+            "t1",
+            json!({
+                "id": "t1",
+                "workflow_id": "wf1",
+                "message_id": "m2",
+                "agent_id": "agent_a",
+                "tool_type": "local",
+                "tool_name": "MemoryTool",
+                "input_params": "{\"operation\":\"search\"}",
+                "success": true,
+                "duration_ms": 5,
+                "iteration": 0
+            }),
+        )
+        .await
+        .unwrap();
+
+        let transcript = load_transcript(&db, "wf1").await.unwrap();
+        assert_eq!(transcript.workflow_name, "Release");
+        assert_eq!(transcript.messages.len(), 2);
+        assert_eq!(
+            transcript.tool_executions[0].input_params["operation"],
+            "search"
+        );
+
+        let markdown = transcript.to_markdown(&TranscriptOptions {
+            include_tool_payloads: true,
+            ..Default::default()
+        });
+        assert!(markdown.contains("- MemoryTool (local) · success · 5 ms"));
+        assert!(markdown.contains("\"operation\": \"search\""));
+    }
+}
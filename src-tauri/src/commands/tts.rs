@@ -0,0 +1,479 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text-to-speech commands.
+//!
+//! `synthesize_speech` speaks a reply with the backend configured in the
+//! `settings:tts` record and the voice of its workflow (`workflow.voice`).
+//! Audio is emitted as `speech_audio` events while it is produced; the
+//! frontend plays each segment as soon as its `segment_end` event arrives.
+
+use crate::commands::speech::load_speech_settings;
+use crate::commands::SecureKeyStore;
+use crate::db::{queries, DBClient};
+use crate::models::streaming::events;
+use crate::security::Validator;
+use crate::speech::read_aloud;
+use crate::speech::tts::{
+    self, SpeechAudioChunk, SpeechAudioKind, SpeechSynthesis, TtsBackend, TtsConfig, VoiceSettings,
+};
+use crate::state::AppState;
+use crate::tools::constants::speech as speech_const;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{Emitter, State, Window};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Keystore entry of the OpenAI API key (shared with the provider settings)
+const OPENAI_KEY_NAME: &str = "OpenAI";
+
+/// Storage key of the text-to-speech settings
+const TTS_SETTINGS_KEY: &str = "settings:tts";
+
+/// Cancellation tokens of the running syntheses, by request ID
+static SYNTHESES: Lazy<Mutex<HashMap<String, CancellationToken>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Loads the text-to-speech settings, defaults when unset.
+pub async fn load_tts_settings(db: &DBClient) -> TtsConfig {
+    match queries::settings::load_config(db, TTS_SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No TTS settings found, using defaults");
+            TtsConfig::default()
+        }
+    }
+}
+
+/// Loads the voice settings of a workflow (defaults when unset).
+async fn load_workflow_voice(db: &DBClient, workflow_id: &str) -> Result<VoiceSettings, String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT voice FROM workflow WHERE meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow voice: {}", e))?;
+    let row = rows
+        .first()
+        .ok_or_else(|| format!("Workflow {} not found", workflow_id))?;
+    Ok(row
+        .get("voice")
+        .and_then(|voice| serde_json::from_value(voice.clone()).ok())
+        .unwrap_or_default())
+}
+
+/// The OpenAI key is only sent to the OpenAI API, never to compatible servers
+fn sends_openai_key(config: &TtsConfig) -> bool {
+    let host = |url: &str| {
+        reqwest::Url::parse(url.trim())
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+    };
+    config.backend == TtsBackend::OpenAi
+        && host(&config.endpoint).is_some()
+        && host(&config.endpoint) == host(speech_const::OPENAI_TTS_ENDPOINT)
+}
+
+/// Speaks a text, streaming the audio as `speech_audio` events.
+///
+/// The text is converted to its read-aloud form (Markdown and code removed),
+/// split into segments of a few sentences and synthesized segment by segment.
+///
+/// # Arguments
+/// * `request_id` - ID chosen by the frontend (UUID), carried by the events
+/// * `text` - Text to speak (Markdown accepted)
+/// * `workflow_id` - Workflow whose voice settings apply (optional)
+///
+/// # Returns
+/// Segments and bytes synthesized, once all audio was emitted
+#[tauri::command]
+#[instrument(
+    name = "synthesize_speech",
+    skip(window, text, state, keystore),
+    fields(request_id = %request_id, text_len = text.len())
+)]
+pub async fn synthesize_speech(
+    window: Window,
+    request_id: String,
+    text: String,
+    workflow_id: Option<String>,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<SpeechSynthesis, String> {
+    let request_id = Validator::validate_uuid(&request_id).map_err(|e| {
+        warn!(error = %e, "Invalid request_id");
+        format!("Invalid request_id: {}", e)
+    })?;
+    if text.trim().is_empty() {
+        return Err("Text to speak cannot be empty".to_string());
+    }
+    if text.len() > speech_const::MAX_TTS_TEXT_LEN {
+        return Err(format!(
+            "Text exceeds maximum length of {} characters",
+            speech_const::MAX_TTS_TEXT_LEN
+        ));
+    }
+
+    let mut config = load_tts_settings(&state.db).await;
+    if let Some(workflow_id) = workflow_id {
+        let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+            warn!(error = %e, "Invalid workflow_id");
+            format!("Invalid workflow_id: {}", e)
+        })?;
+        config = config.with_voice(&load_workflow_voice(&state.db, &workflow_id).await?);
+    }
+    let api_key = if sends_openai_key(&config) {
+        Some(keystore.get_key(OPENAI_KEY_NAME).ok_or_else(|| {
+            warn!("OpenAI API key not configured");
+            "OpenAI API key not configured: add it in the provider settings".to_string()
+        })?)
+    } else {
+        None
+    };
+
+    let language = load_speech_settings(&state.db).await.language;
+    let spoken = read_aloud::prepare(&text, language.as_deref()).text;
+    let segments = tts::split_segments(&spoken, speech_const::TTS_SEGMENT_CHARS);
+
+    let cancellation = CancellationToken::new();
+    if let Ok(mut syntheses) = SYNTHESES.lock() {
+        syntheses.insert(request_id.clone(), cancellation.clone());
+    }
+    let mime_type = config.mime_type().to_string();
+    let emit =
+        |kind: SpeechAudioKind, segment: u32, data: Option<String>, error: Option<String>| {
+            let chunk = SpeechAudioChunk {
+                request_id: request_id.clone(),
+                kind,
+                segment,
+                mime_type: mime_type.clone(),
+                data,
+                error,
+            };
+            if let Err(e) = window.emit(events::SPEECH_AUDIO, &chunk) {
+                warn!(error = %e, "Failed to emit speech audio");
+            }
+        };
+
+    let client = state.llm_manager.http_client();
+    let mut bytes = 0;
+    let mut result = Ok(());
+    for (index, segment) in segments.iter().enumerate() {
+        if cancellation.is_cancelled() {
+            break;
+        }
+        let index = index as u32;
+        let synthesized = tts::synthesize_segment(
+            client,
+            &config,
+            api_key.as_deref(),
+            segment,
+            &cancellation,
+            |audio| {
+                emit(
+                    SpeechAudioKind::Audio,
+                    index,
+                    Some(STANDARD.encode(audio)),
+                    None,
+                )
+            },
+        )
+        .await;
+        match synthesized {
+            // A segment interrupted by `stop_speech` is not played
+            Ok(_) if cancellation.is_cancelled() => break,
+            Ok(segment_bytes) => {
+                bytes += segment_bytes;
+                emit(SpeechAudioKind::SegmentEnd, index, None, None);
+            }
+            Err(e) => {
+                emit(SpeechAudioKind::Error, index, None, Some(e.clone()));
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    if let Ok(mut syntheses) = SYNTHESES.lock() {
+        syntheses.remove(&request_id);
+    }
+    let stopped = cancellation.is_cancelled();
+    emit(SpeechAudioKind::Done, segments.len() as u32, None, None);
+
+    result.map_err(|e| {
+        error!(error = %e, endpoint = %config.endpoint, "Speech synthesis failed");
+        e
+    })?;
+    info!(
+        segments = segments.len(),
+        bytes, stopped, "Speech synthesized"
+    );
+    Ok(SpeechSynthesis {
+        request_id,
+        segments: segments.len() as u32,
+        bytes,
+        mime_type,
+        stopped,
+    })
+}
+
+/// Stops a running synthesis.
+///
+/// # Returns
+/// False if no synthesis with this ID is running
+#[tauri::command]
+#[instrument(name = "stop_speech", fields(request_id = %request_id))]
+pub async fn stop_speech(request_id: String) -> Result<bool, String> {
+    let cancellation = SYNTHESES
+        .lock()
+        .map_err(|e| e.to_string())?
+        .remove(&request_id);
+    match cancellation {
+        Some(cancellation) => {
+            cancellation.cancel();
+            info!("Speech synthesis stopped");
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Gets the text-to-speech settings
+#[tauri::command]
+#[instrument(name = "get_tts_settings", skip(state))]
+pub async fn get_tts_settings(state: State<'_, AppState>) -> Result<TtsConfig, String> {
+    Ok(load_tts_settings(&state.db).await)
+}
+
+/// Updates the text-to-speech settings.
+///
+/// # Arguments
+/// * `settings` - Backend, endpoint, model, default voice and speaking rate
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_tts_settings", skip(state, settings))]
+pub async fn update_tts_settings(
+    settings: TtsConfig,
+    state: State<'_, AppState>,
+) -> Result<TtsConfig, String> {
+    info!(backend = ?settings.backend, "Updating TTS settings");
+
+    let settings = normalize_tts_settings(settings).map_err(|e| {
+        warn!(error = %e, "Invalid TTS settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize TTS settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, TTS_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save TTS settings");
+            format!("Failed to save TTS settings: {}", e)
+        })?;
+
+    info!("TTS settings updated successfully");
+    Ok(settings)
+}
+
+/// Gets the voice settings of a workflow
+#[tauri::command]
+#[instrument(name = "get_workflow_voice", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn get_workflow_voice(
+    workflow_id: String,
+    state: State<'_, AppState>,
+) -> Result<VoiceSettings, String> {
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+    load_workflow_voice(&state.db, &validated_id).await
+}
+
+/// Sets the voice of a workflow, overriding the default voice and speaking
+/// rate. `None` (or empty settings) restores the defaults.
+#[tauri::command]
+#[instrument(name = "set_workflow_voice", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn set_workflow_voice(
+    workflow_id: String,
+    voice: Option<VoiceSettings>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let voice = voice
+        .map(|voice| VoiceSettings {
+            voice: voice
+                .voice
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            speed: voice.speed,
+        })
+        .filter(|voice| *voice != VoiceSettings::default());
+    if let Some(voice) = &voice {
+        tts::validate_voice(voice.voice.as_deref(), voice.speed).map_err(|e| {
+            warn!(error = %e, "Invalid voice settings");
+            e
+        })?;
+    }
+
+    let mut params = vec![("id".to_string(), serde_json::json!(validated_id))];
+    let query = match &voice {
+        Some(voice) => {
+            params.push(("voice".to_string(), serde_json::json!(voice)));
+            "UPDATE type::thing('workflow', $id) SET voice = $voice"
+        }
+        None => "UPDATE type::thing('workflow', $id) SET voice = NONE",
+    };
+    state
+        .db
+        .execute_with_params(query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to set workflow voice");
+            format!("Failed to set workflow voice: {}", e)
+        })?;
+
+    info!(voice = ?voice, "Workflow voice updated");
+    Ok(())
+}
+
+/// Validates and normalizes text-to-speech settings
+fn normalize_tts_settings(settings: TtsConfig) -> Result<TtsConfig, String> {
+    let endpoint = settings.endpoint.trim().trim_end_matches('/').to_string();
+    let url = reqwest::Url::parse(&endpoint).map_err(|e| format!("Invalid TTS endpoint: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("TTS endpoint must be an http(s) URL".to_string());
+    }
+
+    let trimmed = |value: Option<String>| {
+        value
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let model = trimmed(settings.model);
+    if let Some(model) = &model {
+        crate::commands::models::validate_model_id(model)?;
+    }
+    let voice = trimmed(settings.voice);
+    tts::validate_voice(voice.as_deref(), settings.speed)?;
+
+    Ok(TtsConfig {
+        backend: settings.backend,
+        endpoint,
+        model,
+        voice,
+        speed: settings.speed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tts_settings() {
+        let settings = normalize_tts_settings(TtsConfig {
+            backend: TtsBackend::OpenAi,
+            endpoint: " https://api.openai.com/ ".to_string(),
+            model: Some(" ".to_string()),
+            voice: Some(" nova ".to_string()),
+            speed: Some(1.25),
+        })
+        .unwrap();
+        assert_eq!(settings.endpoint, "https://api.openai.com");
+        assert_eq!(settings.model, None);
+        assert_eq!(settings.voice.as_deref(), Some("nova"));
+
+        for endpoint in ["localhost:5000", "ftp://host/tts", ""] {
+            let settings = TtsConfig {
+                endpoint: endpoint.to_string(),
+                ..TtsConfig::default()
+            };
+            assert!(normalize_tts_settings(settings).is_err(), "{}", endpoint);
+        }
+        let settings = TtsConfig {
+            speed: Some(10.0),
+            ..TtsConfig::default()
+        };
+        assert!(normalize_tts_settings(settings).is_err());
+    }
+
+    #[test]
+    fn test_sends_openai_key() {
+        let openai = TtsConfig {
+            backend: TtsBackend::OpenAi,
+            endpoint: speech_const::OPENAI_TTS_ENDPOINT.to_string(),
+            ..TtsConfig::default()
+        };
+        assert!(sends_openai_key(&openai));
+
+        // Compatible servers and piper never receive the key
+        let compatible = TtsConfig {
+            endpoint: "http://127.0.0.1:8880".to_string(),
+            ..openai.clone()
+        };
+        assert!(!sends_openai_key(&compatible));
+        let lookalike = TtsConfig {
+            endpoint: "https://openai.com.example.org".to_string(),
+            ..openai
+        };
+        assert!(!sends_openai_key(&lookalike));
+        assert!(!sends_openai_key(&TtsConfig::default()));
+    }
+
+    #[tokio::test]
+    async fn test_workflow_voice_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db_path = temp_dir.path().join("tts_voice_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+        db.execute("CREATE workflow:wf1 CONTENT { name: 'Voice', agent_id: 'a', status: 'idle' }")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            load_workflow_voice(&db, "wf1").await.unwrap(),
+            VoiceSettings::default()
+        );
+
+        let voice = VoiceSettings {
+            voice: Some("fr_FR-siwis-medium".to_string()),
+            speed: Some(1.5),
+        };
+        db.execute_with_params(
+            "UPDATE workflow:wf1 SET voice = $voice",
+            vec![("voice".to_string(), serde_json::json!(voice))],
+        )
+        .await
+        .unwrap();
+        assert_eq!(load_workflow_voice(&db, "wf1").await.unwrap(), voice);
+
+        assert!(load_workflow_voice(&db, "missing").await.is_err());
+    }
+}
@@ -0,0 +1,486 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Team usage statistics commands.
+//!
+//! The team usage report is generated entirely locally. Usage is grouped in
+//! weekly buckets (per provider/model for LLM usage, per tool for tool
+//! calls); within a bucket, the contribution of each conversation is clipped
+//! and the sums are noised with Laplace noise calibrated to the clip bounds
+//! (differential privacy-style). Buckets with too few conversations are left
+//! out and no conversation IDs, names or contents are exported.
+
+use crate::{
+    db::DBClient,
+    llm::pricing::calculate_cost,
+    models::usage_stats::{TeamUsageReport, ToolUsageBucket, UsageBucket},
+    tools::constants::usage_stats as usage_const,
+    AppState,
+};
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+use tracing::{info, instrument};
+
+/// Assistant message usage
+#[derive(Debug, Deserialize)]
+struct MessageUsageRow {
+    workflow_id: String,
+    provider: Option<String>,
+    model: Option<String>,
+    tokens_input: Option<u64>,
+    tokens_output: Option<u64>,
+    cost_usd: Option<f64>,
+    timestamp: DateTime<Utc>,
+}
+
+/// Tool call
+#[derive(Debug, Deserialize)]
+struct ToolCallRow {
+    workflow_id: String,
+    tool_type: String,
+    tool_name: String,
+    success: bool,
+    created_at: DateTime<Utc>,
+}
+
+/// Model pricing
+#[derive(Debug, Deserialize)]
+struct ModelPriceRow {
+    provider: String,
+    api_name: String,
+    input_price_per_mtok: f64,
+    output_price_per_mtok: f64,
+}
+
+/// Contributions of the conversations of each bucket (one value per metric)
+type Contributions<K> = BTreeMap<K, HashMap<String, Vec<f64>>>;
+
+/// Noised bucket: key, conversation count and metric sums
+type NoisedBucket<K> = (K, f64, Vec<f64>);
+
+/// Returns the Monday of the week of a timestamp (UTC).
+fn week_of(timestamp: DateTime<Utc>) -> NaiveDate {
+    let date = timestamp.date_naive();
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Samples Laplace noise of the given scale.
+fn laplace_noise(scale: f64) -> f64 {
+    // Uniform in [-0.5, 0.5) from 53 random bits
+    let u = (OsRng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+    let tail = (1.0 - 2.0 * u.abs()).max(f64::MIN_POSITIVE);
+    -scale * u.signum() * tail.ln()
+}
+
+/// Adds the contribution of a conversation to a bucket.
+fn contribute<K: Ord>(buckets: &mut Contributions<K>, key: K, workflow_id: &str, values: &[f64]) {
+    let totals = buckets
+        .entry(key)
+        .or_default()
+        .entry(workflow_id.to_string())
+        .or_insert_with(|| vec![0.0; values.len()]);
+    for (total, value) in totals.iter_mut().zip(values) {
+        *total += value;
+    }
+}
+
+/// Sums the clipped contributions of each bucket and noises the sums.
+///
+/// The budget is split evenly between the conversation count (sensitivity 1)
+/// and the metrics (sensitivity: their clip bound). Buckets whose noised
+/// conversation count is below `MIN_BUCKET_CONVERSATIONS` are left out.
+///
+/// # Returns
+/// The kept buckets and the number of suppressed buckets
+fn noised_sums<K: Ord>(
+    buckets: Contributions<K>,
+    clips: &[f64],
+    epsilon: f64,
+    noise: &mut impl FnMut(f64) -> f64,
+) -> (Vec<NoisedBucket<K>>, usize) {
+    let metric_epsilon = epsilon / (clips.len() + 1) as f64;
+    let mut kept = Vec::new();
+    let mut suppressed = 0;
+    for (key, conversations) in buckets {
+        let count = conversations.len() as f64 + noise(1.0 / metric_epsilon);
+        if count < usage_const::MIN_BUCKET_CONVERSATIONS {
+            suppressed += 1;
+            continue;
+        }
+        let sums = clips
+            .iter()
+            .enumerate()
+            .map(|(i, clip)| {
+                let sum: f64 = conversations.values().map(|v| v[i].min(*clip)).sum();
+                (sum + noise(clip / metric_epsilon)).max(0.0)
+            })
+            .collect();
+        kept.push((key, count, sums));
+    }
+    (kept, suppressed)
+}
+
+/// Rounds a noised count.
+fn round_count(value: f64) -> u64 {
+    value.max(0.0).round() as u64
+}
+
+/// Aggregates the LLM usage into noised weekly buckets.
+fn aggregate_usage(
+    rows: &[MessageUsageRow],
+    prices: &HashMap<(String, String), (f64, f64)>,
+    epsilon: f64,
+    noise: &mut impl FnMut(f64) -> f64,
+) -> (Vec<UsageBucket>, usize) {
+    let mut buckets: Contributions<(NaiveDate, String, String)> = BTreeMap::new();
+    for row in rows {
+        let provider = row
+            .provider
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let model = row.model.clone().unwrap_or_else(|| "unknown".to_string());
+        let tokens_input = row.tokens_input.unwrap_or(0);
+        let tokens_output = row.tokens_output.unwrap_or(0);
+        let spend = row.cost_usd.unwrap_or_else(|| {
+            prices
+                .get(&(provider.to_lowercase(), model.clone()))
+                .map(|(input, output)| {
+                    calculate_cost(
+                        tokens_input as usize,
+                        tokens_output as usize,
+                        *input,
+                        *output,
+                    )
+                })
+                .unwrap_or(0.0)
+        });
+        contribute(
+            &mut buckets,
+            (week_of(row.timestamp), provider, model),
+            &row.workflow_id,
+            &[1.0, (tokens_input + tokens_output) as f64, spend],
+        );
+    }
+
+    let clips = [
+        usage_const::MESSAGE_CLIP,
+        usage_const::TOKEN_CLIP,
+        usage_const::SPEND_CLIP_USD,
+    ];
+    let (kept, suppressed) = noised_sums(buckets, &clips, epsilon, noise);
+    let usage = kept
+        .into_iter()
+        .map(
+            |((week, provider, model), conversations, sums)| UsageBucket {
+                week,
+                provider,
+                model,
+                conversations: round_count(conversations),
+                messages: round_count(sums[0]),
+                tokens: round_count(
+                    (sums[1] / usage_const::TOKEN_ROUNDING).round() * usage_const::TOKEN_ROUNDING,
+                ),
+                spend_usd: (sums[2] * 100.0).round() / 100.0,
+            },
+        )
+        .collect();
+    (usage, suppressed)
+}
+
+/// Aggregates the tool calls into noised weekly buckets.
+fn aggregate_tools(
+    rows: &[ToolCallRow],
+    epsilon: f64,
+    noise: &mut impl FnMut(f64) -> f64,
+) -> (Vec<ToolUsageBucket>, usize) {
+    let mut buckets: Contributions<(NaiveDate, String, String)> = BTreeMap::new();
+    for row in rows {
+        contribute(
+            &mut buckets,
+            (
+                week_of(row.created_at),
+                row.tool_type.clone(),
+                row.tool_name.clone(),
+            ),
+            &row.workflow_id,
+            &[1.0, if row.success { 0.0 } else { 1.0 }],
+        );
+    }
+
+    let clips = [usage_const::TOOL_CALL_CLIP, usage_const::TOOL_CALL_CLIP];
+    let (kept, suppressed) = noised_sums(buckets, &clips, epsilon, noise);
+    let tools = kept
+        .into_iter()
+        .map(
+            |((week, tool_type, tool_name), conversations, sums)| ToolUsageBucket {
+                week,
+                tool_type,
+                tool_name,
+                conversations: round_count(conversations),
+                calls: round_count(sums[0]),
+                // Noised independently: never report more failures than calls
+                failures: round_count(sums[1].min(sums[0])),
+            },
+        )
+        .collect();
+    (tools, suppressed)
+}
+
+/// Loads the usage rows since a date.
+async fn load_usage(
+    db: &DBClient,
+    since: DateTime<Utc>,
+) -> Result<(Vec<MessageUsageRow>, Vec<ToolCallRow>), String> {
+    let since = vec![("since".to_string(), serde_json::json!(since.to_rfc3339()))];
+    let messages = db
+        .query_json_with_params(
+            "SELECT workflow_id, provider, model, tokens_input, tokens_output, cost_usd, \
+             timestamp FROM message WHERE role = 'assistant' AND timestamp >= <datetime>$since",
+            since.clone(),
+        )
+        .await
+        .map_err(|e| format!("Failed to load message usage: {}", e))?;
+    let tools = db
+        .query_json_with_params(
+            "SELECT workflow_id, tool_type, tool_name, success, created_at \
+             FROM tool_execution WHERE created_at >= <datetime>$since",
+            since,
+        )
+        .await
+        .map_err(|e| format!("Failed to load tool usage: {}", e))?;
+
+    Ok((
+        messages
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row).ok())
+            .collect(),
+        tools
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row).ok())
+            .collect(),
+    ))
+}
+
+/// Loads the model prices, keyed by (provider, api_name).
+async fn load_prices(db: &DBClient) -> Result<HashMap<(String, String), (f64, f64)>, String> {
+    let rows = db
+        .query_json(
+            "SELECT provider, api_name, (input_price_per_mtok ?? 0.0) AS input_price_per_mtok, \
+             (output_price_per_mtok ?? 0.0) AS output_price_per_mtok FROM llm_model",
+        )
+        .await
+        .map_err(|e| format!("Failed to load model prices: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| serde_json::from_value::<ModelPriceRow>(row).ok())
+        .map(|row| {
+            (
+                (row.provider.to_lowercase(), row.api_name),
+                (row.input_price_per_mtok, row.output_price_per_mtok),
+            )
+        })
+        .collect())
+}
+
+/// Generates the team usage report.
+///
+/// # Arguments
+/// * `days` - Number of days covered (default 30, max 365)
+/// * `epsilon` - Privacy budget of each bucket (default 1.0, between 0.1 and
+///   10; lower is noisier)
+///
+/// # Returns
+/// The aggregated report, to be saved and shared by the frontend
+#[tauri::command]
+#[instrument(name = "export_team_usage", skip(state))]
+pub async fn export_team_usage(
+    days: Option<u32>,
+    epsilon: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<TeamUsageReport, String> {
+    let days = days.unwrap_or(usage_const::DEFAULT_DAYS);
+    if days == 0 || days > usage_const::MAX_DAYS {
+        return Err(format!(
+            "Days must be between 1 and {}",
+            usage_const::MAX_DAYS
+        ));
+    }
+    let epsilon = epsilon.unwrap_or(usage_const::DEFAULT_EPSILON);
+    if !(usage_const::MIN_EPSILON..=usage_const::MAX_EPSILON).contains(&epsilon) {
+        return Err(format!(
+            "Epsilon must be between {} and {}",
+            usage_const::MIN_EPSILON,
+            usage_const::MAX_EPSILON
+        ));
+    }
+
+    let now = Utc::now();
+    let since = now - Duration::days(days as i64);
+    let (messages, tool_calls) = load_usage(&state.db, since).await?;
+    let prices = load_prices(&state.db).await?;
+
+    let (usage, usage_suppressed) =
+        aggregate_usage(&messages, &prices, epsilon, &mut laplace_noise);
+    let (tools, tools_suppressed) = aggregate_tools(&tool_calls, epsilon, &mut laplace_noise);
+
+    info!(
+        usage_buckets = usage.len(),
+        tool_buckets = tools.len(),
+        suppressed = usage_suppressed + tools_suppressed,
+        "Team usage report generated"
+    );
+    Ok(TeamUsageReport {
+        format_version: usage_const::FORMAT_VERSION,
+        generated_at: now,
+        period_start: since.date_naive(),
+        period_end: now.date_naive(),
+        epsilon,
+        usage,
+        tools,
+        suppressed_buckets: usage_suppressed + tools_suppressed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn message(workflow_id: &str, tokens: u64, day: u32) -> MessageUsageRow {
+        MessageUsageRow {
+            workflow_id: workflow_id.to_string(),
+            provider: Some("Mistral".to_string()),
+            model: Some("mistral-large-latest".to_string()),
+            tokens_input: Some(tokens),
+            tokens_output: Some(tokens),
+            cost_usd: None,
+            timestamp: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    fn no_noise(_scale: f64) -> f64 {
+        0.0
+    }
+
+    #[test]
+    fn test_week_of() {
+        let monday = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        for day in 2..=8 {
+            let timestamp = Utc.with_ymd_and_hms(2026, 3, day, 23, 0, 0).unwrap();
+            assert_eq!(week_of(timestamp), monday);
+        }
+        let next = Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap();
+        assert_eq!(week_of(next), NaiveDate::from_ymd_opt(2026, 3, 9).unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_usage_clips_and_suppresses() {
+        let prices = HashMap::from([(
+            ("mistral".to_string(), "mistral-large-latest".to_string()),
+            (2.0, 6.0),
+        )]);
+        let mut rows = vec![
+            message("a", 1000, 2),
+            message("a", 1000, 3),
+            message("b", 1000, 4),
+            // A single huge conversation is clipped
+            message("c", 1_000_000, 5),
+            // Next week: only two conversations, suppressed
+            message("a", 1000, 9),
+            message("b", 1000, 10),
+        ];
+        rows[1].cost_usd = Some(0.5);
+
+        let (usage, suppressed) = aggregate_usage(&rows, &prices, 1.0, &mut no_noise);
+        assert_eq!(suppressed, 1);
+        assert_eq!(usage.len(), 1);
+        let bucket = &usage[0];
+        assert_eq!(bucket.week, NaiveDate::from_ymd_opt(2026, 3, 2).unwrap());
+        assert_eq!(bucket.provider, "Mistral");
+        assert_eq!(bucket.conversations, 3);
+        assert_eq!(bucket.messages, 4);
+        // 2000 + 2000 + 2000 + 200_000 (clipped)
+        assert_eq!(bucket.tokens, 206_000);
+        // a: 0.008 + 0.5, b: 0.008, c: 8.0 clipped to 5.0
+        assert_eq!(bucket.spend_usd, 5.52);
+    }
+
+    #[test]
+    fn test_aggregate_tools() {
+        let call = |workflow_id: &str, success: bool| ToolCallRow {
+            workflow_id: workflow_id.to_string(),
+            tool_type: "local".to_string(),
+            tool_name: "MemoryTool".to_string(),
+            success,
+            created_at: Utc.with_ymd_and_hms(2026, 3, 3, 12, 0, 0).unwrap(),
+        };
+        let rows = vec![
+            call("a", true),
+            call("a", false),
+            call("b", true),
+            call("c", false),
+        ];
+
+        let (tools, suppressed) = aggregate_tools(&rows, 1.0, &mut no_noise);
+        assert_eq!(suppressed, 0);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].conversations, 3);
+        assert_eq!(tools[0].calls, 4);
+        assert_eq!(tools[0].failures, 2);
+
+        // Strong negative noise suppresses the bucket
+        let mut negative = |_scale: f64| -1000.0;
+        let (tools, suppressed) = aggregate_tools(&rows, 1.0, &mut negative);
+        assert!(tools.is_empty());
+        assert_eq!(suppressed, 1);
+    }
+
+    #[test]
+    fn test_noise_scale_follows_epsilon() {
+        let mut scales = Vec::new();
+        let mut record = |scale: f64| {
+            scales.push(scale);
+            0.0
+        };
+        let rows = vec![
+            message("a", 10, 2),
+            message("b", 10, 2),
+            message("c", 10, 2),
+        ];
+        aggregate_usage(&rows, &HashMap::new(), 2.0, &mut record);
+        // Budget split in 4 (conversations + 3 metrics)
+        assert_eq!(
+            scales,
+            vec![
+                2.0,
+                usage_const::MESSAGE_CLIP * 2.0,
+                usage_const::TOKEN_CLIP * 2.0,
+                usage_const::SPEND_CLIP_USD * 2.0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_laplace_noise() {
+        let samples: Vec<f64> = (0..20_000).map(|_| laplace_noise(2.0)).collect();
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        let mean_abs = samples.iter().map(|s| s.abs()).sum::<f64>() / samples.len() as f64;
+        // Laplace(0, b): mean 0, mean absolute deviation b
+        assert!(mean.abs() < 0.1, "mean {}", mean);
+        assert!((mean_abs - 2.0).abs() < 0.1, "mean_abs {}", mean_abs);
+    }
+}
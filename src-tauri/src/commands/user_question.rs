@@ -25,10 +25,10 @@ use crate::state::AppState;
 async fn validate_question_pending(db: &DBClient, question_id: &str) -> Result<(), String> {
     // Query question status (question_id is a validated UUID)
     let result: Vec<serde_json::Value> = db
-        .query_json(&format!(
-            "SELECT status FROM user_question:`{}`",
-            question_id
-        ))
+        .query_json_with_params(
+            "SELECT status FROM type::thing('user_question', $id)",
+            vec![("id".to_string(), json!(question_id))],
+        )
         .await
         .map_err(|e| format!("Failed to query question: {}", e))?;
 
@@ -61,6 +61,7 @@ async fn update_question_answered(
 
     // Build params for update - use bind parameters for user-provided values
     let mut params: Vec<(String, serde_json::Value)> = vec![
+        ("id".to_string(), serde_json::json!(question_id)),
         (
             "selected_options".to_string(),
             serde_json::json!(selected_options_json),
@@ -84,15 +85,9 @@ async fn update_question_answered(
             "text_response".to_string(),
             serde_json::json!(text_response),
         ));
-        format!(
-            "UPDATE user_question:`{}` SET status = $status, selected_options = $selected_options, text_response = $text_response, answered_at = time::now()",
-            question_id
-        )
+        "UPDATE type::thing('user_question', $id) SET status = $status, selected_options = $selected_options, text_response = $text_response, answered_at = time::now()"
     } else {
-        format!(
-            "UPDATE user_question:`{}` SET status = $status, selected_options = $selected_options, answered_at = time::now()",
-            question_id
-        )
+        "UPDATE type::thing('user_question', $id) SET status = $status, selected_options = $selected_options, answered_at = time::now()"
     };
 
     info!(
@@ -101,7 +96,7 @@ async fn update_question_answered(
         "Executing update query"
     );
 
-    db.execute_with_params(&update_query, params)
+    db.execute_with_params(update_query, params)
         .await
         .map_err(|e| format!("Failed to update question: {}", e))?;
 
@@ -111,10 +106,10 @@ async fn update_question_answered(
 /// Verify that the update was successful by reading back the status
 async fn verify_update_success(db: &DBClient, question_id: &str) -> Result<String, String> {
     let verify_result: Vec<serde_json::Value> = db
-        .query_json(&format!(
-            "SELECT status FROM user_question:`{}`",
-            question_id
-        ))
+        .query_json_with_params(
+            "SELECT status FROM type::thing('user_question', $id)",
+            vec![("id".to_string(), json!(question_id))],
+        )
         .await
         .map_err(|e| format!("Failed to verify update: {}", e))?;
 
@@ -225,38 +220,16 @@ pub async fn skip_question(
     let validated_id = Validator::validate_uuid(&question_id)
         .map_err(|e| format!("Invalid question_id: {}", e))?;
 
-    // Validate question exists and is pending (validated_id is safe UUID)
-    let result: Vec<serde_json::Value> = state
-        .db
-        .query_json(&format!(
-            "SELECT status FROM user_question:`{}`",
-            validated_id
-        ))
-        .await
-        .map_err(|e| format!("Failed to query question: {}", e))?;
-
-    let record = result
-        .first()
-        .ok_or_else(|| format!("Question not found: {}", validated_id))?;
-
-    let status = record
-        .get("status")
-        .and_then(|v| v.as_str())
-        .unwrap_or("unknown");
-
-    if status != "pending" {
-        return Err(format!("Question is not pending (status: {})", status));
-    }
-
-    // Update status to skipped (validated_id is safe UUID)
-    let update_query = format!(
-        "UPDATE user_question:`{}` SET status = 'skipped', answered_at = time::now()",
-        validated_id
-    );
+    // Validate question exists and is pending
+    validate_question_pending(&state.db, &validated_id).await?;
 
+    // Update status to skipped
     state
         .db
-        .execute(&update_query)
+        .execute_with_params(
+            "UPDATE type::thing('user_question', $id) SET status = 'skipped', answered_at = time::now()",
+            vec![("id".to_string(), json!(validated_id))],
+        )
         .await
         .map_err(|e| format!("Failed to skip question: {}", e))?;
 
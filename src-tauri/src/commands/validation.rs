@@ -18,14 +18,18 @@
 //! human approval before execution (tools, sub-agents, MCP calls, etc.).
 
 use crate::{
+    db::queries::settings,
+    inbox,
     models::{
+        audit::{AuditAction, AuditLogCreate},
+        pending_item::PendingItemCreate,
         AuditConfig, PartialAuditConfig, PartialRiskThresholds, PartialSelectiveConfig, RiskLevel,
         RiskThresholdConfig, SelectiveValidationConfig, UpdateValidationSettingsRequest,
         ValidationRequest, ValidationRequestCreate, ValidationSettings, ValidationStatus,
         ValidationType,
     },
-    security::Validator,
-    tools::registry::TOOL_REGISTRY,
+    security::{audit, Validator},
+    tools::{registry::TOOL_REGISTRY, validation_notifier},
     AppState,
 };
 use chrono::Utc;
@@ -34,6 +38,9 @@ use tauri::State;
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
+/// Storage key for validation settings in the database
+pub(crate) const VALIDATION_SETTINGS_KEY: &str = "settings:validation";
+
 /// Creates a new validation request for human-in-the-loop approval.
 ///
 /// # Arguments
@@ -95,6 +102,17 @@ pub async fn create_validation_request(
             error!(error = %e, "Failed to create validation request");
             format!("Failed to create validation request: {}", e)
         })?;
+    inbox::track_pending_item(
+        &state.db,
+        PendingItemCreate::validation(
+            &request_id,
+            &validated_workflow_id,
+            &validation_type,
+            &validated_operation,
+            &risk_level,
+        ),
+    )
+    .await;
 
     // Build response object with current timestamp for immediate use
     let request = ValidationRequest {
@@ -159,10 +177,13 @@ pub async fn list_workflow_validations(
 
     let validations: Vec<ValidationRequest> = state
         .db
-        .query(&format!(
-            "SELECT * FROM validation_request WHERE workflow_id = '{}' ORDER BY created_at DESC",
-            validated_workflow_id
-        ))
+        .query_with_params(
+            "SELECT * FROM validation_request WHERE workflow_id = $workflow_id ORDER BY created_at DESC",
+            vec![(
+                "workflow_id".to_string(),
+                serde_json::json!(validated_workflow_id),
+            )],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to load workflow validations");
@@ -194,16 +215,26 @@ pub async fn approve_validation(
     // Update status to approved using SurrealDB record ID format
     state
         .db
-        .execute(&format!(
-            "UPDATE validation_request:`{}` SET status = 'approved'",
-            validated_id
-        ))
+        .execute_with_params(
+            "UPDATE type::thing('validation_request', $id) SET status = 'approved'",
+            vec![("id".to_string(), serde_json::json!(validated_id))],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to approve validation");
             format!("Failed to approve validation: {}", e)
         })?;
 
+    inbox::resolve_validation(&state.db, &validated_id).await;
+    audit::record(
+        &state.db,
+        AuditLogCreate {
+            details: serde_json::json!({ "status": "approved" }),
+            ..AuditLogCreate::new(AuditAction::ValidationDecision, "user", &validated_id)
+        },
+    )
+    .await;
+
     info!("Validation request approved successfully");
     Ok(())
 }
@@ -234,22 +265,32 @@ pub async fn reject_validation(
         format!("Invalid rejection reason: {}", e)
     })?;
 
-    // Update status to rejected and store reason in details using SurrealDB record ID format
-    // Use JSON encoding for the reason to handle special characters
-    let reason_json = serde_json::to_string(&validated_reason)
-        .unwrap_or_else(|_| "\"Unknown reason\"".to_string());
+    // Update status to rejected and store reason in details
     state
         .db
-        .execute(&format!(
-            "UPDATE validation_request:`{}` SET status = 'rejected', details.rejection_reason = {}",
-            validated_id, reason_json
-        ))
+        .execute_with_params(
+            "UPDATE type::thing('validation_request', $id) SET status = 'rejected', details.rejection_reason = $reason",
+            vec![
+                ("id".to_string(), serde_json::json!(validated_id)),
+                ("reason".to_string(), serde_json::json!(validated_reason)),
+            ],
+        )
         .await
         .map_err(|e| {
             error!(error = %e, "Failed to reject validation");
             format!("Failed to reject validation: {}", e)
         })?;
 
+    inbox::resolve_validation(&state.db, &validated_id).await;
+    audit::record(
+        &state.db,
+        AuditLogCreate {
+            details: serde_json::json!({ "status": "rejected", "reason": validated_reason }),
+            ..AuditLogCreate::new(AuditAction::ValidationDecision, "user", &validated_id)
+        },
+    )
+    .await;
+
     info!("Validation request rejected successfully");
     Ok(())
 }
@@ -281,6 +322,8 @@ pub async fn delete_validation(
             format!("Failed to delete validation: {}", e)
         })?;
 
+    inbox::resolve_validation(&state.db, &validated_id).await;
+
     info!("Validation request deleted successfully");
     Ok(())
 }
@@ -302,25 +345,22 @@ pub async fn get_validation_settings(
     info!("Loading validation settings");
 
     // Try to load existing settings from settings:validation record
-    let query = "SELECT config FROM settings:`settings:validation`";
-    let results: Vec<serde_json::Value> = state.db.query_json(query).await.map_err(|e| {
-        error!(error = %e, "Failed to query validation settings");
-        format!("Failed to query validation settings: {}", e)
-    })?;
+    let stored = settings::load_config(&state.db, VALIDATION_SETTINGS_KEY)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to query validation settings");
+            format!("Failed to query validation settings: {}", e)
+        })?;
 
-    // If we have a result with a config field, parse it
-    if let Some(first) = results.first() {
-        if let Some(config) = first.get("config") {
-            if !config.is_null() {
-                match serde_json::from_value::<ValidationSettings>(config.clone()) {
-                    Ok(settings) => {
-                        info!("Validation settings loaded successfully");
-                        return Ok(settings);
-                    }
-                    Err(e) => {
-                        warn!(error = %e, "Failed to parse stored settings, using defaults");
-                    }
-                }
+    // If we have a stored config, parse it
+    if let Some(config) = stored {
+        match serde_json::from_value::<ValidationSettings>(config) {
+            Ok(settings) => {
+                info!("Validation settings loaded successfully");
+                return Ok(settings);
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to parse stored settings, using defaults");
             }
         }
     }
@@ -379,25 +419,32 @@ pub async fn update_validation_settings(
         apply_audit_config(&mut current.audit, audit)?;
     }
 
+    if let Some(notifications) = config.notifications {
+        validation_notifier::validate_notification_config(&notifications).map_err(|e| {
+            warn!(error = %e, "Invalid notification settings");
+            e
+        })?;
+        current.notifications = notifications;
+    }
+
     // Update timestamp
     current.updated_at = Utc::now();
 
-    // Save to database using UPSERT
-    // Follow the same pattern as embedding config (CONTENT with id field)
-    let json_config = serde_json::to_string(&current).map_err(|e| {
+    // Save to database (same record pattern as embedding config)
+    let json_config = serde_json::to_value(&current).map_err(|e| {
         error!(error = %e, "Failed to serialize settings");
         format!("Failed to serialize settings: {}", e)
     })?;
 
-    let upsert_query = format!(
-        "UPSERT settings:`settings:validation` CONTENT {{ id: 'settings:validation', config: {} }}",
-        json_config
-    );
+    settings::save_config(&state.db, VALIDATION_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save validation settings");
+            format!("Failed to save validation settings: {}", e)
+        })?;
 
-    state.db.execute(&upsert_query).await.map_err(|e| {
-        error!(error = %e, "Failed to save validation settings");
-        format!("Failed to save validation settings: {}", e)
-    })?;
+    // The listener is restarted with the new bind address on the next notification
+    validation_notifier::stop_callback_listener().await;
 
     info!("Validation settings updated successfully");
     Ok(current)
@@ -414,26 +461,25 @@ pub async fn reset_validation_settings(
 ) -> Result<ValidationSettings, String> {
     info!("Resetting validation settings to defaults");
 
-    let settings = ValidationSettings::default();
+    let defaults = ValidationSettings::default();
 
     // Save defaults to database (follow embedding config pattern)
-    let json_config = serde_json::to_string(&settings).map_err(|e| {
+    let json_config = serde_json::to_value(&defaults).map_err(|e| {
         error!(error = %e, "Failed to serialize default settings");
         format!("Failed to serialize default settings: {}", e)
     })?;
 
-    let upsert_query = format!(
-        "UPSERT settings:`settings:validation` CONTENT {{ id: 'settings:validation', config: {} }}",
-        json_config
-    );
+    settings::save_config(&state.db, VALIDATION_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save default settings");
+            format!("Failed to save default settings: {}", e)
+        })?;
 
-    state.db.execute(&upsert_query).await.map_err(|e| {
-        error!(error = %e, "Failed to save default settings");
-        format!("Failed to save default settings: {}", e)
-    })?;
+    validation_notifier::stop_callback_listener().await;
 
     info!("Validation settings reset to defaults successfully");
-    Ok(settings)
+    Ok(defaults)
 }
 
 // =====================================================
@@ -444,20 +490,13 @@ pub async fn reset_validation_settings(
 async fn get_validation_settings_internal(
     state: &State<'_, AppState>,
 ) -> Result<ValidationSettings, String> {
-    let query = "SELECT config FROM settings:`settings:validation`";
-    let results: Vec<serde_json::Value> = state
-        .db
-        .query_json(query)
+    let stored = settings::load_config(&state.db, VALIDATION_SETTINGS_KEY)
         .await
         .map_err(|e| format!("Failed to query validation settings: {}", e))?;
 
-    if let Some(first) = results.first() {
-        if let Some(config) = first.get("config") {
-            if !config.is_null() {
-                if let Ok(settings) = serde_json::from_value::<ValidationSettings>(config.clone()) {
-                    return Ok(settings);
-                }
-            }
+    if let Some(config) = stored {
+        if let Ok(settings) = serde_json::from_value::<ValidationSettings>(config) {
+            return Ok(settings);
         }
     }
 
@@ -603,6 +642,13 @@ mod tests {
 
         AppState {
             db: db.clone(),
+            outbox: Arc::new(crate::db::outbox::PersistenceOutbox::new(
+                db_path.with_file_name("outbox.jsonl"),
+                Default::default(),
+            )),
+            attachments: Arc::new(crate::db::attachments::AttachmentStore::new(
+                db_path.with_file_name("attachments"),
+            )),
             registry,
             orchestrator,
             llm_manager,
@@ -616,6 +662,14 @@ mod tests {
                 std::collections::HashMap::new(),
             )),
             app_handle: Arc::new(std::sync::RwLock::new(None)),
+            startup_status: Default::default(),
+            scheduler: Default::default(),
+            job_queue: Default::default(),
+            profiles: Arc::new(crate::profiles::ProfileStore::new(
+                db_path.parent().unwrap(),
+                &db_path,
+            )),
+            config: Default::default(),
         }
     }
 
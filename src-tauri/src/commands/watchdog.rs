@@ -0,0 +1,100 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stuck tool loop watchdog settings commands.
+//!
+//! Settings are stored in the `settings:watchdog` record and read by
+//! [`crate::agents::watchdog::Watchdog`] callers at the start of each run.
+
+use crate::agents::watchdog::{load_watchdog_settings, WATCHDOG_SETTINGS_KEY};
+use crate::db::queries;
+use crate::models::watchdog::WatchdogSettings;
+use crate::AppState;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Gets the watchdog settings.
+/// Returns default settings if none are configured.
+#[tauri::command]
+#[instrument(name = "get_watchdog_settings", skip(state))]
+pub async fn get_watchdog_settings(state: State<'_, AppState>) -> Result<WatchdogSettings, String> {
+    info!("Loading watchdog settings");
+    Ok(load_watchdog_settings(&state.db).await)
+}
+
+/// Updates the watchdog settings.
+///
+/// # Arguments
+/// * `settings` - The complete settings to store
+///
+/// # Returns
+/// The stored settings
+#[tauri::command]
+#[instrument(name = "update_watchdog_settings", skip(state, settings))]
+pub async fn update_watchdog_settings(
+    settings: WatchdogSettings,
+    state: State<'_, AppState>,
+) -> Result<WatchdogSettings, String> {
+    info!("Updating watchdog settings");
+
+    validate_watchdog_settings(&settings).map_err(|e| {
+        warn!(error = %e, "Invalid watchdog settings");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&settings).map_err(|e| {
+        error!(error = %e, "Failed to serialize watchdog settings");
+        format!("Failed to serialize settings: {}", e)
+    })?;
+
+    queries::settings::save_config(&state.db, WATCHDOG_SETTINGS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save watchdog settings");
+            format!("Failed to save watchdog settings: {}", e)
+        })?;
+
+    info!("Watchdog settings updated successfully");
+    Ok(settings)
+}
+
+/// Validates watchdog settings ranges
+fn validate_watchdog_settings(settings: &WatchdogSettings) -> Result<(), String> {
+    if !(10..=3600).contains(&settings.stall_threshold_secs) {
+        return Err("Stall threshold must be between 10 and 3600 seconds".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_watchdog_settings() {
+        assert!(validate_watchdog_settings(&WatchdogSettings::default()).is_ok());
+
+        let settings = WatchdogSettings {
+            stall_threshold_secs: 5,
+            ..Default::default()
+        };
+        assert!(validate_watchdog_settings(&settings).is_err());
+
+        let settings = WatchdogSettings {
+            enabled: false,
+            stall_threshold_secs: 7200,
+        };
+        assert!(validate_watchdog_settings(&settings).is_err());
+    }
+}
@@ -13,12 +13,30 @@
 // limitations under the License.
 
 use crate::{
-    db::queries::{cascade, workflow as wf_queries},
+    agents::{
+        behavior_changelog::run_behavior_position,
+        language,
+        moderation::{moderate_report, ModerationTarget},
+    },
+    commands::{attachment::load_message_attachments, workflow_tag::register_tags, SecureKeyStore},
+    db::{
+        queries::{cascade, workflow as wf_queries},
+        DBClient,
+    },
     models::{
-        Message, ThinkingStep, ToolExecution, Workflow, WorkflowCreate, WorkflowFullState,
-        WorkflowMetrics, WorkflowResult, WorkflowStatus, WorkflowToolExecution,
+        GenerationParams, Message, PaginatedWorkflows, ThinkingStep, ToolExecution, Workflow,
+        WorkflowCreate, WorkflowFullState, WorkflowListQuery, WorkflowMetrics, WorkflowResult,
+        WorkflowSortBy, WorkflowStatus, WorkflowToolExecution,
+    },
+    security::{
+        workflow_vault::{generate_key, is_encrypted, workflow_key_name},
+        Validator, WORKFLOW_VAULT,
+    },
+    tools::{
+        constants::{commands as cmd_const, query_limits},
+        memory::requeue_pending_embeddings,
+        utils::tool_image_paths,
     },
-    security::Validator,
     AppState,
 };
 use std::sync::Arc;
@@ -74,6 +92,8 @@ pub async fn create_workflow(
 }
 
 /// Executes a workflow with a message
+///
+/// `generation` overrides the agent's generation parameters for this message.
 #[tauri::command]
 #[instrument(
     name = "execute_workflow",
@@ -88,6 +108,7 @@ pub async fn execute_workflow(
     workflow_id: String,
     message: String,
     agent_id: String,
+    generation: Option<GenerationParams>,
     state: State<'_, AppState>,
 ) -> Result<WorkflowResult, String> {
     use crate::agents::core::agent::Task;
@@ -113,17 +134,27 @@ pub async fn execute_workflow(
         format!("Invalid agent_id: {}", e)
     })?;
 
+    let generation = generation.unwrap_or_default();
+    generation.validate().map_err(|e| {
+        warn!(error = %e, "Invalid generation parameters");
+        format!("Invalid generation parameters: {}", e)
+    })?;
+
     // 1. Load workflow (OPT-WF-1: Use centralized query constant)
     let query = format!(
-        "{} WHERE meta::id(id) = '{}'",
-        wf_queries::SELECT_BASIC,
-        validated_workflow_id
+        "{} WHERE id = type::thing('workflow', $id)",
+        wf_queries::SELECT_BASIC
     );
+    let params = vec![("id".to_string(), serde_json::json!(validated_workflow_id))];
 
-    let json_results = state.db.query_json(&query).await.map_err(|e| {
-        error!(error = %e, "Failed to load workflow");
-        format!("Failed to load workflow: {}", e)
-    })?;
+    let json_results = state
+        .db
+        .query_json_with_params(&query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow");
+            format!("Failed to load workflow: {}", e)
+        })?;
 
     let workflows: Vec<Workflow> = json_results
         .into_iter()
@@ -146,17 +177,18 @@ pub async fn execute_workflow(
     let task = Task {
         id: task_id.clone(),
         description: validated_message,
-        context: serde_json::json!({}),
+        context: serde_json::json!({ "generation": generation }),
     };
 
     // 3. Execute via orchestrator with MCP support (OPT-WF-9: with timeout)
+    let behavior_position = run_behavior_position(&state.db).await;
     let execution_future = state.orchestrator.execute_with_mcp(
         &validated_agent_id,
         task,
         Some(state.mcp_manager.clone()),
     );
 
-    let report = timeout(
+    let mut report = timeout(
         Duration::from_secs(wf_const::LLM_EXECUTION_TIMEOUT_SECS),
         execution_future,
     )
@@ -173,10 +205,20 @@ pub async fn execute_workflow(
         format!("Execution failed: {}", e)
     })?;
 
-    // 4. Get agent config for accurate provider/model info
+    // 4. Get agent config for accurate provider/model info, and moderate the response
+    let mut moderation = None;
+    let mut generation_used = None;
     let (provider, model) = match state.registry.get(&validated_agent_id).await {
         Some(agent) => {
             let config = agent.config();
+            generation_used = Some(generation.effective(&config.llm));
+            let message_id = Uuid::new_v4().to_string();
+            let target = ModerationTarget {
+                workflow_id: &validated_workflow_id,
+                message_id: &message_id,
+            };
+            moderation =
+                moderate_report(&state.db, &state.llm_manager, config, target, &mut report).await;
             (config.llm.provider.clone(), config.llm.model.clone())
         }
         None => {
@@ -218,7 +260,12 @@ pub async fn execute_workflow(
         },
         tools_used: report.metrics.tools_used.clone(),
         mcp_calls: report.metrics.mcp_calls.clone(),
+        images: tool_image_paths(&tool_executions),
         tool_executions,
+        moderation,
+        context_usage: None,
+        behavior_position,
+        generation: generation_used,
     };
 
     info!(
@@ -263,6 +310,607 @@ pub async fn load_workflows(state: State<'_, AppState>) -> Result<Vec<Workflow>,
     Ok(workflows)
 }
 
+/// Loads a page of workflows with sorting and filters
+///
+/// Keeps the sidebar responsive with thousands of workflows: only one page
+/// is transferred, sorted and filtered by the database.
+///
+/// # Arguments
+/// * `query` - Sort key, filters (status, tag, agent) and pagination
+///   (defaults: last activity first, 50 workflows)
+///
+/// # Returns
+/// Paginated result with workflows and metadata
+#[tauri::command]
+#[instrument(name = "load_workflows_paginated", skip(state))]
+pub async fn load_workflows_paginated(
+    query: Option<WorkflowListQuery>,
+    state: State<'_, AppState>,
+) -> Result<PaginatedWorkflows, String> {
+    info!("Loading paginated workflows");
+    query_workflow_page(&state.db, &query.unwrap_or_default()).await
+}
+
+/// Runs a workflow list query (shared by the command and tests)
+pub(crate) async fn query_workflow_page(
+    db: &DBClient,
+    query: &WorkflowListQuery,
+) -> Result<PaginatedWorkflows, String> {
+    let limit = query
+        .limit
+        .map(|l| l as usize)
+        .unwrap_or(query_limits::DEFAULT_WORKFLOWS_PAGE_SIZE)
+        .clamp(1, query_limits::MAX_WORKFLOWS_PAGE_SIZE) as u32;
+    let offset = query.offset.unwrap_or(0);
+
+    // Filters are bound as parameters, never formatted into the query
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    if let Some(status) = &query.status {
+        conditions.push("status = $status");
+        params.push(("status".to_string(), serde_json::json!(status.to_string())));
+    }
+    if let Some(tag) = &query.tag {
+        let tag = normalize_tag(tag)?;
+        conditions.push("$tag IN tags");
+        params.push(("tag".to_string(), serde_json::json!(tag)));
+    }
+    if let Some(agent_id) = &query.agent_id {
+        let agent_id = Validator::validate_agent_id(agent_id).map_err(|e| {
+            warn!(error = %e, "Invalid agent_id filter");
+            format!("Invalid agent_id: {}", e)
+        })?;
+        conditions.push("agent_id = $agent_id");
+        params.push(("agent_id".to_string(), serde_json::json!(agent_id)));
+    }
+    if let Some(after) = &query.created_after {
+        conditions.push("created_at >= <datetime>$created_after");
+        params.push(("created_after".to_string(), serde_json::json!(after)));
+    }
+    if let Some(before) = &query.created_before {
+        conditions.push("created_at < <datetime>$created_before");
+        params.push(("created_before".to_string(), serde_json::json!(before)));
+    }
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+
+    let direction = if query.ascending { "ASC" } else { "DESC" };
+    let order_clause = match query.sort_by {
+        WorkflowSortBy::LastActivity => format!("updated_at {}", direction),
+        WorkflowSortBy::Cost => format!("total_cost_usd {}, updated_at DESC", direction),
+        WorkflowSortBy::Name => format!("sort_name {}, updated_at DESC", direction),
+    };
+
+    let count_query = format!("SELECT count() FROM workflow{} GROUP ALL", where_clause);
+    let total = db
+        .query_json_with_params(&count_query, params.clone())
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to count workflows");
+            format!("Failed to count workflows: {}", e)
+        })?
+        .first()
+        .and_then(|v| v.get("count"))
+        .and_then(|c| c.as_u64())
+        .unwrap_or(0) as u32;
+
+    let page_query = format!(
+        "{}{} ORDER BY {} LIMIT {} START {}",
+        wf_queries::SELECT_PAGE,
+        where_clause,
+        order_clause,
+        limit,
+        offset
+    );
+    let json_results = db
+        .query_json_with_params(&page_query, params)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflows page");
+            format!("Failed to load workflows: {}", e)
+        })?;
+
+    let workflows: Vec<Workflow> = json_results
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<Vec<Workflow>, _>>()
+        .map_err(|e| {
+            error!(error = %e, "Failed to deserialize workflows");
+            format!("Failed to deserialize workflows: {}", e)
+        })?;
+
+    let has_more = offset + (workflows.len() as u32) < total;
+
+    info!(
+        count = workflows.len(),
+        total = total,
+        has_more = has_more,
+        "Workflows page loaded"
+    );
+
+    Ok(PaginatedWorkflows {
+        workflows,
+        total,
+        offset,
+        limit,
+        has_more,
+    })
+}
+
+/// Normalizes a workflow tag (trimmed, lowercase, bounded length)
+pub(crate) fn normalize_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    if tag.chars().count() > cmd_const::MAX_WORKFLOW_TAG_LEN {
+        return Err(format!(
+            "Tag '{}' exceeds {} characters",
+            tag,
+            cmd_const::MAX_WORKFLOW_TAG_LEN
+        ));
+    }
+    if tag.chars().any(char::is_control) {
+        return Err("Tag cannot contain control characters".to_string());
+    }
+    Ok(tag)
+}
+
+/// Sets the tags of a workflow
+///
+/// Tags are trimmed, lowercased and deduplicated (order preserved).
+///
+/// # Returns
+/// The stored tags
+#[tauri::command]
+#[instrument(name = "set_workflow_tags", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn set_workflow_tags(
+    workflow_id: String,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    info!(count = tags.len(), "Setting workflow tags");
+
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in &tags {
+        let tag = normalize_tag(tag)?;
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.len() > cmd_const::MAX_WORKFLOW_TAGS {
+        return Err(format!(
+            "A workflow can have at most {} tags",
+            cmd_const::MAX_WORKFLOW_TAGS
+        ));
+    }
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('workflow', $id) SET tags = $tags",
+            vec![
+                ("id".to_string(), serde_json::json!(validated_id)),
+                ("tags".to_string(), serde_json::json!(normalized)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update workflow tags");
+            format!("Failed to update workflow tags: {}", e)
+        })?;
+
+    register_tags(&state.db, &normalized).await?;
+
+    info!(tags = ?normalized, "Workflow tags updated");
+    Ok(normalized)
+}
+
+/// Enables or disables the dry run mode of a workflow
+///
+/// In dry run, tools with side effects (SQL statements, file writes, e-mails,
+/// HTTP requests...) return a simulated result describing what they would have
+/// done instead of executing. Read-only tools still run. The flag is read when
+/// a message is sent, so a running execution keeps its mode.
+#[tauri::command]
+#[instrument(name = "set_workflow_dry_run", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn set_workflow_dry_run(
+    workflow_id: String,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!(dry_run, "Setting workflow dry run mode");
+
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    state
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('workflow', $id) SET dry_run = $dry_run",
+            vec![
+                ("id".to_string(), serde_json::json!(validated_id)),
+                ("dry_run".to_string(), serde_json::json!(dry_run)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update workflow dry run mode");
+            format!("Failed to update workflow dry run mode: {}", e)
+        })?;
+
+    info!("Workflow dry run mode updated");
+    Ok(())
+}
+
+/// Sets the response language of a workflow
+///
+/// Overrides the language detected from the user messages. `None` clears the
+/// pinned language, so it is detected again from the next message.
+#[tauri::command]
+#[instrument(name = "set_workflow_language", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn set_workflow_language(
+    workflow_id: String,
+    language: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    info!(language = ?language, "Setting workflow response language");
+
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let language = language.map(|l| l.trim().to_lowercase());
+    if let Some(code) = &language {
+        if !language::is_supported(code) {
+            warn!(language = %code, "Unsupported response language");
+            return Err(format!(
+                "Unsupported language '{}'. Supported languages: {}",
+                code,
+                language::SUPPORTED_LANGUAGES.join(", ")
+            ));
+        }
+    }
+
+    language::set_workflow_language(&state.db, &validated_id, language.as_deref())
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to set workflow language");
+            format!("Failed to set workflow language: {}", e)
+        })?;
+
+    info!("Workflow response language updated");
+    Ok(())
+}
+
+/// Marks a workflow as sensitive, or back as regular
+///
+/// Enabling generates a data key, stores it in the keystore and encrypts the
+/// existing messages, tool logs and workflow memories (their embeddings are
+/// dropped, encrypted memories are only matched by text while the workflow is
+/// open). Disabling requires the workflow to be open: its contents are
+/// decrypted, memories queued for embedding and the key deleted.
+#[tauri::command]
+#[instrument(name = "set_workflow_sensitive", skip(state, keystore), fields(workflow_id = %workflow_id))]
+pub async fn set_workflow_sensitive(
+    workflow_id: String,
+    sensitive: bool,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<(), String> {
+    info!(sensitive, "Setting workflow sensitivity");
+
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    if WORKFLOW_VAULT
+        .is_sensitive(&state.db, &validated_id)
+        .await?
+        == sensitive
+    {
+        return Ok(());
+    }
+    if state
+        .streaming_cancellations
+        .lock()
+        .await
+        .contains_key(&validated_id)
+    {
+        return Err("Cannot change the sensitivity of a running workflow".to_string());
+    }
+
+    let key_name = workflow_key_name(&validated_id);
+    if sensitive {
+        let key = generate_key();
+        keystore.set_key(&key_name, &key).map_err(|e| {
+            error!(error = %e, "Failed to store workflow key");
+            format!("Failed to store workflow key: {}", e)
+        })?;
+        WORKFLOW_VAULT.unlock(&validated_id, &key)?;
+        store_workflow_sensitive(&state.db, &validated_id, true).await?;
+        let count = reseal_workflow_contents(&state.db, &validated_id, true).await?;
+        info!(count, "Workflow contents encrypted");
+    } else {
+        if !WORKFLOW_VAULT.is_unlocked(&validated_id) {
+            return Err("Open the workflow before making it regular again".to_string());
+        }
+        let count = reseal_workflow_contents(&state.db, &validated_id, false).await?;
+        store_workflow_sensitive(&state.db, &validated_id, false).await?;
+        WORKFLOW_VAULT.lock(&validated_id);
+        if let Err(e) = keystore.delete_key(&key_name) {
+            warn!(error = %e, "Failed to delete workflow key");
+        }
+        info!(count, "Workflow contents decrypted");
+
+        let embedding_service = state.embedding_service.read().await.clone();
+        if let Some(service) = embedding_service {
+            requeue_pending_embeddings(&state.db, &service).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Opens a sensitive workflow: loads its data key from the keystore
+///
+/// # Returns
+/// Whether the workflow is sensitive (nothing to unlock otherwise)
+#[tauri::command]
+#[instrument(name = "unlock_workflow", skip(state, keystore), fields(workflow_id = %workflow_id))]
+pub async fn unlock_workflow(
+    workflow_id: String,
+    state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
+) -> Result<bool, String> {
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    if !WORKFLOW_VAULT
+        .is_sensitive(&state.db, &validated_id)
+        .await?
+    {
+        return Ok(false);
+    }
+    if !WORKFLOW_VAULT.is_unlocked(&validated_id) {
+        let key = keystore
+            .get_key(&workflow_key_name(&validated_id))
+            .ok_or_else(|| {
+                error!("Workflow key not found in the keystore");
+                "The encryption key of this workflow was not found".to_string()
+            })?;
+        WORKFLOW_VAULT.unlock(&validated_id, &key)?;
+        info!("Sensitive workflow unlocked");
+    }
+    Ok(true)
+}
+
+/// Closes a sensitive workflow: drops its data key from memory
+///
+/// A running workflow is kept open until the next call, so its responses
+/// can still be stored.
+///
+/// # Returns
+/// Whether the workflow is locked
+#[tauri::command]
+#[instrument(name = "lock_workflow", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn lock_workflow(
+    workflow_id: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    if state
+        .streaming_cancellations
+        .lock()
+        .await
+        .contains_key(&workflow_id)
+    {
+        return Ok(false);
+    }
+    WORKFLOW_VAULT.lock(&workflow_id);
+    Ok(true)
+}
+
+/// Stores the `sensitive` flag of a workflow
+async fn store_workflow_sensitive(
+    db: &DBClient,
+    workflow_id: &str,
+    sensitive: bool,
+) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('workflow', $id) SET sensitive = $sensitive",
+        vec![
+            ("id".to_string(), serde_json::json!(workflow_id)),
+            ("sensitive".to_string(), serde_json::json!(sensitive)),
+        ],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to update workflow sensitivity");
+        format!("Failed to update workflow sensitivity: {}", e)
+    })?;
+    WORKFLOW_VAULT.set_sensitive(workflow_id, sensitive);
+    Ok(())
+}
+
+/// Encrypts (or decrypts) the stored messages, reasoning steps, tool logs and
+/// memories of an open workflow.
+///
+/// Values already in the target form are left untouched, so an interrupted
+/// run can be resumed.
+///
+/// # Returns
+/// Number of updated records
+pub(crate) async fn reseal_workflow_contents(
+    db: &DBClient,
+    workflow_id: &str,
+    encrypt: bool,
+) -> Result<usize, String> {
+    let convert = |value: &str| -> Result<Option<String>, String> {
+        match (encrypt, is_encrypted(value)) {
+            (true, false) => WORKFLOW_VAULT.encrypt(workflow_id, value).map(Some),
+            (false, true) => WORKFLOW_VAULT.decrypt(workflow_id, value).map(Some),
+            _ => Ok(None),
+        }
+    };
+    // Tool logs are JSON strings: the encrypted form is a JSON string too
+    let convert_json = |stored: &str| -> Result<Option<String>, String> {
+        let value: serde_json::Value = serde_json::from_str(stored)
+            .unwrap_or_else(|_| serde_json::Value::String(stored.to_string()));
+        let converted = if encrypt {
+            WORKFLOW_VAULT.encrypt_json(workflow_id, &value)?
+        } else {
+            WORKFLOW_VAULT.decrypt_json(workflow_id, &value)?
+        };
+        Ok((converted != value).then(|| converted.to_string()))
+    };
+    let field = |row: &serde_json::Value, name: &str| -> Option<String> {
+        row.get(name).and_then(|v| v.as_str()).map(String::from)
+    };
+    let mut updated = 0;
+
+    let messages = load_workflow_rows(db, "message", "content", workflow_id).await?;
+    for row in &messages {
+        let (Some(id), Some(content)) = (field(row, "id"), field(row, "content")) else {
+            continue;
+        };
+        // The read-aloud version is dropped, it is prepared again on demand
+        if let Some(content) = convert(&content)? {
+            db.execute_with_params(
+                "UPDATE type::thing('message', $id) SET content = $content, read_aloud = NONE",
+                vec![
+                    ("id".to_string(), serde_json::json!(id)),
+                    ("content".to_string(), serde_json::json!(content)),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to update message: {}", e))?;
+            updated += 1;
+        }
+    }
+
+    let steps = load_workflow_rows(db, "thinking_step", "content", workflow_id).await?;
+    for row in &steps {
+        let (Some(id), Some(content)) = (field(row, "id"), field(row, "content")) else {
+            continue;
+        };
+        if let Some(content) = convert(&content)? {
+            db.execute_with_params(
+                "UPDATE type::thing('thinking_step', $id) SET content = $content",
+                vec![
+                    ("id".to_string(), serde_json::json!(id)),
+                    ("content".to_string(), serde_json::json!(content)),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to update thinking step: {}", e))?;
+            updated += 1;
+        }
+    }
+
+    let executions = load_workflow_rows(
+        db,
+        "tool_execution",
+        "input_params, output_result",
+        workflow_id,
+    )
+    .await?;
+    for row in &executions {
+        let Some(id) = field(row, "id") else {
+            continue;
+        };
+        let input = field(row, "input_params")
+            .map(|v| convert_json(&v))
+            .transpose()?;
+        let output = field(row, "output_result")
+            .map(|v| convert_json(&v))
+            .transpose()?;
+        let mut sets = Vec::new();
+        let mut params = vec![("id".to_string(), serde_json::json!(id))];
+        if let Some(Some(input)) = input {
+            sets.push("input_params = $input_params");
+            params.push(("input_params".to_string(), serde_json::json!(input)));
+        }
+        if let Some(Some(output)) = output {
+            sets.push("output_result = $output_result");
+            params.push(("output_result".to_string(), serde_json::json!(output)));
+        }
+        if !sets.is_empty() {
+            db.execute_with_params(
+                &format!(
+                    "UPDATE type::thing('tool_execution', $id) SET {}",
+                    sets.join(", ")
+                ),
+                params,
+            )
+            .await
+            .map_err(|e| format!("Failed to update tool execution: {}", e))?;
+            updated += 1;
+        }
+    }
+
+    // Encrypted memories lose their embedding; decrypted ones are queued again
+    let embedding_update = if encrypt {
+        "embedding = NONE, embedding_status = NONE"
+    } else {
+        "embedding_status = 'pending'"
+    };
+    let memories = load_workflow_rows(db, "memory", "content", workflow_id).await?;
+    for row in &memories {
+        let (Some(id), Some(content)) = (field(row, "id"), field(row, "content")) else {
+            continue;
+        };
+        if let Some(content) = convert(&content)? {
+            db.execute_with_params(
+                &format!(
+                    "UPDATE type::thing('memory', $id) SET content = $content, {}",
+                    embedding_update
+                ),
+                vec![
+                    ("id".to_string(), serde_json::json!(id)),
+                    ("content".to_string(), serde_json::json!(content)),
+                ],
+            )
+            .await
+            .map_err(|e| format!("Failed to update memory: {}", e))?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Loads the given fields (and ID) of the records of a workflow
+async fn load_workflow_rows(
+    db: &DBClient,
+    table: &str,
+    fields: &str,
+    workflow_id: &str,
+) -> Result<Vec<serde_json::Value>, String> {
+    db.query_json_with_params(
+        &format!(
+            "SELECT meta::id(id) AS id, {} FROM {} WHERE workflow_id = $workflow_id",
+            fields, table
+        ),
+        vec![("workflow_id".to_string(), serde_json::json!(workflow_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to load {} records: {}", table, e))
+}
+
 /// Deletes a workflow and all related entities (cascade delete).
 ///
 /// Deletes in order:
@@ -275,10 +923,11 @@ pub async fn load_workflows(state: State<'_, AppState>) -> Result<Vec<Workflow>,
 /// - Memories (workflow-scoped)
 /// - Workflow itself
 #[tauri::command]
-#[instrument(name = "delete_workflow", skip(state), fields(workflow_id = %workflow_id))]
+#[instrument(name = "delete_workflow", skip(state, keystore), fields(workflow_id = %workflow_id))]
 pub async fn delete_workflow(
     workflow_id: String,
     state: State<'_, AppState>,
+    keystore: State<'_, SecureKeyStore>,
 ) -> Result<(), String> {
     info!("Deleting workflow with cascade");
 
@@ -288,9 +937,14 @@ pub async fn delete_workflow(
         format!("Invalid workflow_id: {}", e)
     })?;
 
+    let sensitive = WORKFLOW_VAULT
+        .is_sensitive(&state.db, &validated_id)
+        .await?;
+
     // OPT-WF-8: Use centralized cascade delete helper
     // This eliminates 8 Arc clones + 8 ID clones by using a single helper function
     cascade::delete_workflow_related(&state.db, &validated_id).await;
+    state.attachments.delete_workflow(&validated_id).await;
 
     // Finally delete the workflow itself
     state
@@ -302,6 +956,12 @@ pub async fn delete_workflow(
             format!("Failed to delete workflow: {}", e)
         })?;
 
+    // Drop the data key of a sensitive workflow
+    if sensitive {
+        let _ = keystore.delete_key(&workflow_key_name(&validated_id));
+    }
+    WORKFLOW_VAULT.forget(&validated_id);
+
     info!("Workflow and all related entities deleted successfully");
     Ok(())
 }
@@ -354,12 +1014,19 @@ pub async fn load_workflow_full_state(
         tokio::try_join!(
             // Query 1: Load workflow (OPT-WF-1: Use centralized query constant)
             async move {
-                let query = format!("{} WHERE meta::id(id) = '{}'", wf_queries::SELECT_BASE, id1);
+                let query = format!(
+                    "{} WHERE id = type::thing('workflow', $id)",
+                    wf_queries::SELECT_BASE
+                );
+                let params = vec![("id".to_string(), serde_json::json!(id1))];
 
-                let json_results = db.query_json(&query).await.map_err(|e| {
-                    error!(error = %e, "Failed to load workflow");
-                    format!("Failed to load workflow: {}", e)
-                })?;
+                let json_results =
+                    db.query_json_with_params(&query, params)
+                        .await
+                        .map_err(|e| {
+                            error!(error = %e, "Failed to load workflow");
+                            format!("Failed to load workflow: {}", e)
+                        })?;
 
                 let workflows: Vec<Workflow> = json_results
                     .into_iter()
@@ -377,8 +1044,7 @@ pub async fn load_workflow_full_state(
             },
             // Query 2: Load messages
             async move {
-                let query = format!(
-                    r#"SELECT
+                let query = r#"SELECT
                     meta::id(id) AS id,
                     workflow_id,
                     role,
@@ -390,19 +1056,27 @@ pub async fn load_workflow_full_state(
                     provider,
                     cost_usd,
                     duration_ms,
+                    context_usage,
+                    behavior_position,
+                    generation,
+                    pinned,
+                    bookmarked,
+                    reactions,
                     timestamp
                 FROM message
-                WHERE workflow_id = '{}'
-                ORDER BY timestamp ASC"#,
-                    id2
-                );
-
-                let json_results = db2.query_json(&query).await.map_err(|e| {
-                    error!(error = %e, "Failed to load messages");
-                    format!("Failed to load messages: {}", e)
-                })?;
-
-                let messages: Vec<Message> = json_results
+                WHERE workflow_id = $workflow_id
+                ORDER BY timestamp ASC"#;
+                let params = vec![("workflow_id".to_string(), serde_json::json!(id2))];
+
+                let json_results =
+                    db2.query_json_with_params(query, params)
+                        .await
+                        .map_err(|e| {
+                            error!(error = %e, "Failed to load messages");
+                            format!("Failed to load messages: {}", e)
+                        })?;
+
+                let mut messages: Vec<Message> = json_results
                     .into_iter()
                     .map(serde_json::from_value)
                     .collect::<std::result::Result<Vec<Message>, _>>()
@@ -410,13 +1084,13 @@ pub async fn load_workflow_full_state(
                         error!(error = %e, "Failed to deserialize messages");
                         format!("Failed to deserialize messages: {}", e)
                     })?;
+                load_message_attachments(&db2, &id2, &mut messages).await;
 
                 Ok::<Vec<Message>, String>(messages)
             },
             // Query 3: Load tool executions
             async move {
-                let query = format!(
-                    r#"SELECT
+                let query = r#"SELECT
                     meta::id(id) AS id,
                     workflow_id,
                     message_id,
@@ -432,15 +1106,17 @@ pub async fn load_workflow_full_state(
                     iteration,
                     created_at
                 FROM tool_execution
-                WHERE workflow_id = '{}'
-                ORDER BY created_at ASC"#,
-                    id3
-                );
-
-                let json_results = db3.query_json(&query).await.map_err(|e| {
-                    error!(error = %e, "Failed to load tool executions");
-                    format!("Failed to load tool executions: {}", e)
-                })?;
+                WHERE workflow_id = $workflow_id
+                ORDER BY created_at ASC"#;
+                let params = vec![("workflow_id".to_string(), serde_json::json!(id3))];
+
+                let json_results =
+                    db3.query_json_with_params(query, params)
+                        .await
+                        .map_err(|e| {
+                            error!(error = %e, "Failed to load tool executions");
+                            format!("Failed to load tool executions: {}", e)
+                        })?;
 
                 let tools: Vec<ToolExecution> = json_results
                     .into_iter()
@@ -455,8 +1131,7 @@ pub async fn load_workflow_full_state(
             },
             // Query 4: Load thinking steps
             async move {
-                let query = format!(
-                    r#"SELECT
+                let query = r#"SELECT
                     meta::id(id) AS id,
                     workflow_id,
                     message_id,
@@ -467,15 +1142,17 @@ pub async fn load_workflow_full_state(
                     tokens,
                     created_at
                 FROM thinking_step
-                WHERE workflow_id = '{}'
-                ORDER BY created_at ASC, step_number ASC"#,
-                    id4
-                );
-
-                let json_results = db4.query_json(&query).await.map_err(|e| {
-                    error!(error = %e, "Failed to load thinking steps");
-                    format!("Failed to load thinking steps: {}", e)
-                })?;
+                WHERE workflow_id = $workflow_id
+                ORDER BY created_at ASC, step_number ASC"#;
+                let params = vec![("workflow_id".to_string(), serde_json::json!(id4))];
+
+                let json_results =
+                    db4.query_json_with_params(query, params)
+                        .await
+                        .map_err(|e| {
+                            error!(error = %e, "Failed to load thinking steps");
+                            format!("Failed to load thinking steps: {}", e)
+                        })?;
 
                 let steps: Vec<ThinkingStep> = json_results
                     .into_iter()
@@ -508,12 +1185,24 @@ pub async fn load_workflow_full_state(
         )
     })??;
 
-    let full_state = WorkflowFullState {
+    let mut full_state = WorkflowFullState {
         workflow: workflow_result,
         messages: messages_result,
         tool_executions: tools_result,
         thinking_steps: thinking_result,
     };
+    full_state
+        .messages
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    full_state
+        .tool_executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+    full_state
+        .thinking_steps
+        .iter_mut()
+        .for_each(|s| WORKFLOW_VAULT.reveal_thinking_step(s));
 
     info!(
         messages = full_state.messages.len(),
@@ -567,6 +1256,10 @@ mod tests {
             system_prompt: "Test agent".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
         let agent = SimpleAgent::new(config);
         registry
@@ -588,6 +1281,13 @@ mod tests {
 
         AppState {
             db: db.clone(),
+            outbox: Arc::new(crate::db::outbox::PersistenceOutbox::new(
+                db_path.with_file_name("outbox.jsonl"),
+                Default::default(),
+            )),
+            attachments: Arc::new(crate::db::attachments::AttachmentStore::new(
+                db_path.with_file_name("attachments"),
+            )),
             registry,
             orchestrator,
             llm_manager,
@@ -601,6 +1301,14 @@ mod tests {
                 std::collections::HashMap::new(),
             )),
             app_handle: Arc::new(std::sync::RwLock::new(None)),
+            startup_status: Default::default(),
+            scheduler: Default::default(),
+            job_queue: Default::default(),
+            profiles: Arc::new(crate::profiles::ProfileStore::new(
+                db_path.parent().unwrap(),
+                &db_path,
+            )),
+            config: Default::default(),
         }
     }
 
@@ -641,6 +1349,11 @@ mod tests {
             tools_used: vec!["tool1".to_string()],
             mcp_calls: vec![],
             tool_executions: vec![],
+            moderation: None,
+            context_usage: None,
+            behavior_position: None,
+            generation: None,
+            images: Vec::new(),
         };
 
         // Verify serialization works
@@ -703,4 +1416,185 @@ mod tests {
         assert_eq!(metrics.duration_ms, 0);
         assert_eq!(metrics.cost_usd, 0.0);
     }
+
+    #[tokio::test]
+    async fn test_reseal_workflow_contents_roundtrip() {
+        use crate::models::{MessageCreate, ThinkingStepCreate, ToolExecutionCreate};
+        use crate::tools::memory::{add_memory_core, AddMemoryParams};
+
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("sensitive_db");
+        let db = Arc::new(
+            DBClient::new(db_path.to_str().unwrap())
+                .await
+                .expect("Failed to create DB"),
+        );
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        let wf_id = uuid::Uuid::new_v4().to_string();
+        db.create(
+            "workflow",
+            &wf_id,
+            WorkflowCreate::new("Secret".into(), "agent".into(), WorkflowStatus::Idle),
+        )
+        .await
+        .unwrap();
+        db.create(
+            "message",
+            "m1",
+            MessageCreate {
+                workflow_id: wf_id.clone(),
+                role: "user".to_string(),
+                content: "my salary is 90k".to_string(),
+                tokens: 0,
+                tokens_input: None,
+                tokens_output: None,
+                model: None,
+                provider: None,
+                cost_usd: None,
+                duration_ms: None,
+                context_usage: None,
+                read_aloud: None,
+                behavior_position: None,
+                generation: None,
+            },
+        )
+        .await
+        .unwrap();
+        db.create(
+            "thinking_step",
+            "s1",
+            ThinkingStepCreate {
+                workflow_id: wf_id.clone(),
+                message_id: "m1".to_string(),
+                agent_id: "agent".to_string(),
+                step_number: 0,
+                content: "compare the salary offers".to_string(),
+                duration_ms: None,
+                tokens: None,
+            },
+        )
+        .await
+        .unwrap();
+        db.create(
+            "tool_execution",
+            "t1",
+            ToolExecutionCreate::local(
+                wf_id.clone(),
+                "m1".to_string(),
+                "agent".to_string(),
+                "MemoryTool".to_string(),
+                serde_json::json!({"content": "salary"}),
+                serde_json::json!({"success": true}),
+                true,
+                None,
+                1,
+                0,
+            ),
+        )
+        .await
+        .unwrap();
+        add_memory_core(
+            AddMemoryParams {
+                memory_type: crate::models::MemoryType::Context,
+                content: "salary negotiation".to_string(),
+                metadata: serde_json::json!({}),
+                workflow_id: Some(wf_id.clone()),
+                importance: 0.5,
+                expires_at: None,
+            },
+            &db,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let key = crate::security::workflow_vault::generate_key();
+        WORKFLOW_VAULT.unlock(&wf_id, &key).unwrap();
+        store_workflow_sensitive(&db, &wf_id, true).await.unwrap();
+        assert_eq!(
+            reseal_workflow_contents(&db, &wf_id, true).await.unwrap(),
+            4
+        );
+        // Records already encrypted are left untouched
+        assert_eq!(
+            reseal_workflow_contents(&db, &wf_id, true).await.unwrap(),
+            0
+        );
+
+        let raw = db
+            .query_json(
+                "SELECT content, input_params FROM message, thinking_step, tool_execution, memory",
+            )
+            .await
+            .unwrap();
+        assert_eq!(raw.len(), 4);
+        assert!(!raw.iter().any(|row| row.to_string().contains("salary")));
+
+        // Open: encrypted memories are matched after decryption
+        let found = search_salary(&db, &wf_id).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0]["content"], "salary negotiation");
+
+        // Closed: excluded from search, placeholder on read, no clear writes
+        WORKFLOW_VAULT.lock(&wf_id);
+        assert!(search_salary(&db, &wf_id).await.is_empty());
+        let mut message: Message = serde_json::from_value(
+            db.query_json("SELECT meta::id(id) AS id, * FROM message")
+                .await
+                .unwrap()
+                .remove(0),
+        )
+        .unwrap();
+        WORKFLOW_VAULT.reveal_message(&mut message);
+        assert_eq!(
+            message.content,
+            crate::security::workflow_vault::LOCKED_PLACEHOLDER
+        );
+        assert!(WORKFLOW_VAULT.seal(&db, &wf_id, "more").await.is_err());
+
+        // Back to regular: contents decrypted, memory queued for embedding
+        WORKFLOW_VAULT.unlock(&wf_id, &key).unwrap();
+        assert_eq!(
+            reseal_workflow_contents(&db, &wf_id, false).await.unwrap(),
+            4
+        );
+        store_workflow_sensitive(&db, &wf_id, false).await.unwrap();
+        let rows = db
+            .query_json("SELECT content, embedding_status FROM memory")
+            .await
+            .unwrap();
+        assert_eq!(rows[0]["content"], "salary negotiation");
+        assert_eq!(rows[0]["embedding_status"], "pending");
+        let execution: ToolExecution = serde_json::from_value(
+            db.query_json("SELECT meta::id(id) AS id, * FROM tool_execution")
+                .await
+                .unwrap()
+                .remove(0),
+        )
+        .unwrap();
+        assert_eq!(execution.input_params["content"], "salary");
+        let steps = db
+            .query_json("SELECT content FROM thinking_step")
+            .await
+            .unwrap();
+        assert_eq!(steps[0]["content"], "compare the salary offers");
+
+        WORKFLOW_VAULT.forget(&wf_id);
+    }
+
+    async fn search_salary(db: &DBClient, wf_id: &str) -> Vec<serde_json::Value> {
+        let params = crate::tools::memory::SearchParams {
+            query_text: "salary".to_string(),
+            limit: 10,
+            type_filter: None,
+            workflow_id: Some(wf_id.to_string()),
+            scope: "workflow".to_string(),
+            threshold: 0.7,
+        };
+        crate::tools::memory::search_memories_core(params, db, None)
+            .await
+            .unwrap()
+            .0
+    }
 }
@@ -0,0 +1,443 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow snapshot commands.
+//!
+//! Renders a workflow into a read-only HTML page (see
+//! [`crate::models::workflow_snapshot`]) and optionally serves it on a
+//! temporary `127.0.0.1` URL for screen-sharing. The URL path contains a
+//! random token, and the server stops after
+//! [`SERVER_TTL_SECS`](crate::tools::constants::snapshot::SERVER_TTL_SECS),
+//! when the workflow is served again, or with `stop_workflow_snapshot_server`.
+
+use crate::{
+    db::{queries::workflow as wf_queries, DBClient},
+    models::{
+        workflow_snapshot::{render_snapshot_html, WorkflowSnapshot},
+        Message, ToolExecution, Workflow,
+    },
+    security::{Validator, WORKFLOW_VAULT},
+    tools::{
+        constants::snapshot::{MAX_REQUEST_HEAD_BYTES, SERVER_TTL_SECS},
+        utils::load_locale_settings,
+    },
+    AppState,
+};
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::State;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Timeout for reading a request on the snapshot server
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Running snapshot servers (workflow_id -> cancellation token)
+static SNAPSHOT_SERVERS: Lazy<Mutex<HashMap<String, CancellationToken>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Creates a read-only HTML snapshot of a workflow.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow to render
+/// * `serve` - Also serve the page on a temporary localhost URL
+///
+/// # Returns
+/// The self-contained HTML page, with the URL and its expiry when served.
+/// Serving a workflow again replaces its previous server.
+#[tauri::command]
+#[instrument(name = "create_workflow_snapshot", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn create_workflow_snapshot(
+    workflow_id: String,
+    serve: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<WorkflowSnapshot, String> {
+    info!("Creating workflow snapshot");
+
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    let mut snapshot = build_workflow_snapshot(&state.db, &validated_id).await?;
+
+    if serve.unwrap_or(false) {
+        let (url, cancel) = start_snapshot_server(snapshot.html.clone(), SERVER_TTL_SECS)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to start snapshot server");
+                format!("Failed to start snapshot server: {}", e)
+            })?;
+
+        if let Some(previous) = SNAPSHOT_SERVERS
+            .lock()
+            .await
+            .insert(validated_id.clone(), cancel)
+        {
+            previous.cancel();
+        }
+
+        snapshot.expires_at = Some(Utc::now() + chrono::Duration::seconds(SERVER_TTL_SECS as i64));
+        snapshot.url = Some(url);
+    }
+
+    info!(
+        messages = snapshot.message_count,
+        tool_executions = snapshot.tool_execution_count,
+        served = snapshot.url.is_some(),
+        "Workflow snapshot created"
+    );
+    Ok(snapshot)
+}
+
+/// Stops the temporary snapshot server of a workflow.
+///
+/// # Returns
+/// True if a server was running for the workflow
+#[tauri::command]
+#[instrument(name = "stop_workflow_snapshot_server", fields(workflow_id = %workflow_id))]
+pub async fn stop_workflow_snapshot_server(workflow_id: String) -> Result<bool, String> {
+    let validated_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+
+    match SNAPSHOT_SERVERS.lock().await.remove(&validated_id) {
+        Some(cancel) => {
+            cancel.cancel();
+            info!("Snapshot server stopped");
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Loads a workflow with its messages and tool executions and renders it.
+async fn build_workflow_snapshot(
+    db: &DBClient,
+    workflow_id: &str,
+) -> Result<WorkflowSnapshot, String> {
+    let workflow_query = format!(
+        "{} WHERE meta::id(id) = $workflow_id",
+        wf_queries::SELECT_BASE
+    );
+
+    let messages_query = r#"SELECT
+            meta::id(id) AS id,
+            workflow_id,
+            role,
+            content,
+            tokens,
+            tokens_input,
+            tokens_output,
+            model,
+            provider,
+            cost_usd,
+            duration_ms,
+            context_usage,
+            timestamp
+        FROM message
+        WHERE workflow_id = $workflow_id
+        ORDER BY timestamp ASC"#;
+
+    let tools_query = r#"SELECT
+            meta::id(id) AS id,
+            workflow_id,
+            message_id,
+            agent_id,
+            tool_type,
+            tool_name,
+            server_name,
+            input_params,
+            output_result,
+            success,
+            error_message,
+            duration_ms,
+            iteration,
+            created_at
+        FROM tool_execution
+        WHERE workflow_id = $workflow_id
+        ORDER BY created_at ASC"#;
+    let params = vec![("workflow_id".to_string(), serde_json::json!(workflow_id))];
+
+    let workflow: Workflow = db
+        .query_json_with_params(&workflow_query, params.clone())
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Workflow not found".to_string())
+        .and_then(|value| {
+            serde_json::from_value(value)
+                .map_err(|e| format!("Failed to deserialize workflow: {}", e))
+        })?;
+
+    // Contents of a sensitive workflow are only exported while it is open
+    if workflow.sensitive && !WORKFLOW_VAULT.is_unlocked(&workflow.id) {
+        return Err("This workflow is sensitive and locked, open it first".to_string());
+    }
+
+    let mut messages: Vec<Message> = db
+        .query_json_with_params(messages_query, params.clone())
+        .await
+        .map_err(|e| format!("Failed to load messages: {}", e))?
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|e| format!("Failed to deserialize messages: {}", e))?;
+
+    let mut tool_executions: Vec<ToolExecution> = db
+        .query_json_with_params(tools_query, params)
+        .await
+        .map_err(|e| format!("Failed to load tool executions: {}", e))?
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|e| format!("Failed to deserialize tool executions: {}", e))?;
+    messages
+        .iter_mut()
+        .for_each(|m| WORKFLOW_VAULT.reveal_message(m));
+    tool_executions
+        .iter_mut()
+        .for_each(|te| WORKFLOW_VAULT.reveal_tool_execution(te));
+
+    let locale = load_locale_settings(db).await.locale;
+    let html = render_snapshot_html(&workflow, &messages, &tool_executions, locale, Utc::now());
+
+    Ok(WorkflowSnapshot {
+        workflow_id: workflow.id,
+        workflow_name: workflow.name,
+        html,
+        message_count: messages.len(),
+        tool_execution_count: tool_executions.len(),
+        url: None,
+        expires_at: None,
+    })
+}
+
+/// Serves a page on `127.0.0.1` under a random path until cancelled or expired.
+///
+/// # Returns
+/// The page URL and the token stopping the server
+async fn start_snapshot_server(
+    html: String,
+    ttl_secs: u64,
+) -> std::io::Result<(String, CancellationToken)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let path = format!("/{}", uuid::Uuid::new_v4().simple());
+    let url = format!("http://{}{}", listener.local_addr()?, path);
+    let cancel = CancellationToken::new();
+
+    let page: Arc<(String, String)> = Arc::new((path, html));
+    let server_cancel = cancel.clone();
+    tokio::spawn(async move {
+        let expiry = tokio::time::sleep(Duration::from_secs(ttl_secs));
+        tokio::pin!(expiry);
+        loop {
+            tokio::select! {
+                _ = server_cancel.cancelled() => break,
+                _ = &mut expiry => {
+                    debug!("Snapshot server expired");
+                    break;
+                }
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        tokio::spawn(handle_snapshot_request(stream, page.clone()));
+                    }
+                    Err(e) => warn!(error = %e, "Snapshot server accept failed"),
+                },
+            }
+        }
+    });
+
+    Ok((url, cancel))
+}
+
+/// Answers a single request: the page for `GET`/`HEAD` on its path, 404 otherwise.
+async fn handle_snapshot_request(mut stream: TcpStream, page: Arc<(String, String)>) {
+    let (ref path, ref html) = *page;
+
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    let read = tokio::time::timeout(REQUEST_READ_TIMEOUT, async {
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            if head.len() > MAX_REQUEST_HEAD_BYTES {
+                return false;
+            }
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return false,
+                Ok(n) => head.extend_from_slice(&buf[..n]),
+            }
+        }
+        true
+    })
+    .await;
+    if !matches!(read, Ok(true)) {
+        return;
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+
+    let (status, content_type, body) = match method {
+        "GET" | "HEAD" if target == path => ("200 OK", "text/html; charset=utf-8", html.as_str()),
+        "GET" | "HEAD" => ("404 Not Found", "text/plain; charset=utf-8", "Not Found"),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "Method Not Allowed",
+        ),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nX-Content-Type-Options: nosniff\r\n\
+         Referrer-Policy: no-referrer\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(body);
+    }
+
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        debug!(error = %e, "Failed to write snapshot response");
+    }
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{MessageCreate, ToolExecutionCreate, WorkflowCreate, WorkflowStatus};
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_build_workflow_snapshot() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("snapshot_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        let workflow = WorkflowCreate::new(
+            "Shared".to_string(),
+            "agent".to_string(),
+            WorkflowStatus::Idle,
+        );
+        db.create("workflow", "wf1", workflow)
+            .await
+            .expect("Failed to create workflow");
+
+        let message = MessageCreate {
+            workflow_id: "wf1".to_string(),
+            role: "assistant".to_string(),
+            content: "Found it".to_string(),
+            tokens: 0,
+            tokens_input: None,
+            tokens_output: None,
+            model: None,
+            provider: None,
+            cost_usd: None,
+            duration_ms: None,
+            context_usage: None,
+            read_aloud: None,
+            behavior_position: None,
+            generation: None,
+        };
+        db.create("message", "msg1", message)
+            .await
+            .expect("Failed to create message");
+
+        let execution = ToolExecutionCreate {
+            workflow_id: "wf1".to_string(),
+            message_id: "msg1".to_string(),
+            agent_id: "agent".to_string(),
+            tool_type: "mcp".to_string(),
+            tool_name: "fetch".to_string(),
+            server_name: Some("web".to_string()),
+            input_params: serde_json::json!({"url": "https://example.com", "api_key": "k-123"}),
+            output_result: serde_json::json!({"status": 200}),
+            success: true,
+            error_message: None,
+            duration_ms: 12,
+            iteration: 1,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
+        };
+        db.create("tool_execution", "exec1", execution)
+            .await
+            .expect("Failed to create tool execution");
+
+        let snapshot = build_workflow_snapshot(&db, "wf1").await.unwrap();
+        assert_eq!(snapshot.workflow_name, "Shared");
+        assert_eq!(snapshot.message_count, 1);
+        assert_eq!(snapshot.tool_execution_count, 1);
+        assert!(snapshot.html.contains("Found it"));
+        assert!(snapshot.html.contains("fetch (web)"));
+        assert!(!snapshot.html.contains("k-123"));
+        assert!(snapshot.url.is_none());
+
+        let missing = build_workflow_snapshot(&db, "unknown").await;
+        assert_eq!(missing.unwrap_err(), "Workflow not found");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_server() {
+        let (url, cancel) = start_snapshot_server("<p>snapshot</p>".to_string(), 60)
+            .await
+            .unwrap();
+        assert!(url.starts_with("http://127.0.0.1:"));
+
+        let client = reqwest::Client::new();
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(response.text().await.unwrap(), "<p>snapshot</p>");
+
+        let base = url.rsplit_once('/').unwrap().0;
+        let response = client.get(format!("{}/other", base)).send().await.unwrap();
+        assert_eq!(response.status(), 404);
+
+        let response = client.post(&url).send().await.unwrap();
+        assert_eq!(response.status(), 405);
+
+        cancel.cancel();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(client.get(&url).send().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_server_expires() {
+        let (url, _cancel) = start_snapshot_server("<p>snapshot</p>".to_string(), 0)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(reqwest::get(&url).await.is_err());
+    }
+}
@@ -0,0 +1,591 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow Tag Commands
+//!
+//! Tag catalog (`workflow_tag` table, one record per tag name with an
+//! optional color) and bulk tag operations. Workflows reference tags by name
+//! in their `tags` array, which stays the source of the list filter: renaming
+//! or deleting a catalog tag rewrites the arrays of every workflow carrying it.
+//! Tags set on a workflow are added to the catalog automatically.
+
+use crate::{
+    commands::workflow::normalize_tag,
+    db::DBClient,
+    models::{BulkTagResult, WorkflowTag},
+    security::Validator,
+    tools::constants::commands as cmd_const,
+    AppState,
+};
+use serde_json::json;
+use std::collections::HashMap;
+use tauri::State;
+use tracing::{error, info, instrument, warn};
+
+/// Normalizes a list of tags (deduplicated, order preserved)
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = normalize_tag(tag)?;
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    if normalized.is_empty() {
+        return Err("At least one tag is required".to_string());
+    }
+    Ok(normalized)
+}
+
+/// Validates a tag color (`#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`)
+fn validate_color(color: Option<String>) -> Result<Option<String>, String> {
+    let Some(color) = color.map(|c| c.trim().to_lowercase()) else {
+        return Ok(None);
+    };
+    if color.is_empty() {
+        return Ok(None);
+    }
+    let valid = color
+        .strip_prefix('#')
+        .filter(|hex| matches!(hex.len(), 3 | 4 | 6 | 8))
+        .is_some_and(|hex| hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if !valid {
+        return Err(format!("Invalid tag color '{}'", color));
+    }
+    Ok(Some(color))
+}
+
+/// Validates the workflow IDs of a bulk operation (deduplicated)
+fn validate_workflow_ids(workflow_ids: &[String]) -> Result<Vec<String>, String> {
+    if workflow_ids.is_empty() {
+        return Err("At least one workflow is required".to_string());
+    }
+    if workflow_ids.len() > cmd_const::MAX_BULK_TAG_WORKFLOWS {
+        return Err(format!(
+            "A bulk tag operation is limited to {} workflows",
+            cmd_const::MAX_BULK_TAG_WORKFLOWS
+        ));
+    }
+    let mut ids: Vec<String> = Vec::with_capacity(workflow_ids.len());
+    for id in workflow_ids {
+        let id = Validator::validate_uuid(id).map_err(|e| {
+            warn!(error = %e, "Invalid workflow_id");
+            format!("Invalid workflow_id: {}", e)
+        })?;
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    Ok(ids)
+}
+
+/// Adds tags to the catalog (existing tags keep their color)
+pub(crate) async fn register_tags(db: &DBClient, tags: &[String]) -> Result<(), String> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+    db.execute_with_params(
+        "FOR $tag IN $tags { UPSERT type::thing('workflow_tag', $tag) SET name = $tag; }",
+        vec![("tags".to_string(), json!(tags))],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to register workflow tags");
+        format!("Failed to register workflow tags: {}", e)
+    })
+}
+
+/// Counts the workflows of each tag
+async fn tag_counts(db: &DBClient) -> Result<HashMap<String, u32>, String> {
+    let rows = db
+        .query_json("SELECT tags AS tag, count() AS count FROM (SELECT tags FROM workflow SPLIT tags) GROUP BY tag")
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to count workflow tags");
+            format!("Failed to count workflow tags: {}", e)
+        })?;
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let tag = row.get("tag")?.as_str()?.to_string();
+            let count = row.get("count")?.as_u64()? as u32;
+            Some((tag, count))
+        })
+        .collect())
+}
+
+/// Lists the tag catalog, including tags carried by workflows only
+pub(crate) async fn list_tags(db: &DBClient) -> Result<Vec<WorkflowTag>, String> {
+    let mut counts = tag_counts(db).await?;
+    let catalog = db
+        .query_json("SELECT name, color FROM workflow_tag")
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow tags");
+            format!("Failed to load workflow tags: {}", e)
+        })?;
+
+    let mut tags: Vec<WorkflowTag> = catalog
+        .into_iter()
+        .filter_map(|row| serde_json::from_value::<WorkflowTag>(row).ok())
+        .map(|mut tag| {
+            tag.workflow_count = counts.remove(&tag.name).unwrap_or(0);
+            tag
+        })
+        .collect();
+    tags.extend(
+        counts
+            .into_iter()
+            .map(|(name, workflow_count)| WorkflowTag {
+                name,
+                color: None,
+                workflow_count,
+            }),
+    );
+    tags.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tags)
+}
+
+/// Loads one tag of the catalog (`None` when unknown and unused)
+async fn find_tag(db: &DBClient, name: &str) -> Result<Option<WorkflowTag>, String> {
+    Ok(list_tags(db).await?.into_iter().find(|t| t.name == name))
+}
+
+/// Renames a tag in the catalog and in every workflow carrying it
+///
+/// Renaming onto an existing tag merges both (the target keeps its color).
+pub(crate) async fn rename_tag(
+    db: &DBClient,
+    name: &str,
+    new_name: &str,
+) -> Result<WorkflowTag, String> {
+    let name = normalize_tag(name)?;
+    let new_name = normalize_tag(new_name)?;
+    let Some(tag) = find_tag(db, &name).await? else {
+        return Err(format!("Tag '{}' not found", name));
+    };
+    if name == new_name {
+        return Ok(tag);
+    }
+
+    db.execute_with_params(
+        "BEGIN TRANSACTION; \
+         UPDATE workflow SET tags = array::union(array::complement(tags, [$old]), [$new]) \
+             WHERE $old IN tags; \
+         DELETE type::thing('workflow_tag', $old); \
+         UPSERT type::thing('workflow_tag', $new) SET name = $new, color = color ?? $color; \
+         COMMIT TRANSACTION;",
+        vec![
+            ("old".to_string(), json!(name)),
+            ("new".to_string(), json!(new_name)),
+            ("color".to_string(), json!(tag.color)),
+        ],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to rename workflow tag");
+        format!("Failed to rename workflow tag: {}", e)
+    })?;
+
+    find_tag(db, &new_name)
+        .await?
+        .ok_or_else(|| format!("Tag '{}' not found", new_name))
+}
+
+/// Deletes a tag from the catalog and from every workflow carrying it
+///
+/// # Returns
+/// Number of workflows untagged
+pub(crate) async fn delete_tag(db: &DBClient, name: &str) -> Result<u32, String> {
+    let name = normalize_tag(name)?;
+    let untagged = find_tag(db, &name)
+        .await?
+        .map(|t| t.workflow_count)
+        .unwrap_or(0);
+
+    db.execute_with_params(
+        "BEGIN TRANSACTION; \
+         UPDATE workflow SET tags = array::complement(tags, [$name]) WHERE $name IN tags; \
+         DELETE type::thing('workflow_tag', $name); \
+         COMMIT TRANSACTION;",
+        vec![("name".to_string(), json!(name))],
+    )
+    .await
+    .map_err(|e| {
+        error!(error = %e, "Failed to delete workflow tag");
+        format!("Failed to delete workflow tag: {}", e)
+    })?;
+    Ok(untagged)
+}
+
+/// Loads the current tags of the given workflows
+async fn load_workflow_tags(
+    db: &DBClient,
+    ids: &[String],
+) -> Result<HashMap<String, Vec<String>>, String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id, (tags ?? []) AS tags FROM workflow WHERE meta::id(id) IN $ids",
+            vec![("ids".to_string(), json!(ids))],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to load workflow tags");
+            format!("Failed to load workflow tags: {}", e)
+        })?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let id = row.get("id")?.as_str()?.to_string();
+            let tags = serde_json::from_value(row.get("tags")?.clone()).ok()?;
+            Some((id, tags))
+        })
+        .collect())
+}
+
+/// Adds tags to several workflows
+///
+/// Workflows that do not exist or would exceed the tag limit are skipped.
+pub(crate) async fn add_tags(
+    db: &DBClient,
+    workflow_ids: &[String],
+    tags: &[String],
+) -> Result<BulkTagResult, String> {
+    let ids = validate_workflow_ids(workflow_ids)?;
+    let tags = normalize_tags(tags)?;
+    let current = load_workflow_tags(db, &ids).await?;
+
+    let (updated, skipped): (Vec<String>, Vec<String>) = ids.into_iter().partition(|id| {
+        current.get(id).is_some_and(|existing| {
+            let added = tags.iter().filter(|t| !existing.contains(t)).count();
+            existing.len() + added <= cmd_const::MAX_WORKFLOW_TAGS
+        })
+    });
+
+    if !updated.is_empty() {
+        db.execute_with_params(
+            "UPDATE workflow SET tags = array::union(tags, $tags) WHERE meta::id(id) IN $ids",
+            vec![
+                ("ids".to_string(), json!(updated)),
+                ("tags".to_string(), json!(tags)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to add workflow tags");
+            format!("Failed to add workflow tags: {}", e)
+        })?;
+        register_tags(db, &tags).await?;
+    }
+
+    Ok(BulkTagResult {
+        updated: updated.len() as u32,
+        skipped,
+    })
+}
+
+/// Removes tags from several workflows
+///
+/// Workflows that do not exist are skipped.
+pub(crate) async fn remove_tags(
+    db: &DBClient,
+    workflow_ids: &[String],
+    tags: &[String],
+) -> Result<BulkTagResult, String> {
+    let ids = validate_workflow_ids(workflow_ids)?;
+    let tags = normalize_tags(tags)?;
+    let current = load_workflow_tags(db, &ids).await?;
+
+    let (updated, skipped): (Vec<String>, Vec<String>) =
+        ids.into_iter().partition(|id| current.contains_key(id));
+
+    if !updated.is_empty() {
+        db.execute_with_params(
+            "UPDATE workflow SET tags = array::complement(tags, $tags) WHERE meta::id(id) IN $ids",
+            vec![
+                ("ids".to_string(), json!(updated)),
+                ("tags".to_string(), json!(tags)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to remove workflow tags");
+            format!("Failed to remove workflow tags: {}", e)
+        })?;
+    }
+
+    Ok(BulkTagResult {
+        updated: updated.len() as u32,
+        skipped,
+    })
+}
+
+/// Lists the workflow tags with their number of workflows
+///
+/// # Returns
+/// Tags sorted by name, including tags not yet in the catalog
+#[tauri::command]
+#[instrument(name = "list_workflow_tags", skip(state))]
+pub async fn list_workflow_tags(state: State<'_, AppState>) -> Result<Vec<WorkflowTag>, String> {
+    let tags = list_tags(&state.db).await?;
+    info!(count = tags.len(), "Workflow tags loaded");
+    Ok(tags)
+}
+
+/// Creates a tag in the catalog
+///
+/// # Arguments
+/// * `name` - Tag name (trimmed, lowercased)
+/// * `color` - Optional display color (`#rrggbb`)
+#[tauri::command]
+#[instrument(name = "create_workflow_tag", skip(state))]
+pub async fn create_workflow_tag(
+    name: String,
+    color: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WorkflowTag, String> {
+    let name = normalize_tag(&name)?;
+    let color = validate_color(color)?;
+    if find_tag(&state.db, &name).await?.is_some() {
+        return Err(format!("Tag '{}' already exists", name));
+    }
+
+    state
+        .db
+        .execute_with_params(
+            "CREATE type::thing('workflow_tag', $name) SET name = $name, color = $color",
+            vec![
+                ("name".to_string(), json!(name)),
+                ("color".to_string(), json!(color)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to create workflow tag");
+            format!("Failed to create workflow tag: {}", e)
+        })?;
+
+    info!(tag = %name, "Workflow tag created");
+    Ok(WorkflowTag {
+        name,
+        color,
+        workflow_count: 0,
+    })
+}
+
+/// Renames a tag in the catalog and in every workflow carrying it
+#[tauri::command]
+#[instrument(name = "rename_workflow_tag", skip(state))]
+pub async fn rename_workflow_tag(
+    name: String,
+    new_name: String,
+    state: State<'_, AppState>,
+) -> Result<WorkflowTag, String> {
+    let tag = rename_tag(&state.db, &name, &new_name).await?;
+    info!(tag = %tag.name, workflows = tag.workflow_count, "Workflow tag renamed");
+    Ok(tag)
+}
+
+/// Sets or clears the display color of a tag
+#[tauri::command]
+#[instrument(name = "set_workflow_tag_color", skip(state))]
+pub async fn set_workflow_tag_color(
+    name: String,
+    color: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WorkflowTag, String> {
+    let name = normalize_tag(&name)?;
+    let color = validate_color(color)?;
+    let Some(mut tag) = find_tag(&state.db, &name).await? else {
+        return Err(format!("Tag '{}' not found", name));
+    };
+
+    state
+        .db
+        .execute_with_params(
+            "UPSERT type::thing('workflow_tag', $name) SET name = $name, color = $color",
+            vec![
+                ("name".to_string(), json!(name)),
+                ("color".to_string(), json!(color)),
+            ],
+        )
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to update workflow tag color");
+            format!("Failed to update workflow tag color: {}", e)
+        })?;
+
+    tag.color = color;
+    Ok(tag)
+}
+
+/// Deletes a tag from the catalog and from every workflow carrying it
+///
+/// # Returns
+/// Number of workflows untagged
+#[tauri::command]
+#[instrument(name = "delete_workflow_tag", skip(state))]
+pub async fn delete_workflow_tag(name: String, state: State<'_, AppState>) -> Result<u32, String> {
+    let untagged = delete_tag(&state.db, &name).await?;
+    info!(workflows = untagged, "Workflow tag deleted");
+    Ok(untagged)
+}
+
+/// Adds tags to several workflows at once
+///
+/// # Returns
+/// Number of workflows updated and the IDs skipped (unknown workflow, or the
+/// tag limit would be exceeded)
+#[tauri::command]
+#[instrument(name = "add_workflow_tags", skip(state, workflow_ids), fields(count = workflow_ids.len()))]
+pub async fn add_workflow_tags(
+    workflow_ids: Vec<String>,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<BulkTagResult, String> {
+    let result = add_tags(&state.db, &workflow_ids, &tags).await?;
+    info!(
+        updated = result.updated,
+        skipped = result.skipped.len(),
+        "Workflow tags added"
+    );
+    Ok(result)
+}
+
+/// Removes tags from several workflows at once
+///
+/// # Returns
+/// Number of workflows updated and the IDs skipped (unknown workflow)
+#[tauri::command]
+#[instrument(name = "remove_workflow_tags", skip(state, workflow_ids), fields(count = workflow_ids.len()))]
+pub async fn remove_workflow_tags(
+    workflow_ids: Vec<String>,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<BulkTagResult, String> {
+    let result = remove_tags(&state.db, &workflow_ids, &tags).await?;
+    info!(
+        updated = result.updated,
+        skipped = result.skipped.len(),
+        "Workflow tags removed"
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{WorkflowCreate, WorkflowStatus};
+    use tempfile::tempdir;
+
+    async fn create_workflow(db: &DBClient, tags: &[&str]) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        db.create(
+            "workflow",
+            &id,
+            WorkflowCreate::new("wf".into(), "agent".into(), WorkflowStatus::Idle),
+        )
+        .await
+        .unwrap();
+        db.execute_with_params(
+            "UPDATE type::thing('workflow', $id) SET tags = $tags",
+            vec![
+                ("id".to_string(), json!(id)),
+                ("tags".to_string(), json!(tags)),
+            ],
+        )
+        .await
+        .unwrap();
+        id
+    }
+
+    async fn tags_of(db: &DBClient, id: &str) -> Vec<String> {
+        load_workflow_tags(db, &[id.to_string()])
+            .await
+            .unwrap()
+            .remove(id)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validate_color() {
+        assert_eq!(
+            validate_color(Some(" #3B82F6 ".into())).unwrap(),
+            Some("#3b82f6".to_string())
+        );
+        assert_eq!(validate_color(Some(String::new())).unwrap(), None);
+        assert_eq!(validate_color(None).unwrap(), None);
+        assert!(validate_color(Some("#12345".into())).is_err());
+        assert!(validate_color(Some("red".into())).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_tag_catalog_and_bulk_operations() {
+        let temp_dir = tempdir().unwrap();
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let a = create_workflow(&db, &["docs"]).await;
+        let b = create_workflow(&db, &["docs", "draft"]).await;
+        let full_tags: Vec<String> = (0..cmd_const::MAX_WORKFLOW_TAGS)
+            .map(|i| format!("t{}", i))
+            .collect();
+        let full_refs: Vec<&str> = full_tags.iter().map(String::as_str).collect();
+        let full = create_workflow(&db, &full_refs).await;
+        let missing = uuid::Uuid::new_v4().to_string();
+
+        // Bulk add skips unknown workflows and workflows at the tag limit
+        let result = add_tags(
+            &db,
+            &[a.clone(), b.clone(), full.clone(), missing.clone()],
+            &[" Release ".to_string()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.updated, 2);
+        assert_eq!(result.skipped, vec![full.clone(), missing]);
+        assert_eq!(tags_of(&db, &b).await, vec!["docs", "draft", "release"]);
+
+        // Tags set on workflows only are listed with their counts
+        let tags = list_tags(&db).await.unwrap();
+        let docs = tags.iter().find(|t| t.name == "docs").unwrap();
+        assert_eq!(docs.workflow_count, 2);
+        let release = tags.iter().find(|t| t.name == "release").unwrap();
+        assert_eq!(release.workflow_count, 2);
+
+        // Renaming onto an existing tag merges both
+        let renamed = rename_tag(&db, "draft", "DOCS").await.unwrap();
+        assert_eq!(renamed.name, "docs");
+        assert_eq!(renamed.workflow_count, 2);
+        assert_eq!(tags_of(&db, &b).await, vec!["docs", "release"]);
+        assert!(rename_tag(&db, "unknown", "other").await.is_err());
+
+        // Bulk remove and delete untag workflows
+        let result = remove_tags(&db, std::slice::from_ref(&a), &["release".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(result.updated, 1);
+        assert_eq!(tags_of(&db, &a).await, vec!["docs"]);
+        assert_eq!(delete_tag(&db, "docs").await.unwrap(), 2);
+        assert!(tags_of(&db, &a).await.is_empty());
+        assert_eq!(tags_of(&db, &b).await, vec!["release"]);
+        assert!(list_tags(&db)
+            .await
+            .unwrap()
+            .iter()
+            .all(|t| t.name != "docs"));
+    }
+}
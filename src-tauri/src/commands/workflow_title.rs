@@ -0,0 +1,336 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow Title Commands
+//!
+//! Generates a concise workflow title from the first exchange with a short
+//! completion on the workflow agent's model. The title is generated once in
+//! the background after the first streamed exchange, and on demand with
+//! [`regenerate_title`]. Sensitive workflows keep their name: the title would
+//! be stored in clear.
+
+use crate::{
+    db::DBClient,
+    llm::ProviderManager,
+    models::{streaming::events, workflow::WorkflowTitleUpdatedEvent, AgentConfig},
+    security::{Validator, WORKFLOW_VAULT},
+    tools::constants::workflow_title as title_const,
+    AppState,
+};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, State};
+use tracing::{debug, info, instrument, warn};
+
+/// Instructions of the title completion
+const TITLE_SYSTEM_PROMPT: &str = "You write short titles for conversations. Reply with the \
+     title only: at most 6 words, in the language of the conversation, without quotes nor \
+     final punctuation.";
+
+/// Builds the title request from the first exchange.
+fn title_prompt(user_message: &str, response: &str) -> String {
+    let excerpt = |text: &str| -> String {
+        text.trim()
+            .chars()
+            .take(title_const::EXCERPT_CHARS)
+            .collect()
+    };
+    let mut prompt = format!("User:\n{}\n", excerpt(user_message));
+    if !response.trim().is_empty() {
+        prompt.push_str(&format!("\nAssistant:\n{}\n", excerpt(response)));
+    }
+    prompt.push_str("\nTitle of this conversation:");
+    prompt
+}
+
+/// Extracts the title from the completion (first line, without label,
+/// quotes, markdown or final punctuation), or None if nothing is left.
+fn clean_title(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let line = match line.split_once(':') {
+        Some((label, rest)) if label.trim().eq_ignore_ascii_case("title") => rest,
+        _ => line,
+    };
+    let line = line
+        .trim_matches(|c: char| c.is_whitespace() || "\"'`*#_«»“”".contains(c))
+        .trim_end_matches(['.', ':', ';', ',', '!']);
+    let title: String = line
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(title_const::MAX_TITLE_CHARS)
+        .collect();
+    let title = title.trim_end().to_string();
+    (!title.is_empty()).then_some(title)
+}
+
+/// Asks the agent's model for a title of the exchange.
+async fn generate_title(
+    llm_manager: &ProviderManager,
+    config: &AgentConfig,
+    user_message: &str,
+    response: &str,
+) -> Result<String, String> {
+    let provider = config
+        .llm
+        .provider
+        .parse()
+        .map_err(|e| format!("Invalid provider: {}", e))?;
+    let completion = llm_manager
+        .complete_with_provider(
+            provider,
+            &title_prompt(user_message, response),
+            Some(TITLE_SYSTEM_PROMPT),
+            Some(&config.llm.model),
+            title_const::TEMPERATURE,
+            title_const::MAX_TOKENS,
+            None,
+        )
+        .await
+        .map_err(|e| format!("Failed to generate title: {}", e))?;
+
+    let title = clean_title(&completion.content).ok_or("The model returned an empty title")?;
+    Validator::validate_workflow_name(&title).map_err(|e| format!("Invalid title: {}", e))
+}
+
+/// Stores a generated title as the workflow name and notifies the frontend.
+async fn apply_title(
+    db: &DBClient,
+    app_handle: Option<&AppHandle>,
+    workflow_id: &str,
+    title: &str,
+) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('workflow', $id) SET name = $name, title_generated = true",
+        vec![
+            ("id".to_string(), json!(workflow_id)),
+            ("name".to_string(), json!(title)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update workflow title: {}", e))?;
+
+    if let Some(handle) = app_handle {
+        let event = WorkflowTitleUpdatedEvent {
+            workflow_id: workflow_id.to_string(),
+            title: title.to_string(),
+        };
+        if let Err(e) = handle.emit(events::WORKFLOW_TITLE_UPDATED, &event) {
+            warn!(error = %e, "Failed to emit workflow_title_updated event");
+        }
+    }
+    Ok(())
+}
+
+/// Whether the workflow title was already generated.
+async fn title_generated(db: &DBClient, workflow_id: &str) -> Result<bool, String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT title_generated FROM workflow WHERE id = type::thing('workflow', $id)",
+            vec![("id".to_string(), json!(workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?;
+    Ok(rows
+        .first()
+        .and_then(|row| row["title_generated"].as_bool())
+        .unwrap_or(false))
+}
+
+/// Generates the title of a workflow in the background after its first exchange.
+///
+/// Does nothing for sensitive workflows or when a title was already generated.
+pub(crate) fn spawn_title_generation(
+    state: &AppState,
+    workflow_id: String,
+    agent_id: String,
+    user_message: String,
+    response: String,
+) {
+    let db = state.db.clone();
+    let llm_manager = state.llm_manager.clone();
+    let registry = state.registry.clone();
+    let app_handle = state.get_app_handle();
+
+    tokio::spawn(async move {
+        match title_generated(&db, &workflow_id).await {
+            Ok(false) => {}
+            Ok(true) => return,
+            Err(e) => {
+                warn!(error = %e, "Failed to check the workflow title");
+                return;
+            }
+        }
+        if WORKFLOW_VAULT
+            .is_sensitive(&db, &workflow_id)
+            .await
+            .unwrap_or(true)
+        {
+            debug!(workflow_id = %workflow_id, "Sensitive workflow, no title generated");
+            return;
+        }
+        let Some(agent) = registry.get(&agent_id).await else {
+            return;
+        };
+
+        let result =
+            match generate_title(&llm_manager, agent.config(), &user_message, &response).await {
+                Ok(title) => apply_title(&db, app_handle.as_ref(), &workflow_id, &title)
+                    .await
+                    .map(|_| title),
+                Err(e) => Err(e),
+            };
+        match result {
+            Ok(title) => {
+                info!(workflow_id = %workflow_id, title = %title, "Workflow title generated")
+            }
+            Err(e) => warn!(workflow_id = %workflow_id, error = %e, "Workflow title not generated"),
+        }
+    });
+}
+
+/// Regenerates the title of a workflow from its first exchange.
+///
+/// # Arguments
+/// * `workflow_id` - The workflow ID
+///
+/// # Returns
+/// The new workflow name
+#[tauri::command]
+#[instrument(name = "regenerate_title", skip(state), fields(workflow_id = %workflow_id))]
+pub async fn regenerate_title(
+    workflow_id: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let workflow_id = Validator::validate_uuid(&workflow_id).map_err(|e| {
+        warn!(error = %e, "Invalid workflow_id");
+        format!("Invalid workflow_id: {}", e)
+    })?;
+    if WORKFLOW_VAULT.is_sensitive(&state.db, &workflow_id).await? {
+        return Err("Titles are not generated for sensitive workflows".to_string());
+    }
+
+    let params = vec![("id".to_string(), json!(workflow_id))];
+    let agent_id = state
+        .db
+        .query_json_with_params(
+            "SELECT agent_id FROM workflow WHERE id = type::thing('workflow', $id)",
+            params.clone(),
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?
+        .first()
+        .and_then(|row| row["agent_id"].as_str().map(str::to_string))
+        .ok_or("Workflow not found")?;
+    let agent = state
+        .registry
+        .get(&agent_id)
+        .await
+        .ok_or("Agent not found")?;
+
+    let messages = state
+        .db
+        .query_json_with_params(
+            "SELECT role, content, timestamp FROM message \
+             WHERE workflow_id = $id AND role IN ['user', 'assistant'] \
+             ORDER BY timestamp ASC LIMIT 10",
+            params,
+        )
+        .await
+        .map_err(|e| format!("Failed to load messages: {}", e))?;
+    let role_content = |role: &str| {
+        messages
+            .iter()
+            .find(|m| m["role"] == role)
+            .and_then(|m| m["content"].as_str().map(str::to_string))
+    };
+    let user_message = role_content("user").ok_or("The workflow has no message yet")?;
+    let response = role_content("assistant").unwrap_or_default();
+
+    let title =
+        generate_title(&state.llm_manager, agent.config(), &user_message, &response).await?;
+    apply_title(
+        &state.db,
+        state.get_app_handle().as_ref(),
+        &workflow_id,
+        &title,
+    )
+    .await?;
+
+    info!(title = %title, "Workflow title regenerated");
+    Ok(title)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_clean_title() {
+        assert_eq!(
+            clean_title("\n  Title: \"Trip to Lisbon.\"\nMore text").as_deref(),
+            Some("Trip to Lisbon")
+        );
+        assert_eq!(
+            clean_title("**Rust   borrow checker**").as_deref(),
+            Some("Rust borrow checker")
+        );
+        assert_eq!(
+            clean_title("Note: use rustup").as_deref(),
+            Some("Note: use rustup")
+        );
+        assert_eq!(clean_title(" \n\"\" ").as_deref(), None);
+        assert_eq!(
+            clean_title(&"a".repeat(200)).map(|t| t.chars().count()),
+            Some(title_const::MAX_TITLE_CHARS)
+        );
+    }
+
+    #[test]
+    fn test_title_prompt() {
+        let prompt = title_prompt("How do I plan a trip?", "");
+        assert!(prompt.contains("How do I plan a trip?"));
+        assert!(!prompt.contains("Assistant:"));
+
+        let long = "x".repeat(title_const::EXCERPT_CHARS + 100);
+        let prompt = title_prompt("Hi", &long);
+        assert!(prompt.contains("Assistant:"));
+        assert!(!prompt.contains(&long));
+    }
+
+    #[tokio::test]
+    async fn test_apply_title() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("title_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        db.execute(
+            "CREATE workflow:wf CONTENT { name: 'New Workflow', agent_id: 'a', status: 'idle' }",
+        )
+        .await
+        .expect("Failed to create workflow");
+        assert!(!title_generated(&db, "wf").await.unwrap());
+
+        apply_title(&db, None, "wf", "Trip to Lisbon")
+            .await
+            .unwrap();
+        assert!(title_generated(&db, "wf").await.unwrap());
+        let rows = db.query_json("SELECT name FROM workflow:wf").await.unwrap();
+        assert_eq!(rows[0]["name"], "Trip to Lisbon");
+    }
+}
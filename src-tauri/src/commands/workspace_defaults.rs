@@ -0,0 +1,167 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workspace defaults commands.
+//!
+//! Defaults are stored in the `settings:workspace_defaults` record and
+//! applied by [`crate::commands::agent::create_agent`] to agents created with
+//! `inherit_workspace_defaults`.
+
+use crate::commands::agent::{validate_mcp_servers, validate_tools};
+use crate::db::{queries::settings, DBClient};
+use crate::models::workspace_defaults::WorkspaceDefaults;
+use crate::AppState;
+use tauri::State;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Storage key of the workspace defaults
+const WORKSPACE_DEFAULTS_KEY: &str = "settings:workspace_defaults";
+
+/// Loads workspace defaults from the database, falling back to empty defaults.
+pub async fn load_workspace_defaults(db: &DBClient) -> WorkspaceDefaults {
+    match settings::load_config(db, WORKSPACE_DEFAULTS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No workspace defaults found, using empty defaults");
+            WorkspaceDefaults::default()
+        }
+    }
+}
+
+/// Gets the workspace default tools and MCP servers.
+/// Returns empty defaults if none are configured.
+#[tauri::command]
+#[instrument(name = "get_workspace_defaults", skip(state))]
+pub async fn get_workspace_defaults(
+    state: State<'_, AppState>,
+) -> Result<WorkspaceDefaults, String> {
+    info!("Loading workspace defaults");
+    Ok(load_workspace_defaults(&state.db).await)
+}
+
+/// Updates the workspace default tools and MCP servers.
+///
+/// Existing agents are not modified; the defaults apply to agents created
+/// afterwards.
+///
+/// # Arguments
+/// * `defaults` - The complete defaults to store
+///
+/// # Returns
+/// The stored (validated) defaults
+#[tauri::command]
+#[instrument(name = "update_workspace_defaults", skip(state, defaults))]
+pub async fn update_workspace_defaults(
+    defaults: WorkspaceDefaults,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceDefaults, String> {
+    info!("Updating workspace defaults");
+
+    let validated = validate_workspace_defaults(&defaults).map_err(|e| {
+        warn!(error = %e, "Invalid workspace defaults");
+        e
+    })?;
+
+    let json_config = serde_json::to_value(&validated).map_err(|e| {
+        error!(error = %e, "Failed to serialize workspace defaults");
+        format!("Failed to serialize workspace defaults: {}", e)
+    })?;
+
+    settings::save_config(&state.db, WORKSPACE_DEFAULTS_KEY, &json_config)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "Failed to save workspace defaults");
+            format!("Failed to save workspace defaults: {}", e)
+        })?;
+
+    info!(
+        tools = validated.tools.len(),
+        mcp_servers = validated.mcp_servers.len(),
+        "Workspace defaults updated successfully"
+    );
+    Ok(validated)
+}
+
+/// Validates tool and MCP server names, dropping blanks and duplicates
+fn validate_workspace_defaults(defaults: &WorkspaceDefaults) -> Result<WorkspaceDefaults, String> {
+    let names = WorkspaceDefaults {
+        tools: validate_tools(&defaults.tools)?,
+        mcp_servers: validate_mcp_servers(&defaults.mcp_servers)?,
+    };
+    let mut validated = WorkspaceDefaults::default();
+    names.apply(&mut validated.tools, &mut validated.mcp_servers);
+    Ok(validated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_validate_workspace_defaults() {
+        let defaults = WorkspaceDefaults {
+            tools: vec![
+                "MemoryTool".to_string(),
+                " ".to_string(),
+                "MemoryTool".to_string(),
+            ],
+            mcp_servers: vec!["serena".to_string()],
+        };
+        let validated = validate_workspace_defaults(&defaults).unwrap();
+        assert_eq!(validated.tools, vec!["MemoryTool"]);
+        assert_eq!(validated.mcp_servers, vec!["serena"]);
+
+        let defaults = WorkspaceDefaults {
+            tools: vec!["UnknownTool".to_string()],
+            mcp_servers: vec![],
+        };
+        assert!(validate_workspace_defaults(&defaults).is_err());
+
+        let defaults = WorkspaceDefaults {
+            tools: vec![],
+            mcp_servers: vec!["bad name!".to_string()],
+        };
+        assert!(validate_workspace_defaults(&defaults).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_workspace_defaults() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("workspace_defaults_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+
+        assert_eq!(
+            load_workspace_defaults(&db).await,
+            WorkspaceDefaults::default()
+        );
+
+        settings::save_config(
+            &db,
+            WORKSPACE_DEFAULTS_KEY,
+            &serde_json::json!({ "tools": ["MemoryTool"], "mcp_servers": ["serena"] }),
+        )
+        .await
+        .unwrap();
+
+        let defaults = load_workspace_defaults(&db).await;
+        assert_eq!(defaults.tools, vec!["MemoryTool"]);
+        assert_eq!(defaults.mcp_servers, vec!["serena"]);
+    }
+}
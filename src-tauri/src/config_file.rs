@@ -0,0 +1,334 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Optional config file (`<data dir>/config.toml`, i.e. `~/.zileo/config.toml`).
+//!
+//! Read once at startup, before logging starts, it overrides the database of
+//! the default profile, the log filter, the provider selected at startup, the
+//! HTTP proxy and the feature flags of the startup subsystems. Environment
+//! variables (`RUST_LOG`, `HTTPS_PROXY`...) take precedence over the file.
+//! A missing file is not an error; an unreadable or invalid file is ignored
+//! and its error reported by `get_effective_config`.
+
+use crate::models::config_file::{
+    ConfigSource, EffectiveConfig, EffectiveSetting, Feature, FileConfig,
+};
+use crate::models::proxy::ProxySettings;
+use crate::tools::constants::config_file::{DEFAULT_LOG_FILTER, FILE_NAME, PROXY_ENV_VARS};
+use crate::tools::constants::profiles::DB_DIR_NAME;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Config file as loaded at startup
+#[derive(Debug, Clone, Default)]
+pub struct LoadedConfig {
+    /// Path of the config file
+    path: PathBuf,
+    /// Whether the file exists and was loaded
+    loaded: bool,
+    /// Error reading or parsing the file
+    error: Option<String>,
+    /// Content of the file (default when not loaded)
+    file: FileConfig,
+    /// `RUST_LOG` at startup
+    env_log_filter: Option<String>,
+    /// Proxy environment variable at startup (name, value)
+    env_proxy: Option<(String, String)>,
+    /// `NO_PROXY` at startup
+    env_no_proxy: Option<String>,
+}
+
+impl LoadedConfig {
+    /// Loads `config.toml` from the data directory.
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(FILE_NAME);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => Some(Ok(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => Some(Err(format!("Failed to read config file: {}", e))),
+        };
+        let env_log_filter = std::env::var("RUST_LOG").ok().filter(|v| !v.is_empty());
+        let env_proxy = PROXY_ENV_VARS.iter().find_map(|name| {
+            std::env::var(name)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| (name.to_string(), v))
+        });
+        let mut config = Self::parse(path, content, env_log_filter, env_proxy);
+        config.env_no_proxy = ["NO_PROXY", "no_proxy"]
+            .iter()
+            .find_map(|name| std::env::var(name).ok());
+        config
+    }
+
+    fn parse(
+        path: PathBuf,
+        content: Option<Result<String, String>>,
+        env_log_filter: Option<String>,
+        env_proxy: Option<(String, String)>,
+    ) -> Self {
+        let mut config = Self {
+            path,
+            env_log_filter,
+            env_proxy,
+            ..Default::default()
+        };
+        match content {
+            None => {}
+            Some(Err(e)) => config.error = Some(e),
+            Some(Ok(content)) => match toml::from_str::<FileConfig>(&content) {
+                Ok(file) => {
+                    config.file = file;
+                    config.loaded = true;
+                }
+                Err(e) => {
+                    config.error = Some(format!("Invalid config file: {}", e.message()));
+                }
+            },
+        }
+        config
+    }
+
+    /// Path of the config file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Error reading or parsing the file, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Database directory of the default profile.
+    pub fn default_db_path(&self, data_dir: &Path) -> PathBuf {
+        self.file
+            .db_path
+            .clone()
+            .unwrap_or_else(|| data_dir.join(DB_DIR_NAME))
+    }
+
+    /// Log filter (`RUST_LOG`, then the file, then the default).
+    pub fn log_filter(&self) -> &str {
+        self.env_log_filter
+            .as_deref()
+            .or(self.file.log_level.as_deref())
+            .unwrap_or(DEFAULT_LOG_FILTER)
+    }
+
+    /// Provider to select at startup, if set in the file.
+    pub fn default_provider(&self) -> Option<&str> {
+        self.file.default_provider.as_deref()
+    }
+
+    /// Proxy settings of the file, replacing the saved ones.
+    pub fn proxy(&self) -> Option<&ProxySettings> {
+        self.file.proxy.as_ref()
+    }
+
+    /// Proxy set by the environment at startup (takes precedence over the
+    /// global proxy of the file and of the saved settings).
+    pub fn env_proxy(&self) -> Option<ProxySettings> {
+        let (_, url) = self.env_proxy.as_ref()?;
+        Some(ProxySettings {
+            url: Some(url.clone()),
+            no_proxy: self
+                .env_no_proxy
+                .iter()
+                .flat_map(|hosts| hosts.split(','))
+                .map(|host| host.trim().to_string())
+                .filter(|host| !host.is_empty())
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Whether a startup subsystem is enabled.
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.file.features.is_enabled(feature)
+    }
+
+    /// Exports the global proxy of the file to the environment read by the
+    /// other HTTP clients and inherited by the MCP server processes.
+    ///
+    /// Does nothing when a proxy environment variable is already set. Must be
+    /// called before any HTTP client is built (i.e. at the start of `main`).
+    pub fn apply_proxy(&self) {
+        let Some(proxy) = &self.file.proxy else {
+            return;
+        };
+        let Some(url) = &proxy.url else {
+            return;
+        };
+        if self.env_proxy.is_some() {
+            return;
+        }
+        std::env::set_var("HTTP_PROXY", url);
+        std::env::set_var("HTTPS_PROXY", url);
+        if !proxy.no_proxy.is_empty() {
+            std::env::set_var("NO_PROXY", proxy.no_proxy.join(","));
+        }
+    }
+
+    /// Settings in effect and where they come from.
+    ///
+    /// # Arguments
+    /// * `db_path` - Database directory of the default profile
+    pub fn effective(&self, db_path: &Path) -> EffectiveConfig {
+        let file_or_default = |set: bool| {
+            if set {
+                ConfigSource::ConfigFile
+            } else {
+                ConfigSource::Default
+            }
+        };
+        let mut settings = Vec::new();
+        let mut push = |key: &str, value: Value, source: ConfigSource| {
+            settings.push(EffectiveSetting {
+                key: key.to_string(),
+                value,
+                source,
+            });
+        };
+
+        push(
+            "db_path",
+            json!(db_path.to_string_lossy()),
+            file_or_default(self.file.db_path.is_some()),
+        );
+        push(
+            "log_level",
+            json!(self.log_filter()),
+            if self.env_log_filter.is_some() {
+                ConfigSource::Environment
+            } else {
+                file_or_default(self.file.log_level.is_some())
+            },
+        );
+        push(
+            "default_provider",
+            json!(self.file.default_provider),
+            file_or_default(self.file.default_provider.is_some()),
+        );
+        match (&self.env_proxy, &self.file.proxy) {
+            (Some((_, url)), _) => push("proxy.url", json!(url), ConfigSource::Environment),
+            (None, Some(proxy)) => {
+                push("proxy.url", json!(proxy.url), ConfigSource::ConfigFile);
+                push(
+                    "proxy.no_proxy",
+                    json!(proxy.no_proxy),
+                    ConfigSource::ConfigFile,
+                );
+            }
+            (None, None) => push("proxy.url", Value::Null, ConfigSource::Default),
+        }
+        if let Some(proxy) = &self.file.proxy {
+            push(
+                "proxy.overrides",
+                json!(proxy.overrides),
+                ConfigSource::ConfigFile,
+            );
+        }
+        for feature in Feature::ALL {
+            push(
+                &format!("features.{}", feature.as_str()),
+                json!(self.is_enabled(feature)),
+                file_or_default(self.file.features.get(feature).is_some()),
+            );
+        }
+
+        EffectiveConfig {
+            path: self.path.to_string_lossy().to_string(),
+            loaded: self.loaded,
+            error: self.error.clone(),
+            settings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setting<'a>(config: &'a EffectiveConfig, key: &str) -> &'a EffectiveSetting {
+        config.settings.iter().find(|s| s.key == key).unwrap()
+    }
+
+    #[test]
+    fn test_effective_config_sources() {
+        let content = r#"
+            log_level = "debug"
+            default_provider = "ollama"
+
+            [proxy]
+            url = "http://proxy.local:3128"
+
+            [features]
+            lan_sync = false
+        "#;
+        let config = LoadedConfig::parse(
+            PathBuf::from("/data/config.toml"),
+            Some(Ok(content.to_string())),
+            Some("trace".to_string()),
+            None,
+        );
+        assert_eq!(config.log_filter(), "trace");
+        assert!(!config.is_enabled(Feature::LanSync));
+        assert_eq!(
+            config.default_db_path(Path::new("/data")),
+            PathBuf::from("/data/db")
+        );
+
+        let effective = config.effective(&config.default_db_path(Path::new("/data")));
+        assert!(effective.loaded);
+        assert_eq!(setting(&effective, "db_path").source, ConfigSource::Default);
+        assert_eq!(
+            setting(&effective, "log_level").source,
+            ConfigSource::Environment
+        );
+        assert_eq!(
+            setting(&effective, "default_provider").value,
+            json!("ollama")
+        );
+        assert_eq!(
+            setting(&effective, "proxy.url").source,
+            ConfigSource::ConfigFile
+        );
+        let lan_sync = setting(&effective, "features.lan_sync");
+        assert_eq!(
+            (lan_sync.value.clone(), lan_sync.source),
+            (json!(false), ConfigSource::ConfigFile)
+        );
+        assert_eq!(
+            setting(&effective, "features.scheduler").source,
+            ConfigSource::Default
+        );
+    }
+
+    #[test]
+    fn test_missing_and_invalid_config() {
+        let missing = LoadedConfig::parse(PathBuf::from("config.toml"), None, None, None);
+        assert!(missing.error().is_none());
+        assert_eq!(missing.log_filter(), DEFAULT_LOG_FILTER);
+
+        let invalid = LoadedConfig::parse(
+            PathBuf::from("config.toml"),
+            Some(Ok("db_path = 3\nlog_level = \"debug\"".to_string())),
+            None,
+            None,
+        );
+        assert!(invalid.error().unwrap().starts_with("Invalid config file"));
+        assert_eq!(invalid.log_filter(), DEFAULT_LOG_FILTER);
+        assert!(!invalid.effective(Path::new("db")).loaded);
+    }
+}
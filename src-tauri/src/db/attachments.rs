@@ -0,0 +1,203 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Blob storage of message attachments.
+//!
+//! Attachment contents are kept out of the database, in
+//! `<attachments dir>/<workflow_id>/<attachment_id>.<ext>` next to the
+//! database directory. Blobs of a sensitive workflow are written encrypted
+//! with the workflow key (base64 content sealed by the workflow vault).
+
+use crate::db::DBClient;
+use crate::models::attachment::{image_extension, Attachment};
+use crate::security::WORKFLOW_VAULT;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock as StdRwLock;
+use tracing::{debug, warn};
+
+/// Attachment blob storage rooted at a directory.
+pub struct AttachmentStore {
+    /// Root directory (one sub-directory per workflow), changed on profile switch
+    dir: StdRwLock<PathBuf>,
+}
+
+impl AttachmentStore {
+    /// Creates a store rooted at `dir` (created on first write).
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: StdRwLock::new(dir.into()),
+        }
+    }
+
+    /// Roots the store at another directory (profile switch).
+    pub fn set_dir(&self, dir: impl Into<PathBuf>) {
+        *self.dir.write().unwrap_or_else(|e| e.into_inner()) = dir.into();
+    }
+
+    /// Current root directory.
+    fn dir(&self) -> PathBuf {
+        self.dir.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Returns the path of an attachment blob.
+    fn blob_path(&self, workflow_id: &str, attachment_id: &str, mime_type: &str) -> PathBuf {
+        let extension = image_extension(mime_type).unwrap_or("bin");
+        self.dir()
+            .join(workflow_id)
+            .join(format!("{}.{}", attachment_id, extension))
+    }
+
+    /// Writes the blob of an attachment, encrypted if the workflow is sensitive.
+    ///
+    /// # Errors
+    /// The workflow is sensitive but locked, or the file cannot be written.
+    pub async fn write(
+        &self,
+        db: &DBClient,
+        attachment_id: &str,
+        workflow_id: &str,
+        mime_type: &str,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        let contents = if WORKFLOW_VAULT.is_sensitive(db, workflow_id).await? {
+            WORKFLOW_VAULT
+                .encrypt(workflow_id, &STANDARD.encode(bytes))?
+                .into_bytes()
+        } else {
+            bytes.to_vec()
+        };
+
+        let path = self.blob_path(workflow_id, attachment_id, mime_type);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create attachment directory: {}", e))?;
+        }
+        tokio::fs::write(&path, contents)
+            .await
+            .map_err(|e| format!("Failed to write attachment: {}", e))?;
+
+        debug!(attachment_id = %attachment_id, path = %path.display(), "Attachment blob written");
+        Ok(())
+    }
+
+    /// Reads the blob of an attachment.
+    ///
+    /// # Errors
+    /// The file is missing, or the workflow is locked for an encrypted blob.
+    pub async fn read(&self, attachment: &Attachment) -> Result<Vec<u8>, String> {
+        let path = self.blob_path(
+            &attachment.workflow_id,
+            &attachment.id,
+            &attachment.mime_type,
+        );
+        let contents = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read attachment {}: {}", attachment.id, e))?;
+
+        match std::str::from_utf8(&contents) {
+            Ok(text) if crate::security::workflow_vault::is_encrypted(text) => {
+                let encoded = WORKFLOW_VAULT.decrypt(&attachment.workflow_id, text)?;
+                STANDARD
+                    .decode(encoded)
+                    .map_err(|e| format!("Invalid attachment content: {}", e))
+            }
+            _ => Ok(contents),
+        }
+    }
+
+    /// Reads an attachment as a data URL (`data:<mime>;base64,<data>`).
+    pub async fn read_data_url(&self, attachment: &Attachment) -> Result<String, String> {
+        let bytes = self.read(attachment).await?;
+        Ok(format!(
+            "data:{};base64,{}",
+            attachment.mime_type,
+            STANDARD.encode(bytes)
+        ))
+    }
+
+    /// Deletes the blobs of attachments (missing files are ignored).
+    pub async fn delete(&self, attachments: &[Attachment]) {
+        for attachment in attachments {
+            let path = self.blob_path(
+                &attachment.workflow_id,
+                &attachment.id,
+                &attachment.mime_type,
+            );
+            remove_path(&path, false).await;
+        }
+    }
+
+    /// Deletes all attachment blobs of a workflow.
+    pub async fn delete_workflow(&self, workflow_id: &str) {
+        remove_path(&self.dir().join(workflow_id), true).await;
+    }
+}
+
+/// Removes a file or directory, logging failures other than "not found".
+async fn remove_path(path: &Path, is_dir: bool) {
+    let result = if is_dir {
+        tokio::fs::remove_dir_all(path).await
+    } else {
+        tokio::fs::remove_file(path).await
+    };
+    if let Err(e) = result {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!(path = %path.display(), error = %e, "Failed to delete attachment blob");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_write_read_delete() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        let store = AttachmentStore::new(temp_dir.path().join("attachments"));
+
+        let workflow_id = uuid::Uuid::new_v4().to_string();
+        let attachment = Attachment {
+            id: uuid::Uuid::new_v4().to_string(),
+            workflow_id: workflow_id.clone(),
+            message_id: uuid::Uuid::new_v4().to_string(),
+            mime_type: "image/png".to_string(),
+            file_name: None,
+            size_bytes: 8,
+            created_at: Utc::now(),
+        };
+        let bytes = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        store
+            .write(&db, &attachment.id, &workflow_id, "image/png", &bytes)
+            .await
+            .expect("Failed to write attachment");
+        assert_eq!(store.read(&attachment).await.unwrap(), bytes);
+        assert_eq!(
+            store.read_data_url(&attachment).await.unwrap(),
+            "data:image/png;base64,iVBORw0KGgo="
+        );
+
+        store.delete_workflow(&workflow_id).await;
+        assert!(store.read(&attachment).await.is_err());
+    }
+}
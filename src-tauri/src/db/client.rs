@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::utils::find_interpolated_id;
 use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::sync::RwLock as StdRwLock;
 use surrealdb::{
     engine::local::{Db, RocksDb},
     Surreal,
@@ -35,15 +38,58 @@ pub struct QueryStats {
     pub row_count: usize,
 }
 
+/// Settings key storing the fingerprint of the applied schema
+const SCHEMA_SETTINGS_KEY: &str = "settings:schema";
+
+/// Ensures the `command` field of `mcp_server` accepts the 'http' value.
+///
+/// Must use REMOVE FIELD + DEFINE FIELD to force the ASSERT constraint update
+/// (SurrealDB does not update ASSERT constraints on existing fields with just DEFINE).
+const MCP_HTTP_MIGRATION: &str = r#"
+    REMOVE FIELD IF EXISTS command ON TABLE mcp_server;
+    DEFINE FIELD command ON mcp_server TYPE string ASSERT $value IN ['docker', 'npx', 'uvx', 'http'];
+"#;
+
+/// Returns the fingerprint of the schema and migrations of this build.
+pub(crate) fn schema_fingerprint() -> String {
+    use super::schema::SCHEMA_SQL;
+
+    let digest = Sha256::digest(format!("{}\n{}", SCHEMA_SQL, MCP_HTTP_MIGRATION).as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Rejects (in debug builds) queries with values interpolated in their text.
+///
+/// Record IDs must be bound with `type::thing('table', $id)` and values with
+/// `$param`: interpolation is injection-prone and breaks on quotes.
+fn debug_assert_parameterized(query: &str) {
+    debug_assert!(
+        find_interpolated_id(query).is_none(),
+        "Interpolated record ID `{}` in query, bind it as a parameter: {}",
+        find_interpolated_id(query).unwrap_or_default(),
+        query
+    );
+}
+
 /// Database client for SurrealDB embedded operations
+///
+/// The connection can be replaced with [`DBClient::reopen`] (profile switch):
+/// every holder of the client then queries the new database.
 pub struct DBClient {
-    pub db: Surreal<Db>,
+    db: StdRwLock<Surreal<Db>>,
 }
 
 impl DBClient {
     /// Creates a new database client and connects to the specified path
-    #[instrument(name = "db_client_new", skip_all, fields(db_path = %path))]
     pub async fn new(path: &str) -> Result<Self> {
+        Ok(Self {
+            db: StdRwLock::new(Self::connect(path).await?),
+        })
+    }
+
+    /// Opens the database at `path`.
+    #[instrument(name = "db_client_connect", skip_all, fields(db_path = %path))]
+    async fn connect(path: &str) -> Result<Surreal<Db>> {
         info!("Initializing SurrealDB connection");
 
         let db = Surreal::new::<RocksDb>(path).await.map_err(|e| {
@@ -57,37 +103,84 @@ impl DBClient {
         })?;
 
         info!("SurrealDB connection established");
-        Ok(Self { db })
+        Ok(db)
+    }
+
+    /// Replaces the connection with the database at `path`.
+    ///
+    /// Queries already running complete on the previous database, which is
+    /// closed once they end. The schema of the new database is not initialized.
+    pub async fn reopen(&self, path: &str) -> Result<()> {
+        let db = Self::connect(path).await?;
+        *self.db.write().unwrap_or_else(|e| e.into_inner()) = db;
+        Ok(())
+    }
+
+    /// Current connection (cheap clone sharing the connection).
+    pub fn conn(&self) -> Surreal<Db> {
+        self.db.read().unwrap_or_else(|e| e.into_inner()).clone()
     }
 
     /// Initializes the database schema
+    ///
+    /// Skipped when the schema applied by a previous run is unchanged (same
+    /// fingerprint stored in `settings:schema`), which keeps cold starts short:
+    /// re-applying every definition and index takes most of the DB startup time.
     #[instrument(name = "db_initialize_schema", skip(self))]
     pub async fn initialize_schema(&self) -> Result<()> {
         use super::schema::SCHEMA_SQL;
 
+        let fingerprint = schema_fingerprint();
+        if self.stored_schema_fingerprint().await.as_deref() == Some(fingerprint.as_str()) {
+            info!("Database schema unchanged, skipping initialization");
+            return Ok(());
+        }
+
         info!("Initializing database schema");
 
-        self.db.query(SCHEMA_SQL).await.map_err(|e| {
+        self.conn().query(SCHEMA_SQL).await.map_err(|e| {
             error!(error = %e, "Failed to initialize schema");
             e
         })?;
 
-        // Run MCP HTTP migration to ensure command field supports 'http' value
-        // Must use REMOVE FIELD + DEFINE FIELD to force ASSERT constraint update
-        // (SurrealDB does not update ASSERT constraints on existing fields with just DEFINE)
-        let mcp_http_migration = r#"
-            REMOVE FIELD IF EXISTS command ON TABLE mcp_server;
-            DEFINE FIELD command ON mcp_server TYPE string ASSERT $value IN ['docker', 'npx', 'uvx', 'http'];
-        "#;
-        self.db.query(mcp_http_migration).await.map_err(|e| {
+        self.conn().query(MCP_HTTP_MIGRATION).await.map_err(|e| {
             warn!(error = %e, "MCP HTTP migration query failed (may be expected if table doesn't exist yet)");
             e
         })?;
 
+        // A failure here only means the schema is applied again on next startup
+        if let Err(e) = self
+            .conn()
+            .query(
+                "UPSERT type::thing('settings', $key) CONTENT { config: { fingerprint: $fingerprint } }",
+            )
+            .bind(("key", SCHEMA_SETTINGS_KEY))
+            .bind(("fingerprint", fingerprint))
+            .await
+        {
+            warn!(error = %e, "Failed to store schema fingerprint");
+        }
+
         info!("Database schema initialized successfully");
         Ok(())
     }
 
+    /// Returns the fingerprint of the schema applied by a previous run.
+    pub(crate) async fn stored_schema_fingerprint(&self) -> Option<String> {
+        let mut response = self
+            .conn()
+            .query("SELECT config FROM type::thing('settings', $key)")
+            .bind(("key", SCHEMA_SETTINGS_KEY))
+            .await
+            .ok()?;
+        let rows: Vec<serde_json::Value> = response.take(0).ok()?;
+        rows.first()?
+            .get("config")?
+            .get("fingerprint")?
+            .as_str()
+            .map(String::from)
+    }
+
     /// Executes a query and returns the results as JSON Value first,
     /// then deserializes using serde_json for proper custom deserializer support.
     #[instrument(name = "db_query", skip(self), fields(query_len = query.len()))]
@@ -96,8 +189,9 @@ impl DBClient {
         T: serde::de::DeserializeOwned,
     {
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing query");
+        debug_assert_parameterized(query);
 
-        let mut result = self.db.query(query).await.map_err(|e| {
+        let mut result = self.conn().query(query).await.map_err(|e| {
             error!(error = %e, "Query execution failed");
             e
         })?;
@@ -116,8 +210,9 @@ impl DBClient {
     #[instrument(name = "db_query_json", skip(self), fields(query_len = query.len()))]
     pub async fn query_json(&self, query: &str) -> Result<Vec<serde_json::Value>> {
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing JSON query");
+        debug_assert_parameterized(query);
 
-        let mut result = self.db.query(query).await.map_err(|e| {
+        let mut result = self.conn().query(query).await.map_err(|e| {
             error!(error = %e, "Query execution failed");
             e
         })?;
@@ -138,8 +233,9 @@ impl DBClient {
     #[instrument(name = "db_execute", skip(self), fields(query_len = query.len()))]
     pub async fn execute(&self, query: &str) -> Result<()> {
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing query (no result)");
+        debug_assert_parameterized(query);
 
-        self.db.query(query).await.map_err(|e| {
+        self.conn().query(query).await.map_err(|e| {
             error!(error = %e, "Query execution failed");
             e
         })?;
@@ -176,15 +272,15 @@ impl DBClient {
             "Attempting to create record"
         );
 
-        // Use CREATE query with backtick-escaped ID for safety
+        // Record ID bound with type::thing() for safety
         // Use RETURN meta::id(id) to get a string ID instead of Thing enum (SDK 2.x serialization issue)
-        let query = format!(
-            "CREATE {}:`{}` CONTENT $data RETURN meta::id(id) AS created_id",
-            table, id
-        );
         let mut result = self
-            .db
-            .query(&query)
+            .conn()
+            .query(
+                "CREATE type::thing($table, $id) CONTENT $data RETURN meta::id(id) AS created_id",
+            )
+            .bind(("table", table.to_string()))
+            .bind(("id", id.to_string()))
             .bind(("data", json_data.clone()))
             .await
             .map_err(|e| {
@@ -229,10 +325,11 @@ impl DBClient {
     {
         debug!("Updating record");
 
-        let _: Vec<serde_json::Value> = self.db.update(id).content(data).await.map_err(|e| {
-            error!(error = %e, "Failed to update record");
-            e
-        })?;
+        let _: Vec<serde_json::Value> =
+            self.conn().update(id).content(data).await.map_err(|e| {
+                error!(error = %e, "Failed to update record");
+                e
+            })?;
 
         debug!("Record updated");
         Ok(())
@@ -253,12 +350,16 @@ impl DBClient {
             anyhow::anyhow!(msg)
         })?;
 
-        // Use raw DELETE query with backtick-escaped ID to avoid SDK issues
-        let query = format!("DELETE {}:`{}`", table, uuid);
-        self.db.query(&query).await.map_err(|e| {
-            error!(error = %e, "Failed to delete record");
-            e
-        })?;
+        // Use raw DELETE query with bound ID to avoid SDK issues
+        self.conn()
+            .query("DELETE type::thing($table, $id)")
+            .bind(("table", table.to_string()))
+            .bind(("id", uuid.to_string()))
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to delete record");
+                e
+            })?;
 
         debug!("Record deleted");
         Ok(())
@@ -291,8 +392,10 @@ impl DBClient {
         T: serde::de::DeserializeOwned,
     {
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing parameterized query");
+        debug_assert_parameterized(query);
 
-        let mut query_builder = self.db.query(query);
+        let db = self.conn();
+        let mut query_builder = db.query(query);
 
         for (name, value) in params {
             query_builder = query_builder.bind((name, value));
@@ -324,7 +427,7 @@ impl DBClient {
     /// # Example
     /// ```ignore
     /// let results = db.query_json_with_params(
-    ///     "SELECT status FROM user_question:`$id`",
+    ///     "SELECT status FROM type::thing('user_question', $id)",
     ///     vec![("id".to_string(), json!("uuid-here"))]
     /// ).await?;
     /// ```
@@ -335,8 +438,10 @@ impl DBClient {
         params: Vec<(String, serde_json::Value)>,
     ) -> Result<Vec<serde_json::Value>> {
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing parameterized JSON query");
+        debug_assert_parameterized(query);
 
-        let mut query_builder = self.db.query(query);
+        let db = self.conn();
+        let mut query_builder = db.query(query);
 
         for (name, value) in params {
             query_builder = query_builder.bind((name, value));
@@ -382,8 +487,10 @@ impl DBClient {
         params: Vec<(String, serde_json::Value)>,
     ) -> Result<()> {
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing parameterized mutation");
+        debug_assert_parameterized(query);
 
-        let mut query_builder = self.db.query(query);
+        let db = self.conn();
+        let mut query_builder = db.query(query);
 
         for (name, value) in params {
             query_builder = query_builder.bind((name, value));
@@ -409,8 +516,8 @@ impl DBClient {
     /// # Example
     /// ```ignore
     /// db.transaction(vec![
-    ///     "CREATE workflow:`123` CONTENT { name: 'Test' }".to_string(),
-    ///     "CREATE message:`456` CONTENT { workflow_id: '123', content: 'Hello' }".to_string(),
+    ///     "CREATE workflow:demo CONTENT { name: 'Test' }".to_string(),
+    ///     "CREATE message:demo CONTENT { workflow_id: 'demo', content: 'Hello' }".to_string(),
     /// ]).await?;
     /// ```
     ///
@@ -421,8 +528,11 @@ impl DBClient {
     pub async fn transaction(&self, queries: Vec<String>) -> Result<()> {
         debug!("Starting transaction with {} queries", queries.len());
 
+        // One connection for the whole transaction (the client may be reopened)
+        let db = self.conn();
+
         // Begin transaction
-        if let Err(e) = self.db.query("BEGIN TRANSACTION").await {
+        if let Err(e) = db.query("BEGIN TRANSACTION").await {
             error!(error = %e, "Failed to begin transaction");
             return Err(e.into());
         }
@@ -430,10 +540,11 @@ impl DBClient {
         // Execute all queries
         for (i, query) in queries.iter().enumerate() {
             debug!(query_index = i, "Executing transaction query");
-            if let Err(e) = self.db.query(query).await {
+            debug_assert_parameterized(query);
+            if let Err(e) = db.query(query).await {
                 error!(error = %e, query_index = i, "Transaction query failed, rolling back");
                 // Attempt to cancel the transaction
-                if let Err(cancel_err) = self.db.query("CANCEL TRANSACTION").await {
+                if let Err(cancel_err) = db.query("CANCEL TRANSACTION").await {
                     warn!(error = %cancel_err, "Failed to cancel transaction after error");
                 }
                 return Err(e.into());
@@ -441,10 +552,10 @@ impl DBClient {
         }
 
         // Commit transaction
-        if let Err(e) = self.db.query("COMMIT TRANSACTION").await {
+        if let Err(e) = db.query("COMMIT TRANSACTION").await {
             error!(error = %e, "Failed to commit transaction");
             // Attempt to cancel on commit failure
-            if let Err(cancel_err) = self.db.query("CANCEL TRANSACTION").await {
+            if let Err(cancel_err) = db.query("CANCEL TRANSACTION").await {
                 warn!(error = %cancel_err, "Failed to cancel transaction after commit failure");
             }
             return Err(e.into());
@@ -480,10 +591,11 @@ impl DBClient {
         use std::time::Instant;
 
         debug!(query_preview = %query.chars().take(100).collect::<String>(), "Executing query with stats");
+        debug_assert_parameterized(query);
 
         let start = Instant::now();
 
-        let mut result = self.db.query(query).await.map_err(|e| {
+        let mut result = self.conn().query(query).await.map_err(|e| {
             error!(error = %e, "Query execution failed");
             e
         })?;
@@ -523,12 +635,12 @@ impl DBClient {
     /// ```ignore
     /// db.transaction_with_params(vec![
     ///     (
-    ///         "CREATE workflow:`123` CONTENT $data".to_string(),
-    ///         vec![("data".to_string(), json!({"name": "Test"}))]
+    ///         "CREATE type::thing('workflow', $id) CONTENT $data".to_string(),
+    ///         vec![("id".to_string(), json!("123")), ("data".to_string(), json!({"name": "Test"}))]
     ///     ),
     ///     (
-    ///         "CREATE message:`456` CONTENT $data".to_string(),
-    ///         vec![("data".to_string(), json!({"workflow_id": "123"}))]
+    ///         "CREATE type::thing('message', $id) CONTENT $data".to_string(),
+    ///         vec![("id".to_string(), json!("456")), ("data".to_string(), json!({"workflow_id": "123"}))]
     ///     ),
     /// ]).await?;
     /// ```
@@ -543,8 +655,11 @@ impl DBClient {
             operations.len()
         );
 
+        // One connection for the whole transaction (the client may be reopened)
+        let db = self.conn();
+
         // Begin transaction
-        if let Err(e) = self.db.query("BEGIN TRANSACTION").await {
+        if let Err(e) = db.query("BEGIN TRANSACTION").await {
             error!(error = %e, "Failed to begin transaction");
             return Err(e.into());
         }
@@ -552,15 +667,16 @@ impl DBClient {
         // Execute all operations with their parameters
         for (i, (query, params)) in operations.iter().enumerate() {
             debug!(op_index = i, "Executing transaction operation");
+            debug_assert_parameterized(query);
 
-            let mut query_builder = self.db.query(query);
+            let mut query_builder = db.query(query);
             for (name, value) in params {
                 query_builder = query_builder.bind((name.clone(), value.clone()));
             }
 
             if let Err(e) = query_builder.await {
                 error!(error = %e, op_index = i, "Transaction operation failed, rolling back");
-                if let Err(cancel_err) = self.db.query("CANCEL TRANSACTION").await {
+                if let Err(cancel_err) = db.query("CANCEL TRANSACTION").await {
                     warn!(error = %cancel_err, "Failed to cancel transaction after error");
                 }
                 return Err(e.into());
@@ -568,9 +684,9 @@ impl DBClient {
         }
 
         // Commit transaction
-        if let Err(e) = self.db.query("COMMIT TRANSACTION").await {
+        if let Err(e) = db.query("COMMIT TRANSACTION").await {
             error!(error = %e, "Failed to commit transaction");
-            if let Err(cancel_err) = self.db.query("CANCEL TRANSACTION").await {
+            if let Err(cancel_err) = db.query("CANCEL TRANSACTION").await {
                 warn!(error = %cancel_err, "Failed to cancel transaction after commit failure");
             }
             return Err(e.into());
@@ -602,6 +718,28 @@ mod tests {
         assert!(result.is_err(), "Should fail with invalid path");
     }
 
+    #[tokio::test]
+    async fn test_db_client_reopen() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db = DBClient::new(temp_dir.path().join("first_db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.execute("CREATE note SET text = 'first'").await.unwrap();
+        assert_eq!(
+            db.query_json("SELECT text FROM note").await.unwrap().len(),
+            1
+        );
+
+        db.reopen(temp_dir.path().join("second_db").to_str().unwrap())
+            .await
+            .unwrap();
+        assert!(db
+            .query_json("SELECT * FROM note")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_db_initialize_schema() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
@@ -615,6 +753,42 @@ mod tests {
         assert!(result.is_ok(), "Schema initialization should succeed");
     }
 
+    #[tokio::test]
+    async fn test_db_initialize_schema_skipped_when_unchanged() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("schema_skip_db");
+        let db_path_str = db_path.to_str().unwrap();
+
+        let db = DBClient::new(db_path_str)
+            .await
+            .expect("DB creation failed");
+        assert!(db.stored_schema_fingerprint().await.is_none());
+
+        db.initialize_schema().await.expect("Schema init failed");
+        assert_eq!(
+            db.stored_schema_fingerprint().await,
+            Some(schema_fingerprint())
+        );
+
+        // A stale fingerprint applies the schema again
+        db.execute_with_params(
+            "UPDATE type::thing('settings', $key) SET config.fingerprint = 'stale'",
+            vec![("key".to_string(), serde_json::json!(SCHEMA_SETTINGS_KEY))],
+        )
+        .await
+        .expect("Update failed");
+        db.initialize_schema().await.expect("Schema init failed");
+        assert_eq!(
+            db.stored_schema_fingerprint().await,
+            Some(schema_fingerprint())
+        );
+
+        // Unchanged schema: tables are still defined after a skipped init
+        db.initialize_schema().await.expect("Schema init failed");
+        let info: Vec<serde_json::Value> = db.query_json("INFO FOR DB").await.unwrap();
+        assert!(info[0]["tables"].get("workflow").is_some());
+    }
+
     #[tokio::test]
     async fn test_db_query_empty_result() {
         let temp_dir = tempdir().expect("Failed to create temp dir");
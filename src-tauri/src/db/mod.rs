@@ -20,6 +20,8 @@
 //!
 //! This module contains:
 //! - [`DBClient`] - Database client for CRUD operations
+//! - [`attachments`] - Blob storage of message attachments
+//! - [`outbox`] - Durable retry queue for failed persistence writes
 //! - [`schema`] - Database schema definitions (7 tables)
 //!
 //! ## Database Engine
@@ -34,11 +36,14 @@
 //! - `workflow` - User workflows
 //! - `agent_state` - Agent configurations and metrics
 //! - `message` - Conversation messages
+//! - `attachment` - Image attachments of messages (blobs on disk)
 //! - `memory` - Vector embeddings for RAG
 //! - `validation_request` - Human-in-the-loop validations
 //! - `task` - Task decomposition tracking
 
+pub mod attachments;
 pub mod client;
+pub mod outbox;
 pub mod queries;
 pub mod schema;
 pub mod utils;
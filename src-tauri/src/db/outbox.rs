@@ -0,0 +1,331 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Durable outbox for persistence writes.
+//!
+//! Records created during a workflow execution (messages, thinking steps,
+//! tool executions, LLM turns) go through [`PersistenceOutbox::create`]. A
+//! failed write (disk full, lock contention) is retried with backoff; if it
+//! still fails, the record is appended to a JSON Lines file next to the
+//! database and a `persistence_warning` event is emitted. Queued records are
+//! written again by [`PersistenceOutbox::flush`], after each workflow
+//! execution, at startup and through the `flush_outbox` command.
+
+use crate::db::DBClient;
+use crate::models::outbox::{OutboxEntry, OutboxFlushResult, PersistenceWarning};
+use crate::models::streaming::events;
+use crate::tools::constants::outbox::{MAX_ENTRIES, RETRY_BASE_DELAY_MS, WRITE_ATTEMPTS};
+use chrono::Utc;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+
+/// Durable queue of records whose database write failed.
+pub struct PersistenceOutbox {
+    /// Outbox file (JSON Lines, one [`OutboxEntry`] per line), changed on profile switch
+    path: StdRwLock<PathBuf>,
+    /// Serializes access to the file
+    lock: Mutex<()>,
+    /// App handle used to emit `persistence_warning` (shared with AppState)
+    app_handle: Arc<StdRwLock<Option<AppHandle>>>,
+}
+
+impl PersistenceOutbox {
+    /// Creates an outbox stored in `path`.
+    pub fn new(path: impl Into<PathBuf>, app_handle: Arc<StdRwLock<Option<AppHandle>>>) -> Self {
+        Self {
+            path: StdRwLock::new(path.into()),
+            lock: Mutex::new(()),
+            app_handle,
+        }
+    }
+
+    /// Current outbox file.
+    fn path(&self) -> PathBuf {
+        self.path.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Stores the outbox in another file (profile switch).
+    ///
+    /// Records queued in the previous file stay there until its profile is
+    /// active again.
+    pub async fn set_path(&self, path: impl Into<PathBuf>) {
+        let _guard = self.lock.lock().await;
+        *self.path.write().unwrap_or_else(|e| e.into_inner()) = path.into();
+    }
+
+    /// Creates a record, queuing it in the outbox if the write keeps failing.
+    ///
+    /// # Errors
+    /// The record could neither be written nor queued (it is lost).
+    pub async fn create<T: Serialize>(
+        &self,
+        db: &DBClient,
+        table: &str,
+        record_id: &str,
+        data: T,
+    ) -> Result<(), String> {
+        let data = serde_json::to_value(data)
+            .map_err(|e| format!("Failed to serialize {} record: {}", table, e))?;
+
+        let mut last_error = String::new();
+        for attempt in 0..WRITE_ATTEMPTS {
+            if attempt > 0 {
+                let delay = RETRY_BASE_DELAY_MS << (attempt - 1);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+            match db.create(table, record_id, data.clone()).await {
+                Ok(_) => return Ok(()),
+                Err(e) => {
+                    warn!(table = %table, record_id = %record_id, attempt, error = %e, "Persistence write failed");
+                    last_error = e.to_string();
+                }
+            }
+        }
+
+        let entry = OutboxEntry {
+            table: table.to_string(),
+            record_id: record_id.to_string(),
+            data,
+            attempts: WRITE_ATTEMPTS,
+            last_error: last_error.clone(),
+            queued_at: Utc::now(),
+        };
+        let pending = {
+            let _guard = self.lock.lock().await;
+            let mut entries = read_entries(&self.path())?;
+            entries.push(entry);
+            if entries.len() > MAX_ENTRIES {
+                let dropped = entries.len() - MAX_ENTRIES;
+                error!(dropped, "Persistence outbox full, dropping oldest records");
+                entries.drain(..dropped);
+            }
+            write_entries(&self.path(), &entries)?;
+            entries.len()
+        };
+
+        warn!(table = %table, record_id = %record_id, pending, "Record queued in persistence outbox");
+        self.emit_warning(&PersistenceWarning {
+            table: table.to_string(),
+            record_id: record_id.to_string(),
+            error: last_error,
+            pending,
+        });
+        Ok(())
+    }
+
+    /// Writes the queued records to the database.
+    ///
+    /// Records already present in the database (the failed write succeeded
+    /// after all) are removed from the outbox. Records that still fail stay
+    /// queued.
+    pub async fn flush(&self, db: &DBClient) -> Result<OutboxFlushResult, String> {
+        let _guard = self.lock.lock().await;
+        let entries = read_entries(&self.path())?;
+        if entries.is_empty() {
+            return Ok(OutboxFlushResult::default());
+        }
+
+        let mut result = OutboxFlushResult::default();
+        let mut remaining = Vec::new();
+        for mut entry in entries {
+            if record_exists(db, &entry.table, &entry.record_id).await {
+                result.flushed += 1;
+                continue;
+            }
+            match db
+                .create(&entry.table, &entry.record_id, entry.data.clone())
+                .await
+            {
+                Ok(_) => result.flushed += 1,
+                Err(e) => {
+                    entry.attempts += 1;
+                    entry.last_error = e.to_string();
+                    result.last_error = Some(entry.last_error.clone());
+                    remaining.push(entry);
+                }
+            }
+        }
+
+        write_entries(&self.path(), &remaining)?;
+        result.remaining = remaining.len();
+        info!(
+            flushed = result.flushed,
+            remaining = result.remaining,
+            "Persistence outbox flushed"
+        );
+        Ok(result)
+    }
+
+    /// Returns the number of queued records.
+    pub async fn pending(&self) -> Result<usize, String> {
+        let _guard = self.lock.lock().await;
+        read_entries(&self.path()).map(|entries| entries.len())
+    }
+
+    fn emit_warning(&self, warning: &PersistenceWarning) {
+        let handle = self.app_handle.read().ok().and_then(|guard| guard.clone());
+        if let Some(handle) = handle {
+            if let Err(e) = handle.emit(events::PERSISTENCE_WARNING, warning) {
+                warn!(error = %e, "Failed to emit persistence warning");
+            }
+        }
+    }
+}
+
+/// Returns true if the record exists in the database.
+async fn record_exists(db: &DBClient, table: &str, record_id: &str) -> bool {
+    db.query_json_with_params(
+        "SELECT meta::id(id) AS id FROM type::thing($table, $record_id)",
+        vec![
+            ("table".to_string(), serde_json::json!(table)),
+            ("record_id".to_string(), serde_json::json!(record_id)),
+        ],
+    )
+    .await
+    .map(|rows| !rows.is_empty())
+    .unwrap_or(false)
+}
+
+/// Reads the outbox file (missing file = empty outbox, corrupted lines skipped).
+fn read_entries(path: &Path) -> Result<Vec<OutboxEntry>, String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read persistence outbox: {}", e)),
+    };
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                warn!(error = %e, "Skipping corrupted persistence outbox entry");
+                None
+            }
+        })
+        .collect())
+}
+
+/// Replaces the outbox file atomically (removed when empty).
+fn write_entries(path: &Path, entries: &[OutboxEntry]) -> Result<(), String> {
+    if entries.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to clear persistence outbox: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let mut content = String::new();
+    for entry in entries {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| format!("Failed to serialize outbox entry: {}", e))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+    let tmp_path = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp_path, content)
+        .and_then(|_| std::fs::rename(&tmp_path, path))
+        .map_err(|e| format!("Failed to write persistence outbox: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ThinkingStepCreate;
+    use tempfile::tempdir;
+
+    fn step(content: &str) -> ThinkingStepCreate {
+        ThinkingStepCreate {
+            workflow_id: "wf-1".to_string(),
+            message_id: "msg-1".to_string(),
+            agent_id: "agent-1".to_string(),
+            step_number: 0,
+            content: content.to_string(),
+            duration_ms: None,
+            tokens: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_outbox_queues_failed_writes_and_flushes() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let outbox = PersistenceOutbox::new(
+            temp_dir.path().join("outbox.jsonl"),
+            Arc::new(StdRwLock::new(None)),
+        );
+
+        // Successful writes are not queued
+        outbox
+            .create(&db, "thinking_step", "step1", step("first"))
+            .await
+            .unwrap();
+        assert_eq!(outbox.pending().await.unwrap(), 0);
+
+        // Writing an existing record fails and is queued
+        outbox
+            .create(&db, "thinking_step", "step1", step("duplicate"))
+            .await
+            .unwrap();
+        assert_eq!(outbox.pending().await.unwrap(), 1);
+
+        // Entries are durable: a new outbox on the same file sees them
+        let reopened = PersistenceOutbox::new(
+            temp_dir.path().join("outbox.jsonl"),
+            Arc::new(StdRwLock::new(None)),
+        );
+        assert_eq!(reopened.pending().await.unwrap(), 1);
+
+        // The record exists, so the flush drops it from the outbox
+        let result = reopened.flush(&db).await.unwrap();
+        assert_eq!(result.flushed, 1);
+        assert_eq!(result.remaining, 0);
+        assert!(!temp_dir.path().join("outbox.jsonl").exists());
+    }
+
+    #[tokio::test]
+    async fn test_outbox_flush_writes_queued_records() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db = DBClient::new(temp_dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let path = temp_dir.path().join("outbox.jsonl");
+        let entry = OutboxEntry {
+            table: "thinking_step".to_string(),
+            record_id: "step2".to_string(),
+            data: serde_json::to_value(step("queued")).unwrap(),
+            attempts: WRITE_ATTEMPTS,
+            last_error: "disk full".to_string(),
+            queued_at: Utc::now(),
+        };
+        write_entries(&path, &[entry]).unwrap();
+
+        let outbox = PersistenceOutbox::new(&path, Arc::new(StdRwLock::new(None)));
+        let result = outbox.flush(&db).await.unwrap();
+        assert_eq!(result.flushed, 1);
+        assert_eq!(result.remaining, 0);
+        assert!(record_exists(&db, "thinking_step", "step2").await);
+    }
+}
@@ -25,18 +25,19 @@
 //! // For listing all workflows (no WHERE clause)
 //! let query = workflow::SELECT_LIST;
 //!
-//! // For loading single workflow by ID
-//! let query = format!("{} WHERE meta::id(id) = '{}'", workflow::SELECT_BASE, id);
+//! // For loading single workflow by ID (bound, never interpolated)
+//! let query = format!("{} WHERE id = type::thing('workflow', $id)", workflow::SELECT_BASE);
 //! ```
 
 /// Workflow query constants.
 ///
-/// Two patterns are provided:
+/// Three patterns are provided:
 /// - `SELECT_BASE`: Core fields without WHERE/ORDER BY (for single workflow lookup)
 /// - `SELECT_LIST`: Full query for listing workflows (with ORDER BY)
+/// - `SELECT_PAGE`: Base query for a sorted/filtered page of the list
 pub mod workflow {
     /// Base SELECT fields for workflow queries.
-    /// Use with `format!("{} WHERE meta::id(id) = '{}'", SELECT_BASE, id)` for single lookup.
+    /// Use with `format!("{} WHERE id = type::thing('workflow', $id)", SELECT_BASE)` for single lookup.
     ///
     /// Includes token metrics with NULL coalescing for backwards compatibility.
     pub const SELECT_BASE: &str = r#"SELECT
@@ -50,7 +51,11 @@ pub mod workflow {
         (total_tokens_input ?? 0) AS total_tokens_input,
         (total_tokens_output ?? 0) AS total_tokens_output,
         (total_cost_usd ?? 0.0) AS total_cost_usd,
-        model_id
+        model_id,
+        response_language,
+        (tags ?? []) AS tags,
+        (sensitive ?? false) AS sensitive,
+        (dry_run ?? false) AS dry_run
     FROM workflow"#;
 
     /// SELECT query for listing all workflows ordered by update time.
@@ -66,12 +71,39 @@ pub mod workflow {
         (total_tokens_input ?? 0) AS total_tokens_input,
         (total_tokens_output ?? 0) AS total_tokens_output,
         (total_cost_usd ?? 0.0) AS total_cost_usd,
-        model_id
+        model_id,
+        response_language,
+        (tags ?? []) AS tags,
+        (sensitive ?? false) AS sensitive,
+        (dry_run ?? false) AS dry_run
     FROM workflow
     ORDER BY updated_at DESC"#;
 
+    /// SELECT query for a page of the workflow list.
+    /// Append the filters, `ORDER BY` and `LIMIT/START` clauses; `sort_name`
+    /// is the lowercased name used for case-insensitive sorting.
+    pub const SELECT_PAGE: &str = r#"SELECT
+        meta::id(id) AS id,
+        name,
+        agent_id,
+        status,
+        created_at,
+        updated_at,
+        completed_at,
+        (total_tokens_input ?? 0) AS total_tokens_input,
+        (total_tokens_output ?? 0) AS total_tokens_output,
+        (total_cost_usd ?? 0.0) AS total_cost_usd,
+        model_id,
+        response_language,
+        (tags ?? []) AS tags,
+        (sensitive ?? false) AS sensitive,
+        (dry_run ?? false) AS dry_run,
+        string::lowercase(name) AS sort_name
+    FROM workflow"#;
+
     /// Minimal SELECT fields for basic workflow validation (existence check).
-    /// Used in execute_workflow/streaming where token metrics aren't needed yet.
+    /// Used in execute_workflow/streaming where token metrics aren't needed yet
+    /// (the response language is needed to build the system prompt).
     pub const SELECT_BASIC: &str = r#"SELECT
         meta::id(id) AS id,
         name,
@@ -79,7 +111,8 @@ pub mod workflow {
         status,
         created_at,
         updated_at,
-        completed_at
+        completed_at,
+        response_language
     FROM workflow"#;
 
     /// Tables that have workflow_id foreign key and need cascade delete (OPT-WF-8).
@@ -89,16 +122,23 @@ pub mod workflow {
         "message",
         "tool_execution",
         "thinking_step",
+        "llm_turn",
         "sub_agent_execution",
         "validation_request",
         "memory",
         "user_question",
+        "attachment",
+        "schedule",
+        "job",
+        "workflow_checkpoint",
+        "pending_item",
     ];
 }
 
 /// Cascade delete helpers (OPT-WF-8).
 pub mod cascade {
     use crate::db::DBClient;
+    use serde_json::json;
     use std::sync::Arc;
     use tracing::{info, warn};
 
@@ -112,8 +152,9 @@ pub mod cascade {
     /// * `table` - Table name to delete from
     /// * `workflow_id` - The workflow ID to match
     pub async fn delete_by_workflow_id(db: &Arc<DBClient>, table: &str, workflow_id: &str) {
-        let query = format!("DELETE {} WHERE workflow_id = '{}'", table, workflow_id);
-        match db.execute(&query).await {
+        let query = format!("DELETE {} WHERE workflow_id = $workflow_id", table);
+        let params = vec![("workflow_id".to_string(), json!(workflow_id))];
+        match db.execute_with_params(&query, params).await {
             Ok(_) => info!(table = %table, workflow_id = %workflow_id, "Cascade deleted records"),
             Err(e) => warn!(error = %e, table = %table, "Cascade delete failed (may not exist)"),
         }
@@ -150,3 +191,39 @@ pub mod cascade {
         info!(workflow_id = %workflow_id, "Cascade delete completed for all related tables");
     }
 }
+
+/// Settings records helpers.
+///
+/// Settings are stored as `settings:<key>` records holding a `config` object
+/// (e.g. key `settings:validation`, record `settings:⟨settings:validation⟩`).
+pub mod settings {
+    use crate::db::DBClient;
+    use serde_json::{json, Value};
+
+    /// Loads the `config` of a settings record (None if not stored).
+    pub async fn load_config(db: &DBClient, key: &str) -> anyhow::Result<Option<Value>> {
+        let rows = db
+            .query_json_with_params(
+                "SELECT config FROM type::thing('settings', $key)",
+                vec![("key".to_string(), json!(key))],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .next()
+            .and_then(|mut row| row.get_mut("config").map(Value::take))
+            .filter(|config| !config.is_null()))
+    }
+
+    /// Stores the `config` of a settings record (created if missing).
+    pub async fn save_config(db: &DBClient, key: &str, config: &Value) -> anyhow::Result<()> {
+        db.execute_with_params(
+            "UPSERT type::thing('settings', $key) CONTENT { config: $config }",
+            vec![
+                ("key".to_string(), json!(key)),
+                ("config".to_string(), config.clone()),
+            ],
+        )
+        .await
+    }
+}
@@ -19,6 +19,10 @@ USE NS zileo;
 DEFINE DATABASE chat;
 USE DB chat;
 
+-- Full-text analyzer (search_messages / search_workflows): language-neutral,
+-- case and accent insensitive
+DEFINE ANALYZER OVERWRITE text_search TOKENIZERS blank, class, punct FILTERS lowercase, ascii;
+
 -- Table: workflow
 -- Extended with cumulative token tracking for Token Display Complet
 DEFINE TABLE OVERWRITE workflow SCHEMAFULL;
@@ -36,6 +40,32 @@ DEFINE FIELD OVERWRITE total_cost_usd ON workflow TYPE float DEFAULT 0.0;
 DEFINE FIELD OVERWRITE model_id ON workflow TYPE option<string>;
 -- Current context size (last API call context window usage)
 DEFINE FIELD OVERWRITE current_context_tokens ON workflow TYPE int DEFAULT 0;
+-- Pinned response language (ISO 639-1 code, detected or set by the user)
+DEFINE FIELD OVERWRITE response_language ON workflow TYPE option<string>;
+-- User-defined tags (workflow list filter)
+DEFINE FIELD OVERWRITE tags ON workflow TYPE array<string> DEFAULT [];
+-- Sensitive workflow (contents encrypted with a keystore-wrapped data key)
+DEFINE FIELD OVERWRITE sensitive ON workflow TYPE bool DEFAULT false;
+-- Dry run: tools with side effects return a simulated result instead of executing
+DEFINE FIELD OVERWRITE dry_run ON workflow TYPE bool DEFAULT false;
+-- Title generated from the first exchange (not generated again automatically)
+DEFINE FIELD OVERWRITE title_generated ON workflow TYPE bool DEFAULT false;
+-- Voice of the spoken replies (overrides the text-to-speech settings)
+DEFINE FIELD OVERWRITE voice ON workflow TYPE option<object>;
+DEFINE FIELD OVERWRITE voice.voice ON workflow TYPE option<string>;
+DEFINE FIELD OVERWRITE voice.speed ON workflow TYPE option<float>;
+-- Indexes for the sorted/filtered workflow list
+DEFINE INDEX OVERWRITE workflow_updated_idx ON workflow FIELDS updated_at;
+DEFINE INDEX OVERWRITE workflow_status_idx ON workflow FIELDS status;
+DEFINE INDEX OVERWRITE workflow_agent_idx ON workflow FIELDS agent_id;
+DEFINE INDEX OVERWRITE workflow_name_search ON workflow FIELDS name SEARCH ANALYZER text_search BM25 HIGHLIGHTS;
+
+-- Table: workflow_tag
+-- Tag catalog (record ID = tag name); workflows reference tags by name in `workflow.tags`
+DEFINE TABLE OVERWRITE workflow_tag SCHEMAFULL;
+DEFINE FIELD OVERWRITE name ON workflow_tag TYPE string;
+DEFINE FIELD OVERWRITE color ON workflow_tag TYPE option<string>;
+DEFINE FIELD OVERWRITE created_at ON workflow_tag TYPE datetime DEFAULT time::now();
 
 -- Table: agent_state
 DEFINE TABLE OVERWRITE agent_state SCHEMAFULL;
@@ -60,6 +90,31 @@ DEFINE FIELD OVERWRITE model ON message TYPE option<string>;
 DEFINE FIELD OVERWRITE provider ON message TYPE option<string>;
 DEFINE FIELD OVERWRITE cost_usd ON message TYPE option<float>;
 DEFINE FIELD OVERWRITE duration_ms ON message TYPE option<int>;
+-- Context window utilization of the run (assistant messages)
+DEFINE FIELD OVERWRITE context_usage ON message TYPE option<object>;
+DEFINE FIELD OVERWRITE context_usage.context_window ON message TYPE option<int>;
+DEFINE FIELD OVERWRITE context_usage.peak_context_tokens ON message TYPE option<int>;
+DEFINE FIELD OVERWRITE context_usage.utilization ON message TYPE option<float>;
+DEFINE FIELD OVERWRITE context_usage.history_messages ON message TYPE option<int>;
+DEFINE FIELD OVERWRITE context_usage.history_sent ON message TYPE option<int>;
+DEFINE FIELD OVERWRITE context_usage.history_truncated ON message TYPE option<int>;
+DEFINE FIELD OVERWRITE context_usage.truncated_before ON message TYPE option<string>;
+-- Read-aloud version of the content (plain text and SSML, see speech::read_aloud)
+DEFINE FIELD OVERWRITE read_aloud ON message TYPE option<object>;
+DEFINE FIELD OVERWRITE read_aloud.text ON message TYPE option<string>;
+DEFINE FIELD OVERWRITE read_aloud.ssml ON message TYPE option<string>;
+-- Behavior changelog position in effect when the run started (assistant messages)
+DEFINE FIELD OVERWRITE behavior_position ON message TYPE option<int>;
+-- Generation parameters actually used for the response (per-request overrides resolved)
+DEFINE FIELD OVERWRITE generation ON message TYPE option<object>;
+DEFINE FIELD OVERWRITE generation.temperature ON message TYPE option<float>;
+DEFINE FIELD OVERWRITE generation.top_p ON message TYPE option<float>;
+DEFINE FIELD OVERWRITE generation.max_tokens ON message TYPE option<int>;
+DEFINE FIELD OVERWRITE generation.stop ON message TYPE array<string> DEFAULT [];
+-- Pinned messages are always sent to the agent; bookmarks and reactions are user marks
+DEFINE FIELD OVERWRITE pinned ON message TYPE bool DEFAULT false;
+DEFINE FIELD OVERWRITE bookmarked ON message TYPE bool DEFAULT false;
+DEFINE FIELD OVERWRITE reactions ON message TYPE array<string> DEFAULT [];
 DEFINE FIELD OVERWRITE timestamp ON message TYPE datetime DEFAULT time::now();
 
 -- =============================================
@@ -72,6 +127,110 @@ DEFINE FIELD OVERWRITE timestamp ON message TYPE datetime DEFAULT time::now();
 --   - message_timestamp_idx: Required for chronological message display in UI
 DEFINE INDEX OVERWRITE message_workflow_idx ON message FIELDS workflow_id;
 DEFINE INDEX OVERWRITE message_timestamp_idx ON message FIELDS timestamp;
+-- Full-text index of the contents (encrypted contents of sensitive workflows never match)
+DEFINE INDEX OVERWRITE message_content_search ON message FIELDS content SEARCH ANALYZER text_search BM25 HIGHLIGHTS;
+
+-- Table: attachment
+-- Image attachments of messages (blobs stored under the app data dir)
+DEFINE TABLE OVERWRITE attachment SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON attachment TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON attachment TYPE string;
+DEFINE FIELD OVERWRITE message_id ON attachment TYPE string;
+DEFINE FIELD OVERWRITE mime_type ON attachment TYPE string
+    ASSERT $value IN ['image/png', 'image/jpeg', 'image/gif', 'image/webp', 'image/svg+xml'];
+DEFINE FIELD OVERWRITE file_name ON attachment TYPE option<string>;
+DEFINE FIELD OVERWRITE size_bytes ON attachment TYPE int;
+DEFINE FIELD OVERWRITE created_at ON attachment TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE attachment_workflow_idx ON attachment FIELDS workflow_id;
+DEFINE INDEX OVERWRITE attachment_message_idx ON attachment FIELDS message_id;
+
+-- Table: sync_pairing
+-- Settings sync pairings with other machines (pairing keys are in the keystore)
+DEFINE TABLE OVERWRITE sync_pairing SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON sync_pairing TYPE string;
+DEFINE FIELD OVERWRITE device_name ON sync_pairing TYPE string;
+DEFINE FIELD OVERWRITE peer_name ON sync_pairing TYPE option<string>;
+DEFINE FIELD OVERWRITE role ON sync_pairing TYPE string
+    ASSERT $value IN ['initiator', 'responder'];
+DEFINE FIELD OVERWRITE transport ON sync_pairing TYPE string
+    ASSERT $value IN ['relay', 'lan'];
+DEFINE FIELD OVERWRITE mailbox_url ON sync_pairing TYPE string;
+DEFINE FIELD OVERWRITE created_at ON sync_pairing TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE last_synced_at ON sync_pairing TYPE option<datetime>;
+DEFINE FIELD OVERWRITE last_received_at ON sync_pairing TYPE option<datetime>;
+
+-- Table: schedule
+-- Scheduled workflow runs (cron expression in local time)
+DEFINE TABLE OVERWRITE schedule SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON schedule TYPE string;
+DEFINE FIELD OVERWRITE name ON schedule TYPE string;
+DEFINE FIELD OVERWRITE cron ON schedule TYPE string;
+DEFINE FIELD OVERWRITE agent_id ON schedule TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON schedule TYPE string;
+DEFINE FIELD OVERWRITE prompt ON schedule TYPE string;
+DEFINE FIELD OVERWRITE paused ON schedule TYPE bool DEFAULT false;
+DEFINE FIELD OVERWRITE next_run_at ON schedule TYPE option<datetime>;
+DEFINE FIELD OVERWRITE last_run_at ON schedule TYPE option<datetime>;
+DEFINE FIELD OVERWRITE last_status ON schedule TYPE option<string>
+    ASSERT $value IS NONE OR $value IN ['success', 'failed'];
+DEFINE FIELD OVERWRITE last_error ON schedule TYPE option<string>;
+DEFINE FIELD OVERWRITE created_at ON schedule TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE schedule_due_idx ON schedule FIELDS paused, next_run_at;
+DEFINE INDEX OVERWRITE schedule_workflow_idx ON schedule FIELDS workflow_id;
+
+-- Table: job
+-- Background workflow executions (interrupted jobs resume after a restart)
+DEFINE TABLE OVERWRITE job SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON job TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON job TYPE string;
+DEFINE FIELD OVERWRITE agent_id ON job TYPE string;
+DEFINE FIELD OVERWRITE message ON job TYPE string;
+DEFINE FIELD OVERWRITE status ON job TYPE string
+    ASSERT $value IN ['queued', 'running', 'done', 'failed'];
+DEFINE FIELD OVERWRITE attempts ON job TYPE int DEFAULT 0;
+DEFINE FIELD OVERWRITE response_message_id ON job TYPE option<string>;
+DEFINE FIELD OVERWRITE error ON job TYPE option<string>;
+DEFINE FIELD OVERWRITE created_at ON job TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE started_at ON job TYPE option<datetime>;
+DEFINE FIELD OVERWRITE finished_at ON job TYPE option<datetime>;
+DEFINE INDEX OVERWRITE job_status_idx ON job FIELDS status, created_at;
+DEFINE INDEX OVERWRITE job_workflow_idx ON job FIELDS workflow_id;
+
+-- Table: workflow_checkpoint
+-- Tool loop state of the running primary agent of a workflow (resume after a crash)
+DEFINE TABLE OVERWRITE workflow_checkpoint SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON workflow_checkpoint TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON workflow_checkpoint TYPE string;
+DEFINE FIELD OVERWRITE agent_id ON workflow_checkpoint TYPE string;
+DEFINE FIELD OVERWRITE iteration ON workflow_checkpoint TYPE int;
+DEFINE FIELD OVERWRITE pending_tools ON workflow_checkpoint TYPE array<string> DEFAULT [];
+DEFINE FIELD OVERWRITE state ON workflow_checkpoint TYPE string;
+DEFINE FIELD OVERWRITE updated_at ON workflow_checkpoint TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE workflow_checkpoint_workflow_idx ON workflow_checkpoint FIELDS workflow_id;
+
+-- Table: knowledge_pack
+-- Curated bundles of documents and memories attached to agents
+DEFINE TABLE OVERWRITE knowledge_pack SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON knowledge_pack TYPE string;
+DEFINE FIELD OVERWRITE name ON knowledge_pack TYPE string;
+DEFINE FIELD OVERWRITE description ON knowledge_pack TYPE string DEFAULT '';
+DEFINE FIELD OVERWRITE agent_ids ON knowledge_pack TYPE array<string> DEFAULT [];
+DEFINE FIELD OVERWRITE memory_ids ON knowledge_pack TYPE array<string> DEFAULT [];
+DEFINE FIELD OVERWRITE created_at ON knowledge_pack TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE updated_at ON knowledge_pack TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE unique_knowledge_pack_name ON knowledge_pack FIELDS name UNIQUE;
+
+-- Table: knowledge_chunk
+-- Chunks of the documents of a knowledge pack (retrieved for the attached agents only)
+DEFINE TABLE OVERWRITE knowledge_chunk SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON knowledge_chunk TYPE string;
+DEFINE FIELD OVERWRITE pack_id ON knowledge_chunk TYPE string;
+DEFINE FIELD OVERWRITE document ON knowledge_chunk TYPE string;
+DEFINE FIELD OVERWRITE position ON knowledge_chunk TYPE int;
+DEFINE FIELD OVERWRITE content ON knowledge_chunk TYPE string;
+DEFINE FIELD OVERWRITE embedding ON knowledge_chunk TYPE option<array<float>>;
+DEFINE FIELD OVERWRITE created_at ON knowledge_chunk TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE knowledge_chunk_pack_idx ON knowledge_chunk FIELDS pack_id, document;
 
 -- Table: memory (vectoriel)
 DEFINE TABLE OVERWRITE memory SCHEMAFULL;
@@ -87,22 +246,27 @@ DEFINE FIELD OVERWRITE metadata.priority ON memory TYPE option<float>;
 DEFINE FIELD OVERWRITE metadata.agent_source ON memory TYPE option<string>;
 DEFINE FIELD OVERWRITE importance ON memory TYPE float DEFAULT 0.5;
 DEFINE FIELD OVERWRITE expires_at ON memory TYPE option<datetime>;
+-- Background embedding state (NONE = stored without embedding service)
+DEFINE FIELD OVERWRITE embedding_status ON memory TYPE option<string> ASSERT $value IS NONE OR $value IN ['pending', 'ready', 'failed'];
 DEFINE FIELD OVERWRITE created_at ON memory TYPE datetime DEFAULT time::now();
 
 -- Index HNSW pour vector search (1024D Mistral/Ollama embeddings)
-DEFINE INDEX OVERWRITE memory_vec_idx ON memory FIELDS embedding HNSW DIMENSION 1024 DIST COSINE;
+-- IF NOT EXISTS: the dimension follows the embedding provider (migrate_embedding_dimension)
+DEFINE INDEX IF NOT EXISTS memory_vec_idx ON memory FIELDS embedding HNSW DIMENSION 1024 DIST COSINE;
 -- Index for workflow scoping
 DEFINE INDEX OVERWRITE memory_workflow_idx ON memory FIELDS workflow_id;
 -- OPT-MEM-4: Composite index for search_memories() with type + workflow_id
 DEFINE INDEX OVERWRITE memory_type_workflow_idx ON memory FIELDS type, workflow_id;
 -- OPT-MEM-4: Composite index for TTL cleanup preparation (type + created_at)
 DEFINE INDEX OVERWRITE memory_type_created_idx ON memory FIELDS type, created_at;
+-- Pending embeddings lookup (search fallback and queue recovery)
+DEFINE INDEX OVERWRITE memory_embedding_status_idx ON memory FIELDS embedding_status;
 
 -- Table: validation_request
 DEFINE TABLE OVERWRITE validation_request SCHEMAFULL;
 DEFINE FIELD OVERWRITE id ON validation_request TYPE string;
 DEFINE FIELD OVERWRITE workflow_id ON validation_request TYPE string;
-DEFINE FIELD OVERWRITE type ON validation_request TYPE string ASSERT $value IN ['tool', 'sub_agent', 'mcp', 'file_op', 'db_op'];
+DEFINE FIELD OVERWRITE type ON validation_request TYPE string ASSERT $value IN ['tool', 'sub_agent', 'mcp', 'file_op', 'db_op', 'token_anomaly', 'tool_first_use'];
 DEFINE FIELD OVERWRITE operation ON validation_request TYPE string;
 DEFINE FIELD OVERWRITE details ON validation_request TYPE object;
 DEFINE FIELD OVERWRITE risk_level ON validation_request TYPE string ASSERT $value IN ['low', 'medium', 'high'];
@@ -176,6 +340,25 @@ DEFINE FIELD OVERWRITE timestamp ON mcp_call_log TYPE datetime DEFAULT time::now
 DEFINE INDEX OVERWRITE mcp_call_workflow ON mcp_call_log FIELDS workflow_id;
 DEFINE INDEX OVERWRITE mcp_call_server ON mcp_call_log FIELDS server_name;
 
+-- Table: audit_log (append-only log of sensitive actions)
+-- details is stored as a JSON string (SCHEMAFULL nested object workaround)
+DEFINE TABLE OVERWRITE audit_log SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON audit_log TYPE string;
+DEFINE FIELD OVERWRITE action ON audit_log TYPE string
+    ASSERT $value IN ['api_key_access', 'tool_execution', 'validation_decision', 'agent_config_change', 'mcp_server_start', 'mcp_server_stop', 'moderation'];
+DEFINE FIELD OVERWRITE actor ON audit_log TYPE string;
+DEFINE FIELD OVERWRITE target ON audit_log TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON audit_log TYPE option<string>;
+DEFINE FIELD OVERWRITE success ON audit_log TYPE bool;
+DEFINE FIELD OVERWRITE details ON audit_log TYPE string DEFAULT '{}';
+DEFINE FIELD OVERWRITE created_at ON audit_log TYPE datetime DEFAULT time::now() READONLY;
+DEFINE INDEX OVERWRITE audit_log_created_idx ON audit_log FIELDS created_at;
+DEFINE INDEX OVERWRITE audit_log_action_idx ON audit_log FIELDS action;
+-- Entries are immutable: only rotation deletes them
+DEFINE EVENT OVERWRITE audit_log_immutable ON audit_log WHEN $event = "UPDATE" THEN {
+    THROW "Audit log entries are immutable";
+};
+
 -- =============================================
 -- Table: llm_model
 -- Stores LLM models (builtin + custom)
@@ -204,6 +387,14 @@ DEFINE FIELD OVERWRITE input_price_per_mtok ON llm_model TYPE float
 DEFINE FIELD OVERWRITE output_price_per_mtok ON llm_model TYPE float
     ASSERT $value >= 0.0 AND $value <= 1000.0
     DEFAULT 0.0;
+-- Metadata reported by the provider API (refresh_provider_models)
+DEFINE FIELD OVERWRITE supports_vision ON llm_model TYPE option<bool>;
+DEFINE FIELD OVERWRITE supports_tools ON llm_model TYPE option<bool>;
+DEFINE FIELD OVERWRITE metadata_drift ON llm_model TYPE array<object> DEFAULT [];
+DEFINE FIELD OVERWRITE metadata_drift[*].field ON llm_model TYPE string;
+DEFINE FIELD OVERWRITE metadata_drift[*].stored ON llm_model TYPE int;
+DEFINE FIELD OVERWRITE metadata_drift[*].reported ON llm_model TYPE int;
+DEFINE FIELD OVERWRITE metadata_refreshed_at ON llm_model TYPE option<datetime>;
 DEFINE FIELD OVERWRITE created_at ON llm_model TYPE datetime DEFAULT time::now();
 DEFINE FIELD OVERWRITE updated_at ON llm_model TYPE datetime DEFAULT time::now();
 
@@ -242,6 +433,108 @@ DEFINE FIELD OVERWRITE updated_at ON custom_provider TYPE datetime DEFAULT time:
 
 DEFINE INDEX OVERWRITE unique_custom_provider_name ON custom_provider FIELDS name UNIQUE;
 
+-- =============================================
+-- Table: external_database
+-- External databases queried by the SqlTool
+-- (connection strings are stored in SecureKeyStore)
+-- =============================================
+DEFINE TABLE OVERWRITE external_database SCHEMAFULL;
+DEFINE FIELD OVERWRITE name ON external_database TYPE string
+    ASSERT string::len($value) > 0 AND string::len($value) <= 64;
+DEFINE FIELD OVERWRITE kind ON external_database TYPE string
+    ASSERT $value IN ['postgres', 'mysql', 'sqlite'];
+DEFINE FIELD OVERWRITE description ON external_database TYPE option<string>;
+DEFINE FIELD OVERWRITE read_only ON external_database TYPE bool DEFAULT false;
+DEFINE FIELD OVERWRITE max_rows ON external_database TYPE int
+    ASSERT $value >= 1 AND $value <= 1000;
+DEFINE FIELD OVERWRITE created_at ON external_database TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE updated_at ON external_database TYPE datetime DEFAULT time::now();
+
+-- =============================================
+-- Table: email_account
+-- Email accounts used by the EmailTool
+-- (passwords are stored in SecureKeyStore)
+-- =============================================
+DEFINE TABLE OVERWRITE email_account SCHEMAFULL;
+DEFINE FIELD OVERWRITE name ON email_account TYPE string
+    ASSERT string::len($value) > 0 AND string::len($value) <= 64;
+DEFINE FIELD OVERWRITE address ON email_account TYPE string;
+DEFINE FIELD OVERWRITE display_name ON email_account TYPE option<string>;
+DEFINE FIELD OVERWRITE imap_host ON email_account TYPE string;
+DEFINE FIELD OVERWRITE imap_port ON email_account TYPE int;
+DEFINE FIELD OVERWRITE smtp_host ON email_account TYPE string;
+DEFINE FIELD OVERWRITE smtp_port ON email_account TYPE int;
+DEFINE FIELD OVERWRITE username ON email_account TYPE string;
+DEFINE FIELD OVERWRITE read_only ON email_account TYPE bool DEFAULT true;
+DEFINE FIELD OVERWRITE created_at ON email_account TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE updated_at ON email_account TYPE datetime DEFAULT time::now();
+
+-- =============================================
+-- Table: feed
+-- RSS/Atom feeds read by the RssTool
+-- =============================================
+DEFINE TABLE OVERWRITE feed SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON feed TYPE string;
+DEFINE FIELD OVERWRITE url ON feed TYPE string
+    ASSERT string::len($value) > 0 AND string::len($value) <= 2048;
+DEFINE FIELD OVERWRITE title ON feed TYPE option<string>;
+DEFINE FIELD OVERWRITE site_url ON feed TYPE option<string>;
+DEFINE FIELD OVERWRITE last_fetched_at ON feed TYPE option<datetime>;
+DEFINE FIELD OVERWRITE last_error ON feed TYPE option<string>;
+DEFINE FIELD OVERWRITE created_at ON feed TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE feed_url_idx ON feed FIELDS url UNIQUE;
+
+-- =============================================
+-- Table: feed_item
+-- Items already returned by the RssTool (deduplication)
+-- =============================================
+DEFINE TABLE OVERWRITE feed_item SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON feed_item TYPE string;
+DEFINE FIELD OVERWRITE feed_id ON feed_item TYPE string;
+DEFINE FIELD OVERWRITE guid ON feed_item TYPE string;
+DEFINE FIELD OVERWRITE title ON feed_item TYPE string;
+DEFINE FIELD OVERWRITE link ON feed_item TYPE option<string>;
+DEFINE FIELD OVERWRITE summary ON feed_item TYPE option<string>;
+DEFINE FIELD OVERWRITE published_at ON feed_item TYPE option<string>;
+DEFINE FIELD OVERWRITE seen_at ON feed_item TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE feed_item_feed_idx ON feed_item FIELDS feed_id, seen_at;
+
+-- =============================================
+-- Table: custom_tool
+-- Tools defined by the user as Rhai scripts
+-- (input_schema holds the serialized JSON Schema)
+-- =============================================
+DEFINE TABLE OVERWRITE custom_tool SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON custom_tool TYPE string;
+DEFINE FIELD OVERWRITE name ON custom_tool TYPE string
+    ASSERT string::len($value) > 0 AND string::len($value) <= 64;
+DEFINE FIELD OVERWRITE description ON custom_tool TYPE string;
+DEFINE FIELD OVERWRITE input_schema ON custom_tool TYPE string;
+DEFINE FIELD OVERWRITE script ON custom_tool TYPE string;
+DEFINE FIELD OVERWRITE enabled ON custom_tool TYPE bool DEFAULT true;
+DEFINE FIELD OVERWRITE created_at ON custom_tool TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE updated_at ON custom_tool TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE custom_tool_name_idx ON custom_tool FIELDS name UNIQUE;
+
+-- =============================================
+-- Table: macro_tool
+-- Named sequences of tool calls defined by the user
+-- (input_schema, steps and output hold serialized JSON; a null
+-- output returns the result of the last step)
+-- =============================================
+DEFINE TABLE OVERWRITE macro_tool SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON macro_tool TYPE string;
+DEFINE FIELD OVERWRITE name ON macro_tool TYPE string
+    ASSERT string::len($value) > 0 AND string::len($value) <= 64;
+DEFINE FIELD OVERWRITE description ON macro_tool TYPE string;
+DEFINE FIELD OVERWRITE input_schema ON macro_tool TYPE string;
+DEFINE FIELD OVERWRITE steps ON macro_tool TYPE string;
+DEFINE FIELD OVERWRITE output ON macro_tool TYPE string;
+DEFINE FIELD OVERWRITE enabled ON macro_tool TYPE bool DEFAULT true;
+DEFINE FIELD OVERWRITE created_at ON macro_tool TYPE datetime DEFAULT time::now();
+DEFINE FIELD OVERWRITE updated_at ON macro_tool TYPE datetime DEFAULT time::now();
+DEFINE INDEX OVERWRITE macro_tool_name_idx ON macro_tool FIELDS name UNIQUE;
+
 -- =============================================
 -- Table: agent
 -- Stores user-created agent configurations
@@ -281,6 +574,22 @@ DEFINE FIELD OVERWRITE max_tool_iterations ON agent TYPE int
 DEFINE FIELD OVERWRITE enable_thinking ON agent TYPE bool
     DEFAULT true;
 
+-- Per-tool parameter defaults and restrictions
+-- Stored as JSON string to bypass SurrealDB SCHEMAFULL nested object filtering
+DEFINE FIELD OVERWRITE tool_settings ON agent TYPE string DEFAULT '{}';
+
+-- Per-MCP-server tool allow/deny lists, keyed by server name
+-- Stored as JSON string to bypass SurrealDB SCHEMAFULL nested object filtering
+DEFINE FIELD OVERWRITE mcp_tool_policy ON agent TYPE string DEFAULT '{}';
+
+-- Regression test cases bundled with the agent (strict flag + cases)
+-- Stored as JSON string to bypass SurrealDB SCHEMAFULL nested object filtering
+DEFINE FIELD OVERWRITE regressions ON agent TYPE string DEFAULT '{}';
+
+-- Output moderation policy (enabled, action, source, blocked terms)
+-- Stored as JSON string to bypass SurrealDB SCHEMAFULL nested object filtering
+DEFINE FIELD OVERWRITE moderation ON agent TYPE string DEFAULT '{}';
+
 -- Timestamps
 DEFINE FIELD OVERWRITE created_at ON agent TYPE datetime DEFAULT time::now();
 DEFINE FIELD OVERWRITE updated_at ON agent TYPE datetime DEFAULT time::now();
@@ -311,6 +620,11 @@ DEFINE FIELD OVERWRITE success ON tool_execution TYPE bool;
 DEFINE FIELD OVERWRITE error_message ON tool_execution TYPE option<string>;
 DEFINE FIELD OVERWRITE duration_ms ON tool_execution TYPE int;
 DEFINE FIELD OVERWRITE iteration ON tool_execution TYPE int;
+-- Repeated calls: previous execution of the same call and output diff (JSON string)
+DEFINE FIELD OVERWRITE previous_execution_id ON tool_execution TYPE option<string>;
+DEFINE FIELD OVERWRITE result_diff ON tool_execution TYPE option<string>;
+-- Start of the execution (Unix time in ms, timeline export)
+DEFINE FIELD OVERWRITE started_at_ms ON tool_execution TYPE option<int>;
 DEFINE FIELD OVERWRITE created_at ON tool_execution TYPE datetime DEFAULT time::now();
 
 -- Indexes for efficient querying
@@ -319,6 +633,94 @@ DEFINE INDEX OVERWRITE tool_exec_message_idx ON tool_execution FIELDS message_id
 DEFINE INDEX OVERWRITE tool_exec_agent_idx ON tool_execution FIELDS agent_id;
 DEFINE INDEX OVERWRITE tool_exec_type_idx ON tool_execution FIELDS tool_type;
 
+-- =============================================
+-- Table: agent_tool_usage
+-- Per-agent tool usage statistics (system prompt hint)
+-- =============================================
+DEFINE TABLE OVERWRITE agent_tool_usage SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON agent_tool_usage TYPE string;
+DEFINE FIELD OVERWRITE agent_id ON agent_tool_usage TYPE string;
+DEFINE FIELD OVERWRITE tool_name ON agent_tool_usage TYPE string
+    ASSERT string::len($value) >= 1 AND string::len($value) <= 256;
+DEFINE FIELD OVERWRITE success_count ON agent_tool_usage TYPE int DEFAULT 0;
+DEFINE FIELD OVERWRITE failure_count ON agent_tool_usage TYPE int DEFAULT 0;
+DEFINE FIELD OVERWRITE task_keywords ON agent_tool_usage TYPE array<string> DEFAULT [];
+DEFINE FIELD OVERWRITE last_used_at ON agent_tool_usage TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX OVERWRITE agent_tool_usage_agent_idx ON agent_tool_usage FIELDS agent_id;
+
+-- =============================================
+-- Table: tool_acknowledgment
+-- First-use acknowledgments of powerful tools, per (agent, tool)
+-- =============================================
+DEFINE TABLE OVERWRITE tool_acknowledgment SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON tool_acknowledgment TYPE string;
+DEFINE FIELD OVERWRITE agent_id ON tool_acknowledgment TYPE string;
+DEFINE FIELD OVERWRITE tool_name ON tool_acknowledgment TYPE string
+    ASSERT string::len($value) >= 1 AND string::len($value) <= 256;
+DEFINE FIELD OVERWRITE kind ON tool_acknowledgment TYPE string
+    ASSERT $value IN ['shell', 'file_system_write', 'external_sql'];
+DEFINE FIELD OVERWRITE acknowledged_at ON tool_acknowledgment TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX OVERWRITE tool_acknowledgment_agent_idx ON tool_acknowledgment FIELDS agent_id;
+
+-- =============================================
+-- Table: pending_item
+-- Items awaiting the user (priority inbox), maintained by each subsystem
+-- =============================================
+DEFINE TABLE OVERWRITE pending_item SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON pending_item TYPE string;
+DEFINE FIELD OVERWRITE kind ON pending_item TYPE string
+    ASSERT $value IN ['validation', 'user_question', 'paused_workflow', 'interrupted_execution',
+        'failed_schedule', 'expiring_credential', 'expiring_api_key'];
+DEFINE FIELD OVERWRITE source_id ON pending_item TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON pending_item TYPE option<string>;
+DEFINE FIELD OVERWRITE title ON pending_item TYPE string;
+DEFINE FIELD OVERWRITE detail ON pending_item TYPE option<string>;
+DEFINE FIELD OVERWRITE priority ON pending_item TYPE string
+    ASSERT $value IN ['low', 'normal', 'high', 'urgent'];
+DEFINE FIELD OVERWRITE due_at ON pending_item TYPE option<datetime>;
+DEFINE FIELD OVERWRITE created_at ON pending_item TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX OVERWRITE pending_item_workflow_idx ON pending_item FIELDS workflow_id;
+
+-- =============================================
+-- Table: behavior_change
+-- Ordered log of the changes affecting agent behavior (prompt, model, tools, MCP)
+-- =============================================
+DEFINE TABLE OVERWRITE behavior_change SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON behavior_change TYPE string;
+DEFINE FIELD OVERWRITE position ON behavior_change TYPE int;
+DEFINE FIELD OVERWRITE kind ON behavior_change TYPE string
+    ASSERT $value IN ['agent_created', 'agent_deleted', 'prompt_changed', 'model_changed',
+        'tools_changed', 'mcp_servers_changed', 'settings_changed', 'mcp_server_added',
+        'mcp_server_updated', 'mcp_server_removed', 'mcp_tools_changed'];
+DEFINE FIELD OVERWRITE agent_id ON behavior_change TYPE option<string>;
+DEFINE FIELD OVERWRITE subject ON behavior_change TYPE string;
+DEFINE FIELD OVERWRITE summary ON behavior_change TYPE string;
+-- Changed fields, stored as JSON string to bypass SurrealDB SCHEMAFULL nested object filtering
+DEFINE FIELD OVERWRITE changes ON behavior_change TYPE string DEFAULT '[]';
+DEFINE FIELD OVERWRITE created_at ON behavior_change TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX OVERWRITE behavior_change_position_idx ON behavior_change FIELDS position UNIQUE;
+DEFINE INDEX OVERWRITE behavior_change_agent_idx ON behavior_change FIELDS agent_id;
+
+-- =============================================
+-- Table: prompt_version
+-- Version history of the prompt library (one row per prompt save)
+-- =============================================
+DEFINE TABLE OVERWRITE prompt_version SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON prompt_version TYPE string;
+DEFINE FIELD OVERWRITE prompt_id ON prompt_version TYPE string;
+DEFINE FIELD OVERWRITE version ON prompt_version TYPE int;
+DEFINE FIELD OVERWRITE name ON prompt_version TYPE string;
+DEFINE FIELD OVERWRITE description ON prompt_version TYPE string;
+DEFINE FIELD OVERWRITE category ON prompt_version TYPE string;
+DEFINE FIELD OVERWRITE content ON prompt_version TYPE string;
+DEFINE FIELD OVERWRITE created_at ON prompt_version TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX OVERWRITE prompt_version_prompt_idx ON prompt_version FIELDS prompt_id, version UNIQUE;
+
 -- =============================================
 -- Table: thinking_step
 -- Captures agent reasoning/thinking steps
@@ -342,6 +744,28 @@ DEFINE INDEX OVERWRITE thinking_workflow_idx ON thinking_step FIELDS workflow_id
 DEFINE INDEX OVERWRITE thinking_message_idx ON thinking_step FIELDS message_id;
 DEFINE INDEX OVERWRITE thinking_agent_idx ON thinking_step FIELDS agent_id;
 
+-- =============================================
+-- Table: llm_turn
+-- LLM calls of the tool loop (execution trace)
+-- =============================================
+DEFINE TABLE OVERWRITE llm_turn SCHEMAFULL;
+DEFINE FIELD OVERWRITE id ON llm_turn TYPE string;
+DEFINE FIELD OVERWRITE workflow_id ON llm_turn TYPE string;
+DEFINE FIELD OVERWRITE message_id ON llm_turn TYPE string;
+DEFINE FIELD OVERWRITE agent_id ON llm_turn TYPE string;
+DEFINE FIELD OVERWRITE iteration ON llm_turn TYPE int
+    ASSERT $value >= 0;
+DEFINE FIELD OVERWRITE tokens_input ON llm_turn TYPE int;
+DEFINE FIELD OVERWRITE tokens_output ON llm_turn TYPE int;
+DEFINE FIELD OVERWRITE duration_ms ON llm_turn TYPE int;
+DEFINE FIELD OVERWRITE tool_calls ON llm_turn TYPE int;
+-- Start of the call (Unix time in ms, timeline export)
+DEFINE FIELD OVERWRITE started_at_ms ON llm_turn TYPE option<int>;
+DEFINE FIELD OVERWRITE created_at ON llm_turn TYPE datetime DEFAULT time::now();
+
+DEFINE INDEX OVERWRITE llm_turn_workflow_idx ON llm_turn FIELDS workflow_id;
+DEFINE INDEX OVERWRITE llm_turn_message_idx ON llm_turn FIELDS message_id;
+
 -- =============================================
 -- Table: sub_agent_execution
 -- Tracks sub-agent spawn/delegate operations
@@ -21,9 +21,40 @@
 //! SurrealDB has specific requirements for string data:
 //! - Null characters (`\0`) cause panics in the Strand type
 //! - These functions sanitize data before database insertion
+//!
+//! Values must be bound as query parameters, never interpolated in the query
+//! text: [`find_interpolated_id`] detects interpolated record IDs, and
+//! [`DBClient`](super::DBClient) asserts their absence in debug builds.
 
+use chrono::{DateTime, SecondsFormat, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde_json::Value;
 
+/// Record IDs written in the query text (`table:`id``, `table:⟨id⟩`) and
+/// quoted UUID literals.
+static INTERPOLATED_ID: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"[A-Za-z_][A-Za-z0-9_]*:(`[^`]*`|⟨[^⟩]*⟩)|['"][0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}['"]"#,
+    )
+    .expect("valid interpolated ID pattern")
+});
+
+/// Returns the first record ID interpolated in a query, if any.
+///
+/// Record IDs must be bound with `type::thing('table', $id)` and values with
+/// `$param`, so that no user data ends up in the query text.
+///
+/// # Example
+///
+/// ```ignore
+/// assert!(find_interpolated_id("SELECT * FROM workflow:`abc`").is_some());
+/// assert!(find_interpolated_id("SELECT * FROM type::thing('workflow', $id)").is_none());
+/// ```
+pub fn find_interpolated_id(query: &str) -> Option<&str> {
+    INTERPOLATED_ID.find(query).map(|m| m.as_str())
+}
+
 /// Sanitizes a JSON value for SurrealDB by removing null characters.
 ///
 /// SurrealDB's Strand type panics on strings containing `\0` characters.
@@ -71,11 +102,55 @@ pub fn sanitize_for_surrealdb(value: Value) -> Value {
     }
 }
 
+/// Encodes a page cursor from the position of the last record of a page.
+///
+/// Records are ordered by `created_at` then ID; the cursor is opaque to the
+/// frontend, which only passes it back to load the next page.
+pub fn encode_cursor(created_at: &DateTime<Utc>, id: &str) -> String {
+    format!(
+        "{}|{}",
+        created_at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        id
+    )
+}
+
+/// Decodes a page cursor into its timestamp (RFC 3339) and record ID.
+pub fn decode_cursor(cursor: &str) -> Result<(String, String), String> {
+    let (created_at, id) = cursor
+        .split_once('|')
+        .ok_or_else(|| "Invalid cursor".to_string())?;
+    DateTime::parse_from_rfc3339(created_at).map_err(|_| "Invalid cursor".to_string())?;
+    if id.is_empty() {
+        return Err("Invalid cursor".to_string());
+    }
+    Ok((created_at.to_string(), id.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_find_interpolated_id() {
+        assert_eq!(
+            find_interpolated_id("SELECT * FROM workflow:`abc` WHERE x = 1"),
+            Some("workflow:`abc`")
+        );
+        assert!(find_interpolated_id("DELETE settings:⟨theme⟩").is_some());
+        assert!(find_interpolated_id(
+            "SELECT * FROM message WHERE workflow_id = '123e4567-e89b-12d3-a456-426614174000'"
+        )
+        .is_some());
+
+        assert!(find_interpolated_id("SELECT * FROM type::thing('workflow', $id)").is_none());
+        assert!(find_interpolated_id(
+            "SELECT meta::id(id) AS id, time::now() FROM message WHERE workflow_id = $workflow_id"
+        )
+        .is_none());
+        assert!(find_interpolated_id("DEFINE FIELD kind ON t TYPE string").is_none());
+    }
+
     #[test]
     fn test_sanitize_simple_string() {
         let value = json!("hello\0world");
@@ -148,4 +223,22 @@ mod tests {
         let result = sanitize_for_surrealdb(value);
         assert_eq!(result, json!(""));
     }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let created_at = DateTime::parse_from_rfc3339("2026-01-02T03:04:05.123456789Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let cursor = encode_cursor(&created_at, "abc");
+        assert_eq!(
+            decode_cursor(&cursor).unwrap(),
+            (
+                "2026-01-02T03:04:05.123456789Z".to_string(),
+                "abc".to_string()
+            )
+        );
+        assert!(decode_cursor("abc").is_err());
+        assert!(decode_cursor("yesterday|abc").is_err());
+        assert!(decode_cursor("2026-01-02T03:04:05Z|").is_err());
+    }
 }
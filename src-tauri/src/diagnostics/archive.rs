@@ -0,0 +1,199 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal zip writer (deflate, no zip64) for the diagnostics bundle.
+
+use chrono::{Datelike, Local, Timelike};
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{self, Write};
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// Version 2.0 (deflate)
+const VERSION: u16 = 20;
+/// File names are UTF-8
+const FLAG_UTF8: u16 = 0x0800;
+const METHOD_DEFLATE: u16 = 8;
+
+/// Central directory entry of a written file
+struct Entry {
+    name: String,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Writes files into a zip archive held in memory
+pub struct ZipWriter {
+    buffer: Vec<u8>,
+    entries: Vec<Entry>,
+    dos_time: u16,
+    dos_date: u16,
+}
+
+impl Default for ZipWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZipWriter {
+    /// Creates an empty archive, its files dated now.
+    pub fn new() -> Self {
+        let now = Local::now();
+        Self {
+            buffer: Vec::new(),
+            entries: Vec::new(),
+            dos_time: ((now.hour() << 11) | (now.minute() << 5) | (now.second() / 2)) as u16,
+            dos_date: (((now.year().max(1980) - 1980) as u32) << 9 | (now.month() << 5) | now.day())
+                as u16,
+        }
+    }
+
+    /// Adds a file (deflated) to the archive.
+    pub fn add_file(&mut self, name: &str, content: &[u8]) -> io::Result<()> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content)?;
+        let compressed = encoder.finish()?;
+
+        let entry = Entry {
+            name: name.to_string(),
+            crc: crc32fast::hash(content),
+            compressed_size: to_u32(compressed.len())?,
+            size: to_u32(content.len())?,
+            offset: to_u32(self.buffer.len())?,
+        };
+
+        let buffer = &mut self.buffer;
+        put_u32(buffer, LOCAL_HEADER_SIGNATURE);
+        put_u16(buffer, VERSION);
+        put_u16(buffer, FLAG_UTF8);
+        put_u16(buffer, METHOD_DEFLATE);
+        put_u16(buffer, self.dos_time);
+        put_u16(buffer, self.dos_date);
+        put_u32(buffer, entry.crc);
+        put_u32(buffer, entry.compressed_size);
+        put_u32(buffer, entry.size);
+        put_u16(buffer, to_u16(name.len())?);
+        put_u16(buffer, 0);
+        buffer.extend_from_slice(name.as_bytes());
+        buffer.extend_from_slice(&compressed);
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Writes the central directory and returns the archive.
+    pub fn finish(mut self) -> io::Result<Vec<u8>> {
+        let directory_offset = to_u32(self.buffer.len())?;
+        for entry in &self.entries {
+            let buffer = &mut self.buffer;
+            put_u32(buffer, CENTRAL_HEADER_SIGNATURE);
+            put_u16(buffer, VERSION);
+            put_u16(buffer, VERSION);
+            put_u16(buffer, FLAG_UTF8);
+            put_u16(buffer, METHOD_DEFLATE);
+            put_u16(buffer, self.dos_time);
+            put_u16(buffer, self.dos_date);
+            put_u32(buffer, entry.crc);
+            put_u32(buffer, entry.compressed_size);
+            put_u32(buffer, entry.size);
+            put_u16(buffer, to_u16(entry.name.len())?);
+            // Extra field, comment, disk number, internal and external attributes
+            put_u16(buffer, 0);
+            put_u16(buffer, 0);
+            put_u16(buffer, 0);
+            put_u16(buffer, 0);
+            put_u32(buffer, 0);
+            put_u32(buffer, entry.offset);
+            buffer.extend_from_slice(entry.name.as_bytes());
+        }
+        let directory_size = to_u32(self.buffer.len())? - directory_offset;
+        let count = to_u16(self.entries.len())?;
+
+        let buffer = &mut self.buffer;
+        put_u32(buffer, END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        put_u16(buffer, 0);
+        put_u16(buffer, 0);
+        put_u16(buffer, count);
+        put_u16(buffer, count);
+        put_u32(buffer, directory_size);
+        put_u32(buffer, directory_offset);
+        put_u16(buffer, 0);
+        Ok(self.buffer)
+    }
+}
+
+fn put_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn to_u16(value: usize) -> io::Result<u16> {
+    u16::try_from(value).map_err(|_| io::Error::other("Zip field too large"))
+}
+
+fn to_u32(value: usize) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| io::Error::other("Zip archive too large (no zip64)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::DeflateDecoder;
+    use std::io::Read;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        let mut zip = ZipWriter::new();
+        zip.add_file("logs.txt", b"line 1\nline 2\n").unwrap();
+        zip.add_file("schema.json", br#"{"up_to_date":true}"#)
+            .unwrap();
+        let bytes = zip.finish().unwrap();
+
+        // End of central directory: 2 entries
+        let end = bytes.len() - 22;
+        assert_eq!(u32_at(&bytes, end), END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        assert_eq!(u16_at(&bytes, end + 10), 2);
+        let directory_offset = u32_at(&bytes, end + 16) as usize;
+        assert_eq!(u32_at(&bytes, directory_offset), CENTRAL_HEADER_SIGNATURE);
+
+        // First local file: name then deflated content
+        assert_eq!(u32_at(&bytes, 0), LOCAL_HEADER_SIGNATURE);
+        let compressed_size = u32_at(&bytes, 18) as usize;
+        let name_len = u16_at(&bytes, 26) as usize;
+        assert_eq!(&bytes[30..30 + name_len], b"logs.txt");
+        let data = &bytes[30 + name_len..30 + name_len + compressed_size];
+        let mut content = String::new();
+        DeflateDecoder::new(data)
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "line 1\nline 2\n");
+        assert_eq!(u32_at(&bytes, 14), crc32fast::hash(content.as_bytes()));
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recent log lines kept in memory.
+//!
+//! Logs are only written to stdout, so the diagnostics bundle takes them from
+//! a [`RecentLogsLayer`] added to the tracing subscriber, which keeps the last
+//! [`LOG_BUFFER_LINES`] events. Lines are redacted when the bundle is built.
+
+use crate::tools::constants::diagnostics::{LOG_BUFFER_LINES, MAX_LOG_LINE_CHARS};
+use chrono::{SecondsFormat, Utc};
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Log lines of the process
+static RECENT_LOGS: Lazy<LogBuffer> = Lazy::new(|| LogBuffer::new(LOG_BUFFER_LINES));
+
+/// Ring buffer of log lines (oldest dropped when full)
+struct LogBuffer {
+    lines: Mutex<VecDeque<String>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    fn push(&self, mut line: String) {
+        if let Some((index, _)) = line.char_indices().nth(MAX_LOG_LINE_CHARS) {
+            line.truncate(index);
+            line.push('…');
+        }
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect()
+    }
+}
+
+/// Tracing layer keeping the recent events in memory
+pub struct RecentLogsLayer;
+
+impl<S: Subscriber> Layer<S> for RecentLogsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        RECENT_LOGS.push(format!(
+            "{} {:>5} {}: {}{}",
+            Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            metadata.level(),
+            metadata.target(),
+            visitor.message,
+            visitor.fields
+        ));
+    }
+}
+
+/// Formats the message and fields of an event
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Returns the recent log lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    RECENT_LOGS.lines()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_log_buffer_drops_oldest() {
+        let buffer = LogBuffer::new(2);
+        buffer.push("one".to_string());
+        buffer.push("two".to_string());
+        buffer.push("three".to_string());
+        assert_eq!(buffer.lines(), vec!["two", "three"]);
+
+        buffer.push("x".repeat(MAX_LOG_LINE_CHARS + 10));
+        assert_eq!(
+            buffer.lines()[1].chars().count(),
+            MAX_LOG_LINE_CHARS + 1,
+            "long lines are truncated"
+        );
+    }
+
+    #[test]
+    fn test_layer_records_events() {
+        let subscriber = tracing_subscriber::registry().with(RecentLogsLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(server = "docs", attempt = 2, "diagnostics-layer-test");
+        });
+
+        let line = recent_lines()
+            .into_iter()
+            .find(|line| line.contains("diagnostics-layer-test"))
+            .expect("event recorded");
+        assert!(line.contains(" WARN "));
+        assert!(line.ends_with("diagnostics-layer-test server=docs attempt=2"));
+    }
+}
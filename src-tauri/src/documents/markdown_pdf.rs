@@ -0,0 +1,732 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Simple PDFs generated from Markdown.
+//!
+//! Supports headings, paragraphs with bold, italic and inline code, nested
+//! lists, block quotes, code blocks, tables (one line per row) and rules on
+//! A4 pages numbered in the footer. Text uses the standard PDF fonts
+//! (Helvetica, Courier) with the WinAnsi encoding: characters outside
+//! Latin-1 and the usual typographic signs are replaced by `?`. Images are
+//! rendered as their alternative text.
+
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, Stream, StringFormat};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// A4 page size (points)
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+/// Page margins (points)
+const MARGIN: f32 = 56.0;
+/// Body font size (points)
+const BODY_SIZE: f32 = 11.0;
+/// Code font size (points)
+const CODE_SIZE: f32 = 9.5;
+/// Line height as a multiple of the font size
+const LINE_SPACING: f32 = 1.35;
+/// Indentation of a list level or block quote (points)
+const INDENT: f32 = 18.0;
+
+/// Standard fonts used by the generated PDFs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Font {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+    Mono,
+}
+
+impl Font {
+    const ALL: [Font; 5] = [
+        Font::Regular,
+        Font::Bold,
+        Font::Italic,
+        Font::BoldItalic,
+        Font::Mono,
+    ];
+
+    fn resource_name(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Italic => "F3",
+            Font::BoldItalic => "F4",
+            Font::Mono => "F5",
+        }
+    }
+
+    fn base_font(self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Italic => "Helvetica-Oblique",
+            Font::BoldItalic => "Helvetica-BoldOblique",
+            Font::Mono => "Courier",
+        }
+    }
+
+    fn from_style(bold: bool, italic: bool, code: bool) -> Self {
+        match (code, bold, italic) {
+            (true, _, _) => Font::Mono,
+            (false, true, true) => Font::BoldItalic,
+            (false, true, false) => Font::Bold,
+            (false, false, true) => Font::Italic,
+            (false, false, false) => Font::Regular,
+        }
+    }
+
+    /// Width of a WinAnsi character in thousandths of the font size.
+    fn char_width(self, byte: u8) -> u16 {
+        let index = byte.wrapping_sub(32) as usize;
+        match self {
+            Font::Mono => 600,
+            Font::Regular | Font::Italic => HELVETICA_WIDTHS.get(index).copied().unwrap_or(556),
+            Font::Bold | Font::BoldItalic => {
+                HELVETICA_BOLD_WIDTHS.get(index).copied().unwrap_or(611)
+            }
+        }
+    }
+
+    /// Width of an encoded text (points).
+    fn text_width(self, text: &[u8], size: f32) -> f32 {
+        let units: u32 = text.iter().map(|byte| self.char_width(*byte) as u32).sum();
+        units as f32 * size / 1000.0
+    }
+}
+
+/// Helvetica widths of the ASCII characters 32 to 126
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+/// Helvetica-Bold widths of the ASCII characters 32 to 126
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+/// Encodes a character in WinAnsi (`?` when it has no code).
+fn win_ansi(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{A0}'..='\u{FF}' => c as u32 as u8,
+        '€' => 0x80,
+        '‚' => 0x82,
+        'ƒ' => 0x83,
+        '„' => 0x84,
+        '…' => 0x85,
+        '†' => 0x86,
+        '‡' => 0x87,
+        'ˆ' => 0x88,
+        '‰' => 0x89,
+        'Š' => 0x8A,
+        '‹' => 0x8B,
+        'Œ' => 0x8C,
+        'Ž' => 0x8E,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        '˜' => 0x98,
+        '™' => 0x99,
+        'š' => 0x9A,
+        '›' => 0x9B,
+        'œ' => 0x9C,
+        'ž' => 0x9E,
+        'Ÿ' => 0x9F,
+        '\t' => b' ',
+        _ => b'?',
+    }
+}
+
+fn encode(text: &str) -> Vec<u8> {
+    text.chars().map(win_ansi).collect()
+}
+
+/// Word of a paragraph (encoded) with its font
+#[derive(Debug, Clone)]
+struct Word {
+    text: Vec<u8>,
+    font: Font,
+    /// A space separates the word from the previous one
+    space_before: bool,
+}
+
+/// Block of the document, laid out one after the other
+#[derive(Debug)]
+enum Block {
+    /// Wrapped text: words, font size, left indentation, list marker (drawn
+    /// in the indentation)
+    Text {
+        words: Vec<Word>,
+        size: f32,
+        indent: f32,
+        marker: Option<String>,
+        space_after: f32,
+    },
+    /// Code lines (not wrapped, cut at the page width)
+    Code { lines: Vec<String>, indent: f32 },
+    /// Horizontal rule
+    Rule,
+}
+
+/// Converts Markdown events into blocks.
+#[derive(Default)]
+struct BlockBuilder {
+    blocks: Vec<Block>,
+    words: Vec<Word>,
+    pending_space: bool,
+    bold: usize,
+    italic: usize,
+    heading: Option<HeadingLevel>,
+    /// Next number of each open list (None for bullet lists)
+    lists: Vec<Option<u64>>,
+    quote_depth: usize,
+    marker: Option<String>,
+    code: Option<String>,
+    table_row: Vec<Vec<Word>>,
+    in_table_head: bool,
+}
+
+impl BlockBuilder {
+    fn indent(&self) -> f32 {
+        (self.lists.len() + self.quote_depth) as f32 * INDENT
+    }
+
+    fn push_text(&mut self, text: &str, code: bool) {
+        let font = if self.heading.is_some() || self.in_table_head {
+            Font::from_style(true, self.italic > 0, code)
+        } else {
+            Font::from_style(self.bold > 0, self.italic > 0, code)
+        };
+        let mut current: Option<Word> = None;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if let Some(word) = current.take() {
+                    self.words.push(word);
+                }
+                self.pending_space = true;
+            } else {
+                let word = current.get_or_insert_with(|| Word {
+                    text: Vec::new(),
+                    font,
+                    space_before: std::mem::take(&mut self.pending_space),
+                });
+                word.text.push(win_ansi(c));
+            }
+        }
+        if let Some(word) = current {
+            self.words.push(word);
+        }
+    }
+
+    /// Ends the current paragraph (no-op without words).
+    fn flush(&mut self, size: f32, space_after: f32) {
+        self.pending_space = false;
+        if self.words.is_empty() {
+            return;
+        }
+        let indent = self.indent();
+        let marker = self.marker.take();
+        self.blocks.push(Block::Text {
+            words: std::mem::take(&mut self.words),
+            size,
+            indent,
+            marker,
+            space_after,
+        });
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Heading { level, .. } => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.6);
+                self.heading = Some(level);
+            }
+            Tag::Strong => self.bold += 1,
+            Tag::Emphasis => self.italic += 1,
+            Tag::BlockQuote(_) => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.6);
+                self.quote_depth += 1;
+                self.italic += 1;
+            }
+            Tag::CodeBlock(_) => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.6);
+                self.code = Some(String::new());
+            }
+            Tag::List(start) => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.3);
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.3);
+                self.marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        Some(format!("{}.", *number - 1))
+                    }
+                    _ => Some("•".to_string()),
+                };
+            }
+            Tag::TableHead => self.in_table_head = true,
+            Tag::TableRow => self.table_row.clear(),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Heading(level) => {
+                let size = match level {
+                    HeadingLevel::H1 => 20.0,
+                    HeadingLevel::H2 => 16.0,
+                    HeadingLevel::H3 => 13.5,
+                    _ => 12.0,
+                };
+                self.flush(size, size * 0.5);
+                self.heading = None;
+            }
+            TagEnd::Paragraph => {
+                let space = if self.lists.is_empty() { 0.6 } else { 0.3 };
+                self.flush(BODY_SIZE, BODY_SIZE * space);
+            }
+            TagEnd::Strong => self.bold = self.bold.saturating_sub(1),
+            TagEnd::Emphasis => self.italic = self.italic.saturating_sub(1),
+            TagEnd::BlockQuote(_) => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.6);
+                self.quote_depth = self.quote_depth.saturating_sub(1);
+                self.italic = self.italic.saturating_sub(1);
+            }
+            TagEnd::CodeBlock => {
+                let code = self.code.take().unwrap_or_default();
+                let lines = code
+                    .trim_end_matches('\n')
+                    .lines()
+                    .map(str::to_string)
+                    .collect();
+                self.blocks.push(Block::Code {
+                    lines,
+                    indent: self.indent(),
+                });
+            }
+            TagEnd::Item => self.flush(BODY_SIZE, BODY_SIZE * 0.3),
+            TagEnd::List(_) => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.3);
+                self.lists.pop();
+            }
+            TagEnd::TableCell => {
+                self.table_row.push(std::mem::take(&mut self.words));
+                self.pending_space = false;
+            }
+            TagEnd::TableHead | TagEnd::TableRow => {
+                self.in_table_head = false;
+                let cells = std::mem::take(&mut self.table_row);
+                for (index, mut cell) in cells.into_iter().enumerate() {
+                    if index > 0 {
+                        self.push_text(" | ", false);
+                    }
+                    if let Some(first) = cell.first_mut() {
+                        first.space_before = index > 0;
+                    }
+                    self.words.append(&mut cell);
+                }
+                self.flush(BODY_SIZE, BODY_SIZE * 0.2);
+            }
+            TagEnd::Table => {
+                if let Some(Block::Text { space_after, .. }) = self.blocks.last_mut() {
+                    *space_after = BODY_SIZE * 0.6;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => match self.code.as_mut() {
+                Some(code) => code.push_str(&text),
+                None => self.push_text(&text, false),
+            },
+            Event::Code(text) => self.push_text(&text, true),
+            Event::SoftBreak => self.pending_space = true,
+            Event::HardBreak => self.flush(BODY_SIZE, 0.0),
+            Event::Rule => {
+                self.flush(BODY_SIZE, BODY_SIZE * 0.6);
+                self.blocks.push(Block::Rule);
+            }
+            Event::TaskListMarker(checked) => {
+                self.marker = Some(if checked { "[x]" } else { "[ ]" }.to_string());
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.push_text(&html, false),
+            _ => {}
+        }
+    }
+}
+
+/// Parses Markdown into blocks.
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut builder = BlockBuilder::default();
+    for event in Parser::new_ext(markdown, options) {
+        builder.event(event);
+    }
+    builder.flush(BODY_SIZE, 0.0);
+    builder.blocks
+}
+
+/// Lays out blocks on pages of content operations.
+struct PageWriter {
+    pages: Vec<Vec<Operation>>,
+    operations: Vec<Operation>,
+    /// Baseline of the next line
+    y: f32,
+}
+
+impl PageWriter {
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            operations: Vec::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.operations));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    /// Moves to the next line, on a new page when the current one is full.
+    fn next_line(&mut self, height: f32) -> f32 {
+        if self.y - height < MARGIN && !self.operations.is_empty() {
+            self.new_page();
+        }
+        self.y -= height;
+        self.y
+    }
+
+    /// Writes runs of text (font, encoded text) from a position.
+    fn text(&mut self, x: f32, y: f32, size: f32, runs: &[(Font, Vec<u8>)]) {
+        self.operations.push(Operation::new("BT", vec![]));
+        self.operations
+            .push(Operation::new("Td", vec![x.into(), y.into()]));
+        for (font, text) in runs {
+            self.operations.push(Operation::new(
+                "Tf",
+                vec![font.resource_name().into(), size.into()],
+            ));
+            self.operations.push(Operation::new(
+                "Tj",
+                vec![Object::String(text.clone(), StringFormat::Literal)],
+            ));
+        }
+        self.operations.push(Operation::new("ET", vec![]));
+    }
+
+    fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.operations.extend([
+            Operation::new("w", vec![0.5.into()]),
+            Operation::new("m", vec![x1.into(), y1.into()]),
+            Operation::new("l", vec![x2.into(), y2.into()]),
+            Operation::new("S", vec![]),
+        ]);
+    }
+
+    /// Wraps words into lines of runs fitting a width.
+    fn wrap(words: &[Word], size: f32, width: f32) -> Vec<Vec<(Font, Vec<u8>)>> {
+        let mut lines: Vec<Vec<(Font, Vec<u8>)>> = Vec::new();
+        let mut line: Vec<(Font, Vec<u8>)> = Vec::new();
+        let mut line_width = 0.0;
+
+        for word in words {
+            let mut text = word.text.clone();
+            let space = word.space_before && !line.is_empty();
+            let space_width = if space {
+                word.font.text_width(b" ", size)
+            } else {
+                0.0
+            };
+            let word_width = word.font.text_width(&text, size);
+
+            if !line.is_empty() && line_width + space_width + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0.0;
+            } else if space {
+                text.insert(0, b' ');
+                line_width += space_width;
+            }
+
+            // Words wider than a line are cut
+            while word.font.text_width(&text, size) > width && text.len() > 1 {
+                let mut cut = text.len() - 1;
+                while cut > 1 && word.font.text_width(&text[..cut], size) > width {
+                    cut -= 1;
+                }
+                let rest = text.split_off(cut);
+                lines.push(vec![(word.font, std::mem::replace(&mut text, rest))]);
+            }
+
+            line_width += word.font.text_width(&text, size);
+            match line.last_mut() {
+                Some((font, run)) if *font == word.font => run.extend_from_slice(&text),
+                _ => line.push((word.font, text)),
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn block(&mut self, block: &Block) {
+        let content_width = PAGE_WIDTH - 2.0 * MARGIN;
+        match block {
+            Block::Text {
+                words,
+                size,
+                indent,
+                marker,
+                space_after,
+            } => {
+                let line_height = size * LINE_SPACING;
+                let x = MARGIN + indent;
+                let lines = Self::wrap(words, *size, content_width - indent);
+                for (index, line) in lines.iter().enumerate() {
+                    let y = self.next_line(line_height);
+                    if let (0, Some(marker)) = (index, marker) {
+                        self.text(x - INDENT, y, *size, &[(Font::Regular, encode(marker))]);
+                    }
+                    self.text(x, y, *size, line);
+                }
+                self.y -= space_after;
+            }
+            Block::Code { lines, indent } => {
+                let line_height = CODE_SIZE * LINE_SPACING;
+                let x = MARGIN + indent + INDENT / 2.0;
+                let max_chars = ((content_width - (x - MARGIN)) / (CODE_SIZE * 0.6)) as usize;
+                for line in lines {
+                    let encoded = encode(line);
+                    let chunks: Vec<&[u8]> = if encoded.is_empty() {
+                        vec![&[]]
+                    } else {
+                        encoded.chunks(max_chars.max(1)).collect()
+                    };
+                    for chunk in chunks {
+                        let y = self.next_line(line_height);
+                        self.text(x, y, CODE_SIZE, &[(Font::Mono, chunk.to_vec())]);
+                    }
+                }
+                self.y -= BODY_SIZE * 0.6;
+            }
+            Block::Rule => {
+                let y = self.next_line(BODY_SIZE * 0.6);
+                self.line(MARGIN, y, PAGE_WIDTH - MARGIN, y);
+                self.y -= BODY_SIZE * 0.6;
+            }
+        }
+    }
+
+    /// Pages with their number in the footer.
+    fn finish(mut self) -> Vec<Vec<Operation>> {
+        if !self.operations.is_empty() || self.pages.is_empty() {
+            self.pages.push(std::mem::take(&mut self.operations));
+        }
+        let count = self.pages.len();
+        let mut pages = std::mem::take(&mut self.pages);
+        for (index, operations) in pages.iter_mut().enumerate() {
+            let footer = encode(&format!("{} / {}", index + 1, count));
+            let width = Font::Regular.text_width(&footer, 9.0);
+            let mut writer = PageWriter::new();
+            writer.text(
+                (PAGE_WIDTH - width) / 2.0,
+                MARGIN / 2.0,
+                9.0,
+                &[(Font::Regular, footer)],
+            );
+            operations.append(&mut writer.operations);
+        }
+        pages
+    }
+}
+
+/// Current date as a PDF date (`D:YYYYMMDDHHmmSSZ`).
+fn pdf_date() -> String {
+    chrono::Utc::now().format("D:%Y%m%d%H%M%SZ").to_string()
+}
+
+/// Renders Markdown as a PDF document.
+///
+/// # Arguments
+/// * `markdown` - Document content
+/// * `title` - Title of the document metadata
+pub fn render(markdown: &str, title: Option<&str>) -> Result<Vec<u8>, String> {
+    let mut writer = PageWriter::new();
+    for block in parse_blocks(markdown) {
+        writer.block(&block);
+    }
+    let pages = writer.finish();
+
+    let mut document = Document::with_version("1.5");
+    let pages_id = document.new_object_id();
+    let mut fonts = lopdf::Dictionary::new();
+    for font in Font::ALL {
+        let font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => font.base_font(),
+            "Encoding" => "WinAnsiEncoding",
+        });
+        fonts.set(font.resource_name(), font_id);
+    }
+    let resources_id = document.add_object(dictionary! { "Font" => fonts });
+
+    let mut kids = Vec::with_capacity(pages.len());
+    for operations in pages {
+        let content = Content { operations }
+            .encode()
+            .map_err(|e| format!("PDF generation failed: {}", e))?;
+        let content_id = document.add_object(Stream::new(dictionary! {}, content));
+        let page_id = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(Object::from(page_id));
+    }
+    let page_count = kids.len() as i64;
+    document.objects.insert(
+        pages_id,
+        Object::Dictionary(dictionary! {
+            "Type" => "Pages",
+            "Kids" => kids,
+            "Count" => page_count,
+            "Resources" => resources_id,
+            "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()],
+        }),
+    );
+    let catalog_id = document.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+
+    let mut info = dictionary! {
+        "Producer" => lopdf::text_string("Zileo Chat"),
+        "CreationDate" => Object::string_literal(pdf_date()),
+    };
+    if let Some(title) = title.map(str::trim).filter(|title| !title.is_empty()) {
+        info.set("Title", lopdf::text_string(title));
+    }
+    let info_id = document.add_object(info);
+    document.trailer.set("Root", catalog_id);
+    document.trailer.set("Info", info_id);
+    document.compress();
+
+    let mut bytes = Vec::new();
+    document
+        .save_to(&mut bytes)
+        .map_err(|e| format!("PDF generation failed: {}", e))?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<Word> {
+        let mut builder = BlockBuilder::default();
+        builder.push_text(text, false);
+        builder.words
+    }
+
+    #[test]
+    fn test_win_ansi() {
+        assert_eq!(encode("Café €5 – “ok”"), b"Caf\xe9 \x805 \x96 \x93ok\x94");
+        assert_eq!(encode("日本"), b"??");
+    }
+
+    #[test]
+    fn test_wrap() {
+        let lines = PageWriter::wrap(&words("one two three four"), 10.0, 50.0);
+        assert!(lines.len() > 1);
+        let texts: Vec<String> = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|(_, run)| String::from_utf8_lossy(run))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(texts.join(" "), "one two three four");
+    }
+
+    #[test]
+    fn test_wrap_cuts_long_words() {
+        let lines = PageWriter::wrap(&words(&"x".repeat(100)), 10.0, 100.0);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            let width: f32 = line
+                .iter()
+                .map(|(font, run)| font.text_width(run, 10.0))
+                .sum();
+            assert!(width <= 100.0);
+        }
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let blocks = parse_blocks(
+            "# Title\n\nSome **bold** text.\n\n- one\n- two\n\n```\nlet x = 1;\n```\n\n---\n\n| A | B |\n|---|---|\n| 1 | 2 |\n",
+        );
+        let markers: Vec<Option<&str>> = blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Text { marker, .. } => Some(marker.as_deref()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(markers, vec![None, None, Some("•"), Some("•"), None, None]);
+        assert!(matches!(blocks[4], Block::Code { .. }));
+        assert!(matches!(blocks[5], Block::Rule));
+        match &blocks[1] {
+            Block::Text { words, .. } => {
+                assert_eq!(words[1].font, Font::Bold);
+                assert!(words[2].space_before);
+            }
+            _ => panic!("expected a paragraph"),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let bytes = render("# Hello\n\nWorld", Some("Greeting")).unwrap();
+        assert!(bytes.starts_with(b"%PDF-1.5"));
+        let document = Document::load_mem(&bytes).unwrap();
+        assert_eq!(document.get_pages().len(), 1);
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Document extraction and generation.
+//!
+//! The extraction pipeline is shared by the PdfTool and the knowledge pack
+//! ingestion (`import_knowledge_document`):
+//! - [`pdf`]: text per page and metadata of a PDF
+//! - [`tables`]: tables detected in extracted text
+//! - [`markdown_pdf`]: simple PDFs generated from Markdown
+//! - [`ocr`]: text recognized in images and scanned pages (OcrTool)
+//!
+//! [`extract_file`] reads a PDF, Markdown or plain text file into text.
+
+pub mod markdown_pdf;
+pub mod ocr;
+pub mod pdf;
+pub mod tables;
+
+use crate::tools::constants::pdf as pdf_const;
+use std::path::Path;
+
+/// Text extracted from a document file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedDocument {
+    /// Title from the PDF metadata, or the file name without extension
+    pub title: String,
+    /// Text of each page (a single page for text files)
+    pub pages: Vec<String>,
+}
+
+impl ExtractedDocument {
+    /// Text of all pages, separated by blank lines.
+    pub fn text(&self) -> String {
+        self.pages
+            .iter()
+            .map(|page| page.trim())
+            .filter(|page| !page.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Lowercase extension of a path, if supported by the extraction.
+fn supported_extension(path: &Path) -> Result<String, String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    if pdf_const::SUPPORTED_EXTENSIONS.contains(&extension.as_str()) {
+        Ok(extension)
+    } else {
+        Err(format!(
+            "Unsupported document type '{}': use {}",
+            extension,
+            pdf_const::SUPPORTED_EXTENSIONS.join(", ")
+        ))
+    }
+}
+
+/// Reads a file after checking it exists and is not too large.
+pub fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    let metadata =
+        std::fs::metadata(path).map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("'{}' is not a file", path.display()));
+    }
+    if metadata.len() > pdf_const::MAX_FILE_BYTES {
+        return Err(format!(
+            "'{}' exceeds the maximum size of {} MB",
+            path.display(),
+            pdf_const::MAX_FILE_BYTES / (1024 * 1024)
+        ));
+    }
+    std::fs::read(path).map_err(|e| format!("Cannot read '{}': {}", path.display(), e))
+}
+
+/// Extracts the text of a PDF, Markdown or plain text file.
+pub fn extract_file(path: &Path) -> Result<ExtractedDocument, String> {
+    let extension = supported_extension(path)?;
+    let bytes = read_file(path)?;
+    let file_title = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    if extension == "pdf" {
+        let pages = pdf::extract_pages(&bytes)?;
+        let title = pdf::read_metadata(&bytes)
+            .ok()
+            .and_then(|metadata| metadata.title)
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or(file_title);
+        Ok(ExtractedDocument { title, pages })
+    } else {
+        let text = String::from_utf8(bytes)
+            .map_err(|_| format!("'{}' is not UTF-8 text", path.display()))?;
+        Ok(ExtractedDocument {
+            title: file_title,
+            pages: vec![text],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_text_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Notes.md");
+        std::fs::write(&path, "# Notes\n\nSome text").unwrap();
+
+        let document = extract_file(&path).unwrap();
+        assert_eq!(document.title, "Notes");
+        assert_eq!(document.text(), "# Notes\n\nSome text");
+    }
+
+    #[test]
+    fn test_extract_generated_pdf() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.PDF");
+        let bytes =
+            markdown_pdf::render("# Quarterly report\n\nRevenue grew.", Some("Q3")).unwrap();
+        std::fs::write(&path, bytes).unwrap();
+
+        let document = extract_file(&path).unwrap();
+        assert_eq!(document.title, "Q3");
+        assert!(document.text().contains("Revenue grew."));
+    }
+
+    #[test]
+    fn test_extract_rejects_unsupported_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        std::fs::write(&path, [0u8; 4]).unwrap();
+        assert!(extract_file(&path).unwrap_err().contains("Unsupported"));
+        assert!(extract_file(&dir.path().join("missing.pdf")).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text recognition in images.
+//!
+//! Two engines, selected in the OCR settings:
+//! - Tesseract: the `tesseract` command reads the image on stdin and writes
+//!   the text on stdout, with the configured language packs
+//! - Ollama: a local vision model transcribes the image (`/api/generate`)
+
+use crate::models::ocr::{OcrEngine, OcrSettings};
+use crate::tools::constants::ocr as ocr_const;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::json;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Whether a tesseract language pack name is well-formed (e.g. "eng",
+/// "chi_sim").
+pub fn is_valid_language(code: &str) -> bool {
+    code.len() >= 3
+        && code.len() <= 32
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Tesseract executable configured in the settings, or found in the PATH.
+fn tesseract_program(settings: &OcrSettings) -> &str {
+    settings
+        .tesseract_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .unwrap_or("tesseract")
+}
+
+/// Error of a tesseract command that could not be started.
+fn spawn_error(program: &str, error: std::io::Error) -> String {
+    if error.kind() == std::io::ErrorKind::NotFound {
+        format!(
+            "Tesseract was not found ('{}'). Install it or set its path in Settings > Validation > OCR",
+            program
+        )
+    } else {
+        format!("Cannot run '{}': {}", program, error)
+    }
+}
+
+/// Parses the output of `tesseract --list-langs` (a header line, then one
+/// language per line). The orientation detection pack is not a language.
+fn parse_languages(output: &str) -> Vec<String> {
+    let mut languages: Vec<String> = output
+        .lines()
+        .skip_while(|line| !line.trim_end().ends_with(':'))
+        .skip(1)
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && *line != "osd")
+        .map(str::to_string)
+        .collect();
+    languages.sort();
+    languages
+}
+
+/// Lists the language packs installed for tesseract.
+pub async fn list_languages(settings: &OcrSettings) -> Result<Vec<String>, String> {
+    let program = tesseract_program(settings);
+    let output = tokio::time::timeout(
+        Duration::from_secs(ocr_const::TIMEOUT_SECS),
+        Command::new(program)
+            .arg("--list-langs")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| "Tesseract did not list its languages in time".to_string())?
+    .map_err(|e| spawn_error(program, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Tesseract failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    // Older versions print the list on stderr
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(parse_languages(&text))
+}
+
+/// Recognizes an image with tesseract.
+async fn recognize_tesseract(
+    settings: &OcrSettings,
+    image: Vec<u8>,
+    languages: &[String],
+) -> Result<String, String> {
+    let program = tesseract_program(settings);
+    let mut child = Command::new(program)
+        .args(["stdin", "stdout", "-l", &languages.join("+")])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| spawn_error(program, e))?;
+
+    // Written concurrently so a full stdout pipe cannot block the input
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| "Cannot write the image to tesseract".to_string())?;
+    let writer = tokio::spawn(async move {
+        let _ = stdin.write_all(&image).await;
+    });
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(ocr_const::TIMEOUT_SECS),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| {
+        format!(
+            "Tesseract did not finish within {} seconds",
+            ocr_const::TIMEOUT_SECS
+        )
+    })?
+    .map_err(|e| format!("Tesseract failed: {}", e))?;
+    let _ = writer.await;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Tesseract failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Recognizes an image with the Ollama vision model.
+async fn recognize_ollama(settings: &OcrSettings, image: &[u8]) -> Result<String, String> {
+    let client = crate::proxy::client_builder("ollama")
+        .timeout(Duration::from_secs(ocr_const::TIMEOUT_SECS))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let url = format!("{}/api/generate", settings.ollama_url.trim_end_matches('/'));
+    let body = json!({
+        "model": settings.ollama_model,
+        "prompt": ocr_const::OLLAMA_PROMPT,
+        "images": [STANDARD.encode(image)],
+        "stream": false,
+    });
+
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Ollama request failed ({}): {}", settings.ollama_url, e))?;
+    let status = response.status();
+    if !status.is_success() {
+        let detail = response.text().await.unwrap_or_default();
+        return Err(format!("Ollama returned {}: {}", status, detail.trim()));
+    }
+    let value: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid Ollama response: {}", e))?;
+    value
+        .get("response")
+        .and_then(|text| text.as_str())
+        .map(|text| text.trim().to_string())
+        .ok_or_else(|| "Invalid Ollama response: missing 'response'".to_string())
+}
+
+/// Recognizes the text of an image (PNG, JPEG, TIFF...) with the configured
+/// engine.
+///
+/// `languages` are tesseract language packs; the Ollama engine ignores them.
+pub async fn recognize(
+    settings: &OcrSettings,
+    image: Vec<u8>,
+    languages: &[String],
+) -> Result<String, String> {
+    match settings.engine {
+        OcrEngine::Tesseract => recognize_tesseract(settings, image, languages).await,
+        OcrEngine::Ollama => recognize_ollama(settings, &image).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_languages() {
+        let output =
+            "List of available languages in \"/usr/share/tesseract-ocr/5/tessdata/\" (3):\n\
+                      fra\neng\nosd\n";
+        assert_eq!(parse_languages(output), vec!["eng", "fra"]);
+        assert!(parse_languages("").is_empty());
+    }
+
+    #[test]
+    fn test_is_valid_language() {
+        assert!(is_valid_language("eng"));
+        assert!(is_valid_language("chi_sim"));
+        assert!(!is_valid_language("en"));
+        assert!(!is_valid_language("eng+fra"));
+        assert!(!is_valid_language("../eng"));
+    }
+
+    #[test]
+    fn test_tesseract_program() {
+        let mut settings = OcrSettings::default();
+        assert_eq!(tesseract_program(&settings), "tesseract");
+        settings.tesseract_path = Some("  ".to_string());
+        assert_eq!(tesseract_program(&settings), "tesseract");
+        settings.tesseract_path = Some("/opt/tesseract/bin/tesseract".to_string());
+        assert_eq!(tesseract_program(&settings), "/opt/tesseract/bin/tesseract");
+    }
+
+    #[tokio::test]
+    async fn test_missing_tesseract() {
+        let settings = OcrSettings {
+            tesseract_path: Some("/nonexistent/tesseract".to_string()),
+            ..Default::default()
+        };
+        let error = recognize(&settings, vec![0; 8], &["eng".to_string()])
+            .await
+            .unwrap_err();
+        assert!(error.contains("not found"));
+        assert!(list_languages(&settings).await.is_err());
+    }
+}
@@ -0,0 +1,370 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! PDF text and metadata extraction.
+//!
+//! Text is extracted per page by `pdf-extract`, which lays out the glyphs of
+//! each line (columns of a table stay separated by spaces). Scanned PDFs have
+//! no text layer and extract as empty pages: [`page_images`] returns their
+//! scanned images for OCR.
+
+use lopdf::xobject::PdfImage;
+use lopdf::{Document, Object};
+use serde::Serialize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Metadata of a PDF (Info dictionary, page count and encryption)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PdfMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    /// Application that created the original document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+    /// Application that produced the PDF
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub producer: Option<String>,
+    /// Creation date (ISO 8601 when the PDF date is well-formed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    /// Last modification date (ISO 8601 when the PDF date is well-formed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<String>,
+    /// PDF version (e.g. "1.7")
+    pub version: String,
+    pub page_count: usize,
+    pub encrypted: bool,
+}
+
+/// Converts a PDF date (`D:YYYYMMDDHHmmSS+HH'mm'`) to ISO 8601.
+///
+/// Returns the raw value when it is not a PDF date.
+fn format_pdf_date(raw: &str) -> String {
+    let value = raw.trim().trim_start_matches("D:");
+    let digits: String = value.chars().take_while(char::is_ascii_digit).collect();
+    if digits.len() < 8 {
+        return raw.to_string();
+    }
+    let field =
+        |start: usize, default: &'static str| digits.get(start..start + 2).unwrap_or(default);
+    let mut iso = format!(
+        "{}-{}-{}T{}:{}:{}",
+        &digits[0..4],
+        field(4, "01"),
+        field(6, "01"),
+        field(8, "00"),
+        field(10, "00"),
+        field(12, "00")
+    );
+
+    let offset = &value[digits.len()..];
+    match offset.chars().next() {
+        Some('Z') => iso.push('Z'),
+        Some(sign @ ('+' | '-')) => {
+            let offset_digits: String = offset.chars().filter(char::is_ascii_digit).collect();
+            if offset_digits.len() >= 2 {
+                let minutes = offset_digits.get(2..4).unwrap_or("00");
+                iso.push_str(&format!("{}{}:{}", sign, &offset_digits[0..2], minutes));
+            }
+        }
+        _ => {}
+    }
+    iso
+}
+
+/// Loads a PDF (the reader decrypts PDFs without a user password).
+fn load(bytes: &[u8]) -> Result<Document, String> {
+    Document::load_mem(bytes).map_err(|e| format!("Invalid PDF: {}", e))
+}
+
+/// Reads the metadata of a PDF.
+pub fn read_metadata(bytes: &[u8]) -> Result<PdfMetadata, String> {
+    let document = load(bytes)?;
+    let mut metadata = PdfMetadata {
+        version: document.version.clone(),
+        page_count: document.get_pages().len(),
+        encrypted: document.is_encrypted() || document.encryption_state.is_some(),
+        ..Default::default()
+    };
+
+    let info = match document.trailer.get(b"Info") {
+        Ok(Object::Reference(id)) => document.get_dictionary(*id).ok(),
+        Ok(Object::Dictionary(dictionary)) => Some(dictionary),
+        _ => None,
+    };
+    if let Some(info) = info {
+        let text = |key: &[u8]| {
+            info.get(key)
+                .ok()
+                .and_then(|object| lopdf::decode_text_string(object).ok())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        metadata.title = text(b"Title");
+        metadata.author = text(b"Author");
+        metadata.subject = text(b"Subject");
+        metadata.keywords = text(b"Keywords");
+        metadata.creator = text(b"Creator");
+        metadata.producer = text(b"Producer");
+        metadata.created = text(b"CreationDate").map(|date| format_pdf_date(&date));
+        metadata.modified = text(b"ModDate").map(|date| format_pdf_date(&date));
+    }
+    Ok(metadata)
+}
+
+/// Extracts the text of each page of a PDF.
+///
+/// Malformed PDFs can make the extractor panic; the panic is reported as an
+/// error.
+pub fn extract_pages(bytes: &[u8]) -> Result<Vec<String>, String> {
+    if !bytes.starts_with(b"%PDF") {
+        return Err("Invalid PDF: missing %PDF header".to_string());
+    }
+    catch_unwind(AssertUnwindSafe(|| {
+        pdf_extract::extract_text_from_mem_by_pages(bytes)
+    }))
+    .map_err(|_| "Invalid PDF: the text layer could not be read".to_string())?
+    .map_err(|e| format!("PDF text extraction failed: {}", e))
+}
+
+/// Scanned image of a PDF page
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageImage {
+    /// Page of the image (1-based)
+    pub page: usize,
+    /// JPEG or PNG bytes, None when the image encoding is not supported
+    pub data: Option<Vec<u8>>,
+}
+
+/// Encodes a PDF image as JPEG (passed through) or PNG.
+///
+/// Supports JPEG images and 8-bit gray or RGB samples (1-bit for gray)
+/// stored raw or with the Flate, LZW and ASCII85 filters. Fax and JBIG2
+/// scans, palettes and CMYK images are not supported.
+fn encode_image(document: &Document, image: &PdfImage) -> Option<Vec<u8>> {
+    let filters = image.filters.as_deref().unwrap_or_default();
+    if filters.iter().any(|filter| filter == "DCTDecode") {
+        return (filters.len() == 1).then(|| image.content.to_vec());
+    }
+
+    let width = u32::try_from(image.width).ok().filter(|w| *w > 0)?;
+    let height = u32::try_from(image.height).ok().filter(|h| *h > 0)?;
+    let (color, channels) = match image.color_space.as_deref() {
+        Some("DeviceGray" | "CalGray") => (png::ColorType::Grayscale, 1),
+        Some("DeviceRGB" | "CalRGB") => (png::ColorType::Rgb, 3),
+        _ => return None,
+    };
+    let (depth, bits) = match image.bits_per_component {
+        Some(8) => (png::BitDepth::Eight, 8),
+        Some(1) if channels == 1 => (png::BitDepth::One, 1),
+        _ => return None,
+    };
+
+    let stream = document.get_object(image.id).ok()?.as_stream().ok()?;
+    let samples = stream.get_plain_content().ok()?;
+    let row_bytes = (width as usize * channels * bits).div_ceil(8);
+    let samples = samples.get(..row_bytes * height as usize)?;
+
+    let mut encoded = Vec::new();
+    let mut encoder = png::Encoder::new(&mut encoded, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(depth);
+    let mut writer = encoder.write_header().ok()?;
+    writer.write_image_data(samples).ok()?;
+    writer.finish().ok()?;
+    Some(encoded)
+}
+
+/// Extracts the largest image of each page of a PDF (the scan of a scanned
+/// page), for at most `max_pages` pages.
+///
+/// `page` limits the extraction to one page (1-based). Pages without images
+/// are omitted.
+pub fn page_images(
+    bytes: &[u8],
+    page: Option<usize>,
+    max_pages: usize,
+) -> Result<Vec<PageImage>, String> {
+    let document = load(bytes)?;
+    let pages = document.get_pages();
+    if let Some(page) = page {
+        if page == 0 || page > pages.len() {
+            return Err(format!(
+                "Page {} does not exist (the PDF has {} pages)",
+                page,
+                pages.len()
+            ));
+        }
+    }
+
+    let mut images = Vec::new();
+    for (number, page_id) in pages {
+        let number = number as usize;
+        if page.is_some_and(|page| page != number) {
+            continue;
+        }
+        if images.len() >= max_pages {
+            break;
+        }
+        // Pages without image resources report an error
+        let largest = document
+            .get_page_images(page_id)
+            .ok()
+            .and_then(|page_images| {
+                page_images
+                    .into_iter()
+                    .max_by_key(|image| image.width.saturating_mul(image.height))
+            });
+        if let Some(image) = largest {
+            images.push(PageImage {
+                page: number,
+                data: encode_image(&document, &image),
+            });
+        }
+    }
+    Ok(images)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::markdown_pdf;
+    use lopdf::{dictionary, Stream};
+
+    #[test]
+    fn test_format_pdf_date() {
+        assert_eq!(
+            format_pdf_date("D:20250614093000+02'00'"),
+            "2025-06-14T09:30:00+02:00"
+        );
+        assert_eq!(format_pdf_date("D:20250614093000Z"), "2025-06-14T09:30:00Z");
+        assert_eq!(format_pdf_date("D:2025061"), "D:2025061");
+        assert_eq!(format_pdf_date("D:20250614"), "2025-06-14T00:00:00");
+    }
+
+    #[test]
+    fn test_read_metadata() {
+        let bytes = markdown_pdf::render("# Title\n\nBody", Some("Annual report")).unwrap();
+        let metadata = read_metadata(&bytes).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Annual report"));
+        assert_eq!(metadata.producer.as_deref(), Some("Zileo Chat"));
+        assert_eq!(metadata.page_count, 1);
+        assert!(!metadata.encrypted);
+        assert!(metadata.created.unwrap().ends_with('Z'));
+    }
+
+    #[test]
+    fn test_extract_pages() {
+        let markdown = format!("First page\n\n{}", "Filler paragraph.\n\n".repeat(80));
+        let bytes = markdown_pdf::render(&markdown, None).unwrap();
+        let pages = extract_pages(&bytes).unwrap();
+        assert!(pages.len() > 1);
+        assert!(pages[0].contains("First page"));
+    }
+
+    /// PDF whose pages each draw one image XObject (None for a blank page).
+    fn scanned_pdf(images: Vec<Option<Stream>>) -> Vec<u8> {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let mut kids = Vec::new();
+        for image in images {
+            let mut page = dictionary! { "Type" => "Page", "Parent" => pages_id };
+            if let Some(image) = image {
+                let image_id = document.add_object(image);
+                page.set(
+                    "Resources",
+                    dictionary! { "XObject" => dictionary! { "Im0" => image_id } },
+                );
+            }
+            kids.push(Object::from(document.add_object(page)));
+        }
+        let count = kids.len() as i64;
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! { "Type" => "Pages", "Kids" => kids, "Count" => count }),
+        );
+        let catalog_id =
+            document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        document.trailer.set("Root", catalog_id);
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn image(color_space: &str, bits: i64, filter: Option<&str>, content: Vec<u8>) -> Stream {
+        let mut dict = dictionary! {
+            "Type" => "XObject",
+            "Subtype" => "Image",
+            "Width" => 64,
+            "Height" => 32,
+            "ColorSpace" => color_space,
+            "BitsPerComponent" => bits,
+        };
+        if let Some(filter) = filter {
+            dict.set("Filter", filter);
+        }
+        let mut stream = Stream::new(dict, content);
+        if filter.is_none() {
+            stream.compress().unwrap();
+        }
+        stream
+    }
+
+    #[test]
+    fn test_page_images() {
+        let jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 1, 2, 3];
+        let bytes = scanned_pdf(vec![
+            Some(image("DeviceGray", 8, None, vec![200; 64 * 32])),
+            None,
+            Some(image("DeviceRGB", 8, Some("DCTDecode"), jpeg.clone())),
+            Some(image("DeviceCMYK", 8, None, vec![0; 64 * 32 * 4])),
+        ]);
+
+        let images = page_images(&bytes, None, 10).unwrap();
+        assert_eq!(
+            images.iter().map(|image| image.page).collect::<Vec<_>>(),
+            vec![1, 3, 4]
+        );
+        assert!(images[0]
+            .data
+            .as_ref()
+            .unwrap()
+            .starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(images[1].data.as_ref(), Some(&jpeg));
+        assert_eq!(images[2].data, None);
+
+        assert_eq!(page_images(&bytes, Some(3), 10).unwrap().len(), 1);
+        assert_eq!(page_images(&bytes, None, 1).unwrap().len(), 1);
+        assert!(page_images(&bytes, Some(5), 10).is_err());
+    }
+
+    #[test]
+    fn test_truncated_samples_are_skipped() {
+        let bytes = scanned_pdf(vec![Some(image("DeviceGray", 8, None, vec![0; 100]))]);
+        assert_eq!(page_images(&bytes, None, 10).unwrap()[0].data, None);
+    }
+
+    #[test]
+    fn test_invalid_pdf() {
+        assert!(extract_pages(b"not a pdf").is_err());
+        assert!(extract_pages(b"%PDF-1.7 truncated").is_err());
+        assert!(read_metadata(b"not a pdf").is_err());
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Table detection in extracted text.
+//!
+//! PDFs have no table structure: the extracted text keeps the columns of a
+//! table separated by runs of spaces (or tabs). A table is a run of at least
+//! two consecutive lines splitting into the same number of cells (two or
+//! more) on those gaps. The first row is usually the header.
+
+use serde::Serialize;
+
+/// Table detected in the text of a page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Table {
+    /// Page of the table (1-based)
+    pub page: usize,
+    /// Rows of cells, the header first when the table has one
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Splits a line into cells on tabs and runs of two or more spaces.
+fn split_cells(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut spaces = 0;
+    for c in line.trim().chars() {
+        match c {
+            '\t' => spaces = 2,
+            ' ' => spaces += 1,
+            c => {
+                if spaces >= 2 && !cell.is_empty() {
+                    cells.push(std::mem::take(&mut cell));
+                } else if spaces == 1 {
+                    cell.push(' ');
+                }
+                spaces = 0;
+                cell.push(c);
+            }
+        }
+    }
+    if !cell.is_empty() {
+        cells.push(cell);
+    }
+    cells
+}
+
+/// Detects the tables of the pages of a document.
+pub fn detect_tables(pages: &[String]) -> Vec<Table> {
+    let mut tables = Vec::new();
+    for (index, page) in pages.iter().enumerate() {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut flush = |rows: &mut Vec<Vec<String>>| {
+            if rows.len() >= 2 {
+                tables.push(Table {
+                    page: index + 1,
+                    rows: std::mem::take(rows),
+                });
+            }
+            rows.clear();
+        };
+
+        for line in page.lines().filter(|line| !line.trim().is_empty()) {
+            let cells = split_cells(line);
+            if cells.len() < 2 {
+                flush(&mut rows);
+                continue;
+            }
+            if rows.first().is_some_and(|first| first.len() != cells.len()) {
+                flush(&mut rows);
+            }
+            rows.push(cells);
+        }
+        flush(&mut rows);
+    }
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_cells() {
+        assert_eq!(
+            split_cells("  Product name    Unit price\t Qty "),
+            vec!["Product name", "Unit price", "Qty"]
+        );
+        assert_eq!(split_cells("A plain sentence."), vec!["A plain sentence."]);
+    }
+
+    #[test]
+    fn test_detect_tables() {
+        let page = "Quarterly sales\n\n\
+                    Region    Q1     Q2\n\
+                    North     120    135\n\
+                    South     98     110\n\
+                    Totals are unaudited.\n\
+                    Name  Role\n"
+            .to_string();
+        let tables = detect_tables(&[String::new(), page]);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].page, 2);
+        assert_eq!(tables[0].rows.len(), 3);
+        assert_eq!(tables[0].rows[0], vec!["Region", "Q1", "Q2"]);
+        assert_eq!(tables[0].rows[2], vec!["South", "98", "110"]);
+    }
+
+    #[test]
+    fn test_column_count_change_splits_tables() {
+        let page = "a  b\nc  d\nx  y  z\nu  v  w\n".to_string();
+        let tables = detect_tables(&[page]);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[1].rows[0].len(), 3);
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Priority inbox: persistence of the items awaiting the user.
+//!
+//! Subsystems call [`track_pending_item`] when an item appears and
+//! [`resolve_pending_item`] when it is handled:
+//!
+//! - validation helper and validation commands (approvals, token anomaly pauses)
+//! - user question tool (agent questions)
+//! - checkpoint commands (interrupted executions, see [`reconcile_pending_items`])
+//! - scheduler (failed scheduled runs, resolved by the next successful run)
+//! - MCP manager (OAuth tokens expiring without refresh token)
+//!
+//! Items of a deleted workflow are removed with it (cascade delete). Both
+//! functions only log failures: the inbox never blocks the subsystem.
+
+use crate::agents::checkpoint::list_checkpoints;
+use crate::db::DBClient;
+use crate::models::pending_item::{PendingItem, PendingItemCreate, PendingItemKind};
+use crate::tools::constants::inbox as inbox_const;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn};
+
+/// Record ID of the item standing for a source record.
+fn pending_item_id(kind: PendingItemKind, source_id: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", kind.as_str(), source_id).as_bytes());
+    digest[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Records (or updates) a pending item.
+pub async fn upsert_pending_item(db: &DBClient, item: &PendingItemCreate) -> anyhow::Result<()> {
+    db.execute_with_params(
+        "UPSERT type::thing('pending_item', $id) SET kind = $kind, source_id = $source_id, \
+         workflow_id = $workflow_id, title = $title, detail = $detail, priority = $priority, \
+         due_at = IF $due_at = NONE THEN NONE ELSE <datetime>$due_at END",
+        vec![
+            (
+                "id".to_string(),
+                json!(pending_item_id(item.kind, &item.source_id)),
+            ),
+            ("kind".to_string(), json!(item.kind)),
+            ("source_id".to_string(), json!(item.source_id)),
+            ("workflow_id".to_string(), json!(item.workflow_id)),
+            ("title".to_string(), json!(item.title)),
+            ("detail".to_string(), json!(item.detail)),
+            ("priority".to_string(), json!(item.priority)),
+            (
+                "due_at".to_string(),
+                json!(item.due_at.map(|t| t.to_rfc3339())),
+            ),
+        ],
+    )
+    .await
+}
+
+/// Records a pending item, logging failures.
+pub async fn track_pending_item(db: &DBClient, item: PendingItemCreate) {
+    match upsert_pending_item(db, &item).await {
+        Ok(()) => debug!(kind = ?item.kind, source_id = %item.source_id, "Pending item recorded"),
+        Err(e) => warn!(error = %e, kind = ?item.kind, "Failed to record pending item"),
+    }
+}
+
+/// Removes the pending item of a source record (no-op if there is none), logging failures.
+pub async fn resolve_pending_item(db: &DBClient, kind: PendingItemKind, source_id: &str) {
+    let id = pending_item_id(kind, source_id);
+    if let Err(e) = db.delete(&format!("pending_item:{}", id)).await {
+        warn!(error = %e, kind = ?kind, "Failed to resolve pending item");
+    }
+}
+
+/// Removes the item of a validation request, whichever kind it was recorded as.
+pub async fn resolve_validation(db: &DBClient, validation_id: &str) {
+    for kind in [PendingItemKind::Validation, PendingItemKind::PausedWorkflow] {
+        resolve_pending_item(db, kind, validation_id).await;
+    }
+}
+
+/// Lists the pending items, most urgent first (oldest first within a priority).
+pub async fn list_pending_items(
+    db: &DBClient,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Vec<PendingItem>> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id, kind, source_id, workflow_id, title, detail, priority, \
+             due_at, created_at FROM pending_item ORDER BY created_at ASC LIMIT $limit",
+            vec![("limit".to_string(), json!(inbox_const::MAX_ITEMS))],
+        )
+        .await?;
+
+    let mut items: Vec<PendingItem> = rows
+        .into_iter()
+        .filter_map(|mut row| {
+            let source = serde_json::from_value::<PendingItemCreate>(row.clone());
+            // The link is derived, not stored
+            if let (Ok(source), Some(object)) = (&source, row.as_object_mut()) {
+                object.insert("link".to_string(), json!(source.link()));
+            }
+            match serde_json::from_value::<PendingItem>(row) {
+                Ok(item) => Some(item),
+                Err(e) => {
+                    warn!(error = %e, "Skipping malformed pending item");
+                    None
+                }
+            }
+        })
+        .collect();
+
+    for item in &mut items {
+        item.priority = item.effective_priority(now);
+    }
+    // Stable sort keeps the creation order within a priority
+    items.sort_by_key(|item| std::cmp::Reverse(item.priority));
+    Ok(items)
+}
+
+/// Brings the inbox up to date after a restart.
+///
+/// Approvals, questions and paused runs died with the previous session and
+/// are dropped; executions interrupted by a crash are recorded from their
+/// checkpoints.
+pub async fn reconcile_pending_items(db: &DBClient) -> anyhow::Result<()> {
+    let stale: Vec<&str> = PendingItemKind::ALL
+        .iter()
+        .filter(|kind| kind.waits_on_run())
+        .map(|kind| kind.as_str())
+        .collect();
+    db.execute_with_params(
+        "DELETE pending_item WHERE kind IN $kinds",
+        vec![("kinds".to_string(), json!(stale))],
+    )
+    .await?;
+
+    let executions = list_checkpoints(db).await.map_err(anyhow::Error::msg)?;
+    for execution in &executions {
+        upsert_pending_item(db, &PendingItemCreate::interrupted_execution(execution)).await?;
+    }
+    info!(
+        interrupted = executions.len(),
+        "Pending items reconciled after restart"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::pending_item::PendingPriority;
+    use crate::models::validation::{RiskLevel, ValidationType};
+    use chrono::Duration;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_track_resolve_and_prioritize() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("inbox_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+        let now = Utc::now();
+
+        track_pending_item(
+            &db,
+            PendingItemCreate::failed_schedule("s1", "Inbox", "wf1", "Provider unavailable"),
+        )
+        .await;
+        track_pending_item(
+            &db,
+            PendingItemCreate::expiring_credential("srv1", "github", now + Duration::days(10)),
+        )
+        .await;
+        track_pending_item(
+            &db,
+            PendingItemCreate::validation(
+                "v1",
+                "wf2",
+                &ValidationType::Tool,
+                "Write file",
+                &RiskLevel::Medium,
+            ),
+        )
+        .await;
+        track_pending_item(
+            &db,
+            PendingItemCreate::user_question("q1", "wf2", "Which file?"),
+        )
+        .await;
+
+        let items = list_pending_items(&db, now).await.unwrap();
+        let kinds: Vec<PendingItemKind> = items.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PendingItemKind::Validation,
+                PendingItemKind::UserQuestion,
+                PendingItemKind::FailedSchedule,
+                PendingItemKind::ExpiringCredential,
+            ]
+        );
+        assert_eq!(items[0].link.workflow_id.as_deref(), Some("wf2"));
+        assert_eq!(items[0].link.target_id, "v1");
+
+        // Tracking again updates the item; a close deadline raises its priority
+        track_pending_item(
+            &db,
+            PendingItemCreate::expiring_credential("srv1", "github", now + Duration::hours(1)),
+        )
+        .await;
+        let items = list_pending_items(&db, now).await.unwrap();
+        assert_eq!(items.len(), 4);
+        let credential = items
+            .iter()
+            .find(|i| i.kind == PendingItemKind::ExpiringCredential)
+            .unwrap();
+        assert_eq!(credential.priority, PendingPriority::High);
+
+        resolve_pending_item(&db, PendingItemKind::Validation, "v1").await;
+        resolve_pending_item(&db, PendingItemKind::Validation, "unknown").await;
+        assert_eq!(list_pending_items(&db, now).await.unwrap().len(), 3);
+
+        // After a restart, nobody waits for the question anymore
+        reconcile_pending_items(&db).await.unwrap();
+        let kinds: Vec<PendingItemKind> = list_pending_items(&db, now)
+            .await
+            .unwrap()
+            .iter()
+            .map(|i| i.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PendingItemKind::ExpiringCredential,
+                PendingItemKind::FailedSchedule
+            ]
+        );
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Background Job Queue
+//!
+//! Jobs (`job` table) execute a message with an agent in a workflow without
+//! keeping the IPC call that enqueued them open. The [`JobQueue`] held by the
+//! application state is started by the deferred startup once the agents are
+//! loaded:
+//!
+//! 1. jobs left `running` by a previous session are queued again (or failed
+//!    after `MAX_ATTEMPTS` executions)
+//! 2. queued jobs start in enqueue order, up to `MAX_CONCURRENT_JOBS` at a
+//!    time and one at a time per workflow
+//! 3. each job stores its response as an assistant message of the workflow
+//!    (see [`crate::scheduler::execute_prompt`]) and emits `job_status` on
+//!    every state change
+//!
+//! A failed job is stored as a system message of the workflow.
+
+use crate::db::DBClient;
+use crate::models::job::{Job, JobStatus, JobStatusEvent};
+use crate::models::streaming::{events, CompletionStatus};
+use crate::models::MessageCreate;
+use crate::scheduler::{execute_prompt, save_message, RunContext};
+use crate::tools::constants::jobs as jobs_const;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+/// Fields selected when loading jobs
+pub const JOB_SELECT_FIELDS: &str = "meta::id(id) AS id, workflow_id, agent_id, message, status, \
+     attempts, response_message_id, error, created_at, started_at, finished_at";
+
+/// Background runner of the queued jobs
+pub struct JobQueue {
+    /// Stops the dispatch loop
+    shutdown: CancellationToken,
+    /// Wakes the dispatch loop (job enqueued or finished)
+    wake: Notify,
+    /// Workflows with a job in progress
+    running: Mutex<HashSet<String>>,
+    /// Whether the dispatch loop was started
+    started: AtomicBool,
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobQueue {
+    /// Creates a stopped queue.
+    pub fn new() -> Self {
+        Self {
+            shutdown: CancellationToken::new(),
+            wake: Notify::new(),
+            running: Mutex::new(HashSet::new()),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Resumes the interrupted jobs, then starts the dispatch loop (once).
+    pub fn start(self: &Arc<Self>, context: RunContext) {
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let queue = self.clone();
+        tokio::spawn(async move {
+            match resume_interrupted(&context.db).await {
+                Ok(0) => {}
+                Ok(count) => info!(count, "Interrupted jobs queued again"),
+                Err(e) => warn!(error = %e, "Failed to resume interrupted jobs"),
+            }
+            info!(
+                max_concurrent = jobs_const::MAX_CONCURRENT_JOBS,
+                "Job queue started"
+            );
+            loop {
+                if let Err(e) = queue.dispatch(&context).await {
+                    warn!(error = %e, "Failed to dispatch queued jobs");
+                }
+                tokio::select! {
+                    _ = queue.shutdown.cancelled() => break,
+                    _ = queue.wake.notified() => {}
+                    _ = tokio::time::sleep(Duration::from_secs(jobs_const::POLL_SECS)) => {}
+                }
+            }
+            info!("Job queue stopped");
+        });
+    }
+
+    /// Whether a job is in progress.
+    pub fn is_busy(&self) -> bool {
+        self.running
+            .lock()
+            .map(|running| !running.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Stops the dispatch loop (jobs in progress complete).
+    #[allow(dead_code)]
+    pub fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// Wakes the dispatch loop after a job was enqueued.
+    pub fn notify(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Starts the queued jobs that have a free slot.
+    ///
+    /// A job is marked `running` before it is spawned, so it never starts
+    /// twice.
+    ///
+    /// # Returns
+    /// The spawned jobs (awaited by tests)
+    pub async fn dispatch(
+        self: &Arc<Self>,
+        context: &RunContext,
+    ) -> Result<Vec<tokio::task::JoinHandle<JobStatusEvent>>, String> {
+        let query = format!(
+            "SELECT {} FROM job WHERE status = 'queued' ORDER BY created_at",
+            JOB_SELECT_FIELDS
+        );
+        let rows = context
+            .db
+            .query_json(&query)
+            .await
+            .map_err(|e| format!("Failed to load queued jobs: {}", e))?;
+
+        let mut started = Vec::new();
+        for job in rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value::<Job>(row).ok())
+        {
+            let inserted = match self.running.lock() {
+                Ok(mut running) if running.len() < jobs_const::MAX_CONCURRENT_JOBS => {
+                    running.insert(job.workflow_id.clone())
+                }
+                _ => break,
+            };
+            if !inserted {
+                debug!(job_id = %job.id, "Workflow busy, job kept queued");
+                continue;
+            }
+
+            if let Err(e) = mark_running(&context.db, &job.id).await {
+                self.release(&job.workflow_id);
+                return Err(e);
+            }
+            emit_status(context, &job, JobStatus::Running, None);
+
+            let queue = self.clone();
+            let context = context.clone();
+            started.push(tokio::spawn(async move {
+                let event = run_job(&context, &job).await;
+                queue.release(&job.workflow_id);
+                queue.notify();
+                event
+            }));
+        }
+        Ok(started)
+    }
+
+    /// Frees the slot of a workflow.
+    fn release(&self, workflow_id: &str) {
+        if let Ok(mut running) = self.running.lock() {
+            running.remove(workflow_id);
+        }
+    }
+}
+
+/// Queues again the jobs left `running` by a previous session.
+///
+/// Jobs that already used `MAX_ATTEMPTS` executions fail instead, so a job
+/// crashing the app is not retried forever.
+///
+/// # Returns
+/// The number of jobs queued again
+pub async fn resume_interrupted(db: &DBClient) -> Result<usize, String> {
+    db.execute_with_params(
+        "UPDATE job SET status = 'failed', error = $error, finished_at = time::now() \
+         WHERE status = 'running' AND attempts >= $max_attempts",
+        vec![
+            (
+                "error".to_string(),
+                serde_json::json!("Interrupted too many times"),
+            ),
+            (
+                "max_attempts".to_string(),
+                serde_json::json!(jobs_const::MAX_ATTEMPTS),
+            ),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to fail interrupted jobs: {}", e))?;
+
+    let interrupted = db
+        .query_json("SELECT meta::id(id) AS id FROM job WHERE status = 'running'")
+        .await
+        .map_err(|e| format!("Failed to load interrupted jobs: {}", e))?;
+    if interrupted.is_empty() {
+        return Ok(0);
+    }
+    db.execute("UPDATE job SET status = 'queued', started_at = NONE WHERE status = 'running'")
+        .await
+        .map_err(|e| format!("Failed to resume interrupted jobs: {}", e))?;
+    Ok(interrupted.len())
+}
+
+/// Marks a job as running and counts the execution.
+async fn mark_running(db: &DBClient, job_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('job', $id) SET status = 'running', attempts += 1, \
+         started_at = time::now()",
+        vec![("id".to_string(), serde_json::json!(job_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to start job: {}", e))
+}
+
+/// Executes a job and records its outcome.
+///
+/// A failure is stored as a system message of the workflow.
+pub async fn run_job(context: &RunContext, job: &Job) -> JobStatusEvent {
+    info!(job_id = %job.id, workflow_id = %job.workflow_id, "Job started");
+
+    let result = execute_prompt(context, &job.workflow_id, &job.agent_id, &job.message).await;
+    crate::metrics::record_workflow_execution(
+        "job",
+        &if result.is_ok() {
+            CompletionStatus::Completed
+        } else {
+            CompletionStatus::Error
+        },
+    );
+    let (status, response_message_id, error) = match result {
+        Ok(message_id) => (JobStatus::Done, Some(message_id), None),
+        Err(e) => {
+            warn!(job_id = %job.id, error = %e, "Job failed");
+            if let Err(e) = save_message(
+                &context.db,
+                MessageCreate::system(
+                    job.workflow_id.clone(),
+                    format!("Background execution failed: {}", e),
+                ),
+            )
+            .await
+            {
+                warn!(error = %e, "Failed to store the job error");
+            }
+            (JobStatus::Failed, None, Some(e))
+        }
+    };
+
+    if let Err(e) = context
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('job', $id) SET status = $status, \
+             response_message_id = $message_id, error = $error, finished_at = time::now()",
+            vec![
+                ("id".to_string(), serde_json::json!(job.id)),
+                ("status".to_string(), serde_json::json!(status.as_str())),
+                (
+                    "message_id".to_string(),
+                    serde_json::json!(response_message_id),
+                ),
+                ("error".to_string(), serde_json::json!(error)),
+            ],
+        )
+        .await
+    {
+        error!(error = %e, "Failed to record the job outcome");
+    }
+
+    info!(job_id = %job.id, status = ?status, "Job finished");
+    emit_status(context, job, status, error)
+}
+
+/// Emits the `job_status` event of a job.
+fn emit_status(
+    context: &RunContext,
+    job: &Job,
+    status: JobStatus,
+    error: Option<String>,
+) -> JobStatusEvent {
+    let event = JobStatusEvent {
+        job_id: job.id.clone(),
+        workflow_id: job.workflow_id.clone(),
+        status,
+        error,
+    };
+    let handle = context
+        .app_handle
+        .read()
+        .ok()
+        .and_then(|guard| guard.clone());
+    if let Some(handle) = handle {
+        if let Err(e) = handle.emit(events::JOB_STATUS, &event) {
+            warn!(error = %e, "Failed to emit job status event");
+        }
+    }
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::job::JobCreate;
+    use crate::state::AppState;
+    use tempfile::tempdir;
+    use uuid::Uuid;
+
+    async fn create_job(db: &DBClient, status: JobStatus, attempts: u32) -> String {
+        let job_id = Uuid::new_v4().to_string();
+        let record = JobCreate {
+            workflow_id: Uuid::new_v4().to_string(),
+            agent_id: "missing_agent".to_string(),
+            message: "Hello".to_string(),
+            status,
+        };
+        db.create("job", &job_id, record).await.unwrap();
+        db.execute_with_params(
+            "UPDATE type::thing('job', $id) SET attempts = $attempts",
+            vec![
+                ("id".to_string(), serde_json::json!(job_id)),
+                ("attempts".to_string(), serde_json::json!(attempts)),
+            ],
+        )
+        .await
+        .unwrap();
+        job_id
+    }
+
+    async fn job_status(db: &DBClient, job_id: &str) -> (String, Option<String>) {
+        let rows = db
+            .query_json_with_params(
+                "SELECT status, error FROM type::thing('job', $id)",
+                vec![("id".to_string(), serde_json::json!(job_id))],
+            )
+            .await
+            .unwrap();
+        (
+            rows[0]["status"].as_str().unwrap().to_string(),
+            rows[0]["error"].as_str().map(String::from),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resume_interrupted() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("jobs_db");
+        let state = AppState::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let interrupted = create_job(&state.db, JobStatus::Running, 1).await;
+        let exhausted = create_job(&state.db, JobStatus::Running, jobs_const::MAX_ATTEMPTS).await;
+        let done = create_job(&state.db, JobStatus::Done, 1).await;
+
+        assert_eq!(resume_interrupted(&state.db).await.unwrap(), 1);
+        assert_eq!(
+            job_status(&state.db, &interrupted).await,
+            ("queued".to_string(), None)
+        );
+        assert_eq!(
+            job_status(&state.db, &exhausted).await,
+            (
+                "failed".to_string(),
+                Some("Interrupted too many times".to_string())
+            )
+        );
+        assert_eq!(job_status(&state.db, &done).await.0, "done");
+        assert_eq!(resume_interrupted(&state.db).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_job_stored_as_system_message() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("jobs_db");
+        let state = AppState::new(db_path.to_str().unwrap()).await.unwrap();
+        let job_id = create_job(&state.db, JobStatus::Queued, 0).await;
+
+        let queue = Arc::new(JobQueue::new());
+        let jobs = queue.dispatch(&state.run_context()).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        for job in jobs {
+            let event = job.await.unwrap();
+            assert_eq!(event.status, JobStatus::Failed);
+            assert_eq!(
+                event.error.as_deref(),
+                Some("Agent not found: missing_agent")
+            );
+        }
+        assert_eq!(
+            job_status(&state.db, &job_id).await,
+            (
+                "failed".to_string(),
+                Some("Agent not found: missing_agent".to_string())
+            )
+        );
+
+        let messages = state
+            .db
+            .query_json("SELECT role, content FROM message")
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["role"], "system");
+        assert_eq!(
+            messages[0]["content"],
+            "Background execution failed: Agent not found: missing_agent"
+        );
+        assert!(queue
+            .dispatch(&state.run_context())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
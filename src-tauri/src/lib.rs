@@ -21,9 +21,16 @@
 //!
 //! - [`agents`] - Multi-agent system infrastructure (registry, orchestrator, agent trait)
 //! - [`commands`] - Tauri IPC command handlers for frontend communication
+//! - [`config_file`] - Optional config file overriding startup settings
 //! - [`db`] - SurrealDB database client and schema management
+//! - [`diagnostics`] - Diagnostics bundle for bug reports (logs, health, sanitized config)
+//! - [`metrics`] - Metrics of executions, tool calls, LLM requests and MCP errors (Prometheus, OTLP)
 //! - [`models`] - Data models shared between frontend and backend
+//! - [`plugins`] - Third-party tools run as sandboxed WebAssembly components
+//! - [`profiles`] - Profiles with isolated databases (work, personal...)
+//! - [`proxy`] - Proxy of the outgoing requests (providers, embedding, MCP)
 //! - [`security`] - Input validation and secure key storage
+//! - [`startup`] - Deferred startup phases (providers, MCP servers, agents)
 //! - [`state`] - Application state management
 //!
 //! ## Architecture
@@ -47,14 +54,30 @@
 
 pub mod agents;
 pub mod commands;
+pub mod config_file;
 pub mod db;
+pub mod diagnostics;
+pub mod documents;
+pub mod inbox;
+pub mod jobs;
 pub mod llm;
 pub mod mcp;
+pub mod metrics;
 pub mod models;
+pub mod plugins;
+pub mod profiles;
+pub mod proxy;
+pub mod scheduler;
 pub mod security;
+pub mod settings_sync;
+pub mod speech;
+pub mod startup;
 pub mod state;
 pub mod tools;
 
+#[cfg(test)]
+pub(crate) mod test_harness;
+
 pub use commands::SecureKeyStore;
 pub use llm::ProviderManager;
 pub use mcp::{MCPClient, MCPError, MCPManager, MCPResult, MCPServerHandle};
@@ -0,0 +1,231 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Gemini Tool Adapter
+//!
+//! Implements the ProviderToolAdapter trait for Google Gemini's function calling API.
+//!
+//! ## Gemini API Specifics
+//!
+//! - Tools and messages are sent in OpenAI format and converted to
+//!   `functionDeclarations` / `contents` by `GeminiProvider`
+//! - `args` in function calls are JSON objects (no parsing needed)
+//! - Call IDs are optional in the API; `GeminiProvider` fills missing ones
+//! - `tool_choice` maps to `toolConfig.functionCallingConfig.mode`: "AUTO", "ANY", "NONE"
+//! - Response path: `candidates[0].content.parts[].functionCall`
+//! - No `tool_calls` finish reason: a response with function calls is not finished
+
+use crate::llm::gemini::{candidate_parts, response_text, response_thoughts, response_usage};
+use crate::llm::tool_adapter::{helpers, ProviderToolAdapter};
+use crate::models::function_calling::{FunctionCall, FunctionCallResult, ToolChoiceMode};
+use crate::tools::ToolDefinition;
+use serde_json::{json, Value};
+use tracing::debug;
+
+/// Adapter for Google Gemini's function calling API.
+///
+/// Exposes Gemini responses to the agent loop in the same shape as the
+/// OpenAI-compatible adapters, so the conversation history stays uniform.
+#[derive(Debug, Clone, Default)]
+pub struct GeminiToolAdapter;
+
+impl GeminiToolAdapter {
+    /// Creates a new Gemini tool adapter.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ProviderToolAdapter for GeminiToolAdapter {
+    fn format_tools(&self, tools: &[ToolDefinition]) -> Vec<Value> {
+        tools.iter().map(helpers::tool_definition_to_json).collect()
+    }
+
+    fn parse_tool_calls(&self, response: &Value) -> Vec<FunctionCall> {
+        candidate_parts(response)
+            .iter()
+            .filter_map(|part| part.get("functionCall"))
+            .filter_map(|call| {
+                let name = call.get("name").and_then(|v| v.as_str())?.to_string();
+                let id = call
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| helpers::generate_call_id("gemini"));
+                let arguments = call.get("args").cloned().unwrap_or_else(|| json!({}));
+
+                debug!(tool = %name, call_id = %id, "Parsed Gemini function call");
+
+                Some(FunctionCall {
+                    id,
+                    name,
+                    arguments,
+                })
+            })
+            .collect()
+    }
+
+    fn format_tool_result(&self, result: &FunctionCallResult) -> Value {
+        // The function name is required to build the Gemini functionResponse
+        json!({
+            "role": "tool",
+            "tool_call_id": &result.call_id,
+            "name": &result.function_name,
+            "content": helpers::result_to_string(result)
+        })
+    }
+
+    fn get_tool_choice(&self, mode: ToolChoiceMode) -> Value {
+        let mode = match mode {
+            ToolChoiceMode::Auto => "AUTO",
+            ToolChoiceMode::Required => "ANY",
+            ToolChoiceMode::None => "NONE",
+        };
+        json!({ "functionCallingConfig": { "mode": mode } })
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn extract_content(&self, response: &Value) -> Option<String> {
+        response_text(response)
+    }
+
+    fn extract_reasoning(&self, response: &Value) -> Option<String> {
+        response_thoughts(response)
+    }
+
+    fn has_tool_calls(&self, response: &Value) -> bool {
+        candidate_parts(response)
+            .iter()
+            .any(|part| part.get("functionCall").is_some())
+    }
+
+    fn is_finished(&self, response: &Value) -> bool {
+        !self.has_tool_calls(response)
+    }
+
+    fn build_assistant_message(&self, response: &Value) -> Value {
+        let tool_calls: Vec<Value> = self
+            .parse_tool_calls(response)
+            .into_iter()
+            .map(|call| {
+                json!({
+                    "id": call.id,
+                    "type": "function",
+                    "function": {
+                        "name": call.name,
+                        "arguments": call.arguments.to_string()
+                    }
+                })
+            })
+            .collect();
+
+        let mut message = json!({
+            "role": "assistant",
+            "content": response_text(response).unwrap_or_default()
+        });
+        if !tool_calls.is_empty() {
+            message["tool_calls"] = Value::Array(tool_calls);
+        }
+        message
+    }
+
+    /// Extracts token usage from Gemini's response format.
+    ///
+    /// - `usageMetadata.promptTokenCount` = input tokens
+    /// - `usageMetadata.candidatesTokenCount` + `thoughtsTokenCount` = output tokens
+    fn extract_usage(&self, response: &Value) -> (usize, usize) {
+        response_usage(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_response() -> Value {
+        json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"text": "Let me check."},
+                        {"functionCall": {"id": "gemini_1", "name": "MemoryTool", "args": {"operation": "search"}}}
+                    ]
+                },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 100, "candidatesTokenCount": 20}
+        })
+    }
+
+    #[test]
+    fn test_parse_tool_calls() {
+        let adapter = GeminiToolAdapter::new();
+        let calls = adapter.parse_tool_calls(&sample_response());
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "gemini_1");
+        assert_eq!(calls[0].name, "MemoryTool");
+        assert_eq!(calls[0].arguments["operation"], "search");
+        assert!(adapter.has_tool_calls(&sample_response()));
+        assert!(!adapter.is_finished(&sample_response()));
+    }
+
+    #[test]
+    fn test_text_response() {
+        let adapter = GeminiToolAdapter::new();
+        let response = json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "Done."}]},
+                "finishReason": "STOP"
+            }]
+        });
+
+        assert!(adapter.parse_tool_calls(&response).is_empty());
+        assert!(adapter.is_finished(&response));
+        assert_eq!(
+            adapter.extract_content(&response),
+            Some("Done.".to_string())
+        );
+        assert_eq!(adapter.extract_usage(&response), (0, 0));
+    }
+
+    #[test]
+    fn test_build_assistant_message() {
+        let adapter = GeminiToolAdapter::new();
+        let message = adapter.build_assistant_message(&sample_response());
+
+        assert_eq!(message["role"], "assistant");
+        assert_eq!(message["content"], "Let me check.");
+        assert_eq!(message["tool_calls"][0]["id"], "gemini_1");
+        assert_eq!(message["tool_calls"][0]["function"]["name"], "MemoryTool");
+        assert_eq!(
+            message["tool_calls"][0]["function"]["arguments"],
+            "{\"operation\":\"search\"}"
+        );
+    }
+
+    #[test]
+    fn test_tool_choice_and_usage() {
+        let adapter = GeminiToolAdapter::new();
+        assert_eq!(
+            adapter.get_tool_choice(ToolChoiceMode::Required),
+            json!({"functionCallingConfig": {"mode": "ANY"}})
+        );
+        assert_eq!(adapter.extract_usage(&sample_response()), (100, 20));
+    }
+}
@@ -134,6 +134,11 @@ impl ProviderToolAdapter for MistralToolAdapter {
         })
     }
 
+    fn format_tool_images(&self, _text: &str, _image_urls: &[String]) -> Option<Value> {
+        // Mistral requires the assistant turn right after the tool results
+        None
+    }
+
     fn get_tool_choice(&self, mode: ToolChoiceMode) -> Value {
         match mode {
             ToolChoiceMode::Auto => json!("auto"),
@@ -147,10 +152,12 @@ impl ProviderToolAdapter for MistralToolAdapter {
     }
 
     fn extract_content(&self, response: &Value) -> Option<String> {
-        response
-            .pointer("/choices/0/message/content")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
+        // Reasoning models (Magistral) return an array of thinking/text blocks
+        helpers::split_reasoning(response.pointer("/choices/0/message/content")).1
+    }
+
+    fn extract_reasoning(&self, response: &Value) -> Option<String> {
+        helpers::split_reasoning(response.pointer("/choices/0/message/content")).0
     }
 
     fn has_tool_calls(&self, response: &Value) -> bool {
@@ -180,6 +187,7 @@ impl ProviderToolAdapter for MistralToolAdapter {
         response
             .pointer("/choices/0/message")
             .cloned()
+            .map(helpers::strip_reasoning)
             .unwrap_or_else(|| {
                 json!({
                     "role": "assistant",
@@ -302,6 +310,13 @@ mod tests {
         assert_eq!(formatted["name"], "MemoryTool");
     }
 
+    #[test]
+    fn test_format_tool_images_unsupported() {
+        let adapter = MistralToolAdapter::new();
+        let urls = vec!["data:image/png;base64,AAAA".to_string()];
+        assert!(adapter.format_tool_images("Screenshot", &urls).is_none());
+    }
+
     #[test]
     fn test_tool_choice_modes() {
         let adapter = MistralToolAdapter::new();
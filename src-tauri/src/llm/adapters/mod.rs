@@ -21,11 +21,14 @@
 //!
 //! - **Mistral**: Full function calling support with `tool_call_id`
 //! - **Ollama**: OpenAI-compatible format (no native tool_call_id)
+//! - **Gemini**: `functionCall` parts, converted to the OpenAI message format
 
+mod gemini_adapter;
 mod mistral_adapter;
 mod ollama_adapter;
 mod openai_adapter;
 
+pub use gemini_adapter::GeminiToolAdapter;
 pub use mistral_adapter::MistralToolAdapter;
 pub use ollama_adapter::OllamaToolAdapter;
 pub use openai_adapter::OpenAiToolAdapter;
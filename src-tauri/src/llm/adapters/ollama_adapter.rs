@@ -147,11 +147,21 @@ impl ProviderToolAdapter for OllamaToolAdapter {
 
     fn extract_content(&self, response: &Value) -> Option<String> {
         // Ollama response: { "message": { "content": "..." } }
+        helpers::split_reasoning(response.pointer("/message/content"))
+            .1
+            .filter(|s| !s.is_empty())
+    }
+
+    fn extract_reasoning(&self, response: &Value) -> Option<String> {
+        // Thinking models return { "message": { "thinking": "..." } }; older
+        // Ollama versions leave a <think> block in the content instead
         response
-            .pointer("/message/content")
+            .pointer("/message/thinking")
             .and_then(|v| v.as_str())
+            .map(str::trim)
             .filter(|s| !s.is_empty())
-            .map(|s| s.to_string())
+            .map(str::to_string)
+            .or_else(|| helpers::split_reasoning(response.pointer("/message/content")).0)
     }
 
     fn has_tool_calls(&self, response: &Value) -> bool {
@@ -180,12 +190,16 @@ impl ProviderToolAdapter for OllamaToolAdapter {
 
     fn build_assistant_message(&self, response: &Value) -> Value {
         // Ollama: extract message directly (not nested in choices)
-        response.get("message").cloned().unwrap_or_else(|| {
-            json!({
-                "role": "assistant",
-                "content": ""
+        response
+            .get("message")
+            .cloned()
+            .map(helpers::strip_reasoning)
+            .unwrap_or_else(|| {
+                json!({
+                    "role": "assistant",
+                    "content": ""
+                })
             })
-        })
     }
 
     /// Extracts token usage from Ollama's response format.
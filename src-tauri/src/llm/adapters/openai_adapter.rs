@@ -143,10 +143,20 @@ impl ProviderToolAdapter for OpenAiToolAdapter {
     }
 
     fn extract_content(&self, response: &Value) -> Option<String> {
-        response
-            .pointer("/choices/0/message/content")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
+        helpers::split_reasoning(response.pointer("/choices/0/message/content")).1
+    }
+
+    fn extract_reasoning(&self, response: &Value) -> Option<String> {
+        // Dedicated field (DeepSeek, vLLM, LM Studio), else thinking blocks or
+        // a leading <think> block in the content
+        ["reasoning_content", "reasoning"]
+            .iter()
+            .filter_map(|field| response.pointer(&format!("/choices/0/message/{}", field)))
+            .filter_map(|v| v.as_str())
+            .map(str::trim)
+            .find(|s| !s.is_empty())
+            .map(str::to_string)
+            .or_else(|| helpers::split_reasoning(response.pointer("/choices/0/message/content")).0)
     }
 
     fn has_tool_calls(&self, response: &Value) -> bool {
@@ -174,6 +184,7 @@ impl ProviderToolAdapter for OpenAiToolAdapter {
         response
             .pointer("/choices/0/message")
             .cloned()
+            .map(helpers::strip_reasoning)
             .unwrap_or_else(|| {
                 json!({
                     "role": "assistant",
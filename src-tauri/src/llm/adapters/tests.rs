@@ -341,3 +341,93 @@ fn test_mcp_tool_name_parsing() {
     assert!(!local_call.is_mcp_tool());
     assert!(local_call.parse_mcp_name().is_none());
 }
+
+#[test]
+fn test_reasoning_kept_out_of_the_answer() {
+    // Magistral: thinking blocks in the content array
+    let mistral = MistralToolAdapter::new();
+    let response = json!({
+        "choices": [{
+            "message": {
+                "role": "assistant",
+                "content": [
+                    {"type": "thinking", "thinking": [{"type": "text", "text": "2 + 2 is 4."}]},
+                    {"type": "text", "text": "4"}
+                ]
+            },
+            "finish_reason": "stop"
+        }]
+    });
+    assert_eq!(mistral.extract_content(&response), Some("4".to_string()));
+    assert_eq!(
+        mistral.extract_reasoning(&response),
+        Some("2 + 2 is 4.".to_string())
+    );
+    assert_eq!(
+        mistral.build_assistant_message(&response)["content"],
+        json!("4")
+    );
+
+    // Ollama: dedicated thinking field, or a <think> block in older versions
+    let ollama = OllamaToolAdapter::new();
+    let response = json!({
+        "message": {"role": "assistant", "content": "4", "thinking": "2 + 2 is 4."},
+        "done": true
+    });
+    assert_eq!(ollama.extract_content(&response), Some("4".to_string()));
+    assert_eq!(
+        ollama.extract_reasoning(&response),
+        Some("2 + 2 is 4.".to_string())
+    );
+    assert!(ollama
+        .build_assistant_message(&response)
+        .get("thinking")
+        .is_none());
+    let response = json!({
+        "message": {"role": "assistant", "content": "<think>2 + 2 is 4.</think>\n4"},
+        "done": true
+    });
+    assert_eq!(ollama.extract_content(&response), Some("4".to_string()));
+    assert_eq!(
+        ollama.extract_reasoning(&response),
+        Some("2 + 2 is 4.".to_string())
+    );
+
+    // OpenAI-compatible: reasoning_content field (DeepSeek)
+    let openai = OpenAiToolAdapter::new();
+    let response = json!({
+        "choices": [{
+            "message": {"role": "assistant", "content": "4", "reasoning_content": "2 + 2 is 4."},
+            "finish_reason": "stop"
+        }]
+    });
+    assert_eq!(openai.extract_content(&response), Some("4".to_string()));
+    assert_eq!(
+        openai.extract_reasoning(&response),
+        Some("2 + 2 is 4.".to_string())
+    );
+    assert!(openai
+        .build_assistant_message(&response)
+        .get("reasoning_content")
+        .is_none());
+
+    // Gemini: thought summary parts
+    let gemini = GeminiToolAdapter::new();
+    let response = json!({
+        "candidates": [{
+            "content": {"role": "model", "parts": [
+                {"text": "2 + 2 is 4.", "thought": true},
+                {"text": "4"}
+            ]}
+        }]
+    });
+    assert_eq!(gemini.extract_content(&response), Some("4".to_string()));
+    assert_eq!(
+        gemini.extract_reasoning(&response),
+        Some("2 + 2 is 4.".to_string())
+    );
+
+    // No reasoning
+    let response = json!({"message": {"role": "assistant", "content": "4"}, "done": true});
+    assert_eq!(ollama.extract_reasoning(&response), None);
+}
@@ -43,6 +43,8 @@
 // Allow dead code until MemoryTool integration in Phase 3
 #![allow(dead_code)]
 
+use crate::tools::constants::proxy::TARGET_EMBEDDING;
+use futures::stream::{self, StreamExt, TryStreamExt};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -81,6 +83,9 @@ pub const MAX_EMBEDDING_TEXT_LENGTH: usize = 50_000;
 /// Maximum batch size for embedding requests
 pub const MAX_BATCH_SIZE: usize = 96;
 
+/// Ollama embedding requests sent concurrently within a batch
+pub const OLLAMA_PIPELINE_DEPTH: usize = 4;
+
 /// Default timeout for embedding requests (milliseconds)
 pub const DEFAULT_TIMEOUT_MS: u64 = 30_000;
 
@@ -398,7 +403,7 @@ impl EmbeddingService {
     ///
     /// The service must be configured with a provider before use.
     pub fn new() -> Self {
-        let client = Client::builder()
+        let client = crate::proxy::client_builder(TARGET_EMBEDDING)
             .timeout(std::time::Duration::from_millis(DEFAULT_TIMEOUT_MS))
             .build()
             .expect("Failed to create HTTP client");
@@ -420,7 +425,7 @@ impl EmbeddingService {
     /// A configured EmbeddingService ready for use
     pub fn with_provider(provider: EmbeddingProvider) -> Self {
         let dimension = provider.dimension();
-        let client = Client::builder()
+        let client = crate::proxy::client_builder(TARGET_EMBEDDING)
             .timeout(std::time::Duration::from_millis(DEFAULT_TIMEOUT_MS))
             .build()
             .expect("Failed to create HTTP client");
@@ -551,7 +556,6 @@ impl EmbeddingService {
                 self.embed_batch_mistral(texts, api_key, model).await
             }
             EmbeddingProvider::Ollama { base_url, model } => {
-                // Ollama doesn't support batch, so we process sequentially
                 self.embed_batch_ollama(texts, base_url, model).await
             }
         }
@@ -742,7 +746,10 @@ impl EmbeddingService {
         Ok(result.embedding)
     }
 
-    /// Embeds batch using Ollama API (sequential, no native batch support)
+    /// Embeds batch using Ollama API.
+    ///
+    /// `/api/embeddings` takes a single prompt, so the requests are pipelined:
+    /// up to [`OLLAMA_PIPELINE_DEPTH`] are in flight, results keep input order.
     async fn embed_batch_ollama(
         &self,
         texts: &[&str],
@@ -751,14 +758,18 @@ impl EmbeddingService {
     ) -> Result<Vec<Vec<f32>>, EmbeddingError> {
         debug!(
             batch_size = texts.len(),
-            "Processing Ollama batch sequentially"
+            depth = OLLAMA_PIPELINE_DEPTH,
+            "Processing Ollama batch with pipelined requests"
         );
 
-        let mut embeddings = Vec::with_capacity(texts.len());
-        for text in texts {
-            let embedding = self.embed_ollama(text, base_url, model).await?;
-            embeddings.push(embedding);
-        }
+        let requests: Vec<_> = texts
+            .iter()
+            .map(|text| self.embed_ollama(text, base_url, model))
+            .collect();
+        let embeddings: Vec<Vec<f32>> = stream::iter(requests)
+            .buffered(OLLAMA_PIPELINE_DEPTH)
+            .try_collect()
+            .await?;
 
         debug!(count = embeddings.len(), "Ollama batch embedding completed");
 
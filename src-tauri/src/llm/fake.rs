@@ -0,0 +1,417 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic fake LLM provider
+//!
+//! Answers from a script instead of calling an API, so workflows, streaming
+//! and sub-agents can run in CI or while diagnosing issues offline.
+//!
+//! ## Activation
+//!
+//! The provider is enabled by the `ZILEO_FAKE_LLM` environment variable:
+//! - `echo` - no script, every call echoes the latest message
+//! - any other value - path of a JSON script file
+//!
+//! Agents select it with the provider name `fake` (model `fake-model`).
+//!
+//! ## Script
+//!
+//! ```json
+//! {
+//!   "turns": [
+//!     {"match": "invoices", "tool_calls": [{"name": "MemoryTool", "arguments": {"operation": "list"}}]},
+//!     {"content": "I found 3 invoices."},
+//!     {"match": "fail", "error": "Simulated outage"}
+//!   ],
+//!   "default_response": "Nothing scripted for this message."
+//! }
+//! ```
+//!
+//! Each call consumes the first unused turn whose `match` (case-insensitive)
+//! is contained in the latest message, or that has no `match`. When no turn
+//! applies, `default_response` (or an echo of the latest message) is returned.
+
+use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType};
+use super::utils::{content_text, estimate_tokens, simulate_streaming};
+use crate::models::llm_models::ModelMetadata;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// Provider name selecting the fake provider in agent configurations
+pub const FAKE_PROVIDER_NAME: &str = "fake";
+
+/// Only model of the fake provider
+pub const FAKE_MODEL: &str = "fake-model";
+
+/// Environment variable enabling the fake provider (`echo` or script path)
+pub const FAKE_LLM_ENV: &str = "ZILEO_FAKE_LLM";
+
+/// Maximum characters of the latest message quoted by echo responses
+const ECHO_MAX_CHARS: usize = 200;
+
+/// Scripted tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FakeToolCall {
+    /// Tool name as exposed to the agent (`mcp__server__tool` for MCP tools)
+    pub name: String,
+    /// Tool arguments
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// Scripted LLM turn
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FakeTurn {
+    /// Substring the latest message must contain (None = any message)
+    #[serde(default, rename = "match")]
+    pub match_text: Option<String>,
+    /// Text content of the response
+    #[serde(default)]
+    pub content: String,
+    /// Tool calls requested by the response
+    #[serde(default)]
+    pub tool_calls: Vec<FakeToolCall>,
+    /// Error returned instead of a response
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl FakeTurn {
+    fn matches(&self, message: &str) -> bool {
+        self.match_text
+            .as_ref()
+            .is_none_or(|m| message.to_lowercase().contains(&m.to_lowercase()))
+    }
+}
+
+/// Script of the fake provider
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FakeScript {
+    /// Turns in consumption order
+    #[serde(default)]
+    pub turns: Vec<FakeTurn>,
+    /// Response when no turn applies (None = echo the latest message)
+    #[serde(default)]
+    pub default_response: Option<String>,
+}
+
+/// Deterministic fake LLM provider
+pub struct FakeProvider {
+    script: FakeScript,
+    /// Whether each turn has been consumed
+    consumed: Mutex<Vec<bool>>,
+    /// Number of calls, used to build response and tool call IDs
+    calls: AtomicUsize,
+}
+
+impl FakeProvider {
+    /// Creates a provider answering from `script`
+    pub fn new(script: FakeScript) -> Self {
+        let consumed = vec![false; script.turns.len()];
+        Self {
+            script,
+            consumed: Mutex::new(consumed),
+            calls: AtomicUsize::new(0),
+        }
+    }
+
+    /// Creates a provider echoing the latest message
+    pub fn echo() -> Self {
+        Self::new(FakeScript::default())
+    }
+
+    /// Metadata of the single model served by the fake provider
+    pub fn model_metadata(&self) -> Vec<ModelMetadata> {
+        vec![ModelMetadata {
+            api_name: FAKE_MODEL.to_string(),
+            name: Some("Fake Model".to_string()),
+            context_window: Some(32_768),
+            max_output_tokens: Some(4_096),
+            supports_vision: Some(false),
+            supports_tools: Some(true),
+        }]
+    }
+
+    /// Creates the provider configured by `ZILEO_FAKE_LLM`
+    ///
+    /// # Returns
+    /// `None` when the variable is not set or the script cannot be loaded
+    pub fn from_env() -> Option<Self> {
+        let value = std::env::var(FAKE_LLM_ENV).ok()?;
+        if value.trim().is_empty() {
+            return None;
+        }
+        if value.trim() == "echo" {
+            info!("Fake LLM provider enabled (echo mode)");
+            return Some(Self::echo());
+        }
+
+        let script = std::fs::read_to_string(&value)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                serde_json::from_str::<FakeScript>(&content).map_err(|e| e.to_string())
+            });
+        match script {
+            Ok(script) => {
+                info!(path = %value, turns = script.turns.len(), "Fake LLM provider enabled");
+                Some(Self::new(script))
+            }
+            Err(e) => {
+                warn!(path = %value, error = %e, "Failed to load fake LLM script");
+                None
+            }
+        }
+    }
+
+    /// Consumes the turn answering `message`
+    fn next_turn(&self, message: &str) -> FakeTurn {
+        let mut consumed = self.consumed.lock().unwrap_or_else(|e| e.into_inner());
+        let index = self
+            .script
+            .turns
+            .iter()
+            .enumerate()
+            .position(|(i, turn)| !consumed[i] && turn.matches(message));
+
+        match index {
+            Some(i) => {
+                consumed[i] = true;
+                debug!(turn = i, "Fake LLM turn consumed");
+                self.script.turns[i].clone()
+            }
+            None => FakeTurn {
+                content: self.script.default_response.clone().unwrap_or_else(|| {
+                    format!(
+                        "Fake response to: {}",
+                        message.chars().take(ECHO_MAX_CHARS).collect::<String>()
+                    )
+                }),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Answers an OpenAI-format chat completion request
+    ///
+    /// The response uses the OpenAI format (parsed with `OpenAiToolAdapter`).
+    /// Scripted tool calls are returned as `tool_calls` with the
+    /// `tool_calls` finish reason.
+    pub async fn complete_with_tools(
+        &self,
+        messages: Vec<Value>,
+        _tools: Vec<Value>,
+        model: &str,
+    ) -> Result<Value, LLMError> {
+        let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+        let latest = messages
+            .last()
+            .and_then(|m| m.get("content"))
+            .map(content_text)
+            .unwrap_or_default();
+        let turn = self.next_turn(&latest);
+        if let Some(error) = turn.error {
+            return Err(LLMError::RequestFailed(error));
+        }
+
+        let prompt_tokens: usize = messages
+            .iter()
+            .filter_map(|m| m.get("content"))
+            .filter(|c| c.is_string() || c.is_array())
+            .map(|c| estimate_tokens(&content_text(c)))
+            .sum();
+        let tool_calls: Vec<Value> = turn
+            .tool_calls
+            .iter()
+            .enumerate()
+            .map(|(i, tc)| {
+                json!({
+                    "id": format!("call_{}_{}", call, i + 1),
+                    "type": "function",
+                    "function": {"name": tc.name, "arguments": tc.arguments.to_string()}
+                })
+            })
+            .collect();
+        let completion_tokens = estimate_tokens(&turn.content)
+            + tool_calls
+                .iter()
+                .map(|tc| estimate_tokens(&tc.to_string()))
+                .sum::<usize>();
+
+        let mut message = json!({"role": "assistant", "content": turn.content});
+        let finish_reason = if tool_calls.is_empty() {
+            "stop"
+        } else {
+            message["tool_calls"] = Value::Array(tool_calls);
+            "tool_calls"
+        };
+
+        Ok(json!({
+            "id": format!("fake-{}", call),
+            "object": "chat.completion",
+            "model": model,
+            "choices": [{"index": 0, "message": message, "finish_reason": finish_reason}],
+            "usage": {
+                "prompt_tokens": prompt_tokens,
+                "completion_tokens": completion_tokens,
+                "total_tokens": prompt_tokens + completion_tokens
+            }
+        }))
+    }
+}
+
+#[async_trait]
+impl LLMProvider for FakeProvider {
+    fn provider_type(&self) -> ProviderType {
+        ProviderType::Custom(FAKE_PROVIDER_NAME.to_string())
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        vec![FAKE_MODEL.to_string()]
+    }
+
+    fn default_model(&self) -> String {
+        FAKE_MODEL.to_string()
+    }
+
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    async fn complete(
+        &self,
+        prompt: &str,
+        system_prompt: Option<&str>,
+        model: Option<&str>,
+        _temperature: f32,
+        _max_tokens: usize,
+    ) -> Result<LLMResponse, LLMError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let turn = self.next_turn(prompt);
+        if let Some(error) = turn.error {
+            return Err(LLMError::RequestFailed(error));
+        }
+
+        Ok(LLMResponse {
+            tokens_input: estimate_tokens(prompt) + system_prompt.map_or(0, estimate_tokens),
+            tokens_output: estimate_tokens(&turn.content),
+            content: turn.content,
+            model: model.unwrap_or(FAKE_MODEL).to_string(),
+            provider: self.provider_type(),
+            finish_reason: Some("stop".to_string()),
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        prompt: &str,
+        system_prompt: Option<&str>,
+        model: Option<&str>,
+        temperature: f32,
+        max_tokens: usize,
+    ) -> Result<mpsc::Receiver<Result<String, LLMError>>, LLMError> {
+        let response = self
+            .complete(prompt, system_prompt, model, temperature, max_tokens)
+            .await?;
+        Ok(simulate_streaming(response.content, None, Some(0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::adapters::OpenAiToolAdapter;
+    use crate::llm::tool_adapter::ProviderToolAdapter;
+
+    fn script() -> FakeScript {
+        serde_json::from_value(json!({
+            "turns": [
+                {"match": "INVOICES", "tool_calls": [{"name": "MemoryTool", "arguments": {"operation": "list"}}]},
+                {"content": "I found 3 invoices."},
+                {"match": "fail", "error": "Simulated outage"}
+            ],
+            "default_response": "Nothing scripted."
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scripted_tool_call_turns() {
+        let provider = FakeProvider::new(script());
+        let adapter = OpenAiToolAdapter::new();
+
+        let first = provider
+            .complete_with_tools(
+                vec![json!({"role": "user", "content": "List my invoices"})],
+                vec![],
+                FAKE_MODEL,
+            )
+            .await
+            .unwrap();
+        let calls = adapter.parse_tool_calls(&first);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "MemoryTool");
+        assert_eq!(calls[0].arguments, json!({"operation": "list"}));
+        assert!(!adapter.is_finished(&first));
+
+        let second = provider
+            .complete_with_tools(
+                vec![json!({"role": "tool", "content": "[3 items]"})],
+                vec![],
+                FAKE_MODEL,
+            )
+            .await
+            .unwrap();
+        assert!(adapter.is_finished(&second));
+        assert_eq!(
+            adapter.extract_content(&second).as_deref(),
+            Some("I found 3 invoices.")
+        );
+        assert!(adapter.extract_usage(&second).0 > 0);
+
+        // Script exhausted for this message: default response
+        let third = provider
+            .complete("Anything else?", None, None, 0.7, 100)
+            .await;
+        assert_eq!(third.unwrap().content, "Nothing scripted.");
+
+        let error = provider.complete("Please fail", None, None, 0.7, 100).await;
+        assert!(matches!(error, Err(LLMError::RequestFailed(m)) if m == "Simulated outage"));
+    }
+
+    #[tokio::test]
+    async fn test_echo_and_streaming() {
+        let provider = FakeProvider::echo();
+        let response = provider
+            .complete("Hello", Some("system"), None, 0.7, 100)
+            .await
+            .unwrap();
+        assert_eq!(response.content, "Fake response to: Hello");
+        assert_eq!(response.model, FAKE_MODEL);
+
+        let mut rx = provider
+            .complete_stream("Hello", None, None, 0.7, 100)
+            .await
+            .unwrap();
+        let mut streamed = String::new();
+        while let Some(chunk) = rx.recv().await {
+            streamed.push_str(&chunk.unwrap());
+        }
+        assert_eq!(streamed, "Fake response to: Hello");
+    }
+}
@@ -0,0 +1,881 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Google Gemini provider implementation
+//!
+//! Calls the Gemini `generateContent` REST API directly. The rest of the
+//! application exchanges messages and tool definitions in the OpenAI format,
+//! so they are converted here to Gemini `contents`, `systemInstruction` and
+//! `functionDeclarations`. Tool responses are returned raw and parsed by
+//! `GeminiToolAdapter`.
+
+use super::model_discovery;
+use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType, SamplingOptions};
+use super::tool_adapter::helpers;
+use super::utils::{content_images, content_text, simulate_streaming};
+use crate::models::llm_models::ModelMetadata;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, info, instrument, warn};
+
+/// Available Gemini models
+pub const GEMINI_MODELS: &[&str] = &[
+    "gemini-2.5-flash",
+    "gemini-2.5-pro",
+    "gemini-2.5-flash-lite",
+];
+
+/// Default Gemini model
+pub const DEFAULT_GEMINI_MODEL: &str = "gemini-2.5-flash";
+
+/// Gemini API base URL
+const GEMINI_API_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Harm categories accepted in safety settings
+pub const GEMINI_SAFETY_CATEGORIES: &[&str] = &[
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+    "HARM_CATEGORY_CIVIC_INTEGRITY",
+];
+
+/// Block thresholds accepted in safety settings
+pub const GEMINI_SAFETY_THRESHOLDS: &[&str] = &[
+    "BLOCK_LOW_AND_ABOVE",
+    "BLOCK_MEDIUM_AND_ABOVE",
+    "BLOCK_ONLY_HIGH",
+    "BLOCK_NONE",
+    "OFF",
+];
+
+/// JSON Schema keywords rejected by the Gemini function declaration schema
+const UNSUPPORTED_SCHEMA_KEYS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$defs",
+    "definitions",
+    "additionalProperties",
+];
+
+/// Safety setting passed through to the Gemini API.
+///
+/// Categories without a setting use the API default threshold.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeminiSafetySetting {
+    /// Harm category (e.g., "HARM_CATEGORY_HARASSMENT")
+    pub category: String,
+    /// Block threshold (e.g., "BLOCK_ONLY_HIGH")
+    pub threshold: String,
+}
+
+impl GeminiSafetySetting {
+    /// Validates the category and threshold against the values the API accepts.
+    pub fn validate(&self) -> Result<(), String> {
+        if !GEMINI_SAFETY_CATEGORIES.contains(&self.category.as_str()) {
+            return Err(format!("Unknown harm category '{}'", self.category));
+        }
+        if !GEMINI_SAFETY_THRESHOLDS.contains(&self.threshold.as_str()) {
+            return Err(format!("Unknown block threshold '{}'", self.threshold));
+        }
+        Ok(())
+    }
+}
+
+/// Error body returned by the Gemini API
+#[derive(Debug, Deserialize)]
+struct GeminiErrorResponse {
+    error: GeminiErrorDetail,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiErrorDetail {
+    message: String,
+}
+
+/// Google Gemini provider implementation
+pub struct GeminiProvider {
+    /// API key
+    api_key: Arc<RwLock<Option<String>>>,
+    /// Safety settings sent with every request
+    safety_settings: Arc<RwLock<Vec<GeminiSafetySetting>>>,
+    /// Shared HTTP client (connection pooling)
+    http_client: Arc<reqwest::Client>,
+}
+
+impl GeminiProvider {
+    /// Creates a new unconfigured Gemini provider with a shared HTTP client.
+    pub fn new(http_client: Arc<reqwest::Client>) -> Self {
+        Self {
+            api_key: Arc::new(RwLock::new(None)),
+            safety_settings: Arc::new(RwLock::new(Vec::new())),
+            http_client,
+        }
+    }
+
+    /// Configures the provider with an API key
+    pub async fn configure(&self, api_key: &str) -> Result<(), LLMError> {
+        if api_key.is_empty() {
+            return Err(LLMError::MissingApiKey("Gemini".to_string()));
+        }
+        *self.api_key.write().await = Some(api_key.to_string());
+        info!("Gemini provider configured");
+        Ok(())
+    }
+
+    /// Clears the provider configuration
+    #[allow(dead_code)] // API completeness - provider lifecycle
+    pub async fn clear(&self) {
+        *self.api_key.write().await = None;
+        info!("Gemini provider cleared");
+    }
+
+    /// Gets the API key if configured
+    #[allow(dead_code)] // API completeness - provider inspection
+    pub async fn get_api_key(&self) -> Option<String> {
+        self.api_key.read().await.clone()
+    }
+
+    /// Gets the safety settings sent with every request
+    pub async fn get_safety_settings(&self) -> Vec<GeminiSafetySetting> {
+        self.safety_settings.read().await.clone()
+    }
+
+    /// Replaces the safety settings sent with every request
+    pub async fn set_safety_settings(&self, settings: Vec<GeminiSafetySetting>) {
+        *self.safety_settings.write().await = settings;
+    }
+
+    /// Sends a `generateContent` request and returns the raw JSON response.
+    ///
+    /// Function calls without an `id` get a generated one, so the adapter and
+    /// the conversation history refer to the same call.
+    async fn generate_content(&self, model: &str, body: Value) -> Result<Value, LLMError> {
+        let api_key = self
+            .api_key
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| LLMError::NotConfigured("Gemini".to_string()))?;
+
+        let url = format!("{}/models/{}:generateContent", GEMINI_API_URL, model);
+
+        let response = self
+            .http_client
+            .post(&url)
+            .header("x-goog-api-key", api_key)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| LLMError::RequestFailed(format!("HTTP request failed: {}", e)))?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| LLMError::RequestFailed(format!("Failed to read response body: {}", e)))?;
+
+        if !status.is_success() {
+            let error_msg = serde_json::from_str::<GeminiErrorResponse>(&text)
+                .map(|e| e.error.message)
+                .unwrap_or_else(|_| text.clone());
+            return Err(LLMError::RequestFailed(format!(
+                "Gemini API error ({}): {}",
+                status, error_msg
+            )));
+        }
+
+        let mut json_response: Value = serde_json::from_str(&text).map_err(|e| {
+            LLMError::RequestFailed(format!(
+                "Failed to parse Gemini response: {}. Body: {}",
+                e,
+                &text[..text.len().min(500)]
+            ))
+        })?;
+
+        if let Some(reason) = json_response
+            .pointer("/promptFeedback/blockReason")
+            .and_then(|v| v.as_str())
+        {
+            return Err(LLMError::RequestFailed(format!(
+                "Gemini blocked the prompt: {}",
+                reason
+            )));
+        }
+
+        assign_call_ids(&mut json_response);
+        Ok(json_response)
+    }
+
+    /// Builds a `generateContent` request body from OpenAI-style messages and tools.
+    async fn build_request(
+        &self,
+        messages: &[Value],
+        tools: &[Value],
+        tool_choice: Option<Value>,
+        temperature: f32,
+        max_tokens: usize,
+    ) -> Value {
+        let (system_instruction, contents) = convert_messages(messages);
+
+        let mut body = Map::new();
+        body.insert("contents".to_string(), Value::Array(contents));
+        if let Some(system) = system_instruction {
+            body.insert("systemInstruction".to_string(), system);
+        }
+        if !tools.is_empty() {
+            body.insert("tools".to_string(), convert_tools(tools));
+            if let Some(tool_config) = tool_choice {
+                body.insert("toolConfig".to_string(), tool_config);
+            }
+        }
+        let safety_settings = self.get_safety_settings().await;
+        if !safety_settings.is_empty() {
+            body.insert("safetySettings".to_string(), json!(safety_settings));
+        }
+        body.insert(
+            "generationConfig".to_string(),
+            json!({
+                "temperature": temperature,
+                "maxOutputTokens": max_tokens
+            }),
+        );
+        Value::Object(body)
+    }
+
+    /// Makes a `generateContent` call with function calling support.
+    ///
+    /// # Arguments
+    /// * `messages` - Conversation history as OpenAI-style JSON messages
+    /// * `tools` - Tool definitions in OpenAI format
+    /// * `tool_choice` - Gemini `toolConfig` value
+    /// * `model` - Model to use
+    /// * `temperature` - Sampling temperature
+    /// * `max_tokens` - Maximum tokens to generate
+    /// * `sampling` - Optional top_p and stop sequences
+    ///
+    /// # Returns
+    /// Raw JSON response from the API (caller should use adapter to parse)
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(
+        name = "gemini_complete_with_tools",
+        skip(self, messages, tools, tool_choice, sampling),
+        fields(provider = "gemini", model = %model, tools_count = tools.len())
+    )]
+    pub async fn complete_with_tools(
+        &self,
+        messages: Vec<Value>,
+        tools: Vec<Value>,
+        tool_choice: Option<Value>,
+        model: &str,
+        temperature: f32,
+        max_tokens: usize,
+        sampling: &SamplingOptions,
+    ) -> Result<Value, LLMError> {
+        let mut body = self
+            .build_request(&messages, &tools, tool_choice, temperature, max_tokens)
+            .await;
+        if let Some(top_p) = sampling.top_p {
+            body["generationConfig"]["topP"] = json!(top_p);
+        }
+        if !sampling.stop.is_empty() {
+            body["generationConfig"]["stopSequences"] = json!(sampling.stop);
+        }
+
+        debug!(
+            model = model,
+            temperature = temperature,
+            max_tokens = max_tokens,
+            "Making Gemini API request with tools"
+        );
+
+        let json_response = self.generate_content(model, body).await?;
+
+        let (tokens_input, tokens_output) = response_usage(&json_response);
+        info!(
+            tokens_input = tokens_input,
+            tokens_output = tokens_output,
+            "Gemini tool completion successful"
+        );
+
+        Ok(json_response)
+    }
+
+    /// Tests the connection by listing the models available to the API key.
+    pub async fn test_connection(&self) -> Result<bool, LLMError> {
+        let api_key = self
+            .api_key
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| LLMError::NotConfigured("Gemini".to_string()))?;
+
+        let response = self
+            .http_client
+            .get(format!("{}/models", GEMINI_API_URL))
+            .header("x-goog-api-key", api_key)
+            .send()
+            .await
+            .map_err(|e| LLMError::ConnectionError(format!("Connection failed: {}", e)))?;
+
+        Ok(response.status().is_success())
+    }
+
+    /// Lists the `generateContent` models of the API key with their metadata.
+    #[instrument(name = "gemini_list_model_metadata", skip(self))]
+    pub async fn list_model_metadata(&self) -> Result<Vec<ModelMetadata>, LLMError> {
+        let api_key = self
+            .api_key
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| LLMError::NotConfigured("Gemini".to_string()))?;
+        let request = self
+            .http_client
+            .get(format!("{}/models", GEMINI_API_URL))
+            .query(&[("pageSize", "1000")])
+            .header("x-goog-api-key", api_key);
+        let body = model_discovery::fetch_json(request, "Gemini").await?;
+        Ok(model_discovery::parse_gemini_models(&body))
+    }
+}
+
+impl Default for GeminiProvider {
+    /// Creates a default GeminiProvider with a new HTTP client.
+    ///
+    /// Note: For production use, prefer using `new()` with a shared HTTP client
+    /// from ProviderManager to benefit from connection pooling.
+    fn default() -> Self {
+        let http_client = Arc::new(
+            reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(300))
+                .build()
+                .expect("Failed to create HTTP client"),
+        );
+        Self::new(http_client)
+    }
+}
+
+#[async_trait]
+impl LLMProvider for GeminiProvider {
+    fn provider_type(&self) -> ProviderType {
+        ProviderType::Gemini
+    }
+
+    fn available_models(&self) -> Vec<String> {
+        GEMINI_MODELS.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn default_model(&self) -> String {
+        DEFAULT_GEMINI_MODEL.to_string()
+    }
+
+    fn is_configured(&self) -> bool {
+        self.api_key
+            .try_read()
+            .map(|guard| guard.is_some())
+            .unwrap_or(false)
+    }
+
+    #[instrument(
+        name = "gemini_complete",
+        skip(self, prompt, system_prompt),
+        fields(
+            provider = "gemini",
+            model = %model.unwrap_or(DEFAULT_GEMINI_MODEL),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn complete(
+        &self,
+        prompt: &str,
+        system_prompt: Option<&str>,
+        model: Option<&str>,
+        temperature: f32,
+        max_tokens: usize,
+    ) -> Result<LLMResponse, LLMError> {
+        let model = model.unwrap_or(DEFAULT_GEMINI_MODEL);
+
+        let mut messages = Vec::new();
+        if let Some(system) = system_prompt {
+            messages.push(json!({"role": "system", "content": system}));
+        }
+        messages.push(json!({"role": "user", "content": prompt}));
+
+        let body = self
+            .build_request(&messages, &[], None, temperature, max_tokens)
+            .await;
+        let json_response = self.generate_content(model, body).await?;
+
+        let finish_reason = json_response
+            .pointer("/candidates/0/finishReason")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let content = response_text(&json_response).unwrap_or_default();
+        if content.is_empty() && finish_reason.as_deref() == Some("SAFETY") {
+            warn!("Gemini response blocked by safety settings");
+        }
+
+        let (tokens_input, tokens_output) = response_usage(&json_response);
+        info!(
+            tokens_input = tokens_input,
+            tokens_output = tokens_output,
+            response_len = content.len(),
+            "Gemini completion successful"
+        );
+
+        Ok(LLMResponse {
+            content,
+            tokens_input,
+            tokens_output,
+            model: model.to_string(),
+            provider: ProviderType::Gemini,
+            finish_reason,
+        })
+    }
+
+    async fn complete_stream(
+        &self,
+        prompt: &str,
+        system_prompt: Option<&str>,
+        model: Option<&str>,
+        temperature: f32,
+        max_tokens: usize,
+    ) -> Result<mpsc::Receiver<Result<String, LLMError>>, LLMError> {
+        let response = self
+            .complete(prompt, system_prompt, model, temperature, max_tokens)
+            .await?;
+        Ok(simulate_streaming(response.content, None, None))
+    }
+}
+
+// ============================================================================
+// Format conversion
+// ============================================================================
+
+/// Returns the parts of the first candidate.
+pub(crate) fn candidate_parts(response: &Value) -> &[Value] {
+    response
+        .pointer("/candidates/0/content/parts")
+        .and_then(|v| v.as_array())
+        .map(|parts| parts.as_slice())
+        .unwrap_or(&[])
+}
+
+/// Concatenates the text parts of the first candidate, skipping thoughts.
+///
+/// Returns None when the candidate has no text part.
+pub(crate) fn response_text(response: &Value) -> Option<String> {
+    let texts: Vec<&str> = candidate_parts(response)
+        .iter()
+        .filter(|part| part.get("thought").and_then(|v| v.as_bool()) != Some(true))
+        .filter_map(|part| part.get("text").and_then(|v| v.as_str()))
+        .collect();
+    if texts.is_empty() {
+        None
+    } else {
+        Some(texts.concat())
+    }
+}
+
+/// Concatenates the thought summaries of the first candidate.
+///
+/// Returns None when the model returned no thought part.
+pub(crate) fn response_thoughts(response: &Value) -> Option<String> {
+    let thoughts: Vec<&str> = candidate_parts(response)
+        .iter()
+        .filter(|part| part.get("thought").and_then(|v| v.as_bool()) == Some(true))
+        .filter_map(|part| part.get("text").and_then(|v| v.as_str()))
+        .collect();
+    if thoughts.is_empty() {
+        None
+    } else {
+        Some(thoughts.concat())
+    }
+}
+
+/// Extracts (input, output) token counts from `usageMetadata`.
+///
+/// Thinking tokens are billed as output and counted as such.
+pub(crate) fn response_usage(response: &Value) -> (usize, usize) {
+    let count = |field: &str| {
+        response
+            .pointer(&format!("/usageMetadata/{}", field))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as usize
+    };
+    (
+        count("promptTokenCount"),
+        count("candidatesTokenCount") + count("thoughtsTokenCount"),
+    )
+}
+
+/// Gives every function call of the first candidate an `id`.
+fn assign_call_ids(response: &mut Value) {
+    let Some(parts) = response
+        .pointer_mut("/candidates/0/content/parts")
+        .and_then(|v| v.as_array_mut())
+    else {
+        return;
+    };
+    for call in parts
+        .iter_mut()
+        .filter_map(|part| part.get_mut("functionCall"))
+        .filter_map(|call| call.as_object_mut())
+    {
+        if !call.get("id").is_some_and(|id| id.is_string()) {
+            call.insert("id".to_string(), json!(helpers::generate_call_id("gemini")));
+        }
+    }
+}
+
+/// Converts OpenAI-style messages to Gemini `systemInstruction` and `contents`.
+///
+/// - `system` messages are joined into the system instruction
+/// - `assistant` messages become `model` turns, `tool_calls` become `functionCall` parts
+/// - `tool` messages become `functionResponse` parts of a `user` turn
+/// - images of `user` messages (data URLs) become `inlineData` parts
+///
+/// Consecutive messages of the same role are merged, as Gemini expects
+/// alternating turns and all responses of parallel calls in one turn.
+fn convert_messages(messages: &[Value]) -> (Option<Value>, Vec<Value>) {
+    let mut system_texts: Vec<String> = Vec::new();
+    let mut contents: Vec<Value> = Vec::new();
+
+    for message in messages {
+        let role = message
+            .get("role")
+            .and_then(|v| v.as_str())
+            .unwrap_or("user");
+        let content = message.get("content").cloned().unwrap_or_default();
+        let text = content_text(&content);
+        let text = text.as_str();
+
+        let (gemini_role, parts) =
+            match role {
+                "system" => {
+                    if !text.is_empty() {
+                        system_texts.push(text.to_string());
+                    }
+                    continue;
+                }
+                "assistant" => {
+                    let mut parts = Vec::new();
+                    if !text.is_empty() {
+                        parts.push(json!({"text": text}));
+                    }
+                    let tool_calls = message
+                        .get("tool_calls")
+                        .and_then(|v| v.as_array())
+                        .map(|calls| calls.as_slice())
+                        .unwrap_or(&[]);
+                    for call in tool_calls {
+                        let name = call
+                            .pointer("/function/name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default();
+                        let args = match call.pointer("/function/arguments") {
+                            Some(Value::String(s)) => helpers::parse_arguments_string(s),
+                            Some(args @ Value::Object(_)) => args.clone(),
+                            _ => json!({}),
+                        };
+                        parts.push(json!({"functionCall": {"name": name, "args": args}}));
+                    }
+                    ("model", parts)
+                }
+                "tool" => {
+                    let name = message
+                        .get("name")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default();
+                    // functionResponse.response must be an object
+                    let response = match serde_json::from_str::<Value>(text) {
+                        Ok(obj @ Value::Object(_)) => obj,
+                        Ok(other) => json!({"content": other}),
+                        Err(_) => json!({"content": text}),
+                    };
+                    (
+                        "user",
+                        vec![json!({"functionResponse": {"name": name, "response": response}})],
+                    )
+                }
+                _ => {
+                    let mut parts = vec![json!({"text": text})];
+                    parts.extend(content_images(&content).into_iter().map(
+                        |(mime, data)| json!({"inlineData": {"mimeType": mime, "data": data}}),
+                    ));
+                    ("user", parts)
+                }
+            };
+
+        if parts.is_empty() {
+            continue;
+        }
+
+        let same_role = contents
+            .last()
+            .and_then(|last| last.get("role"))
+            .and_then(|v| v.as_str())
+            == Some(gemini_role);
+        match contents.last_mut().and_then(|last| last.get_mut("parts")) {
+            Some(Value::Array(last_parts)) if same_role => last_parts.extend(parts),
+            _ => contents.push(json!({"role": gemini_role, "parts": parts})),
+        }
+    }
+
+    let system_instruction = if system_texts.is_empty() {
+        None
+    } else {
+        Some(json!({"parts": [{"text": system_texts.join("\n\n")}]}))
+    };
+
+    (system_instruction, contents)
+}
+
+/// Converts OpenAI-format tool definitions to a Gemini `tools` value.
+fn convert_tools(tools: &[Value]) -> Value {
+    let declarations: Vec<Value> = tools
+        .iter()
+        .filter_map(|tool| {
+            let function = tool.get("function")?;
+            let mut declaration = Map::new();
+            declaration.insert("name".to_string(), function.get("name")?.clone());
+            if let Some(description) = function.get("description") {
+                declaration.insert("description".to_string(), description.clone());
+            }
+            // Gemini rejects object schemas without properties
+            let has_properties = function
+                .pointer("/parameters/properties")
+                .and_then(|v| v.as_object())
+                .is_some_and(|props| !props.is_empty());
+            if has_properties {
+                declaration.insert(
+                    "parameters".to_string(),
+                    sanitize_schema(&function["parameters"]),
+                );
+            }
+            Some(Value::Object(declaration))
+        })
+        .collect();
+
+    json!([{ "functionDeclarations": declarations }])
+}
+
+/// Removes the JSON Schema keywords the Gemini schema subset rejects.
+fn sanitize_schema(schema: &Value) -> Value {
+    match schema {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !UNSUPPORTED_SCHEMA_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| {
+                    let value = match value {
+                        // Property names are not keywords: only sanitize their schemas
+                        Value::Object(props) if key == "properties" => Value::Object(
+                            props
+                                .iter()
+                                .map(|(name, prop)| (name.clone(), sanitize_schema(prop)))
+                                .collect(),
+                        ),
+                        other => sanitize_schema(other),
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(sanitize_schema).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gemini_provider_new() {
+        let provider = GeminiProvider::default();
+        assert_eq!(provider.provider_type(), ProviderType::Gemini);
+        assert_eq!(provider.default_model(), DEFAULT_GEMINI_MODEL);
+        assert!(provider
+            .available_models()
+            .contains(&"gemini-2.5-pro".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_gemini_provider_configure() {
+        let provider = GeminiProvider::default();
+        assert!(!provider.is_configured());
+        assert!(provider.configure("").await.is_err());
+
+        provider.configure("test-api-key").await.unwrap();
+        assert!(provider.is_configured());
+        assert_eq!(
+            provider.get_api_key().await,
+            Some("test-api-key".to_string())
+        );
+
+        provider.clear().await;
+        assert!(!provider.is_configured());
+    }
+
+    #[test]
+    fn test_safety_setting_validate() {
+        let setting = GeminiSafetySetting {
+            category: "HARM_CATEGORY_HARASSMENT".to_string(),
+            threshold: "BLOCK_ONLY_HIGH".to_string(),
+        };
+        assert!(setting.validate().is_ok());
+
+        let setting = GeminiSafetySetting {
+            threshold: "BLOCK_EVERYTHING".to_string(),
+            ..setting
+        };
+        assert!(setting.validate().is_err());
+    }
+
+    #[test]
+    fn test_convert_messages() {
+        let messages = vec![
+            json!({"role": "system", "content": "Be brief."}),
+            json!({"role": "user", "content": "Weather in Paris and Rome?"}),
+            json!({
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [
+                    {"id": "c1", "type": "function", "function": {"name": "Weather", "arguments": "{\"city\":\"Paris\"}"}},
+                    {"id": "c2", "type": "function", "function": {"name": "Weather", "arguments": "{\"city\":\"Rome\"}"}}
+                ]
+            }),
+            json!({"role": "tool", "tool_call_id": "c1", "name": "Weather", "content": "{\"temp\":20}"}),
+            json!({"role": "tool", "tool_call_id": "c2", "name": "Weather", "content": "sunny"}),
+        ];
+
+        let (system, contents) = convert_messages(&messages);
+
+        assert_eq!(system.unwrap()["parts"][0]["text"], "Be brief.");
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0]["role"], "user");
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(
+            contents[1]["parts"][0]["functionCall"]["args"]["city"],
+            "Paris"
+        );
+        assert_eq!(contents[1]["parts"].as_array().unwrap().len(), 2);
+        // Both tool responses are merged in one user turn
+        assert_eq!(contents[2]["role"], "user");
+        let responses = contents[2]["parts"].as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["functionResponse"]["response"]["temp"], 20);
+        assert_eq!(
+            responses[1]["functionResponse"]["response"]["content"],
+            "sunny"
+        );
+    }
+
+    #[test]
+    fn test_convert_image_message() {
+        let messages = vec![json!({"role": "user", "content": [
+            {"type": "text", "text": "What is this?"},
+            {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw0KGgo="}}
+        ]})];
+
+        let (_, contents) = convert_messages(&messages);
+        let parts = contents[0]["parts"].as_array().unwrap();
+        assert_eq!(parts[0]["text"], "What is this?");
+        assert_eq!(parts[1]["inlineData"]["mimeType"], "image/png");
+        assert_eq!(parts[1]["inlineData"]["data"], "iVBORw0KGgo=");
+    }
+
+    #[test]
+    fn test_convert_tools() {
+        let tools = vec![
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "MemoryTool",
+                    "description": "Store memory",
+                    "parameters": {
+                        "$schema": "http://json-schema.org/draft-07/schema#",
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "additionalProperties": {"type": "string", "additionalProperties": false}
+                        }
+                    }
+                }
+            }),
+            json!({
+                "type": "function",
+                "function": {
+                    "name": "ListTool",
+                    "description": "List",
+                    "parameters": {"type": "object", "properties": {}}
+                }
+            }),
+        ];
+
+        let converted = convert_tools(&tools);
+        let declarations = converted[0]["functionDeclarations"].as_array().unwrap();
+        assert_eq!(declarations.len(), 2);
+
+        let params = &declarations[0]["parameters"];
+        assert!(params.get("$schema").is_none());
+        assert!(params.get("additionalProperties").is_none());
+        // A property named like a keyword is kept, its schema is sanitized
+        assert_eq!(
+            params["properties"]["additionalProperties"]["type"],
+            "string"
+        );
+        assert!(params["properties"]["additionalProperties"]
+            .get("additionalProperties")
+            .is_none());
+
+        assert!(declarations[1].get("parameters").is_none());
+    }
+
+    #[test]
+    fn test_response_parsing() {
+        let mut response = json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"text": "thinking...", "thought": true},
+                        {"text": "Hello"},
+                        {"functionCall": {"name": "Weather", "args": {"city": "Paris"}}},
+                        {"functionCall": {"id": "given", "name": "Weather", "args": {}}}
+                    ]
+                },
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 12, "candidatesTokenCount": 5, "thoughtsTokenCount": 3}
+        });
+
+        assign_call_ids(&mut response);
+
+        assert_eq!(response_text(&response), Some("Hello".to_string()));
+        assert_eq!(response_usage(&response), (12, 8));
+        let parts = candidate_parts(&response);
+        assert!(parts[2]["functionCall"]["id"]
+            .as_str()
+            .unwrap()
+            .starts_with("gemini_"));
+        assert_eq!(parts[3]["functionCall"]["id"], "given");
+        assert_eq!(response_text(&json!({})), None);
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local model runtime status.
+//!
+//! Parses the Ollama `/api/ps` and `/api/tags` responses and reads the host
+//! memory. The host memory is read from `/proc/meminfo` on Linux and is
+//! unknown on other platforms.
+
+use crate::models::local_llm::{LoadedModel, SystemMemory};
+use serde_json::Value;
+
+/// Parses the models loaded by Ollama (`/api/ps`).
+pub fn parse_ollama_ps(body: &Value) -> Vec<LoadedModel> {
+    body.get("models")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            Some(LoadedModel {
+                name: model.get("name")?.as_str()?.to_string(),
+                size_bytes: model.get("size").and_then(Value::as_u64).unwrap_or(0),
+                vram_bytes: model.get("size_vram").and_then(Value::as_u64).unwrap_or(0),
+                expires_at: model
+                    .get("expires_at")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+            })
+        })
+        .collect()
+}
+
+/// Reads the size of an installed model from the `/api/tags` response.
+///
+/// A name without tag matches the `latest` tag, as in Ollama.
+pub fn parse_ollama_model_size(body: &Value, model: &str) -> Option<u64> {
+    let wanted = if model.contains(':') {
+        model.to_string()
+    } else {
+        format!("{}:latest", model)
+    };
+    body.get("models")
+        .and_then(Value::as_array)?
+        .iter()
+        .find(|m| m.get("name").and_then(Value::as_str) == Some(wanted.as_str()))
+        .and_then(|m| m.get("size"))
+        .and_then(Value::as_u64)
+}
+
+/// Parses the `MemTotal` and `MemAvailable` entries of `/proc/meminfo`.
+pub fn parse_meminfo(content: &str) -> Option<SystemMemory> {
+    let entry = |key: &str| {
+        content.lines().find_map(|line| {
+            let value = line.strip_prefix(key)?.strip_prefix(':')?;
+            let kilobytes: u64 = value.trim().trim_end_matches("kB").trim().parse().ok()?;
+            Some(kilobytes * 1024)
+        })
+    };
+    Some(SystemMemory {
+        total_bytes: entry("MemTotal")?,
+        available_bytes: entry("MemAvailable")?,
+    })
+}
+
+/// Reads the host memory, None when unavailable on this platform.
+pub async fn read_system_memory() -> Option<SystemMemory> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let content = tokio::fs::read_to_string("/proc/meminfo").await.ok()?;
+    parse_meminfo(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_ollama_ps() {
+        let body = json!({"models": [{
+            "name": "qwen2.5:7b", "model": "qwen2.5:7b",
+            "size": 5_500_000_000u64, "size_vram": 4_000_000_000u64,
+            "expires_at": "2025-06-01T12:05:00Z"
+        }]});
+        let models = parse_ollama_ps(&body);
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].name, "qwen2.5:7b");
+        assert_eq!(models[0].vram_bytes, 4_000_000_000);
+        assert!(parse_ollama_ps(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_parse_ollama_model_size() {
+        let body = json!({"models": [
+            {"name": "llama3.2:latest", "size": 2_019_393_189u64},
+            {"name": "qwen2.5:7b", "size": 4_683_087_332u64}
+        ]});
+        assert_eq!(
+            parse_ollama_model_size(&body, "llama3.2"),
+            Some(2_019_393_189)
+        );
+        assert_eq!(
+            parse_ollama_model_size(&body, "qwen2.5:7b"),
+            Some(4_683_087_332)
+        );
+        assert_eq!(parse_ollama_model_size(&body, "qwen2.5"), None);
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let content = "MemTotal:       16318480 kB\nMemFree:         1024000 kB\nMemAvailable:    8159240 kB\n";
+        let memory = parse_meminfo(content).unwrap();
+        assert_eq!(memory.total_bytes, 16_318_480 * 1024);
+        assert_eq!(memory.available_bytes, 8_159_240 * 1024);
+        assert!(parse_meminfo("MemTotal: 1024 kB\n").is_none());
+    }
+}
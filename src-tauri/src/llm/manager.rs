@@ -15,11 +15,15 @@
 //! LLM Provider Manager - orchestrates multiple providers
 
 use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use super::fake::{FakeProvider, FAKE_PROVIDER_NAME};
+use super::gemini::{GeminiProvider, GeminiSafetySetting};
 use super::mistral::MistralProvider;
 use super::ollama::OllamaProvider;
 use super::openai_compatible::OpenAiCompatibleProvider;
-use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType};
-use super::retry::{with_retry, RetryConfig};
+use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType, SamplingOptions};
+use super::retry::{with_retry, with_retry_observed, RetryConfig, RetryObserver};
+use super::utility::{UtilityModelConfig, UtilityTarget};
+use crate::models::llm_models::ModelMetadata;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -35,6 +39,8 @@ pub struct ProviderConfig {
     pub mistral_model: String,
     /// Default model for Ollama
     pub ollama_model: String,
+    /// Default model for Gemini
+    pub gemini_model: String,
     /// Ollama server URL
     pub ollama_url: String,
 }
@@ -45,6 +51,7 @@ impl Default for ProviderConfig {
             active_provider: ProviderType::Ollama, // Default to local
             mistral_model: super::mistral::DEFAULT_MISTRAL_MODEL.to_string(),
             ollama_model: super::ollama::DEFAULT_OLLAMA_MODEL.to_string(),
+            gemini_model: super::gemini::DEFAULT_GEMINI_MODEL.to_string(),
             ollama_url: super::ollama::DEFAULT_OLLAMA_URL.to_string(),
         }
     }
@@ -74,23 +81,30 @@ pub struct ProviderManager {
     mistral: Arc<MistralProvider>,
     /// Ollama provider instance
     ollama: Arc<OllamaProvider>,
+    /// Gemini provider instance
+    gemini: Arc<GeminiProvider>,
     /// Custom OpenAI-compatible providers (keyed by provider name)
     custom_providers: Arc<RwLock<HashMap<String, Arc<OpenAiCompatibleProvider>>>>,
+    /// Scripted fake provider (`ZILEO_FAKE_LLM`), selected as custom provider "fake"
+    fake: Arc<RwLock<Option<Arc<FakeProvider>>>>,
     /// Configuration state
     config: Arc<RwLock<ProviderConfig>>,
-    /// Shared HTTP client for all providers (connection pooling)
+    /// Shared HTTP client of the other requests (moderation, speech, settings sync);
+    /// each provider has its own pooled client, using its proxy
     #[allow(dead_code)] // Stored for http_client() accessor
     http_client: Arc<reqwest::Client>,
-    /// Retry configuration for API calls (OPT-LLM-4)
-    retry_config: RetryConfig,
+    /// Retry configuration for API calls (OPT-LLM-4), updated from `settings:llm_retry`
+    retry_config: Arc<RwLock<RetryConfig>>,
     /// Circuit breakers for each provider (OPT-LLM-6)
     circuit_breakers: Arc<RwLock<HashMap<ProviderType, CircuitBreaker>>>,
+    /// Model of internal summarization passes, updated from `settings:utility_model`
+    utility_model: Arc<RwLock<Option<UtilityModelConfig>>>,
 }
 
 impl ProviderManager {
     /// Creates a new provider manager with default configuration.
     ///
-    /// Initializes a shared HTTP client with connection pooling for all providers.
+    /// Initializes an HTTP client with connection pooling for each provider.
     /// This improves performance by reusing connections and avoiding TLS handshake
     /// overhead on subsequent requests (OPT-LLM-2).
     ///
@@ -122,15 +136,25 @@ impl ProviderManager {
                 "Ollama".to_string(),
             ),
         );
+        circuit_breakers.insert(
+            ProviderType::Gemini,
+            CircuitBreaker::new(
+                CircuitBreakerConfig::for_llm_provider(),
+                "Gemini".to_string(),
+            ),
+        );
 
         Self {
-            mistral: Arc::new(MistralProvider::new(http_client.clone())),
-            ollama: Arc::new(OllamaProvider::new(http_client.clone())),
+            mistral: Arc::new(MistralProvider::new(Self::provider_http_client("mistral"))),
+            ollama: Arc::new(OllamaProvider::new(Self::provider_http_client("ollama"))),
+            gemini: Arc::new(GeminiProvider::new(Self::provider_http_client("gemini"))),
             custom_providers: Arc::new(RwLock::new(HashMap::new())),
+            fake: Arc::new(RwLock::new(FakeProvider::from_env().map(Arc::new))),
             config: Arc::new(RwLock::new(ProviderConfig::default())),
             http_client,
-            retry_config: RetryConfig::default(),
+            retry_config: Arc::new(RwLock::new(RetryConfig::default())),
             circuit_breakers: Arc::new(RwLock::new(circuit_breakers)),
+            utility_model: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -161,18 +185,42 @@ impl ProviderManager {
                 "Ollama".to_string(),
             ),
         );
+        circuit_breakers.insert(
+            ProviderType::Gemini,
+            CircuitBreaker::new(
+                CircuitBreakerConfig::for_llm_provider(),
+                "Gemini".to_string(),
+            ),
+        );
 
         Self {
-            mistral: Arc::new(MistralProvider::new(http_client.clone())),
-            ollama: Arc::new(OllamaProvider::new(http_client.clone())),
+            mistral: Arc::new(MistralProvider::new(Self::provider_http_client("mistral"))),
+            ollama: Arc::new(OllamaProvider::new(Self::provider_http_client("ollama"))),
+            gemini: Arc::new(GeminiProvider::new(Self::provider_http_client("gemini"))),
             custom_providers: Arc::new(RwLock::new(HashMap::new())),
+            fake: Arc::new(RwLock::new(FakeProvider::from_env().map(Arc::new))),
             config: Arc::new(RwLock::new(ProviderConfig::default())),
             http_client,
-            retry_config,
+            retry_config: Arc::new(RwLock::new(retry_config)),
             circuit_breakers: Arc::new(RwLock::new(circuit_breakers)),
+            utility_model: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Creates the HTTP client of a provider, using its proxy (see [`crate::proxy`]).
+    ///
+    /// # Arguments
+    /// * `provider` - Provider name ("mistral", "ollama", "gemini" or a custom provider name)
+    pub fn provider_http_client(provider: &str) -> Arc<reqwest::Client> {
+        Arc::new(
+            crate::proxy::client_builder(provider)
+                .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+                .pool_max_idle_per_host(HTTP_POOL_MAX_IDLE_PER_HOST)
+                .build()
+                .expect("Failed to create HTTP client"),
+        )
+    }
+
     /// Returns a reference to the shared HTTP client.
     ///
     /// This can be used by external code that needs to make HTTP requests
@@ -182,6 +230,26 @@ impl ProviderManager {
         &self.http_client
     }
 
+    /// Returns the retry policy applied to completion requests.
+    pub async fn get_retry_config(&self) -> RetryConfig {
+        self.retry_config.read().await.clone()
+    }
+
+    /// Replaces the retry policy applied to completion requests.
+    pub async fn set_retry_config(&self, retry_config: RetryConfig) {
+        *self.retry_config.write().await = retry_config;
+    }
+
+    /// Returns the model used by internal summarization passes (None = agent model).
+    pub async fn get_utility_model(&self) -> Option<UtilityModelConfig> {
+        self.utility_model.read().await.clone()
+    }
+
+    /// Replaces the model used by internal summarization passes.
+    pub async fn set_utility_model(&self, utility_model: Option<UtilityModelConfig>) {
+        *self.utility_model.write().await = utility_model;
+    }
+
     /// Checks if the circuit breaker allows requests to the given provider (OPT-LLM-6).
     ///
     /// Returns Ok(()) if the circuit is available, or CircuitOpen error if not.
@@ -264,6 +332,12 @@ impl ProviderManager {
         info!(provider = name, "Custom provider unregistered from manager");
     }
 
+    /// Unregisters all custom providers (profile switch).
+    pub async fn clear_custom_providers(&self) {
+        self.custom_providers.write().await.clear();
+        info!("Custom providers unregistered from manager");
+    }
+
     /// Gets a custom provider by name.
     pub async fn get_custom_provider(&self, name: &str) -> Option<Arc<OpenAiCompatibleProvider>> {
         self.custom_providers.read().await.get(name).cloned()
@@ -275,6 +349,33 @@ impl ProviderManager {
         self.custom_providers.read().await.contains_key(name)
     }
 
+    /// Enables (Some) or disables (None) the fake provider.
+    #[allow(dead_code)] // Used by tests; the app enables it via ZILEO_FAKE_LLM
+    pub async fn set_fake_provider(&self, provider: Option<FakeProvider>) {
+        *self.fake.write().await = provider.map(Arc::new);
+        info!(
+            enabled = self.fake.read().await.is_some(),
+            "Fake provider updated"
+        );
+    }
+
+    /// Checks if the fake provider is enabled.
+    pub fn is_fake_provider_enabled(&self) -> bool {
+        self.fake
+            .try_read()
+            .map(|guard| guard.is_some())
+            .unwrap_or(false)
+    }
+
+    /// Gets the fake provider, or NotConfigured when it is disabled.
+    async fn fake_provider(&self) -> Result<Arc<FakeProvider>, LLMError> {
+        self.fake
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| LLMError::NotConfigured(FAKE_PROVIDER_NAME.to_string()))
+    }
+
     /// Gets the current configuration
     pub async fn get_config(&self) -> ProviderConfig {
         self.config.read().await.clone()
@@ -286,6 +387,10 @@ impl ProviderManager {
         let is_configured = match &provider {
             ProviderType::Mistral => self.mistral.is_configured(),
             ProviderType::Ollama => self.ollama.is_configured(),
+            ProviderType::Gemini => self.gemini.is_configured(),
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                self.is_fake_provider_enabled()
+            }
             ProviderType::Custom(ref name) => self
                 .custom_providers
                 .read()
@@ -317,6 +422,18 @@ impl ProviderManager {
         Ok(())
     }
 
+    /// Configures the Gemini provider with an API key
+    pub async fn configure_gemini(&self, api_key: &str) -> Result<(), LLMError> {
+        self.gemini.configure(api_key).await?;
+        info!("Gemini provider configured via manager");
+        Ok(())
+    }
+
+    /// Replaces the safety settings sent with every Gemini request
+    pub async fn set_gemini_safety_settings(&self, settings: Vec<GeminiSafetySetting>) {
+        self.gemini.set_safety_settings(settings).await;
+    }
+
     /// Configures the Ollama provider
     pub async fn configure_ollama(&self, url: Option<&str>) -> Result<(), LLMError> {
         let url_to_use = match url {
@@ -340,6 +457,7 @@ impl ProviderManager {
         match provider {
             ProviderType::Mistral => config.mistral_model = model.to_string(),
             ProviderType::Ollama => config.ollama_model = model.to_string(),
+            ProviderType::Gemini => config.gemini_model = model.to_string(),
             ProviderType::Custom(_) => {
                 // Custom providers don't have a config-level default model;
                 // their default model is managed via provider_settings in the DB
@@ -355,6 +473,7 @@ impl ProviderManager {
         match provider {
             ProviderType::Mistral => config.mistral_model.clone(),
             ProviderType::Ollama => config.ollama_model.clone(),
+            ProviderType::Gemini => config.gemini_model.clone(),
             ProviderType::Custom(_) => String::new(),
         }
     }
@@ -364,6 +483,10 @@ impl ProviderManager {
         match provider {
             ProviderType::Mistral => self.mistral.available_models(),
             ProviderType::Ollama => self.ollama.available_models(),
+            ProviderType::Gemini => self.gemini.available_models(),
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                vec![super::fake::FAKE_MODEL.to_string()]
+            }
             ProviderType::Custom(_) => Vec::new(), // Custom providers list models from DB
         }
     }
@@ -373,6 +496,10 @@ impl ProviderManager {
         match provider {
             ProviderType::Mistral => self.mistral.is_configured(),
             ProviderType::Ollama => self.ollama.is_configured(),
+            ProviderType::Gemini => self.gemini.is_configured(),
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                self.is_fake_provider_enabled()
+            }
             ProviderType::Custom(ref name) => self
                 .custom_providers
                 .try_read()
@@ -382,7 +509,6 @@ impl ProviderManager {
     }
 
     /// Gets all configured providers
-    #[allow(dead_code)] // API completeness - provider inspection
     pub fn get_configured_providers(&self) -> Vec<ProviderType> {
         let mut providers = Vec::new();
         if self.mistral.is_configured() {
@@ -391,6 +517,9 @@ impl ProviderManager {
         if self.ollama.is_configured() {
             providers.push(ProviderType::Ollama);
         }
+        if self.gemini.is_configured() {
+            providers.push(ProviderType::Gemini);
+        }
         // Custom providers added at runtime
         if let Ok(guard) = self.custom_providers.try_read() {
             for (name, p) in guard.iter() {
@@ -399,6 +528,9 @@ impl ProviderManager {
                 }
             }
         }
+        if self.is_fake_provider_enabled() {
+            providers.push(ProviderType::Custom(FAKE_PROVIDER_NAME.to_string()));
+        }
         providers
     }
 
@@ -432,6 +564,7 @@ impl ProviderManager {
                 .unwrap_or_else(|| match &provider {
                     ProviderType::Mistral => config.mistral_model.clone(),
                     ProviderType::Ollama => config.ollama_model.clone(),
+                    ProviderType::Gemini => config.gemini_model.clone(),
                     ProviderType::Custom(_) => String::new(),
                 });
             (provider, model_str)
@@ -439,6 +572,8 @@ impl ProviderManager {
 
         // Check circuit breaker before making request (OPT-LLM-6)
         self.check_circuit_breaker(provider_type.clone()).await?;
+        let started = std::time::Instant::now();
+        let retry_config = self.get_retry_config().await;
 
         debug!(
             ?provider_type,
@@ -467,7 +602,7 @@ impl ProviderManager {
                                 .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
                 )
                 .await
             }
@@ -485,10 +620,41 @@ impl ProviderManager {
                                 .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
                 )
                 .await
             }
+            ProviderType::Gemini => {
+                let gemini = self.gemini.clone();
+                with_retry(
+                    || {
+                        let p = prompt_owned.clone();
+                        let sp = system_prompt_owned.clone();
+                        let m = model_owned.clone();
+                        let provider = gemini.clone();
+                        async move {
+                            provider
+                                .complete(&p, sp.as_deref(), Some(&m), temperature, max_tokens)
+                                .await
+                        }
+                    },
+                    &retry_config,
+                )
+                .await
+            }
+            // The fake provider is deterministic: no retry
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                self.fake_provider()
+                    .await?
+                    .complete(
+                        prompt,
+                        system_prompt,
+                        Some(&model_to_use),
+                        temperature,
+                        max_tokens,
+                    )
+                    .await
+            }
             ProviderType::Custom(ref name) => {
                 let custom = self
                     .custom_providers
@@ -509,12 +675,21 @@ impl ProviderManager {
                                 .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
                 )
                 .await
             }
         };
 
+        crate::metrics::observe_llm_request(&provider_type, started.elapsed(), result.is_ok());
+        if let Ok(response) = &result {
+            crate::metrics::record_llm_tokens(
+                &provider_type,
+                response.tokens_input,
+                response.tokens_output,
+            );
+        }
+
         // Record result for circuit breaker (OPT-LLM-6)
         match &result {
             Ok(_) => self.record_circuit_success(provider_type).await,
@@ -524,10 +699,64 @@ impl ProviderManager {
         result
     }
 
+    /// Completes an internal summarization or extraction prompt.
+    ///
+    /// Uses the configured utility model, or the fallback provider and model
+    /// (typically the calling agent's) when none is set or its provider is not
+    /// configured.
+    #[allow(dead_code)] // Entry point of summarization passes (compression, titles...)
+    #[instrument(
+        name = "manager_complete_utility",
+        skip(self, prompt, system_prompt, on_retry),
+        fields(prompt_len = prompt.len())
+    )]
+    pub async fn complete_utility(
+        &self,
+        prompt: &str,
+        system_prompt: Option<&str>,
+        fallback_provider: ProviderType,
+        fallback_model: &str,
+        on_retry: Option<&RetryObserver<'_>>,
+    ) -> Result<LLMResponse, LLMError> {
+        let config = self.get_utility_model().await;
+        let target = UtilityTarget::resolve(
+            config.as_ref(),
+            |provider| self.is_provider_configured(provider.clone()),
+            fallback_provider,
+            fallback_model,
+        );
+        if let (Some(config), false) = (&config, target.is_utility) {
+            warn!(
+                provider = %config.provider,
+                "Utility model provider unavailable, falling back to agent model"
+            );
+        }
+
+        debug!(
+            provider = ?target.provider,
+            model = %target.model,
+            is_utility = target.is_utility,
+            "Executing utility completion"
+        );
+
+        self.complete_with_provider(
+            target.provider,
+            prompt,
+            system_prompt,
+            Some(&target.model),
+            target.temperature,
+            target.max_tokens,
+            on_retry,
+        )
+        .await
+    }
+
     /// Completes a prompt using a specific provider with automatic retry.
     ///
     /// This method wraps the provider completion with retry logic (OPT-LLM-4)
-    /// and circuit breaker protection (OPT-LLM-6).
+    /// and circuit breaker protection (OPT-LLM-6). `on_retry` is notified
+    /// before each retry.
+    #[allow(clippy::too_many_arguments)]
     pub async fn complete_with_provider(
         &self,
         provider: ProviderType,
@@ -536,9 +765,12 @@ impl ProviderManager {
         model: Option<&str>,
         temperature: f32,
         max_tokens: usize,
+        on_retry: Option<&RetryObserver<'_>>,
     ) -> Result<LLMResponse, LLMError> {
         // Check circuit breaker before making request (OPT-LLM-6)
         self.check_circuit_breaker(provider.clone()).await?;
+        let started = std::time::Instant::now();
+        let retry_config = self.get_retry_config().await;
 
         // Clone values for the retry closure
         let prompt_owned = prompt.to_string();
@@ -548,7 +780,7 @@ impl ProviderManager {
         let result = match &provider {
             ProviderType::Mistral => {
                 let mistral = self.mistral.clone();
-                with_retry(
+                with_retry_observed(
                     || {
                         let p = prompt_owned.clone();
                         let sp = system_prompt_owned.clone();
@@ -559,13 +791,14 @@ impl ProviderManager {
                                 .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
+                    on_retry,
                 )
                 .await
             }
             ProviderType::Ollama => {
                 let ollama = self.ollama.clone();
-                with_retry(
+                with_retry_observed(
                     || {
                         let p = prompt_owned.clone();
                         let sp = system_prompt_owned.clone();
@@ -576,10 +809,35 @@ impl ProviderManager {
                                 .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
+                    on_retry,
                 )
                 .await
             }
+            ProviderType::Gemini => {
+                let gemini = self.gemini.clone();
+                with_retry_observed(
+                    || {
+                        let p = prompt_owned.clone();
+                        let sp = system_prompt_owned.clone();
+                        let m = model_owned.clone();
+                        let prov = gemini.clone();
+                        async move {
+                            prov.complete(&p, sp.as_deref(), m.as_deref(), temperature, max_tokens)
+                                .await
+                        }
+                    },
+                    &retry_config,
+                    on_retry,
+                )
+                .await
+            }
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                self.fake_provider()
+                    .await?
+                    .complete(prompt, system_prompt, model, temperature, max_tokens)
+                    .await
+            }
             ProviderType::Custom(ref name) => {
                 let custom = self
                     .custom_providers
@@ -588,7 +846,7 @@ impl ProviderManager {
                     .get(name)
                     .cloned()
                     .ok_or_else(|| LLMError::NotConfigured(name.clone()))?;
-                with_retry(
+                with_retry_observed(
                     || {
                         let p = prompt_owned.clone();
                         let sp = system_prompt_owned.clone();
@@ -600,12 +858,22 @@ impl ProviderManager {
                                 .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
+                    on_retry,
                 )
                 .await
             }
         };
 
+        crate::metrics::observe_llm_request(&provider, started.elapsed(), result.is_ok());
+        if let Ok(response) = &result {
+            crate::metrics::record_llm_tokens(
+                &provider,
+                response.tokens_input,
+                response.tokens_output,
+            );
+        }
+
         // Record result for circuit breaker (OPT-LLM-6)
         match &result {
             Ok(_) => self.record_circuit_success(provider).await,
@@ -629,13 +897,15 @@ impl ProviderManager {
     /// * `model` - Model to use
     /// * `temperature` - Sampling temperature
     /// * `max_tokens` - Maximum tokens to generate
+    /// * `sampling` - Optional top_p and stop sequences
+    /// * `on_retry` - Notified before each retry
     ///
     /// # Returns
     /// Raw JSON response from the API (caller should use adapter to parse)
     #[allow(clippy::too_many_arguments)]
     #[instrument(
         name = "manager_complete_with_tools",
-        skip(self, messages, tools, tool_choice),
+        skip(self, messages, tools, tool_choice, sampling, on_retry),
         fields(provider = ?provider, tools_count = tools.len())
     )]
     pub async fn complete_with_tools(
@@ -647,9 +917,13 @@ impl ProviderManager {
         model: &str,
         temperature: f32,
         max_tokens: usize,
+        sampling: &SamplingOptions,
+        on_retry: Option<&RetryObserver<'_>>,
     ) -> Result<serde_json::Value, LLMError> {
         // Check circuit breaker before making request (OPT-LLM-6)
         self.check_circuit_breaker(provider.clone()).await?;
+        let started = std::time::Instant::now();
+        let retry_config = self.get_retry_config().await;
 
         debug!(
             ?provider,
@@ -664,7 +938,7 @@ impl ProviderManager {
         let result = match &provider {
             ProviderType::Mistral => {
                 let mistral = self.mistral.clone();
-                with_retry(
+                with_retry_observed(
                     || {
                         let msgs = messages.clone();
                         let tls = tools.clone();
@@ -672,32 +946,82 @@ impl ProviderManager {
                         let m = model_owned.clone();
                         let prov = mistral.clone();
                         async move {
-                            prov.complete_with_tools(msgs, tls, tc, &m, temperature, max_tokens)
-                                .await
+                            prov.complete_with_tools(
+                                msgs,
+                                tls,
+                                tc,
+                                &m,
+                                temperature,
+                                max_tokens,
+                                sampling,
+                            )
+                            .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
+                    on_retry,
                 )
                 .await
             }
             ProviderType::Ollama => {
                 let ollama = self.ollama.clone();
                 // Ollama doesn't use tool_choice, so we ignore it
-                with_retry(
+                with_retry_observed(
                     || {
                         let msgs = messages.clone();
                         let tls = tools.clone();
                         let m = model_owned.clone();
                         let prov = ollama.clone();
                         async move {
-                            prov.complete_with_tools(msgs, tls, &m, temperature, max_tokens)
-                                .await
+                            prov.complete_with_tools(
+                                msgs,
+                                tls,
+                                &m,
+                                temperature,
+                                max_tokens,
+                                sampling,
+                            )
+                            .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
+                    on_retry,
                 )
                 .await
             }
+            ProviderType::Gemini => {
+                let gemini = self.gemini.clone();
+                with_retry_observed(
+                    || {
+                        let msgs = messages.clone();
+                        let tls = tools.clone();
+                        let tc = tool_choice.clone();
+                        let m = model_owned.clone();
+                        let prov = gemini.clone();
+                        async move {
+                            prov.complete_with_tools(
+                                msgs,
+                                tls,
+                                tc,
+                                &m,
+                                temperature,
+                                max_tokens,
+                                sampling,
+                            )
+                            .await
+                        }
+                    },
+                    &retry_config,
+                    on_retry,
+                )
+                .await
+            }
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                self.fake_provider()
+                    .await?
+                    .complete_with_tools(messages, tools, model)
+                    .await
+            }
             ProviderType::Custom(ref name) => {
                 let custom = self
                     .custom_providers
@@ -706,7 +1030,7 @@ impl ProviderManager {
                     .get(name)
                     .cloned()
                     .ok_or_else(|| LLMError::NotConfigured(name.clone()))?;
-                with_retry(
+                with_retry_observed(
                     || {
                         let msgs = messages.clone();
                         let tls = tools.clone();
@@ -714,16 +1038,27 @@ impl ProviderManager {
                         let m = model_owned.clone();
                         let prov = custom.clone();
                         async move {
-                            prov.complete_with_tools(msgs, tls, tc, &m, temperature, max_tokens)
-                                .await
+                            prov.complete_with_tools(
+                                msgs,
+                                tls,
+                                tc,
+                                &m,
+                                temperature,
+                                max_tokens,
+                                sampling,
+                            )
+                            .await
                         }
                     },
-                    &self.retry_config,
+                    &retry_config,
+                    on_retry,
                 )
                 .await
             }
         };
 
+        crate::metrics::observe_llm_request(&provider, started.elapsed(), result.is_ok());
+
         // Record result for circuit breaker (OPT-LLM-6)
         match &result {
             Ok(_) => self.record_circuit_success(provider).await,
@@ -756,6 +1091,7 @@ impl ProviderManager {
                 .unwrap_or_else(|| match &provider {
                     ProviderType::Mistral => config.mistral_model.clone(),
                     ProviderType::Ollama => config.ollama_model.clone(),
+                    ProviderType::Gemini => config.gemini_model.clone(),
                     ProviderType::Custom(_) => String::new(),
                 });
             (provider, model_str)
@@ -787,6 +1123,29 @@ impl ProviderManager {
                     )
                     .await
             }
+            ProviderType::Gemini => {
+                self.gemini
+                    .complete_stream(
+                        prompt,
+                        system_prompt,
+                        Some(&model_to_use),
+                        temperature,
+                        max_tokens,
+                    )
+                    .await
+            }
+            ProviderType::Custom(ref name) if name == FAKE_PROVIDER_NAME => {
+                self.fake_provider()
+                    .await?
+                    .complete_stream(
+                        prompt,
+                        system_prompt,
+                        Some(&model_to_use),
+                        temperature,
+                        max_tokens,
+                    )
+                    .await
+            }
             ProviderType::Custom(ref name) => {
                 let custom = self
                     .custom_providers
@@ -808,6 +1167,31 @@ impl ProviderManager {
         }
     }
 
+    /// Lists the chat models of a provider with the metadata its API reports.
+    ///
+    /// Used by `refresh_provider_models` to upsert the `llm_model` records.
+    #[instrument(name = "manager_list_model_metadata", skip(self), fields(provider = %provider))]
+    pub async fn list_model_metadata(
+        &self,
+        provider: &ProviderType,
+    ) -> Result<Vec<ModelMetadata>, LLMError> {
+        match provider {
+            ProviderType::Mistral => self.mistral.list_model_metadata().await,
+            ProviderType::Ollama => self.ollama.list_model_metadata().await,
+            ProviderType::Gemini => self.gemini.list_model_metadata().await,
+            ProviderType::Custom(name) if name == FAKE_PROVIDER_NAME => {
+                Ok(self.fake_provider().await?.model_metadata())
+            }
+            ProviderType::Custom(name) => {
+                self.get_custom_provider(name)
+                    .await
+                    .ok_or_else(|| LLMError::NotConfigured(name.clone()))?
+                    .list_model_metadata()
+                    .await
+            }
+        }
+    }
+
     /// Gets reference to Mistral provider
     pub fn mistral(&self) -> &Arc<MistralProvider> {
         &self.mistral
@@ -817,6 +1201,11 @@ impl ProviderManager {
     pub fn ollama(&self) -> &Arc<OllamaProvider> {
         &self.ollama
     }
+
+    /// Gets reference to Gemini provider
+    pub fn gemini(&self) -> &Arc<GeminiProvider> {
+        &self.gemini
+    }
 }
 
 impl Default for ProviderManager {
@@ -1046,4 +1435,44 @@ mod tests {
             .unwrap();
         assert_eq!(status.consecutive_failures, 0);
     }
+
+    #[tokio::test]
+    async fn test_fake_provider_routing() {
+        let manager = ProviderManager::new();
+        let fake = ProviderType::Custom(FAKE_PROVIDER_NAME.to_string());
+        manager.set_fake_provider(None).await;
+        assert!(!manager.is_provider_configured(fake.clone()));
+        assert!(matches!(
+            manager
+                .complete_with_provider(fake.clone(), "Hi", None, None, 0.7, 100, None)
+                .await,
+            Err(LLMError::NotConfigured(_))
+        ));
+
+        manager.set_fake_provider(Some(FakeProvider::echo())).await;
+        assert!(manager.is_provider_configured(fake.clone()));
+        assert!(manager.get_configured_providers().contains(&fake));
+
+        let response = manager
+            .complete_with_provider(fake.clone(), "Hi", None, None, 0.7, 100, None)
+            .await
+            .unwrap();
+        assert_eq!(response.content, "Fake response to: Hi");
+
+        let raw = manager
+            .complete_with_tools(
+                fake,
+                vec![serde_json::json!({"role": "user", "content": "Hi"})],
+                vec![],
+                None,
+                "fake-model",
+                0.7,
+                100,
+                &SamplingOptions::default(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(raw["choices"][0]["finish_reason"], "stop");
+    }
 }
@@ -18,8 +18,10 @@
 //! Reasoning models return a different response format with thinking blocks
 //! that requires custom HTTP handling.
 
-use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType};
+use super::model_discovery;
+use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType, SamplingOptions};
 use super::utils::simulate_streaming;
+use crate::models::llm_models::ModelMetadata;
 use async_trait::async_trait;
 use rig::completion::Prompt;
 use rig::providers::mistral;
@@ -131,6 +133,10 @@ struct MistralToolChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<serde_json::Value>,
@@ -254,6 +260,9 @@ pub struct MistralProvider {
 /// Mistral API base URL
 const MISTRAL_API_URL: &str = "https://api.mistral.ai/v1/chat/completions";
 
+/// Mistral model list URL
+const MISTRAL_MODELS_URL: &str = "https://api.mistral.ai/v1/models";
+
 /// Models that are reasoning models and require custom HTTP handling
 const REASONING_MODELS: &[&str] = &["magistral-small-latest", "magistral-medium-latest"];
 
@@ -295,10 +304,16 @@ impl MistralProvider {
     }
 
     /// Configures the provider with an API key
+    ///
+    /// The rig client shares the HTTP client of the provider (and its proxy).
     pub async fn configure(&self, api_key: &str) -> Result<(), LLMError> {
-        let client = mistral::Client::new(api_key).map_err(|e| {
-            LLMError::RequestFailed(format!("Failed to create Mistral client: {}", e))
-        })?;
+        let client = mistral::Client::<reqwest::Client>::builder()
+            .api_key(api_key)
+            .http_client((*self.http_client).clone())
+            .build()
+            .map_err(|e| {
+                LLMError::RequestFailed(format!("Failed to create Mistral client: {}", e))
+            })?;
         *self.client.write().await = Some(client);
         *self.api_key.write().await = Some(api_key.to_string());
         info!("Mistral provider configured");
@@ -317,6 +332,23 @@ impl MistralProvider {
         self.api_key.read().await.clone()
     }
 
+    /// Lists the chat models of the account with their metadata.
+    #[instrument(name = "mistral_list_model_metadata", skip(self))]
+    pub async fn list_model_metadata(&self) -> Result<Vec<ModelMetadata>, LLMError> {
+        let api_key = self
+            .api_key
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| LLMError::NotConfigured("Mistral".to_string()))?;
+        let request = self
+            .http_client
+            .get(MISTRAL_MODELS_URL)
+            .header("Authorization", format!("Bearer {}", api_key));
+        let body = model_discovery::fetch_json(request, "Mistral").await?;
+        Ok(model_discovery::parse_mistral_models(&body))
+    }
+
     /// Checks if a model is a reasoning model (Magistral)
     fn is_reasoning_model(model: &str) -> bool {
         REASONING_MODELS
@@ -460,12 +492,14 @@ impl MistralProvider {
     /// * `model` - Model to use
     /// * `temperature` - Sampling temperature
     /// * `max_tokens` - Maximum tokens to generate
+    /// * `sampling` - Optional top_p and stop sequences
     ///
     /// # Returns
     /// Raw JSON response from the API (caller should use adapter to parse)
+    #[allow(clippy::too_many_arguments)]
     #[instrument(
         name = "mistral_complete_with_tools",
-        skip(self, messages, tools, tool_choice),
+        skip(self, messages, tools, tool_choice, sampling),
         fields(provider = "mistral", model = %model, tools_count = tools.len())
     )]
     pub async fn complete_with_tools(
@@ -476,6 +510,7 @@ impl MistralProvider {
         model: &str,
         temperature: f32,
         max_tokens: usize,
+        sampling: &SamplingOptions,
     ) -> Result<serde_json::Value, LLMError> {
         let api_key = self
             .api_key
@@ -489,6 +524,8 @@ impl MistralProvider {
             messages,
             temperature: Some(temperature),
             max_tokens: Some(max_tokens),
+            top_p: sampling.top_p,
+            stop: sampling.stop.clone(),
             tools: if tools.is_empty() { None } else { Some(tools) },
             tool_choice,
         };
@@ -15,7 +15,7 @@
 //! # LLM Integration Module
 //!
 //! This module provides the LLM provider abstraction layer using rig-core.
-//! It supports multiple providers (Mistral, Ollama) with a unified interface.
+//! It supports multiple providers (Mistral, Ollama, Gemini) with a unified interface.
 //!
 //! ## Architecture
 //!
@@ -23,6 +23,8 @@
 //! - [`ProviderManager`] - Manages provider instances and configuration
 //! - [`MistralProvider`] - Mistral AI cloud API integration
 //! - [`OllamaProvider`] - Local Ollama server integration
+//! - [`GeminiProvider`] - Google Gemini cloud API integration
+//! - [`fake::FakeProvider`] - Scripted provider for offline tests (`ZILEO_FAKE_LLM`)
 //!
 //! ## Usage
 //!
@@ -37,14 +39,20 @@
 pub mod adapters;
 pub mod circuit_breaker;
 pub mod embedding;
+pub mod fake;
+pub mod gemini;
+pub mod local_status;
 mod manager;
 mod mistral;
+pub mod model_discovery;
+pub mod moderation;
 mod ollama;
 pub mod openai_compatible;
 pub mod pricing;
 mod provider;
 pub mod retry;
 pub mod tool_adapter;
+pub mod utility;
 pub mod utils;
 
 pub use manager::ProviderManager;
@@ -60,11 +68,13 @@ pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
 
 // Re-export for future use (tools, external integrations)
 #[allow(unused_imports)]
+pub use gemini::GeminiProvider;
+#[allow(unused_imports)]
 pub use mistral::MistralProvider;
 #[allow(unused_imports)]
 pub use ollama::OllamaProvider;
 #[allow(unused_imports)]
-pub use provider::{LLMProvider, LLMResponse};
+pub use provider::{LLMProvider, LLMResponse, SamplingOptions};
 
 // Embedding service exports (will be used by MemoryTool in Phase 3)
 #[allow(unused_imports)]
@@ -76,7 +86,7 @@ pub use embedding::{
 // Tool adapter exports for JSON function calling
 // Exported for public API but consumed directly from submodules internally
 #[allow(unused_imports)]
-pub use adapters::{MistralToolAdapter, OllamaToolAdapter, OpenAiToolAdapter};
+pub use adapters::{GeminiToolAdapter, MistralToolAdapter, OllamaToolAdapter, OpenAiToolAdapter};
 #[allow(unused_imports)]
 pub use tool_adapter::ProviderToolAdapter;
 
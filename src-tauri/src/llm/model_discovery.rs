@@ -0,0 +1,289 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Model metadata discovery.
+//!
+//! Parses the model list responses of each provider into [`ModelMetadata`]
+//! (context window, output limit, vision and tool support). Only chat models
+//! are kept; values a provider does not report stay `None`.
+
+use super::provider::LLMError;
+use crate::models::llm_models::ModelMetadata;
+use serde_json::Value;
+
+/// Sends a model list request and reads its JSON body.
+pub(super) async fn fetch_json(
+    request: reqwest::RequestBuilder,
+    provider: &str,
+) -> Result<Value, LLMError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| LLMError::ConnectionError(format!("Connection failed: {}", e)))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| LLMError::RequestFailed(format!("Failed to read response body: {}", e)))?;
+    if !status.is_success() {
+        return Err(LLMError::RequestFailed(format!(
+            "{} API error ({}): {}",
+            provider,
+            status,
+            body.chars().take(500).collect::<String>()
+        )));
+    }
+    serde_json::from_str(&body).map_err(|e| {
+        LLMError::RequestFailed(format!("Failed to parse {} models list: {}", provider, e))
+    })
+}
+
+/// Reads a non-zero token count
+fn token_count(value: Option<&Value>) -> Option<usize> {
+    value
+        .and_then(Value::as_u64)
+        .filter(|count| *count > 0)
+        .map(|count| count as usize)
+}
+
+/// Returns whether a JSON array of strings contains `item`
+fn array_contains(value: Option<&Value>, item: &str) -> Option<bool> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().any(|v| v.as_str() == Some(item)))
+}
+
+/// Parses the Mistral `/v1/models` response.
+///
+/// Models without chat completion (embeddings, moderation, OCR) are skipped.
+pub fn parse_mistral_models(body: &Value) -> Vec<ModelMetadata> {
+    body.get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let api_name = model.get("id")?.as_str()?.to_string();
+            let capabilities = model.get("capabilities");
+            let capability = |name: &str| {
+                capabilities
+                    .and_then(|c| c.get(name))
+                    .and_then(Value::as_bool)
+            };
+            if capability("completion_chat") == Some(false) {
+                return None;
+            }
+            Some(ModelMetadata {
+                api_name,
+                name: None,
+                context_window: token_count(model.get("max_context_length")),
+                max_output_tokens: None,
+                supports_vision: capability("vision"),
+                supports_tools: capability("function_calling"),
+            })
+        })
+        .collect()
+}
+
+/// Parses the Gemini `/models` response.
+///
+/// Only models supporting `generateContent` are kept; the `models/` prefix is
+/// removed from their names.
+pub fn parse_gemini_models(body: &Value) -> Vec<ModelMetadata> {
+    body.get("models")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let name = model.get("name")?.as_str()?;
+            if array_contains(model.get("supportedGenerationMethods"), "generateContent")
+                != Some(true)
+            {
+                return None;
+            }
+            Some(ModelMetadata {
+                api_name: name.trim_start_matches("models/").to_string(),
+                name: model
+                    .get("displayName")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                context_window: token_count(model.get("inputTokenLimit")),
+                max_output_tokens: token_count(model.get("outputTokenLimit")),
+                supports_vision: None,
+                supports_tools: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses the names of the Ollama `/api/tags` response.
+pub fn parse_ollama_tags(body: &Value) -> Vec<String> {
+    body.get("models")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| model.get("name").and_then(Value::as_str))
+        .map(String::from)
+        .collect()
+}
+
+/// Parses the Ollama `/api/show` response of a model.
+///
+/// Returns None for models without completion (embedding models). The context
+/// length is read from the architecture-prefixed `*.context_length` entry.
+pub fn parse_ollama_show(api_name: &str, body: &Value) -> Option<ModelMetadata> {
+    let capabilities = body.get("capabilities");
+    if array_contains(capabilities, "completion") == Some(false) {
+        return None;
+    }
+    let context_window = body
+        .get("model_info")
+        .and_then(Value::as_object)
+        .and_then(|info| {
+            info.iter()
+                .find(|(key, _)| key.ends_with(".context_length"))
+                .and_then(|(_, value)| token_count(Some(value)))
+        });
+    Some(ModelMetadata {
+        api_name: api_name.to_string(),
+        name: None,
+        context_window,
+        max_output_tokens: None,
+        supports_vision: array_contains(capabilities, "vision"),
+        supports_tools: array_contains(capabilities, "tools"),
+    })
+}
+
+/// Parses an OpenAI-compatible `/models` response.
+///
+/// The base format only lists IDs; the extensions of OpenRouter
+/// (`context_length`, `architecture`, `supported_parameters`) and Groq
+/// (`context_window`) are read when present.
+pub fn parse_openai_models(body: &Value) -> Vec<ModelMetadata> {
+    body.get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let api_name = model.get("id")?.as_str()?;
+            if api_name.is_empty() {
+                return None;
+            }
+            let max_output_tokens = token_count(
+                model
+                    .get("top_provider")
+                    .and_then(|p| p.get("max_completion_tokens")),
+            )
+            .or_else(|| token_count(model.get("max_completion_tokens")));
+            Some(ModelMetadata {
+                api_name: api_name.to_string(),
+                name: model.get("name").and_then(Value::as_str).map(String::from),
+                context_window: token_count(model.get("context_length"))
+                    .or_else(|| token_count(model.get("context_window"))),
+                max_output_tokens,
+                supports_vision: array_contains(
+                    model
+                        .get("architecture")
+                        .and_then(|a| a.get("input_modalities")),
+                    "image",
+                ),
+                supports_tools: array_contains(model.get("supported_parameters"), "tools"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_mistral_models() {
+        let body = json!({"object": "list", "data": [
+            {"id": "mistral-large-latest", "max_context_length": 131072,
+             "capabilities": {"completion_chat": true, "function_calling": true, "vision": false}},
+            {"id": "mistral-embed", "max_context_length": 8192,
+             "capabilities": {"completion_chat": false, "function_calling": false}}
+        ]});
+        let models = parse_mistral_models(&body);
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].api_name, "mistral-large-latest");
+        assert_eq!(models[0].context_window, Some(131072));
+        assert_eq!(models[0].supports_tools, Some(true));
+        assert_eq!(models[0].supports_vision, Some(false));
+    }
+
+    #[test]
+    fn test_parse_gemini_models() {
+        let body = json!({"models": [
+            {"name": "models/gemini-2.5-flash", "displayName": "Gemini 2.5 Flash",
+             "inputTokenLimit": 1048576, "outputTokenLimit": 65536,
+             "supportedGenerationMethods": ["generateContent", "countTokens"]},
+            {"name": "models/text-embedding-004", "inputTokenLimit": 2048,
+             "supportedGenerationMethods": ["embedContent"]}
+        ]});
+        let models = parse_gemini_models(&body);
+        assert_eq!(
+            models,
+            vec![ModelMetadata {
+                api_name: "gemini-2.5-flash".to_string(),
+                name: Some("Gemini 2.5 Flash".to_string()),
+                context_window: Some(1_048_576),
+                max_output_tokens: Some(65_536),
+                supports_vision: None,
+                supports_tools: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_ollama_models() {
+        let tags = json!({"models": [{"name": "qwen2.5:7b"}, {"name": "nomic-embed-text"}]});
+        assert_eq!(
+            parse_ollama_tags(&tags),
+            vec!["qwen2.5:7b", "nomic-embed-text"]
+        );
+
+        let show = json!({
+            "capabilities": ["completion", "tools"],
+            "model_info": {"general.architecture": "qwen2", "qwen2.context_length": 32768}
+        });
+        let model = parse_ollama_show("qwen2.5:7b", &show).unwrap();
+        assert_eq!(model.context_window, Some(32768));
+        assert_eq!(model.supports_tools, Some(true));
+        assert_eq!(model.supports_vision, Some(false));
+
+        let embedding = json!({"capabilities": ["embedding"]});
+        assert!(parse_ollama_show("nomic-embed-text", &embedding).is_none());
+    }
+
+    #[test]
+    fn test_parse_openai_models() {
+        let body = json!({"data": [
+            {"id": "openai/gpt-4o", "name": "GPT-4o", "context_length": 128000,
+             "top_provider": {"max_completion_tokens": 16384},
+             "architecture": {"input_modalities": ["text", "image"]},
+             "supported_parameters": ["tools", "temperature"]},
+            {"id": "llama-3.3-70b-versatile", "context_window": 131072},
+            {"id": ""}
+        ]});
+        let models = parse_openai_models(&body);
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].max_output_tokens, Some(16384));
+        assert_eq!(models[0].supports_vision, Some(true));
+        assert_eq!(models[0].supports_tools, Some(true));
+        assert_eq!(models[1].context_window, Some(131072));
+        assert_eq!(models[1].supports_tools, None);
+    }
+}
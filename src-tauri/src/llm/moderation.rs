@@ -0,0 +1,312 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Output Moderation
+//!
+//! Classifies agent responses according to the agent's [`ModerationPolicy`].
+//!
+//! - **Provider**: Mistral (`/v1/moderations`) and OpenAI-compatible custom
+//!   providers (`{base_url}/moderations`) expose a moderation endpoint. Ollama
+//!   has none; it (and any failed call) falls back to the local classifier.
+//! - **Local**: pattern-based checks that always run: leaked secrets (API keys,
+//!   private keys), payment card numbers and the policy's blocked terms.
+
+use crate::llm::{ProviderManager, ProviderType};
+use crate::models::moderation::{ModerationOutcome, ModerationPolicy, ModerationSource};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Mistral moderation API endpoint
+const MISTRAL_MODERATION_URL: &str = "https://api.mistral.ai/v1/moderations";
+
+/// Mistral moderation model
+const MISTRAL_MODERATION_MODEL: &str = "mistral-moderation-latest";
+
+/// Moderation model requested from OpenAI-compatible providers
+const OPENAI_MODERATION_MODEL: &str = "omni-moderation-latest";
+
+/// Timeout of a provider moderation call
+const MODERATION_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Maximum characters sent to a provider moderation endpoint
+const MAX_MODERATION_INPUT_CHARS: usize = 20_000;
+
+/// Source name of the local classifier
+pub const LOCAL_SOURCE: &str = "local";
+
+/// Patterns of credentials that should never appear in a response
+static SECRET_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        r"\bsk-[A-Za-z0-9_-]{20,}",
+        r"\bAKIA[0-9A-Z]{16}\b",
+        r"\bgh[pousr]_[A-Za-z0-9]{36}\b",
+        r"-----BEGIN [A-Z ]*PRIVATE KEY-----",
+    ]
+    .iter()
+    .filter_map(|p| Regex::new(p).ok())
+    .collect()
+});
+
+/// Candidate payment card numbers (13-19 digits, optionally grouped)
+static CARD_PATTERN: Lazy<Option<Regex>> =
+    Lazy::new(|| Regex::new(r"\b(?:\d[ -]?){12,18}\d\b").ok());
+
+/// Checks a response against an agent's moderation policy.
+///
+/// The local classifier always runs; with [`ModerationSource::Provider`], the
+/// categories flagged by the provider's moderation endpoint are added.
+///
+/// # Arguments
+/// * `llm_manager` - Provider manager (API keys and HTTP client)
+/// * `provider` - Agent provider name (e.g. "Mistral", "Ollama", custom name)
+/// * `policy` - Agent moderation policy
+/// * `text` - Response to check
+pub async fn moderate(
+    llm_manager: &ProviderManager,
+    provider: &str,
+    policy: &ModerationPolicy,
+    text: &str,
+) -> ModerationOutcome {
+    let mut categories = classify_locally(text, &policy.blocked_terms);
+    let mut source = LOCAL_SOURCE.to_string();
+
+    if policy.source == ModerationSource::Provider {
+        match classify_with_provider(llm_manager, provider, text).await {
+            Some(Ok(provider_categories)) => {
+                categories.extend(provider_categories);
+                source = provider.to_lowercase();
+            }
+            Some(Err(e)) => {
+                warn!(provider = %provider, error = %e, "Provider moderation failed, using local classifier");
+            }
+            None => {
+                debug!(provider = %provider, "No moderation endpoint, using local classifier");
+            }
+        }
+    }
+
+    categories.sort();
+    categories.dedup();
+
+    ModerationOutcome {
+        flagged: !categories.is_empty(),
+        categories,
+        action: policy.action,
+        source,
+    }
+}
+
+/// Runs the local classifier.
+///
+/// # Returns
+/// Flagged categories: `secret`, `pii` and `blocked_term`
+pub fn classify_locally(text: &str, blocked_terms: &[String]) -> Vec<String> {
+    let mut categories = Vec::new();
+
+    if SECRET_PATTERNS.iter().any(|p| p.is_match(text)) {
+        categories.push("secret".to_string());
+    }
+
+    let has_card_number = CARD_PATTERN.as_ref().is_some_and(|pattern| {
+        pattern.find_iter(text).any(|m| {
+            let digits: Vec<u32> = m.as_str().chars().filter_map(|c| c.to_digit(10)).collect();
+            digits.len() >= 13 && luhn_valid(&digits)
+        })
+    });
+    if has_card_number {
+        categories.push("pii".to_string());
+    }
+
+    let lowered = text.to_lowercase();
+    let has_blocked_term = blocked_terms
+        .iter()
+        .map(|term| term.trim().to_lowercase())
+        .filter(|term| !term.is_empty())
+        .any(|term| contains_word(&lowered, &term));
+    if has_blocked_term {
+        categories.push("blocked_term".to_string());
+    }
+
+    categories
+}
+
+/// Calls the moderation endpoint of the agent's provider.
+///
+/// # Returns
+/// None if the provider has no moderation endpoint or is not configured
+async fn classify_with_provider(
+    llm_manager: &ProviderManager,
+    provider: &str,
+    text: &str,
+) -> Option<Result<Vec<String>, String>> {
+    let input: String = text.chars().take(MAX_MODERATION_INPUT_CHARS).collect();
+
+    let (url, api_key, body) = match provider.parse::<ProviderType>().ok()? {
+        ProviderType::Mistral => {
+            let api_key = llm_manager.mistral().get_api_key().await?;
+            let body = json!({"model": MISTRAL_MODERATION_MODEL, "input": [input]});
+            (MISTRAL_MODERATION_URL.to_string(), api_key, body)
+        }
+        ProviderType::Ollama | ProviderType::Gemini => return None,
+        ProviderType::Custom(name) => {
+            let custom = llm_manager.get_custom_provider(&name).await?;
+            let api_key = custom.get_api_key().await?;
+            let base_url = custom.get_base_url().await?;
+            let body = json!({"model": OPENAI_MODERATION_MODEL, "input": input});
+            (format!("{}/moderations", base_url), api_key, body)
+        }
+    };
+
+    let response = llm_manager
+        .http_client()
+        .post(&url)
+        .bearer_auth(api_key)
+        .timeout(MODERATION_TIMEOUT)
+        .json(&body)
+        .send()
+        .await;
+
+    let result = match response {
+        Ok(response) if response.status().is_success() => response
+            .json::<Value>()
+            .await
+            .map_err(|e| format!("Invalid moderation response: {}", e))
+            .and_then(|body| {
+                flagged_categories(&body).ok_or_else(|| "Malformed moderation response".to_string())
+            }),
+        Ok(response) => Err(format!("HTTP {}", response.status())),
+        Err(e) => Err(e.to_string()),
+    };
+    Some(result)
+}
+
+/// Extracts the flagged categories of a moderation response.
+///
+/// Mistral and OpenAI both return `results[].categories` as a map of category
+/// name to boolean.
+///
+/// # Returns
+/// None if the response has no `results` array
+pub fn flagged_categories(body: &Value) -> Option<Vec<String>> {
+    let results = body.get("results")?.as_array()?;
+    Some(
+        results
+            .iter()
+            .filter_map(|result| result.get("categories")?.as_object())
+            .flat_map(|categories| {
+                categories
+                    .iter()
+                    .filter(|(_, flagged)| flagged.as_bool() == Some(true))
+                    .map(|(name, _)| name.clone())
+            })
+            .collect(),
+    )
+}
+
+/// Returns true if `term` appears in `text` as a whole word (or phrase).
+fn contains_word(text: &str, term: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(term).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + term.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+/// Luhn checksum used by payment card numbers.
+fn luhn_valid(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::moderation::ModerationAction;
+
+    #[test]
+    fn test_classify_locally() {
+        assert!(classify_locally("The answer is 42.", &[]).is_empty());
+        assert_eq!(
+            classify_locally("Use key sk-abcdefghijklmnopqrstuvwxyz123", &[]),
+            vec!["secret"]
+        );
+        // Valid Luhn test card number, grouped
+        assert_eq!(
+            classify_locally("Card: 4111 1111 1111 1111", &[]),
+            vec!["pii"]
+        );
+        assert!(classify_locally("Order 1234 5678 9012 3456", &[]).is_empty());
+
+        let terms = vec!["Project Falcon".to_string()];
+        assert_eq!(
+            classify_locally("Status of project falcon: on track", &terms),
+            vec!["blocked_term"]
+        );
+        assert!(classify_locally("Falconry is a hobby", &["falcon".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_flagged_categories() {
+        let body = json!({"results": [{
+            "flagged": true,
+            "categories": {"hate": false, "violence": true, "pii": true},
+            "category_scores": {"hate": 0.01, "violence": 0.9, "pii": 0.8}
+        }]});
+        let mut categories = flagged_categories(&body).unwrap();
+        categories.sort();
+        assert_eq!(categories, vec!["pii", "violence"]);
+
+        assert!(flagged_categories(&json!({"error": "unauthorized"})).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_moderate_falls_back_to_local_classifier() {
+        let manager = ProviderManager::new();
+        let policy = ModerationPolicy {
+            enabled: true,
+            action: ModerationAction::Block,
+            source: ModerationSource::Provider,
+            blocked_terms: vec!["confidential".to_string()],
+        };
+
+        let outcome = moderate(&manager, "Ollama", &policy, "This is CONFIDENTIAL.").await;
+        assert!(outcome.flagged);
+        assert_eq!(outcome.categories, vec!["blocked_term"]);
+        assert_eq!(outcome.action, ModerationAction::Block);
+        assert_eq!(outcome.source, LOCAL_SOURCE);
+
+        let outcome = moderate(&manager, "Ollama", &policy, "All good.").await;
+        assert!(!outcome.flagged);
+    }
+}
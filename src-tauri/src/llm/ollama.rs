@@ -14,8 +14,11 @@
 
 //! Ollama local provider implementation using rig-core
 
-use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType};
-use super::utils::simulate_streaming;
+use super::provider::{LLMError, LLMProvider, LLMResponse, ProviderType, SamplingOptions};
+use super::utils::{content_images, content_text, simulate_streaming};
+use super::{local_status, model_discovery};
+use crate::models::llm_models::ModelMetadata;
+use crate::models::local_llm::LoadedModel;
 use async_trait::async_trait;
 use rig::client::Nothing;
 use rig::completion::Prompt;
@@ -77,6 +80,29 @@ fn get_think_param(model: &str, enable_thinking: bool) -> serde_json::Value {
     }
 }
 
+/// Converts OpenAI-style multimodal contents to Ollama's `images` field.
+///
+/// Ollama expects a string `content` plus base64 `images` on the message.
+fn convert_image_content(messages: Vec<serde_json::Value>) -> Vec<serde_json::Value> {
+    messages
+        .into_iter()
+        .map(|mut message| {
+            let Some(content) = message.get("content").filter(|c| c.is_array()).cloned() else {
+                return message;
+            };
+            let images: Vec<String> = content_images(&content)
+                .into_iter()
+                .map(|(_, data)| data)
+                .collect();
+            message["content"] = serde_json::Value::String(content_text(&content));
+            if !images.is_empty() {
+                message["images"] = serde_json::json!(images);
+            }
+            message
+        })
+        .collect()
+}
+
 /// Response structure for Ollama chat with thinking support
 #[derive(Debug, Deserialize, Serialize)]
 struct OllamaChatResponse {
@@ -149,19 +175,18 @@ impl OllamaProvider {
         *self.server_url.write().await = server_url.to_string();
 
         // Create client with custom URL if provided
-        let client = if server_url != DEFAULT_OLLAMA_URL {
-            ollama::Client::builder()
-                .api_key(Nothing)
-                .base_url(server_url)
-                .build()
-                .map_err(|e| {
-                    LLMError::ConnectionError(format!("Failed to create Ollama client: {}", e))
-                })?
+        // The rig client shares the HTTP client of the provider (and its proxy)
+        let builder = ollama::Client::<reqwest::Client>::builder()
+            .api_key(Nothing)
+            .http_client((*self.http_client).clone());
+        let builder = if server_url != DEFAULT_OLLAMA_URL {
+            builder.base_url(server_url)
         } else {
-            ollama::Client::new(Nothing).map_err(|e| {
-                LLMError::ConnectionError(format!("Failed to create Ollama client: {}", e))
-            })?
+            builder
         };
+        let client = builder.build().map_err(|e| {
+            LLMError::ConnectionError(format!("Failed to create Ollama client: {}", e))
+        })?;
 
         *self.client.write().await = Some(client);
         *self.configured.write().await = true;
@@ -197,6 +222,53 @@ impl OllamaProvider {
         Ok(response.status().is_success())
     }
 
+    /// Lists the installed completion models with their metadata.
+    ///
+    /// Capabilities and context length come from `/api/show`, called for each
+    /// model listed by `/api/tags`.
+    #[instrument(name = "ollama_list_model_metadata", skip(self))]
+    pub async fn list_model_metadata(&self) -> Result<Vec<ModelMetadata>, LLMError> {
+        let url = self.server_url.read().await.clone();
+        let tags = model_discovery::fetch_json(
+            self.http_client.get(format!("{}/api/tags", url)),
+            "Ollama",
+        )
+        .await?;
+
+        let mut models = Vec::new();
+        for name in model_discovery::parse_ollama_tags(&tags) {
+            let request = self
+                .http_client
+                .post(format!("{}/api/show", url))
+                .json(&serde_json::json!({ "model": name }));
+            let show = model_discovery::fetch_json(request, "Ollama").await?;
+            models.extend(model_discovery::parse_ollama_show(&name, &show));
+        }
+        Ok(models)
+    }
+
+    /// Lists the models currently loaded in memory (`/api/ps`)
+    #[instrument(name = "ollama_loaded_models", skip(self))]
+    pub async fn loaded_models(&self) -> Result<Vec<LoadedModel>, LLMError> {
+        let url = self.server_url.read().await.clone();
+        let body =
+            model_discovery::fetch_json(self.http_client.get(format!("{}/api/ps", url)), "Ollama")
+                .await?;
+        Ok(local_status::parse_ollama_ps(&body))
+    }
+
+    /// Gets the size of an installed model, None when it is not installed
+    #[instrument(name = "ollama_model_size", skip(self))]
+    pub async fn model_size(&self, model: &str) -> Result<Option<u64>, LLMError> {
+        let url = self.server_url.read().await.clone();
+        let body = model_discovery::fetch_json(
+            self.http_client.get(format!("{}/api/tags", url)),
+            "Ollama",
+        )
+        .await?;
+        Ok(local_status::parse_ollama_model_size(&body, model))
+    }
+
     /// Complete with thinking support using direct HTTP call (bypasses rig-core)
     ///
     /// Returns a tuple of (LLMResponse, Option<thinking_content>)
@@ -297,6 +369,7 @@ impl OllamaProvider {
     /// * `model` - Model to use (must support tools: qwen2.5, llama3.1+, mistral)
     /// * `temperature` - Sampling temperature
     /// * `max_tokens` - Maximum tokens to generate
+    /// * `sampling` - Optional top_p and stop sequences (sent as model options)
     ///
     /// # Returns
     /// Raw JSON response from the API (caller should use adapter to parse)
@@ -308,7 +381,7 @@ impl OllamaProvider {
     /// - mistral, mistral-nemo
     #[instrument(
         name = "ollama_complete_with_tools",
-        skip(self, messages, tools),
+        skip(self, messages, tools, sampling),
         fields(provider = "ollama", model = %model, tools_count = tools.len())
     )]
     pub async fn complete_with_tools(
@@ -318,6 +391,7 @@ impl OllamaProvider {
         model: &str,
         temperature: f32,
         max_tokens: usize,
+        sampling: &SamplingOptions,
     ) -> Result<serde_json::Value, LLMError> {
         let server_url = self.server_url.read().await.clone();
         let url = format!("{}/api/chat", server_url);
@@ -325,7 +399,7 @@ impl OllamaProvider {
         // Build request body with tools
         let mut body = serde_json::json!({
             "model": model,
-            "messages": messages,
+            "messages": convert_image_content(messages),
             "stream": false,
             "options": {
                 "temperature": temperature,
@@ -333,6 +407,13 @@ impl OllamaProvider {
             }
         });
 
+        if let Some(top_p) = sampling.top_p {
+            body["options"]["top_p"] = serde_json::json!(top_p);
+        }
+        if !sampling.stop.is_empty() {
+            body["options"]["stop"] = serde_json::json!(sampling.stop);
+        }
+
         // Add tools if provided
         if !tools.is_empty() {
             body["tools"] = serde_json::json!(tools);
@@ -548,6 +629,23 @@ impl LLMProvider for OllamaProvider {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_convert_image_content() {
+        let messages = vec![
+            serde_json::json!({"role": "system", "content": "Be brief"}),
+            serde_json::json!({"role": "user", "content": [
+                {"type": "text", "text": "Describe"},
+                {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/4AA="}}
+            ]}),
+        ];
+
+        let converted = convert_image_content(messages);
+        assert_eq!(converted[0]["content"], "Be brief");
+        assert!(converted[0].get("images").is_none());
+        assert_eq!(converted[1]["content"], "Describe");
+        assert_eq!(converted[1]["images"], serde_json::json!(["/9j/4AA="]));
+    }
+
     #[test]
     fn test_ollama_models() {
         assert!(OLLAMA_MODELS.contains(&"llama3.2"));
@@ -16,13 +16,18 @@
 //!
 //! Supports any API that follows the OpenAI chat completions format:
 //! - POST `{base_url}/chat/completions` for completions
-//! - GET `{base_url}/models` for connection testing
+//! - GET `{base_url}/models` for connection testing and model discovery
+//!
+//! Works with hosted gateways (OpenRouter, Groq, Together AI) as well as
+//! local servers (LM Studio, vLLM, llama.cpp server) whose base URL ends in `/v1`.
 //!
 //! Handles both standard and reasoning model response formats via
 //! a polymorphic content deserializer (string or array of content blocks).
 
-use super::provider::{LLMError, LLMResponse, ProviderType};
+use super::model_discovery;
+use super::provider::{LLMError, LLMResponse, ProviderType, SamplingOptions};
 use super::utils::simulate_streaming;
+use crate::models::llm_models::ModelMetadata;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
@@ -112,6 +117,32 @@ struct ApiErrorDetail {
     message: String,
 }
 
+/// Response of `GET {base_url}/models`
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    data: Vec<ModelEntry>,
+}
+
+/// Model entry in the models list
+#[derive(Debug, Deserialize)]
+struct ModelEntry {
+    id: String,
+}
+
+/// Parses a models list body into sorted, deduplicated model IDs.
+fn parse_model_ids(body: &str) -> Result<Vec<String>, serde_json::Error> {
+    let response: ModelsResponse = serde_json::from_str(body)?;
+    let mut ids: Vec<String> = response
+        .data
+        .into_iter()
+        .map(|m| m.id)
+        .filter(|id| !id.is_empty())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    Ok(ids)
+}
+
 // ============================================================================
 // Function Calling Types
 // ============================================================================
@@ -126,6 +157,10 @@ struct ToolChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<serde_json::Value>,
@@ -245,6 +280,12 @@ impl OpenAiCompatibleProvider {
         Ok(())
     }
 
+    /// Sets the base URL alone, for a provider without stored API key: its
+    /// models can still be listed (local servers need no key).
+    pub async fn set_base_url(&self, base_url: &str) {
+        *self.base_url.write().await = Some(base_url.trim_end_matches('/').to_string());
+    }
+
     /// Clears the provider configuration.
     #[allow(dead_code)] // API completeness - provider lifecycle
     pub async fn clear(&self) {
@@ -421,9 +462,10 @@ impl OpenAiCompatibleProvider {
     }
 
     /// Makes a completion request with function calling support.
+    #[allow(clippy::too_many_arguments)]
     #[instrument(
         name = "openai_compat_complete_with_tools",
-        skip(self, messages, tools, tool_choice),
+        skip(self, messages, tools, tool_choice, sampling),
         fields(provider = %self.provider_name, model = %model, tools_count = tools.len())
     )]
     pub async fn complete_with_tools(
@@ -434,6 +476,7 @@ impl OpenAiCompatibleProvider {
         model: &str,
         temperature: f32,
         max_tokens: usize,
+        sampling: &SamplingOptions,
     ) -> Result<serde_json::Value, LLMError> {
         let api_key = self
             .api_key
@@ -451,6 +494,8 @@ impl OpenAiCompatibleProvider {
             messages,
             temperature: Some(temperature),
             max_tokens: Some(max_tokens),
+            top_p: sampling.top_p,
+            stop: sampling.stop.clone(),
             tools: if tools.is_empty() { None } else { Some(tools) },
             tool_choice,
         };
@@ -566,6 +611,65 @@ impl OpenAiCompatibleProvider {
 
         Ok(response.status().is_success())
     }
+
+    /// Lists the model IDs served at `{base_url}/models`.
+    #[instrument(name = "openai_compat_list_models", skip(self), fields(provider = %self.provider_name))]
+    pub async fn list_models(&self) -> Result<Vec<String>, LLMError> {
+        let response = self
+            .models_request()
+            .await?
+            .send()
+            .await
+            .map_err(|e| LLMError::ConnectionError(format!("Connection failed: {}", e)))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| LLMError::RequestFailed(format!("Failed to read response body: {}", e)))?;
+
+        if !status.is_success() {
+            return Err(LLMError::RequestFailed(format!(
+                "{} API error ({}): {}",
+                self.provider_name,
+                status,
+                &body[..body.len().min(500)]
+            )));
+        }
+
+        let models = parse_model_ids(&body).map_err(|e| {
+            LLMError::RequestFailed(format!(
+                "Failed to parse {} models list: {}",
+                self.provider_name, e
+            ))
+        })?;
+
+        info!(provider = %self.provider_name, count = models.len(), "Listed provider models");
+        Ok(models)
+    }
+
+    /// Lists the models served at `{base_url}/models` with the metadata the
+    /// provider reports.
+    #[instrument(name = "openai_compat_list_model_metadata", skip(self), fields(provider = %self.provider_name))]
+    pub async fn list_model_metadata(&self) -> Result<Vec<ModelMetadata>, LLMError> {
+        let request = self.models_request().await?;
+        let body = model_discovery::fetch_json(request, &self.provider_name).await?;
+        Ok(model_discovery::parse_openai_models(&body))
+    }
+
+    /// Request to `{base_url}/models`, authenticated only when an API key is
+    /// stored.
+    async fn models_request(&self) -> Result<reqwest::RequestBuilder, LLMError> {
+        let base_url = self.base_url.read().await.clone().ok_or_else(|| {
+            LLMError::NotConfigured(format!("Base URL not set for {}", self.provider_name))
+        })?;
+
+        let request = self.http_client.get(format!("{}/models", base_url));
+        Ok(match self.api_key.read().await.as_deref() {
+            Some(api_key) => request.header("Authorization", format!("Bearer {}", api_key)),
+            None => request,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -663,6 +767,40 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_list_models_without_api_key() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 4096];
+            let len = stream.read(&mut request).await.unwrap();
+            let body = r#"{"object": "list", "data": [{"id": "local-model"}]}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..len]).to_lowercase()
+        });
+
+        let http_client = Arc::new(reqwest::Client::new());
+        let provider = OpenAiCompatibleProvider::new("local", http_client);
+        assert!(provider.list_models().await.is_err());
+
+        provider.set_base_url(&format!("{}/", base_url)).await;
+        assert!(!provider.is_configured());
+        let models = provider.list_models().await.expect("keyless listing");
+        assert_eq!(models, vec!["local-model"]);
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("get /v1/models "));
+        assert!(!request.contains("authorization:"));
+    }
+
     #[test]
     fn test_deserialize_standard_content() {
         let json = r#"{"role": "assistant", "content": "Hello world"}"#;
@@ -682,4 +820,20 @@ mod tests {
         let msg: ChatResponseMessage = serde_json::from_str(json).expect("parse should succeed");
         assert_eq!(msg.content, "The answer is 42");
     }
+
+    #[test]
+    fn test_parse_model_ids() {
+        let body = r#"{
+            "object": "list",
+            "data": [
+                {"id": "qwen2.5-7b-instruct", "object": "model", "owned_by": "organization_owner"},
+                {"id": "llama-3.1-8b", "object": "model"},
+                {"id": "qwen2.5-7b-instruct", "object": "model"}
+            ]
+        }"#;
+        let ids = parse_model_ids(body).expect("parse should succeed");
+        assert_eq!(ids, vec!["llama-3.1-8b", "qwen2.5-7b-instruct"]);
+
+        assert!(parse_model_ids(r#"{"models": []}"#).is_err());
+    }
 }
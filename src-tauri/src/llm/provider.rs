@@ -20,7 +20,7 @@ use thiserror::Error;
 
 /// LLM provider type.
 ///
-/// Mistral and Ollama are builtin providers with dedicated implementations.
+/// Mistral, Ollama and Gemini are builtin providers with dedicated implementations.
 /// Custom(String) represents user-created OpenAI-compatible providers
 /// (RouterLab, OpenRouter, Together AI, etc.).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -29,6 +29,8 @@ pub enum ProviderType {
     Mistral,
     /// Local Ollama server
     Ollama,
+    /// Google Gemini cloud API
+    Gemini,
     /// User-created OpenAI-compatible provider (e.g., Custom("routerlab"))
     Custom(String),
 }
@@ -38,6 +40,7 @@ impl Serialize for ProviderType {
         match self {
             ProviderType::Mistral => s.serialize_str("mistral"),
             ProviderType::Ollama => s.serialize_str("ollama"),
+            ProviderType::Gemini => s.serialize_str("gemini"),
             ProviderType::Custom(name) => s.serialize_str(name),
         }
     }
@@ -49,6 +52,7 @@ impl<'de> Deserialize<'de> for ProviderType {
         Ok(match s.as_str() {
             "mistral" => ProviderType::Mistral,
             "ollama" => ProviderType::Ollama,
+            "gemini" => ProviderType::Gemini,
             other => ProviderType::Custom(other.to_string()),
         })
     }
@@ -59,6 +63,7 @@ impl std::fmt::Display for ProviderType {
         match self {
             ProviderType::Mistral => write!(f, "Mistral"),
             ProviderType::Ollama => write!(f, "Ollama"),
+            ProviderType::Gemini => write!(f, "Gemini"),
             ProviderType::Custom(name) => write!(f, "{}", name),
         }
     }
@@ -71,6 +76,7 @@ impl std::str::FromStr for ProviderType {
         match s.to_lowercase().as_str() {
             "mistral" => Ok(ProviderType::Mistral),
             "ollama" => Ok(ProviderType::Ollama),
+            "gemini" => Ok(ProviderType::Gemini),
             other => {
                 if other.is_empty() {
                     Err(LLMError::InvalidProvider(s.to_string()))
@@ -99,6 +105,27 @@ pub struct LLMResponse {
     pub finish_reason: Option<String>,
 }
 
+/// Optional sampling controls forwarded to tool-enabled completions.
+///
+/// Temperature and max tokens stay explicit arguments; these are the extra
+/// knobs a single request may override. Empty values leave the provider default.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SamplingOptions {
+    /// Nucleus sampling probability mass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Sequences that stop generation when produced
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+}
+
+impl SamplingOptions {
+    /// Returns true when no option overrides the provider default
+    pub fn is_empty(&self) -> bool {
+        self.top_p.is_none() && self.stop.is_empty()
+    }
+}
+
 /// LLM error types
 #[derive(Debug, Error)]
 #[allow(dead_code)]
@@ -138,6 +165,10 @@ pub enum LLMError {
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// Request abandoned because the workflow was cancelled
+    #[error("Request cancelled by the user")]
+    Cancelled,
 }
 
 impl From<anyhow::Error> for LLMError {
@@ -209,6 +240,7 @@ mod tests {
     fn test_provider_type_display() {
         assert_eq!(ProviderType::Mistral.to_string(), "Mistral");
         assert_eq!(ProviderType::Ollama.to_string(), "Ollama");
+        assert_eq!(ProviderType::Gemini.to_string(), "Gemini");
         assert_eq!(
             ProviderType::Custom("routerlab".to_string()).to_string(),
             "routerlab"
@@ -233,6 +265,10 @@ mod tests {
             "OLLAMA".parse::<ProviderType>().unwrap(),
             ProviderType::Ollama
         );
+        assert_eq!(
+            "Gemini".parse::<ProviderType>().unwrap(),
+            ProviderType::Gemini
+        );
     }
 
     #[test]
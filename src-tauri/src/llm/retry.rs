@@ -18,6 +18,11 @@
 //! It handles transient failures (network issues, rate limits, server errors)
 //! while failing fast on non-recoverable errors (auth failures, bad requests).
 //!
+//! The policy is configurable at runtime (`settings:llm_retry`, see
+//! `update_llm_retry_settings`). Delays are jittered so that concurrent
+//! workflows hitting the same rate limit do not retry in lockstep, and each
+//! retry can be reported to an observer (e.g. as an `llm_retry` stream chunk).
+//!
 //! ## Usage
 //!
 //! ```rust,ignore
@@ -31,15 +36,20 @@
 //! ).await;
 //! ```
 
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::future::Future;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, warn};
+use uuid::Uuid;
 
 use super::provider::LLMError;
 
 /// Configuration for retry behavior
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct RetryConfig {
     /// Maximum number of retry attempts (not including the initial attempt)
     pub max_retries: u32,
@@ -49,6 +59,8 @@ pub struct RetryConfig {
     pub max_delay_ms: u64,
     /// Multiplier for exponential backoff (default: 2.0)
     pub backoff_multiplier: f64,
+    /// Fraction of each delay that is randomized (0.0 = none, 1.0 = full jitter)
+    pub jitter: f64,
 }
 
 impl Default for RetryConfig {
@@ -58,19 +70,21 @@ impl Default for RetryConfig {
             initial_delay_ms: 1000,
             max_delay_ms: 30000,
             backoff_multiplier: 2.0,
+            jitter: 0.2,
         }
     }
 }
 
-#[allow(dead_code)]
 impl RetryConfig {
     /// Creates a new RetryConfig with custom values
+    #[allow(dead_code)] // Used by tests and custom configurations
     pub fn new(max_retries: u32, initial_delay_ms: u64, max_delay_ms: u64) -> Self {
         Self {
             max_retries,
             initial_delay_ms,
             max_delay_ms,
             backoff_multiplier: 2.0,
+            jitter: 0.2,
         }
     }
 
@@ -81,13 +95,54 @@ impl RetryConfig {
         let clamped_ms = delay_ms.min(self.max_delay_ms as f64) as u64;
         Duration::from_millis(clamped_ms)
     }
+
+    /// Calculates the jittered delay for a given attempt number (0-indexed)
+    ///
+    /// The delay is drawn uniformly between `(1 - jitter) * delay` and `delay`.
+    pub fn jittered_delay_for_attempt(&self, attempt: u32) -> Duration {
+        let delay = self.delay_for_attempt(attempt);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        if jitter == 0.0 {
+            return delay;
+        }
+        let random = (Uuid::new_v4().as_u128() % 10_000) as f64 / 10_000.0;
+        delay.mul_f64(1.0 - jitter * random)
+    }
+}
+
+/// Retry notified to the observer before waiting for the next attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryEvent {
+    /// Retry number (1-indexed)
+    pub attempt: u32,
+    /// Maximum number of retries
+    pub max_retries: u32,
+    /// Delay before the retry (milliseconds)
+    pub delay_ms: u64,
+    /// Error of the failed attempt
+    pub error: String,
+}
+
+/// Observer notified of each retry (e.g. to emit a stream event)
+pub type RetryObserver<'a> = dyn Fn(&RetryEvent) + Send + Sync + 'a;
+
+/// HTTP status code in provider error messages, e.g. "Mistral API error (429 Too Many Requests)"
+static STATUS_CODE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\((\d{3})\b").expect("valid status code regex"));
+
+/// Extracts the HTTP status code from a provider error message
+fn status_code(message: &str) -> Option<u16> {
+    STATUS_CODE
+        .captures(message)
+        .and_then(|caps| caps[1].parse().ok())
 }
 
 /// Determines if an LLM error is retryable
 ///
 /// Retryable errors:
 /// - ConnectionError: Network issues, transient failures
-/// - RequestFailed: May be rate limit (429) or server error (5xx)
+/// - RequestFailed: Rate limit (429), timeout (408) or server error (5xx),
+///   or no HTTP status in the message (request or body read failure)
 /// - StreamingError: May be transient network issue
 ///
 /// Non-retryable errors:
@@ -97,11 +152,16 @@ impl RetryConfig {
 /// - ModelNotFound: Invalid model, won't fix itself
 /// - CircuitOpen: Provider temporarily unavailable, circuit breaker handles recovery
 /// - Internal: Programming error, won't fix itself
+/// - RequestFailed with another 4xx status: Bad request or auth, won't fix itself
 pub fn is_retryable(error: &LLMError) -> bool {
-    matches!(
-        error,
-        LLMError::ConnectionError(_) | LLMError::RequestFailed(_) | LLMError::StreamingError(_)
-    )
+    match error {
+        LLMError::ConnectionError(_) | LLMError::StreamingError(_) => true,
+        LLMError::RequestFailed(message) => match status_code(message) {
+            Some(status) => status == 408 || status == 429 || status >= 500,
+            None => true,
+        },
+        _ => false,
+    }
 }
 
 /// Executes an async operation with retry logic and exponential backoff
@@ -124,6 +184,22 @@ pub fn is_retryable(error: &LLMError) -> bool {
 /// ).await;
 /// ```
 pub async fn with_retry<F, T, Fut>(operation: F, config: &RetryConfig) -> Result<T, LLMError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, LLMError>>,
+{
+    with_retry_observed(operation, config, None).await
+}
+
+/// Executes an async operation with retry logic, notifying each retry
+///
+/// Same as [`with_retry`], with `on_retry` called before waiting for each
+/// retry.
+pub async fn with_retry_observed<F, T, Fut>(
+    operation: F,
+    config: &RetryConfig,
+    on_retry: Option<&RetryObserver<'_>>,
+) -> Result<T, LLMError>
 where
     F: Fn() -> Fut,
     Fut: Future<Output = Result<T, LLMError>>,
@@ -163,7 +239,7 @@ where
                 }
 
                 // Calculate delay and wait
-                let delay = config.delay_for_attempt(attempt);
+                let delay = config.jittered_delay_for_attempt(attempt);
                 warn!(
                     attempt = attempt + 1,
                     max_retries = config.max_retries,
@@ -171,6 +247,14 @@ where
                     error = %error,
                     "Retrying after transient error"
                 );
+                if let Some(on_retry) = on_retry {
+                    on_retry(&RetryEvent {
+                        attempt: attempt + 1,
+                        max_retries: config.max_retries,
+                        delay_ms: delay.as_millis() as u64,
+                        error: error.to_string(),
+                    });
+                }
 
                 sleep(delay).await;
                 attempt += 1;
@@ -192,6 +276,31 @@ mod tests {
         assert_eq!(config.initial_delay_ms, 1000);
         assert_eq!(config.max_delay_ms, 30000);
         assert!((config.backoff_multiplier - 2.0).abs() < f64::EPSILON);
+        assert!((config.jitter - 0.2).abs() < f64::EPSILON);
+
+        // Stored settings may omit fields
+        let stored: RetryConfig = serde_json::from_str(r#"{"max_retries": 5}"#).unwrap();
+        assert_eq!(stored.max_retries, 5);
+        assert_eq!(stored.initial_delay_ms, 1000);
+    }
+
+    #[test]
+    fn test_jittered_delay_within_bounds() {
+        let config = RetryConfig::default();
+        for _ in 0..50 {
+            let delay = config.jittered_delay_for_attempt(1);
+            assert!(delay >= Duration::from_millis(1600));
+            assert!(delay <= Duration::from_millis(2000));
+        }
+
+        let config = RetryConfig {
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.jittered_delay_for_attempt(1),
+            Duration::from_millis(2000)
+        );
     }
 
     #[test]
@@ -245,6 +354,20 @@ mod tests {
         assert!(!is_retryable(&LLMError::ModelNotFound("gpt-4".to_string())));
         assert!(!is_retryable(&LLMError::CircuitOpen("mistral".to_string())));
         assert!(!is_retryable(&LLMError::Internal("bug".to_string())));
+
+        // HTTP status in provider error messages
+        assert!(is_retryable(&LLMError::RequestFailed(
+            "Mistral API error (429 Too Many Requests): rate limited".to_string()
+        )));
+        assert!(is_retryable(&LLMError::RequestFailed(
+            "Groq API error (503 Service Unavailable): overloaded".to_string()
+        )));
+        assert!(!is_retryable(&LLMError::RequestFailed(
+            "Mistral API error (400 Bad Request): invalid model".to_string()
+        )));
+        assert!(!is_retryable(&LLMError::RequestFailed(
+            "Mistral API error (401 Unauthorized): invalid key".to_string()
+        )));
     }
 
     #[tokio::test]
@@ -298,6 +421,42 @@ mod tests {
         assert_eq!(call_count.load(Ordering::SeqCst), 3); // 2 failures + 1 success
     }
 
+    #[tokio::test]
+    async fn test_retry_notifies_observer() {
+        let config = RetryConfig::new(3, 10, 100);
+        let call_count = Arc::new(AtomicU32::new(0));
+        let call_count_clone = call_count.clone();
+        let events = std::sync::Mutex::new(Vec::new());
+        let on_retry = |event: &RetryEvent| events.lock().unwrap().push(event.clone());
+
+        let result = with_retry_observed(
+            || {
+                let count = call_count_clone.clone();
+                async move {
+                    if count.fetch_add(1, Ordering::SeqCst) < 2 {
+                        Err(LLMError::RequestFailed(
+                            "API error (429 Too Many Requests)".to_string(),
+                        ))
+                    } else {
+                        Ok::<_, LLMError>("success".to_string())
+                    }
+                }
+            },
+            &config,
+            Some(&on_retry),
+        )
+        .await;
+
+        assert!(result.is_ok());
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].attempt, 1);
+        assert_eq!(events[1].attempt, 2);
+        assert_eq!(events[1].max_retries, 3);
+        assert!(events[0].error.contains("429"));
+        assert!(events[1].delay_ms <= 20);
+    }
+
     #[tokio::test]
     async fn test_retry_max_exceeded() {
         let config = RetryConfig::new(2, 10, 100); // Short delays, max 2 retries
@@ -24,6 +24,7 @@
 //!
 //! The adapters handle these differences transparently.
 
+use crate::llm::utils::multimodal_content;
 use crate::models::function_calling::{FunctionCall, FunctionCallResult, ToolChoiceMode};
 use crate::tools::ToolDefinition;
 use serde_json::Value;
@@ -99,6 +100,23 @@ pub trait ProviderToolAdapter: Send + Sync {
     /// ```
     fn format_tool_result(&self, result: &FunctionCallResult) -> Value;
 
+    /// Formats images produced by tools (screenshots) as a message following
+    /// the tool results, so the model can see them.
+    ///
+    /// # Arguments
+    /// * `text` - Caption of the images
+    /// * `image_urls` - Data URLs of the images
+    ///
+    /// # Returns
+    /// A multimodal user message, or `None` when the provider rejects a user
+    /// message between tool results and the next assistant turn
+    fn format_tool_images(&self, text: &str, image_urls: &[String]) -> Option<Value> {
+        Some(serde_json::json!({
+            "role": "user",
+            "content": multimodal_content(text, image_urls),
+        }))
+    }
+
     /// Converts our `ToolChoiceMode` to the provider's specific format.
     ///
     /// # Arguments
@@ -124,6 +142,20 @@ pub trait ProviderToolAdapter: Send + Sync {
     /// The text content if present, `None` if the response only contains tool calls
     fn extract_content(&self, response: &Value) -> Option<String>;
 
+    /// Extracts the reasoning trace of a reasoning model from the response.
+    ///
+    /// The trace is kept out of [`extract_content`](Self::extract_content), so
+    /// the answer never contains the model's thinking.
+    ///
+    /// # Arguments
+    /// * `response` - The raw JSON response from the provider
+    ///
+    /// # Returns
+    /// The reasoning text if the model exposed one
+    fn extract_reasoning(&self, _response: &Value) -> Option<String> {
+        None
+    }
+
     /// Checks if the response contains tool calls.
     ///
     /// # Arguments
@@ -229,6 +261,79 @@ pub mod helpers {
     pub fn parse_arguments_string(args_str: &str) -> Value {
         serde_json::from_str(args_str).unwrap_or(json!({}))
     }
+
+    /// Splits the `<think>...</think>` block that some reasoning models
+    /// (DeepSeek-R1, QwQ) write before their answer.
+    ///
+    /// Returns (reasoning, answer). An unterminated block (output cut by the
+    /// token limit) is all reasoning.
+    pub fn split_think_tags(content: &str) -> (Option<String>, String) {
+        let Some(rest) = content.trim_start().strip_prefix("<think>") else {
+            return (None, content.to_string());
+        };
+        let (reasoning, answer) = rest.split_once("</think>").unwrap_or((rest, ""));
+        let reasoning = reasoning.trim();
+        (
+            (!reasoning.is_empty()).then(|| reasoning.to_string()),
+            answer.trim_start().to_string(),
+        )
+    }
+
+    /// Concatenates the blocks of one type (`text` or `thinking`) of an
+    /// array-form message content, e.g.
+    /// `[{"type": "thinking", "thinking": [{"type": "text", "text": "..."}]},
+    /// {"type": "text", "text": "..."}]`.
+    pub fn content_blocks_text(content: &Value, block_type: &str) -> Option<String> {
+        let texts: Vec<String> = content
+            .as_array()?
+            .iter()
+            .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some(block_type))
+            .filter_map(|block| match block.get(block_type)? {
+                Value::String(text) => Some(text.clone()),
+                Value::Array(parts) => Some(
+                    parts
+                        .iter()
+                        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+                        .collect(),
+                ),
+                _ => None,
+            })
+            .collect();
+        (!texts.is_empty()).then(|| texts.concat())
+    }
+
+    /// Splits the content of an assistant message into (reasoning, answer).
+    ///
+    /// Handles plain strings (with an optional leading `<think>` block) and
+    /// array content with `thinking` and `text` blocks.
+    pub fn split_reasoning(content: Option<&Value>) -> (Option<String>, Option<String>) {
+        match content {
+            Some(Value::String(text)) => {
+                let (reasoning, answer) = split_think_tags(text);
+                (reasoning, Some(answer))
+            }
+            Some(blocks @ Value::Array(_)) => (
+                content_blocks_text(blocks, "thinking"),
+                content_blocks_text(blocks, "text"),
+            ),
+            _ => (None, None),
+        }
+    }
+
+    /// Removes the reasoning from an assistant message sent back in the
+    /// conversation history: it only costs context, and some APIs reject
+    /// their own reasoning fields in requests.
+    pub fn strip_reasoning(mut message: Value) -> Value {
+        if let (Some(_), answer) = split_reasoning(message.get("content")) {
+            message["content"] = json!(answer.unwrap_or_default());
+        }
+        if let Some(fields) = message.as_object_mut() {
+            for field in ["reasoning_content", "reasoning", "thinking"] {
+                fields.remove(field);
+            }
+        }
+        message
+    }
 }
 
 #[cfg(test)]
@@ -297,4 +402,52 @@ mod tests {
         assert!(invalid.is_object());
         assert!(invalid.as_object().unwrap().is_empty());
     }
+
+    #[test]
+    fn test_split_think_tags() {
+        assert_eq!(
+            split_think_tags("<think>\nCheck units.\n</think>\n\n42 km"),
+            (Some("Check units.".to_string()), "42 km".to_string())
+        );
+        assert_eq!(
+            split_think_tags("No reasoning <think>here</think>"),
+            (None, "No reasoning <think>here</think>".to_string())
+        );
+        // Output cut inside the reasoning
+        assert_eq!(
+            split_think_tags("<think>Still thinking"),
+            (Some("Still thinking".to_string()), String::new())
+        );
+        assert_eq!(
+            split_think_tags("<think></think>Answer"),
+            (None, "Answer".to_string())
+        );
+    }
+
+    #[test]
+    fn test_split_reasoning_content_blocks() {
+        let content = json!([
+            {"type": "thinking", "thinking": [{"type": "text", "text": "Let me think..."}]},
+            {"type": "text", "text": "The answer"}
+        ]);
+        assert_eq!(
+            split_reasoning(Some(&content)),
+            (
+                Some("Let me think...".to_string()),
+                Some("The answer".to_string())
+            )
+        );
+        assert_eq!(split_reasoning(Some(&Value::Null)), (None, None));
+
+        let message = strip_reasoning(json!({
+            "role": "assistant",
+            "content": content,
+            "reasoning_content": "Let me think...",
+            "tool_calls": []
+        }));
+        assert_eq!(
+            message,
+            json!({"role": "assistant", "content": "The answer", "tool_calls": []})
+        );
+    }
 }
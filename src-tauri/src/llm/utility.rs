@@ -0,0 +1,155 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Utility Model for Internal LLM Passes
+//!
+//! Summarization and extraction passes (history compression, memory
+//! consolidation, title generation...) do not need the agent's main model.
+//! The utility model is a cheap or local model configured once
+//! (`settings:utility_model`, see `update_utility_model_settings`) and used by
+//! all of them through `ProviderManager::complete_utility`.
+//!
+//! When no utility model is set, or its provider is not configured, passes
+//! fall back to the model of the agent that triggered them.
+
+use serde::{Deserialize, Serialize};
+
+use super::provider::ProviderType;
+
+/// Default sampling temperature of utility passes
+pub const DEFAULT_UTILITY_TEMPERATURE: f32 = 0.2;
+
+/// Default maximum output tokens of utility passes
+pub const DEFAULT_UTILITY_MAX_TOKENS: usize = 1024;
+
+/// Model used by internal summarization and extraction passes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UtilityModelConfig {
+    /// Provider name (builtin or custom, e.g. "ollama")
+    pub provider: String,
+    /// Model identifier used in API calls
+    pub model: String,
+    /// Sampling temperature
+    #[serde(default = "default_temperature")]
+    pub temperature: f32,
+    /// Maximum output tokens
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: usize,
+}
+
+fn default_temperature() -> f32 {
+    DEFAULT_UTILITY_TEMPERATURE
+}
+
+fn default_max_tokens() -> usize {
+    DEFAULT_UTILITY_MAX_TOKENS
+}
+
+/// Provider, model and sampling parameters resolved for a utility pass
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtilityTarget {
+    /// Provider to call
+    pub provider: ProviderType,
+    /// Model to use
+    pub model: String,
+    /// Sampling temperature
+    pub temperature: f32,
+    /// Maximum output tokens
+    pub max_tokens: usize,
+    /// Whether the configured utility model is used (false on fallback)
+    pub is_utility: bool,
+}
+
+impl UtilityTarget {
+    /// Resolves the target of a utility pass.
+    ///
+    /// Uses `config` when set and `is_configured` accepts its provider,
+    /// otherwise the fallback provider and model with the default parameters.
+    pub fn resolve(
+        config: Option<&UtilityModelConfig>,
+        is_configured: impl Fn(&ProviderType) -> bool,
+        fallback_provider: ProviderType,
+        fallback_model: &str,
+    ) -> Self {
+        let utility = config.and_then(|config| {
+            let provider = config.provider.parse::<ProviderType>().ok()?;
+            is_configured(&provider).then(|| Self {
+                provider,
+                model: config.model.clone(),
+                temperature: config.temperature,
+                max_tokens: config.max_tokens,
+                is_utility: true,
+            })
+        });
+
+        utility.unwrap_or_else(|| Self {
+            provider: fallback_provider,
+            model: fallback_model.to_string(),
+            temperature: DEFAULT_UTILITY_TEMPERATURE,
+            max_tokens: DEFAULT_UTILITY_MAX_TOKENS,
+            is_utility: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_utility_target() {
+        let config = UtilityModelConfig {
+            provider: "ollama".to_string(),
+            model: "llama3.2:3b".to_string(),
+            temperature: 0.1,
+            max_tokens: 512,
+        };
+
+        let target = UtilityTarget::resolve(
+            Some(&config),
+            |_| true,
+            ProviderType::Mistral,
+            "mistral-large-latest",
+        );
+        assert!(target.is_utility);
+        assert_eq!(target.provider, ProviderType::Ollama);
+        assert_eq!(target.model, "llama3.2:3b");
+        assert_eq!(target.max_tokens, 512);
+
+        // Unconfigured utility provider: fall back to the agent model
+        let target = UtilityTarget::resolve(
+            Some(&config),
+            |provider| *provider != ProviderType::Ollama,
+            ProviderType::Mistral,
+            "mistral-large-latest",
+        );
+        assert!(!target.is_utility);
+        assert_eq!(target.provider, ProviderType::Mistral);
+        assert_eq!(target.model, "mistral-large-latest");
+        assert_eq!(target.temperature, DEFAULT_UTILITY_TEMPERATURE);
+
+        let target =
+            UtilityTarget::resolve(None, |_| true, ProviderType::Gemini, "gemini-2.5-flash");
+        assert!(!target.is_utility);
+        assert_eq!(target.provider, ProviderType::Gemini);
+    }
+
+    #[test]
+    fn test_utility_config_defaults() {
+        let config: UtilityModelConfig =
+            serde_json::from_str(r#"{"provider": "ollama", "model": "qwen3:4b"}"#).unwrap();
+        assert_eq!(config.temperature, DEFAULT_UTILITY_TEMPERATURE);
+        assert_eq!(config.max_tokens, DEFAULT_UTILITY_MAX_TOKENS);
+    }
+}
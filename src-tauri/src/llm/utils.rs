@@ -20,8 +20,11 @@
 //!
 //! - [`estimate_tokens`] - Estimates token count using word-based approximation
 //! - [`simulate_streaming`] - Simulates streaming by chunking a complete response
+//! - [`multimodal_content`] / [`content_text`] / [`content_images`] - Image
+//!   attachments in OpenAI-format message content
 
 use super::LLMError;
+use serde_json::{json, Value};
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::warn;
@@ -114,6 +117,66 @@ pub fn simulate_streaming(
     rx
 }
 
+// ============================================================================
+// Multimodal Content
+// ============================================================================
+
+/// Builds the content of a user message with images.
+///
+/// Messages stay in OpenAI format across providers: with images, the content
+/// is an array of `text` and `image_url` parts (data URLs); without, a string.
+/// Providers with another format convert it (see [`content_text`] and
+/// [`content_images`]).
+pub fn multimodal_content(text: &str, image_urls: &[String]) -> Value {
+    if image_urls.is_empty() {
+        return Value::String(text.to_string());
+    }
+    let mut parts = vec![json!({"type": "text", "text": text})];
+    parts.extend(
+        image_urls
+            .iter()
+            .map(|url| json!({"type": "image_url", "image_url": {"url": url}})),
+    );
+    Value::Array(parts)
+}
+
+/// Returns the text of a message content (string or array of parts).
+pub fn content_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|part| part.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Returns the images of a message content as `(mime_type, base64 data)`.
+///
+/// Only data URLs are returned; a string content has no images.
+pub fn content_images(content: &Value) -> Vec<(String, String)> {
+    let Some(parts) = content.as_array() else {
+        return Vec::new();
+    };
+    parts
+        .iter()
+        .filter(|part| part.get("type").and_then(|t| t.as_str()) == Some("image_url"))
+        .filter_map(|part| {
+            part.pointer("/image_url/url")
+                .or_else(|| part.get("image_url"))
+                .and_then(|u| u.as_str())
+        })
+        .filter_map(|url| {
+            let (header, data) = url.strip_prefix("data:")?.split_once(',')?;
+            let mime = header.strip_suffix(";base64")?;
+            Some((mime.to_string(), data.to_string()))
+        })
+        .collect()
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -217,4 +280,32 @@ mod tests {
 
         assert_eq!(chunk_count, 4); // 20 / 5 = 4 chunks
     }
+
+    #[test]
+    fn test_multimodal_content() {
+        assert_eq!(multimodal_content("Hi", &[]), json!("Hi"));
+
+        let content = multimodal_content(
+            "What is this?",
+            &["data:image/png;base64,iVBORw0KGgo=".to_string()],
+        );
+        assert_eq!(content[0]["type"], "text");
+        assert_eq!(
+            content[1]["image_url"]["url"],
+            "data:image/png;base64,iVBORw0KGgo="
+        );
+
+        assert_eq!(content_text(&content), "What is this?");
+        assert_eq!(content_text(&json!("plain")), "plain");
+        assert_eq!(
+            content_images(&content),
+            vec![("image/png".to_string(), "iVBORw0KGgo=".to_string())]
+        );
+        assert!(content_images(&json!("plain")).is_empty());
+
+        // Remote URLs are not inlined
+        let remote =
+            json!([{"type": "image_url", "image_url": {"url": "https://example.com/a.png"}}]);
+        assert!(content_images(&remote).is_empty());
+    }
 }
@@ -16,11 +16,24 @@
 
 mod agents;
 mod commands;
+mod config_file;
 mod db;
+mod diagnostics;
+mod documents;
+mod inbox;
+mod jobs;
 mod llm;
 mod mcp;
+mod metrics;
 mod models;
+mod plugins;
+mod profiles;
+mod proxy;
+mod scheduler;
 mod security;
+mod settings_sync;
+mod speech;
+mod startup;
 mod state;
 mod tools;
 
@@ -40,10 +53,12 @@ use tracing_subscriber::{
 ///
 /// In debug mode, uses pretty console output.
 /// In release mode, uses JSON format for machine parsing.
-/// Controlled via RUST_LOG environment variable (default: info).
-fn init_tracing() {
-    let env_filter = EnvFilter::try_from_default_env()
-        .unwrap_or_else(|_| EnvFilter::new("zileo_chat=info,warn"));
+/// Controlled via RUST_LOG environment variable or the `log_level` of the
+/// config file (default: info). Recent events are also kept in memory for
+/// the diagnostics bundle.
+fn init_tracing(filter: &str) {
+    let env_filter = EnvFilter::try_new(filter)
+        .unwrap_or_else(|_| EnvFilter::new(tools::constants::config_file::DEFAULT_LOG_FILTER));
 
     // Use JSON format in release, pretty format in debug
     #[cfg(not(debug_assertions))]
@@ -57,6 +72,7 @@ fn init_tracing() {
         tracing_subscriber::registry()
             .with(env_filter)
             .with(json_layer)
+            .with(diagnostics::logs::RecentLogsLayer)
             .init();
     }
 
@@ -71,117 +87,51 @@ fn init_tracing() {
         tracing_subscriber::registry()
             .with(env_filter)
             .with(fmt_layer)
+            .with(diagnostics::logs::RecentLogsLayer)
             .init();
     }
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize structured logging
-    init_tracing();
-
-    // Get database path
+    // Get home directory
     let app_data_dir = std::env::var("HOME")
         .or_else(|_| std::env::var("USERPROFILE"))
         .unwrap_or_else(|_| ".".to_string());
 
-    let db_path = format!("{}/.zileo/db", app_data_dir);
-    std::fs::create_dir_all(format!("{}/.zileo", app_data_dir))?;
-
-    // Initialize AppState
-    let app_state = AppState::new(&db_path)
-        .await
-        .expect("Failed to initialize AppState");
-
-    tracing::info!("Application state initialized");
-
-    // Note: Agents are loaded in setup hook after app_handle is set
-    // This ensures AgentToolContext has access to app_handle for validation events
-
-    // Load MCP servers from database
-    if let Err(e) = app_state.mcp_manager.load_from_db().await {
-        tracing::warn!(error = %e, "Failed to load MCP servers from database");
-    } else {
-        let count = app_state.mcp_manager.connected_count().await;
-        tracing::info!(count = count, "MCP servers loaded from database");
-    }
-
-    // Seed builtin LLM models if needed
-    {
-        use models::llm_models::get_all_builtin_models;
-
-        let builtin_models = get_all_builtin_models();
-        let mut inserted = 0;
+    // Data directory (database of the active profile, see profiles.rs)
+    let data_dir = std::path::Path::new(&app_data_dir).join(".zileo");
+    std::fs::create_dir_all(&data_dir)?;
 
-        for model in &builtin_models {
-            let check_query = format!(
-                "SELECT count() FROM llm_model WHERE id = '{}' GROUP ALL",
-                model.id
-            );
-            let count_result: Vec<serde_json::Value> = app_state
-                .db
-                .db
-                .query(&check_query)
-                .await
-                .map(|mut r| r.take(0).unwrap_or_default())
-                .unwrap_or_default();
+    // Optional config file, read before logging starts (log filter) and
+    // before any HTTP client is built (proxy)
+    let config = config_file::LoadedConfig::load(&data_dir);
+    config.apply_proxy();
+    proxy::init(&config);
 
-            let exists = count_result
-                .first()
-                .and_then(|v| v.get("count").and_then(|c| c.as_i64()))
-                .unwrap_or(0)
-                > 0;
-
-            if !exists {
-                let insert_query = format!(
-                    "CREATE llm_model:`{}` CONTENT {{ \
-                        id: '{}', \
-                        provider: '{}', \
-                        name: '{}', \
-                        api_name: '{}', \
-                        context_window: {}, \
-                        max_output_tokens: {}, \
-                        temperature_default: {}, \
-                        is_builtin: true, \
-                        created_at: time::now(), \
-                        updated_at: time::now() \
-                    }}",
-                    model.id,
-                    model.id,
-                    model.provider,
-                    model.name.replace('\'', "''"),
-                    model.api_name.replace('\'', "''"),
-                    model.context_window,
-                    model.max_output_tokens,
-                    model.temperature_default
-                );
-
-                if app_state.db.execute(&insert_query).await.is_ok() {
-                    inserted += 1;
-                }
-            }
-        }
-
-        if inserted > 0 {
-            tracing::info!(
-                total = builtin_models.len(),
-                inserted = inserted,
-                "Builtin LLM models seeded"
-            );
-        } else {
-            tracing::debug!("All builtin LLM models already exist");
-        }
+    // Initialize structured logging
+    init_tracing(config.log_filter());
+    if let Some(error) = config.error() {
+        tracing::warn!(path = %config.path().display(), error = %error, "Config file ignored");
     }
 
-    // Initialize secure keystore
-    let keystore = commands::SecureKeyStore::default();
+    // Initialize AppState and the secure keystore (also stores MCP OAuth tokens)
+    // concurrently: both only touch the disk and do not depend on each other
+    let (app_state, keystore) = tokio::join!(
+        AppState::for_active_profile(&data_dir, config),
+        tokio::task::spawn_blocking(commands::SecureKeyStore::default)
+    );
+    let app_state = app_state.expect("Failed to initialize AppState");
+    tracing::info!("Application state initialized");
+    let keystore = keystore.expect("Failed to initialize secure keystore");
     tracing::info!("Secure keystore initialized");
+    app_state
+        .mcp_manager
+        .set_oauth_store(std::sync::Arc::new(keystore.clone()))
+        .await;
 
-    // Initialize LLM providers from saved configuration
-    app_state.initialize_providers_from_config(&keystore).await;
-
-    // Initialize embedding service from saved configuration (if any)
-    app_state.initialize_embedding_from_config(&keystore).await;
+    // Providers, model seeding, MCP servers and agents are restored by the
+    // deferred startup in the setup hook, after the window shows
 
     // Run Tauri application
     tauri::Builder::default()
@@ -189,40 +139,73 @@ async fn main() -> anyhow::Result<()> {
         .manage(keystore)
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .invoke_handler(tauri::generate_handler![
             // Workflow commands
             commands::workflow::create_workflow,
             commands::workflow::execute_workflow,
             commands::workflow::load_workflows,
             commands::workflow::delete_workflow,
+            commands::workflow::set_workflow_language,
+            commands::workflow::load_workflows_paginated,
+            commands::workflow::set_workflow_tags,
+            commands::workflow::set_workflow_sensitive,
+            commands::workflow::set_workflow_dry_run,
+            commands::workflow::unlock_workflow,
+            commands::workflow::lock_workflow,
             commands::workflow::load_workflow_full_state,
+            // Workflow snapshot commands
+            commands::workflow_snapshot::create_workflow_snapshot,
+            commands::workflow_snapshot::stop_workflow_snapshot_server,
             // Agent commands (CRUD)
             commands::agent::list_agents,
             commands::agent::get_agent_config,
             commands::agent::create_agent,
             commands::agent::update_agent,
             commands::agent::delete_agent,
+            commands::agent::run_agent_regressions,
+            commands::agent::probe_agent,
+            commands::agent::get_agent_tool_usage,
+            commands::agent::reset_agent_tool_usage,
+            commands::agent::get_agent_tool_acknowledgments,
+            commands::agent::revoke_agent_tool_acknowledgment,
+            // Behavior changelog commands
+            commands::behavior_changelog::list_behavior_changes,
             // Security commands
             commands::security::save_api_key,
             commands::security::get_api_key,
             commands::security::delete_api_key,
             commands::security::has_api_key,
             commands::security::list_api_key_providers,
+            commands::security::rotate_api_key,
+            commands::security::list_api_key_metadata,
             // LLM commands
             commands::llm::get_llm_config,
             commands::llm::configure_mistral,
+            commands::llm::configure_gemini,
             commands::llm::configure_ollama,
             commands::llm::set_active_provider,
             commands::llm::set_default_model,
             commands::llm::get_available_models,
             commands::llm::test_ollama_connection,
+            commands::llm::get_local_llm_status,
+            commands::llm::check_local_model_fit,
             commands::llm::test_mistral_connection,
             commands::llm::test_llm_completion,
+            commands::llm::get_llm_retry_settings,
+            commands::llm::update_llm_retry_settings,
+            commands::llm::get_gemini_safety_settings,
+            commands::llm::update_gemini_safety_settings,
+            commands::llm::get_utility_model_settings,
+            commands::llm::update_utility_model_settings,
             // Custom provider commands
             commands::custom_provider::list_providers,
             commands::custom_provider::create_custom_provider,
             commands::custom_provider::update_custom_provider,
             commands::custom_provider::delete_custom_provider,
+            commands::custom_provider::list_custom_provider_models,
+            // Command palette commands
+            commands::action::search_actions,
             // Model CRUD commands
             commands::models::list_models,
             commands::models::get_model,
@@ -233,6 +216,7 @@ async fn main() -> anyhow::Result<()> {
             commands::models::get_provider_settings,
             commands::models::update_provider_settings,
             commands::models::test_provider_connection,
+            commands::models::refresh_provider_models,
             commands::models::seed_builtin_models,
             // Validation commands (Phase 5)
             commands::validation::create_validation_request,
@@ -245,6 +229,32 @@ async fn main() -> anyhow::Result<()> {
             commands::validation::get_validation_settings,
             commands::validation::update_validation_settings,
             commands::validation::reset_validation_settings,
+            // Moderation commands
+            // Audit log commands
+            commands::audit::query_audit_log,
+            commands::audit::export_audit_log,
+            // Persistence outbox commands
+            commands::outbox::flush_outbox,
+            commands::outbox::get_outbox_pending,
+            // Token anomaly settings commands
+            commands::token_anomaly::get_token_anomaly_settings,
+            commands::token_anomaly::update_token_anomaly_settings,
+            // Watchdog settings commands
+            commands::watchdog::get_watchdog_settings,
+            commands::watchdog::update_watchdog_settings,
+            // Inbox commands
+            commands::inbox::get_pending_items,
+            // Search commands
+            commands::search::search_messages,
+            commands::search::search_workflows,
+            // Transcript commands
+            commands::transcript::export_workflow_transcript,
+            // Locale settings commands
+            commands::locale::get_locale_settings,
+            commands::locale::update_locale_settings,
+            // Workspace defaults commands
+            commands::workspace_defaults::get_workspace_defaults,
+            commands::workspace_defaults::update_workspace_defaults,
             // Tool discovery for validation settings
             commands::validation::list_available_tools,
             // Memory commands (Phase 5)
@@ -254,25 +264,85 @@ async fn main() -> anyhow::Result<()> {
             commands::memory::delete_memory,
             commands::memory::search_memories,
             commands::memory::clear_memories_by_type,
+            // Knowledge pack commands
+            commands::knowledge_pack::list_knowledge_packs,
+            commands::knowledge_pack::create_knowledge_pack,
+            commands::knowledge_pack::update_knowledge_pack,
+            commands::knowledge_pack::delete_knowledge_pack,
+            commands::knowledge_pack::add_knowledge_document,
+            commands::knowledge_pack::import_knowledge_document,
+            commands::knowledge_pack::remove_knowledge_document,
+            commands::knowledge_pack::set_knowledge_pack_memories,
+            commands::knowledge_pack::attach_knowledge_pack,
+            commands::knowledge_pack::detach_knowledge_pack,
+            commands::knowledge_pack::list_agent_knowledge_packs,
+            commands::knowledge_pack::set_agent_knowledge_packs,
+            commands::knowledge_pack::search_agent_knowledge,
+            // Schedule commands
+            commands::schedule::create_schedule,
+            commands::schedule::list_schedules,
+            commands::schedule::pause_schedule,
+            commands::schedule::resume_schedule,
+            commands::schedule::delete_schedule,
+            // Background job commands
+            commands::job::enqueue_workflow,
+            commands::job::get_job_status,
             // Streaming commands (Phase 5)
             commands::streaming::execute_workflow_streaming,
             commands::streaming::cancel_workflow_streaming,
+            // Checkpoint commands (resume after a crash)
+            commands::checkpoint::list_resumable_executions,
+            commands::checkpoint::resume_workflow_execution,
+            commands::checkpoint::discard_workflow_checkpoint,
             // Message commands (Phase 6 - Message Persistence)
             commands::message::save_message,
             commands::message::load_workflow_messages,
             commands::message::load_workflow_messages_paginated,
             commands::message::delete_message,
             commands::message::clear_workflow_messages,
+            commands::message::pin_message,
+            commands::message::list_pinned_messages,
+            commands::message::bookmark_message,
+            commands::message::list_bookmarked_messages,
+            commands::message::toggle_message_reaction,
+            // Attachment commands
+            commands::attachment::get_attachment_data,
+            // Settings sync commands
+            commands::settings_sync::create_sync_pairing,
+            commands::settings_sync::accept_sync_pairing,
+            commands::settings_sync::list_sync_pairings,
+            commands::settings_sync::sync_settings_now,
+            commands::settings_sync::delete_sync_pairing,
+            // Speech commands
+            commands::speech::transcribe_audio,
+            commands::speech::get_read_aloud,
+            commands::speech::get_speech_settings,
+            commands::speech::update_speech_settings,
+            // Text-to-speech commands
+            commands::tts::synthesize_speech,
+            commands::tts::stop_speech,
+            commands::tts::get_tts_settings,
+            commands::tts::update_tts_settings,
+            commands::tts::get_workflow_voice,
+            commands::tts::set_workflow_voice,
+            // Team usage commands
+            commands::usage_stats::export_team_usage,
             // Tool execution commands (Phase 3 - Tool Execution Persistence)
             commands::tool_execution::save_tool_execution,
             commands::tool_execution::get_tool_execution,
             commands::tool_execution::load_workflow_tool_executions,
+            commands::tool_execution::load_workflow_tool_executions_page,
             commands::tool_execution::load_message_tool_executions,
             commands::tool_execution::delete_tool_execution,
             commands::tool_execution::clear_workflow_tool_executions,
+            // Execution trace commands
+            commands::execution_trace::get_execution_trace,
+            commands::execution_trace::export_workflow_trace,
             // Thinking step commands (Phase 4 - Thinking Steps Persistence)
             commands::thinking::save_thinking_step,
             commands::thinking::load_workflow_thinking_steps,
+            commands::thinking::load_workflow_thinking_steps_page,
+            commands::thinking::get_thinking_step,
             commands::thinking::load_message_thinking_steps,
             commands::thinking::delete_thinking_step,
             commands::thinking::clear_workflow_thinking_steps,
@@ -294,16 +364,62 @@ async fn main() -> anyhow::Result<()> {
             commands::mcp::create_mcp_server,
             commands::mcp::update_mcp_server,
             commands::mcp::delete_mcp_server,
+            commands::mcp::import_mcp_servers_from_json,
             commands::mcp::test_mcp_server,
             commands::mcp::start_mcp_server,
             commands::mcp::stop_mcp_server,
             commands::mcp::list_mcp_tools,
             commands::mcp::call_mcp_tool,
             commands::mcp::get_mcp_latency_metrics,
+            commands::mcp::begin_mcp_oauth,
+            commands::mcp::complete_mcp_oauth,
+            commands::mcp::list_mcp_resources,
+            commands::mcp::read_mcp_resource,
+            commands::mcp::list_mcp_prompts,
+            commands::mcp::get_mcp_prompt,
+            commands::mcp::get_mcp_server_status_history,
+            // External database commands (SqlTool)
+            commands::external_db::list_external_databases,
+            commands::external_db::save_external_database,
+            commands::external_db::delete_external_database,
+            commands::external_db::test_external_database,
+            commands::email::list_email_accounts,
+            commands::email::save_email_account,
+            commands::email::delete_email_account,
+            commands::email::test_email_account,
+            commands::feed::list_feeds,
+            commands::feed::add_feed,
+            commands::feed::delete_feed,
+            commands::feed::create_feed_digest,
+            // Desktop access permissions (ClipboardTool, ScreenshotTool, PdfTool, OcrTool)
+            commands::desktop_access::get_desktop_access_settings,
+            commands::desktop_access::update_desktop_access_settings,
+            // OCR settings (OcrTool)
+            commands::ocr::get_ocr_settings,
+            commands::ocr::update_ocr_settings,
+            commands::ocr::list_ocr_languages,
+            // Tool plugins (WASM components)
+            commands::plugin::list_plugins,
+            commands::plugin::install_plugin,
+            commands::plugin::set_plugin_permissions,
+            commands::plugin::uninstall_plugin,
+            // Custom tools (Rhai scripts)
+            commands::custom_tool::list_custom_tools,
+            commands::custom_tool::create_custom_tool,
+            commands::custom_tool::update_custom_tool,
+            commands::custom_tool::delete_custom_tool,
+            commands::custom_tool::test_custom_tool,
+            // Macro tools (sequences of tool calls)
+            commands::macro_tool::list_macro_tools,
+            commands::macro_tool::create_macro_tool,
+            commands::macro_tool::update_macro_tool,
+            commands::macro_tool::delete_macro_tool,
             // Migration commands (Memory Tool Phase 2 + MCP HTTP + Memory v2)
             commands::migration::migrate_memory_schema,
             commands::migration::get_memory_schema_status,
             commands::migration::migrate_mcp_http_schema,
+            commands::migration::get_embedding_dimension_status,
+            commands::migration::migrate_embedding_dimension,
             commands::migration::migrate_memory_v2_schema,
             // Embedding commands (Memory Tool Phase 5)
             commands::embedding::get_embedding_config,
@@ -313,6 +429,7 @@ async fn main() -> anyhow::Result<()> {
             commands::embedding::export_memories,
             commands::embedding::import_memories,
             commands::embedding::regenerate_embeddings,
+            commands::embedding::get_regeneration_checkpoint,
             commands::embedding::reinit_embedding_service,
             commands::embedding::test_embedding,
             commands::embedding::get_memory_token_stats,
@@ -323,6 +440,10 @@ async fn main() -> anyhow::Result<()> {
             commands::prompt::update_prompt,
             commands::prompt::delete_prompt,
             commands::prompt::search_prompts,
+            commands::prompt::render_prompt,
+            commands::prompt::launch_prompt,
+            commands::prompt::list_prompt_versions,
+            commands::prompt::compare_prompt_versions,
             // Import/Export commands
             commands::import_export::prepare_export_preview,
             commands::import_export::generate_export_file,
@@ -330,20 +451,52 @@ async fn main() -> anyhow::Result<()> {
             commands::import_export::execute_import,
             commands::import_export::save_export_to_file,
             commands::import_export::read_import_file,
+            commands::import_export::export_workspace,
+            commands::import_export::import_workspace,
+            commands::import_export::import_chat_history,
             // User Question commands (Phase 8 - UserQuestionTool)
             commands::user_question::submit_user_response,
             commands::user_question::get_pending_questions,
             commands::user_question::skip_question,
+            // Startup commands
+            commands::startup::get_startup_status,
+            // Profile commands
+            commands::profile::list_profiles,
+            commands::profile::create_profile,
+            commands::profile::switch_profile,
+            // Config file commands
+            commands::config_file::get_effective_config,
+            // Metrics commands
+            commands::metrics::get_metrics_settings,
+            commands::metrics::update_metrics_settings,
+            commands::metrics::get_metrics_export_status,
+            // Proxy commands
+            commands::proxy::get_proxy_settings,
+            commands::proxy::update_proxy_settings,
+            // Diagnostics commands
+            commands::diagnostics::generate_diagnostics,
+            // Workflow title commands
+            commands::workflow_title::regenerate_title,
+            // Workflow tag commands
+            commands::workflow_tag::list_workflow_tags,
+            commands::workflow_tag::create_workflow_tag,
+            commands::workflow_tag::rename_workflow_tag,
+            commands::workflow_tag::set_workflow_tag_color,
+            commands::workflow_tag::delete_workflow_tag,
+            commands::workflow_tag::add_workflow_tags,
+            commands::workflow_tag::remove_workflow_tags,
         ])
         .setup(|app| {
             // === Create native Help menu with legal notices ===
             let legal_notice = MenuItemBuilder::with_id("legal-notice", "Mentions l\u{00e9}gales")
                 .build(app)
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
-            let privacy_policy =
-                MenuItemBuilder::with_id("privacy-policy", "Politique de confidentialit\u{00e9} & RGPD")
-                    .build(app)
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+            let privacy_policy = MenuItemBuilder::with_id(
+                "privacy-policy",
+                "Politique de confidentialit\u{00e9} & RGPD",
+            )
+            .build(app)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
             let separator = PredefinedMenuItem::separator(app)
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
             let about = PredefinedMenuItem::about(
@@ -400,118 +553,13 @@ async fn main() -> anyhow::Result<()> {
                 tracing::info!("App handle set in AppState for event emission");
             }
 
-            // Load agents from database AFTER app_handle is set
+            // Run the deferred startup phases AFTER app_handle is set
             // This ensures AgentToolContext has access to app_handle for validation events
-            // Clone the necessary data for the async task
-            let db = state.inner().db.clone();
-            let registry = state.inner().registry.clone();
-            let orchestrator = state.inner().orchestrator.clone();
-            let llm_manager = state.inner().llm_manager.clone();
-            let tool_factory = state.inner().tool_factory.clone();
-            let app_handle_clone = state.inner().app_handle.clone();
-            let mcp_manager = state.inner().mcp_manager.clone();
-
+            let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                // Suppress unused warning for orchestrator (used in context)
-                let _ = &orchestrator;
-
-                // Set app handle in ToolFactory for validation event emission
-                // This is needed for sub-agents that don't have agent_context
-                // Clone handle first to avoid holding guard across await
-                let handle_to_set = app_handle_clone.read().ok().and_then(|g| g.clone());
-                if let Some(handle) = handle_to_set {
-                    tool_factory.set_app_handle(handle).await;
-                }
-
-                // Load agents from database
-                let query = "SELECT meta::id(id) AS id, name, lifecycle, llm, tools, mcp_servers, system_prompt, max_tool_iterations, enable_thinking FROM agent";
-                let results: Vec<serde_json::Value> = match db.db.query(query).await {
-                    Ok(mut r) => r.take(0).unwrap_or_default(),
-                    Err(e) => {
-                        tracing::warn!(error = %e, "Failed to query agents from database");
-                        return;
-                    }
-                };
-
-                let mut loaded = 0;
-                for row in results {
-                    let id = row["id"].as_str().unwrap_or("").to_string();
-                    if id.is_empty() {
-                        continue;
-                    }
-
-                    let name = row["name"].as_str().unwrap_or("Unknown").to_string();
-                    let lifecycle_str = row["lifecycle"].as_str().unwrap_or("permanent");
-                    let lifecycle = if lifecycle_str == "temporary" {
-                        crate::models::Lifecycle::Temporary
-                    } else {
-                        crate::models::Lifecycle::Permanent
-                    };
-
-                    let llm_value = &row["llm"];
-                    let llm = crate::models::LLMConfig {
-                        provider: llm_value["provider"].as_str().unwrap_or("Mistral").to_string(),
-                        model: llm_value["model"].as_str().unwrap_or("mistral-large-latest").to_string(),
-                        temperature: llm_value["temperature"].as_f64().unwrap_or(0.7) as f32,
-                        max_tokens: llm_value["max_tokens"].as_u64().unwrap_or(4096) as usize,
-                    };
-
-                    let tools: Vec<String> = row["tools"]
-                        .as_array()
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-                        .unwrap_or_default();
-
-                    let mcp_servers_list: Vec<String> = row["mcp_servers"]
-                        .as_array()
-                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-                        .unwrap_or_default();
-
-                    let system_prompt = row["system_prompt"].as_str().unwrap_or("You are a helpful assistant.").to_string();
-
-                    let max_tool_iterations = row["max_tool_iterations"]
-                        .as_u64()
-                        .map(|v| v as usize)
-                        .unwrap_or(50)
-                        .clamp(1, 200);
-
-                    let enable_thinking = row["enable_thinking"].as_bool().unwrap_or(true);
-
-                    let config = crate::models::AgentConfig {
-                        id: id.clone(),
-                        name,
-                        lifecycle,
-                        llm,
-                        tools,
-                        mcp_servers: mcp_servers_list,
-                        system_prompt,
-                        max_tool_iterations,
-                        enable_thinking,
-                    };
-
-                    // Create agent context with app_handle
-                    // Note: No cancellation token during startup agent loading
-                    let app_handle = app_handle_clone.read().ok().and_then(|guard| guard.clone());
-                    let context = crate::tools::AgentToolContext::new(
-                        registry.clone(),
-                        orchestrator.clone(),
-                        llm_manager.clone(),
-                        Some(mcp_manager.clone()),
-                        tool_factory.clone(),
-                        app_handle,
-                        None, // No cancellation token during startup
-                    );
-
-                    let llm_agent = crate::agents::LLMAgent::with_context(
-                        config,
-                        llm_manager.clone(),
-                        tool_factory.clone(),
-                        context,
-                    );
-                    registry.register(id, std::sync::Arc::new(llm_agent)).await;
-                    loaded += 1;
-                }
-
-                tracing::info!(count = loaded, "Agents loaded from database");
+                let state = handle.state::<AppState>();
+                let keystore = handle.state::<commands::SecureKeyStore>();
+                startup::run_deferred_startup(state.inner(), keystore.inner()).await;
             });
 
             Ok(())
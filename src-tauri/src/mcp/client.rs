@@ -55,13 +55,19 @@
 //! ```
 
 use crate::mcp::http_handle::MCPHttpHandle;
+use crate::mcp::notifications::ProgressSender;
+use crate::mcp::oauth::OAuthCredentialStore;
 use crate::mcp::server_handle::MCPServerHandle;
-use crate::mcp::{MCPError, MCPResult, MCPToolCallResponse};
+use crate::mcp::{MCPContent, MCPError, MCPResourceReadResult, MCPResult, MCPToolCallResponse};
 use crate::models::mcp::{
-    MCPDeploymentMethod, MCPResource, MCPServerConfig, MCPServerStatus, MCPTestResult, MCPTool,
+    MCPDeploymentMethod, MCPPrompt, MCPPromptMessage, MCPPromptResult, MCPResource,
+    MCPResourceContents, MCPServerConfig, MCPServerStatus, MCPTestResult, MCPTool,
     MCPToolCallResult,
 };
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 /// Transport handle types
@@ -86,6 +92,8 @@ pub struct MCPClient {
     config: MCPServerConfig,
     /// Whether auto-reconnect is enabled
     auto_reconnect: bool,
+    /// OAuth credential store for HTTP servers
+    oauth_store: Option<Arc<dyn OAuthCredentialStore>>,
 }
 
 impl MCPClient {
@@ -97,6 +105,7 @@ impl MCPClient {
             handle: None,
             config,
             auto_reconnect: false,
+            oauth_store: None,
         }
     }
 
@@ -117,7 +126,20 @@ impl MCPClient {
     ///
     /// Returns an error if the connection or initialization fails.
     pub async fn connect(config: MCPServerConfig) -> MCPResult<Self> {
+        Self::connect_with_oauth(config, None).await
+    }
+
+    /// Creates and connects a new MCP client using OAuth credentials
+    ///
+    /// HTTP servers authorized through OAuth get their stored access token,
+    /// refreshed automatically when expired or rejected (HTTP 401).
+    /// The store is ignored for stdio servers.
+    pub async fn connect_with_oauth(
+        config: MCPServerConfig,
+        oauth_store: Option<Arc<dyn OAuthCredentialStore>>,
+    ) -> MCPResult<Self> {
         let mut client = Self::new(config);
+        client.oauth_store = oauth_store;
         client.connect_internal().await?;
         Ok(client)
     }
@@ -149,7 +171,11 @@ impl MCPClient {
         let handle = match self.config.command {
             MCPDeploymentMethod::Http => {
                 // HTTP transport
-                let mut http_handle = MCPHttpHandle::connect(self.config.clone()).await?;
+                let mut http_handle = MCPHttpHandle::connect_with_oauth(
+                    self.config.clone(),
+                    self.oauth_store.clone(),
+                )
+                .await?;
                 http_handle.initialize().await?;
                 TransportHandle::Http(http_handle)
             }
@@ -284,6 +310,17 @@ impl MCPClient {
         }
     }
 
+    /// Returns the list of available prompts
+    ///
+    /// Returns an empty slice if not connected.
+    pub fn prompts(&self) -> &[MCPPrompt] {
+        match &self.handle {
+            Some(TransportHandle::Stdio(h)) => h.list_prompts(),
+            Some(TransportHandle::Http(h)) => h.list_prompts(),
+            None => &[],
+        }
+    }
+
     /// Calls a tool on the MCP server
     ///
     /// # Arguments
@@ -302,12 +339,34 @@ impl MCPClient {
         &mut self,
         tool_name: &str,
         arguments: serde_json::Value,
+    ) -> MCPResult<MCPToolCallResult> {
+        self.call_tool_with_progress(tool_name, arguments, None, None)
+            .await
+    }
+
+    /// Calls a tool, forwarding its progress notifications to `progress`
+    ///
+    /// Same as [`Self::call_tool`], with `notifications/progress` messages
+    /// sent by the server during the call delivered on the channel. The call
+    /// is cancelled on the server when `cancellation` fires.
+    pub async fn call_tool_with_progress(
+        &mut self,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        progress: Option<ProgressSender>,
+        cancellation: Option<&CancellationToken>,
     ) -> MCPResult<MCPToolCallResult> {
         let start = Instant::now();
 
         let response = match self.handle.as_mut() {
-            Some(TransportHandle::Stdio(h)) => h.call_tool(tool_name, arguments).await?,
-            Some(TransportHandle::Http(h)) => h.call_tool(tool_name, arguments).await?,
+            Some(TransportHandle::Stdio(h)) => {
+                h.call_tool_with_progress(tool_name, arguments, progress, cancellation)
+                    .await?
+            }
+            Some(TransportHandle::Http(h)) => {
+                h.call_tool_with_progress(tool_name, arguments, progress, cancellation)
+                    .await?
+            }
             None => {
                 return Err(MCPError::ServerNotRunning {
                     server: self.config.name.clone(),
@@ -380,6 +439,42 @@ impl MCPClient {
         self.auto_reconnect = enabled;
     }
 
+    /// Sends a `ping` request to check that the server is responsive
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ProtocolError` if the server does not implement
+    /// `ping`, or a transport error if it does not answer.
+    pub async fn ping(&mut self) -> MCPResult<()> {
+        match self.handle.as_mut() {
+            Some(TransportHandle::Stdio(h)) => h.ping().await,
+            Some(TransportHandle::Http(h)) => h.ping().await,
+            None => Err(MCPError::ServerNotRunning {
+                server: self.config.name.clone(),
+                status: "disconnected".to_string(),
+            }),
+        }
+    }
+
+    /// Whether the server sent `notifications/tools/list_changed` since
+    /// the tool list was last refreshed
+    pub fn tools_changed(&self) -> bool {
+        match self.handle.as_ref() {
+            Some(TransportHandle::Stdio(h)) => h.tools_changed(),
+            Some(TransportHandle::Http(h)) => h.tools_changed(),
+            None => false,
+        }
+    }
+
+    /// Returns and resets the tool list change flag
+    pub fn take_tools_changed(&self) -> bool {
+        match self.handle.as_ref() {
+            Some(TransportHandle::Stdio(h)) => h.take_tools_changed(),
+            Some(TransportHandle::Http(h)) => h.take_tools_changed(),
+            None => false,
+        }
+    }
+
     /// Refreshes the tools list from the server
     ///
     /// Use this to update the tools list if the server's capabilities
@@ -395,6 +490,144 @@ impl MCPClient {
         }
     }
 
+    /// Refreshes the resources list from the server
+    pub async fn refresh_resources(&mut self) -> MCPResult<Vec<MCPResource>> {
+        match self.handle.as_mut() {
+            Some(TransportHandle::Stdio(h)) => h.refresh_resources().await,
+            Some(TransportHandle::Http(h)) => h.refresh_resources().await,
+            None => Err(MCPError::ServerNotRunning {
+                server: self.config.name.clone(),
+                status: "disconnected".to_string(),
+            }),
+        }
+    }
+
+    /// Reads a resource from the server
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - URI of the resource (as listed in `resources()`)
+    ///
+    /// # Returns
+    ///
+    /// The resource contents (several items if the URI denotes a collection).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not connected or if the server rejects the URI.
+    pub async fn read_resource(&mut self, uri: &str) -> MCPResult<Vec<MCPResourceContents>> {
+        let result = match self.handle.as_mut() {
+            Some(TransportHandle::Stdio(h)) => h.read_resource(uri).await?,
+            Some(TransportHandle::Http(h)) => h.read_resource(uri).await?,
+            None => {
+                return Err(MCPError::ServerNotRunning {
+                    server: self.config.name.clone(),
+                    status: "disconnected".to_string(),
+                })
+            }
+        };
+
+        Ok(convert_resource_contents(result))
+    }
+
+    /// Reads several resources from the server
+    ///
+    /// Over HTTP the reads are sent as one JSON-RPC batch when the server
+    /// supports it; stdio servers are read one after the other.
+    ///
+    /// # Returns
+    ///
+    /// One result per URI, in the same order. Errors of individual reads
+    /// (e.g. unknown URI) are returned in their slot.
+    pub async fn read_resources(
+        &mut self,
+        uris: &[String],
+    ) -> MCPResult<Vec<MCPResult<Vec<MCPResourceContents>>>> {
+        let results = match self.handle.as_mut() {
+            Some(TransportHandle::Stdio(h)) => {
+                let mut results = Vec::with_capacity(uris.len());
+                for uri in uris {
+                    results.push(h.read_resource(uri).await);
+                }
+                results
+            }
+            Some(TransportHandle::Http(h)) => h.read_resources(uris).await?,
+            None => {
+                return Err(MCPError::ServerNotRunning {
+                    server: self.config.name.clone(),
+                    status: "disconnected".to_string(),
+                })
+            }
+        };
+
+        Ok(results
+            .into_iter()
+            .map(|result| result.map(convert_resource_contents))
+            .collect())
+    }
+
+    /// Refreshes the prompts list from the server
+    pub async fn refresh_prompts(&mut self) -> MCPResult<Vec<MCPPrompt>> {
+        match self.handle.as_mut() {
+            Some(TransportHandle::Stdio(h)) => h.refresh_prompts().await,
+            Some(TransportHandle::Http(h)) => h.refresh_prompts().await,
+            None => Err(MCPError::ServerNotRunning {
+                server: self.config.name.clone(),
+                status: "disconnected".to_string(),
+            }),
+        }
+    }
+
+    /// Renders a prompt on the server
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the prompt (as listed in `prompts()`)
+    /// * `arguments` - Argument values
+    ///
+    /// # Returns
+    ///
+    /// The rendered messages as text. Images and embedded resources without
+    /// text are replaced by a short placeholder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not connected or if the server rejects the prompt.
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> MCPResult<MCPPromptResult> {
+        let result = match self.handle.as_mut() {
+            Some(TransportHandle::Stdio(h)) => h.get_prompt(name, arguments).await?,
+            Some(TransportHandle::Http(h)) => h.get_prompt(name, arguments).await?,
+            None => {
+                return Err(MCPError::ServerNotRunning {
+                    server: self.config.name.clone(),
+                    status: "disconnected".to_string(),
+                })
+            }
+        };
+
+        Ok(MCPPromptResult {
+            description: result.description,
+            messages: result
+                .messages
+                .into_iter()
+                .map(|m| MCPPromptMessage {
+                    role: m.role,
+                    text: match m.content {
+                        MCPContent::Text { text } => text,
+                        MCPContent::Image { mime_type, .. } => format!("[image: {}]", mime_type),
+                        MCPContent::Resource { resource } => resource
+                            .text
+                            .unwrap_or_else(|| format!("[resource: {}]", resource.uri)),
+                    },
+                })
+                .collect(),
+        })
+    }
+
     /// Checks if the underlying process/connection is still alive
     ///
     /// For stdio transport, checks if the process is running.
@@ -423,6 +656,20 @@ impl Drop for MCPClient {
     }
 }
 
+/// Converts a `resources/read` result to our model type
+fn convert_resource_contents(result: MCPResourceReadResult) -> Vec<MCPResourceContents> {
+    result
+        .contents
+        .into_iter()
+        .map(|c| MCPResourceContents {
+            uri: c.uri,
+            mime_type: c.mime_type,
+            text: c.text,
+            blob: c.blob,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
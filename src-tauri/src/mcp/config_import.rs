@@ -0,0 +1,299 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of standard MCP client configuration files.
+//!
+//! Claude Desktop, Cursor, Windsurf and VS Code describe MCP servers as a
+//! JSON map of server name to `{ "command", "args", "env" }` (stdio servers)
+//! or `{ "url" }` (remote servers), under a `mcpServers` key (`servers` for
+//! VS Code). [`parse_mcp_servers_json`] converts each entry into an
+//! [`MCPServerConfig`]:
+//!
+//! - stdio commands must be one of the supported launchers (`npx`, `uvx`,
+//!   `docker`), possibly given as a path (`/usr/local/bin/npx`, `npx.cmd`)
+//!   or wrapped in `cmd /c` on Windows
+//! - remote entries become `http` servers
+//!
+//! Entries that cannot be converted are returned with an error so that the
+//! rest of the block can still be imported.
+
+use crate::models::mcp::{MCPDeploymentMethod, MCPServerConfig};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Keys holding the server map, by client
+const SERVER_MAP_KEYS: &[&str] = &["mcpServers", "servers"];
+
+/// Keys holding the endpoint of a remote server, by client
+const URL_KEYS: &[&str] = &["url", "serverUrl", "httpUrl"];
+
+/// One server entry of an `mcpServers` block
+#[derive(Debug)]
+pub struct ParsedServerEntry {
+    /// Server key in the JSON
+    pub name: String,
+    /// Converted configuration (new ID, disabled), or the conversion error
+    pub config: Result<MCPServerConfig, String>,
+}
+
+/// Parses an `mcpServers` JSON document.
+///
+/// Accepts a full client configuration file, the `mcpServers` object alone
+/// wrapped in braces, or a bare map of servers. Imported servers get a new
+/// UUID and are disabled, so they can be reviewed before being started.
+///
+/// # Errors
+///
+/// Returns an error if the document is not valid JSON or contains no server map.
+pub fn parse_mcp_servers_json(json: &str) -> Result<Vec<ParsedServerEntry>, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let root = root
+        .as_object()
+        .ok_or_else(|| "Expected a JSON object".to_string())?;
+
+    let servers = SERVER_MAP_KEYS
+        .iter()
+        .find_map(|key| root.get(*key).and_then(Value::as_object))
+        .or_else(|| is_server_map(root).then_some(root))
+        .ok_or_else(|| "No \"mcpServers\" object found".to_string())?;
+
+    Ok(servers
+        .iter()
+        .map(|(name, entry)| ParsedServerEntry {
+            name: name.clone(),
+            config: parse_server_entry(name, entry),
+        })
+        .collect())
+}
+
+/// Returns true if every value of the map looks like a server entry.
+fn is_server_map(map: &Map<String, Value>) -> bool {
+    !map.is_empty()
+        && map.values().all(|entry| {
+            entry.as_object().is_some_and(|fields| {
+                fields.contains_key("command") || URL_KEYS.iter().any(|k| fields.contains_key(*k))
+            })
+        })
+}
+
+/// Converts one server entry into a configuration.
+fn parse_server_entry(name: &str, entry: &Value) -> Result<MCPServerConfig, String> {
+    let fields = entry
+        .as_object()
+        .ok_or_else(|| "Server entry must be an object".to_string())?;
+
+    let env = parse_env(fields.get("env"))?;
+    let (command, args) = match URL_KEYS
+        .iter()
+        .find_map(|key| fields.get(*key).and_then(Value::as_str))
+    {
+        Some(url) => (MCPDeploymentMethod::Http, vec![url.to_string()]),
+        None => {
+            let command = fields
+                .get("command")
+                .and_then(Value::as_str)
+                .ok_or_else(|| "Missing \"command\" or \"url\"".to_string())?;
+            let args = parse_args(fields.get("args"))?;
+            resolve_launcher(command, args)?
+        }
+    };
+
+    Ok(MCPServerConfig {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: name.trim().to_string(),
+        enabled: false,
+        command,
+        args,
+        env,
+        description: None,
+    })
+}
+
+/// Maps a stdio command to a supported launcher.
+fn resolve_launcher(
+    command: &str,
+    mut args: Vec<String>,
+) -> Result<(MCPDeploymentMethod, Vec<String>), String> {
+    let mut program = executable_name(command);
+
+    // Windows configs wrap launchers: "command": "cmd", "args": ["/c", "npx", ...]
+    if program == "cmd" && args.first().is_some_and(|a| a.eq_ignore_ascii_case("/c")) {
+        if args.len() < 2 {
+            return Err("Missing command after \"cmd /c\"".to_string());
+        }
+        program = executable_name(&args[1]);
+        args.drain(..2);
+    }
+
+    let method = match program.as_str() {
+        "npx" => MCPDeploymentMethod::Npx,
+        "uvx" => MCPDeploymentMethod::Uvx,
+        "docker" => MCPDeploymentMethod::Docker,
+        _ => {
+            return Err(format!(
+                "Unsupported command \"{}\" (supported: npx, uvx, docker, or a url)",
+                command
+            ))
+        }
+    };
+    Ok((method, args))
+}
+
+/// Returns the lowercase program name of a command, without path or extension.
+fn executable_name(command: &str) -> String {
+    let base = command
+        .trim()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    [".cmd", ".exe", ".bat"]
+        .iter()
+        .find_map(|ext| base.strip_suffix(ext))
+        .map(str::to_string)
+        .unwrap_or(base)
+}
+
+/// Parses the `args` array (strings only).
+fn parse_args(value: Option<&Value>) -> Result<Vec<String>, String> {
+    let Some(value) = value else {
+        return Ok(Vec::new());
+    };
+    value
+        .as_array()
+        .ok_or_else(|| "\"args\" must be an array".to_string())?
+        .iter()
+        .map(|arg| {
+            arg.as_str()
+                .map(str::to_string)
+                .ok_or_else(|| "\"args\" must only contain strings".to_string())
+        })
+        .collect()
+}
+
+/// Parses the `env` object (numbers and booleans are converted to strings).
+fn parse_env(value: Option<&Value>) -> Result<HashMap<String, String>, String> {
+    let Some(value) = value else {
+        return Ok(HashMap::new());
+    };
+    value
+        .as_object()
+        .ok_or_else(|| "\"env\" must be an object".to_string())?
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                _ => return Err(format!("Environment variable \"{}\" must be a string", key)),
+            };
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_of<'a>(entries: &'a [ParsedServerEntry], name: &str) -> &'a MCPServerConfig {
+        entries
+            .iter()
+            .find(|e| e.name == name)
+            .and_then(|e| e.config.as_ref().ok())
+            .unwrap_or_else(|| panic!("{} not parsed", name))
+    }
+
+    #[test]
+    fn test_parse_claude_desktop_config() {
+        let json = r#"{
+            "globalShortcut": "Ctrl+Space",
+            "mcpServers": {
+                "filesystem": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"]
+                },
+                "fetch": { "command": "/opt/homebrew/bin/uvx", "args": ["mcp-server-fetch"] },
+                "github": {
+                    "command": "docker",
+                    "args": ["run", "-i", "--rm", "ghcr.io/github/github-mcp-server"],
+                    "env": { "GITHUB_TOKEN": "ghp_x", "DEBUG": true, "PORT": 8080 }
+                },
+                "windows": { "command": "cmd", "args": ["/c", "npx.cmd", "-y", "pkg"] },
+                "remote": { "url": "https://example.com/mcp" },
+                "local-script": { "command": "node", "args": ["server.js"] },
+                "broken": { "args": ["x"] }
+            }
+        }"#;
+
+        let entries = parse_mcp_servers_json(json).unwrap();
+        assert_eq!(entries.len(), 7);
+
+        let filesystem = config_of(&entries, "filesystem");
+        assert_eq!(filesystem.command, MCPDeploymentMethod::Npx);
+        assert_eq!(filesystem.args.len(), 3);
+        assert!(!filesystem.enabled);
+        assert!(uuid::Uuid::parse_str(&filesystem.id).is_ok());
+
+        assert_eq!(
+            config_of(&entries, "fetch").command,
+            MCPDeploymentMethod::Uvx
+        );
+
+        let github = config_of(&entries, "github");
+        assert_eq!(github.command, MCPDeploymentMethod::Docker);
+        assert_eq!(github.env["DEBUG"], "true");
+        assert_eq!(github.env["PORT"], "8080");
+
+        let windows = config_of(&entries, "windows");
+        assert_eq!(windows.command, MCPDeploymentMethod::Npx);
+        assert_eq!(windows.args, vec!["-y", "pkg"]);
+
+        let remote = config_of(&entries, "remote");
+        assert_eq!(remote.command, MCPDeploymentMethod::Http);
+        assert_eq!(remote.args, vec!["https://example.com/mcp"]);
+
+        let errors: Vec<_> = entries
+            .iter()
+            .filter_map(|e| e.config.as_ref().err().map(|err| (e.name.as_str(), err)))
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .any(|(name, err)| *name == "local-script" && err.contains("Unsupported command")));
+        assert!(errors
+            .iter()
+            .any(|(name, err)| *name == "broken" && err.contains("Missing")));
+    }
+
+    #[test]
+    fn test_parse_server_map_variants() {
+        // VS Code
+        let entries = parse_mcp_servers_json(
+            r#"{ "servers": { "a": { "command": "uvx", "args": ["a"] } } }"#,
+        )
+        .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        // Bare server map
+        let entries =
+            parse_mcp_servers_json(r#"{ "a": { "serverUrl": "https://a.dev/sse" } }"#).unwrap();
+        assert_eq!(config_of(&entries, "a").command, MCPDeploymentMethod::Http);
+
+        assert!(parse_mcp_servers_json("[]").is_err());
+        assert!(parse_mcp_servers_json("{ not json").is_err());
+        assert!(parse_mcp_servers_json(r#"{ "theme": "dark" }"#)
+            .unwrap_err()
+            .contains("mcpServers"));
+    }
+}
@@ -32,7 +32,7 @@ pub enum MCPErrorCategory {
     Protocol,
     /// Server-side or process errors (ProcessSpawnFailed, InitializationFailed, ServerNotRunning)
     ServerInternal,
-    /// Invalid configuration (InvalidConfig)
+    /// Invalid configuration (InvalidConfig, ToolNotAllowed)
     Configuration,
     /// Resource not found (ToolNotFound, ServerNotFound, ServerAlreadyExists)
     ResourceNotFound,
@@ -40,6 +40,8 @@ pub enum MCPErrorCategory {
     Database,
     /// Resilience pattern errors (CircuitBreakerOpen)
     Resilience,
+    /// Requests abandoned by the caller (Cancelled)
+    Cancelled,
 }
 
 impl std::fmt::Display for MCPErrorCategory {
@@ -52,6 +54,7 @@ impl std::fmt::Display for MCPErrorCategory {
             MCPErrorCategory::ResourceNotFound => write!(f, "resource_not_found"),
             MCPErrorCategory::Database => write!(f, "database"),
             MCPErrorCategory::Resilience => write!(f, "resilience"),
+            MCPErrorCategory::Cancelled => write!(f, "cancelled"),
         }
     }
 }
@@ -96,6 +99,13 @@ pub enum MCPError {
         /// Tool name that was requested
         tool: String,
     },
+    /// Tool denied by the calling agent's MCP tool policy
+    ToolNotAllowed {
+        /// Server name
+        server: String,
+        /// Tool name that was requested
+        tool: String,
+    },
     /// Requested server was not found in the registry
     ServerNotFound {
         /// Server name that was requested
@@ -164,6 +174,13 @@ pub enum MCPError {
         /// Last error message
         last_error: String,
     },
+    /// Request cancelled by the caller (the server was notified)
+    Cancelled {
+        /// Server name
+        server: String,
+        /// Cancelled operation
+        operation: String,
+    },
 }
 
 impl fmt::Display for MCPError {
@@ -192,6 +209,13 @@ impl fmt::Display for MCPError {
             MCPError::ToolNotFound { server, tool } => {
                 write!(f, "Tool '{}' not found on MCP server '{}'", tool, server)
             }
+            MCPError::ToolNotAllowed { server, tool } => {
+                write!(
+                    f,
+                    "Tool '{}' on MCP server '{}' is not allowed for this agent",
+                    tool, server
+                )
+            }
             MCPError::ServerNotFound { server } => {
                 write!(f, "MCP server '{}' not found in registry", server)
             }
@@ -248,6 +272,13 @@ impl fmt::Display for MCPError {
                     server, attempts, last_error
                 )
             }
+            MCPError::Cancelled { server, operation } => {
+                write!(
+                    f,
+                    "Operation '{}' on MCP server '{}' was cancelled",
+                    operation, server
+                )
+            }
         }
     }
 }
@@ -280,6 +311,7 @@ impl MCPError {
 
             // Configuration category
             MCPError::InvalidConfig { .. } => MCPErrorCategory::Configuration,
+            MCPError::ToolNotAllowed { .. } => MCPErrorCategory::Configuration,
 
             // Resource not found category
             MCPError::ToolNotFound { .. } => MCPErrorCategory::ResourceNotFound,
@@ -292,6 +324,9 @@ impl MCPError {
             // Resilience category
             MCPError::CircuitBreakerOpen { .. } => MCPErrorCategory::Resilience,
             MCPError::RetryExhausted { .. } => MCPErrorCategory::Resilience,
+
+            // Cancelled category
+            MCPError::Cancelled { .. } => MCPErrorCategory::Cancelled,
         }
     }
 
@@ -381,6 +416,30 @@ mod tests {
         assert!(err.to_string().contains("serena"));
     }
 
+    #[test]
+    fn test_tool_not_allowed_display() {
+        let err = MCPError::ToolNotAllowed {
+            server: "serena".to_string(),
+            tool: "replace_symbol_body".to_string(),
+        };
+        assert!(err.to_string().contains("replace_symbol_body"));
+        assert!(err.to_string().contains("not allowed"));
+        assert_eq!(err.category(), MCPErrorCategory::Configuration);
+        assert!(!err.is_transient());
+    }
+
+    #[test]
+    fn test_cancelled_display() {
+        let err = MCPError::Cancelled {
+            server: "serena".to_string(),
+            operation: "tools/call find_symbol".to_string(),
+        };
+        assert!(err.to_string().contains("find_symbol"));
+        assert!(err.to_string().contains("cancelled"));
+        assert_eq!(err.category(), MCPErrorCategory::Cancelled);
+        assert!(!err.is_transient());
+    }
+
     #[test]
     fn test_timeout_display() {
         let err = MCPError::Timeout {
@@ -14,48 +14,100 @@
 
 //! MCP HTTP Transport Handle
 //!
-//! Manages communication with remote MCP servers over HTTP/SSE transport.
+//! Manages communication with remote MCP servers over HTTP.
 //! This is an alternative to the stdio-based `MCPServerHandle` for servers
 //! that expose HTTP endpoints (SaaS, remote servers).
 //!
 //! ## Transport Protocol
 //!
-//! MCP over HTTP uses:
-//! - **POST** requests for JSON-RPC messages (requests and notifications)
-//! - **SSE** (Server-Sent Events) for server-initiated messages and streaming
+//! The **Streamable HTTP** transport (MCP 2025-03-26 and later) is used by default:
+//! - **POST** requests carry JSON-RPC messages, with
+//!   `Accept: application/json, text/event-stream`
+//! - The server answers with either a single JSON body or an **SSE** stream
+//!   that may carry server requests/notifications before the response
+//! - The `Mcp-Session-Id` returned on initialization is sent with every
+//!   subsequent request; an expired session (HTTP 404) triggers a new
+//!   initialization and the request is retried once
+//! - Interrupted SSE streams are resumed with a `GET` carrying `Last-Event-ID`
+//! - The session is terminated with an HTTP `DELETE` on disconnect
+//! - Servers negotiating a revision that allows JSON-RPC batches (2025-03-26)
+//!   receive independent requests (e.g. the lists fetched on initialization)
+//!   as one batch POST; a rejected batch falls back to one request per POST
 //!
-//! ## URL Configuration
+//! Servers that reject the initialization POST (400/404/405) are assumed to
+//! use the legacy **HTTP+SSE** transport (MCP 2024-11-05): a long-lived `GET`
+//! stream announces the POST endpoint in an `endpoint` event, and responses
+//! are delivered on that stream.
 //!
-//! For HTTP deployment method, the server `args[0]` should contain the base URL:
-//! - `https://api.example.com/mcp` - Base endpoint for the MCP server
+//! ## URL Configuration
 //!
-//! The client will POST JSON-RPC messages to this URL and optionally
-//! connect to `{base_url}/sse` for server-sent events.
-
+//! For HTTP deployment method, the server `args[0]` should contain the endpoint URL:
+//! - `https://api.example.com/mcp` - Streamable HTTP endpoint
+//! - `https://api.example.com/sse` - Legacy HTTP+SSE endpoint
+
+use crate::mcp::notifications::{server_request_reply, NotificationState, ProgressSender};
+use crate::mcp::oauth::{MCPOAuthClient, OAuthCredentialStore};
+use crate::mcp::protocol::supports_batching;
+use crate::mcp::sse::{SseEvent, SseParser};
+use crate::mcp::JsonRpcId;
 use crate::mcp::{
     JsonRpcRequest, JsonRpcResponse, MCPError, MCPInitializeParams, MCPInitializeResult,
-    MCPResourceDefinition, MCPResourcesListResult, MCPResult, MCPToolCallParams,
-    MCPToolCallResponse, MCPToolDefinition, MCPToolsListResult,
+    MCPPromptDefinition, MCPPromptGetParams, MCPPromptGetResult, MCPPromptsListResult,
+    MCPResourceDefinition, MCPResourceReadParams, MCPResourceReadResult, MCPResourcesListResult,
+    MCPResult, MCPToolCallParams, MCPToolCallResponse, MCPToolDefinition, MCPToolsListResult,
+};
+use crate::models::mcp::{
+    MCPPrompt, MCPPromptArgument, MCPResource, MCPServerConfig, MCPServerStatus, MCPTool,
 };
-use crate::models::mcp::{MCPResource, MCPServerConfig, MCPServerStatus, MCPTool};
+use crate::tools::constants::proxy::TARGET_MCP;
+use futures_util::StreamExt;
 use once_cell::sync::Lazy;
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Client, Response, StatusCode};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 /// Default timeout for HTTP operations (30 seconds)
 const DEFAULT_HTTP_TIMEOUT_MS: u64 = 30000;
 
+/// Session ID header (Streamable HTTP)
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+/// Negotiated protocol version header (Streamable HTTP)
+const PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+
+/// Header used to resume an interrupted SSE stream
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+/// Accept header for POST requests (JSON or SSE response)
+const ACCEPT_JSON_OR_SSE: &str = "application/json, text/event-stream";
+
+/// Accept header for GET requests (SSE stream)
+const ACCEPT_SSE: &str = "text/event-stream";
+
+/// Maximum attempts to resume an interrupted SSE response stream
+const MAX_SSE_RESUME_ATTEMPTS: u32 = 3;
+
+/// Maximum delay honored from an SSE `retry` field (milliseconds)
+const MAX_SSE_RETRY_DELAY_MS: u64 = 5000;
+
 /// Shared HTTP client for connection pooling (OPT-8: migrated from lazy_static to once_cell)
 ///
 /// Reuses TCP/TLS connections across all MCPHttpHandle instances.
+/// Requests use the proxy of the "mcp" target (see [`crate::proxy`]).
 /// Configured with:
 /// - 5 idle connections per host
 /// - 90 second idle timeout
 /// - 30 second request timeout
-static SHARED_HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
+pub(crate) static SHARED_HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
+    crate::proxy::client_builder(TARGET_MCP)
         .pool_max_idle_per_host(5)
         .pool_idle_timeout(Duration::from_secs(90))
         .timeout(Duration::from_millis(DEFAULT_HTTP_TIMEOUT_MS))
@@ -63,6 +115,54 @@ static SHARED_HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
         .expect("Failed to create shared HTTP client")
 });
 
+/// Shared HTTP client for long-lived SSE streams (legacy HTTP+SSE transport)
+///
+/// Same pooling as [`SHARED_HTTP_CLIENT`] but without a total request timeout,
+/// which would otherwise close the stream after 30 seconds.
+static SHARED_STREAMING_CLIENT: Lazy<Client> = Lazy::new(|| {
+    crate::proxy::client_builder(TARGET_MCP)
+        .pool_max_idle_per_host(5)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .connect_timeout(Duration::from_millis(DEFAULT_HTTP_TIMEOUT_MS))
+        .build()
+        .expect("Failed to create shared streaming HTTP client")
+});
+
+/// HTTP transport variant used by a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpTransportKind {
+    /// Streamable HTTP (MCP 2025-03-26 and later)
+    StreamableHttp,
+    /// Legacy HTTP+SSE (MCP 2024-11-05)
+    LegacySse,
+}
+
+/// Pending legacy requests, keyed by serialized JSON-RPC ID
+type PendingResponses = Arc<Mutex<HashMap<String, oneshot::Sender<JsonRpcResponse>>>>;
+
+/// Legacy HTTP+SSE channel: POST endpoint and the stream reader task
+struct LegacySseChannel {
+    /// Endpoint announced by the server for POST messages
+    endpoint: String,
+    /// Requests waiting for a response on the stream
+    pending: PendingResponses,
+    /// Background task reading the SSE stream
+    reader: JoinHandle<()>,
+}
+
+/// Outcome of a Streamable HTTP POST
+enum PostOutcome {
+    /// JSON-RPC responses received (one per request, in request order)
+    Response(Vec<JsonRpcResponse>),
+    /// Server no longer knows the session (HTTP 404), re-initialization needed
+    SessionExpired,
+    /// Server does not support Streamable HTTP (initialization rejected),
+    /// or does not accept JSON-RPC batches
+    Unsupported(StatusCode),
+    /// Server requires (new) authorization (HTTP 401)
+    Unauthorized,
+}
+
 /// MCP HTTP Transport Handle
 ///
 /// Manages communication with a remote MCP server over HTTP.
@@ -105,6 +205,8 @@ pub struct MCPHttpHandle {
     tools: Vec<MCPTool>,
     /// Discovered resources after initialization
     resources: Vec<MCPResource>,
+    /// Discovered prompts after initialization
+    prompts: Vec<MCPPrompt>,
     /// Request ID counter for JSON-RPC
     request_id: AtomicI64,
     /// Server info from initialization (name, version)
@@ -113,6 +215,22 @@ pub struct MCPHttpHandle {
     connected: bool,
     /// Custom headers for this connection (API key, etc.)
     headers: reqwest::header::HeaderMap,
+    /// Transport variant negotiated during initialization
+    transport: HttpTransportKind,
+    /// Session ID assigned by the server (`Mcp-Session-Id`)
+    session_id: Option<String>,
+    /// Protocol version negotiated during initialization
+    protocol_version: Option<String>,
+    /// Whether the server rejected a JSON-RPC batch (requests are then sent one by one)
+    batch_rejected: bool,
+    /// ID of the last SSE event received (for stream resumption)
+    last_event_id: Option<String>,
+    /// Legacy HTTP+SSE channel (None for Streamable HTTP)
+    legacy: Option<LegacySseChannel>,
+    /// OAuth credential store (None if OAuth is not available)
+    oauth_store: Option<Arc<dyn OAuthCredentialStore>>,
+    /// Notifications received on response and legacy streams
+    notifications: NotificationState,
 }
 
 impl MCPHttpHandle {
@@ -130,6 +248,22 @@ impl MCPHttpHandle {
     ///
     /// Returns an error if the URL is invalid or connection fails.
     pub async fn connect(config: MCPServerConfig) -> MCPResult<Self> {
+        Self::connect_with_oauth(config, None).await
+    }
+
+    /// Creates a new HTTP handle using OAuth credentials from `oauth_store`
+    ///
+    /// Stored credentials for the server are sent as Bearer token (refreshed
+    /// first if expired), and refreshed again when the server answers 401.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Server configuration with HTTP URL in args[0]
+    /// * `oauth_store` - Credential store (None disables OAuth)
+    pub async fn connect_with_oauth(
+        config: MCPServerConfig,
+        oauth_store: Option<Arc<dyn OAuthCredentialStore>>,
+    ) -> MCPResult<Self> {
         info!(
             server_id = %config.id,
             server_name = %config.name,
@@ -196,12 +330,24 @@ impl MCPHttpHandle {
             status: MCPServerStatus::Starting,
             tools: Vec::new(),
             resources: Vec::new(),
+            prompts: Vec::new(),
             request_id: AtomicI64::new(1),
             server_info: None,
             connected: false,
             headers,
+            transport: HttpTransportKind::StreamableHttp,
+            session_id: None,
+            protocol_version: None,
+            batch_rejected: false,
+            last_event_id: None,
+            legacy: None,
+            oauth_store,
+            notifications: NotificationState::default(),
         };
 
+        // Stored OAuth credentials take precedence over env.API_KEY
+        handle.apply_oauth_credentials(false).await;
+
         // Test connection with a simple request
         handle.test_connectivity().await?;
         handle.connected = true;
@@ -262,7 +408,63 @@ impl MCPHttpHandle {
             "Initializing MCP HTTP session"
         );
 
-        // Send initialize request
+        let init_result = self.handshake().await?;
+
+        // List tools, resources and prompts (one batch when supported)
+        let mut requests = vec![
+            JsonRpcRequest::new("tools/list", None, self.next_request_id()),
+            JsonRpcRequest::new("resources/list", None, self.next_request_id()),
+        ];
+        if init_result.capabilities.prompts.is_some() {
+            requests.push(JsonRpcRequest::new(
+                "prompts/list",
+                None,
+                self.next_request_id(),
+            ));
+        }
+
+        let mut responses = self.send_batch(requests).await?.into_iter();
+        if let Some(response) = responses.next() {
+            self.apply_tools_list(response)?;
+        }
+        if let Some(response) = responses.next() {
+            self.apply_resources_list(response)?;
+        }
+        if let Some(response) = responses.next() {
+            if let Err(e) = self.apply_prompts_list(response) {
+                warn!(
+                    server_id = %self.config.id,
+                    error = %e,
+                    "Failed to list prompts during initialization"
+                );
+            }
+        }
+
+        self.status = MCPServerStatus::Running;
+
+        info!(
+            server_id = %self.config.id,
+            server_name = %init_result.server_info.name,
+            server_version = %init_result.server_info.version,
+            transport = ?self.transport,
+            tools_count = self.tools.len(),
+            resources_count = self.resources.len(),
+            prompts_count = self.prompts.len(),
+            "MCP HTTP session initialized"
+        );
+
+        Ok(init_result)
+    }
+
+    /// Performs the initialization handshake (`initialize` + `initialized`).
+    ///
+    /// Starts a new session: any previous session ID is discarded. Falls back
+    /// to the legacy HTTP+SSE transport when the server rejects the POST.
+    async fn handshake(&mut self) -> MCPResult<MCPInitializeResult> {
+        self.session_id = None;
+        self.protocol_version = None;
+        self.last_event_id = None;
+
         let params = MCPInitializeParams::default();
         let request = JsonRpcRequest::new(
             "initialize",
@@ -270,7 +472,37 @@ impl MCPHttpHandle {
             self.next_request_id(),
         );
 
-        let response = self.send_request(request).await?;
+        let response = if self.legacy.is_some() {
+            self.send_legacy(&request).await?
+        } else {
+            match self.post_authorized(std::slice::from_ref(&request)).await? {
+                PostOutcome::Response(responses) => self.single_response(responses)?,
+                PostOutcome::Unsupported(status) => {
+                    info!(
+                        server_id = %self.config.id,
+                        status = %status,
+                        "Streamable HTTP rejected, falling back to legacy HTTP+SSE transport"
+                    );
+                    self.legacy = Some(self.open_legacy_channel().await?);
+                    self.transport = HttpTransportKind::LegacySse;
+                    self.send_legacy(&request).await?
+                }
+                PostOutcome::SessionExpired => {
+                    return Err(MCPError::InitializationFailed {
+                        server: self.config.name.clone(),
+                        message: "Endpoint not found (HTTP 404)".to_string(),
+                    })
+                }
+                PostOutcome::Unauthorized => return Err(self.unauthorized_error()),
+            }
+        };
+
+        if let Some(error) = response.error {
+            return Err(MCPError::InitializationFailed {
+                server: self.config.name.clone(),
+                message: error.message,
+            });
+        }
 
         // Parse initialization result
         let init_result: MCPInitializeResult =
@@ -281,29 +513,24 @@ impl MCPHttpHandle {
                 }
             })?)?;
 
-        // Store server info
+        // Store server info and negotiated version
         self.server_info = Some((
             init_result.server_info.name.clone(),
             init_result.server_info.version.clone(),
         ));
+        if self.transport == HttpTransportKind::StreamableHttp {
+            self.protocol_version = Some(init_result.protocol_version.clone());
+        }
 
         // Send initialized notification
         self.send_notification("notifications/initialized", None)
             .await?;
 
-        // Refresh tools and resources
-        self.refresh_tools_internal().await?;
-        self.refresh_resources_internal().await?;
-
-        self.status = MCPServerStatus::Running;
-
-        info!(
+        debug!(
             server_id = %self.config.id,
-            server_name = %init_result.server_info.name,
-            server_version = %init_result.server_info.version,
-            tools_count = self.tools.len(),
-            resources_count = self.resources.len(),
-            "MCP HTTP session initialized"
+            session_id = ?self.session_id,
+            protocol_version = %init_result.protocol_version,
+            "MCP HTTP handshake completed"
         );
 
         Ok(init_result)
@@ -319,18 +546,172 @@ impl MCPHttpHandle {
         &self.resources
     }
 
+    /// Returns the list of available prompts
+    pub fn list_prompts(&self) -> &[MCPPrompt] {
+        &self.prompts
+    }
+
+    /// Sends a JSON-RPC `ping` request (liveness check)
+    pub async fn ping(&mut self) -> MCPResult<()> {
+        let request = JsonRpcRequest::new("ping", None, self.next_request_id());
+        let response = self.send_request(request).await?;
+
+        response
+            .into_result()
+            .map(|_| ())
+            .map_err(|e| MCPError::ProtocolError {
+                code: e.code,
+                message: e.message,
+            })
+    }
+
     /// Refreshes the tools list from the server
     pub async fn refresh_tools(&mut self) -> MCPResult<Vec<MCPTool>> {
         self.refresh_tools_internal().await?;
         Ok(self.tools.clone())
     }
 
+    /// Refreshes the resources list from the server
+    pub async fn refresh_resources(&mut self) -> MCPResult<Vec<MCPResource>> {
+        self.refresh_resources_internal().await?;
+        Ok(self.resources.clone())
+    }
+
+    /// Reads a resource from the server (`resources/read`)
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - URI of the resource (as listed by `resources/list`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ProtocolError` if the server rejects the request
+    /// (e.g. unknown URI).
+    pub async fn read_resource(&mut self, uri: &str) -> MCPResult<MCPResourceReadResult> {
+        debug!(
+            server_id = %self.config.id,
+            uri = %uri,
+            "Reading MCP resource via HTTP"
+        );
+
+        let params = MCPResourceReadParams {
+            uri: uri.to_string(),
+        };
+        let request = JsonRpcRequest::new(
+            "resources/read",
+            Some(serde_json::to_value(&params)?),
+            self.next_request_id(),
+        );
+
+        let response = self.send_request(request).await?;
+        resource_read_result(response)
+    }
+
+    /// Reads several resources from the server (`resources/read`)
+    ///
+    /// The reads are sent as one JSON-RPC batch when the server supports it.
+    ///
+    /// # Arguments
+    ///
+    /// * `uris` - URIs of the resources (as listed by `resources/list`)
+    ///
+    /// # Returns
+    ///
+    /// One result per URI, in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batch could not be sent; errors of individual
+    /// reads (e.g. unknown URI) are returned in their slot.
+    pub async fn read_resources(
+        &mut self,
+        uris: &[String],
+    ) -> MCPResult<Vec<MCPResult<MCPResourceReadResult>>> {
+        debug!(
+            server_id = %self.config.id,
+            count = uris.len(),
+            "Reading MCP resources via HTTP"
+        );
+
+        let mut requests = Vec::with_capacity(uris.len());
+        for uri in uris {
+            let params = MCPResourceReadParams { uri: uri.clone() };
+            requests.push(JsonRpcRequest::new(
+                "resources/read",
+                Some(serde_json::to_value(&params)?),
+                self.next_request_id(),
+            ));
+        }
+
+        let responses = self.send_batch(requests).await?;
+        Ok(responses.into_iter().map(resource_read_result).collect())
+    }
+
+    /// Refreshes the prompts list from the server
+    pub async fn refresh_prompts(&mut self) -> MCPResult<Vec<MCPPrompt>> {
+        self.refresh_prompts_internal().await?;
+        Ok(self.prompts.clone())
+    }
+
+    /// Renders a prompt on the server (`prompts/get`)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the prompt (as listed by `prompts/list`)
+    /// * `arguments` - Argument values
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ProtocolError` if the server rejects the request
+    /// (e.g. unknown prompt, missing required argument).
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> MCPResult<MCPPromptGetResult> {
+        debug!(
+            server_id = %self.config.id,
+            prompt = %name,
+            "Getting MCP prompt via HTTP"
+        );
+
+        let params = MCPPromptGetParams {
+            name: name.to_string(),
+            arguments,
+        };
+        let request = JsonRpcRequest::new(
+            "prompts/get",
+            Some(serde_json::to_value(&params)?),
+            self.next_request_id(),
+        );
+
+        let response = self.send_request(request).await?;
+
+        if let Some(error) = response.error {
+            return Err(MCPError::ProtocolError {
+                code: error.code,
+                message: error.message,
+            });
+        }
+
+        let result = response.result.ok_or_else(|| MCPError::ProtocolError {
+            code: -32600,
+            message: "No result in prompt get response".to_string(),
+        })?;
+
+        Ok(serde_json::from_value(result)?)
+    }
+
     /// Internal method to refresh tools
     async fn refresh_tools_internal(&mut self) -> MCPResult<()> {
         let request = JsonRpcRequest::new("tools/list", None, self.next_request_id());
 
         let response = self.send_request(request).await?;
+        self.apply_tools_list(response)
+    }
 
+    /// Stores the tools of a `tools/list` response
+    fn apply_tools_list(&mut self, response: JsonRpcResponse) -> MCPResult<()> {
         if let Some(result) = response.result {
             let tools_result: MCPToolsListResult = serde_json::from_value(result)?;
             self.tools = tools_result
@@ -354,7 +735,11 @@ impl MCPHttpHandle {
         let request = JsonRpcRequest::new("resources/list", None, self.next_request_id());
 
         let response = self.send_request(request).await?;
+        self.apply_resources_list(response)
+    }
 
+    /// Stores the resources of a `resources/list` response
+    fn apply_resources_list(&mut self, response: JsonRpcResponse) -> MCPResult<()> {
         if let Some(result) = response.result {
             let resources_result: MCPResourcesListResult = serde_json::from_value(result)?;
             self.resources = resources_result
@@ -373,6 +758,41 @@ impl MCPHttpHandle {
         Ok(())
     }
 
+    /// Internal method to refresh prompts
+    async fn refresh_prompts_internal(&mut self) -> MCPResult<()> {
+        let request = JsonRpcRequest::new("prompts/list", None, self.next_request_id());
+
+        let response = self.send_request(request).await?;
+        self.apply_prompts_list(response)
+    }
+
+    /// Stores the prompts of a `prompts/list` response
+    fn apply_prompts_list(&mut self, response: JsonRpcResponse) -> MCPResult<()> {
+        if let Some(error) = response.error {
+            return Err(MCPError::ProtocolError {
+                code: error.code,
+                message: error.message,
+            });
+        }
+
+        if let Some(result) = response.result {
+            let prompts_result: MCPPromptsListResult = serde_json::from_value(result)?;
+            self.prompts = prompts_result
+                .prompts
+                .into_iter()
+                .map(|p| self.convert_prompt_definition(p))
+                .collect();
+
+            debug!(
+                server_id = %self.config.id,
+                prompts_count = self.prompts.len(),
+                "Refreshed prompts list"
+            );
+        }
+
+        Ok(())
+    }
+
     /// Converts MCP tool definition to our model type
     fn convert_tool_definition(&self, def: MCPToolDefinition) -> MCPTool {
         MCPTool {
@@ -392,6 +812,23 @@ impl MCPHttpHandle {
         }
     }
 
+    /// Converts MCP prompt definition to our model type
+    fn convert_prompt_definition(&self, def: MCPPromptDefinition) -> MCPPrompt {
+        MCPPrompt {
+            name: def.name,
+            description: def.description,
+            arguments: def
+                .arguments
+                .into_iter()
+                .map(|arg| MCPPromptArgument {
+                    name: arg.name,
+                    description: arg.description,
+                    required: arg.required.unwrap_or(false),
+                })
+                .collect(),
+        }
+    }
+
     /// Calls a tool on the MCP server
     ///
     /// # Arguments
@@ -406,6 +843,26 @@ impl MCPHttpHandle {
         &mut self,
         tool_name: &str,
         arguments: serde_json::Value,
+    ) -> MCPResult<MCPToolCallResponse> {
+        self.call_tool_with_progress(tool_name, arguments, None, None)
+            .await
+    }
+
+    /// Calls a tool, forwarding its progress notifications to `progress`
+    ///
+    /// The request ID is sent as `_meta.progressToken` when `progress` is set.
+    /// Progress is only received when the server answers with an SSE stream
+    /// (or on the legacy HTTP+SSE stream).
+    ///
+    /// When `cancellation` fires before the response, the HTTP request is
+    /// aborted, the server is sent a `notifications/cancelled` for the request
+    /// and `MCPError::Cancelled` is returned.
+    pub async fn call_tool_with_progress(
+        &mut self,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        progress: Option<ProgressSender>,
+        cancellation: Option<&CancellationToken>,
     ) -> MCPResult<MCPToolCallResponse> {
         debug!(
             server_id = %self.config.id,
@@ -413,18 +870,46 @@ impl MCPHttpHandle {
             "Calling MCP tool via HTTP"
         );
 
+        let request_id = self.next_request_id();
+        let progress_token = progress.map(|sender| {
+            let token = Value::from(request_id);
+            self.notifications.register_progress(&token, sender);
+            token
+        });
         let params = MCPToolCallParams {
             name: tool_name.to_string(),
             arguments,
+            meta: progress_token
+                .as_ref()
+                .map(|token| serde_json::json!({"progressToken": token})),
         };
 
         let request = JsonRpcRequest::new(
             "tools/call",
             Some(serde_json::to_value(&params)?),
-            self.next_request_id(),
+            request_id,
         );
 
-        let response = self.send_request(request).await?;
+        let response = match cancellation {
+            Some(token) => {
+                let outcome = tokio::select! {
+                    biased;
+                    response = self.send_request(request) => Some(response),
+                    _ = token.cancelled() => None,
+                };
+                match outcome {
+                    Some(response) => response,
+                    None => Err(self
+                        .cancel_request(request_id, &format!("tools/call {}", tool_name))
+                        .await),
+                }
+            }
+            None => self.send_request(request).await,
+        };
+        if let Some(token) = progress_token {
+            self.notifications.unregister_progress(&token);
+        }
+        let response = response?;
 
         // Check for error response
         if let Some(error) = response.error {
@@ -471,45 +956,33 @@ impl MCPHttpHandle {
     }
 
     /// Sends a JSON-RPC request to the server
-    async fn send_request(&self, request: JsonRpcRequest) -> MCPResult<JsonRpcResponse> {
+    ///
+    /// With Streamable HTTP, an expired session is re-initialized and the
+    /// request retried once.
+    async fn send_request(&mut self, request: JsonRpcRequest) -> MCPResult<JsonRpcResponse> {
         debug!(
             server_id = %self.config.id,
             method = %request.method,
             request_id = ?request.id,
+            transport = ?self.transport,
             "Sending HTTP request"
         );
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .headers(self.headers.clone())
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| MCPError::ConnectionFailed {
-                server: self.config.name.clone(),
-                message: format!("HTTP request failed: {}", e),
-            })?;
-
-        // Check HTTP status
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            return Err(MCPError::ConnectionFailed {
-                server: self.config.name.clone(),
-                message: format!("HTTP {} - {}", status, body),
-            });
-        }
-
-        // Parse JSON-RPC response
-        let json_response: JsonRpcResponse =
-            response
-                .json()
-                .await
-                .map_err(|e| MCPError::SerializationError {
-                    context: "HTTP response parsing".to_string(),
-                    message: e.to_string(),
-                })?;
+        let json_response = if self.legacy.is_some() {
+            self.send_legacy(&request).await?
+        } else {
+            match self.post_in_session(std::slice::from_ref(&request)).await? {
+                PostOutcome::Response(responses) => self.single_response(responses)?,
+                PostOutcome::SessionExpired => return Err(self.session_expired_error()),
+                PostOutcome::Unsupported(status) => {
+                    return Err(MCPError::ConnectionFailed {
+                        server: self.config.name.clone(),
+                        message: format!("HTTP {}", status),
+                    })
+                }
+                PostOutcome::Unauthorized => return Err(self.unauthorized_error()),
+            }
+        };
 
         // Check for JSON-RPC error
         if let Some(ref error) = json_response.error {
@@ -532,28 +1005,567 @@ impl MCPHttpHandle {
         Ok(json_response)
     }
 
-    /// Sends a JSON-RPC notification (no response expected)
-    async fn send_notification(
-        &self,
-        method: &str,
-        params: Option<serde_json::Value>,
-    ) -> MCPResult<()> {
-        debug!(
-            server_id = %self.config.id,
-            method = %method,
-            "Sending HTTP notification"
-        );
+    /// Sends several requests, as one JSON-RPC batch when the server accepts it.
+    ///
+    /// Requests are sent one by one on the legacy transport, on protocol
+    /// revisions without batching, and once the server rejected a batch.
+    ///
+    /// # Returns
+    /// One response per request, in request order
+    async fn send_batch(
+        &mut self,
+        requests: Vec<JsonRpcRequest>,
+    ) -> MCPResult<Vec<JsonRpcResponse>> {
+        if requests.len() > 1 && self.batching_enabled() {
+            debug!(
+                server_id = %self.config.id,
+                batch_size = requests.len(),
+                "Sending JSON-RPC batch"
+            );
 
-        let notification = serde_json::json!({
-            "jsonrpc": "2.0",
-            "method": method,
+            match self.post_in_session(&requests).await? {
+                PostOutcome::Response(responses) => return Ok(responses),
+                PostOutcome::Unsupported(status) => {
+                    warn!(
+                        server_id = %self.config.id,
+                        status = %status,
+                        "JSON-RPC batch rejected, sending requests one by one"
+                    );
+                    self.batch_rejected = true;
+                }
+                PostOutcome::SessionExpired => return Err(self.session_expired_error()),
+                PostOutcome::Unauthorized => return Err(self.unauthorized_error()),
+            }
+        }
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            responses.push(self.send_request(request).await?);
+        }
+        Ok(responses)
+    }
+
+    /// Returns true if independent requests can be sent as one JSON-RPC batch.
+    fn batching_enabled(&self) -> bool {
+        !self.batch_rejected
+            && self.transport == HttpTransportKind::StreamableHttp
+            && self
+                .protocol_version
+                .as_deref()
+                .is_some_and(supports_batching)
+    }
+
+    /// POSTs requests in the current session.
+    ///
+    /// An expired session is re-initialized and the requests retried once.
+    async fn post_in_session(&mut self, requests: &[JsonRpcRequest]) -> MCPResult<PostOutcome> {
+        match self.post_authorized(requests).await? {
+            PostOutcome::SessionExpired => {
+                warn!(
+                    server_id = %self.config.id,
+                    "MCP session expired, re-initializing"
+                );
+                self.handshake().await?;
+                self.post_authorized(requests).await
+            }
+            outcome => Ok(outcome),
+        }
+    }
+
+    /// POSTs requests, refreshing the OAuth token and retrying once on 401.
+    async fn post_authorized(&mut self, requests: &[JsonRpcRequest]) -> MCPResult<PostOutcome> {
+        match self.post_streamable(requests).await? {
+            PostOutcome::Unauthorized if self.apply_oauth_credentials(true).await => {
+                self.post_streamable(requests).await
+            }
+            outcome => Ok(outcome),
+        }
+    }
+
+    /// Returns the only response of a single-request POST.
+    fn single_response(&self, responses: Vec<JsonRpcResponse>) -> MCPResult<JsonRpcResponse> {
+        responses
+            .into_iter()
+            .next()
+            .ok_or_else(|| MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: "No JSON-RPC response received".to_string(),
+            })
+    }
+
+    /// Error returned when the session expires again after re-initialization.
+    fn session_expired_error(&self) -> MCPError {
+        MCPError::ConnectionFailed {
+            server: self.config.name.clone(),
+            message: "Session expired again after re-initialization".to_string(),
+        }
+    }
+
+    /// Loads the stored OAuth credentials and sets the Authorization header.
+    ///
+    /// Expired tokens (or any token when `force_refresh` is set) are refreshed
+    /// and the new credentials saved.
+    ///
+    /// # Returns
+    /// true if a (new) access token was applied
+    async fn apply_oauth_credentials(&mut self, force_refresh: bool) -> bool {
+        let Some(store) = self.oauth_store.clone() else {
+            return false;
+        };
+        let Some(mut credentials) = store.load_credentials(&self.config.id) else {
+            return false;
+        };
+
+        if force_refresh || credentials.needs_refresh() {
+            let oauth = MCPOAuthClient::new(self.config.name.clone(), credentials.resource.clone());
+            match oauth.refresh(&credentials).await {
+                Ok(refreshed) => {
+                    if let Err(e) = store.save_credentials(&self.config.id, &refreshed) {
+                        warn!(server_id = %self.config.id, error = %e, "Failed to save refreshed OAuth token");
+                    }
+                    credentials = refreshed;
+                }
+                Err(e) => {
+                    warn!(server_id = %self.config.id, error = %e, "OAuth token refresh failed");
+                    if force_refresh {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        match HeaderValue::from_str(&credentials.authorization_header()) {
+            Ok(value) => {
+                self.headers.insert(AUTHORIZATION, value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Error returned when the server keeps rejecting the credentials.
+    fn unauthorized_error(&self) -> MCPError {
+        MCPError::ConnectionFailed {
+            server: self.config.name.clone(),
+            message: "Unauthorized (HTTP 401): authorize the server with OAuth".to_string(),
+        }
+    }
+
+    /// POSTs requests using the Streamable HTTP transport.
+    ///
+    /// A single request is sent as a JSON-RPC object, several as a batch
+    /// array. The response is either a JSON body or an SSE stream carrying the
+    /// responses (possibly preceded by server requests and notifications).
+    async fn post_streamable(&mut self, requests: &[JsonRpcRequest]) -> MCPResult<PostOutcome> {
+        let is_batch = requests.len() > 1;
+        let builder = self
+            .client
+            .post(&self.base_url)
+            .headers(self.request_headers())
+            .header(ACCEPT, ACCEPT_JSON_OR_SSE);
+        let builder = match requests {
+            [request] => builder.json(request),
+            _ => builder.json(requests),
+        };
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: format!("HTTP request failed: {}", e),
+            })?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            return Ok(PostOutcome::Unauthorized);
+        }
+        if status == StatusCode::NOT_FOUND && self.session_id.is_some() {
+            return Ok(PostOutcome::SessionExpired);
+        }
+        let is_initialize = matches!(requests, [request] if request.method == "initialize");
+        if is_initialize
+            && matches!(
+                status,
+                StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED
+            )
+        {
+            return Ok(PostOutcome::Unsupported(status));
+        }
+        if is_batch && status == StatusCode::BAD_REQUEST {
+            return Ok(PostOutcome::Unsupported(status));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: format!("HTTP {} - {}", status, body),
+            });
+        }
+
+        // The session ID is assigned on the initialize response
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            self.session_id = Some(session_id.to_string());
+        }
+
+        let request_ids: Vec<JsonRpcId> = requests.iter().map(|r| r.id.clone()).collect();
+        if is_event_stream(&response) {
+            let responses = self.read_sse_response(response, &request_ids).await?;
+            return Ok(PostOutcome::Response(responses));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| MCPError::SerializationError {
+                context: "HTTP response parsing".to_string(),
+                message: e.to_string(),
+            })?;
+        let responses: Vec<JsonRpcResponse> = match body {
+            Value::Array(_) => serde_json::from_value(body)?,
+            // A server without batch support answers a batch with a single error
+            _ if is_batch => return Ok(PostOutcome::Unsupported(status)),
+            _ => vec![serde_json::from_value(body)?],
+        };
+
+        match responses.as_slice() {
+            [_] if !is_batch => Ok(PostOutcome::Response(responses)),
+            _ => order_responses(&request_ids, responses)
+                .map(PostOutcome::Response)
+                .ok_or_else(|| MCPError::ConnectionFailed {
+                    server: self.config.name.clone(),
+                    message: "JSON-RPC batch response is missing responses".to_string(),
+                }),
+        }
+    }
+
+    /// Reads an SSE response stream until the responses to `request_ids` arrive.
+    ///
+    /// If the stream closes first, it is resumed with a GET carrying
+    /// `Last-Event-ID` (up to [`MAX_SSE_RESUME_ATTEMPTS`] times).
+    ///
+    /// # Returns
+    /// The responses, in the order of `request_ids`
+    async fn read_sse_response(
+        &mut self,
+        response: Response,
+        request_ids: &[JsonRpcId],
+    ) -> MCPResult<Vec<JsonRpcResponse>> {
+        let mut response = response;
+        let mut received: Vec<Option<JsonRpcResponse>> = vec![None; request_ids.len()];
+        let mut attempts = 0;
+        let mut retry_delay_ms = 0;
+
+        loop {
+            let mut parser = SseParser::default();
+            let mut body = response.bytes_stream();
+
+            while let Some(chunk) = body.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        warn!(server_id = %self.config.id, error = %e, "SSE stream interrupted");
+                        break;
+                    }
+                };
+                for event in parser.feed(&chunk) {
+                    if let Some(id) = event.id.clone() {
+                        self.last_event_id = Some(id);
+                    }
+                    if let Some(retry) = event.retry {
+                        retry_delay_ms = retry.min(MAX_SSE_RETRY_DELAY_MS);
+                    }
+                    for json_response in self.handle_stream_event(&event).await {
+                        let slot = request_ids
+                            .iter()
+                            .position(|id| json_response.id.as_ref() == Some(id));
+                        if let Some(slot) = slot {
+                            received[slot] = Some(json_response);
+                        }
+                    }
+                    if received.iter().all(Option::is_some) {
+                        return Ok(received.into_iter().flatten().collect());
+                    }
+                }
+            }
+
+            attempts += 1;
+            let last_event_id = match (&self.last_event_id, attempts <= MAX_SSE_RESUME_ATTEMPTS) {
+                (Some(id), true) => id.clone(),
+                _ => {
+                    return Err(MCPError::ConnectionFailed {
+                        server: self.config.name.clone(),
+                        message: "SSE stream closed before the response was received".to_string(),
+                    })
+                }
+            };
+
+            info!(
+                server_id = %self.config.id,
+                last_event_id = %last_event_id,
+                attempt = attempts,
+                "Resuming SSE stream"
+            );
+            if retry_delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(retry_delay_ms)).await;
+            }
+
+            response = self
+                .client
+                .get(&self.base_url)
+                .headers(self.request_headers())
+                .header(ACCEPT, ACCEPT_SSE)
+                .header(LAST_EVENT_ID_HEADER, last_event_id)
+                .send()
+                .await
+                .map_err(|e| MCPError::ConnectionFailed {
+                    server: self.config.name.clone(),
+                    message: format!("Failed to resume SSE stream: {}", e),
+                })?;
+
+            if !response.status().is_success() || !is_event_stream(&response) {
+                return Err(MCPError::ConnectionFailed {
+                    server: self.config.name.clone(),
+                    message: format!("Failed to resume SSE stream: HTTP {}", response.status()),
+                });
+            }
+        }
+    }
+
+    /// Handles an SSE event of a Streamable HTTP response stream.
+    ///
+    /// The event may carry a single JSON-RPC message or a batch of them.
+    ///
+    /// # Returns
+    /// The JSON-RPC responses carried by the event
+    async fn handle_stream_event(&self, event: &SseEvent) -> Vec<JsonRpcResponse> {
+        if event.data.is_empty() {
+            return Vec::new();
+        }
+        let messages = match serde_json::from_str::<Vec<Value>>(&event.data) {
+            Ok(batch) => batch.iter().map(Value::to_string).collect(),
+            Err(_) => vec![event.data.clone()],
+        };
+
+        let mut responses = Vec::new();
+        for data in &messages {
+            if let Some(response) = handle_server_message(
+                &self.client,
+                &self.base_url,
+                self.request_headers(),
+                &self.config.id,
+                &self.notifications,
+                data,
+            )
+            .await
+            {
+                responses.push(response);
+            }
+        }
+        responses
+    }
+
+    /// Opens the legacy HTTP+SSE stream and waits for the `endpoint` event.
+    async fn open_legacy_channel(&self) -> MCPResult<LegacySseChannel> {
+        let response = SHARED_STREAMING_CLIENT
+            .get(&self.base_url)
+            .headers(self.headers.clone())
+            .header(ACCEPT, ACCEPT_SSE)
+            .send()
+            .await
+            .map_err(|e| MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: format!("Failed to open SSE stream: {}", e),
+            })?;
+
+        if !response.status().is_success() || !is_event_stream(&response) {
+            return Err(MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: format!(
+                    "Server supports neither Streamable HTTP nor HTTP+SSE (GET returned HTTP {})",
+                    response.status()
+                ),
+            });
+        }
+
+        let mut body = response.bytes_stream();
+        let mut parser = SseParser::default();
+        let mut buffered: Vec<SseEvent> = Vec::new();
+
+        let endpoint =
+            tokio::time::timeout(Duration::from_millis(DEFAULT_HTTP_TIMEOUT_MS), async {
+                while let Some(chunk) = body.next().await {
+                    let chunk = chunk.map_err(|e| MCPError::ConnectionFailed {
+                        server: self.config.name.clone(),
+                        message: format!("SSE stream error: {}", e),
+                    })?;
+                    let mut events = parser.feed(&chunk).into_iter();
+                    if let Some(event) = events.by_ref().find(|e| e.event == "endpoint") {
+                        buffered.extend(events);
+                        return Ok(event.data);
+                    }
+                }
+                Err(MCPError::ConnectionFailed {
+                    server: self.config.name.clone(),
+                    message: "SSE stream closed before the endpoint event".to_string(),
+                })
+            })
+            .await
+            .map_err(|_| MCPError::Timeout {
+                operation: "waiting for SSE endpoint event".to_string(),
+                timeout_ms: DEFAULT_HTTP_TIMEOUT_MS,
+            })??;
+
+        let endpoint =
+            resolve_endpoint(&self.base_url, &endpoint).ok_or_else(|| MCPError::InvalidConfig {
+                field: "endpoint".to_string(),
+                reason: format!("Invalid SSE endpoint announced by server: {}", endpoint),
+            })?;
+
+        info!(
+            server_id = %self.config.id,
+            endpoint = %endpoint,
+            "Legacy HTTP+SSE channel opened"
+        );
+
+        let pending: PendingResponses = Arc::new(Mutex::new(HashMap::new()));
+        let reader = tokio::spawn(run_legacy_reader(
+            body,
+            parser,
+            buffered,
+            pending.clone(),
+            self.client.clone(),
+            endpoint.clone(),
+            self.headers.clone(),
+            self.config.id.clone(),
+            self.notifications.clone(),
+        ));
+
+        Ok(LegacySseChannel {
+            endpoint,
+            pending,
+            reader,
+        })
+    }
+
+    /// Sends a request over the legacy HTTP+SSE transport and waits for the
+    /// response on the SSE stream.
+    async fn send_legacy(&self, request: &JsonRpcRequest) -> MCPResult<JsonRpcResponse> {
+        let channel = self
+            .legacy
+            .as_ref()
+            .ok_or_else(|| MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: "SSE channel is not open".to_string(),
+            })?;
+
+        let key = request_key(&request.id);
+        let (tx, rx) = oneshot::channel();
+        channel.pending.lock().await.insert(key.clone(), tx);
+
+        let post = self
+            .client
+            .post(&channel.endpoint)
+            .headers(self.headers.clone())
+            .json(request)
+            .send()
+            .await;
+
+        let status_error = match post {
+            Ok(response) if response.status().is_success() => None,
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                Some(format!("HTTP {} - {}", status, body))
+            }
+            Err(e) => Some(format!("HTTP request failed: {}", e)),
+        };
+        if let Some(message) = status_error {
+            channel.pending.lock().await.remove(&key);
+            return Err(MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message,
+            });
+        }
+
+        match tokio::time::timeout(Duration::from_millis(DEFAULT_HTTP_TIMEOUT_MS), rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: "SSE stream closed before the response was received".to_string(),
+            }),
+            Err(_) => {
+                channel.pending.lock().await.remove(&key);
+                Err(MCPError::Timeout {
+                    operation: format!("{} (HTTP+SSE)", request.method),
+                    timeout_ms: DEFAULT_HTTP_TIMEOUT_MS,
+                })
+            }
+        }
+    }
+
+    /// Notifies the server that a request was cancelled.
+    ///
+    /// # Returns
+    /// The `MCPError::Cancelled` error of the request
+    async fn cancel_request(&self, request_id: i64, operation: &str) -> MCPError {
+        info!(
+            server_id = %self.config.id,
+            request_id,
+            operation = %operation,
+            "Cancelling MCP request"
+        );
+        let params = serde_json::json!({
+            "requestId": request_id,
+            "reason": "Cancelled by the user"
+        });
+        if let Err(e) = self
+            .send_notification("notifications/cancelled", Some(params))
+            .await
+        {
+            warn!(
+                server_id = %self.config.id,
+                error = %e,
+                "Failed to notify the server of the cancellation"
+            );
+        }
+        MCPError::Cancelled {
+            server: self.config.name.clone(),
+            operation: operation.to_string(),
+        }
+    }
+
+    /// Sends a JSON-RPC notification (no response expected)
+    async fn send_notification(
+        &self,
+        method: &str,
+        params: Option<serde_json::Value>,
+    ) -> MCPResult<()> {
+        debug!(
+            server_id = %self.config.id,
+            method = %method,
+            "Sending HTTP notification"
+        );
+
+        let notification = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": method,
             "params": params.unwrap_or(serde_json::Value::Object(serde_json::Map::new()))
         });
 
+        let (url, headers) = match &self.legacy {
+            Some(channel) => (channel.endpoint.as_str(), self.headers.clone()),
+            None => (self.base_url.as_str(), self.request_headers()),
+        };
+
         let response = self
             .client
-            .post(&self.base_url)
-            .headers(self.headers.clone())
+            .post(url)
+            .headers(headers)
+            .header(ACCEPT, ACCEPT_JSON_OR_SSE)
             .json(&notification)
             .send()
             .await
@@ -563,7 +1575,7 @@ impl MCPHttpHandle {
             })?;
 
         // For notifications, we just check that the request succeeded
-        // The server may or may not return a response
+        // (Streamable HTTP servers answer 202 Accepted without body)
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
@@ -579,6 +1591,27 @@ impl MCPHttpHandle {
         Ok(())
     }
 
+    /// Builds the headers of a Streamable HTTP request (custom headers,
+    /// session ID and negotiated protocol version).
+    fn request_headers(&self) -> HeaderMap {
+        let mut headers = self.headers.clone();
+        if let Some(value) = self
+            .session_id
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(SESSION_ID_HEADER, value);
+        }
+        if let Some(value) = self
+            .protocol_version
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(PROTOCOL_VERSION_HEADER, value);
+        }
+        headers
+    }
+
     /// Generates the next request ID
     fn next_request_id(&self) -> i64 {
         self.request_id.fetch_add(1, Ordering::SeqCst)
@@ -586,6 +1619,16 @@ impl MCPHttpHandle {
 
     /// Disconnects from the MCP server
     ///
+    /// Returns and resets the `notifications/tools/list_changed` flag
+    pub fn take_tools_changed(&self) -> bool {
+        self.notifications.take_tools_changed()
+    }
+
+    /// Whether a `notifications/tools/list_changed` is pending
+    pub fn tools_changed(&self) -> bool {
+        self.notifications.tools_changed()
+    }
+
     /// Sends a shutdown notification and marks the connection as closed.
     pub async fn disconnect(&mut self) -> MCPResult<()> {
         if !self.connected {
@@ -600,10 +1643,29 @@ impl MCPHttpHandle {
         // Send shutdown notification (best effort)
         let _ = self.send_notification("shutdown", None).await;
 
+        // Terminate the Streamable HTTP session (best effort, 405 = not supported)
+        if self.session_id.is_some() {
+            if let Err(e) = self
+                .client
+                .delete(&self.base_url)
+                .headers(self.request_headers())
+                .send()
+                .await
+            {
+                debug!(server_id = %self.config.id, error = %e, "Session termination failed");
+            }
+            self.session_id = None;
+        }
+
+        if let Some(channel) = self.legacy.take() {
+            channel.reader.abort();
+        }
+
         self.connected = false;
         self.status = MCPServerStatus::Stopped;
         self.tools.clear();
         self.resources.clear();
+        self.prompts.clear();
 
         info!(
             server_id = %self.config.id,
@@ -639,6 +1701,182 @@ impl MCPHttpHandle {
     pub fn set_error_status(&mut self) {
         self.status = MCPServerStatus::Error;
     }
+
+    /// Returns the transport variant in use
+    pub fn transport(&self) -> HttpTransportKind {
+        self.transport
+    }
+
+    /// Returns the Streamable HTTP session ID, if the server assigned one
+    pub fn session_id(&self) -> Option<&str> {
+        self.session_id.as_deref()
+    }
+}
+
+/// Returns true if the response body is an SSE stream.
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with(ACCEPT_SSE))
+        .unwrap_or(false)
+}
+
+/// Resolves the POST endpoint announced by a legacy SSE server.
+fn resolve_endpoint(base_url: &str, endpoint: &str) -> Option<String> {
+    reqwest::Url::parse(base_url)
+        .ok()?
+        .join(endpoint.trim())
+        .ok()
+        .map(|url| url.to_string())
+}
+
+/// Orders batch responses like the requests they answer.
+///
+/// # Returns
+/// None if a request has no response
+fn order_responses(
+    request_ids: &[JsonRpcId],
+    responses: Vec<JsonRpcResponse>,
+) -> Option<Vec<JsonRpcResponse>> {
+    let mut received: Vec<Option<JsonRpcResponse>> = vec![None; request_ids.len()];
+    for response in responses {
+        let slot = request_ids
+            .iter()
+            .position(|id| response.id.as_ref() == Some(id));
+        if let Some(slot) = slot {
+            received[slot] = Some(response);
+        }
+    }
+    received.into_iter().collect()
+}
+
+/// Converts a `resources/read` response into its result.
+fn resource_read_result(response: JsonRpcResponse) -> MCPResult<MCPResourceReadResult> {
+    if let Some(error) = response.error {
+        return Err(MCPError::ProtocolError {
+            code: error.code,
+            message: error.message,
+        });
+    }
+
+    let result = response.result.ok_or_else(|| MCPError::ProtocolError {
+        code: -32600,
+        message: "No result in resource read response".to_string(),
+    })?;
+
+    Ok(serde_json::from_value(result)?)
+}
+
+/// Key of a pending legacy request.
+fn request_key(id: &JsonRpcId) -> String {
+    serde_json::to_string(id).unwrap_or_default()
+}
+
+/// Handles a JSON-RPC message sent by the server on an SSE stream.
+///
+/// Server requests are answered (`ping` with an empty result, anything else
+/// with "method not found"); notifications are recorded in `notifications`.
+///
+/// # Returns
+/// The message as a response if it is one
+async fn handle_server_message(
+    client: &Client,
+    url: &str,
+    headers: HeaderMap,
+    server_id: &str,
+    notifications: &NotificationState,
+    data: &str,
+) -> Option<JsonRpcResponse> {
+    let message: Value = match serde_json::from_str(data) {
+        Ok(message) => message,
+        Err(e) => {
+            warn!(server_id = %server_id, error = %e, "Invalid JSON-RPC message on SSE stream");
+            return None;
+        }
+    };
+
+    let Some(method) = message.get("method").and_then(|m| m.as_str()) else {
+        return serde_json::from_value(message).ok();
+    };
+
+    let Some(id) = message.get("id").filter(|id| !id.is_null()) else {
+        notifications.handle(server_id, method, message.get("params"));
+        return None;
+    };
+
+    debug!(server_id = %server_id, method = %method, "Server request received");
+    let reply = server_request_reply(id, method);
+
+    if let Err(e) = client
+        .post(url)
+        .headers(headers)
+        .header(ACCEPT, ACCEPT_JSON_OR_SSE)
+        .json(&reply)
+        .send()
+        .await
+    {
+        warn!(server_id = %server_id, error = %e, "Failed to answer server request");
+    }
+    None
+}
+
+/// Reads the legacy SSE stream and dispatches responses to pending requests.
+#[allow(clippy::too_many_arguments)]
+async fn run_legacy_reader<B: AsRef<[u8]>>(
+    mut body: impl futures_util::Stream<Item = reqwest::Result<B>> + Unpin,
+    mut parser: SseParser,
+    buffered: Vec<SseEvent>,
+    pending: PendingResponses,
+    client: Client,
+    endpoint: String,
+    headers: HeaderMap,
+    server_id: String,
+    notifications: NotificationState,
+) {
+    let mut events = buffered;
+    loop {
+        for event in events.drain(..) {
+            if event.event != "message" || event.data.is_empty() {
+                continue;
+            }
+            let Some(response) = handle_server_message(
+                &client,
+                &endpoint,
+                headers.clone(),
+                &server_id,
+                &notifications,
+                &event.data,
+            )
+            .await
+            else {
+                continue;
+            };
+            let key = response.id.as_ref().map(request_key).unwrap_or_default();
+            match pending.lock().await.remove(&key) {
+                Some(tx) => {
+                    let _ = tx.send(response);
+                }
+                None => debug!(server_id = %server_id, "Unmatched response on SSE stream"),
+            }
+        }
+
+        match body.next().await {
+            Some(Ok(chunk)) => events = parser.feed(chunk.as_ref()),
+            Some(Err(e)) => {
+                warn!(server_id = %server_id, error = %e, "Legacy SSE stream error");
+                break;
+            }
+            None => {
+                warn!(server_id = %server_id, "Legacy SSE stream closed by server");
+                break;
+            }
+        }
+    }
+
+    // Wake up waiting requests (their senders are dropped)
+    pending.lock().await.clear();
 }
 
 impl Drop for MCPHttpHandle {
@@ -656,14 +1894,55 @@ impl Drop for MCPHttpHandle {
             // Mark as disconnected to prevent double-cleanup
             self.connected = false;
         }
+
+        // The legacy SSE reader task would otherwise outlive the handle
+        if let Some(channel) = self.legacy.take() {
+            channel.reader.abort();
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mcp::oauth::{MemoryCredentialStore, OAuthCredentials};
+    use crate::mcp::test_server::{
+        accepted, http, json_reply, spawn_mock_server, sse_reply, MockReply, MockRequest,
+    };
     use crate::models::mcp::MCPDeploymentMethod;
+    use serde_json::json;
     use std::collections::HashMap;
+    use std::sync::atomic::AtomicBool;
+    use tokio::sync::mpsc;
+
+    fn result_for(request: &MockRequest, result: Value) -> Value {
+        json!({"jsonrpc": "2.0", "id": request.body["id"], "result": result})
+    }
+
+    fn init_result(request: &MockRequest) -> Value {
+        result_for(
+            request,
+            json!({
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "serverInfo": {"name": "mock", "version": "1.0"}
+            }),
+        )
+    }
+
+    fn tools_result(request: &MockRequest) -> Value {
+        result_for(
+            request,
+            json!({"tools": [{"name": "echo", "description": "Echo", "inputSchema": {"type": "object"}}]}),
+        )
+    }
+
+    fn mock_config(url: String) -> MCPServerConfig {
+        MCPServerConfig {
+            args: vec![url],
+            ..create_test_http_config()
+        }
+    }
 
     fn create_test_http_config() -> MCPServerConfig {
         MCPServerConfig {
@@ -724,4 +2003,502 @@ mod tests {
         // Empty args should fail at connect time
         assert!(config.args.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_streamable_http_session_and_sse_response() {
+        let requests: Arc<std::sync::Mutex<Vec<MockRequest>>> = Arc::default();
+        let log = requests.clone();
+        let url = spawn_mock_server(move |request| {
+            log.lock().unwrap().push(request.clone());
+            match (request.method.as_str(), request.rpc_method()) {
+                ("POST", "initialize") => {
+                    json_reply(&[("Mcp-Session-Id", "session-1")], init_result(&request))
+                }
+                ("POST", "tools/list") => {
+                    // Server request interleaved before the response
+                    let ping = json!({"jsonrpc": "2.0", "id": "srv-1", "method": "ping"});
+                    sse_reply(&format!(
+                        "id: e1\ndata: {}\n\nid: e2\ndata: {}\n\n",
+                        ping,
+                        tools_result(&request)
+                    ))
+                }
+                ("POST", "resources/list") => {
+                    json_reply(&[], result_for(&request, json!({"resources": []})))
+                }
+                ("POST", _) | ("DELETE", _) => accepted(),
+                _ => http("405 Method Not Allowed", &[], ""),
+            }
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(url)).await.unwrap();
+        let init = handle.initialize().await.unwrap();
+
+        assert_eq!(init.server_info.name, "mock");
+        assert_eq!(handle.transport(), HttpTransportKind::StreamableHttp);
+        assert_eq!(handle.session_id(), Some("session-1"));
+        assert_eq!(handle.list_tools().len(), 1);
+
+        handle.disconnect().await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let tools_list = requests
+            .iter()
+            .find(|r| r.rpc_method() == "tools/list")
+            .unwrap();
+        assert_eq!(tools_list.header(SESSION_ID_HEADER), Some("session-1"));
+        assert_eq!(
+            tools_list.header(PROTOCOL_VERSION_HEADER),
+            Some("2025-06-18")
+        );
+        assert_eq!(tools_list.header("accept"), Some(ACCEPT_JSON_OR_SSE));
+        // The ping server request was answered
+        assert!(requests
+            .iter()
+            .any(|r| r.body["id"] == "srv-1" && r.body["result"] == json!({})));
+        // The session was terminated
+        assert!(requests
+            .iter()
+            .any(|r| r.method == "DELETE" && r.header(SESSION_ID_HEADER) == Some("session-1")));
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_reinitializes_expired_session() {
+        let expired = Arc::new(AtomicBool::new(false));
+        let sessions = Arc::new(AtomicI64::new(0));
+        let (flag, counter) = (expired.clone(), sessions.clone());
+        let url = spawn_mock_server(move |request| {
+            if flag.load(Ordering::SeqCst) && request.header(SESSION_ID_HEADER) == Some("s1") {
+                return http("404 Not Found", &[], "");
+            }
+            match request.rpc_method() {
+                "initialize" => {
+                    let session = format!("s{}", counter.fetch_add(1, Ordering::SeqCst) + 1);
+                    json_reply(&[("Mcp-Session-Id", &session)], init_result(&request))
+                }
+                "tools/list" => json_reply(&[], tools_result(&request)),
+                "resources/list" => json_reply(&[], result_for(&request, json!({"resources": []}))),
+                _ => accepted(),
+            }
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(url)).await.unwrap();
+        handle.initialize().await.unwrap();
+        assert_eq!(handle.session_id(), Some("s1"));
+
+        expired.store(true, Ordering::SeqCst);
+        let tools = handle.refresh_tools().await.unwrap();
+
+        assert_eq!(tools.len(), 1);
+        assert_eq!(handle.session_id(), Some("s2"));
+        assert_eq!(sessions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_prompts() {
+        let url = spawn_mock_server(|request| match request.rpc_method() {
+            "initialize" => json_reply(
+                &[],
+                result_for(
+                    &request,
+                    json!({
+                        "protocolVersion": "2025-06-18",
+                        "capabilities": {"prompts": {}},
+                        "serverInfo": {"name": "mock", "version": "1.0"}
+                    }),
+                ),
+            ),
+            "tools/list" => json_reply(&[], tools_result(&request)),
+            "resources/list" => json_reply(&[], result_for(&request, json!({"resources": []}))),
+            "prompts/list" => json_reply(
+                &[],
+                result_for(
+                    &request,
+                    json!({"prompts": [{
+                        "name": "review",
+                        "arguments": [{"name": "code", "required": true}]
+                    }]}),
+                ),
+            ),
+            "prompts/get" => match request.body["params"]["arguments"]["code"].as_str() {
+                Some(code) => json_reply(
+                    &[],
+                    result_for(
+                        &request,
+                        json!({"messages": [{
+                            "role": "user",
+                            "content": {"type": "text", "text": format!("Review {}", code)}
+                        }]}),
+                    ),
+                ),
+                None => json_reply(
+                    &[],
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": request.body["id"],
+                        "error": {"code": -32602, "message": "Missing argument: code"}
+                    }),
+                ),
+            },
+            _ => accepted(),
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(url)).await.unwrap();
+        handle.initialize().await.unwrap();
+
+        let prompts = handle.list_prompts();
+        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts[0].name, "review");
+        assert!(prompts[0].arguments[0].required);
+
+        let arguments = HashMap::from([("code".to_string(), "main.rs".to_string())]);
+        let result = handle.get_prompt("review", arguments).await.unwrap();
+        assert_eq!(result.messages.len(), 1);
+        assert!(matches!(
+            result.messages[0].content,
+            crate::mcp::MCPContent::Text { ref text } if text == "Review main.rs"
+        ));
+
+        let err = handle
+            .get_prompt("review", HashMap::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, MCPError::ProtocolError { code: -32602, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_refreshes_oauth_token_on_401() {
+        let url = spawn_mock_server(|request| {
+            if request.path == "/token" {
+                let form = request.form();
+                assert_eq!(form["grant_type"], "refresh_token");
+                assert_eq!(form["refresh_token"], "refresh-1");
+                return json_reply(
+                    &[],
+                    json!({"access_token": "access-2", "token_type": "Bearer", "expires_in": 3600}),
+                );
+            }
+            if request.method == "POST"
+                && request.header("authorization") != Some("Bearer access-2")
+            {
+                return http("401 Unauthorized", &[("WWW-Authenticate", "Bearer")], "");
+            }
+            match request.rpc_method() {
+                "initialize" => json_reply(&[("Mcp-Session-Id", "s1")], init_result(&request)),
+                "tools/list" => json_reply(&[], tools_result(&request)),
+                "resources/list" => json_reply(&[], result_for(&request, json!({"resources": []}))),
+                _ => accepted(),
+            }
+        })
+        .await;
+
+        let store = Arc::new(MemoryCredentialStore::default());
+        let credentials = OAuthCredentials {
+            client_id: "client-1".to_string(),
+            client_secret: None,
+            token_endpoint: format!("{}/token", url),
+            resource: url.clone(),
+            access_token: "access-1".to_string(),
+            refresh_token: Some("refresh-1".to_string()),
+            expires_at: None,
+            scope: None,
+        };
+        store.save_credentials("http_test", &credentials).unwrap();
+
+        let mut handle = MCPHttpHandle::connect_with_oauth(mock_config(url), Some(store.clone()))
+            .await
+            .unwrap();
+        handle.initialize().await.unwrap();
+
+        assert_eq!(handle.list_tools().len(), 1);
+        let saved = store.load_credentials("http_test").unwrap();
+        assert_eq!(saved.access_token, "access-2");
+        assert_eq!(saved.refresh_token.as_deref(), Some("refresh-1"));
+        assert!(saved.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_unauthorized_without_credentials() {
+        let url = spawn_mock_server(|request| match request.method.as_str() {
+            "POST" => http("401 Unauthorized", &[("WWW-Authenticate", "Bearer")], ""),
+            _ => http("405 Method Not Allowed", &[], ""),
+        })
+        .await;
+
+        let store = Arc::new(MemoryCredentialStore::default());
+        let mut handle = MCPHttpHandle::connect_with_oauth(mock_config(url), Some(store))
+            .await
+            .unwrap();
+        let error = handle.initialize().await.unwrap_err();
+
+        assert!(error.to_string().contains("401"));
+        assert_eq!(handle.transport(), HttpTransportKind::StreamableHttp);
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_resumes_interrupted_stream() {
+        let resumed_from: Arc<std::sync::Mutex<Option<String>>> = Arc::default();
+        let pending_id: Arc<std::sync::Mutex<Value>> = Arc::default();
+        let (resumed, pending) = (resumed_from.clone(), pending_id.clone());
+        let url = spawn_mock_server(move |request| match request.method.as_str() {
+            "GET" => {
+                *resumed.lock().unwrap() =
+                    request.header(LAST_EVENT_ID_HEADER).map(String::from);
+                let response = MockRequest {
+                    body: json!({"id": pending.lock().unwrap().clone()}),
+                    ..request
+                };
+                sse_reply(&format!("id: e2\ndata: {}\n\n", tools_result(&response)))
+            }
+            "POST" => match request.rpc_method() {
+                "initialize" => json_reply(&[("Mcp-Session-Id", "s1")], init_result(&request)),
+                "tools/list" => {
+                    // Stream closes after a notification, before the response
+                    *pending.lock().unwrap() = request.body["id"].clone();
+                    sse_reply(
+                        "id: e1\ndata: {\"jsonrpc\":\"2.0\",\"method\":\"notifications/progress\"}\n\n",
+                    )
+                }
+                "resources/list" => json_reply(&[], result_for(&request, json!({"resources": []}))),
+                _ => accepted(),
+            },
+            _ => http("405 Method Not Allowed", &[], ""),
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(url)).await.unwrap();
+        handle.initialize().await.unwrap();
+
+        assert_eq!(handle.list_tools().len(), 1);
+        assert_eq!(resumed_from.lock().unwrap().as_deref(), Some("e1"));
+    }
+
+    fn batching_init_result(request: &MockRequest) -> Value {
+        result_for(
+            request,
+            json!({
+                "protocolVersion": "2025-03-26",
+                "capabilities": {"prompts": {}, "resources": {}},
+                "serverInfo": {"name": "mock", "version": "1.0"}
+            }),
+        )
+    }
+
+    fn batch_item_result(item: &Value) -> Value {
+        let result = match item["method"].as_str().unwrap_or_default() {
+            "tools/list" => {
+                json!({"tools": [{"name": "echo", "description": "Echo", "inputSchema": {"type": "object"}}]})
+            }
+            "resources/list" => json!({"resources": [{"uri": "file:///a", "name": "a"}]}),
+            "prompts/list" => json!({"prompts": [{"name": "review"}]}),
+            "resources/read" if item["params"]["uri"] == "file:///a" => {
+                json!({"contents": [{"uri": "file:///a", "text": "A"}]})
+            }
+            _ => {
+                return json!({
+                    "jsonrpc": "2.0",
+                    "id": item["id"],
+                    "error": {"code": -32002, "message": "Resource not found"}
+                })
+            }
+        };
+        json!({"jsonrpc": "2.0", "id": item["id"], "result": result})
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_sends_json_rpc_batches() {
+        let requests: Arc<std::sync::Mutex<Vec<MockRequest>>> = Arc::default();
+        let log = requests.clone();
+        let url = spawn_mock_server(move |request| {
+            log.lock().unwrap().push(request.clone());
+            if request.method != "POST" {
+                return accepted();
+            }
+            match &request.body {
+                Value::Array(items) => {
+                    // Responses in reverse order: the client must match them by ID
+                    let responses: Vec<Value> = items.iter().rev().map(batch_item_result).collect();
+                    if items[0]["method"] == "resources/read" {
+                        let events: String = responses
+                            .iter()
+                            .map(|r| format!("data: {}\n\n", r))
+                            .collect();
+                        sse_reply(&events)
+                    } else {
+                        json_reply(&[], Value::Array(responses))
+                    }
+                }
+                _ if request.rpc_method() == "initialize" => {
+                    json_reply(&[], batching_init_result(&request))
+                }
+                _ => accepted(),
+            }
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(url)).await.unwrap();
+        handle.initialize().await.unwrap();
+
+        assert_eq!(handle.list_tools().len(), 1);
+        assert_eq!(handle.list_resources().len(), 1);
+        assert_eq!(handle.list_prompts().len(), 1);
+
+        let results = handle
+            .read_resources(&["file:///a".to_string(), "file:///b".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results[0].as_ref().unwrap().contents[0].text.as_deref(),
+            Some("A")
+        );
+        assert!(matches!(
+            &results[1],
+            Err(MCPError::ProtocolError { code: -32002, .. })
+        ));
+
+        let requests = requests.lock().unwrap();
+        let batches: Vec<&MockRequest> = requests.iter().filter(|r| r.body.is_array()).collect();
+        assert_eq!(batches.len(), 2);
+        let init_methods: Vec<&str> = batches[0]
+            .body
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|item| item["method"].as_str())
+            .collect();
+        assert_eq!(
+            init_methods,
+            ["tools/list", "resources/list", "prompts/list"]
+        );
+        // No list request was sent on its own
+        assert!(!requests.iter().any(|r| r.rpc_method() == "tools/list"));
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_rejected_batch_falls_back_to_single_requests() {
+        let requests: Arc<std::sync::Mutex<Vec<MockRequest>>> = Arc::default();
+        let log = requests.clone();
+        let url = spawn_mock_server(move |request| {
+            log.lock().unwrap().push(request.clone());
+            if request.method != "POST" {
+                return accepted();
+            }
+            match request.rpc_method() {
+                _ if request.body.is_array() => http("400 Bad Request", &[], ""),
+                "initialize" => json_reply(&[], batching_init_result(&request)),
+                "tools/list" | "resources/list" | "prompts/list" | "resources/read" => {
+                    json_reply(&[], batch_item_result(&request.body))
+                }
+                _ => accepted(),
+            }
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(url)).await.unwrap();
+        handle.initialize().await.unwrap();
+
+        assert_eq!(handle.list_tools().len(), 1);
+        assert_eq!(handle.list_resources().len(), 1);
+        assert_eq!(handle.list_prompts().len(), 1);
+
+        let results = handle
+            .read_resources(&["file:///a".to_string(), "file:///b".to_string()])
+            .await
+            .unwrap();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+
+        // Only the first batch was attempted
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.iter().filter(|r| r.body.is_array()).count(), 1);
+        assert_eq!(
+            requests
+                .iter()
+                .filter(|r| r.rpc_method() == "resources/read")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_order_responses() {
+        let response = |id: i64| JsonRpcResponse {
+            jsonrpc: "2.0".to_string(),
+            result: Some(json!(id)),
+            error: None,
+            id: Some(JsonRpcId::Number(id)),
+        };
+        let ids = [JsonRpcId::Number(1), JsonRpcId::Number(2)];
+
+        let ordered = order_responses(&ids, vec![response(2), response(1)]).unwrap();
+        assert_eq!(ordered[0].result, Some(json!(1)));
+        assert_eq!(ordered[1].result, Some(json!(2)));
+
+        assert!(order_responses(&ids, vec![response(1)]).is_none());
+        assert!(supports_batching("2025-03-26"));
+        assert!(!supports_batching("2025-06-18"));
+    }
+
+    #[tokio::test]
+    async fn test_legacy_sse_fallback() {
+        let stream: Arc<std::sync::Mutex<Option<mpsc::UnboundedSender<String>>>> = Arc::default();
+        let sender = stream.clone();
+        let url = spawn_mock_server(move |request| {
+            match (request.method.as_str(), request.path.as_str()) {
+                ("GET", "/sse") => {
+                    let (tx, rx) = mpsc::unbounded_channel();
+                    tx.send("event: endpoint\ndata: /messages?sessionId=abc\n\n".to_string())
+                        .unwrap();
+                    *sender.lock().unwrap() = Some(tx);
+                    MockReply::Stream(rx)
+                }
+                ("POST", path) if path.starts_with("/messages?sessionId=abc") => {
+                    let response = match request.rpc_method() {
+                        "initialize" => Some(init_result(&request)),
+                        "tools/list" => Some(tools_result(&request)),
+                        "resources/list" => Some(result_for(&request, json!({"resources": []}))),
+                        _ => None,
+                    };
+                    if let (Some(response), Some(tx)) = (response, sender.lock().unwrap().as_ref())
+                    {
+                        tx.send(format!("event: message\ndata: {}\n\n", response))
+                            .unwrap();
+                    }
+                    accepted()
+                }
+                _ => http("405 Method Not Allowed", &[], ""),
+            }
+        })
+        .await;
+
+        let mut handle = MCPHttpHandle::connect(mock_config(format!("{}/sse", url)))
+            .await
+            .unwrap();
+        handle.initialize().await.unwrap();
+
+        assert_eq!(handle.transport(), HttpTransportKind::LegacySse);
+        assert_eq!(handle.session_id(), None);
+        assert_eq!(handle.list_tools().len(), 1);
+        assert_eq!(handle.list_tools()[0].name, "echo");
+
+        handle.disconnect().await.unwrap();
+    }
+
+    #[test]
+    fn test_resolve_endpoint() {
+        assert_eq!(
+            resolve_endpoint("https://api.example.com/sse", "/messages?sessionId=1").as_deref(),
+            Some("https://api.example.com/messages?sessionId=1")
+        );
+        assert_eq!(
+            resolve_endpoint("https://api.example.com/sse", "https://other.example.com/m")
+                .as_deref(),
+            Some("https://other.example.com/m")
+        );
+        assert_eq!(resolve_endpoint("not a url", "/messages"), None);
+    }
 }
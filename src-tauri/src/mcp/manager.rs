@@ -19,6 +19,8 @@
 //! - Database persistence for server configurations
 //! - Tool routing across multiple servers
 //! - Automatic server startup on application launch
+//! - Supervision: periodic health checks, automatic restart of crashed
+//!   stdio servers and per-server status history
 //!
 //! ## Architecture
 //!
@@ -41,28 +43,97 @@
 //!
 //! Server configurations are stored in the `mcp_server` table and
 //! automatically loaded on startup. Tool calls are logged to `mcp_call_log`.
+//!
+//! ## Supervision
+//!
+//! The health check task pings every running server (falling back to
+//! `tools/list` for servers without `ping`). Crashed stdio processes are
+//! restarted with exponential backoff, up to `MAX_RESTART_ATTEMPTS` times.
+//! Every status transition is kept in a bounded per-server history and
+//! emitted as the `mcp_server_status_changed` event.
+//!
+//! ## Schema Cache
+//!
+//! The tools and resources of each server are cached with a checksum
+//! ([`ServerSchema`]) when first requested. The cache is only invalidated when
+//! the server sends `notifications/tools/list_changed`, when its resources are
+//! refreshed, or when it stops or restarts, so building a prompt never waits
+//! on an MCP server.
 
+use crate::agents::behavior_changelog::record_behavior_changes_best_effort;
 use crate::db::{sanitize_for_surrealdb, DBClient};
+use crate::inbox;
 use crate::mcp::circuit_breaker::CircuitBreaker;
 use crate::mcp::client::MCPClient;
+use crate::mcp::notifications::ProgressSender;
+use crate::mcp::oauth::{
+    MCPOAuthClient, OAuthAuthorizationStart, OAuthCredentialStore, PendingAuthorizations,
+};
 use crate::mcp::{MCPError, MCPResult};
+use crate::models::agent::MCPToolPolicy;
+use crate::models::audit::{AuditAction, AuditLogCreate};
+use crate::models::behavior_changelog::{mcp_server_change, mcp_tools_change};
 use crate::models::mcp::{
-    MCPCallLogCreate, MCPServer, MCPServerConfig, MCPServerCreate, MCPServerStatus, MCPTestResult,
-    MCPTool, MCPToolCallRequest, MCPToolCallResult,
+    MCPCallLogCreate, MCPDeploymentMethod, MCPPrompt, MCPPromptResult, MCPResource,
+    MCPResourceContents, MCPServer, MCPServerConfig, MCPServerCreate, MCPServerStatus,
+    MCPServerStatusChange, MCPTestResult, MCPTool, MCPToolCallRequest, MCPToolCallResult,
 };
+use crate::models::pending_item::{PendingItemCreate, PendingItemKind};
+use crate::models::streaming::events;
+use crate::security::audit;
 use chrono::Utc;
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
 use tokio::sync::{broadcast, RwLock};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-/// Tool cache TTL (1 hour)
-const TOOL_CACHE_TTL: Duration = Duration::from_secs(3600);
+/// Default health check interval (30 seconds, `ping` is cheap)
+const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Maximum status transitions kept per server
+const STATUS_HISTORY_LIMIT: usize = 50;
+
+/// Maximum automatic restart attempts of a crashed stdio server
+const MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Delay after the first failed restart attempt (doubles with each attempt)
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(30);
+
+/// Maximum delay between two restart attempts (10 minutes)
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(600);
+
+/// Crashed server waiting for an automatic restart
+struct PendingRestart {
+    /// Configuration used to respawn the server
+    config: MCPServerConfig,
+    /// Restart attempts that failed so far
+    failed_attempts: u32,
+    /// Earliest time of the next attempt
+    next_attempt_at: Instant,
+}
+
+/// Outcome of a health check
+enum HealthOutcome {
+    /// Server answered the probe
+    Healthy,
+    /// Server is running but the probe failed
+    Unhealthy(MCPError),
+    /// Stdio server process exited
+    Crashed,
+}
 
-/// Default health check interval (5 minutes)
-const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+/// Delay before the next restart attempt after `failed_attempts` failures
+fn restart_backoff(failed_attempts: u32) -> Duration {
+    let factor = 2u32.saturating_pow(failed_attempts.saturating_sub(1));
+    RESTART_BACKOFF_BASE
+        .saturating_mul(factor)
+        .min(RESTART_BACKOFF_MAX)
+}
 
 /// Maximum retry attempts for transient MCP errors
 const MCP_MAX_RETRY_ATTEMPTS: u32 = 2;
@@ -70,6 +141,32 @@ const MCP_MAX_RETRY_ATTEMPTS: u32 = 2;
 /// Initial retry delay in milliseconds (doubles with each attempt)
 const MCP_INITIAL_RETRY_DELAY_MS: u64 = 500;
 
+/// Cached tools and resources of a server
+#[derive(Debug, Clone)]
+pub struct ServerSchema {
+    /// Tools discovered on the server
+    pub tools: Vec<MCPTool>,
+    /// Resources discovered on the server
+    pub resources: Vec<MCPResource>,
+    /// SHA-256 of the tools and resources (hex), changes with the schema
+    pub checksum: String,
+}
+
+impl ServerSchema {
+    /// Creates a schema and computes its checksum
+    pub fn new(tools: Vec<MCPTool>, resources: Vec<MCPResource>) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(serde_json::to_vec(&tools).unwrap_or_default());
+        hasher.update(serde_json::to_vec(&resources).unwrap_or_default());
+        let checksum = format!("{:x}", hasher.finalize());
+        Self {
+            tools,
+            resources,
+            checksum,
+        }
+    }
+}
+
 /// MCP Manager
 ///
 /// Manages the lifecycle of multiple MCP servers and provides
@@ -101,14 +198,24 @@ pub struct MCPManager {
     clients: RwLock<HashMap<String, MCPClient>>,
     /// Database client for persistence
     db: Arc<DBClient>,
-    /// Tool cache with TTL (server_name -> (tools, cached_at))
-    tool_cache: RwLock<HashMap<String, (Vec<MCPTool>, Instant)>>,
+    /// Tools and resources per server (server_name -> schema)
+    schema_cache: RwLock<HashMap<String, ServerSchema>>,
     /// Circuit breakers per server (server_name -> CircuitBreaker)
     circuit_breakers: RwLock<HashMap<String, CircuitBreaker>>,
     /// ID to Name lookup table for O(1) access (server_id -> server_name)
     id_to_name: RwLock<HashMap<String, String>>,
     /// Shutdown signal sender for health check task
     health_check_shutdown: broadcast::Sender<()>,
+    /// OAuth credential store for HTTP servers (set at startup)
+    oauth_store: RwLock<Option<Arc<dyn OAuthCredentialStore>>>,
+    /// OAuth authorizations waiting for their code
+    oauth_flows: PendingAuthorizations,
+    /// Status transitions per server (server_name -> oldest first)
+    status_history: RwLock<HashMap<String, VecDeque<MCPServerStatusChange>>>,
+    /// Crashed servers waiting for an automatic restart (server_name -> state)
+    pending_restarts: RwLock<HashMap<String, PendingRestart>>,
+    /// App handle for status change events (set at startup)
+    app_handle: RwLock<Option<AppHandle>>,
 }
 
 impl MCPManager {
@@ -131,13 +238,31 @@ impl MCPManager {
         Ok(Self {
             clients: RwLock::new(HashMap::new()),
             db,
-            tool_cache: RwLock::new(HashMap::new()),
+            schema_cache: RwLock::new(HashMap::new()),
             circuit_breakers: RwLock::new(HashMap::new()),
             id_to_name: RwLock::new(HashMap::new()),
             health_check_shutdown: shutdown_tx,
+            oauth_store: RwLock::new(None),
+            oauth_flows: PendingAuthorizations::default(),
+            status_history: RwLock::new(HashMap::new()),
+            pending_restarts: RwLock::new(HashMap::new()),
+            app_handle: RwLock::new(None),
         })
     }
 
+    /// Sets the credential store used for OAuth-protected HTTP servers
+    ///
+    /// Must be called before `load_from_db()` so that servers authorized in a
+    /// previous session connect with their stored tokens.
+    pub async fn set_oauth_store(&self, store: Arc<dyn OAuthCredentialStore>) {
+        *self.oauth_store.write().await = Some(store);
+    }
+
+    /// Sets the app handle used to emit `mcp_server_status_changed` events
+    pub async fn set_app_handle(&self, handle: AppHandle) {
+        *self.app_handle.write().await = Some(handle);
+    }
+
     /// Loads server configurations from the database
     ///
     /// Queries all enabled servers from the database and spawns them.
@@ -227,7 +352,17 @@ impl MCPManager {
 
         let name = config.name.clone();
         let id = config.id.clone();
-        let client = MCPClient::connect(config.clone()).await?;
+        let oauth_store = self.oauth_store.read().await.clone();
+        let client = match MCPClient::connect_with_oauth(config.clone(), oauth_store).await {
+            Ok(client) => client,
+            Err(e) => {
+                self.record_status_change(&id, &name, MCPServerStatus::Error, Some(e.to_string()))
+                    .await;
+                self.audit_server(AuditAction::McpServerStart, &id, &name, Some(&e))
+                    .await;
+                return Err(e);
+            }
+        };
 
         let server = MCPServer {
             config: config.clone(),
@@ -264,6 +399,11 @@ impl MCPManager {
             "MCP server spawned and registered by name"
         );
 
+        self.record_status_change(&id, &name, server.status.clone(), None)
+            .await;
+        self.audit_server(AuditAction::McpServerStart, &id, &name, None)
+            .await;
+
         Ok(server)
     }
 
@@ -282,41 +422,101 @@ impl MCPManager {
     pub async fn stop_server(&self, id: &str) -> MCPResult<()> {
         info!(server_id = %id, "Stopping MCP server");
 
+        // A crashed server waiting for its restart is stopped by cancelling it
+        let cancelled_restart = self.cancel_pending_restart(id).await;
+
         // O(1) lookup via id_to_name table (OPT-7)
         let name = {
             let id_lookup = self.id_to_name.read().await;
             id_lookup.get(id).cloned()
-        }
-        .ok_or_else(|| MCPError::ServerNotFound {
-            server: id.to_string(),
-        })?;
+        };
+        let name = match (name, cancelled_restart) {
+            (Some(name), _) => name,
+            (None, Some(name)) => {
+                self.record_status_change(id, &name, MCPServerStatus::Stopped, None)
+                    .await;
+                info!(server_id = %id, server_name = %name, "MCP server restart cancelled");
+                return Ok(());
+            }
+            (None, None) => {
+                return Err(MCPError::ServerNotFound {
+                    server: id.to_string(),
+                })
+            }
+        };
 
-        let mut client = {
-            let mut clients = self.clients.write().await;
-            clients
-                .remove(&name)
+        let mut client =
+            self.unregister_client(&name)
+                .await
                 .ok_or_else(|| MCPError::ServerNotFound {
                     server: id.to_string(),
-                })?
-        };
+                })?;
 
-        // Cleanup lookup table and circuit breaker
-        {
-            let mut id_lookup = self.id_to_name.write().await;
-            id_lookup.remove(id);
-        }
-        {
-            let mut breakers = self.circuit_breakers.write().await;
-            breakers.remove(&name);
-        }
+        let disconnected = client.disconnect().await;
+        self.audit_server(
+            AuditAction::McpServerStop,
+            id,
+            &name,
+            disconnected.as_ref().err(),
+        )
+        .await;
+        disconnected?;
 
-        client.disconnect().await?;
+        self.record_status_change(id, &name, MCPServerStatus::Stopped, None)
+            .await;
 
         info!(server_id = %id, server_name = %name, "MCP server stopped");
 
         Ok(())
     }
 
+    /// Records a server start or stop in the audit log.
+    async fn audit_server(
+        &self,
+        action: AuditAction,
+        server_id: &str,
+        server_name: &str,
+        error: Option<&MCPError>,
+    ) {
+        audit::record(
+            &self.db,
+            AuditLogCreate {
+                success: error.is_none(),
+                details: serde_json::json!({
+                    "server_id": server_id,
+                    "error": error.map(|e| e.to_string())
+                }),
+                ..AuditLogCreate::new(action, "mcp_manager", server_name)
+            },
+        )
+        .await;
+    }
+
+    /// Removes a client from the registry, the lookup table, the circuit breakers
+    /// and the schema cache
+    async fn unregister_client(&self, name: &str) -> Option<MCPClient> {
+        let client = self.clients.write().await.remove(name)?;
+        self.id_to_name.write().await.remove(&client.config().id);
+        self.circuit_breakers.write().await.remove(name);
+        self.invalidate_schema_cache(name).await;
+        Some(client)
+    }
+
+    /// Cancels the pending automatic restart of a server
+    ///
+    /// # Returns
+    ///
+    /// The server name if a restart was pending.
+    async fn cancel_pending_restart(&self, id: &str) -> Option<String> {
+        let mut pending = self.pending_restarts.write().await;
+        let name = pending
+            .iter()
+            .find(|(_, restart)| restart.config.id == id)
+            .map(|(name, _)| name.clone())?;
+        pending.remove(&name);
+        Some(name)
+    }
+
     /// Gets a server by ID
     ///
     /// Checks both running servers (via O(1) lookup) and configured servers (in database).
@@ -392,12 +592,20 @@ impl MCPManager {
         }
 
         // Then add configured but not running servers from database
+        // (crashed or failed servers keep their last recorded status)
         let configs = self.get_saved_configs().await?;
+        let history = self.status_history.read().await;
         for config in configs {
             if !seen_ids.contains(&config.id) {
+                let status = history
+                    .get(&config.name)
+                    .and_then(|h| h.back())
+                    .map(|change| change.status.clone())
+                    .filter(|status| *status != MCPServerStatus::Running)
+                    .unwrap_or(MCPServerStatus::Stopped);
                 servers.push(MCPServer {
                     config,
-                    status: MCPServerStatus::Stopped,
+                    status,
                     tools: Vec::new(),
                     resources: Vec::new(),
                     created_at: Utc::now(),
@@ -435,6 +643,65 @@ impl MCPManager {
         server_name: &str,
         tool_name: &str,
         arguments: serde_json::Value,
+    ) -> MCPResult<MCPToolCallResult> {
+        self.call_tool_with_progress(server_name, tool_name, arguments, None, None, None)
+            .await
+    }
+
+    /// Calls a tool on behalf of an agent, forwarding its progress
+    /// notifications to `progress`
+    ///
+    /// Same as [`Self::call_tool`], except that:
+    /// - the call is rejected with `MCPError::ToolNotAllowed` when `policy`
+    ///   (the agent's policy for this server) denies the tool
+    /// - `notifications/progress` messages sent by the server during the call
+    ///   are delivered on the channel
+    /// - the call is cancelled on the server (and not retried) when
+    ///   `cancellation` fires, returning `MCPError::Cancelled`
+    ///
+    /// A tool list change notified during the call is applied once the call
+    /// returns.
+    pub async fn call_tool_with_progress(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        policy: Option<&MCPToolPolicy>,
+        progress: Option<ProgressSender>,
+        cancellation: Option<&CancellationToken>,
+    ) -> MCPResult<MCPToolCallResult> {
+        if policy.is_some_and(|policy| !policy.allows(tool_name)) {
+            warn!(
+                server_name = %server_name,
+                tool_name = %tool_name,
+                "MCP tool call denied by agent policy"
+            );
+            return Err(MCPError::ToolNotAllowed {
+                server: server_name.to_string(),
+                tool: tool_name.to_string(),
+            });
+        }
+
+        let result = self
+            .call_tool_internal(server_name, tool_name, arguments, progress, cancellation)
+            .await;
+        if let Err(e) = &result {
+            if !matches!(e, MCPError::Cancelled { .. }) {
+                crate::metrics::record_mcp_error(server_name, &e.category().to_string());
+            }
+        }
+        self.refresh_changed_tools(server_name).await;
+        result
+    }
+
+    /// Calls a tool with retries and circuit breaker (see [`Self::call_tool`])
+    async fn call_tool_internal(
+        &self,
+        server_name: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        progress: Option<ProgressSender>,
+        cancellation: Option<&CancellationToken>,
     ) -> MCPResult<MCPToolCallResult> {
         debug!(
             server_name = %server_name,
@@ -473,7 +740,14 @@ impl MCPManager {
                         server: server_name.to_string(),
                     })?;
 
-                client.call_tool(tool_name, arguments.clone()).await
+                client
+                    .call_tool_with_progress(
+                        tool_name,
+                        arguments.clone(),
+                        progress.clone(),
+                        cancellation,
+                    )
+                    .await
             };
 
             match result {
@@ -515,6 +789,8 @@ impl MCPManager {
 
                     return Ok(call_result);
                 }
+                // Abandoned by the caller: neither a server failure nor retried
+                Err(e @ MCPError::Cancelled { .. }) => return Err(e),
                 Err(e) => {
                     // Check if error is retryable
                     let is_retryable = Self::is_retryable_error(&e);
@@ -531,9 +807,6 @@ impl MCPManager {
                             }
                         }
 
-                        // Invalidate tool cache on failure
-                        self.invalidate_tool_cache(server_name).await;
-
                         // Log failed call
                         let log_entry = MCPCallLogCreate {
                             id: Uuid::new_v4().to_string(),
@@ -574,7 +847,21 @@ impl MCPManager {
                     );
 
                     last_error = Some(e);
-                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    let backoff = tokio::time::sleep(Duration::from_millis(delay_ms));
+                    match cancellation {
+                        Some(token) => {
+                            tokio::select! {
+                                _ = backoff => {}
+                                _ = token.cancelled() => {
+                                    return Err(MCPError::Cancelled {
+                                        server: server_name.to_string(),
+                                        operation: format!("tools/call {}", tool_name),
+                                    });
+                                }
+                            }
+                        }
+                        None => backoff.await,
+                    }
                 }
             }
         }
@@ -618,8 +905,7 @@ impl MCPManager {
 
     /// Lists tools available on a specific server by NAME.
     ///
-    /// Uses a cache with 1-hour TTL to avoid redundant calls.
-    /// Cache is automatically invalidated on tool call errors.
+    /// Served from the schema cache (see [`Self::server_schema`]).
     ///
     /// # Arguments
     ///
@@ -629,41 +915,111 @@ impl MCPManager {
     ///
     /// Returns the list of tools, or empty list if server not found.
     pub async fn list_server_tools(&self, server_name: &str) -> Vec<MCPTool> {
-        // Check cache first
-        {
-            let cache = self.tool_cache.read().await;
-            if let Some((tools, cached_at)) = cache.get(server_name) {
-                if cached_at.elapsed() < TOOL_CACHE_TTL {
-                    debug!(server = %server_name, "Tool cache hit");
-                    return tools.clone();
-                }
-            }
+        self.server_schema(server_name)
+            .await
+            .map(|schema| schema.tools)
+            .unwrap_or_default()
+    }
+
+    /// Returns the cached tools and resources of a server by NAME.
+    ///
+    /// The schema is taken from the connected client on first access, then
+    /// served from the cache until the server notifies a tool list change,
+    /// its resources are refreshed, or it stops. Returns `None` if the server
+    /// is not connected.
+    pub async fn server_schema(&self, server_name: &str) -> Option<ServerSchema> {
+        self.refresh_changed_tools(server_name).await;
+
+        if let Some(schema) = self.schema_cache.read().await.get(server_name) {
+            debug!(server = %server_name, "Schema cache hit");
+            return Some(schema.clone());
         }
 
-        // Cache miss or expired - fetch from client
-        debug!(server = %server_name, "Tool cache miss, fetching from client");
-        let clients = self.clients.read().await;
-        let tools = clients
+        debug!(server = %server_name, "Schema cache miss, reading client");
+        let schema = {
+            let clients = self.clients.read().await;
+            let client = clients.get(server_name)?;
+            ServerSchema::new(client.tools().to_vec(), client.resources().to_vec())
+        };
+        self.schema_cache
+            .write()
+            .await
+            .insert(server_name.to_string(), schema.clone());
+        Some(schema)
+    }
+
+    /// Refreshes the tools of a server that sent `notifications/tools/list_changed`.
+    ///
+    /// The client tool list is fetched again and the cached schema replaced
+    /// (kept when the checksum did not change). Does nothing when no change
+    /// was notified.
+    async fn refresh_changed_tools(&self, server_name: &str) {
+        let changed = self
+            .clients
+            .read()
+            .await
             .get(server_name)
-            .map(|c| c.tools().to_vec())
-            .unwrap_or_default();
+            .is_some_and(|client| client.tools_changed());
+        if !changed {
+            return;
+        }
 
-        // Update cache
-        if !tools.is_empty() {
-            let mut cache = self.tool_cache.write().await;
-            cache.insert(server_name.to_string(), (tools.clone(), Instant::now()));
+        let (previous_tools, schema) = {
+            let mut clients = self.clients.write().await;
+            let Some(client) = clients.get_mut(server_name) else {
+                return;
+            };
+            if !client.take_tools_changed() {
+                // Refreshed concurrently
+                return;
+            }
+            let previous_tools = client.tools().to_vec();
+            if let Err(e) = client.refresh_tools().await {
+                warn!(
+                    server = %server_name,
+                    error = %e,
+                    "Failed to refresh tool list after change notification"
+                );
+            }
+            (
+                previous_tools,
+                ServerSchema::new(client.tools().to_vec(), client.resources().to_vec()),
+            )
+        };
+        let tools_change = mcp_tools_change(server_name, &previous_tools, &schema.tools);
+
+        {
+            let mut cache = self.schema_cache.write().await;
+            let changed = cache
+                .get(server_name)
+                .is_none_or(|cached| cached.checksum != schema.checksum);
+            info!(
+                server = %server_name,
+                tools_count = schema.tools.len(),
+                changed = changed,
+                "Tool list refreshed after change notification"
+            );
+            if changed {
+                cache.insert(server_name.to_string(), schema);
+            }
         }
 
-        tools
+        if let Some(change) = tools_change {
+            record_behavior_changes_best_effort(&self.db, vec![change]).await;
+        }
     }
 
-    /// Invalidates the tool cache for a specific server.
-    ///
-    /// Call this when a tool call fails to force a refresh on next access.
-    pub async fn invalidate_tool_cache(&self, server_name: &str) {
-        let mut cache = self.tool_cache.write().await;
-        cache.remove(server_name);
-        debug!(server = %server_name, "Tool cache invalidated");
+    /// Invalidates the cached schema of a server, read again on next access.
+    pub async fn invalidate_schema_cache(&self, server_name: &str) {
+        if self
+            .schema_cache
+            .write()
+            .await
+            .remove(server_name)
+            .is_some()
+        {
+            debug!(server = %server_name, "Schema cache invalidated");
+        }
     }
 
     /// Lists all tools across all connected servers
@@ -679,6 +1035,243 @@ impl MCPManager {
             .collect()
     }
 
+    /// Lists resources available on a specific server by NAME.
+    ///
+    /// # Returns
+    ///
+    /// Returns the cached resources (discovered during initialization or the
+    /// last refresh), or an empty list if the server is not connected.
+    pub async fn list_server_resources(&self, server_name: &str) -> Vec<MCPResource> {
+        self.server_schema(server_name)
+            .await
+            .map(|schema| schema.resources)
+            .unwrap_or_default()
+    }
+
+    /// Lists all resources across all connected servers
+    ///
+    /// # Returns
+    ///
+    /// Returns a map of server name to list of resources.
+    pub async fn list_all_resources(&self) -> HashMap<String, Vec<MCPResource>> {
+        let clients = self.clients.read().await;
+        clients
+            .values()
+            .map(|client| (client.config().name.clone(), client.resources().to_vec()))
+            .collect()
+    }
+
+    /// Re-fetches the resources list of a server by NAME.
+    ///
+    /// Servers may expose resources that change over time (files, documents);
+    /// the list discovered at initialization can become stale.
+    pub async fn refresh_server_resources(&self, server_name: &str) -> MCPResult<Vec<MCPResource>> {
+        let resources = {
+            let mut clients = self.clients.write().await;
+            let client = clients
+                .get_mut(server_name)
+                .ok_or(MCPError::ServerNotFound {
+                    server: server_name.to_string(),
+                })?;
+            client.refresh_resources().await?
+        };
+        self.invalidate_schema_cache(server_name).await;
+        Ok(resources)
+    }
+
+    /// Reads a resource from a server by NAME.
+    ///
+    /// Goes through the server's circuit breaker like tool calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Server NAME (e.g., "Serena", "Context7")
+    /// * `uri` - Resource URI
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ServerNotFound` if the server is not connected,
+    /// `MCPError::CircuitBreakerOpen` if the server is failing, or the
+    /// server's error (e.g. unknown URI).
+    pub async fn read_resource(
+        &self,
+        server_name: &str,
+        uri: &str,
+    ) -> MCPResult<Vec<MCPResourceContents>> {
+        debug!(server_name = %server_name, uri = %uri, "Reading MCP resource");
+
+        self.check_circuit_breaker(server_name).await?;
+
+        let result = {
+            let mut clients = self.clients.write().await;
+            let client = clients
+                .get_mut(server_name)
+                .ok_or(MCPError::ServerNotFound {
+                    server: server_name.to_string(),
+                })?;
+            client.read_resource(uri).await
+        };
+
+        self.record_circuit_breaker_result(server_name, &result)
+            .await;
+        result
+    }
+
+    /// Reads several resources from a server by NAME.
+    ///
+    /// Over HTTP the reads are sent as one JSON-RPC batch when the server
+    /// supports it. Goes through the server's circuit breaker like tool calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Server NAME (e.g., "Serena", "Context7")
+    /// * `uris` - Resource URIs
+    ///
+    /// # Returns
+    ///
+    /// One result per URI, in the same order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ServerNotFound` if the server is not connected,
+    /// `MCPError::CircuitBreakerOpen` if the server is failing, or the
+    /// transport error if the reads could not be sent.
+    pub async fn read_resources(
+        &self,
+        server_name: &str,
+        uris: &[String],
+    ) -> MCPResult<Vec<MCPResult<Vec<MCPResourceContents>>>> {
+        debug!(server_name = %server_name, count = uris.len(), "Reading MCP resources");
+
+        self.check_circuit_breaker(server_name).await?;
+
+        let result = {
+            let mut clients = self.clients.write().await;
+            let client = clients
+                .get_mut(server_name)
+                .ok_or(MCPError::ServerNotFound {
+                    server: server_name.to_string(),
+                })?;
+            client.read_resources(uris).await
+        };
+
+        self.record_circuit_breaker_result(server_name, &result)
+            .await;
+        result
+    }
+
+    /// Lists prompts available on a specific server by NAME.
+    ///
+    /// # Returns
+    ///
+    /// Returns the prompts discovered during initialization, or an empty
+    /// list if the server is not connected.
+    pub async fn list_server_prompts(&self, server_name: &str) -> Vec<MCPPrompt> {
+        let clients = self.clients.read().await;
+        clients
+            .get(server_name)
+            .map(|c| c.prompts().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Lists all prompts across all connected servers
+    ///
+    /// # Returns
+    ///
+    /// Returns a map of server name to list of prompts.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<MCPPrompt>> {
+        let clients = self.clients.read().await;
+        clients
+            .values()
+            .map(|client| (client.config().name.clone(), client.prompts().to_vec()))
+            .collect()
+    }
+
+    /// Re-fetches the prompts list of a server by NAME.
+    pub async fn refresh_server_prompts(&self, server_name: &str) -> MCPResult<Vec<MCPPrompt>> {
+        let mut clients = self.clients.write().await;
+        let client = clients
+            .get_mut(server_name)
+            .ok_or(MCPError::ServerNotFound {
+                server: server_name.to_string(),
+            })?;
+        client.refresh_prompts().await
+    }
+
+    /// Renders a prompt on a server by NAME.
+    ///
+    /// Goes through the server's circuit breaker like tool calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_name` - Server NAME (e.g., "Serena", "Context7")
+    /// * `prompt_name` - Prompt name
+    /// * `arguments` - Argument values
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ServerNotFound` if the server is not connected,
+    /// `MCPError::CircuitBreakerOpen` if the server is failing, or the
+    /// server's error (e.g. unknown prompt, missing required argument).
+    pub async fn get_prompt(
+        &self,
+        server_name: &str,
+        prompt_name: &str,
+        arguments: HashMap<String, String>,
+    ) -> MCPResult<MCPPromptResult> {
+        debug!(server_name = %server_name, prompt = %prompt_name, "Getting MCP prompt");
+
+        self.check_circuit_breaker(server_name).await?;
+
+        let result = {
+            let mut clients = self.clients.write().await;
+            let client = clients
+                .get_mut(server_name)
+                .ok_or(MCPError::ServerNotFound {
+                    server: server_name.to_string(),
+                })?;
+            client.get_prompt(prompt_name, arguments).await
+        };
+
+        self.record_circuit_breaker_result(server_name, &result)
+            .await;
+        result
+    }
+
+    /// Fails fast if the server's circuit breaker is open.
+    async fn check_circuit_breaker(&self, server_name: &str) -> MCPResult<()> {
+        let mut breakers = self.circuit_breakers.write().await;
+        if let Some(breaker) = breakers.get_mut(server_name) {
+            if !breaker.allow_request() {
+                let remaining = breaker
+                    .remaining_cooldown()
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                return Err(MCPError::CircuitBreakerOpen {
+                    server: server_name.to_string(),
+                    cooldown_remaining_secs: remaining,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the outcome of a request in the server's circuit breaker.
+    ///
+    /// Protocol errors (unknown URI or prompt) do not indicate a failing
+    /// server and count as a success.
+    async fn record_circuit_breaker_result<T>(&self, server_name: &str, result: &MCPResult<T>) {
+        let failed = matches!(result, Err(e) if Self::is_retryable_error(e));
+        let mut breakers = self.circuit_breakers.write().await;
+        if let Some(breaker) = breakers.get_mut(server_name) {
+            if failed {
+                breaker.record_failure();
+            } else {
+                breaker.record_success();
+            }
+        }
+    }
+
     /// Tests a server configuration without saving it
     ///
     /// # Arguments
@@ -747,8 +1340,8 @@ impl MCPManager {
     // Database Operations
     // =========================================================================
 
-    /// Saves a server configuration to the database
-    async fn save_server_config(&self, config: &MCPServerConfig) -> MCPResult<()> {
+    /// Saves a server configuration to the database (without starting it)
+    pub async fn save_server_config(&self, config: &MCPServerConfig) -> MCPResult<()> {
         let create_data = MCPServerCreate::from_config(config);
 
         debug!(
@@ -771,46 +1364,48 @@ impl MCPManager {
             env_count = config.env.len(),
             "Server configuration saved to database"
         );
+        if let Some(change) = mcp_server_change(None, Some(config)) {
+            record_behavior_changes_best_effort(&self.db, vec![change]).await;
+        }
 
         Ok(())
     }
 
     /// Updates a server configuration in the database
     pub async fn update_server_config(&self, config: &MCPServerConfig) -> MCPResult<()> {
-        // Serialize each field to JSON for the query
+        let previous = self
+            .get_server(&config.id)
+            .await
+            .map(|server| server.config);
+
         // Using explicit SET like other working update functions (agent, task, etc.)
-        let name_json = serde_json::to_string(&config.name)?;
-        let command_json = serde_json::to_string(&config.command)?;
-        let args_json = serde_json::to_string(&config.args)?;
         // env is stored as a JSON string (to bypass SurrealDB SCHEMAFULL filtering)
-        // First serialize HashMap to JSON string, then encode that string for SQL
         let env_str = serde_json::to_string(&config.env)?; // {"KEY":"value"}
-        let env_json = serde_json::to_string(&env_str)?; // "{\"KEY\":\"value\"}"
-        let description_json = match &config.description {
-            Some(desc) => serde_json::to_string(desc)?,
-            None => "NONE".to_string(),
-        };
-
-        let query = format!(
-            "UPDATE mcp_server:`{}` SET \
-                name = {}, \
-                enabled = {}, \
-                command = {}, \
-                args = {}, \
-                env = {}, \
-                description = {}, \
-                updated_at = time::now()",
-            config.id,
-            name_json,
-            config.enabled,
-            command_json,
-            args_json,
-            env_json,
-            description_json
-        );
+        let params = vec![
+            ("id".to_string(), serde_json::json!(config.id)),
+            ("name".to_string(), serde_json::json!(config.name)),
+            ("enabled".to_string(), serde_json::json!(config.enabled)),
+            ("command".to_string(), serde_json::json!(config.command)),
+            ("args".to_string(), serde_json::json!(config.args)),
+            ("env".to_string(), serde_json::json!(env_str)),
+            (
+                "description".to_string(),
+                serde_json::json!(config.description),
+            ),
+        ];
 
         self.db
-            .execute(&query)
+            .execute_with_params(
+                "UPDATE type::thing('mcp_server', $id) SET \
+                    name = $name, \
+                    enabled = $enabled, \
+                    command = $command, \
+                    args = $args, \
+                    env = $env, \
+                    description = $description ?? NONE, \
+                    updated_at = time::now()",
+                params,
+            )
             .await
             .map_err(|e| MCPError::DatabaseError {
                 context: "update server config".to_string(),
@@ -833,28 +1428,39 @@ impl MCPManager {
             server_id = %config.id,
             "Server configuration updated in database"
         );
+        if let Some(change) = mcp_server_change(previous.as_ref(), Some(config)) {
+            record_behavior_changes_best_effort(&self.db, vec![change]).await;
+        }
 
         Ok(())
     }
 
     /// Deletes a server configuration from the database
     pub async fn delete_server_config(&self, id: &str) -> MCPResult<()> {
+        let previous = self.get_server(id).await.map(|server| server.config);
+
         // First stop the server if running (by ID)
         let _ = self.stop_server(id).await;
 
-        // Use raw query instead of SDK delete method (which has issues with record IDs)
-        let query = format!("DELETE mcp_server:`{}`", id);
+        // Forget the status history of the deleted server
+        self.status_history
+            .write()
+            .await
+            .retain(|_, entries| !entries.iter().any(|change| change.server_id == id));
 
-        let _: Vec<serde_json::Value> =
-            self.db
-                .query_json(&query)
-                .await
-                .map_err(|e| MCPError::DatabaseError {
-                    context: "delete server config".to_string(),
-                    message: e.to_string(),
-                })?;
+        self.db
+            .delete(&format!("mcp_server:{}", id))
+            .await
+            .map_err(|e| MCPError::DatabaseError {
+                context: "delete server config".to_string(),
+                message: e.to_string(),
+            })?;
 
         debug!(server_id = %id, "Server configuration deleted from database");
+        inbox::resolve_pending_item(&self.db, PendingItemKind::ExpiringCredential, id).await;
+        if let Some(change) = mcp_server_change(previous.as_ref(), None) {
+            record_behavior_changes_best_effort(&self.db, vec![change]).await;
+        }
 
         Ok(())
     }
@@ -952,9 +1558,14 @@ impl MCPManager {
         // Sanitize to remove null characters that cause SurrealDB panics
         let json_data = sanitize_for_surrealdb(json_data);
 
-        let query = format!("CREATE mcp_call_log:`{}` CONTENT $data", log.id);
         self.db
-            .execute_with_params(&query, vec![("data".to_string(), json_data)])
+            .execute_with_params(
+                "CREATE type::thing('mcp_call_log', $id) CONTENT $data",
+                vec![
+                    ("id".to_string(), serde_json::json!(log.id)),
+                    ("data".to_string(), json_data),
+                ],
+            )
             .await
             .map_err(|e| MCPError::DatabaseError {
                 context: "log call".to_string(),
@@ -1014,6 +1625,130 @@ impl MCPManager {
         self.spawn_server_internal(config).await
     }
 
+    /// Starts the OAuth authorization of an HTTP server
+    ///
+    /// Discovers the authorization server, registers a client for
+    /// `redirect_uri` and returns the URL to open in the browser.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Server ID (must use the HTTP deployment method)
+    /// * `redirect_uri` - Redirect URI receiving the authorization code
+    pub async fn begin_oauth(
+        &self,
+        id: &str,
+        redirect_uri: &str,
+    ) -> MCPResult<OAuthAuthorizationStart> {
+        if self.oauth_store.read().await.is_none() {
+            return Err(MCPError::InvalidConfig {
+                field: "oauth".to_string(),
+                reason: "OAuth credential store is not available".to_string(),
+            });
+        }
+
+        let config = self
+            .get_server_config(id)
+            .await?
+            .ok_or_else(|| MCPError::ServerNotFound {
+                server: id.to_string(),
+            })?;
+        if config.command != MCPDeploymentMethod::Http {
+            return Err(MCPError::InvalidConfig {
+                field: "command".to_string(),
+                reason: "OAuth is only supported for HTTP servers".to_string(),
+            });
+        }
+        let url = config
+            .args
+            .first()
+            .cloned()
+            .ok_or_else(|| MCPError::InvalidConfig {
+                field: "args".to_string(),
+                reason: "HTTP deployment requires URL in args[0]".to_string(),
+            })?;
+
+        let pending = MCPOAuthClient::new(config.name.clone(), url)
+            .begin(id, redirect_uri)
+            .await?;
+        let start = OAuthAuthorizationStart::from(&pending);
+        self.oauth_flows.insert(pending).await;
+
+        Ok(start)
+    }
+
+    /// Completes an OAuth authorization started by `begin_oauth()`
+    ///
+    /// Exchanges the code for tokens, stores them and restarts the server
+    /// (if enabled) so that it connects with the new access token.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - State returned by `begin_oauth()`
+    /// * `code` - Authorization code received on the redirect URI
+    ///
+    /// # Returns
+    ///
+    /// The server ID
+    pub async fn complete_oauth(&self, state: &str, code: &str) -> MCPResult<String> {
+        let pending =
+            self.oauth_flows
+                .take(state)
+                .await
+                .ok_or_else(|| MCPError::InvalidConfig {
+                    field: "state".to_string(),
+                    reason: "Unknown or expired OAuth authorization".to_string(),
+                })?;
+        let store =
+            self.oauth_store
+                .read()
+                .await
+                .clone()
+                .ok_or_else(|| MCPError::InvalidConfig {
+                    field: "oauth".to_string(),
+                    reason: "OAuth credential store is not available".to_string(),
+                })?;
+        let config = self
+            .get_server_config(&pending.server_id)
+            .await?
+            .ok_or_else(|| MCPError::ServerNotFound {
+                server: pending.server_id.clone(),
+            })?;
+
+        let credentials = MCPOAuthClient::new(config.name.clone(), pending.resource.clone())
+            .exchange_code(&pending, code)
+            .await?;
+        store
+            .save_credentials(&config.id, &credentials)
+            .map_err(|e| MCPError::InvalidConfig {
+                field: "oauth".to_string(),
+                reason: format!("Failed to store OAuth credentials: {}", e),
+            })?;
+        // Without refresh token, the user has to authorize again before expiry
+        match (&credentials.refresh_token, credentials.expires_at) {
+            (None, Some(expires_at)) => {
+                inbox::track_pending_item(
+                    &self.db,
+                    PendingItemCreate::expiring_credential(&config.id, &config.name, expires_at),
+                )
+                .await
+            }
+            _ => {
+                inbox::resolve_pending_item(
+                    &self.db,
+                    PendingItemKind::ExpiringCredential,
+                    &config.id,
+                )
+                .await
+            }
+        }
+
+        if config.enabled {
+            self.restart_server(&config.id).await?;
+        }
+
+        Ok(config.id)
+    }
+
     /// Gets the circuit breaker state for a server
     ///
     /// Returns None if the server doesn't have a circuit breaker (not running).
@@ -1040,14 +1775,14 @@ impl MCPManager {
 
     /// Starts periodic health checks for all connected servers (OPT-8)
     ///
-    /// Spawns a background task that periodically checks server health
-    /// using `list_tools()` as a health probe. Unhealthy servers will have
-    /// their circuit breakers updated accordingly.
+    /// Spawns a background task that periodically pings each server.
+    /// Unhealthy servers will have their circuit breakers updated accordingly,
+    /// and crashed stdio servers are restarted with exponential backoff.
     ///
     /// # Arguments
     ///
     /// * `manager` - Arc reference to self (needed for background task)
-    /// * `interval` - How often to check health (default: 5 minutes)
+    /// * `interval` - How often to check health (default: 30 seconds)
     ///
     /// # Returns
     ///
@@ -1087,19 +1822,14 @@ impl MCPManager {
 
     /// Checks health of all connected servers
     ///
-    /// Uses `list_tools()` as a health probe. Updates circuit breakers
-    /// based on results.
+    /// Pings every server, updates circuit breakers based on results, then
+    /// runs the automatic restarts that are due.
     async fn check_all_servers_health(&self) {
         let server_names: Vec<String> = {
             let clients = self.clients.read().await;
             clients.keys().cloned().collect()
         };
 
-        if server_names.is_empty() {
-            debug!("No servers to health check");
-            return;
-        }
-
         debug!(
             server_count = server_names.len(),
             "Running health checks for MCP servers"
@@ -1108,51 +1838,227 @@ impl MCPManager {
         for name in server_names {
             self.check_server_health(&name).await;
         }
+
+        self.run_pending_restarts().await;
+    }
+
+    /// Sends the health probe to a server
+    ///
+    /// Uses `ping`, falling back to `tools/list` for servers that reject it.
+    async fn probe_server(client: &mut MCPClient) -> MCPResult<()> {
+        match client.ping().await {
+            Err(MCPError::ProtocolError { .. }) => client.refresh_tools().await.map(|_| ()),
+            result => result,
+        }
     }
 
     /// Checks health of a single server
     ///
-    /// Uses `refresh_tools()` as a health probe (makes actual network call)
-    /// and updates circuit breaker.
+    /// A stdio server whose process exited is unregistered and scheduled for
+    /// an automatic restart. Otherwise the server is probed (actual request)
+    /// and its circuit breaker and status are updated.
     async fn check_server_health(&self, server_name: &str) {
-        let result = {
+        let (config, outcome) = {
             let mut clients = self.clients.write().await;
-            if let Some(client) = clients.get_mut(server_name) {
-                // Use refresh_tools as health probe - it makes a real network call
-                match client.refresh_tools().await {
-                    Ok(tools) => {
-                        debug!(
-                            server = %server_name,
-                            tool_count = tools.len(),
-                            "Health check passed"
-                        );
-                        Ok(())
-                    }
-                    Err(e) => {
-                        warn!(
-                            server = %server_name,
-                            error = %e,
-                            "Health check failed"
-                        );
-                        Err(e)
-                    }
-                }
-            } else {
+            let Some(client) = clients.get_mut(server_name) else {
                 // Server was removed during iteration
                 return;
+            };
+            let config = client.config().clone();
+            let outcome =
+                if config.command != MCPDeploymentMethod::Http && !client.is_process_alive() {
+                    HealthOutcome::Crashed
+                } else {
+                    match Self::probe_server(client).await {
+                        Ok(()) => HealthOutcome::Healthy,
+                        Err(e) => HealthOutcome::Unhealthy(e),
+                    }
+                };
+            (config, outcome)
+        };
+
+        match outcome {
+            HealthOutcome::Healthy => {
+                debug!(server = %server_name, "Health check passed");
+                // Notifications read while probing (e.g. tool list changes)
+                self.refresh_changed_tools(server_name).await;
+                if let Some(breaker) = self.circuit_breakers.write().await.get_mut(server_name) {
+                    breaker.record_success();
+                }
+                self.record_status_change(&config.id, server_name, MCPServerStatus::Running, None)
+                    .await;
+            }
+            HealthOutcome::Unhealthy(e) => {
+                warn!(server = %server_name, error = %e, "Health check failed");
+                if let Some(breaker) = self.circuit_breakers.write().await.get_mut(server_name) {
+                    breaker.record_failure();
+                }
+                self.record_status_change(
+                    &config.id,
+                    server_name,
+                    MCPServerStatus::Error,
+                    Some(e.to_string()),
+                )
+                .await;
+            }
+            HealthOutcome::Crashed => {
+                warn!(server = %server_name, "MCP server process exited, scheduling restart");
+                self.unregister_client(server_name).await;
+                self.record_status_change(
+                    &config.id,
+                    server_name,
+                    MCPServerStatus::Disconnected,
+                    Some("Server process exited".to_string()),
+                )
+                .await;
+                self.pending_restarts.write().await.insert(
+                    server_name.to_string(),
+                    PendingRestart {
+                        config,
+                        failed_attempts: 0,
+                        next_attempt_at: Instant::now(),
+                    },
+                );
             }
+        }
+    }
+
+    /// Restarts the crashed servers whose backoff delay has elapsed
+    ///
+    /// Gives up after `MAX_RESTART_ATTEMPTS` failed attempts.
+    async fn run_pending_restarts(&self) {
+        let due: Vec<(String, MCPServerConfig, u32)> = {
+            let now = Instant::now();
+            let pending = self.pending_restarts.read().await;
+            pending
+                .iter()
+                .filter(|(_, restart)| restart.next_attempt_at <= now)
+                .map(|(name, restart)| {
+                    (
+                        name.clone(),
+                        restart.config.clone(),
+                        restart.failed_attempts,
+                    )
+                })
+                .collect()
         };
 
-        // Update circuit breaker based on result
-        let mut breakers = self.circuit_breakers.write().await;
-        if let Some(breaker) = breakers.get_mut(server_name) {
+        for (name, config, failed_attempts) in due {
+            // Stopped or restarted manually in the meantime
+            if !self.pending_restarts.read().await.contains_key(&name)
+                || self.clients.read().await.contains_key(&name)
+            {
+                continue;
+            }
+
+            let attempt = failed_attempts + 1;
+            info!(server = %name, attempt = attempt, "Restarting crashed MCP server");
+            self.record_status_change(
+                &config.id,
+                &name,
+                MCPServerStatus::Starting,
+                Some(format!(
+                    "Restart attempt {}/{}",
+                    attempt, MAX_RESTART_ATTEMPTS
+                )),
+            )
+            .await;
+
+            let result = self.spawn_server_internal(config.clone()).await;
+            let mut pending = self.pending_restarts.write().await;
             match result {
-                Ok(()) => breaker.record_success(),
-                Err(_) => breaker.record_failure(),
+                Ok(_) => {
+                    pending.remove(&name);
+                    info!(server = %name, attempt = attempt, "Crashed MCP server restarted");
+                }
+                Err(e) if attempt >= MAX_RESTART_ATTEMPTS => {
+                    pending.remove(&name);
+                    drop(pending);
+                    warn!(server = %name, error = %e, "Giving up restarting MCP server");
+                    self.record_status_change(
+                        &config.id,
+                        &name,
+                        MCPServerStatus::Error,
+                        Some(format!(
+                            "Automatic restart abandoned after {} attempts",
+                            attempt
+                        )),
+                    )
+                    .await;
+                }
+                Err(e) => {
+                    let delay = restart_backoff(attempt);
+                    warn!(
+                        server = %name,
+                        error = %e,
+                        retry_in_secs = delay.as_secs(),
+                        "MCP server restart failed"
+                    );
+                    if let Some(restart) = pending.get_mut(&name) {
+                        restart.failed_attempts = attempt;
+                        restart.next_attempt_at = Instant::now() + delay;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Records a status transition and emits `mcp_server_status_changed`
+    ///
+    /// Ignored when neither the status nor the message changed.
+    async fn record_status_change(
+        &self,
+        server_id: &str,
+        server_name: &str,
+        status: MCPServerStatus,
+        message: Option<String>,
+    ) {
+        let change = {
+            let mut history = self.status_history.write().await;
+            let entries = history.entry(server_name.to_string()).or_default();
+            if let Some(last) = entries.back() {
+                if last.status == status && last.message == message {
+                    return;
+                }
+            }
+            let change = MCPServerStatusChange {
+                server_id: server_id.to_string(),
+                server_name: server_name.to_string(),
+                status,
+                previous_status: entries.back().map(|last| last.status.clone()),
+                message,
+                timestamp: Utc::now(),
+            };
+            entries.push_back(change.clone());
+            while entries.len() > STATUS_HISTORY_LIMIT {
+                entries.pop_front();
+            }
+            change
+        };
+
+        debug!(
+            server = %server_name,
+            status = %change.status,
+            "MCP server status changed"
+        );
+
+        if let Some(handle) = self.app_handle.read().await.as_ref() {
+            if let Err(e) = handle.emit(events::MCP_SERVER_STATUS_CHANGED, &change) {
+                warn!(error = %e, "Failed to emit MCP server status change");
             }
         }
     }
 
+    /// Returns the status transitions of a server (oldest first)
+    pub async fn get_status_history(&self, server_name: &str) -> Vec<MCPServerStatusChange> {
+        self.status_history
+            .read()
+            .await
+            .get(server_name)
+            .map(|entries| entries.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
     /// Stops the health check background task
     ///
     /// Sends a shutdown signal to the health check task.
@@ -1230,4 +2136,349 @@ mod tests {
         // Verify timestamp is NOT in the serialized output
         assert!(!json.contains("\"timestamp\":"));
     }
+
+    async fn create_test_manager(dir: &std::path::Path) -> MCPManager {
+        let db = DBClient::new(dir.join("mcp_db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        MCPManager::new(Arc::new(db)).await.unwrap()
+    }
+
+    #[test]
+    fn test_restart_backoff() {
+        assert_eq!(restart_backoff(1), RESTART_BACKOFF_BASE);
+        assert_eq!(restart_backoff(2), RESTART_BACKOFF_BASE * 2);
+        assert_eq!(restart_backoff(3), RESTART_BACKOFF_BASE * 4);
+        assert_eq!(restart_backoff(30), RESTART_BACKOFF_MAX);
+    }
+
+    #[tokio::test]
+    async fn test_status_history() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = create_test_manager(temp_dir.path()).await;
+
+        manager
+            .record_status_change("id1", "srv", MCPServerStatus::Running, None)
+            .await;
+        // Unchanged status is not recorded twice
+        manager
+            .record_status_change("id1", "srv", MCPServerStatus::Running, None)
+            .await;
+        manager
+            .record_status_change(
+                "id1",
+                "srv",
+                MCPServerStatus::Error,
+                Some("timeout".to_string()),
+            )
+            .await;
+
+        let history = manager.get_status_history("srv").await;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].previous_status, None);
+        assert_eq!(history[1].status, MCPServerStatus::Error);
+        assert_eq!(history[1].previous_status, Some(MCPServerStatus::Running));
+        assert_eq!(history[1].message.as_deref(), Some("timeout"));
+        assert!(manager.get_status_history("other").await.is_empty());
+
+        for i in 0..STATUS_HISTORY_LIMIT {
+            manager
+                .record_status_change(
+                    "id1",
+                    "srv",
+                    MCPServerStatus::Error,
+                    Some(format!("error {}", i)),
+                )
+                .await;
+        }
+        let history = manager.get_status_history("srv").await;
+        assert_eq!(history.len(), STATUS_HISTORY_LIMIT);
+        assert_eq!(
+            history.last().unwrap().message.as_deref(),
+            Some(format!("error {}", STATUS_HISTORY_LIMIT - 1).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_check_pings_server() {
+        use crate::mcp::test_server::{accepted, http, json_reply, spawn_mock_server};
+        use serde_json::json;
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let failing = Arc::new(AtomicBool::new(false));
+        let pings = Arc::new(AtomicUsize::new(0));
+        let (failing_flag, ping_count) = (failing.clone(), pings.clone());
+        let url = spawn_mock_server(move |request| {
+            let id = request.body["id"].clone();
+            let result = match request.rpc_method() {
+                _ if failing_flag.load(Ordering::SeqCst) => {
+                    return http("500 Internal Server Error", &[], "")
+                }
+                "initialize" => json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {},
+                    "serverInfo": {"name": "mock", "version": "1.0"}
+                }),
+                "ping" => {
+                    ping_count.fetch_add(1, Ordering::SeqCst);
+                    json!({})
+                }
+                "tools/list" => json!({"tools": []}),
+                "resources/list" => json!({"resources": []}),
+                "prompts/list" => json!({"prompts": []}),
+                _ => return accepted(),
+            };
+            json_reply(&[], json!({"jsonrpc": "2.0", "id": id, "result": result}))
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = create_test_manager(temp_dir.path()).await;
+        let config = MCPServerConfig {
+            command: MCPDeploymentMethod::Http,
+            args: vec![url],
+            ..create_test_config("http_id", "remote")
+        };
+        manager.spawn_server_internal(config).await.unwrap();
+
+        manager.check_all_servers_health().await;
+        assert_eq!(pings.load(Ordering::SeqCst), 1);
+        let history = manager.get_status_history("remote").await;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].status, MCPServerStatus::Running);
+
+        failing.store(true, Ordering::SeqCst);
+        manager.check_all_servers_health().await;
+        let history = manager.get_status_history("remote").await;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].status, MCPServerStatus::Error);
+        // HTTP servers are not restarted, only reported
+        assert!(manager.pending_restarts.read().await.is_empty());
+
+        manager.stop_server("http_id").await.unwrap();
+        let history = manager.get_status_history("remote").await;
+        assert_eq!(history.last().unwrap().status, MCPServerStatus::Stopped);
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_progress_and_list_changed() {
+        use crate::mcp::test_server::{accepted, json_reply, spawn_mock_server, sse_reply};
+        use serde_json::json;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let changed = Arc::new(AtomicBool::new(false));
+        let changed_flag = changed.clone();
+        let tool_lists = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let tool_lists_count = tool_lists.clone();
+        let url = spawn_mock_server(move |request| {
+            let id = request.body["id"].clone();
+            let reply = |result: serde_json::Value| {
+                json_reply(&[], json!({"jsonrpc": "2.0", "id": id, "result": result}))
+            };
+            match request.rpc_method() {
+                "initialize" => reply(json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {"tools": {"listChanged": true}},
+                    "serverInfo": {"name": "mock", "version": "1.0"}
+                })),
+                "tools/list" => {
+                    tool_lists_count.fetch_add(1, Ordering::SeqCst);
+                    let mut tools = vec![json!({"name": "index", "inputSchema": {"type": "object"}})];
+                    if changed_flag.load(Ordering::SeqCst) {
+                        tools.push(json!({"name": "search", "inputSchema": {"type": "object"}}));
+                    }
+                    reply(json!({"tools": tools}))
+                }
+                "resources/list" => reply(json!({"resources": []})),
+                "prompts/list" => reply(json!({"prompts": []})),
+                "tools/call" => {
+                    changed_flag.store(true, Ordering::SeqCst);
+                    let token = request.body["params"]["_meta"]["progressToken"].clone();
+                    let progress = json!({
+                        "jsonrpc": "2.0",
+                        "method": "notifications/progress",
+                        "params": {"progressToken": token, "progress": 1, "total": 2, "message": "Half way"}
+                    });
+                    let list_changed =
+                        json!({"jsonrpc": "2.0", "method": "notifications/tools/list_changed"});
+                    let response = json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "result": {"content": [{"type": "text", "text": "done"}]}
+                    });
+                    sse_reply(&format!(
+                        "data: {}\n\ndata: {}\n\ndata: {}\n\n",
+                        progress, list_changed, response
+                    ))
+                }
+                _ => accepted(),
+            }
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = create_test_manager(temp_dir.path()).await;
+        let config = MCPServerConfig {
+            command: MCPDeploymentMethod::Http,
+            args: vec![url],
+            ..create_test_config("http_id", "remote")
+        };
+        manager.spawn_server_internal(config).await.unwrap();
+        assert_eq!(manager.list_server_tools("remote").await.len(), 1);
+        let checksum = manager.server_schema("remote").await.unwrap().checksum;
+
+        // Served from the cache: no tools/list after initialization
+        for _ in 0..3 {
+            assert_eq!(manager.list_server_tools("remote").await.len(), 1);
+        }
+        assert_eq!(tool_lists.load(Ordering::SeqCst), 1);
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let result = manager
+            .call_tool_with_progress("remote", "index", json!({}), None, Some(tx), None)
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let progress = rx.recv().await.unwrap();
+        assert_eq!(progress.percent(), Some(50));
+        assert_eq!(progress.message.as_deref(), Some("Half way"));
+
+        // The cached tool list was replaced after the change notification
+        let tools = manager.list_server_tools("remote").await;
+        assert_eq!(tools.len(), 2);
+        assert!(tools.iter().any(|t| t.name == "search"));
+        assert_eq!(tool_lists.load(Ordering::SeqCst), 2);
+        let schema = manager.server_schema("remote").await.unwrap();
+        assert_ne!(schema.checksum, checksum);
+        assert_eq!(tool_lists.load(Ordering::SeqCst), 2);
+
+        // Stopping the server drops its cached schema
+        manager.stop_server("http_id").await.unwrap();
+        assert!(manager.schema_cache.read().await.is_empty());
+        assert!(manager.server_schema("remote").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tool_call_cancellation_notifies_server() {
+        use crate::mcp::test_server::{accepted, json_reply, spawn_mock_server, MockReply};
+        use serde_json::json;
+
+        let (cancelled_tx, mut cancelled_rx) = tokio::sync::mpsc::unbounded_channel();
+        let open_streams = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let streams = open_streams.clone();
+        let url = spawn_mock_server(move |request| {
+            let id = request.body["id"].clone();
+            let reply = |result: serde_json::Value| {
+                json_reply(&[], json!({"jsonrpc": "2.0", "id": id, "result": result}))
+            };
+            match request.rpc_method() {
+                "initialize" => reply(json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {"tools": {}},
+                    "serverInfo": {"name": "mock", "version": "1.0"}
+                })),
+                "tools/list" => reply(json!({
+                    "tools": [{"name": "crawl", "inputSchema": {"type": "object"}}]
+                })),
+                "resources/list" => reply(json!({"resources": []})),
+                "prompts/list" => reply(json!({"prompts": []})),
+                // Long-running tool: the SSE stream stays open without a response
+                "tools/call" => {
+                    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+                    streams.lock().unwrap().push((id, tx));
+                    MockReply::Stream(rx)
+                }
+                "notifications/cancelled" => {
+                    let _ = cancelled_tx.send(request.body["params"].clone());
+                    accepted()
+                }
+                _ => accepted(),
+            }
+        })
+        .await;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = create_test_manager(temp_dir.path()).await;
+        let config = MCPServerConfig {
+            command: MCPDeploymentMethod::Http,
+            args: vec![url],
+            ..create_test_config("http_id", "remote")
+        };
+        manager.spawn_server_internal(config).await.unwrap();
+
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+        let result = manager
+            .call_tool_with_progress("remote", "crawl", json!({}), None, None, Some(&token))
+            .await;
+        assert!(matches!(result, Err(MCPError::Cancelled { .. })));
+
+        // The server was told which request to stop
+        let params = tokio::time::timeout(Duration::from_secs(5), cancelled_rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        let call_id = open_streams.lock().unwrap()[0].0.clone();
+        assert_eq!(params["requestId"], call_id);
+
+        // A cancellation is not a server failure
+        let breakers = manager.circuit_breakers.read().await;
+        assert_eq!(breakers["remote"].failure_count(), 0);
+    }
+
+    #[test]
+    fn test_server_schema_checksum() {
+        let tool = |name: &str| MCPTool {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({"type": "object"}),
+        };
+        let a = ServerSchema::new(vec![tool("index")], Vec::new());
+        let b = ServerSchema::new(vec![tool("index")], Vec::new());
+        let c = ServerSchema::new(vec![tool("search")], Vec::new());
+        assert_eq!(a.checksum, b.checksum);
+        assert_ne!(a.checksum, c.checksum);
+        assert_eq!(a.checksum.len(), 64);
+    }
+
+    #[tokio::test]
+    async fn test_call_tool_denied_by_policy() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let manager = create_test_manager(temp_dir.path()).await;
+        let policy = MCPToolPolicy {
+            allow: Some(vec!["find_symbol".to_string()]),
+            deny: Vec::new(),
+        };
+
+        let denied = manager
+            .call_tool_with_progress(
+                "serena",
+                "replace_symbol_body",
+                serde_json::json!({}),
+                Some(&policy),
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(denied, Err(MCPError::ToolNotAllowed { .. })));
+
+        // Allowed tools go through to the server lookup
+        let allowed = manager
+            .call_tool_with_progress(
+                "serena",
+                "find_symbol",
+                serde_json::json!({}),
+                Some(&policy),
+                None,
+                None,
+            )
+            .await;
+        assert!(matches!(allowed, Err(MCPError::ServerNotFound { .. })));
+    }
 }
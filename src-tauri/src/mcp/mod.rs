@@ -45,8 +45,12 @@
 //! - [`error`]: MCP-specific error types
 //! - [`protocol`]: JSON-RPC 2.0 and MCP protocol types
 //! - [`server_handle`]: Process spawning and lifecycle management (stdio transport)
-//! - [`http_handle`]: HTTP/SSE transport for remote MCP servers
+//! - [`http_handle`]: Streamable HTTP and legacy HTTP+SSE transports for remote MCP servers
+//! - [`sse`]: Server-Sent Events parser used by the HTTP transports
+//! - [`notifications`]: Server notifications (tool list changes, progress)
+//! - [`oauth`]: OAuth 2.1 authorization (PKCE, dynamic registration, token refresh)
 //! - [`client`]: High-level MCP client interface
+//! - [`config_import`]: Parsing of `mcpServers` blocks from other MCP clients' config files
 //! - [`manager`]: MCPManager for server registry and coordination
 //!
 //! ## Usage
@@ -75,6 +79,7 @@
 pub mod circuit_breaker;
 #[allow(dead_code)]
 pub mod client;
+pub mod config_import;
 pub mod error;
 #[allow(dead_code)]
 pub mod helpers;
@@ -82,18 +87,27 @@ pub mod helpers;
 pub mod http_handle;
 #[allow(dead_code)]
 pub mod manager;
+pub mod notifications;
+#[allow(dead_code)]
+pub mod oauth;
 #[allow(dead_code)]
 pub mod protocol;
 #[allow(dead_code)]
 pub mod server_handle;
+#[allow(dead_code)]
+pub mod sse;
+#[cfg(test)]
+pub(crate) mod test_server;
 
 // Re-export commonly used types
 pub use error::{MCPError, MCPResult};
 #[allow(unused_imports)]
 pub use protocol::{
     JsonRpcError, JsonRpcId, JsonRpcRequest, JsonRpcResponse, MCPClientCapabilities, MCPClientInfo,
-    MCPContent, MCPInitializeParams, MCPInitializeResult, MCPResourceContent,
-    MCPResourceDefinition, MCPResourcesListResult, MCPServerCapabilities, MCPServerInfo,
+    MCPContent, MCPInitializeParams, MCPInitializeResult, MCPPromptArgumentDefinition,
+    MCPPromptDefinition, MCPPromptGetParams, MCPPromptGetResult, MCPPromptMessage,
+    MCPPromptsListResult, MCPResourceContent, MCPResourceDefinition, MCPResourceReadParams,
+    MCPResourceReadResult, MCPResourcesListResult, MCPServerCapabilities, MCPServerInfo,
     MCPToolCallParams, MCPToolCallResponse, MCPToolDefinition, MCPToolsListResult, MCP_CLIENT_NAME,
     MCP_CLIENT_VERSION, MCP_PROTOCOL_VERSION,
 };
@@ -108,5 +122,8 @@ pub use helpers::{parse_deployment_method, parse_env_json};
 #[allow(unused_imports)]
 pub use http_handle::MCPHttpHandle;
 pub use manager::MCPManager;
+pub use notifications::MCPProgress;
+#[allow(unused_imports)]
+pub use notifications::ProgressSender;
 #[allow(unused_imports)]
 pub use server_handle::MCPServerHandle;
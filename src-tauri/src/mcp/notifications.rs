@@ -0,0 +1,205 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! MCP server notifications and requests
+//!
+//! Messages sent by a server on its own initiative, received by the
+//! transports while they wait for a response:
+//! - `notifications/tools/list_changed` marks the tool list as stale; the
+//!   manager refreshes it and updates its schema cache on next access
+//! - `notifications/progress` is forwarded to the sender registered for its
+//!   progress token (tool calls made with progress reporting)
+//! - server requests are answered (`ping`) or rejected ("method not found")
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tracing::debug;
+
+/// Notification sent when the server's tool list changed
+pub const TOOLS_LIST_CHANGED: &str = "notifications/tools/list_changed";
+
+/// Notification carrying the progress of a long-running request
+pub const PROGRESS: &str = "notifications/progress";
+
+/// Parameters of a `notifications/progress` notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MCPProgress {
+    /// Token sent in the request `_meta.progressToken`
+    pub progress_token: Value,
+    /// Progress so far (increases with each notification)
+    pub progress: f64,
+    /// Total when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+    /// Human-readable progress message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl MCPProgress {
+    /// Progress percentage (0-100), when the total is known
+    pub fn percent(&self) -> Option<u8> {
+        self.total
+            .filter(|total| *total > 0.0)
+            .map(|total| (self.progress / total * 100.0).clamp(0.0, 100.0) as u8)
+    }
+}
+
+/// Channel receiving the progress notifications of a request
+pub type ProgressSender = mpsc::UnboundedSender<MCPProgress>;
+
+/// Notification state of one server connection
+///
+/// Cloned into the readers of the connection (e.g. the legacy SSE stream
+/// task), so notifications are recorded wherever they are received.
+#[derive(Clone, Default)]
+pub struct NotificationState {
+    /// Set by `notifications/tools/list_changed`, reset when consumed
+    tools_changed: Arc<AtomicBool>,
+    /// Progress senders keyed by serialized progress token
+    progress: Arc<Mutex<HashMap<String, ProgressSender>>>,
+}
+
+impl NotificationState {
+    /// Records a notification received from the server
+    pub fn handle(&self, server_id: &str, method: &str, params: Option<&Value>) {
+        match method {
+            TOOLS_LIST_CHANGED => {
+                debug!(server_id = %server_id, "Server tool list changed");
+                self.tools_changed.store(true, Ordering::SeqCst);
+            }
+            PROGRESS => {
+                let Some(progress) =
+                    params.and_then(|p| serde_json::from_value::<MCPProgress>(p.clone()).ok())
+                else {
+                    debug!(server_id = %server_id, "Invalid progress notification");
+                    return;
+                };
+                let senders = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+                match senders.get(&token_key(&progress.progress_token)) {
+                    Some(sender) => {
+                        let _ = sender.send(progress);
+                    }
+                    None => debug!(server_id = %server_id, "Progress for unknown token"),
+                }
+            }
+            _ => debug!(server_id = %server_id, method = %method, "Server notification received"),
+        }
+    }
+
+    /// Whether the tool list changed since it was last refreshed
+    pub fn tools_changed(&self) -> bool {
+        self.tools_changed.load(Ordering::SeqCst)
+    }
+
+    /// Returns and resets the tool list change flag
+    pub fn take_tools_changed(&self) -> bool {
+        self.tools_changed.swap(false, Ordering::SeqCst)
+    }
+
+    /// Forwards progress notifications for `token` to `sender`
+    pub fn register_progress(&self, token: &Value, sender: ProgressSender) {
+        self.progress
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(token_key(token), sender);
+    }
+
+    /// Stops forwarding progress notifications for `token`
+    pub fn unregister_progress(&self, token: &Value) {
+        self.progress
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&token_key(token));
+    }
+}
+
+/// Key of a progress token (tokens are strings or integers)
+fn token_key(token: &Value) -> String {
+    token.to_string()
+}
+
+/// Builds the reply to a request sent by the server
+///
+/// `ping` gets an empty result; other methods are not supported.
+pub fn server_request_reply(id: &Value, method: &str) -> Value {
+    if method == "ping" {
+        serde_json::json!({"jsonrpc": "2.0", "id": id, "result": {}})
+    } else {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": -32601, "message": format!("Method not supported: {}", method)}
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_tools_list_changed_flag() {
+        let state = NotificationState::default();
+        assert!(!state.tools_changed());
+
+        state.clone().handle("srv", TOOLS_LIST_CHANGED, None);
+        assert!(state.tools_changed());
+        assert!(state.take_tools_changed());
+        assert!(!state.take_tools_changed());
+    }
+
+    #[test]
+    fn test_progress_routed_by_token() {
+        let state = NotificationState::default();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        state.register_progress(&json!(7), tx);
+
+        let params =
+            json!({"progressToken": 7, "progress": 25, "total": 50, "message": "Indexing"});
+        state.handle("srv", PROGRESS, Some(&params));
+        state.handle(
+            "srv",
+            PROGRESS,
+            Some(&json!({"progressToken": "other", "progress": 1})),
+        );
+
+        let progress = rx.try_recv().unwrap();
+        assert_eq!(progress.percent(), Some(50));
+        assert_eq!(progress.message.as_deref(), Some("Indexing"));
+        assert!(rx.try_recv().is_err());
+
+        state.unregister_progress(&json!(7));
+        state.handle("srv", PROGRESS, Some(&params));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_server_request_reply() {
+        assert_eq!(
+            server_request_reply(&json!(1), "ping"),
+            json!({"jsonrpc": "2.0", "id": 1, "result": {}})
+        );
+        assert_eq!(
+            server_request_reply(&json!("a"), "sampling/createMessage")["error"]["code"],
+            -32601
+        );
+    }
+}
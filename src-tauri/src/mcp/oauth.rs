@@ -0,0 +1,859 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! MCP OAuth 2.1 Authorization
+//!
+//! OAuth client for remote (HTTP) MCP servers that require authorization,
+//! following the MCP authorization specification:
+//!
+//! 1. **Discovery**: Protected Resource Metadata (RFC 9728) points to the
+//!    authorization server, whose metadata (RFC 8414) lists the endpoints.
+//!    Servers without metadata use the default `/authorize`, `/token` and
+//!    `/register` endpoints.
+//! 2. **Dynamic Client Registration** (RFC 7591) as a public client.
+//! 3. **Authorization Code + PKCE** (S256), with the MCP server URL as the
+//!    `resource` indicator (RFC 8707).
+//! 4. **Refresh**: expired tokens are refreshed before connecting and when
+//!    the server answers HTTP 401.
+//!
+//! Credentials are persisted through an [`OAuthCredentialStore`]
+//! (`SecureKeyStore` in the application).
+
+use crate::mcp::error::{MCPError, MCPResult};
+use crate::mcp::http_handle::SHARED_HTTP_CLIENT;
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::OsRng;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Client name sent during dynamic client registration
+const CLIENT_NAME: &str = "Zileo Chat";
+
+/// Tokens expiring within this delay are refreshed before use (seconds)
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// Maximum time between `begin` and `complete` of an authorization (seconds)
+const PENDING_AUTHORIZATION_TTL_SECS: i64 = 600;
+
+/// OAuth credentials of an MCP server (persisted in the credential store)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuthCredentials {
+    /// Client ID obtained by dynamic registration
+    pub client_id: String,
+    /// Client secret (None for public clients)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    /// Token endpoint (used for refresh)
+    pub token_endpoint: String,
+    /// Resource indicator (MCP server URL)
+    pub resource: String,
+    /// Access token sent as Bearer
+    pub access_token: String,
+    /// Refresh token (None if the server did not issue one)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+    /// Access token expiry (None if unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Granted scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl OAuthCredentials {
+    /// Returns true if the access token is expired or about to expire.
+    pub fn needs_refresh(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= Utc::now() + Duration::seconds(REFRESH_MARGIN_SECS))
+            .unwrap_or(false)
+    }
+
+    /// Returns the `Authorization` header value.
+    pub fn authorization_header(&self) -> String {
+        format!("Bearer {}", self.access_token)
+    }
+}
+
+/// Persistent storage of OAuth credentials, keyed by MCP server ID
+pub trait OAuthCredentialStore: Send + Sync {
+    /// Loads the credentials of a server.
+    fn load_credentials(&self, server_id: &str) -> Option<OAuthCredentials>;
+
+    /// Saves the credentials of a server.
+    fn save_credentials(
+        &self,
+        server_id: &str,
+        credentials: &OAuthCredentials,
+    ) -> Result<(), String>;
+}
+
+/// Authorization server metadata (RFC 8414)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AuthorizationServerMetadata {
+    /// Authorization endpoint
+    pub authorization_endpoint: String,
+    /// Token endpoint
+    pub token_endpoint: String,
+    /// Dynamic client registration endpoint
+    #[serde(default)]
+    pub registration_endpoint: Option<String>,
+}
+
+/// Protected resource metadata (RFC 9728)
+#[derive(Debug, Deserialize)]
+struct ProtectedResourceMetadata {
+    /// Authorization servers protecting the resource
+    #[serde(default)]
+    authorization_servers: Vec<String>,
+    /// Scopes the resource understands
+    #[serde(default)]
+    scopes_supported: Vec<String>,
+}
+
+/// Dynamic client registration response (RFC 7591)
+#[derive(Debug, Deserialize)]
+struct ClientRegistrationResponse {
+    client_id: String,
+    #[serde(default)]
+    client_secret: Option<String>,
+}
+
+/// Token endpoint response
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<i64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// Authorization started by `begin_mcp_oauth`, waiting for the code
+#[derive(Debug, Clone)]
+pub struct PendingAuthorization {
+    /// MCP server ID
+    pub server_id: String,
+    /// URL to open in the browser
+    pub authorization_url: String,
+    /// Anti-CSRF state (also the key of the pending authorization)
+    pub state: String,
+    /// PKCE code verifier
+    pub code_verifier: String,
+    /// Redirect URI registered for the client
+    pub redirect_uri: String,
+    /// Registered client ID
+    pub client_id: String,
+    /// Registered client secret
+    pub client_secret: Option<String>,
+    /// Token endpoint
+    pub token_endpoint: String,
+    /// Resource indicator (MCP server URL)
+    pub resource: String,
+    /// Creation time (pending authorizations expire)
+    pub created_at: DateTime<Utc>,
+}
+
+/// Authorization start returned to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthAuthorizationStart {
+    /// MCP server ID
+    pub server_id: String,
+    /// URL to open in the browser
+    pub authorization_url: String,
+    /// State to pass back with the authorization code
+    pub state: String,
+}
+
+impl From<&PendingAuthorization> for OAuthAuthorizationStart {
+    fn from(pending: &PendingAuthorization) -> Self {
+        Self {
+            server_id: pending.server_id.clone(),
+            authorization_url: pending.authorization_url.clone(),
+            state: pending.state.clone(),
+        }
+    }
+}
+
+/// Authorizations waiting for their code, keyed by state
+#[derive(Debug, Default)]
+pub struct PendingAuthorizations {
+    flows: Mutex<HashMap<String, PendingAuthorization>>,
+}
+
+impl PendingAuthorizations {
+    /// Stores a pending authorization, dropping expired ones.
+    pub async fn insert(&self, pending: PendingAuthorization) {
+        let mut flows = self.flows.lock().await;
+        flows.retain(|_, flow| !is_expired(flow));
+        flows.insert(pending.state.clone(), pending);
+    }
+
+    /// Removes and returns the pending authorization for `state`.
+    ///
+    /// # Returns
+    /// None if the state is unknown or the authorization expired
+    pub async fn take(&self, state: &str) -> Option<PendingAuthorization> {
+        self.flows
+            .lock()
+            .await
+            .remove(state)
+            .filter(|flow| !is_expired(flow))
+    }
+}
+
+/// Returns true if a pending authorization is too old to be completed.
+fn is_expired(pending: &PendingAuthorization) -> bool {
+    Utc::now() - pending.created_at > Duration::seconds(PENDING_AUTHORIZATION_TTL_SECS)
+}
+
+/// PKCE verifier and S256 challenge
+#[derive(Debug, Clone)]
+pub struct Pkce {
+    /// Code verifier (sent with the token request)
+    pub verifier: String,
+    /// Code challenge (sent with the authorization request)
+    pub challenge: String,
+}
+
+impl Pkce {
+    /// Generates a random verifier and its challenge.
+    pub fn generate() -> Self {
+        let verifier = random_token(32);
+        Self {
+            challenge: pkce_challenge(&verifier),
+            verifier,
+        }
+    }
+}
+
+/// Computes the S256 code challenge of a verifier.
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// Generates a random URL-safe token from `bytes` random bytes.
+fn random_token(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    OsRng.fill_bytes(&mut buffer);
+    URL_SAFE_NO_PAD.encode(buffer)
+}
+
+/// OAuth client for one MCP server
+pub struct MCPOAuthClient {
+    /// Server name (for errors)
+    server_name: String,
+    /// MCP server URL (resource indicator)
+    resource: String,
+    /// HTTP client
+    client: Client,
+}
+
+impl MCPOAuthClient {
+    /// Creates an OAuth client for the MCP server at `resource`.
+    pub fn new(server_name: impl Into<String>, resource: impl Into<String>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            resource: resource.into(),
+            client: SHARED_HTTP_CLIENT.clone(),
+        }
+    }
+
+    /// Starts an authorization: discovers the authorization server, registers
+    /// a client and builds the authorization URL (PKCE + state).
+    pub async fn begin(
+        &self,
+        server_id: &str,
+        redirect_uri: &str,
+    ) -> MCPResult<PendingAuthorization> {
+        let (metadata, scopes) = self.discover().await?;
+        let (client_id, client_secret) = self.register(&metadata, redirect_uri).await?;
+
+        let pkce = Pkce::generate();
+        let state = random_token(16);
+
+        let mut url =
+            Url::parse(&metadata.authorization_endpoint).map_err(|e| MCPError::InvalidConfig {
+                field: "authorization_endpoint".to_string(),
+                reason: format!("Invalid authorization endpoint: {}", e),
+            })?;
+        {
+            let mut query = url.query_pairs_mut();
+            query
+                .append_pair("response_type", "code")
+                .append_pair("client_id", &client_id)
+                .append_pair("redirect_uri", redirect_uri)
+                .append_pair("code_challenge", &pkce.challenge)
+                .append_pair("code_challenge_method", "S256")
+                .append_pair("state", &state)
+                .append_pair("resource", &self.resource);
+            if !scopes.is_empty() {
+                query.append_pair("scope", &scopes.join(" "));
+            }
+        }
+
+        info!(
+            server_id = %server_id,
+            client_id = %client_id,
+            "OAuth authorization started"
+        );
+
+        Ok(PendingAuthorization {
+            server_id: server_id.to_string(),
+            authorization_url: url.to_string(),
+            state,
+            code_verifier: pkce.verifier,
+            redirect_uri: redirect_uri.to_string(),
+            client_id,
+            client_secret,
+            token_endpoint: metadata.token_endpoint,
+            resource: self.resource.clone(),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Exchanges an authorization code for tokens.
+    pub async fn exchange_code(
+        &self,
+        pending: &PendingAuthorization,
+        code: &str,
+    ) -> MCPResult<OAuthCredentials> {
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", pending.redirect_uri.as_str()),
+            ("client_id", pending.client_id.as_str()),
+            ("code_verifier", pending.code_verifier.as_str()),
+            ("resource", pending.resource.as_str()),
+        ];
+        if let Some(secret) = pending.client_secret.as_deref() {
+            form.push(("client_secret", secret));
+        }
+
+        let response = self.token_request(&pending.token_endpoint, &form).await?;
+        info!(server_id = %pending.server_id, "OAuth authorization completed");
+
+        Ok(OAuthCredentials {
+            client_id: pending.client_id.clone(),
+            client_secret: pending.client_secret.clone(),
+            token_endpoint: pending.token_endpoint.clone(),
+            resource: pending.resource.clone(),
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            expires_at: expiry(response.expires_in),
+            scope: response.scope,
+        })
+    }
+
+    /// Refreshes an access token.
+    ///
+    /// The previous refresh token is kept if the server does not rotate it.
+    pub async fn refresh(&self, credentials: &OAuthCredentials) -> MCPResult<OAuthCredentials> {
+        let refresh_token =
+            credentials
+                .refresh_token
+                .as_deref()
+                .ok_or_else(|| MCPError::ConnectionFailed {
+                    server: self.server_name.clone(),
+                    message: "OAuth token expired and no refresh token is available".to_string(),
+                })?;
+
+        let mut form = vec![
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", credentials.client_id.as_str()),
+            ("resource", credentials.resource.as_str()),
+        ];
+        if let Some(secret) = credentials.client_secret.as_deref() {
+            form.push(("client_secret", secret));
+        }
+
+        let response = self
+            .token_request(&credentials.token_endpoint, &form)
+            .await?;
+        debug!(server = %self.server_name, "OAuth access token refreshed");
+
+        Ok(OAuthCredentials {
+            access_token: response.access_token,
+            refresh_token: response
+                .refresh_token
+                .or_else(|| credentials.refresh_token.clone()),
+            expires_at: expiry(response.expires_in),
+            scope: response.scope.or_else(|| credentials.scope.clone()),
+            ..credentials.clone()
+        })
+    }
+
+    /// Discovers the authorization server metadata and the resource scopes.
+    async fn discover(&self) -> MCPResult<(AuthorizationServerMetadata, Vec<String>)> {
+        let resource = self.parse_url(&self.resource, "resource")?;
+
+        let resource_metadata: Option<ProtectedResourceMetadata> = self
+            .fetch_first(&well_known_urls(&resource, "oauth-protected-resource"))
+            .await;
+        let (issuer, scopes) = match resource_metadata {
+            Some(metadata) => (
+                metadata.authorization_servers.into_iter().next(),
+                metadata.scopes_supported,
+            ),
+            None => (None, Vec::new()),
+        };
+
+        let issuer = match issuer {
+            Some(issuer) => self.parse_url(&issuer, "authorization_servers")?,
+            None => origin(&resource),
+        };
+
+        let mut candidates = well_known_urls(&issuer, "oauth-authorization-server");
+        candidates.extend(well_known_urls(&issuer, "openid-configuration"));
+        if let Some(metadata) = self.fetch_first(&candidates).await {
+            return Ok((metadata, scopes));
+        }
+
+        // No metadata: default endpoints relative to the authorization server
+        debug!(server = %self.server_name, "No OAuth metadata found, using default endpoints");
+        let base = origin(&issuer);
+        let endpoint = |path: &str| base.join(path).map(|u| u.to_string()).unwrap_or_default();
+        Ok((
+            AuthorizationServerMetadata {
+                authorization_endpoint: endpoint("/authorize"),
+                token_endpoint: endpoint("/token"),
+                registration_endpoint: Some(endpoint("/register")),
+            },
+            scopes,
+        ))
+    }
+
+    /// Registers a public client for `redirect_uri`.
+    async fn register(
+        &self,
+        metadata: &AuthorizationServerMetadata,
+        redirect_uri: &str,
+    ) -> MCPResult<(String, Option<String>)> {
+        let endpoint = metadata.registration_endpoint.as_deref().ok_or_else(|| {
+            MCPError::ConnectionFailed {
+                server: self.server_name.clone(),
+                message: "Authorization server does not support dynamic client registration"
+                    .to_string(),
+            }
+        })?;
+
+        let body = json!({
+            "client_name": CLIENT_NAME,
+            "redirect_uris": [redirect_uri],
+            "grant_types": ["authorization_code", "refresh_token"],
+            "response_types": ["code"],
+            "token_endpoint_auth_method": "none"
+        });
+
+        let response = self
+            .client
+            .post(endpoint)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| self.error(format!("Client registration failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(self.error(format!(
+                "Client registration failed: HTTP {} - {}",
+                status, body
+            )));
+        }
+
+        let registration: ClientRegistrationResponse =
+            response
+                .json()
+                .await
+                .map_err(|e| MCPError::SerializationError {
+                    context: "OAuth client registration".to_string(),
+                    message: e.to_string(),
+                })?;
+        Ok((registration.client_id, registration.client_secret))
+    }
+
+    /// Sends a form-encoded token request.
+    async fn token_request(
+        &self,
+        token_endpoint: &str,
+        form: &[(&str, &str)],
+    ) -> MCPResult<TokenResponse> {
+        let response = self
+            .client
+            .post(token_endpoint)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| self.error(format!("Token request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let reason = body["error_description"]
+                .as_str()
+                .or_else(|| body["error"].as_str())
+                .unwrap_or("unknown error");
+            return Err(self.error(format!(
+                "Token request failed: HTTP {} - {}",
+                status, reason
+            )));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| MCPError::SerializationError {
+                context: "OAuth token response".to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// GETs the first candidate URL returning valid JSON metadata.
+    async fn fetch_first<T: serde::de::DeserializeOwned>(&self, urls: &[String]) -> Option<T> {
+        for url in urls {
+            match self.client.get(url).send().await {
+                Ok(response) if response.status().is_success() => {
+                    match response.json::<T>().await {
+                        Ok(metadata) => return Some(metadata),
+                        Err(e) => warn!(url = %url, error = %e, "Invalid OAuth metadata"),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => debug!(url = %url, error = %e, "OAuth metadata request failed"),
+            }
+        }
+        None
+    }
+
+    fn parse_url(&self, url: &str, field: &str) -> MCPResult<Url> {
+        Url::parse(url).map_err(|e| MCPError::InvalidConfig {
+            field: field.to_string(),
+            reason: format!("Invalid URL '{}': {}", url, e),
+        })
+    }
+
+    fn error(&self, message: String) -> MCPError {
+        MCPError::ConnectionFailed {
+            server: self.server_name.clone(),
+            message: format!("OAuth: {}", message),
+        }
+    }
+}
+
+/// Converts `expires_in` (seconds) to an absolute expiry.
+fn expiry(expires_in: Option<i64>) -> Option<DateTime<Utc>> {
+    expires_in.map(|secs| Utc::now() + Duration::seconds(secs))
+}
+
+/// Origin (scheme, host, port) of a URL.
+fn origin(url: &Url) -> Url {
+    let mut origin = url.clone();
+    origin.set_path("/");
+    origin.set_query(None);
+    origin.set_fragment(None);
+    origin
+}
+
+/// Well-known metadata URLs for `url`: path-aware first, then root.
+fn well_known_urls(url: &Url, suffix: &str) -> Vec<String> {
+    let base = origin(url);
+    let path = url.path().trim_end_matches('/');
+    let mut urls = Vec::new();
+    if !path.is_empty() {
+        if let Ok(u) = base.join(&format!("/.well-known/{}{}", suffix, path)) {
+            urls.push(u.to_string());
+        }
+    }
+    if let Ok(u) = base.join(&format!("/.well-known/{}", suffix)) {
+        urls.push(u.to_string());
+    }
+    urls
+}
+
+/// In-memory credential store for tests
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MemoryCredentialStore {
+    credentials: std::sync::Mutex<HashMap<String, OAuthCredentials>>,
+}
+
+#[cfg(test)]
+impl OAuthCredentialStore for MemoryCredentialStore {
+    fn load_credentials(&self, server_id: &str) -> Option<OAuthCredentials> {
+        self.credentials.lock().unwrap().get(server_id).cloned()
+    }
+
+    fn save_credentials(
+        &self,
+        server_id: &str,
+        credentials: &OAuthCredentials,
+    ) -> Result<(), String> {
+        self.credentials
+            .lock()
+            .unwrap()
+            .insert(server_id.to_string(), credentials.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::test_server::{http, json_reply, spawn_mock_server};
+
+    #[test]
+    fn test_pkce_challenge_rfc7636_vector() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_pkce_generate() {
+        let first = Pkce::generate();
+        let second = Pkce::generate();
+        assert_eq!(first.verifier.len(), 43);
+        assert_eq!(first.challenge, pkce_challenge(&first.verifier));
+        assert_ne!(first.verifier, second.verifier);
+    }
+
+    #[test]
+    fn test_well_known_urls() {
+        let url = Url::parse("https://example.com/api/mcp").unwrap();
+        assert_eq!(
+            well_known_urls(&url, "oauth-protected-resource"),
+            vec![
+                "https://example.com/.well-known/oauth-protected-resource/api/mcp",
+                "https://example.com/.well-known/oauth-protected-resource",
+            ]
+        );
+
+        let root = Url::parse("https://auth.example.com").unwrap();
+        assert_eq!(
+            well_known_urls(&root, "oauth-authorization-server"),
+            vec!["https://auth.example.com/.well-known/oauth-authorization-server"]
+        );
+    }
+
+    #[test]
+    fn test_credentials_needs_refresh() {
+        let mut credentials = OAuthCredentials {
+            client_id: "client".to_string(),
+            client_secret: None,
+            token_endpoint: "https://auth.example.com/token".to_string(),
+            resource: "https://example.com/mcp".to_string(),
+            access_token: "token".to_string(),
+            refresh_token: None,
+            expires_at: None,
+            scope: None,
+        };
+        assert!(!credentials.needs_refresh());
+
+        credentials.expires_at = Some(Utc::now() + Duration::seconds(30));
+        assert!(credentials.needs_refresh());
+
+        credentials.expires_at = Some(Utc::now() + Duration::hours(1));
+        assert!(!credentials.needs_refresh());
+        assert_eq!(credentials.authorization_header(), "Bearer token");
+    }
+
+    #[tokio::test]
+    async fn test_pending_authorizations_expire() {
+        let flows = PendingAuthorizations::default();
+        let pending = PendingAuthorization {
+            server_id: "srv".to_string(),
+            authorization_url: "https://auth.example.com/authorize".to_string(),
+            state: "state-1".to_string(),
+            code_verifier: "verifier".to_string(),
+            redirect_uri: "http://127.0.0.1:8765/callback".to_string(),
+            client_id: "client".to_string(),
+            client_secret: None,
+            token_endpoint: "https://auth.example.com/token".to_string(),
+            resource: "https://example.com/mcp".to_string(),
+            created_at: Utc::now(),
+        };
+        flows.insert(pending.clone()).await;
+        flows
+            .insert(PendingAuthorization {
+                state: "state-2".to_string(),
+                created_at: Utc::now() - Duration::hours(1),
+                ..pending
+            })
+            .await;
+
+        assert!(flows.take("state-1").await.is_some());
+        assert!(flows.take("state-1").await.is_none());
+        assert!(flows.take("state-2").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_authorization_code_flow() {
+        let received_verifier: std::sync::Arc<std::sync::Mutex<Option<String>>> =
+            Default::default();
+        let verifier = received_verifier.clone();
+        let url = spawn_mock_server(move |request| {
+            let path = request
+                .path
+                .split('?')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            match path.as_str() {
+                "/.well-known/oauth-protected-resource/mcp" => {
+                    let host = request.header("host").unwrap_or_default().to_string();
+                    json_reply(
+                        &[],
+                        json!({
+                            "resource": format!("http://{}/mcp", host),
+                            "authorization_servers": [format!("http://{}/auth", host)],
+                            "scopes_supported": ["tools"]
+                        }),
+                    )
+                }
+                "/.well-known/oauth-authorization-server/auth" => {
+                    let host = request.header("host").unwrap_or_default().to_string();
+                    json_reply(
+                        &[],
+                        json!({
+                            "issuer": format!("http://{}/auth", host),
+                            "authorization_endpoint": format!("http://{}/auth/authorize", host),
+                            "token_endpoint": format!("http://{}/auth/token", host),
+                            "registration_endpoint": format!("http://{}/auth/register", host)
+                        }),
+                    )
+                }
+                "/auth/register" => {
+                    assert_eq!(request.body["token_endpoint_auth_method"], "none");
+                    assert_eq!(
+                        request.body["redirect_uris"][0],
+                        "http://127.0.0.1:8765/callback"
+                    );
+                    json_reply(&[], json!({"client_id": "client-1"}))
+                }
+                "/auth/token" => {
+                    let form = request.form();
+                    assert_eq!(form["grant_type"], "authorization_code");
+                    assert_eq!(form["code"], "code-1");
+                    assert_eq!(form["client_id"], "client-1");
+                    *verifier.lock().unwrap() = Some(form["code_verifier"].clone());
+                    json_reply(
+                        &[],
+                        json!({
+                            "access_token": "access-1",
+                            "token_type": "Bearer",
+                            "expires_in": 3600,
+                            "refresh_token": "refresh-1",
+                            "scope": "tools"
+                        }),
+                    )
+                }
+                _ => http("404 Not Found", &[], ""),
+            }
+        })
+        .await;
+
+        let resource = format!("{}/mcp", url);
+        let client = MCPOAuthClient::new("Remote", resource.clone());
+        let pending = client
+            .begin("srv", "http://127.0.0.1:8765/callback")
+            .await
+            .unwrap();
+
+        let authorization_url = Url::parse(&pending.authorization_url).unwrap();
+        assert_eq!(authorization_url.path(), "/auth/authorize");
+        let query: HashMap<String, String> = authorization_url.query_pairs().into_owned().collect();
+        assert_eq!(query["response_type"], "code");
+        assert_eq!(query["client_id"], "client-1");
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert_eq!(
+            query["code_challenge"],
+            pkce_challenge(&pending.code_verifier)
+        );
+        assert_eq!(query["state"], pending.state);
+        assert_eq!(query["resource"], resource);
+        assert_eq!(query["scope"], "tools");
+
+        let credentials = client.exchange_code(&pending, "code-1").await.unwrap();
+        assert_eq!(
+            received_verifier.lock().unwrap().as_deref(),
+            Some(pending.code_verifier.as_str())
+        );
+        assert_eq!(credentials.client_id, "client-1");
+        assert_eq!(credentials.access_token, "access-1");
+        assert_eq!(credentials.refresh_token.as_deref(), Some("refresh-1"));
+        assert_eq!(credentials.token_endpoint, format!("{}/auth/token", url));
+        assert_eq!(credentials.resource, resource);
+        assert!(!credentials.needs_refresh());
+    }
+
+    #[tokio::test]
+    async fn test_discovery_falls_back_to_default_endpoints() {
+        let url = spawn_mock_server(|request| match request.path.as_str() {
+            "/register" => json_reply(&[], json!({"client_id": "client-2"})),
+            _ => http("404 Not Found", &[], ""),
+        })
+        .await;
+
+        let pending = MCPOAuthClient::new("Remote", format!("{}/mcp", url))
+            .begin("srv", "http://127.0.0.1:8765/callback")
+            .await
+            .unwrap();
+
+        assert!(pending
+            .authorization_url
+            .starts_with(&format!("{}/authorize?", url)));
+        assert_eq!(pending.token_endpoint, format!("{}/token", url));
+        assert_eq!(pending.client_id, "client-2");
+    }
+
+    #[tokio::test]
+    async fn test_token_error_is_reported() {
+        let url = spawn_mock_server(|_| {
+            http(
+                "400 Bad Request",
+                &[("Content-Type", "application/json")],
+                r#"{"error":"invalid_grant","error_description":"Refresh token revoked"}"#,
+            )
+        })
+        .await;
+
+        let credentials = OAuthCredentials {
+            client_id: "client".to_string(),
+            client_secret: None,
+            token_endpoint: format!("{}/token", url),
+            resource: format!("{}/mcp", url),
+            access_token: "token".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_at: None,
+            scope: None,
+        };
+        let error = MCPOAuthClient::new("Remote", credentials.resource.clone())
+            .refresh(&credentials)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("Refresh token revoked"));
+    }
+}
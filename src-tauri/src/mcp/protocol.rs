@@ -19,10 +19,22 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// MCP Protocol version supported by this implementation
 pub const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
 
+/// Protocol revisions that allow JSON-RPC batches over the Streamable HTTP transport.
+///
+/// Batching was introduced in 2025-03-26 and removed again in 2025-06-18, so it is
+/// only used when a server explicitly negotiates one of these revisions.
+pub const MCP_BATCHING_PROTOCOL_VERSIONS: &[&str] = &["2025-03-26"];
+
+/// Returns true when the negotiated protocol revision accepts JSON-RPC batches
+pub fn supports_batching(protocol_version: &str) -> bool {
+    MCP_BATCHING_PROTOCOL_VERSIONS.contains(&protocol_version)
+}
+
 /// Application name used in MCP client info
 pub const MCP_CLIENT_NAME: &str = "Zileo Chat";
 
@@ -344,6 +356,9 @@ pub struct MCPToolCallParams {
     /// Tool arguments
     #[serde(default)]
     pub arguments: Value,
+    /// Request metadata (`progressToken` to receive progress notifications)
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
 }
 
 /// MCP tools/call response
@@ -429,6 +444,89 @@ pub struct MCPResourcesListResult {
     pub resources: Vec<MCPResourceDefinition>,
 }
 
+/// MCP resources/read request parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPResourceReadParams {
+    /// URI of the resource to read
+    pub uri: String,
+}
+
+/// MCP resources/read response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MCPResourceReadResult {
+    /// Resource contents (a URI may resolve to several items, e.g. a directory)
+    #[serde(default)]
+    pub contents: Vec<MCPResourceContent>,
+}
+
+// =============================================================================
+// MCP Prompts Types
+// =============================================================================
+
+/// Argument of an MCP prompt template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPPromptArgumentDefinition {
+    /// Argument name
+    pub name: String,
+    /// Argument description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be provided
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+}
+
+/// MCP Prompt definition from server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPPromptDefinition {
+    /// Prompt name (unique within the server)
+    pub name: String,
+    /// Prompt description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Arguments accepted by the prompt
+    #[serde(default)]
+    pub arguments: Vec<MCPPromptArgumentDefinition>,
+}
+
+/// MCP prompts/list response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPPromptsListResult {
+    /// List of available prompts
+    #[serde(default)]
+    pub prompts: Vec<MCPPromptDefinition>,
+}
+
+/// MCP prompts/get request parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPPromptGetParams {
+    /// Name of the prompt
+    pub name: String,
+    /// Argument values
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Message of a rendered MCP prompt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPPromptMessage {
+    /// Message role ("user" or "assistant")
+    pub role: String,
+    /// Message content
+    pub content: MCPContent,
+}
+
+/// MCP prompts/get response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MCPPromptGetResult {
+    /// Prompt description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Rendered messages
+    #[serde(default)]
+    pub messages: Vec<MCPPromptMessage>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -573,6 +671,7 @@ mod tests {
         let params = MCPToolCallParams {
             name: "find_symbol".to_string(),
             arguments: serde_json::json!({"name": "MyClass"}),
+            meta: None,
         };
 
         let json = serde_json::to_string(&params).unwrap();
@@ -617,6 +716,67 @@ mod tests {
         assert_eq!(response.is_error, Some(false));
     }
 
+    #[test]
+    fn test_mcp_resource_read_result() {
+        let json = r#"{
+            "contents": [
+                {"uri": "file:///README.md", "mimeType": "text/markdown", "text": "Hello"},
+                {"uri": "file:///logo.png", "mimeType": "image/png", "blob": "iVBORw0KGgo="}
+            ]
+        }"#;
+
+        let result: MCPResourceReadResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.contents.len(), 2);
+        assert_eq!(result.contents[0].text.as_deref(), Some("Hello"));
+        assert_eq!(result.contents[1].mime_type.as_deref(), Some("image/png"));
+        assert!(result.contents[1].text.is_none());
+
+        let params = serde_json::to_value(MCPResourceReadParams {
+            uri: "file:///README.md".to_string(),
+        })
+        .unwrap();
+        assert_eq!(params, serde_json::json!({"uri": "file:///README.md"}));
+    }
+
+    #[test]
+    fn test_mcp_prompts() {
+        let json = r#"{
+            "prompts": [
+                {
+                    "name": "code_review",
+                    "description": "Review code",
+                    "arguments": [{"name": "code", "description": "Code to review", "required": true}]
+                },
+                {"name": "summarize"}
+            ]
+        }"#;
+        let list: MCPPromptsListResult = serde_json::from_str(json).unwrap();
+        assert_eq!(list.prompts.len(), 2);
+        assert_eq!(list.prompts[0].arguments[0].required, Some(true));
+        assert!(list.prompts[1].arguments.is_empty());
+
+        let params = serde_json::to_value(MCPPromptGetParams {
+            name: "summarize".to_string(),
+            arguments: HashMap::new(),
+        })
+        .unwrap();
+        assert_eq!(params, serde_json::json!({"name": "summarize"}));
+
+        let json = r#"{
+            "description": "Review code",
+            "messages": [
+                {"role": "user", "content": {"type": "text", "text": "Please review"}}
+            ]
+        }"#;
+        let result: MCPPromptGetResult = serde_json::from_str(json).unwrap();
+        assert_eq!(result.messages.len(), 1);
+        assert_eq!(result.messages[0].role, "user");
+        assert!(matches!(
+            result.messages[0].content,
+            MCPContent::Text { ref text } if text == "Please review"
+        ));
+    }
+
     #[test]
     fn test_mcp_resource_definition() {
         let json = r#"{
@@ -31,20 +31,27 @@
 //! - Responses are read from the process stdout
 //! - Each message is a single JSON line
 
+use crate::mcp::notifications::{server_request_reply, NotificationState, ProgressSender};
 use crate::mcp::{
     JsonRpcRequest, JsonRpcResponse, MCPContent, MCPError, MCPInitializeParams,
-    MCPInitializeResult, MCPResourceDefinition, MCPResourcesListResult, MCPResult,
-    MCPToolCallParams, MCPToolCallResponse, MCPToolDefinition, MCPToolsListResult,
+    MCPInitializeResult, MCPPromptDefinition, MCPPromptGetParams, MCPPromptGetResult,
+    MCPPromptsListResult, MCPResourceDefinition, MCPResourceReadParams, MCPResourceReadResult,
+    MCPResourcesListResult, MCPResult, MCPToolCallParams, MCPToolCallResponse, MCPToolDefinition,
+    MCPToolsListResult,
 };
 use crate::models::mcp::{
-    MCPDeploymentMethod, MCPResource, MCPServerConfig, MCPServerStatus, MCPTool,
+    MCPDeploymentMethod, MCPPrompt, MCPPromptArgument, MCPResource, MCPServerConfig,
+    MCPServerStatus, MCPTool,
 };
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Write};
 use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
 use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 /// Default timeout for MCP operations (30 seconds)
@@ -83,16 +90,22 @@ pub struct MCPServerHandle {
     stdin: Option<Mutex<ChildStdin>>,
     /// Process stdout reader for reading responses (Arc for spawn_blocking)
     stdout_reader: Option<Arc<std::sync::Mutex<BufReader<ChildStdout>>>>,
+    /// Stdout read left running by a timed out or cancelled request
+    pending_read: Mutex<Option<JoinHandle<MCPResult<String>>>>,
     /// Current server status
     status: MCPServerStatus,
     /// Discovered tools after initialization
     tools: Vec<MCPTool>,
     /// Discovered resources after initialization
     resources: Vec<MCPResource>,
+    /// Discovered prompts after initialization
+    prompts: Vec<MCPPrompt>,
     /// Request ID counter for JSON-RPC
     request_id: AtomicI64,
     /// Server info from initialization
     server_info: Option<(String, String)>,
+    /// Notifications received while waiting for responses
+    notifications: NotificationState,
 }
 
 impl MCPServerHandle {
@@ -171,11 +184,14 @@ impl MCPServerHandle {
             child: Some(child),
             stdin: Some(Mutex::new(stdin)),
             stdout_reader: Some(Arc::new(std::sync::Mutex::new(BufReader::new(stdout)))),
+            pending_read: Mutex::new(None),
             status: MCPServerStatus::Starting,
             tools: Vec::new(),
             resources: Vec::new(),
+            prompts: Vec::new(),
             request_id: AtomicI64::new(1),
             server_info: None,
+            notifications: NotificationState::default(),
         })
     }
 
@@ -265,7 +281,7 @@ impl MCPServerHandle {
         let notification = JsonRpcRequest::notification("notifications/initialized", None);
         self.send_notification(notification).await?;
 
-        // Discover tools and resources if supported
+        // Discover tools, resources and prompts if supported
         if init_result.capabilities.tools.is_some() {
             match self.list_tools_internal().await {
                 Ok(tools) => self.tools = tools,
@@ -288,6 +304,17 @@ impl MCPServerHandle {
             }
         }
 
+        if init_result.capabilities.prompts.is_some() {
+            match self.list_prompts_internal().await {
+                Ok(prompts) => self.prompts = prompts,
+                Err(e) => warn!(
+                    server_id = %self.config.id,
+                    error = %e,
+                    "Failed to list prompts during initialization"
+                ),
+            }
+        }
+
         self.status = MCPServerStatus::Running;
 
         info!(
@@ -296,6 +323,7 @@ impl MCPServerHandle {
             server_version = %init_result.server_info.version,
             tools_count = self.tools.len(),
             resources_count = self.resources.len(),
+            prompts_count = self.prompts.len(),
             "MCP session initialized"
         );
 
@@ -321,12 +349,150 @@ impl MCPServerHandle {
         &self.resources
     }
 
+    /// Lists available prompts from the server
+    ///
+    /// # Returns
+    ///
+    /// Returns the cached list of prompts discovered during initialization.
+    pub fn list_prompts(&self) -> &[MCPPrompt] {
+        &self.prompts
+    }
+
+    /// Sends a JSON-RPC `ping` request (liveness check)
+    pub async fn ping(&mut self) -> MCPResult<()> {
+        let request = JsonRpcRequest::new("ping", None, self.next_request_id());
+        let response = self.send_request(request).await?;
+
+        response
+            .into_result()
+            .map(|_| ())
+            .map_err(|e| MCPError::ProtocolError {
+                code: e.code,
+                message: e.message,
+            })
+    }
+
     /// Refreshes the tools list from the server
     pub async fn refresh_tools(&mut self) -> MCPResult<Vec<MCPTool>> {
         self.tools = self.list_tools_internal().await?;
         Ok(self.tools.clone())
     }
 
+    /// Refreshes the resources list from the server
+    pub async fn refresh_resources(&mut self) -> MCPResult<Vec<MCPResource>> {
+        self.resources = self.list_resources_internal().await?;
+        Ok(self.resources.clone())
+    }
+
+    /// Reads a resource from the server (`resources/read`)
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - URI of the resource (as listed by `resources/list`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ServerNotRunning` if the server is not running,
+    /// or `MCPError::ProtocolError` if the server rejects the request.
+    pub async fn read_resource(&mut self, uri: &str) -> MCPResult<MCPResourceReadResult> {
+        if self.status != MCPServerStatus::Running {
+            return Err(MCPError::ServerNotRunning {
+                server: self.config.name.clone(),
+                status: self.status.to_string(),
+            });
+        }
+
+        debug!(
+            server_id = %self.config.id,
+            uri = %uri,
+            "Reading MCP resource"
+        );
+
+        let params = MCPResourceReadParams {
+            uri: uri.to_string(),
+        };
+        let request = JsonRpcRequest::new(
+            "resources/read",
+            Some(serde_json::to_value(&params)?),
+            self.next_request_id(),
+        );
+
+        let response = self.send_request(request).await?;
+
+        let result = response
+            .into_result()
+            .map_err(|e| MCPError::ProtocolError {
+                code: e.code,
+                message: e.message,
+            })?;
+
+        if result.is_null() {
+            return Ok(MCPResourceReadResult::default());
+        }
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Refreshes the prompts list from the server
+    pub async fn refresh_prompts(&mut self) -> MCPResult<Vec<MCPPrompt>> {
+        self.prompts = self.list_prompts_internal().await?;
+        Ok(self.prompts.clone())
+    }
+
+    /// Renders a prompt on the server (`prompts/get`)
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the prompt (as listed by `prompts/list`)
+    /// * `arguments` - Argument values
+    ///
+    /// # Errors
+    ///
+    /// Returns `MCPError::ServerNotRunning` if the server is not running,
+    /// or `MCPError::ProtocolError` if the server rejects the request
+    /// (e.g. unknown prompt, missing required argument).
+    pub async fn get_prompt(
+        &mut self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> MCPResult<MCPPromptGetResult> {
+        if self.status != MCPServerStatus::Running {
+            return Err(MCPError::ServerNotRunning {
+                server: self.config.name.clone(),
+                status: self.status.to_string(),
+            });
+        }
+
+        debug!(
+            server_id = %self.config.id,
+            prompt = %name,
+            "Getting MCP prompt"
+        );
+
+        let params = MCPPromptGetParams {
+            name: name.to_string(),
+            arguments,
+        };
+        let request = JsonRpcRequest::new(
+            "prompts/get",
+            Some(serde_json::to_value(&params)?),
+            self.next_request_id(),
+        );
+
+        let response = self.send_request(request).await?;
+
+        let result = response
+            .into_result()
+            .map_err(|e| MCPError::ProtocolError {
+                code: e.code,
+                message: e.message,
+            })?;
+
+        if result.is_null() {
+            return Ok(MCPPromptGetResult::default());
+        }
+        Ok(serde_json::from_value(result)?)
+    }
+
     /// Internal method to fetch tools from server
     async fn list_tools_internal(&mut self) -> MCPResult<Vec<MCPTool>> {
         let request = JsonRpcRequest::new("tools/list", None, self.next_request_id());
@@ -369,6 +535,27 @@ impl MCPServerHandle {
             .collect())
     }
 
+    /// Internal method to fetch prompts from server
+    async fn list_prompts_internal(&mut self) -> MCPResult<Vec<MCPPrompt>> {
+        let request = JsonRpcRequest::new("prompts/list", None, self.next_request_id());
+        let response = self.send_request(request).await?;
+
+        let result = response
+            .into_result()
+            .map_err(|e| MCPError::ProtocolError {
+                code: e.code,
+                message: e.message,
+            })?;
+
+        let prompts_result: MCPPromptsListResult = serde_json::from_value(result)?;
+
+        Ok(prompts_result
+            .prompts
+            .into_iter()
+            .map(|p| self.convert_prompt_definition(p))
+            .collect())
+    }
+
     /// Converts a protocol tool definition to the model type
     fn convert_tool_definition(&self, def: MCPToolDefinition) -> MCPTool {
         MCPTool {
@@ -388,6 +575,23 @@ impl MCPServerHandle {
         }
     }
 
+    /// Converts a protocol prompt definition to the model type
+    fn convert_prompt_definition(&self, def: MCPPromptDefinition) -> MCPPrompt {
+        MCPPrompt {
+            name: def.name,
+            description: def.description,
+            arguments: def
+                .arguments
+                .into_iter()
+                .map(|arg| MCPPromptArgument {
+                    name: arg.name,
+                    description: arg.description,
+                    required: arg.required.unwrap_or(false),
+                })
+                .collect(),
+        }
+    }
+
     /// Calls a tool on the MCP server
     ///
     /// # Arguments
@@ -407,6 +611,23 @@ impl MCPServerHandle {
         &mut self,
         name: &str,
         arguments: serde_json::Value,
+    ) -> MCPResult<MCPToolCallResponse> {
+        self.call_tool_with_progress(name, arguments, None, None)
+            .await
+    }
+
+    /// Calls a tool, forwarding its progress notifications to `progress`
+    ///
+    /// The request ID is sent as `_meta.progressToken` when `progress` is set.
+    /// When `cancellation` fires before the response, the server is sent a
+    /// `notifications/cancelled` for the request (so it can stop the tool) and
+    /// `MCPError::Cancelled` is returned; a late response is discarded.
+    pub async fn call_tool_with_progress(
+        &mut self,
+        name: &str,
+        arguments: serde_json::Value,
+        progress: Option<ProgressSender>,
+        cancellation: Option<&CancellationToken>,
     ) -> MCPResult<MCPToolCallResponse> {
         // Verify server is running
         if self.status != MCPServerStatus::Running {
@@ -422,18 +643,46 @@ impl MCPServerHandle {
             "Calling MCP tool"
         );
 
+        let request_id = self.next_request_id();
+        let progress_token = progress.map(|sender| {
+            let token = serde_json::Value::from(request_id);
+            self.notifications.register_progress(&token, sender);
+            token
+        });
         let params = MCPToolCallParams {
             name: name.to_string(),
             arguments,
+            meta: progress_token
+                .as_ref()
+                .map(|token| serde_json::json!({"progressToken": token})),
         };
 
         let request = JsonRpcRequest::new(
             "tools/call",
             Some(serde_json::to_value(&params)?),
-            self.next_request_id(),
+            request_id,
         );
 
-        let response = self.send_request(request).await?;
+        let response = match cancellation {
+            Some(token) => {
+                let outcome = tokio::select! {
+                    biased;
+                    response = self.send_request(request) => Some(response),
+                    _ = token.cancelled() => None,
+                };
+                match outcome {
+                    Some(response) => response,
+                    None => Err(self
+                        .cancel_request(request_id, &format!("tools/call {}", name))
+                        .await),
+                }
+            }
+            None => self.send_request(request).await,
+        };
+        if let Some(token) = progress_token {
+            self.notifications.unregister_progress(&token);
+        }
+        let response = response?;
 
         let result = response
             .into_result()
@@ -477,23 +726,9 @@ impl MCPServerHandle {
 
     /// Sends a JSON-RPC request and waits for response with timeout.
     ///
-    /// The read operation uses `spawn_blocking` with a timeout to prevent
-    /// indefinite blocking if the MCP server doesn't respond.
+    /// Notifications received before the response are recorded, and server
+    /// requests answered, while waiting.
     async fn send_request(&mut self, request: JsonRpcRequest) -> MCPResult<JsonRpcResponse> {
-        let stdin = self.stdin.as_ref().ok_or(MCPError::ConnectionFailed {
-            server: self.config.name.clone(),
-            message: "Process stdin not available".to_string(),
-        })?;
-
-        let stdout_reader = self
-            .stdout_reader
-            .as_ref()
-            .ok_or(MCPError::ConnectionFailed {
-                server: self.config.name.clone(),
-                message: "Process stdout not available".to_string(),
-            })?
-            .clone();
-
         // Serialize request
         let mut request_json = serde_json::to_string(&request)?;
         request_json.push('\n');
@@ -505,28 +740,127 @@ impl MCPServerHandle {
             "Sending JSON-RPC request"
         );
 
-        // Write request to stdin
-        {
-            let mut stdin_guard = stdin.lock().await;
-            stdin_guard
-                .write_all(request_json.as_bytes())
-                .map_err(|e| MCPError::IoError {
-                    context: "writing request".to_string(),
-                    message: e.to_string(),
-                })?;
-            stdin_guard.flush().map_err(|e| MCPError::IoError {
-                context: "flushing stdin".to_string(),
+        self.write_line(&request_json, "writing request").await?;
+
+        // Server notifications and requests may precede the response
+        loop {
+            let line = self.read_line().await?;
+            let message: serde_json::Value = serde_json::from_str(&line)?;
+
+            if let Some(method) = message.get("method").and_then(|m| m.as_str()) {
+                match message.get("id").filter(|id| !id.is_null()) {
+                    Some(id) => {
+                        let mut reply = server_request_reply(id, method).to_string();
+                        reply.push('\n');
+                        self.write_line(&reply, "answering server request").await?;
+                    }
+                    None => {
+                        self.notifications
+                            .handle(&self.config.id, method, message.get("params"))
+                    }
+                }
+                continue;
+            }
+
+            debug!(
+                server_id = %self.config.id,
+                response_bytes = line.len(),
+                "Received JSON-RPC response"
+            );
+
+            let response: JsonRpcResponse = serde_json::from_value(message)?;
+
+            // Late response of a timed out or cancelled request
+            if response.id.as_ref().is_some_and(|id| *id != request.id) {
+                debug!(
+                    server_id = %self.config.id,
+                    id = ?response.id,
+                    "Discarding response of an abandoned request"
+                );
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+
+    /// Notifies the server that a request was cancelled.
+    ///
+    /// The server should stop processing it and not answer; a response sent
+    /// anyway is discarded by the next request.
+    ///
+    /// # Returns
+    /// The `MCPError::Cancelled` error of the request
+    async fn cancel_request(&mut self, request_id: i64, operation: &str) -> MCPError {
+        info!(
+            server_id = %self.config.id,
+            request_id,
+            operation = %operation,
+            "Cancelling MCP request"
+        );
+        let notification = JsonRpcRequest::notification(
+            "notifications/cancelled",
+            Some(serde_json::json!({
+                "requestId": request_id,
+                "reason": "Cancelled by the user"
+            })),
+        );
+        if let Err(e) = self.send_notification(notification).await {
+            warn!(
+                server_id = %self.config.id,
+                error = %e,
+                "Failed to notify the server of the cancellation"
+            );
+        }
+        MCPError::Cancelled {
+            server: self.config.name.clone(),
+            operation: operation.to_string(),
+        }
+    }
+
+    /// Writes a line to the process stdin.
+    async fn write_line(&self, line: &str, context: &str) -> MCPResult<()> {
+        let stdin = self.stdin.as_ref().ok_or(MCPError::ConnectionFailed {
+            server: self.config.name.clone(),
+            message: "Process stdin not available".to_string(),
+        })?;
+
+        let mut stdin_guard = stdin.lock().await;
+        stdin_guard
+            .write_all(line.as_bytes())
+            .map_err(|e| MCPError::IoError {
+                context: context.to_string(),
                 message: e.to_string(),
             })?;
-        }
+        stdin_guard.flush().map_err(|e| MCPError::IoError {
+            context: "flushing stdin".to_string(),
+            message: e.to_string(),
+        })
+    }
 
-        // Read response from stdout with timeout
-        // We use spawn_blocking because read_line is a blocking I/O operation
+    /// Reads the next message line from the process stdout with timeout.
+    ///
+    /// The read uses `spawn_blocking` because `read_line` is blocking I/O.
+    /// The timeout applies per message, so progress notifications keep
+    /// long-running requests alive.
+    ///
+    /// A read interrupted by the timeout or by a cancelled request keeps
+    /// running in `pending_read` and is resumed by the next call, so no line
+    /// is lost.
+    async fn read_line(&self) -> MCPResult<String> {
+        let stdout_reader = self
+            .stdout_reader
+            .as_ref()
+            .ok_or(MCPError::ConnectionFailed {
+                server: self.config.name.clone(),
+                message: "Process stdout not available".to_string(),
+            })?
+            .clone();
         let server_name = self.config.name.clone();
         let server_id = self.config.id.clone();
         let timeout_duration = Duration::from_millis(DEFAULT_TIMEOUT_MS);
 
-        let read_result = tokio::time::timeout(timeout_duration, async {
+        let mut pending_read = self.pending_read.lock().await;
+        let read = pending_read.get_or_insert_with(|| {
             tokio::task::spawn_blocking(move || {
                 let mut stdout_guard = stdout_reader.lock().map_err(|e| MCPError::IoError {
                     context: "locking stdout".to_string(),
@@ -541,16 +875,17 @@ impl MCPServerHandle {
                     })?;
                 Ok::<String, MCPError>(response_line)
             })
-            .await
-            .map_err(|e| MCPError::IoError {
-                context: "spawn_blocking join".to_string(),
-                message: e.to_string(),
-            })?
-        })
-        .await;
+        });
+        let read_result = tokio::time::timeout(timeout_duration, read).await;
 
         let response_line = match read_result {
-            Ok(inner_result) => inner_result?,
+            Ok(joined) => {
+                pending_read.take();
+                joined.map_err(|e| MCPError::IoError {
+                    context: "spawn_blocking join".to_string(),
+                    message: e.to_string(),
+                })??
+            }
             Err(_) => {
                 warn!(
                     server_id = %server_id,
@@ -571,23 +906,11 @@ impl MCPServerHandle {
             });
         }
 
-        debug!(
-            server_id = %self.config.id,
-            response_bytes = response_line.len(),
-            "Received JSON-RPC response"
-        );
-
-        let response: JsonRpcResponse = serde_json::from_str(&response_line)?;
-        Ok(response)
+        Ok(response_line)
     }
 
     /// Sends a JSON-RPC notification (no response expected)
     async fn send_notification(&mut self, notification: JsonRpcRequest) -> MCPResult<()> {
-        let stdin = self.stdin.as_ref().ok_or(MCPError::ConnectionFailed {
-            server: self.config.name.clone(),
-            message: "Process stdin not available".to_string(),
-        })?;
-
         let mut request_json = serde_json::to_string(&notification)?;
         request_json.push('\n');
 
@@ -597,19 +920,17 @@ impl MCPServerHandle {
             "Sending JSON-RPC notification"
         );
 
-        let mut stdin_guard = stdin.lock().await;
-        stdin_guard
-            .write_all(request_json.as_bytes())
-            .map_err(|e| MCPError::IoError {
-                context: "writing notification".to_string(),
-                message: e.to_string(),
-            })?;
-        stdin_guard.flush().map_err(|e| MCPError::IoError {
-            context: "flushing stdin".to_string(),
-            message: e.to_string(),
-        })?;
+        self.write_line(&request_json, "writing notification").await
+    }
 
-        Ok(())
+    /// Returns and resets the `notifications/tools/list_changed` flag
+    pub fn take_tools_changed(&self) -> bool {
+        self.notifications.take_tools_changed()
+    }
+
+    /// Whether a `notifications/tools/list_changed` is pending
+    pub fn tools_changed(&self) -> bool {
+        self.notifications.tools_changed()
     }
 
     /// Gets the next request ID
@@ -655,6 +976,7 @@ impl MCPServerHandle {
         self.status = MCPServerStatus::Stopped;
         self.tools.clear();
         self.resources.clear();
+        self.prompts.clear();
 
         Ok(())
     }
@@ -836,11 +1158,14 @@ mod tests {
             child: None,
             stdin: None,
             stdout_reader: None,
+            pending_read: Mutex::new(None),
             status: MCPServerStatus::Stopped,
             tools: Vec::new(),
             resources: Vec::new(),
+            prompts: Vec::new(),
             request_id: AtomicI64::new(1),
             server_info: None,
+            notifications: NotificationState::default(),
         };
 
         assert_eq!(handle.next_request_id(), 1);
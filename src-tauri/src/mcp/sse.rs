@@ -0,0 +1,155 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-Sent Events parser
+//!
+//! Incremental parser for `text/event-stream` bodies used by the MCP HTTP
+//! transports. Bytes are fed as they arrive from the network; complete
+//! events are returned once their terminating blank line is received.
+
+/// Single Server-Sent Event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// Event type (`message` when not specified)
+    pub event: String,
+    /// Event data (multiple `data:` lines joined with `\n`)
+    pub data: String,
+    /// Event ID (used as `Last-Event-ID` when resuming a stream)
+    pub id: Option<String>,
+    /// Reconnection delay requested by the server, in milliseconds
+    pub retry: Option<u64>,
+}
+
+/// Incremental Server-Sent Events parser
+#[derive(Debug, Default)]
+pub struct SseParser {
+    /// Bytes of the current incomplete line
+    buffer: Vec<u8>,
+    /// Event type of the event being built
+    event: Option<String>,
+    /// Data lines of the event being built
+    data: Vec<String>,
+    /// ID of the event being built
+    id: Option<String>,
+    /// Retry delay of the event being built
+    retry: Option<u64>,
+}
+
+impl SseParser {
+    /// Feeds a chunk of the stream and returns the events it completes.
+    ///
+    /// Events without data are still returned when they carry an `id` or
+    /// `retry` field, so callers can track the last event ID.
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
+            let mut line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            let line = String::from_utf8_lossy(&line);
+            if let Some(event) = self.process_line(&line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Processes a complete line, returning an event on blank lines.
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // Comment (often used as keep-alive)
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            "retry" => self.retry = value.parse().ok(),
+            _ => {}
+        }
+        None
+    }
+
+    /// Builds the pending event and resets the parser state.
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = SseEvent {
+            event: self.event.take().unwrap_or_else(|| "message".to_string()),
+            data: std::mem::take(&mut self.data).join("\n"),
+            id: self.id.take(),
+            retry: self.retry.take(),
+        };
+        if event.data.is_empty() && event.id.is_none() && event.retry.is_none() {
+            None
+        } else {
+            Some(event)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_event() {
+        let mut parser = SseParser::default();
+        let events = parser.feed(b"id: 7\nevent: message\ndata: {\"a\":1}\n\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: "message".to_string(),
+                data: "{\"a\":1}".to_string(),
+                id: Some("7".to_string()),
+                retry: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_split_chunks_and_crlf() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b"event: endpo").is_empty());
+        assert!(parser
+            .feed(b"int\r\ndata: /messages?session=1\r\n")
+            .is_empty());
+        let events = parser.feed(b"\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "endpoint");
+        assert_eq!(events[0].data, "/messages?session=1");
+    }
+
+    #[test]
+    fn test_parse_multiline_data_comments_and_retry() {
+        let mut parser = SseParser::default();
+        let events = parser.feed(b": keep-alive\n\ndata: line1\ndata:line2\n\nretry: 1500\n\n");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, "message");
+        assert_eq!(events[0].data, "line1\nline2");
+        assert_eq!(events[1].data, "");
+        assert_eq!(events[1].retry, Some(1500));
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mock HTTP server for MCP transport tests
+//!
+//! Minimal HTTP/1.1 server answering each request through a handler
+//! closure, with support for long-lived SSE streams.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// Request received by the mock server
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// HTTP method
+    pub method: String,
+    /// Path and query
+    pub path: String,
+    /// Headers (lowercase names)
+    pub headers: HashMap<String, String>,
+    /// Raw body
+    pub raw_body: String,
+    /// Body parsed as JSON (Null if not JSON)
+    pub body: Value,
+}
+
+impl MockRequest {
+    /// JSON-RPC method of the body (empty if none)
+    pub fn rpc_method(&self) -> &str {
+        self.body["method"].as_str().unwrap_or_default()
+    }
+
+    /// Header value by lowercase name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|v| v.as_str())
+    }
+
+    /// Body parsed as `application/x-www-form-urlencoded`
+    pub fn form(&self) -> HashMap<String, String> {
+        reqwest::Url::parse(&format!("http://localhost/?{}", self.raw_body))
+            .map(|url| url.query_pairs().into_owned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Reply of the mock server
+pub enum MockReply {
+    /// Complete response
+    Full(String),
+    /// SSE stream written chunk by chunk until the sender is dropped
+    Stream(mpsc::UnboundedReceiver<String>),
+}
+
+/// Complete response with a Content-Length body
+pub fn http(status: &str, headers: &[(&str, &str)], body: &str) -> MockReply {
+    let mut response = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+    MockReply::Full(response)
+}
+
+/// 200 response with a JSON body
+pub fn json_reply(headers: &[(&str, &str)], body: Value) -> MockReply {
+    let mut all = vec![("Content-Type", "application/json")];
+    all.extend_from_slice(headers);
+    http("200 OK", &all, &body.to_string())
+}
+
+/// 200 response with a complete SSE body
+pub fn sse_reply(events: &str) -> MockReply {
+    http("200 OK", &[("Content-Type", "text/event-stream")], events)
+}
+
+/// 202 response without body
+pub fn accepted() -> MockReply {
+    http("202 Accepted", &[], "")
+}
+
+/// Starts a mock HTTP server and returns its base URL.
+pub async fn spawn_mock_server<F>(handler: F) -> String
+where
+    F: Fn(MockRequest) -> MockReply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handler = Arc::new(handler);
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let handler = handler.clone();
+            tokio::spawn(async move { serve_connection(socket, handler.as_ref()).await });
+        }
+    });
+    format!("http://{}", addr)
+}
+
+async fn serve_connection<F>(mut socket: TcpStream, handler: &F)
+where
+    F: Fn(MockRequest) -> MockReply,
+{
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + length {
+        match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+        }
+    }
+    let raw_body = String::from_utf8_lossy(&buffer[header_end..header_end + length]).to_string();
+    let body = serde_json::from_str(&raw_body).unwrap_or(Value::Null);
+
+    match handler(MockRequest {
+        method,
+        path,
+        headers,
+        raw_body,
+        body,
+    }) {
+        MockReply::Full(response) => {
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+        MockReply::Stream(mut rx) => {
+            let head =
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n";
+            if socket.write_all(head.as_bytes()).await.is_err() {
+                return;
+            }
+            while let Some(event) = rx.recv().await {
+                if socket.write_all(event.as_bytes()).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+    let _ = socket.shutdown().await;
+}
@@ -0,0 +1,302 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics exporters.
+//!
+//! - Prometheus: `GET /metrics` on `127.0.0.1:<port>` (text exposition format)
+//! - OTLP: periodic push of the cumulative metrics to an OTLP/HTTP collector
+//!   (JSON encoding)
+//!
+//! [`apply_settings`] (re)starts the exporters enabled by the settings and
+//! stops the others.
+
+use super::METRICS_REGISTRY;
+use crate::models::metrics::{MetricsExportStatus, MetricsSettings};
+use crate::tools::constants::metrics::{
+    MAX_OTLP_INTERVAL_SECS, MAX_REQUEST_HEAD_BYTES, MIN_OTLP_INTERVAL_SECS, OTLP_TIMEOUT_SECS,
+    REQUEST_READ_TIMEOUT_SECS,
+};
+use once_cell::sync::Lazy;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Running exporter
+struct Exporter {
+    /// Settings it was started with
+    settings: MetricsSettings,
+    cancel: CancellationToken,
+}
+
+/// Running exporters
+#[derive(Default)]
+struct Exporters {
+    prometheus: Option<(Exporter, String)>,
+    otlp: Option<Exporter>,
+}
+
+static EXPORTERS: Lazy<Mutex<Exporters>> = Lazy::new(|| Mutex::new(Exporters::default()));
+
+/// Validates the metrics export settings.
+pub fn validate_settings(settings: &MetricsSettings) -> Result<(), String> {
+    if settings.prometheus_port == 0 {
+        return Err("Prometheus port must be between 1 and 65535".to_string());
+    }
+    if !(MIN_OTLP_INTERVAL_SECS..=MAX_OTLP_INTERVAL_SECS).contains(&settings.otlp_interval_secs) {
+        return Err(format!(
+            "OTLP push interval must be between {} and {} seconds",
+            MIN_OTLP_INTERVAL_SECS, MAX_OTLP_INTERVAL_SECS
+        ));
+    }
+    let endpoint = reqwest::Url::parse(&settings.otlp_endpoint)
+        .map_err(|e| format!("Invalid OTLP endpoint: {}", e))?;
+    if !matches!(endpoint.scheme(), "http" | "https") {
+        return Err("OTLP endpoint must be an http or https URL".to_string());
+    }
+    Ok(())
+}
+
+/// Starts the exporters enabled by the settings and stops the others.
+///
+/// An exporter whose settings did not change keeps running.
+pub async fn apply_settings(settings: &MetricsSettings) -> Result<MetricsExportStatus, String> {
+    let mut exporters = EXPORTERS.lock().await;
+
+    let prometheus_unchanged = exporters.prometheus.as_ref().is_some_and(|(running, _)| {
+        settings.prometheus_enabled && running.settings.prometheus_port == settings.prometheus_port
+    });
+    if !prometheus_unchanged {
+        if let Some((previous, _)) = exporters.prometheus.take() {
+            previous.cancel.cancel();
+            info!("Prometheus metrics endpoint stopped");
+        }
+        if settings.prometheus_enabled {
+            let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.prometheus_port));
+            let listener = TcpListener::bind(addr)
+                .await
+                .map_err(|e| format!("Failed to serve metrics on {}: {}", addr, e))?;
+            let url = format!("http://{}/metrics", listener.local_addr().unwrap_or(addr));
+            let cancel = CancellationToken::new();
+            tokio::spawn(run_prometheus_endpoint(listener, cancel.clone()));
+            info!(url = %url, "Prometheus metrics endpoint started");
+            exporters.prometheus = Some((
+                Exporter {
+                    settings: settings.clone(),
+                    cancel,
+                },
+                url,
+            ));
+        }
+    }
+
+    let otlp_unchanged = exporters.otlp.as_ref().is_some_and(|running| {
+        settings.otlp_enabled
+            && running.settings.otlp_endpoint == settings.otlp_endpoint
+            && running.settings.otlp_interval_secs == settings.otlp_interval_secs
+    });
+    if !otlp_unchanged {
+        if let Some(previous) = exporters.otlp.take() {
+            previous.cancel.cancel();
+            info!("OTLP metrics exporter stopped");
+        }
+        if settings.otlp_enabled {
+            let client = reqwest::Client::builder()
+                .timeout(Duration::from_secs(OTLP_TIMEOUT_SECS))
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            let cancel = CancellationToken::new();
+            tokio::spawn(run_otlp_exporter(
+                client,
+                settings.otlp_endpoint.clone(),
+                Duration::from_secs(settings.otlp_interval_secs),
+                cancel.clone(),
+            ));
+            info!(endpoint = %settings.otlp_endpoint, "OTLP metrics exporter started");
+            exporters.otlp = Some(Exporter {
+                settings: settings.clone(),
+                cancel,
+            });
+        }
+    }
+
+    Ok(status_of(&exporters))
+}
+
+/// Running exporters.
+pub async fn export_status() -> MetricsExportStatus {
+    status_of(&*EXPORTERS.lock().await)
+}
+
+fn status_of(exporters: &Exporters) -> MetricsExportStatus {
+    MetricsExportStatus {
+        prometheus_url: exporters.prometheus.as_ref().map(|(_, url)| url.clone()),
+        otlp_active: exporters.otlp.is_some(),
+    }
+}
+
+async fn run_prometheus_endpoint(listener: TcpListener, cancel: CancellationToken) {
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(handle_metrics_request(stream));
+                }
+                Err(e) => warn!(error = %e, "Metrics endpoint accept failed"),
+            },
+        }
+    }
+}
+
+/// Answers a single request: the metrics for `GET`/`HEAD /metrics`, 404 otherwise.
+async fn handle_metrics_request(mut stream: TcpStream) {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    let read = tokio::time::timeout(Duration::from_secs(REQUEST_READ_TIMEOUT_SECS), async {
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            if head.len() > MAX_REQUEST_HEAD_BYTES {
+                return false;
+            }
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return false,
+                Ok(n) => head.extend_from_slice(&buf[..n]),
+            }
+        }
+        true
+    })
+    .await;
+    if !matches!(read, Ok(true)) {
+        return;
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default();
+
+    let (status, content_type, body) = match method {
+        "GET" | "HEAD" if path == "/metrics" => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            METRICS_REGISTRY.render_prometheus(),
+        ),
+        "GET" | "HEAD" => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "Not Found".to_string(),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "Method Not Allowed".to_string(),
+        ),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        debug!(error = %e, "Failed to write metrics response");
+    }
+    let _ = stream.shutdown().await;
+}
+
+async fn run_otlp_exporter(
+    client: reqwest::Client,
+    endpoint: String,
+    interval: Duration,
+    cancel: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately: wait one interval before the first push
+    ticker.tick().await;
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = ticker.tick() => {
+                let body = METRICS_REGISTRY.to_otlp_json();
+                match client.post(&endpoint).json(&body).send().await {
+                    Ok(response) if response.status().is_success() => {
+                        debug!("Metrics pushed to the OTLP collector");
+                    }
+                    Ok(response) => {
+                        warn!(status = %response.status(), "OTLP collector rejected the metrics");
+                    }
+                    Err(e) => warn!(error = %e, "Failed to push metrics to the OTLP collector"),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_settings() {
+        let mut settings = MetricsSettings::default();
+        assert!(validate_settings(&settings).is_ok());
+
+        settings.otlp_interval_secs = 1;
+        assert!(validate_settings(&settings).is_err());
+
+        settings = MetricsSettings {
+            otlp_endpoint: "grpc://127.0.0.1:4317".to_string(),
+            ..Default::default()
+        };
+        assert!(validate_settings(&settings).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prometheus_endpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let cancel = CancellationToken::new();
+        tokio::spawn(run_prometheus_endpoint(listener, cancel.clone()));
+        crate::metrics::record_mcp_error("endpoint-test", "timeout");
+
+        let request = |path: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let metrics = request("/metrics").await;
+        assert!(metrics.starts_with("HTTP/1.1 200 OK"));
+        assert!(metrics
+            .contains("zileo_mcp_errors_total{server=\"endpoint-test\",category=\"timeout\"} 1"));
+        assert!(request("/other").await.starts_with("HTTP/1.1 404"));
+        cancel.cancel();
+    }
+}
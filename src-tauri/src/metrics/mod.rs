@@ -0,0 +1,494 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Metrics
+//!
+//! In-memory counters and histograms of the app, for power users monitoring
+//! it like a service:
+//!
+//! - `zileo_workflow_executions_total{trigger, status}` - finished workflow
+//!   executions (interactive, resumed, scheduled or background job)
+//! - `zileo_tool_calls_total{tool, success}` - tool calls made by agents
+//! - `zileo_llm_request_duration_seconds{provider, success}` - LLM request
+//!   latency (retries included)
+//! - `zileo_llm_tokens_total{provider, direction}` - input and output tokens
+//! - `zileo_mcp_errors_total{server, category}` - failed MCP tool calls
+//!
+//! Metrics are counted from process start, whatever the export settings;
+//! [`export`] serves them on a local Prometheus endpoint and/or pushes them to
+//! an OTLP/HTTP collector.
+
+pub mod export;
+
+use crate::llm::ProviderType;
+use crate::models::streaming::CompletionStatus;
+use crate::tools::constants::metrics::LLM_LATENCY_BUCKETS_SECS;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Kind of a metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricKind {
+    Counter,
+    Histogram,
+}
+
+/// Metric definition
+struct MetricDef {
+    name: &'static str,
+    help: &'static str,
+    kind: MetricKind,
+}
+
+const WORKFLOW_EXECUTIONS: MetricDef = MetricDef {
+    name: "zileo_workflow_executions_total",
+    help: "Finished workflow executions",
+    kind: MetricKind::Counter,
+};
+const TOOL_CALLS: MetricDef = MetricDef {
+    name: "zileo_tool_calls_total",
+    help: "Tool calls made by agents",
+    kind: MetricKind::Counter,
+};
+const LLM_REQUEST_DURATION: MetricDef = MetricDef {
+    name: "zileo_llm_request_duration_seconds",
+    help: "LLM request latency, retries included",
+    kind: MetricKind::Histogram,
+};
+const LLM_TOKENS: MetricDef = MetricDef {
+    name: "zileo_llm_tokens_total",
+    help: "LLM tokens consumed (input) and generated (output)",
+    kind: MetricKind::Counter,
+};
+const MCP_ERRORS: MetricDef = MetricDef {
+    name: "zileo_mcp_errors_total",
+    help: "Failed MCP tool calls",
+    kind: MetricKind::Counter,
+};
+
+/// All metrics, in exposition order
+const METRICS: [MetricDef; 5] = [
+    WORKFLOW_EXECUTIONS,
+    TOOL_CALLS,
+    LLM_REQUEST_DURATION,
+    LLM_TOKENS,
+    MCP_ERRORS,
+];
+
+/// Label pairs of a series, in definition order
+type Labels = Vec<(&'static str, String)>;
+
+/// Histogram series
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations per bucket of `LLM_LATENCY_BUCKETS_SECS` (not cumulative),
+    /// plus the overflow bucket
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LLM_LATENCY_BUCKETS_SECS.len() + 1];
+        }
+        let index = LLM_LATENCY_BUCKETS_SECS
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(LLM_LATENCY_BUCKETS_SECS.len());
+        self.buckets[index] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Series of all metrics
+#[derive(Debug, Default)]
+struct Series {
+    counters: BTreeMap<(&'static str, Labels), u64>,
+    histograms: BTreeMap<(&'static str, Labels), Histogram>,
+}
+
+/// Metrics registry
+pub struct MetricsRegistry {
+    /// Time the counting started (nanoseconds since the Unix epoch)
+    started_at_nanos: u128,
+    series: Mutex<Series>,
+}
+
+impl MetricsRegistry {
+    fn new() -> Self {
+        Self {
+            started_at_nanos: unix_nanos(),
+            series: Mutex::new(Series::default()),
+        }
+    }
+
+    fn add(&self, metric: &MetricDef, labels: Labels, value: u64) {
+        if let Ok(mut series) = self.series.lock() {
+            *series.counters.entry((metric.name, labels)).or_default() += value;
+        }
+    }
+
+    fn observe(&self, metric: &MetricDef, labels: Labels, value: f64) {
+        if let Ok(mut series) = self.series.lock() {
+            series
+                .histograms
+                .entry((metric.name, labels))
+                .or_default()
+                .observe(value);
+        }
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    pub fn render_prometheus(&self) -> String {
+        let Ok(series) = self.series.lock() else {
+            return String::new();
+        };
+        let mut out = String::new();
+        for metric in &METRICS {
+            let kind = match metric.kind {
+                MetricKind::Counter => "counter",
+                MetricKind::Histogram => "histogram",
+            };
+            out.push_str(&format!("# HELP {} {}\n", metric.name, metric.help));
+            out.push_str(&format!("# TYPE {} {}\n", metric.name, kind));
+            for ((_, labels), value) in series.counters.iter().filter(|(k, _)| k.0 == metric.name) {
+                out.push_str(&format!(
+                    "{}{} {}\n",
+                    metric.name,
+                    prometheus_labels(labels, None),
+                    value
+                ));
+            }
+            for ((_, labels), histogram) in
+                series.histograms.iter().filter(|(k, _)| k.0 == metric.name)
+            {
+                let mut cumulative = 0;
+                for (bound, count) in LLM_LATENCY_BUCKETS_SECS.iter().zip(&histogram.buckets) {
+                    cumulative += count;
+                    out.push_str(&format!(
+                        "{}_bucket{} {}\n",
+                        metric.name,
+                        prometheus_labels(labels, Some(&bound.to_string())),
+                        cumulative
+                    ));
+                }
+                out.push_str(&format!(
+                    "{}_bucket{} {}\n",
+                    metric.name,
+                    prometheus_labels(labels, Some("+Inf")),
+                    histogram.count
+                ));
+                out.push_str(&format!(
+                    "{}_sum{} {}\n",
+                    metric.name,
+                    prometheus_labels(labels, None),
+                    histogram.sum
+                ));
+                out.push_str(&format!(
+                    "{}_count{} {}\n",
+                    metric.name,
+                    prometheus_labels(labels, None),
+                    histogram.count
+                ));
+            }
+        }
+        out
+    }
+
+    /// Builds an OTLP/HTTP JSON export request of the metrics (cumulative
+    /// temporality, see the OpenTelemetry `ExportMetricsServiceRequest`).
+    pub fn to_otlp_json(&self) -> Value {
+        let Ok(series) = self.series.lock() else {
+            return json!({ "resourceMetrics": [] });
+        };
+        let start = self.started_at_nanos.to_string();
+        let now = unix_nanos().to_string();
+        let attributes = |labels: &Labels| -> Vec<Value> {
+            labels
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+                .collect()
+        };
+
+        let metrics: Vec<Value> = METRICS
+            .iter()
+            .filter_map(|metric| {
+                let data = match metric.kind {
+                    MetricKind::Counter => {
+                        let points: Vec<Value> = series
+                            .counters
+                            .iter()
+                            .filter(|(k, _)| k.0 == metric.name)
+                            .map(|((_, labels), value)| {
+                                json!({
+                                    "attributes": attributes(labels),
+                                    "startTimeUnixNano": start,
+                                    "timeUnixNano": now,
+                                    "asInt": value.to_string(),
+                                })
+                            })
+                            .collect();
+                        if points.is_empty() {
+                            return None;
+                        }
+                        json!({ "sum": {
+                            "aggregationTemporality": 2,
+                            "isMonotonic": true,
+                            "dataPoints": points,
+                        }})
+                    }
+                    MetricKind::Histogram => {
+                        let points: Vec<Value> = series
+                            .histograms
+                            .iter()
+                            .filter(|(k, _)| k.0 == metric.name)
+                            .map(|((_, labels), histogram)| {
+                                json!({
+                                    "attributes": attributes(labels),
+                                    "startTimeUnixNano": start,
+                                    "timeUnixNano": now,
+                                    "count": histogram.count.to_string(),
+                                    "sum": histogram.sum,
+                                    "bucketCounts": histogram
+                                        .buckets
+                                        .iter()
+                                        .map(|c| c.to_string())
+                                        .collect::<Vec<_>>(),
+                                    "explicitBounds": LLM_LATENCY_BUCKETS_SECS,
+                                })
+                            })
+                            .collect();
+                        if points.is_empty() {
+                            return None;
+                        }
+                        json!({ "histogram": {
+                            "aggregationTemporality": 2,
+                            "dataPoints": points,
+                        }})
+                    }
+                };
+                let mut metric_json = json!({
+                    "name": metric.name,
+                    "description": metric.help,
+                });
+                if let (Some(target), Some(source)) =
+                    (metric_json.as_object_mut(), data.as_object())
+                {
+                    target.extend(source.clone());
+                }
+                Some(metric_json)
+            })
+            .collect();
+
+        json!({
+            "resourceMetrics": [{
+                "resource": { "attributes": [
+                    { "key": "service.name", "value": { "stringValue": "zileo-chat" } },
+                    { "key": "service.version", "value": { "stringValue": env!("CARGO_PKG_VERSION") } },
+                ]},
+                "scopeMetrics": [{
+                    "scope": { "name": "zileo_chat" },
+                    "metrics": metrics,
+                }],
+            }]
+        })
+    }
+}
+
+/// Metrics of the process
+pub static METRICS_REGISTRY: Lazy<MetricsRegistry> = Lazy::new(MetricsRegistry::new);
+
+/// Counts a finished workflow execution.
+///
+/// # Arguments
+/// * `trigger` - "interactive", "resume", "schedule" or "job"
+/// * `status` - Final status of the execution
+pub fn record_workflow_execution(trigger: &'static str, status: &CompletionStatus) {
+    let status = match status {
+        CompletionStatus::Completed => "completed",
+        CompletionStatus::Error => "error",
+        CompletionStatus::Cancelled => "cancelled",
+    };
+    METRICS_REGISTRY.add(
+        &WORKFLOW_EXECUTIONS,
+        vec![
+            ("trigger", trigger.to_string()),
+            ("status", status.to_string()),
+        ],
+        1,
+    );
+}
+
+/// Counts a tool call made by an agent.
+pub fn record_tool_call(tool: &str, success: bool) {
+    METRICS_REGISTRY.add(
+        &TOOL_CALLS,
+        vec![("tool", tool.to_string()), ("success", success.to_string())],
+        1,
+    );
+}
+
+/// Records the latency of an LLM request.
+pub fn observe_llm_request(provider: &ProviderType, duration: Duration, success: bool) {
+    METRICS_REGISTRY.observe(
+        &LLM_REQUEST_DURATION,
+        vec![
+            ("provider", provider_label(provider)),
+            ("success", success.to_string()),
+        ],
+        duration.as_secs_f64(),
+    );
+}
+
+/// Counts the tokens of an LLM response.
+pub fn record_llm_tokens(provider: &ProviderType, input: usize, output: usize) {
+    let provider = provider_label(provider);
+    for (direction, tokens) in [("input", input), ("output", output)] {
+        if tokens > 0 {
+            METRICS_REGISTRY.add(
+                &LLM_TOKENS,
+                vec![
+                    ("provider", provider.clone()),
+                    ("direction", direction.to_string()),
+                ],
+                tokens as u64,
+            );
+        }
+    }
+}
+
+/// Counts a failed MCP tool call.
+///
+/// # Arguments
+/// * `server` - MCP server name
+/// * `category` - Error category ("connection", "timeout", "protocol"...)
+pub fn record_mcp_error(server: &str, category: &str) {
+    METRICS_REGISTRY.add(
+        &MCP_ERRORS,
+        vec![
+            ("server", server.to_string()),
+            ("category", category.to_string()),
+        ],
+        1,
+    );
+}
+
+fn provider_label(provider: &ProviderType) -> String {
+    provider.to_string().to_lowercase()
+}
+
+/// Label set in the Prometheus format, with an optional `le` label.
+fn prometheus_labels(labels: &Labels, le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn unix_nanos() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_prometheus() {
+        let registry = MetricsRegistry::new();
+        registry.add(
+            &TOOL_CALLS,
+            vec![
+                ("tool", "say \"hi\"".to_string()),
+                ("success", "true".to_string()),
+            ],
+            2,
+        );
+        let labels = vec![("provider", "mistral".to_string())];
+        registry.observe(&LLM_REQUEST_DURATION, labels.clone(), 0.3);
+        registry.observe(&LLM_REQUEST_DURATION, labels, 500.0);
+
+        let text = registry.render_prometheus();
+        assert!(text.contains("# TYPE zileo_tool_calls_total counter\n"));
+        assert!(
+            text.contains("zileo_tool_calls_total{tool=\"say \\\"hi\\\"\",success=\"true\"} 2\n")
+        );
+        assert!(text.contains(
+            "zileo_llm_request_duration_seconds_bucket{provider=\"mistral\",le=\"0.25\"} 0\n"
+        ));
+        assert!(text.contains(
+            "zileo_llm_request_duration_seconds_bucket{provider=\"mistral\",le=\"0.5\"} 1\n"
+        ));
+        assert!(text.contains(
+            "zileo_llm_request_duration_seconds_bucket{provider=\"mistral\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains("zileo_llm_request_duration_seconds_count{provider=\"mistral\"} 2\n"));
+        assert!(text.contains("# TYPE zileo_mcp_errors_total counter\n"));
+    }
+
+    #[test]
+    fn test_otlp_json() {
+        let registry = MetricsRegistry::new();
+        registry.add(&MCP_ERRORS, vec![("server", "github".to_string())], 1);
+        registry.observe(
+            &LLM_REQUEST_DURATION,
+            vec![("provider", "ollama".to_string())],
+            1.5,
+        );
+
+        let export = registry.to_otlp_json();
+        let metrics = &export["resourceMetrics"][0]["scopeMetrics"][0]["metrics"];
+        assert_eq!(metrics.as_array().unwrap().len(), 2);
+        let histogram = &metrics[0];
+        assert_eq!(histogram["name"], "zileo_llm_request_duration_seconds");
+        let point = &histogram["histogram"]["dataPoints"][0];
+        assert_eq!(point["count"], "1");
+        assert_eq!(
+            point["bucketCounts"].as_array().unwrap().len(),
+            LLM_LATENCY_BUCKETS_SECS.len() + 1
+        );
+        assert_eq!(point["bucketCounts"][3], "1");
+        let counter = &metrics[1];
+        assert_eq!(counter["sum"]["isMonotonic"], true);
+        assert_eq!(counter["sum"]["dataPoints"][0]["asInt"], "1");
+        assert_eq!(
+            counter["sum"]["dataPoints"][0]["attributes"][0]["value"]["stringValue"],
+            "github"
+        );
+    }
+}
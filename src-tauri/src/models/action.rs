@@ -0,0 +1,225 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Command palette action models.
+//!
+//! The backend owns the registry of invocable actions (create an agent, start
+//! a workflow from a prompt template, open a settings section...) so the
+//! frontend command palette always reflects the features that exist.
+//! Synchronized with src/types/action.ts
+
+use serde::{Deserialize, Serialize};
+
+/// Group an action belongs to in the palette
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionCategory {
+    /// Creates a new entity (agent, prompt, MCP server...)
+    Create,
+    /// Starts a workflow
+    Workflow,
+    /// Opens a page or settings section
+    Navigate,
+}
+
+/// Invocable action listed in the command palette
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaletteAction {
+    /// Stable identifier (e.g. `agent.create`, `settings.open:mcp`)
+    pub id: String,
+    /// Palette group
+    pub category: ActionCategory,
+    /// Displayed title (English fallback)
+    pub title: String,
+    /// i18n key of the title, for static actions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_key: Option<String>,
+    /// Short description
+    pub description: String,
+    /// Additional search terms
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Frontend route to open
+    pub route: String,
+    /// Parameters passed to the page (e.g. `{"prompt_id": "..."}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<serde_json::Value>,
+}
+
+/// Action matching a palette query
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActionMatch {
+    /// Matched action
+    pub action: PaletteAction,
+    /// Relevance score (higher is better, 0 for an empty query)
+    pub score: u32,
+}
+
+/// Scores `query` as a fuzzy (subsequence) match of `candidate`.
+///
+/// Every query character must appear in order in the candidate. Matches at
+/// word starts and consecutive matches score higher, and a literal substring
+/// or prefix match gets a bonus. Returns `None` when the query does not match.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query = query.to_lowercase();
+    let candidate = candidate.to_lowercase();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let query_chars: Vec<char> = query.chars().collect();
+    let mut score = (0..chars.len())
+        .filter(|&start| chars[start] == query_chars[0])
+        .filter_map(|start| subsequence_score(&query_chars, &chars, start))
+        .max()?;
+
+    if candidate.starts_with(&query) {
+        score += 15;
+    } else if candidate.contains(&query) {
+        score += 10;
+    }
+
+    Some(score)
+}
+
+/// Scores a greedy subsequence match of `query` in `chars` starting at `start`.
+fn subsequence_score(query: &[char], chars: &[char], start: usize) -> Option<u32> {
+    let mut score = 0;
+    let mut position = start;
+    let mut previous_match: Option<usize> = None;
+
+    for &q in query {
+        let index = (position..chars.len()).find(|&i| chars[i] == q)?;
+        score += 1;
+        if index == 0 || !chars[index - 1].is_alphanumeric() {
+            score += 8;
+        }
+        if previous_match.is_some_and(|p| p + 1 == index) {
+            score += 5;
+        }
+        previous_match = Some(index);
+        position = index + 1;
+    }
+    Some(score)
+}
+
+/// Scores an action against a query.
+///
+/// Each whitespace-separated query term must match the title, a keyword or
+/// the description; the best field score of every term is summed. Description
+/// matches count for half.
+pub fn score_action(action: &PaletteAction, query: &str) -> Option<u32> {
+    let mut total = 0;
+    for term in query.split_whitespace() {
+        let title = fuzzy_score(term, &action.title);
+        let keyword = action
+            .keywords
+            .iter()
+            .filter_map(|k| fuzzy_score(term, k))
+            .max();
+        let description = fuzzy_score(term, &action.description).map(|s| s / 2);
+        total += [title, keyword, description].into_iter().flatten().max()?;
+    }
+    Some(total)
+}
+
+/// Ranks actions by relevance to `query`, keeping at most `limit` results.
+///
+/// An empty query returns the actions in registry order. Ties go to the
+/// shorter title, then to registry order.
+pub fn rank_actions(actions: Vec<PaletteAction>, query: &str, limit: usize) -> Vec<ActionMatch> {
+    let mut matches: Vec<ActionMatch> = actions
+        .into_iter()
+        .filter_map(|action| {
+            score_action(&action, query).map(|score| ActionMatch { action, score })
+        })
+        .collect();
+    if !query.trim().is_empty() {
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.action.title.len().cmp(&b.action.title.len()))
+        });
+    }
+    matches.truncate(limit);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn action(id: &str, title: &str, keywords: &[&str]) -> PaletteAction {
+        PaletteAction {
+            id: id.to_string(),
+            category: ActionCategory::Navigate,
+            title: title.to_string(),
+            label_key: None,
+            description: String::new(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            route: "/".to_string(),
+            params: None,
+        }
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "Create agent"), Some(0));
+        assert!(fuzzy_score("crag", "Create agent").is_some());
+        assert!(fuzzy_score("xyz", "Create agent").is_none());
+        // Order matters
+        assert!(fuzzy_score("tnega", "Create agent").is_none());
+        // Case insensitive
+        assert!(fuzzy_score("AGENT", "create agent").is_some());
+
+        // Prefix > word-start substring > scattered subsequence
+        let prefix = fuzzy_score("mem", "Memory settings").unwrap();
+        let word = fuzzy_score("mem", "Open memory").unwrap();
+        let scattered = fuzzy_score("mem", "Move items").unwrap();
+        assert!(prefix > word);
+        assert!(word > scattered);
+    }
+
+    #[test]
+    fn test_rank_actions() {
+        let actions = vec![
+            action(
+                "settings.open:providers",
+                "Open providers settings",
+                &["llm"],
+            ),
+            action("agent.create", "Create agent", &["new"]),
+            action("settings.open:agents", "Open agents settings", &[]),
+        ];
+
+        let all = rank_actions(actions.clone(), "", 10);
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].action.id, "settings.open:providers");
+
+        let ranked = rank_actions(actions.clone(), "agent", 10);
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].action.id, "agent.create");
+
+        // Every term must match, keywords included
+        let ranked = rank_actions(actions.clone(), "new agent", 10);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].action.id, "agent.create");
+
+        let ranked = rank_actions(actions.clone(), "llm", 10);
+        assert_eq!(ranked[0].action.id, "settings.open:providers");
+
+        assert_eq!(rank_actions(actions, "", 1).len(), 1);
+    }
+}
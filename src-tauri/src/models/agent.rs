@@ -12,8 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::models::moderation::ModerationPolicy;
+use crate::models::regression::RegressionSuite;
 use crate::tools::registry::TOOL_REGISTRY;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Agent lifecycle type
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -80,6 +83,147 @@ pub struct AgentConfig {
     /// Enable thinking mode for supported models (default: true for thinking models)
     #[serde(default = "default_enable_thinking")]
     pub enable_thinking: bool,
+    /// Per-tool parameter defaults and restrictions, keyed by tool name
+    #[serde(default)]
+    pub tool_settings: HashMap<String, ToolSettings>,
+    /// MCP tools the agent may use, keyed by MCP server name
+    /// (servers without a policy expose all their tools)
+    #[serde(default)]
+    pub mcp_tool_policy: HashMap<String, MCPToolPolicy>,
+    /// Regression test cases bundled with the agent
+    #[serde(default)]
+    pub regressions: RegressionSuite,
+    /// Output moderation applied before responses are persisted
+    #[serde(default)]
+    pub moderation: ModerationPolicy,
+}
+
+/// Per-agent parameter defaults and restrictions for a single tool.
+///
+/// Applied by the `ToolFactory` when the agent's tool instances are created,
+/// so the same tool can behave differently depending on the agent using it.
+///
+/// # Example
+///
+/// A MemoryTool restricted to read operations on general memories:
+/// ```json
+/// {
+///   "MemoryTool": {
+///     "defaults": { "scope": "general" },
+///     "fixed": { "workflow_id": "wf_knowledge" },
+///     "allowed_operations": ["describe", "get", "list", "search"],
+///     "timeout_secs": 30,
+///     "max_calls_per_workflow": 50,
+///     "max_calls_per_minute": 10
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolSettings {
+    /// Parameter values used when the LLM omits them
+    #[serde(default)]
+    pub defaults: serde_json::Map<String, serde_json::Value>,
+    /// Parameter values enforced on every call (the LLM cannot override them)
+    #[serde(default)]
+    pub fixed: serde_json::Map<String, serde_json::Value>,
+    /// Operations the agent may invoke (None = all operations allowed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_operations: Option<Vec<String>>,
+    /// Call timeout in seconds (None = the tool's registry default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Maximum calls of the tool in one workflow (None = unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_calls_per_workflow: Option<u32>,
+    /// Maximum calls of the tool per minute, across workflows (None = unlimited)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_calls_per_minute: Option<u32>,
+}
+
+impl ToolSettings {
+    /// Returns true if the settings have no effect on the tool.
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty()
+            && self.fixed.is_empty()
+            && self.allowed_operations.is_none()
+            && self.timeout_secs.is_none()
+            && self.max_calls_per_workflow.is_none()
+            && self.max_calls_per_minute.is_none()
+    }
+}
+
+/// Parses the tool_settings field from its JSON string storage format.
+///
+/// The field is stored as a JSON string in SurrealDB to bypass SCHEMAFULL
+/// filtering of nested objects (same approach as `mcp_server.env`).
+///
+/// # Returns
+/// Map of tool name to settings, or an empty map if the value is missing or invalid
+pub fn parse_tool_settings_json(
+    value: Option<&serde_json::Value>,
+) -> HashMap<String, ToolSettings> {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| serde_json::from_str::<HashMap<String, ToolSettings>>(s).ok())
+        .unwrap_or_default()
+}
+
+/// Per-agent allow/deny lists for the tools of one MCP server.
+///
+/// Enforced when the agent's MCP tool definitions are collected and again
+/// when a tool is called, so a denied tool is neither offered to the LLM nor
+/// executable.
+///
+/// # Example
+///
+/// Only the read tools of Serena:
+/// ```json
+/// {
+///   "serena": {
+///     "allow": ["find_symbol", "get_symbols_overview"]
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MCPToolPolicy {
+    /// Tools the agent may use (None = all tools of the server)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+    /// Tools the agent may not use (takes precedence over `allow`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl MCPToolPolicy {
+    /// Returns true if the policy permits the tool.
+    pub fn allows(&self, tool_name: &str) -> bool {
+        if self.deny.iter().any(|t| t == tool_name) {
+            return false;
+        }
+        self.allow
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|t| t == tool_name))
+    }
+
+    /// Returns true if the policy permits every tool.
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_none() && self.deny.is_empty()
+    }
+}
+
+/// Parses the mcp_tool_policy field from its JSON string storage format.
+///
+/// Stored as a JSON string like `tool_settings`.
+///
+/// # Returns
+/// Map of MCP server name to policy, or an empty map if the value is missing or invalid
+pub fn parse_mcp_tool_policy_json(
+    value: Option<&serde_json::Value>,
+) -> HashMap<String, MCPToolPolicy> {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| serde_json::from_str::<HashMap<String, MCPToolPolicy>>(s).ok())
+        .unwrap_or_default()
 }
 
 /// Default value for max_tool_iterations
@@ -92,6 +236,11 @@ fn default_enable_thinking() -> bool {
     true
 }
 
+/// Default value for inherit_workspace_defaults
+fn default_inherit_workspace_defaults() -> bool {
+    true
+}
+
 // Allow dead code until Phase 6: Full Agent Integration
 #[allow(dead_code)]
 impl AgentConfig {
@@ -110,6 +259,14 @@ impl AgentConfig {
     pub fn has_valid_tools(&self) -> bool {
         self.validate_tools().is_empty()
     }
+
+    /// Returns true if the agent's MCP tool policy permits `tool_name` on
+    /// `server_name`.
+    pub fn allows_mcp_tool(&self, server_name: &str, tool_name: &str) -> bool {
+        self.mcp_tool_policy
+            .get(server_name)
+            .is_none_or(|policy| policy.allows(tool_name))
+    }
 }
 
 /// LLM provider configuration
@@ -146,6 +303,21 @@ pub struct AgentConfigCreate {
     /// Enable thinking mode for supported models (default: true for thinking models)
     #[serde(default = "default_enable_thinking")]
     pub enable_thinking: bool,
+    /// Per-tool parameter defaults and restrictions, keyed by tool name
+    #[serde(default)]
+    pub tool_settings: HashMap<String, ToolSettings>,
+    /// MCP tools the agent may use, keyed by MCP server name
+    #[serde(default)]
+    pub mcp_tool_policy: HashMap<String, MCPToolPolicy>,
+    /// Regression test cases bundled with the agent
+    #[serde(default)]
+    pub regressions: RegressionSuite,
+    /// Output moderation applied before responses are persisted
+    #[serde(default)]
+    pub moderation: ModerationPolicy,
+    /// Add the workspace default tools and MCP servers (default: true)
+    #[serde(default = "default_inherit_workspace_defaults")]
+    pub inherit_workspace_defaults: bool,
 }
 
 /// Agent configuration for updates (all fields optional except lifecycle which cannot change)
@@ -172,6 +344,18 @@ pub struct AgentConfigUpdate {
     /// Enable thinking mode for supported models
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enable_thinking: Option<bool>,
+    /// Per-tool parameter defaults and restrictions (replaces existing settings)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_settings: Option<HashMap<String, ToolSettings>>,
+    /// MCP tool policies (replaces existing policies)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mcp_tool_policy: Option<HashMap<String, MCPToolPolicy>>,
+    /// Regression test cases (replaces existing suite)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regressions: Option<RegressionSuite>,
+    /// Output moderation policy (replaces existing policy)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ModerationPolicy>,
 }
 
 /// Agent summary for listing (lightweight representation)
@@ -281,6 +465,10 @@ mod tests {
             system_prompt: "You are a helpful assistant.".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: HashMap::new(),
+            mcp_tool_policy: HashMap::new(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -328,6 +516,10 @@ mod tests {
             system_prompt: "Test".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: HashMap::new(),
+            mcp_tool_policy: HashMap::new(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         assert!(config.has_valid_tools());
@@ -355,6 +547,10 @@ mod tests {
             system_prompt: "Test".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: HashMap::new(),
+            mcp_tool_policy: HashMap::new(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         assert!(!config.has_valid_tools());
@@ -389,9 +585,94 @@ mod tests {
             system_prompt: "Test".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: HashMap::new(),
+            mcp_tool_policy: HashMap::new(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         assert!(config.has_valid_tools());
         assert_eq!(config.tools.len(), 7);
     }
+
+    #[test]
+    fn test_agent_config_tool_settings_default() {
+        let json = r#"{
+            "id": "a1",
+            "name": "Agent",
+            "lifecycle": "permanent",
+            "llm": {"provider": "Mistral", "model": "m", "temperature": 0.7, "max_tokens": 1000},
+            "tools": ["MemoryTool"],
+            "mcp_servers": [],
+            "system_prompt": "Test"
+        }"#;
+
+        let config: AgentConfig = serde_json::from_str(json).unwrap();
+        assert!(config.tool_settings.is_empty());
+    }
+
+    #[test]
+    fn test_tool_settings_serialization() {
+        let settings: ToolSettings = serde_json::from_value(serde_json::json!({
+            "defaults": {"scope": "general"},
+            "allowed_operations": ["list", "search"]
+        }))
+        .unwrap();
+
+        assert_eq!(settings.defaults["scope"], "general");
+        assert!(settings.fixed.is_empty());
+        assert_eq!(
+            settings.allowed_operations,
+            Some(vec!["list".to_string(), "search".to_string()])
+        );
+        assert!(!settings.is_empty());
+        assert!(ToolSettings::default().is_empty());
+    }
+
+    #[test]
+    fn test_mcp_tool_policy() {
+        let policy: MCPToolPolicy = serde_json::from_value(serde_json::json!({
+            "allow": ["find_symbol", "replace_symbol_body"],
+            "deny": ["replace_symbol_body"]
+        }))
+        .unwrap();
+
+        assert!(policy.allows("find_symbol"));
+        assert!(!policy.allows("replace_symbol_body"));
+        assert!(!policy.allows("write_file"));
+        assert!(MCPToolPolicy::default().allows("write_file"));
+        assert!(MCPToolPolicy::default().is_empty());
+
+        let value = serde_json::json!("{\"serena\":{\"deny\":[\"write_file\"]}}");
+        let parsed = parse_mcp_tool_policy_json(Some(&value));
+        assert_eq!(parsed["serena"].deny, vec!["write_file"]);
+        assert!(parse_mcp_tool_policy_json(None).is_empty());
+
+        let mut config: AgentConfig = serde_json::from_value(serde_json::json!({
+            "id": "a1",
+            "name": "Agent",
+            "lifecycle": "permanent",
+            "llm": {"provider": "Mistral", "model": "m", "temperature": 0.7, "max_tokens": 1000},
+            "tools": [],
+            "mcp_servers": ["serena", "context7"],
+            "system_prompt": "Test"
+        }))
+        .unwrap();
+        assert!(config.mcp_tool_policy.is_empty());
+
+        config.mcp_tool_policy = parsed;
+        assert!(!config.allows_mcp_tool("serena", "write_file"));
+        assert!(config.allows_mcp_tool("serena", "find_symbol"));
+        assert!(config.allows_mcp_tool("context7", "write_file"));
+    }
+
+    #[test]
+    fn test_parse_tool_settings_json() {
+        let value = serde_json::json!("{\"MemoryTool\":{\"fixed\":{\"workflow_id\":\"wf_1\"}}}");
+        let parsed = parse_tool_settings_json(Some(&value));
+        assert_eq!(parsed["MemoryTool"].fixed["workflow_id"], "wf_1");
+
+        assert!(parse_tool_settings_json(None).is_empty());
+        assert!(parse_tool_settings_json(Some(&serde_json::json!("not json"))).is_empty());
+    }
 }
@@ -0,0 +1,205 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-agent tool usage statistics ("memory of tools").
+//!
+//! After each run, the tools an agent called are counted per tool together
+//! with keywords of the task they were used for. A compact summary is added
+//! to the agent's system prompt so it favors tools that worked for similar
+//! tasks, which helps agents with large tool sets.
+
+use crate::tools::constants::tool_usage::{
+    KEYWORDS_PER_TASK, MAX_HINT_TOOLS, MAX_TASK_KEYWORDS, MIN_KEYWORD_CHARS,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Words ignored when extracting task keywords (English and French)
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "also", "avec", "been", "before", "cette", "could", "dans", "does",
+    "donne", "each", "faire", "from", "have", "into", "just", "leur", "like", "make", "mais",
+    "merci", "more", "need", "nous", "please", "pour", "quel", "quelle", "should", "some", "sous",
+    "such", "sur", "than", "that", "their", "them", "then", "there", "these", "they", "this",
+    "tous", "tout", "very", "vous", "want", "were", "what", "when", "where", "which", "while",
+    "will", "with", "would", "your",
+];
+
+/// Tool usage statistics of an agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentToolUsage {
+    /// Agent ID
+    pub agent_id: String,
+    /// Tool name as called by the agent (`mcp__server__tool` for MCP tools)
+    pub tool_name: String,
+    /// Number of successful calls
+    pub success_count: u64,
+    /// Number of failed calls
+    pub failure_count: u64,
+    /// Keywords of recent tasks the tool was used for successfully (most recent first)
+    #[serde(default)]
+    pub task_keywords: Vec<String>,
+    /// Last time the tool was called
+    pub last_used_at: DateTime<Utc>,
+}
+
+impl AgentToolUsage {
+    /// Total number of calls
+    pub fn total_calls(&self) -> u64 {
+        self.success_count + self.failure_count
+    }
+}
+
+/// Extracts the most significant keywords of a task description.
+///
+/// Keeps the first distinct words of at least `MIN_KEYWORD_CHARS` characters
+/// which are neither stop words nor numbers.
+pub fn extract_task_keywords(task: &str) -> Vec<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    for word in task
+        .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+        .map(|w| w.trim_matches(|c| c == '-' || c == '_').to_lowercase())
+    {
+        if keywords.len() >= KEYWORDS_PER_TASK {
+            break;
+        }
+        if word.chars().count() < MIN_KEYWORD_CHARS
+            || word.chars().all(|c| c.is_numeric())
+            || STOP_WORDS.contains(&word.as_str())
+            || keywords.contains(&word)
+        {
+            continue;
+        }
+        keywords.push(word);
+    }
+    keywords
+}
+
+/// Merges new task keywords in front of the existing ones, keeping at most
+/// `MAX_TASK_KEYWORDS` distinct keywords.
+pub fn merge_task_keywords(existing: &[String], new: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::with_capacity(MAX_TASK_KEYWORDS);
+    for keyword in new.iter().chain(existing) {
+        if merged.len() >= MAX_TASK_KEYWORDS {
+            break;
+        }
+        if !merged.contains(keyword) {
+            merged.push(keyword.clone());
+        }
+    }
+    merged
+}
+
+/// Formats the "past tool usage" section of the system prompt.
+///
+/// Only tools in `available_tools` are listed, most successful first.
+///
+/// # Returns
+/// `None` when no available tool has been used yet
+pub fn format_tool_usage_hint(
+    usage: &[AgentToolUsage],
+    available_tools: &[String],
+) -> Option<String> {
+    let mut relevant: Vec<&AgentToolUsage> = usage
+        .iter()
+        .filter(|u| u.total_calls() > 0 && available_tools.contains(&u.tool_name))
+        .collect();
+    if relevant.is_empty() {
+        return None;
+    }
+    relevant.sort_by(|a, b| {
+        b.success_count
+            .cmp(&a.success_count)
+            .then(b.total_calls().cmp(&a.total_calls()))
+            .then(a.tool_name.cmp(&b.tool_name))
+    });
+
+    let mut hint = String::from(
+        "## Your Past Tool Usage\n\n\
+         Tools you called in previous runs (successful/total calls, typical tasks). \
+         Prefer tools that worked for similar tasks.\n",
+    );
+    for u in relevant.into_iter().take(MAX_HINT_TOOLS) {
+        hint.push_str(&format!(
+            "- **{}**: {}/{} successful",
+            u.tool_name,
+            u.success_count,
+            u.total_calls()
+        ));
+        if !u.task_keywords.is_empty() {
+            hint.push_str(&format!(" - tasks: {}", u.task_keywords.join(", ")));
+        }
+        hint.push('\n');
+    }
+    Some(hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(tool_name: &str, success_count: u64, failure_count: u64) -> AgentToolUsage {
+        AgentToolUsage {
+            agent_id: "agent1".to_string(),
+            tool_name: tool_name.to_string(),
+            success_count,
+            failure_count,
+            task_keywords: vec!["invoices".to_string()],
+            last_used_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_extract_task_keywords() {
+        assert_eq!(
+            extract_task_keywords(
+                "Please summarize the 2024 invoices from Acme, then the invoices"
+            ),
+            vec!["summarize", "invoices", "acme"]
+        );
+        assert_eq!(
+            extract_task_keywords("Analyse les factures pour le client"),
+            vec!["analyse", "factures", "client"]
+        );
+        assert!(extract_task_keywords("ok, go").is_empty());
+    }
+
+    #[test]
+    fn test_merge_task_keywords() {
+        let existing: Vec<String> = (0..MAX_TASK_KEYWORDS).map(|i| format!("k{}", i)).collect();
+        let merged = merge_task_keywords(&existing, &["new".to_string(), "k1".to_string()]);
+        assert_eq!(merged.len(), MAX_TASK_KEYWORDS);
+        assert_eq!(merged[0], "new");
+        assert_eq!(merged[1], "k1");
+        assert_eq!(merged[2], "k0");
+    }
+
+    #[test]
+    fn test_format_tool_usage_hint() {
+        let stats = vec![
+            usage("TodoTool", 2, 3),
+            usage("MemoryTool", 9, 1),
+            usage("mcp__old__search", 50, 0),
+        ];
+        let available = vec!["MemoryTool".to_string(), "TodoTool".to_string()];
+
+        let hint = format_tool_usage_hint(&stats, &available).unwrap();
+        assert!(hint.starts_with("## Your Past Tool Usage"));
+        assert!(hint.contains("- **MemoryTool**: 9/10 successful - tasks: invoices"));
+        assert!(hint.find("MemoryTool").unwrap() < hint.find("TodoTool").unwrap());
+        // Tools no longer available are not listed
+        assert!(!hint.contains("mcp__old__search"));
+
+        assert!(format_tool_usage_hint(&stats, &[]).is_none());
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API key metadata models.
+//!
+//! Each provider API key stored in the keystore has metadata (creation date,
+//! last use, optional expiry) kept next to it in the OS keychain. It is never
+//! secret and is used to remind the user to rotate stale or expiring keys.
+
+use crate::tools::constants::api_keys::{EXPIRY_WARNING_DAYS, STALE_AFTER_DAYS};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Metadata of a stored API key
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyMetadata {
+    /// Provider name (keystore entry)
+    pub provider: String,
+    /// Date the current key was saved
+    pub created_at: DateTime<Utc>,
+    /// Date the key was last read to call the provider (hour resolution)
+    #[serde(default)]
+    pub last_used: Option<DateTime<Utc>>,
+    /// Expiry date set by the user (e.g. from the provider console)
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Date the key was last rotated
+    #[serde(default)]
+    pub rotated_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKeyMetadata {
+    /// Metadata of a key saved at `now`.
+    pub fn new(provider: &str, now: DateTime<Utc>, expires_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            provider: provider.to_string(),
+            created_at: now,
+            last_used: None,
+            expires_at,
+            rotated_at: None,
+        }
+    }
+
+    /// Status of the key at `now` (expiry takes precedence over age).
+    pub fn status(&self, now: DateTime<Utc>) -> ApiKeyStatus {
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= now {
+                return ApiKeyStatus::Expired;
+            }
+            if expires_at - now <= Duration::days(EXPIRY_WARNING_DAYS) {
+                return ApiKeyStatus::ExpiringSoon;
+            }
+        }
+        if now - self.created_at >= Duration::days(STALE_AFTER_DAYS) {
+            ApiKeyStatus::Stale
+        } else {
+            ApiKeyStatus::Ok
+        }
+    }
+}
+
+/// Status of a stored API key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyStatus {
+    /// Recent key, not expiring soon
+    Ok,
+    /// Key older than `STALE_AFTER_DAYS`: rotation recommended
+    Stale,
+    /// Key expiring within `EXPIRY_WARNING_DAYS`
+    ExpiringSoon,
+    /// Key past its expiry date
+    Expired,
+}
+
+/// API key metadata with its status, as returned by `list_api_key_metadata`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKeyInfo {
+    /// Key metadata
+    #[serde(flatten)]
+    pub metadata: ApiKeyMetadata,
+    /// Status at the time of the request
+    pub status: ApiKeyStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() {
+        let now = Utc::now();
+        let mut metadata = ApiKeyMetadata::new("Mistral", now - Duration::days(10), None);
+        assert_eq!(metadata.status(now), ApiKeyStatus::Ok);
+
+        metadata.created_at = now - Duration::days(STALE_AFTER_DAYS);
+        assert_eq!(metadata.status(now), ApiKeyStatus::Stale);
+
+        metadata.expires_at = Some(now + Duration::days(EXPIRY_WARNING_DAYS + 1));
+        assert_eq!(metadata.status(now), ApiKeyStatus::Stale);
+
+        metadata.created_at = now;
+        assert_eq!(metadata.status(now), ApiKeyStatus::Ok);
+
+        metadata.expires_at = Some(now + Duration::days(3));
+        assert_eq!(metadata.status(now), ApiKeyStatus::ExpiringSoon);
+
+        metadata.expires_at = Some(now - Duration::hours(1));
+        assert_eq!(metadata.status(now), ApiKeyStatus::Expired);
+    }
+
+    #[test]
+    fn test_metadata_serialization() {
+        let metadata: ApiKeyMetadata =
+            serde_json::from_str(r#"{"provider":"Gemini","created_at":"2025-01-01T00:00:00Z"}"#)
+                .unwrap();
+        assert_eq!(metadata.last_used, None);
+        assert_eq!(metadata.expires_at, None);
+
+        let info = serde_json::to_value(ApiKeyInfo {
+            status: metadata.status(metadata.created_at),
+            metadata,
+        })
+        .unwrap();
+        assert_eq!(info["provider"], "Gemini");
+        assert_eq!(info["status"], "ok");
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message attachment models.
+//!
+//! Images attached to user messages (and charts attached to assistant
+//! responses) are stored as blobs under the app data
+//! directory (see `db::attachments`); the `attachment` table keeps their
+//! metadata. Synchronized with src/types/message.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Image types accepted as attachments: (MIME type, file extension)
+pub const SUPPORTED_IMAGE_TYPES: &[(&str, &str)] = &[
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("image/svg+xml", "svg"),
+];
+
+/// Image types that can be sent to vision models, the only ones accepted from
+/// the user (SVG is only accepted for charts rendered by the ChartTool)
+pub const VISION_IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Attachment of a message (metadata, the blob is stored on disk)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// Associated workflow ID
+    pub workflow_id: String,
+    /// Message the attachment belongs to
+    pub message_id: String,
+    /// MIME type detected from the content (e.g. "image/png")
+    pub mime_type: String,
+    /// Original file name, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// Blob size in bytes
+    pub size_bytes: u64,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Payload for creating an attachment record.
+///
+/// ID and timestamp are generated server-side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentCreate {
+    /// Associated workflow ID
+    pub workflow_id: String,
+    /// Message the attachment belongs to
+    pub message_id: String,
+    /// MIME type
+    pub mime_type: String,
+    /// Original file name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// Blob size in bytes
+    pub size_bytes: u64,
+}
+
+/// Attachment sent with `save_message`: base64 data or a local file path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentInput {
+    /// Base64 content, optionally as a data URL (`data:image/png;base64,...`)
+    #[serde(default)]
+    pub data: Option<String>,
+    /// Path of a local image file
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Original file name (defaults to the file name of `path`)
+    #[serde(default)]
+    pub file_name: Option<String>,
+}
+
+/// Detects the image type of a blob from its signature.
+///
+/// Returns the MIME type, or None if the content is not a supported image.
+pub fn detect_image_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if is_svg(bytes) {
+        Some("image/svg+xml")
+    } else {
+        None
+    }
+}
+
+/// Checks whether a blob is an SVG document: its root element is `<svg>`,
+/// after the XML prolog, comments and doctype.
+fn is_svg(bytes: &[u8]) -> bool {
+    let head = &bytes[..bytes.len().min(1024)];
+    let text = String::from_utf8_lossy(head);
+    let mut rest = text.trim_start_matches('\u{feff}');
+    loop {
+        rest = rest.trim_start();
+        let end = if rest.starts_with("<?") {
+            "?>"
+        } else if rest.starts_with("<!--") {
+            "-->"
+        } else if rest.starts_with("<!") {
+            ">"
+        } else {
+            break;
+        };
+        match rest.find(end) {
+            Some(index) => rest = &rest[index + end.len()..],
+            None => return false,
+        }
+    }
+    rest.strip_prefix("<svg").is_some_and(|name_end| {
+        name_end.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/')
+    })
+}
+
+/// Returns the file extension of a supported MIME type.
+pub fn image_extension(mime_type: &str) -> Option<&'static str> {
+    SUPPORTED_IMAGE_TYPES
+        .iter()
+        .find(|(mime, _)| *mime == mime_type)
+        .map(|(_, ext)| *ext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_image_mime() {
+        assert_eq!(
+            detect_image_mime(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"),
+            Some("image/png")
+        );
+        assert_eq!(
+            detect_image_mime(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some("image/jpeg")
+        );
+        assert_eq!(detect_image_mime(b"GIF89a\x01\0"), Some("image/gif"));
+        assert_eq!(
+            detect_image_mime(b"RIFF\x24\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(
+            detect_image_mime(b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>"),
+            Some("image/svg+xml")
+        );
+        assert_eq!(
+            detect_image_mime(b"<?xml version=\"1.0\"?>\n<svg></svg>"),
+            Some("image/svg+xml")
+        );
+        assert_eq!(
+            detect_image_mime(
+                b"<?xml version=\"1.0\"?>\n<!-- chart -->\n<!DOCTYPE svg>\n<svg>\n</svg>"
+            ),
+            Some("image/svg+xml")
+        );
+        assert_eq!(detect_image_mime(b"<html><svg></svg></html>"), None);
+        assert_eq!(
+            detect_image_mime(b"<?xml version=\"1.0\"?><html><svg></svg></html>"),
+            None
+        );
+        assert_eq!(detect_image_mime(b"<!-- <svg> --><html></html>"), None);
+        assert_eq!(detect_image_mime(b"<svgfoo/>"), None);
+        assert_eq!(detect_image_mime(b"%PDF-1.7"), None);
+        assert_eq!(detect_image_mime(b""), None);
+    }
+
+    #[test]
+    fn test_image_extension() {
+        assert_eq!(image_extension("image/jpeg"), Some("jpg"));
+        assert_eq!(image_extension("image/svg+xml"), Some("svg"));
+        assert_eq!(image_extension("application/pdf"), None);
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit log models.
+//!
+//! Sensitive actions (API key access, tool executions, validation decisions,
+//! agent configuration changes, MCP server start/stop, output moderation
+//! checks) are recorded in the
+//! append-only `audit_log` table when `ValidationSettings::audit` enables it
+//! (see [`crate::security::audit`]).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of audited action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// API key saved, read or deleted from the keystore
+    ApiKeyAccess,
+    /// Tool executed by an agent
+    ToolExecution,
+    /// Validation request approved or rejected
+    ValidationDecision,
+    /// Agent created, updated or deleted
+    AgentConfigChange,
+    /// MCP server started
+    McpServerStart,
+    /// MCP server stopped
+    McpServerStop,
+    /// Agent response checked by its moderation policy
+    Moderation,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditAction::ApiKeyAccess => write!(f, "api_key_access"),
+            AuditAction::ToolExecution => write!(f, "tool_execution"),
+            AuditAction::ValidationDecision => write!(f, "validation_decision"),
+            AuditAction::AgentConfigChange => write!(f, "agent_config_change"),
+            AuditAction::McpServerStart => write!(f, "mcp_server_start"),
+            AuditAction::McpServerStop => write!(f, "mcp_server_stop"),
+            AuditAction::Moderation => write!(f, "moderation"),
+        }
+    }
+}
+
+/// Payload for creating an audit log entry.
+///
+/// ID and created_at are generated server-side. Details never contain secrets
+/// or tool payloads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogCreate {
+    /// Audited action
+    pub action: AuditAction,
+    /// Who performed the action ("user", "system", an agent ID...)
+    pub actor: String,
+    /// Object of the action (provider, tool, validation request, agent, server)
+    pub target: String,
+    /// Associated workflow ID
+    pub workflow_id: Option<String>,
+    /// Whether the action succeeded
+    pub success: bool,
+    /// Additional context (JSON object)
+    pub details: serde_json::Value,
+}
+
+impl AuditLogCreate {
+    /// Creates a successful entry without workflow or details.
+    pub fn new(action: AuditAction, actor: &str, target: &str) -> Self {
+        Self {
+            action,
+            actor: actor.to_string(),
+            target: target.to_string(),
+            workflow_id: None,
+            success: true,
+            details: serde_json::json!({}),
+        }
+    }
+}
+
+/// Audit log entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    /// Entry ID
+    pub id: String,
+    /// Audited action
+    pub action: AuditAction,
+    /// Who performed the action
+    pub actor: String,
+    /// Object of the action
+    pub target: String,
+    /// Associated workflow ID
+    pub workflow_id: Option<String>,
+    /// Whether the action succeeded
+    pub success: bool,
+    /// Additional context
+    pub details: serde_json::Value,
+    /// Action timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filter of audit log queries and exports (all criteria optional)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuditLogFilter {
+    /// Restrict to one action
+    pub action: Option<AuditAction>,
+    /// Restrict to one actor
+    pub actor: Option<String>,
+    /// Restrict to one workflow
+    pub workflow_id: Option<String>,
+    /// Entries at or after this date
+    pub since: Option<DateTime<Utc>>,
+    /// Entries before this date
+    pub until: Option<DateTime<Utc>>,
+    /// Maximum number of entries, newest first
+    pub limit: Option<usize>,
+}
+
+/// File format of an audit log export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    /// One JSON object per line
+    #[default]
+    Jsonl,
+    /// Comma-separated values with a header row
+    Csv,
+}
@@ -0,0 +1,596 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Changelog of the changes affecting agent behavior.
+//!
+//! Prompt edits, model switches, tool grants, MCP server assignments and MCP
+//! server changes are recorded in one ordered log. Each entry gets a
+//! `position`; workflow runs store the position of the last change in effect
+//! (`behavior_position` of the response message), so a run that went wrong
+//! can be correlated with the changes made since an earlier run.
+
+use crate::models::mcp::{MCPServerConfig, MCPTool};
+use crate::models::AgentConfig;
+use crate::tools::constants::behavior_changelog::MAX_VALUE_CHARS;
+use crate::tools::validation_helper::safe_truncate;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Kind of behavior change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BehaviorChangeKind {
+    /// Agent created
+    AgentCreated,
+    /// Agent deleted
+    AgentDeleted,
+    /// System prompt edited
+    PromptChanged,
+    /// Provider, model or sampling parameters changed
+    ModelChanged,
+    /// Local tools granted or revoked
+    ToolsChanged,
+    /// MCP servers assigned or removed, or MCP tool policy changed
+    McpServersChanged,
+    /// Tool settings, iteration limit, thinking or moderation changed
+    SettingsChanged,
+    /// MCP server configuration added
+    McpServerAdded,
+    /// MCP server configuration updated
+    McpServerUpdated,
+    /// MCP server configuration removed
+    McpServerRemoved,
+    /// Tool list of a running MCP server changed
+    McpToolsChanged,
+}
+
+/// Value of one field before and after a change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorFieldChange {
+    /// Changed field (e.g. "system_prompt", "llm.model")
+    pub field: String,
+    /// Previous value (None when the field did not exist)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// New value (None when the field was removed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl BehaviorFieldChange {
+    /// Builds a field change, truncating long values.
+    fn new(field: &str, before: Option<String>, after: Option<String>) -> Self {
+        let truncate = |value: String| safe_truncate(&value, MAX_VALUE_CHARS, true);
+        Self {
+            field: field.to_string(),
+            before: before.map(truncate),
+            after: after.map(truncate),
+        }
+    }
+}
+
+/// Change to record in the behavior changelog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorChangeCreate {
+    /// Kind of change
+    pub kind: BehaviorChangeKind,
+    /// Agent affected (None for MCP server changes, which affect every agent using the server)
+    pub agent_id: Option<String>,
+    /// Name of the changed agent or MCP server
+    pub subject: String,
+    /// One-line description of the change
+    pub summary: String,
+    /// Changed fields
+    pub changes: Vec<BehaviorFieldChange>,
+}
+
+/// Recorded behavior change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BehaviorChange {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// Position in the changelog (increasing, starts at 1)
+    pub position: u64,
+    /// Kind of change
+    pub kind: BehaviorChangeKind,
+    /// Agent affected (None for MCP server changes)
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Name of the changed agent or MCP server
+    pub subject: String,
+    /// One-line description of the change
+    pub summary: String,
+    /// Changed fields
+    #[serde(default)]
+    pub changes: Vec<BehaviorFieldChange>,
+    /// Time of the change
+    pub created_at: DateTime<Utc>,
+}
+
+/// Names added to and removed from a list
+fn list_diff(before: &[String], after: &[String]) -> (Vec<String>, Vec<String>) {
+    let before: BTreeSet<&String> = before.iter().collect();
+    let after: BTreeSet<&String> = after.iter().collect();
+    (
+        after.difference(&before).map(|s| s.to_string()).collect(),
+        before.difference(&after).map(|s| s.to_string()).collect(),
+    )
+}
+
+/// Summary of a list change ("granted: a, b; revoked: c")
+fn list_summary(added: &[String], removed: &[String], add_verb: &str, remove_verb: &str) -> String {
+    let mut parts = Vec::new();
+    if !added.is_empty() {
+        parts.push(format!("{}: {}", add_verb, added.join(", ")));
+    }
+    if !removed.is_empty() {
+        parts.push(format!("{}: {}", remove_verb, removed.join(", ")));
+    }
+    parts.join("; ")
+}
+
+/// Compact JSON of a value (for settings without a natural text form)
+fn json_text<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// Change recording the creation of an agent.
+pub fn agent_created(config: &AgentConfig) -> BehaviorChangeCreate {
+    BehaviorChangeCreate {
+        kind: BehaviorChangeKind::AgentCreated,
+        agent_id: Some(config.id.clone()),
+        subject: config.name.clone(),
+        summary: format!(
+            "Agent created with {}/{}",
+            config.llm.provider, config.llm.model
+        ),
+        changes: vec![
+            BehaviorFieldChange::new("system_prompt", None, Some(config.system_prompt.clone())),
+            BehaviorFieldChange::new(
+                "llm.model",
+                None,
+                Some(format!("{}/{}", config.llm.provider, config.llm.model)),
+            ),
+            BehaviorFieldChange::new("tools", None, Some(config.tools.join(", "))),
+            BehaviorFieldChange::new("mcp_servers", None, Some(config.mcp_servers.join(", "))),
+        ],
+    }
+}
+
+/// Change recording the deletion of an agent.
+pub fn agent_deleted(config: &AgentConfig) -> BehaviorChangeCreate {
+    BehaviorChangeCreate {
+        kind: BehaviorChangeKind::AgentDeleted,
+        agent_id: Some(config.id.clone()),
+        subject: config.name.clone(),
+        summary: "Agent deleted".to_string(),
+        changes: Vec::new(),
+    }
+}
+
+/// Behavior changes between two configurations of an agent.
+///
+/// Returns one change per kind (prompt, model, tools, MCP servers, settings);
+/// name and regression suite edits do not affect behavior and are ignored.
+pub fn diff_agent_configs(before: &AgentConfig, after: &AgentConfig) -> Vec<BehaviorChangeCreate> {
+    let change = |kind, summary: String, changes| BehaviorChangeCreate {
+        kind,
+        agent_id: Some(after.id.clone()),
+        subject: after.name.clone(),
+        summary,
+        changes,
+    };
+    let mut result = Vec::new();
+
+    if before.system_prompt != after.system_prompt {
+        result.push(change(
+            BehaviorChangeKind::PromptChanged,
+            format!(
+                "System prompt edited ({} -> {} characters)",
+                before.system_prompt.chars().count(),
+                after.system_prompt.chars().count()
+            ),
+            vec![BehaviorFieldChange::new(
+                "system_prompt",
+                Some(before.system_prompt.clone()),
+                Some(after.system_prompt.clone()),
+            )],
+        ));
+    }
+
+    let (old, new) = (&before.llm, &after.llm);
+    let mut llm_changes = Vec::new();
+    if old.provider != new.provider || old.model != new.model {
+        llm_changes.push(BehaviorFieldChange::new(
+            "llm.model",
+            Some(format!("{}/{}", old.provider, old.model)),
+            Some(format!("{}/{}", new.provider, new.model)),
+        ));
+    }
+    if old.temperature != new.temperature {
+        llm_changes.push(BehaviorFieldChange::new(
+            "llm.temperature",
+            Some(old.temperature.to_string()),
+            Some(new.temperature.to_string()),
+        ));
+    }
+    if old.max_tokens != new.max_tokens {
+        llm_changes.push(BehaviorFieldChange::new(
+            "llm.max_tokens",
+            Some(old.max_tokens.to_string()),
+            Some(new.max_tokens.to_string()),
+        ));
+    }
+    if !llm_changes.is_empty() {
+        let fields: Vec<&str> = llm_changes.iter().map(|c| c.field.as_str()).collect();
+        let summary = if fields.contains(&"llm.model") {
+            format!(
+                "Model switched from {}/{} to {}/{}",
+                old.provider, old.model, new.provider, new.model
+            )
+        } else {
+            format!("Model parameters changed: {}", fields.join(", "))
+        };
+        result.push(change(
+            BehaviorChangeKind::ModelChanged,
+            summary,
+            llm_changes,
+        ));
+    }
+
+    let (granted, revoked) = list_diff(&before.tools, &after.tools);
+    if !granted.is_empty() || !revoked.is_empty() {
+        result.push(change(
+            BehaviorChangeKind::ToolsChanged,
+            format!(
+                "Tools {}",
+                list_summary(&granted, &revoked, "granted", "revoked")
+            ),
+            vec![BehaviorFieldChange::new(
+                "tools",
+                Some(before.tools.join(", ")),
+                Some(after.tools.join(", ")),
+            )],
+        ));
+    }
+
+    let (assigned, removed) = list_diff(&before.mcp_servers, &after.mcp_servers);
+    let mut mcp_changes = Vec::new();
+    let mut mcp_summary = Vec::new();
+    if !assigned.is_empty() || !removed.is_empty() {
+        mcp_changes.push(BehaviorFieldChange::new(
+            "mcp_servers",
+            Some(before.mcp_servers.join(", ")),
+            Some(after.mcp_servers.join(", ")),
+        ));
+        mcp_summary.push(format!(
+            "MCP servers {}",
+            list_summary(&assigned, &removed, "assigned", "removed")
+        ));
+    }
+    // Policies of removed servers are dropped with the server
+    let policy_changed: Vec<String> = after
+        .mcp_servers
+        .iter()
+        .filter(|server| before.mcp_tool_policy.get(*server) != after.mcp_tool_policy.get(*server))
+        .cloned()
+        .collect();
+    for server in &policy_changed {
+        mcp_changes.push(BehaviorFieldChange::new(
+            &format!("mcp_tool_policy.{}", server),
+            before.mcp_tool_policy.get(server).map(json_text),
+            after.mcp_tool_policy.get(server).map(json_text),
+        ));
+    }
+    if !policy_changed.is_empty() {
+        mcp_summary.push(format!(
+            "MCP tool policy changed: {}",
+            policy_changed.join(", ")
+        ));
+    }
+    if !mcp_changes.is_empty() {
+        result.push(change(
+            BehaviorChangeKind::McpServersChanged,
+            mcp_summary.join("; "),
+            mcp_changes,
+        ));
+    }
+
+    let mut settings = Vec::new();
+    if before.max_tool_iterations != after.max_tool_iterations {
+        settings.push(BehaviorFieldChange::new(
+            "max_tool_iterations",
+            Some(before.max_tool_iterations.to_string()),
+            Some(after.max_tool_iterations.to_string()),
+        ));
+    }
+    if before.enable_thinking != after.enable_thinking {
+        settings.push(BehaviorFieldChange::new(
+            "enable_thinking",
+            Some(before.enable_thinking.to_string()),
+            Some(after.enable_thinking.to_string()),
+        ));
+    }
+    if before.tool_settings != after.tool_settings {
+        settings.push(BehaviorFieldChange::new(
+            "tool_settings",
+            Some(json_text(&before.tool_settings)),
+            Some(json_text(&after.tool_settings)),
+        ));
+    }
+    if json_text(&before.moderation) != json_text(&after.moderation) {
+        settings.push(BehaviorFieldChange::new(
+            "moderation",
+            Some(json_text(&before.moderation)),
+            Some(json_text(&after.moderation)),
+        ));
+    }
+    if !settings.is_empty() {
+        let fields: Vec<&str> = settings.iter().map(|c| c.field.as_str()).collect();
+        let summary = format!("Settings changed: {}", fields.join(", "));
+        result.push(change(
+            BehaviorChangeKind::SettingsChanged,
+            summary,
+            settings,
+        ));
+    }
+
+    result
+}
+
+/// Change recording an MCP server configuration added, updated or removed.
+///
+/// Only the names of environment variables are recorded (values may be
+/// secrets). Returns None when an update changed nothing that affects agents.
+pub fn mcp_server_change(
+    before: Option<&MCPServerConfig>,
+    after: Option<&MCPServerConfig>,
+) -> Option<BehaviorChangeCreate> {
+    let env_names = |config: &MCPServerConfig| {
+        let mut names: Vec<&String> = config.env.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let fields = |config: &MCPServerConfig| {
+        vec![
+            ("name", config.name.clone()),
+            ("enabled", config.enabled.to_string()),
+            ("command", config.command.to_string()),
+            ("args", config.args.join(" ")),
+            ("env", env_names(config)),
+        ]
+    };
+
+    let (kind, subject, summary, changes) = match (before, after) {
+        (None, Some(added)) => (
+            BehaviorChangeKind::McpServerAdded,
+            added.name.clone(),
+            format!("MCP server added ({})", added.command),
+            fields(added)
+                .into_iter()
+                .map(|(field, value)| BehaviorFieldChange::new(field, None, Some(value)))
+                .collect::<Vec<_>>(),
+        ),
+        (Some(removed), None) => (
+            BehaviorChangeKind::McpServerRemoved,
+            removed.name.clone(),
+            "MCP server removed".to_string(),
+            Vec::new(),
+        ),
+        (Some(old), Some(new)) => {
+            let changes: Vec<BehaviorFieldChange> = fields(old)
+                .into_iter()
+                .zip(fields(new))
+                .filter(|((_, a), (_, b))| a != b)
+                .map(|((field, a), (_, b))| BehaviorFieldChange::new(field, Some(a), Some(b)))
+                .collect();
+            if changes.is_empty() {
+                return None;
+            }
+            let names: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+            let summary = format!("MCP server updated: {}", names.join(", "));
+            (
+                BehaviorChangeKind::McpServerUpdated,
+                new.name.clone(),
+                summary,
+                changes,
+            )
+        }
+        (None, None) => return None,
+    };
+
+    Some(BehaviorChangeCreate {
+        kind,
+        agent_id: None,
+        subject,
+        summary,
+        changes,
+    })
+}
+
+/// Change recording a new tool list announced by a running MCP server.
+///
+/// Returns None when no tool was added or removed.
+pub fn mcp_tools_change(
+    server_name: &str,
+    before: &[MCPTool],
+    after: &[MCPTool],
+) -> Option<BehaviorChangeCreate> {
+    let names = |tools: &[MCPTool]| tools.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+    let (before, after) = (names(before), names(after));
+    let (added, removed) = list_diff(&before, &after);
+    if added.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(BehaviorChangeCreate {
+        kind: BehaviorChangeKind::McpToolsChanged,
+        agent_id: None,
+        subject: server_name.to_string(),
+        summary: format!(
+            "MCP tools {}",
+            list_summary(&added, &removed, "added", "removed")
+        ),
+        changes: vec![BehaviorFieldChange::new(
+            "tools",
+            Some(before.join(", ")),
+            Some(after.join(", ")),
+        )],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::mcp::MCPDeploymentMethod;
+    use crate::models::{LLMConfig, Lifecycle};
+    use std::collections::HashMap;
+
+    fn config() -> AgentConfig {
+        AgentConfig {
+            id: "agent_1".to_string(),
+            name: "Researcher".to_string(),
+            lifecycle: Lifecycle::Permanent,
+            llm: LLMConfig {
+                provider: "Mistral".to_string(),
+                model: "mistral-large-latest".to_string(),
+                temperature: 0.7,
+                max_tokens: 4096,
+            },
+            tools: vec!["MemoryTool".to_string(), "TodoTool".to_string()],
+            mcp_servers: vec!["serena".to_string()],
+            system_prompt: "You research topics.".to_string(),
+            max_tool_iterations: 50,
+            enable_thinking: false,
+            tool_settings: HashMap::new(),
+            mcp_tool_policy: HashMap::new(),
+            regressions: Default::default(),
+            moderation: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_diff_agent_configs() {
+        let before = config();
+        let mut after = config();
+        assert!(diff_agent_configs(&before, &after).is_empty());
+
+        // Renaming does not affect behavior
+        after.name = "Senior Researcher".to_string();
+        assert!(diff_agent_configs(&before, &after).is_empty());
+
+        after.system_prompt = "You research topics and cite sources.".to_string();
+        after.llm.model = "mistral-small-latest".to_string();
+        after.tools = vec!["MemoryTool".to_string(), "CalculatorTool".to_string()];
+        after.mcp_servers = vec![];
+        after.enable_thinking = true;
+        let changes = diff_agent_configs(&before, &after);
+
+        let kinds: Vec<BehaviorChangeKind> = changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                BehaviorChangeKind::PromptChanged,
+                BehaviorChangeKind::ModelChanged,
+                BehaviorChangeKind::ToolsChanged,
+                BehaviorChangeKind::McpServersChanged,
+                BehaviorChangeKind::SettingsChanged,
+            ]
+        );
+        assert!(changes
+            .iter()
+            .all(|c| c.agent_id.as_deref() == Some("agent_1") && c.subject == "Senior Researcher"));
+        assert_eq!(
+            changes[1].summary,
+            "Model switched from Mistral/mistral-large-latest to Mistral/mistral-small-latest"
+        );
+        assert_eq!(
+            changes[2].summary,
+            "Tools granted: CalculatorTool; revoked: TodoTool"
+        );
+        assert_eq!(changes[3].summary, "MCP servers removed: serena");
+        assert_eq!(changes[4].summary, "Settings changed: enable_thinking");
+        assert_eq!(changes[4].changes[0].before.as_deref(), Some("false"));
+        assert_eq!(changes[4].changes[0].after.as_deref(), Some("true"));
+    }
+
+    #[test]
+    fn test_diff_model_parameters_only() {
+        let before = config();
+        let mut after = config();
+        after.llm.temperature = 0.2;
+        let changes = diff_agent_configs(&before, &after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, BehaviorChangeKind::ModelChanged);
+        assert_eq!(
+            changes[0].summary,
+            "Model parameters changed: llm.temperature"
+        );
+    }
+
+    #[test]
+    fn test_mcp_server_change_hides_env_values() {
+        let before = MCPServerConfig {
+            id: "srv_1".to_string(),
+            name: "github".to_string(),
+            enabled: true,
+            command: MCPDeploymentMethod::Docker,
+            args: vec!["run".to_string(), "-i".to_string(), "github:1".to_string()],
+            env: HashMap::from([("GITHUB_TOKEN".to_string(), "secret-1".to_string())]),
+            description: None,
+        };
+
+        let added = mcp_server_change(None, Some(&before)).unwrap();
+        assert_eq!(added.kind, BehaviorChangeKind::McpServerAdded);
+        assert!(added.agent_id.is_none());
+        assert!(!serde_json::to_string(&added).unwrap().contains("secret-1"));
+
+        // A new secret value is not a recorded change
+        let mut after = before.clone();
+        after
+            .env
+            .insert("GITHUB_TOKEN".to_string(), "secret-2".to_string());
+        after.description = Some("GitHub".to_string());
+        assert!(mcp_server_change(Some(&before), Some(&after)).is_none());
+
+        after.args[2] = "github:2".to_string();
+        let updated = mcp_server_change(Some(&before), Some(&after)).unwrap();
+        assert_eq!(updated.kind, BehaviorChangeKind::McpServerUpdated);
+        assert_eq!(updated.summary, "MCP server updated: args");
+        assert_eq!(updated.changes[0].after.as_deref(), Some("run -i github:2"));
+
+        let removed = mcp_server_change(Some(&after), None).unwrap();
+        assert_eq!(removed.kind, BehaviorChangeKind::McpServerRemoved);
+    }
+
+    #[test]
+    fn test_mcp_tools_change() {
+        let tool = |name: &str| MCPTool {
+            name: name.to_string(),
+            description: String::new(),
+            input_schema: serde_json::json!({"type": "object"}),
+        };
+        let before = vec![tool("index"), tool("search")];
+        assert!(mcp_tools_change("docs", &before, &before).is_none());
+
+        let after = vec![tool("index"), tool("fetch")];
+        let change = mcp_tools_change("docs", &before, &after).unwrap();
+        assert_eq!(change.kind, BehaviorChangeKind::McpToolsChanged);
+        assert_eq!(change.summary, "MCP tools added: fetch; removed: search");
+    }
+}
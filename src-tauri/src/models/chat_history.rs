@@ -0,0 +1,377 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chat history exported by other assistants.
+//!
+//! Parses the `conversations.json` file of a ChatGPT data export (message
+//! tree in `mapping`) and of a Claude data export (`chat_messages` list)
+//! into plain conversations. Only visible text exchanged between the user
+//! and the assistant is kept: system prompts, tool calls and hidden messages
+//! are dropped.
+
+use crate::models::message::MessageRole;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Assistant that produced an export file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatHistorySource {
+    /// OpenAI ChatGPT
+    Chatgpt,
+    /// Anthropic Claude
+    Claude,
+}
+
+impl ChatHistorySource {
+    /// Name shown to the user (also used as workflow tag, lowercased)
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChatHistorySource::Chatgpt => "ChatGPT",
+            ChatHistorySource::Claude => "Claude",
+        }
+    }
+}
+
+/// Message of an imported conversation
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedMessage {
+    /// ID in the source export
+    pub external_id: String,
+    /// User or assistant
+    pub role: MessageRole,
+    /// Text content
+    pub content: String,
+    /// When the message was written
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Conversation of an export file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedConversation {
+    /// ID in the source export
+    pub external_id: String,
+    /// Conversation title
+    pub title: String,
+    /// Creation date
+    pub created_at: DateTime<Utc>,
+    /// Last activity
+    pub updated_at: DateTime<Utc>,
+    /// Messages, oldest first
+    pub messages: Vec<ImportedMessage>,
+}
+
+/// Detects the source of a parsed `conversations.json` file.
+pub fn detect_source(export: &Value) -> Option<ChatHistorySource> {
+    let first = export.as_array()?.first()?;
+    if first.get("mapping").is_some() {
+        Some(ChatHistorySource::Chatgpt)
+    } else if first.get("chat_messages").is_some() {
+        Some(ChatHistorySource::Claude)
+    } else {
+        None
+    }
+}
+
+/// Parses the conversations of an export file.
+///
+/// # Errors
+/// The file is not a list of conversations.
+pub fn parse_conversations(
+    source: ChatHistorySource,
+    export: &Value,
+) -> Result<Vec<ImportedConversation>, String> {
+    let conversations = export
+        .as_array()
+        .ok_or_else(|| "Expected a list of conversations".to_string())?;
+    Ok(conversations
+        .iter()
+        .filter_map(|conversation| match source {
+            ChatHistorySource::Chatgpt => parse_chatgpt_conversation(conversation),
+            ChatHistorySource::Claude => parse_claude_conversation(conversation),
+        })
+        .collect())
+}
+
+/// Reads an epoch timestamp in seconds (ChatGPT exports).
+fn epoch(value: &Value) -> Option<DateTime<Utc>> {
+    let seconds = value.as_f64()?;
+    Utc.timestamp_millis_opt((seconds * 1000.0) as i64).single()
+}
+
+/// Reads an RFC 3339 timestamp (Claude exports).
+fn rfc3339(value: &Value) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.as_str()?)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+fn title_or_default(title: &Value) -> String {
+    match title.as_str().map(str::trim) {
+        Some(title) if !title.is_empty() => title.to_string(),
+        _ => "Untitled conversation".to_string(),
+    }
+}
+
+/// Parses a ChatGPT conversation: follows the branch ending at `current_node`
+/// (the one shown in ChatGPT) up to the root.
+fn parse_chatgpt_conversation(conversation: &Value) -> Option<ImportedConversation> {
+    let mapping = conversation.get("mapping")?.as_object()?;
+    let created_at = epoch(&conversation["create_time"]).unwrap_or_else(Utc::now);
+
+    let mut branch = Vec::new();
+    let mut node_id = conversation["current_node"].as_str();
+    while let Some(node) = node_id.and_then(|id| mapping.get(id)) {
+        branch.push(node);
+        // Guard against cycles in malformed files
+        if branch.len() > mapping.len() {
+            break;
+        }
+        node_id = node["parent"].as_str();
+    }
+    branch.reverse();
+
+    let messages: Vec<ImportedMessage> = branch
+        .into_iter()
+        .filter_map(|node| {
+            let message = node.get("message")?;
+            let role = match message["author"]["role"].as_str()? {
+                "user" => MessageRole::User,
+                "assistant" => MessageRole::Assistant,
+                _ => return None,
+            };
+            if message["metadata"]["is_visually_hidden_from_conversation"].as_bool() == Some(true) {
+                return None;
+            }
+            let content = &message["content"];
+            if !matches!(
+                content["content_type"].as_str(),
+                Some("text" | "multimodal_text")
+            ) {
+                return None;
+            }
+            let text = content["parts"]
+                .as_array()?
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let text = text.trim();
+            (!text.is_empty()).then(|| ImportedMessage {
+                external_id: message["id"].as_str().unwrap_or_default().to_string(),
+                role,
+                content: text.to_string(),
+                timestamp: epoch(&message["create_time"]).unwrap_or(created_at),
+            })
+        })
+        .collect();
+
+    Some(ImportedConversation {
+        external_id: conversation["id"]
+            .as_str()
+            .or(conversation["conversation_id"].as_str())?
+            .to_string(),
+        title: title_or_default(&conversation["title"]),
+        created_at,
+        updated_at: epoch(&conversation["update_time"]).unwrap_or(created_at),
+        messages,
+    })
+}
+
+/// Parses a Claude conversation.
+fn parse_claude_conversation(conversation: &Value) -> Option<ImportedConversation> {
+    let created_at = rfc3339(&conversation["created_at"]).unwrap_or_else(Utc::now);
+
+    let messages = conversation["chat_messages"]
+        .as_array()?
+        .iter()
+        .filter_map(|message| {
+            let role = match message["sender"].as_str()? {
+                "human" => MessageRole::User,
+                "assistant" => MessageRole::Assistant,
+                _ => return None,
+            };
+            // Newer exports split the text into typed blocks, older ones only have `text`
+            let blocks: Vec<&str> = message["content"]
+                .as_array()
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter(|block| block["type"] == "text")
+                        .filter_map(|block| block["text"].as_str())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let text = if blocks.is_empty() {
+                message["text"].as_str().unwrap_or_default().to_string()
+            } else {
+                blocks.join("\n\n")
+            };
+            let text = text.trim();
+            (!text.is_empty()).then(|| ImportedMessage {
+                external_id: message["uuid"].as_str().unwrap_or_default().to_string(),
+                role,
+                content: text.to_string(),
+                timestamp: rfc3339(&message["created_at"]).unwrap_or(created_at),
+            })
+        })
+        .collect();
+
+    Some(ImportedConversation {
+        external_id: conversation["uuid"].as_str()?.to_string(),
+        title: title_or_default(&conversation["name"]),
+        created_at,
+        updated_at: rfc3339(&conversation["updated_at"]).unwrap_or(created_at),
+        messages,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chatgpt_export() -> Value {
+        json!([{
+            "id": "conv-1",
+            "title": "Trip to Lisbon",
+            "create_time": 1_700_000_000.5,
+            "update_time": 1_700_000_300.0,
+            "current_node": "a2",
+            "mapping": {
+                "root": { "id": "root", "message": null, "parent": null, "children": ["sys"] },
+                "sys": {
+                    "id": "sys",
+                    "message": {
+                        "id": "sys",
+                        "author": { "role": "system" },
+                        "content": { "content_type": "text", "parts": [""] },
+                        "metadata": { "is_visually_hidden_from_conversation": true }
+                    },
+                    "parent": "root",
+                    "children": ["u1"]
+                },
+                "u1": {
+                    "id": "u1",
+                    "message": {
+                        "id": "u1",
+                        "author": { "role": "user" },
+                        "create_time": 1_700_000_010.0,
+                        "content": { "content_type": "text", "parts": ["Where to eat?"] },
+                        "metadata": {}
+                    },
+                    "parent": "sys",
+                    "children": ["a1", "a2"]
+                },
+                "a1": {
+                    "id": "a1",
+                    "message": {
+                        "id": "a1",
+                        "author": { "role": "assistant" },
+                        "content": { "content_type": "text", "parts": ["Discarded draft"] },
+                        "metadata": {}
+                    },
+                    "parent": "u1",
+                    "children": []
+                },
+                "a2": {
+                    "id": "a2",
+                    "message": {
+                        "id": "a2",
+                        "author": { "role": "assistant" },
+                        "create_time": 1_700_000_020.0,
+                        "content": { "content_type": "text", "parts": ["Try Alfama."] },
+                        "metadata": {}
+                    },
+                    "parent": "u1",
+                    "children": []
+                }
+            }
+        }])
+    }
+
+    #[test]
+    fn test_parse_chatgpt_current_branch() {
+        let export = chatgpt_export();
+        assert_eq!(detect_source(&export), Some(ChatHistorySource::Chatgpt));
+
+        let conversations = parse_conversations(ChatHistorySource::Chatgpt, &export).unwrap();
+        assert_eq!(conversations.len(), 1);
+        let conversation = &conversations[0];
+        assert_eq!(conversation.title, "Trip to Lisbon");
+        assert_eq!(
+            conversation.created_at.timestamp_millis(),
+            1_700_000_000_500
+        );
+
+        let contents: Vec<&str> = conversation
+            .messages
+            .iter()
+            .map(|m| m.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["Where to eat?", "Try Alfama."]);
+        assert_eq!(conversation.messages[1].role, MessageRole::Assistant);
+    }
+
+    #[test]
+    fn test_parse_claude_conversation() {
+        let export = json!([{
+            "uuid": "c-1",
+            "name": "",
+            "created_at": "2024-05-01T10:00:00.000000Z",
+            "updated_at": "2024-05-01T10:05:00Z",
+            "chat_messages": [
+                {
+                    "uuid": "m-1",
+                    "sender": "human",
+                    "text": "Explain borrowing",
+                    "created_at": "2024-05-01T10:00:01Z"
+                },
+                {
+                    "uuid": "m-2",
+                    "sender": "assistant",
+                    "text": "",
+                    "content": [
+                        { "type": "text", "text": "References borrow a value." },
+                        { "type": "tool_use", "name": "search" }
+                    ],
+                    "created_at": "2024-05-01T10:00:09Z"
+                },
+                { "uuid": "m-3", "sender": "assistant", "text": "  " }
+            ]
+        }]);
+        assert_eq!(detect_source(&export), Some(ChatHistorySource::Claude));
+
+        let conversations = parse_conversations(ChatHistorySource::Claude, &export).unwrap();
+        let conversation = &conversations[0];
+        assert_eq!(conversation.title, "Untitled conversation");
+        assert_eq!(conversation.messages.len(), 2);
+        assert_eq!(
+            conversation.messages[1].content,
+            "References borrow a value."
+        );
+        assert_eq!(
+            conversation.messages[0].timestamp,
+            rfc3339(&json!("2024-05-01T10:00:01Z")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_detect_and_reject_unknown_files() {
+        assert_eq!(detect_source(&json!([{ "foo": 1 }])), None);
+        assert_eq!(detect_source(&json!({ "mapping": {} })), None);
+        assert!(parse_conversations(ChatHistorySource::Claude, &json!({})).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow execution checkpoint models.
+//!
+//! While the primary agent of a workflow runs its tool loop, the loop state is
+//! saved after each step in the `workflow_checkpoint` table (one record per
+//! workflow). A checkpoint left behind by a crash lets the execution resume
+//! from its last step instead of starting over.
+//! Synchronized with src/types/checkpoint.ts
+
+use crate::models::function_calling::FunctionCall;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Loop state of an interrupted execution (stored encrypted for a sensitive workflow)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointState {
+    /// Task being executed (the user message)
+    pub task: String,
+    /// Task context without the conversation (locale, response language...)
+    pub context: serde_json::Value,
+    /// Messages sent to the LLM (API-native format), including tool results
+    pub messages: Vec<serde_json::Value>,
+    /// Tool calls requested by the last LLM response and not executed yet
+    pub pending_tool_calls: Vec<FunctionCall>,
+    /// True if the execution started the workflow (its system prompt is not stored yet)
+    pub first_message: bool,
+    /// Input tokens of the last LLM call
+    pub tokens_input: usize,
+    /// Output tokens generated so far
+    pub tokens_output: usize,
+    /// Local tools used so far
+    pub tools_used: Vec<String>,
+    /// MCP tools called so far
+    pub mcp_calls: Vec<String>,
+}
+
+/// Checkpoint of the primary agent execution of a workflow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowCheckpoint {
+    /// Workflow being executed
+    pub workflow_id: String,
+    /// Agent executing the task
+    pub agent_id: String,
+    /// Last tool loop iteration started
+    pub iteration: usize,
+    /// Loop state
+    pub state: CheckpointState,
+}
+
+/// Interrupted execution that can be resumed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumableExecution {
+    /// Workflow of the execution
+    pub workflow_id: String,
+    /// Agent executing the task
+    pub agent_id: String,
+    /// Last tool loop iteration started
+    pub iteration: usize,
+    /// Tools requested and not executed yet when the execution stopped
+    pub pending_tools: Vec<String>,
+    /// Time of the last checkpoint
+    pub updated_at: DateTime<Utc>,
+}
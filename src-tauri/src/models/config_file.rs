@@ -0,0 +1,182 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Config file models.
+//!
+//! The optional `~/.zileo/config.toml` overrides startup settings for
+//! headless setups and power users (see [`crate::config_file`]). Every key is
+//! optional; unknown keys are rejected so that typos are reported.
+
+use crate::models::proxy::ProxySettings;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Content of `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FileConfig {
+    /// Database directory of the default profile
+    pub db_path: Option<PathBuf>,
+    /// Log filter (`RUST_LOG` syntax, e.g. "debug" or "zileo_chat=trace,warn")
+    pub log_level: Option<String>,
+    /// Provider selected at startup ("mistral", "ollama", "gemini" or a custom provider name)
+    pub default_provider: Option<String>,
+    /// Proxy of the outgoing requests (LLM providers, embeddings, MCP servers),
+    /// replacing the proxy settings saved in the app
+    pub proxy: Option<ProxySettings>,
+    /// Feature flags (unset flags keep their default)
+    pub features: FeatureFlags,
+}
+
+/// Startup subsystems that can be disabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// Runner of the scheduled workflow runs
+    Scheduler,
+    /// Runner of the background jobs
+    BackgroundJobs,
+    /// Connection of the enabled MCP servers at startup
+    McpAutostart,
+    /// Mailbox server of the local network settings sync
+    LanSync,
+}
+
+impl Feature {
+    /// All features
+    pub const ALL: [Self; 4] = [
+        Self::Scheduler,
+        Self::BackgroundJobs,
+        Self::McpAutostart,
+        Self::LanSync,
+    ];
+
+    /// Key in the `[features]` table
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Scheduler => "scheduler",
+            Self::BackgroundJobs => "background_jobs",
+            Self::McpAutostart => "mcp_autostart",
+            Self::LanSync => "lan_sync",
+        }
+    }
+}
+
+/// `[features]` table of `config.toml` (all features enabled by default)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureFlags {
+    /// Run the scheduled workflow runs
+    pub scheduler: Option<bool>,
+    /// Run the background jobs
+    pub background_jobs: Option<bool>,
+    /// Connect the enabled MCP servers at startup
+    pub mcp_autostart: Option<bool>,
+    /// Serve the local network settings sync mailbox
+    pub lan_sync: Option<bool>,
+}
+
+impl FeatureFlags {
+    /// Value set in the file, if any.
+    pub fn get(&self, feature: Feature) -> Option<bool> {
+        match feature {
+            Feature::Scheduler => self.scheduler,
+            Feature::BackgroundJobs => self.background_jobs,
+            Feature::McpAutostart => self.mcp_autostart,
+            Feature::LanSync => self.lan_sync,
+        }
+    }
+
+    /// Whether a feature is enabled.
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.get(feature).unwrap_or(true)
+    }
+}
+
+/// Where an effective setting comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    /// Built-in default or value saved in the app
+    Default,
+    /// `config.toml`
+    ConfigFile,
+    /// Environment variable (takes precedence over the file)
+    Environment,
+}
+
+/// Setting in effect, as returned by `get_effective_config`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveSetting {
+    /// Setting key, as written in `config.toml` (e.g. "proxy.url", "features.scheduler")
+    pub key: String,
+    /// Value in effect
+    pub value: serde_json::Value,
+    /// Where the value comes from
+    pub source: ConfigSource,
+}
+
+/// Effective startup configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveConfig {
+    /// Path of the config file
+    pub path: String,
+    /// Whether the file exists and was loaded
+    pub loaded: bool,
+    /// Error reading or parsing the file (the file is then ignored)
+    pub error: Option<String>,
+    /// Settings in effect
+    pub settings: Vec<EffectiveSetting>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::proxy::ProxyOverride;
+
+    #[test]
+    fn test_parse_file_config() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            db_path = "/srv/zileo/db"
+            log_level = "debug"
+            default_provider = "ollama"
+
+            [proxy]
+            url = "http://proxy.local:3128"
+            no_proxy = ["localhost"]
+
+            [proxy.overrides]
+            ollama = { mode = "direct" }
+
+            [features]
+            scheduler = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.db_path, Some(PathBuf::from("/srv/zileo/db")));
+        let proxy = config.proxy.unwrap();
+        assert_eq!(proxy.no_proxy, vec!["localhost"]);
+        assert_eq!(proxy.overrides["ollama"], ProxyOverride::Direct);
+        assert!(!config.features.is_enabled(Feature::Scheduler));
+        assert!(config.features.is_enabled(Feature::LanSync));
+        assert_eq!(config.features.get(Feature::LanSync), None);
+
+        assert_eq!(
+            toml::from_str::<FileConfig>("").unwrap(),
+            FileConfig::default()
+        );
+        assert!(toml::from_str::<FileConfig>("log_levl = \"debug\"").is_err());
+        assert!(toml::from_str::<FileConfig>("[features]\nschedular = false").is_err());
+    }
+}
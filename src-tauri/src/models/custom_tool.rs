@@ -0,0 +1,74 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Custom script tool models.
+//!
+//! Tools defined by the user as a Rhai script and a JSON Schema of their
+//! input, stored in the `custom_tool` table and exposed to agents as
+//! `custom__<name>`.
+//! Synchronized with src/types/customTool.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Custom tool stored in the database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomTool {
+    /// Custom tool ID (UUID)
+    pub id: String,
+    /// Tool name (lowercase letters, digits and `_`), fixed at creation
+    pub name: String,
+    /// Description for the LLM
+    pub description: String,
+    /// JSON Schema of the input (an object)
+    pub input_schema: Value,
+    /// Rhai script; the input is the `input` variable and the value of the
+    /// last expression is the result
+    pub script: String,
+    /// Whether agents can use the tool
+    pub enabled: bool,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Payload for creating a custom tool (ID and timestamps are generated
+/// server-side).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomToolCreate {
+    /// Tool name
+    pub name: String,
+    /// Description for the LLM
+    pub description: String,
+    /// JSON Schema of the input
+    pub input_schema: Value,
+    /// Rhai script
+    pub script: String,
+}
+
+/// Payload for updating a custom tool (all fields optional; the name cannot
+/// change since agents refer to the tool by name)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CustomToolUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_schema: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
@@ -0,0 +1,37 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Desktop access permission models.
+//!
+//! [`DesktopAccessSettings`] are stored in the `settings:desktop_access`
+//! record and checked by the ClipboardTool, ScreenshotTool, PdfTool and
+//! OcrTool on every call.
+//! Synchronized with src/types/desktop-access.ts
+
+use serde::{Deserialize, Serialize};
+
+/// Permissions of the desktop access tools (all disabled by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DesktopAccessSettings {
+    /// Agents may read and write the system clipboard (ClipboardTool)
+    #[serde(default)]
+    pub clipboard: bool,
+    /// Agents may capture the screen and windows (ScreenshotTool)
+    #[serde(default)]
+    pub screenshot: bool,
+    /// Agents may read local PDF files and images and save generated PDFs
+    /// (PdfTool, OcrTool)
+    #[serde(default)]
+    pub documents: bool,
+}
@@ -0,0 +1,98 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diagnostics bundle attached to bug reports.
+//!
+//! The bundle is a zip archive of recent logs, schema version, provider and
+//! MCP server health, database statistics and configuration, with secrets
+//! stripped (see [`crate::diagnostics`]).
+
+use crate::models::mcp::{MCPDeploymentMethod, MCPServerStatus};
+use serde::{Deserialize, Serialize};
+
+/// Bundle written by the `generate_diagnostics` command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiagnosticsBundle {
+    /// Path of the zip archive
+    pub path: String,
+    /// Size of the archive in bytes
+    pub size_bytes: u64,
+    /// Files of the archive
+    pub files: Vec<String>,
+}
+
+/// Schema version of the database
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaInfo {
+    /// Application version
+    pub app_version: String,
+    /// Fingerprint of the schema of this build
+    pub expected_fingerprint: String,
+    /// Fingerprint of the schema applied to the database
+    pub applied_fingerprint: Option<String>,
+    /// Whether the applied schema is the one of this build
+    pub up_to_date: bool,
+}
+
+/// Health of an LLM provider
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderHealth {
+    /// Provider name
+    pub provider: String,
+    /// Whether the provider is configured
+    pub configured: bool,
+    /// Whether the provider is the active one
+    pub active: bool,
+    /// Circuit breaker state (Closed, Open, HalfOpen), once requests were made
+    pub circuit_state: Option<String>,
+    /// Consecutive failed requests
+    pub consecutive_failures: u32,
+}
+
+/// Health of an MCP server (configuration without env values)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpServerHealth {
+    /// Server ID
+    pub id: String,
+    /// Server name
+    pub name: String,
+    /// Whether the server is enabled
+    pub enabled: bool,
+    /// Deployment method
+    pub command: MCPDeploymentMethod,
+    /// Names of the environment variables (values are never included)
+    pub env_keys: Vec<String>,
+    /// Runtime status
+    pub status: MCPServerStatus,
+    /// Number of discovered tools
+    pub tool_count: usize,
+}
+
+/// Record count of a database table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableStats {
+    /// Table name
+    pub table: String,
+    /// Number of records
+    pub count: u64,
+}
+
+/// Database statistics
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatabaseStats {
+    /// Size of the database directory on disk in bytes
+    pub size_bytes: u64,
+    /// Record count of each table
+    pub tables: Vec<TableStats>,
+}
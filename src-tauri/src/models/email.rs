@@ -0,0 +1,121 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Email account models.
+//!
+//! Accounts the EmailTool reads (IMAP) and sends from (SMTP). Passwords are
+//! stored separately in SecureKeyStore.
+//! Synchronized with src/types/email.ts
+
+use serde::{Deserialize, Serialize};
+
+fn default_read_only() -> bool {
+    true
+}
+
+/// Email account metadata stored in the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailAccount {
+    /// Identifier used by agents (lowercase letters, digits, `-` and `_`)
+    pub name: String,
+    /// Address messages are sent from
+    pub address: String,
+    /// Name shown to recipients
+    #[serde(default)]
+    pub display_name: Option<String>,
+    /// IMAP server (implicit TLS)
+    pub imap_host: String,
+    pub imap_port: u16,
+    /// SMTP server (implicit TLS, or STARTTLS on port 587)
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    /// Login of both servers
+    pub username: String,
+    /// Rejects sending, even confirmed drafts (default)
+    #[serde(default = "default_read_only")]
+    pub read_only: bool,
+    /// Creation timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+/// Input to create or update an email account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailAccountInput {
+    /// Identifier used by agents
+    pub name: String,
+    /// Address messages are sent from
+    pub address: String,
+    /// Name shown to recipients
+    #[serde(default)]
+    pub display_name: Option<String>,
+    pub imap_host: String,
+    /// IMAP port (default when omitted)
+    #[serde(default)]
+    pub imap_port: Option<u16>,
+    pub smtp_host: String,
+    /// SMTP port (default when omitted)
+    #[serde(default)]
+    pub smtp_port: Option<u16>,
+    /// Login of both servers (the address when omitted)
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Rejects sending (default)
+    #[serde(default = "default_read_only")]
+    pub read_only: bool,
+}
+
+/// Summary of a message listed by read_inbox
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EmailSummary {
+    /// Message UID in the mailbox (passed to read_message)
+    pub uid: u32,
+    pub from: String,
+    pub subject: String,
+    pub date: Option<String>,
+    /// Whether the message was read
+    pub seen: bool,
+    /// Start of the text body
+    pub preview: String,
+}
+
+/// Message returned by read_message
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EmailMessage {
+    pub uid: u32,
+    pub from: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub date: Option<String>,
+    /// `Message-ID` header, passed as `in_reply_to` to answer the message
+    pub message_id: Option<String>,
+    /// Text body (HTML converted to text)
+    pub body: String,
+    /// Whether the body was truncated
+    pub truncated: bool,
+    /// File names of the attachments (not downloaded)
+    pub attachments: Vec<String>,
+}
+
+/// Message to send, confirmed by the user
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EmailDraft {
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    pub subject: String,
+    pub body: String,
+    /// `Message-ID` of the answered message
+    pub in_reply_to: Option<String>,
+}
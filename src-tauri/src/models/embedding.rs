@@ -93,6 +93,39 @@ pub struct RegenerateResult {
     pub failed: usize,
 }
 
+/// Progress of an embedding regeneration.
+///
+/// Emitted as the `embedding_regeneration_progress` event and stored as the
+/// checkpoint of an interrupted run, which can be resumed after `cursor`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegenerateProgress {
+    /// Memory type filter of the run
+    pub type_filter: Option<String>,
+    /// Only memories without embedding (dimension migration)
+    #[serde(default)]
+    pub missing_only: bool,
+    /// ID of the last memory of the completed pages
+    pub cursor: Option<String>,
+    /// Number of memories to regenerate
+    pub total: usize,
+    /// Number of memories processed
+    pub processed: usize,
+    /// Number of embeddings successfully generated
+    pub success: usize,
+    /// Number of failures
+    pub failed: usize,
+}
+
+impl From<RegenerateProgress> for RegenerateResult {
+    fn from(progress: RegenerateProgress) -> Self {
+        Self {
+            processed: progress.processed,
+            success: progress.success,
+            failed: progress.failed,
+        }
+    }
+}
+
 /// Result of embedding test operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EmbeddingTestResult {
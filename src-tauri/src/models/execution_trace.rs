@@ -0,0 +1,367 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution trace models.
+//!
+//! Each LLM call of the tool loop is persisted as an [`LlmTurn`]. Together
+//! with the tool executions of the same message, turns are assembled into an
+//! [`ExecutionTrace`] graph (LLM turn -> tool calls -> next turn -> response)
+//! returned by the `get_execution_trace` command.
+
+use crate::models::tool_execution::{ToolExecution, ToolType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// LLM call entity recorded during the tool loop.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmTurn {
+    /// Unique identifier (UUID)
+    pub id: String,
+    /// Associated workflow ID
+    pub workflow_id: String,
+    /// Associated message ID (the assistant message this call contributed to)
+    pub message_id: String,
+    /// Agent ID that made the call
+    pub agent_id: String,
+    /// Iteration number in the tool loop (1-indexed, matches tool executions)
+    pub iteration: u32,
+    /// Input tokens of this call
+    pub tokens_input: u64,
+    /// Output tokens of this call
+    pub tokens_output: u64,
+    /// Call duration in milliseconds
+    pub duration_ms: u64,
+    /// Number of tool calls requested by this call
+    pub tool_calls: u32,
+    /// Unix time (milliseconds) at which the call started (None for turns
+    /// recorded before start times were persisted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at_ms: Option<u64>,
+    /// Timestamp when the turn was recorded
+    pub created_at: DateTime<Utc>,
+}
+
+/// Payload for creating a new LLM turn record.
+///
+/// ID and created_at are generated server-side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmTurnCreate {
+    /// Associated workflow ID
+    pub workflow_id: String,
+    /// Associated message ID
+    pub message_id: String,
+    /// Agent ID
+    pub agent_id: String,
+    /// Iteration number in the tool loop
+    pub iteration: u32,
+    /// Input tokens of this call
+    pub tokens_input: u64,
+    /// Output tokens of this call
+    pub tokens_output: u64,
+    /// Call duration in milliseconds
+    pub duration_ms: u64,
+    /// Number of tool calls requested by this call
+    pub tool_calls: u32,
+    /// Unix time (milliseconds) at which the call started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at_ms: Option<u64>,
+}
+
+/// Kind of node in an execution trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceNodeKind {
+    /// LLM call
+    LlmTurn,
+    /// Tool call (local or MCP)
+    ToolCall,
+    /// Final response
+    Response,
+}
+
+/// Node of an execution trace graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceNode {
+    /// Node ID (unique within the trace)
+    pub id: String,
+    /// Node kind
+    pub kind: TraceNodeKind,
+    /// Display label (turn number, tool name)
+    pub label: String,
+    /// Tool loop iteration the node belongs to
+    pub iteration: u32,
+    /// Duration in milliseconds (None when not recorded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Input tokens (LLM turns only, None when not recorded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_input: Option<u64>,
+    /// Output tokens (LLM turns only, None when not recorded)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens_output: Option<u64>,
+    /// Whether the tool call succeeded (tool calls only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+    /// Tool execution record ID (tool calls only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_execution_id: Option<String>,
+}
+
+/// Directed edge between two trace nodes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEdge {
+    /// Source node ID
+    pub from: String,
+    /// Target node ID
+    pub to: String,
+}
+
+/// Execution flow graph of a single assistant message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    /// Workflow ID
+    pub workflow_id: String,
+    /// Assistant message ID
+    pub message_id: String,
+    /// Graph nodes in execution order
+    pub nodes: Vec<TraceNode>,
+    /// Graph edges
+    pub edges: Vec<TraceEdge>,
+    /// Number of tool loop iterations
+    pub iterations: u32,
+    /// Sum of node durations in milliseconds
+    pub total_duration_ms: u64,
+    /// Sum of LLM turn input tokens
+    pub tokens_input: u64,
+    /// Sum of LLM turn output tokens
+    pub tokens_output: u64,
+}
+
+/// Response node ID
+const RESPONSE_NODE_ID: &str = "response";
+
+impl ExecutionTrace {
+    /// Builds the trace graph from the recorded turns and tool executions.
+    ///
+    /// Iterations that only have tool executions (messages recorded before
+    /// turns were persisted) get an LLM turn node without timings or tokens.
+    pub fn build(
+        workflow_id: String,
+        message_id: String,
+        turns: &[LlmTurn],
+        tool_executions: &[ToolExecution],
+    ) -> Self {
+        let iterations: BTreeSet<u32> = turns
+            .iter()
+            .map(|t| t.iteration)
+            .chain(tool_executions.iter().map(|te| te.iteration))
+            .collect();
+
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        // Nodes whose outgoing edge points to the next turn (or the response)
+        let mut pending: Vec<String> = Vec::new();
+
+        for iteration in &iterations {
+            let turn = turns.iter().find(|t| t.iteration == *iteration);
+            let turn_id = format!("turn-{}", iteration);
+            nodes.push(TraceNode {
+                id: turn_id.clone(),
+                kind: TraceNodeKind::LlmTurn,
+                label: format!("LLM turn {}", iteration),
+                iteration: *iteration,
+                duration_ms: turn.map(|t| t.duration_ms),
+                tokens_input: turn.map(|t| t.tokens_input),
+                tokens_output: turn.map(|t| t.tokens_output),
+                success: None,
+                tool_execution_id: None,
+            });
+            edges.extend(pending.drain(..).map(|from| TraceEdge {
+                from,
+                to: turn_id.clone(),
+            }));
+
+            let calls: Vec<&ToolExecution> = tool_executions
+                .iter()
+                .filter(|te| te.iteration == *iteration)
+                .collect();
+            if calls.is_empty() {
+                pending.push(turn_id);
+                continue;
+            }
+            for call in calls {
+                let tool_id = format!("tool-{}", call.id);
+                nodes.push(TraceNode {
+                    id: tool_id.clone(),
+                    kind: TraceNodeKind::ToolCall,
+                    label: tool_label(call),
+                    iteration: *iteration,
+                    duration_ms: Some(call.duration_ms),
+                    tokens_input: None,
+                    tokens_output: None,
+                    success: Some(call.success),
+                    tool_execution_id: Some(call.id.clone()),
+                });
+                edges.push(TraceEdge {
+                    from: turn_id.clone(),
+                    to: tool_id.clone(),
+                });
+                pending.push(tool_id);
+            }
+        }
+
+        if !nodes.is_empty() {
+            let last_iteration = iterations.last().copied().unwrap_or(0);
+            nodes.push(TraceNode {
+                id: RESPONSE_NODE_ID.to_string(),
+                kind: TraceNodeKind::Response,
+                label: "Response".to_string(),
+                iteration: last_iteration,
+                duration_ms: None,
+                tokens_input: None,
+                tokens_output: None,
+                success: None,
+                tool_execution_id: None,
+            });
+            edges.extend(pending.drain(..).map(|from| TraceEdge {
+                from,
+                to: RESPONSE_NODE_ID.to_string(),
+            }));
+        }
+
+        Self {
+            workflow_id,
+            message_id,
+            iterations: iterations.len() as u32,
+            total_duration_ms: nodes.iter().filter_map(|n| n.duration_ms).sum(),
+            tokens_input: nodes.iter().filter_map(|n| n.tokens_input).sum(),
+            tokens_output: nodes.iter().filter_map(|n| n.tokens_output).sum(),
+            nodes,
+            edges,
+        }
+    }
+}
+
+/// Display label of a tool call node (`server:tool` for MCP tools).
+fn tool_label(execution: &ToolExecution) -> String {
+    match (&execution.tool_type, &execution.server_name) {
+        (ToolType::Mcp, Some(server)) => format!("{}:{}", server, execution.tool_name),
+        _ => execution.tool_name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn turn(iteration: u32, tokens_input: u64, tool_calls: u32) -> LlmTurn {
+        LlmTurn {
+            id: format!("t{}", iteration),
+            workflow_id: "wf".to_string(),
+            message_id: "msg".to_string(),
+            agent_id: "agent".to_string(),
+            iteration,
+            tokens_input,
+            tokens_output: 10,
+            duration_ms: 100,
+            tool_calls,
+            started_at_ms: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn tool(id: &str, iteration: u32, success: bool) -> ToolExecution {
+        ToolExecution {
+            id: id.to_string(),
+            workflow_id: "wf".to_string(),
+            message_id: "msg".to_string(),
+            agent_id: "agent".to_string(),
+            tool_type: ToolType::Mcp,
+            tool_name: "find_symbol".to_string(),
+            server_name: Some("serena".to_string()),
+            input_params: json!({}),
+            output_result: json!({}),
+            success,
+            error_message: None,
+            duration_ms: 50,
+            iteration,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn edge(from: &str, to: &str) -> TraceEdge {
+        TraceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_tool_loop_graph() {
+        let turns = vec![turn(1, 1000, 2), turn(2, 1500, 0)];
+        let tools = vec![tool("a", 1, true), tool("b", 1, false)];
+
+        let trace = ExecutionTrace::build("wf".to_string(), "msg".to_string(), &turns, &tools);
+
+        let ids: Vec<&str> = trace.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["turn-1", "tool-a", "tool-b", "turn-2", "response"]
+        );
+        assert_eq!(
+            trace.edges,
+            vec![
+                edge("turn-1", "tool-a"),
+                edge("turn-1", "tool-b"),
+                edge("tool-a", "turn-2"),
+                edge("tool-b", "turn-2"),
+                edge("turn-2", "response"),
+            ]
+        );
+        assert_eq!(trace.iterations, 2);
+        assert_eq!(trace.tokens_input, 2500);
+        assert_eq!(trace.tokens_output, 20);
+        assert_eq!(trace.total_duration_ms, 300);
+        assert_eq!(trace.nodes[1].label, "serena:find_symbol");
+        assert_eq!(trace.nodes[2].success, Some(false));
+    }
+
+    #[test]
+    fn test_build_without_recorded_turns() {
+        let tools = vec![tool("a", 1, true)];
+
+        let trace = ExecutionTrace::build("wf".to_string(), "msg".to_string(), &[], &tools);
+
+        assert_eq!(trace.nodes.len(), 3);
+        assert_eq!(trace.nodes[0].kind, TraceNodeKind::LlmTurn);
+        assert!(trace.nodes[0].tokens_input.is_none());
+        assert_eq!(
+            trace.edges,
+            vec![edge("turn-1", "tool-a"), edge("tool-a", "response")]
+        );
+    }
+
+    #[test]
+    fn test_build_empty() {
+        let trace = ExecutionTrace::build("wf".to_string(), "msg".to_string(), &[], &[]);
+        assert!(trace.nodes.is_empty());
+        assert!(trace.edges.is_empty());
+        assert_eq!(trace.iterations, 0);
+    }
+}
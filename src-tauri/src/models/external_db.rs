@@ -0,0 +1,128 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External database connector models.
+//!
+//! Metadata of the external databases (PostgreSQL, MySQL, SQLite) the SqlTool
+//! can query. Connection strings are stored separately in SecureKeyStore.
+//! Synchronized with src/types/external-db.ts
+
+use serde::{Deserialize, Serialize};
+
+/// Database engine of an external database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalDbKind {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+impl ExternalDbKind {
+    /// Detects the engine from the scheme of a connection string.
+    pub fn from_connection_string(connection_string: &str) -> Option<Self> {
+        let scheme = connection_string.split(':').next()?.to_ascii_lowercase();
+        match scheme.as_str() {
+            "postgres" | "postgresql" => Some(Self::Postgres),
+            "mysql" | "mariadb" => Some(Self::Mysql),
+            "sqlite" => Some(Self::Sqlite),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for ExternalDbKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Postgres => write!(f, "postgres"),
+            Self::Mysql => write!(f, "mysql"),
+            Self::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// External database metadata stored in the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalDatabase {
+    /// Identifier used by agents (lowercase letters, digits, `-` and `_`)
+    pub name: String,
+    /// Database engine, detected from the connection string
+    pub kind: ExternalDbKind,
+    /// What the database contains (shown to agents)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Rejects write statements, even confirmed ones
+    #[serde(default)]
+    pub read_only: bool,
+    /// Maximum rows returned by a query
+    pub max_rows: usize,
+    /// Creation timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+/// Input to create or update an external database.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExternalDatabaseInput {
+    /// Identifier used by agents
+    pub name: String,
+    /// What the database contains
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Rejects write statements
+    #[serde(default)]
+    pub read_only: bool,
+    /// Maximum rows returned by a query (default when omitted)
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+}
+
+/// Rows returned by a read statement
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SqlQueryResult {
+    /// Column names, in order
+    pub columns: Vec<String>,
+    /// Row values, in column order
+    pub rows: Vec<Vec<serde_json::Value>>,
+    /// Number of rows returned
+    pub row_count: usize,
+    /// Whether more rows matched than the row limit
+    pub truncated: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_from_connection_string() {
+        assert_eq!(
+            ExternalDbKind::from_connection_string("postgresql://user@host/db"),
+            Some(ExternalDbKind::Postgres)
+        );
+        assert_eq!(
+            ExternalDbKind::from_connection_string("MySQL://host/db"),
+            Some(ExternalDbKind::Mysql)
+        );
+        assert_eq!(
+            ExternalDbKind::from_connection_string("sqlite:///tmp/data.db"),
+            Some(ExternalDbKind::Sqlite)
+        );
+        assert_eq!(
+            ExternalDbKind::from_connection_string("mongodb://host"),
+            None
+        );
+        assert_eq!(ExternalDbKind::from_connection_string("no scheme"), None);
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! News feed models.
+//!
+//! RSS/Atom feeds read by the RssTool and the items already returned to
+//! agents (used to report only new items).
+//! Synchronized with src/types/feed.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Feed stored in the database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Feed {
+    /// Feed ID (UUID)
+    pub id: String,
+    /// Feed URL (http or https)
+    pub url: String,
+    /// Feed title, read when the feed was added
+    pub title: Option<String>,
+    /// Website of the feed
+    pub site_url: Option<String>,
+    /// Last fetch by the RssTool
+    pub last_fetched_at: Option<DateTime<Utc>>,
+    /// Error of the last fetch, if it failed
+    pub last_error: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Payload for creating a feed (ID and timestamps are generated server-side).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedCreate {
+    /// Feed URL
+    pub url: String,
+    /// Feed title
+    pub title: Option<String>,
+    /// Website of the feed
+    pub site_url: Option<String>,
+}
+
+/// Item of a feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeedItem {
+    /// Feed of the item
+    pub feed_id: String,
+    /// Stable identifier of the item (guid, id or link)
+    pub guid: String,
+    /// Item title
+    pub title: String,
+    /// Link to the article
+    pub link: Option<String>,
+    /// Plain text summary (truncated)
+    pub summary: Option<String>,
+    /// Publication date as given by the feed (RFC 3339 when it could be parsed)
+    pub published_at: Option<String>,
+}
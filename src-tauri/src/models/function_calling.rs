@@ -41,7 +41,7 @@ use serde::{Deserialize, Serialize};
 ///   }
 /// }
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FunctionCall {
     /// Unique identifier for this call, generated by the provider.
     /// Mistral provides this; Ollama requires synthetic ID generation.
@@ -0,0 +1,189 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-request generation parameters.
+//!
+//! A message may override the sampling settings of the agent for a single
+//! execution. The values actually used are recorded on the assistant message.
+//! Synchronized with src/types/generation.ts
+
+use super::agent::LLMConfig;
+use crate::tools::constants::commands as cmd_const;
+use serde::{Deserialize, Serialize};
+
+/// Generation parameters of a single request.
+///
+/// Unset fields fall back to the agent LLM configuration (temperature,
+/// max tokens) or to the provider default (top_p, stop sequences).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GenerationParams {
+    /// Sampling temperature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling probability mass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Maximum tokens to generate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+    /// Sequences that stop generation when produced
+    #[serde(default)]
+    pub stop: Vec<String>,
+}
+
+impl GenerationParams {
+    /// Validates the overrides against the bounds accepted for agents.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(temperature) = self.temperature {
+            if !(cmd_const::MIN_TEMPERATURE..=cmd_const::MAX_TEMPERATURE).contains(&temperature) {
+                return Err(format!(
+                    "Temperature must be between {} and {}",
+                    cmd_const::MIN_TEMPERATURE,
+                    cmd_const::MAX_TEMPERATURE
+                ));
+            }
+        }
+        if let Some(top_p) = self.top_p {
+            if !(top_p > 0.0 && top_p <= 1.0) {
+                return Err("top_p must be greater than 0 and at most 1".to_string());
+            }
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            if !(cmd_const::MIN_MAX_TOKENS..=cmd_const::MAX_MAX_TOKENS).contains(&max_tokens) {
+                return Err(format!(
+                    "max_tokens must be between {} and {}",
+                    cmd_const::MIN_MAX_TOKENS,
+                    cmd_const::MAX_MAX_TOKENS
+                ));
+            }
+        }
+        if self.stop.len() > cmd_const::MAX_STOP_SEQUENCES {
+            return Err(format!(
+                "At most {} stop sequences are allowed",
+                cmd_const::MAX_STOP_SEQUENCES
+            ));
+        }
+        for sequence in &self.stop {
+            if sequence.is_empty() {
+                return Err("Stop sequences cannot be empty".to_string());
+            }
+            if sequence.chars().count() > cmd_const::MAX_STOP_SEQUENCE_LEN {
+                return Err(format!(
+                    "Stop sequences cannot exceed {} characters",
+                    cmd_const::MAX_STOP_SEQUENCE_LEN
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parameters actually used with an agent configuration (temperature and
+    /// max tokens resolved from the agent when not overridden).
+    pub fn effective(&self, llm: &LLMConfig) -> Self {
+        Self {
+            temperature: Some(self.temperature.unwrap_or(llm.temperature)),
+            top_p: self.top_p,
+            max_tokens: Some(self.max_tokens.unwrap_or(llm.max_tokens)),
+            stop: self.stop.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn llm() -> LLMConfig {
+        LLMConfig {
+            provider: "Mistral".to_string(),
+            model: "mistral-large-latest".to_string(),
+            temperature: 0.7,
+            max_tokens: 4096,
+        }
+    }
+
+    #[test]
+    fn test_validate_bounds() {
+        assert!(GenerationParams::default().validate().is_ok());
+        let valid = GenerationParams {
+            temperature: Some(0.2),
+            top_p: Some(0.9),
+            max_tokens: Some(1024),
+            stop: vec!["###".to_string()],
+        };
+        assert!(valid.validate().is_ok());
+
+        let cases = [
+            GenerationParams {
+                temperature: Some(2.5),
+                ..Default::default()
+            },
+            GenerationParams {
+                top_p: Some(0.0),
+                ..Default::default()
+            },
+            GenerationParams {
+                top_p: Some(1.5),
+                ..Default::default()
+            },
+            GenerationParams {
+                max_tokens: Some(10),
+                ..Default::default()
+            },
+            GenerationParams {
+                stop: vec![String::new()],
+                ..Default::default()
+            },
+            GenerationParams {
+                stop: vec!["a".to_string(); cmd_const::MAX_STOP_SEQUENCES + 1],
+                ..Default::default()
+            },
+            GenerationParams {
+                stop: vec!["x".repeat(cmd_const::MAX_STOP_SEQUENCE_LEN + 1)],
+                ..Default::default()
+            },
+        ];
+        for params in cases {
+            assert!(
+                params.validate().is_err(),
+                "{:?} should be rejected",
+                params
+            );
+        }
+    }
+
+    #[test]
+    fn test_effective_falls_back_to_agent() {
+        let params = GenerationParams {
+            top_p: Some(0.5),
+            stop: vec!["END".to_string()],
+            ..Default::default()
+        };
+        let effective = params.effective(&llm());
+        assert_eq!(effective.temperature, Some(0.7));
+        assert_eq!(effective.max_tokens, Some(4096));
+        assert_eq!(effective.top_p, Some(0.5));
+        assert_eq!(effective.stop, vec!["END".to_string()]);
+
+        let overridden = GenerationParams {
+            temperature: Some(0.1),
+            max_tokens: Some(512),
+            ..Default::default()
+        }
+        .effective(&llm());
+        assert_eq!(overridden.temperature, Some(0.1));
+        assert_eq!(overridden.max_tokens, Some(512));
+        assert!(overridden.stop.is_empty());
+    }
+}
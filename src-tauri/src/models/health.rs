@@ -0,0 +1,147 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Agent health check models.
+//!
+//! An [`AgentHealthReport`] is produced by the `probe_agent` command before
+//! execution. It lists one [`HealthCheck`] per dependency of the agent
+//! (provider, model, each tool, each MCP server).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a single health check (ordered by severity)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Dependency is available
+    Ok,
+    /// Dependency could not be fully verified, execution may still succeed
+    Warning,
+    /// Dependency is unavailable, execution will fail or be degraded
+    Error,
+}
+
+/// Dependency verified by a health check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthCheckKind {
+    /// LLM provider configuration
+    Provider,
+    /// Model availability for the provider
+    Model,
+    /// Local tool
+    Tool,
+    /// MCP server
+    McpServer,
+}
+
+/// Single health check result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// Dependency kind
+    pub kind: HealthCheckKind,
+    /// Dependency name (provider, model, tool or server name)
+    pub target: String,
+    /// Check outcome
+    pub status: HealthStatus,
+    /// Human-readable detail
+    pub message: String,
+}
+
+impl HealthCheck {
+    /// Creates a check result.
+    pub fn new(
+        kind: HealthCheckKind,
+        target: impl Into<String>,
+        status: HealthStatus,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            kind,
+            target: target.into(),
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Health report of an agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentHealthReport {
+    /// Agent ID
+    pub agent_id: String,
+    /// Worst status among all checks
+    pub status: HealthStatus,
+    /// True if no check reported an error
+    pub healthy: bool,
+    /// Individual check results
+    pub checks: Vec<HealthCheck>,
+    /// Probe timestamp
+    pub checked_at: DateTime<Utc>,
+}
+
+impl AgentHealthReport {
+    /// Builds a report from check results.
+    pub fn new(agent_id: String, checks: Vec<HealthCheck>) -> Self {
+        let status = checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(HealthStatus::Ok);
+        Self {
+            agent_id,
+            status,
+            healthy: status != HealthStatus::Error,
+            checks,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_status_is_worst_check() {
+        let ok = HealthCheck::new(HealthCheckKind::Tool, "MemoryTool", HealthStatus::Ok, "");
+        let warn = HealthCheck::new(HealthCheckKind::Model, "m", HealthStatus::Warning, "");
+        let err = HealthCheck::new(HealthCheckKind::McpServer, "s", HealthStatus::Error, "");
+
+        let report = AgentHealthReport::new("a".to_string(), vec![ok.clone(), warn.clone()]);
+        assert_eq!(report.status, HealthStatus::Warning);
+        assert!(report.healthy);
+
+        let report = AgentHealthReport::new("a".to_string(), vec![ok, err, warn]);
+        assert_eq!(report.status, HealthStatus::Error);
+        assert!(!report.healthy);
+
+        let report = AgentHealthReport::new("a".to_string(), vec![]);
+        assert_eq!(report.status, HealthStatus::Ok);
+    }
+
+    #[test]
+    fn test_serialization() {
+        let check = HealthCheck::new(
+            HealthCheckKind::McpServer,
+            "serena",
+            HealthStatus::Error,
+            "stopped",
+        );
+        let json = serde_json::to_value(&check).unwrap();
+        assert_eq!(json["kind"], "mcp_server");
+        assert_eq!(json["status"], "error");
+    }
+}
@@ -30,7 +30,8 @@
 //! 3. User resolves conflicts via ConflictResolution
 //! 4. Backend executes import and returns ImportResult
 
-use chrono::Utc;
+use crate::models::chat_history::ChatHistorySource;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -444,6 +445,115 @@ pub struct ImportError {
     pub error: String,
 }
 
+// ============================================================================
+// Workspace Archive Types
+// ============================================================================
+
+/// Options of a workspace archive export.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceExportOptions {
+    /// Workflows to export (all non-sensitive workflows if None)
+    #[serde(default)]
+    pub workflow_ids: Option<Vec<String>>,
+    /// Whether memory embeddings are included (recomputed after import otherwise)
+    #[serde(default)]
+    pub include_embeddings: bool,
+}
+
+/// Record counts of a workspace archive, per table.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceCounts {
+    pub workflows: usize,
+    pub messages: usize,
+    pub tool_executions: usize,
+    pub thinking_steps: usize,
+    pub memories: usize,
+}
+
+/// Manifest of a workspace archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceArchiveManifest {
+    /// Archive format version
+    pub version: String,
+    /// Version of the application that produced the archive
+    pub app_version: String,
+    /// Export timestamp
+    pub exported_at: DateTime<Utc>,
+    /// Number of records per table
+    pub counts: WorkspaceCounts,
+    /// Number of sensitive workflows left out of the archive
+    pub skipped_sensitive: usize,
+    /// Whether memories carry their embeddings
+    pub include_embeddings: bool,
+}
+
+/// Single-file archive of conversations and memories.
+///
+/// Records keep their database field names; datetimes are RFC 3339 strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceArchive {
+    pub manifest: WorkspaceArchiveManifest,
+    pub workflows: Vec<serde_json::Value>,
+    pub messages: Vec<serde_json::Value>,
+    pub tool_executions: Vec<serde_json::Value>,
+    pub thinking_steps: Vec<serde_json::Value>,
+    pub memories: Vec<serde_json::Value>,
+}
+
+/// Result of a workspace archive import.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceImportResult {
+    /// Number of records imported per table
+    pub imported: WorkspaceCounts,
+    /// Number of records skipped per table (already present)
+    pub skipped: WorkspaceCounts,
+    /// Non-blocking issues (e.g. agents missing in this installation)
+    pub warnings: Vec<String>,
+    /// Records that could not be imported
+    pub errors: Vec<ImportError>,
+}
+
+// ============================================================================
+// Chat History Import Types
+// ============================================================================
+
+/// Options of a chat history import (ChatGPT or Claude `conversations.json`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatHistoryImportOptions {
+    /// Assistant that produced the file (detected if None)
+    #[serde(default)]
+    pub source: Option<ChatHistorySource>,
+    /// Agent of the imported workflows
+    pub agent_id: String,
+    /// Also store each exchange as a memory, recalled in new conversations
+    #[serde(default)]
+    pub create_memories: bool,
+}
+
+/// Result of a chat history import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatHistoryImportResult {
+    /// Assistant that produced the file
+    pub source: ChatHistorySource,
+    /// Number of conversations imported as workflows
+    pub workflows: usize,
+    /// Number of messages imported
+    pub messages: usize,
+    /// Number of memories created
+    pub memories: usize,
+    /// Number of conversations skipped (already imported or without text)
+    pub skipped: usize,
+    /// Conversations that could not be imported
+    pub errors: Vec<ImportError>,
+}
+
 // ============================================================================
 // Constants
 // ============================================================================
@@ -457,6 +567,12 @@ pub const APP_VERSION: &str = "0.1.0";
 /// Maximum import file size in bytes (10MB)
 pub const MAX_IMPORT_FILE_SIZE: usize = 10 * 1024 * 1024;
 
+/// Maximum size of a chat history export file in bytes (256MB)
+pub const MAX_CHAT_HISTORY_FILE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Current format version of workspace archives
+pub const WORKSPACE_ARCHIVE_VERSION: &str = "1.0";
+
 /// Sensitive env var key patterns to warn about
 pub const SENSITIVE_ENV_PATTERNS: &[&str] = &[
     "API_KEY",
@@ -0,0 +1,104 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Background job models.
+//!
+//! A job executes a message with an agent in a workflow independently of the
+//! IPC call that enqueued it. Jobs are persisted in the `job` table so that
+//! interrupted jobs resume after an app restart.
+//! Synchronized with src/types/job.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// State of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Waiting for a free execution slot
+    Queued,
+    /// Executing
+    Running,
+    /// The response was stored in the workflow
+    Done,
+    /// The execution failed (error stored as a system message of the workflow)
+    Failed,
+}
+
+impl JobStatus {
+    /// Returns the status as stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Background workflow execution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    /// Job ID (UUID)
+    pub id: String,
+    /// Workflow receiving the response
+    pub workflow_id: String,
+    /// Agent executing the message
+    pub agent_id: String,
+    /// User message (already stored in the workflow)
+    pub message: String,
+    /// Current state
+    pub status: JobStatus,
+    /// Number of executions started (an interrupted job is retried)
+    pub attempts: u32,
+    /// ID of the assistant message, once done
+    pub response_message_id: Option<String>,
+    /// Error, if the job failed
+    pub error: Option<String>,
+    /// Enqueue timestamp
+    pub created_at: DateTime<Utc>,
+    /// Start of the last execution
+    pub started_at: Option<DateTime<Utc>>,
+    /// End of the execution
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Payload for enqueuing a job.
+///
+/// ID and timestamps are generated server-side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCreate {
+    /// Workflow receiving the response
+    pub workflow_id: String,
+    /// Agent executing the message
+    pub agent_id: String,
+    /// User message
+    pub message: String,
+    /// Initial state (queued)
+    pub status: JobStatus,
+}
+
+/// Payload of the `job_status` event, emitted when a job changes state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatusEvent {
+    /// Job ID
+    pub job_id: String,
+    /// Workflow of the job
+    pub workflow_id: String,
+    /// New state
+    pub status: JobStatus,
+    /// Error, if the job failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,101 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Knowledge pack models.
+//!
+//! A knowledge pack is a curated bundle of documents (split into chunks stored
+//! in the `knowledge_chunk` table) and existing memories. Packs are attached to
+//! agents; at execution the relevant chunks are retrieved from the attached
+//! packs only (see [`crate::agents::knowledge`]).
+//! Synchronized with src/types/knowledge.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Knowledge pack with its documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgePack {
+    /// Pack ID (UUID)
+    pub id: String,
+    /// Pack name (unique)
+    pub name: String,
+    /// Description of the pack content
+    #[serde(default)]
+    pub description: String,
+    /// IDs of the agents the pack is attached to
+    #[serde(default)]
+    pub agent_ids: Vec<String>,
+    /// IDs of the memories curated into the pack
+    #[serde(default)]
+    pub memory_ids: Vec<String>,
+    /// Documents of the pack (not stored on the pack record)
+    #[serde(default)]
+    pub documents: Vec<KnowledgeDocument>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Payload for creating a knowledge pack.
+///
+/// ID and timestamps are generated server-side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgePackCreate {
+    /// Pack name
+    pub name: String,
+    /// Description of the pack content
+    pub description: String,
+    /// IDs of the agents the pack is attached to
+    pub agent_ids: Vec<String>,
+    /// IDs of the memories curated into the pack
+    pub memory_ids: Vec<String>,
+}
+
+/// Document of a knowledge pack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeDocument {
+    /// Document title (unique within the pack)
+    pub title: String,
+    /// Number of chunks the document was split into
+    pub chunk_count: usize,
+    /// Number of chunks stored with an embedding
+    pub embedded_count: usize,
+}
+
+/// Payload for creating a document chunk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeChunkCreate {
+    /// Pack ID
+    pub pack_id: String,
+    /// Title of the source document
+    pub document: String,
+    /// Position of the chunk in the document
+    pub position: usize,
+    /// Chunk text
+    pub content: String,
+}
+
+/// Chunk retrieved from an attached pack for a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeChunk {
+    /// Pack ID
+    pub pack_id: String,
+    /// Title of the source document (None for a curated memory)
+    pub document: Option<String>,
+    /// Chunk text
+    pub content: String,
+    /// Relevance score (cosine similarity, or keyword overlap without embeddings)
+    pub score: f64,
+}
@@ -29,7 +29,7 @@ use serde::{Deserialize, Serialize};
 
 /// LLM provider type supported by the application.
 ///
-/// Mistral and Ollama are builtin providers with dedicated implementations.
+/// Mistral, Ollama and Gemini are builtin providers with dedicated implementations.
 /// Custom(String) represents user-created OpenAI-compatible providers
 /// (RouterLab, OpenRouter, Together AI, etc.).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -38,6 +38,8 @@ pub enum ProviderType {
     Mistral,
     /// Ollama local inference server
     Ollama,
+    /// Google Gemini cloud API
+    Gemini,
     /// User-created OpenAI-compatible provider (e.g., Custom("routerlab"))
     Custom(String),
 }
@@ -47,6 +49,7 @@ impl Serialize for ProviderType {
         match self {
             ProviderType::Mistral => s.serialize_str("mistral"),
             ProviderType::Ollama => s.serialize_str("ollama"),
+            ProviderType::Gemini => s.serialize_str("gemini"),
             ProviderType::Custom(name) => s.serialize_str(name),
         }
     }
@@ -58,6 +61,7 @@ impl<'de> Deserialize<'de> for ProviderType {
         Ok(match s.as_str() {
             "mistral" => ProviderType::Mistral,
             "ollama" => ProviderType::Ollama,
+            "gemini" => ProviderType::Gemini,
             other => ProviderType::Custom(other.to_string()),
         })
     }
@@ -68,6 +72,7 @@ impl std::fmt::Display for ProviderType {
         match self {
             ProviderType::Mistral => write!(f, "mistral"),
             ProviderType::Ollama => write!(f, "ollama"),
+            ProviderType::Gemini => write!(f, "gemini"),
             ProviderType::Custom(name) => write!(f, "{}", name),
         }
     }
@@ -80,6 +85,7 @@ impl std::str::FromStr for ProviderType {
         match s.to_lowercase().as_str() {
             "mistral" => Ok(ProviderType::Mistral),
             "ollama" => Ok(ProviderType::Ollama),
+            "gemini" => Ok(ProviderType::Gemini),
             other => {
                 if other.is_empty() {
                     Err(format!("Unknown provider type: {}", s))
@@ -110,6 +116,8 @@ impl std::str::FromStr for ProviderType {
 /// - `temperature_default`: Default sampling temperature (0.0 - 2.0)
 /// - `is_builtin`: Whether this is a system-provided model (cannot be deleted)
 /// - `is_reasoning`: Whether this is a reasoning/thinking model (Magistral, DeepSeek-R1, etc.)
+/// - `supports_vision` / `supports_tools`: Capabilities reported by the provider API (unknown if None)
+/// - `metadata_drift`: Builtin values that differ from the provider API (see `refresh_provider_models`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LLMModel {
     /// Unique identifier
@@ -137,6 +145,18 @@ pub struct LLMModel {
     /// Price per million output tokens (USD) - user configurable
     #[serde(default)]
     pub output_price_per_mtok: f64,
+    /// Whether the model accepts images (reported by the provider API)
+    #[serde(default)]
+    pub supports_vision: Option<bool>,
+    /// Whether the model supports function calling (reported by the provider API)
+    #[serde(default)]
+    pub supports_tools: Option<bool>,
+    /// Builtin values that differ from the provider API at the last refresh
+    #[serde(default)]
+    pub metadata_drift: Vec<ModelMetadataDrift>,
+    /// Last refresh from the provider API
+    #[serde(default)]
+    pub metadata_refreshed_at: Option<DateTime<Utc>>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -146,7 +166,7 @@ pub struct LLMModel {
 /// Parameters for creating a new builtin model.
 #[derive(Debug, Clone)]
 pub struct BuiltinModelParams {
-    /// Provider type (Mistral or Ollama)
+    /// Builtin provider type
     pub provider: ProviderType,
     /// Human-readable display name
     pub name: String,
@@ -180,6 +200,10 @@ impl LLMModel {
             is_reasoning: request.is_reasoning,
             input_price_per_mtok: request.input_price_per_mtok,
             output_price_per_mtok: request.output_price_per_mtok,
+            supports_vision: None,
+            supports_tools: None,
+            metadata_drift: Vec::new(),
+            metadata_refreshed_at: None,
             created_at: now,
             updated_at: now,
         }
@@ -202,12 +226,81 @@ impl LLMModel {
             is_reasoning: params.is_reasoning,
             input_price_per_mtok: 0.0,
             output_price_per_mtok: 0.0,
+            supports_vision: None,
+            supports_tools: None,
+            metadata_drift: Vec::new(),
+            metadata_refreshed_at: None,
             created_at: now,
             updated_at: now,
         }
     }
 }
 
+// ============================================================================
+// Model Metadata Discovery
+// ============================================================================
+
+/// Model metadata reported by a provider's model list API.
+///
+/// Fields the provider does not report are None.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelMetadata {
+    /// Model identifier used in API calls
+    pub api_name: String,
+    /// Display name reported by the provider
+    pub name: Option<String>,
+    /// Maximum context length in tokens
+    pub context_window: Option<usize>,
+    /// Maximum generation length in tokens
+    pub max_output_tokens: Option<usize>,
+    /// Whether the model accepts images
+    pub supports_vision: Option<bool>,
+    /// Whether the model supports function calling
+    pub supports_tools: Option<bool>,
+}
+
+/// Builtin model value that differs from the provider API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelMetadataDrift {
+    /// Drifted field (`context_window` or `max_output_tokens`)
+    pub field: String,
+    /// Value stored on the builtin model
+    pub stored: usize,
+    /// Value reported by the provider API
+    pub reported: usize,
+}
+
+/// Outcome of refreshing the models of one provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelRefreshReport {
+    /// Refreshed provider
+    pub provider: ProviderType,
+    /// Models reported by the provider API
+    pub discovered: usize,
+    /// Models created from the API
+    pub created: usize,
+    /// Existing models whose metadata changed
+    pub updated: usize,
+    /// IDs of the builtin models whose metadata drifted from the API
+    pub drifted: Vec<String>,
+    /// Error when the model list could not be fetched
+    pub error: Option<String>,
+}
+
+impl ModelRefreshReport {
+    /// Report of a provider whose model list could not be fetched.
+    pub fn failure(provider: ProviderType, error: String) -> Self {
+        Self {
+            provider,
+            discovered: 0,
+            created: 0,
+            updated: 0,
+            drifted: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
 // ============================================================================
 // Create Model Request
 // ============================================================================
@@ -473,7 +566,7 @@ impl ProviderSettings {
     pub fn default_for(provider: ProviderType) -> Self {
         let base_url = match &provider {
             ProviderType::Ollama => Some("http://localhost:11434".into()),
-            ProviderType::Mistral => None,
+            ProviderType::Mistral | ProviderType::Gemini => None,
             ProviderType::Custom(_) => None,
         };
         Self {
@@ -542,10 +635,29 @@ pub const MISTRAL_BUILTIN_MODELS: &[(&str, &str, usize, usize)] = &[];
 /// Ollama builtin models: empty - users add their own models
 pub const OLLAMA_BUILTIN_MODELS: &[(&str, &str, usize, usize)] = &[];
 
+/// Gemini builtin models: (api_name, name, context_window, max_output_tokens)
+pub const GEMINI_BUILTIN_MODELS: &[(&str, &str, usize, usize)] = &[
+    ("gemini-2.5-flash", "Gemini 2.5 Flash", 1_048_576, 65_536),
+    ("gemini-2.5-pro", "Gemini 2.5 Pro", 1_048_576, 65_536),
+];
+
 /// Returns all builtin models for seeding the database.
-/// Currently returns empty - users add their own custom models.
+///
+/// Only Gemini ships builtin models; Mistral and Ollama users add their own.
 pub fn get_all_builtin_models() -> Vec<LLMModel> {
-    Vec::new()
+    GEMINI_BUILTIN_MODELS
+        .iter()
+        .map(|(api_name, name, context_window, max_output_tokens)| {
+            LLMModel::new_builtin(BuiltinModelParams {
+                provider: ProviderType::Gemini,
+                name: name.to_string(),
+                api_name: api_name.to_string(),
+                context_window: *context_window,
+                max_output_tokens: *max_output_tokens,
+                is_reasoning: false,
+            })
+        })
+        .collect()
 }
 
 // ============================================================================
@@ -560,6 +672,7 @@ mod tests {
     fn test_provider_type_display() {
         assert_eq!(ProviderType::Mistral.to_string(), "mistral");
         assert_eq!(ProviderType::Ollama.to_string(), "ollama");
+        assert_eq!(ProviderType::Gemini.to_string(), "gemini");
         assert_eq!(
             ProviderType::Custom("routerlab".to_string()).to_string(),
             "routerlab"
@@ -703,8 +816,12 @@ mod tests {
     #[test]
     fn test_get_all_builtin_models() {
         let models = get_all_builtin_models();
-        // No builtin models - users add their own
-        assert!(models.is_empty());
+        assert_eq!(models.len(), GEMINI_BUILTIN_MODELS.len());
+        assert!(models
+            .iter()
+            .all(|m| m.is_builtin && m.provider == ProviderType::Gemini));
+        assert!(models.iter().any(|m| m.id == "gemini-2.5-flash"));
+        assert!(models.iter().any(|m| m.id == "gemini-2.5-pro"));
     }
 
     #[test]
@@ -0,0 +1,180 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local model runtime status.
+//!
+//! Memory usage of the models loaded by Ollama and of the host, used by the
+//! settings screen and to warn before selecting a model that does not fit.
+//! Synchronized with src/types/local-llm.ts
+
+use crate::tools::constants::commands as cmd_const;
+use serde::{Deserialize, Serialize};
+
+/// A model currently loaded by Ollama (`/api/ps`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadedModel {
+    /// Model name (e.g. "qwen2.5:7b")
+    pub name: String,
+    /// Total memory used by the model (bytes)
+    pub size_bytes: u64,
+    /// Part of the model held in VRAM (bytes)
+    pub vram_bytes: u64,
+    /// When Ollama unloads the model if it stays idle
+    pub expires_at: Option<String>,
+}
+
+/// Host memory (bytes)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SystemMemory {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// Runtime status of the local Ollama server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalLlmStatus {
+    /// Ollama server URL
+    pub server_url: String,
+    /// Whether `/api/ps` answered
+    pub reachable: bool,
+    /// Models currently loaded
+    pub loaded_models: Vec<LoadedModel>,
+    /// VRAM used by the loaded models (bytes)
+    pub vram_used_bytes: u64,
+    /// Host memory, None when it cannot be read on this platform
+    pub system_memory: Option<SystemMemory>,
+    /// Error of the Ollama request when unreachable
+    pub error: Option<String>,
+}
+
+/// Whether a local model fits in the available memory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelFitCheck {
+    /// Model name
+    pub model: String,
+    /// Size of the model on disk (bytes), None when not installed
+    pub model_size_bytes: Option<u64>,
+    /// Memory the model needs once loaded, with the runtime overhead (bytes)
+    pub required_bytes: Option<u64>,
+    /// Memory usable by the model (bytes)
+    pub available_bytes: Option<u64>,
+    /// None when the size or the available memory is unknown
+    pub fits: Option<bool>,
+    /// Message shown before selecting the model
+    pub warning: Option<String>,
+}
+
+impl ModelFitCheck {
+    /// Compares the memory a model needs with the memory it can use.
+    ///
+    /// A model that is already loaded always fits. Idle loaded models are
+    /// evicted by Ollama when another one is loaded, so their memory counts
+    /// as available.
+    pub fn evaluate(
+        model: &str,
+        model_size_bytes: Option<u64>,
+        loaded_models: &[LoadedModel],
+        system_memory: Option<SystemMemory>,
+    ) -> Self {
+        let required_bytes = model_size_bytes
+            .map(|size| size + size * cmd_const::LOCAL_MODEL_MEMORY_OVERHEAD_PERCENT / 100);
+        let available_bytes = system_memory.map(|memory| {
+            let reclaimable: u64 = loaded_models.iter().map(|m| m.size_bytes).sum();
+            (memory.available_bytes + reclaimable).min(memory.total_bytes)
+        });
+        let already_loaded = loaded_models.iter().any(|m| m.name == model);
+
+        let (fits, warning) = match (model_size_bytes, required_bytes, available_bytes) {
+            _ if already_loaded => (Some(true), None),
+            (None, _, _) => (
+                None,
+                Some(format!("Model '{}' is not installed in Ollama", model)),
+            ),
+            (_, Some(required), Some(available)) if required > available => (
+                Some(false),
+                Some(format!(
+                    "Model '{}' needs about {:.1} GB of memory but only {:.1} GB is available",
+                    model,
+                    gigabytes(required),
+                    gigabytes(available)
+                )),
+            ),
+            (_, Some(_), Some(_)) => (Some(true), None),
+            _ => (None, None),
+        };
+
+        Self {
+            model: model.to_string(),
+            model_size_bytes,
+            required_bytes,
+            available_bytes,
+            fits,
+            warning,
+        }
+    }
+}
+
+fn gigabytes(bytes: u64) -> f64 {
+    bytes as f64 / 1_000_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1_000_000_000;
+
+    fn loaded(name: &str, size_bytes: u64) -> LoadedModel {
+        LoadedModel {
+            name: name.to_string(),
+            size_bytes,
+            vram_bytes: size_bytes,
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_model_fit_check() {
+        let memory = Some(SystemMemory {
+            total_bytes: 16 * GB,
+            available_bytes: 6 * GB,
+        });
+
+        let fits = ModelFitCheck::evaluate("small", Some(4 * GB), &[], memory);
+        assert_eq!(fits.fits, Some(true));
+        assert!(fits.warning.is_none());
+
+        let too_big = ModelFitCheck::evaluate("large", Some(12 * GB), &[], memory);
+        assert_eq!(too_big.fits, Some(false));
+        assert!(too_big.warning.unwrap().contains("large"));
+
+        // Memory of idle loaded models can be reclaimed
+        let reclaimed =
+            ModelFitCheck::evaluate("large", Some(8 * GB), &[loaded("other", 4 * GB)], memory);
+        assert_eq!(reclaimed.available_bytes, Some(10 * GB));
+        assert_eq!(reclaimed.fits, Some(true));
+
+        let loaded_model =
+            ModelFitCheck::evaluate("large", Some(12 * GB), &[loaded("large", 12 * GB)], memory);
+        assert_eq!(loaded_model.fits, Some(true));
+
+        let unknown_memory = ModelFitCheck::evaluate("small", Some(4 * GB), &[], None);
+        assert_eq!(unknown_memory.fits, None);
+        assert!(unknown_memory.warning.is_none());
+
+        let missing = ModelFitCheck::evaluate("missing", None, &[], memory);
+        assert_eq!(missing.fits, None);
+        assert!(missing.warning.is_some());
+    }
+}
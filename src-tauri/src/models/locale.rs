@@ -0,0 +1,198 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Locale-aware formatting.
+//!
+//! The format locale is stored in the `settings:locale` record and applies to
+//! dates and numbers injected in system prompts and returned by tools. It is
+//! independent from the response language, which follows the UI language of
+//! each message.
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Supported format locales
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English (`Saturday 18 October 2026, 14:05:09`, `1,234.5`)
+    #[default]
+    En,
+    /// French (`samedi 18 octobre 2026, 14:05:09`, `1 234,5`)
+    Fr,
+}
+
+/// English weekday names, Monday first
+const EN_WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// French weekday names, Monday first
+const FR_WEEKDAYS: [&str; 7] = [
+    "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+];
+
+/// English month names
+const EN_MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// French month names
+const FR_MONTHS: [&str; 12] = [
+    "janvier",
+    "février",
+    "mars",
+    "avril",
+    "mai",
+    "juin",
+    "juillet",
+    "août",
+    "septembre",
+    "octobre",
+    "novembre",
+    "décembre",
+];
+
+impl Locale {
+    /// Returns the locale code (`en`, `fr`)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Fr => "fr",
+        }
+    }
+
+    /// Formats a date and time with weekday and month names.
+    pub fn format_datetime<Tz: TimeZone>(&self, datetime: &DateTime<Tz>) -> String {
+        let (weekdays, months) = match self {
+            Self::En => (&EN_WEEKDAYS, &EN_MONTHS),
+            Self::Fr => (&FR_WEEKDAYS, &FR_MONTHS),
+        };
+        format!(
+            "{} {:02} {} {}, {:02}:{:02}:{:02}",
+            weekdays[datetime.weekday().num_days_from_monday() as usize],
+            datetime.day(),
+            months[datetime.month0() as usize],
+            datetime.year(),
+            datetime.hour(),
+            datetime.minute(),
+            datetime.second()
+        )
+    }
+
+    /// Formats a number with the locale's grouping and decimal separators.
+    ///
+    /// Digits are kept as-is (shortest representation that round-trips).
+    pub fn format_number(&self, value: f64) -> String {
+        let (group_separator, decimal_separator) = match self {
+            Self::En => (",", "."),
+            // Narrow no-break space, as used by French typography
+            Self::Fr => ("\u{202F}", ","),
+        };
+
+        let raw = value.abs().to_string();
+        let (integer, fraction) = match raw.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (raw.as_str(), None),
+        };
+
+        let mut formatted = String::new();
+        if value.is_sign_negative() && value != 0.0 {
+            formatted.push('-');
+        }
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                formatted.push_str(group_separator);
+            }
+            formatted.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            formatted.push_str(decimal_separator);
+            formatted.push_str(fraction);
+        }
+        formatted
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Format locale settings (stored in `settings:locale`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocaleSettings {
+    /// Locale used for dates and numbers in prompts and tool outputs
+    #[serde(default)]
+    pub locale: Locale,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_format_datetime() {
+        let datetime = Utc.with_ymd_and_hms(2026, 8, 1, 9, 5, 3).unwrap();
+        assert_eq!(
+            Locale::En.format_datetime(&datetime),
+            "Saturday 01 August 2026, 09:05:03"
+        );
+        assert_eq!(
+            Locale::Fr.format_datetime(&datetime),
+            "samedi 01 août 2026, 09:05:03"
+        );
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(Locale::En.format_number(1234567.891), "1,234,567.891");
+        assert_eq!(
+            Locale::Fr.format_number(1234567.891),
+            "1\u{202F}234\u{202F}567,891"
+        );
+        assert_eq!(Locale::En.format_number(-1024.0), "-1,024");
+        assert_eq!(Locale::En.format_number(999.5), "999.5");
+        assert_eq!(Locale::Fr.format_number(0.25), "0,25");
+        assert_eq!(Locale::En.format_number(-0.0), "0");
+    }
+
+    #[test]
+    fn test_settings_default() {
+        let settings: LocaleSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.locale, Locale::En);
+        let settings: LocaleSettings = serde_json::from_str(r#"{"locale":"fr"}"#).unwrap();
+        assert_eq!(settings.locale, Locale::Fr);
+    }
+}
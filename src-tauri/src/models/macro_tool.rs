@@ -0,0 +1,93 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Macro tool models.
+//!
+//! Named sequences of tool calls defined by the user, stored in the
+//! `macro_tool` table and exposed to agents as a single tool
+//! `macro__<name>`.
+//! Synchronized with src/types/macro-tool.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One tool call of a macro
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroStep {
+    /// Step ID, referenced by later steps as `{{steps.<id>...}}`
+    pub id: String,
+    /// Tool called (e.g. "MemoryTool", "plugin__search", "custom__summary")
+    pub tool: String,
+    /// Input template of the call: `{{input.<param>}}` and
+    /// `{{steps.<id>.<path>}}` placeholders are replaced before the call
+    pub input: Value,
+}
+
+/// Macro tool stored in the database
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolMacro {
+    /// Macro ID (UUID)
+    pub id: String,
+    /// Macro name (lowercase letters, digits and `_`), fixed at creation
+    pub name: String,
+    /// Description for the LLM
+    pub description: String,
+    /// JSON Schema of the input (an object)
+    pub input_schema: Value,
+    /// Tool calls, run in order
+    pub steps: Vec<MacroStep>,
+    /// Output template (None = result of the last step)
+    pub output: Option<Value>,
+    /// Whether agents can use the macro
+    pub enabled: bool,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Payload for creating a macro tool (ID and timestamps are generated
+/// server-side).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolMacroCreate {
+    /// Macro name
+    pub name: String,
+    /// Description for the LLM
+    pub description: String,
+    /// JSON Schema of the input
+    pub input_schema: Value,
+    /// Tool calls
+    pub steps: Vec<MacroStep>,
+    /// Output template
+    #[serde(default)]
+    pub output: Option<Value>,
+}
+
+/// Payload for updating a macro tool (all fields optional; the name cannot
+/// change since agents refer to the macro by name). The output template is
+/// replaced along with the steps it refers to.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ToolMacroUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_schema: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<MacroStep>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+}
@@ -139,6 +139,73 @@ pub struct MCPResource {
     pub mime_type: Option<String>,
 }
 
+/// Contents of an MCP resource
+///
+/// One item of a `resources/read` result. Text resources carry `text`,
+/// binary resources carry base64-encoded `blob`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MCPResourceContents {
+    /// Resource URI
+    pub uri: String,
+    /// MIME type of the content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Text content (text resources)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Base64-encoded content (binary resources)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob: Option<String>,
+}
+
+/// MCP prompt argument
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MCPPromptArgument {
+    /// Argument name
+    pub name: String,
+    /// Optional description of the argument
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the argument must be provided
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// MCP prompt definition
+///
+/// Describes a prompt template exposed by an MCP server. The server renders
+/// the template with argument values (`prompts/get`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MCPPrompt {
+    /// Prompt name (unique identifier within the server)
+    pub name: String,
+    /// Optional description of the prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Arguments accepted by the prompt
+    #[serde(default)]
+    pub arguments: Vec<MCPPromptArgument>,
+}
+
+/// Message of a rendered MCP prompt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MCPPromptMessage {
+    /// Message role ("user" or "assistant")
+    pub role: String,
+    /// Text of the message (non-text content is summarized)
+    pub text: String,
+}
+
+/// Rendered MCP prompt (`prompts/get` result)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MCPPromptResult {
+    /// Optional description of the prompt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Rendered messages
+    pub messages: Vec<MCPPromptMessage>,
+}
+
 /// Complete MCP server entity
 ///
 /// Combines configuration with runtime state (status, discovered tools/resources).
@@ -160,6 +227,27 @@ pub struct MCPServer {
     pub updated_at: DateTime<Utc>,
 }
 
+/// MCP server status transition
+///
+/// Recorded by the manager's supervision (health checks, crashes, restarts)
+/// and emitted as the `mcp_server_status_changed` event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MCPServerStatusChange {
+    /// Server ID
+    pub server_id: String,
+    /// Server name
+    pub server_name: String,
+    /// New status
+    pub status: MCPServerStatus,
+    /// Status before the transition (None for the first recorded status)
+    pub previous_status: Option<MCPServerStatus>,
+    /// Reason of the transition (error message, restart attempt...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Transition timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
 /// MCP server connection test result
 ///
 /// Returned after testing an MCP server configuration.
@@ -178,6 +266,57 @@ pub struct MCPTestResult {
     pub latency_ms: u64,
 }
 
+/// Outcome of importing one server from an `mcpServers` JSON block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MCPServerImportAction {
+    /// Created as a new server
+    Created,
+    /// Created under a new name (the name was already taken)
+    Renamed,
+    /// Replaced the configuration of the existing server with the same name
+    Overwritten,
+    /// Not imported (the name was already taken)
+    Skipped,
+    /// Not imported (unsupported command or invalid configuration)
+    Failed,
+}
+
+/// Import result of one server of an `mcpServers` JSON block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPServerImportResult {
+    /// Server key in the imported JSON
+    pub source_name: String,
+    /// Name of the server in Zileo (differs from `source_name` when renamed)
+    pub name: String,
+    /// ID of the created or overwritten server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_id: Option<String>,
+    /// Deployment method detected from the entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<MCPDeploymentMethod>,
+    /// What happened to the entry
+    pub action: MCPServerImportAction,
+    /// Reason of a skip or failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Report of an `mcpServers` JSON import
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MCPServerImportReport {
+    /// One result per server entry, sorted by source name
+    pub results: Vec<MCPServerImportResult>,
+    /// Number of servers created (including renamed) or overwritten
+    pub imported: usize,
+    /// Number of servers skipped because of a name conflict
+    pub skipped: usize,
+    /// Number of entries that could not be imported
+    pub failed: usize,
+    /// True if nothing was written (preview)
+    pub dry_run: bool,
+}
+
 /// MCP tool call request
 ///
 /// Request to execute a tool on an MCP server.
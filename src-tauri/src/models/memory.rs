@@ -46,6 +46,31 @@ impl std::fmt::Display for MemoryType {
     }
 }
 
+/// Embedding state of a memory
+///
+/// Memories are stored immediately and embedded in the background; until
+/// the embedding is `Ready`, searches find them by text only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryEmbeddingStatus {
+    /// Waiting for the background embedding
+    Pending,
+    /// Embedding stored, found by vector search
+    Ready,
+    /// Embedding failed after retries (text search only)
+    Failed,
+}
+
+impl std::fmt::Display for MemoryEmbeddingStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryEmbeddingStatus::Pending => write!(f, "pending"),
+            MemoryEmbeddingStatus::Ready => write!(f, "ready"),
+            MemoryEmbeddingStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
 /// Memory entity for persistent context and RAG
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Memory {
@@ -68,6 +93,9 @@ pub struct Memory {
     /// Optional expiration timestamp for TTL
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<DateTime<Utc>>,
+    /// Background embedding state (None when stored without embedding service)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_status: Option<MemoryEmbeddingStatus>,
     /// Creation timestamp (set by database)
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
@@ -95,6 +123,9 @@ pub struct MemoryCreate {
     pub metadata: serde_json::Value,
     /// Importance score (0.0-1.0)
     pub importance: f64,
+    /// Background embedding state (None when stored without embedding service)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding_status: Option<MemoryEmbeddingStatus>,
     /// Optional expiration timestamp for TTL.
     /// Not serialized into CONTENT - set via separate UPDATE with datetime cast
     /// because SurrealDB SCHEMAFULL rejects ISO 8601 strings for option<datetime>.
@@ -114,6 +145,7 @@ impl MemoryCreate {
             workflow_id: None,
             metadata,
             importance: 0.5,
+            embedding_status: None,
             expires_at: None,
         }
     }
@@ -132,6 +164,7 @@ impl MemoryCreate {
             workflow_id: Some(workflow_id),
             metadata,
             importance: 0.5,
+            embedding_status: None,
             expires_at: None,
         }
     }
@@ -151,6 +184,7 @@ impl MemoryCreate {
             workflow_id,
             metadata,
             importance,
+            embedding_status: None,
             expires_at,
         }
     }
@@ -345,6 +379,7 @@ mod tests {
             metadata: serde_json::json!({"source": "settings"}),
             importance: 0.5,
             expires_at: None,
+            embedding_status: None,
             created_at: Utc::now(),
         };
 
@@ -375,6 +410,7 @@ mod tests {
             metadata: serde_json::json!({}),
             importance: 0.3,
             expires_at: None,
+            embedding_status: None,
             created_at: Utc::now(),
         };
 
@@ -439,6 +475,7 @@ mod tests {
             metadata: serde_json::json!({}),
             importance: 0.7,
             expires_at: None,
+            embedding_status: None,
             created_at: Utc::now(),
         };
 
@@ -17,6 +17,8 @@
 //! This module provides types for storing and retrieving conversation messages
 //! with associated metrics (tokens, cost, duration) for analytics and recovery.
 
+use super::attachment::Attachment;
+use super::generation::GenerationParams;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -76,10 +78,93 @@ pub struct Message {
     /// Generation duration in milliseconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_ms: Option<u64>,
+    /// Context window utilization of the run (assistant messages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_usage: Option<ContextUsage>,
+    /// Behavior changelog position in effect when the run started (assistant messages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behavior_position: Option<u64>,
+    /// Generation parameters used for the response (assistant messages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<GenerationParams>,
+    /// Image attachments (user messages), loaded from the `attachment` table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    /// Pinned: always sent to the agent, even when left out of the history
+    #[serde(default)]
+    pub pinned: bool,
+    /// Bookmarked by the user
+    #[serde(default)]
+    pub bookmarked: bool,
+    /// Reactions of the user (emoji)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<String>,
     /// Message timestamp
     pub timestamp: DateTime<Utc>,
 }
 
+/// Context window utilization of the LLM calls behind an assistant message.
+///
+/// Tells the user how full the model's context was and which part of the
+/// conversation was left out of the prompt, so a response that "forgot"
+/// earlier content can be explained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContextUsage {
+    /// Context window of the model in tokens (None if the model is unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_window: Option<u64>,
+    /// Largest prompt sent to the model during the run, in tokens
+    pub peak_context_tokens: u64,
+    /// `peak_context_tokens / context_window` (None if the window is unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub utilization: Option<f64>,
+    /// Messages stored in the workflow when the run started
+    pub history_messages: u32,
+    /// Messages sent to the model as conversation history
+    pub history_sent: u32,
+    /// Earlier messages left out of the prompt
+    pub history_truncated: u32,
+    /// Timestamp of the oldest message sent, when earlier messages were left out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truncated_before: Option<DateTime<Utc>>,
+}
+
+impl ContextUsage {
+    /// Builds the usage report of a run.
+    pub fn new(
+        context_window: Option<u64>,
+        peak_context_tokens: u64,
+        history_messages: u32,
+        history_sent: u32,
+        oldest_sent: Option<DateTime<Utc>>,
+    ) -> Self {
+        let history_truncated = history_messages.saturating_sub(history_sent);
+        Self {
+            context_window,
+            peak_context_tokens,
+            utilization: context_window
+                .filter(|w| *w > 0)
+                .map(|w| peak_context_tokens as f64 / w as f64),
+            history_messages,
+            history_sent,
+            history_truncated,
+            truncated_before: oldest_sent.filter(|_| history_truncated > 0),
+        }
+    }
+}
+
+/// Read-aloud version of a message, for text-to-speech.
+///
+/// Stored next to the Markdown content (field `read_aloud` of the message
+/// record), never in place of it. See [`crate::speech::read_aloud`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadAloud {
+    /// Plain text without Markdown, code blocks or abbreviations
+    pub text: String,
+    /// Same content as an SSML document (`<speak>...</speak>`)
+    pub ssml: String,
+}
+
 /// Payload for creating a new message.
 ///
 /// ID and timestamp are generated server-side.
@@ -111,6 +196,18 @@ pub struct MessageCreate {
     /// Duration in milliseconds
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration_ms: Option<u64>,
+    /// Context window utilization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_usage: Option<ContextUsage>,
+    /// Read-aloud version of the content (assistant messages, when enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_aloud: Option<ReadAloud>,
+    /// Behavior changelog position in effect when the run started (assistant messages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behavior_position: Option<u64>,
+    /// Generation parameters used for the response (assistant messages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<GenerationParams>,
 }
 
 /// Response for paginated message loading.
@@ -145,6 +242,10 @@ impl MessageCreate {
             provider: None,
             cost_usd: None,
             duration_ms: None,
+            context_usage: None,
+            read_aloud: None,
+            behavior_position: None,
+            generation: None,
         }
     }
 
@@ -170,6 +271,10 @@ impl MessageCreate {
             provider,
             cost_usd: None, // Cost calculation is provider-specific
             duration_ms,
+            context_usage: None,
+            read_aloud: None,
+            behavior_position: None,
+            generation: None,
         }
     }
 
@@ -187,8 +292,44 @@ impl MessageCreate {
             provider: None,
             cost_usd: None,
             duration_ms: None,
+            context_usage: None,
+            read_aloud: None,
+            behavior_position: None,
+            generation: None,
+        }
+    }
+}
+
+/// Formats the pinned messages sent to the agent besides the history.
+///
+/// Each message is truncated to `max_chars` characters. Returns None when
+/// there is no pinned message.
+pub fn format_pinned_context(pinned: &[Message], max_chars: usize) -> Option<String> {
+    if pinned.is_empty() {
+        return None;
+    }
+    let mut context = String::from(
+        "## Pinned Messages\n\nThe user pinned these earlier messages of the conversation. \
+         Keep them in mind even if they are not part of the recent history.\n",
+    );
+    for message in pinned {
+        let role = match message.role {
+            MessageRole::User => "User",
+            MessageRole::Assistant => "Assistant",
+            MessageRole::System => "System",
+        };
+        let mut content: String = message.content.trim().chars().take(max_chars).collect();
+        if message.content.trim().chars().count() > max_chars {
+            content.push_str("...");
         }
+        context.push_str(&format!(
+            "\n### {} ({})\n{}\n",
+            role,
+            message.timestamp.format("%Y-%m-%d %H:%M UTC"),
+            content
+        ));
     }
+    Some(context)
 }
 
 #[cfg(test)]
@@ -233,6 +374,13 @@ mod tests {
             provider: None,
             cost_usd: None,
             duration_ms: None,
+            context_usage: None,
+            behavior_position: None,
+            generation: None,
+            attachments: Vec::new(),
+            pinned: false,
+            bookmarked: false,
+            reactions: Vec::new(),
             timestamp: Utc::now(),
         };
 
@@ -260,6 +408,13 @@ mod tests {
             provider: Some("Mistral".to_string()),
             cost_usd: Some(0.001),
             duration_ms: Some(1500),
+            context_usage: None,
+            behavior_position: None,
+            generation: None,
+            attachments: Vec::new(),
+            pinned: false,
+            bookmarked: false,
+            reactions: Vec::new(),
             timestamp: Utc::now(),
         };
 
@@ -302,4 +457,53 @@ mod tests {
         assert_eq!(MessageRole::Assistant.to_string(), "assistant");
         assert_eq!(MessageRole::System.to_string(), "system");
     }
+
+    #[test]
+    fn test_context_usage() {
+        let oldest = Utc::now();
+        let usage = ContextUsage::new(Some(32000), 8000, 60, 50, Some(oldest));
+        assert_eq!(usage.utilization, Some(0.25));
+        assert_eq!(usage.history_truncated, 10);
+        assert_eq!(usage.truncated_before, Some(oldest));
+
+        // Nothing left out: no truncation timestamp
+        let usage = ContextUsage::new(Some(32000), 8000, 4, 4, Some(oldest));
+        assert_eq!(usage.history_truncated, 0);
+        assert!(usage.truncated_before.is_none());
+
+        // Unknown model: no utilization ratio
+        let usage = ContextUsage::new(None, 8000, 4, 4, None);
+        assert!(usage.utilization.is_none());
+        let json = serde_json::to_value(&usage).unwrap();
+        assert!(json.get("utilization").is_none());
+        assert_eq!(json["peak_context_tokens"], 8000);
+    }
+
+    #[test]
+    fn test_format_pinned_context() {
+        assert!(format_pinned_context(&[], 100).is_none());
+
+        let message: Message = serde_json::from_value(serde_json::json!({
+            "id": "m1",
+            "workflow_id": "wf",
+            "role": "user",
+            "content": "  Always answer in metric units  ",
+            "tokens": 0,
+            "pinned": true,
+            "timestamp": "2026-10-01T09:01:00Z"
+        }))
+        .unwrap();
+        assert!(message.pinned);
+        assert!(!message.bookmarked);
+        assert!(message.reactions.is_empty());
+
+        let context = format_pinned_context(std::slice::from_ref(&message), 100).unwrap();
+        assert!(context.starts_with("## Pinned Messages"));
+        assert!(
+            context.contains("### User (2026-10-01 09:01 UTC)\nAlways answer in metric units\n")
+        );
+
+        let context = format_pinned_context(&[message], 6).unwrap();
+        assert!(context.contains("\nAlways...\n"));
+    }
 }
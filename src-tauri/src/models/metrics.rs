@@ -0,0 +1,61 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics export settings.
+//!
+//! The metrics of the app (see [`crate::metrics`]) are always counted in
+//! memory; these settings expose them on a local Prometheus `/metrics`
+//! endpoint and/or push them to a local OpenTelemetry collector (OTLP/HTTP).
+
+use serde::{Deserialize, Serialize};
+
+/// Metrics export settings (`settings:metrics`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsSettings {
+    /// Serve the Prometheus endpoint on `127.0.0.1:<prometheus_port>/metrics`
+    pub prometheus_enabled: bool,
+    /// Port of the Prometheus endpoint
+    pub prometheus_port: u16,
+    /// Push the metrics to an OTLP/HTTP collector
+    pub otlp_enabled: bool,
+    /// OTLP/HTTP metrics endpoint of the collector
+    pub otlp_endpoint: String,
+    /// Interval between two pushes (seconds)
+    pub otlp_interval_secs: u64,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        use crate::tools::constants::metrics::{
+            DEFAULT_OTLP_ENDPOINT, DEFAULT_OTLP_INTERVAL_SECS, DEFAULT_PROMETHEUS_PORT,
+        };
+        Self {
+            prometheus_enabled: false,
+            prometheus_port: DEFAULT_PROMETHEUS_PORT,
+            otlp_enabled: false,
+            otlp_endpoint: DEFAULT_OTLP_ENDPOINT.to_string(),
+            otlp_interval_secs: DEFAULT_OTLP_INTERVAL_SECS,
+        }
+    }
+}
+
+/// Running metrics exporters
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetricsExportStatus {
+    /// URL of the Prometheus endpoint, when served
+    pub prometheus_url: Option<String>,
+    /// Whether the metrics are pushed to the OTLP collector
+    pub otlp_active: bool,
+}
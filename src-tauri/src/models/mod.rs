@@ -12,32 +12,81 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod action;
 pub mod agent;
+pub mod agent_tool_usage;
+pub mod api_key;
+pub mod attachment;
+pub mod audit;
+pub mod behavior_changelog;
+pub mod chat_history;
+pub mod checkpoint;
+pub mod config_file;
 pub mod custom_provider;
+pub mod custom_tool;
+pub mod desktop_access;
+pub mod diagnostics;
+pub mod email;
 pub mod embedding;
+pub mod execution_trace;
+pub mod external_db;
+pub mod feed;
 pub mod function_calling;
+pub mod generation;
+pub mod health;
 pub mod import_export;
+pub mod job;
+pub mod knowledge_pack;
 pub mod llm_models;
+pub mod local_llm;
+pub mod locale;
+pub mod macro_tool;
 pub mod mcp;
 pub mod memory;
 pub mod message;
+pub mod metrics;
+pub mod moderation;
+pub mod ocr;
+pub mod outbox;
+pub mod pending_item;
+pub mod plugin;
+pub mod profile;
 pub mod prompt;
+pub mod proxy;
+pub mod regression;
+pub mod schedule;
+pub mod search;
 pub mod serde_utils;
+pub mod settings_sync;
+pub mod startup;
 pub mod streaming;
 pub mod sub_agent;
 pub mod task;
 pub mod thinking_step;
+pub mod token_anomaly;
+pub mod tool_acknowledgment;
+pub mod tool_effects;
 pub mod tool_execution;
+pub mod transcript;
+pub mod usage_stats;
 pub mod user_question;
 pub mod validation;
+pub mod watchdog;
 pub mod workflow;
+pub mod workflow_snapshot;
+pub mod workflow_trace;
+pub mod workspace_defaults;
 
 pub use agent::{
     AgentConfig, AgentConfigCreate, AgentConfigUpdate, AgentSummary, LLMConfig, Lifecycle,
+    MCPToolPolicy, ToolSettings,
 };
+pub use generation::GenerationParams;
+pub use regression::{RegressionReport, RegressionSuite};
 pub use workflow::{
-    Workflow, WorkflowCreate, WorkflowFullState, WorkflowMetrics, WorkflowResult, WorkflowStatus,
-    WorkflowToolExecution,
+    BulkTagResult, PaginatedWorkflows, Workflow, WorkflowCreate, WorkflowFullState,
+    WorkflowListQuery, WorkflowMetrics, WorkflowResult, WorkflowSortBy, WorkflowStatus,
+    WorkflowTag, WorkflowToolExecution,
 };
 
 // Re-export types for Phase 5 implementation
@@ -53,10 +102,11 @@ pub use task::{Task, TaskCreate, TaskPriority, TaskStatus, TaskUpdate};
 // ValidationMode and TimeoutBehavior are used by frontend via UpdateValidationSettingsRequest
 #[allow(unused_imports)]
 pub use validation::{
-    AuditConfig, PartialAuditConfig, PartialRiskThresholds, PartialSelectiveConfig, RiskLevel,
-    RiskThresholdConfig, SelectiveValidationConfig, TimeoutBehavior,
-    UpdateValidationSettingsRequest, ValidationMode, ValidationRequest, ValidationRequestCreate,
-    ValidationSettings, ValidationStatus, ValidationType,
+    AuditConfig, NotificationChannel, NotificationChannelType, PartialAuditConfig,
+    PartialRiskThresholds, PartialSelectiveConfig, RiskLevel, RiskThresholdConfig,
+    SelectiveValidationConfig, TimeoutBehavior, UpdateValidationSettingsRequest, ValidationMode,
+    ValidationNotificationConfig, ValidationRequest, ValidationRequestCreate, ValidationSettings,
+    ValidationStatus, ValidationType,
 };
 
 // Re-export types for future RAG/streaming phases (currently unused)
@@ -83,13 +133,13 @@ pub use message::{Message, MessageCreate, PaginatedMessages};
 pub use message::MessageRole;
 
 // Re-export tool execution types for Phase 3 Tool Execution Persistence
-pub use tool_execution::{ToolExecution, ToolExecutionCreate};
+pub use tool_execution::{ToolExecution, ToolExecutionCreate, ToolExecutionPage};
 // Re-export ToolType for future use (currently unused in commands)
 #[allow(unused_imports)]
 pub use tool_execution::ToolType;
 
 // Re-export thinking step types for Phase 4 Thinking Steps Persistence
-pub use thinking_step::{ThinkingStep, ThinkingStepCreate};
+pub use thinking_step::{ThinkingStep, ThinkingStepCreate, ThinkingStepPage};
 
 // Re-export sub-agent types for Phase 6A Sub-Agent System Infrastructure
 // These will be used in Phase B/C/D when tools are implemented
@@ -111,12 +161,14 @@ pub use llm_models::{
 };
 // Re-export builtin model data (Phase 2 will use these for seeding)
 #[allow(unused_imports)]
-pub use llm_models::{get_all_builtin_models, MISTRAL_BUILTIN_MODELS, OLLAMA_BUILTIN_MODELS};
+pub use llm_models::{
+    get_all_builtin_models, GEMINI_BUILTIN_MODELS, MISTRAL_BUILTIN_MODELS, OLLAMA_BUILTIN_MODELS,
+};
 
 // Re-export embedding settings types for Phase 5 implementation
 pub use embedding::{
     CategoryTokenStats, EmbeddingConfigSettings, EmbeddingTestResult, ExportFormat, ImportResult,
-    MemoryStats, MemoryTokenStats, RegenerateResult,
+    MemoryStats, MemoryTokenStats, RegenerateProgress, RegenerateResult,
 };
 
 // Re-export prompt library types for Prompt Library feature
@@ -0,0 +1,121 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Agent output moderation models.
+//!
+//! A [`ModerationPolicy`] is stored with an agent configuration. When enabled,
+//! the agent's response is classified before it is persisted (provider
+//! moderation endpoint or local classifier) and, if flagged, blocked, reported
+//! to the user or annotated. Every check is recorded in the audit log (see
+//! [`crate::security::audit`]).
+
+use serde::{Deserialize, Serialize};
+
+/// Action taken when a response is flagged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    /// Replace the response with a notice
+    Block,
+    /// Keep the response and warn the user
+    #[default]
+    Warn,
+    /// Keep the response and append a moderation note to it
+    Annotate,
+}
+
+impl std::fmt::Display for ModerationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModerationAction::Block => write!(f, "block"),
+            ModerationAction::Warn => write!(f, "warn"),
+            ModerationAction::Annotate => write!(f, "annotate"),
+        }
+    }
+}
+
+/// Classifier used to check responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationSource {
+    /// Moderation endpoint of the agent's provider, falling back to the local
+    /// classifier when the provider has none (e.g. Ollama) or the call fails
+    #[default]
+    Provider,
+    /// Local classifier only (no network call)
+    Local,
+}
+
+/// Output moderation policy of an agent
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModerationPolicy {
+    /// Check responses before they are persisted
+    #[serde(default)]
+    pub enabled: bool,
+    /// Action taken when a response is flagged
+    #[serde(default)]
+    pub action: ModerationAction,
+    /// Classifier used
+    #[serde(default)]
+    pub source: ModerationSource,
+    /// Additional terms flagged by the local classifier (case-insensitive, whole words)
+    #[serde(default)]
+    pub blocked_terms: Vec<String>,
+}
+
+/// Result of a moderation check, returned with the workflow result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationOutcome {
+    /// Whether the response was flagged
+    pub flagged: bool,
+    /// Flagged categories (e.g. "hate", "pii", "blocked_term")
+    pub categories: Vec<String>,
+    /// Action applied (only meaningful when flagged)
+    pub action: ModerationAction,
+    /// Classifier that produced the verdict ("mistral", custom provider name or "local")
+    pub source: String,
+}
+
+/// Parses the moderation field from its JSON string storage format.
+///
+/// Stored as a JSON string like `tool_settings` (SCHEMAFULL nested object workaround).
+pub fn parse_moderation_json(value: Option<&serde_json::Value>) -> ModerationPolicy {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| serde_json::from_str::<ModerationPolicy>(s).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_moderation_json() {
+        let value = json!(r#"{"enabled": true, "action": "block", "blocked_terms": ["secret"]}"#);
+        let policy = parse_moderation_json(Some(&value));
+        assert!(policy.enabled);
+        assert_eq!(policy.action, ModerationAction::Block);
+        assert_eq!(policy.source, ModerationSource::Provider);
+        assert_eq!(policy.blocked_terms, vec!["secret".to_string()]);
+
+        assert_eq!(parse_moderation_json(None), ModerationPolicy::default());
+        assert!(!parse_moderation_json(Some(&json!("{}"))).enabled);
+        assert_eq!(
+            parse_moderation_json(Some(&json!("not json"))),
+            ModerationPolicy::default()
+        );
+    }
+}
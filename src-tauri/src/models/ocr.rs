@@ -0,0 +1,77 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OCR settings models.
+//!
+//! [`OcrSettings`] are stored in the `settings:ocr` record and read by the
+//! OcrTool on every call. Synchronized with src/types/ocr.ts
+
+use crate::llm::embedding::DEFAULT_OLLAMA_URL;
+use crate::tools::constants::ocr as ocr_const;
+use serde::{Deserialize, Serialize};
+
+/// Text recognition engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrEngine {
+    /// Tesseract command line, with its installed language packs
+    #[default]
+    Tesseract,
+    /// Vision model served by Ollama
+    Ollama,
+}
+
+/// OCR engine settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrSettings {
+    /// Recognition engine
+    #[serde(default)]
+    pub engine: OcrEngine,
+    /// Tesseract language packs used by default (e.g. ["eng", "fra"])
+    #[serde(default = "default_languages")]
+    pub languages: Vec<String>,
+    /// Path of the tesseract executable, None to find it in the PATH
+    #[serde(default)]
+    pub tesseract_path: Option<String>,
+    /// Base URL of the Ollama server
+    #[serde(default = "default_ollama_url")]
+    pub ollama_url: String,
+    /// Vision model transcribing the images (e.g. "llama3.2-vision")
+    #[serde(default = "default_ollama_model")]
+    pub ollama_model: String,
+}
+
+fn default_languages() -> Vec<String> {
+    vec![ocr_const::DEFAULT_LANGUAGE.to_string()]
+}
+
+fn default_ollama_url() -> String {
+    DEFAULT_OLLAMA_URL.to_string()
+}
+
+fn default_ollama_model() -> String {
+    ocr_const::DEFAULT_OLLAMA_MODEL.to_string()
+}
+
+impl Default for OcrSettings {
+    fn default() -> Self {
+        Self {
+            engine: OcrEngine::default(),
+            languages: default_languages(),
+            tesseract_path: None,
+            ollama_url: default_ollama_url(),
+            ollama_model: default_ollama_model(),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistence outbox models.
+//!
+//! Records whose database write failed during a workflow execution are kept
+//! in a durable outbox file and written again later (see
+//! [`crate::db::outbox::PersistenceOutbox`]).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Record waiting in the outbox
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// Target table
+    pub table: String,
+    /// Record ID
+    pub record_id: String,
+    /// Record content (as passed to `DBClient::create`)
+    pub data: serde_json::Value,
+    /// Failed write attempts so far
+    pub attempts: u32,
+    /// Error of the last attempt
+    pub last_error: String,
+    /// When the record was queued
+    pub queued_at: DateTime<Utc>,
+}
+
+/// Result of an outbox flush (`flush_outbox`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutboxFlushResult {
+    /// Records written to the database
+    pub flushed: usize,
+    /// Records still waiting in the outbox
+    pub remaining: usize,
+    /// Error of the last failed record, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Payload of the `persistence_warning` event, emitted when a record could
+/// not be written and was queued in the outbox
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistenceWarning {
+    /// Target table
+    pub table: String,
+    /// Record ID
+    pub record_id: String,
+    /// Database error
+    pub error: String,
+    /// Records waiting in the outbox, including this one
+    pub pending: usize,
+}
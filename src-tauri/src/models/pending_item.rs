@@ -0,0 +1,421 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Items awaiting the user (priority inbox).
+//!
+//! Each subsystem records its own pending items when they appear (validation
+//! requests, agent questions, paused or interrupted runs, failed scheduled
+//! runs, expiring MCP OAuth tokens, stale or expiring API keys) and resolves them when they are handled.
+//! `get_pending_items` returns them in one list, most urgent first, with a
+//! reference to the screen where each one is handled.
+
+use crate::models::api_key::ApiKeyStatus;
+use crate::models::checkpoint::ResumableExecution;
+use crate::models::validation::{RiskLevel, ValidationType};
+use crate::tools::constants::inbox as inbox_const;
+use crate::tools::validation_helper::safe_truncate;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of pending item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingItemKind {
+    /// Operation waiting for approval
+    Validation,
+    /// Agent question waiting for an answer
+    UserQuestion,
+    /// Run paused by the token anomaly monitor, waiting for confirmation
+    PausedWorkflow,
+    /// Execution interrupted by a crash, waiting to be resumed or discarded
+    InterruptedExecution,
+    /// Last scheduled run of a schedule failed
+    FailedSchedule,
+    /// MCP OAuth access token expiring without refresh token
+    ExpiringCredential,
+    /// Provider API key stale, expiring or expired
+    ExpiringApiKey,
+}
+
+impl PendingItemKind {
+    /// All kinds
+    pub const ALL: [Self; 7] = [
+        Self::Validation,
+        Self::UserQuestion,
+        Self::PausedWorkflow,
+        Self::InterruptedExecution,
+        Self::FailedSchedule,
+        Self::ExpiringCredential,
+        Self::ExpiringApiKey,
+    ];
+
+    /// Kind name, as stored in the database
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Validation => "validation",
+            Self::UserQuestion => "user_question",
+            Self::PausedWorkflow => "paused_workflow",
+            Self::InterruptedExecution => "interrupted_execution",
+            Self::FailedSchedule => "failed_schedule",
+            Self::ExpiringCredential => "expiring_credential",
+            Self::ExpiringApiKey => "expiring_api_key",
+        }
+    }
+
+    /// Kinds whose item only exists while a run of the current session waits
+    pub fn waits_on_run(&self) -> bool {
+        matches!(
+            self,
+            Self::Validation | Self::UserQuestion | Self::PausedWorkflow
+        )
+    }
+}
+
+/// Priority of a pending item (ordered, `Urgent` highest)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingPriority {
+    Low,
+    Normal,
+    High,
+    Urgent,
+}
+
+/// Where a pending item is handled in the app
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingItemLink {
+    /// Frontend route (e.g. "/agent", "/settings/mcp")
+    pub route: String,
+    /// Workflow to open on the route
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workflow_id: Option<String>,
+    /// Record to focus (validation, question, schedule or MCP server ID)
+    pub target_id: String,
+}
+
+/// Pending item recorded by a subsystem
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingItemCreate {
+    /// Kind of item
+    pub kind: PendingItemKind,
+    /// ID of the record the item stands for (validation, question, schedule, server)
+    pub source_id: String,
+    /// Workflow concerned (None for MCP servers and API keys)
+    pub workflow_id: Option<String>,
+    /// One-line description
+    pub title: String,
+    /// Additional information (error, operation details)
+    pub detail: Option<String>,
+    /// Priority when recorded (see [`PendingItem::effective_priority`])
+    pub priority: PendingPriority,
+    /// Deadline (e.g. token expiry)
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Pending item, as returned by `get_pending_items`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingItem {
+    /// Item ID (derived from kind and source ID)
+    pub id: String,
+    /// Kind of item
+    pub kind: PendingItemKind,
+    /// ID of the record the item stands for
+    pub source_id: String,
+    /// Workflow concerned
+    #[serde(default)]
+    pub workflow_id: Option<String>,
+    /// One-line description
+    pub title: String,
+    /// Additional information
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// Priority (raised as the deadline approaches)
+    pub priority: PendingPriority,
+    /// Deadline
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Where the item is handled
+    pub link: PendingItemLink,
+    /// Time the item appeared
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingItem {
+    /// Priority at `now`: items with a deadline get more urgent as it approaches.
+    pub fn effective_priority(&self, now: DateTime<Utc>) -> PendingPriority {
+        let Some(due_at) = self.due_at else {
+            return self.priority;
+        };
+        let remaining = due_at - now;
+        let by_deadline = if remaining <= Duration::zero() {
+            PendingPriority::Urgent
+        } else if remaining <= Duration::hours(inbox_const::DUE_HIGH_HOURS) {
+            PendingPriority::High
+        } else if remaining <= Duration::hours(inbox_const::DUE_NORMAL_HOURS) {
+            PendingPriority::Normal
+        } else {
+            PendingPriority::Low
+        };
+        self.priority.max(by_deadline)
+    }
+}
+
+/// Route of the workflow page (agent screen)
+const WORKFLOW_ROUTE: &str = "/agent";
+/// Route of the MCP server settings
+const MCP_SETTINGS_ROUTE: &str = "/settings/mcp";
+/// Route of the LLM provider settings
+const PROVIDER_SETTINGS_ROUTE: &str = "/settings/providers";
+
+impl PendingItemCreate {
+    /// Link to the screen where the item is handled.
+    pub fn link(&self) -> PendingItemLink {
+        let route = match self.kind {
+            PendingItemKind::ExpiringCredential => MCP_SETTINGS_ROUTE,
+            PendingItemKind::ExpiringApiKey => PROVIDER_SETTINGS_ROUTE,
+            _ => WORKFLOW_ROUTE,
+        };
+        PendingItemLink {
+            route: route.to_string(),
+            workflow_id: self.workflow_id.clone(),
+            target_id: self.source_id.clone(),
+        }
+    }
+
+    /// Validation request waiting for approval (token anomaly pauses are paused workflows).
+    pub fn validation(
+        validation_id: &str,
+        workflow_id: &str,
+        validation_type: &ValidationType,
+        operation: &str,
+        risk_level: &RiskLevel,
+    ) -> Self {
+        let (kind, title, priority) = match validation_type {
+            ValidationType::TokenAnomaly => (
+                PendingItemKind::PausedWorkflow,
+                "Run paused: unusual token consumption".to_string(),
+                PendingPriority::High,
+            ),
+            _ => (
+                PendingItemKind::Validation,
+                format!("Approval required ({})", validation_type),
+                match risk_level {
+                    RiskLevel::High => PendingPriority::Urgent,
+                    _ => PendingPriority::High,
+                },
+            ),
+        };
+        Self {
+            kind,
+            source_id: validation_id.to_string(),
+            workflow_id: Some(workflow_id.to_string()),
+            title,
+            detail: Some(safe_truncate(
+                operation,
+                inbox_const::MAX_DETAIL_CHARS,
+                true,
+            )),
+            priority,
+            due_at: None,
+        }
+    }
+
+    /// Agent question waiting for an answer.
+    pub fn user_question(question_id: &str, workflow_id: &str, question: &str) -> Self {
+        Self {
+            kind: PendingItemKind::UserQuestion,
+            source_id: question_id.to_string(),
+            workflow_id: Some(workflow_id.to_string()),
+            title: "Agent question".to_string(),
+            detail: Some(safe_truncate(question, inbox_const::MAX_DETAIL_CHARS, true)),
+            priority: PendingPriority::High,
+            due_at: None,
+        }
+    }
+
+    /// Execution interrupted by a crash.
+    pub fn interrupted_execution(execution: &ResumableExecution) -> Self {
+        Self {
+            kind: PendingItemKind::InterruptedExecution,
+            source_id: execution.workflow_id.clone(),
+            workflow_id: Some(execution.workflow_id.clone()),
+            title: "Execution interrupted".to_string(),
+            detail: Some(format!(
+                "Stopped at iteration {} of agent {}",
+                execution.iteration, execution.agent_id
+            )),
+            priority: PendingPriority::Normal,
+            due_at: None,
+        }
+    }
+
+    /// Failed scheduled run.
+    pub fn failed_schedule(
+        schedule_id: &str,
+        schedule_name: &str,
+        workflow_id: &str,
+        error: &str,
+    ) -> Self {
+        Self {
+            kind: PendingItemKind::FailedSchedule,
+            source_id: schedule_id.to_string(),
+            workflow_id: Some(workflow_id.to_string()),
+            title: format!("Scheduled run '{}' failed", schedule_name),
+            detail: Some(safe_truncate(error, inbox_const::MAX_DETAIL_CHARS, true)),
+            priority: PendingPriority::Normal,
+            due_at: None,
+        }
+    }
+
+    /// MCP OAuth access token that cannot be refreshed.
+    pub fn expiring_credential(
+        server_id: &str,
+        server_name: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            kind: PendingItemKind::ExpiringCredential,
+            source_id: server_id.to_string(),
+            workflow_id: None,
+            title: format!("Authorization of MCP server '{}' expires", server_name),
+            detail: Some("No refresh token: authorize the server again".to_string()),
+            priority: PendingPriority::Low,
+            due_at: Some(expires_at),
+        }
+    }
+
+    /// Provider API key to rotate (stale, expiring or expired).
+    pub fn expiring_api_key(
+        provider: &str,
+        status: ApiKeyStatus,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let (title, priority) = match status {
+            ApiKeyStatus::Expired => (
+                format!("API key of '{}' expired", provider),
+                PendingPriority::High,
+            ),
+            ApiKeyStatus::ExpiringSoon => (
+                format!("API key of '{}' expires", provider),
+                PendingPriority::Normal,
+            ),
+            ApiKeyStatus::Stale | ApiKeyStatus::Ok => (
+                format!("API key of '{}' should be rotated", provider),
+                PendingPriority::Low,
+            ),
+        };
+        Self {
+            kind: PendingItemKind::ExpiringApiKey,
+            source_id: provider.to_string(),
+            workflow_id: None,
+            title,
+            detail: Some("Rotate the key in the provider settings".to_string()),
+            priority,
+            due_at: expires_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(create: PendingItemCreate) -> PendingItem {
+        PendingItem {
+            id: "item".to_string(),
+            link: create.link(),
+            kind: create.kind,
+            source_id: create.source_id,
+            workflow_id: create.workflow_id,
+            title: create.title,
+            detail: create.detail,
+            priority: create.priority,
+            due_at: create.due_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validation_items() {
+        let high = PendingItemCreate::validation(
+            "v1",
+            "wf1",
+            &ValidationType::Tool,
+            "Delete file",
+            &RiskLevel::High,
+        );
+        assert_eq!(high.kind, PendingItemKind::Validation);
+        assert_eq!(high.priority, PendingPriority::Urgent);
+        assert_eq!(
+            high.link(),
+            PendingItemLink {
+                route: "/agent".to_string(),
+                workflow_id: Some("wf1".to_string()),
+                target_id: "v1".to_string(),
+            }
+        );
+
+        let paused = PendingItemCreate::validation(
+            "v2",
+            "wf1",
+            &ValidationType::TokenAnomaly,
+            "Token spike",
+            &RiskLevel::High,
+        );
+        assert_eq!(paused.kind, PendingItemKind::PausedWorkflow);
+        assert_eq!(paused.priority, PendingPriority::High);
+    }
+
+    #[test]
+    fn test_deadline_raises_priority() {
+        let now = Utc::now();
+        let expiring = |hours: i64| {
+            item(PendingItemCreate::expiring_credential(
+                "srv",
+                "github",
+                now + Duration::hours(hours),
+            ))
+        };
+
+        assert_eq!(
+            expiring(24 * 7).effective_priority(now),
+            PendingPriority::Low
+        );
+        assert_eq!(
+            expiring(48).effective_priority(now),
+            PendingPriority::Normal
+        );
+        assert_eq!(expiring(2).effective_priority(now), PendingPriority::High);
+        assert_eq!(
+            expiring(-1).effective_priority(now),
+            PendingPriority::Urgent
+        );
+        assert_eq!(expiring(2).link.route, "/settings/mcp");
+
+        let api_key = item(PendingItemCreate::expiring_api_key(
+            "Mistral",
+            ApiKeyStatus::Stale,
+            None,
+        ));
+        assert_eq!(api_key.effective_priority(now), PendingPriority::Low);
+        assert_eq!(api_key.link.route, "/settings/providers");
+        assert_eq!(api_key.link.target_id, "Mistral");
+
+        // Items without deadline keep their priority
+        let failed = item(PendingItemCreate::failed_schedule(
+            "s1", "Inbox", "wf1", "boom",
+        ));
+        assert_eq!(failed.effective_priority(now), PendingPriority::Normal);
+    }
+}
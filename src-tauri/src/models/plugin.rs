@@ -0,0 +1,91 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tool plugin models.
+//!
+//! Plugins are installed once in `~/.zileo/plugins` (see [`crate::plugins`]);
+//! what each one may do is granted per profile in the `settings:plugins`
+//! record. Synchronized with src/types/plugin.ts
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Host function a plugin can only use once granted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginCapability {
+    /// HTTP requests to the hosts listed in the manifest
+    Network,
+    /// Reading local text files
+    ReadFiles,
+}
+
+impl PluginCapability {
+    /// Name of the capability, as serialized.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Network => "network",
+            Self::ReadFiles => "read_files",
+        }
+    }
+}
+
+/// User decision for one installed plugin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginGrant {
+    /// Whether agents can use the plugin
+    #[serde(default)]
+    pub enabled: bool,
+    /// Capabilities granted by the user (a subset of the requested ones)
+    #[serde(default)]
+    pub capabilities: Vec<PluginCapability>,
+    /// SHA-256 of the manifest and component the grant was given for; a
+    /// modified plugin is disabled until approved again
+    pub sha256: String,
+}
+
+/// Capability grants of the installed plugins, keyed by plugin ID
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PluginSettings {
+    #[serde(default)]
+    pub plugins: HashMap<String, PluginGrant>,
+}
+
+/// Installed plugin, as listed in the settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginInfo {
+    /// Plugin ID (directory name in `~/.zileo/plugins`)
+    pub id: String,
+    /// Tool name given to agents (`plugin__<id>`)
+    pub tool_name: String,
+    /// Display name from the manifest
+    pub name: String,
+    /// Version from the manifest
+    pub version: String,
+    /// Description from the manifest
+    pub description: String,
+    /// Capabilities requested by the manifest
+    pub requested_capabilities: Vec<PluginCapability>,
+    /// Hosts the plugin requests network access to
+    pub network_hosts: Vec<String>,
+    /// Directories the plugin requests read access to
+    pub read_dirs: Vec<String>,
+    /// Capabilities granted in the active profile
+    pub granted_capabilities: Vec<PluginCapability>,
+    /// Whether agents of the active profile can use the plugin
+    pub enabled: bool,
+    /// Why the plugin cannot be used (invalid manifest, component modified
+    /// since it was approved...)
+    pub error: Option<String>,
+}
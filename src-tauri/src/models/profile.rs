@@ -0,0 +1,70 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Profile models.
+//!
+//! A profile (e.g. "Work", "Personal") is an isolated data set: its own
+//! database, persistence outbox and attachments (see [`crate::profiles`]).
+
+use crate::tools::constants::profiles::DEFAULT_PROFILE_ID;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Profile ID (derived from the name, used as directory name)
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Registry of the profiles, as stored in `profiles.json` and returned by
+/// the profile commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileRegistry {
+    /// ID of the active profile
+    pub active: String,
+    /// Profiles, in creation order
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE_ID.to_string(),
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: "Default".to_string(),
+                created_at: Utc::now(),
+            }],
+        }
+    }
+}
+
+impl ProfileRegistry {
+    /// Profile with the given ID.
+    pub fn get(&self, id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.id == id)
+    }
+}
+
+/// Payload of the `profile_switched` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileSwitchedEvent {
+    /// Profile now active
+    pub profile: Profile,
+}
@@ -17,6 +17,7 @@
 //! Types for managing reusable prompt templates with variable interpolation.
 //! Synchronized with src/types/prompt.ts
 
+use crate::models::mcp::MCPPrompt;
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -78,6 +79,15 @@ pub struct Prompt {
     pub variables: Vec<PromptVariable>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// MCP server providing the prompt (None for library prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_server: Option<String>,
+    /// Number of workflows launched from the prompt
+    #[serde(default)]
+    pub usage_count: u64,
+    /// Last launch of a workflow from the prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 /// Lightweight prompt for list display
@@ -89,6 +99,60 @@ pub struct PromptSummary {
     pub category: PromptCategory,
     pub variables_count: u32,
     pub updated_at: DateTime<Utc>,
+    /// MCP server providing the prompt (None for library prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_server: Option<String>,
+    /// Number of workflows launched from the prompt
+    #[serde(default)]
+    pub usage_count: u64,
+}
+
+/// Prompt rendered with variable values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderedPrompt {
+    pub prompt_id: String,
+    /// Content with the values (and default values) substituted
+    pub content: String,
+    /// Variables without a value nor a default value, left as `{{name}}`
+    pub missing_variables: Vec<String>,
+}
+
+/// Saved state of a prompt; each create or update adds a version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptVersion {
+    pub prompt_id: String,
+    /// Version number, starting at 1
+    pub version: u32,
+    pub name: String,
+    pub description: String,
+    pub category: PromptCategory,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Output of one prompt version in a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptVersionRun {
+    pub version: u32,
+    /// Rendered prompt sent to the agent
+    pub input: String,
+    pub response: String,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub tokens_input: u64,
+    pub tokens_output: u64,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+}
+
+/// Side-by-side outputs of two prompt versions run with the same agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptComparison {
+    pub prompt_id: String,
+    pub agent_id: String,
+    pub a: PromptVersionRun,
+    pub b: PromptVersionRun,
 }
 
 /// Data for creating a new prompt
@@ -160,7 +224,6 @@ impl Prompt {
     /// let result = Prompt::interpolate(content, &values);
     /// assert_eq!(result, "Hello Alice!");
     /// ```
-    #[allow(dead_code)] // Used in Phase 3 (Frontend)
     pub fn interpolate(content: &str, values: &HashMap<String, String>) -> String {
         let pattern =
             Regex::new(r"\{\{([a-zA-Z_][a-zA-Z0-9_]*)\}\}").expect("Invalid regex pattern");
@@ -175,6 +238,65 @@ impl Prompt {
             })
             .into_owned()
     }
+
+    /// Values used to render the prompt: the non-blank given values, then the
+    /// default values of the variables.
+    fn effective_values(&self, values: &HashMap<String, String>) -> HashMap<String, String> {
+        let mut effective: HashMap<String, String> = values
+            .iter()
+            .filter(|(_, value)| !value.trim().is_empty())
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        for variable in &self.variables {
+            if let Some(default) = &variable.default_value {
+                effective
+                    .entry(variable.name.clone())
+                    .or_insert_with(|| default.clone());
+            }
+        }
+        effective
+    }
+
+    /// Variables of the content without a value nor a default value.
+    pub fn missing_variables(&self, values: &HashMap<String, String>) -> Vec<String> {
+        let effective = self.effective_values(values);
+        Self::detect_variables(&self.content)
+            .into_iter()
+            .map(|variable| variable.name)
+            .filter(|name| !effective.contains_key(name))
+            .collect()
+    }
+
+    /// Renders the content with the variable values.
+    ///
+    /// Blank values count as missing; missing variables fall back to their
+    /// default value or stay as `{{name}}` and are listed in the result.
+    pub fn render(&self, values: &HashMap<String, String>) -> RenderedPrompt {
+        RenderedPrompt {
+            prompt_id: self.id.clone(),
+            content: Self::interpolate(&self.content, &self.effective_values(values)),
+            missing_variables: self.missing_variables(values),
+        }
+    }
+}
+
+impl PromptVersion {
+    /// Prompt as it was at this version, for rendering.
+    pub fn to_prompt(&self) -> Prompt {
+        Prompt {
+            id: self.prompt_id.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            category: self.category.clone(),
+            content: self.content.clone(),
+            variables: Prompt::detect_variables(&self.content),
+            created_at: self.created_at,
+            updated_at: self.created_at,
+            mcp_server: None,
+            usage_count: 0,
+            last_used_at: None,
+        }
+    }
 }
 
 impl From<&Prompt> for PromptSummary {
@@ -186,6 +308,43 @@ impl From<&Prompt> for PromptSummary {
             category: prompt.category.clone(),
             variables_count: prompt.variables.len() as u32,
             updated_at: prompt.updated_at,
+            mcp_server: prompt.mcp_server.clone(),
+            usage_count: prompt.usage_count,
+        }
+    }
+}
+
+// ===== MCP Prompts =====
+
+impl Prompt {
+    /// Builds a library prompt from a prompt discovered on an MCP server.
+    ///
+    /// The ID is `mcp:{server}:{name}` and the prompt arguments become its
+    /// variables. The server renders the content from the argument values,
+    /// so `content` holds the rendered text (empty until rendered) rather
+    /// than `{{variable}}` placeholders.
+    pub fn from_mcp(server_name: &str, prompt: &MCPPrompt, content: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: format!("mcp:{}:{}", server_name, prompt.name),
+            name: prompt.name.clone(),
+            description: prompt.description.clone().unwrap_or_default(),
+            category: PromptCategory::User,
+            content,
+            variables: prompt
+                .arguments
+                .iter()
+                .map(|arg| PromptVariable {
+                    name: arg.name.clone(),
+                    description: arg.description.clone(),
+                    default_value: None,
+                })
+                .collect(),
+            created_at: now,
+            updated_at: now,
+            mcp_server: Some(server_name.to_string()),
+            usage_count: 0,
+            last_used_at: None,
         }
     }
 }
@@ -311,10 +470,72 @@ mod tests {
             }],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            mcp_server: None,
+            usage_count: 0,
+            last_used_at: None,
         };
         let summary = PromptSummary::from(&prompt);
         assert_eq!(summary.id, "test-id");
         assert_eq!(summary.name, "Test Prompt");
         assert_eq!(summary.variables_count, 1);
+        assert!(summary.mcp_server.is_none());
+    }
+
+    #[test]
+    fn test_render_and_missing_variables() {
+        let mut prompt = Prompt::from_mcp(
+            "local",
+            &MCPPrompt {
+                name: "review".to_string(),
+                description: None,
+                arguments: vec![],
+            },
+            "Review {{file}} for {{focus}} in {{lang}}".to_string(),
+        );
+        prompt.variables = vec![PromptVariable {
+            name: "lang".to_string(),
+            description: None,
+            default_value: Some("Rust".to_string()),
+        }];
+
+        let mut values = HashMap::new();
+        values.insert("file".to_string(), "main.rs".to_string());
+        values.insert("focus".to_string(), "  ".to_string());
+
+        let rendered = prompt.render(&values);
+        assert_eq!(rendered.content, "Review main.rs for {{focus}} in Rust");
+        assert_eq!(rendered.missing_variables, vec!["focus".to_string()]);
+        assert_eq!(rendered.prompt_id, "mcp:local:review");
+
+        values.insert("focus".to_string(), "safety".to_string());
+        values.insert("lang".to_string(), "Go".to_string());
+        let rendered = prompt.render(&values);
+        assert_eq!(rendered.content, "Review main.rs for safety in Go");
+        assert!(prompt.missing_variables(&values).is_empty());
+    }
+
+    #[test]
+    fn test_prompt_from_mcp() {
+        use crate::models::mcp::MCPPromptArgument;
+
+        let mcp_prompt = MCPPrompt {
+            name: "code_review".to_string(),
+            description: Some("Review code".to_string()),
+            arguments: vec![MCPPromptArgument {
+                name: "code".to_string(),
+                description: Some("Code to review".to_string()),
+                required: true,
+            }],
+        };
+        let prompt = Prompt::from_mcp("GitHub", &mcp_prompt, String::new());
+        assert_eq!(prompt.id, "mcp:GitHub:code_review");
+        assert_eq!(prompt.description, "Review code");
+        assert_eq!(prompt.category, PromptCategory::User);
+        assert_eq!(prompt.variables[0].name, "code");
+        assert_eq!(prompt.mcp_server.as_deref(), Some("GitHub"));
+
+        let json = serde_json::to_value(PromptSummary::from(&prompt)).unwrap();
+        assert_eq!(json["mcp_server"], "GitHub");
+        assert_eq!(json["variables_count"], 1);
     }
 }
@@ -0,0 +1,73 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proxy settings models.
+//!
+//! Outgoing requests of the LLM providers, the embedding service and the MCP
+//! HTTP transports go through a global proxy, which each of them may override
+//! (see [`crate::proxy`]). Saved with `update_proxy_settings` and overridable
+//! by the `[proxy]` table of `~/.zileo/config.toml`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Proxy of one target, overriding the global proxy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ProxyOverride {
+    /// Direct connection (global proxy bypassed)
+    Direct,
+    /// Dedicated proxy
+    Custom {
+        /// Proxy URL (http, https, socks5 or socks5h)
+        url: String,
+    },
+}
+
+/// Proxy settings of the outgoing requests
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProxySettings {
+    /// Global proxy URL (http, https, socks5 or socks5h); None = direct connection
+    pub url: Option<String>,
+    /// Hosts reached without the global proxy ("example.com" also matches its
+    /// subdomains, "*" matches all hosts); loopback addresses always bypass proxies
+    pub no_proxy: Vec<String>,
+    /// Per-target overrides, keyed by provider ("mistral", "ollama", "gemini",
+    /// custom provider name), "embedding" or "mcp"
+    pub overrides: BTreeMap<String, ProxyOverride>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_settings_serialization() {
+        let settings: ProxySettings = serde_json::from_value(serde_json::json!({
+            "url": "socks5h://127.0.0.1:1080",
+            "overrides": {
+                "ollama": {"mode": "direct"},
+                "mcp": {"mode": "custom", "url": "http://proxy.local:3128"}
+            }
+        }))
+        .unwrap();
+        assert!(settings.no_proxy.is_empty());
+        assert_eq!(settings.overrides["ollama"], ProxyOverride::Direct);
+
+        let json = serde_json::to_value(&settings).unwrap();
+        assert_eq!(json["overrides"]["mcp"]["mode"], "custom");
+        assert_eq!(json["overrides"]["mcp"]["url"], "http://proxy.local:3128");
+    }
+}
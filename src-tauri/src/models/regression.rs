@@ -0,0 +1,271 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Agent regression test models.
+//!
+//! A [`RegressionSuite`] is stored with an agent configuration and holds
+//! evaluation fixtures (input + assertions). Suites are executed by the
+//! `run_agent_regressions` command; when `strict` is set, system prompt
+//! updates are rejected if any case fails.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Regression test cases bundled with an agent
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegressionSuite {
+    /// Block system prompt updates when a regression fails
+    #[serde(default)]
+    pub strict: bool,
+    /// Test cases executed in order
+    #[serde(default)]
+    pub cases: Vec<RegressionCase>,
+}
+
+impl RegressionSuite {
+    /// Returns true if the suite has no cases to run.
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+}
+
+/// Single regression test case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressionCase {
+    /// Case name (unique within the suite)
+    pub name: String,
+    /// User input sent to the agent
+    pub input: String,
+    /// Assertions evaluated against the agent's response
+    pub assertions: Vec<RegressionAssertion>,
+    /// Mocked tool outputs, keyed by function name (`MemoryTool`, `mcp__server__tool`)
+    ///
+    /// Mocked tools return the given value without executing or requesting validation.
+    #[serde(default)]
+    pub mock_tools: HashMap<String, serde_json::Value>,
+}
+
+/// Assertion evaluated against a regression case result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RegressionAssertion {
+    /// Response contains the text (case-insensitive)
+    Contains { value: String },
+    /// Response does not contain the text (case-insensitive)
+    NotContains { value: String },
+    /// Response matches the regular expression
+    Regex { pattern: String },
+    /// Tool was called at least once
+    ToolCalled { tool: String },
+    /// Tool was never called
+    ToolNotCalled { tool: String },
+}
+
+impl RegressionAssertion {
+    /// Checks that the assertion is well-formed (non-empty value, valid regex).
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Contains { value } | Self::NotContains { value } if value.is_empty() => {
+                Err("Assertion value cannot be empty".to_string())
+            }
+            Self::ToolCalled { tool } | Self::ToolNotCalled { tool } if tool.trim().is_empty() => {
+                Err("Assertion tool cannot be empty".to_string())
+            }
+            Self::Regex { pattern } => Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid regex '{}': {}", pattern, e)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Evaluates the assertion.
+    ///
+    /// # Arguments
+    /// * `response` - Final agent response
+    /// * `tools_called` - Names of the tools called (local and MCP)
+    ///
+    /// # Returns
+    /// None if the assertion holds, otherwise a failure description
+    pub fn evaluate(&self, response: &str, tools_called: &[String]) -> Option<String> {
+        let lower = response.to_lowercase();
+        match self {
+            Self::Contains { value } => (!lower.contains(&value.to_lowercase()))
+                .then(|| format!("Response does not contain '{}'", value)),
+            Self::NotContains { value } => lower
+                .contains(&value.to_lowercase())
+                .then(|| format!("Response contains '{}'", value)),
+            Self::Regex { pattern } => match Regex::new(pattern) {
+                Ok(re) => (!re.is_match(response))
+                    .then(|| format!("Response does not match /{}/", pattern)),
+                Err(e) => Some(format!("Invalid regex '{}': {}", pattern, e)),
+            },
+            Self::ToolCalled { tool } => (!tools_called.iter().any(|t| t == tool))
+                .then(|| format!("Tool '{}' was not called", tool)),
+            Self::ToolNotCalled { tool } => tools_called
+                .iter()
+                .any(|t| t == tool)
+                .then(|| format!("Tool '{}' was called", tool)),
+        }
+    }
+}
+
+/// Result of a single regression case
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionCaseResult {
+    /// Case name
+    pub name: String,
+    /// Whether all assertions held
+    pub passed: bool,
+    /// Failure descriptions (assertion failures or execution error)
+    pub failures: Vec<String>,
+    /// Final agent response
+    pub response: String,
+    /// Tools called during the case
+    pub tools_called: Vec<String>,
+    /// Execution duration in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Result of a regression suite run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionReport {
+    /// Agent the suite belongs to
+    pub agent_id: String,
+    /// Whether every case passed
+    pub passed: bool,
+    /// Number of cases executed
+    pub total: usize,
+    /// Number of failed cases
+    pub failed: usize,
+    /// Per-case results
+    pub results: Vec<RegressionCaseResult>,
+}
+
+impl RegressionReport {
+    /// Builds a report from case results.
+    pub fn new(agent_id: String, results: Vec<RegressionCaseResult>) -> Self {
+        let failed = results.iter().filter(|r| !r.passed).count();
+        Self {
+            agent_id,
+            passed: failed == 0,
+            total: results.len(),
+            failed,
+            results,
+        }
+    }
+}
+
+/// Parses the regressions field from its JSON string storage format.
+///
+/// Stored as a JSON string like `tool_settings` (SCHEMAFULL nested object workaround).
+pub fn parse_regressions_json(value: Option<&serde_json::Value>) -> RegressionSuite {
+    value
+        .and_then(|v| v.as_str())
+        .and_then(|s| serde_json::from_str::<RegressionSuite>(s).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_assertion_deserialization() {
+        let assertion: RegressionAssertion =
+            serde_json::from_value(json!({"type": "tool_called", "tool": "MemoryTool"})).unwrap();
+        assert_eq!(
+            assertion,
+            RegressionAssertion::ToolCalled {
+                tool: "MemoryTool".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_assertion_evaluate() {
+        let tools = vec!["MemoryTool".to_string()];
+        let response = "The answer is 42.";
+
+        let contains = RegressionAssertion::Contains {
+            value: "ANSWER".to_string(),
+        };
+        assert!(contains.evaluate(response, &tools).is_none());
+
+        let not_contains = RegressionAssertion::NotContains {
+            value: "answer".to_string(),
+        };
+        assert!(not_contains.evaluate(response, &tools).is_some());
+
+        let regex = RegressionAssertion::Regex {
+            pattern: r"\b\d+\b".to_string(),
+        };
+        assert!(regex.evaluate(response, &tools).is_none());
+
+        let called = RegressionAssertion::ToolCalled {
+            tool: "TodoTool".to_string(),
+        };
+        assert!(called.evaluate(response, &tools).is_some());
+
+        let not_called = RegressionAssertion::ToolNotCalled {
+            tool: "TodoTool".to_string(),
+        };
+        assert!(not_called.evaluate(response, &tools).is_none());
+    }
+
+    #[test]
+    fn test_assertion_validate() {
+        assert!(RegressionAssertion::Regex {
+            pattern: "(".to_string()
+        }
+        .validate()
+        .is_err());
+        assert!(RegressionAssertion::Contains {
+            value: String::new()
+        }
+        .validate()
+        .is_err());
+        assert!(RegressionAssertion::ToolCalled {
+            tool: "MemoryTool".to_string()
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_report_counts() {
+        let result = |passed| RegressionCaseResult {
+            name: "case".to_string(),
+            passed,
+            failures: vec![],
+            response: String::new(),
+            tools_called: vec![],
+            duration_ms: 0,
+        };
+        let report = RegressionReport::new("agent".to_string(), vec![result(true), result(false)]);
+        assert!(!report.passed);
+        assert_eq!(report.total, 2);
+        assert_eq!(report.failed, 1);
+    }
+
+    #[test]
+    fn test_parse_regressions_json() {
+        let value = json!(r#"{"strict":true,"cases":[{"name":"a","input":"hi","assertions":[]}]}"#);
+        let suite = parse_regressions_json(Some(&value));
+        assert!(suite.strict);
+        assert_eq!(suite.cases.len(), 1);
+        assert!(parse_regressions_json(None).is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduled workflow run models.
+//!
+//! A schedule runs a prompt with an agent in a workflow on a cron
+//! expression. Runs are stored as regular messages of the workflow.
+//! Synchronized with src/types/schedule.ts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of the last run of a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleRunStatus {
+    /// The response was stored in the workflow
+    Success,
+    /// The run failed (error stored as a system message of the workflow)
+    Failed,
+}
+
+impl ScheduleRunStatus {
+    /// Returns the status as stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Scheduled workflow run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    /// Schedule ID (UUID)
+    pub id: String,
+    /// Schedule name
+    pub name: String,
+    /// Cron expression (local time)
+    pub cron: String,
+    /// Agent executing the prompt
+    pub agent_id: String,
+    /// Workflow receiving the messages of the runs
+    pub workflow_id: String,
+    /// Prompt sent at each run
+    pub prompt: String,
+    /// Paused schedules do not run
+    pub paused: bool,
+    /// Next run (None when paused)
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Start of the last run
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Outcome of the last run
+    pub last_status: Option<ScheduleRunStatus>,
+    /// Error of the last run, if it failed
+    pub last_error: Option<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Payload for creating a schedule.
+///
+/// ID and timestamps are generated server-side (`next_run_at` is set after
+/// creation with a datetime cast).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleCreate {
+    /// Schedule name
+    pub name: String,
+    /// Cron expression
+    pub cron: String,
+    /// Agent executing the prompt
+    pub agent_id: String,
+    /// Workflow receiving the messages of the runs
+    pub workflow_id: String,
+    /// Prompt sent at each run
+    pub prompt: String,
+    /// Whether the schedule starts paused
+    pub paused: bool,
+}
+
+/// Payload of the `schedule_run` event, emitted when a run finishes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRunEvent {
+    /// Schedule ID
+    pub schedule_id: String,
+    /// Workflow that received the messages
+    pub workflow_id: String,
+    /// Outcome of the run
+    pub status: ScheduleRunStatus,
+    /// Error, if the run failed
+    pub error: Option<String>,
+}
@@ -0,0 +1,261 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Full-text search over past conversations.
+//!
+//! Message contents and workflow names are indexed by the database
+//! (`text_search` analyzer). Matches come back as highlight segments rather
+//! than markup, so the frontend never renders stored content as HTML.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Opening marker of a match in highlighted text (never rendered)
+pub const MATCH_START: char = '\u{2}';
+/// Closing marker of a match in highlighted text (never rendered)
+pub const MATCH_END: char = '\u{3}';
+
+/// Search terms and pagination
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchQuery {
+    /// Keywords (all must match)
+    pub text: String,
+    /// Maximum number of hits (default 20, max 100)
+    pub limit: Option<u32>,
+    /// Number of hits to skip
+    pub offset: Option<u32>,
+}
+
+/// Part of a highlighted text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HighlightSegment {
+    /// Text of the segment
+    pub text: String,
+    /// Whether the segment matches a search keyword
+    pub matched: bool,
+}
+
+/// Message matching a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageSearchHit {
+    /// Message ID
+    pub message_id: String,
+    /// Workflow of the message
+    pub workflow_id: String,
+    /// Name of the workflow (None if it no longer exists)
+    pub workflow_name: Option<String>,
+    /// Message role (user, assistant, system)
+    pub role: String,
+    /// Message timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Relevance (BM25), higher is better
+    pub score: f64,
+    /// Excerpt of the content around the first match
+    pub snippet: Vec<HighlightSegment>,
+}
+
+/// Workflow whose name matches a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSearchHit {
+    /// Workflow ID
+    pub workflow_id: String,
+    /// Workflow name, with the matches highlighted
+    pub name: Vec<HighlightSegment>,
+    /// Agent of the workflow
+    pub agent_id: String,
+    /// Last activity
+    pub updated_at: DateTime<Utc>,
+    /// Relevance (BM25), higher is better
+    pub score: f64,
+}
+
+/// Page of search hits, most relevant first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResults<T> {
+    /// Hits in the current page
+    pub hits: Vec<T>,
+    /// Total number of hits
+    pub total: u32,
+    /// Current offset (number of hits skipped)
+    pub offset: u32,
+    /// Page size limit
+    pub limit: u32,
+    /// Whether more hits are available after this page
+    pub has_more: bool,
+}
+
+/// Splits a text highlighted with [`MATCH_START`] / [`MATCH_END`] into segments.
+pub fn parse_highlighted(highlighted: &str) -> Vec<HighlightSegment> {
+    let mut segments: Vec<HighlightSegment> = Vec::new();
+    let mut current = String::new();
+    let mut matched = false;
+
+    for c in highlighted.chars() {
+        let toggle = match c {
+            MATCH_START => !matched,
+            MATCH_END => matched,
+            _ => false,
+        };
+        if toggle {
+            if !current.is_empty() {
+                segments.push(HighlightSegment {
+                    text: std::mem::take(&mut current),
+                    matched,
+                });
+            }
+            matched = !matched;
+        } else if c != MATCH_START && c != MATCH_END {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        segments.push(HighlightSegment {
+            text: current,
+            matched,
+        });
+    }
+    segments
+}
+
+/// Builds an excerpt of a highlighted text around its first match.
+///
+/// Keeps `context_chars` characters before the first match and fills the
+/// rest of the `2 * context_chars` budget after it; cut ends get an ellipsis.
+pub fn highlight_snippet(highlighted: &str, context_chars: usize) -> Vec<HighlightSegment> {
+    let segments = parse_highlighted(highlighted);
+    let Some(first_match) = segments.iter().position(|s| s.matched) else {
+        // No match to center on: start of the text
+        return truncate_segments(segments, 0, 2 * context_chars);
+    };
+
+    let before: usize = segments[..first_match]
+        .iter()
+        .map(|s| s.text.chars().count())
+        .sum();
+    let start = before.saturating_sub(context_chars);
+    truncate_segments(segments, start, 2 * context_chars)
+}
+
+/// Keeps `budget` characters of the segments from character `start`.
+fn truncate_segments(
+    segments: Vec<HighlightSegment>,
+    start: usize,
+    budget: usize,
+) -> Vec<HighlightSegment> {
+    let total: usize = segments.iter().map(|s| s.text.chars().count()).sum();
+    let end = (start + budget).min(total);
+
+    let mut result = Vec::new();
+    let mut position = 0;
+    for segment in segments {
+        let len = segment.text.chars().count();
+        let (from, to) = (start.max(position), end.min(position + len));
+        if from < to {
+            let text: String = segment
+                .text
+                .chars()
+                .skip(from - position)
+                .take(to - from)
+                .collect();
+            result.push(HighlightSegment {
+                text,
+                matched: segment.matched,
+            });
+        }
+        position += len;
+    }
+
+    if start > 0 {
+        prepend_ellipsis(&mut result);
+    }
+    if end < total {
+        result.push(HighlightSegment {
+            text: "…".to_string(),
+            matched: false,
+        });
+    }
+    result
+}
+
+/// Marks the start of a cut excerpt.
+fn prepend_ellipsis(segments: &mut Vec<HighlightSegment>) {
+    match segments.first_mut() {
+        Some(first) if !first.matched => first.text.insert(0, '…'),
+        _ => segments.insert(
+            0,
+            HighlightSegment {
+                text: "…".to_string(),
+                matched: false,
+            },
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlighted(text: &str) -> String {
+        text.replace('[', &MATCH_START.to_string())
+            .replace(']', &MATCH_END.to_string())
+    }
+
+    fn render(segments: &[HighlightSegment]) -> String {
+        segments
+            .iter()
+            .map(|s| {
+                if s.matched {
+                    format!("[{}]", s.text)
+                } else {
+                    s.text.clone()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_highlighted() {
+        let segments = parse_highlighted(&highlighted("Deploy the [release] to [staging]"));
+        assert_eq!(segments.len(), 4);
+        assert_eq!(
+            segments[1],
+            HighlightSegment {
+                text: "release".to_string(),
+                matched: true
+            }
+        );
+        assert_eq!(render(&segments), "Deploy the [release] to [staging]");
+        assert!(parse_highlighted("").is_empty());
+    }
+
+    #[test]
+    fn test_snippet_centers_on_first_match() {
+        let text = format!("{}[needle] tail", "a".repeat(50));
+        let snippet = highlight_snippet(&highlighted(&text), 10);
+        assert_eq!(render(&snippet), "…aaaaaaaaaa[needle] tai…");
+
+        let text = format!("start [needle]{}", "b".repeat(50));
+        let snippet = highlight_snippet(&highlighted(&text), 10);
+        assert_eq!(render(&snippet), "start [needle]bbbbbbbb…");
+    }
+
+    #[test]
+    fn test_snippet_without_match_and_multibyte() {
+        let snippet = highlight_snippet("été à la plage", 3);
+        assert_eq!(render(&snippet), "été à …");
+
+        let snippet = highlight_snippet(&highlighted("ça [marché]"), 40);
+        assert_eq!(render(&snippet), "ça [marché]");
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Settings sync models.
+//!
+//! A pairing links this machine to another machine of the user; its key is
+//! stored in the keystore, the `sync_pairing` table keeps the rest.
+//! Synchronized with src/types/sync.ts
+
+use super::import_export::{ExportPackage, ImportCounts, ImportError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Side of a pairing (each side writes its own mailbox, reads the other)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncRole {
+    /// Machine that created the pairing code
+    Initiator,
+    /// Machine that accepted the pairing code
+    Responder,
+}
+
+impl SyncRole {
+    /// Returns the role of the other machine.
+    pub fn peer(self) -> Self {
+        match self {
+            Self::Initiator => Self::Responder,
+            Self::Responder => Self::Initiator,
+        }
+    }
+
+    /// Returns the role as stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Initiator => "initiator",
+            Self::Responder => "responder",
+        }
+    }
+}
+
+/// How sealed payloads travel between the machines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncTransport {
+    /// Mailbox relay server reachable by both machines
+    Relay,
+    /// Mailbox server hosted by the initiator on the local network
+    Lan,
+}
+
+impl SyncTransport {
+    /// Returns the transport as stored in the database.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Relay => "relay",
+            Self::Lan => "lan",
+        }
+    }
+}
+
+/// Pairing with another machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncPairing {
+    /// Pairing ID (UUID, shared by both machines)
+    pub id: String,
+    /// Name of this machine, sent to the peer
+    pub device_name: String,
+    /// Name of the other machine (known after its first payload for the initiator)
+    #[serde(default)]
+    pub peer_name: Option<String>,
+    /// Side of this machine
+    pub role: SyncRole,
+    /// Payload transport
+    pub transport: SyncTransport,
+    /// Base URL of the mailbox server (relay or initiator on the local network)
+    pub mailbox_url: String,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last successful sync
+    #[serde(default)]
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Creation time of the last payload applied from the peer
+    #[serde(default)]
+    pub last_received_at: Option<DateTime<Utc>>,
+}
+
+/// Payload for creating a pairing record.
+///
+/// ID and timestamps are set server-side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPairingCreate {
+    /// Name of this machine
+    pub device_name: String,
+    /// Name of the other machine
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_name: Option<String>,
+    /// Side of this machine
+    pub role: SyncRole,
+    /// Payload transport
+    pub transport: SyncTransport,
+    /// Base URL of the mailbox server
+    pub mailbox_url: String,
+}
+
+/// New pairing with its code, to show on this machine and enter on the other
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncPairingOffer {
+    /// The stored pairing
+    pub pairing: SyncPairing,
+    /// Pairing code (contains the pairing key: show it to the user only)
+    pub code: String,
+    /// Pairing code as an SVG QR code
+    pub qr_svg: String,
+}
+
+/// Settings sent to the peer (sealed with the pairing key)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSnapshot {
+    /// Name of the sending machine
+    pub device_name: String,
+    /// Creation time (payloads not newer than the last applied one are ignored)
+    pub created_at: DateTime<Utc>,
+    /// Agents, prompts and sanitized MCP servers, with timestamps
+    pub package: ExportPackage,
+}
+
+/// Outcome of a sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResult {
+    /// Pairing ID
+    pub pairing_id: String,
+    /// Entities sent to the peer
+    pub pushed: ImportCounts,
+    /// Whether a new payload of the peer was applied
+    pub received: bool,
+    /// Entities created or updated from the peer payload
+    pub imported: ImportCounts,
+    /// Entities of the peer payload left unchanged (identical or older)
+    pub skipped: ImportCounts,
+    /// Entity import errors
+    pub errors: Vec<ImportError>,
+    /// Sync timestamp
+    pub synced_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_role() {
+        assert_eq!(SyncRole::Initiator.peer(), SyncRole::Responder);
+        assert_eq!(SyncRole::Responder.peer(), SyncRole::Initiator);
+        assert_eq!(
+            serde_json::to_value(SyncRole::Responder).unwrap(),
+            serde_json::json!(SyncRole::Responder.as_str())
+        );
+        assert_eq!(
+            serde_json::to_value(SyncTransport::Lan).unwrap(),
+            serde_json::json!(SyncTransport::Lan.as_str())
+        );
+    }
+}
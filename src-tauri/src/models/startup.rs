@@ -0,0 +1,188 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Startup progress models.
+//!
+//! Only the database is initialized before the window shows. The other
+//! [`StartupPhase`]s run concurrently afterwards; the [`StartupStatus`] is
+//! emitted as the `startup_progress` event after each phase transition and
+//! returned by the `get_startup_status` command.
+
+use serde::{Deserialize, Serialize};
+
+/// Initialization phase of the application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    /// Database connection and schema
+    Database,
+    /// LLM providers restored from the keystore and custom providers
+    Providers,
+    /// Embedding service restored from the saved configuration
+    Embedding,
+    /// Builtin LLM models seeding
+    Models,
+    /// Enabled MCP servers connection
+    McpServers,
+    /// Agents loaded from the database
+    Agents,
+}
+
+impl StartupPhase {
+    /// All phases, in display order
+    pub const ALL: [StartupPhase; 6] = [
+        StartupPhase::Database,
+        StartupPhase::Providers,
+        StartupPhase::Embedding,
+        StartupPhase::Models,
+        StartupPhase::McpServers,
+        StartupPhase::Agents,
+    ];
+}
+
+/// State of a startup phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhaseStatus {
+    /// Not started yet
+    Pending,
+    /// In progress
+    Running,
+    /// Finished successfully
+    Completed,
+    /// Finished with an error (the application still starts, degraded)
+    Failed,
+}
+
+/// Progress of a single startup phase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupPhaseProgress {
+    /// Phase
+    pub phase: StartupPhase,
+    /// Current state
+    pub status: StartupPhaseStatus,
+    /// Duration in milliseconds (set once finished)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Error message (set when failed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Startup progress of the application (`startup_progress` event payload)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartupStatus {
+    /// Progress of each phase, in display order
+    pub phases: Vec<StartupPhaseProgress>,
+    /// True once every phase has finished (completed or failed)
+    pub ready: bool,
+}
+
+impl Default for StartupStatus {
+    fn default() -> Self {
+        Self {
+            phases: StartupPhase::ALL
+                .iter()
+                .map(|phase| StartupPhaseProgress {
+                    phase: *phase,
+                    status: StartupPhaseStatus::Pending,
+                    duration_ms: None,
+                    error: None,
+                })
+                .collect(),
+            ready: false,
+        }
+    }
+}
+
+impl StartupStatus {
+    /// Marks a phase as running.
+    pub fn start(&mut self, phase: StartupPhase) {
+        self.update(phase, StartupPhaseStatus::Running, None, None);
+    }
+
+    /// Marks a phase as finished and updates `ready`.
+    pub fn finish(&mut self, phase: StartupPhase, duration_ms: u64, error: Option<String>) {
+        let status = if error.is_some() {
+            StartupPhaseStatus::Failed
+        } else {
+            StartupPhaseStatus::Completed
+        };
+        self.update(phase, status, Some(duration_ms), error);
+        self.ready = self.phases.iter().all(|p| {
+            matches!(
+                p.status,
+                StartupPhaseStatus::Completed | StartupPhaseStatus::Failed
+            )
+        });
+    }
+
+    /// Returns the state of a phase.
+    #[allow(dead_code)] // Used by the library and tests, not the binary
+    pub fn status_of(&self, phase: StartupPhase) -> StartupPhaseStatus {
+        self.phases
+            .iter()
+            .find(|p| p.phase == phase)
+            .map(|p| p.status)
+            .unwrap_or(StartupPhaseStatus::Pending)
+    }
+
+    fn update(
+        &mut self,
+        phase: StartupPhase,
+        status: StartupPhaseStatus,
+        duration_ms: Option<u64>,
+        error: Option<String>,
+    ) {
+        if let Some(progress) = self.phases.iter_mut().find(|p| p.phase == phase) {
+            progress.status = status;
+            progress.duration_ms = duration_ms;
+            progress.error = error;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_startup_status_ready() {
+        let mut status = StartupStatus::default();
+        assert_eq!(status.phases.len(), StartupPhase::ALL.len());
+        assert!(!status.ready);
+
+        status.start(StartupPhase::Database);
+        assert_eq!(
+            status.status_of(StartupPhase::Database),
+            StartupPhaseStatus::Running
+        );
+
+        for (i, phase) in StartupPhase::ALL.iter().enumerate() {
+            let error = (*phase == StartupPhase::McpServers).then(|| "boom".to_string());
+            status.finish(*phase, i as u64, error);
+            assert_eq!(status.ready, i == StartupPhase::ALL.len() - 1);
+        }
+        assert_eq!(
+            status.status_of(StartupPhase::McpServers),
+            StartupPhaseStatus::Failed
+        );
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["phases"][4]["phase"], "mcp_servers");
+        assert_eq!(json["phases"][4]["status"], "failed");
+        assert_eq!(json["phases"][4]["error"], "boom");
+        assert!(json["phases"][0].get("error").is_none());
+    }
+}
@@ -29,6 +29,8 @@ pub enum ChunkType {
     ToolStart,
     /// Tool execution completed
     ToolEnd,
+    /// Tool execution progress (MCP progress notifications)
+    ToolProgress,
     /// Reasoning/thinking step
     Reasoning,
     /// Error occurred
@@ -47,6 +49,10 @@ pub enum ChunkType {
     TaskUpdate,
     /// Task completed
     TaskComplete,
+    /// LLM request failed with a transient error and will be retried
+    LlmRetry,
+    /// No stream event for longer than the watchdog threshold
+    PossiblyStuck,
 }
 
 /// Streaming chunk emitted during workflow execution
@@ -77,7 +83,7 @@ pub struct StreamChunk {
     /// Sub-agent metrics (for sub_agent_complete chunks)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metrics: Option<SubAgentStreamMetrics>,
-    /// Progress percentage 0-100 (for sub_agent_progress chunks)
+    /// Progress percentage 0-100 (for sub_agent_progress/tool_progress chunks)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub progress: Option<u8>,
     /// Task ID (for task_* chunks)
@@ -98,6 +104,12 @@ pub struct StreamChunk {
     /// Cumulative token count (running total)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens_total: Option<usize>,
+    /// Retry number, 1-indexed (for llm_retry chunks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_attempt: Option<u32>,
+    /// Maximum number of retries (for llm_retry chunks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
 }
 
 /// Metrics included in sub-agent complete events
@@ -131,6 +143,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -159,6 +173,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: Some(tokens_delta),
             tokens_total: Some(tokens_total),
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -181,6 +197,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -203,6 +221,40 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
+        }
+    }
+
+    /// Creates a tool progress chunk
+    ///
+    /// Emitted when an MCP server reports progress during a tool call.
+    /// `progress` is None when the server does not know the total.
+    pub fn tool_progress(
+        workflow_id: String,
+        tool: String,
+        progress: Option<u8>,
+        message: Option<String>,
+    ) -> Self {
+        Self {
+            workflow_id,
+            chunk_type: ChunkType::ToolProgress,
+            content: message,
+            tool: Some(tool),
+            duration: None,
+            sub_agent_id: None,
+            sub_agent_name: None,
+            parent_agent_id: None,
+            metrics: None,
+            progress: progress.map(|p| p.min(100)),
+            task_id: None,
+            task_name: None,
+            task_status: None,
+            task_priority: None,
+            tokens_delta: None,
+            tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -225,6 +277,73 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
+        }
+    }
+
+    /// Creates an LLM retry chunk
+    ///
+    /// Emitted when an LLM request failed with a transient error (rate limit,
+    /// server error, connection error) and is retried after `delay_ms`.
+    pub fn llm_retry(
+        workflow_id: String,
+        attempt: u32,
+        max_retries: u32,
+        delay_ms: u64,
+        error: String,
+    ) -> Self {
+        Self {
+            workflow_id,
+            chunk_type: ChunkType::LlmRetry,
+            content: Some(error),
+            tool: None,
+            duration: Some(delay_ms),
+            sub_agent_id: None,
+            sub_agent_name: None,
+            parent_agent_id: None,
+            metrics: None,
+            progress: None,
+            task_id: None,
+            task_name: None,
+            task_status: None,
+            task_priority: None,
+            tokens_delta: None,
+            tokens_total: None,
+            retry_attempt: Some(attempt),
+            max_retries: Some(max_retries),
+        }
+    }
+
+    /// Creates a possibly stuck chunk
+    ///
+    /// Emitted by the watchdog when a run produced no stream event for
+    /// `silent_ms`; `content` describes the current phase.
+    pub fn possibly_stuck(
+        workflow_id: String,
+        phase: String,
+        tool: Option<String>,
+        silent_ms: u64,
+    ) -> Self {
+        Self {
+            workflow_id,
+            chunk_type: ChunkType::PossiblyStuck,
+            content: Some(phase),
+            tool,
+            duration: Some(silent_ms),
+            sub_agent_id: None,
+            sub_agent_name: None,
+            parent_agent_id: None,
+            metrics: None,
+            progress: None,
+            task_id: None,
+            task_name: None,
+            task_status: None,
+            task_priority: None,
+            tokens_delta: None,
+            tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -247,6 +366,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -277,6 +398,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -310,6 +433,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -341,6 +466,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -372,6 +499,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -402,6 +531,8 @@ impl StreamChunk {
             task_priority: Some(priority),
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -432,6 +563,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 
@@ -462,6 +595,8 @@ impl StreamChunk {
             task_priority: None,
             tokens_delta: None,
             tokens_total: None,
+            retry_attempt: None,
+            max_retries: None,
         }
     }
 }
@@ -590,6 +725,28 @@ pub mod events {
     pub const SUB_AGENT_COMPLETE: &str = "sub_agent_complete";
     /// Sub-agent error event name
     pub const SUB_AGENT_ERROR: &str = "sub_agent_error";
+    /// Token usage anomaly event name
+    pub const TOKEN_ANOMALY: &str = "token_anomaly";
+    /// MCP server status change event name (supervision)
+    pub const MCP_SERVER_STATUS_CHANGED: &str = "mcp_server_status_changed";
+    /// Startup progress event name (deferred initialization phases)
+    pub const STARTUP_PROGRESS: &str = "startup_progress";
+    /// Persistence write queued in the outbox event name
+    pub const PERSISTENCE_WARNING: &str = "persistence_warning";
+    /// Synthesized speech audio chunk event name
+    pub const SPEECH_AUDIO: &str = "speech_audio";
+    /// Scheduled workflow run finished event name
+    pub const SCHEDULE_RUN: &str = "schedule_run";
+    /// Background job state change event name
+    pub const JOB_STATUS: &str = "job_status";
+    /// Active profile changed event name
+    pub const PROFILE_SWITCHED: &str = "profile_switched";
+    /// Workflow title generated event name
+    pub const WORKFLOW_TITLE_UPDATED: &str = "workflow_title_updated";
+    /// Embedding dimension migration progress event name
+    pub const EMBEDDING_MIGRATION_PROGRESS: &str = "embedding_migration_progress";
+    /// Embedding regeneration progress event name
+    pub const EMBEDDING_REGENERATION_PROGRESS: &str = "embedding_regeneration_progress";
 }
 
 #[cfg(test)]
@@ -633,6 +790,17 @@ mod tests {
         let chunk = StreamChunk::tool_end("wf_001".to_string(), "search".to_string(), 150);
         assert_eq!(chunk.chunk_type, ChunkType::ToolEnd);
         assert_eq!(chunk.duration, Some(150));
+
+        let chunk = StreamChunk::tool_progress(
+            "wf_001".to_string(),
+            "mcp__docs__index".to_string(),
+            Some(40),
+            Some("Indexing files".to_string()),
+        );
+        let json = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(json["chunk_type"], "tool_progress");
+        assert_eq!(json["progress"], 40);
+        assert_eq!(json["content"], "Indexing files");
     }
 
     #[test]
@@ -642,6 +810,42 @@ mod tests {
         assert_eq!(chunk.content, Some("Connection failed".to_string()));
     }
 
+    #[test]
+    fn test_stream_chunk_llm_retry() {
+        let chunk = StreamChunk::llm_retry(
+            "wf_001".to_string(),
+            1,
+            3,
+            1200,
+            "API request failed: API error (429 Too Many Requests)".to_string(),
+        );
+        let json = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(json["chunk_type"], "llm_retry");
+        assert_eq!(json["retry_attempt"], 1);
+        assert_eq!(json["max_retries"], 3);
+        assert_eq!(json["duration"], 1200);
+
+        let token = StreamChunk::error("wf_001".to_string(), "failed".to_string());
+        assert!(!serde_json::to_string(&token)
+            .unwrap()
+            .contains("retry_attempt"));
+    }
+
+    #[test]
+    fn test_stream_chunk_possibly_stuck() {
+        let chunk = StreamChunk::possibly_stuck(
+            "wf_001".to_string(),
+            "Executing web_fetch (iteration 2)".to_string(),
+            Some("web_fetch".to_string()),
+            120_000,
+        );
+        let json = serde_json::to_value(&chunk).unwrap();
+        assert_eq!(json["chunk_type"], "possibly_stuck");
+        assert_eq!(json["content"], "Executing web_fetch (iteration 2)");
+        assert_eq!(json["tool"], "web_fetch");
+        assert_eq!(json["duration"], 120_000);
+    }
+
     #[test]
     fn test_workflow_complete_success() {
         let complete = WorkflowComplete::success("wf_001".to_string());
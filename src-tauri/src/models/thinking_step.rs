@@ -51,6 +51,19 @@ pub struct ThinkingStep {
     pub created_at: DateTime<Utc>,
 }
 
+/// Page of the thinking steps of a workflow (cursor-based).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThinkingStepPage {
+    /// Steps in the current page (chronological order)
+    pub steps: Vec<ThinkingStep>,
+    /// Cursor of the next page (`None` on the last page)
+    pub next_cursor: Option<String>,
+    /// Whether more steps are available after this page
+    pub has_more: bool,
+    /// Whether the contents were shortened to a preview
+    pub summary: bool,
+}
+
 /// Payload for creating a new thinking step record.
 ///
 /// ID and created_at are generated server-side.
@@ -0,0 +1,136 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token usage anomaly detection models.
+//!
+//! Each agent run is compared against a rolling baseline (median token usage
+//! of the agent's recent runs). When a run exceeds `threshold_multiplier`
+//! times the baseline, a `token_anomaly` event is emitted and the run can
+//! optionally be paused until the user confirms it may continue.
+
+use serde::{Deserialize, Serialize};
+
+/// Token anomaly detection settings (stored in `settings:token_anomaly`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenAnomalySettings {
+    /// Enable anomaly detection
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Alert when a run uses more than this multiple of the baseline
+    #[serde(default = "default_threshold_multiplier")]
+    pub threshold_multiplier: f64,
+    /// Number of recent runs used to compute the baseline
+    #[serde(default = "default_baseline_window")]
+    pub baseline_window: usize,
+    /// Minimum number of runs before alerts are raised
+    #[serde(default = "default_min_baseline_runs")]
+    pub min_baseline_runs: usize,
+    /// Pause the run and request user confirmation when an anomaly is detected
+    #[serde(default)]
+    pub pause_for_confirmation: bool,
+}
+
+impl Default for TokenAnomalySettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            threshold_multiplier: default_threshold_multiplier(),
+            baseline_window: default_baseline_window(),
+            min_baseline_runs: default_min_baseline_runs(),
+            pause_for_confirmation: false,
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_threshold_multiplier() -> f64 {
+    5.0
+}
+
+fn default_baseline_window() -> usize {
+    20
+}
+
+fn default_min_baseline_runs() -> usize {
+    5
+}
+
+/// Payload of the `token_anomaly` event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenAnomalyEvent {
+    /// Workflow ID
+    pub workflow_id: String,
+    /// Agent ID
+    pub agent_id: String,
+    /// Tokens used by the current run so far
+    pub tokens_used: usize,
+    /// Baseline tokens per run
+    pub baseline_tokens: usize,
+    /// tokens_used / baseline_tokens
+    pub ratio: f64,
+    /// Configured threshold multiplier
+    pub threshold_multiplier: f64,
+    /// Whether the run is paused waiting for confirmation
+    pub paused: bool,
+}
+
+/// Computes the rolling baseline (median) of recent run token counts.
+///
+/// # Returns
+/// None if fewer than `min_runs` non-zero samples are available
+pub fn compute_baseline(samples: &[usize], min_runs: usize) -> Option<usize> {
+    let mut values: Vec<usize> = samples.iter().copied().filter(|v| *v > 0).collect();
+    if values.is_empty() || values.len() < min_runs {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        Some(values[mid])
+    } else {
+        Some((values[mid - 1] + values[mid]) / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_defaults() {
+        let settings: TokenAnomalySettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, TokenAnomalySettings::default());
+        assert!(settings.enabled);
+        assert_eq!(settings.threshold_multiplier, 5.0);
+        assert!(!settings.pause_for_confirmation);
+    }
+
+    #[test]
+    fn test_compute_baseline_median() {
+        assert_eq!(compute_baseline(&[100, 300, 200], 3), Some(200));
+        assert_eq!(compute_baseline(&[100, 200, 300, 400], 3), Some(250));
+        // A single past spike does not skew the baseline
+        assert_eq!(compute_baseline(&[100, 110, 120, 90, 50_000], 5), Some(110));
+    }
+
+    #[test]
+    fn test_compute_baseline_insufficient_samples() {
+        assert_eq!(compute_baseline(&[], 1), None);
+        assert_eq!(compute_baseline(&[100, 200], 3), None);
+        assert_eq!(compute_baseline(&[0, 0, 100], 2), None);
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! First-use acknowledgments of powerful tools.
+//!
+//! The first time an agent calls a powerful tool (shell commands, file system
+//! writes, external SQL), the user must acknowledge it once for that agent.
+//! The acknowledgment is recorded per (agent, tool) pair; later calls follow
+//! the normal risk-based validation.
+//!
+//! Such capabilities are provided by MCP servers, so powerful tools are
+//! recognized from the words of their name (`run_command`, `write_file`,
+//! `execute_sql`, ...).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Words naming a shell command execution
+const SHELL_WORDS: &[&str] = &[
+    "bash",
+    "cmd",
+    "command",
+    "exec",
+    "powershell",
+    "shell",
+    "terminal",
+    "zsh",
+];
+
+/// Words naming a SQL database
+const SQL_WORDS: &[&str] = &["mysql", "postgres", "postgresql", "sql", "sqlite"];
+
+/// Words naming a write operation
+const WRITE_WORDS: &[&str] = &[
+    "append", "create", "delete", "edit", "mkdir", "move", "patch", "remove", "rename", "write",
+];
+
+/// Words naming a file system entry
+const FILE_WORDS: &[&str] = &["dir", "directory", "file", "files", "folder", "path"];
+
+/// Kind of powerful tool requiring a first-use acknowledgment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerfulToolKind {
+    /// Runs shell commands
+    Shell,
+    /// Writes to the file system
+    FileSystemWrite,
+    /// Runs queries on an external SQL database
+    ExternalSql,
+}
+
+impl std::fmt::Display for PowerfulToolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerfulToolKind::Shell => write!(f, "shell"),
+            PowerfulToolKind::FileSystemWrite => write!(f, "file_system_write"),
+            PowerfulToolKind::ExternalSql => write!(f, "external_sql"),
+        }
+    }
+}
+
+/// Acknowledgment of a powerful tool for an agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolAcknowledgment {
+    /// Agent ID
+    pub agent_id: String,
+    /// Tool name as called by the agent (`mcp__server__tool` for MCP tools)
+    pub tool_name: String,
+    /// Kind of powerful tool
+    pub kind: PowerfulToolKind,
+    /// When the user acknowledged the tool
+    pub acknowledged_at: DateTime<Utc>,
+}
+
+/// Returns the kind of powerful tool named `tool_name`, if any.
+///
+/// `tool_name` is the tool name without the MCP server prefix.
+pub fn classify_powerful_tool(tool_name: &str) -> Option<PowerfulToolKind> {
+    let words = name_words(tool_name);
+    let has = |list: &[&str]| words.iter().any(|w| list.contains(&w.as_str()));
+    let has_word = |word: &str| words.iter().any(|w| w == word);
+
+    if has(SHELL_WORDS) {
+        Some(PowerfulToolKind::Shell)
+    } else if has(SQL_WORDS)
+        || (has_word("query") && has(&["read", "write", "execute", "run"]))
+        || (has_word("table") && has(&["alter", "create", "drop"]))
+    {
+        Some(PowerfulToolKind::ExternalSql)
+    } else if has(WRITE_WORDS) && has(FILE_WORDS) {
+        Some(PowerfulToolKind::FileSystemWrite)
+    } else {
+        None
+    }
+}
+
+/// Splits a tool name into lowercase words (snake_case, kebab-case, camelCase).
+pub(crate) fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_numeric();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_powerful_tool() {
+        for name in [
+            "run_command",
+            "execute_command",
+            "shell",
+            "bashExec",
+            "terminal-run",
+        ] {
+            assert_eq!(
+                classify_powerful_tool(name),
+                Some(PowerfulToolKind::Shell),
+                "{}",
+                name
+            );
+        }
+        for name in [
+            "write_file",
+            "edit_file",
+            "create_directory",
+            "move_file",
+            "writeFile",
+        ] {
+            assert_eq!(
+                classify_powerful_tool(name),
+                Some(PowerfulToolKind::FileSystemWrite),
+                "{}",
+                name
+            );
+        }
+        for name in [
+            "execute_sql",
+            "read_query",
+            "write_query",
+            "create_table",
+            "postgres",
+        ] {
+            assert_eq!(
+                classify_powerful_tool(name),
+                Some(PowerfulToolKind::ExternalSql),
+                "{}",
+                name
+            );
+        }
+        for name in [
+            "read_file",
+            "list_directory",
+            "find_symbol",
+            "search_query",
+            "profile",
+        ] {
+            assert_eq!(classify_powerful_tool(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_powerful_tool_kind_serialization() {
+        assert_eq!(
+            serde_json::to_string(&PowerfulToolKind::FileSystemWrite).unwrap(),
+            "\"file_system_write\""
+        );
+        assert_eq!(PowerfulToolKind::ExternalSql.to_string(), "external_sql");
+    }
+}
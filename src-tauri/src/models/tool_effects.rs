@@ -0,0 +1,232 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Effects of tool calls.
+//!
+//! Whether a tool call only reads data or has side effects is decided here
+//! once, for the risk level of its validation
+//! ([`classify_tool_call`](super::validation::classify_tool_call)) and for
+//! the calls simulated in a dry run workflow (`tools::dry_run`):
+//!
+//! - Local tools: the effect of each operation is listed in
+//!   `LOCAL_OPERATION_EFFECTS`; operations of unlisted tools (plugins, custom
+//!   tools) are destructive when their name is, read-only otherwise.
+//! - MCP tools: recognized from the words of their name. A tool is read-only
+//!   only when its name holds a read verb (`get_issue`, `list_files`) and no
+//!   word with side effects, so `get_or_create_issue` is a write. SQL tools
+//!   only read when they run a single read-only statement.
+
+use super::tool_acknowledgment::{classify_powerful_tool, name_words, PowerfulToolKind};
+use serde_json::Value;
+
+/// Effects of a tool call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolCallEffects {
+    /// Only reads data
+    ReadOnly,
+    /// Writes data of the app (memories, tasks)
+    Writes,
+    /// Acts outside the app: sends mail, writes files or external databases
+    External,
+    /// Deletes data
+    Destructive,
+}
+
+/// Local tool operations with side effects; other operations only read
+const LOCAL_OPERATION_EFFECTS: &[(&str, &str, ToolCallEffects)] = &[
+    ("MemoryTool", "add", ToolCallEffects::Writes),
+    ("MemoryTool", "delete", ToolCallEffects::Destructive),
+    ("MemoryTool", "clear_by_type", ToolCallEffects::Destructive),
+    ("TodoTool", "create", ToolCallEffects::Writes),
+    ("TodoTool", "update_status", ToolCallEffects::Writes),
+    ("TodoTool", "complete", ToolCallEffects::Writes),
+    ("TodoTool", "delete", ToolCallEffects::Destructive),
+    ("SqlTool", "execute", ToolCallEffects::External),
+    ("EmailTool", "send_draft", ToolCallEffects::External),
+    ("ClipboardTool", "write", ToolCallEffects::External),
+    ("PdfTool", "create", ToolCallEffects::External),
+];
+
+/// Words of read-only tool names (`get_issue`, `list_files`...)
+const READ_ONLY_WORDS: &[&str] = &[
+    "browse", "count", "describe", "fetch", "find", "get", "info", "inspect", "list", "lookup",
+    "read", "search", "show", "stat", "status", "view",
+];
+
+/// Words of tool names with side effects, even next to a read verb
+/// (`get_or_create_issue`, `find_and_replace`)
+const SIDE_EFFECT_WORDS: &[&str] = &[
+    "add", "append", "apply", "archive", "assign", "cancel", "close", "commit", "create", "deploy",
+    "edit", "execute", "insert", "install", "merge", "modify", "move", "patch", "post", "publish",
+    "push", "put", "rename", "replace", "restart", "run", "save", "send", "set", "start", "stop",
+    "submit", "trigger", "update", "upload", "upsert", "write",
+];
+
+/// Words of tool names that destroy data
+const DESTRUCTIVE_WORDS: &[&str] = &[
+    "clear", "delete", "destroy", "drop", "erase", "kill", "purge", "remove", "reset", "truncate",
+    "wipe",
+];
+
+/// First keywords of read-only SQL statements
+const READ_ONLY_STATEMENTS: &[&str] = &["describe", "explain", "select", "show"];
+
+/// Argument names holding a SQL statement
+pub(crate) const STATEMENT_ARGS: &[&str] = &["sql", "query", "statement"];
+
+/// Effects of a tool call.
+///
+/// # Arguments
+/// * `tool_name` - Local tool name, or MCP tool name without server prefix
+/// * `is_mcp` - Whether the tool is served by an MCP server
+/// * `arguments` - Tool call arguments (`operation` of local tools)
+pub fn tool_call_effects(tool_name: &str, is_mcp: bool, arguments: &Value) -> ToolCallEffects {
+    if !is_mcp {
+        let operation = arguments
+            .get("operation")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        return local_operation_effects(tool_name, operation);
+    }
+
+    match classify_powerful_tool(tool_name) {
+        Some(PowerfulToolKind::Shell) => return ToolCallEffects::External,
+        Some(PowerfulToolKind::ExternalSql) => {
+            let read_only = STATEMENT_ARGS
+                .iter()
+                .find_map(|name| arguments[*name].as_str())
+                .is_some_and(is_read_only_statement);
+            return if read_only {
+                ToolCallEffects::ReadOnly
+            } else {
+                ToolCallEffects::External
+            };
+        }
+        Some(PowerfulToolKind::FileSystemWrite) if !has_word(tool_name, DESTRUCTIVE_WORDS) => {
+            return ToolCallEffects::External
+        }
+        _ => {}
+    }
+    name_effects(tool_name)
+}
+
+/// Effects of an operation of a local tool.
+fn local_operation_effects(tool_name: &str, operation: &str) -> ToolCallEffects {
+    let mut listed = LOCAL_OPERATION_EFFECTS
+        .iter()
+        .filter(|(tool, _, _)| *tool == tool_name)
+        .peekable();
+    if listed.peek().is_none() {
+        // Plugins and custom tools: only their operation name is known
+        return if has_word(operation, DESTRUCTIVE_WORDS) {
+            ToolCallEffects::Destructive
+        } else {
+            ToolCallEffects::ReadOnly
+        };
+    }
+    listed
+        .find(|(_, op, _)| *op == operation)
+        .map_or(ToolCallEffects::ReadOnly, |(_, _, effects)| *effects)
+}
+
+/// Effects of a tool from the words of its name.
+fn name_effects(name: &str) -> ToolCallEffects {
+    if has_word(name, DESTRUCTIVE_WORDS) {
+        ToolCallEffects::Destructive
+    } else if has_word(name, READ_ONLY_WORDS) && !has_word(name, SIDE_EFFECT_WORDS) {
+        ToolCallEffects::ReadOnly
+    } else {
+        ToolCallEffects::Writes
+    }
+}
+
+/// Whether a SQL text is a single read-only statement (`SELECT`, `SHOW`...).
+pub fn is_read_only_statement(sql: &str) -> bool {
+    let sql = sql.trim();
+    let statement = sql.strip_suffix(';').unwrap_or(sql);
+    !statement.contains(';')
+        && statement
+            .split_whitespace()
+            .next()
+            .is_some_and(|keyword| READ_ONLY_STATEMENTS.contains(&keyword.to_lowercase().as_str()))
+}
+
+/// Whether a name holds one of `words`.
+fn has_word(name: &str, words: &[&str]) -> bool {
+    name_words(name).iter().any(|w| words.contains(&w.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_local_tool_effects() {
+        let effects =
+            |tool, operation| tool_call_effects(tool, false, &json!({ "operation": operation }));
+
+        assert_eq!(effects("MemoryTool", "search"), ToolCallEffects::ReadOnly);
+        assert_eq!(effects("MemoryTool", "add"), ToolCallEffects::Writes);
+        assert_eq!(
+            effects("MemoryTool", "clear_by_type"),
+            ToolCallEffects::Destructive
+        );
+        assert_eq!(
+            effects("EmailTool", "send_draft"),
+            ToolCallEffects::External
+        );
+        assert_eq!(
+            effects("EmailTool", "read_inbox"),
+            ToolCallEffects::ReadOnly
+        );
+        assert_eq!(
+            effects("custom__cleanup", "purge_cache"),
+            ToolCallEffects::Destructive
+        );
+        assert_eq!(effects("CalculatorTool", ""), ToolCallEffects::ReadOnly);
+    }
+
+    #[test]
+    fn test_mcp_tool_effects() {
+        let effects = |tool| tool_call_effects(tool, true, &Value::Null);
+
+        assert_eq!(effects("get_issue"), ToolCallEffects::ReadOnly);
+        assert_eq!(effects("listFiles"), ToolCallEffects::ReadOnly);
+        assert_eq!(effects("get_or_create_issue"), ToolCallEffects::Writes);
+        assert_eq!(effects("fetch_and_update_record"), ToolCallEffects::Writes);
+        assert_eq!(effects("find_and_replace"), ToolCallEffects::Writes);
+        assert_eq!(effects("browser_navigate"), ToolCallEffects::Writes);
+        assert_eq!(effects("deleteBranch"), ToolCallEffects::Destructive);
+        assert_eq!(effects("write_file"), ToolCallEffects::External);
+        assert_eq!(effects("delete_file"), ToolCallEffects::Destructive);
+        assert_eq!(effects("run_command"), ToolCallEffects::External);
+
+        let sql = |query| tool_call_effects("execute_sql", true, &json!({ "query": query }));
+        assert_eq!(sql("SELECT * FROM users"), ToolCallEffects::ReadOnly);
+        assert_eq!(sql("DELETE FROM users"), ToolCallEffects::External);
+    }
+
+    #[test]
+    fn test_is_read_only_statement() {
+        assert!(is_read_only_statement("SELECT * FROM users"));
+        assert!(is_read_only_statement("  explain select 1;"));
+        assert!(!is_read_only_statement("DELETE FROM users"));
+        assert!(!is_read_only_statement("SELECT 1; DROP TABLE users"));
+        assert!(!is_read_only_statement(
+            "WITH x AS (SELECT 1) DELETE FROM users"
+        ));
+        assert!(!is_read_only_statement(""));
+    }
+}
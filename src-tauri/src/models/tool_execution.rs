@@ -36,6 +36,21 @@ where
     }
 }
 
+/// Deserialize an optional JSON string from DB back into serde_json::Value.
+fn deserialize_optional_json_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<serde_json::Value>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(serde_json::Value::String(s)) => serde_json::from_str(&s)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        other => Ok(other),
+    }
+}
+
 /// Serialize serde_json::Value to a JSON string for DB storage.
 fn serialize_as_json_string<S>(value: &serde_json::Value, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -45,6 +60,20 @@ where
     serializer.serialize_str(&s)
 }
 
+/// Serialize an optional serde_json::Value to a JSON string for DB storage.
+fn serialize_optional_as_json_string<S>(
+    value: &Option<serde_json::Value>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        Some(value) => serialize_as_json_string(value, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 /// Tool type indicating execution context
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -99,10 +128,38 @@ pub struct ToolExecution {
     pub duration_ms: u64,
     /// Iteration number within the tool execution loop (0-indexed)
     pub iteration: u32,
+    /// Previous execution of the same call (same tool and input) in the workflow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_execution_id: Option<String>,
+    /// Changes of the output since the previous execution, as an array of
+    /// `add`/`remove`/`replace` operations (empty = unchanged)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_optional_json_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub result_diff: Option<serde_json::Value>,
+    /// Unix time (milliseconds) at which the execution started (None for
+    /// executions recorded before start times were persisted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at_ms: Option<u64>,
     /// Timestamp when the execution was recorded
     pub created_at: DateTime<Utc>,
 }
 
+/// Page of the tool executions of a workflow (cursor-based).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolExecutionPage {
+    /// Executions in the current page (chronological order)
+    pub executions: Vec<ToolExecution>,
+    /// Cursor of the next page (`None` on the last page)
+    pub next_cursor: Option<String>,
+    /// Whether more executions are available after this page
+    pub has_more: bool,
+    /// Whether the payloads (input, output, result diff) were omitted
+    pub summary: bool,
+}
+
 /// Payload for creating a new tool execution record.
 ///
 /// ID and created_at are generated server-side.
@@ -136,6 +193,18 @@ pub struct ToolExecutionCreate {
     pub duration_ms: u64,
     /// Iteration number
     pub iteration: u32,
+    /// Previous execution of the same call
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_execution_id: Option<String>,
+    /// Output changes since the previous execution (serialized as JSON string)
+    #[serde(
+        serialize_with = "serialize_optional_as_json_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub result_diff: Option<serde_json::Value>,
+    /// Unix time (milliseconds) at which the execution started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at_ms: Option<u64>,
 }
 
 impl ToolExecutionCreate {
@@ -167,6 +236,9 @@ impl ToolExecutionCreate {
             error_message,
             duration_ms,
             iteration,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
         }
     }
 
@@ -199,6 +271,9 @@ impl ToolExecutionCreate {
             error_message,
             duration_ms,
             iteration,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
         }
     }
 }
@@ -249,6 +324,9 @@ mod tests {
             error_message: None,
             duration_ms: 150,
             iteration: 0,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
             created_at: Utc::now(),
         };
 
@@ -275,6 +353,9 @@ mod tests {
             error_message: None,
             duration_ms: 500,
             iteration: 1,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
             created_at: Utc::now(),
         };
 
@@ -299,6 +380,9 @@ mod tests {
             error_message: Some("Task not found".to_string()),
             duration_ms: 10,
             iteration: 0,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
             created_at: Utc::now(),
         };
 
@@ -0,0 +1,527 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Readable transcripts of a workflow.
+//!
+//! A transcript lists the messages of a workflow in order. Each assistant
+//! message is preceded by what happened during its run: thinking steps
+//! (optional), tool calls (payloads optional) and sub-agent reports.
+
+use crate::models::message::{Message, MessageRole};
+use crate::models::sub_agent::SubAgentExecution;
+use crate::models::thinking_step::ThinkingStep;
+use crate::models::tool_execution::ToolExecution;
+use crate::tools::constants::transcript as transcript_const;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Output format of a transcript
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// Markdown document
+    #[default]
+    Markdown,
+    /// Standalone HTML page
+    Html,
+    /// PDF printed from the HTML page by a headless browser
+    Pdf,
+}
+
+/// What a transcript contains
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptOptions {
+    /// Output format
+    #[serde(default)]
+    pub format: TranscriptFormat,
+    /// Include the thinking steps of assistant messages
+    #[serde(default)]
+    pub include_thinking: bool,
+    /// Include tool inputs and outputs (tool names and outcomes are always listed)
+    #[serde(default)]
+    pub include_tool_payloads: bool,
+}
+
+/// Workflow contents to render
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    /// Workflow name
+    pub workflow_name: String,
+    /// Agent of the workflow
+    pub agent_id: String,
+    /// Rendering date
+    pub exported_at: DateTime<Utc>,
+    /// Messages, oldest first
+    pub messages: Vec<Message>,
+    /// Tool executions, oldest first
+    pub tool_executions: Vec<ToolExecution>,
+    /// Thinking steps, oldest first
+    pub thinking_steps: Vec<ThinkingStep>,
+    /// Sub-agent executions, oldest first
+    pub sub_agents: Vec<SubAgentExecution>,
+}
+
+/// A message and what happened during its run
+struct Entry<'a> {
+    message: &'a Message,
+    thinking: Vec<&'a ThinkingStep>,
+    tools: Vec<&'a ToolExecution>,
+    reports: Vec<&'a SubAgentExecution>,
+}
+
+impl Transcript {
+    /// Groups the run details with their message.
+    ///
+    /// Sub-agent reports have no message: they belong to the first assistant
+    /// message written after they started. The others are returned apart.
+    fn entries(&self) -> (Vec<Entry<'_>>, Vec<&SubAgentExecution>) {
+        let mut entries: Vec<Entry> = self
+            .messages
+            .iter()
+            .map(|message| Entry {
+                message,
+                thinking: self
+                    .thinking_steps
+                    .iter()
+                    .filter(|s| s.message_id == message.id)
+                    .collect(),
+                tools: self
+                    .tool_executions
+                    .iter()
+                    .filter(|t| t.message_id == message.id)
+                    .collect(),
+                reports: Vec::new(),
+            })
+            .collect();
+
+        let mut unattached = Vec::new();
+        for report in &self.sub_agents {
+            let entry = entries.iter_mut().find(|e| {
+                e.message.role == MessageRole::Assistant && e.message.timestamp >= report.created_at
+            });
+            match entry {
+                Some(entry) => entry.reports.push(report),
+                None => unattached.push(report),
+            }
+        }
+        (entries, unattached)
+    }
+
+    /// Renders the transcript as Markdown.
+    pub fn to_markdown(&self, options: &TranscriptOptions) -> String {
+        let mut out = format!(
+            "# {}\n\n- Agent: {}\n- Exported: {}\n",
+            self.workflow_name,
+            self.agent_id,
+            format_time(&self.exported_at)
+        );
+        let (entries, unattached) = self.entries();
+
+        for entry in &entries {
+            let message = entry.message;
+            out.push_str(&format!("\n---\n\n## {}\n\n", message_heading(message)));
+
+            if options.include_thinking && !entry.thinking.is_empty() {
+                let steps: Vec<&str> = entry.thinking.iter().map(|s| s.content.trim()).collect();
+                out.push_str(&format!(
+                    "**Thinking**\n\n{}\n\n",
+                    quote(&steps.join("\n\n"), "")
+                ));
+            }
+
+            if !entry.tools.is_empty() {
+                out.push_str("**Tool calls**\n\n");
+                for tool in &entry.tools {
+                    out.push_str(&format!("- {}\n", tool_summary(tool)));
+                    if options.include_tool_payloads {
+                        for (label, payload) in tool_payloads(tool) {
+                            out.push_str(&format!(
+                                "\n  {}:\n\n{}\n",
+                                label,
+                                indent(&fenced(&payload, "json"), "  ")
+                            ));
+                        }
+                        out.push('\n');
+                    }
+                }
+                out.push('\n');
+            }
+
+            if !entry.reports.is_empty() {
+                out.push_str("**Agent reports**\n\n");
+                for report in &entry.reports {
+                    push_markdown_report(&mut out, report);
+                }
+            }
+
+            out.push_str(message.content.trim_end());
+            out.push('\n');
+        }
+
+        if !unattached.is_empty() {
+            out.push_str("\n---\n\n## Agent reports\n\n");
+            for report in &unattached {
+                push_markdown_report(&mut out, report);
+            }
+        }
+        out
+    }
+
+    /// Renders the transcript as a standalone HTML page.
+    pub fn to_html(&self, options: &TranscriptOptions) -> String {
+        let mut body = format!(
+            "<h1>{}</h1>\n<p class=\"meta\">Agent: {} · Exported: {}</p>\n",
+            escape_html(&self.workflow_name),
+            escape_html(&self.agent_id),
+            format_time(&self.exported_at)
+        );
+        let (entries, unattached) = self.entries();
+
+        for entry in &entries {
+            let message = entry.message;
+            body.push_str(&format!(
+                "<section class=\"message {}\">\n<h2>{}</h2>\n",
+                message.role,
+                escape_html(&message_heading(message))
+            ));
+
+            if options.include_thinking && !entry.thinking.is_empty() {
+                body.push_str("<details class=\"thinking\"><summary>Thinking</summary>\n");
+                for step in &entry.thinking {
+                    body.push_str(&format!(
+                        "<div class=\"text\">{}</div>\n",
+                        escape_html(&step.content)
+                    ));
+                }
+                body.push_str("</details>\n");
+            }
+
+            if !entry.tools.is_empty() {
+                body.push_str("<h3>Tool calls</h3>\n<ul class=\"tools\">\n");
+                for tool in &entry.tools {
+                    body.push_str(&format!("<li>{}", escape_html(&tool_summary(tool))));
+                    if options.include_tool_payloads {
+                        for (label, payload) in tool_payloads(tool) {
+                            body.push_str(&format!(
+                                "<details><summary>{}</summary><pre>{}</pre></details>",
+                                label,
+                                escape_html(&payload)
+                            ));
+                        }
+                    }
+                    body.push_str("</li>\n");
+                }
+                body.push_str("</ul>\n");
+            }
+
+            if !entry.reports.is_empty() {
+                body.push_str("<h3>Agent reports</h3>\n");
+                for report in &entry.reports {
+                    push_html_report(&mut body, report);
+                }
+            }
+
+            body.push_str(&format!(
+                "<div class=\"text\">{}</div>\n</section>\n",
+                escape_html(message.content.trim_end())
+            ));
+        }
+
+        if !unattached.is_empty() {
+            body.push_str("<section>\n<h2>Agent reports</h2>\n");
+            for report in &unattached {
+                push_html_report(&mut body, report);
+            }
+            body.push_str("</section>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&self.workflow_name),
+            HTML_STYLE,
+            body
+        )
+    }
+}
+
+/// Page style of HTML transcripts (also used for PDF printing)
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:50rem;margin:2rem auto;padding:0 1rem;color:#1f2328;line-height:1.5}\
+h1{margin-bottom:0}.meta{color:#59636e}section{border-top:1px solid #d1d9e0;padding:0.5rem 0}\
+h2{font-size:1rem;color:#59636e}h3{font-size:0.9rem;margin:0.5rem 0}\
+.text{white-space:pre-wrap}.thinking,.report{color:#59636e;border-left:3px solid #d1d9e0;padding-left:0.75rem}\
+pre{background:#f6f8fa;padding:0.5rem;overflow-x:auto;white-space:pre-wrap}";
+
+/// Heading of a message: role, date and model.
+fn message_heading(message: &Message) -> String {
+    let role = match message.role {
+        MessageRole::User => "User",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::System => "System",
+    };
+    let mut heading = format!("{} · {}", role, format_time(&message.timestamp));
+    if let Some(model) = &message.model {
+        heading.push_str(&format!(" · {}", model));
+    }
+    heading
+}
+
+/// One-line outcome of a tool call.
+fn tool_summary(tool: &ToolExecution) -> String {
+    let outcome = if tool.success {
+        "success".to_string()
+    } else {
+        match &tool.error_message {
+            Some(error) => format!("failed: {}", error),
+            None => "failed".to_string(),
+        }
+    };
+    format!(
+        "{} ({}) · {} · {} ms",
+        tool.tool_name, tool.tool_type, outcome, tool.duration_ms
+    )
+}
+
+/// Labelled input and output of a tool call, pretty-printed and truncated.
+fn tool_payloads(tool: &ToolExecution) -> Vec<(&'static str, String)> {
+    [
+        ("Input", &tool.input_params),
+        ("Output", &tool.output_result),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.is_null())
+    .map(|(label, value)| {
+        let text = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => serde_json::to_string_pretty(other).unwrap_or_default(),
+        };
+        (
+            label,
+            truncate(&text, transcript_const::MAX_TOOL_PAYLOAD_CHARS),
+        )
+    })
+    .collect()
+}
+
+/// Appends a sub-agent report as a Markdown list item.
+fn push_markdown_report(out: &mut String, report: &SubAgentExecution) {
+    out.push_str(&format!(
+        "- **{}** ({}): {}\n",
+        report.sub_agent_name,
+        report.status,
+        report.task_description.lines().next().unwrap_or_default()
+    ));
+    if let Some(text) = report_text(report) {
+        out.push_str(&format!("\n{}\n", quote(text.trim(), "  ")));
+    }
+    out.push('\n');
+}
+
+/// Appends a sub-agent report as an HTML block.
+fn push_html_report(out: &mut String, report: &SubAgentExecution) {
+    out.push_str(&format!(
+        "<div class=\"report\"><strong>{}</strong> ({}): {}",
+        escape_html(&report.sub_agent_name),
+        report.status,
+        escape_html(&report.task_description)
+    ));
+    if let Some(text) = report_text(report) {
+        out.push_str(&format!("<div class=\"text\">{}</div>", escape_html(text)));
+    }
+    out.push_str("</div>\n");
+}
+
+/// Report summary, or error of a failed sub-agent.
+fn report_text(report: &SubAgentExecution) -> Option<&str> {
+    report
+        .result_summary
+        .as_deref()
+        .or(report.error_message.as_deref())
+        .filter(|text| !text.trim().is_empty())
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+/// Keeps the first `max_chars` characters of a text.
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+/// Wraps a text in a Markdown code fence longer than any backtick run it contains.
+fn fenced(text: &str, language: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}{}\n{}\n{}", fence, language, text.trim_end(), fence)
+}
+
+/// Turns a text into a Markdown block quote.
+fn quote(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}> {}", prefix, line).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escapes text for HTML element content and attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::sub_agent::SubAgentStatus;
+    use crate::models::tool_execution::ToolType;
+    use chrono::TimeZone;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 1, 10, minute, 0).unwrap()
+    }
+
+    fn message(id: &str, role: MessageRole, content: &str, minute: u32) -> Message {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "workflow_id": "wf1",
+            "role": role.to_string(),
+            "content": content,
+            "tokens": 0,
+            "timestamp": at(minute)
+        }))
+        .unwrap()
+    }
+
+    fn transcript() -> Transcript {
+        Transcript {
+            workflow_name: "Release <notes>".to_string(),
+            agent_id: "agent_a".to_string(),
+            exported_at: at(30),
+            messages: vec![
+                message("m1", MessageRole::User, "Summarize the release", 0),
+                message("m2", MessageRole::Assistant, "Here is ```the``` summary", 5),
+            ],
+            tool_executions: vec![ToolExecution {
+                id: "t1".to_string(),
+                workflow_id: "wf1".to_string(),
+                message_id: "m2".to_string(),
+                agent_id: "agent_a".to_string(),
+                tool_type: ToolType::Local,
+                tool_name: "MemoryTool".to_string(),
+                server_name: None,
+                input_params: serde_json::json!({ "operation": "search" }),
+                output_result: serde_json::json!({ "results": [] }),
+                success: true,
+                error_message: None,
+                duration_ms: 12,
+                iteration: 0,
+                previous_execution_id: None,
+                result_diff: None,
+                started_at_ms: None,
+                created_at: at(2),
+            }],
+            thinking_steps: vec![ThinkingStep {
+                id: "s1".to_string(),
+                workflow_id: "wf1".to_string(),
+                message_id: "m2".to_string(),
+                agent_id: "agent_a".to_string(),
+                step_number: 0,
+                content: "Read the changelog first".to_string(),
+                duration_ms: None,
+                tokens: None,
+                created_at: at(1),
+            }],
+            sub_agents: vec![SubAgentExecution {
+                id: "r1".to_string(),
+                workflow_id: "wf1".to_string(),
+                parent_agent_id: "agent_a".to_string(),
+                sub_agent_id: "agent_b".to_string(),
+                sub_agent_name: "Researcher".to_string(),
+                task_description: "Collect the merged PRs".to_string(),
+                status: SubAgentStatus::Completed,
+                duration_ms: Some(3000),
+                tokens_input: None,
+                tokens_output: None,
+                result_summary: Some("12 PRs merged".to_string()),
+                error_message: None,
+                parent_execution_id: None,
+                created_at: at(3),
+                completed_at: Some(at(4)),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_markdown_options() {
+        let transcript = transcript();
+        let minimal = transcript.to_markdown(&TranscriptOptions::default());
+        assert!(minimal.starts_with("# Release <notes>\n"));
+        assert!(minimal.contains("## Assistant · 2025-03-01 10:05 UTC"));
+        assert!(minimal.contains("- MemoryTool (local) · success · 12 ms"));
+        assert!(minimal.contains("- **Researcher** (completed): Collect the merged PRs"));
+        assert!(minimal.contains("  > 12 PRs merged"));
+        assert!(!minimal.contains("Read the changelog"));
+        assert!(!minimal.contains("\"operation\""));
+
+        let full = transcript.to_markdown(&TranscriptOptions {
+            format: TranscriptFormat::Markdown,
+            include_thinking: true,
+            include_tool_payloads: true,
+        });
+        assert!(full.contains("> Read the changelog first"));
+        assert!(full.contains("\"operation\": \"search\""));
+        // Report and tools come before the answer they led to
+        assert!(full.find("Researcher").unwrap() < full.find("Here is").unwrap());
+    }
+
+    #[test]
+    fn test_html_escapes_contents() {
+        let html = transcript().to_html(&TranscriptOptions {
+            format: TranscriptFormat::Html,
+            include_thinking: true,
+            include_tool_payloads: false,
+        });
+        assert!(html.contains("<title>Release &lt;notes&gt;</title>"));
+        assert!(html.contains("<summary>Thinking</summary>"));
+        assert!(!html.contains("<notes>"));
+    }
+
+    #[test]
+    fn test_fence_and_truncate() {
+        assert_eq!(fenced("a ``` b", ""), "````\na ``` b\n````");
+        assert_eq!(fenced("{}", "json"), "```json\n{}\n```");
+        assert_eq!(truncate("héllo", 2), "hé…");
+        assert_eq!(truncate("hé", 2), "hé");
+    }
+}
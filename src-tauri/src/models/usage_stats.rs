@@ -0,0 +1,79 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Team usage statistics models.
+//!
+//! The team usage report aggregates local usage into weekly buckets with
+//! noised counts, so that a team lead sees spend and tool patterns without
+//! any per-conversation detail. Synchronized with src/types/usageStats.ts
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// LLM usage of a week for a provider and model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageBucket {
+    /// Monday of the week (UTC)
+    pub week: NaiveDate,
+    /// Provider name
+    pub provider: String,
+    /// Model name
+    pub model: String,
+    /// Conversations using the model (noised)
+    pub conversations: u64,
+    /// Assistant responses (noised)
+    pub messages: u64,
+    /// Input and output tokens (noised, rounded to the thousand)
+    pub tokens: u64,
+    /// Estimated spend in USD (noised, rounded to the cent)
+    pub spend_usd: f64,
+}
+
+/// Tool usage of a week
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolUsageBucket {
+    /// Monday of the week (UTC)
+    pub week: NaiveDate,
+    /// Tool type (`local` or `mcp`)
+    pub tool_type: String,
+    /// Tool name
+    pub tool_name: String,
+    /// Conversations calling the tool (noised)
+    pub conversations: u64,
+    /// Tool calls (noised)
+    pub calls: u64,
+    /// Failed tool calls (noised)
+    pub failures: u64,
+}
+
+/// Aggregated usage report for a team, generated locally
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamUsageReport {
+    /// Report format version
+    pub format_version: u32,
+    /// Generation timestamp
+    pub generated_at: DateTime<Utc>,
+    /// First day covered
+    pub period_start: NaiveDate,
+    /// Last day covered
+    pub period_end: NaiveDate,
+    /// Privacy budget of each bucket (lower is noisier)
+    pub epsilon: f64,
+    /// LLM usage buckets
+    pub usage: Vec<UsageBucket>,
+    /// Tool usage buckets
+    pub tools: Vec<ToolUsageBucket>,
+    /// Buckets left out because too few conversations contributed to them
+    pub suppressed_buckets: usize,
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use super::serde_utils::deserialize_thing_id;
+use super::tool_acknowledgment::{classify_powerful_tool, PowerfulToolKind};
+use super::tool_effects::{tool_call_effects, ToolCallEffects};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +36,8 @@ pub enum ValidationType {
     Mcp,
     FileOp,
     DbOp,
+    TokenAnomaly,
+    ToolFirstUse,
 }
 
 /// Risk level of the operation
@@ -130,6 +134,8 @@ impl std::fmt::Display for ValidationType {
             ValidationType::Mcp => write!(f, "mcp"),
             ValidationType::FileOp => write!(f, "file_op"),
             ValidationType::DbOp => write!(f, "db_op"),
+            ValidationType::TokenAnomaly => write!(f, "token_anomaly"),
+            ValidationType::ToolFirstUse => write!(f, "tool_first_use"),
         }
     }
 }
@@ -229,7 +235,7 @@ impl Default for RiskThresholdConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuditConfig {
-    /// Enable validation decision logging
+    /// Record sensitive actions in the audit log (see [`crate::security::audit`])
     pub enable_logging: bool,
     /// Log retention in days (7-90)
     pub retention_days: i32,
@@ -244,6 +250,81 @@ impl Default for AuditConfig {
     }
 }
 
+/// External channel receiving pending validation requests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannelType {
+    /// JSON `POST` to an arbitrary URL
+    Webhook,
+    /// ntfy topic URL (e.g. `https://ntfy.sh/my-topic`)
+    Ntfy,
+    /// Gotify server URL (application token required)
+    Gotify,
+}
+
+impl std::fmt::Display for NotificationChannelType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationChannelType::Webhook => write!(f, "webhook"),
+            NotificationChannelType::Ntfy => write!(f, "ntfy"),
+            NotificationChannelType::Gotify => write!(f, "gotify"),
+        }
+    }
+}
+
+/// Notification channel configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationChannel {
+    /// Channel type
+    #[serde(rename = "type")]
+    pub channel_type: NotificationChannelType,
+    /// Target URL (webhook endpoint, ntfy topic URL or Gotify server URL)
+    pub url: String,
+    /// Access token (Bearer token for webhook/ntfy, application token for Gotify)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Whether requests are forwarded to this channel
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Forwarding of pending validation requests to external channels.
+///
+/// Each notification carries signed one-time approve/reject links served by a
+/// callback listener bound to `callback_bind_address:callback_port`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ValidationNotificationConfig {
+    /// Forward pending requests to the channels
+    pub enabled: bool,
+    /// Configured channels
+    pub channels: Vec<NotificationChannel>,
+    /// Address the callback listener binds to
+    pub callback_bind_address: String,
+    /// Port of the callback listener (0 = random)
+    pub callback_port: u16,
+    /// Base URL used in links when the listener is reached through a proxy or tunnel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_base_url: Option<String>,
+}
+
+impl Default for ValidationNotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channels: Vec::new(),
+            callback_bind_address: "127.0.0.1".to_string(),
+            callback_port: 0,
+            public_base_url: None,
+        }
+    }
+}
+
 /// Main validation settings configuration
 #[allow(dead_code)] // API type for validation settings creation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -292,6 +373,9 @@ pub struct ValidationSettings {
     pub timeout_behavior: TimeoutBehavior,
     /// Audit settings
     pub audit: AuditConfig,
+    /// External notification channels
+    #[serde(default)]
+    pub notifications: ValidationNotificationConfig,
     /// Last update timestamp
     pub updated_at: DateTime<Utc>,
 }
@@ -305,11 +389,154 @@ impl Default for ValidationSettings {
             timeout_seconds: 60,
             timeout_behavior: TimeoutBehavior::default(),
             audit: AuditConfig::default(),
+            notifications: ValidationNotificationConfig::default(),
             updated_at: Utc::now(),
         }
     }
 }
 
+impl ValidationSettings {
+    /// Time the user has to answer a validation request (30-300 seconds).
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.timeout_seconds.clamp(30, 300) as u64)
+    }
+}
+
+/// Risk assessment of a tool call
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolCallRisk {
+    /// Category matched against the selective validation settings
+    pub validation_type: ValidationType,
+    /// Risk level matched against the risk thresholds
+    pub risk_level: RiskLevel,
+}
+
+/// Classifies a tool call by risk before it is executed.
+///
+/// - Shell, SQL and file system write tools are high risk; SQL and file
+///   writes fall under the `db_ops` and `file_ops` selective settings.
+/// - Local tools are low risk, medium for operations that delete data or
+///   act outside the app (`clear_by_type`, `send_draft`...).
+/// - Other MCP tools are medium risk: low when they only read, high when
+///   they destroy data.
+///
+/// Effects come from [`tool_call_effects`], also used by dry runs.
+///
+/// `tool_name` is the tool name without the MCP server prefix.
+pub fn classify_tool_call(
+    tool_name: &str,
+    is_mcp: bool,
+    arguments: &serde_json::Value,
+) -> ToolCallRisk {
+    let default_type = if is_mcp {
+        ValidationType::Mcp
+    } else {
+        ValidationType::Tool
+    };
+    let risk = |validation_type, risk_level| ToolCallRisk {
+        validation_type,
+        risk_level,
+    };
+
+    // The SqlTool asks to confirm each write statement itself
+    if !is_mcp && tool_name == "SqlTool" {
+        return risk(ValidationType::DbOp, RiskLevel::Low);
+    }
+
+    match classify_powerful_tool(tool_name) {
+        Some(PowerfulToolKind::Shell) => return risk(default_type, RiskLevel::High),
+        Some(PowerfulToolKind::ExternalSql) => return risk(ValidationType::DbOp, RiskLevel::High),
+        Some(PowerfulToolKind::FileSystemWrite) => {
+            return risk(ValidationType::FileOp, RiskLevel::High)
+        }
+        None => {}
+    }
+
+    let effects = tool_call_effects(tool_name, is_mcp, arguments);
+    let level = match (is_mcp, effects) {
+        (_, ToolCallEffects::ReadOnly) | (false, ToolCallEffects::Writes) => RiskLevel::Low,
+        (true, ToolCallEffects::Destructive) => RiskLevel::High,
+        _ => RiskLevel::Medium,
+    };
+    risk(default_type, level)
+}
+
+/// Preview of the changes a tool call will make, attached to its validation
+/// request (`details.preview`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChangePreview {
+    /// Records that will be deleted
+    Rows {
+        /// Table of the records
+        table: String,
+        /// Number of affected records
+        total: u64,
+        /// First affected records
+        rows: Vec<PreviewRow>,
+    },
+    /// Fields of a record that will be updated
+    Change {
+        /// Table of the record
+        table: String,
+        /// Record ID
+        id: String,
+        /// Readable name of the record
+        label: String,
+        /// Updated fields
+        fields: Vec<FieldChange>,
+    },
+    /// Line diff of a file that will be written
+    Diff {
+        /// File path
+        path: String,
+        /// Whether the file does not exist yet
+        new_file: bool,
+        /// Number of added lines
+        additions: usize,
+        /// Number of deleted lines
+        deletions: usize,
+        /// Unified diff hunks
+        diff: String,
+        /// Whether the diff was cut to the preview limit
+        truncated: bool,
+    },
+    /// Statement that will be run (affected rows are unknown before execution)
+    Statement {
+        /// Statement text
+        statement: String,
+    },
+    /// HTTP request that will be sent
+    Request {
+        /// HTTP method
+        method: String,
+        /// Request URL
+        url: String,
+        /// Request body, if any
+        body: Option<String>,
+    },
+}
+
+/// Record listed in a [`ChangePreview::Rows`] preview
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreviewRow {
+    /// Record ID
+    pub id: String,
+    /// Short description of the record
+    pub summary: String,
+}
+
+/// Field updated in a [`ChangePreview::Change`] preview
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    /// Field name
+    pub field: String,
+    /// Current value
+    pub before: serde_json::Value,
+    /// Value after the call
+    pub after: serde_json::Value,
+}
+
 /// Update request for partial updates (all fields optional)
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -326,6 +553,8 @@ pub struct UpdateValidationSettingsRequest {
     pub timeout_behavior: Option<TimeoutBehavior>,
     /// Update audit settings
     pub audit: Option<PartialAuditConfig>,
+    /// Replace notification settings
+    pub notifications: Option<ValidationNotificationConfig>,
 }
 
 /// Partial selective config for updates
@@ -438,4 +667,105 @@ mod tests {
         // Verify that 'validation_type' is serialized as 'type' in JSON
         assert!(json.contains("\"type\":\"mcp\""));
     }
+
+    #[test]
+    fn test_validation_settings_notifications_default() {
+        // Settings saved before notifications existed still load
+        let json = serde_json::json!({
+            "mode": "selective",
+            "selectiveConfig": {
+                "tools": false, "subAgents": true, "mcp": true, "fileOps": true, "dbOps": true
+            },
+            "riskThresholds": { "autoApproveLow": true, "alwaysConfirmHigh": false },
+            "timeoutSeconds": 60,
+            "timeoutBehavior": "reject",
+            "audit": { "enableLogging": true, "retentionDays": 30 },
+            "updatedAt": "2025-01-01T00:00:00Z"
+        });
+        let settings: ValidationSettings = serde_json::from_value(json).unwrap();
+        assert!(!settings.notifications.enabled);
+        assert!(settings.notifications.channels.is_empty());
+        assert_eq!(settings.notifications.callback_bind_address, "127.0.0.1");
+
+        let channel: NotificationChannel = serde_json::from_value(serde_json::json!({
+            "type": "ntfy",
+            "url": "https://ntfy.sh/approvals"
+        }))
+        .unwrap();
+        assert_eq!(channel.channel_type, NotificationChannelType::Ntfy);
+        assert!(channel.enabled);
+        assert!(channel.token.is_none());
+    }
+
+    #[test]
+    fn test_classify_tool_call() {
+        let args = |operation: &str| serde_json::json!({ "operation": operation });
+        let classify = |name, is_mcp, arguments: &serde_json::Value| {
+            let risk = classify_tool_call(name, is_mcp, arguments);
+            (risk.validation_type, risk.risk_level)
+        };
+
+        assert_eq!(
+            classify("MemoryTool", false, &args("search")),
+            (ValidationType::Tool, RiskLevel::Low)
+        );
+        assert_eq!(
+            classify("TodoTool", false, &args("delete")),
+            (ValidationType::Tool, RiskLevel::Medium)
+        );
+        assert_eq!(
+            classify("list_issues", true, &serde_json::Value::Null),
+            (ValidationType::Mcp, RiskLevel::Low)
+        );
+        assert_eq!(
+            classify("create_issue", true, &serde_json::Value::Null),
+            (ValidationType::Mcp, RiskLevel::Medium)
+        );
+        assert_eq!(
+            classify("deleteBranch", true, &serde_json::Value::Null),
+            (ValidationType::Mcp, RiskLevel::High)
+        );
+        assert_eq!(
+            classify("run_command", true, &serde_json::Value::Null),
+            (ValidationType::Mcp, RiskLevel::High)
+        );
+        assert_eq!(
+            classify("write_file", true, &serde_json::Value::Null),
+            (ValidationType::FileOp, RiskLevel::High)
+        );
+        assert_eq!(
+            classify("execute_sql", true, &serde_json::Value::Null),
+            (ValidationType::DbOp, RiskLevel::High)
+        );
+        assert_eq!(
+            classify("SqlTool", false, &args("query")),
+            (ValidationType::DbOp, RiskLevel::Low)
+        );
+        assert_eq!(
+            classify("MemoryTool", false, &args("clear_by_type")),
+            (ValidationType::Tool, RiskLevel::Medium)
+        );
+        assert_eq!(
+            classify("EmailTool", false, &args("send_draft")),
+            (ValidationType::Tool, RiskLevel::Medium)
+        );
+        assert_eq!(
+            classify("get_or_create_issue", true, &serde_json::Value::Null),
+            (ValidationType::Mcp, RiskLevel::Medium)
+        );
+        assert_eq!(
+            classify("fetch_and_update_record", true, &serde_json::Value::Null),
+            (ValidationType::Mcp, RiskLevel::Medium)
+        );
+    }
+
+    #[test]
+    fn test_settings_timeout_is_clamped() {
+        let mut settings = ValidationSettings::default();
+        assert_eq!(settings.timeout().as_secs(), 60);
+        settings.timeout_seconds = 5;
+        assert_eq!(settings.timeout().as_secs(), 30);
+        settings.timeout_seconds = 1_000;
+        assert_eq!(settings.timeout().as_secs(), 300);
+    }
 }
@@ -0,0 +1,126 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stuck tool loop watchdog models.
+//!
+//! While an agent runs its tool loop, a watchdog measures the time elapsed
+//! since the last stream event of the workflow. When it exceeds
+//! `stall_threshold_secs`, a `possibly_stuck` chunk reporting the current
+//! phase is emitted so the frontend can offer to cancel the run.
+
+use serde::{Deserialize, Serialize};
+
+/// Watchdog settings (stored in `settings:watchdog`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchdogSettings {
+    /// Enable the watchdog
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Seconds without any stream event before the run is reported as possibly stuck
+    #[serde(default = "default_stall_threshold_secs")]
+    pub stall_threshold_secs: u64,
+}
+
+impl Default for WatchdogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            stall_threshold_secs: default_stall_threshold_secs(),
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_stall_threshold_secs() -> u64 {
+    120
+}
+
+/// Phase of the tool loop reported by the watchdog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunPhase {
+    /// Waiting for the response of an LLM call
+    LlmCall {
+        /// Tool loop iteration (1-indexed)
+        iteration: usize,
+    },
+    /// Executing the tool calls of an LLM response
+    Tools {
+        /// Tool loop iteration (1-indexed)
+        iteration: usize,
+        /// Names of the called tools
+        tools: Vec<String>,
+    },
+}
+
+impl RunPhase {
+    /// Describes the phase for the `possibly_stuck` chunk.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::LlmCall { iteration } => {
+                format!("Waiting for the LLM response (iteration {})", iteration)
+            }
+            Self::Tools { iteration, tools } => {
+                format!("Executing {} (iteration {})", tools.join(", "), iteration)
+            }
+        }
+    }
+
+    /// Tool being executed, when a single tool runs.
+    pub fn tool(&self) -> Option<String> {
+        match self {
+            Self::Tools { tools, .. } if tools.len() == 1 => tools.first().cloned(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_defaults() {
+        let settings: WatchdogSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, WatchdogSettings::default());
+        assert!(settings.enabled);
+        assert_eq!(settings.stall_threshold_secs, 120);
+    }
+
+    #[test]
+    fn test_run_phase_describe() {
+        let phase = RunPhase::LlmCall { iteration: 2 };
+        assert_eq!(
+            phase.describe(),
+            "Waiting for the LLM response (iteration 2)"
+        );
+        assert_eq!(phase.tool(), None);
+
+        let phase = RunPhase::Tools {
+            iteration: 3,
+            tools: vec!["MemoryTool".to_string()],
+        };
+        assert_eq!(phase.describe(), "Executing MemoryTool (iteration 3)");
+        assert_eq!(phase.tool(), Some("MemoryTool".to_string()));
+
+        let phase = RunPhase::Tools {
+            iteration: 1,
+            tools: vec!["a".to_string(), "b".to_string()],
+        };
+        assert_eq!(phase.describe(), "Executing a, b (iteration 1)");
+        assert_eq!(phase.tool(), None);
+    }
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::generation::GenerationParams;
+use super::message::ContextUsage;
+use super::moderation::ModerationOutcome;
 use super::serde_utils::{deserialize_thing_id, deserialize_workflow_status};
 use super::{Message, ThinkingStep, ToolExecution};
 use chrono::{DateTime, Utc};
@@ -64,6 +67,30 @@ pub struct Workflow {
     /// Current context size (last API call context window usage)
     #[serde(default)]
     pub current_context_tokens: u64,
+    /// Language pinned for the responses (ISO 639-1 code), detected from the
+    /// first user messages or set by the user
+    #[serde(default)]
+    pub response_language: Option<String>,
+    /// User-defined tags (used to filter the workflow list)
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Sensitive workflow: messages, memories and tool logs are encrypted
+    /// and only readable while the workflow is open
+    #[serde(default)]
+    pub sensitive: bool,
+    /// Dry run: tools with side effects return a simulated result describing
+    /// what they would have done instead of executing
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Payload of the `workflow_title_updated` event, emitted when a title is generated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTitleUpdatedEvent {
+    /// Workflow ID
+    pub workflow_id: String,
+    /// New workflow name
+    pub title: String,
 }
 
 /// Workflow creation payload - only fields needed for creation
@@ -91,6 +118,80 @@ impl WorkflowCreate {
     }
 }
 
+/// Sort key of the workflow list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkflowSortBy {
+    /// Last activity (`updated_at`)
+    #[default]
+    LastActivity,
+    /// Cumulative cost (`total_cost_usd`)
+    Cost,
+    /// Name (case-insensitive)
+    Name,
+}
+
+/// Query of a workflow list page (sorting, filters and pagination)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkflowListQuery {
+    /// Sort key
+    pub sort_by: WorkflowSortBy,
+    /// Ascending order (descending by default: most recent, most expensive, Z-A)
+    pub ascending: bool,
+    /// Only workflows with this status
+    pub status: Option<WorkflowStatus>,
+    /// Only workflows with this tag
+    pub tag: Option<String>,
+    /// Only workflows of this agent
+    pub agent_id: Option<String>,
+    /// Only workflows created at or after this date
+    pub created_after: Option<DateTime<Utc>>,
+    /// Only workflows created before this date
+    pub created_before: Option<DateTime<Utc>>,
+    /// Page size (default 50, capped at 200)
+    pub limit: Option<u32>,
+    /// Number of workflows to skip
+    pub offset: Option<u32>,
+}
+
+/// Tag of the workflow catalog, with the number of workflows using it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTag {
+    /// Tag name (trimmed, lowercase)
+    pub name: String,
+    /// Display color (CSS color, e.g. `#3b82f6`)
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Number of workflows carrying the tag
+    #[serde(default)]
+    pub workflow_count: u32,
+}
+
+/// Outcome of a bulk tag operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BulkTagResult {
+    /// Number of workflows updated
+    pub updated: u32,
+    /// Workflows left untouched (not found, or the tag limit would be exceeded)
+    pub skipped: Vec<String>,
+}
+
+/// Page of the workflow list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaginatedWorkflows {
+    /// Workflows in the current page
+    pub workflows: Vec<Workflow>,
+    /// Total number of workflows matching the filters
+    pub total: u32,
+    /// Current offset (number of workflows skipped)
+    pub offset: u32,
+    /// Page size limit
+    pub limit: u32,
+    /// Whether more workflows are available after this page
+    pub has_more: bool,
+}
+
 impl std::fmt::Display for WorkflowStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -142,6 +243,22 @@ pub struct WorkflowResult {
     pub mcp_calls: Vec<String>,
     /// Detailed tool execution data for persistence
     pub tool_executions: Vec<WorkflowToolExecution>,
+    /// Output moderation outcome (None if the agent has no enabled policy)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ModerationOutcome>,
+    /// Context window utilization of the run (stored with the assistant message)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_usage: Option<ContextUsage>,
+    /// Behavior changelog position in effect when the run started (stored with the assistant message)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub behavior_position: Option<u64>,
+    /// Generation parameters used for the run (stored with the assistant message)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation: Option<GenerationParams>,
+    /// Images produced by tools: ChartTool charts and ScreenshotTool captures
+    /// (paths, attached to the assistant message)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
 }
 
 /// Metrics collected during workflow execution
@@ -226,6 +343,10 @@ mod tests {
             total_cost_usd: 0.0,
             model_id: None,
             current_context_tokens: 0,
+            response_language: None,
+            tags: vec![],
+            sensitive: false,
+            dry_run: false,
         };
 
         let json = serde_json::to_string(&workflow).unwrap();
@@ -254,6 +375,11 @@ mod tests {
             tools_used: vec!["tool1".to_string(), "tool2".to_string()],
             mcp_calls: vec!["mcp_call1".to_string()],
             tool_executions: vec![],
+            moderation: None,
+            context_usage: None,
+            behavior_position: None,
+            generation: None,
+            images: Vec::new(),
         };
 
         let json = serde_json::to_string(&result).unwrap();
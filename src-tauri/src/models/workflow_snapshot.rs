@@ -0,0 +1,361 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow snapshot models.
+//!
+//! A snapshot is a static, self-contained HTML page of a workflow: the
+//! transcript with the tool executions of each assistant message as
+//! collapsible details. Secrets found in tool parameters, tool results and
+//! message contents are redacted before rendering (see
+//! [`crate::security::redaction`]). The page has no script and no external
+//! resource, so it can be exported or served read-only by the
+//! `create_workflow_snapshot` command.
+
+use crate::models::locale::Locale;
+use crate::models::message::{Message, MessageRole};
+use crate::models::tool_execution::ToolExecution;
+use crate::models::workflow::Workflow;
+use crate::security::redaction::{redact_json, redact_text};
+use crate::tools::constants::snapshot::MAX_TOOL_DETAIL_CHARS;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Snapshot returned by the `create_workflow_snapshot` command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowSnapshot {
+    /// Workflow ID
+    pub workflow_id: String,
+    /// Workflow name
+    pub workflow_name: String,
+    /// Self-contained HTML page
+    pub html: String,
+    /// Number of messages in the transcript
+    pub message_count: usize,
+    /// Number of tool executions in the transcript
+    pub tool_execution_count: usize,
+    /// Temporary localhost URL serving the page (only when served)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Time at which the temporary server stops (only when served)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Escapes text for inclusion in HTML content and attributes.
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Pretty-prints a redacted JSON value, truncated to the detail limit.
+fn render_detail(value: &Value) -> String {
+    let pretty = serde_json::to_string_pretty(&redact_json(value)).unwrap_or_default();
+    let char_count = pretty.chars().count();
+    let text = if char_count > MAX_TOOL_DETAIL_CHARS {
+        let truncated: String = pretty.chars().take(MAX_TOOL_DETAIL_CHARS).collect();
+        format!(
+            "{}\n... [truncated, {} more characters]",
+            truncated,
+            char_count - MAX_TOOL_DETAIL_CHARS
+        )
+    } else {
+        pretty
+    };
+    escape_html(&text)
+}
+
+/// Renders a tool execution as a collapsible `<details>` block.
+fn render_tool_execution(execution: &ToolExecution) -> String {
+    let name = match execution.server_name {
+        Some(ref server) => format!("{} ({})", execution.tool_name, server),
+        None => execution.tool_name.clone(),
+    };
+    let (status_class, status) = if execution.success {
+        ("ok", "success")
+    } else {
+        ("error", "failed")
+    };
+    let error = execution
+        .error_message
+        .as_deref()
+        .map(|message| {
+            format!(
+                "<p class=\"error\">{}</p>",
+                escape_html(&redact_text(message))
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        "<details class=\"tool\"><summary><span class=\"tool-name\">{}</span> \
+         <span class=\"{}\">{}</span> <span class=\"meta\">{} ms</span></summary>\
+         {}<h4>Input</h4><pre>{}</pre><h4>Output</h4><pre>{}</pre></details>",
+        escape_html(&name),
+        status_class,
+        status,
+        execution.duration_ms,
+        error,
+        render_detail(&execution.input_params),
+        render_detail(&execution.output_result)
+    )
+}
+
+/// Renders a message with the tool executions recorded during it.
+fn render_message(message: &Message, executions: &[&ToolExecution], locale: Locale) -> String {
+    let role = match message.role {
+        MessageRole::User => "User",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::System => "System",
+    };
+    let mut meta = vec![locale.format_datetime(&message.timestamp)];
+    if let Some(ref model) = message.model {
+        meta.push(model.clone());
+    }
+    if let (Some(input), Some(output)) = (message.tokens_input, message.tokens_output) {
+        meta.push(format!(
+            "{} / {} tokens",
+            locale.format_number(input as f64),
+            locale.format_number(output as f64)
+        ));
+    }
+    let tools: String = executions
+        .iter()
+        .map(|execution| render_tool_execution(execution))
+        .collect();
+
+    format!(
+        "<article class=\"message {}\"><header><strong>{}</strong> \
+         <span class=\"meta\">{}</span></header>{}<div class=\"content\">{}</div></article>",
+        message.role,
+        role,
+        escape_html(&meta.join(" · ")),
+        tools,
+        escape_html(&redact_text(&message.content))
+    )
+}
+
+/// Inline stylesheet of the snapshot page
+const SNAPSHOT_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:900px;margin:2rem auto;\
+padding:0 1rem;color:#1f2328;background:#fff}h1{font-size:1.4rem;margin-bottom:.25rem}\
+.meta{color:#656d76;font-size:.85rem}.message{border:1px solid #d0d7de;border-radius:8px;\
+padding:.75rem 1rem;margin:1rem 0}.message.user{background:#f6f8fa}.message.system{border-color:#cf222e}\
+.content{white-space:pre-wrap;word-wrap:break-word;margin-top:.5rem}.tool{margin:.5rem 0;\
+border-left:3px solid #8c959f;padding-left:.5rem}.tool summary{cursor:pointer}\
+.tool-name{font-family:monospace}.ok{color:#1a7f37}.error{color:#cf222e}h4{margin:.5rem 0 .25rem;\
+font-size:.85rem}pre{background:#f6f8fa;padding:.5rem;overflow-x:auto;font-size:.8rem;\
+white-space:pre-wrap;word-wrap:break-word}footer{margin-top:2rem}";
+
+/// Renders the snapshot HTML page of a workflow.
+///
+/// Tool executions are attached to the message they were recorded during;
+/// executions of unknown messages are listed after the transcript.
+pub fn render_snapshot_html(
+    workflow: &Workflow,
+    messages: &[Message],
+    tool_executions: &[ToolExecution],
+    locale: Locale,
+    generated_at: DateTime<Utc>,
+) -> String {
+    let mut executions_by_message: HashMap<&str, Vec<&ToolExecution>> = HashMap::new();
+    for execution in tool_executions {
+        executions_by_message
+            .entry(execution.message_id.as_str())
+            .or_default()
+            .push(execution);
+    }
+
+    let mut body: String = messages
+        .iter()
+        .map(|message| {
+            let executions = executions_by_message
+                .remove(message.id.as_str())
+                .unwrap_or_default();
+            render_message(message, &executions, locale)
+        })
+        .collect();
+
+    let mut orphans: Vec<&ToolExecution> = executions_by_message.into_values().flatten().collect();
+    if !orphans.is_empty() {
+        orphans.sort_by_key(|execution| execution.created_at);
+        body.push_str("<section><h2>Other tool executions</h2>");
+        for execution in orphans {
+            body.push_str(&render_tool_execution(execution));
+        }
+        body.push_str("</section>");
+    }
+
+    let summary = format!(
+        "{} messages · {} tool executions · {} / {} tokens",
+        messages.len(),
+        tool_executions.len(),
+        locale.format_number(workflow.total_tokens_input as f64),
+        locale.format_number(workflow.total_tokens_output as f64)
+    );
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\"><head><meta charset=\"utf-8\">\
+         <meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'; style-src 'unsafe-inline'\">\
+         <meta name=\"referrer\" content=\"no-referrer\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <title>{}</title><style>{}</style></head><body>\
+         <h1>{}</h1><p class=\"meta\">{}</p>{}\
+         <footer class=\"meta\">Read-only snapshot generated {}</footer></body></html>\n",
+        locale.code(),
+        escape_html(&workflow.name),
+        SNAPSHOT_STYLE,
+        escape_html(&workflow.name),
+        escape_html(&summary),
+        body,
+        escape_html(&locale.format_datetime(&generated_at))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tool_execution::ToolType;
+    use crate::models::workflow::WorkflowStatus;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn workflow() -> Workflow {
+        let created = Utc.with_ymd_and_hms(2026, 10, 1, 9, 0, 0).unwrap();
+        Workflow {
+            id: "wf".to_string(),
+            name: "Release <notes>".to_string(),
+            agent_id: "agent".to_string(),
+            status: WorkflowStatus::Completed,
+            created_at: created,
+            updated_at: created,
+            completed_at: None,
+            total_tokens_input: 1200,
+            total_tokens_output: 300,
+            total_cost_usd: 0.0,
+            model_id: None,
+            current_context_tokens: 0,
+            response_language: None,
+            tags: vec![],
+            sensitive: false,
+            dry_run: false,
+        }
+    }
+
+    fn message(id: &str, role: MessageRole, content: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            workflow_id: "wf".to_string(),
+            role,
+            content: content.to_string(),
+            tokens: 0,
+            tokens_input: None,
+            tokens_output: None,
+            model: None,
+            provider: None,
+            cost_usd: None,
+            duration_ms: None,
+            context_usage: None,
+            behavior_position: None,
+            generation: None,
+            attachments: Vec::new(),
+            pinned: false,
+            bookmarked: false,
+            reactions: Vec::new(),
+            timestamp: Utc.with_ymd_and_hms(2026, 10, 1, 9, 1, 0).unwrap(),
+        }
+    }
+
+    fn execution(message_id: &str, input: Value, output: Value) -> ToolExecution {
+        ToolExecution {
+            id: "exec".to_string(),
+            workflow_id: "wf".to_string(),
+            message_id: message_id.to_string(),
+            agent_id: "agent".to_string(),
+            tool_type: ToolType::Mcp,
+            tool_name: "search".to_string(),
+            server_name: Some("docs".to_string()),
+            input_params: input,
+            output_result: output,
+            success: false,
+            error_message: Some("upstream rejected Bearer abcdefghijklmnop".to_string()),
+            duration_ms: 42,
+            iteration: 1,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: None,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 1, 9, 1, 5).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_render_snapshot_html() {
+        let messages = vec![
+            message("m1", MessageRole::User, "<script>alert(1)</script>"),
+            message("m2", MessageRole::Assistant, "Done, password=hunter2"),
+        ];
+        let executions = vec![
+            execution(
+                "m2",
+                json!({"query": "notes", "api_key": "abc"}),
+                json!({"result": "ok"}),
+            ),
+            execution("gone", json!({}), json!({})),
+        ];
+        let generated_at = Utc.with_ymd_and_hms(2026, 10, 1, 10, 0, 0).unwrap();
+
+        let html = render_snapshot_html(
+            &workflow(),
+            &messages,
+            &executions,
+            Locale::En,
+            generated_at,
+        );
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Release &lt;notes&gt;</title>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("hunter2"));
+        assert!(!html.contains("&quot;abc&quot;"));
+        assert!(!html.contains("abcdefghijklmnop"));
+        assert!(html.contains("search (docs)"));
+        assert!(html.contains("Other tool executions"));
+        assert!(html.contains("2 messages · 2 tool executions · 1,200 / 300 tokens"));
+        assert!(html.contains("Thursday 01 October 2026, 10:00:00"));
+
+        // Tool details are attached to their message
+        let assistant = html.find("Done, password").unwrap();
+        let tool = html.find("search (docs)").unwrap();
+        assert!(tool < assistant);
+        assert!(html.find("message assistant").unwrap() < tool);
+    }
+
+    #[test]
+    fn test_render_detail_truncates() {
+        let long = "x".repeat(MAX_TOOL_DETAIL_CHARS + 100);
+        let detail = render_detail(&json!(long));
+        assert!(detail.contains("[truncated, 102 more characters]"));
+    }
+}
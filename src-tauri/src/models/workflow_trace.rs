@@ -0,0 +1,522 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workflow timeline export.
+//!
+//! The LLM calls, tool calls and sub-agent runs recorded for a workflow are
+//! laid out as a span tree (workflow -> agent run -> iteration -> LLM call /
+//! tool calls) in the Jaeger JSON format, which Jaeger loads as a trace file.
+//! Span tags follow the OpenTelemetry GenAI conventions (`gen_ai.*`), the
+//! names used by LLM observability tools like Langfuse.
+
+use crate::models::execution_trace::LlmTurn;
+use crate::models::sub_agent::SubAgentExecution;
+use crate::models::tool_execution::{ToolExecution, ToolType};
+use crate::security::redaction::redact_text;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Process ID of the spans
+const PROCESS_ID: &str = "p1";
+/// Service name of the process
+const SERVICE_NAME: &str = "zileo-chat";
+
+/// Trace file (`{"data": [trace]}`, the format of Jaeger's JSON export)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTraceExport {
+    /// Traces (a single one: the workflow)
+    pub data: Vec<WorkflowTrace>,
+}
+
+/// Trace of a workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowTrace {
+    /// Trace ID (the workflow UUID without dashes)
+    #[serde(rename = "traceID")]
+    pub trace_id: String,
+    /// Spans, parents first
+    pub spans: Vec<TraceSpan>,
+    /// Processes referenced by the spans
+    pub processes: BTreeMap<String, TraceProcess>,
+}
+
+/// Timed operation of the trace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceSpan {
+    /// Trace ID
+    #[serde(rename = "traceID")]
+    pub trace_id: String,
+    /// Span ID (16 hex digits)
+    #[serde(rename = "spanID")]
+    pub span_id: String,
+    /// Operation name (`chat mistral-large`, `execute_tool find_symbol`...)
+    #[serde(rename = "operationName")]
+    pub operation_name: String,
+    /// Parent span (empty for the root span)
+    pub references: Vec<SpanReference>,
+    /// Start time (Unix time in microseconds)
+    #[serde(rename = "startTime")]
+    pub start_time: u64,
+    /// Duration in microseconds
+    pub duration: u64,
+    /// Attributes of the span
+    pub tags: Vec<SpanTag>,
+    /// Span events (none recorded)
+    pub logs: Vec<Value>,
+    /// Process of the span
+    #[serde(rename = "processID")]
+    pub process_id: String,
+}
+
+/// Reference to the parent span
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpanReference {
+    /// Reference type (always `CHILD_OF`)
+    #[serde(rename = "refType")]
+    pub ref_type: String,
+    /// Trace ID
+    #[serde(rename = "traceID")]
+    pub trace_id: String,
+    /// Parent span ID
+    #[serde(rename = "spanID")]
+    pub span_id: String,
+}
+
+/// Typed attribute of a span or process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpanTag {
+    /// Attribute name
+    pub key: String,
+    /// Value type (`string`, `int64` or `bool`)
+    #[serde(rename = "type")]
+    pub value_type: String,
+    /// Attribute value
+    pub value: Value,
+}
+
+impl SpanTag {
+    fn string(key: &str, value: impl Into<String>) -> Self {
+        Self::new(key, "string", Value::String(value.into()))
+    }
+
+    fn int(key: &str, value: u64) -> Self {
+        Self::new(key, "int64", json!(value))
+    }
+
+    fn bool(key: &str, value: bool) -> Self {
+        Self::new(key, "bool", Value::Bool(value))
+    }
+
+    fn new(key: &str, value_type: &str, value: Value) -> Self {
+        Self {
+            key: key.to_string(),
+            value_type: value_type.to_string(),
+            value,
+        }
+    }
+}
+
+/// Process (service) emitting the spans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceProcess {
+    /// Service name
+    #[serde(rename = "serviceName")]
+    pub service_name: String,
+    /// Process attributes
+    pub tags: Vec<SpanTag>,
+}
+
+/// Span being laid out (times in milliseconds)
+struct PendingSpan {
+    key: String,
+    parent: Option<String>,
+    operation_name: String,
+    start_ms: u64,
+    end_ms: u64,
+    tags: Vec<SpanTag>,
+}
+
+/// Start of a record: its recorded start, or its end minus its duration for
+/// records persisted before start times were.
+fn start_ms(started_at_ms: Option<u64>, created_at: DateTime<Utc>, duration_ms: u64) -> u64 {
+    started_at_ms.unwrap_or_else(|| {
+        (created_at.timestamp_millis().max(0) as u64).saturating_sub(duration_ms)
+    })
+}
+
+/// Span ID derived from a key unique within the trace.
+fn span_id(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .take(8)
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Span covering children spans, None without children.
+fn covering_span(
+    key: String,
+    parent: Option<String>,
+    operation_name: String,
+    children: &[PendingSpan],
+    tags: Vec<SpanTag>,
+) -> Option<PendingSpan> {
+    Some(PendingSpan {
+        start_ms: children.iter().map(|s| s.start_ms).min()?,
+        end_ms: children.iter().map(|s| s.end_ms).max()?,
+        key,
+        parent,
+        operation_name,
+        tags,
+    })
+}
+
+impl WorkflowTraceExport {
+    /// Lays out the recorded calls of a workflow as a trace.
+    ///
+    /// Each assistant message is an agent run whose iterations hold the LLM
+    /// call and the tool calls of one tool loop turn. Tool inputs and outputs
+    /// are not exported; error messages are redacted.
+    pub fn build(
+        workflow_id: &str,
+        workflow_name: &str,
+        turns: &[LlmTurn],
+        tool_executions: &[ToolExecution],
+        sub_agents: &[SubAgentExecution],
+    ) -> Self {
+        let trace_id = workflow_id.replace('-', "");
+        let root_key = format!("workflow:{}", workflow_id);
+
+        // Message ID -> iteration -> leaf spans
+        let mut runs: BTreeMap<&str, (String, BTreeMap<u32, Vec<PendingSpan>>)> = BTreeMap::new();
+        for turn in turns {
+            let start = start_ms(turn.started_at_ms, turn.created_at, turn.duration_ms);
+            let (_, iterations) = runs
+                .entry(turn.message_id.as_str())
+                .or_insert_with(|| (turn.agent_id.clone(), BTreeMap::new()));
+            iterations
+                .entry(turn.iteration)
+                .or_default()
+                .push(PendingSpan {
+                    key: format!("turn:{}", turn.id),
+                    parent: None,
+                    operation_name: "chat".to_string(),
+                    start_ms: start,
+                    end_ms: start + turn.duration_ms,
+                    tags: vec![
+                        SpanTag::string("gen_ai.operation.name", "chat"),
+                        SpanTag::string("gen_ai.agent.id", turn.agent_id.clone()),
+                        SpanTag::int("gen_ai.usage.input_tokens", turn.tokens_input),
+                        SpanTag::int("gen_ai.usage.output_tokens", turn.tokens_output),
+                        SpanTag::int("zileo.tool_calls", turn.tool_calls as u64),
+                    ],
+                });
+        }
+        for execution in tool_executions {
+            let start = start_ms(
+                execution.started_at_ms,
+                execution.created_at,
+                execution.duration_ms,
+            );
+            let mut tags = vec![
+                SpanTag::string("gen_ai.operation.name", "execute_tool"),
+                SpanTag::string("gen_ai.tool.name", execution.tool_name.clone()),
+                SpanTag::string("gen_ai.agent.id", execution.agent_id.clone()),
+                SpanTag::bool("zileo.tool.success", execution.success),
+            ];
+            if let (ToolType::Mcp, Some(server)) = (&execution.tool_type, &execution.server_name) {
+                tags.push(SpanTag::string("zileo.mcp.server", server.clone()));
+            }
+            if !execution.success {
+                tags.push(SpanTag::bool("error", true));
+                if let Some(message) = &execution.error_message {
+                    tags.push(SpanTag::string("error.message", redact_text(message)));
+                }
+            }
+            let (_, iterations) = runs
+                .entry(execution.message_id.as_str())
+                .or_insert_with(|| (execution.agent_id.clone(), BTreeMap::new()));
+            iterations
+                .entry(execution.iteration)
+                .or_default()
+                .push(PendingSpan {
+                    key: format!("tool:{}", execution.id),
+                    parent: None,
+                    operation_name: format!("execute_tool {}", execution.tool_name),
+                    start_ms: start,
+                    end_ms: start + execution.duration_ms,
+                    tags,
+                });
+        }
+
+        // Agent runs and their iterations
+        let mut children = Vec::new();
+        for (message_id, (agent_id, iterations)) in runs {
+            let run_key = format!("message:{}", message_id);
+            let mut run_spans = Vec::new();
+            for (iteration, mut leaves) in iterations {
+                let iteration_key = format!("iteration:{}:{}", message_id, iteration);
+                leaves.sort_by_key(|s| s.start_ms);
+                if let Some(span) = covering_span(
+                    iteration_key.clone(),
+                    Some(run_key.clone()),
+                    format!("iteration {}", iteration),
+                    &leaves,
+                    vec![SpanTag::int("zileo.iteration", iteration as u64)],
+                ) {
+                    run_spans.push(span);
+                    run_spans.extend(leaves.into_iter().map(|leaf| PendingSpan {
+                        parent: Some(iteration_key.clone()),
+                        ..leaf
+                    }));
+                }
+            }
+            let run = covering_span(
+                run_key,
+                Some(root_key.clone()),
+                format!("invoke_agent {}", agent_id),
+                &run_spans,
+                vec![
+                    SpanTag::string("gen_ai.operation.name", "invoke_agent"),
+                    SpanTag::string("gen_ai.agent.id", agent_id),
+                    SpanTag::string("zileo.message_id", message_id),
+                ],
+            );
+            children.extend(run);
+            children.extend(run_spans);
+        }
+
+        for sub_agent in sub_agents {
+            let start = sub_agent.created_at.timestamp_millis().max(0) as u64;
+            let end = match (sub_agent.completed_at, sub_agent.duration_ms) {
+                (_, Some(duration)) => start + duration,
+                (Some(completed_at), None) => completed_at.timestamp_millis().max(0) as u64,
+                (None, None) => start,
+            };
+            let mut tags = vec![
+                SpanTag::string("gen_ai.operation.name", "invoke_agent"),
+                SpanTag::string("gen_ai.agent.id", sub_agent.sub_agent_id.clone()),
+                SpanTag::string("gen_ai.agent.name", sub_agent.sub_agent_name.clone()),
+                SpanTag::string("zileo.parent_agent_id", sub_agent.parent_agent_id.clone()),
+            ];
+            if let (Some(input), Some(output)) = (sub_agent.tokens_input, sub_agent.tokens_output) {
+                tags.push(SpanTag::int("gen_ai.usage.input_tokens", input));
+                tags.push(SpanTag::int("gen_ai.usage.output_tokens", output));
+            }
+            if let Some(message) = &sub_agent.error_message {
+                tags.push(SpanTag::bool("error", true));
+                tags.push(SpanTag::string("error.message", redact_text(message)));
+            }
+            children.push(PendingSpan {
+                key: format!("sub_agent:{}", sub_agent.id),
+                parent: Some(root_key.clone()),
+                operation_name: format!("invoke_agent {}", sub_agent.sub_agent_name),
+                start_ms: start,
+                end_ms: end.max(start),
+                tags,
+            });
+        }
+
+        let mut pending = Vec::with_capacity(children.len() + 1);
+        pending.extend(covering_span(
+            root_key,
+            None,
+            format!("workflow {}", workflow_name),
+            &children,
+            vec![
+                SpanTag::string("zileo.workflow_id", workflow_id),
+                SpanTag::string("zileo.workflow_name", workflow_name),
+            ],
+        ));
+        pending.extend(children);
+
+        let spans = pending
+            .into_iter()
+            .map(|span| TraceSpan {
+                trace_id: trace_id.clone(),
+                span_id: span_id(&span.key),
+                operation_name: span.operation_name,
+                references: span
+                    .parent
+                    .map(|parent| SpanReference {
+                        ref_type: "CHILD_OF".to_string(),
+                        trace_id: trace_id.clone(),
+                        span_id: span_id(&parent),
+                    })
+                    .into_iter()
+                    .collect(),
+                start_time: span.start_ms * 1000,
+                duration: (span.end_ms - span.start_ms) * 1000,
+                tags: span.tags,
+                logs: Vec::new(),
+                process_id: PROCESS_ID.to_string(),
+            })
+            .collect();
+
+        let process = TraceProcess {
+            service_name: SERVICE_NAME.to_string(),
+            tags: vec![SpanTag::string(
+                "service.version",
+                env!("CARGO_PKG_VERSION"),
+            )],
+        };
+        Self {
+            data: vec![WorkflowTrace {
+                trace_id,
+                spans,
+                processes: BTreeMap::from([(PROCESS_ID.to_string(), process)]),
+            }],
+        }
+    }
+
+    /// Number of spans of the trace.
+    pub fn span_count(&self) -> usize {
+        self.data.iter().map(|trace| trace.spans.len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const WORKFLOW_ID: &str = "6f1c2a4e-0000-4000-8000-000000000001";
+    const T0: u64 = 1_790_000_000_000;
+
+    fn turn(id: &str, iteration: u32, started_at_ms: Option<u64>, duration_ms: u64) -> LlmTurn {
+        LlmTurn {
+            id: id.to_string(),
+            workflow_id: WORKFLOW_ID.to_string(),
+            message_id: "msg".to_string(),
+            agent_id: "agent".to_string(),
+            iteration,
+            tokens_input: 100,
+            tokens_output: 20,
+            duration_ms,
+            tool_calls: 1,
+            started_at_ms,
+            created_at: Utc.timestamp_millis_opt(T0 as i64 + 10_000).unwrap(),
+        }
+    }
+
+    fn tool(id: &str, started_at_ms: u64, success: bool) -> ToolExecution {
+        ToolExecution {
+            id: id.to_string(),
+            workflow_id: WORKFLOW_ID.to_string(),
+            message_id: "msg".to_string(),
+            agent_id: "agent".to_string(),
+            tool_type: ToolType::Mcp,
+            tool_name: "find_symbol".to_string(),
+            server_name: Some("serena".to_string()),
+            input_params: json!({}),
+            output_result: json!({}),
+            success,
+            error_message: (!success).then(|| "401 for api_key=abc123".to_string()),
+            duration_ms: 300,
+            iteration: 1,
+            previous_execution_id: None,
+            result_diff: None,
+            started_at_ms: Some(started_at_ms),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn tag<'a>(span: &'a TraceSpan, key: &str) -> Option<&'a Value> {
+        span.tags.iter().find(|t| t.key == key).map(|t| &t.value)
+    }
+
+    #[test]
+    fn test_build_span_tree() {
+        let turns = vec![turn("t1", 1, Some(T0), 1000), turn("t2", 2, None, 500)];
+        let tools = vec![tool("a", T0 + 1000, true), tool("b", T0 + 1000, false)];
+
+        let export = WorkflowTraceExport::build(WORKFLOW_ID, "Release", &turns, &tools, &[]);
+        let trace = &export.data[0];
+        assert_eq!(trace.trace_id, "6f1c2a4e000040008000000000000001");
+        assert_eq!(export.span_count(), 8);
+
+        let names: Vec<&str> = trace
+            .spans
+            .iter()
+            .map(|s| s.operation_name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "workflow Release",
+                "invoke_agent agent",
+                "iteration 1",
+                "chat",
+                "execute_tool find_symbol",
+                "execute_tool find_symbol",
+                "iteration 2",
+                "chat",
+            ]
+        );
+
+        // Parents: workflow <- agent run <- iteration <- calls
+        let parent = |index: usize| trace.spans[index].references.first().map(|r| &r.span_id);
+        assert!(parent(0).is_none());
+        assert_eq!(parent(1), Some(&trace.spans[0].span_id));
+        assert_eq!(parent(2), Some(&trace.spans[1].span_id));
+        assert_eq!(parent(4), Some(&trace.spans[2].span_id));
+        assert_eq!(parent(7), Some(&trace.spans[6].span_id));
+
+        // Times in microseconds; the legacy turn ends at its record time
+        assert_eq!(trace.spans[0].start_time, T0 * 1000);
+        assert_eq!(trace.spans[0].duration, 10_000_000);
+        assert_eq!(trace.spans[2].duration, 1_300_000);
+        assert_eq!(trace.spans[7].start_time, (T0 + 9_500) * 1000);
+
+        let failed = &trace.spans[5];
+        assert_eq!(tag(failed, "error"), Some(&json!(true)));
+        assert_eq!(
+            tag(failed, "error.message"),
+            Some(&json!("401 for api_key=[REDACTED]"))
+        );
+        assert_eq!(tag(failed, "zileo.mcp.server"), Some(&json!("serena")));
+        assert_eq!(
+            tag(&trace.spans[3], "gen_ai.usage.input_tokens"),
+            Some(&json!(100))
+        );
+    }
+
+    #[test]
+    fn test_build_empty_and_jaeger_format() {
+        let export = WorkflowTraceExport::build(WORKFLOW_ID, "Empty", &[], &[], &[]);
+        assert_eq!(export.span_count(), 0);
+
+        let turns = vec![turn("t1", 1, Some(T0), 1000)];
+        let value = serde_json::to_value(WorkflowTraceExport::build(
+            WORKFLOW_ID,
+            "W",
+            &turns,
+            &[],
+            &[],
+        ))
+        .unwrap();
+        let span = &value["data"][0]["spans"][1];
+        assert!(span["traceID"].is_string());
+        assert_eq!(span["spanID"].as_str().unwrap().len(), 16);
+        assert_eq!(span["references"][0]["refType"], "CHILD_OF");
+        assert_eq!(span["processID"], "p1");
+        assert_eq!(
+            value["data"][0]["processes"]["p1"]["serviceName"],
+            "zileo-chat"
+        );
+    }
+}
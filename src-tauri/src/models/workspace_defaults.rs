@@ -0,0 +1,78 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Workspace-level agent defaults.
+//!
+//! [`WorkspaceDefaults`] are stored in the `settings:workspace_defaults`
+//! record. Agents created with `inherit_workspace_defaults` (the default)
+//! receive the baseline tools and MCP servers in addition to their own.
+
+use serde::{Deserialize, Serialize};
+
+/// Baseline tool and MCP server assignments for new agents
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceDefaults {
+    /// Local tools assigned to every new agent
+    #[serde(default)]
+    pub tools: Vec<String>,
+    /// MCP server NAMES (not IDs) assigned to every new agent
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+}
+
+impl WorkspaceDefaults {
+    /// Merges the defaults into an agent's tools and MCP servers.
+    ///
+    /// Agent entries keep their order; defaults not already present are
+    /// appended after them.
+    pub fn apply(&self, tools: &mut Vec<String>, mcp_servers: &mut Vec<String>) {
+        merge_unique(tools, &self.tools);
+        merge_unique(mcp_servers, &self.mcp_servers);
+    }
+}
+
+/// Appends the items of `defaults` missing from `target`.
+fn merge_unique(target: &mut Vec<String>, defaults: &[String]) {
+    for item in defaults {
+        if !target.contains(item) {
+            target.push(item.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_merges_without_duplicates() {
+        let defaults = WorkspaceDefaults {
+            tools: vec!["MemoryTool".to_string(), "TodoTool".to_string()],
+            mcp_servers: vec!["serena".to_string()],
+        };
+        let mut tools = vec!["TodoTool".to_string(), "CalculatorTool".to_string()];
+        let mut mcp_servers = Vec::new();
+
+        defaults.apply(&mut tools, &mut mcp_servers);
+
+        assert_eq!(tools, vec!["TodoTool", "CalculatorTool", "MemoryTool"]);
+        assert_eq!(mcp_servers, vec!["serena"]);
+    }
+
+    #[test]
+    fn test_deserialize_empty() {
+        let defaults: WorkspaceDefaults = serde_json::from_str("{}").unwrap();
+        assert_eq!(defaults, WorkspaceDefaults::default());
+    }
+}
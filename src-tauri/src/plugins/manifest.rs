@@ -0,0 +1,272 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Plugin manifest (`plugin.toml`).
+//!
+//! ```toml
+//! id = "weather"
+//! name = "Weather"
+//! version = "1.0.0"
+//! description = "Current weather of a city"
+//!
+//! [capabilities]
+//! network = ["api.open-meteo.com"]
+//! read_files = ["~/Documents/weather"]
+//! ```
+//!
+//! The manifest only requests capabilities; the user grants them when
+//! installing the plugin.
+
+use crate::models::plugin::PluginCapability;
+use crate::tools::constants::plugins as plugin_const;
+use crate::tools::utils::resolve_path;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Maximum length of the display name
+const MAX_NAME_LEN: usize = 64;
+/// Maximum length of the version
+const MAX_VERSION_LEN: usize = 32;
+/// Maximum length of the description
+const MAX_DESCRIPTION_LEN: usize = 1000;
+
+/// Capabilities requested by a plugin
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RequestedCapabilities {
+    /// Hosts the plugin sends HTTP requests to (empty: no network access)
+    #[serde(default)]
+    pub network: Vec<String>,
+    /// Directories the plugin reads text files from, absolute or starting
+    /// with `~` (empty: no file access)
+    #[serde(default)]
+    pub read_files: Vec<String>,
+}
+
+/// Parsed `plugin.toml`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginManifest {
+    /// Plugin ID, also the name of its directory
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Plugin version
+    pub version: String,
+    /// What the plugin does
+    #[serde(default)]
+    pub description: String,
+    /// Requested capabilities
+    #[serde(default)]
+    pub capabilities: RequestedCapabilities,
+}
+
+/// Whether a plugin ID is well-formed: lowercase letters, digits, `-` and
+/// `_`, starting with a letter (it becomes part of a tool name).
+pub fn is_valid_id(id: &str) -> bool {
+    id.len() <= plugin_const::MAX_ID_LEN
+        && id.starts_with(|c: char| c.is_ascii_lowercase())
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+/// Whether a network host is a plain host name or IP address.
+fn is_valid_host(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+impl PluginManifest {
+    /// Parses and validates a manifest; network hosts are lowercased.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut manifest: Self =
+            toml::from_str(text).map_err(|e| format!("Invalid plugin manifest: {}", e))?;
+
+        manifest.id = manifest.id.trim().to_string();
+        if !is_valid_id(&manifest.id) {
+            return Err(format!(
+                "Invalid plugin ID '{}': use lowercase letters, digits, '-' and '_' (at most {} characters)",
+                manifest.id,
+                plugin_const::MAX_ID_LEN
+            ));
+        }
+        manifest.name = manifest.name.trim().to_string();
+        if manifest.name.is_empty() || manifest.name.chars().count() > MAX_NAME_LEN {
+            return Err(format!(
+                "Plugin name must be 1 to {} characters",
+                MAX_NAME_LEN
+            ));
+        }
+        manifest.version = manifest.version.trim().to_string();
+        if manifest.version.is_empty() || manifest.version.len() > MAX_VERSION_LEN {
+            return Err(format!(
+                "Plugin version must be 1 to {} characters",
+                MAX_VERSION_LEN
+            ));
+        }
+        manifest.description = manifest.description.trim().to_string();
+        if manifest.description.chars().count() > MAX_DESCRIPTION_LEN {
+            return Err(format!(
+                "Plugin description exceeds {} characters",
+                MAX_DESCRIPTION_LEN
+            ));
+        }
+
+        let mut hosts: Vec<String> = Vec::new();
+        for host in &manifest.capabilities.network {
+            let host = host.trim().to_ascii_lowercase();
+            if !is_valid_host(&host) {
+                return Err(format!(
+                    "Invalid network host '{}': list host names only, without scheme, port or path",
+                    host
+                ));
+            }
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+        if hosts.len() > plugin_const::MAX_NETWORK_HOSTS {
+            return Err(format!(
+                "A plugin can request at most {} network hosts",
+                plugin_const::MAX_NETWORK_HOSTS
+            ));
+        }
+        manifest.capabilities.network = hosts;
+
+        let mut dirs: Vec<String> = Vec::new();
+        for dir in &manifest.capabilities.read_files {
+            let dir = dir.trim().to_string();
+            if dir.is_empty() || resolve_path(&dir).is_err() {
+                return Err(format!(
+                    "Invalid read directory '{}': use an absolute path or a path starting with '~'",
+                    dir
+                ));
+            }
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        if dirs.len() > plugin_const::MAX_READ_DIRS {
+            return Err(format!(
+                "A plugin can request at most {} read directories",
+                plugin_const::MAX_READ_DIRS
+            ));
+        }
+        manifest.capabilities.read_files = dirs;
+
+        Ok(manifest)
+    }
+
+    /// Capabilities the user is asked to grant.
+    pub fn requested_capabilities(&self) -> Vec<PluginCapability> {
+        let mut capabilities = Vec::new();
+        if !self.capabilities.network.is_empty() {
+            capabilities.push(PluginCapability::Network);
+        }
+        if !self.capabilities.read_files.is_empty() {
+            capabilities.push(PluginCapability::ReadFiles);
+        }
+        capabilities
+    }
+
+    /// Whether the manifest lists a network host.
+    pub fn allows_host(&self, host: &str) -> bool {
+        self.capabilities
+            .network
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+    }
+
+    /// Whether a file is inside a directory listed in the manifest.
+    ///
+    /// `path` must be canonical; the listed directories are canonicalized
+    /// too, so symbolic links cannot lead outside of them.
+    pub fn allows_path(&self, path: &Path) -> bool {
+        self.capabilities.read_files.iter().any(|dir| {
+            resolve_path(dir)
+                .ok()
+                .and_then(|dir| dir.canonicalize().ok())
+                .is_some_and(|dir| path.starts_with(dir))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = PluginManifest::parse(
+            r#"
+            id = "weather"
+            name = " Weather "
+            version = "1.0.0"
+            description = "Current weather of a city"
+
+            [capabilities]
+            network = ["API.open-meteo.com", "api.open-meteo.com"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.id, "weather");
+        assert_eq!(manifest.name, "Weather");
+        assert_eq!(manifest.capabilities.network, vec!["api.open-meteo.com"]);
+        assert_eq!(
+            manifest.requested_capabilities(),
+            vec![PluginCapability::Network]
+        );
+        assert!(manifest.allows_host("API.Open-Meteo.com"));
+        assert!(!manifest.allows_host("example.com"));
+
+        // No capabilities section: nothing requested
+        let manifest =
+            PluginManifest::parse("id = \"echo\"\nname = \"Echo\"\nversion = \"0.1\"").unwrap();
+        assert!(manifest.requested_capabilities().is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_manifests() {
+        let invalid = [
+            // Missing version
+            "id = \"echo\"\nname = \"Echo\"",
+            // ID with uppercase letters or path separators
+            "id = \"Echo\"\nname = \"Echo\"\nversion = \"1\"",
+            "id = \"../echo\"\nname = \"Echo\"\nversion = \"1\"",
+            // URL instead of a host
+            "id = \"echo\"\nname = \"Echo\"\nversion = \"1\"\n[capabilities]\nnetwork = [\"https://example.com\"]",
+            // Relative read directory
+            "id = \"echo\"\nname = \"Echo\"\nversion = \"1\"\n[capabilities]\nread_files = [\"notes\"]",
+            // Unknown capability
+            "id = \"echo\"\nname = \"Echo\"\nversion = \"1\"\n[capabilities]\nshell = true",
+        ];
+        for text in invalid {
+            assert!(PluginManifest::parse(text).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_is_valid_id() {
+        assert!(is_valid_id("weather"));
+        assert!(is_valid_id("web-search_2"));
+        assert!(!is_valid_id(""));
+        assert!(!is_valid_id("2fa"));
+        assert!(!is_valid_id("my plugin"));
+        assert!(!is_valid_id(&"a".repeat(plugin_const::MAX_ID_LEN + 1)));
+    }
+}
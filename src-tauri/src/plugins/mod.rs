@@ -0,0 +1,558 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Tool Plugins
+//!
+//! Third-party tools packaged as WebAssembly components and run by Wasmtime
+//! in a sandbox:
+//!
+//! - A plugin is a directory of `~/.zileo/plugins` holding its manifest
+//!   (`plugin.toml`, see [`manifest`]) and its component (`plugin.wasm`)
+//! - The component targets the `tool-plugin` world of
+//!   `src-tauri/wit/tool-plugin.wit`: it exports the `tool` interface
+//!   (definition, validate and execute on JSON strings) and may import the
+//!   `host` interface
+//! - Host functions other than logging need a capability requested by the
+//!   manifest and granted by the user ([`PluginCapability`]). Grants are
+//!   stored per profile (`settings:plugins`) with the SHA-256 of the
+//!   component: a modified component is disabled until approved again
+//! - Agents use a plugin as the local tool `plugin__<id>`
+//!
+//! Each call runs in a fresh instance with bounded fuel and memory
+//! ([`runtime`]), so plugins keep no state between calls.
+
+pub mod manifest;
+pub mod runtime;
+
+use crate::commands::plugin::load_plugin_settings;
+use crate::db::DBClient;
+use crate::models::plugin::{PluginCapability, PluginInfo, PluginSettings};
+use crate::tools::constants::plugins as plugin_const;
+use crate::tools::utils::data_dir;
+use crate::tools::ToolDefinition;
+use manifest::PluginManifest;
+use once_cell::sync::Lazy;
+use runtime::PluginTool;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, info};
+use wasmtime::component::Component;
+use wasmtime::Engine;
+
+/// Plugins installed in `~/.zileo/plugins`.
+pub static PLUGIN_HOST: Lazy<PluginHost> =
+    Lazy::new(|| PluginHost::new(data_dir().join(plugin_const::DIR_NAME)));
+
+/// Tool name of a plugin (`plugin__<id>`).
+pub fn tool_name(id: &str) -> String {
+    format!("{}{}", plugin_const::TOOL_PREFIX, id)
+}
+
+/// Plugin ID of a plugin tool name, None for other tools.
+pub fn plugin_id(tool_name: &str) -> Option<&str> {
+    tool_name
+        .strip_prefix(plugin_const::TOOL_PREFIX)
+        .filter(|id| manifest::is_valid_id(id))
+}
+
+/// SHA-256 of a plugin (manifest then component), in hexadecimal.
+///
+/// Covers the manifest so that added network hosts need a new approval.
+fn plugin_sha256(manifest: &str, component: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(manifest.as_bytes());
+    hasher.update([0]);
+    hasher.update(component);
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Compiled plugin, ready to be instantiated.
+pub struct LoadedPlugin {
+    /// Manifest of the plugin
+    pub manifest: PluginManifest,
+    /// SHA-256 of the manifest and component
+    pub sha256: String,
+    /// Tool definition returned by the component
+    pub definition: ToolDefinition,
+    /// Compiled component
+    component: Component,
+}
+
+/// Plugin files read from a directory
+struct PluginFiles {
+    manifest_text: String,
+    manifest: PluginManifest,
+    component: Vec<u8>,
+    sha256: String,
+}
+
+/// Reads and validates the manifest and component of a plugin directory.
+fn read_plugin(dir: &Path) -> Result<PluginFiles, String> {
+    let manifest_path = dir.join(plugin_const::MANIFEST_FILE);
+    let text = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Cannot read '{}': {}", manifest_path.display(), e))?;
+    let manifest = PluginManifest::parse(&text)?;
+
+    let component_path = dir.join(plugin_const::COMPONENT_FILE);
+    let size = std::fs::metadata(&component_path)
+        .map_err(|e| format!("Cannot read '{}': {}", component_path.display(), e))?
+        .len();
+    if size > plugin_const::MAX_COMPONENT_BYTES {
+        return Err(format!(
+            "Plugin component exceeds {} MB",
+            plugin_const::MAX_COMPONENT_BYTES / (1024 * 1024)
+        ));
+    }
+    let component = std::fs::read(&component_path)
+        .map_err(|e| format!("Cannot read '{}': {}", component_path.display(), e))?;
+    Ok(PluginFiles {
+        manifest,
+        sha256: plugin_sha256(&text, &component),
+        manifest_text: text,
+        component,
+    })
+}
+
+/// Directory of installed plugins, with the compiled components.
+///
+/// Cloning is cheap: clones share the engine and the compiled components.
+#[derive(Clone)]
+pub struct PluginHost {
+    /// Engine shared by all plugins
+    engine: Engine,
+    /// Installation directory (`~/.zileo/plugins`)
+    dir: PathBuf,
+    /// Compiled plugins by ID, recompiled when their component changes
+    loaded: Arc<Mutex<HashMap<String, Arc<LoadedPlugin>>>>,
+}
+
+impl PluginHost {
+    /// Creates a host for the plugins installed in `dir`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            engine: runtime::new_engine(),
+            dir,
+            loaded: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Whether a plugin is installed.
+    pub fn is_installed(&self, id: &str) -> bool {
+        manifest::is_valid_id(id)
+            && self
+                .dir
+                .join(id)
+                .join(plugin_const::MANIFEST_FILE)
+                .is_file()
+    }
+
+    /// Compiles a plugin and reads its tool definition.
+    fn compile(&self, files: PluginFiles) -> Result<LoadedPlugin, String> {
+        let component = runtime::compile(&self.engine, &files.component)?;
+        let definition = runtime::describe(&self.engine, &component, &files.manifest)?;
+        Ok(LoadedPlugin {
+            manifest: files.manifest,
+            sha256: files.sha256,
+            definition,
+            component,
+        })
+    }
+
+    /// Loads an installed plugin, compiling it unless its component is
+    /// unchanged since the last load. Blocking (compilation).
+    pub fn load(&self, id: &str) -> Result<Arc<LoadedPlugin>, String> {
+        if !self.is_installed(id) {
+            return Err(format!("Plugin '{}' is not installed", id));
+        }
+        let files = read_plugin(&self.dir.join(id))?;
+        if files.manifest.id != id {
+            return Err(format!(
+                "Plugin directory '{}' holds plugin '{}'",
+                id, files.manifest.id
+            ));
+        }
+
+        if let Some(plugin) = self.loaded.lock().ok().and_then(|loaded| {
+            loaded
+                .get(id)
+                .filter(|plugin| plugin.sha256 == files.sha256)
+                .cloned()
+        }) {
+            return Ok(plugin);
+        }
+
+        debug!(plugin = %id, "Compiling plugin");
+        let plugin = Arc::new(self.compile(files)?);
+        if let Ok(mut loaded) = self.loaded.lock() {
+            loaded.insert(id.to_string(), plugin.clone());
+        }
+        Ok(plugin)
+    }
+
+    /// Installs (or replaces) a plugin from a directory holding its
+    /// `plugin.toml` and `plugin.wasm`. Blocking (compilation, copy).
+    ///
+    /// The component is compiled and described before anything is copied.
+    pub fn install(&self, source: &Path) -> Result<Arc<LoadedPlugin>, String> {
+        let files = read_plugin(source)?;
+        let id = files.manifest.id.clone();
+        let manifest_text = files.manifest_text.clone();
+        let component = files.component.clone();
+        let plugin = Arc::new(self.compile(files)?);
+
+        // Written next to the target, then swapped in
+        let target = self.dir.join(&id);
+        let staging = self.dir.join(format!(".{}.installing", id));
+        let copy = || -> std::io::Result<()> {
+            if staging.exists() {
+                std::fs::remove_dir_all(&staging)?;
+            }
+            std::fs::create_dir_all(&staging)?;
+            std::fs::write(staging.join(plugin_const::MANIFEST_FILE), &manifest_text)?;
+            std::fs::write(staging.join(plugin_const::COMPONENT_FILE), &component)?;
+            if target.exists() {
+                std::fs::remove_dir_all(&target)?;
+            }
+            std::fs::rename(&staging, &target)
+        };
+        copy().map_err(|e| {
+            let _ = std::fs::remove_dir_all(&staging);
+            format!("Cannot install plugin '{}': {}", id, e)
+        })?;
+
+        if let Ok(mut loaded) = self.loaded.lock() {
+            loaded.insert(id.clone(), plugin.clone());
+        }
+        info!(plugin = %id, version = %plugin.manifest.version, "Plugin installed");
+        Ok(plugin)
+    }
+
+    /// Removes an installed plugin.
+    pub fn uninstall(&self, id: &str) -> Result<(), String> {
+        if !self.is_installed(id) {
+            return Err(format!("Plugin '{}' is not installed", id));
+        }
+        std::fs::remove_dir_all(self.dir.join(id))
+            .map_err(|e| format!("Cannot remove plugin '{}': {}", id, e))?;
+        if let Ok(mut loaded) = self.loaded.lock() {
+            loaded.remove(id);
+        }
+        info!(plugin = %id, "Plugin uninstalled");
+        Ok(())
+    }
+
+    /// Describes an installed plugin with its grants in `settings`.
+    ///
+    /// Does not compile the component: errors found at load time are
+    /// reported when an agent uses the plugin.
+    pub fn info(&self, id: &str, settings: &PluginSettings) -> PluginInfo {
+        let grant = settings.plugins.get(id);
+        let mut info = PluginInfo {
+            id: id.to_string(),
+            tool_name: tool_name(id),
+            name: id.to_string(),
+            version: String::new(),
+            description: String::new(),
+            requested_capabilities: Vec::new(),
+            network_hosts: Vec::new(),
+            read_dirs: Vec::new(),
+            granted_capabilities: grant
+                .map(|grant| grant.capabilities.clone())
+                .unwrap_or_default(),
+            enabled: false,
+            error: None,
+        };
+
+        match read_plugin(&self.dir.join(id)) {
+            Ok(PluginFiles {
+                manifest, sha256, ..
+            }) => {
+                info.requested_capabilities = manifest.requested_capabilities();
+                info.network_hosts = manifest.capabilities.network.clone();
+                info.read_dirs = manifest.capabilities.read_files.clone();
+                info.name = manifest.name;
+                info.version = manifest.version;
+                info.description = manifest.description;
+                if manifest.id != id {
+                    info.error = Some(format!("Directory holds plugin '{}'", manifest.id));
+                } else if let Some(grant) = grant {
+                    if grant.sha256 == sha256 {
+                        info.enabled = grant.enabled;
+                    } else {
+                        info.error = Some("Plugin modified since it was approved".to_string());
+                    }
+                }
+            }
+            Err(e) => info.error = Some(e),
+        }
+        info
+    }
+
+    /// Lists the installed plugins, sorted by ID. Blocking (hashing).
+    pub fn list(&self, settings: &PluginSettings) -> Vec<PluginInfo> {
+        let mut ids: Vec<String> = std::fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                    .filter(|id| manifest::is_valid_id(id))
+                    .collect()
+            })
+            .unwrap_or_default();
+        ids.sort();
+        ids.iter().map(|id| self.info(id, settings)).collect()
+    }
+
+    /// Creates the tool of an enabled plugin for the active profile.
+    ///
+    /// # Arguments
+    /// * `db` - Database of the active profile (capability grants)
+    /// * `id` - Plugin ID
+    pub async fn create_tool(&self, db: &DBClient, id: &str) -> Result<PluginTool, String> {
+        let settings = load_plugin_settings(db).await;
+        let grant = settings
+            .plugins
+            .get(id)
+            .filter(|grant| grant.enabled)
+            .cloned()
+            .ok_or_else(|| {
+                format!(
+                    "Plugin '{}' is not enabled. The user can enable it in Settings > Validation > Plugins",
+                    id
+                )
+            })?;
+
+        let host = self.clone();
+        let plugin_id = id.to_string();
+        let plugin = tokio::task::spawn_blocking(move || host.load(&plugin_id))
+            .await
+            .map_err(|e| format!("Plugin loading failed: {}", e))??;
+        if plugin.sha256 != grant.sha256 {
+            return Err(format!(
+                "Plugin '{}' was modified since it was approved. The user can approve it again in Settings > Validation > Plugins",
+                id
+            ));
+        }
+
+        // Grants beyond the manifest are ignored
+        let requested = plugin.manifest.requested_capabilities();
+        let capabilities: Vec<PluginCapability> = grant
+            .capabilities
+            .into_iter()
+            .filter(|capability| requested.contains(capability))
+            .collect();
+        Ok(PluginTool::new(self.engine.clone(), plugin, capabilities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::plugin::PluginGrant;
+    use crate::tools::{Tool, ToolError};
+    use serde_json::json;
+
+    /// Component echoing its input; rejects inputs of 2 bytes or less
+    /// ("{}"). Written by hand in the WebAssembly text format.
+    const ECHO_COMPONENT: &str = r#"
+(component
+  (core module $m
+    (memory (export "memory") 1)
+    (global $heap (mut i32) (i32.const 4096))
+    (func (export "realloc") (param i32 i32 i32 i32) (result i32)
+      (local $p i32)
+      global.get $heap
+      local.set $p
+      global.get $heap
+      local.get 3
+      i32.add
+      global.set $heap
+      local.get $p)
+    (data (i32.const 16) "echo")
+    (data (i32.const 32) "Returns its input")
+    (data (i32.const 64) "{\"type\":\"object\"}")
+    (data (i32.const 96) "input must not be empty")
+    (data (i32.const 256) "\10\00\00\00\04\00\00\00\20\00\00\00\11\00\00\00\40\00\00\00\11\00\00\00\40\00\00\00\11\00\00\00\00")
+    (func (export "definition") (result i32)
+      i32.const 256)
+    (func (export "validate") (param i32 i32) (result i32)
+      local.get 1
+      i32.const 2
+      i32.le_u
+      if
+        i32.const 512
+        i32.const 1
+        i32.store8
+        i32.const 516
+        i32.const 96
+        i32.store
+        i32.const 520
+        i32.const 23
+        i32.store
+      else
+        i32.const 512
+        i32.const 0
+        i32.store8
+      end
+      i32.const 512)
+    (func (export "execute") (param i32 i32) (result i32)
+      i32.const 544
+      i32.const 0
+      i32.store8
+      i32.const 548
+      local.get 0
+      i32.store
+      i32.const 552
+      local.get 1
+      i32.store
+      i32.const 544))
+  (core instance $i (instantiate $m))
+  (type $def (record
+    (field "name" string)
+    (field "description" string)
+    (field "input-schema" string)
+    (field "output-schema" string)
+    (field "requires-confirmation" bool)))
+  (component $c
+    (import "def" (type $d (eq $def)))
+    (import "definition" (func $f1 (result $d)))
+    (import "validate" (func $f2 (param "input" string) (result (result (error string)))))
+    (import "execute" (func $f3 (param "input" string) (result (result string (error string)))))
+    (export $de "tool-definition" (type $d))
+    (export "definition" (func $f1) (func (result $de)))
+    (export "validate" (func $f2))
+    (export "execute" (func $f3)))
+  (func $definition (result $def)
+    (canon lift (core func $i "definition") (memory $i "memory") (realloc (func $i "realloc"))))
+  (func $validate (param "input" string) (result (result (error string)))
+    (canon lift (core func $i "validate") (memory $i "memory") (realloc (func $i "realloc"))))
+  (func $execute (param "input" string) (result (result string (error string)))
+    (canon lift (core func $i "execute") (memory $i "memory") (realloc (func $i "realloc"))))
+  (instance $tool (instantiate $c
+    (with "def" (type $def))
+    (with "definition" (func $definition))
+    (with "validate" (func $validate))
+    (with "execute" (func $execute))))
+  (export "zileo:plugin/tool@0.1.0" (instance $tool)))
+"#;
+
+    /// Writes an installable echo plugin in `dir`.
+    fn write_echo_plugin(dir: &Path) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join(plugin_const::MANIFEST_FILE),
+            "id = \"echo\"\nname = \"Echo\"\nversion = \"1.0.0\"\n\n[capabilities]\nread_files = [\"~/notes\"]\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(plugin_const::COMPONENT_FILE), ECHO_COMPONENT).unwrap();
+    }
+
+    #[test]
+    fn test_plugin_tool_names() {
+        assert_eq!(tool_name("echo"), "plugin__echo");
+        assert_eq!(plugin_id("plugin__echo"), Some("echo"));
+        assert_eq!(plugin_id("plugin__"), None);
+        assert_eq!(plugin_id("plugin__Echo"), None);
+        assert_eq!(plugin_id("MemoryTool"), None);
+    }
+
+    #[test]
+    fn test_install_list_uninstall() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_echo_plugin(&source);
+        let host = PluginHost::new(temp.path().join("plugins"));
+
+        let plugin = host.install(&source).unwrap();
+        assert_eq!(plugin.definition.id, "plugin__echo");
+        assert_eq!(plugin.definition.name, "echo");
+        assert_eq!(plugin.definition.description, "Returns its input");
+        assert!(!plugin.definition.requires_confirmation);
+        assert!(host.is_installed("echo"));
+
+        // Installed but not granted in this profile
+        let plugins = host.list(&PluginSettings::default());
+        assert_eq!(plugins.len(), 1);
+        assert_eq!(plugins[0].tool_name, "plugin__echo");
+        assert_eq!(
+            plugins[0].requested_capabilities,
+            vec![PluginCapability::ReadFiles]
+        );
+        assert!(!plugins[0].enabled);
+
+        // Granted for this component, then the component changes
+        let mut settings = PluginSettings::default();
+        settings.plugins.insert(
+            "echo".to_string(),
+            PluginGrant {
+                enabled: true,
+                capabilities: vec![PluginCapability::ReadFiles],
+                sha256: plugin.sha256.clone(),
+            },
+        );
+        assert!(host.info("echo", &settings).enabled);
+        std::fs::write(
+            temp.path()
+                .join("plugins/echo")
+                .join(plugin_const::COMPONENT_FILE),
+            format!("{} ", ECHO_COMPONENT),
+        )
+        .unwrap();
+        let info = host.info("echo", &settings);
+        assert!(!info.enabled);
+        assert!(info.error.is_some());
+
+        host.uninstall("echo").unwrap();
+        assert!(host.list(&settings).is_empty());
+        assert!(host.uninstall("echo").is_err());
+    }
+
+    #[test]
+    fn test_install_rejects_invalid_component() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_echo_plugin(&source);
+        std::fs::write(source.join(plugin_const::COMPONENT_FILE), b"\0asm").unwrap();
+        let host = PluginHost::new(temp.path().join("plugins"));
+
+        assert!(host.install(&source).is_err());
+        assert!(!host.is_installed("echo"));
+    }
+
+    #[tokio::test]
+    async fn test_plugin_tool_execute() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        write_echo_plugin(&source);
+        let host = PluginHost::new(temp.path().join("plugins"));
+        let plugin = host.install(&source).unwrap();
+        let tool = PluginTool::new(host.engine.clone(), plugin, Vec::new());
+
+        let output = tool.execute(json!({ "text": "hello" })).await.unwrap();
+        assert_eq!(output, json!({ "text": "hello" }));
+
+        // Rejected by the plugin's own validation
+        let error = tool.execute(json!({})).await.unwrap_err();
+        assert!(matches!(error, ToolError::ValidationFailed(_)));
+        // Rejected by the host before the plugin runs
+        assert!(tool.execute(json!("hello")).await.is_err());
+    }
+}
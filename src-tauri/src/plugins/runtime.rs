@@ -0,0 +1,519 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Wasmtime sandbox of the plugins.
+//!
+//! Every call gets a fresh store and instance, with the fuel and memory
+//! limits of [`plugin_const`]. Host functions check the capabilities
+//! granted to the plugin before touching the network or the disk.
+
+use super::manifest::PluginManifest;
+use super::{plugin_id, LoadedPlugin, PLUGIN_HOST};
+use crate::models::plugin::PluginCapability;
+use crate::tools::constants::plugins as plugin_const;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::utils::{data_dir, resolve_path};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use bindings::exports::zileo::plugin::tool::ToolDefinition as PluginDefinition;
+use bindings::zileo::plugin::host::{Host, HttpResponse, LogLevel};
+use bindings::ToolPlugin;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+use tracing::{debug, error, info, warn};
+use wasmtime::component::{Component, Linker};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder};
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "wit/tool-plugin.wit",
+        world: "tool-plugin",
+    });
+}
+
+/// Creates the engine shared by all plugins (component model, fuel metering).
+pub fn new_engine() -> Engine {
+    let mut config = Config::new();
+    config.wasm_component_model(true);
+    config.consume_fuel(true);
+    Engine::new(&config).expect("Wasmtime engine configuration is valid")
+}
+
+/// Compiles a plugin component.
+pub fn compile(engine: &Engine, bytes: &[u8]) -> Result<Component, String> {
+    Component::new(engine, bytes).map_err(|e| format!("Invalid plugin component: {}", e))
+}
+
+/// State of a plugin instance: what its host functions may do.
+struct HostState {
+    /// Capabilities granted by the user
+    capabilities: Vec<PluginCapability>,
+    /// Manifest of the plugin (ID, network hosts, read directories)
+    manifest: PluginManifest,
+    /// Runtime the HTTP requests are sent from (None while describing)
+    runtime: Option<Handle>,
+    /// Memory limits of the instance
+    limits: StoreLimits,
+}
+
+impl HostState {
+    fn new(
+        manifest: &PluginManifest,
+        capabilities: Vec<PluginCapability>,
+        runtime: Option<Handle>,
+    ) -> Self {
+        Self {
+            capabilities,
+            manifest: manifest.clone(),
+            runtime,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(plugin_const::MAX_MEMORY_BYTES)
+                .build(),
+        }
+    }
+
+    /// Fails unless the user granted `capability` to the plugin.
+    fn require(&self, capability: PluginCapability) -> Result<(), String> {
+        if self.capabilities.contains(&capability) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Capability '{}' was not granted to plugin '{}'",
+                capability.as_str(),
+                self.manifest.id
+            ))
+        }
+    }
+}
+
+impl Host for HostState {
+    fn log(&mut self, level: LogLevel, message: String) {
+        let plugin = self.manifest.id.as_str();
+        match level {
+            LogLevel::Debug => debug!(plugin = %plugin, "{}", message),
+            LogLevel::Info => info!(plugin = %plugin, "{}", message),
+            LogLevel::Warn => warn!(plugin = %plugin, "{}", message),
+            LogLevel::Error => error!(plugin = %plugin, "{}", message),
+        }
+    }
+
+    fn http_request(
+        &mut self,
+        method: String,
+        url: String,
+        body: Option<String>,
+    ) -> Result<HttpResponse, String> {
+        self.require(PluginCapability::Network)?;
+
+        let url = reqwest::Url::parse(&url).map_err(|e| format!("Invalid URL: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("Only http(s) URLs are allowed".to_string());
+        }
+        let host = url.host_str().unwrap_or_default();
+        if !self.manifest.allows_host(host) {
+            return Err(format!(
+                "Host '{}' is not listed in the plugin manifest",
+                host
+            ));
+        }
+        let method = reqwest::Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+            .map_err(|_| format!("Invalid HTTP method '{}'", method))?;
+        let runtime = self
+            .runtime
+            .clone()
+            .ok_or_else(|| "HTTP requests are not available here".to_string())?;
+
+        debug!(plugin = %self.manifest.id, method = %method, url = %url, "Plugin HTTP request");
+        runtime.block_on(async move {
+            // Redirects could lead outside the listed hosts
+            let client = crate::proxy::client_builder("plugins")
+                .timeout(Duration::from_secs(plugin_const::HTTP_TIMEOUT_SECS))
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+            let mut request = client.request(method, url);
+            if let Some(body) = body {
+                request = request.body(body);
+            }
+            let mut response = request
+                .send()
+                .await
+                .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+            let status = response.status().as_u16();
+            let mut bytes = Vec::new();
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| format!("Failed to read the response: {}", e))?
+            {
+                if bytes.len() + chunk.len() > plugin_const::MAX_HOST_READ_BYTES {
+                    return Err(format!(
+                        "Response exceeds {} bytes",
+                        plugin_const::MAX_HOST_READ_BYTES
+                    ));
+                }
+                bytes.extend_from_slice(&chunk);
+            }
+            Ok(HttpResponse {
+                status,
+                body: String::from_utf8_lossy(&bytes).into_owned(),
+            })
+        })
+    }
+
+    fn read_file(&mut self, path: String) -> Result<String, String> {
+        self.require(PluginCapability::ReadFiles)?;
+
+        let path = resolve_path(&path).map_err(|e| e.to_string())?;
+        // Symbolic links and `..` are resolved before the directories are checked
+        let path = path
+            .canonicalize()
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+        if data_dir()
+            .canonicalize()
+            .is_ok_and(|dir| path.starts_with(dir))
+        {
+            return Err(format!(
+                "'{}' is in the application data directory",
+                path.display()
+            ));
+        }
+        if !self.manifest.allows_path(&path) {
+            return Err(format!(
+                "'{}' is not in a directory listed in the plugin manifest",
+                path.display()
+            ));
+        }
+        let metadata = std::fs::metadata(&path)
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))?;
+        if !metadata.is_file() {
+            return Err(format!("'{}' is not a file", path.display()));
+        }
+        if metadata.len() > plugin_const::MAX_HOST_READ_BYTES as u64 {
+            return Err(format!(
+                "'{}' exceeds {} bytes",
+                path.display(),
+                plugin_const::MAX_HOST_READ_BYTES
+            ));
+        }
+        debug!(plugin = %self.manifest.id, path = %path.display(), "Plugin file read");
+        std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read '{}': {}", path.display(), e))
+    }
+}
+
+/// Instantiates a component in a new store with the call limits.
+fn instantiate(
+    engine: &Engine,
+    component: &Component,
+    state: HostState,
+) -> Result<(Store<HostState>, ToolPlugin), String> {
+    let mut store = Store::new(engine, state);
+    store.limiter(|state| &mut state.limits);
+    store
+        .set_fuel(plugin_const::FUEL_PER_CALL)
+        .map_err(|e| format!("Cannot set the plugin fuel: {}", e))?;
+
+    let mut linker = Linker::new(engine);
+    ToolPlugin::add_to_linker(&mut linker, |state: &mut HostState| state)
+        .map_err(|e| format!("Cannot link the plugin: {}", e))?;
+    let plugin = ToolPlugin::instantiate(&mut store, component, &linker)
+        .map_err(|e| format!("Cannot instantiate the plugin: {}", e))?;
+    Ok((store, plugin))
+}
+
+/// Parses a JSON Schema returned by a plugin.
+fn parse_schema(schema: &str, field: &str) -> Result<Value, String> {
+    if schema.trim().is_empty() {
+        return Ok(json!({ "type": "object" }));
+    }
+    let value: Value = serde_json::from_str(schema)
+        .map_err(|e| format!("Plugin {} is not valid JSON: {}", field, e))?;
+    if !value.is_object() {
+        return Err(format!("Plugin {} must be a JSON object", field));
+    }
+    Ok(value)
+}
+
+/// Calls the `definition` export and converts it to a tool definition
+/// named `plugin__<id>`.
+pub fn describe(
+    engine: &Engine,
+    component: &Component,
+    manifest: &PluginManifest,
+) -> Result<ToolDefinition, String> {
+    let (mut store, plugin) = instantiate(
+        engine,
+        component,
+        HostState::new(manifest, Vec::new(), None),
+    )?;
+    let definition: PluginDefinition = plugin
+        .zileo_plugin_tool()
+        .call_definition(&mut store)
+        .map_err(|e| format!("Plugin definition failed: {}", e))?;
+
+    if definition.description.trim().is_empty() {
+        return Err("Plugin definition has no description".to_string());
+    }
+    let name = match definition.name.trim() {
+        "" => manifest.name.clone(),
+        name => name.to_string(),
+    };
+    Ok(ToolDefinition {
+        id: super::tool_name(&manifest.id),
+        name,
+        description: definition.description,
+        input_schema: parse_schema(&definition.input_schema, "input schema")?,
+        output_schema: parse_schema(&definition.output_schema, "output schema")?,
+        requires_confirmation: definition.requires_confirmation,
+    })
+}
+
+/// Validates then executes a JSON input in a fresh instance.
+fn run(
+    engine: &Engine,
+    plugin: &LoadedPlugin,
+    state: HostState,
+    input: &str,
+) -> ToolResult<String> {
+    let failed = |e: wasmtime::Error| {
+        ToolError::ExecutionFailed(format!("Plugin '{}' failed: {}", plugin.manifest.id, e))
+    };
+    let (mut store, instance) =
+        instantiate(engine, &plugin.component, state).map_err(ToolError::ExecutionFailed)?;
+    let tool = instance.zileo_plugin_tool();
+
+    tool.call_validate(&mut store, input)
+        .map_err(failed)?
+        .map_err(ToolError::ValidationFailed)?;
+    tool.call_execute(&mut store, input)
+        .map_err(failed)?
+        .map_err(ToolError::ExecutionFailed)
+}
+
+/// Tool backed by a plugin component.
+pub struct PluginTool {
+    /// Engine the component was compiled with
+    engine: Engine,
+    /// Compiled plugin
+    plugin: Arc<LoadedPlugin>,
+    /// Capabilities granted in the active profile
+    capabilities: Vec<PluginCapability>,
+}
+
+impl PluginTool {
+    /// Creates a tool for a loaded plugin.
+    ///
+    /// # Arguments
+    /// * `engine` - Engine the component was compiled with
+    /// * `plugin` - Compiled plugin
+    /// * `capabilities` - Capabilities granted by the user
+    pub fn new(
+        engine: Engine,
+        plugin: Arc<LoadedPlugin>,
+        capabilities: Vec<PluginCapability>,
+    ) -> Self {
+        Self {
+            engine,
+            plugin,
+            capabilities,
+        }
+    }
+
+    /// Creates the tool of a plugin enabled in the active profile.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let id = plugin_id(ctx.tool_name)
+                .ok_or_else(|| format!("Invalid plugin tool name '{}'", ctx.tool_name))?;
+            let tool = PLUGIN_HOST.create_tool(&ctx.factory.get_db(), id).await?;
+            info!(plugin = %id, "Plugin tool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Bounded by the sandbox fuel, the call timeout also covers network
+    // requests
+    ToolRegistration::Family {
+        matches: |name| plugin_id(name).is_some(),
+        metadata: ToolMetadata {
+            name: "PluginTool",
+            category: ToolCategory::Basic,
+            requires_context: false,
+            default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+            constructor: PluginTool::build,
+        },
+    }
+}
+
+#[async_trait]
+impl Tool for PluginTool {
+    fn definition(&self) -> ToolDefinition {
+        self.plugin.definition.clone()
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+
+        let state = HostState::new(
+            &self.plugin.manifest,
+            self.capabilities.clone(),
+            Some(Handle::current()),
+        );
+        let engine = self.engine.clone();
+        let plugin = self.plugin.clone();
+        let input = input.to_string();
+        let output = tokio::task::spawn_blocking(move || run(&engine, &plugin, state, &input))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Plugin task failed: {}", e)))??;
+
+        serde_json::from_str(&output).map_err(|e| {
+            ToolError::ExecutionFailed(format!(
+                "Plugin '{}' returned invalid JSON: {}",
+                self.plugin.manifest.id, e
+            ))
+        })
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be a JSON object".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        self.plugin.definition.requires_confirmation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(network: &[&str], read_files: &[&str]) -> PluginManifest {
+        PluginManifest {
+            id: "echo".to_string(),
+            name: "Echo".to_string(),
+            version: "1.0.0".to_string(),
+            description: String::new(),
+            capabilities: super::super::manifest::RequestedCapabilities {
+                network: network.iter().map(|host| host.to_string()).collect(),
+                read_files: read_files.iter().map(|dir| dir.to_string()).collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_host_functions_require_capabilities() {
+        let mut state = HostState::new(&manifest(&["example.com"], &[]), Vec::new(), None);
+        let error = state
+            .http_request("GET".to_string(), "https://example.com".to_string(), None)
+            .unwrap_err();
+        assert!(error.contains("'network' was not granted"));
+        let error = state.read_file("/etc/hostname".to_string()).unwrap_err();
+        assert!(error.contains("'read_files' was not granted"));
+    }
+
+    #[test]
+    fn test_http_request_checks_manifest_hosts() {
+        let mut state = HostState::new(
+            &manifest(&["example.com"], &[]),
+            vec![PluginCapability::Network],
+            None,
+        );
+        let error = state
+            .http_request("GET".to_string(), "https://other.org/a".to_string(), None)
+            .unwrap_err();
+        assert!(error.contains("not listed"));
+        let error = state
+            .http_request("GET".to_string(), "file:///etc/passwd".to_string(), None)
+            .unwrap_err();
+        assert!(error.contains("http(s)"));
+    }
+
+    #[test]
+    fn test_read_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes");
+        std::fs::create_dir(&notes).unwrap();
+        let path = notes.join("notes.txt");
+        std::fs::write(&path, "hello").unwrap();
+
+        let mut state = HostState::new(
+            &manifest(&[], &[&notes.to_string_lossy()]),
+            vec![PluginCapability::ReadFiles],
+            None,
+        );
+        assert_eq!(
+            state
+                .read_file(path.to_string_lossy().into_owned())
+                .unwrap(),
+            "hello"
+        );
+        assert!(state.read_file("notes.txt".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_read_file_checks_manifest_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes");
+        std::fs::create_dir(&notes).unwrap();
+        let secret = dir.path().join("secret.txt");
+        std::fs::write(&secret, "token").unwrap();
+
+        let mut state = HostState::new(
+            &manifest(&[], &[&notes.to_string_lossy()]),
+            vec![PluginCapability::ReadFiles],
+            None,
+        );
+        let error = state
+            .read_file(secret.to_string_lossy().into_owned())
+            .unwrap_err();
+        assert!(error.contains("not in a directory listed"));
+        // `..` cannot leave a listed directory
+        let error = state
+            .read_file(notes.join("../secret.txt").to_string_lossy().into_owned())
+            .unwrap_err();
+        assert!(error.contains("not in a directory listed"));
+        #[cfg(unix)]
+        {
+            let link = notes.join("link.txt");
+            std::os::unix::fs::symlink(&secret, &link).unwrap();
+            let error = state
+                .read_file(link.to_string_lossy().into_owned())
+                .unwrap_err();
+            assert!(error.contains("not in a directory listed"));
+        }
+    }
+
+    #[test]
+    fn test_parse_schema() {
+        assert_eq!(
+            parse_schema(" ", "output schema").unwrap(),
+            json!({ "type": "object" })
+        );
+        assert!(parse_schema("[1]", "input schema").is_err());
+        assert!(parse_schema("{", "input schema").is_err());
+    }
+}
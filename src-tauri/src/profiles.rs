@@ -0,0 +1,229 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Profiles: isolated data sets (e.g. work and personal).
+//!
+//! Each profile has its own database, persistence outbox and attachments.
+//! The default profile keeps the layout of the data directory from before
+//! profiles existed (`<data dir>/db`); the other profiles live in
+//! `<data dir>/profiles/<id>/`. The registry of the profiles and the active
+//! one is `<data dir>/profiles.json` (absent until a profile is created).
+//!
+//! API keys stay in the OS keychain, shared by all profiles.
+
+use crate::models::profile::{Profile, ProfileRegistry};
+use crate::tools::constants::profiles::{
+    DB_DIR_NAME, DEFAULT_PROFILE_ID, DIR_NAME, MAX_NAME_LEN, REGISTRY_FILE,
+};
+use anyhow::{bail, Context};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::info;
+
+/// Profile registry and directories, rooted at the data directory
+pub struct ProfileStore {
+    /// Data directory
+    root: PathBuf,
+    /// Database directory of the default profile
+    default_db: PathBuf,
+    /// Serializes registry updates
+    lock: Mutex<()>,
+}
+
+impl ProfileStore {
+    /// Creates a store rooted at `root`, the default profile using `default_db`.
+    pub fn new(root: impl Into<PathBuf>, default_db: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            default_db: default_db.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Loads the registry (only the default profile if none was created).
+    pub fn load(&self) -> anyhow::Result<ProfileRegistry> {
+        let path = self.root.join(REGISTRY_FILE);
+        if !path.exists() {
+            return Ok(ProfileRegistry::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_str(&content).context("Invalid profile registry")
+    }
+
+    /// Writes the registry (through a temporary file, never left half-written).
+    fn save(&self, registry: &ProfileRegistry) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.root)?;
+        let path = self.root.join(REGISTRY_FILE);
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_string_pretty(registry)?)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Database directory of a profile.
+    pub fn db_path(&self, profile_id: &str) -> PathBuf {
+        if profile_id == DEFAULT_PROFILE_ID {
+            self.default_db.clone()
+        } else {
+            self.root.join(DIR_NAME).join(profile_id).join(DB_DIR_NAME)
+        }
+    }
+
+    /// Creates a profile (its database is created when it is first opened).
+    pub fn create(&self, name: &str) -> anyhow::Result<Profile> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("Profile name cannot be empty");
+        }
+        if name.chars().count() > MAX_NAME_LEN {
+            bail!(
+                "Profile name too long (maximum {} characters)",
+                MAX_NAME_LEN
+            );
+        }
+
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut registry = self.load()?;
+        if registry
+            .profiles
+            .iter()
+            .any(|profile| profile.name.eq_ignore_ascii_case(name))
+        {
+            bail!("A profile named '{}' already exists", name);
+        }
+
+        let profile = Profile {
+            id: unique_profile_id(name, &registry),
+            name: name.to_string(),
+            created_at: Utc::now(),
+        };
+        if let Some(dir) = self.db_path(&profile.id).parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        registry.profiles.push(profile.clone());
+        self.save(&registry)?;
+
+        info!(profile_id = %profile.id, "Profile created");
+        Ok(profile)
+    }
+
+    /// Marks a profile as active (opened at next startup).
+    pub fn set_active(&self, profile_id: &str) -> anyhow::Result<ProfileRegistry> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut registry = self.load()?;
+        if registry.get(profile_id).is_none() {
+            bail!("Profile not found: {}", profile_id);
+        }
+        registry.active = profile_id.to_string();
+        self.save(&registry)?;
+        Ok(registry)
+    }
+}
+
+/// Derives an unused profile ID from a name ("Work notes" -> "work-notes").
+fn unique_profile_id(name: &str, registry: &ProfileRegistry) -> String {
+    let slug = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base = if slug.is_empty() {
+        "profile".to_string()
+    } else {
+        slug
+    };
+
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while registry.get(&id).is_some() {
+        id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+/// Persistence outbox file of a database directory.
+pub fn outbox_path(db_path: &Path) -> PathBuf {
+    db_path.with_file_name(crate::tools::constants::outbox::FILE_NAME)
+}
+
+/// Attachments directory of a database directory.
+pub fn attachments_dir(db_path: &Path) -> PathBuf {
+    db_path.with_file_name(crate::tools::constants::attachments::DIR_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_and_activate_profiles() {
+        let dir = tempdir().unwrap();
+        let store = ProfileStore::new(dir.path(), dir.path().join("db"));
+
+        let registry = store.load().unwrap();
+        assert_eq!(registry.active, DEFAULT_PROFILE_ID);
+        assert_eq!(store.db_path(DEFAULT_PROFILE_ID), dir.path().join("db"));
+
+        let work = store.create("  Work notes ").unwrap();
+        assert_eq!(work.id, "work-notes");
+        assert_eq!(work.name, "Work notes");
+        assert_eq!(
+            store.db_path(&work.id),
+            dir.path().join("profiles").join("work-notes").join("db")
+        );
+        assert!(dir.path().join("profiles").join("work-notes").is_dir());
+
+        // Names are unique, IDs derived from similar names too
+        assert!(store.create("work NOTES").is_err());
+        assert_eq!(store.create("Work/notes").unwrap().id, "work-notes-2");
+        assert_eq!(store.create("Défaut").unwrap().id, "d-faut");
+        assert_eq!(store.create("Default 2").unwrap().id, "default-2");
+        assert!(store.create(" ").is_err());
+
+        let registry = store.set_active(&work.id).unwrap();
+        assert_eq!(registry.active, "work-notes");
+        assert!(store.set_active("unknown").is_err());
+
+        let loaded = store.load().unwrap();
+        assert_eq!(loaded, registry);
+        assert_eq!(loaded.profiles.len(), 5);
+        assert_eq!(loaded.profiles[0].id, DEFAULT_PROFILE_ID);
+    }
+
+    #[test]
+    fn test_storage_paths() {
+        let db_path = Path::new("/data/profiles/work/db");
+        assert_eq!(
+            outbox_path(db_path),
+            Path::new("/data/profiles/work").join(crate::tools::constants::outbox::FILE_NAME)
+        );
+        assert_eq!(
+            attachments_dir(db_path),
+            Path::new("/data/profiles/work").join(crate::tools::constants::attachments::DIR_NAME)
+        );
+    }
+}
@@ -0,0 +1,251 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Proxy of the outgoing requests.
+//!
+//! The HTTP clients of the LLM providers, the embedding service and the MCP
+//! HTTP transports are built with [`client_builder`], which resolves the proxy
+//! of each request from the settings in effect, so that settings changes apply
+//! without rebuilding the clients. For a target (provider name, "embedding" or
+//! "mcp"), the proxy is:
+//!
+//! 1. none for loopback hosts (e.g. a local Ollama server)
+//! 2. the override of the target, if any
+//! 3. the global proxy, unless the host matches `no_proxy`
+//!
+//! The settings are the `[proxy]` table of the config file if present, else
+//! the saved settings; a proxy environment variable (`HTTPS_PROXY`...) set at
+//! startup replaces their global proxy.
+
+use crate::config_file::LoadedConfig;
+use crate::models::proxy::{ProxyOverride, ProxySettings};
+use crate::tools::constants::proxy::{LOOPBACK_HOSTS, SUPPORTED_SCHEMES};
+use once_cell::sync::Lazy;
+use reqwest::{ClientBuilder, Proxy, Url};
+use std::net::IpAddr;
+use std::sync::RwLock;
+
+/// Proxy settings layers
+#[derive(Debug, Default)]
+struct ProxyLayers {
+    /// Proxy environment variables at startup
+    env: Option<ProxySettings>,
+    /// `[proxy]` table of the config file
+    file: Option<ProxySettings>,
+    /// Settings saved in the database of the active profile
+    saved: ProxySettings,
+}
+
+impl ProxyLayers {
+    /// Proxy URL of a request, if any.
+    fn resolve(&self, target: &str, url: &Url) -> Option<Url> {
+        let host = url.host_str()?;
+        if is_loopback(host) {
+            return None;
+        }
+        let settings = self.file.as_ref().unwrap_or(&self.saved);
+        match settings.overrides.get(target) {
+            Some(ProxyOverride::Direct) => return None,
+            Some(ProxyOverride::Custom { url }) => return Url::parse(url).ok(),
+            None => {}
+        }
+        let global = match &self.env {
+            Some(env) => env,
+            None => settings,
+        };
+        let proxy = global.url.as_deref()?;
+        if matches_no_proxy(host, &global.no_proxy) {
+            return None;
+        }
+        Url::parse(proxy).ok()
+    }
+}
+
+static LAYERS: Lazy<RwLock<ProxyLayers>> = Lazy::new(|| RwLock::new(ProxyLayers::default()));
+
+/// Sets the proxy layers of the config file and of the environment.
+pub fn init(config: &LoadedConfig) {
+    if let Ok(mut layers) = LAYERS.write() {
+        layers.env = config.env_proxy();
+        layers.file = config.proxy().cloned();
+    }
+}
+
+/// Replaces the saved proxy settings (startup, profile switch, update).
+pub fn set_saved(settings: ProxySettings) {
+    if let Ok(mut layers) = LAYERS.write() {
+        layers.saved = settings;
+    }
+}
+
+/// Whether the config file replaces the saved proxy settings.
+pub fn overridden_by_config_file() -> bool {
+    LAYERS
+        .read()
+        .map(|layers| layers.file.is_some())
+        .unwrap_or(false)
+}
+
+/// HTTP client builder whose requests use the proxy of `target`.
+///
+/// # Arguments
+/// * `target` - Provider name ("mistral", "ollama", "gemini", custom name), "embedding", "mcp"
+///   or "plugins"
+pub fn client_builder(target: &str) -> ClientBuilder {
+    let target = target.to_string();
+    reqwest::Client::builder().proxy(Proxy::custom(move |url| {
+        LAYERS
+            .read()
+            .ok()
+            .and_then(|layers| layers.resolve(&target, url))
+    }))
+}
+
+/// Validates the proxy URLs of the settings.
+pub fn validate_settings(settings: &ProxySettings) -> Result<(), String> {
+    if let Some(url) = &settings.url {
+        validate_url(url)?;
+    }
+    for (target, proxy) in &settings.overrides {
+        if target.trim().is_empty() {
+            return Err("Proxy override target cannot be empty".to_string());
+        }
+        if let ProxyOverride::Custom { url } = proxy {
+            validate_url(url).map_err(|e| format!("{} ({})", e, target))?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_url(url: &str) -> Result<(), String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid proxy URL '{}': {}", url, e))?;
+    if !SUPPORTED_SCHEMES.contains(&parsed.scheme()) {
+        return Err(format!(
+            "Unsupported proxy scheme '{}' (expected one of {})",
+            parsed.scheme(),
+            SUPPORTED_SCHEMES.join(", ")
+        ));
+    }
+    if parsed.host_str().is_none() {
+        return Err(format!("Proxy URL '{}' has no host", url));
+    }
+    Ok(())
+}
+
+fn is_loopback(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    LOOPBACK_HOSTS.contains(&host)
+        || host
+            .parse::<IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false)
+}
+
+/// Whether a host matches a `no_proxy` list ("example.com" and ".example.com"
+/// match the domain and its subdomains, "*" matches all hosts).
+fn matches_no_proxy(host: &str, no_proxy: &[String]) -> bool {
+    let host = host.to_ascii_lowercase();
+    no_proxy.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        let domain = entry.trim_start_matches('.');
+        entry == "*"
+            || (!domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain))))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_proxy_layers() {
+        let mut layers = ProxyLayers {
+            saved: ProxySettings {
+                url: Some("socks5h://proxy.local:1080".to_string()),
+                no_proxy: vec!["internal.corp".to_string()],
+                overrides: [
+                    ("gemini".to_string(), ProxyOverride::Direct),
+                    (
+                        "mcp".to_string(),
+                        ProxyOverride::Custom {
+                            url: "http://mcp-proxy:3128".to_string(),
+                        },
+                    ),
+                ]
+                .into(),
+            },
+            ..Default::default()
+        };
+        let resolve = |layers: &ProxyLayers, target, u| layers.resolve(target, &url(u));
+
+        assert_eq!(
+            resolve(&layers, "mistral", "https://api.mistral.ai/v1"),
+            Some(url("socks5h://proxy.local:1080"))
+        );
+        assert_eq!(resolve(&layers, "ollama", "http://localhost:11434"), None);
+        assert_eq!(resolve(&layers, "ollama", "http://127.0.0.1:11434"), None);
+        assert_eq!(resolve(&layers, "ollama", "http://[::1]:11434"), None);
+        assert_eq!(
+            resolve(&layers, "mistral", "https://llm.internal.corp"),
+            None
+        );
+        assert_eq!(resolve(&layers, "gemini", "https://googleapis.com"), None);
+        assert_eq!(
+            resolve(&layers, "mcp", "https://mcp.example.com"),
+            Some(url("http://mcp-proxy:3128"))
+        );
+
+        // The environment replaces the global proxy, the file the saved settings
+        layers.env = Some(ProxySettings {
+            url: Some("http://env-proxy:8080".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            resolve(&layers, "mistral", "https://llm.internal.corp"),
+            Some(url("http://env-proxy:8080"))
+        );
+        layers.file = Some(ProxySettings::default());
+        assert_eq!(
+            resolve(&layers, "mcp", "https://mcp.example.com"),
+            Some(url("http://env-proxy:8080"))
+        );
+        layers.env = None;
+        assert_eq!(resolve(&layers, "mcp", "https://mcp.example.com"), None);
+    }
+
+    #[test]
+    fn test_validate_settings() {
+        let mut settings = ProxySettings {
+            url: Some("socks5://127.0.0.1:1080".to_string()),
+            ..Default::default()
+        };
+        assert!(validate_settings(&settings).is_ok());
+
+        settings.url = Some("ftp://proxy.local".to_string());
+        assert!(validate_settings(&settings).is_err());
+
+        settings.url = None;
+        settings.overrides.insert(
+            "ollama".to_string(),
+            ProxyOverride::Custom {
+                url: "not a url".to_string(),
+            },
+        );
+        assert!(validate_settings(&settings).unwrap_err().contains("ollama"));
+    }
+}
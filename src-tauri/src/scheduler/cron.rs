@@ -0,0 +1,288 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cron expressions of schedules.
+//!
+//! Standard 5-field expressions (`minute hour day-of-month month day-of-week`)
+//! with `*`, lists, ranges, steps and month/day names (`jan`, `mon`...), plus
+//! the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shortcuts.
+//! As in cron, when both the day of month and the day of week are restricted,
+//! a day matching either runs.
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Timelike,
+};
+
+/// Month names (index 0 is January)
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Day names (index 0 is Sunday)
+const DAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Candidate times examined before giving up (a match exists within 4 years
+/// for any satisfiable expression, days and hours are skipped at once)
+const MAX_ITERATIONS: usize = 100_000;
+
+/// Parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+    /// Matching minutes (bits 0-59)
+    minutes: u64,
+    /// Matching hours (bits 0-23)
+    hours: u64,
+    /// Matching days of month (bits 1-31)
+    days: u64,
+    /// Matching months (bits 1-12)
+    months: u64,
+    /// Matching days of week (bits 0-6, Sunday is 0)
+    weekdays: u64,
+    /// Day of month field is `*`
+    any_day: bool,
+    /// Day of week field is `*`
+    any_weekday: bool,
+}
+
+impl CronExpression {
+    /// Parses a cron expression.
+    ///
+    /// # Errors
+    /// Returns a message naming the invalid field.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let expanded = match expression.to_lowercase().as_str() {
+            "@hourly" => "0 * * * *".to_string(),
+            "@daily" | "@midnight" => "0 0 * * *".to_string(),
+            "@weekly" => "0 0 * * 0".to_string(),
+            "@monthly" => "0 0 1 * *".to_string(),
+            "@yearly" | "@annually" => "0 0 1 1 *".to_string(),
+            _ => expression.to_string(),
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Cron expression must have 5 fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        }
+
+        let mut weekdays = parse_field(fields[4], 0, 7, DAY_NAMES, 0, "day of week")?;
+        // 7 is an alias of Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(fields[0], 0, 59, &[], 0, "minute")?,
+            hours: parse_field(fields[1], 0, 23, &[], 0, "hour")?,
+            days: parse_field(fields[2], 1, 31, &[], 0, "day of month")?,
+            months: parse_field(fields[3], 1, 12, MONTH_NAMES, 1, "month")?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    /// Returns the first matching time strictly after `after` (at a whole minute).
+    ///
+    /// Times skipped by a daylight saving change do not run; the first of two
+    /// ambiguous times runs.
+    ///
+    /// # Returns
+    /// The next run, or None if the expression never matches (e.g. February 30)
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let local = after.naive_local();
+        let mut time = local.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        for _ in 0..MAX_ITERATIONS {
+            if !bit(self.months, time.month()) {
+                time = start_of_next_month(time)?;
+                continue;
+            }
+            if !self.day_matches(time.date()) {
+                time = time.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !bit(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+            match timezone.from_local_datetime(&time) {
+                LocalResult::Single(next) | LocalResult::Ambiguous(next, _) => return Some(next),
+                LocalResult::None => time += Duration::minutes(1),
+            }
+        }
+        None
+    }
+
+    /// Whether a date matches the day of month and day of week fields.
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = bit(self.days, date.day());
+        let weekday = bit(self.weekdays, date.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+/// Whether a bit is set.
+fn bit(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+/// Midnight of the first day of the next month.
+fn start_of_next_month(time: NaiveDateTime) -> Option<NaiveDateTime> {
+    let (year, month) = if time.month() == 12 {
+        (time.year() + 1, 1)
+    } else {
+        (time.year(), time.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Parses one field into a bit set of the matching values.
+///
+/// `names[i]` stands for the value `i + name_offset`.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    name_offset: u32,
+    label: &str,
+) -> Result<u64, String> {
+    let invalid = || format!("Invalid {} field: '{}'", label, field);
+    let value = |text: &str| -> Result<u32, String> {
+        let lower = text.to_lowercase();
+        let parsed = match names.iter().position(|name| *name == lower) {
+            Some(index) => index as u32 + name_offset,
+            None => text.parse::<u32>().map_err(|_| invalid())?,
+        };
+        if (min..=max).contains(&parsed) {
+            Ok(parsed)
+        } else {
+            Err(format!(
+                "Invalid {} field: {} is out of range {}-{}",
+                label, parsed, min, max
+            ))
+        }
+    };
+
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| invalid())?;
+                if step == 0 {
+                    return Err(invalid());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            let start = value(range)?;
+            // "5/15" means from 5 to the end of the range
+            (start, if part.contains('/') { max } else { start })
+        };
+        if start > end {
+            return Err(invalid());
+        }
+
+        for v in (start..=end).step_by(step as usize) {
+            set |= 1 << v;
+        }
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn at(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        CronExpression::parse(expression)
+            .unwrap()
+            .next_after(&at(after))
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn test_next_after() {
+        // Every 15 minutes, strictly after
+        assert_eq!(
+            next("*/15 * * * *", "2026-03-10T08:15:00Z"),
+            "2026-03-10T08:30:00+00:00"
+        );
+        // Weekdays at 9:30 (Friday evening -> Monday)
+        assert_eq!(
+            next("30 9 * * mon-fri", "2026-03-13T18:00:00Z"),
+            "2026-03-16T09:30:00+00:00"
+        );
+        // Month rollover and year rollover
+        assert_eq!(
+            next("@monthly", "2026-12-15T10:00:00Z"),
+            "2027-01-01T00:00:00+00:00"
+        );
+        // Day of month OR day of week when both are restricted
+        assert_eq!(
+            next("0 12 1 * sun", "2026-03-02T00:00:00Z"),
+            "2026-03-08T12:00:00+00:00"
+        );
+        // Sunday as 7, leap day
+        assert_eq!(
+            next("0 0 * * 7", "2026-03-10T00:00:00Z"),
+            "2026-03-15T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 29 feb *", "2026-01-01T00:00:00Z"),
+            "2028-02-29T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(CronExpression::parse("* * * *").is_err());
+        assert!(CronExpression::parse("60 * * * *").is_err());
+        assert!(CronExpression::parse("*/0 * * * *").is_err());
+        assert!(CronExpression::parse("5-1 * * * *").is_err());
+        assert!(CronExpression::parse("0 0 * foo *").is_err());
+        assert!(CronExpression::parse("0 9,17 1-15/2 JAN,jul 1-5").is_ok());
+
+        // Satisfiable syntax, but never runs
+        let never = CronExpression::parse("0 0 30 2 *").unwrap();
+        assert!(never.next_after(&at("2026-01-01T00:00:00Z")).is_none());
+    }
+}
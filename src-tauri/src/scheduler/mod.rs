@@ -0,0 +1,573 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Scheduled Workflow Runs
+//!
+//! Schedules (`schedule` table) run a prompt with an agent in a workflow on a
+//! cron expression ([`cron`]). The [`Scheduler`] held by the application state
+//! checks every `TICK_SECS` for due schedules once the deferred startup has
+//! loaded the agents. Each run:
+//!
+//! 1. stores the prompt as a user message of the workflow
+//! 2. executes it through the orchestrator (with MCP tools)
+//! 3. moderates the response and stores it as an assistant message
+//! 4. records the outcome on the schedule and emits `schedule_run`
+//!
+//! A failed run is stored as a system message. Runs missed while the app was
+//! closed are caught up once at startup, then the schedule resumes from now.
+
+pub mod cron;
+
+pub use cron::CronExpression;
+
+use crate::agents::behavior_changelog::run_behavior_position;
+use crate::agents::core::agent::{ReportStatus, Task};
+use crate::agents::core::{AgentOrchestrator, AgentRegistry};
+use crate::agents::moderation::{moderate_report, ModerationTarget};
+use crate::db::DBClient;
+use crate::inbox;
+use crate::llm::ProviderManager;
+use crate::mcp::MCPManager;
+use crate::models::pending_item::{PendingItemCreate, PendingItemKind};
+use crate::models::schedule::{Schedule, ScheduleRunEvent, ScheduleRunStatus};
+use crate::models::streaming::{events, CompletionStatus};
+use crate::models::MessageCreate;
+use crate::security::WORKFLOW_VAULT;
+use crate::tools::constants::scheduler as scheduler_const;
+use crate::tools::constants::workflow as wf_const;
+use chrono::{DateTime, Local, Utc};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock as StdRwLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+/// Fields selected when loading schedules
+pub const SCHEDULE_SELECT_FIELDS: &str = "meta::id(id) AS id, name, cron, agent_id, workflow_id, \
+     prompt, paused, next_run_at, last_run_at, last_status, last_error, created_at";
+
+/// Dependencies of background runs: scheduled runs and queued jobs
+/// (shared with the application state)
+#[derive(Clone)]
+pub struct RunContext {
+    /// Database client
+    pub db: Arc<DBClient>,
+    /// Agent registry
+    pub registry: Arc<AgentRegistry>,
+    /// Agent orchestrator
+    pub orchestrator: Arc<AgentOrchestrator>,
+    /// LLM provider manager (moderation)
+    pub llm_manager: Arc<ProviderManager>,
+    /// MCP server manager
+    pub mcp_manager: Arc<MCPManager>,
+    /// Tauri app handle for the run events
+    pub app_handle: Arc<StdRwLock<Option<AppHandle>>>,
+}
+
+/// Background runner of the schedules
+pub struct Scheduler {
+    /// Stops the check loop
+    shutdown: CancellationToken,
+    /// Schedules with a run in progress (a due schedule is not started twice)
+    running: Mutex<HashSet<String>>,
+    /// Whether the check loop was started
+    started: AtomicBool,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    /// Creates a stopped scheduler.
+    pub fn new() -> Self {
+        Self {
+            shutdown: CancellationToken::new(),
+            running: Mutex::new(HashSet::new()),
+            started: AtomicBool::new(false),
+        }
+    }
+
+    /// Starts the check loop (once).
+    pub fn start(self: &Arc<Self>, context: RunContext) {
+        if self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            info!(tick_secs = scheduler_const::TICK_SECS, "Scheduler started");
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(scheduler_const::TICK_SECS));
+            loop {
+                tokio::select! {
+                    _ = scheduler.shutdown.cancelled() => break,
+                    _ = interval.tick() => {
+                        if let Err(e) = scheduler.run_due(&context).await {
+                            warn!(error = %e, "Failed to check due schedules");
+                        }
+                    }
+                }
+            }
+            info!("Scheduler stopped");
+        });
+    }
+
+    /// Whether a scheduled run is in progress.
+    pub fn is_busy(&self) -> bool {
+        self.running
+            .lock()
+            .map(|running| !running.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Stops the check loop (runs in progress complete).
+    #[allow(dead_code)]
+    pub fn stop(&self) {
+        self.shutdown.cancel();
+    }
+
+    /// Starts the runs of the due schedules.
+    ///
+    /// The next run of each due schedule is computed before its run starts,
+    /// so a slow run never fires twice.
+    ///
+    /// # Returns
+    /// The spawned runs (awaited by tests)
+    pub async fn run_due(
+        self: &Arc<Self>,
+        context: &RunContext,
+    ) -> Result<Vec<tokio::task::JoinHandle<ScheduleRunEvent>>, String> {
+        let query = format!(
+            "SELECT {} FROM schedule WHERE paused = false AND next_run_at <= time::now()",
+            SCHEDULE_SELECT_FIELDS
+        );
+        let rows = context
+            .db
+            .query_json(&query)
+            .await
+            .map_err(|e| format!("Failed to load due schedules: {}", e))?;
+
+        let now = Utc::now();
+        let mut runs = Vec::new();
+        for schedule in rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value::<Schedule>(row).ok())
+        {
+            let next = next_run(&schedule.cron, now).unwrap_or(None);
+            set_run_times(&context.db, &schedule.id, next, Some(now)).await?;
+
+            let inserted = self
+                .running
+                .lock()
+                .map(|mut running| running.insert(schedule.id.clone()))
+                .unwrap_or(false);
+            if !inserted {
+                debug!(schedule_id = %schedule.id, "Previous run still in progress, run skipped");
+                continue;
+            }
+
+            let scheduler = self.clone();
+            let context = context.clone();
+            runs.push(tokio::spawn(async move {
+                let event = run_schedule(&context, &schedule).await;
+                if let Ok(mut running) = scheduler.running.lock() {
+                    running.remove(&schedule.id);
+                }
+                event
+            }));
+        }
+        Ok(runs)
+    }
+}
+
+/// Computes the next run of a cron expression after a time (local time).
+///
+/// # Returns
+/// The next run, or None if the expression never matches
+pub fn next_run(cron: &str, after: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+    let expression = CronExpression::parse(cron)?;
+    Ok(expression
+        .next_after(&after.with_timezone(&Local))
+        .map(|next| next.with_timezone(&Utc)))
+}
+
+/// Sets the next run (and the last run start) of a schedule.
+pub async fn set_run_times(
+    db: &DBClient,
+    schedule_id: &str,
+    next_run_at: Option<DateTime<Utc>>,
+    last_run_at: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    // Datetime casts: SCHEMAFULL tables reject ISO strings for datetime fields
+    let mut assignments = vec![
+        "next_run_at = IF $next_run_at = NONE THEN NONE ELSE <datetime>$next_run_at END"
+            .to_string(),
+    ];
+    let mut params = vec![
+        ("id".to_string(), serde_json::json!(schedule_id)),
+        (
+            "next_run_at".to_string(),
+            serde_json::json!(next_run_at.map(|t| t.to_rfc3339())),
+        ),
+    ];
+    if let Some(last_run_at) = last_run_at {
+        assignments.push("last_run_at = <datetime>$last_run_at".to_string());
+        params.push((
+            "last_run_at".to_string(),
+            serde_json::json!(last_run_at.to_rfc3339()),
+        ));
+    }
+
+    db.execute_with_params(
+        &format!(
+            "UPDATE type::thing('schedule', $id) SET {}",
+            assignments.join(", ")
+        ),
+        params,
+    )
+    .await
+    .map_err(|e| format!("Failed to update schedule: {}", e))
+}
+
+/// Runs a schedule and records its outcome.
+///
+/// A failure is stored as a system message of the workflow.
+pub async fn run_schedule(context: &RunContext, schedule: &Schedule) -> ScheduleRunEvent {
+    info!(schedule_id = %schedule.id, workflow_id = %schedule.workflow_id, "Scheduled run started");
+
+    let result = execute_run(context, schedule).await;
+    crate::metrics::record_workflow_execution(
+        "schedule",
+        &if result.is_ok() {
+            CompletionStatus::Completed
+        } else {
+            CompletionStatus::Error
+        },
+    );
+    let (status, error) = match &result {
+        Ok(()) => (ScheduleRunStatus::Success, None),
+        Err(e) => {
+            warn!(schedule_id = %schedule.id, error = %e, "Scheduled run failed");
+            let notice = format!("Scheduled run '{}' failed: {}", schedule.name, e);
+            if let Err(e) = save_message(
+                &context.db,
+                MessageCreate::system(schedule.workflow_id.clone(), notice),
+            )
+            .await
+            {
+                warn!(error = %e, "Failed to store the scheduled run error");
+            }
+            (ScheduleRunStatus::Failed, Some(e.clone()))
+        }
+    };
+
+    if let Err(e) = context
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('schedule', $id) SET last_status = $status, last_error = $error",
+            vec![
+                ("id".to_string(), serde_json::json!(schedule.id)),
+                ("status".to_string(), serde_json::json!(status.as_str())),
+                ("error".to_string(), serde_json::json!(error)),
+            ],
+        )
+        .await
+    {
+        error!(error = %e, "Failed to record the scheduled run outcome");
+    }
+    // A failed run waits for the user until the next successful one
+    match &error {
+        Some(e) => {
+            inbox::track_pending_item(
+                &context.db,
+                PendingItemCreate::failed_schedule(
+                    &schedule.id,
+                    &schedule.name,
+                    &schedule.workflow_id,
+                    e,
+                ),
+            )
+            .await
+        }
+        None => {
+            inbox::resolve_pending_item(&context.db, PendingItemKind::FailedSchedule, &schedule.id)
+                .await
+        }
+    }
+
+    let event = ScheduleRunEvent {
+        schedule_id: schedule.id.clone(),
+        workflow_id: schedule.workflow_id.clone(),
+        status,
+        error,
+    };
+    let handle = context
+        .app_handle
+        .read()
+        .ok()
+        .and_then(|guard| guard.clone());
+    if let Some(handle) = handle {
+        if let Err(e) = handle.emit(events::SCHEDULE_RUN, &event) {
+            warn!(error = %e, "Failed to emit schedule run event");
+        }
+    }
+    info!(schedule_id = %schedule.id, status = ?status, "Scheduled run finished");
+    event
+}
+
+/// Executes the prompt of a schedule and stores the messages of the run.
+async fn execute_run(context: &RunContext, schedule: &Schedule) -> Result<(), String> {
+    let workflows = context
+        .db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id FROM workflow WHERE meta::id(id) = $id",
+            vec![("id".to_string(), serde_json::json!(schedule.workflow_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load workflow: {}", e))?;
+    if workflows.is_empty() {
+        return Err("Workflow not found".to_string());
+    }
+    if context.registry.get(&schedule.agent_id).await.is_none() {
+        return Err(format!("Agent not found: {}", schedule.agent_id));
+    }
+
+    save_message(
+        &context.db,
+        MessageCreate::user(schedule.workflow_id.clone(), schedule.prompt.clone()),
+    )
+    .await?;
+    execute_prompt(
+        context,
+        &schedule.workflow_id,
+        &schedule.agent_id,
+        &schedule.prompt,
+    )
+    .await
+    .map(|_| ())
+}
+
+/// Executes a prompt with an agent and stores the response in the workflow.
+///
+/// The prompt itself must already be stored as a user message. The response
+/// is moderated, then stored as an assistant message and counted in the
+/// token totals of the workflow.
+///
+/// # Returns
+/// The ID of the assistant message
+pub async fn execute_prompt(
+    context: &RunContext,
+    workflow_id: &str,
+    agent_id: &str,
+    prompt: &str,
+) -> Result<String, String> {
+    let task = Task {
+        id: Uuid::new_v4().to_string(),
+        description: prompt.to_string(),
+        context: serde_json::json!({ "workflow_id": workflow_id }),
+    };
+    execute_task(context, workflow_id, agent_id, task).await
+}
+
+/// Executes a task with an agent and stores the response in the workflow
+/// (see [`execute_prompt`]).
+///
+/// # Returns
+/// The ID of the assistant message
+pub async fn execute_task(
+    context: &RunContext,
+    workflow_id: &str,
+    agent_id: &str,
+    task: Task,
+) -> Result<String, String> {
+    let agent = context
+        .registry
+        .get(agent_id)
+        .await
+        .ok_or_else(|| format!("Agent not found: {}", agent_id))?;
+
+    let behavior_position = run_behavior_position(&context.db).await;
+    let execution =
+        context
+            .orchestrator
+            .execute_with_mcp(agent_id, task, Some(context.mcp_manager.clone()));
+    let mut report = tokio::time::timeout(
+        Duration::from_secs(wf_const::LLM_EXECUTION_TIMEOUT_SECS),
+        execution,
+    )
+    .await
+    .map_err(|_| {
+        format!(
+            "Execution timed out after {} seconds",
+            wf_const::LLM_EXECUTION_TIMEOUT_SECS
+        )
+    })?
+    .map_err(|e| format!("Execution failed: {}", e))?;
+    if matches!(report.status, ReportStatus::Failed) {
+        return Err(report.response);
+    }
+
+    let message_id = Uuid::new_v4().to_string();
+    let config = agent.config();
+    let target = ModerationTarget {
+        workflow_id,
+        message_id: &message_id,
+    };
+    moderate_report(
+        &context.db,
+        &context.llm_manager,
+        config,
+        target,
+        &mut report,
+    )
+    .await;
+
+    let mut message = MessageCreate::assistant(
+        workflow_id.to_string(),
+        report.response.clone(),
+        Some(report.metrics.tokens_input as u64),
+        Some(report.metrics.tokens_output as u64),
+        Some(config.llm.model.clone()),
+        Some(config.llm.provider.clone()),
+        Some(report.metrics.duration_ms),
+    );
+    message.behavior_position = behavior_position;
+    save_message_with_id(&context.db, &message_id, message).await?;
+
+    context
+        .db
+        .execute_with_params(
+            "UPDATE type::thing('workflow', $id) SET \
+                total_tokens_input = (total_tokens_input ?? 0) + $tokens_input, \
+                total_tokens_output = (total_tokens_output ?? 0) + $tokens_output, \
+                updated_at = time::now()",
+            vec![
+                ("id".to_string(), serde_json::json!(workflow_id)),
+                (
+                    "tokens_input".to_string(),
+                    serde_json::json!(report.metrics.tokens_input),
+                ),
+                (
+                    "tokens_output".to_string(),
+                    serde_json::json!(report.metrics.tokens_output),
+                ),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to update workflow: {}", e))?;
+    Ok(message_id)
+}
+
+/// Stores a message of a run (encrypted for a sensitive workflow).
+pub async fn save_message(db: &DBClient, message: MessageCreate) -> Result<(), String> {
+    save_message_with_id(db, &Uuid::new_v4().to_string(), message).await
+}
+
+/// Stores a message of a run with a known ID.
+async fn save_message_with_id(
+    db: &DBClient,
+    message_id: &str,
+    mut message: MessageCreate,
+) -> Result<(), String> {
+    message.content = WORKFLOW_VAULT
+        .seal(db, &message.workflow_id, &message.content)
+        .await?;
+    db.create("message", message_id, message)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Failed to save message: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_failed_run_stored_as_system_message() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("scheduler_db");
+        let state = AppState::new(db_path.to_str().unwrap()).await.unwrap();
+
+        let workflow_id = Uuid::new_v4().to_string();
+        state
+            .db
+            .create(
+                "workflow",
+                &workflow_id,
+                crate::models::WorkflowCreate::new(
+                    "Scheduled".to_string(),
+                    "missing_agent".to_string(),
+                    crate::models::WorkflowStatus::Idle,
+                ),
+            )
+            .await
+            .unwrap();
+        let schedule = Schedule {
+            id: Uuid::new_v4().to_string(),
+            name: "Orphan".to_string(),
+            cron: "@daily".to_string(),
+            agent_id: "missing_agent".to_string(),
+            workflow_id: workflow_id.clone(),
+            prompt: "Prompt".to_string(),
+            paused: false,
+            next_run_at: None,
+            last_run_at: None,
+            last_status: None,
+            last_error: None,
+            created_at: Utc::now(),
+        };
+
+        let event = run_schedule(&state.run_context(), &schedule).await;
+        assert_eq!(event.status, ScheduleRunStatus::Failed);
+        assert_eq!(
+            event.error.as_deref(),
+            Some("Agent not found: missing_agent")
+        );
+
+        let messages = state
+            .db
+            .query_json("SELECT role, content FROM message")
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["role"], "system");
+        assert_eq!(
+            messages[0]["content"],
+            "Scheduled run 'Orphan' failed: Agent not found: missing_agent"
+        );
+
+        let items = crate::inbox::list_pending_items(&state.db, Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].kind, PendingItemKind::FailedSchedule);
+        assert_eq!(items[0].source_id, schedule.id);
+    }
+
+    #[test]
+    fn test_next_run() {
+        let now = Utc::now();
+        let next = next_run("*/5 * * * *", now).unwrap().unwrap();
+        assert!(next > now && next <= now + chrono::Duration::minutes(5));
+        assert!(next_run("0 0 30 2 *", now).unwrap().is_none());
+        assert!(next_run("not cron", now).is_err());
+    }
+}
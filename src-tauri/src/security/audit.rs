@@ -0,0 +1,352 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit log of sensitive actions.
+//!
+//! [`record`] appends an entry to the `audit_log` table when audit logging is
+//! enabled in the validation settings (`audit.enable_logging`). Recording is
+//! best effort: a failure is logged and never fails the audited action.
+//!
+//! Entries cannot be updated (a schema event rejects it). They are only
+//! deleted by rotation, which runs at startup and every `ROTATION_INTERVAL`
+//! recorded entries: entries older than `audit.retention_days` are deleted,
+//! then the oldest entries beyond `MAX_ENTRIES`.
+
+use crate::commands::validation::VALIDATION_SETTINGS_KEY;
+use crate::db::{queries::settings, DBClient};
+use crate::models::audit::{AuditExportFormat, AuditLogCreate, AuditLogEntry, AuditLogFilter};
+use crate::models::{AuditConfig, ValidationSettings};
+use crate::tools::constants::audit::{
+    DEFAULT_QUERY_LIMIT, MAX_ENTRIES, MAX_QUERY_LIMIT, ROTATION_INTERVAL,
+};
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Entries recorded since the last rotation
+static RECORDED_SINCE_ROTATION: AtomicU64 = AtomicU64::new(0);
+
+/// Loads the audit configuration from the validation settings.
+pub async fn load_audit_config(db: &DBClient) -> AuditConfig {
+    settings::load_config(db, VALIDATION_SETTINGS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|value| serde_json::from_value::<ValidationSettings>(value).ok())
+        .map(|settings| settings.audit)
+        .unwrap_or_default()
+}
+
+/// Records an audited action, if audit logging is enabled.
+pub async fn record(db: &DBClient, entry: AuditLogCreate) {
+    let config = load_audit_config(db).await;
+    if !config.enable_logging {
+        return;
+    }
+
+    let created = db
+        .query_json_with_params(
+            "CREATE type::thing('audit_log', $id) CONTENT { \
+                id: $id, action: $action, actor: $actor, target: $target, \
+                workflow_id: $workflow_id, success: $success, details: $details \
+             } RETURN NONE",
+            vec![
+                ("id".to_string(), json!(Uuid::new_v4().to_string())),
+                ("action".to_string(), json!(entry.action.to_string())),
+                ("actor".to_string(), json!(entry.actor)),
+                ("target".to_string(), json!(entry.target)),
+                ("workflow_id".to_string(), json!(entry.workflow_id)),
+                ("success".to_string(), json!(entry.success)),
+                ("details".to_string(), json!(entry.details.to_string())),
+            ],
+        )
+        .await;
+    if let Err(e) = created {
+        warn!(action = %entry.action, error = %e, "Failed to record audit log entry");
+        return;
+    }
+    debug!(action = %entry.action, target = %entry.target, "Audit log entry recorded");
+
+    if RECORDED_SINCE_ROTATION.fetch_add(1, Ordering::Relaxed) + 1 >= ROTATION_INTERVAL {
+        RECORDED_SINCE_ROTATION.store(0, Ordering::Relaxed);
+        if let Err(e) = rotate(db, &config).await {
+            warn!(error = %e, "Failed to rotate audit log");
+        }
+    }
+}
+
+/// Deletes entries past the retention period, then the oldest entries beyond
+/// `MAX_ENTRIES`.
+///
+/// # Returns
+/// Number of deleted entries
+pub async fn rotate(db: &DBClient, config: &AuditConfig) -> anyhow::Result<u64> {
+    let before = count(db).await?;
+
+    let cutoff = Utc::now() - Duration::days(i64::from(config.retention_days.max(1)));
+    db.query_json_with_params(
+        "DELETE audit_log WHERE created_at < <datetime>$cutoff RETURN NONE",
+        vec![("cutoff".to_string(), json!(cutoff.to_rfc3339()))],
+    )
+    .await?;
+
+    let remaining = count(db).await?;
+    if remaining > MAX_ENTRIES {
+        // Date of the newest entry to delete
+        let oldest_kept = db
+            .query_json_with_params(
+                &format!(
+                    "SELECT created_at FROM audit_log ORDER BY created_at ASC LIMIT 1 START {}",
+                    remaining - MAX_ENTRIES - 1
+                ),
+                vec![],
+            )
+            .await?;
+        if let Some(created_at) = oldest_kept.first().map(|row| row["created_at"].clone()) {
+            db.query_json_with_params(
+                "DELETE audit_log WHERE created_at <= <datetime>$created_at RETURN NONE",
+                vec![("created_at".to_string(), created_at)],
+            )
+            .await?;
+        }
+    }
+
+    let deleted = before.saturating_sub(count(db).await?);
+    if deleted > 0 {
+        info!(deleted, "Audit log rotated");
+    }
+    Ok(deleted)
+}
+
+/// Number of audit log entries.
+async fn count(db: &DBClient) -> anyhow::Result<u64> {
+    Ok(db
+        .query_json_with_params("SELECT count() AS total FROM audit_log GROUP ALL", vec![])
+        .await?
+        .first()
+        .and_then(|row| row["total"].as_u64())
+        .unwrap_or(0))
+}
+
+/// Loads audit log entries matching a filter, newest first.
+pub async fn query(db: &DBClient, filter: &AuditLogFilter) -> anyhow::Result<Vec<AuditLogEntry>> {
+    let mut conditions = Vec::new();
+    let mut params = Vec::new();
+    if let Some(action) = filter.action {
+        conditions.push("action = $action");
+        params.push(("action".to_string(), json!(action.to_string())));
+    }
+    if let Some(ref actor) = filter.actor {
+        conditions.push("actor = $actor");
+        params.push(("actor".to_string(), json!(actor)));
+    }
+    if let Some(ref workflow_id) = filter.workflow_id {
+        conditions.push("workflow_id = $workflow_id");
+        params.push(("workflow_id".to_string(), json!(workflow_id)));
+    }
+    if let Some(since) = filter.since {
+        conditions.push("created_at >= <datetime>$since");
+        params.push(("since".to_string(), json!(since.to_rfc3339())));
+    }
+    if let Some(until) = filter.until {
+        conditions.push("created_at < <datetime>$until");
+        params.push(("until".to_string(), json!(until.to_rfc3339())));
+    }
+    let filter_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let limit = filter
+        .limit
+        .unwrap_or(DEFAULT_QUERY_LIMIT)
+        .clamp(1, MAX_QUERY_LIMIT);
+
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT meta::id(id) AS id, action, actor, target, workflow_id, success, \
+                 details, created_at FROM audit_log {} ORDER BY created_at DESC LIMIT {}",
+                filter_clause, limit
+            ),
+            params,
+        )
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|mut row| {
+            // details is stored as a JSON string
+            let details = row["details"]
+                .as_str()
+                .and_then(|s| serde_json::from_str::<Value>(s).ok())
+                .unwrap_or_else(|| json!({}));
+            row["details"] = details;
+            serde_json::from_value(row).ok()
+        })
+        .collect())
+}
+
+/// Renders entries in an export format.
+pub fn render_export(entries: &[AuditLogEntry], format: AuditExportFormat) -> String {
+    match format {
+        AuditExportFormat::Jsonl => entries
+            .iter()
+            .filter_map(|entry| serde_json::to_string(entry).ok())
+            .map(|line| line + "\n")
+            .collect(),
+        AuditExportFormat::Csv => {
+            let mut csv =
+                String::from("created_at,action,actor,target,workflow_id,success,details\n");
+            for entry in entries {
+                let fields = [
+                    entry.created_at.to_rfc3339(),
+                    entry.action.to_string(),
+                    entry.actor.clone(),
+                    entry.target.clone(),
+                    entry.workflow_id.clone().unwrap_or_default(),
+                    entry.success.to_string(),
+                    entry.details.to_string(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                csv.push_str(&row.join(","));
+                csv.push('\n');
+            }
+            csv
+        }
+    }
+}
+
+/// Quotes a CSV field when needed (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::audit::AuditAction;
+    use tempfile::tempdir;
+
+    async fn setup_db() -> (tempfile::TempDir, DBClient) {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("audit_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+        (temp_dir, db)
+    }
+
+    #[tokio::test]
+    async fn test_record_and_query() {
+        let (_dir, db) = setup_db().await;
+
+        record(
+            &db,
+            AuditLogCreate::new(AuditAction::ApiKeyAccess, "user", "Mistral"),
+        )
+        .await;
+        record(
+            &db,
+            AuditLogCreate {
+                workflow_id: Some("wf1".to_string()),
+                success: false,
+                details: json!({ "duration_ms": 12 }),
+                ..AuditLogCreate::new(AuditAction::ToolExecution, "agent_a", "MemoryTool")
+            },
+        )
+        .await;
+
+        let all = query(&db, &AuditLogFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        let tools = query(
+            &db,
+            &AuditLogFilter {
+                action: Some(AuditAction::ToolExecution),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].target, "MemoryTool");
+        assert_eq!(tools[0].workflow_id.as_deref(), Some("wf1"));
+        assert!(!tools[0].success);
+        assert_eq!(tools[0].details["duration_ms"], 12);
+
+        // Entries are immutable
+        let update = db
+            .query_json_with_params(
+                "UPDATE type::thing('audit_log', $id) SET success = true",
+                vec![("id".to_string(), json!(tools[0].id))],
+            )
+            .await;
+        assert!(update.is_err());
+
+        let csv = render_export(&tools, AuditExportFormat::Csv);
+        assert!(csv.starts_with("created_at,action,actor,target,workflow_id,success,details\n"));
+        assert!(csv.contains(",tool_execution,agent_a,MemoryTool,wf1,false,"));
+    }
+
+    #[tokio::test]
+    async fn test_record_disabled_and_rotation() {
+        let (_dir, db) = setup_db().await;
+
+        let mut settings = ValidationSettings::default();
+        settings.audit.enable_logging = false;
+        settings::save_config(
+            &db,
+            VALIDATION_SETTINGS_KEY,
+            &serde_json::to_value(&settings).unwrap(),
+        )
+        .await
+        .unwrap();
+        record(
+            &db,
+            AuditLogCreate::new(AuditAction::McpServerStart, "user", "git"),
+        )
+        .await;
+        assert_eq!(count(&db).await.unwrap(), 0);
+
+        db.query_json_with_params(
+            "CREATE type::thing('audit_log', 'old') CONTENT { id: 'old', action: 'mcp_server_stop', actor: 'user', \
+             target: 'git', success: true, created_at: time::now() - 40d } RETURN NONE",
+            vec![],
+        )
+        .await
+        .unwrap();
+        settings.audit.enable_logging = true;
+        settings::save_config(
+            &db,
+            VALIDATION_SETTINGS_KEY,
+            &serde_json::to_value(&settings).unwrap(),
+        )
+        .await
+        .unwrap();
+        record(
+            &db,
+            AuditLogCreate::new(AuditAction::McpServerStart, "user", "git"),
+        )
+        .await;
+
+        assert_eq!(rotate(&db, &settings.audit).await.unwrap(), 1);
+        assert_eq!(count(&db).await.unwrap(), 1);
+    }
+}
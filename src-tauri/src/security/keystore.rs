@@ -18,6 +18,7 @@
 //! The keys are stored in the OS keychain (Linux: libsecret, macOS: Keychain, Windows: Credential Manager)
 //! and additionally encrypted with AES-256-GCM for defense in depth.
 
+use crate::models::api_key::ApiKeyMetadata;
 use aes_gcm::{
     aead::{Aead, KeyInit, OsRng},
     Aes256Gcm, Nonce,
@@ -29,6 +30,8 @@ use thiserror::Error;
 const KEYRING_SERVICE: &str = "zileo-chat";
 /// Username prefix for keyring entries
 const KEYRING_USER_PREFIX: &str = "api_key_";
+/// Username prefix for the metadata entries of API keys
+const KEYRING_METADATA_PREFIX: &str = "key_metadata_";
 /// Key used for storing the encryption master key in keyring
 const MASTER_KEY_NAME: &str = "__master_encryption_key__";
 /// AES-256 key size in bytes
@@ -163,6 +166,42 @@ impl KeyStore {
         }
     }
 
+    /// Stores the metadata of a provider API key.
+    ///
+    /// Metadata is not secret and is stored as plain JSON next to the key.
+    pub fn save_metadata(&self, metadata: &ApiKeyMetadata) -> Result<(), KeyStoreError> {
+        let json = serde_json::to_string(metadata)
+            .map_err(|e| KeyStoreError::InvalidFormat(format!("Invalid metadata: {}", e)))?;
+        Self::get_metadata_entry(&metadata.provider)?
+            .set_password(&json)
+            .map_err(|e| KeyStoreError::KeychainError(format!("Failed to store metadata: {}", e)))
+    }
+
+    /// Retrieves the metadata of a provider API key, if any.
+    pub fn get_metadata(&self, provider: &str) -> Result<Option<ApiKeyMetadata>, KeyStoreError> {
+        match Self::get_metadata_entry(provider)?.get_password() {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| KeyStoreError::InvalidFormat(format!("Invalid metadata: {}", e))),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(KeyStoreError::KeychainError(format!(
+                "Failed to retrieve metadata: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Deletes the metadata of a provider API key (no-op if absent).
+    pub fn delete_metadata(&self, provider: &str) -> Result<(), KeyStoreError> {
+        match Self::get_metadata_entry(provider)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(KeyStoreError::KeychainError(format!(
+                "Failed to delete metadata: {}",
+                e
+            ))),
+        }
+    }
+
     /// Lists all providers that have stored API keys.
     ///
     /// Note: This is a best-effort operation as keyring does not support
@@ -171,6 +210,7 @@ impl KeyStore {
         // Common provider names to check
         const KNOWN_PROVIDERS: &[&str] = &[
             "Mistral",
+            "Gemini",
             "Ollama",
             "OpenAI",
             "Anthropic",
@@ -227,6 +267,13 @@ impl KeyStore {
             .map_err(|e| KeyStoreError::KeychainError(format!("Failed to create entry: {}", e)))
     }
 
+    /// Gets the keyring entry of the metadata of a provider API key.
+    fn get_metadata_entry(provider: &str) -> Result<Entry, KeyStoreError> {
+        let username = format!("{}{}", KEYRING_METADATA_PREFIX, provider);
+        Entry::new(KEYRING_SERVICE, &username)
+            .map_err(|e| KeyStoreError::KeychainError(format!("Failed to create entry: {}", e)))
+    }
+
     /// Encrypts data using AES-256-GCM.
     fn encrypt(&self, cipher: &Aes256Gcm, plaintext: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
         use aes_gcm::aead::rand_core::RngCore;
@@ -447,6 +494,13 @@ mod tests {
         let retrieved = store.get("TestProvider").unwrap();
         assert_eq!(retrieved, "test-api-key-12345");
 
+        // Metadata
+        let metadata = ApiKeyMetadata::new("TestProvider", chrono::Utc::now(), None);
+        store.save_metadata(&metadata).unwrap();
+        assert_eq!(store.get_metadata("TestProvider").unwrap(), Some(metadata));
+        store.delete_metadata("TestProvider").unwrap();
+        assert_eq!(store.get_metadata("TestProvider").unwrap(), None);
+
         // Delete
         store.delete("TestProvider").unwrap();
 
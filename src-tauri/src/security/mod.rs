@@ -17,13 +17,20 @@
 //! Provides:
 //! - Input validation utilities for sanitizing and validating user input
 //! - Secure API key storage using OS keychain (keyring) + AES-256 encryption
+//! - Encryption of sensitive workflows with keystore-wrapped data keys
+//! - Audit log of sensitive actions
+//! - Redaction of secrets in exported data
 //! - Security-related error types
 
+pub mod audit;
 pub mod keystore;
+pub mod redaction;
 pub mod validation;
+pub mod workflow_vault;
 
 pub use keystore::{KeyStore, KeyStoreError};
 pub use validation::Validator;
+pub use workflow_vault::WORKFLOW_VAULT;
 // ValidationError is used in tests
 #[allow(unused_imports)]
 pub use validation::ValidationError;
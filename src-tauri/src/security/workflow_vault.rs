@@ -0,0 +1,361 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encryption of sensitive workflows.
+//!
+//! A workflow marked as sensitive has its own AES-256-GCM data key, stored in
+//! the keystore (which wraps it with the master key and the OS keychain). The
+//! key is loaded into [`WORKFLOW_VAULT`] when the workflow is opened and
+//! dropped when it is closed; message contents, reasoning steps, tool logs and
+//! workflow-scoped memories are encrypted on write and decrypted on read while it is open.
+//!
+//! Encrypted values are stored as `enc:v1:<base64(nonce || ciphertext)>`, so
+//! queries can exclude them (search, exports) with a prefix check.
+
+use crate::db::DBClient;
+use crate::models::{Message, ThinkingStep, ToolExecution};
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Prefix of encrypted values
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Text shown in place of encrypted content of a closed workflow
+pub const LOCKED_PLACEHOLDER: &str = "[encrypted]";
+
+/// AES-GCM nonce size in bytes
+const NONCE_SIZE: usize = 12;
+
+/// Data key size in bytes (AES-256)
+const KEY_SIZE: usize = 32;
+
+/// Keystore entry name of the data key of a workflow
+pub fn workflow_key_name(workflow_id: &str) -> String {
+    format!("workflow_key_{}", workflow_id)
+}
+
+/// Returns true if a stored value is encrypted.
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Generates a new random data key, base64-encoded for the keystore.
+pub fn generate_key() -> String {
+    let mut key = [0u8; KEY_SIZE];
+    OsRng.fill_bytes(&mut key);
+    STANDARD.encode(key)
+}
+
+/// Data keys of the open sensitive workflows.
+#[derive(Default)]
+pub struct WorkflowVault {
+    /// Ciphers keyed by workflow ID (present while the workflow is open)
+    ciphers: RwLock<HashMap<String, Aes256Gcm>>,
+    /// Cached `sensitive` flags keyed by workflow ID
+    sensitivity: RwLock<HashMap<String, bool>>,
+}
+
+/// Process-wide vault shared by commands and agent tools
+pub static WORKFLOW_VAULT: Lazy<WorkflowVault> = Lazy::new(WorkflowVault::default);
+
+impl WorkflowVault {
+    /// Loads the data key of a workflow (opens it).
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Workflow ID
+    /// * `key` - Base64 data key, as stored in the keystore
+    pub fn unlock(&self, workflow_id: &str, key: &str) -> Result<(), String> {
+        let bytes = STANDARD
+            .decode(key)
+            .map_err(|e| format!("Invalid workflow key: {}", e))?;
+        let cipher = Aes256Gcm::new_from_slice(&bytes)
+            .map_err(|e| format!("Invalid workflow key: {}", e))?;
+        self.ciphers
+            .write()
+            .map_err(|e| e.to_string())?
+            .insert(workflow_id.to_string(), cipher);
+        Ok(())
+    }
+
+    /// Drops the data key of a workflow (closes it).
+    pub fn lock(&self, workflow_id: &str) {
+        if let Ok(mut ciphers) = self.ciphers.write() {
+            ciphers.remove(workflow_id);
+        }
+    }
+
+    /// Returns true if the workflow key is loaded.
+    pub fn is_unlocked(&self, workflow_id: &str) -> bool {
+        self.ciphers
+            .read()
+            .map(|ciphers| ciphers.contains_key(workflow_id))
+            .unwrap_or(false)
+    }
+
+    /// Returns the IDs of the open sensitive workflows.
+    pub fn unlocked_workflows(&self) -> Vec<String> {
+        self.ciphers
+            .read()
+            .map(|ciphers| ciphers.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Encrypts a value with the workflow key.
+    ///
+    /// # Errors
+    /// The workflow is locked.
+    pub fn encrypt(&self, workflow_id: &str, plaintext: &str) -> Result<String, String> {
+        let ciphers = self.ciphers.read().map_err(|e| e.to_string())?;
+        let cipher = ciphers
+            .get(workflow_id)
+            .ok_or_else(|| locked_error(workflow_id))?;
+
+        let mut nonce_bytes = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), plaintext.as_bytes())
+            .map_err(|e| format!("Encryption failed: {}", e))?;
+
+        let mut data = nonce_bytes.to_vec();
+        data.extend(ciphertext);
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(data)))
+    }
+
+    /// Decrypts a value with the workflow key (plain values are returned as is).
+    ///
+    /// # Errors
+    /// The workflow is locked or the value is corrupted.
+    pub fn decrypt(&self, workflow_id: &str, value: &str) -> Result<String, String> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let ciphers = self.ciphers.read().map_err(|e| e.to_string())?;
+        let cipher = ciphers
+            .get(workflow_id)
+            .ok_or_else(|| locked_error(workflow_id))?;
+
+        let data = STANDARD
+            .decode(encoded)
+            .map_err(|e| format!("Invalid encrypted value: {}", e))?;
+        if data.len() < NONCE_SIZE {
+            return Err("Invalid encrypted value: too short".to_string());
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|e| format!("Decryption failed: {}", e))?;
+        String::from_utf8(plaintext).map_err(|e| format!("Invalid decrypted value: {}", e))
+    }
+
+    /// Decrypts a value for display, or returns [`LOCKED_PLACEHOLDER`].
+    pub fn reveal(&self, workflow_id: &str, value: &str) -> String {
+        self.decrypt(workflow_id, value)
+            .unwrap_or_else(|_| LOCKED_PLACEHOLDER.to_string())
+    }
+
+    /// Decrypts a loaded value in place for display (see [`Self::reveal`]).
+    pub fn reveal_in_place(&self, workflow_id: &str, value: &mut String) {
+        if is_encrypted(value) {
+            *value = self.reveal(workflow_id, value);
+        }
+    }
+
+    /// Encrypts a JSON value (tool logs) into an encrypted JSON string.
+    pub fn encrypt_json(
+        &self,
+        workflow_id: &str,
+        value: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        if matches!(value, serde_json::Value::String(s) if is_encrypted(s)) {
+            return Ok(value.clone());
+        }
+        self.encrypt(workflow_id, &value.to_string())
+            .map(serde_json::Value::String)
+    }
+
+    /// Decrypts a JSON value encrypted with [`Self::encrypt_json`].
+    ///
+    /// # Errors
+    /// The workflow is locked or the value is corrupted.
+    pub fn decrypt_json(
+        &self,
+        workflow_id: &str,
+        value: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        match value {
+            serde_json::Value::String(s) if is_encrypted(s) => {
+                let plaintext = self.decrypt(workflow_id, s)?;
+                Ok(
+                    serde_json::from_str(&plaintext)
+                        .unwrap_or(serde_json::Value::String(plaintext)),
+                )
+            }
+            other => Ok(other.clone()),
+        }
+    }
+
+    /// Decrypts a loaded JSON value in place for display (placeholder when locked).
+    pub fn reveal_json_in_place(&self, workflow_id: &str, value: &mut serde_json::Value) {
+        if matches!(value, serde_json::Value::String(s) if is_encrypted(s)) {
+            *value = self
+                .decrypt_json(workflow_id, value)
+                .unwrap_or_else(|_| serde_json::Value::String(LOCKED_PLACEHOLDER.to_string()));
+        }
+    }
+
+    /// Decrypts a loaded message for display.
+    pub fn reveal_message(&self, message: &mut Message) {
+        self.reveal_in_place(&message.workflow_id, &mut message.content);
+    }
+
+    /// Decrypts a loaded thinking step for display.
+    pub fn reveal_thinking_step(&self, step: &mut ThinkingStep) {
+        self.reveal_in_place(&step.workflow_id, &mut step.content);
+    }
+
+    /// Decrypts a loaded tool execution log for display.
+    pub fn reveal_tool_execution(&self, execution: &mut ToolExecution) {
+        self.reveal_json_in_place(&execution.workflow_id, &mut execution.input_params);
+        self.reveal_json_in_place(&execution.workflow_id, &mut execution.output_result);
+        if let Some(diff) = execution.result_diff.as_mut() {
+            self.reveal_json_in_place(&execution.workflow_id, diff);
+        }
+    }
+
+    /// Returns true if the workflow is marked as sensitive (cached).
+    pub async fn is_sensitive(&self, db: &DBClient, workflow_id: &str) -> Result<bool, String> {
+        if let Some(&sensitive) = self
+            .sensitivity
+            .read()
+            .map_err(|e| e.to_string())?
+            .get(workflow_id)
+        {
+            return Ok(sensitive);
+        }
+
+        let rows: Vec<serde_json::Value> = db
+            .query_json_with_params(
+                "SELECT (sensitive ?? false) AS sensitive FROM workflow WHERE meta::id(id) = $id",
+                vec![("id".to_string(), serde_json::json!(workflow_id))],
+            )
+            .await
+            .map_err(|e| format!("Failed to load workflow: {}", e))?;
+        let sensitive = rows
+            .first()
+            .and_then(|row| row.get("sensitive"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        self.set_sensitive(workflow_id, sensitive);
+        Ok(sensitive)
+    }
+
+    /// Records the `sensitive` flag of a workflow.
+    pub fn set_sensitive(&self, workflow_id: &str, sensitive: bool) {
+        if let Ok(mut cache) = self.sensitivity.write() {
+            cache.insert(workflow_id.to_string(), sensitive);
+        }
+    }
+
+    /// Forgets a deleted workflow.
+    pub fn forget(&self, workflow_id: &str) {
+        self.lock(workflow_id);
+        if let Ok(mut cache) = self.sensitivity.write() {
+            cache.remove(workflow_id);
+        }
+    }
+
+    /// Prepares a value for storage: encrypted if the workflow is sensitive.
+    ///
+    /// # Errors
+    /// The workflow is sensitive but locked (nothing may be written in clear).
+    pub async fn seal(
+        &self,
+        db: &DBClient,
+        workflow_id: &str,
+        plaintext: &str,
+    ) -> Result<String, String> {
+        if self.is_sensitive(db, workflow_id).await? {
+            self.encrypt(workflow_id, plaintext)
+        } else {
+            Ok(plaintext.to_string())
+        }
+    }
+
+    /// Prepares a JSON value (tool logs) for storage, see [`Self::seal`].
+    pub async fn seal_json(
+        &self,
+        db: &DBClient,
+        workflow_id: &str,
+        value: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        if self.is_sensitive(db, workflow_id).await? {
+            self.encrypt_json(workflow_id, &value)
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+fn locked_error(workflow_id: &str) -> String {
+    format!(
+        "Workflow {} is sensitive and locked, open it to access its content",
+        workflow_id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let vault = WorkflowVault::default();
+        let key = generate_key();
+
+        assert!(vault.encrypt("wf_1", "secret").is_err());
+        vault.unlock("wf_1", &key).unwrap();
+        assert!(vault.is_unlocked("wf_1"));
+
+        let encrypted = vault.encrypt("wf_1", "secret plan").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("secret"));
+        assert_ne!(encrypted, vault.encrypt("wf_1", "secret plan").unwrap());
+        assert_eq!(vault.decrypt("wf_1", &encrypted).unwrap(), "secret plan");
+        assert_eq!(vault.decrypt("wf_1", "plain").unwrap(), "plain");
+
+        // Another key cannot decrypt
+        vault.unlock("wf_2", &generate_key()).unwrap();
+        assert!(vault.decrypt("wf_2", &encrypted).is_err());
+
+        vault.lock("wf_1");
+        assert!(vault.decrypt("wf_1", &encrypted).is_err());
+        assert_eq!(vault.reveal("wf_1", &encrypted), LOCKED_PLACEHOLDER);
+        assert_eq!(vault.reveal("wf_1", "plain"), "plain");
+    }
+
+    #[test]
+    fn test_unlock_rejects_invalid_key() {
+        let vault = WorkflowVault::default();
+        assert!(vault.unlock("wf", "not base64!").is_err());
+        assert!(vault.unlock("wf", &STANDARD.encode([0u8; 8])).is_err());
+        assert!(!vault.is_unlocked("wf"));
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sealing of sync payloads with the pairing key.
+//!
+//! Payloads are encrypted with AES-256-GCM; the pairing ID and the sender
+//! role are authenticated as associated data, so a payload cannot be replayed
+//! to another pairing or reflected back to its sender. Mailbox IDs are derived
+//! from the key with HMAC-SHA256: the mailbox server only sees opaque IDs and
+//! sealed blobs.
+
+use crate::models::settings_sync::SyncRole;
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng, Payload},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Pairing key size in bytes (AES-256)
+pub const KEY_SIZE: usize = 32;
+
+/// AES-GCM nonce size in bytes
+const NONCE_SIZE: usize = 12;
+
+/// Generates a new random pairing key.
+pub fn generate_pairing_key() -> [u8; KEY_SIZE] {
+    let mut key = [0u8; KEY_SIZE];
+    OsRng.fill_bytes(&mut key);
+    key
+}
+
+/// Cipher of a pairing.
+pub struct SyncCipher {
+    cipher: Aes256Gcm,
+    key: [u8; KEY_SIZE],
+    pairing_id: String,
+}
+
+impl SyncCipher {
+    /// Creates the cipher of a pairing from its key.
+    pub fn new(pairing_id: &str, key: &[u8]) -> Result<Self, String> {
+        let key: [u8; KEY_SIZE] = key
+            .try_into()
+            .map_err(|_| format!("Pairing key must be {} bytes", KEY_SIZE))?;
+        let cipher =
+            Aes256Gcm::new_from_slice(&key).map_err(|e| format!("Invalid pairing key: {}", e))?;
+        Ok(Self {
+            cipher,
+            key,
+            pairing_id: pairing_id.to_string(),
+        })
+    }
+
+    /// Returns the mailbox ID written by `role`.
+    pub fn mailbox_id(&self, role: SyncRole) -> String {
+        let mut mac =
+            <HmacSha256 as Mac>::new_from_slice(&self.key).expect("HMAC accepts keys of any size");
+        mac.update(b"zileo-sync mailbox:");
+        mac.update(self.pairing_id.as_bytes());
+        mac.update(b":");
+        mac.update(role.as_str().as_bytes());
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Associated data of a payload sent by `sender`.
+    fn aad(&self, sender: SyncRole) -> Vec<u8> {
+        format!("{}:{}", self.pairing_id, sender.as_str()).into_bytes()
+    }
+
+    /// Seals a payload sent by `sender`: base64(nonce || ciphertext).
+    pub fn seal(&self, sender: SyncRole, plaintext: &[u8]) -> Result<String, String> {
+        let mut nonce_bytes = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let aad = self.aad(sender);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce_bytes),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| format!("Failed to seal sync payload: {}", e))?;
+
+        let mut sealed = nonce_bytes.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(sealed))
+    }
+
+    /// Opens a payload sent by `sender`.
+    ///
+    /// # Errors
+    /// The payload is malformed, was sealed with another key, for another
+    /// pairing or by another role, or was modified.
+    pub fn open(&self, sender: SyncRole, sealed: &str) -> Result<Vec<u8>, String> {
+        let data = STANDARD
+            .decode(sealed.trim())
+            .map_err(|e| format!("Invalid sync payload: {}", e))?;
+        if data.len() <= NONCE_SIZE {
+            return Err("Invalid sync payload: too short".to_string());
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+        let aad = self.aad(sender);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| "Sync payload could not be authenticated".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open() {
+        let key = generate_pairing_key();
+        let cipher = SyncCipher::new("pairing-1", &key).unwrap();
+
+        let sealed = cipher.seal(SyncRole::Initiator, b"settings").unwrap();
+        assert_eq!(
+            cipher.open(SyncRole::Initiator, &sealed).unwrap(),
+            b"settings"
+        );
+
+        // Reflected to its sender's role
+        assert!(cipher.open(SyncRole::Responder, &sealed).is_err());
+        // Another pairing with the same key
+        let other = SyncCipher::new("pairing-2", &key).unwrap();
+        assert!(other.open(SyncRole::Initiator, &sealed).is_err());
+        // Another key
+        let other = SyncCipher::new("pairing-1", &generate_pairing_key()).unwrap();
+        assert!(other.open(SyncRole::Initiator, &sealed).is_err());
+
+        assert!(cipher.open(SyncRole::Initiator, "AAAA").is_err());
+        assert!(SyncCipher::new("pairing-1", &key[..16]).is_err());
+    }
+
+    #[test]
+    fn test_mailbox_id() {
+        let key = generate_pairing_key();
+        let cipher = SyncCipher::new("pairing-1", &key).unwrap();
+
+        let initiator = cipher.mailbox_id(SyncRole::Initiator);
+        assert_eq!(initiator.len(), 64);
+        assert!(initiator.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(initiator, cipher.mailbox_id(SyncRole::Responder));
+        assert_eq!(
+            initiator,
+            SyncCipher::new("pairing-1", &key)
+                .unwrap()
+                .mailbox_id(SyncRole::Initiator)
+        );
+    }
+}
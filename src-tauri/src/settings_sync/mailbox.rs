@@ -0,0 +1,355 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mailbox transport of sealed sync payloads.
+//!
+//! A mailbox server keeps the last payload written to each mailbox:
+//!
+//! - `PUT /v1/mailbox/<id>` stores the request body (204)
+//! - `GET /v1/mailbox/<id>` returns it (200), or 404 if none was written
+//!
+//! The relay transport uses any server implementing these two routes. The
+//! local network transport uses [`MailboxServer`], hosted in-process by the
+//! initiator. Mailbox IDs are 64 hex characters (see
+//! [`SyncCipher::mailbox_id`](super::crypto::SyncCipher::mailbox_id)).
+
+use crate::tools::constants::sync as sync_const;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+/// Returns true if `id` is a valid mailbox ID.
+pub fn is_valid_mailbox_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn mailbox_url(base_url: &str, mailbox_id: &str) -> String {
+    format!(
+        "{}/v1/mailbox/{}",
+        base_url.trim().trim_end_matches('/'),
+        mailbox_id
+    )
+}
+
+/// Writes a sealed payload to a mailbox.
+pub async fn put_mailbox(
+    client: &reqwest::Client,
+    base_url: &str,
+    mailbox_id: &str,
+    sealed: String,
+) -> Result<(), String> {
+    let response = client
+        .put(mailbox_url(base_url, mailbox_id))
+        .header("Content-Type", "text/plain")
+        .body(sealed)
+        .timeout(Duration::from_secs(sync_const::TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| format!("Cannot reach sync mailbox at {}: {}", base_url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Sync mailbox rejected the payload: {}",
+            response.status()
+        ));
+    }
+    Ok(())
+}
+
+/// Reads the sealed payload of a mailbox, None if nothing was written yet.
+pub async fn get_mailbox(
+    client: &reqwest::Client,
+    base_url: &str,
+    mailbox_id: &str,
+) -> Result<Option<String>, String> {
+    let response = client
+        .get(mailbox_url(base_url, mailbox_id))
+        .timeout(Duration::from_secs(sync_const::TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| format!("Cannot reach sync mailbox at {}: {}", base_url, e))?;
+
+    match response.status() {
+        reqwest::StatusCode::NOT_FOUND => Ok(None),
+        status if status.is_success() => {
+            let body = response
+                .text()
+                .await
+                .map_err(|e| format!("Failed to read sync payload: {}", e))?;
+            if body.len() > sync_const::MAX_PAYLOAD_BYTES {
+                return Err("Sync payload exceeds maximum size".to_string());
+            }
+            Ok(Some(body))
+        }
+        status => Err(format!("Sync mailbox returned {}", status)),
+    }
+}
+
+/// Returns the address of this machine on the local network.
+///
+/// Uses the source address the OS picks to reach a public address (a UDP
+/// "connection" sends no packet).
+pub fn local_network_ip() -> Option<IpAddr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then_some(ip)
+}
+
+/// In-process mailbox server for the local network transport.
+pub struct MailboxServer {
+    addr: SocketAddr,
+    cancellation: CancellationToken,
+}
+
+impl MailboxServer {
+    /// Starts a server listening on `addr` (port 0 picks a free port).
+    pub async fn start(addr: SocketAddr) -> Result<Self, String> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| format!("Cannot listen on {}: {}", addr, e))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| format!("Cannot read listening address: {}", e))?;
+
+        let server_mailboxes = Arc::new(Mutex::new(HashMap::new()));
+        let cancellation = CancellationToken::new();
+        let token = cancellation.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((socket, peer)) => {
+                            let mailboxes = server_mailboxes.clone();
+                            tokio::spawn(async move {
+                                let timeout = Duration::from_secs(sync_const::TIMEOUT_SECS);
+                                if tokio::time::timeout(timeout, serve_connection(socket, &mailboxes))
+                                    .await
+                                    .is_err()
+                                {
+                                    debug!(peer = %peer, "Sync mailbox connection timed out");
+                                }
+                            });
+                        }
+                        Err(e) => warn!(error = %e, "Sync mailbox accept failed"),
+                    },
+                }
+            }
+            debug!("Sync mailbox server stopped");
+        });
+
+        info!(addr = %addr, "Sync mailbox server started");
+        Ok(Self { addr, cancellation })
+    }
+
+    /// Returns the listening port.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+}
+
+impl Drop for MailboxServer {
+    fn drop(&mut self) {
+        self.cancellation.cancel();
+    }
+}
+
+/// Mailbox server of the local network pairings created on this machine
+pub static LAN_MAILBOX: Lazy<Mutex<Option<MailboxServer>>> = Lazy::new(|| Mutex::new(None));
+
+/// Starts the local network mailbox server if needed and returns its port.
+///
+/// # Arguments
+/// * `port` - Port to listen on when starting (0 picks a free port)
+pub async fn ensure_lan_mailbox(port: u16) -> Result<u16, String> {
+    let mut server = LAN_MAILBOX.lock().await;
+    if let Some(server) = server.as_ref() {
+        return Ok(server.port());
+    }
+    let started = MailboxServer::start(SocketAddr::from(([0, 0, 0, 0], port))).await?;
+    let port = started.port();
+    *server = Some(started);
+    Ok(port)
+}
+
+/// Stops the local network mailbox server.
+pub async fn stop_lan_mailbox() {
+    if LAN_MAILBOX.lock().await.take().is_some() {
+        info!("Sync mailbox server stopped");
+    }
+}
+
+/// Serves one request.
+async fn serve_connection(mut socket: TcpStream, mailboxes: &Mutex<HashMap<String, String>>) {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buffer.len() > sync_const::MAX_HEAD_BYTES {
+            respond(&mut socket, "431 Request Header Fields Too Large", "").await;
+            return;
+        }
+        match socket.read(&mut chunk).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let path = request_line.next().unwrap_or_default().to_string();
+    let content_length: usize = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse().ok())
+        .unwrap_or(0);
+
+    let Some(id) = path
+        .strip_prefix("/v1/mailbox/")
+        .filter(|id| is_valid_mailbox_id(id))
+        .map(str::to_lowercase)
+    else {
+        respond(&mut socket, "404 Not Found", "").await;
+        return;
+    };
+
+    match method.as_str() {
+        "GET" => {
+            let payload = mailboxes.lock().await.get(&id).cloned();
+            match payload {
+                Some(payload) => respond(&mut socket, "200 OK", &payload).await,
+                None => respond(&mut socket, "404 Not Found", "").await,
+            }
+        }
+        "PUT" => {
+            if content_length > sync_const::MAX_PAYLOAD_BYTES {
+                respond(&mut socket, "413 Payload Too Large", "").await;
+                return;
+            }
+            while buffer.len() < header_end + content_length {
+                match socket.read(&mut chunk).await {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                }
+            }
+            let Ok(body) =
+                String::from_utf8(buffer[header_end..header_end + content_length].to_vec())
+            else {
+                respond(&mut socket, "400 Bad Request", "").await;
+                return;
+            };
+
+            let mut mailboxes = mailboxes.lock().await;
+            if !mailboxes.contains_key(&id) && mailboxes.len() >= sync_const::MAX_MAILBOXES {
+                drop(mailboxes);
+                respond(&mut socket, "507 Insufficient Storage", "").await;
+                return;
+            }
+            mailboxes.insert(id, body);
+            drop(mailboxes);
+            respond(&mut socket, "204 No Content", "").await;
+        }
+        _ => respond(&mut socket, "405 Method Not Allowed", "").await,
+    }
+}
+
+async fn respond(socket: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nConnection: close\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = socket.write_all(response.as_bytes()).await;
+    let _ = socket.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mailbox_server_roundtrip() {
+        let server = MailboxServer::start(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let base_url = format!("http://127.0.0.1:{}", server.port());
+        let client = reqwest::Client::new();
+        let id = "a".repeat(64);
+
+        assert_eq!(get_mailbox(&client, &base_url, &id).await.unwrap(), None);
+        put_mailbox(&client, &base_url, &id, "sealed-1".to_string())
+            .await
+            .unwrap();
+        put_mailbox(&client, &base_url, &id, "sealed-2".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            get_mailbox(&client, &base_url, &id).await.unwrap(),
+            Some("sealed-2".to_string())
+        );
+
+        // Invalid mailbox ID
+        assert!(put_mailbox(&client, &base_url, "../etc", "x".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mailbox_server_limits() {
+        let server = MailboxServer::start(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let base_url = format!("http://127.0.0.1:{}", server.port());
+        let client = reqwest::Client::new();
+
+        for i in 0..sync_const::MAX_MAILBOXES {
+            let id = format!("{:064x}", i);
+            put_mailbox(&client, &base_url, &id, "x".to_string())
+                .await
+                .unwrap();
+        }
+        let error = put_mailbox(&client, &base_url, &"f".repeat(64), "x".to_string())
+            .await
+            .unwrap_err();
+        assert!(error.contains("507"));
+
+        // Rejected before the body is read
+        let oversized = "x".repeat(sync_const::MAX_PAYLOAD_BYTES + 1);
+        assert!(
+            put_mailbox(&client, &base_url, &format!("{:064x}", 0), oversized)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_valid_mailbox_id() {
+        assert!(is_valid_mailbox_id(&"0f".repeat(32)));
+        assert!(!is_valid_mailbox_id(&"0g".repeat(32)));
+        assert!(!is_valid_mailbox_id("abc"));
+    }
+}
@@ -0,0 +1,313 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Merge of a peer payload into the local settings.
+//!
+//! Entities are matched by name, as in imports. A missing entity is created;
+//! an entity whose content differs is overwritten when the peer copy was
+//! updated more recently, otherwise the local copy wins (and reaches the peer
+//! with the next sync). Identical entities are left untouched, so syncing
+//! twice does not bump timestamps back and forth. Deletions are not synced.
+//!
+//! MCP server env values matching sensitive patterns are cleared before they
+//! leave the machine; an empty value received from the peer keeps the local
+//! value.
+
+use crate::models::import_export::{
+    is_sensitive_env_key, ConflictResolution, ExportPackage, ImportCounts, ImportSelection,
+    MCPAdditions, MCPServerExportData,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Imports to run for a peer payload
+#[derive(Debug, Clone)]
+pub struct MergePlan {
+    /// Entities to create or overwrite (by name)
+    pub selection: ImportSelection,
+    /// Overwrite resolutions, keyed `<entity type>:<name>`
+    pub resolutions: HashMap<String, ConflictResolution>,
+    /// Local env values kept for sanitized MCP env vars, keyed by server name
+    pub mcp_additions: HashMap<String, MCPAdditions>,
+    /// Entities left unchanged (identical, or newer locally)
+    pub unchanged: ImportCounts,
+}
+
+/// Clears the sensitive env values of the MCP servers of a package.
+pub fn sanitize_for_sync(package: &mut ExportPackage) {
+    for server in &mut package.mcp_servers {
+        for (key, value) in server.env.iter_mut() {
+            if is_sensitive_env_key(key) {
+                value.clear();
+            }
+        }
+    }
+}
+
+/// Decision for one entity
+enum Decision {
+    Create,
+    Overwrite,
+    Unchanged,
+}
+
+/// Compares a remote entity with its local copy (if any).
+fn decide<T: Serialize>(
+    remote: &T,
+    remote_updated_at: Option<&str>,
+    local: Option<(&T, Option<&str>)>,
+) -> Decision {
+    let Some((local, local_updated_at)) = local else {
+        return Decision::Create;
+    };
+    if content(remote) == content(local) {
+        return Decision::Unchanged;
+    }
+    match (parse_time(remote_updated_at), parse_time(local_updated_at)) {
+        (Some(remote), Some(local)) if remote > local => Decision::Overwrite,
+        (Some(_), None) => Decision::Overwrite,
+        _ => Decision::Unchanged,
+    }
+}
+
+/// Entity content without its timestamps.
+fn content<T: Serialize>(entity: &T) -> serde_json::Value {
+    let mut value = serde_json::to_value(entity).unwrap_or_default();
+    if let Some(object) = value.as_object_mut() {
+        object.remove("createdAt");
+        object.remove("updatedAt");
+    }
+    value
+}
+
+fn parse_time(value: Option<&str>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Remote MCP server with its cleared env values replaced by the local ones.
+fn with_local_env(
+    remote: &MCPServerExportData,
+    local: &MCPServerExportData,
+) -> (MCPServerExportData, HashMap<String, String>) {
+    let kept: HashMap<String, String> = remote
+        .env
+        .iter()
+        .filter(|(_, value)| value.is_empty())
+        .filter_map(|(key, _)| {
+            local
+                .env
+                .get(key)
+                .filter(|value| !value.is_empty())
+                .map(|value| (key.clone(), value.clone()))
+        })
+        .collect();
+    let mut effective = remote.clone();
+    effective.env.extend(kept.clone());
+    (effective, kept)
+}
+
+/// Plans the merge of a peer package into the local one.
+///
+/// # Arguments
+/// * `remote` - Package received from the peer (sanitized)
+/// * `local` - Local package (not sanitized), with timestamps
+pub fn plan_merge(remote: &ExportPackage, local: &ExportPackage) -> MergePlan {
+    let mut plan = MergePlan {
+        selection: ImportSelection {
+            agents: Vec::new(),
+            mcp_servers: Vec::new(),
+            models: Vec::new(),
+            prompts: Vec::new(),
+        },
+        resolutions: HashMap::new(),
+        mcp_additions: HashMap::new(),
+        unchanged: ImportCounts::default(),
+    };
+
+    for agent in &remote.agents {
+        let local = local
+            .agents
+            .iter()
+            .find(|local| local.name == agent.name)
+            .map(|local| (local, local.updated_at.as_deref()));
+        match decide(agent, agent.updated_at.as_deref(), local) {
+            Decision::Create => plan.selection.agents.push(agent.name.clone()),
+            Decision::Overwrite => {
+                plan.selection.agents.push(agent.name.clone());
+                plan.resolutions.insert(
+                    format!("agent:{}", agent.name),
+                    ConflictResolution::Overwrite,
+                );
+            }
+            Decision::Unchanged => plan.unchanged.agents += 1,
+        }
+    }
+
+    for prompt in &remote.prompts {
+        let local = local
+            .prompts
+            .iter()
+            .find(|local| local.name == prompt.name)
+            .map(|local| (local, local.updated_at.as_deref()));
+        match decide(prompt, prompt.updated_at.as_deref(), local) {
+            Decision::Create => plan.selection.prompts.push(prompt.name.clone()),
+            Decision::Overwrite => {
+                plan.selection.prompts.push(prompt.name.clone());
+                plan.resolutions.insert(
+                    format!("prompt:{}", prompt.name),
+                    ConflictResolution::Overwrite,
+                );
+            }
+            Decision::Unchanged => plan.unchanged.prompts += 1,
+        }
+    }
+
+    for server in &remote.mcp_servers {
+        let local_server = local
+            .mcp_servers
+            .iter()
+            .find(|local| local.name == server.name);
+        let (effective, kept) = match local_server {
+            Some(local) => with_local_env(server, local),
+            None => (server.clone(), HashMap::new()),
+        };
+        let local = local_server.map(|local| (local, local.updated_at.as_deref()));
+        match decide(&effective, server.updated_at.as_deref(), local) {
+            Decision::Create => plan.selection.mcp_servers.push(server.name.clone()),
+            Decision::Overwrite => {
+                plan.selection.mcp_servers.push(server.name.clone());
+                plan.resolutions.insert(
+                    format!("mcp:{}", server.name),
+                    ConflictResolution::Overwrite,
+                );
+                if !kept.is_empty() {
+                    plan.mcp_additions.insert(
+                        server.name.clone(),
+                        MCPAdditions {
+                            add_env: kept,
+                            add_args: Vec::new(),
+                        },
+                    );
+                }
+            }
+            Decision::Unchanged => plan.unchanged.mcp_servers += 1,
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::import_export::PromptExportData;
+
+    fn prompt(name: &str, content: &str, updated_at: &str) -> PromptExportData {
+        PromptExportData {
+            name: name.to_string(),
+            description: String::new(),
+            category: "custom".to_string(),
+            content: content.to_string(),
+            created_at: None,
+            updated_at: Some(updated_at.to_string()),
+        }
+    }
+
+    fn server(name: &str, env: &[(&str, &str)], updated_at: &str) -> MCPServerExportData {
+        MCPServerExportData {
+            name: name.to_string(),
+            enabled: true,
+            command: "npx".to_string(),
+            args: vec!["-y".to_string(), "server".to_string()],
+            env: env
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            description: None,
+            created_at: None,
+            updated_at: Some(updated_at.to_string()),
+        }
+    }
+
+    fn package(
+        prompts: Vec<PromptExportData>,
+        mcp_servers: Vec<MCPServerExportData>,
+    ) -> ExportPackage {
+        ExportPackage::new(Vec::new(), mcp_servers, Vec::new(), prompts, None)
+    }
+
+    const OLD: &str = "2025-01-01T00:00:00Z";
+    const NEW: &str = "2025-06-01T00:00:00Z";
+
+    #[test]
+    fn test_plan_merge_prompts() {
+        let remote = package(
+            vec![
+                prompt("new", "a", NEW),
+                prompt("same", "b", NEW),
+                prompt("newer", "remote", NEW),
+                prompt("older", "remote", OLD),
+            ],
+            Vec::new(),
+        );
+        let local = package(
+            vec![
+                prompt("same", "b", OLD),
+                prompt("newer", "local", OLD),
+                prompt("older", "local", NEW),
+            ],
+            Vec::new(),
+        );
+
+        let plan = plan_merge(&remote, &local);
+        assert_eq!(plan.selection.prompts, vec!["new", "newer"]);
+        assert_eq!(plan.resolutions.len(), 1);
+        assert_eq!(
+            plan.resolutions.get("prompt:newer"),
+            Some(&ConflictResolution::Overwrite)
+        );
+        assert_eq!(plan.unchanged.prompts, 2);
+    }
+
+    #[test]
+    fn test_plan_merge_keeps_local_secrets() {
+        let mut remote = package(
+            Vec::new(),
+            vec![
+                server("github", &[("GITHUB_TOKEN", "remote-secret")], NEW),
+                server("search", &[("API_KEY", "remote"), ("REGION", "eu")], NEW),
+            ],
+        );
+        sanitize_for_sync(&mut remote);
+        assert_eq!(remote.mcp_servers[0].env["GITHUB_TOKEN"], "");
+        assert_eq!(remote.mcp_servers[1].env["REGION"], "eu");
+
+        let local = package(
+            Vec::new(),
+            vec![
+                // Same config apart from the cleared secret: nothing to do
+                server("github", &[("GITHUB_TOKEN", "local-secret")], OLD),
+                server("search", &[("API_KEY", "local"), ("REGION", "us")], OLD),
+            ],
+        );
+
+        let plan = plan_merge(&remote, &local);
+        assert_eq!(plan.selection.mcp_servers, vec!["search"]);
+        assert_eq!(plan.unchanged.mcp_servers, 1);
+        assert_eq!(plan.mcp_additions["search"].add_env["API_KEY"], "local");
+    }
+}
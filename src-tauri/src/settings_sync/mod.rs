@@ -0,0 +1,42 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Settings Sync
+//!
+//! End-to-end encrypted sync of agents, prompts and MCP server
+//! configurations between two machines of the same user, built on the
+//! import/export serializers.
+//!
+//! ```text
+//!  Desktop (initiator)                          Laptop (responder)
+//!  create_sync_pairing ──── pairing code / QR ───▶ accept_sync_pairing
+//!
+//!  sync_settings_now                     sync_settings_now
+//!   ├─ export, sanitize, seal ─▶ mailbox A ──────▶ open, merge
+//!   └─ open, merge ◀────────── mailbox B ◀─────── export, sanitize, seal
+//! ```
+//!
+//! - [`pairing`]: pairing codes carrying the pairing key (shown as QR code)
+//! - [`crypto`]: AES-256-GCM sealing and mailbox ID derivation
+//! - [`mailbox`]: mailbox client, and the server hosted by the initiator for
+//!   the local network transport
+//! - [`merge`]: conflict handling when applying a peer payload
+//!
+//! The mailbox server (relay or local) only ever sees opaque mailbox IDs and
+//! sealed payloads.
+
+pub mod crypto;
+pub mod mailbox;
+pub mod merge;
+pub mod pairing;
@@ -0,0 +1,156 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pairing codes.
+//!
+//! The code shown by the initiator (as text and QR code) carries everything
+//! the other machine needs: pairing ID, pairing key, transport and mailbox
+//! URL. It is the only channel the key travels on, so it must be transferred
+//! directly between the two machines (scan or copy), never through the relay.
+
+use super::crypto::KEY_SIZE;
+use crate::models::settings_sync::SyncTransport;
+use crate::tools::constants::sync as sync_const;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use qrcode::{render::svg, EcLevel, QrCode};
+use serde::{Deserialize, Serialize};
+
+/// Content of a pairing code
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairingCode {
+    /// Pairing ID (UUID)
+    pub pairing_id: String,
+    /// Pairing key
+    pub key: [u8; KEY_SIZE],
+    /// Payload transport
+    pub transport: SyncTransport,
+    /// Base URL of the mailbox server
+    pub mailbox_url: String,
+    /// Name of the initiator
+    pub device_name: String,
+}
+
+/// Serialized form (short field names keep the QR code small)
+#[derive(Serialize, Deserialize)]
+struct RawPairingCode {
+    p: String,
+    k: String,
+    t: SyncTransport,
+    m: String,
+    d: String,
+}
+
+impl PairingCode {
+    /// Encodes the code as `zileo-sync:1:<base64url(json)>`.
+    pub fn encode(&self) -> String {
+        let raw = RawPairingCode {
+            p: self.pairing_id.clone(),
+            k: URL_SAFE_NO_PAD.encode(self.key),
+            t: self.transport,
+            m: self.mailbox_url.clone(),
+            d: self.device_name.clone(),
+        };
+        let json = serde_json::to_vec(&raw).expect("pairing code serializes");
+        format!(
+            "{}{}",
+            sync_const::PAIRING_CODE_PREFIX,
+            URL_SAFE_NO_PAD.encode(json)
+        )
+    }
+
+    /// Decodes a pairing code (surrounding whitespace is ignored).
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let encoded = code
+            .trim()
+            .strip_prefix(sync_const::PAIRING_CODE_PREFIX)
+            .ok_or_else(|| "Not a Zileo pairing code".to_string())?;
+        let json = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| "Malformed pairing code".to_string())?;
+        let raw: RawPairingCode =
+            serde_json::from_slice(&json).map_err(|_| "Malformed pairing code".to_string())?;
+
+        let key: [u8; KEY_SIZE] = URL_SAFE_NO_PAD
+            .decode(&raw.k)
+            .ok()
+            .and_then(|key| key.try_into().ok())
+            .ok_or_else(|| "Malformed pairing key".to_string())?;
+
+        Ok(Self {
+            pairing_id: raw.p,
+            key,
+            transport: raw.t,
+            mailbox_url: raw.m,
+            device_name: raw.d,
+        })
+    }
+}
+
+/// Renders a pairing code as an SVG QR code.
+pub fn render_qr_svg(code: &str) -> Result<String, String> {
+    let qr = QrCode::with_error_correction_level(code.as_bytes(), EcLevel::M)
+        .map_err(|e| format!("Failed to create QR code: {}", e))?;
+    Ok(qr
+        .render::<svg::Color>()
+        .min_dimensions(256, 256)
+        .quiet_zone(true)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings_sync::crypto::generate_pairing_key;
+
+    fn code() -> PairingCode {
+        PairingCode {
+            pairing_id: uuid::Uuid::new_v4().to_string(),
+            key: generate_pairing_key(),
+            transport: SyncTransport::Lan,
+            mailbox_url: "http://192.168.1.20:48123".to_string(),
+            device_name: "Desktop".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let code = code();
+        let encoded = code.encode();
+        assert!(encoded.starts_with(sync_const::PAIRING_CODE_PREFIX));
+        assert_eq!(
+            PairingCode::decode(&format!(" {}\n", encoded)).unwrap(),
+            code
+        );
+
+        assert!(PairingCode::decode("zileo-sync:1:not-json").is_err());
+        assert!(PairingCode::decode("https://example.com").is_err());
+
+        // Truncated key
+        let raw = serde_json::json!({
+            "p": code.pairing_id, "k": "AAAA", "t": "relay", "m": "https://relay.example", "d": "x"
+        });
+        let encoded = format!(
+            "{}{}",
+            sync_const::PAIRING_CODE_PREFIX,
+            URL_SAFE_NO_PAD.encode(raw.to_string())
+        );
+        assert!(PairingCode::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_render_qr_svg() {
+        let svg = render_qr_svg(&code().encode()).unwrap();
+        assert!(svg.contains("<svg"));
+    }
+}
@@ -0,0 +1,422 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Speech Transcription
+//!
+//! Voice messages recorded in the frontend are transcribed by a local
+//! Whisper server, then sent as regular user messages. Two server APIs are
+//! supported (`settings:speech`, see `update_speech_settings`):
+//!
+//! - [`SpeechBackend::WhisperCpp`]: the whisper.cpp `server` example
+//!   (`POST /inference`)
+//! - [`SpeechBackend::OpenAiCompatible`]: servers exposing
+//!   `POST /v1/audio/transcriptions` (faster-whisper-server, LocalAI...)
+//!
+//! Audio never leaves the machine as long as the endpoint is local.
+//!
+//! In the other direction, [`read_aloud`] prepares a plain text and SSML
+//! version of responses, spoken by a text-to-speech backend ([`tts`]).
+
+pub mod read_aloud;
+pub mod tts;
+
+use reqwest::multipart::{Form, Part};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::tools::constants::speech as speech_const;
+
+/// Audio types accepted for transcription: (MIME type, file extension)
+pub const SUPPORTED_AUDIO_TYPES: &[(&str, &str)] = &[
+    ("audio/wav", "wav"),
+    ("audio/webm", "webm"),
+    ("audio/ogg", "ogg"),
+    ("audio/mpeg", "mp3"),
+    ("audio/mp4", "m4a"),
+    ("audio/flac", "flac"),
+];
+
+/// API of the transcription server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechBackend {
+    /// whisper.cpp server (`/inference`)
+    #[default]
+    WhisperCpp,
+    /// OpenAI-compatible server (`/v1/audio/transcriptions`)
+    OpenAiCompatible,
+}
+
+/// Transcription server settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeechConfig {
+    /// Server API
+    #[serde(default)]
+    pub backend: SpeechBackend,
+    /// Base URL of the server (e.g. "http://127.0.0.1:8080")
+    pub endpoint: String,
+    /// Model name sent to OpenAI-compatible servers (whisper.cpp loads its model at startup)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Default spoken language (ISO 639-1 code), None for auto-detection
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Prepare a read-aloud version of each assistant response when it is saved
+    #[serde(default)]
+    pub read_aloud: bool,
+}
+
+impl Default for SpeechConfig {
+    fn default() -> Self {
+        Self {
+            backend: SpeechBackend::default(),
+            endpoint: speech_const::DEFAULT_ENDPOINT.to_string(),
+            model: None,
+            language: None,
+            read_aloud: false,
+        }
+    }
+}
+
+impl SpeechConfig {
+    /// Returns the URL of the transcription route of the configured backend.
+    pub fn transcription_url(&self) -> String {
+        let base = self.endpoint.trim().trim_end_matches('/');
+        match self.backend {
+            SpeechBackend::WhisperCpp => format!("{}/inference", base),
+            SpeechBackend::OpenAiCompatible => format!("{}/v1/audio/transcriptions", base),
+        }
+    }
+}
+
+/// Result of a transcription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transcription {
+    /// Transcribed text
+    pub text: String,
+    /// Language detected by the server, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Audio duration in seconds, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+}
+
+/// Detects the audio type of a recording from its signature.
+///
+/// Returns the MIME type, or None if the content is not a supported format.
+pub fn detect_audio_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WAVE" {
+        Some("audio/wav")
+    } else if bytes.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        Some("audio/webm")
+    } else if bytes.starts_with(b"OggS") {
+        Some("audio/ogg")
+    } else if bytes.starts_with(b"fLaC") {
+        Some("audio/flac")
+    } else if bytes.len() >= 8 && &bytes[4..8] == b"ftyp" {
+        Some("audio/mp4")
+    } else if bytes.starts_with(b"ID3")
+        // MPEG frame sync with a valid layer (layer 00 is AAC ADTS)
+        || (bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] & 0xE0 == 0xE0 && bytes[1] & 0x06 != 0)
+    {
+        Some("audio/mpeg")
+    } else {
+        None
+    }
+}
+
+/// Returns the file extension of a supported audio MIME type.
+pub fn audio_extension(mime_type: &str) -> Option<&'static str> {
+    SUPPORTED_AUDIO_TYPES
+        .iter()
+        .find(|(mime, _)| *mime == mime_type)
+        .map(|(_, ext)| *ext)
+}
+
+/// Validates a language code ("auto" or a 2-3 letter ISO 639 code).
+pub fn validate_language(language: &str) -> Result<(), String> {
+    let valid = language == "auto"
+        || ((2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase()));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid language '{}': expected an ISO 639-1 code (e.g. \"en\") or \"auto\"",
+            language
+        ))
+    }
+}
+
+/// Transcribes a recording with the configured server.
+///
+/// # Arguments
+/// * `client` - HTTP client
+/// * `config` - Server settings
+/// * `audio` - Recording content
+/// * `mime_type` - Audio type (see [`detect_audio_mime`])
+/// * `language` - Spoken language, overrides the configured default
+///
+/// # Errors
+/// The server is unreachable, answers with an error, or no speech is recognized.
+pub async fn transcribe(
+    client: &reqwest::Client,
+    config: &SpeechConfig,
+    audio: Vec<u8>,
+    mime_type: &str,
+    language: Option<&str>,
+) -> Result<Transcription, String> {
+    let extension = audio_extension(mime_type).unwrap_or("bin");
+    let file = Part::bytes(audio)
+        .file_name(format!("recording.{}", extension))
+        .mime_str(mime_type)
+        .map_err(|e| format!("Invalid audio type: {}", e))?;
+
+    let language = language.or(config.language.as_deref());
+    let mut form = Form::new()
+        .part("file", file)
+        .text("response_format", "json");
+    match config.backend {
+        SpeechBackend::WhisperCpp => {
+            form = form
+                .text("temperature", "0.0")
+                .text("language", language.unwrap_or("auto").to_string());
+        }
+        SpeechBackend::OpenAiCompatible => {
+            let model = config.model.as_deref().unwrap_or("whisper-1");
+            form = form.text("model", model.to_string());
+            if let Some(language) = language.filter(|language| *language != "auto") {
+                form = form.text("language", language.to_string());
+            }
+        }
+    }
+
+    let url = config.transcription_url();
+    let response = client
+        .post(&url)
+        .multipart(form)
+        .timeout(Duration::from_secs(speech_const::TIMEOUT_SECS))
+        .send()
+        .await
+        .map_err(|e| format!("Cannot reach transcription server at {}: {}", url, e))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read transcription response: {}", e))?;
+    if !status.is_success() {
+        let detail: String = body.chars().take(200).collect();
+        return Err(format!(
+            "Transcription server returned {}: {}",
+            status,
+            detail.trim()
+        ));
+    }
+
+    let value: Value = serde_json::from_str(&body)
+        .map_err(|e| format!("Invalid transcription response: {}", e))?;
+    parse_transcription(&value)
+}
+
+/// Parses a transcription response (`{"text": ...}`, optionally with
+/// `language` and `duration` as in verbose responses).
+fn parse_transcription(value: &Value) -> Result<Transcription, String> {
+    if let Some(error) = value.get("error") {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .or_else(|| error.as_str())
+            .unwrap_or("unknown error");
+        return Err(format!("Transcription failed: {}", message));
+    }
+
+    let text = value
+        .get("text")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "Transcription response has no text".to_string())?
+        .trim()
+        .to_string();
+    if text.is_empty() {
+        return Err("No speech detected in the recording".to_string());
+    }
+
+    Ok(Transcription {
+        text,
+        language: value
+            .get("language")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        duration_secs: value.get("duration").and_then(Value::as_f64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::test_server::{http, json_reply, spawn_mock_server};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    const WAV: &[u8] = b"RIFF\x24\0\0\0WAVEfmt ";
+
+    #[test]
+    fn test_detect_audio_mime() {
+        assert_eq!(detect_audio_mime(WAV), Some("audio/wav"));
+        assert_eq!(
+            detect_audio_mime(&[0x1A, 0x45, 0xDF, 0xA3, 0x9F]),
+            Some("audio/webm")
+        );
+        assert_eq!(detect_audio_mime(b"OggS\0\x02"), Some("audio/ogg"));
+        assert_eq!(detect_audio_mime(b"ID3\x04\0"), Some("audio/mpeg"));
+        assert_eq!(detect_audio_mime(&[0xFF, 0xFB, 0x90]), Some("audio/mpeg"));
+        assert_eq!(detect_audio_mime(b"\0\0\0\x20ftypM4A "), Some("audio/mp4"));
+        assert_eq!(detect_audio_mime(b"fLaC\0"), Some("audio/flac"));
+        // AAC ADTS and non-audio content
+        assert_eq!(detect_audio_mime(&[0xFF, 0xF1, 0x50]), None);
+        assert_eq!(detect_audio_mime(b"RIFF\x24\0\0\0WEBPVP8 "), None);
+        assert_eq!(detect_audio_mime(b""), None);
+    }
+
+    #[test]
+    fn test_validate_language() {
+        assert!(validate_language("en").is_ok());
+        assert!(validate_language("yue").is_ok());
+        assert!(validate_language("auto").is_ok());
+        assert!(validate_language("EN").is_err());
+        assert!(validate_language("english").is_err());
+        assert!(validate_language("").is_err());
+    }
+
+    #[test]
+    fn test_transcription_url() {
+        let mut config = SpeechConfig {
+            endpoint: "http://127.0.0.1:8080/".to_string(),
+            ..SpeechConfig::default()
+        };
+        assert_eq!(
+            config.transcription_url(),
+            "http://127.0.0.1:8080/inference"
+        );
+
+        config.backend = SpeechBackend::OpenAiCompatible;
+        assert_eq!(
+            config.transcription_url(),
+            "http://127.0.0.1:8080/v1/audio/transcriptions"
+        );
+    }
+
+    #[test]
+    fn test_parse_transcription() {
+        let transcription = parse_transcription(
+            &json!({"text": " Hello world.\n", "language": "en", "duration": 1.5}),
+        )
+        .unwrap();
+        assert_eq!(transcription.text, "Hello world.");
+        assert_eq!(transcription.language.as_deref(), Some("en"));
+        assert_eq!(transcription.duration_secs, Some(1.5));
+
+        assert!(parse_transcription(&json!({"text": "  "})).is_err());
+        assert!(
+            parse_transcription(&json!({"error": {"message": "bad audio"}}))
+                .unwrap_err()
+                .contains("bad audio")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_whisper_cpp() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let endpoint = spawn_mock_server(move |request| {
+            seen.lock().unwrap().push(request);
+            json_reply(&[], json!({"text": "Summarize the last report"}))
+        })
+        .await;
+
+        let config = SpeechConfig {
+            endpoint,
+            language: Some("fr".to_string()),
+            ..SpeechConfig::default()
+        };
+        let client = reqwest::Client::new();
+        let transcription = transcribe(&client, &config, WAV.to_vec(), "audio/wav", Some("en"))
+            .await
+            .unwrap();
+        assert_eq!(transcription.text, "Summarize the last report");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/inference");
+        let body = &requests[0].raw_body;
+        assert!(body.contains("filename=\"recording.wav\""));
+        // The language argument overrides the configured default
+        assert!(body.contains("name=\"language\"\r\n\r\nen\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_openai_compatible() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let endpoint = spawn_mock_server(move |request| {
+            seen.lock().unwrap().push(request);
+            json_reply(&[], json!({"text": "Bonjour"}))
+        })
+        .await;
+
+        let config = SpeechConfig {
+            backend: SpeechBackend::OpenAiCompatible,
+            endpoint,
+            model: Some("Systran/faster-whisper-small".to_string()),
+            language: None,
+            read_aloud: false,
+        };
+        let client = reqwest::Client::new();
+        transcribe(&client, &config, WAV.to_vec(), "audio/wav", None)
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].path, "/v1/audio/transcriptions");
+        let body = &requests[0].raw_body;
+        assert!(body.contains("name=\"model\"\r\n\r\nSystran/faster-whisper-small\r\n"));
+        // Auto-detection: no language field
+        assert!(!body.contains("name=\"language\""));
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_server_error() {
+        let endpoint = spawn_mock_server(|_| {
+            http("500 Internal Server Error", &[], "failed to read WAV file")
+        })
+        .await;
+
+        let config = SpeechConfig {
+            endpoint,
+            ..SpeechConfig::default()
+        };
+        let error = transcribe(
+            &reqwest::Client::new(),
+            &config,
+            WAV.to_vec(),
+            "audio/wav",
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("500"));
+        assert!(error.contains("failed to read WAV file"));
+    }
+}
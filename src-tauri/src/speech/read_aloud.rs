@@ -0,0 +1,361 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-aloud version of Markdown responses.
+//!
+//! Converts a response into plain text and SSML for a text-to-speech engine:
+//! Markdown syntax is removed, code blocks are replaced by a short mention,
+//! links keep their label (bare URLs their host) and common abbreviations
+//! are expanded. The result is stored next to the message, the Markdown
+//! content is never modified.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::models::message::ReadAloud;
+
+/// Language-specific wording of the read-aloud text
+struct Wording {
+    /// Spoken in place of a code block
+    code_omitted: &'static str,
+    /// Abbreviations and their spoken form
+    abbreviations: Lazy<Vec<(Regex, &'static str)>>,
+}
+
+static ENGLISH: Wording = Wording {
+    code_omitted: "Code block omitted.",
+    abbreviations: Lazy::new(|| {
+        abbreviation_patterns(&[
+            ("e.g.", "for example"),
+            ("i.e.", "that is"),
+            ("etc.", "et cetera"),
+            ("vs.", "versus"),
+            ("approx.", "approximately"),
+            ("a.k.a.", "also known as"),
+            ("cf.", "compare"),
+            ("w/o", "without"),
+            ("w/", "with"),
+        ])
+    }),
+};
+
+static FRENCH: Wording = Wording {
+    code_omitted: "Bloc de code omis.",
+    abbreviations: Lazy::new(|| {
+        abbreviation_patterns(&[
+            ("p. ex.", "par exemple"),
+            ("c.-à-d.", "c'est-à-dire"),
+            ("c-à-d", "c'est-à-dire"),
+            ("etc.", "et cetera"),
+            ("env.", "environ"),
+            ("cf.", "voir"),
+            ("vs.", "contre"),
+            ("svp", "s'il vous plaît"),
+        ])
+    }),
+};
+
+/// Abbreviations match between whitespace, brackets or punctuation
+fn abbreviation_patterns(
+    abbreviations: &[(&'static str, &'static str)],
+) -> Vec<(Regex, &'static str)> {
+    abbreviations
+        .iter()
+        .map(|(abbreviation, expansion)| {
+            let pattern = format!(
+                r"(?i)(^|[\s(\[])({})([\s,;:)\]]|$)",
+                regex::escape(abbreviation)
+            );
+            (
+                Regex::new(&pattern).expect("valid abbreviation pattern"),
+                *expansion,
+            )
+        })
+        .collect()
+}
+
+fn wording(language: Option<&str>) -> &'static Wording {
+    match language {
+        Some("fr") => &FRENCH,
+        _ => &ENGLISH,
+    }
+}
+
+static HEADING: Lazy<Regex> = Lazy::new(|| Regex::new(r"^#{1,6}\s+(.*?)\s*#*$").unwrap());
+static RULE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:(?:\*\s*){3,}|(?:-\s*){3,}|(?:_\s*){3,})$").unwrap());
+static LIST_ITEM: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:[-*+]|\d{1,9}[.)])\s+(?:\[[ xX]\]\s+)?(.*)$").unwrap());
+static TABLE_SEPARATOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\|?\s*:?-+:?\s*(?:\|\s*:?-+:?\s*)*\|?$").unwrap());
+
+static IMAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"!\[([^\]]*)\]\([^)]*\)").unwrap());
+static LINK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[([^\]]+)\](?:\([^)]*\)|\[[^\]]*\])").unwrap());
+static URL: Lazy<Regex> = Lazy::new(|| Regex::new(r"<?(https?://[^\s<>)]+)>?").unwrap());
+static INLINE_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"`+([^`]+)`+").unwrap());
+static HTML_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?[a-zA-Z][^>]*>").unwrap());
+static STRONG: Lazy<Regex> = Lazy::new(|| Regex::new(r"\*\*(.+?)\*\*|__(.+?)__").unwrap());
+static EMPHASIS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\*([^*\s](?:[^*]*[^*\s])?)\*|\b_([^_]+)_\b").unwrap());
+static STRIKETHROUGH: Lazy<Regex> = Lazy::new(|| Regex::new(r"~~(.+?)~~").unwrap());
+static WHITESPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\s+").unwrap());
+
+/// Block of the read-aloud text
+#[derive(Debug, PartialEq)]
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    /// Code block, spoken as [`Wording::code_omitted`]
+    Code,
+}
+
+/// Prepares the read-aloud version of a Markdown text.
+///
+/// # Arguments
+/// * `markdown` - Message content
+/// * `language` - Language of the text (ISO 639-1 code), English wording when unknown
+pub fn prepare(markdown: &str, language: Option<&str>) -> ReadAloud {
+    let wording = wording(language);
+    let blocks = parse_blocks(markdown, wording);
+
+    let text = blocks
+        .iter()
+        .map(|block| match block {
+            Block::Heading(text) | Block::Paragraph(text) => text.as_str(),
+            Block::Code => wording.code_omitted,
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let mut ssml = match language {
+        Some(language) => format!("<speak xml:lang=\"{}\">", escape_xml(language)),
+        None => "<speak>".to_string(),
+    };
+    for block in &blocks {
+        match block {
+            Block::Heading(text) => ssml.push_str(&format!(
+                "<p><emphasis level=\"moderate\">{}</emphasis></p><break time=\"400ms\"/>",
+                escape_xml(text)
+            )),
+            Block::Paragraph(text) => ssml.push_str(&format!("<p>{}</p>", escape_xml(text))),
+            Block::Code => ssml.push_str(&format!(
+                "<break time=\"300ms\"/><p>{}</p><break time=\"300ms\"/>",
+                escape_xml(wording.code_omitted)
+            )),
+        }
+    }
+    ssml.push_str("</speak>");
+
+    ReadAloud { text, ssml }
+}
+
+/// Splits Markdown into spoken blocks (one per heading, paragraph, list
+/// item, table row and code block).
+fn parse_blocks(markdown: &str, wording: &Wording) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph = String::new();
+    let mut fence: Option<&str> = None;
+
+    let flush = |paragraph: &mut String, blocks: &mut Vec<Block>| {
+        let text = sentence(&clean_inline(paragraph, wording));
+        if !text.is_empty() {
+            blocks.push(Block::Paragraph(text));
+        }
+        paragraph.clear();
+    };
+
+    for raw_line in markdown.lines() {
+        let mut line = raw_line.trim();
+
+        if let Some(marker) = fence {
+            if line.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| line.starts_with(m)) {
+            flush(&mut paragraph, &mut blocks);
+            fence = Some(marker);
+            blocks.push(Block::Code);
+            continue;
+        }
+
+        while let Some(rest) = line.strip_prefix('>') {
+            line = rest.trim_start();
+        }
+        if line.is_empty() || RULE.is_match(line) || TABLE_SEPARATOR.is_match(line) {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        if let Some(caps) = HEADING.captures(line) {
+            flush(&mut paragraph, &mut blocks);
+            let text = sentence(&clean_inline(&caps[1], wording));
+            if !text.is_empty() {
+                blocks.push(Block::Heading(text));
+            }
+        } else if line.starts_with('|') {
+            flush(&mut paragraph, &mut blocks);
+            let cells: Vec<&str> = line
+                .trim_matches('|')
+                .split('|')
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .collect();
+            paragraph.push_str(&cells.join(", "));
+            flush(&mut paragraph, &mut blocks);
+        } else if let Some(caps) = LIST_ITEM.captures(line) {
+            flush(&mut paragraph, &mut blocks);
+            paragraph.push_str(&caps[1]);
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(line);
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+
+    blocks
+}
+
+/// Removes inline Markdown and HTML, expands abbreviations
+fn clean_inline(text: &str, wording: &Wording) -> String {
+    let text = IMAGE.replace_all(text, "$1");
+    let text = LINK.replace_all(&text, "$1");
+    let text = URL.replace_all(&text, |caps: &Captures| {
+        reqwest::Url::parse(&caps[1])
+            .ok()
+            .and_then(|url| {
+                url.host_str()
+                    .map(|h| h.trim_start_matches("www.").to_string())
+            })
+            .unwrap_or_default()
+    });
+    let text = INLINE_CODE.replace_all(&text, "$1");
+    let text = HTML_TAG.replace_all(&text, "");
+    let text = STRONG.replace_all(&text, "$1$2");
+    let text = EMPHASIS.replace_all(&text, "$1$2");
+    let text = STRIKETHROUGH.replace_all(&text, "$1");
+
+    let mut text = text.into_owned();
+    for (pattern, expansion) in wording.abbreviations.iter() {
+        text = pattern
+            .replace_all(&text, |caps: &Captures| {
+                let capitalized = caps[2].starts_with(|c: char| c.is_uppercase());
+                let expansion = if capitalized {
+                    let mut chars = expansion.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars).collect())
+                        .unwrap_or_default()
+                } else {
+                    expansion.to_string()
+                };
+                format!("{}{}{}", &caps[1], expansion, &caps[3])
+            })
+            .into_owned();
+    }
+
+    WHITESPACE.replace_all(&text, " ").trim().to_string()
+}
+
+/// Ends a block with punctuation, so the TTS engine pauses between blocks
+fn sentence(text: &str) -> String {
+    match text.chars().last() {
+        None => String::new(),
+        Some(c) if ".!?:;…".contains(c) => text.to_string(),
+        Some(_) => format!("{}.", text),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepare_plain_text() {
+        let markdown = "## Setup steps\n\n\
+            Install **Rust** and run `cargo build`, e.g. on *Linux*:\n\n\
+            ```bash\ncurl https://sh.rustup.rs | sh\n```\n\n\
+            - See [the book](https://doc.rust-lang.org/book/)\n\
+            - Or https://www.rust-lang.org/learn\n\n\
+            > Tip: ~~never~~ always use <b>stable</b>\n\n\
+            | Tool | Version |\n|------|:-------:|\n| rustc | 1.80 |\n\n---\n";
+
+        let read_aloud = prepare(markdown, Some("en"));
+        assert_eq!(
+            read_aloud.text,
+            "Setup steps.\n\n\
+             Install Rust and run cargo build, for example on Linux:\n\n\
+             Code block omitted.\n\n\
+             See the book.\n\n\
+             Or rust-lang.org.\n\n\
+             Tip: never always use stable.\n\n\
+             Tool, Version.\n\n\
+             rustc, 1.80."
+        );
+        // The code is not read
+        assert!(!read_aloud.text.contains("curl"));
+    }
+
+    #[test]
+    fn test_prepare_ssml() {
+        let read_aloud = prepare("# Q&A\n\nUse x < y i.e. a bound\n\n```\nx\n```", Some("en"));
+        assert_eq!(
+            read_aloud.ssml,
+            "<speak xml:lang=\"en\">\
+             <p><emphasis level=\"moderate\">Q&amp;A.</emphasis></p><break time=\"400ms\"/>\
+             <p>Use x &lt; y that is a bound.</p>\
+             <break time=\"300ms\"/><p>Code block omitted.</p><break time=\"300ms\"/>\
+             </speak>"
+        );
+        assert!(prepare("text", None).ssml.starts_with("<speak><p>"));
+    }
+
+    #[test]
+    fn test_abbreviations() {
+        assert_eq!(
+            prepare("E.g. apples, pears etc.", None).text,
+            "For example apples, pears et cetera."
+        );
+        // Only whole abbreviations are expanded
+        assert_eq!(
+            prepare("See the file svp.txt", Some("fr")).text,
+            "See the file svp.txt."
+        );
+        assert_eq!(
+            prepare("Du code, p. ex. en Rust", Some("fr")).text,
+            "Du code, par exemple en Rust."
+        );
+        assert_eq!(
+            prepare("```\nfn main() {}\n```", Some("fr")).text,
+            "Bloc de code omis."
+        );
+        // snake_case identifiers are not emphasis
+        assert_eq!(
+            prepare("Call my_func_name now", None).text,
+            "Call my_func_name now."
+        );
+    }
+}
@@ -0,0 +1,479 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text-to-speech.
+//!
+//! Replies are split into segments of a few sentences. Each segment is
+//! synthesized by the configured backend (`settings:tts`, see
+//! `update_tts_settings`) and its audio is streamed to the frontend in
+//! chunks, so playback starts before the whole reply is synthesized:
+//!
+//! - [`TtsBackend::Piper`]: piper HTTP server (`POST /`, WAV), local
+//! - [`TtsBackend::OpenAi`]: OpenAI `POST /v1/audio/speech` (MP3), or any
+//!   server exposing the same API
+//!
+//! Each workflow can override the voice and speaking rate ([`VoiceSettings`]).
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+use crate::tools::constants::speech as speech_const;
+
+/// Text-to-speech backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TtsBackend {
+    /// piper HTTP server, runs locally
+    #[default]
+    Piper,
+    /// OpenAI speech API (or a compatible server)
+    OpenAi,
+}
+
+/// Text-to-speech settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TtsConfig {
+    /// Backend
+    #[serde(default)]
+    pub backend: TtsBackend,
+    /// Base URL of the server (e.g. "http://127.0.0.1:5000")
+    pub endpoint: String,
+    /// Model sent to OpenAI (piper loads its voices at startup)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Default voice (piper voice name or OpenAI voice, e.g. "alloy")
+    #[serde(default)]
+    pub voice: Option<String>,
+    /// Default speaking rate (1.0 is the natural rate of the voice)
+    #[serde(default)]
+    pub speed: Option<f32>,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            backend: TtsBackend::default(),
+            endpoint: speech_const::DEFAULT_TTS_ENDPOINT.to_string(),
+            model: None,
+            voice: None,
+            speed: None,
+        }
+    }
+}
+
+impl TtsConfig {
+    /// Returns the URL of the synthesis route of the configured backend.
+    pub fn speech_url(&self) -> String {
+        let base = self.endpoint.trim().trim_end_matches('/');
+        match self.backend {
+            TtsBackend::Piper => format!("{}/", base),
+            TtsBackend::OpenAi => format!("{}/v1/audio/speech", base),
+        }
+    }
+
+    /// Returns the type of the audio produced by the backend.
+    pub fn mime_type(&self) -> &'static str {
+        match self.backend {
+            TtsBackend::Piper => "audio/wav",
+            TtsBackend::OpenAi => "audio/mpeg",
+        }
+    }
+
+    /// Applies the voice settings of a workflow over the defaults.
+    pub fn with_voice(mut self, voice: &VoiceSettings) -> Self {
+        if voice.voice.is_some() {
+            self.voice = voice.voice.clone();
+        }
+        if voice.speed.is_some() {
+            self.speed = voice.speed;
+        }
+        self
+    }
+
+    /// Builds the JSON body of a synthesis request.
+    fn request_body(&self, text: &str) -> Value {
+        match self.backend {
+            TtsBackend::Piper => {
+                let mut body = json!({ "text": text });
+                if let Some(voice) = &self.voice {
+                    body["voice"] = json!(voice);
+                }
+                // piper stretches phonemes: a faster rate is a shorter length
+                if let Some(speed) = self.speed {
+                    body["length_scale"] = json!(1.0 / speed);
+                }
+                body
+            }
+            TtsBackend::OpenAi => json!({
+                "model": self.model.as_deref().unwrap_or("tts-1"),
+                "input": text,
+                "voice": self.voice.as_deref().unwrap_or("alloy"),
+                "response_format": "mp3",
+                "speed": self.speed.unwrap_or(1.0),
+            }),
+        }
+    }
+}
+
+/// Voice settings of a workflow (`workflow.voice`), override [`TtsConfig`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoiceSettings {
+    /// Voice
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub voice: Option<String>,
+    /// Speaking rate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+}
+
+/// Validates a voice name and speaking rate.
+pub fn validate_voice(voice: Option<&str>, speed: Option<f32>) -> Result<(), String> {
+    if let Some(voice) = voice {
+        let valid = !voice.is_empty()
+            && voice.len() <= 100
+            && voice
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+        if !valid {
+            return Err(format!("Invalid voice name '{}'", voice));
+        }
+    }
+    if let Some(speed) = speed {
+        if !speech_const::TTS_SPEED_RANGE.contains(&speed) {
+            return Err(format!(
+                "Speaking rate must be between {} and {}",
+                speech_const::TTS_SPEED_RANGE.start(),
+                speech_const::TTS_SPEED_RANGE.end()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Kind of a [`SpeechAudioChunk`] event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechAudioKind {
+    /// Audio bytes of the current segment
+    Audio,
+    /// The segment is complete and can be played
+    SegmentEnd,
+    /// All segments are synthesized (or synthesis was stopped)
+    Done,
+    /// Synthesis failed
+    Error,
+}
+
+/// Audio streamed to the frontend (`speech_audio` event)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechAudioChunk {
+    /// Synthesis request ID (chosen by the frontend)
+    pub request_id: String,
+    /// Event kind
+    pub kind: SpeechAudioKind,
+    /// Segment index
+    pub segment: u32,
+    /// Audio type of the segment
+    pub mime_type: String,
+    /// Audio bytes as base64 (audio events)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// Error message (error events)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Outcome of a synthesis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechSynthesis {
+    /// Synthesis request ID
+    pub request_id: String,
+    /// Segments synthesized
+    pub segments: u32,
+    /// Audio bytes streamed
+    pub bytes: usize,
+    /// Audio type of the segments
+    pub mime_type: String,
+    /// Whether synthesis was stopped before the end
+    pub stopped: bool,
+}
+
+/// Splits a text into segments of whole sentences of about `max_chars`.
+///
+/// Sentences longer than `max_chars` are split between words.
+pub fn split_segments(text: &str, max_chars: usize) -> Vec<String> {
+    let pieces = sentences(text).into_iter().flat_map(|sentence| {
+        if sentence.chars().count() <= max_chars {
+            vec![sentence.to_string()]
+        } else {
+            join_within(sentence.split_whitespace(), max_chars)
+        }
+    });
+    join_within(pieces, max_chars)
+}
+
+/// Joins pieces with spaces into strings of at most `max_chars` (unless a
+/// single piece is longer)
+fn join_within<I, S>(pieces: I, max_chars: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut joined = Vec::new();
+    let mut current = String::new();
+    for piece in pieces {
+        let piece = piece.as_ref();
+        if !current.is_empty() && current.chars().count() + 1 + piece.chars().count() > max_chars {
+            joined.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(piece);
+    }
+    if !current.is_empty() {
+        joined.push(current);
+    }
+    joined
+}
+
+/// Splits a text after sentence punctuation and at line breaks
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let sentence_end = matches!(c, '.' | '!' | '?' | '…')
+            && chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if sentence_end || c == '\n' {
+            let end = i + c.len_utf8();
+            sentences.push(text[start..end].trim());
+            start = end;
+        }
+    }
+    sentences.push(text[start..].trim());
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
+/// Synthesizes a segment, passing the audio to `on_audio` as it arrives
+/// (in chunks of up to `TTS_CHUNK_BYTES`).
+///
+/// # Arguments
+/// * `client` - HTTP client
+/// * `config` - Backend settings (with the workflow voice applied)
+/// * `api_key` - Bearer token (OpenAI)
+/// * `text` - Segment text
+/// * `cancellation` - Stops the download
+/// * `on_audio` - Receives the audio chunks
+///
+/// # Returns
+/// Number of audio bytes received
+///
+/// # Errors
+/// The server is unreachable or answers with an error.
+pub async fn synthesize_segment<F>(
+    client: &reqwest::Client,
+    config: &TtsConfig,
+    api_key: Option<&str>,
+    text: &str,
+    cancellation: &CancellationToken,
+    mut on_audio: F,
+) -> Result<usize, String>
+where
+    F: FnMut(&[u8]),
+{
+    let url = config.speech_url();
+    let mut request = client
+        .post(&url)
+        .json(&config.request_body(text))
+        .timeout(Duration::from_secs(speech_const::TTS_TIMEOUT_SECS));
+    if let Some(api_key) = api_key {
+        request = request.bearer_auth(api_key);
+    }
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| format!("Cannot reach speech server at {}: {}", url, e))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        let detail: String = body.chars().take(200).collect();
+        return Err(format!(
+            "Speech server returned {}: {}",
+            status,
+            detail.trim()
+        ));
+    }
+
+    let mut total = 0;
+    let mut buffer = Vec::with_capacity(speech_const::TTS_CHUNK_BYTES);
+    loop {
+        let chunk = tokio::select! {
+            _ = cancellation.cancelled() => return Ok(total),
+            chunk = response.chunk() => {
+                chunk.map_err(|e| format!("Failed to read speech audio: {}", e))?
+            }
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
+        total += chunk.len();
+        buffer.extend_from_slice(&chunk);
+        if buffer.len() >= speech_const::TTS_CHUNK_BYTES {
+            on_audio(&buffer);
+            buffer.clear();
+        }
+    }
+    if !buffer.is_empty() {
+        on_audio(&buffer);
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::test_server::{http, spawn_mock_server};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_split_segments() {
+        let text = "First sentence. Second one! Third?\nA list item\n\nVersion 1.5 is out.";
+        assert_eq!(
+            split_segments(text, 400),
+            vec!["First sentence. Second one! Third? A list item Version 1.5 is out."]
+        );
+        assert_eq!(
+            split_segments(text, 30),
+            vec![
+                "First sentence. Second one!",
+                "Third? A list item",
+                "Version 1.5 is out."
+            ]
+        );
+
+        // A sentence longer than a segment is split between words
+        let segments = split_segments(&"word ".repeat(50), 40);
+        assert!(segments.iter().all(|s| s.chars().count() <= 40));
+        assert_eq!(segments.join(" "), "word ".repeat(50).trim());
+
+        assert!(split_segments("  \n ", 40).is_empty());
+    }
+
+    #[test]
+    fn test_request_body() {
+        let config = TtsConfig {
+            speed: Some(2.0),
+            ..TtsConfig::default()
+        }
+        .with_voice(&VoiceSettings {
+            voice: Some("fr_FR-siwis-medium".to_string()),
+            speed: None,
+        });
+        assert_eq!(config.speech_url(), "http://127.0.0.1:5000/");
+        assert_eq!(
+            config.request_body("Bonjour"),
+            json!({"text": "Bonjour", "voice": "fr_FR-siwis-medium", "length_scale": 0.5})
+        );
+
+        let config = TtsConfig {
+            backend: TtsBackend::OpenAi,
+            endpoint: "https://api.openai.com/".to_string(),
+            ..TtsConfig::default()
+        };
+        assert_eq!(
+            config.speech_url(),
+            "https://api.openai.com/v1/audio/speech"
+        );
+        assert_eq!(config.mime_type(), "audio/mpeg");
+        assert_eq!(config.request_body("Hi")["voice"], "alloy");
+        assert_eq!(config.request_body("Hi")["model"], "tts-1");
+    }
+
+    #[test]
+    fn test_validate_voice() {
+        assert!(validate_voice(Some("en_US-lessac-medium"), Some(1.2)).is_ok());
+        assert!(validate_voice(None, None).is_ok());
+        assert!(validate_voice(Some("../voices"), None).is_err());
+        assert!(validate_voice(Some(""), None).is_err());
+        assert!(validate_voice(None, Some(0.1)).is_err());
+        assert!(validate_voice(None, Some(f32::NAN)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_synthesize_segment() {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let endpoint = spawn_mock_server(move |request| {
+            seen.lock().unwrap().push(request);
+            http(
+                "200 OK",
+                &[("Content-Type", "audio/mpeg")],
+                "ID3 fake audio",
+            )
+        })
+        .await;
+
+        let config = TtsConfig {
+            backend: TtsBackend::OpenAi,
+            endpoint,
+            voice: Some("nova".to_string()),
+            ..TtsConfig::default()
+        };
+        let mut audio = Vec::new();
+        let bytes = synthesize_segment(
+            &reqwest::Client::new(),
+            &config,
+            Some("sk-test"),
+            "Hello there.",
+            &CancellationToken::new(),
+            |chunk| audio.extend_from_slice(chunk),
+        )
+        .await
+        .unwrap();
+        assert_eq!(bytes, audio.len());
+        assert_eq!(audio, b"ID3 fake audio");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].path, "/v1/audio/speech");
+        assert_eq!(requests[0].header("authorization"), Some("Bearer sk-test"));
+        assert_eq!(requests[0].body["input"], "Hello there.");
+        assert_eq!(requests[0].body["voice"], "nova");
+    }
+
+    #[tokio::test]
+    async fn test_synthesize_segment_error() {
+        let endpoint = spawn_mock_server(|_| http("404 Not Found", &[], "voice not found")).await;
+        let config = TtsConfig {
+            endpoint,
+            ..TtsConfig::default()
+        };
+        let error = synthesize_segment(
+            &reqwest::Client::new(),
+            &config,
+            None,
+            "Hi",
+            &CancellationToken::new(),
+            |_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(error.contains("404"));
+        assert!(error.contains("voice not found"));
+    }
+}
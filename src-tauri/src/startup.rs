@@ -0,0 +1,286 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deferred application startup.
+//!
+//! [`AppState::new`] only opens the database (schema applied when it changed),
+//! so the window shows as soon as possible. Everything else runs in
+//! [`run_deferred_startup`] once the app handle is set, with the independent
+//! phases running concurrently:
+//!
+//! - LLM providers and embedding service restored from saved configuration
+//!   (stale or expiring API keys are then reported in the priority inbox)
+//! - builtin LLM models seeding
+//! - enabled MCP servers connection (the slowest phase: process spawns)
+//! - agents loaded from the database
+//!
+//! The scheduler of the scheduled workflow runs and the background job queue
+//! (which resumes the jobs interrupted by the previous session) start once all
+//! phases ended.
+//!
+//! The feature flags of the config file (`~/.zileo/config.toml`) disable the
+//! scheduler, the job queue, the MCP servers connection and the sync mailbox;
+//! its `default_provider` is selected once the providers are restored.
+//!
+//! The phases depending on the database ([`restore_profile_data`]) run again
+//! when the user switches to another profile.
+//!
+//! Each phase transition updates `AppState::startup_status` and emits the
+//! `startup_progress` event, which drives the frontend splash screen.
+
+use crate::commands::agent::load_agents_from_db;
+use crate::commands::models::insert_missing_builtin_models;
+use crate::commands::SecureKeyStore;
+use crate::llm::ProviderType;
+use crate::mcp::MCPManager;
+use crate::models::config_file::Feature;
+use crate::models::startup::{StartupPhase, StartupStatus};
+use crate::models::streaming::events;
+use crate::state::AppState;
+use crate::tools::memory::requeue_pending_embeddings;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Instant;
+use tauri::Emitter;
+use tracing::{debug, info, warn};
+
+/// Runs the deferred startup phases.
+///
+/// Must be called after the app handle is set in the state, so that agents
+/// and MCP supervision can emit events. Phase failures are logged and
+/// reported in the startup status; the application keeps running degraded.
+pub async fn run_deferred_startup(state: &AppState, keystore: &SecureKeyStore) {
+    let started = Instant::now();
+
+    if let Some(handle) = state.get_app_handle() {
+        // ToolFactory emits validation events for sub-agents without agent_context
+        state.tool_factory.set_app_handle(handle.clone()).await;
+        // MCP supervision emits mcp_server_status_changed events
+        state.mcp_manager.set_app_handle(handle).await;
+    }
+    MCPManager::start_health_checks(state.mcp_manager.clone(), None);
+
+    // Phases emitted before the frontend listens are recovered with get_startup_status
+    emit_status(state);
+
+    // Mailbox server of the local network sync pairings created on this machine
+    if state.config.is_enabled(Feature::LanSync) {
+        if let Err(e) = crate::commands::settings_sync::restore_lan_mailbox(&state.db).await {
+            warn!(error = %e, "Failed to start the sync mailbox server");
+        }
+    } else {
+        info!("Sync mailbox server disabled by the config file");
+    }
+
+    // Data of the active profile (outbox, inbox, providers, MCP servers, agents)
+    restore_profile_data(state, keystore).await;
+
+    // Scheduled runs and queued jobs need the agents and MCP servers loaded above
+    if state.config.is_enabled(Feature::Scheduler) {
+        state.scheduler.start(state.run_context());
+    } else {
+        info!("Scheduler disabled by the config file");
+    }
+    if state.config.is_enabled(Feature::BackgroundJobs) {
+        state.job_queue.start(state.run_context());
+    } else {
+        info!("Background jobs disabled by the config file");
+    }
+
+    info!(
+        duration_ms = started.elapsed().as_millis() as u64,
+        "Deferred startup completed"
+    );
+}
+
+/// Restores the data of the active profile.
+///
+/// Replays the persistence outbox, rotates the audit log, reconciles the
+/// pending items, then runs the phases restoring providers, embedding,
+/// models, MCP servers and agents. Also run after a profile switch.
+pub async fn restore_profile_data(state: &AppState, keystore: &SecureKeyStore) {
+    // Records queued in the persistence outbox during a previous session
+    match state.outbox.flush(&state.db).await {
+        Ok(result) if result.flushed > 0 || result.remaining > 0 => info!(
+            flushed = result.flushed,
+            remaining = result.remaining,
+            "Persistence outbox replayed"
+        ),
+        Ok(_) => {}
+        Err(e) => warn!(error = %e, "Failed to replay persistence outbox"),
+    }
+
+    // Audit log entries past the retention period or the size limit
+    let audit_config = crate::security::audit::load_audit_config(&state.db).await;
+    if let Err(e) = crate::security::audit::rotate(&state.db, &audit_config).await {
+        warn!(error = %e, "Failed to rotate audit log");
+    }
+
+    // Pending items of the previous session (runs that waited, crashed executions)
+    if let Err(e) = crate::inbox::reconcile_pending_items(&state.db).await {
+        warn!(error = %e, "Failed to reconcile pending items");
+    }
+
+    // Proxy of the provider, embedding and MCP requests made by the phases below
+    crate::proxy::set_saved(crate::commands::proxy::load_proxy_settings(&state.db).await);
+
+    // Prometheus endpoint and OTLP push of the metrics
+    let metrics_settings = crate::commands::metrics::load_metrics_settings(&state.db).await;
+    if let Err(e) = crate::metrics::export::apply_settings(&metrics_settings).await {
+        warn!(error = %e, "Failed to start metrics exporters");
+    }
+
+    tokio::join!(
+        run_phase(state, StartupPhase::Providers, async {
+            state.initialize_providers_from_config(keystore).await;
+            apply_default_provider(state).await;
+            Ok(())
+        }),
+        run_phase(state, StartupPhase::Embedding, async {
+            state.initialize_embedding_from_config(keystore).await;
+            let service = state.embedding_service.read().await.clone();
+            if let Some(service) = service {
+                requeue_pending_embeddings(&state.db, &service).await?;
+            }
+            Ok(())
+        }),
+        run_phase(state, StartupPhase::Models, async {
+            insert_missing_builtin_models(&state.db).await.map(|_| ())
+        }),
+        run_phase(state, StartupPhase::McpServers, async {
+            if !state.config.is_enabled(Feature::McpAutostart) {
+                info!("MCP servers autostart disabled by the config file");
+                return Ok(());
+            }
+            state
+                .mcp_manager
+                .load_from_db()
+                .await
+                .map_err(|e| e.to_string())?;
+            let count = state.mcp_manager.connected_count().await;
+            info!(count = count, "MCP servers loaded from database");
+            Ok(())
+        }),
+        run_phase(state, StartupPhase::Agents, async {
+            load_agents_from_db(state).await.map(|_| ())
+        }),
+    );
+
+    // Stale or expiring API keys (providers restored above)
+    crate::commands::security::check_api_key_expiry(&state.db, keystore).await;
+}
+
+/// Selects the `default_provider` of the config file, if set and configured.
+async fn apply_default_provider(state: &AppState) {
+    let Some(name) = state.config.default_provider() else {
+        return;
+    };
+    let result = match ProviderType::from_str(name) {
+        Ok(provider) => state.llm_manager.set_active_provider(provider).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => info!(provider = %name, "Default provider selected from the config file"),
+        Err(e) => {
+            warn!(provider = %name, error = %e, "Failed to select the config file default provider")
+        }
+    }
+}
+
+/// Runs one startup phase, recording and emitting its progress.
+async fn run_phase<F>(state: &AppState, phase: StartupPhase, work: F)
+where
+    F: Future<Output = Result<(), String>>,
+{
+    update_status(state, |status| status.start(phase));
+    let started = Instant::now();
+
+    let result = work.await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(()) => debug!(phase = ?phase, duration_ms = duration_ms, "Startup phase completed"),
+        Err(e) => {
+            warn!(phase = ?phase, duration_ms = duration_ms, error = %e, "Startup phase failed")
+        }
+    }
+
+    update_status(state, |status| {
+        status.finish(phase, duration_ms, result.err())
+    });
+}
+
+/// Applies a change to the startup status and emits the new status.
+fn update_status(state: &AppState, change: impl FnOnce(&mut StartupStatus)) {
+    if let Ok(mut guard) = state.startup_status.write() {
+        change(&mut guard);
+    }
+    emit_status(state);
+}
+
+/// Emits the current startup status (no-op before the app handle is set).
+fn emit_status(state: &AppState) {
+    let Some(handle) = state.get_app_handle() else {
+        return;
+    };
+    let status = match state.startup_status.read() {
+        Ok(guard) => guard.clone(),
+        Err(_) => return,
+    };
+    if let Err(e) = handle.emit(events::STARTUP_PROGRESS, &status) {
+        warn!(error = %e, "Failed to emit startup progress");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::startup::StartupPhaseStatus;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_run_deferred_startup() {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("startup_test_db");
+        let state = AppState::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create AppState");
+
+        {
+            let status = state.startup_status.read().unwrap();
+            assert_eq!(
+                status.status_of(StartupPhase::Database),
+                StartupPhaseStatus::Completed
+            );
+            assert_eq!(
+                status.status_of(StartupPhase::Agents),
+                StartupPhaseStatus::Pending
+            );
+            assert!(!status.ready);
+        }
+
+        let keystore = SecureKeyStore::new_without_encryption();
+        run_deferred_startup(&state, &keystore).await;
+
+        let status = state.startup_status.read().unwrap().clone();
+        assert!(status.ready);
+        for phase in StartupPhase::ALL {
+            assert_eq!(
+                status.status_of(phase),
+                StartupPhaseStatus::Completed,
+                "{:?}",
+                phase
+            );
+        }
+    }
+}
@@ -13,12 +13,20 @@
 // limitations under the License.
 
 use crate::agents::core::{AgentOrchestrator, AgentRegistry};
+use crate::config_file::LoadedConfig;
+use crate::db::attachments::AttachmentStore;
+use crate::db::outbox::PersistenceOutbox;
 use crate::db::DBClient;
+use crate::jobs::JobQueue;
 use crate::llm::embedding::EmbeddingService;
 use crate::llm::ProviderManager;
 use crate::mcp::MCPManager;
+use crate::models::startup::{StartupPhase, StartupStatus};
+use crate::profiles::{attachments_dir, outbox_path, ProfileStore};
+use crate::scheduler::{RunContext, Scheduler};
 use crate::tools::ToolFactory;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock as StdRwLock};
 use tauri::AppHandle;
 use tokio::sync::{Mutex, RwLock};
@@ -28,6 +36,10 @@ use tokio_util::sync::CancellationToken;
 pub struct AppState {
     /// Database client
     pub db: Arc<DBClient>,
+    /// Durable retry queue for persistence writes made during workflow execution
+    pub outbox: Arc<PersistenceOutbox>,
+    /// Blob storage of message attachments
+    pub attachments: Arc<AttachmentStore>,
     /// Agent registry
     pub registry: Arc<AgentRegistry>,
     /// Agent orchestrator
@@ -51,14 +63,64 @@ pub struct AppState {
     /// Tauri app handle for event emission (set after app initialization)
     /// Uses std::sync::RwLock for synchronous access in setup hook
     pub app_handle: Arc<StdRwLock<Option<AppHandle>>>,
+    /// Startup progress (database phase done on creation, other phases
+    /// completed by `startup::run_deferred_startup`)
+    pub startup_status: Arc<StdRwLock<StartupStatus>>,
+    /// Runner of the scheduled workflow runs (started by the deferred startup)
+    pub scheduler: Arc<Scheduler>,
+    /// Runner of the background jobs (started by the deferred startup)
+    pub job_queue: Arc<JobQueue>,
+    /// Profiles (isolated databases) and the active one
+    pub profiles: Arc<ProfileStore>,
+    /// Config file loaded at startup (`~/.zileo/config.toml`)
+    pub config: Arc<LoadedConfig>,
 }
 
 impl AppState {
     /// Creates new application state
+    ///
+    /// Only the database is initialized here; providers, MCP servers and agents
+    /// are restored by `startup::run_deferred_startup` once the window shows.
+    /// The default profile uses `db_path` (see [`AppState::for_active_profile`]).
+    #[allow(dead_code)] // Used by tests; the app opens the active profile
     pub async fn new(db_path: &str) -> anyhow::Result<Self> {
-        // Initialize database
+        let path = Path::new(db_path);
+        let profiles = ProfileStore::new(path.parent().unwrap_or(Path::new(".")), path);
+        Self::with_profiles(db_path, profiles, LoadedConfig::default()).await
+    }
+
+    /// Creates the application state on the database of the active profile.
+    ///
+    /// # Arguments
+    /// * `data_dir` - Data directory (default profile database in `<data_dir>/db`)
+    /// * `config` - Config file (may move the default profile database)
+    pub async fn for_active_profile(data_dir: &Path, config: LoadedConfig) -> anyhow::Result<Self> {
+        let profiles = ProfileStore::new(data_dir, config.default_db_path(data_dir));
+        let registry = profiles.load()?;
+        let db_path = profiles.db_path(&registry.active);
+        if let Some(dir) = db_path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        tracing::info!(profile_id = %registry.active, "Opening active profile");
+        Self::with_profiles(&db_path.to_string_lossy(), profiles, config).await
+    }
+
+    /// Creates the application state on `db_path`.
+    async fn with_profiles(
+        db_path: &str,
+        profiles: ProfileStore,
+        config: LoadedConfig,
+    ) -> anyhow::Result<Self> {
+        // Initialize database (schema skipped when unchanged since last run)
+        let db_started = std::time::Instant::now();
         let db = Arc::new(DBClient::new(db_path).await?);
         db.initialize_schema().await?;
+        let mut startup_status = StartupStatus::default();
+        startup_status.finish(
+            StartupPhase::Database,
+            db_started.elapsed().as_millis() as u64,
+            None,
+        );
 
         // Initialize agent registry and orchestrator
         let registry = Arc::new(AgentRegistry::new());
@@ -88,8 +150,19 @@ impl AppState {
         // Initialize app handle as None (set later in setup hook)
         let app_handle = Arc::new(StdRwLock::new(None));
 
+        // Outbox file next to the database directory
+        let outbox = Arc::new(PersistenceOutbox::new(
+            outbox_path(Path::new(db_path)),
+            app_handle.clone(),
+        ));
+
+        // Attachment blobs next to the database directory
+        let attachments = Arc::new(AttachmentStore::new(attachments_dir(Path::new(db_path))));
+
         Ok(Self {
             db,
+            outbox,
+            attachments,
             registry,
             orchestrator,
             llm_manager,
@@ -98,6 +171,11 @@ impl AppState {
             embedding_service,
             streaming_cancellations,
             app_handle,
+            startup_status: Arc::new(StdRwLock::new(startup_status)),
+            scheduler: Arc::new(Scheduler::new()),
+            job_queue: Arc::new(JobQueue::new()),
+            profiles: Arc::new(profiles),
+            config: Arc::new(config),
         })
     }
 
@@ -112,8 +190,19 @@ impl AppState {
         }
     }
 
+    /// Dependencies of the background runs (schedules and queued jobs).
+    pub fn run_context(&self) -> RunContext {
+        RunContext {
+            db: self.db.clone(),
+            registry: self.registry.clone(),
+            orchestrator: self.orchestrator.clone(),
+            llm_manager: self.llm_manager.clone(),
+            mcp_manager: self.mcp_manager.clone(),
+            app_handle: self.app_handle.clone(),
+        }
+    }
+
     /// Gets the app handle if available.
-    #[allow(dead_code)]
     pub fn get_app_handle(&self) -> Option<AppHandle> {
         self.app_handle.read().ok().and_then(|guard| guard.clone())
     }
@@ -192,6 +281,14 @@ impl AppState {
         &self,
         keystore: &crate::commands::SecureKeyStore,
     ) {
+        // Restore the retry policy for transient LLM failures
+        let retry_config = crate::commands::llm::load_llm_retry_settings(&self.db).await;
+        self.llm_manager.set_retry_config(retry_config).await;
+
+        // Restore the utility model of internal summarization passes
+        let utility_model = crate::commands::llm::load_utility_model_settings(&self.db).await;
+        self.llm_manager.set_utility_model(utility_model).await;
+
         // Initialize Ollama (local provider, always available)
         if let Err(e) = self.llm_manager.configure_ollama(None).await {
             tracing::warn!(error = %e, "Failed to initialize Ollama provider");
@@ -212,6 +309,21 @@ impl AppState {
             tracing::debug!("No Mistral API key found in keystore");
         }
 
+        // Initialize Gemini if API key is stored
+        if let Some(api_key) = keystore.get_key("Gemini") {
+            if !api_key.is_empty() {
+                if let Err(e) = self.llm_manager.configure_gemini(&api_key).await {
+                    tracing::warn!(error = %e, "Failed to initialize Gemini provider");
+                } else {
+                    tracing::info!("Gemini provider initialized from saved API key");
+                }
+            }
+        }
+        let safety_settings = crate::commands::llm::load_gemini_safety_settings(&self.db).await;
+        self.llm_manager
+            .set_gemini_safety_settings(safety_settings)
+            .await;
+
         // Initialize custom providers from database
         let query = "SELECT name, base_url FROM custom_provider WHERE enabled = true";
         match self.db.query_json(query).await {
@@ -229,13 +341,14 @@ impl AppState {
                     let provider = std::sync::Arc::new(
                         crate::llm::openai_compatible::OpenAiCompatibleProvider::new(
                             &name,
-                            self.llm_manager.http_client().clone(),
+                            crate::llm::ProviderManager::provider_http_client(&name),
                         ),
                     );
 
-                    // Configure with API key if available
-                    if let Some(api_key) = keystore.get_key(&name) {
-                        if !api_key.is_empty() {
+                    // Configure with API key if available; without one its
+                    // models can still be listed
+                    match keystore.get_key(&name).filter(|key| !key.is_empty()) {
+                        Some(api_key) => {
                             if let Err(e) = provider.configure(&api_key, &base_url).await {
                                 tracing::warn!(
                                     provider = %name,
@@ -244,6 +357,7 @@ impl AppState {
                                 );
                             }
                         }
+                        None => provider.set_base_url(&base_url).await,
                     }
 
                     self.llm_manager
@@ -266,19 +380,15 @@ impl AppState {
         &self,
         keystore: &crate::commands::SecureKeyStore,
     ) {
+        use crate::commands::embedding::EMBEDDING_CONFIG_KEY;
+        use crate::db::queries::settings;
         use crate::llm::embedding::{EmbeddingProvider, EmbeddingService};
         use crate::models::EmbeddingConfigSettings;
 
         tracing::info!("Initializing embedding service from saved configuration...");
 
-        // Load config from database using direct record access
-        // Note: Using backtick-escaped ID for direct access instead of WHERE clause
-        // to ensure correct record ID matching in SurrealDB
-        // Note: Using query_json and SELECT config (not SELECT *) to avoid
-        // SurrealDB SDK 2.x serialization issues with Thing enum type in id field
-        let query = "SELECT config FROM settings:`settings:embedding_config`";
-        let results: Vec<serde_json::Value> = match self.db.query_json(query).await {
-            Ok(r) => r,
+        let stored = match settings::load_config(&self.db, EMBEDDING_CONFIG_KEY).await {
+            Ok(stored) => stored,
             Err(e) => {
                 tracing::debug!(error = %e, "No embedding config found in database (this is normal on first run)");
                 return;
@@ -286,17 +396,14 @@ impl AppState {
         };
 
         // Parse config from result
-        let config: EmbeddingConfigSettings = match results
-            .first()
-            .and_then(|row| row.get("config"))
-            .and_then(|c| serde_json::from_value(c.clone()).ok())
-        {
-            Some(c) => c,
-            None => {
-                tracing::debug!("No embedding config stored, using defaults on first save");
-                return;
-            }
-        };
+        let config: EmbeddingConfigSettings =
+            match stored.and_then(|c| serde_json::from_value(c).ok()) {
+                Some(c) => c,
+                None => {
+                    tracing::debug!("No embedding config stored, using defaults on first save");
+                    return;
+                }
+            };
 
         tracing::info!(
             provider = %config.provider,
@@ -383,6 +490,10 @@ mod tests {
             system_prompt: "Test".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         let agent = SimpleAgent::new(config);
@@ -0,0 +1,244 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixture builders for command tests
+//!
+//! Fixtures are created through the same commands as the frontend uses, so
+//! the database and the runtime registries stay consistent.
+
+use super::TestApp;
+use crate::commands::agent::create_agent;
+use crate::commands::mcp::create_mcp_server;
+use crate::commands::message::save_message;
+use crate::commands::workflow::create_workflow;
+use crate::mcp::test_server::{accepted, json_reply, spawn_mock_server, MockRequest};
+use crate::models::mcp::{MCPDeploymentMethod, MCPServer, MCPServerConfig};
+use crate::models::{AgentConfigCreate, LLMConfig, Lifecycle};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Agent fixture (Mistral agent without tools by default)
+pub struct AgentFixture {
+    config: AgentConfigCreate,
+}
+
+impl AgentFixture {
+    /// Starts a permanent agent named `name`
+    pub fn new(name: &str) -> Self {
+        Self {
+            config: AgentConfigCreate {
+                name: name.to_string(),
+                lifecycle: Lifecycle::Permanent,
+                llm: LLMConfig {
+                    provider: "Mistral".to_string(),
+                    model: "mistral-large-latest".to_string(),
+                    temperature: 0.5,
+                    max_tokens: 4096,
+                },
+                tools: Vec::new(),
+                mcp_servers: Vec::new(),
+                system_prompt: "You are a helpful assistant.".to_string(),
+                max_tool_iterations: 50,
+                enable_thinking: false,
+                tool_settings: HashMap::new(),
+                mcp_tool_policy: HashMap::new(),
+                regressions: Default::default(),
+                moderation: Default::default(),
+                // Workspace defaults would make fixtures depend on settings
+                inherit_workspace_defaults: false,
+            },
+        }
+    }
+
+    /// Makes the agent temporary
+    pub fn temporary(mut self) -> Self {
+        self.config.lifecycle = Lifecycle::Temporary;
+        self
+    }
+
+    /// Sets the LLM provider and model
+    pub fn llm(mut self, provider: &str, model: &str) -> Self {
+        self.config.llm.provider = provider.to_string();
+        self.config.llm.model = model.to_string();
+        self
+    }
+
+    /// Adds a local tool (must be a registered tool name)
+    pub fn tool(mut self, tool: &str) -> Self {
+        self.config.tools.push(tool.to_string());
+        self
+    }
+
+    /// Adds an MCP server by name
+    pub fn mcp_server(mut self, server_name: &str) -> Self {
+        self.config.mcp_servers.push(server_name.to_string());
+        self
+    }
+
+    /// Sets the system prompt
+    pub fn system_prompt(mut self, prompt: &str) -> Self {
+        self.config.system_prompt = prompt.to_string();
+        self
+    }
+
+    /// Configuration passed to `create_agent`
+    pub fn build(self) -> AgentConfigCreate {
+        self.config
+    }
+
+    /// Creates the agent with `create_agent` and returns its ID
+    pub async fn create(self, app: &TestApp) -> String {
+        create_agent(self.build(), app.state())
+            .await
+            .expect("Failed to create agent fixture")
+    }
+}
+
+/// Workflow fixture with optional conversation messages
+pub struct WorkflowFixture {
+    name: String,
+    agent_id: String,
+    messages: Vec<(String, String)>,
+}
+
+impl WorkflowFixture {
+    /// Starts a workflow named `name` for the agent `agent_id`
+    pub fn new(name: &str, agent_id: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            agent_id: agent_id.to_string(),
+            messages: Vec::new(),
+        }
+    }
+
+    /// Adds a message (`user`, `assistant` or `system`)
+    pub fn message(mut self, role: &str, content: &str) -> Self {
+        self.messages.push((role.to_string(), content.to_string()));
+        self
+    }
+
+    /// Creates the workflow and its messages, returns the workflow ID
+    pub async fn create(self, app: &TestApp) -> String {
+        let workflow_id = create_workflow(self.name, self.agent_id, app.state())
+            .await
+            .expect("Failed to create workflow fixture");
+
+        for (role, content) in self.messages {
+            save_message(
+                workflow_id.clone(),
+                role,
+                content,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                app.state(),
+            )
+            .await
+            .expect("Failed to create message fixture");
+        }
+
+        workflow_id
+    }
+}
+
+/// Mock MCP server over the HTTP transport
+///
+/// Answers `initialize`, `ping`, the list methods and `tools/call`
+/// (echoing the arguments as text).
+#[derive(Default)]
+pub struct MockMcpServer {
+    tools: Vec<Value>,
+    resources: Vec<Value>,
+    prompts: Vec<Value>,
+}
+
+impl MockMcpServer {
+    /// Starts a server without tools, resources or prompts
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tool accepting any object
+    pub fn tool(mut self, name: &str, description: &str) -> Self {
+        self.tools.push(json!({
+            "name": name,
+            "description": description,
+            "inputSchema": {"type": "object"}
+        }));
+        self
+    }
+
+    /// Adds a text resource
+    pub fn resource(mut self, uri: &str, name: &str) -> Self {
+        self.resources
+            .push(json!({"uri": uri, "name": name, "mimeType": "text/plain"}));
+        self
+    }
+
+    /// Adds a prompt without arguments
+    pub fn prompt(mut self, name: &str, description: &str) -> Self {
+        self.prompts
+            .push(json!({"name": name, "description": description, "arguments": []}));
+        self
+    }
+
+    /// Starts the server and returns its URL
+    pub async fn spawn(self) -> String {
+        spawn_mock_server(move |request: MockRequest| {
+            let result = match request.rpc_method() {
+                "initialize" => json!({
+                    "protocolVersion": "2025-06-18",
+                    "capabilities": {"tools": {}, "resources": {}, "prompts": {}},
+                    "serverInfo": {"name": "mock", "version": "1.0"}
+                }),
+                "ping" => json!({}),
+                "tools/list" => json!({"tools": self.tools}),
+                "resources/list" => json!({"resources": self.resources}),
+                "prompts/list" => json!({"prompts": self.prompts}),
+                "tools/call" => json!({
+                    "content": [{"type": "text", "text": request.body["params"]["arguments"].to_string()}],
+                    "isError": false
+                }),
+                _ => return accepted(),
+            };
+            json_reply(
+                &[],
+                json!({"jsonrpc": "2.0", "id": request.body["id"], "result": result}),
+            )
+        })
+        .await
+    }
+
+    /// Registers a running mock server with `create_mcp_server`
+    pub async fn register(app: &TestApp, name: &str, url: &str) -> MCPServer {
+        let config = MCPServerConfig {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            enabled: true,
+            command: MCPDeploymentMethod::Http,
+            args: vec![url.to_string()],
+            env: HashMap::new(),
+            description: None,
+        };
+        create_mcp_server(config, app.state())
+            .await
+            .expect("Failed to register mock MCP server")
+    }
+}
@@ -0,0 +1,1011 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration test harness for the command layer
+//!
+//! Runs Tauri commands end-to-end without the Tauri runtime boilerplate:
+//! - [`TestApp`] - fully initialized [`AppState`] against a temporary database,
+//!   managed by a mock Tauri app so commands receive a real `State`
+//! - [`fixtures`] - builders for agents, workflows and mock MCP servers
+//! - [`snapshot`] - JSON snapshot assertions for command outputs
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let app = TestApp::new().await;
+//! let agent_id = AgentFixture::new("Researcher").tool("MemoryTool").create(&app).await;
+//!
+//! let agents = list_agents(app.state()).await.unwrap();
+//! assert_json_snapshot("list_agents", &agents);
+//! ```
+
+pub mod fixtures;
+pub mod snapshot;
+
+use crate::state::AppState;
+use tauri::test::{mock_app, MockRuntime};
+use tauri::{App, Manager, State};
+use tempfile::TempDir;
+
+pub use fixtures::{AgentFixture, MockMcpServer, WorkflowFixture};
+pub use snapshot::assert_json_snapshot;
+
+/// Application under test
+///
+/// Owns the mock Tauri app managing the [`AppState`] and the temporary
+/// directory of its database (removed on drop).
+pub struct TestApp {
+    app: App<MockRuntime>,
+    _temp_dir: TempDir,
+}
+
+impl TestApp {
+    /// Creates an application with an initialized schema and no agents,
+    /// workflows or MCP servers
+    pub async fn new() -> Self {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test_db");
+        let state = AppState::new(db_path.to_str().expect("Invalid temp path"))
+            .await
+            .expect("Failed to initialize AppState");
+
+        let app = mock_app();
+        app.manage(state);
+
+        Self {
+            app,
+            _temp_dir: temp_dir,
+        }
+    }
+
+    /// State to pass to commands
+    pub fn state(&self) -> State<'_, AppState> {
+        self.app.state::<AppState>()
+    }
+
+    /// Application state (direct access to the DB, registry, managers...)
+    pub fn app_state(&self) -> &AppState {
+        self.state().inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::language::pin_workflow_language;
+    use crate::commands::agent::{delete_agent, get_agent_config, list_agents};
+    use crate::commands::mcp::{
+        call_mcp_tool, list_mcp_prompts, list_mcp_resources, list_mcp_servers, list_mcp_tools,
+    };
+    use crate::commands::message::load_workflow_messages;
+    use crate::commands::schedule::{
+        create_schedule, delete_schedule, list_schedules, pause_schedule, resume_schedule,
+    };
+    use crate::commands::workflow::{
+        load_workflows, load_workflows_paginated, set_workflow_language, set_workflow_tags,
+    };
+    use crate::models::mcp::MCPToolCallRequest;
+    use crate::models::{WorkflowListQuery, WorkflowSortBy, WorkflowStatus};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_agent_commands_end_to_end() {
+        let app = TestApp::new().await;
+
+        let agent_id = AgentFixture::new("Researcher")
+            .tool("MemoryTool")
+            .system_prompt("You search the knowledge base.")
+            .create(&app)
+            .await;
+
+        assert!(app.app_state().registry.get(&agent_id).await.is_some());
+
+        let agents = list_agents(app.state()).await.unwrap();
+        assert_json_snapshot("agent_list", &agents);
+
+        let config = get_agent_config(agent_id.clone(), app.state())
+            .await
+            .unwrap();
+        assert_json_snapshot("agent_config", &config);
+
+        delete_agent(agent_id, app.state()).await.unwrap();
+        assert!(list_agents(app.state()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_workflow_commands_end_to_end() {
+        let app = TestApp::new().await;
+        let agent_id = AgentFixture::new("Writer").create(&app).await;
+
+        let workflow_id = WorkflowFixture::new("Draft release notes", &agent_id)
+            .message("user", "Summarize the changes")
+            .message("assistant", "Here is the summary.")
+            .create(&app)
+            .await;
+
+        let workflows = load_workflows(app.state()).await.unwrap();
+        assert_eq!(workflows.len(), 1);
+        assert_eq!(workflows[0].id, workflow_id);
+
+        let messages = load_workflow_messages(workflow_id, app.state())
+            .await
+            .unwrap();
+        assert_json_snapshot("workflow_messages", &messages);
+    }
+
+    #[tokio::test]
+    async fn test_message_generation_parameters_round_trip() {
+        use crate::commands::message::save_message;
+        use crate::models::GenerationParams;
+
+        let app = TestApp::new().await;
+        let agent_id = AgentFixture::new("Writer").create(&app).await;
+        let workflow_id = WorkflowFixture::new("Poem", &agent_id).create(&app).await;
+        let generation = GenerationParams {
+            temperature: Some(0.25),
+            top_p: Some(0.5),
+            max_tokens: Some(1024),
+            stop: vec!["THE END".to_string()],
+        };
+
+        save_message(
+            workflow_id.clone(),
+            "assistant".to_string(),
+            "Roses are red.".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(generation.clone()),
+            None,
+            app.state(),
+        )
+        .await
+        .unwrap();
+
+        let messages = load_workflow_messages(workflow_id, app.state())
+            .await
+            .unwrap();
+        assert_eq!(messages[0].generation, Some(generation));
+    }
+
+    #[tokio::test]
+    async fn test_message_attachments_end_to_end() {
+        use crate::commands::attachment::get_attachment_data;
+        use crate::commands::message::{delete_message, save_message};
+        use crate::models::attachment::AttachmentInput;
+
+        let app = TestApp::new().await;
+        let agent_id = AgentFixture::new("Viewer").create(&app).await;
+        let workflow_id = WorkflowFixture::new("Screenshot", &agent_id)
+            .create(&app)
+            .await;
+        let png = AttachmentInput {
+            data: Some("data:image/png;base64,iVBORw0KGgo=".to_string()),
+            path: None,
+            file_name: Some("screen.png".to_string()),
+        };
+
+        let save = |role: &str, attachments: Vec<AttachmentInput>| {
+            save_message(
+                workflow_id.clone(),
+                role.to_string(),
+                "What is on this screenshot?".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(attachments),
+                app.state(),
+            )
+        };
+
+        // System messages carry no attachments (user images, response charts only)
+        assert!(save("system", vec![png.clone()]).await.is_err());
+        let message_id = save("user", vec![png]).await.unwrap();
+
+        let messages = load_workflow_messages(workflow_id.clone(), app.state())
+            .await
+            .unwrap();
+        let attachment = &messages[0].attachments[0];
+        assert_eq!(attachment.mime_type, "image/png");
+        assert_eq!(attachment.file_name.as_deref(), Some("screen.png"));
+        assert_eq!(attachment.size_bytes, 8);
+
+        let data_url = get_attachment_data(attachment.id.clone(), app.state())
+            .await
+            .unwrap();
+        assert_eq!(data_url, "data:image/png;base64,iVBORw0KGgo=");
+
+        // Deleting the message removes its attachments
+        let attachment_id = attachment.id.clone();
+        delete_message(message_id, app.state()).await.unwrap();
+        assert!(get_attachment_data(attachment_id, app.state())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_workflow_language_pinning() {
+        let app = TestApp::new().await;
+        let agent_id = AgentFixture::new("Writer").create(&app).await;
+        let workflow_id = WorkflowFixture::new("Notes", &agent_id).create(&app).await;
+        let db = &app.app_state().db;
+        let pinned_language = || async {
+            load_workflows(app.state()).await.unwrap()[0]
+                .response_language
+                .clone()
+        };
+
+        // Too short to detect: nothing pinned yet
+        let pinned = pin_workflow_language(db, &workflow_id, None, "merci")
+            .await
+            .unwrap();
+        assert_eq!(pinned, None);
+
+        let pinned = pin_workflow_language(
+            db,
+            &workflow_id,
+            None,
+            "Peux-tu résumer les changements de cette version ?",
+        )
+        .await
+        .unwrap();
+        assert_eq!(pinned.as_deref(), Some("fr"));
+        assert_eq!(pinned_language().await.as_deref(), Some("fr"));
+
+        // Later messages keep the pinned language
+        let pinned = pin_workflow_language(
+            db,
+            &workflow_id,
+            Some("fr"),
+            "What is the date of the release?",
+        )
+        .await
+        .unwrap();
+        assert_eq!(pinned.as_deref(), Some("fr"));
+
+        set_workflow_language(workflow_id.clone(), Some("EN".to_string()), app.state())
+            .await
+            .unwrap();
+        assert_eq!(pinned_language().await.as_deref(), Some("en"));
+
+        assert!(
+            set_workflow_language(workflow_id.clone(), Some("xx".to_string()), app.state())
+                .await
+                .is_err()
+        );
+
+        set_workflow_language(workflow_id, None, app.state())
+            .await
+            .unwrap();
+        assert_eq!(pinned_language().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_workflow_list_sorting_filtering_pagination() {
+        let app = TestApp::new().await;
+        let writer = AgentFixture::new("Writer").create(&app).await;
+        let reviewer = AgentFixture::new("Reviewer").create(&app).await;
+        let beta = WorkflowFixture::new("beta", &writer).create(&app).await;
+        let alpha = WorkflowFixture::new("Alpha", &writer).create(&app).await;
+        let charlie = WorkflowFixture::new("charlie", &reviewer)
+            .create(&app)
+            .await;
+
+        let db = &app.app_state().db;
+        for (id, cost, updated_at, status) in [
+            (&beta, 0.5, "2026-01-03T00:00:00Z", "idle"),
+            (&alpha, 2.0, "2026-01-01T00:00:00Z", "completed"),
+            (&charlie, 1.0, "2026-01-02T00:00:00Z", "completed"),
+        ] {
+            db.execute_with_params(
+                "UPDATE type::thing('workflow', $id) SET total_cost_usd = $cost, \
+                 created_at = <datetime>$updated_at, updated_at = <datetime>$updated_at, \
+                 status = $status",
+                vec![
+                    ("id".to_string(), serde_json::json!(id)),
+                    ("cost".to_string(), serde_json::json!(cost)),
+                    ("updated_at".to_string(), serde_json::json!(updated_at)),
+                    ("status".to_string(), serde_json::json!(status)),
+                ],
+            )
+            .await
+            .unwrap();
+        }
+        let tags = set_workflow_tags(
+            alpha.clone(),
+            vec![
+                " Release ".to_string(),
+                "release".to_string(),
+                "docs".to_string(),
+            ],
+            app.state(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tags, vec!["release", "docs"]);
+        set_workflow_tags(charlie.clone(), vec!["release".to_string()], app.state())
+            .await
+            .unwrap();
+        assert!(
+            set_workflow_tags(beta.clone(), vec!["  ".to_string()], app.state())
+                .await
+                .is_err()
+        );
+
+        let page = |query: WorkflowListQuery| async {
+            let page = load_workflows_paginated(Some(query), app.state())
+                .await
+                .unwrap();
+            let ids: Vec<String> = page.workflows.iter().map(|w| w.id.clone()).collect();
+            (ids, page.total, page.has_more)
+        };
+
+        // Default: last activity first
+        let (ids, total, has_more) = page(WorkflowListQuery::default()).await;
+        assert_eq!(ids, vec![beta.clone(), charlie.clone(), alpha.clone()]);
+        assert_eq!((total, has_more), (3, false));
+
+        let (ids, _, _) = page(WorkflowListQuery {
+            sort_by: WorkflowSortBy::Cost,
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![alpha.clone(), charlie.clone(), beta.clone()]);
+
+        // Case-insensitive name sort
+        let (ids, _, _) = page(WorkflowListQuery {
+            sort_by: WorkflowSortBy::Name,
+            ascending: true,
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![alpha.clone(), beta.clone(), charlie.clone()]);
+
+        // Filters
+        let (ids, total, _) = page(WorkflowListQuery {
+            status: Some(WorkflowStatus::Completed),
+            tag: Some("RELEASE".to_string()),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![charlie.clone(), alpha.clone()]);
+        assert_eq!(total, 2);
+        let (ids, _, _) = page(WorkflowListQuery {
+            agent_id: Some(reviewer),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![charlie.clone()]);
+        let day = |d: &str| Some(d.parse::<chrono::DateTime<chrono::Utc>>().unwrap());
+        let (ids, total, _) = page(WorkflowListQuery {
+            created_after: day("2026-01-02T00:00:00Z"),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![beta.clone(), charlie.clone()]);
+        assert_eq!(total, 2);
+        let (ids, _, _) = page(WorkflowListQuery {
+            created_after: day("2026-01-01T00:00:00Z"),
+            created_before: day("2026-01-02T00:00:00Z"),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![alpha.clone()]);
+
+        // Pagination
+        let (ids, total, has_more) = page(WorkflowListQuery {
+            limit: Some(2),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![beta, charlie]);
+        assert_eq!((total, has_more), (3, true));
+        let (ids, _, has_more) = page(WorkflowListQuery {
+            limit: Some(2),
+            offset: Some(2),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(ids, vec![alpha]);
+        assert!(!has_more);
+    }
+
+    #[tokio::test]
+    async fn test_mcp_commands_end_to_end() {
+        let app = TestApp::new().await;
+        let url = MockMcpServer::new()
+            .tool("echo", "Echoes its arguments")
+            .resource("file:///notes.txt", "Notes")
+            .prompt("review", "Reviews a document")
+            .spawn()
+            .await;
+        let server = MockMcpServer::register(&app, "mock", &url).await;
+        assert_eq!(server.tools.len(), 1);
+
+        let agent_id = AgentFixture::new("Assistant")
+            .temporary()
+            .llm("Mistral", "mistral-small-latest")
+            .mcp_server("mock")
+            .create(&app)
+            .await;
+        let config = get_agent_config(agent_id, app.state()).await.unwrap();
+        assert_eq!(config.mcp_servers, vec!["mock"]);
+
+        let servers = list_mcp_servers(app.state()).await.unwrap();
+        assert_eq!(servers.len(), 1);
+
+        let resources = list_mcp_resources("mock".to_string(), None, app.state())
+            .await
+            .unwrap();
+        assert_json_snapshot("mcp_resources", &resources);
+
+        let prompts = list_mcp_prompts(Some("mock".to_string()), None, app.state())
+            .await
+            .unwrap();
+        assert_eq!(prompts.len(), 1);
+        assert_eq!(prompts[0].mcp_server.as_deref(), Some("mock"));
+
+        let tools = list_mcp_tools("mock".to_string(), app.state())
+            .await
+            .unwrap();
+        assert_json_snapshot("mcp_tools", &tools);
+
+        let result = call_mcp_tool(
+            MCPToolCallRequest {
+                server_name: "mock".to_string(),
+                tool_name: "echo".to_string(),
+                arguments: json!({"text": "hello"}),
+            },
+            app.state(),
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        assert_json_snapshot("mcp_echo_call", &result.content);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_commands_end_to_end() {
+        let app = TestApp::new().await;
+        let agent_id = AgentFixture::new("Digest").create(&app).await;
+
+        let schedule = create_schedule(
+            " Morning digest ".to_string(),
+            "0 9 * * mon-fri".to_string(),
+            agent_id.clone(),
+            "Summarize the news".to_string(),
+            None,
+            app.state(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(schedule.name, "Morning digest");
+        assert!(!schedule.paused);
+        assert!(schedule.next_run_at.unwrap() > chrono::Utc::now());
+        assert!(schedule.last_status.is_none());
+
+        // A workflow named after the schedule receives the runs
+        let workflows = load_workflows(app.state()).await.unwrap();
+        assert_eq!(workflows.len(), 1);
+        assert_eq!(workflows[0].id, schedule.workflow_id);
+        assert_eq!(workflows[0].name, "Morning digest");
+
+        let paused = pause_schedule(schedule.id.clone(), app.state())
+            .await
+            .unwrap();
+        assert!(paused.paused);
+        assert!(paused.next_run_at.is_none());
+        let resumed = resume_schedule(schedule.id.clone(), app.state())
+            .await
+            .unwrap();
+        assert!(!resumed.paused);
+        assert!(resumed.next_run_at.is_some());
+
+        // Invalid schedules
+        let create = |cron: &str, agent_id: &str, workflow_id: Option<String>| {
+            create_schedule(
+                "Other".to_string(),
+                cron.to_string(),
+                agent_id.to_string(),
+                "Prompt".to_string(),
+                workflow_id,
+                app.state(),
+            )
+        };
+        assert!(create("0 9 * *", &agent_id, None).await.is_err());
+        assert_eq!(
+            create("0 0 30 2 *", &agent_id, None).await.unwrap_err(),
+            "Cron expression never matches a date"
+        );
+        assert_eq!(
+            create("@daily", "missing_agent", None).await.unwrap_err(),
+            "Agent not found"
+        );
+        assert_eq!(
+            create("@daily", &agent_id, Some(uuid::Uuid::new_v4().to_string()))
+                .await
+                .unwrap_err(),
+            "Workflow not found"
+        );
+
+        assert_eq!(list_schedules(app.state()).await.unwrap().len(), 1);
+        delete_schedule(schedule.id, app.state()).await.unwrap();
+        assert!(list_schedules(app.state()).await.unwrap().is_empty());
+    }
+
+    /// Registers a SimpleAgent, which echoes the task without calling a provider.
+    async fn register_echo_agent(app: &TestApp, agent_id: &str) {
+        use crate::agents::SimpleAgent;
+        use crate::models::{AgentConfig, LLMConfig, Lifecycle};
+        use std::sync::Arc;
+
+        let config = AgentConfig {
+            id: agent_id.to_string(),
+            name: "Echo Agent".to_string(),
+            lifecycle: Lifecycle::Permanent,
+            llm: LLMConfig {
+                provider: "Demo".to_string(),
+                model: "test".to_string(),
+                temperature: 0.7,
+                max_tokens: 1000,
+            },
+            tools: vec![],
+            mcp_servers: vec![],
+            system_prompt: "Test".to_string(),
+            max_tool_iterations: 50,
+            enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
+        };
+        app.app_state()
+            .registry
+            .register(config.id.clone(), Arc::new(SimpleAgent::new(config)))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_run_end_to_end() {
+        use crate::commands::schedule::load_schedule;
+        use crate::models::schedule::ScheduleRunStatus;
+        use crate::scheduler::{set_run_times, Scheduler};
+        use std::sync::Arc;
+
+        let app = TestApp::new().await;
+        let state = app.app_state();
+        register_echo_agent(&app, "scheduled_agent").await;
+
+        let schedule = create_schedule(
+            "Inbox".to_string(),
+            "@hourly".to_string(),
+            "scheduled_agent".to_string(),
+            "Check the inbox".to_string(),
+            None,
+            app.state(),
+        )
+        .await
+        .unwrap();
+        let scheduler = Arc::new(Scheduler::new());
+
+        // Not due yet
+        let runs = scheduler.run_due(&state.run_context()).await.unwrap();
+        assert!(runs.is_empty());
+
+        let past = chrono::Utc::now() - chrono::Duration::minutes(5);
+        set_run_times(&state.db, &schedule.id, Some(past), None)
+            .await
+            .unwrap();
+        let runs = scheduler.run_due(&state.run_context()).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        for run in runs {
+            let event = run.await.unwrap();
+            assert_eq!(event.status, ScheduleRunStatus::Success);
+            assert_eq!(event.workflow_id, schedule.workflow_id);
+        }
+
+        let schedule = load_schedule(&state.db, &schedule.id).await.unwrap();
+        assert_eq!(schedule.last_status, Some(ScheduleRunStatus::Success));
+        assert!(schedule.last_run_at.is_some());
+        assert!(schedule.next_run_at.unwrap() > chrono::Utc::now());
+
+        let messages = load_workflow_messages(schedule.workflow_id.clone(), app.state())
+            .await
+            .unwrap();
+        let contents: Vec<&str> = messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["Check the inbox", "Check the inbox"]);
+        assert!(matches!(
+            messages[1].role,
+            crate::models::MessageRole::Assistant
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_enqueued_workflow_end_to_end() {
+        use crate::commands::job::{enqueue_workflow, get_job_status};
+        use crate::jobs::JobQueue;
+        use crate::models::job::JobStatus;
+        use std::sync::Arc;
+
+        let app = TestApp::new().await;
+        let state = app.app_state();
+        register_echo_agent(&app, "job_agent").await;
+        let workflow_id = WorkflowFixture::new("Background", "job_agent")
+            .create(&app)
+            .await;
+
+        let first = enqueue_workflow(
+            workflow_id.clone(),
+            "First".to_string(),
+            "job_agent".to_string(),
+            app.state(),
+        )
+        .await
+        .unwrap();
+        let second = enqueue_workflow(
+            workflow_id.clone(),
+            "Second".to_string(),
+            "job_agent".to_string(),
+            app.state(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.status, JobStatus::Queued);
+        assert_eq!(first.attempts, 0);
+
+        // The user messages are stored when enqueuing
+        let messages = load_workflow_messages(workflow_id.clone(), app.state())
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+
+        // One job at a time per workflow, in enqueue order
+        let queue = Arc::new(JobQueue::new());
+        for (job, message) in [(&first, "First"), (&second, "Second")] {
+            let started = queue.dispatch(&state.run_context()).await.unwrap();
+            assert_eq!(started.len(), 1);
+            for run in started {
+                let event = run.await.unwrap();
+                assert_eq!(event.job_id, job.id);
+                assert_eq!(event.status, JobStatus::Done);
+            }
+
+            let job = get_job_status(job.id.clone(), app.state()).await.unwrap();
+            assert_eq!(job.status, JobStatus::Done);
+            assert_eq!(job.attempts, 1);
+            assert!(job.finished_at.is_some());
+            let messages = load_workflow_messages(workflow_id.clone(), app.state())
+                .await
+                .unwrap();
+            let response = messages
+                .iter()
+                .find(|m| Some(&m.id) == job.response_message_id.as_ref())
+                .unwrap();
+            assert_eq!(response.content, message);
+        }
+        assert!(queue
+            .dispatch(&state.run_context())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Invalid requests
+        assert_eq!(
+            enqueue_workflow(
+                uuid::Uuid::new_v4().to_string(),
+                "Hello".to_string(),
+                "job_agent".to_string(),
+                app.state(),
+            )
+            .await
+            .unwrap_err(),
+            "Workflow not found"
+        );
+        assert_eq!(
+            enqueue_workflow(
+                workflow_id,
+                "Hello".to_string(),
+                "missing_agent".to_string(),
+                app.state(),
+            )
+            .await
+            .unwrap_err(),
+            "Agent not found"
+        );
+        assert_eq!(
+            get_job_status(uuid::Uuid::new_v4().to_string(), app.state())
+                .await
+                .unwrap_err(),
+            "Job not found"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_interrupted_execution() {
+        use crate::agents::checkpoint::{load_checkpoint, save_checkpoint};
+        use crate::commands::checkpoint::{
+            discard_workflow_checkpoint, list_resumable_executions, resume_workflow_execution,
+        };
+        use crate::models::checkpoint::{CheckpointState, WorkflowCheckpoint};
+
+        let app = TestApp::new().await;
+        let state = app.app_state();
+        register_echo_agent(&app, "resumed_agent").await;
+        let workflow_id = WorkflowFixture::new("Interrupted", "resumed_agent")
+            .create(&app)
+            .await;
+
+        assert_eq!(
+            resume_workflow_execution(workflow_id.clone(), app.state())
+                .await
+                .unwrap_err(),
+            "No interrupted execution to resume for this workflow"
+        );
+
+        // Checkpoint left behind by an execution killed mid-loop
+        let checkpoint = WorkflowCheckpoint {
+            workflow_id: workflow_id.clone(),
+            agent_id: "resumed_agent".to_string(),
+            iteration: 2,
+            state: CheckpointState {
+                task: "Summarize the report".to_string(),
+                context: serde_json::json!({ "locale": "en" }),
+                messages: vec![
+                    serde_json::json!({ "role": "system", "content": "You summarize" }),
+                    serde_json::json!({ "role": "user", "content": "Summarize the report" }),
+                ],
+                pending_tool_calls: vec![],
+                first_message: true,
+                tokens_input: 0,
+                tokens_output: 0,
+                tools_used: vec![],
+                mcp_calls: vec![],
+            },
+        };
+        save_checkpoint(&state.db, &checkpoint).await.unwrap();
+
+        let executions = list_resumable_executions(app.state()).await.unwrap();
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].workflow_id, workflow_id);
+        assert_eq!(executions[0].iteration, 2);
+
+        let message_id = resume_workflow_execution(workflow_id.clone(), app.state())
+            .await
+            .unwrap();
+        assert!(load_checkpoint(&state.db, &workflow_id)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(list_resumable_executions(app.state())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The system prompt of the interrupted first message is stored with the response
+        let messages = load_workflow_messages(workflow_id.clone(), app.state())
+            .await
+            .unwrap();
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().any(|m| {
+            matches!(m.role, crate::models::MessageRole::System) && m.content == "You summarize"
+        }));
+        let response = messages.iter().find(|m| m.id == message_id).unwrap();
+        assert_eq!(response.content, "Summarize the report");
+
+        save_checkpoint(&state.db, &checkpoint).await.unwrap();
+        discard_workflow_checkpoint(workflow_id.clone(), app.state())
+            .await
+            .unwrap();
+        assert!(list_resumable_executions(app.state())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_workflow_stops_agent_loop() {
+        use crate::agents::core::agent::{Agent, ReportStatus, Task};
+        use crate::agents::LLMAgent;
+        use crate::llm::fake::{FakeProvider, FakeScript, FAKE_MODEL, FAKE_PROVIDER_NAME};
+        use crate::models::{AgentConfig, LLMConfig, Lifecycle};
+        use crate::tools::AgentToolContext;
+
+        let app = TestApp::new().await;
+        let state = app.app_state();
+        let script: FakeScript = serde_json::from_value(serde_json::json!({
+            "turns": [{"content": "Never generated"}]
+        }))
+        .unwrap();
+        state
+            .llm_manager
+            .set_fake_provider(Some(FakeProvider::new(script)))
+            .await;
+
+        let config = AgentConfig {
+            id: "cancelled_agent".to_string(),
+            name: "Cancelled Agent".to_string(),
+            lifecycle: Lifecycle::Permanent,
+            llm: LLMConfig {
+                provider: FAKE_PROVIDER_NAME.to_string(),
+                model: FAKE_MODEL.to_string(),
+                temperature: 0.7,
+                max_tokens: 1000,
+            },
+            tools: vec![],
+            mcp_servers: vec![],
+            system_prompt: "Test".to_string(),
+            max_tool_iterations: 50,
+            enable_thinking: false,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
+        };
+        let agent = LLMAgent::with_context(
+            config,
+            state.llm_manager.clone(),
+            state.tool_factory.clone(),
+            AgentToolContext::from_app_state_full(state),
+        );
+
+        // Cancelled by cancel_workflow_streaming before the next iteration
+        let token = state.create_cancellation_token("wf_cancelled").await;
+        token.cancel();
+        let task = Task {
+            id: "task_cancelled".to_string(),
+            description: "Write a long report".to_string(),
+            context: serde_json::json!({
+                "workflow_id": "wf_cancelled",
+                "is_primary_agent": true
+            }),
+        };
+        let report = agent.execute_with_mcp(task, None).await.unwrap();
+
+        assert!(matches!(report.status, ReportStatus::Failed));
+        assert!(report.response.contains("cancelled"));
+        assert!(report.metrics.llm_turns.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_team_usage_export() {
+        use crate::commands::usage_stats::export_team_usage;
+        use crate::models::MessageCreate;
+
+        let app = TestApp::new().await;
+        let agent_id = AgentFixture::new("Writer").create(&app).await;
+        let mut workflow_ids = Vec::new();
+        for i in 0..10 {
+            let workflow_id = WorkflowFixture::new(&format!("Conversation {}", i), &agent_id)
+                .create(&app)
+                .await;
+            let message = MessageCreate::assistant(
+                workflow_id.clone(),
+                format!("Secret answer {}", i),
+                Some(1000),
+                Some(500),
+                Some("mistral-large-latest".to_string()),
+                Some("Mistral".to_string()),
+                Some(1200),
+            );
+            app.app_state()
+                .db
+                .create("message", &uuid::Uuid::new_v4().to_string(), message)
+                .await
+                .unwrap();
+            workflow_ids.push(workflow_id);
+        }
+
+        let report = export_team_usage(Some(7), Some(10.0), app.state())
+            .await
+            .unwrap();
+        assert_eq!(report.epsilon, 10.0);
+        assert_eq!(
+            report.period_end - report.period_start,
+            chrono::Duration::days(7)
+        );
+        assert!(report.tools.is_empty());
+        let conversations: u64 = report.usage.iter().map(|b| b.conversations).sum();
+        // Ten conversations, noise scale 0.4 on the count
+        assert!((5..=15).contains(&conversations));
+        assert!(report.usage.iter().all(|b| b.provider == "Mistral"));
+
+        // No per-conversation detail
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(workflow_ids.iter().all(|id| !json.contains(id.as_str())));
+        assert!(!json.contains("Secret") && !json.contains("Conversation"));
+
+        assert!(export_team_usage(Some(0), None, app.state()).await.is_err());
+        assert!(export_team_usage(None, Some(0.0), app.state())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_behavior_changelog_end_to_end() {
+        use crate::commands::agent::update_agent;
+        use crate::commands::behavior_changelog::list_behavior_changes;
+        use crate::models::behavior_changelog::BehaviorChangeKind;
+        use crate::models::AgentConfigUpdate;
+        use crate::scheduler::execute_prompt;
+
+        let app = TestApp::new().await;
+        let state = app.app_state();
+        let agent_id = AgentFixture::new("Researcher")
+            .tool("MemoryTool")
+            .create(&app)
+            .await;
+        let other_id = AgentFixture::new("Writer").create(&app).await;
+
+        let update: AgentConfigUpdate = serde_json::from_value(json!({
+            "system_prompt": "You search the knowledge base and cite sources.",
+            "tools": ["MemoryTool", "TodoTool"],
+        }))
+        .unwrap();
+        update_agent(agent_id.clone(), update, app.state())
+            .await
+            .unwrap();
+
+        let changes = list_behavior_changes(Some(agent_id.clone()), None, None, None, app.state())
+            .await
+            .unwrap();
+        let kinds: Vec<BehaviorChangeKind> = changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                BehaviorChangeKind::ToolsChanged,
+                BehaviorChangeKind::PromptChanged,
+                BehaviorChangeKind::AgentCreated,
+            ]
+        );
+        assert_eq!(changes[0].summary, "Tools granted: TodoTool");
+        assert!(changes
+            .iter()
+            .all(|c| c.agent_id.as_deref() == Some(agent_id.as_str())));
+
+        // Runs record the position of the last change in effect
+        register_echo_agent(&app, "echo_agent").await;
+        let workflow_id = WorkflowFixture::new("Research", &other_id)
+            .create(&app)
+            .await;
+        execute_prompt(&state.run_context(), &workflow_id, "echo_agent", "Find it")
+            .await
+            .unwrap();
+        let messages = load_workflow_messages(workflow_id, app.state())
+            .await
+            .unwrap();
+        assert_eq!(
+            messages.last().unwrap().behavior_position,
+            Some(changes[0].position)
+        );
+        assert_eq!(changes[0].position, 4);
+
+        // Changes made since that run
+        delete_agent(other_id, app.state()).await.unwrap();
+        let since = list_behavior_changes(None, Some(4), None, None, app.state())
+            .await
+            .unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].kind, BehaviorChangeKind::AgentDeleted);
+        assert_eq!(since[0].subject, "Writer");
+    }
+}
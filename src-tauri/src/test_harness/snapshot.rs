@@ -0,0 +1,130 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON snapshot assertions
+//!
+//! Command outputs are serialized to pretty JSON and compared with the file
+//! `src/test_harness/snapshots/<name>.json`. Values that change between runs
+//! (UUIDs, timestamps, durations) are redacted first.
+//!
+//! A missing snapshot is written on first run. Set `UPDATE_SNAPSHOTS=1` to
+//! rewrite existing snapshots after an intended output change, then review
+//! the diff before committing.
+
+use chrono::DateTime;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Environment variable rewriting the snapshots instead of comparing them
+const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Keys whose values are always redacted (run-dependent numbers)
+const REDACTED_KEYS: &[&str] = &["duration_ms", "latency_ms"];
+
+/// Directory of the snapshot files
+fn snapshot_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/test_harness/snapshots")
+}
+
+/// Replaces run-dependent values with placeholders
+///
+/// - UUID strings -> `[uuid]`
+/// - RFC 3339 timestamps -> `[timestamp]`
+/// - values of [`REDACTED_KEYS`] -> `[redacted]`
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::String(s) if uuid::Uuid::parse_str(&s).is_ok() => Value::from("[uuid]"),
+        Value::String(s) if DateTime::parse_from_rfc3339(&s).is_ok() => Value::from("[timestamp]"),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, v)| {
+                    let v = if REDACTED_KEYS.contains(&key.as_str()) {
+                        Value::from("[redacted]")
+                    } else {
+                        redact(v)
+                    };
+                    (key, v)
+                })
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Asserts that `value` matches the snapshot `name`
+///
+/// # Panics
+///
+/// Panics with both versions when the redacted value differs from the
+/// stored snapshot.
+pub fn assert_json_snapshot<T: Serialize>(name: &str, value: &T) {
+    let value = serde_json::to_value(value).expect("Snapshot value is not serializable");
+    let actual = format!(
+        "{}\n",
+        serde_json::to_string_pretty(&redact(value)).expect("Failed to format snapshot")
+    );
+
+    let path = snapshot_dir().join(format!("{}.json", name));
+    let update = std::env::var(UPDATE_ENV).is_ok_and(|v| v == "1");
+
+    match std::fs::read_to_string(&path) {
+        Ok(expected) if !update => {
+            assert!(
+                expected == actual,
+                "Snapshot '{}' does not match ({}).\n\
+                 Run with {}=1 to update it.\n\
+                 --- expected\n{}\n+++ actual\n{}",
+                name,
+                path.display(),
+                UPDATE_ENV,
+                expected,
+                actual
+            );
+        }
+        _ => {
+            std::fs::create_dir_all(snapshot_dir()).expect("Failed to create snapshot dir");
+            std::fs::write(&path, actual).expect("Failed to write snapshot");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redact() {
+        let value = json!({
+            "id": "3f2b8c1e-6a4d-4e1f-9b7a-2c5d8e9f0a1b",
+            "name": "Agent",
+            "created_at": "2025-01-15T10:30:00Z",
+            "duration_ms": 42,
+            "items": [{"updated_at": "2025-01-15T10:30:00.123+02:00", "count": 3}]
+        });
+
+        assert_eq!(
+            redact(value),
+            json!({
+                "id": "[uuid]",
+                "name": "Agent",
+                "created_at": "[timestamp]",
+                "duration_ms": "[redacted]",
+                "items": [{"updated_at": "[timestamp]", "count": 3}]
+            })
+        );
+    }
+}
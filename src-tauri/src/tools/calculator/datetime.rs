@@ -0,0 +1,214 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Date, time zone and duration helpers of the UnitsTool.
+//!
+//! Date-times are parsed as ISO 8601 / RFC 9557 strings: with an offset
+//! (`2025-03-30T01:30:00+01:00`), a time zone annotation
+//! (`2025-03-30T01:30[Europe/Paris]`), or neither, in which case they are
+//! civil times of the requested (or local) time zone. Durations accept the
+//! ISO 8601 (`P1M2DT3H`) and friendly (`2 weeks 3 days`, `1h 30m ago`)
+//! formats.
+
+use jiff::civil::{Date, Time};
+use jiff::fmt::temporal::Pieces;
+use jiff::tz::TimeZone;
+use jiff::{Span, SpanTotal, Timestamp, Unit, Zoned};
+
+/// Resolves a time zone name (`local` or none for the system time zone).
+pub fn resolve_time_zone(name: Option<&str>) -> Result<TimeZone, String> {
+    match name.map(str::trim).filter(|name| !name.is_empty()) {
+        None => Ok(TimeZone::system()),
+        Some(name) if name.eq_ignore_ascii_case("local") => Ok(TimeZone::system()),
+        Some(name) if name.eq_ignore_ascii_case("utc") || name == "Z" => Ok(TimeZone::UTC),
+        Some(name) => TimeZone::get(name).map_err(|_| {
+            format!(
+                "Unknown time zone '{}': use an IANA name such as 'Europe/Paris' or 'America/New_York'",
+                name
+            )
+        }),
+    }
+}
+
+/// Parses a date or date-time (`now` and `today` are accepted).
+///
+/// `time_zone` applies to inputs without an offset or annotation.
+pub fn parse_datetime(input: &str, time_zone: &TimeZone) -> Result<Zoned, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("now") {
+        return Ok(Timestamp::now().to_zoned(time_zone.clone()));
+    }
+    if input.eq_ignore_ascii_case("today") {
+        let today = Timestamp::now().to_zoned(time_zone.clone()).date();
+        return today.to_zoned(time_zone.clone()).map_err(|e| e.to_string());
+    }
+
+    let pieces = Pieces::parse(input).map_err(|e| {
+        format!(
+            "Invalid date '{}' ({}): use ISO 8601, e.g. 2025-06-10, 2025-06-10T14:30 or 2025-06-10T14:30:00+02:00",
+            input, e
+        )
+    })?;
+    let datetime = pieces
+        .date()
+        .to_datetime(pieces.time().unwrap_or(Time::midnight()));
+    let annotation = pieces.to_time_zone().map_err(|e| e.to_string())?;
+    match pieces.to_numeric_offset() {
+        Some(offset) => {
+            let timestamp = offset.to_timestamp(datetime).map_err(|e| e.to_string())?;
+            Ok(timestamp.to_zoned(annotation.unwrap_or_else(|| TimeZone::fixed(offset))))
+        }
+        None => datetime
+            .to_zoned(annotation.unwrap_or_else(|| time_zone.clone()))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Parses a duration (ISO 8601 or friendly format).
+pub fn parse_duration(input: &str) -> Result<Span, String> {
+    input.trim().parse::<Span>().map_err(|e| {
+        format!(
+            "Invalid duration '{}' ({}): use e.g. '2 weeks 3 days', '1h 30m', '-45 minutes' or 'P1M2DT3H'",
+            input.trim(),
+            e
+        )
+    })
+}
+
+/// Parses a unit name used to express differences (`days`, `hours`...).
+pub fn parse_span_unit(name: &str) -> Option<Unit> {
+    match name.trim().to_ascii_lowercase().trim_end_matches('s') {
+        "year" => Some(Unit::Year),
+        "month" => Some(Unit::Month),
+        "week" => Some(Unit::Week),
+        "day" => Some(Unit::Day),
+        "hour" => Some(Unit::Hour),
+        "minute" => Some(Unit::Minute),
+        "second" => Some(Unit::Second),
+        _ => None,
+    }
+}
+
+/// Total of a span in a unit, days counted as 24 hours (durations without
+/// a reference date cannot contain months or years).
+pub fn span_total(span: &Span, unit: Unit) -> Result<f64, String> {
+    span.total(SpanTotal::from(unit).days_are_24_hours())
+        .map_err(|_| {
+            "Durations with years or months depend on the date: use date_add or date_diff"
+                .to_string()
+        })
+}
+
+/// Counts the weekdays (Monday to Friday) from `start` (included) to `end`
+/// (excluded); negative when `end` is before `start`.
+pub fn count_weekdays(start: Date, end: Date) -> Result<i64, String> {
+    if end < start {
+        return count_weekdays(end, start).map(|count| -count);
+    }
+    let days = i64::from(
+        start
+            .until((Unit::Day, end))
+            .map_err(|e| e.to_string())?
+            .get_days(),
+    );
+    let first = i64::from(start.weekday().to_monday_zero_offset());
+    let mut count = days / 7 * 5;
+    for offset in 0..days % 7 {
+        if (first + offset) % 7 < 5 {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paris() -> TimeZone {
+        resolve_time_zone(Some("Europe/Paris")).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_time_zone() {
+        assert_eq!(resolve_time_zone(Some("UTC")).unwrap(), TimeZone::UTC);
+        assert!(resolve_time_zone(Some("local")).is_ok());
+        assert!(resolve_time_zone(None).is_ok());
+        assert!(resolve_time_zone(Some("Mars/Olympus")).is_err());
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        let civil = parse_datetime("2025-06-10 14:30", &paris()).unwrap();
+        assert_eq!(civil.to_string(), "2025-06-10T14:30:00+02:00[Europe/Paris]");
+
+        let offset = parse_datetime("2025-06-10T14:30:00-05:00", &paris()).unwrap();
+        assert_eq!(offset.to_string(), "2025-06-10T14:30:00-05:00[-05:00]");
+
+        let annotated = parse_datetime("2025-01-10T09:00[America/New_York]", &paris()).unwrap();
+        assert_eq!(
+            annotated.to_string(),
+            "2025-01-10T09:00:00-05:00[America/New_York]"
+        );
+
+        let date = parse_datetime("2025-06-10", &TimeZone::UTC).unwrap();
+        assert_eq!(date.to_string(), "2025-06-10T00:00:00+00:00[UTC]");
+
+        assert!(parse_datetime("now", &paris()).is_ok());
+        assert!(parse_datetime("next tuesday", &paris()).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let span = parse_duration("2 weeks 3 days").unwrap();
+        assert_eq!(span_total(&span, Unit::Day).unwrap(), 17.0);
+        let span = parse_duration("1h 30m ago").unwrap();
+        assert_eq!(span_total(&span, Unit::Minute).unwrap(), -90.0);
+        let span = parse_duration("PT2H").unwrap();
+        assert_eq!(span_total(&span, Unit::Second).unwrap(), 7200.0);
+        assert!(span_total(&parse_duration("1 month").unwrap(), Unit::Day).is_err());
+        assert!(parse_duration("a while").is_err());
+    }
+
+    #[test]
+    fn test_parse_span_unit() {
+        assert_eq!(parse_span_unit("Days"), Some(Unit::Day));
+        assert_eq!(parse_span_unit("hour"), Some(Unit::Hour));
+        assert_eq!(parse_span_unit("fortnights"), None);
+    }
+
+    #[test]
+    fn test_count_weekdays() {
+        let monday = jiff::civil::date(2025, 6, 9);
+        assert_eq!(count_weekdays(monday, monday).unwrap(), 0);
+        assert_eq!(
+            count_weekdays(monday, jiff::civil::date(2025, 6, 14)).unwrap(),
+            5
+        );
+        assert_eq!(
+            count_weekdays(monday, jiff::civil::date(2025, 6, 23)).unwrap(),
+            10
+        );
+        // Saturday to the next Tuesday: Monday only
+        let saturday = jiff::civil::date(2025, 6, 14);
+        assert_eq!(
+            count_weekdays(saturday, jiff::civil::date(2025, 6, 17)).unwrap(),
+            1
+        );
+        assert_eq!(
+            count_weekdays(jiff::civil::date(2025, 6, 17), saturday).unwrap(),
+            -1
+        );
+    }
+}
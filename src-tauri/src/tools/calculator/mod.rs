@@ -23,7 +23,14 @@
 //! - Rounding (floor, ceil, round, trunc)
 //! - Utility (abs, sign, degrees, radians)
 //! - Mathematical constants (PI, E, TAU, SQRT2, LN2, LN10)
+//!
+//! The UnitsTool completes it with unit conversions, time zone conversions,
+//! date arithmetic and duration parsing.
 
+mod datetime;
 mod tool;
+mod units;
+mod units_tool;
 
 pub use tool::CalculatorTool;
+pub use units_tool::UnitsTool;
@@ -16,13 +16,20 @@
 //!
 //! Stateless tool providing mathematical operations for agents.
 
+use crate::models::locale::Locale;
 use crate::tools::constants::calculator::{BINARY_OPS, UNARY_OPS, VALID_CONSTANTS};
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
 use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::load_locale_settings;
 use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::f64::consts::{E, FRAC_1_SQRT_2, LN_10, LN_2, PI, SQRT_2, TAU};
-use tracing::debug;
+use std::sync::Arc;
+use tracing::{debug, info};
 
 /// Scientific calculator tool for agents.
 ///
@@ -37,13 +44,24 @@ use tracing::debug;
 ///
 /// Unlike MemoryTool or TodoTool, CalculatorTool is completely stateless.
 /// It does not require database access or workflow scoping.
-pub struct CalculatorTool;
+///
+/// Results are returned as numbers (`result`) and as strings formatted
+/// for the configured locale (`formatted`).
+pub struct CalculatorTool {
+    /// Locale used for the `formatted` result
+    locale: Locale,
+}
 
 impl CalculatorTool {
-    /// Creates a new CalculatorTool instance.
+    /// Creates a new CalculatorTool instance (English number format).
     pub fn new() -> Self {
-        debug!("CalculatorTool instance created");
-        Self
+        Self::with_locale(Locale::default())
+    }
+
+    /// Creates a new CalculatorTool instance formatting results for `locale`.
+    pub fn with_locale(locale: Locale) -> Self {
+        debug!(locale = %locale, "CalculatorTool instance created");
+        Self { locale }
     }
 
     /// Executes a unary operation.
@@ -165,6 +183,7 @@ impl CalculatorTool {
             .field("operation", json!(operation))
             .field("value", json!(value))
             .field("result", json!(result))
+            .field("formatted", json!(self.locale.format_number(result)))
             .message("Calculation completed successfully")
             .build())
     }
@@ -280,6 +299,7 @@ impl CalculatorTool {
             .field("a", json!(a))
             .field("b", json!(b))
             .field("result", json!(result))
+            .field("formatted", json!(self.locale.format_number(result)))
             .message("Calculation completed successfully")
             .build())
     }
@@ -307,9 +327,30 @@ impl CalculatorTool {
             .field("operation", json!("constant"))
             .field("name", json!(name))
             .field("result", json!(value))
+            .field("formatted", json!(self.locale.format_number(value)))
             .message(description)
             .build())
     }
+
+    /// Creates the tool with the locale of the settings.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let locale = load_locale_settings(&ctx.factory.get_db()).await.locale;
+            let tool = CalculatorTool::with_locale(locale);
+            info!(locale = %locale, "CalculatorTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "CalculatorTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: CalculatorTool::build,
+    })
 }
 
 impl Default for CalculatorTool {
@@ -382,6 +423,10 @@ EXAMPLES:
 9. Modulo: {"operation": "modulo", "a": 17, "b": 5}
 10. Minimum: {"operation": "min", "a": 3.5, "b": 2.1}
 
+OUTPUT:
+- result: numeric value (use it for further calculations)
+- formatted: result in the user's number format (use it when presenting the value)
+
 ERROR HANDLING:
 - sqrt(negative): Domain error - use abs() first or check input
 - ln/log10(<=0): Domain error - value must be positive
@@ -1212,8 +1257,31 @@ mod tests {
 
     #[test]
     fn test_default_impl() {
-        let tool = CalculatorTool;
+        let tool = CalculatorTool::default();
         let def = tool.definition();
         assert_eq!(def.id, "CalculatorTool");
     }
+
+    #[tokio::test]
+    async fn test_formatted_result_uses_locale() {
+        let result = calculator()
+            .execute(json!({"operation": "multiply", "a": 1234.5, "b": 2}))
+            .await
+            .unwrap();
+        assert_eq!(result["result"].as_f64().unwrap(), 2469.0);
+        assert_eq!(result["formatted"], "2,469");
+
+        let tool = CalculatorTool::with_locale(Locale::Fr);
+        let result = tool
+            .execute(json!({"operation": "divide", "a": 10000, "b": 8}))
+            .await
+            .unwrap();
+        assert_eq!(result["formatted"], "1\u{202F}250");
+
+        let result = tool
+            .execute(json!({"operation": "constant", "name": "pi"}))
+            .await
+            .unwrap();
+        assert_eq!(result["formatted"], "3,141592653589793");
+    }
 }
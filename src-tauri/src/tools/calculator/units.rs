@@ -0,0 +1,635 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unit conversion table.
+//!
+//! Each unit is defined by its factor to the base unit of its category;
+//! temperatures, which have an offset, are converted through kelvins.
+
+use std::f64::consts::PI;
+
+/// Category of units that can be converted into each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Length,
+    Mass,
+    Volume,
+    Area,
+    Speed,
+    Time,
+    Data,
+    Energy,
+    Power,
+    Pressure,
+    Angle,
+    Frequency,
+    Temperature,
+}
+
+impl Category {
+    /// All categories, in the order they are listed
+    pub const ALL: [Category; 13] = [
+        Self::Length,
+        Self::Mass,
+        Self::Volume,
+        Self::Area,
+        Self::Speed,
+        Self::Time,
+        Self::Data,
+        Self::Energy,
+        Self::Power,
+        Self::Pressure,
+        Self::Angle,
+        Self::Frequency,
+        Self::Temperature,
+    ];
+
+    /// Returns the category name used in the tool input and output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Length => "length",
+            Self::Mass => "mass",
+            Self::Volume => "volume",
+            Self::Area => "area",
+            Self::Speed => "speed",
+            Self::Time => "time",
+            Self::Data => "data",
+            Self::Energy => "energy",
+            Self::Power => "power",
+            Self::Pressure => "pressure",
+            Self::Angle => "angle",
+            Self::Frequency => "frequency",
+            Self::Temperature => "temperature",
+        }
+    }
+
+    /// Parses a category name.
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Convertible unit
+#[derive(Debug)]
+pub struct Unit {
+    /// Symbol (case-sensitive: `MB` is a megabyte, `Mbit` a megabit)
+    pub symbol: &'static str,
+    /// Accepted names, matched case-insensitively
+    pub names: &'static [&'static str],
+    /// Category of the unit
+    pub category: Category,
+    /// Value of one unit in the base unit of the category (kelvin-sized
+    /// degrees for temperatures)
+    pub factor: f64,
+}
+
+const fn unit(
+    symbol: &'static str,
+    names: &'static [&'static str],
+    category: Category,
+    factor: f64,
+) -> Unit {
+    Unit {
+        symbol,
+        names,
+        category,
+        factor,
+    }
+}
+
+use Category::*;
+
+/// Supported units. Base units: m, kg, L, m², m/s, s, B, J, W, Pa, rad, Hz, K.
+pub static UNITS: &[Unit] = &[
+    // Length
+    unit(
+        "nm",
+        &["nanometer", "nanometers", "nanometre", "nanometres"],
+        Length,
+        1e-9,
+    ),
+    unit(
+        "um",
+        &["µm", "micrometer", "micrometers", "micron", "microns"],
+        Length,
+        1e-6,
+    ),
+    unit(
+        "mm",
+        &["millimeter", "millimeters", "millimetre", "millimetres"],
+        Length,
+        1e-3,
+    ),
+    unit(
+        "cm",
+        &["centimeter", "centimeters", "centimetre", "centimetres"],
+        Length,
+        1e-2,
+    ),
+    unit("m", &["meter", "meters", "metre", "metres"], Length, 1.0),
+    unit(
+        "km",
+        &["kilometer", "kilometers", "kilometre", "kilometres"],
+        Length,
+        1e3,
+    ),
+    unit("in", &["inch", "inches", "\""], Length, 0.0254),
+    unit("ft", &["foot", "feet", "'"], Length, 0.3048),
+    unit("yd", &["yard", "yards"], Length, 0.9144),
+    unit("mi", &["mile", "miles"], Length, 1609.344),
+    unit("nmi", &["nautical mile", "nautical miles"], Length, 1852.0),
+    // Mass
+    unit("mg", &["milligram", "milligrams"], Mass, 1e-6),
+    unit("g", &["gram", "grams"], Mass, 1e-3),
+    unit("kg", &["kilogram", "kilograms", "kilo", "kilos"], Mass, 1.0),
+    unit(
+        "t",
+        &["tonne", "tonnes", "metric ton", "metric tons"],
+        Mass,
+        1e3,
+    ),
+    unit("oz", &["ounce", "ounces"], Mass, 0.028_349_523_125),
+    unit("lb", &["lbs", "pound", "pounds"], Mass, 0.453_592_37),
+    unit("st", &["stone", "stones"], Mass, 6.350_293_18),
+    unit(
+        "short_ton",
+        &["short ton", "short tons", "us ton", "us tons"],
+        Mass,
+        907.184_74,
+    ),
+    // Volume
+    unit(
+        "ml",
+        &[
+            "mL",
+            "milliliter",
+            "milliliters",
+            "millilitre",
+            "millilitres",
+        ],
+        Volume,
+        1e-3,
+    ),
+    unit(
+        "cl",
+        &[
+            "cL",
+            "centiliter",
+            "centiliters",
+            "centilitre",
+            "centilitres",
+        ],
+        Volume,
+        1e-2,
+    ),
+    unit(
+        "dl",
+        &["dL", "deciliter", "deciliters", "decilitre", "decilitres"],
+        Volume,
+        0.1,
+    ),
+    unit(
+        "l",
+        &["L", "liter", "liters", "litre", "litres"],
+        Volume,
+        1.0,
+    ),
+    unit(
+        "m3",
+        &[
+            "m³",
+            "cubic meter",
+            "cubic meters",
+            "cubic metre",
+            "cubic metres",
+        ],
+        Volume,
+        1e3,
+    ),
+    unit(
+        "cm3",
+        &["cm³", "cc", "cubic centimeter", "cubic centimeters"],
+        Volume,
+        1e-3,
+    ),
+    unit(
+        "tsp",
+        &["teaspoon", "teaspoons"],
+        Volume,
+        0.004_928_921_593_75,
+    ),
+    unit(
+        "tbsp",
+        &["tablespoon", "tablespoons"],
+        Volume,
+        0.014_786_764_781_25,
+    ),
+    unit(
+        "fl_oz",
+        &["fl oz", "fluid ounce", "fluid ounces"],
+        Volume,
+        0.029_573_529_562_5,
+    ),
+    unit("cup", &["cups"], Volume, 0.236_588_236_5),
+    unit(
+        "pt",
+        &["pint", "pints", "us pint", "us pints"],
+        Volume,
+        0.473_176_473,
+    ),
+    unit("qt", &["quart", "quarts"], Volume, 0.946_352_946),
+    unit(
+        "gal",
+        &["gallon", "gallons", "us gallon", "us gallons"],
+        Volume,
+        3.785_411_784,
+    ),
+    unit(
+        "imp_pt",
+        &["imperial pint", "imperial pints"],
+        Volume,
+        0.568_261_25,
+    ),
+    unit(
+        "imp_gal",
+        &["imperial gallon", "imperial gallons"],
+        Volume,
+        4.546_09,
+    ),
+    // Area
+    unit(
+        "mm2",
+        &["mm²", "square millimeter", "square millimeters"],
+        Area,
+        1e-6,
+    ),
+    unit(
+        "cm2",
+        &["cm²", "square centimeter", "square centimeters"],
+        Area,
+        1e-4,
+    ),
+    unit(
+        "m2",
+        &[
+            "m²",
+            "square meter",
+            "square meters",
+            "square metre",
+            "square metres",
+        ],
+        Area,
+        1.0,
+    ),
+    unit("ha", &["hectare", "hectares"], Area, 1e4),
+    unit(
+        "km2",
+        &["km²", "square kilometer", "square kilometers"],
+        Area,
+        1e6,
+    ),
+    unit(
+        "in2",
+        &["in²", "square inch", "square inches"],
+        Area,
+        0.000_645_16,
+    ),
+    unit(
+        "ft2",
+        &["ft²", "square foot", "square feet"],
+        Area,
+        0.092_903_04,
+    ),
+    unit(
+        "yd2",
+        &["yd²", "square yard", "square yards"],
+        Area,
+        0.836_127_36,
+    ),
+    unit("ac", &["acre", "acres"], Area, 4_046.856_422_4),
+    unit(
+        "mi2",
+        &["mi²", "square mile", "square miles"],
+        Area,
+        2_589_988.110_336,
+    ),
+    // Speed
+    unit(
+        "m/s",
+        &["mps", "meters per second", "metres per second"],
+        Speed,
+        1.0,
+    ),
+    unit(
+        "km/h",
+        &["kph", "kmh", "kilometers per hour", "kilometres per hour"],
+        Speed,
+        1.0 / 3.6,
+    ),
+    unit("mph", &["mi/h", "miles per hour"], Speed, 0.447_04),
+    unit("kn", &["kt", "knot", "knots"], Speed, 1852.0 / 3600.0),
+    unit("ft/s", &["fps", "feet per second"], Speed, 0.3048),
+    // Time (fixed lengths: a year is 365.25 days, use date_add for calendar math)
+    unit("ns", &["nanosecond", "nanoseconds"], Time, 1e-9),
+    unit("us", &["µs", "microsecond", "microseconds"], Time, 1e-6),
+    unit("ms", &["millisecond", "milliseconds"], Time, 1e-3),
+    unit("s", &["sec", "secs", "second", "seconds"], Time, 1.0),
+    unit("min", &["mins", "minute", "minutes"], Time, 60.0),
+    unit("h", &["hr", "hrs", "hour", "hours"], Time, 3_600.0),
+    unit("d", &["day", "days"], Time, 86_400.0),
+    unit("wk", &["week", "weeks"], Time, 604_800.0),
+    unit("yr", &["year", "years"], Time, 31_557_600.0),
+    // Data
+    unit("bit", &["bits"], Data, 0.125),
+    unit("B", &["byte", "bytes"], Data, 1.0),
+    unit("kB", &["KB", "kilobyte", "kilobytes"], Data, 1e3),
+    unit("MB", &["megabyte", "megabytes"], Data, 1e6),
+    unit("GB", &["gigabyte", "gigabytes"], Data, 1e9),
+    unit("TB", &["terabyte", "terabytes"], Data, 1e12),
+    unit("PB", &["petabyte", "petabytes"], Data, 1e15),
+    unit("KiB", &["kibibyte", "kibibytes"], Data, 1_024.0),
+    unit("MiB", &["mebibyte", "mebibytes"], Data, 1_048_576.0),
+    unit("GiB", &["gibibyte", "gibibytes"], Data, 1_073_741_824.0),
+    unit("TiB", &["tebibyte", "tebibytes"], Data, 1_099_511_627_776.0),
+    unit("kbit", &["kb", "kilobit", "kilobits"], Data, 125.0),
+    unit("Mbit", &["Mb", "megabit", "megabits"], Data, 125_000.0),
+    unit("Gbit", &["Gb", "gigabit", "gigabits"], Data, 125_000_000.0),
+    // Energy
+    unit("J", &["joule", "joules"], Energy, 1.0),
+    unit("kJ", &["kilojoule", "kilojoules"], Energy, 1e3),
+    unit("MJ", &["megajoule", "megajoules"], Energy, 1e6),
+    unit("cal", &["calorie", "calories"], Energy, 4.184),
+    unit(
+        "kcal",
+        &["Cal", "kilocalorie", "kilocalories"],
+        Energy,
+        4_184.0,
+    ),
+    unit(
+        "Wh",
+        &["watt hour", "watt hours", "watt-hour", "watt-hours"],
+        Energy,
+        3_600.0,
+    ),
+    unit(
+        "kWh",
+        &[
+            "kilowatt hour",
+            "kilowatt hours",
+            "kilowatt-hour",
+            "kilowatt-hours",
+        ],
+        Energy,
+        3.6e6,
+    ),
+    unit("MWh", &["megawatt hour", "megawatt hours"], Energy, 3.6e9),
+    unit(
+        "BTU",
+        &["btu", "british thermal unit", "british thermal units"],
+        Energy,
+        1_055.055_852_62,
+    ),
+    unit(
+        "eV",
+        &["electronvolt", "electronvolts"],
+        Energy,
+        1.602_176_634e-19,
+    ),
+    // Power
+    unit("W", &["watt", "watts"], Power, 1.0),
+    unit("kW", &["kilowatt", "kilowatts"], Power, 1e3),
+    unit("MW", &["megawatt", "megawatts"], Power, 1e6),
+    unit("hp", &["horsepower"], Power, 745.699_871_582_270_2),
+    unit("PS", &["metric horsepower", "ch", "cv"], Power, 735.498_75),
+    // Pressure
+    unit("Pa", &["pascal", "pascals"], Pressure, 1.0),
+    unit("hPa", &["hectopascal", "hectopascals"], Pressure, 1e2),
+    unit("kPa", &["kilopascal", "kilopascals"], Pressure, 1e3),
+    unit("MPa", &["megapascal", "megapascals"], Pressure, 1e6),
+    unit("mbar", &["millibar", "millibars"], Pressure, 1e2),
+    unit("bar", &["bars"], Pressure, 1e5),
+    unit("atm", &["atmosphere", "atmospheres"], Pressure, 101_325.0),
+    unit(
+        "psi",
+        &["pounds per square inch"],
+        Pressure,
+        6_894.757_293_168,
+    ),
+    unit(
+        "mmHg",
+        &["torr", "millimeter of mercury", "millimeters of mercury"],
+        Pressure,
+        133.322_387_415,
+    ),
+    unit(
+        "inHg",
+        &["inch of mercury", "inches of mercury"],
+        Pressure,
+        3_386.388_666_6,
+    ),
+    // Angle
+    unit("rad", &["radian", "radians"], Angle, 1.0),
+    unit("deg", &["°", "degree", "degrees"], Angle, PI / 180.0),
+    unit("grad", &["gon", "gradian", "gradians"], Angle, PI / 200.0),
+    unit("arcmin", &["arcminute", "arcminutes"], Angle, PI / 10_800.0),
+    unit(
+        "arcsec",
+        &["arcsecond", "arcseconds"],
+        Angle,
+        PI / 648_000.0,
+    ),
+    unit(
+        "turn",
+        &["turns", "revolution", "revolutions"],
+        Angle,
+        2.0 * PI,
+    ),
+    // Frequency
+    unit("Hz", &["hertz"], Frequency, 1.0),
+    unit("kHz", &["kilohertz"], Frequency, 1e3),
+    unit("MHz", &["megahertz"], Frequency, 1e6),
+    unit("GHz", &["gigahertz"], Frequency, 1e9),
+    unit("rpm", &["revolutions per minute"], Frequency, 1.0 / 60.0),
+    // Temperature (see `to_kelvin`)
+    unit("C", &["°C", "celsius", "degC"], Temperature, 1.0),
+    unit("F", &["°F", "fahrenheit", "degF"], Temperature, 5.0 / 9.0),
+    unit("K", &["kelvin", "kelvins"], Temperature, 1.0),
+];
+
+/// Finds a unit by symbol or name.
+///
+/// Symbols and names match exactly first, then lowercase names (three
+/// letters or more) case-insensitively, then symbols and names
+/// case-insensitively when only one unit matches (`mb` is ambiguous).
+pub fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = name.trim();
+    if let Some(unit) = UNITS
+        .iter()
+        .find(|unit| unit.symbol == name || unit.names.contains(&name))
+    {
+        return Some(unit);
+    }
+    if let Some(unit) = UNITS.iter().find(|unit| {
+        unit.names.iter().any(|candidate| {
+            candidate.chars().count() >= 3
+                && !candidate.chars().any(char::is_uppercase)
+                && candidate.eq_ignore_ascii_case(name)
+        })
+    }) {
+        return Some(unit);
+    }
+    let mut matches = UNITS.iter().filter(|unit| {
+        unit.symbol.eq_ignore_ascii_case(name)
+            || unit
+                .names
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(name))
+    });
+    match (matches.next(), matches.next()) {
+        (Some(unit), None) => Some(unit),
+        _ => None,
+    }
+}
+
+/// Converts a temperature to kelvins.
+fn to_kelvin(value: f64, unit: &Unit) -> f64 {
+    match unit.symbol {
+        "C" => value + 273.15,
+        "F" => (value - 32.0) * 5.0 / 9.0 + 273.15,
+        _ => value,
+    }
+}
+
+/// Converts kelvins to a temperature unit.
+fn from_kelvin(kelvin: f64, unit: &Unit) -> f64 {
+    match unit.symbol {
+        "C" => kelvin - 273.15,
+        "F" => (kelvin - 273.15) * 9.0 / 5.0 + 32.0,
+        _ => kelvin,
+    }
+}
+
+/// Rounds to a number of significant digits, removing the binary noise of
+/// the factors (`0.30000000000000004` becomes `0.3`).
+pub fn round_significant(value: f64, digits: i32) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let scale = 10f64.powi(digits - 1 - magnitude);
+    if !scale.is_finite() || scale == 0.0 {
+        return value;
+    }
+    (value * scale).round() / scale
+}
+
+/// Converts a value between two units of the same category.
+pub fn convert(value: f64, from: &Unit, to: &Unit) -> Result<f64, String> {
+    if from.category != to.category {
+        return Err(format!(
+            "Cannot convert {} ({}) to {} ({})",
+            from.symbol,
+            from.category.as_str(),
+            to.symbol,
+            to.category.as_str()
+        ));
+    }
+    let result = if from.category == Temperature {
+        let kelvin = to_kelvin(value, from);
+        if kelvin < 0.0 {
+            return Err(format!("{} {} is below absolute zero", value, from.symbol));
+        }
+        from_kelvin(kelvin, to)
+    } else {
+        value * from.factor / to.factor
+    };
+    if !result.is_finite() {
+        return Err("Conversion result is out of range".to_string());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert_names(value: f64, from: &str, to: &str) -> f64 {
+        let result = convert(value, find_unit(from).unwrap(), find_unit(to).unwrap()).unwrap();
+        round_significant(result, 12)
+    }
+
+    #[test]
+    fn test_find_unit() {
+        assert_eq!(find_unit("MB").unwrap().symbol, "MB");
+        assert_eq!(find_unit("Mb").unwrap().symbol, "Mbit");
+        assert_eq!(find_unit("Miles").unwrap().symbol, "mi");
+        assert_eq!(find_unit(" °C ").unwrap().symbol, "C");
+        assert_eq!(find_unit("KM").unwrap().symbol, "km");
+        assert_eq!(find_unit("kph").unwrap().symbol, "km/h");
+        // Megabyte or megabit
+        assert!(find_unit("mb").is_none());
+        assert!(find_unit("furlong").is_none());
+    }
+
+    #[test]
+    fn test_symbols_are_unique() {
+        for (i, unit) in UNITS.iter().enumerate() {
+            assert!(
+                UNITS[i + 1..]
+                    .iter()
+                    .all(|other| other.symbol != unit.symbol),
+                "duplicate symbol {}",
+                unit.symbol
+            );
+            assert_eq!(find_unit(unit.symbol).unwrap().symbol, unit.symbol);
+        }
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(convert_names(1.0, "mi", "km"), 1.609344);
+        assert_eq!(convert_names(100.0, "km/h", "mph"), 62.1371192237);
+        assert_eq!(convert_names(1.0, "GiB", "MB"), 1073.741824);
+        assert_eq!(convert_names(100.0, "Mbit", "MB"), 12.5);
+        assert_eq!(convert_names(2.0, "cups", "ml"), 473.176473);
+        assert_eq!(
+            convert_names(180.0, "deg", "rad"),
+            round_significant(PI, 12)
+        );
+        assert_eq!(convert_names(1.0, "kWh", "J"), 3.6e6);
+        assert_eq!(convert_names(0.1, "m", "cm"), 10.0);
+    }
+
+    #[test]
+    fn test_convert_temperature() {
+        assert_eq!(convert_names(100.0, "C", "F"), 212.0);
+        assert_eq!(convert_names(-40.0, "F", "C"), -40.0);
+        assert_eq!(convert_names(0.0, "K", "C"), -273.15);
+        assert!(convert(-300.0, find_unit("C").unwrap(), find_unit("K").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_convert_rejects_other_category() {
+        let error = convert(1.0, find_unit("kg").unwrap(), find_unit("m").unwrap()).unwrap_err();
+        assert!(error.contains("mass"));
+    }
+
+    #[test]
+    fn test_category_parse() {
+        assert_eq!(Category::parse("Length"), Some(Length));
+        assert_eq!(Category::parse("money"), None);
+    }
+}
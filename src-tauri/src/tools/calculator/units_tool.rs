@@ -0,0 +1,731 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Units Tool Implementation
+//!
+//! Stateless tool converting units and computing with dates, time zones
+//! and durations.
+
+use super::datetime::{
+    count_weekdays, parse_datetime, parse_duration, parse_span_unit, resolve_time_zone, span_total,
+};
+use super::units::{convert, find_unit, round_significant, Category, UNITS};
+use crate::models::locale::Locale;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::constants::units::{SIGNIFICANT_DIGITS, VALID_OPERATIONS};
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::load_locale_settings;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use chrono::{FixedOffset, NaiveDate, TimeZone as _};
+use jiff::tz::TimeZone;
+use jiff::{Unit, Zoned};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Unit conversion and date/time tool for agents.
+///
+/// This tool provides the operations LLMs routinely get wrong:
+/// - Unit conversions (length, mass, volume, data, temperature...)
+/// - Time zone conversions (DST-aware, bundled IANA database)
+/// - Date arithmetic (add durations, differences, weekday counts)
+/// - Duration parsing and totals
+///
+/// # Stateless Design
+///
+/// Like CalculatorTool, UnitsTool does not require database access or
+/// workflow scoping. Dates without a time zone use the `time_zone` input,
+/// or the system time zone.
+pub struct UnitsTool {
+    /// Locale used for the `formatted` results
+    locale: Locale,
+}
+
+impl UnitsTool {
+    /// Creates a new UnitsTool instance (English format).
+    pub fn new() -> Self {
+        Self::with_locale(Locale::default())
+    }
+
+    /// Creates a new UnitsTool instance formatting results for `locale`.
+    pub fn with_locale(locale: Locale) -> Self {
+        debug!(locale = %locale, "UnitsTool instance created");
+        Self { locale }
+    }
+
+    /// Reads a required string field.
+    fn required_str<'a>(input: &'a Value, field: &str, operation: &str) -> ToolResult<&'a str> {
+        input[field]
+            .as_str()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| {
+                ToolError::InvalidInput(format!(
+                    "Operation '{}' requires '{}' field (string)",
+                    operation, field
+                ))
+            })
+    }
+
+    /// Resolves the optional `time_zone` field.
+    fn time_zone(input: &Value, field: &str) -> ToolResult<TimeZone> {
+        resolve_time_zone(input[field].as_str()).map_err(ToolError::InvalidInput)
+    }
+
+    /// Reads the optional unit used to express a difference or duration.
+    fn span_unit(input: &Value, default: Unit) -> ToolResult<Unit> {
+        match input["unit"].as_str() {
+            None => Ok(default),
+            Some(name) => parse_span_unit(name).ok_or_else(|| {
+                ToolError::InvalidInput(format!(
+                    "Unknown unit '{}': use years, months, weeks, days, hours, minutes or seconds",
+                    name
+                ))
+            }),
+        }
+    }
+
+    /// Describes a date-time: ISO string, time zone, offset and localized text.
+    fn describe(&self, zoned: &Zoned) -> Value {
+        let time_zone = zoned
+            .time_zone()
+            .iana_name()
+            .map(str::to_string)
+            .unwrap_or_else(|| zoned.offset().to_string());
+        json!({
+            "datetime": zoned.to_string(),
+            "time_zone": time_zone,
+            "offset": zoned.offset().to_string(),
+            "weekday": zoned.strftime("%A").to_string(),
+            "formatted": self.format_zoned(zoned),
+        })
+    }
+
+    /// Formats a date-time with the locale's weekday and month names.
+    fn format_zoned(&self, zoned: &Zoned) -> String {
+        let offset = FixedOffset::east_opt(zoned.offset().seconds());
+        let civil = NaiveDate::from_ymd_opt(
+            i32::from(zoned.year()),
+            zoned.month() as u32,
+            zoned.day() as u32,
+        )
+        .and_then(|date| {
+            date.and_hms_opt(
+                zoned.hour() as u32,
+                zoned.minute() as u32,
+                zoned.second() as u32,
+            )
+        });
+        match (offset, civil) {
+            (Some(offset), Some(civil)) => match offset.from_local_datetime(&civil).single() {
+                Some(datetime) => self.locale.format_datetime(&datetime),
+                None => zoned.to_string(),
+            },
+            _ => zoned.to_string(),
+        }
+    }
+
+    /// Converts a value between two units.
+    fn convert_units(&self, input: &Value) -> ToolResult<Value> {
+        let value = input["value"].as_f64().ok_or_else(|| {
+            ToolError::InvalidInput("Operation 'convert' requires 'value' field (number)".into())
+        })?;
+        let from_name = Self::required_str(input, "from", "convert")?;
+        let to_name = Self::required_str(input, "to", "convert")?;
+        let unknown = |name: &str| {
+            ToolError::InvalidInput(format!(
+                "Unknown or ambiguous unit '{}'. Use the 'list_units' operation to see the supported units",
+                name
+            ))
+        };
+        let from = find_unit(from_name).ok_or_else(|| unknown(from_name))?;
+        let to = find_unit(to_name).ok_or_else(|| unknown(to_name))?;
+
+        let result = round_significant(
+            convert(value, from, to).map_err(ToolError::ExecutionFailed)?,
+            SIGNIFICANT_DIGITS,
+        );
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("convert"))
+            .field("value", json!(value))
+            .field("from", json!(from.symbol))
+            .field("to", json!(to.symbol))
+            .field("category", json!(from.category.as_str()))
+            .field("result", json!(result))
+            .field("formatted", json!(self.locale.format_number(result)))
+            .message(format!(
+                "{} {} = {} {}",
+                value, from.symbol, result, to.symbol
+            ))
+            .build())
+    }
+
+    /// Lists the supported units, optionally of one category.
+    fn list_units(&self, input: &Value) -> ToolResult<Value> {
+        let categories: Vec<Category> = match input["category"].as_str() {
+            None => Category::ALL.to_vec(),
+            Some(name) => vec![Category::parse(name).ok_or_else(|| {
+                let valid: Vec<&str> = Category::ALL.iter().map(|c| c.as_str()).collect();
+                ToolError::InvalidInput(format!(
+                    "Unknown category '{}'. Valid categories: {:?}",
+                    name, valid
+                ))
+            })?],
+        };
+
+        let mut listing = serde_json::Map::new();
+        for category in &categories {
+            let units: Vec<Value> = UNITS
+                .iter()
+                .filter(|unit| unit.category == *category)
+                .map(|unit| json!({"symbol": unit.symbol, "names": unit.names}))
+                .collect();
+            listing.insert(category.as_str().to_string(), json!(units));
+        }
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("list_units"))
+            .field("units", Value::Object(listing))
+            .message(format!("{} categories", categories.len()))
+            .build())
+    }
+
+    /// Converts a date-time to another time zone.
+    fn convert_timezone(&self, input: &Value) -> ToolResult<Value> {
+        let datetime = Self::required_str(input, "datetime", "convert_timezone")?;
+        Self::required_str(input, "to_time_zone", "convert_timezone")?;
+        let from_zone = Self::time_zone(input, "time_zone")?;
+        let to_zone = Self::time_zone(input, "to_time_zone")?;
+
+        let source = parse_datetime(datetime, &from_zone).map_err(ToolError::InvalidInput)?;
+        let target = source.with_time_zone(to_zone);
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("convert_timezone"))
+            .field("source", self.describe(&source))
+            .field("result", self.describe(&target))
+            .message(format!("{} is {}", source, target))
+            .build())
+    }
+
+    /// Adds a duration to a date-time (negative durations subtract).
+    fn date_add(&self, input: &Value) -> ToolResult<Value> {
+        let duration = Self::required_str(input, "duration", "date_add")?;
+        let time_zone = Self::time_zone(input, "time_zone")?;
+        let start = parse_datetime(input["datetime"].as_str().unwrap_or("now"), &time_zone)
+            .map_err(ToolError::InvalidInput)?;
+        let span = parse_duration(duration).map_err(ToolError::InvalidInput)?;
+
+        let result = start.checked_add(span).map_err(|e| {
+            ToolError::ExecutionFailed(format!("Cannot add {} to {}: {}", duration, start, e))
+        })?;
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("date_add"))
+            .field("start", self.describe(&start))
+            .field("duration", json!(format!("{:#}", span)))
+            .field("result", self.describe(&result))
+            .message(format!("{} + {:#} = {}", start, span, result))
+            .build())
+    }
+
+    /// Computes the difference between two date-times.
+    fn date_diff(&self, input: &Value) -> ToolResult<Value> {
+        let start = Self::required_str(input, "start", "date_diff")?;
+        let end = Self::required_str(input, "end", "date_diff")?;
+        let time_zone = Self::time_zone(input, "time_zone")?;
+        let unit = Self::span_unit(input, Unit::Day)?;
+        let start = parse_datetime(start, &time_zone).map_err(ToolError::InvalidInput)?;
+        let end = parse_datetime(end, &time_zone).map_err(ToolError::InvalidInput)?;
+
+        let failed = |e: jiff::Error| ToolError::ExecutionFailed(e.to_string());
+        let difference = start.until((Unit::Year, &end)).map_err(failed)?;
+        let total = start
+            .until((unit, &end))
+            .and_then(|span| span.total((unit, &start)))
+            .map_err(failed)?;
+        let elapsed_hours = end
+            .timestamp()
+            .duration_since(start.timestamp())
+            .as_secs_f64()
+            / 3600.0;
+        let weekdays =
+            count_weekdays(start.date(), end.date()).map_err(ToolError::ExecutionFailed)?;
+        let unit_name = format!("{:?}s", unit).to_lowercase();
+        let total = round_significant(total, SIGNIFICANT_DIGITS);
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("date_diff"))
+            .field("start", self.describe(&start))
+            .field("end", self.describe(&end))
+            .field("difference", json!(format!("{:#}", difference)))
+            .field("unit", json!(unit_name))
+            .field("total", json!(total))
+            .field(
+                "elapsed_hours",
+                json!(round_significant(elapsed_hours, SIGNIFICANT_DIGITS)),
+            )
+            .field("weekdays", json!(weekdays))
+            .message(format!("{:#} ({} {})", difference, total, unit_name))
+            .build())
+    }
+
+    /// Describes a date: weekday, ISO week, day of year, offset and DST.
+    fn date_info(&self, input: &Value) -> ToolResult<Value> {
+        let time_zone = Self::time_zone(input, "time_zone")?;
+        let zoned = parse_datetime(input["datetime"].as_str().unwrap_or("now"), &time_zone)
+            .map_err(ToolError::InvalidInput)?;
+        let offset_info = zoned.time_zone().to_offset_info(zoned.timestamp());
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("date_info"))
+            .field("result", self.describe(&zoned))
+            .field("iso_week", json!(zoned.date().iso_week_date().week()))
+            .field("day_of_year", json!(zoned.day_of_year()))
+            .field("days_in_month", json!(zoned.days_in_month()))
+            .field("leap_year", json!(zoned.in_leap_year()))
+            .field("abbreviation", json!(offset_info.abbreviation()))
+            .field("dst", json!(offset_info.dst().is_dst()))
+            .message(format!("{} ({})", zoned, zoned.strftime("%A")))
+            .build())
+    }
+
+    /// Parses a duration and returns its normalized forms and total.
+    fn parse_duration_total(&self, input: &Value) -> ToolResult<Value> {
+        let duration = Self::required_str(input, "duration", "parse_duration")?;
+        let unit = Self::span_unit(input, Unit::Second)?;
+        let span = parse_duration(duration).map_err(ToolError::InvalidInput)?;
+        let total = round_significant(
+            span_total(&span, unit).map_err(ToolError::InvalidInput)?,
+            SIGNIFICANT_DIGITS,
+        );
+        let unit_name = format!("{:?}s", unit).to_lowercase();
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("operation", json!("parse_duration"))
+            .field("duration", json!(format!("{:#}", span)))
+            .field("iso", json!(span.to_string()))
+            .field("unit", json!(unit_name))
+            .field("total", json!(total))
+            .field("formatted", json!(self.locale.format_number(total)))
+            .message(format!("{:#} = {} {}", span, total, unit_name))
+            .build())
+    }
+
+    /// Creates the tool with the locale of the settings.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let locale = load_locale_settings(&ctx.factory.get_db()).await.locale;
+            let tool = UnitsTool::with_locale(locale);
+            info!(locale = %locale, "UnitsTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "UnitsTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: UnitsTool::build,
+    })
+}
+
+impl Default for UnitsTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for UnitsTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "UnitsTool".to_string(),
+            name: "Units and Dates".to_string(),
+            description: r#"Converts units and computes with dates, time zones and durations.
+
+USE THIS TOOL WHEN:
+- You need to convert a quantity between units (miles to km, °F to °C, GiB to MB, cups to ml)
+- You need the time in another time zone (handles daylight saving time)
+- You need to add or subtract a duration to a date ("in 3 weeks", "90 days ago")
+- You need the time between two dates (days, hours, weekdays)
+- You need the weekday, ISO week or day of year of a date
+- You need the total of a duration ("2h 45m" in minutes)
+
+DO NOT USE THIS TOOL WHEN:
+- You need general math (use CalculatorTool)
+- You need currency conversion (exchange rates are not available)
+
+OPERATIONS:
+
+**convert** (requires "value", "from", "to"): unit conversion
+- Categories: length, mass, volume, area, speed, time, data, energy, power, pressure, angle, frequency, temperature
+- Units by symbol or name: "km", "miles", "°F", "kWh", "MiB", "Mbit", "fl oz"
+- Case matters for symbols that differ only by case: MB (megabyte) vs Mb (megabit)
+
+**list_units** (optional "category"): supported units
+
+**convert_timezone** (requires "datetime", "to_time_zone"; optional "time_zone"):
+- Converts a date-time to another IANA time zone ("Asia/Tokyo", "UTC", "local")
+
+**date_add** (requires "duration"; optional "datetime" (default now), "time_zone"):
+- Adds a calendar duration: "1 month", "2 weeks 3 days", "-90 days", "1h 30m ago", "P1Y2M"
+
+**date_diff** (requires "start", "end"; optional "unit" (default days), "time_zone"):
+- Returns the difference (years, months, days...), the total in "unit", the elapsed hours and the number of weekdays (Mon-Fri, end excluded)
+
+**date_info** (optional "datetime" (default now), "time_zone"):
+- Weekday, ISO week, day of year, days in month, leap year, offset, DST
+
+**parse_duration** (requires "duration"; optional "unit" (default seconds)):
+- Normalizes a duration and returns its total in "unit" (months and years are rejected: their length depends on the date)
+
+DATES:
+- ISO 8601: "2025-06-10", "2025-06-10T14:30", "2025-06-10T14:30:00+02:00", "2025-06-10T14:30[Europe/Paris]"
+- "now" and "today"
+- Dates without offset are read in "time_zone" (default: the user's local time zone)
+
+EXAMPLES:
+1. {"operation": "convert", "value": 26.2, "from": "miles", "to": "km"}
+2. {"operation": "convert", "value": 350, "from": "F", "to": "C"}
+3. {"operation": "convert_timezone", "datetime": "2025-06-10T09:00", "time_zone": "America/New_York", "to_time_zone": "Europe/Paris"}
+4. {"operation": "date_add", "datetime": "2025-01-31", "duration": "1 month"}
+5. {"operation": "date_diff", "start": "2025-06-10", "end": "2025-12-25", "unit": "days"}
+6. {"operation": "date_info", "datetime": "2025-12-25"}
+7. {"operation": "parse_duration", "duration": "2h 45m", "unit": "minutes"}
+
+OUTPUT:
+- result: numeric value or date-time description (datetime, time_zone, offset, weekday, formatted)
+- formatted: value in the user's format (use it when presenting the value)"#
+                .to_string(),
+
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "value": {
+                        "type": "number",
+                        "description": "Value to convert (for 'convert')"
+                    },
+                    "from": {
+                        "type": "string",
+                        "description": "Source unit symbol or name (for 'convert')"
+                    },
+                    "to": {
+                        "type": "string",
+                        "description": "Target unit symbol or name (for 'convert')"
+                    },
+                    "category": {
+                        "type": "string",
+                        "description": "Unit category (for 'list_units')"
+                    },
+                    "datetime": {
+                        "type": "string",
+                        "description": "ISO 8601 date or date-time, 'now' or 'today'"
+                    },
+                    "start": {
+                        "type": "string",
+                        "description": "Start date (for 'date_diff')"
+                    },
+                    "end": {
+                        "type": "string",
+                        "description": "End date (for 'date_diff')"
+                    },
+                    "duration": {
+                        "type": "string",
+                        "description": "Duration, e.g. '2 weeks 3 days', '1h 30m ago', 'P1M'"
+                    },
+                    "unit": {
+                        "type": "string",
+                        "enum": ["years", "months", "weeks", "days", "hours", "minutes", "seconds"],
+                        "description": "Unit of the total (for 'date_diff' and 'parse_duration')"
+                    },
+                    "time_zone": {
+                        "type": "string",
+                        "description": "IANA time zone of dates without offset (default: local)"
+                    },
+                    "to_time_zone": {
+                        "type": "string",
+                        "description": "Target IANA time zone (for 'convert_timezone')"
+                    }
+                },
+                "required": ["operation"]
+            }),
+
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "operation": {"type": "string"},
+                    "result": {},
+                    "formatted": {"type": "string"},
+                    "total": {"type": "number"},
+                    "unit": {"type": "string"},
+                    "difference": {"type": "string"},
+                    "weekdays": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+
+        let operation = input["operation"].as_str().unwrap_or_default();
+        debug!(operation = %operation, "Executing units operation");
+
+        match operation {
+            "convert" => self.convert_units(&input),
+            "list_units" => self.list_units(&input),
+            "convert_timezone" => self.convert_timezone(&input),
+            "date_add" => self.date_add(&input),
+            "date_diff" => self.date_diff(&input),
+            "date_info" => self.date_info(&input),
+            "parse_duration" => self.parse_duration_total(&input),
+            _ => Err(ToolError::InvalidInput(format!(
+                "Unknown operation: '{}'. Valid operations: {:?}",
+                operation, VALID_OPERATIONS
+            ))),
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        let operation = input["operation"].as_str().ok_or_else(|| {
+            ToolError::InvalidInput(
+                "Missing required field 'operation'. Specify operation type.".to_string(),
+            )
+        })?;
+
+        if !VALID_OPERATIONS.contains(&operation) {
+            return Err(ToolError::InvalidInput(format!(
+                "Unknown operation: '{}'. Valid operations: {:?}",
+                operation, VALID_OPERATIONS
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false // Conversions have no side effects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units() -> UnitsTool {
+        UnitsTool::new()
+    }
+
+    #[tokio::test]
+    async fn test_convert() {
+        let result = units()
+            .execute(json!({"operation": "convert", "value": 26.2, "from": "miles", "to": "km"}))
+            .await
+            .unwrap();
+        assert!(result["success"].as_bool().unwrap());
+        assert_eq!(result["result"], json!(42.1648128));
+        assert_eq!(result["category"], json!("length"));
+        assert_eq!(result["to"], json!("km"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_formatted_for_locale() {
+        let result = UnitsTool::with_locale(Locale::Fr)
+            .execute(json!({"operation": "convert", "value": 1.5, "from": "kg", "to": "g"}))
+            .await
+            .unwrap();
+        assert_eq!(result["formatted"], json!("1\u{202F}500"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_errors() {
+        let tool = units();
+        let result = tool
+            .execute(json!({"operation": "convert", "value": 1, "from": "kg", "to": "km"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::ExecutionFailed(_))));
+
+        let result = tool
+            .execute(json!({"operation": "convert", "value": 1, "from": "furlong", "to": "m"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::InvalidInput(msg)) if msg.contains("list_units")));
+
+        let result = tool
+            .execute(json!({"operation": "convert", "from": "kg", "to": "g"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_list_units() {
+        let result = units()
+            .execute(json!({"operation": "list_units", "category": "temperature"}))
+            .await
+            .unwrap();
+        let temperatures = result["units"]["temperature"].as_array().unwrap();
+        assert_eq!(temperatures.len(), 3);
+        assert!(result["units"]["length"].is_null());
+
+        let result = units()
+            .execute(json!({"operation": "list_units", "category": "money"}))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_convert_timezone_across_dst() {
+        let result = units()
+            .execute(json!({
+                "operation": "convert_timezone",
+                "datetime": "2025-03-10T09:00",
+                "time_zone": "America/New_York",
+                "to_time_zone": "Europe/Paris"
+            }))
+            .await
+            .unwrap();
+        // New York is already on DST, Paris is not yet: 5 hours apart
+        assert_eq!(
+            result["result"]["datetime"],
+            json!("2025-03-10T14:00:00+01:00[Europe/Paris]")
+        );
+        assert_eq!(result["source"]["offset"], json!("-04"));
+    }
+
+    #[tokio::test]
+    async fn test_date_add_clamps_month_end() {
+        let result = units()
+            .execute(json!({
+                "operation": "date_add",
+                "datetime": "2025-01-31",
+                "duration": "1 month",
+                "time_zone": "UTC"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(
+            result["result"]["datetime"],
+            json!("2025-02-28T00:00:00+00:00[UTC]")
+        );
+        assert_eq!(result["result"]["weekday"], json!("Friday"));
+    }
+
+    #[tokio::test]
+    async fn test_date_diff() {
+        let result = units()
+            .execute(json!({
+                "operation": "date_diff",
+                "start": "2025-06-09",
+                "end": "2025-06-23",
+                "time_zone": "UTC"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result["total"], json!(14.0));
+        assert_eq!(result["unit"], json!("days"));
+        assert_eq!(result["weekdays"], json!(10));
+        assert_eq!(result["difference"], json!("14d"));
+    }
+
+    #[tokio::test]
+    async fn test_date_diff_elapsed_hours_over_dst() {
+        let result = units()
+            .execute(json!({
+                "operation": "date_diff",
+                "start": "2025-03-29T12:00",
+                "end": "2025-03-30T12:00",
+                "time_zone": "Europe/Paris",
+                "unit": "hours"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result["elapsed_hours"], json!(23.0));
+    }
+
+    #[tokio::test]
+    async fn test_date_info() {
+        let result = units()
+            .execute(json!({
+                "operation": "date_info",
+                "datetime": "2024-12-30T12:00[Europe/Paris]"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result["result"]["weekday"], json!("Monday"));
+        // ISO week 1 of 2025
+        assert_eq!(result["iso_week"], json!(1));
+        assert_eq!(result["day_of_year"], json!(365));
+        assert_eq!(result["leap_year"], json!(true));
+        assert_eq!(result["dst"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn test_parse_duration() {
+        let result = units()
+            .execute(
+                json!({"operation": "parse_duration", "duration": "2h 45m", "unit": "minutes"}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["total"], json!(165.0));
+        assert_eq!(result["iso"], json!("PT2H45M"));
+
+        let result = units()
+            .execute(json!({"operation": "parse_duration", "duration": "1 month"}))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let tool = units();
+        assert!(tool.validate_input(&json!({})).is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "exchange_rate"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "date_info"}))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_definition() {
+        let definition = units().definition();
+        assert_eq!(definition.id, "UnitsTool");
+        assert!(!definition.requires_confirmation);
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chart Tool Module
+//!
+//! Provides the ChartTool, which renders data series as images:
+//! - Bar charts (grouped by series), line charts and pie charts
+//! - PNG or SVG output
+//!
+//! Rendered charts are stored as attachments of the assistant response.
+
+mod render;
+mod spec;
+mod tool;
+
+pub use tool::ChartTool;
+
+use crate::tools::constants::chart as chart_const;
+use crate::tools::utils::data_dir;
+use std::path::PathBuf;
+
+/// Directory of the rendered charts (`~/.zileo/charts`).
+///
+/// Only the SVG files of this directory are accepted as attachments: unlike
+/// the shared temp directory, other users cannot place files in it.
+pub fn output_dir() -> PathBuf {
+    data_dir().join(chart_const::OUTPUT_DIR_NAME)
+}
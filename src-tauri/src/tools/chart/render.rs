@@ -0,0 +1,324 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chart rendering with plotters (PNG bitmap or SVG).
+//!
+//! Text is drawn with a system font loaded on first use: plotters is built
+//! without system font discovery to stay free of native dependencies.
+
+use super::spec::{ChartFormat, ChartSpec, ChartType};
+use crate::tools::constants::chart as chart_const;
+use once_cell::sync::Lazy;
+use plotters::coord::ranged1d::{DefaultFormatting, KeyPointHint};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::register_font;
+use std::ops::Range;
+use std::path::Path;
+
+/// Font family used for all chart text.
+const FONT_FAMILY: &str = "sans-serif";
+
+/// Outcome of the font registration (done once per process).
+static FONT: Lazy<Result<(), String>> = Lazy::new(load_font);
+
+/// Registers the first usable font of `FONT_PATHS`.
+fn load_font() -> Result<(), String> {
+    for path in chart_const::FONT_PATHS {
+        let Ok(bytes) = std::fs::read(path) else {
+            continue;
+        };
+        // plotters keeps fonts for the process lifetime
+        let bytes: &'static [u8] = Box::leak(bytes.into_boxed_slice());
+        let registered = [FontStyle::Normal, FontStyle::Bold]
+            .into_iter()
+            .all(|style| register_font(FONT_FAMILY, style, bytes).is_ok());
+        if registered {
+            return Ok(());
+        }
+    }
+    Err("No font available to render chart text".to_string())
+}
+
+/// X axis of bar and line charts: one unit per category, labelled at the centers.
+struct CategoryAxis(usize);
+
+impl Ranged for CategoryAxis {
+    type FormatOption = DefaultFormatting;
+    type ValueType = f64;
+
+    fn map(&self, value: &f64, limit: (i32, i32)) -> i32 {
+        let size = f64::from(limit.1 - limit.0);
+        limit.0 + (value / self.0 as f64 * size).round() as i32
+    }
+
+    fn key_points<Hint: KeyPointHint>(&self, hint: Hint) -> Vec<f64> {
+        if hint.weight().allow_light_points() {
+            return Vec::new();
+        }
+        axis_key_points(self.0, hint.max_num_points())
+    }
+
+    fn range(&self) -> Range<f64> {
+        0.0..self.0 as f64
+    }
+}
+
+/// Renders a chart to an image file.
+///
+/// # Errors
+/// No font is available, or drawing/writing the image failed.
+pub fn render_chart(spec: &ChartSpec, path: &Path) -> Result<(), String> {
+    FONT.clone()?;
+    let size = (spec.width, spec.height);
+    match spec.format {
+        ChartFormat::Png => draw(spec, BitMapBackend::new(path, size).into_drawing_area()),
+        ChartFormat::Svg => draw(spec, SVGBackend::new(path, size).into_drawing_area()),
+    }
+}
+
+fn draw_error(e: impl std::fmt::Display) -> String {
+    format!("Failed to render chart: {}", e)
+}
+
+/// Draws the whole chart on a drawing area.
+fn draw<DB: DrawingBackend>(spec: &ChartSpec, root: DrawingArea<DB, Shift>) -> Result<(), String> {
+    root.fill(&WHITE).map_err(draw_error)?;
+    let area = match &spec.title {
+        Some(title) => root.titled(title, (FONT_FAMILY, 24)).map_err(draw_error)?,
+        None => root.clone(),
+    };
+    match spec.chart_type {
+        ChartType::Pie => draw_pie(spec, &area)?,
+        ChartType::Bar | ChartType::Line => draw_xy(spec, &area)?,
+    }
+    root.present().map_err(draw_error)
+}
+
+/// Draws a bar or line chart (one category per unit of the X axis).
+fn draw_xy<DB: DrawingBackend>(
+    spec: &ChartSpec,
+    area: &DrawingArea<DB, Shift>,
+) -> Result<(), String> {
+    let points = spec.point_count();
+    let (y_min, y_max) = value_range(spec);
+
+    let mut chart = ChartBuilder::on(area)
+        .margin(16)
+        .x_label_area_size(if spec.x_label.is_some() { 50 } else { 30 })
+        .y_label_area_size(if spec.y_label.is_some() { 70 } else { 55 })
+        .build_cartesian_2d(CategoryAxis(points), y_min..y_max)
+        .map_err(draw_error)?;
+
+    let label_formatter = |x: &f64| category_label(&spec.labels, *x);
+    let mut mesh = chart.configure_mesh();
+    mesh.disable_x_mesh()
+        .x_label_formatter(&label_formatter)
+        .label_style((FONT_FAMILY, 14));
+    if let Some(x_label) = &spec.x_label {
+        mesh.x_desc(x_label.as_str());
+    }
+    if let Some(y_label) = &spec.y_label {
+        mesh.y_desc(y_label.as_str());
+    }
+    mesh.draw().map_err(draw_error)?;
+
+    let bar_width = 0.8 / spec.series.len() as f64;
+    for (index, series) in spec.series.iter().enumerate() {
+        let color = series_color(index);
+        let annotation = if spec.chart_type == ChartType::Bar {
+            let offset = 0.1 + index as f64 * bar_width;
+            chart
+                .draw_series(series.values.iter().enumerate().map(|(i, value)| {
+                    let x = i as f64 + offset;
+                    Rectangle::new([(x, 0.0), (x + bar_width, *value)], color.filled())
+                }))
+                .map_err(draw_error)?
+        } else {
+            let line = series
+                .values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| (i as f64 + 0.5, *value));
+            chart
+                .draw_series(LineSeries::new(line, color.stroke_width(2)).point_size(3))
+                .map_err(draw_error)?
+        };
+        if !series.name.is_empty() {
+            annotation
+                .label(series.name.as_str())
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 5), (x + 12, y + 5)], color.filled())
+                });
+        }
+    }
+
+    if spec.series.iter().any(|series| !series.name.is_empty()) {
+        chart
+            .configure_series_labels()
+            .label_font((FONT_FAMILY, 14))
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(draw_error)?;
+    }
+    Ok(())
+}
+
+/// Draws a pie chart of the single series, one slice per label.
+fn draw_pie<DB: DrawingBackend>(
+    spec: &ChartSpec,
+    area: &DrawingArea<DB, Shift>,
+) -> Result<(), String> {
+    let (width, height) = area.dim_in_pixel();
+    let center = (width as i32 / 2, height as i32 / 2);
+    let radius = f64::from(width.min(height)) * 0.35;
+    let colors: Vec<RGBColor> = (0..spec.labels.len()).map(series_color).collect();
+
+    let mut pie = Pie::new(
+        &center,
+        &radius,
+        &spec.series[0].values,
+        &colors,
+        &spec.labels,
+    );
+    pie.start_angle(-90.0);
+    pie.label_style((FONT_FAMILY, 14).into_font().color(&BLACK));
+    pie.percentages((FONT_FAMILY, 12).into_font().color(&WHITE));
+    area.draw(&pie).map_err(draw_error)
+}
+
+/// Color of the n-th series (or pie slice).
+fn series_color(index: usize) -> RGBColor {
+    let (r, g, b) = Palette99::COLORS[index % Palette99::COLORS.len()];
+    RGBColor(r, g, b)
+}
+
+/// Y axis range covering all values, with a margin (bars always start at 0).
+fn value_range(spec: &ChartSpec) -> (f64, f64) {
+    let values = spec
+        .series
+        .iter()
+        .flat_map(|series| series.values.iter().copied());
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    });
+    let padding = if max > min {
+        (max - min) * 0.05
+    } else {
+        max.abs().max(1.0) * 0.1
+    };
+
+    if spec.chart_type == ChartType::Bar {
+        let lower = if min >= 0.0 { 0.0 } else { min - padding };
+        let upper = if max <= 0.0 { 0.0 } else { max + padding };
+        (lower, upper)
+    } else {
+        (min - padding, max + padding)
+    }
+}
+
+/// X positions of the labelled categories (centers, thinned out past
+/// `MAX_AXIS_LABELS` or the labels fitting on the axis).
+fn axis_key_points(points: usize, max_labels: usize) -> Vec<f64> {
+    let max_labels = max_labels.clamp(1, chart_const::MAX_AXIS_LABELS);
+    let step = points.div_ceil(max_labels).max(1);
+    (0..points).step_by(step).map(|i| i as f64 + 0.5).collect()
+}
+
+/// Label of the category centered at `x` (its 1-based position without labels).
+fn category_label(labels: &[String], x: f64) -> String {
+    let index = (x - 0.5).round().max(0.0) as usize;
+    labels
+        .get(index)
+        .cloned()
+        .unwrap_or_else(|| (index + 1).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(input: serde_json::Value) -> ChartSpec {
+        ChartSpec::from_input(&input).unwrap()
+    }
+
+    #[test]
+    fn test_value_range() {
+        let bar = spec(json!({
+            "chart_type": "bar",
+            "labels": ["a", "b"],
+            "series": [{"values": [10, 20]}]
+        }));
+        let (min, max) = value_range(&bar);
+        assert_eq!(min, 0.0);
+        assert!(max > 20.0);
+
+        let line = spec(json!({"chart_type": "line", "series": [{"values": [10, 20]}]}));
+        let (min, max) = value_range(&line);
+        assert!(min > 0.0 && min < 10.0);
+        assert!(max > 20.0);
+
+        let flat = spec(json!({"chart_type": "line", "series": [{"values": [5, 5]}]}));
+        let (min, max) = value_range(&flat);
+        assert!(min < 5.0 && max > 5.0);
+    }
+
+    #[test]
+    fn test_axis_labels() {
+        assert_eq!(axis_key_points(3, 10), vec![0.5, 1.5, 2.5]);
+        assert_eq!(axis_key_points(3, 2), vec![0.5, 2.5]);
+        assert_eq!(
+            axis_key_points(100, usize::MAX).len(),
+            chart_const::MAX_AXIS_LABELS
+        );
+
+        let labels = vec!["Q1".to_string(), "Q2".to_string()];
+        assert_eq!(category_label(&labels, 1.5), "Q2");
+        assert_eq!(category_label(&[], 2.5), "3");
+    }
+
+    #[test]
+    fn test_render_chart_files() {
+        if FONT.is_err() {
+            // No system font in this environment
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let charts = [
+            json!({
+                "chart_type": "bar",
+                "title": "Sales",
+                "labels": ["Q1", "Q2", "Q3"],
+                "series": [{"name": "2024", "values": [3, -1, 4]}, {"name": "2025", "values": [2, 5, 1]}]
+            }),
+            json!({"chart_type": "line", "format": "svg", "x_label": "day", "series": [{"values": [1, 3, 2, 5]}]}),
+            json!({"chart_type": "pie", "labels": ["a", "b", "c"], "series": [{"values": [1, 2, 3]}]}),
+        ];
+        for (index, input) in charts.into_iter().enumerate() {
+            let spec = spec(input);
+            let path = dir
+                .path()
+                .join(format!("chart-{}.{}", index, spec.format.extension()));
+            render_chart(&spec, &path).unwrap();
+
+            let bytes = std::fs::read(&path).unwrap();
+            match spec.format {
+                ChartFormat::Png => assert!(bytes.starts_with(b"\x89PNG")),
+                ChartFormat::Svg => assert!(String::from_utf8_lossy(&bytes).contains("<svg")),
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chart specification parsed from the tool input.
+
+use crate::tools::constants::chart as chart_const;
+use crate::tools::utils::{validate_enum_value, validate_length};
+use crate::tools::{ToolError, ToolResult};
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Kind of chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartType {
+    Bar,
+    Line,
+    Pie,
+}
+
+/// Image format of a rendered chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartFormat {
+    Png,
+    Svg,
+}
+
+impl ChartFormat {
+    /// File extension of the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ChartFormat::Png => "png",
+            ChartFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Named series of values.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChartSeries {
+    /// Series name (legend)
+    #[serde(default)]
+    pub name: String,
+    /// One value per category (or X position)
+    pub values: Vec<f64>,
+}
+
+/// Validated chart specification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSpec {
+    pub chart_type: ChartType,
+    pub format: ChartFormat,
+    pub title: Option<String>,
+    /// Category labels (X axis of bar/line charts, slices of pie charts)
+    pub labels: Vec<String>,
+    pub series: Vec<ChartSeries>,
+    pub x_label: Option<String>,
+    pub y_label: Option<String>,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ChartSpec {
+    /// Parses and validates a chart specification from the tool input.
+    ///
+    /// # Errors
+    /// Unknown type or format, missing or inconsistent data, limits exceeded.
+    pub fn from_input(input: &Value) -> ToolResult<Self> {
+        let chart_type = input["chart_type"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing 'chart_type' field".to_string()))?;
+        validate_enum_value(chart_type, chart_const::VALID_TYPES, "chart_type")?;
+        let chart_type = match chart_type {
+            "bar" => ChartType::Bar,
+            "line" => ChartType::Line,
+            _ => ChartType::Pie,
+        };
+
+        let format = match input.get("format").and_then(Value::as_str) {
+            None => ChartFormat::Png,
+            Some(format) => {
+                validate_enum_value(format, chart_const::VALID_FORMATS, "format")?;
+                if format == "svg" {
+                    ChartFormat::Svg
+                } else {
+                    ChartFormat::Png
+                }
+            }
+        };
+
+        let title = optional_text(input, "title")?;
+        let x_label = optional_text(input, "x_label")?;
+        let y_label = optional_text(input, "y_label")?;
+
+        let labels: Vec<String> = match input.get("labels").filter(|v| !v.is_null()) {
+            None => Vec::new(),
+            Some(labels) => serde_json::from_value(labels.clone()).map_err(|_| {
+                ToolError::InvalidInput("'labels' must be an array of strings".to_string())
+            })?,
+        };
+        let series: Vec<ChartSeries> = match input.get("series").filter(|v| !v.is_null()) {
+            None => Vec::new(),
+            Some(series) => serde_json::from_value(series.clone()).map_err(|_| {
+                ToolError::InvalidInput(
+                    "'series' must be an array of {\"name\": string, \"values\": [numbers]}"
+                        .to_string(),
+                )
+            })?,
+        };
+
+        let spec = Self {
+            chart_type,
+            format,
+            title,
+            labels,
+            series,
+            x_label,
+            y_label,
+            width: dimension(input, "width", chart_const::DEFAULT_WIDTH)?,
+            height: dimension(input, "height", chart_const::DEFAULT_HEIGHT)?,
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Number of values of each series.
+    pub fn point_count(&self) -> usize {
+        self.series.first().map_or(0, |series| series.values.len())
+    }
+
+    /// Checks the consistency of the data.
+    fn validate(&self) -> ToolResult<()> {
+        if self.series.is_empty() {
+            return Err(ToolError::InvalidInput(
+                "At least one series is required".to_string(),
+            ));
+        }
+        if self.series.len() > chart_const::MAX_SERIES {
+            return Err(ToolError::InvalidInput(format!(
+                "At most {} series are allowed",
+                chart_const::MAX_SERIES
+            )));
+        }
+
+        let points = self.point_count();
+        if points == 0 || points > chart_const::MAX_POINTS {
+            return Err(ToolError::InvalidInput(format!(
+                "Each series needs between 1 and {} values",
+                chart_const::MAX_POINTS
+            )));
+        }
+        for series in &self.series {
+            validate_length(&series.name, chart_const::MAX_LABEL_LENGTH, "series name")?;
+            if series.values.len() != points {
+                return Err(ToolError::InvalidInput(
+                    "All series must have the same number of values".to_string(),
+                ));
+            }
+            if series.values.iter().any(|value| !value.is_finite()) {
+                return Err(ToolError::InvalidInput(
+                    "Series values must be finite numbers".to_string(),
+                ));
+            }
+        }
+
+        for label in &self.labels {
+            validate_length(label, chart_const::MAX_LABEL_LENGTH, "label")?;
+        }
+        if self.labels.is_empty() {
+            if self.chart_type != ChartType::Line {
+                return Err(ToolError::InvalidInput(
+                    "'labels' are required for bar and pie charts".to_string(),
+                ));
+            }
+        } else if self.labels.len() != points {
+            return Err(ToolError::InvalidInput(format!(
+                "Expected {} labels (one per value), got {}",
+                points,
+                self.labels.len()
+            )));
+        }
+
+        if self.chart_type == ChartType::Pie {
+            if self.series.len() != 1 {
+                return Err(ToolError::InvalidInput(
+                    "A pie chart takes exactly one series".to_string(),
+                ));
+            }
+            let values = &self.series[0].values;
+            if values.iter().any(|value| *value < 0.0) || values.iter().sum::<f64>() <= 0.0 {
+                return Err(ToolError::InvalidInput(
+                    "Pie chart values must be non-negative with a positive total".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads an optional text field (title, axis description).
+fn optional_text(input: &Value, field: &str) -> ToolResult<Option<String>> {
+    match input.get(field).filter(|v| !v.is_null()) {
+        None => Ok(None),
+        Some(value) => {
+            let text = value
+                .as_str()
+                .ok_or_else(|| ToolError::InvalidInput(format!("'{}' must be a string", field)))?
+                .trim();
+            validate_length(text, chart_const::MAX_TITLE_LENGTH, field)?;
+            Ok(Some(text.to_string()).filter(|text| !text.is_empty()))
+        }
+    }
+}
+
+/// Reads an optional image dimension.
+fn dimension(input: &Value, field: &str, default: u32) -> ToolResult<u32> {
+    match input.get(field).filter(|v| !v.is_null()) {
+        None => Ok(default),
+        Some(value) => value
+            .as_u64()
+            .filter(|value| {
+                (chart_const::MIN_DIMENSION as u64..=chart_const::MAX_DIMENSION as u64)
+                    .contains(value)
+            })
+            .map(|value| value as u32)
+            .ok_or_else(|| {
+                ToolError::InvalidInput(format!(
+                    "'{}' must be between {} and {} pixels",
+                    field,
+                    chart_const::MIN_DIMENSION,
+                    chart_const::MAX_DIMENSION
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_bar_chart() {
+        let spec = ChartSpec::from_input(&json!({
+            "chart_type": "bar",
+            "title": " Sales ",
+            "labels": ["Q1", "Q2", "Q3"],
+            "series": [
+                {"name": "2024", "values": [10, 12.5, 8]},
+                {"name": "2025", "values": [11, 14, 9]}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(spec.chart_type, ChartType::Bar);
+        assert_eq!(spec.format, ChartFormat::Png);
+        assert_eq!(spec.title.as_deref(), Some("Sales"));
+        assert_eq!(spec.point_count(), 3);
+        assert_eq!(spec.series[0].values, vec![10.0, 12.5, 8.0]);
+        assert_eq!(
+            (spec.width, spec.height),
+            (chart_const::DEFAULT_WIDTH, chart_const::DEFAULT_HEIGHT)
+        );
+    }
+
+    #[test]
+    fn test_line_chart_labels_optional() {
+        let spec = ChartSpec::from_input(&json!({
+            "chart_type": "line",
+            "format": "svg",
+            "series": [{"name": "latency", "values": [1, 2, 3]}],
+            "width": 400
+        }))
+        .unwrap();
+        assert_eq!(spec.format, ChartFormat::Svg);
+        assert!(spec.labels.is_empty());
+        assert_eq!(spec.width, 400);
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        let invalid = [
+            json!({"chart_type": "radar", "labels": ["a"], "series": [{"values": [1]}]}),
+            json!({"chart_type": "bar", "format": "gif", "labels": ["a"], "series": [{"values": [1]}]}),
+            json!({"chart_type": "bar", "series": [{"values": [1]}]}),
+            json!({"chart_type": "bar", "labels": ["a", "b"], "series": [{"values": [1]}]}),
+            json!({"chart_type": "line", "series": []}),
+            json!({"chart_type": "line", "series": [{"values": [1, 2]}, {"values": [1]}]}),
+            json!({"chart_type": "line", "series": [{"values": ["x"]}]}),
+            json!({"chart_type": "line", "series": [{"values": [1]}], "width": 50}),
+            json!({"chart_type": "pie", "labels": ["a", "b"], "series": [{"values": [1, -1]}]}),
+            json!({"chart_type": "pie", "labels": ["a"], "series": [{"values": [1]}, {"values": [2]}]}),
+        ];
+        for input in invalid {
+            assert!(ChartSpec::from_input(&input).is_err(), "{}", input);
+        }
+    }
+}
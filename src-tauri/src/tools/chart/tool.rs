@@ -0,0 +1,286 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ChartTool implementation.
+//!
+//! Charts are rendered to the system temp directory; the paths reported in
+//! the tool results are attached to the assistant response when it is saved.
+
+use super::render::render_chart;
+use super::spec::{ChartSpec, ChartType};
+use crate::tools::constants::chart as chart_const;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Tool rendering bar, line and pie charts as images.
+pub struct ChartTool {
+    /// Current workflow ID (sub-directory of the rendered charts)
+    workflow_id: String,
+    /// Agent ID using this tool
+    agent_id: String,
+    /// Directory of the rendered charts
+    output_dir: PathBuf,
+}
+
+impl ChartTool {
+    /// Creates a new ChartTool.
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Workflow ID
+    /// * `agent_id` - Agent ID using the tool
+    pub fn new(workflow_id: String, agent_id: String) -> Self {
+        Self {
+            workflow_id,
+            agent_id,
+            output_dir: super::output_dir(),
+        }
+    }
+
+    /// Renders a chart and returns the path of the image.
+    #[instrument(skip(self, spec), fields(agent_id = %self.agent_id))]
+    async fn render(&self, spec: ChartSpec) -> ToolResult<Value> {
+        let chart_id = Uuid::new_v4().to_string();
+        let dir = self.output_dir.join(&self.workflow_id);
+        let path = dir.join(format!("{}.{}", chart_id, spec.format.extension()));
+        tokio::fs::create_dir_all(&dir).await.map_err(|e| {
+            ToolError::ExecutionFailed(format!("Failed to create chart directory: {}", e))
+        })?;
+
+        let chart_type = spec.chart_type;
+        let format = spec.format.extension();
+        let render_path = path.clone();
+        tokio::task::spawn_blocking(move || render_chart(&spec, &render_path))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Chart rendering failed: {}", e)))?
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(chart_id = %chart_id, path = %path.display(), "Chart rendered");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .id("chart_id", chart_id)
+            .field(
+                "chart_type",
+                match chart_type {
+                    ChartType::Bar => "bar",
+                    ChartType::Line => "line",
+                    ChartType::Pie => "pie",
+                },
+            )
+            .field("format", format)
+            .field("path", path.to_string_lossy().into_owned())
+            .message(
+                "Chart rendered; it is attached to your response. \
+                 Describe what it shows, without linking the file",
+            )
+            .build())
+    }
+
+    /// Creates the tool for the workflow of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = ChartTool::new(wf_id, ctx.agent_id);
+            info!("ChartTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "ChartTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: ChartTool::build,
+    })
+}
+
+#[async_trait]
+impl Tool for ChartTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "ChartTool".to_string(),
+            name: "Chart Tool".to_string(),
+            description: format!(
+                r#"Renders a bar, line or pie chart as an image attached to your response.
+
+USE THIS TOOL WHEN:
+- Numbers are easier to understand visually (comparisons, trends, shares)
+- The user asks for a chart or graph
+
+CHART TYPES:
+- bar: one bar per label and series (grouped when there are several series)
+- line: one line per series; labels are optional X positions
+- pie: exactly one series of non-negative values, one slice per label
+
+IMPORTANT CONSTRAINTS:
+- At most {} series of at most {} values, all series with the same number of values
+- "labels" has one entry per value (required for bar and pie)
+- Format "png" (default) or "svg"
+- The image is attached automatically: do not add links or file paths to your answer
+
+EXAMPLES:
+1. {{"chart_type": "bar", "title": "Revenue by quarter", "labels": ["Q1", "Q2", "Q3", "Q4"], "series": [{{"name": "2024", "values": [120, 135, 150, 170]}}, {{"name": "2025", "values": [140, 160, 155, 190]}}], "y_label": "kEUR"}}
+2. {{"chart_type": "line", "title": "Daily active users", "labels": ["Mon", "Tue", "Wed"], "series": [{{"name": "users", "values": [320, 410, 380]}}]}}
+3. {{"chart_type": "pie", "title": "Traffic sources", "labels": ["Search", "Direct", "Social"], "series": [{{"values": [55, 30, 15]}}], "format": "svg"}}"#,
+                chart_const::MAX_SERIES,
+                chart_const::MAX_POINTS
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "chart_type": {
+                        "type": "string",
+                        "enum": chart_const::VALID_TYPES,
+                        "description": "Kind of chart"
+                    },
+                    "title": {
+                        "type": "string",
+                        "description": "Chart title"
+                    },
+                    "labels": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "One label per value (categories, X positions or pie slices)"
+                    },
+                    "series": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "name": {"type": "string", "description": "Series name (legend)"},
+                                "values": {"type": "array", "items": {"type": "number"}}
+                            },
+                            "required": ["values"]
+                        },
+                        "description": "Data series"
+                    },
+                    "x_label": {
+                        "type": "string",
+                        "description": "X axis description (bar and line)"
+                    },
+                    "y_label": {
+                        "type": "string",
+                        "description": "Y axis description (bar and line)"
+                    },
+                    "format": {
+                        "type": "string",
+                        "enum": chart_const::VALID_FORMATS,
+                        "description": "Image format (default: png)"
+                    },
+                    "width": {
+                        "type": "integer",
+                        "minimum": chart_const::MIN_DIMENSION,
+                        "maximum": chart_const::MAX_DIMENSION,
+                        "description": "Image width in pixels"
+                    },
+                    "height": {
+                        "type": "integer",
+                        "minimum": chart_const::MIN_DIMENSION,
+                        "maximum": chart_const::MAX_DIMENSION,
+                        "description": "Image height in pixels"
+                    }
+                },
+                "required": ["chart_type", "series"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "chart_id": {"type": "string"},
+                    "chart_type": {"type": "string"},
+                    "format": {"type": "string"},
+                    "path": {"type": "string"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        self.render(ChartSpec::from_input(&input)?).await
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+        ChartSpec::from_input(input).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definition() {
+        let tool = ChartTool::new("wf".to_string(), "agent".to_string());
+        let definition = tool.definition();
+        assert_eq!(definition.id, "ChartTool");
+        assert!(!definition.requires_confirmation);
+        assert_eq!(
+            definition.input_schema["required"],
+            json!(["chart_type", "series"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_renders_chart() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = ChartTool {
+            output_dir: dir.path().to_path_buf(),
+            ..ChartTool::new("wf-1".to_string(), "agent".to_string())
+        };
+
+        assert!(tool.execute(json!("bar")).await.is_err());
+        assert!(tool
+            .execute(json!({"chart_type": "pie", "series": [{"values": [1]}]}))
+            .await
+            .is_err());
+
+        let result = tool
+            .execute(json!({
+                "chart_type": "bar",
+                "labels": ["a", "b"],
+                "series": [{"name": "n", "values": [1, 2]}],
+                "format": "svg"
+            }))
+            .await;
+        let Ok(result) = result else {
+            // No system font in this environment
+            return;
+        };
+        assert_eq!(result["success"], true);
+        assert_eq!(result["format"], "svg");
+        let path = PathBuf::from(result["path"].as_str().unwrap());
+        assert!(path.starts_with(dir.path().join("wf-1")));
+        assert!(path.is_file());
+    }
+}
@@ -0,0 +1,242 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-agent tool configuration wrapper.
+//!
+//! [`ConfiguredTool`] wraps any [`Tool`] and applies the [`ToolSettings`] of the
+//! agent that owns it:
+//! - `defaults` are merged into the input when the LLM omits a parameter
+//! - `fixed` values always win; a conflicting value from the LLM is rejected
+//! - `allowed_operations` restricts the `operation` parameter
+//!
+//! The wrapped tool's definition is adjusted so the LLM only sees the
+//! operations it may call and no longer sees fixed parameters.
+
+use crate::models::ToolSettings;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Tool wrapper enforcing per-agent parameter defaults and restrictions.
+pub struct ConfiguredTool {
+    /// Underlying tool instance
+    inner: Arc<dyn Tool>,
+    /// Settings applied to every call
+    settings: ToolSettings,
+}
+
+impl ConfiguredTool {
+    /// Wraps a tool with agent-specific settings.
+    pub fn new(inner: Arc<dyn Tool>, settings: ToolSettings) -> Self {
+        Self { inner, settings }
+    }
+
+    /// Applies defaults, fixed values and operation restrictions to an input.
+    ///
+    /// # Errors
+    /// * `PermissionDenied` - Operation not allowed or fixed parameter overridden
+    /// * `InvalidInput` - Input is not a JSON object
+    fn apply(&self, input: &Value) -> ToolResult<Value> {
+        let Some(obj) = input.as_object() else {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        };
+        let mut obj = obj.clone();
+        let tool_id = self.inner.definition().id;
+
+        if let Some(ref allowed) = self.settings.allowed_operations {
+            if let Some(op) = obj.get("operation").and_then(|v| v.as_str()) {
+                if !allowed.iter().any(|a| a == op) {
+                    warn!(tool = %tool_id, operation = %op, "Operation blocked by agent tool settings");
+                    return Err(ToolError::PermissionDenied(format!(
+                        "Operation '{}' is not allowed for this agent. Allowed operations: {}",
+                        op,
+                        allowed.join(", ")
+                    )));
+                }
+            }
+        }
+
+        for (key, value) in &self.settings.fixed {
+            match obj.get(key) {
+                Some(provided) if provided != value => {
+                    warn!(tool = %tool_id, parameter = %key, "Fixed parameter override rejected");
+                    return Err(ToolError::PermissionDenied(format!(
+                        "Parameter '{}' is fixed to {} for this agent and cannot be changed",
+                        key, value
+                    )));
+                }
+                _ => {
+                    obj.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        for (key, value) in &self.settings.defaults {
+            if obj.get(key).map(Value::is_null).unwrap_or(true) {
+                obj.insert(key.clone(), value.clone());
+            }
+        }
+
+        Ok(Value::Object(obj))
+    }
+}
+
+#[async_trait]
+impl Tool for ConfiguredTool {
+    /// Returns the inner definition restricted to the agent's settings.
+    fn definition(&self) -> ToolDefinition {
+        let mut definition = self.inner.definition();
+        let schema = &mut definition.input_schema;
+
+        if let Some(ref allowed) = self.settings.allowed_operations {
+            if let Some(ops) = schema
+                .pointer_mut("/properties/operation/enum")
+                .and_then(|v| v.as_array_mut())
+            {
+                ops.retain(|op| op.as_str().is_some_and(|s| allowed.iter().any(|a| a == s)));
+            }
+        }
+
+        if let Some(props) = schema.get_mut("properties").and_then(|v| v.as_object_mut()) {
+            for key in self.settings.fixed.keys() {
+                props.remove(key);
+            }
+        }
+        if let Some(required) = schema.get_mut("required").and_then(|v| v.as_array_mut()) {
+            required.retain(|r| {
+                r.as_str()
+                    .is_none_or(|s| !self.settings.fixed.contains_key(s))
+            });
+        }
+
+        definition
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        let input = self.apply(&input)?;
+        self.inner.execute(input).await
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        let input = self.apply(input)?;
+        self.inner.validate_input(&input)
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        self.inner.requires_confirmation()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Tool echoing its input, used to observe applied settings
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn definition(&self) -> ToolDefinition {
+            ToolDefinition {
+                id: "EchoTool".to_string(),
+                name: "Echo".to_string(),
+                description: "Echoes input".to_string(),
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "operation": {"type": "string", "enum": ["read", "write", "delete"]},
+                        "scope": {"type": "string"},
+                        "workflow_id": {"type": "string"}
+                    },
+                    "required": ["operation", "workflow_id"]
+                }),
+                output_schema: json!({}),
+                requires_confirmation: false,
+            }
+        }
+
+        async fn execute(&self, input: Value) -> ToolResult<Value> {
+            Ok(input)
+        }
+
+        fn validate_input(&self, _input: &Value) -> ToolResult<()> {
+            Ok(())
+        }
+    }
+
+    fn configured(settings: Value) -> ConfiguredTool {
+        ConfiguredTool::new(
+            Arc::new(EchoTool),
+            serde_json::from_value(settings).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_defaults_applied_when_missing() {
+        let tool = configured(json!({"defaults": {"scope": "general"}}));
+
+        let result = tool.execute(json!({"operation": "read"})).await.unwrap();
+        assert_eq!(result["scope"], "general");
+
+        let result = tool
+            .execute(json!({"operation": "read", "scope": "workflow"}))
+            .await
+            .unwrap();
+        assert_eq!(result["scope"], "workflow");
+    }
+
+    #[tokio::test]
+    async fn test_fixed_values_enforced() {
+        let tool = configured(json!({"fixed": {"workflow_id": "wf_locked"}}));
+
+        let result = tool.execute(json!({"operation": "read"})).await.unwrap();
+        assert_eq!(result["workflow_id"], "wf_locked");
+
+        let result = tool
+            .execute(json!({"operation": "read", "workflow_id": "wf_other"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_allowed_operations() {
+        let tool = configured(json!({"allowed_operations": ["read"]}));
+
+        assert!(tool.execute(json!({"operation": "read"})).await.is_ok());
+        let result = tool.execute(json!({"operation": "delete"})).await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+        assert!(tool.validate_input(&json!({"operation": "write"})).is_err());
+    }
+
+    #[test]
+    fn test_definition_restricted() {
+        let tool = configured(json!({
+            "fixed": {"workflow_id": "wf_locked"},
+            "allowed_operations": ["read", "write"]
+        }));
+
+        let schema = tool.definition().input_schema;
+        assert_eq!(
+            schema["properties"]["operation"]["enum"],
+            json!(["read", "write"])
+        );
+        assert!(schema["properties"].get("workflow_id").is_none());
+        assert_eq!(schema["required"], json!(["operation"]));
+    }
+}
@@ -41,6 +41,19 @@ pub mod memory {
     pub const SCORE_WEIGHT_RECENCY: f64 = 0.15;
     pub const RECENCY_DECAY_DAYS: f64 = 30.0;
 
+    // Background embedding queue
+    pub const EMBEDDING_MAX_CONCURRENT: usize = 4;
+    pub const EMBEDDING_MAX_ATTEMPTS: u32 = 3;
+    pub const EMBEDDING_RETRY_BASE_MS: u64 = 1000;
+
+    // Embedding regeneration
+    /// Memories embedded per provider request
+    pub const REGENERATION_BATCH_SIZE: usize = 32;
+    /// Batches embedded concurrently
+    pub const REGENERATION_CONCURRENCY: usize = 4;
+    /// Largest embedding dimension accepted for the vector index
+    pub const MAX_EMBEDDING_DIMENSION: usize = 8192;
+
     /// Types that are stored as general (cross-workflow) by default
     pub const GENERAL_SCOPE_TYPES: &[&str] = &["user_pref", "knowledge"];
     /// Types that are stored as workflow-scoped by default (complement of GENERAL_SCOPE_TYPES)
@@ -89,6 +102,18 @@ pub mod user_question {
     pub const CIRCUIT_COOLDOWN_SECS: u64 = 60;
 }
 
+// ===== Resource Tool =====
+pub mod resource {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &["list", "read"];
+
+    /// Maximum characters of text content returned per resource item
+    pub const MAX_TEXT_CHARS: usize = 50_000;
+
+    /// Maximum number of resources read in one call (`uris`)
+    pub const MAX_READ_URIS: usize = 10;
+}
+
 // ===== Sub-Agent Tools =====
 #[allow(unused_imports)]
 pub mod sub_agent {
@@ -111,12 +136,16 @@ pub mod sub_agent {
     /// Maximum characters for task description truncation.
     pub const TASK_DESC_TRUNCATE_CHARS: usize = 100;
 
-    /// Default timeout for validation responses (seconds).
+    /// Default timeout for validation responses (seconds), see `ValidationSettings::timeout`.
     pub const VALIDATION_TIMEOUT_SECS: u64 = 60;
 
     /// Polling interval for checking validation status (milliseconds).
     pub const VALIDATION_POLL_MS: u64 = 500;
 
+    /// Number of times a validation request is asked before being rejected
+    /// (`ask_again` timeout behavior).
+    pub const VALIDATION_MAX_ASKS: u32 = 3;
+
     // OPT-SA-8: Circuit Breaker for Sub-Agent Execution
     /// Number of consecutive failures before opening the circuit breaker.
     /// When reached, sub-agent executions are rejected until cooldown expires.
@@ -162,6 +191,349 @@ pub mod calculator {
     pub const VALID_CONSTANTS: &[&str] = &["pi", "e", "tau", "sqrt2", "ln2", "ln10"];
 }
 
+// ===== Units Tool =====
+/// Operations and precision of the UnitsTool.
+pub mod units {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &[
+        "convert",
+        "list_units",
+        "convert_timezone",
+        "date_add",
+        "date_diff",
+        "date_info",
+        "parse_duration",
+    ];
+    /// Significant digits kept in numeric results (removes floating point noise)
+    pub const SIGNIFICANT_DIGITS: i32 = 12;
+}
+
+// ===== Text Tool =====
+/// Operations and limits of the TextTool.
+pub mod text {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &[
+        "regex_match",
+        "regex_replace",
+        "diff",
+        "convert",
+        "hash",
+        "encode",
+        "decode",
+    ];
+    /// Valid encodings of encode/decode
+    pub const VALID_ENCODINGS: &[&str] = &["base64", "base64url", "hex", "url", "html"];
+    /// Valid hash algorithms
+    pub const VALID_ALGORITHMS: &[&str] = &["md5", "sha1", "sha256", "sha512"];
+    /// Maximum length of an input text (bytes)
+    pub const MAX_TEXT_LENGTH: usize = 500_000;
+    /// Maximum length of a regex pattern
+    pub const MAX_PATTERN_LENGTH: usize = 1_000;
+    /// Maximum size of a compiled regex (bytes)
+    pub const REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+    /// Matches returned by regex_match by default
+    pub const DEFAULT_MAX_MATCHES: usize = 100;
+    /// Maximum matches returned by regex_match
+    pub const MAX_MATCHES: usize = 1_000;
+    /// Unchanged lines shown around each diff hunk by default
+    pub const DEFAULT_CONTEXT_LINES: usize = 3;
+    /// Maximum unchanged lines around each diff hunk
+    pub const MAX_CONTEXT_LINES: usize = 50;
+}
+
+// ===== PDF Tool =====
+/// Operations and limits of the PdfTool and of the document extraction.
+pub mod pdf {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &["extract_text", "extract_tables", "metadata", "create"];
+    /// File extensions read by the document extraction
+    pub const SUPPORTED_EXTENSIONS: &[&str] = &["pdf", "md", "markdown", "txt"];
+    /// Maximum size of a file read by the document extraction (bytes)
+    pub const MAX_FILE_BYTES: u64 = 50 * 1024 * 1024;
+    /// Characters of extracted text returned to the agent
+    pub const MAX_TEXT_CHARS: usize = 100_000;
+    /// Maximum tables returned by extract_tables
+    pub const MAX_TABLES: usize = 50;
+    /// Maximum length of the Markdown of a generated PDF
+    pub const MAX_MARKDOWN_LENGTH: usize = 200_000;
+    /// Maximum length of a generated PDF title
+    pub const MAX_TITLE_LENGTH: usize = 200;
+    /// Directory of the generated PDFs, under the user's documents
+    pub const OUTPUT_DIR_NAME: &str = "Zileo";
+}
+
+// ===== OCR Tool =====
+/// Operations, engine defaults and limits of the OcrTool.
+pub mod ocr {
+    /// Settings record of the OCR engine
+    pub const SETTINGS_KEY: &str = "settings:ocr";
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &["recognize", "list_languages"];
+    /// Image extensions recognized directly (PDFs are read page by page)
+    pub const IMAGE_EXTENSIONS: &[&str] = &[
+        "png", "jpg", "jpeg", "tif", "tiff", "bmp", "gif", "webp", "pnm", "pgm", "ppm",
+    ];
+    /// Tesseract language pack used when none is configured
+    pub const DEFAULT_LANGUAGE: &str = "eng";
+    /// Vision model used by the Ollama engine by default
+    pub const DEFAULT_OLLAMA_MODEL: &str = "llama3.2-vision";
+    /// Instruction sent with each image to the Ollama vision model
+    pub const OLLAMA_PROMPT: &str = "Transcribe all the text in this image exactly as written, \
+        keeping the line breaks. Reply with the text only, without any comment.";
+    /// Maximum language packs combined in one recognition
+    pub const MAX_LANGUAGES: usize = 8;
+    /// Maximum size of a recognized image (bytes)
+    pub const MAX_IMAGE_BYTES: u64 = 25 * 1024 * 1024;
+    /// Maximum PDF pages recognized in one call
+    pub const MAX_PDF_PAGES: usize = 20;
+    /// Recognition timeout per image (seconds)
+    pub const TIMEOUT_SECS: u64 = 120;
+    /// Characters of recognized text returned to the agent
+    pub const MAX_TEXT_CHARS: usize = 100_000;
+}
+
+// ===== Tool Plugins =====
+/// Layout, sandbox limits and capabilities of the WASM tool plugins.
+pub mod plugins {
+    /// Settings record of the capability grants (per profile)
+    pub const SETTINGS_KEY: &str = "settings:plugins";
+    /// Prefix of plugin tool names (`plugin__<id>`)
+    pub const TOOL_PREFIX: &str = "plugin__";
+    /// Directory of the installed plugins, in the data directory
+    pub const DIR_NAME: &str = "plugins";
+    /// Manifest file of a plugin
+    pub const MANIFEST_FILE: &str = "plugin.toml";
+    /// WebAssembly component of a plugin
+    pub const COMPONENT_FILE: &str = "plugin.wasm";
+    /// Maximum length of a plugin ID
+    pub const MAX_ID_LEN: usize = 64;
+    /// Maximum size of a plugin component (bytes)
+    pub const MAX_COMPONENT_BYTES: u64 = 50 * 1024 * 1024;
+    /// Maximum hosts a plugin may request network access to
+    pub const MAX_NETWORK_HOSTS: usize = 16;
+    /// Maximum directories a plugin may request read access to
+    pub const MAX_READ_DIRS: usize = 16;
+    /// Instructions a plugin may run per call (Wasmtime fuel)
+    pub const FUEL_PER_CALL: u64 = 5_000_000_000;
+    /// Maximum linear memory of a plugin instance (bytes)
+    pub const MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+    /// Timeout of an HTTP request made by a plugin (seconds)
+    pub const HTTP_TIMEOUT_SECS: u64 = 30;
+    /// Maximum size of an HTTP response or file read by a plugin (bytes)
+    pub const MAX_HOST_READ_BYTES: usize = 5 * 1024 * 1024;
+}
+
+// ===== Custom Script Tools =====
+/// Limits of the user-defined Rhai tools (`custom_tool` table).
+pub mod script {
+    /// Prefix of custom tool names (`custom__<name>`)
+    pub const TOOL_PREFIX: &str = "custom__";
+    /// Maximum number of custom tools
+    pub const MAX_CUSTOM_TOOLS: usize = 100;
+    /// Maximum length of a custom tool name
+    pub const MAX_NAME_LEN: usize = 64;
+    /// Maximum length of a custom tool description
+    pub const MAX_DESCRIPTION_LEN: usize = 1000;
+    /// Maximum size of a script (bytes)
+    pub const MAX_SCRIPT_BYTES: usize = 100_000;
+    /// Maximum size of an input schema, serialized (bytes)
+    pub const MAX_SCHEMA_BYTES: usize = 20_000;
+    /// Operations a script may run per call
+    pub const MAX_OPERATIONS: u64 = 50_000_000;
+    /// Maximum nesting of function calls
+    pub const MAX_CALL_LEVELS: usize = 32;
+    /// Maximum nesting of expressions
+    pub const MAX_EXPR_DEPTH: usize = 64;
+    /// Maximum length of a string built by a script (bytes)
+    pub const MAX_STRING_BYTES: usize = 1024 * 1024;
+    /// Maximum length of an array built by a script
+    pub const MAX_ARRAY_LEN: usize = 100_000;
+    /// Maximum entries of a map built by a script
+    pub const MAX_MAP_LEN: usize = 10_000;
+    /// Wall-clock limit of a call (seconds)
+    pub const TIMEOUT_SECS: u64 = 10;
+    /// Maximum size of a result, serialized (bytes)
+    pub const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+}
+
+// ===== Macro Tools =====
+/// Limits of the user-defined tool sequences (`macro_tool` table).
+pub mod macros {
+    /// Prefix of macro tool names (`macro__<name>`)
+    pub const TOOL_PREFIX: &str = "macro__";
+    /// Maximum number of macro tools
+    pub const MAX_MACRO_TOOLS: usize = 100;
+    /// Maximum length of a macro or step name
+    pub const MAX_NAME_LEN: usize = 64;
+    /// Maximum length of a macro description
+    pub const MAX_DESCRIPTION_LEN: usize = 1000;
+    /// Maximum number of steps of a macro
+    pub const MAX_STEPS: usize = 10;
+    /// Maximum size of the steps and output template, serialized (bytes)
+    pub const MAX_TEMPLATE_BYTES: usize = 50_000;
+}
+
+// ===== External Database Tool =====
+/// Limits of the SqlTool on user-configured external databases.
+pub mod external_db {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &["list_databases", "query", "execute"];
+    /// Rows returned by a query when the database sets no limit
+    pub const DEFAULT_MAX_ROWS: usize = 100;
+    /// Maximum rows a database may allow per query
+    pub const MAX_ROWS: usize = 1_000;
+    /// Maximum length of a SQL statement
+    pub const MAX_STATEMENT_LENGTH: usize = 20_000;
+    /// Maximum number of bound parameters
+    pub const MAX_PARAMS: usize = 64;
+    /// Characters kept per text cell of a result
+    pub const MAX_CELL_CHARS: usize = 2_000;
+    /// Connection timeout (seconds)
+    pub const CONNECT_TIMEOUT_SECS: u64 = 10;
+    /// Statement timeout (seconds)
+    pub const STATEMENT_TIMEOUT_SECS: u64 = 60;
+    /// Maximum length of a database name
+    pub const MAX_NAME_LENGTH: usize = 64;
+    /// Maximum length of a database description
+    pub const MAX_DESCRIPTION_LENGTH: usize = 500;
+}
+
+// ===== Email Tool =====
+/// Limits and defaults of the EmailTool.
+pub mod email {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] =
+        &["list_accounts", "read_inbox", "read_message", "send_draft"];
+    /// Default IMAP port (implicit TLS)
+    pub const DEFAULT_IMAP_PORT: u16 = 993;
+    /// Default SMTP port (implicit TLS)
+    pub const DEFAULT_SMTP_PORT: u16 = 465;
+    /// SMTP port using STARTTLS
+    pub const STARTTLS_SMTP_PORT: u16 = 587;
+    /// Mailbox read by read_inbox when none is given
+    pub const DEFAULT_MAILBOX: &str = "INBOX";
+    /// Messages listed by read_inbox by default
+    pub const DEFAULT_LIST_LIMIT: usize = 10;
+    /// Maximum messages listed by read_inbox
+    pub const MAX_LIST_LIMIT: usize = 50;
+    /// Characters of a message body returned by read_message
+    pub const MAX_BODY_CHARS: usize = 20_000;
+    /// Characters of the preview of a listed message
+    pub const PREVIEW_CHARS: usize = 200;
+    /// Maximum recipients of a draft (to and cc)
+    pub const MAX_RECIPIENTS: usize = 20;
+    /// Maximum length of a draft subject
+    pub const MAX_SUBJECT_LENGTH: usize = 500;
+    /// Maximum length of a draft body
+    pub const MAX_BODY_LENGTH: usize = 100_000;
+    /// Maximum length of an account name
+    pub const MAX_NAME_LENGTH: usize = 64;
+    /// Connection and command timeout (seconds)
+    pub const TIMEOUT_SECS: u64 = 30;
+}
+
+// ===== RSS Tool =====
+/// Limits and defaults of the RssTool and of the feed digests.
+pub mod rss {
+    /// Valid operations
+    pub const VALID_OPERATIONS: &[&str] = &["list_feeds", "fetch_new", "recent_items"];
+    /// Maximum number of feeds
+    pub const MAX_FEEDS: usize = 100;
+    /// Maximum length of a feed URL
+    pub const MAX_URL_LENGTH: usize = 2048;
+    /// Maximum size of a downloaded feed (bytes)
+    pub const MAX_FEED_BYTES: usize = 5 * 1024 * 1024;
+    /// Request timeout of a feed (seconds)
+    pub const TIMEOUT_SECS: u64 = 20;
+    /// Feeds downloaded at the same time by fetch_new
+    pub const MAX_CONCURRENT_FETCHES: usize = 4;
+    /// Maximum new items returned per feed by fetch_new
+    pub const MAX_NEW_ITEMS_PER_FEED: usize = 30;
+    /// Items listed by recent_items by default
+    pub const DEFAULT_RECENT_LIMIT: usize = 20;
+    /// Maximum items listed by recent_items
+    pub const MAX_RECENT_LIMIT: usize = 100;
+    /// Characters of an item summary
+    pub const SUMMARY_CHARS: usize = 500;
+    /// Characters of an item title
+    pub const TITLE_CHARS: usize = 300;
+    /// Items kept per feed for deduplication (oldest are pruned)
+    pub const MAX_STORED_ITEMS_PER_FEED: usize = 500;
+    /// Cron expression of a digest created without one (every day at 8:00)
+    pub const DEFAULT_DIGEST_CRON: &str = "0 8 * * *";
+    /// Prompt of the scheduled digest runs
+    pub const DIGEST_PROMPT: &str = "Use the RssTool fetch_new operation to get the new items \
+of the news feeds, then write a digest of them: group related items by topic, summarize each \
+topic in a few sentences and keep the links of the items. If there are no new items, say so \
+in one sentence. Mention the feeds that could not be fetched.";
+}
+
+// ===== Chart Tool =====
+/// Limits and defaults of the ChartTool.
+pub mod chart {
+    /// Valid chart types
+    pub const VALID_TYPES: &[&str] = &["bar", "line", "pie"];
+    /// Valid output formats
+    pub const VALID_FORMATS: &[&str] = &["png", "svg"];
+    /// Default image size (pixels)
+    pub const DEFAULT_WIDTH: u32 = 800;
+    pub const DEFAULT_HEIGHT: u32 = 500;
+    /// Allowed image size range (pixels)
+    pub const MIN_DIMENSION: u32 = 200;
+    pub const MAX_DIMENSION: u32 = 2_000;
+    /// Maximum series per chart
+    pub const MAX_SERIES: usize = 10;
+    /// Maximum values per series
+    pub const MAX_POINTS: usize = 500;
+    /// Maximum length of the title and axis descriptions
+    pub const MAX_TITLE_LENGTH: usize = 200;
+    /// Maximum length of a category label or series name
+    pub const MAX_LABEL_LENGTH: usize = 100;
+    /// Category labels shown on the X axis at most (others are skipped)
+    pub const MAX_AXIS_LABELS: usize = 20;
+    /// Directory (in the data directory) of rendered charts, until they
+    /// are stored as attachments of the response
+    pub const OUTPUT_DIR_NAME: &str = "charts";
+    /// Fonts tried in order for chart text (first existing file is used)
+    pub const FONT_PATHS: &[&str] = &[
+        "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+        "/usr/share/fonts/TTF/DejaVuSans.ttf",
+        "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+        "/usr/share/fonts/truetype/liberation/LiberationSans-Regular.ttf",
+        "/usr/share/fonts/liberation-sans/LiberationSans-Regular.ttf",
+        "/usr/share/fonts/noto/NotoSans-Regular.ttf",
+        "/usr/share/fonts/truetype/noto/NotoSans-Regular.ttf",
+        "/System/Library/Fonts/Supplemental/Arial.ttf",
+        "/Library/Fonts/Arial.ttf",
+        "C:\\Windows\\Fonts\\arial.ttf",
+        "C:\\Windows\\Fonts\\segoeui.ttf",
+    ];
+}
+
+// ===== Desktop Access Tools =====
+/// Permissions and limits of the ClipboardTool and ScreenshotTool.
+pub mod desktop {
+    /// Storage key of the desktop access permissions
+    pub const SETTINGS_KEY: &str = "settings:desktop_access";
+    /// Valid ClipboardTool operations
+    pub const CLIPBOARD_OPERATIONS: &[&str] = &["read", "write"];
+    /// Characters of clipboard text returned to the agent
+    pub const MAX_CLIPBOARD_READ_CHARS: usize = 50_000;
+    /// Maximum length of a text written to the clipboard
+    pub const MAX_CLIPBOARD_WRITE_LENGTH: usize = 100_000;
+    /// Valid ScreenshotTool operations
+    pub const SCREENSHOT_OPERATIONS: &[&str] = &["list_windows", "capture"];
+    /// Valid capture targets
+    pub const CAPTURE_TARGETS: &[&str] = &["screen", "window"];
+    /// Width wider captures are scaled down to (pixels)
+    pub const MAX_CAPTURE_WIDTH: u32 = 1600;
+    /// Maximum windows returned by list_windows
+    pub const MAX_LISTED_WINDOWS: usize = 50;
+    /// Directory (in the system temp directory) of captures,
+    /// until they are stored as attachments of the response
+    pub const OUTPUT_DIR_NAME: &str = "zileo-screenshots";
+}
+
 // ===== Workflow Constants (OPT-WF-3, OPT-WF-9) =====
 /// Constants for workflow execution and streaming.
 #[allow(dead_code)]
@@ -169,6 +541,10 @@ pub mod workflow {
     /// Maximum number of messages to include in LLM context (OPT-WF-3).
     /// Prevents context overflow while maintaining conversation coherence.
     pub const MESSAGE_HISTORY_LIMIT: usize = 50;
+    /// Maximum number of pinned messages sent to the agent besides the history
+    pub const PINNED_CONTEXT_LIMIT: usize = 20;
+    /// Maximum characters of a pinned message in the agent context
+    pub const MAX_PINNED_MESSAGE_CHARS: usize = 4000;
 
     // OPT-WF-9: Tokio Timeout Constants
     /// Timeout (seconds) for LLM execution operations.
@@ -182,6 +558,463 @@ pub mod workflow {
     /// Timeout (seconds) for loading workflow full state (multiple parallel queries).
     /// Default: 60 seconds - accounts for multiple parallel queries.
     pub const FULL_STATE_LOAD_TIMEOUT_SECS: u64 = 60;
+
+    /// Time (milliseconds) a cancelled execution is given to stop its LLM
+    /// request and cancel its MCP tool calls on their servers before it is
+    /// dropped.
+    pub const CANCEL_GRACE_PERIOD_MS: u64 = 3_000;
+}
+
+// ===== Tool Execution Limits =====
+/// Timeouts and concurrency of the tool calls made by the agent loop.
+pub mod tool_execution {
+    /// Default timeout (seconds) for local tools that have no own wait logic.
+    pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+    /// Timeout (seconds) for MCP tool calls (progress notifications included).
+    pub const MCP_TIMEOUT_SECS: u64 = 300;
+
+    /// Maximum timeout (seconds) an agent may configure for a tool.
+    pub const MAX_TIMEOUT_SECS: u64 = 3600;
+
+    /// Maximum calls per workflow an agent may configure for a tool.
+    pub const MAX_CALLS_PER_WORKFLOW: u32 = 10_000;
+
+    /// Maximum calls per minute an agent may configure for a tool.
+    pub const MAX_CALLS_PER_MINUTE: u32 = 600;
+
+    /// Workflows whose per-workflow tool call counts are kept in memory
+    /// per agent (least recently used are evicted).
+    pub const MAX_QUOTA_TRACKED_WORKFLOWS: usize = 256;
+
+    /// Latest executions of a tool searched for the previous result of a
+    /// repeated call (result diffs).
+    pub const MAX_DIFF_LOOKBACK: usize = 20;
+
+    /// Maximum tool calls of one LLM response executed concurrently.
+    pub const MAX_PARALLEL_CALLS: usize = 4;
+
+    /// Tools that make a whole batch of calls run sequentially
+    /// (user interaction or sub-agent limits).
+    pub const SEQUENTIAL_TOOLS: &[&str] = &[
+        "UserQuestionTool",
+        "SpawnAgentTool",
+        "DelegateTaskTool",
+        "ParallelTasksTool",
+        "WorkflowTool",
+    ];
+}
+
+// ===== Workflow Tool Constants =====
+/// Limits for saved workflows invoked as steps by the WorkflowTool.
+pub mod workflow_tool {
+    /// Workflow invocations per task of the calling agent
+    pub const MAX_RUNS_PER_TASK: usize = 5;
+    /// Workflows returned by `list_workflows`
+    pub const MAX_LISTED_WORKFLOWS: usize = 50;
+    /// Characters of the template shown by `list_workflows`
+    pub const TEMPLATE_PREVIEW_CHARS: usize = 200;
+    /// Results longer than this are summarized before being returned
+    pub const SUMMARY_THRESHOLD_CHARS: usize = 4_000;
+    /// Maximum output tokens of the summary
+    pub const SUMMARY_MAX_TOKENS: usize = 800;
+    /// Maximum characters of a returned result (summary fallback or full result)
+    pub const MAX_RESULT_CHARS: usize = 20_000;
+}
+
+// ===== Workflow Snapshot Constants =====
+/// Limits for the read-only HTML snapshot of a workflow.
+pub mod snapshot {
+    /// Maximum characters of a tool input/output block in the snapshot
+    pub const MAX_TOOL_DETAIL_CHARS: usize = 20_000;
+    /// Lifetime (seconds) of the temporary localhost snapshot server
+    pub const SERVER_TTL_SECS: u64 = 30 * 60;
+    /// Maximum size of an HTTP request head accepted by the snapshot server
+    pub const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+}
+
+// ===== Agent Tool Usage Constants =====
+/// Limits for per-agent tool usage statistics and the system prompt hint.
+pub mod tool_usage {
+    /// Keywords extracted from one task description
+    pub const KEYWORDS_PER_TASK: usize = 3;
+    /// Keywords kept per tool
+    pub const MAX_TASK_KEYWORDS: usize = 8;
+    /// Minimum characters of a task keyword
+    pub const MIN_KEYWORD_CHARS: usize = 4;
+    /// Maximum tools listed in the system prompt hint
+    pub const MAX_HINT_TOOLS: usize = 10;
+}
+
+// ===== Behavior Changelog Constants =====
+/// Limits of the behavior changelog of agents.
+pub mod behavior_changelog {
+    /// Maximum characters of a before/after value of a recorded field
+    pub const MAX_VALUE_CHARS: usize = 4_000;
+    /// Default number of changes returned by the list command
+    pub const DEFAULT_LIST_LIMIT: usize = 100;
+    /// Maximum number of changes returned by the list command
+    pub const MAX_LIST_LIMIT: usize = 1_000;
+}
+
+// ===== Priority Inbox Constants =====
+/// Limits and deadlines of the pending items of the priority inbox.
+pub mod inbox {
+    /// Maximum characters of the detail of a pending item
+    pub const MAX_DETAIL_CHARS: usize = 300;
+    /// Items due within this delay (hours) are at least high priority
+    pub const DUE_HIGH_HOURS: i64 = 24;
+    /// Items due within this delay (hours) are at least normal priority
+    pub const DUE_NORMAL_HOURS: i64 = 72;
+    /// Maximum number of pending items returned
+    pub const MAX_ITEMS: usize = 500;
+}
+
+// ===== Full-Text Search Constants =====
+/// Limits of the message and workflow search.
+pub mod search {
+    /// Default number of hits per page
+    pub const DEFAULT_PAGE_SIZE: usize = 20;
+    /// Maximum number of hits per page
+    pub const MAX_PAGE_SIZE: usize = 100;
+    /// Maximum characters of the search text
+    pub const MAX_QUERY_CHARS: usize = 200;
+    /// Characters of context kept before the first match of a snippet
+    pub const SNIPPET_CONTEXT_CHARS: usize = 80;
+}
+
+// ===== Transcript Export Constants =====
+/// Rendering of workflow transcripts.
+pub mod transcript {
+    /// Headless browsers tried, in order, to print a transcript to PDF
+    pub const PDF_RENDERERS: &[&str] = &[
+        "chromium",
+        "chromium-browser",
+        "google-chrome",
+        "google-chrome-stable",
+        "microsoft-edge",
+    ];
+    /// Maximum duration of a PDF rendering (seconds)
+    pub const PDF_RENDER_TIMEOUT_SECS: u64 = 60;
+    /// Maximum characters of a tool payload (input or output) in a transcript
+    pub const MAX_TOOL_PAYLOAD_CHARS: usize = 4_000;
+}
+
+// ===== Validation Notification Constants =====
+/// Limits for forwarding validation requests to external channels.
+pub mod validation_notification {
+    /// Timeout (seconds) for delivering a notification to one channel
+    pub const SEND_TIMEOUT_SECS: u64 = 10;
+    /// Maximum characters of the operation description in a notification
+    pub const MAX_OPERATION_CHARS: usize = 500;
+    /// Maximum size of an HTTP request head accepted by the callback listener
+    pub const MAX_REQUEST_HEAD_BYTES: usize = 8 * 1024;
+    /// Timeout (seconds) for reading a request on the callback listener
+    pub const REQUEST_READ_TIMEOUT_SECS: u64 = 5;
+}
+
+// ===== Validation Preview Constants =====
+/// Limits of the change previews attached to validation requests.
+pub mod validation_preview {
+    /// Maximum records listed in a deletion preview
+    pub const MAX_PREVIEW_ROWS: usize = 20;
+    /// Maximum characters of a record summary
+    pub const MAX_SUMMARY_CHARS: usize = 200;
+    /// Maximum size of a file read to compute a diff
+    pub const MAX_FILE_BYTES: u64 = 1024 * 1024;
+    /// Maximum cells of the line comparison table (old lines x new lines);
+    /// larger changes are shown as a full replacement
+    pub const MAX_DIFF_CELLS: usize = 4_000_000;
+    /// Unchanged lines shown around each change
+    pub const DIFF_CONTEXT_LINES: usize = 3;
+    /// Maximum lines of a diff preview
+    pub const MAX_DIFF_LINES: usize = 400;
+    /// Maximum characters of a statement preview
+    pub const MAX_STATEMENT_CHARS: usize = 4_000;
+}
+
+// ===== Dry Run Constants =====
+/// Arguments read by the previews of the tool calls simulated in a dry run workflow.
+pub mod dry_run {
+    /// Argument names holding the URL of an HTTP request
+    pub const URL_ARGS: &[&str] = &["url", "uri", "endpoint"];
+    /// Argument names holding the body of an HTTP request
+    pub const BODY_ARGS: &[&str] = &["body", "data", "json", "payload"];
+    /// Argument names holding a shell command
+    pub const COMMAND_ARGS: &[&str] = &["command", "cmd", "script"];
+}
+
+// ===== Audit Log Constants =====
+/// Size limits of the audit log.
+pub mod audit {
+    /// Maximum entries kept; older entries are deleted by rotation
+    pub const MAX_ENTRIES: u64 = 100_000;
+    /// Number of recorded entries between two rotations
+    pub const ROTATION_INTERVAL: u64 = 500;
+    /// Entries returned by a query without limit
+    pub const DEFAULT_QUERY_LIMIT: usize = 500;
+    /// Maximum entries returned by a query or written by an export
+    pub const MAX_QUERY_LIMIT: usize = MAX_ENTRIES as usize;
+}
+
+// ===== API Key Constants =====
+/// Age and expiry thresholds of the stored provider API keys.
+pub mod api_keys {
+    /// Age after which a key is reported as stale (rotation recommended)
+    pub const STALE_AFTER_DAYS: i64 = 90;
+    /// Days before expiry from which a key is reported as expiring soon
+    pub const EXPIRY_WARNING_DAYS: i64 = 14;
+    /// Minimum interval between two writes of the last use date of a key
+    pub const LAST_USED_RESOLUTION_SECS: i64 = 3_600;
+}
+
+// ===== Profile Constants =====
+/// Storage layout of the profiles (isolated databases).
+pub mod profiles {
+    /// ID of the profile using the data directory itself
+    pub const DEFAULT_PROFILE_ID: &str = "default";
+    /// Registry of the profiles, in the data directory
+    pub const REGISTRY_FILE: &str = "profiles.json";
+    /// Directory of the non-default profiles, in the data directory
+    pub const DIR_NAME: &str = "profiles";
+    /// Database directory name, in the directory of a profile
+    pub const DB_DIR_NAME: &str = "db";
+    /// Maximum length of a profile name
+    pub const MAX_NAME_LEN: usize = 64;
+}
+
+// ===== Config File Constants =====
+/// Optional startup config file (`~/.zileo/config.toml`).
+pub mod config_file {
+    /// Config file name, in the data directory
+    pub const FILE_NAME: &str = "config.toml";
+    /// Log filter when neither `RUST_LOG` nor the config file sets one
+    pub const DEFAULT_LOG_FILTER: &str = "zileo_chat=info,warn";
+    /// Environment variables of the HTTP proxy (checked before the config file)
+    pub const PROXY_ENV_VARS: [&str; 5] = [
+        "HTTPS_PROXY",
+        "https_proxy",
+        "HTTP_PROXY",
+        "http_proxy",
+        "ALL_PROXY",
+    ];
+}
+
+// ===== Proxy Constants =====
+/// Proxy of the outgoing requests (LLM providers, embedding, MCP HTTP transports).
+pub mod proxy {
+    /// Storage key of the saved proxy settings
+    pub const SETTINGS_KEY: &str = "settings:proxy";
+    /// Proxy target of the embedding service
+    pub const TARGET_EMBEDDING: &str = "embedding";
+    /// Proxy target of the MCP HTTP transports
+    pub const TARGET_MCP: &str = "mcp";
+    /// Proxy URL schemes supported by the HTTP clients
+    pub const SUPPORTED_SCHEMES: [&str; 4] = ["http", "https", "socks5", "socks5h"];
+    /// Hosts never proxied (e.g. a local Ollama server), besides loopback IPs
+    pub const LOOPBACK_HOSTS: [&str; 2] = ["localhost", "localhost.localdomain"];
+}
+
+// ===== Metrics Constants =====
+/// Metrics counted by the app and their exporters (Prometheus, OTLP).
+pub mod metrics {
+    /// Storage key of the metrics export settings
+    pub const SETTINGS_KEY: &str = "settings:metrics";
+    /// Default port of the Prometheus endpoint
+    pub const DEFAULT_PROMETHEUS_PORT: u16 = 9464;
+    /// Default OTLP/HTTP metrics endpoint (local collector)
+    pub const DEFAULT_OTLP_ENDPOINT: &str = "http://127.0.0.1:4318/v1/metrics";
+    /// Default interval between two OTLP pushes (seconds)
+    pub const DEFAULT_OTLP_INTERVAL_SECS: u64 = 60;
+    /// Minimum interval between two OTLP pushes (seconds)
+    pub const MIN_OTLP_INTERVAL_SECS: u64 = 5;
+    /// Maximum interval between two OTLP pushes (seconds)
+    pub const MAX_OTLP_INTERVAL_SECS: u64 = 3600;
+    /// Timeout of an OTLP push (seconds)
+    pub const OTLP_TIMEOUT_SECS: u64 = 10;
+    /// Upper bounds (seconds) of the LLM request latency histogram buckets
+    pub const LLM_LATENCY_BUCKETS_SECS: [f64; 10] =
+        [0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0];
+    /// Maximum size of a request head on the Prometheus endpoint (bytes)
+    pub const MAX_REQUEST_HEAD_BYTES: usize = 8192;
+    /// Timeout for reading a request on the Prometheus endpoint (seconds)
+    pub const REQUEST_READ_TIMEOUT_SECS: u64 = 10;
+}
+
+// ===== Diagnostics Constants =====
+/// Diagnostics bundle attached to bug reports.
+pub mod diagnostics {
+    /// Recent log lines kept in memory for the bundle
+    pub const LOG_BUFFER_LINES: usize = 2000;
+    /// Maximum characters of a kept log line (longer lines are truncated)
+    pub const MAX_LOG_LINE_CHARS: usize = 2000;
+}
+
+// ===== Workflow Title Constants =====
+/// Titles generated from the first exchange of a workflow.
+pub mod workflow_title {
+    /// Maximum characters of a generated title
+    pub const MAX_TITLE_CHARS: usize = 60;
+    /// Characters of the user message and of the response sent to the model
+    pub const EXCERPT_CHARS: usize = 1500;
+    /// Maximum tokens of the title completion
+    pub const MAX_TOKENS: usize = 32;
+    /// Temperature of the title completion
+    pub const TEMPERATURE: f32 = 0.3;
+}
+
+// ===== Persistence Outbox Constants =====
+/// Retry policy of persistence writes made during workflow execution.
+pub mod outbox {
+    /// Outbox file name, next to the database directory
+    pub const FILE_NAME: &str = "outbox.jsonl";
+    /// Write attempts before a record is queued in the outbox
+    pub const WRITE_ATTEMPTS: u32 = 3;
+    /// Delay (milliseconds) before the first retry, doubled for each retry
+    pub const RETRY_BASE_DELAY_MS: u64 = 100;
+    /// Maximum records kept in the outbox (oldest dropped beyond)
+    pub const MAX_ENTRIES: usize = 10_000;
+}
+
+// ===== Message Attachment Constants =====
+/// Limits of the image attachments of user messages.
+pub mod attachments {
+    /// Directory of the attachment blobs, next to the database directory
+    pub const DIR_NAME: &str = "attachments";
+    /// Maximum attachments per message
+    pub const MAX_PER_MESSAGE: usize = 4;
+    /// Maximum size of an attachment (10MB)
+    pub const MAX_SIZE_BYTES: usize = 10 * 1024 * 1024;
+    /// Maximum length of an attachment file name
+    pub const MAX_FILE_NAME_LEN: usize = 255;
+    /// Tools whose results carry an image `path` attached to the assistant response
+    pub const IMAGE_TOOLS: &[&str] = &["ChartTool", "ScreenshotTool"];
+}
+
+// ===== Speech Transcription Constants =====
+/// Limits and defaults of voice message transcription.
+pub mod speech {
+    /// Default endpoint of the local transcription server (whisper.cpp server)
+    pub const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:8080";
+    /// Maximum size of a recorded voice message (25MB)
+    pub const MAX_AUDIO_SIZE_BYTES: usize = 25 * 1024 * 1024;
+    /// Timeout of a transcription request in seconds
+    pub const TIMEOUT_SECS: u64 = 120;
+    /// Default endpoint of the local text-to-speech server (piper HTTP server)
+    pub const DEFAULT_TTS_ENDPOINT: &str = "http://127.0.0.1:5000";
+    /// Endpoint of the OpenAI text-to-speech API
+    pub const OPENAI_TTS_ENDPOINT: &str = "https://api.openai.com";
+    /// Maximum length of a text to synthesize in characters
+    pub const MAX_TTS_TEXT_LEN: usize = 20_000;
+    /// Target length of a synthesized segment (replies are played segment by segment)
+    pub const TTS_SEGMENT_CHARS: usize = 400;
+    /// Audio bytes per streamed event
+    pub const TTS_CHUNK_BYTES: usize = 32 * 1024;
+    /// Timeout of the synthesis of a segment in seconds
+    pub const TTS_TIMEOUT_SECS: u64 = 60;
+    /// Accepted speaking rates (1.0 is the natural rate of the voice)
+    pub const TTS_SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.25..=4.0;
+}
+
+// ===== Settings Sync Constants =====
+/// Limits of the peer sync of settings between two machines.
+pub mod sync {
+    /// Prefix of pairing codes (shown as QR codes)
+    pub const PAIRING_CODE_PREFIX: &str = "zileo-sync:1:";
+    /// Maximum size of a sealed sync payload (5MB)
+    pub const MAX_PAYLOAD_BYTES: usize = 5 * 1024 * 1024;
+    /// Maximum mailboxes kept by the local network mailbox server
+    pub const MAX_MAILBOXES: usize = 32;
+    /// Maximum size of the request head accepted by the mailbox server
+    pub const MAX_HEAD_BYTES: usize = 16 * 1024;
+    /// Timeout of a mailbox request in seconds
+    pub const TIMEOUT_SECS: u64 = 30;
+    /// Maximum length of a device name
+    pub const MAX_DEVICE_NAME_LEN: usize = 64;
+}
+
+// ===== Knowledge Pack Constants =====
+/// Limits of the knowledge packs attached to agents.
+pub mod knowledge {
+    /// Maximum length of a pack name
+    pub const MAX_PACK_NAME_LEN: usize = 64;
+    /// Maximum length of a pack description
+    pub const MAX_PACK_DESCRIPTION_LEN: usize = 1000;
+    /// Maximum length of a document title
+    pub const MAX_DOCUMENT_TITLE_LEN: usize = 128;
+    /// Maximum length of a document in characters
+    pub const MAX_DOCUMENT_LEN: usize = 200_000;
+    /// Target length of a document chunk in characters
+    pub const CHUNK_CHARS: usize = 1200;
+    /// Maximum number of chunks of a pack
+    pub const MAX_PACK_CHUNKS: usize = 1000;
+    /// Maximum number of memories curated into a pack
+    pub const MAX_PACK_MEMORIES: usize = 200;
+    /// Number of chunks retrieved per execution
+    pub const TOP_K: usize = 5;
+    /// Minimum cosine similarity of a retrieved chunk
+    pub const SIMILARITY_THRESHOLD: f64 = 0.5;
+    /// Maximum length of the retrieved context added to the system prompt
+    pub const MAX_CONTEXT_CHARS: usize = 8000;
+}
+
+// ===== Scheduler Constants =====
+/// Limits of the scheduled workflow runs.
+pub mod scheduler {
+    /// Interval between two checks for due schedules in seconds
+    pub const TICK_SECS: u64 = 30;
+    /// Maximum number of schedules
+    pub const MAX_SCHEDULES: usize = 100;
+    /// Maximum length of a schedule name
+    pub const MAX_NAME_LEN: usize = 64;
+    /// Maximum length of a cron expression
+    pub const MAX_CRON_LEN: usize = 128;
+}
+
+// ===== Job Queue Constants =====
+/// Limits of the background job queue.
+pub mod jobs {
+    /// Interval between two checks for queued jobs in seconds (enqueuing
+    /// wakes the queue immediately)
+    pub const POLL_SECS: u64 = 5;
+    /// Maximum number of jobs executing at the same time
+    pub const MAX_CONCURRENT_JOBS: usize = 2;
+    /// Maximum number of queued jobs
+    pub const MAX_QUEUED_JOBS: usize = 100;
+    /// Maximum number of executions of a job interrupted by restarts
+    pub const MAX_ATTEMPTS: u32 = 3;
+}
+
+// ===== Team Usage Statistics Constants =====
+/// Aggregation and noise parameters of the team usage report.
+///
+/// The contribution of a conversation to a bucket is clipped to the bounds
+/// below, which are the sensitivities of the Laplace noise.
+pub mod usage_stats {
+    /// Report format version
+    pub const FORMAT_VERSION: u32 = 1;
+    /// Default number of days covered
+    pub const DEFAULT_DAYS: u32 = 30;
+    /// Maximum number of days covered
+    pub const MAX_DAYS: u32 = 365;
+    /// Default privacy budget of a bucket
+    pub const DEFAULT_EPSILON: f64 = 1.0;
+    /// Minimum privacy budget
+    pub const MIN_EPSILON: f64 = 0.1;
+    /// Maximum privacy budget
+    pub const MAX_EPSILON: f64 = 10.0;
+    /// Maximum responses counted per conversation and bucket
+    pub const MESSAGE_CLIP: f64 = 50.0;
+    /// Maximum tokens counted per conversation and bucket
+    pub const TOKEN_CLIP: f64 = 200_000.0;
+    /// Maximum spend counted per conversation and bucket (USD)
+    pub const SPEND_CLIP_USD: f64 = 5.0;
+    /// Maximum tool calls counted per conversation and bucket
+    pub const TOOL_CALL_CLIP: f64 = 50.0;
+    /// Buckets with fewer (noised) conversations are left out
+    pub const MIN_BUCKET_CONVERSATIONS: f64 = 3.0;
+    /// Token counts are rounded to this granularity
+    pub const TOKEN_ROUNDING: f64 = 1000.0;
 }
 
 // ===== Query Limits (OPT-DB-8) =====
@@ -198,6 +1031,14 @@ pub mod query_limits {
     pub const DEFAULT_MESSAGES_LIMIT: usize = 500;
     /// Default limit for model list
     pub const DEFAULT_MODELS_LIMIT: usize = 100;
+    /// Default page size for the workflow list
+    pub const DEFAULT_WORKFLOWS_PAGE_SIZE: usize = 50;
+    /// Maximum page size for the workflow list
+    pub const MAX_WORKFLOWS_PAGE_SIZE: usize = 200;
+    /// Default page size for tool executions and thinking steps
+    pub const DEFAULT_TRACE_PAGE_SIZE: usize = 50;
+    /// Maximum page size for tool executions and thinking steps
+    pub const MAX_TRACE_PAGE_SIZE: usize = 500;
 }
 
 // ===== Command Validation Constants (OPT-2) =====
@@ -218,12 +1059,32 @@ pub mod commands {
     pub const MIN_MAX_TOKENS: usize = 256;
     /// Maximum max_tokens value
     pub const MAX_MAX_TOKENS: usize = 128000;
+    /// Maximum stop sequences of a per-request generation override
+    pub const MAX_STOP_SEQUENCES: usize = 4;
+    /// Maximum length of a stop sequence (characters)
+    pub const MAX_STOP_SEQUENCE_LEN: usize = 64;
     /// Valid lifecycle values
     pub const VALID_LIFECYCLES: &[&str] = &["permanent", "temporary"];
+    /// Maximum serialized size of an agent's tool_settings (16KB)
+    pub const MAX_TOOL_SETTINGS_SIZE: usize = 16 * 1024;
+    /// Maximum serialized size of an agent's MCP tool policy (16KB)
+    pub const MAX_MCP_TOOL_POLICY_SIZE: usize = 16 * 1024;
+    /// Maximum number of regression cases per agent
+    pub const MAX_REGRESSION_CASES: usize = 50;
+    /// Maximum serialized size of an agent's regression suite (256KB)
+    pub const MAX_REGRESSIONS_SIZE: usize = 256 * 1024;
+    /// Maximum number of blocked terms in an agent's moderation policy
+    pub const MAX_MODERATION_BLOCKED_TERMS: usize = 100;
+    /// Maximum length of a moderation blocked term
+    pub const MAX_MODERATION_TERM_LEN: usize = 128;
 
     // ----- MCP Server -----
     /// Maximum length for MCP server names/IDs
     pub const MAX_MCP_SERVER_NAME_LEN: usize = 64;
+    /// Maximum size of an imported `mcpServers` JSON document (256KB)
+    pub const MAX_MCP_IMPORT_JSON_SIZE: usize = 256 * 1024;
+    /// Maximum number of servers in an imported `mcpServers` JSON document
+    pub const MAX_MCP_IMPORT_SERVERS: usize = 100;
     /// Maximum length for MCP server descriptions
     pub const MAX_MCP_DESCRIPTION_LEN: usize = 1024;
     /// Maximum number of command arguments
@@ -236,10 +1097,32 @@ pub mod commands {
     pub const MAX_MCP_ENV_NAME_LEN: usize = 128;
     /// Maximum length for environment variable values
     pub const MAX_MCP_ENV_VALUE_LEN: usize = 4096;
+    /// Maximum length for OAuth state and authorization code
+    pub const MAX_OAUTH_PARAM_LEN: usize = 2048;
+    /// Maximum length for MCP resource URIs
+    pub const MAX_MCP_RESOURCE_URI_LEN: usize = 2048;
+    /// Maximum length for MCP prompt and prompt argument names
+    pub const MAX_MCP_PROMPT_NAME_LEN: usize = 128;
+    /// Maximum number of MCP prompt arguments
+    pub const MAX_MCP_PROMPT_ARGS_COUNT: usize = 50;
+    /// Maximum length for MCP prompt argument values
+    pub const MAX_MCP_PROMPT_ARG_VALUE_LEN: usize = 10_000;
+
+    // ----- Workflow -----
+    /// Maximum number of tags per workflow
+    pub const MAX_WORKFLOW_TAGS: usize = 10;
+    /// Maximum length for each workflow tag
+    pub const MAX_WORKFLOW_TAG_LEN: usize = 32;
+    /// Maximum number of workflows in a bulk tag operation
+    pub const MAX_BULK_TAG_WORKFLOWS: usize = 500;
 
     // ----- Message -----
     /// Maximum length for message content
     pub const MAX_MESSAGE_CONTENT_LEN: usize = 100_000;
+    /// Maximum number of reactions on a message
+    pub const MAX_MESSAGE_REACTIONS: usize = 20;
+    /// Maximum length of a reaction (an emoji may span several code points)
+    pub const MAX_REACTION_LEN: usize = 32;
 
     // ----- Tool Execution -----
     /// Maximum length for tool names
@@ -250,10 +1133,24 @@ pub mod commands {
     // ----- Thinking -----
     /// Maximum length for thinking content (50KB)
     pub const MAX_THINKING_CONTENT_LEN: usize = 50 * 1024;
+    /// Characters of thinking content kept in summary pages
+    pub const THINKING_PREVIEW_CHARS: usize = 200;
 
     // ----- Models -----
     /// Maximum length for model IDs
     pub const MAX_MODEL_ID_LEN: usize = 128;
     /// Valid model providers (lowercase)
     pub const VALID_MODEL_PROVIDERS: &[&str] = &["mistral", "ollama"];
+    /// Context window bounds of a model (tokens)
+    pub const MIN_MODEL_CONTEXT_WINDOW: usize = 1024;
+    pub const MAX_MODEL_CONTEXT_WINDOW: usize = 2_000_000;
+    /// Context window of a discovered model whose API does not report it
+    pub const DISCOVERED_MODEL_CONTEXT_WINDOW: usize = 32_768;
+    /// Output limit of a discovered model whose API does not report it
+    pub const DISCOVERED_MODEL_MAX_OUTPUT: usize = 4_096;
+    /// Maximum length of a model display name
+    pub const MAX_MODEL_NAME_LEN: usize = 64;
+    /// Memory a loaded local model needs on top of its weights (KV cache,
+    /// runtime buffers), as a percentage of its size
+    pub const LOCAL_MODEL_MEMORY_OVERHEAD_PERCENT: u64 = 20;
 }
@@ -47,6 +47,7 @@ use crate::mcp::MCPManager;
 use crate::state::AppState;
 use crate::tools::sub_agent_circuit_breaker::SubAgentCircuitBreaker;
 use crate::tools::ToolFactory;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tauri::AppHandle;
 use tokio::sync::Mutex;
@@ -97,6 +98,11 @@ pub struct AgentToolContext {
     ///
     /// The circuit breaker is shared across all sub-agent tools in the workflow.
     pub circuit_breaker: Option<Arc<Mutex<SubAgentCircuitBreaker>>>,
+    /// Cancellation tokens of the running workflows (shared with AppState)
+    ///
+    /// Agents are registered once and shared across workflows, so the token of
+    /// the current run is looked up by workflow ID when tools are executed.
+    pub workflow_cancellations: Option<Arc<Mutex<HashMap<String, CancellationToken>>>>,
 }
 
 #[allow(dead_code)]
@@ -142,6 +148,7 @@ impl AgentToolContext {
             app_handle,
             cancellation_token,
             circuit_breaker: None, // OPT-SA-8: Default to None for backward compatibility
+            workflow_cancellations: None,
         }
     }
 
@@ -174,6 +181,7 @@ impl AgentToolContext {
             app_handle,
             cancellation_token: None, // Use from_app_state_with_cancellation for token support
             circuit_breaker: None, // Use from_app_state_with_resilience for full resilience support
+            workflow_cancellations: Some(app_state.streaming_cancellations.clone()),
         }
     }
 
@@ -214,6 +222,7 @@ impl AgentToolContext {
             app_handle,
             cancellation_token,
             circuit_breaker: None, // Use from_app_state_with_resilience for circuit breaker
+            workflow_cancellations: Some(app_state.streaming_cancellations.clone()),
         }
     }
 
@@ -258,6 +267,7 @@ impl AgentToolContext {
             app_handle,
             cancellation_token,
             circuit_breaker,
+            workflow_cancellations: Some(app_state.streaming_cancellations.clone()),
         }
     }
 
@@ -304,6 +314,19 @@ impl AgentToolContext {
             Some(app_handle),
         )
     }
+
+    /// Returns the cancellation token of a running workflow.
+    ///
+    /// Falls back to the context's own `cancellation_token` when the workflow
+    /// is not registered (or the context was not built from AppState).
+    pub async fn workflow_cancellation(&self, workflow_id: &str) -> Option<CancellationToken> {
+        if let Some(ref cancellations) = self.workflow_cancellations {
+            if let Some(token) = cancellations.lock().await.get(workflow_id) {
+                return Some(token.clone());
+            }
+        }
+        self.cancellation_token.clone()
+    }
 }
 
 #[cfg(test)]
@@ -432,6 +455,19 @@ mod tests {
         assert!(context2.cancellation_token.is_none());
     }
 
+    #[tokio::test]
+    async fn test_context_workflow_cancellation_lookup() {
+        let state = create_test_state().await;
+        let context = AgentToolContext::from_app_state_full(&state);
+        assert!(context.workflow_cancellation("wf_running").await.is_none());
+
+        // Token created after the context (agents are registered at startup)
+        let token = state.create_cancellation_token("wf_running").await;
+        let found = context.workflow_cancellation("wf_running").await.unwrap();
+        token.cancel();
+        assert!(found.is_cancelled());
+    }
+
     // =========================================================================
     // OPT-SA-8: Circuit Breaker Tests
     // =========================================================================
@@ -52,7 +52,10 @@ use crate::models::sub_agent::{
 };
 use crate::models::Lifecycle;
 use crate::tools::context::AgentToolContext;
-use crate::tools::sub_agent_executor::SubAgentExecutor;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::sub_agent_executor::{SubAgentExecutor, SUB_AGENT_EXECUTION_COMPLETE};
 use crate::tools::utils::sub_agent_description_template;
 use crate::tools::validation_helper::ValidationHelper;
 use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
@@ -372,33 +375,36 @@ impl DelegateTaskTool {
             )
         };
 
-        let update_query = format!(
-            "UPDATE sub_agent_execution:`{}` SET \
-             status = '{}', \
-             duration_ms = {}, \
-             tokens_input = {}, \
-             tokens_output = {}, \
-             result_summary = {}, \
-             error_message = {}, \
-             completed_at = time::now()",
-            execution_id,
-            completion.status,
-            completion.duration_ms,
-            completion.tokens_input.unwrap_or(0),
-            completion.tokens_output.unwrap_or(0),
-            completion
-                .result_summary
-                .as_ref()
-                .map(|s| serde_json::to_string(s).unwrap_or_else(|_| "null".to_string()))
-                .unwrap_or_else(|| "null".to_string()),
-            completion
-                .error_message
-                .as_ref()
-                .map(|s| serde_json::to_string(s).unwrap_or_else(|_| "null".to_string()))
-                .unwrap_or_else(|| "null".to_string()),
-        );
-
-        if let Err(e) = self.db.execute(&update_query).await {
+        let params = vec![
+            ("id".to_string(), serde_json::json!(execution_id)),
+            ("status".to_string(), serde_json::json!(completion.status)),
+            (
+                "duration_ms".to_string(),
+                serde_json::json!(completion.duration_ms),
+            ),
+            (
+                "tokens_input".to_string(),
+                serde_json::json!(completion.tokens_input.unwrap_or(0)),
+            ),
+            (
+                "tokens_output".to_string(),
+                serde_json::json!(completion.tokens_output.unwrap_or(0)),
+            ),
+            (
+                "result_summary".to_string(),
+                serde_json::json!(completion.result_summary),
+            ),
+            (
+                "error_message".to_string(),
+                serde_json::json!(completion.error_message),
+            ),
+        ];
+
+        if let Err(e) = self
+            .db
+            .execute_with_params(SUB_AGENT_EXECUTION_COMPLETE, params)
+            .await
+        {
             warn!(
                 execution_id = %execution_id,
                 error = %e,
@@ -489,6 +495,34 @@ impl DelegateTaskTool {
             "max_allowed": MAX_SUB_AGENTS
         }))
     }
+
+    /// Creates the tool with the agent system access of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let context = ctx.require_agent_context()?;
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = DelegateTaskTool::new(
+                ctx.factory.get_db(),
+                context,
+                ctx.agent_id,
+                wf_id,
+                ctx.is_primary_agent,
+            );
+            info!("DelegateTaskTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Bounded by the sub-agent inactivity timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "DelegateTaskTool",
+        category: ToolCategory::SubAgent,
+        requires_context: true,
+        default_timeout_secs: None,
+        constructor: DelegateTaskTool::build,
+    })
 }
 
 #[async_trait]
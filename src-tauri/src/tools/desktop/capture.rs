@@ -0,0 +1,162 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Native screen and window capture.
+//!
+//! Captures go through `xcap` when the `screen-capture` feature is enabled;
+//! without it every capture fails with a message naming the feature, so the
+//! ScreenshotTool stays registered and reports why it cannot work.
+
+use serde::Serialize;
+use std::path::Path;
+
+/// A capturable window, as listed to the agent
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowInfo {
+    /// Window ID passed back to capture the window
+    pub id: u32,
+    /// Window title
+    pub title: String,
+    /// Name of the owning application
+    pub app_name: String,
+    /// Whether the window has the focus
+    pub is_focused: bool,
+}
+
+/// Size of a saved capture, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CaptureSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+#[cfg(feature = "screen-capture")]
+mod native {
+    use super::{CaptureSize, WindowInfo};
+    use crate::tools::constants::desktop as desktop_const;
+    use std::path::Path;
+    use xcap::image::{imageops, ImageFormat, RgbaImage};
+    use xcap::{Monitor, Window};
+
+    /// Saves a capture as PNG, scaled down to the maximum width.
+    fn save(image: RgbaImage, path: &Path) -> Result<CaptureSize, String> {
+        let image = if image.width() > desktop_const::MAX_CAPTURE_WIDTH {
+            let height = (u64::from(image.height()) * u64::from(desktop_const::MAX_CAPTURE_WIDTH)
+                / u64::from(image.width()))
+            .max(1) as u32;
+            imageops::resize(
+                &image,
+                desktop_const::MAX_CAPTURE_WIDTH,
+                height,
+                imageops::FilterType::Triangle,
+            )
+        } else {
+            image
+        };
+        image
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(|e| format!("Failed to save capture: {}", e))?;
+        Ok(CaptureSize {
+            width: image.width(),
+            height: image.height(),
+        })
+    }
+
+    pub fn list_windows() -> Result<Vec<WindowInfo>, String> {
+        let windows = Window::all().map_err(|e| format!("Failed to list windows: {}", e))?;
+        Ok(windows
+            .iter()
+            .filter(|window| !window.is_minimized().unwrap_or(false))
+            .filter_map(|window| {
+                Some(WindowInfo {
+                    id: window.id().ok()?,
+                    title: window.title().unwrap_or_default(),
+                    app_name: window.app_name().unwrap_or_default(),
+                    is_focused: window.is_focused().unwrap_or(false),
+                })
+            })
+            .take(desktop_const::MAX_LISTED_WINDOWS)
+            .collect())
+    }
+
+    pub fn capture_screen(monitor: Option<usize>, path: &Path) -> Result<CaptureSize, String> {
+        let monitors = Monitor::all().map_err(|e| format!("Failed to list screens: {}", e))?;
+        let monitor = match monitor {
+            Some(index) => monitors.get(index).ok_or_else(|| {
+                format!(
+                    "Screen {} not found ({} screens available)",
+                    index,
+                    monitors.len()
+                )
+            })?,
+            None => monitors
+                .iter()
+                .find(|monitor| monitor.is_primary().unwrap_or(false))
+                .or_else(|| monitors.first())
+                .ok_or("No screen found")?,
+        };
+        let image = monitor
+            .capture_image()
+            .map_err(|e| format!("Failed to capture the screen: {}", e))?;
+        save(image, path)
+    }
+
+    pub fn capture_window(window_id: u32, path: &Path) -> Result<CaptureSize, String> {
+        let windows = Window::all().map_err(|e| format!("Failed to list windows: {}", e))?;
+        let window = windows
+            .iter()
+            .find(|window| window.id().ok() == Some(window_id))
+            .ok_or_else(|| format!("Window {} not found; call list_windows first", window_id))?;
+        let image = window
+            .capture_image()
+            .map_err(|e| format!("Failed to capture the window: {}", e))?;
+        save(image, path)
+    }
+}
+
+#[cfg(not(feature = "screen-capture"))]
+mod native {
+    use super::{CaptureSize, WindowInfo};
+    use std::path::Path;
+
+    const UNAVAILABLE: &str =
+        "Screen capture is not available in this build (enable the `screen-capture` feature)";
+
+    pub fn list_windows() -> Result<Vec<WindowInfo>, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn capture_screen(_monitor: Option<usize>, _path: &Path) -> Result<CaptureSize, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+
+    pub fn capture_window(_window_id: u32, _path: &Path) -> Result<CaptureSize, String> {
+        Err(UNAVAILABLE.to_string())
+    }
+}
+
+/// Lists the visible windows (blocking).
+pub fn list_windows() -> Result<Vec<WindowInfo>, String> {
+    native::list_windows()
+}
+
+/// Captures a screen (the primary one by default) to a PNG file (blocking).
+pub fn capture_screen(monitor: Option<usize>, path: &Path) -> Result<CaptureSize, String> {
+    native::capture_screen(monitor, path)
+}
+
+/// Captures a window to a PNG file (blocking).
+pub fn capture_window(window_id: u32, path: &Path) -> Result<CaptureSize, String> {
+    native::capture_window(window_id, path)
+}
@@ -0,0 +1,260 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ClipboardTool implementation.
+
+use super::ensure_permission;
+use crate::db::DBClient;
+use crate::tools::constants::desktop as desktop_const;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{validate_enum_value, validate_length};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::{info, instrument};
+
+/// Tool reading and writing the system clipboard.
+pub struct ClipboardTool {
+    /// Database client (desktop access permissions)
+    db: Arc<DBClient>,
+    /// Agent ID using this tool
+    agent_id: String,
+    /// Tauri app handle (clipboard plugin)
+    app_handle: Option<tauri::AppHandle>,
+}
+
+impl ClipboardTool {
+    /// Creates a new ClipboardTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `agent_id` - Agent ID using the tool
+    /// * `app_handle` - Tauri app handle, required to reach the clipboard
+    pub fn new(db: Arc<DBClient>, agent_id: String, app_handle: Option<tauri::AppHandle>) -> Self {
+        Self {
+            db,
+            agent_id,
+            app_handle,
+        }
+    }
+
+    fn app_handle(&self) -> ToolResult<&tauri::AppHandle> {
+        self.app_handle.as_ref().ok_or_else(|| {
+            ToolError::DependencyError("The clipboard is not available here".to_string())
+        })
+    }
+
+    /// Reads the clipboard text, truncated to the read limit.
+    #[instrument(skip(self), fields(agent_id = %self.agent_id))]
+    fn read(&self) -> ToolResult<Value> {
+        let text = self.app_handle()?.clipboard().read_text().map_err(|e| {
+            ToolError::ExecutionFailed(format!("Failed to read the clipboard: {}", e))
+        })?;
+
+        let length = text.chars().count();
+        let truncated = length > desktop_const::MAX_CLIPBOARD_READ_CHARS;
+        let text: String = text
+            .chars()
+            .take(desktop_const::MAX_CLIPBOARD_READ_CHARS)
+            .collect();
+
+        info!(length, truncated, "Clipboard read");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("text", text)
+            .field("length", length)
+            .field("truncated", truncated)
+            .build())
+    }
+
+    /// Replaces the clipboard content with a text.
+    #[instrument(skip(self, text), fields(agent_id = %self.agent_id))]
+    fn write(&self, text: &str) -> ToolResult<Value> {
+        self.app_handle()?
+            .clipboard()
+            .write_text(text)
+            .map_err(|e| {
+                ToolError::ExecutionFailed(format!("Failed to write the clipboard: {}", e))
+            })?;
+
+        info!(length = text.len(), "Clipboard written");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("length", text.chars().count())
+            .message("Text copied to the clipboard")
+            .build())
+    }
+
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let tool = ClipboardTool::new(ctx.factory.get_db(), ctx.agent_id, ctx.app_handle);
+            info!("ClipboardTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "ClipboardTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: ClipboardTool::build,
+    })
+}
+
+#[async_trait]
+impl Tool for ClipboardTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "ClipboardTool".to_string(),
+            name: "Clipboard Tool".to_string(),
+            description: format!(
+                r#"Reads or writes the system clipboard of the user.
+
+USE THIS TOOL WHEN:
+- The user refers to what they copied ("the text I copied", "my clipboard")
+- The user asks you to put a result in the clipboard
+
+OPERATIONS:
+- read: returns the clipboard text (the first {} characters)
+- write: replaces the clipboard content with "text"
+
+IMPORTANT CONSTRAINTS:
+- Only text is supported
+- The user must enable clipboard access in the settings; a permission error means it is disabled
+- Do not write to the clipboard unless the user asked for it
+
+EXAMPLES:
+1. {{"operation": "read"}}
+2. {{"operation": "write", "text": "SELECT * FROM orders WHERE status = 'open'"}}"#,
+                desktop_const::MAX_CLIPBOARD_READ_CHARS
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": desktop_const::CLIPBOARD_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "text": {
+                        "type": "string",
+                        "maxLength": desktop_const::MAX_CLIPBOARD_WRITE_LENGTH,
+                        "description": "Text to copy (write)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "text": {"type": "string"},
+                    "length": {"type": "integer"},
+                    "truncated": {"type": "boolean"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        ensure_permission(&self.db, |settings| settings.clipboard, "ClipboardTool").await?;
+
+        match input["operation"].as_str().unwrap_or_default() {
+            "read" => self.read(),
+            _ => self.write(input["text"].as_str().unwrap_or_default()),
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation".to_string()))?;
+        validate_enum_value(operation, desktop_const::CLIPBOARD_OPERATIONS, "operation")?;
+
+        if operation == "write" {
+            let text = input["text"].as_str().ok_or_else(|| {
+                ToolError::InvalidInput("Missing 'text' for write operation".to_string())
+            })?;
+            validate_length(text, desktop_const::MAX_CLIPBOARD_WRITE_LENGTH, "text")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::desktop_access::DesktopAccessSettings;
+
+    async fn setup(settings: Option<DesktopAccessSettings>) -> (ClipboardTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        if let Some(settings) = settings {
+            crate::db::queries::settings::save_config(
+                &db,
+                desktop_const::SETTINGS_KEY,
+                &serde_json::to_value(settings).unwrap(),
+            )
+            .await
+            .unwrap();
+        }
+        (
+            ClipboardTool::new(Arc::new(db), "agent".to_string(), None),
+            dir,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = setup(None).await;
+        assert!(tool.validate_input(&json!({"operation": "read"})).is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "write", "text": "hello"}))
+            .is_ok());
+        assert!(tool.validate_input(&json!({"operation": "write"})).is_err());
+        assert!(tool.validate_input(&json!({"operation": "clear"})).is_err());
+        assert!(tool.validate_input(&json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_permission() {
+        let (tool, _dir) = setup(None).await;
+        let result = tool.execute(json!({"operation": "read"})).await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+
+        let (tool, _dir) = setup(Some(DesktopAccessSettings {
+            clipboard: true,
+            ..Default::default()
+        }))
+        .await;
+        // Permission granted: fails on the missing app handle instead
+        let result = tool.execute(json!({"operation": "read"})).await;
+        assert!(matches!(result, Err(ToolError::DependencyError(_))));
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Desktop Access Tool Module
+//!
+//! Provides tools operating on what the user currently sees:
+//! - ClipboardTool: reads and writes the system clipboard
+//! - ScreenshotTool: lists windows and captures the screen or a window
+//!
+//! Each tool is gated by its toggle in the desktop access settings, checked
+//! on every call (all disabled by default). The PdfTool and the OcrTool share
+//! the check for their local documents toggle.
+
+pub mod capture;
+mod clipboard;
+mod screenshot;
+
+pub use clipboard::ClipboardTool;
+pub use screenshot::ScreenshotTool;
+
+use crate::commands::desktop_access::load_desktop_access_settings;
+use crate::db::DBClient;
+use crate::models::desktop_access::DesktopAccessSettings;
+use crate::tools::{ToolError, ToolResult};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// Fails with `PermissionDenied` unless the user enabled the permission.
+pub(crate) async fn ensure_permission(
+    db: &DBClient,
+    allowed: fn(&DesktopAccessSettings) -> bool,
+    tool_name: &str,
+) -> ToolResult<()> {
+    if allowed(&load_desktop_access_settings(db).await) {
+        Ok(())
+    } else {
+        Err(ToolError::PermissionDenied(format!(
+            "{} is disabled. The user can enable it in Settings > Validation > Desktop Access",
+            tool_name
+        )))
+    }
+}
+
+/// Data URL of a capture, shown to the model after the tool result.
+pub fn capture_data_url(path: &str) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    Some(format!("data:image/png;base64,{}", STANDARD.encode(bytes)))
+}
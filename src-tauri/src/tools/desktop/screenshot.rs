@@ -0,0 +1,305 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ScreenshotTool implementation.
+//!
+//! Captures are saved as PNG in the system temp directory. The model sees
+//! each capture right after the tool result, and the paths reported in the
+//! results are attached to the assistant response when it is saved.
+
+use super::capture;
+use super::ensure_permission;
+use crate::db::DBClient;
+use crate::tools::constants::desktop as desktop_const;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::validate_enum_value;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// Tool capturing the screen or a window of the user.
+pub struct ScreenshotTool {
+    /// Database client (desktop access permissions)
+    db: Arc<DBClient>,
+    /// Current workflow ID (sub-directory of the captures)
+    workflow_id: String,
+    /// Agent ID using this tool
+    agent_id: String,
+    /// Directory of the captures
+    output_dir: PathBuf,
+}
+
+impl ScreenshotTool {
+    /// Creates a new ScreenshotTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `workflow_id` - Workflow ID
+    /// * `agent_id` - Agent ID using the tool
+    pub fn new(db: Arc<DBClient>, workflow_id: String, agent_id: String) -> Self {
+        Self {
+            db,
+            workflow_id,
+            agent_id,
+            output_dir: std::env::temp_dir().join(desktop_const::OUTPUT_DIR_NAME),
+        }
+    }
+
+    /// Lists the visible windows.
+    #[instrument(skip(self), fields(agent_id = %self.agent_id))]
+    async fn list_windows(&self) -> ToolResult<Value> {
+        let windows = tokio::task::spawn_blocking(capture::list_windows)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Window listing failed: {}", e)))?
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(count = windows.len(), "Windows listed");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .count(windows.len())
+            .data("windows", windows)
+            .build())
+    }
+
+    /// Captures the screen or a window and returns the path of the image.
+    #[instrument(skip(self, input), fields(agent_id = %self.agent_id))]
+    async fn capture(&self, input: &Value) -> ToolResult<Value> {
+        let target = input["target"].as_str().unwrap_or("screen").to_string();
+        let monitor = input["monitor"].as_u64().map(|index| index as usize);
+        let window_id = input["window_id"].as_u64().map(|id| id as u32);
+
+        let capture_id = Uuid::new_v4().to_string();
+        let dir = self.output_dir.join(&self.workflow_id);
+        let path = dir.join(format!("{}.png", capture_id));
+        tokio::fs::create_dir_all(&dir).await.map_err(|e| {
+            ToolError::ExecutionFailed(format!("Failed to create capture directory: {}", e))
+        })?;
+
+        let capture_path = path.clone();
+        let capture_target = target.clone();
+        let size = tokio::task::spawn_blocking(move || match window_id {
+            Some(id) if capture_target == "window" => capture::capture_window(id, &capture_path),
+            _ => capture::capture_screen(monitor, &capture_path),
+        })
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("Capture failed: {}", e)))?
+        .map_err(ToolError::ExecutionFailed)?;
+
+        info!(capture_id = %capture_id, target = %target, path = %path.display(), "Screen captured");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .id("capture_id", capture_id)
+            .field("target", target)
+            .field("width", size.width)
+            .field("height", size.height)
+            .field("path", path.to_string_lossy().into_owned())
+            .message(
+                "Screenshot captured; the image follows and is attached to your response. \
+                 Do not link the file",
+            )
+            .build())
+    }
+
+    /// Creates the tool for the workflow of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = ScreenshotTool::new(ctx.factory.get_db(), wf_id, ctx.agent_id);
+            info!("ScreenshotTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "ScreenshotTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: ScreenshotTool::build,
+    })
+}
+
+#[async_trait]
+impl Tool for ScreenshotTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "ScreenshotTool".to_string(),
+            name: "Screenshot Tool".to_string(),
+            description: format!(
+                r#"Captures the screen or a window of the user, so you can see what they see.
+
+USE THIS TOOL WHEN:
+- The user refers to something on their screen ("this error", "the window I have open")
+- Seeing an application is needed to help the user
+
+OPERATIONS:
+- list_windows: lists the visible windows (id, title, app_name, is_focused), at most {}
+- capture: captures a screen ("target": "screen", optional "monitor" index, primary by default)
+  or a window ("target": "window" with a "window_id" from list_windows)
+
+IMPORTANT CONSTRAINTS:
+- The user must enable screen capture in the settings; a permission error means it is disabled
+- Captures wider than {} pixels are scaled down
+- The capture is shown to you after the result (vision models only) and attached to your response:
+  do not add links or file paths to your answer
+
+EXAMPLES:
+1. {{"operation": "capture"}}
+2. {{"operation": "list_windows"}}
+3. {{"operation": "capture", "target": "window", "window_id": 48234}}"#,
+                desktop_const::MAX_LISTED_WINDOWS,
+                desktop_const::MAX_CAPTURE_WIDTH
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": desktop_const::SCREENSHOT_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "target": {
+                        "type": "string",
+                        "enum": desktop_const::CAPTURE_TARGETS,
+                        "description": "What to capture (capture, default: screen)"
+                    },
+                    "monitor": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Screen index (capture of a screen, default: primary)"
+                    },
+                    "window_id": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Window ID from list_windows (capture of a window)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "capture_id": {"type": "string"},
+                    "target": {"type": "string"},
+                    "width": {"type": "integer"},
+                    "height": {"type": "integer"},
+                    "path": {"type": "string"},
+                    "windows": {"type": "array"},
+                    "count": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        ensure_permission(&self.db, |settings| settings.screenshot, "ScreenshotTool").await?;
+
+        match input["operation"].as_str().unwrap_or_default() {
+            "list_windows" => self.list_windows().await,
+            _ => self.capture(&input).await,
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation".to_string()))?;
+        validate_enum_value(operation, desktop_const::SCREENSHOT_OPERATIONS, "operation")?;
+
+        if operation == "capture" {
+            let target = input["target"].as_str().unwrap_or("screen");
+            validate_enum_value(target, desktop_const::CAPTURE_TARGETS, "target")?;
+            if target == "window" && input["window_id"].as_u64().is_none() {
+                return Err(ToolError::InvalidInput(
+                    "Missing 'window_id' for a window capture (see list_windows)".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::desktop_access::DesktopAccessSettings;
+
+    async fn setup(screenshot: bool) -> (ScreenshotTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let settings = DesktopAccessSettings {
+            screenshot,
+            ..Default::default()
+        };
+        crate::db::queries::settings::save_config(
+            &db,
+            desktop_const::SETTINGS_KEY,
+            &serde_json::to_value(settings).unwrap(),
+        )
+        .await
+        .unwrap();
+        let tool = ScreenshotTool {
+            output_dir: dir.path().join("captures"),
+            ..ScreenshotTool::new(Arc::new(db), "wf".to_string(), "agent".to_string())
+        };
+        (tool, dir)
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = setup(false).await;
+        assert!(tool
+            .validate_input(&json!({"operation": "capture"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "list_windows"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "capture", "target": "window", "window_id": 3}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "capture", "target": "window"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "capture", "target": "region"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "record"}))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_permission() {
+        let (tool, _dir) = setup(false).await;
+        let result = tool.execute(json!({"operation": "capture"})).await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dry run of tool calls.
+//!
+//! In a workflow with the `dry_run` flag, the agent loop does not execute the
+//! tool calls with side effects: they return a simulated result describing
+//! what they would have done, so the plan of an agent can be previewed
+//! safely. Read-only calls still run, so the agent works on real data.
+//!
+//! Calls with side effects are the ones [`tool_call_effects`] does not rate
+//! read-only, the same effects that set the risk level of their validation,
+//! plus:
+//!
+//! - Plugin tools granted network access (their requests are only known
+//!   once they run)
+//! - Macro tools with a step having side effects
+//!
+//! The simulated result holds a [`ChangePreview`] when one can be computed:
+//! the SQL statement or shell command, the diff of a written file, the HTTP
+//! request, or the records a deletion would remove (see
+//! [`validation_preview`]).
+
+use crate::commands::plugin::load_plugin_settings;
+use crate::db::DBClient;
+use crate::models::plugin::PluginCapability;
+use crate::models::tool_acknowledgment::{classify_powerful_tool, name_words, PowerfulToolKind};
+use crate::models::tool_effects::{tool_call_effects, ToolCallEffects};
+use crate::models::validation::ChangePreview;
+use crate::plugins::{plugin_id, PLUGIN_HOST};
+use crate::tools::constants::dry_run::{BODY_ARGS, COMMAND_ARGS, URL_ARGS};
+use crate::tools::constants::validation_preview::MAX_STATEMENT_CHARS;
+use crate::tools::macros::{macro_name, MacroTool};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::validation_helper::safe_truncate;
+use crate::tools::validation_preview::{self, build_preview};
+use serde_json::{json, Value};
+use tracing::warn;
+
+/// Whether a workflow is in dry run mode (false when it cannot be loaded).
+pub async fn is_workflow_dry_run(db: &DBClient, workflow_id: &str) -> bool {
+    let rows = db
+        .query_json_with_params(
+            "SELECT (dry_run ?? false) AS dry_run FROM workflow WHERE meta::id(id) = $id",
+            vec![("id".to_string(), json!(workflow_id))],
+        )
+        .await;
+    match rows {
+        Ok(rows) => rows
+            .first()
+            .and_then(|row| row.get("dry_run"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        Err(e) => {
+            warn!(workflow_id = %workflow_id, error = %e, "Failed to load workflow dry run mode");
+            false
+        }
+    }
+}
+
+/// Simulates a local tool call, if it has side effects.
+///
+/// # Arguments
+/// * `db` - Database client (previews, plugin grants, macros)
+/// * `workflow_id` - Workflow of the call
+/// * `tool_name` - Local tool name
+/// * `arguments` - Tool call arguments
+///
+/// # Returns
+/// The simulated result, None when the call can run
+pub async fn simulate_local(
+    db: &DBClient,
+    workflow_id: &str,
+    tool_name: &str,
+    arguments: &Value,
+) -> Option<Value> {
+    if let Some(name) = macro_name(tool_name) {
+        return simulate_macro(db, name, tool_name, arguments).await;
+    }
+    if let Some(id) = plugin_id(tool_name) {
+        let hosts = plugin_network_hosts(db, id).await?;
+        let mut result = simulated_result(tool_name, None, arguments, None);
+        result["network_hosts"] = json!(hosts);
+        return Some(result);
+    }
+
+    if tool_call_effects(tool_name, false, arguments) == ToolCallEffects::ReadOnly {
+        return None;
+    }
+    let preview = match tool_name {
+        "SqlTool" => arguments["sql"]
+            .as_str()
+            .map(|sql| ChangePreview::Statement {
+                statement: safe_truncate(sql, MAX_STATEMENT_CHARS, true),
+            }),
+        _ => build_preview(db, workflow_id, tool_name, false, arguments).await,
+    };
+    Some(simulated_result(
+        tool_name,
+        arguments["operation"].as_str(),
+        arguments,
+        preview,
+    ))
+}
+
+/// Simulates an MCP tool call, if it has side effects.
+///
+/// # Arguments
+/// * `db` - Database client
+/// * `workflow_id` - Workflow of the call
+/// * `server` - MCP server name
+/// * `tool` - Tool name without server prefix
+/// * `arguments` - Tool call arguments
+///
+/// # Returns
+/// The simulated result, None when the call can run
+pub async fn simulate_mcp(
+    db: &DBClient,
+    workflow_id: &str,
+    server: &str,
+    tool: &str,
+    arguments: &Value,
+) -> Option<Value> {
+    if tool_call_effects(tool, true, arguments) == ToolCallEffects::ReadOnly {
+        return None;
+    }
+    let preview = match classify_powerful_tool(tool) {
+        Some(PowerfulToolKind::Shell) => validation_preview::string_arg(arguments, COMMAND_ARGS)
+            .map(|command| ChangePreview::Statement {
+                statement: safe_truncate(command, MAX_STATEMENT_CHARS, true),
+            }),
+        Some(PowerfulToolKind::ExternalSql | PowerfulToolKind::FileSystemWrite) => {
+            build_preview(db, workflow_id, tool, true, arguments).await
+        }
+        None => request_preview(tool, arguments),
+    };
+    let mut result = simulated_result(tool, None, arguments, preview);
+    result["server"] = json!(server);
+    Some(result)
+}
+
+/// Simulated result of a call: what it would have done.
+fn simulated_result(
+    tool_name: &str,
+    operation: Option<&str>,
+    arguments: &Value,
+    preview: Option<ChangePreview>,
+) -> Value {
+    let call = match operation {
+        Some(operation) => format!("{} {}", tool_name, operation),
+        None => tool_name.to_string(),
+    };
+    let mut builder = ResponseBuilder::new()
+        .success(true)
+        .field("dry_run", true)
+        .message(format!(
+            "Dry run: {} was not executed. The workflow is in dry run mode, calls with side effects are simulated and change nothing.",
+            call
+        ))
+        .field("tool", tool_name)
+        .field("arguments", arguments.clone());
+    if let Some(operation) = operation {
+        builder = builder.field("operation", operation);
+    }
+    if let Some(preview) = preview {
+        builder = builder.data("preview", preview);
+    }
+    builder.build()
+}
+
+/// Simulates a macro with a step having side effects, listing its steps.
+///
+/// Steps are classified from their input templates: an operation given by
+/// a placeholder may have side effects.
+async fn simulate_macro(
+    db: &DBClient,
+    name: &str,
+    tool_name: &str,
+    arguments: &Value,
+) -> Option<Value> {
+    let tool_macro = MacroTool::load_macro(db, name).await.ok()?;
+    let mut steps = Vec::with_capacity(tool_macro.steps.len());
+    let mut simulated = false;
+    for step in &tool_macro.steps {
+        let side_effects = step_has_side_effects(db, &step.tool, &step.input).await;
+        simulated |= side_effects;
+        steps.push(json!({
+            "id": step.id,
+            "tool": step.tool,
+            "input": step.input,
+            "side_effects": side_effects
+        }));
+    }
+    if !simulated {
+        return None;
+    }
+    let mut result = simulated_result(tool_name, None, arguments, None);
+    result["steps"] = json!(steps);
+    Some(result)
+}
+
+/// Whether a macro step has side effects.
+async fn step_has_side_effects(db: &DBClient, tool_name: &str, input: &Value) -> bool {
+    if let Some(id) = plugin_id(tool_name) {
+        return plugin_network_hosts(db, id).await.is_some();
+    }
+    input["operation"]
+        .as_str()
+        .is_some_and(|operation| operation.contains("{{"))
+        || tool_call_effects(tool_name, false, input) != ToolCallEffects::ReadOnly
+}
+
+/// Hosts a plugin may send requests to, None without granted network access.
+async fn plugin_network_hosts(db: &DBClient, id: &str) -> Option<Vec<String>> {
+    let settings = load_plugin_settings(db).await;
+    let granted = settings.plugins.get(id).is_some_and(|grant| {
+        grant.enabled && grant.capabilities.contains(&PluginCapability::Network)
+    });
+    if !granted {
+        return None;
+    }
+    let id = id.to_string();
+    // Reading the manifest hashes the plugin files
+    let info = tokio::task::spawn_blocking(move || PLUGIN_HOST.info(&id, &settings))
+        .await
+        .ok()?;
+    Some(info.network_hosts)
+}
+
+/// HTTP request preview of an MCP call taking a URL.
+///
+/// The method is the `method` argument, or the HTTP verb of the tool name
+/// (`GET` by default).
+fn request_preview(tool: &str, arguments: &Value) -> Option<ChangePreview> {
+    let url = validation_preview::string_arg(arguments, URL_ARGS)?;
+    let method = arguments["method"]
+        .as_str()
+        .map(str::to_uppercase)
+        .or_else(|| {
+            name_words(tool)
+                .into_iter()
+                .find(|w| ["delete", "patch", "post", "put"].contains(&w.as_str()))
+                .map(|w| w.to_uppercase())
+        })
+        .unwrap_or_else(|| "GET".to_string());
+    let body = BODY_ARGS
+        .iter()
+        .find_map(|name| arguments.get(*name).filter(|v| !v.is_null()))
+        .map(|body| match body {
+            Value::String(text) => safe_truncate(text, MAX_STATEMENT_CHARS, true),
+            other => safe_truncate(&other.to_string(), MAX_STATEMENT_CHARS, true),
+        });
+    Some(ChangePreview::Request {
+        method,
+        url: url.to_string(),
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_request_preview() {
+        let post = json!({ "url": "https://api.example.com/items", "body": { "name": "x" } });
+        assert_eq!(
+            request_preview("http_post", &post),
+            Some(ChangePreview::Request {
+                method: "POST".to_string(),
+                url: "https://api.example.com/items".to_string(),
+                body: Some(r#"{"name":"x"}"#.to_string()),
+            })
+        );
+
+        let explicit = json!({ "url": "https://example.com", "method": "put" });
+        assert!(matches!(
+            request_preview("send_request", &explicit),
+            Some(ChangePreview::Request { method, body: None, .. }) if method == "PUT"
+        ));
+        assert!(request_preview("send_message", &json!({ "text": "hi" })).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_calls() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("dry_run_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let execute =
+            json!({ "operation": "execute", "database": "crm", "sql": "DELETE FROM leads" });
+        let result = simulate_local(&db, "wf1", "SqlTool", &execute)
+            .await
+            .expect("SqlTool execute is simulated");
+        assert_eq!(result["dry_run"], true);
+        assert_eq!(result["operation"], "execute");
+        assert_eq!(result["preview"]["kind"], "statement");
+        assert_eq!(result["preview"]["statement"], "DELETE FROM leads");
+
+        let query = json!({ "operation": "query", "database": "crm", "sql": "SELECT 1" });
+        assert!(simulate_local(&db, "wf1", "SqlTool", &query)
+            .await
+            .is_none());
+        let evaluate = json!({ "expression": "1 + 1" });
+        assert!(simulate_local(&db, "wf1", "CalculatorTool", &evaluate)
+            .await
+            .is_none());
+
+        let command = json!({ "command": "rm -rf build" });
+        let result = simulate_mcp(&db, "wf1", "shell", "run_command", &command)
+            .await
+            .expect("shell commands are simulated");
+        assert_eq!(result["server"], "shell");
+        assert_eq!(result["preview"]["statement"], "rm -rf build");
+
+        let select = json!({ "query": "SELECT * FROM users" });
+        assert!(simulate_mcp(&db, "wf1", "db", "execute_sql", &select)
+            .await
+            .is_none());
+        let read = json!({ "path": "/tmp/notes.txt" });
+        assert!(simulate_mcp(&db, "wf1", "fs", "read_file", &read)
+            .await
+            .is_none());
+        let issue = json!({ "title": "Bug" });
+        let result = simulate_mcp(&db, "wf1", "github", "create_issue", &issue)
+            .await
+            .expect("writes are simulated");
+        assert!(result.get("preview").is_none());
+        assert_eq!(result["arguments"]["title"], "Bug");
+    }
+
+    #[tokio::test]
+    async fn test_is_workflow_dry_run() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("dry_run_flag_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        db.create(
+            "workflow",
+            "wf1",
+            json!({ "id": "wf1", "name": "Plan", "agent_id": "a1", "status": "idle", "dry_run": true }),
+        )
+        .await
+        .unwrap();
+
+        assert!(is_workflow_dry_run(&db, "wf1").await);
+        assert!(!is_workflow_dry_run(&db, "missing").await);
+    }
+}
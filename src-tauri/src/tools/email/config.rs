@@ -0,0 +1,183 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Email account configuration.
+//!
+//! Metadata lives in the `email_account` table; each password is stored in
+//! SecureKeyStore under [`password_key`].
+
+use crate::db::DBClient;
+use crate::models::email::EmailAccount;
+use crate::tools::constants::email as email_const;
+use serde_json::json;
+use tracing::warn;
+
+/// Fields selected for an email account
+const ACCOUNT_SELECT_FIELDS: &str = "name, address, display_name, imap_host, imap_port, \
+     smtp_host, smtp_port, username, read_only, \
+     time::format(created_at, '%Y-%m-%dT%H:%M:%SZ') AS created_at";
+
+/// SecureKeyStore entry of the password of an account
+pub fn password_key(name: &str) -> String {
+    format!("email_account_{}", name)
+}
+
+/// Validates an account name (lowercase letters, digits, `-` and `_`).
+pub fn validate_account_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > email_const::MAX_NAME_LENGTH {
+        return Err(format!(
+            "Account name must be 1-{} characters",
+            email_const::MAX_NAME_LENGTH
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(
+            "Account name must contain only lowercase letters, numbers, '-' and '_'".to_string(),
+        );
+    }
+    Ok(name.to_string())
+}
+
+/// Lists the configured email accounts, sorted by name.
+pub async fn list_accounts(db: &DBClient) -> Result<Vec<EmailAccount>, String> {
+    let rows = db
+        .query_json(&format!(
+            "SELECT {} FROM email_account ORDER BY name",
+            ACCOUNT_SELECT_FIELDS
+        ))
+        .await
+        .map_err(|e| format!("Failed to list email accounts: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let name = row["name"].as_str().unwrap_or_default().to_string();
+            match serde_json::from_value(row) {
+                Ok(account) => Some(account),
+                Err(e) => {
+                    warn!(name = %name, error = %e, "Skipping malformed email account record");
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Loads an email account by name.
+pub async fn load_account(db: &DBClient, name: &str) -> Result<Option<EmailAccount>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM email_account WHERE name = $name",
+                ACCOUNT_SELECT_FIELDS
+            ),
+            vec![("name".to_string(), json!(name))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load email account: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .next()
+        .and_then(|row| match serde_json::from_value(row) {
+            Ok(account) => Some(account),
+            Err(e) => {
+                warn!(name = %name, error = %e, "Skipping malformed email account record");
+                None
+            }
+        }))
+}
+
+/// Creates or updates an email account.
+pub async fn save_account(db: &DBClient, account: &EmailAccount) -> Result<(), String> {
+    db.execute_with_params(
+        "UPSERT type::thing('email_account', $name) SET name = $name, address = $address, \
+         display_name = $display_name, imap_host = $imap_host, imap_port = $imap_port, \
+         smtp_host = $smtp_host, smtp_port = $smtp_port, username = $username, \
+         read_only = $read_only, updated_at = time::now()",
+        vec![
+            ("name".to_string(), json!(account.name)),
+            ("address".to_string(), json!(account.address)),
+            ("display_name".to_string(), json!(account.display_name)),
+            ("imap_host".to_string(), json!(account.imap_host)),
+            ("imap_port".to_string(), json!(account.imap_port)),
+            ("smtp_host".to_string(), json!(account.smtp_host)),
+            ("smtp_port".to_string(), json!(account.smtp_port)),
+            ("username".to_string(), json!(account.username)),
+            ("read_only".to_string(), json!(account.read_only)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to save email account: {}", e))
+}
+
+/// Deletes an email account.
+pub async fn delete_account(db: &DBClient, name: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "DELETE type::thing('email_account', $name)",
+        vec![("name".to_string(), json!(name))],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete email account: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_account_name() {
+        assert_eq!(validate_account_name(" work "), Ok("work".to_string()));
+        assert!(validate_account_name("").is_err());
+        assert!(validate_account_name("Work").is_err());
+        assert!(validate_account_name("my work").is_err());
+        assert!(validate_account_name(&"a".repeat(65)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_load_delete_account() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let mut account = EmailAccount {
+            name: "work".to_string(),
+            address: "me@example.com".to_string(),
+            display_name: Some("Me".to_string()),
+            imap_host: "imap.example.com".to_string(),
+            imap_port: 993,
+            smtp_host: "smtp.example.com".to_string(),
+            smtp_port: 465,
+            username: "me@example.com".to_string(),
+            read_only: true,
+            created_at: None,
+        };
+        save_account(&db, &account).await.unwrap();
+        account.read_only = false;
+        account.display_name = None;
+        save_account(&db, &account).await.unwrap();
+
+        let loaded = load_account(&db, "work").await.unwrap().unwrap();
+        assert!(!loaded.read_only);
+        assert_eq!(loaded.display_name, None);
+        assert_eq!(loaded.smtp_port, 465);
+        assert!(loaded.created_at.is_some());
+        assert_eq!(list_accounts(&db).await.unwrap().len(), 1);
+
+        delete_account(&db, "work").await.unwrap();
+        assert!(load_account(&db, "work").await.unwrap().is_none());
+    }
+}
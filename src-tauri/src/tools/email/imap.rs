@@ -0,0 +1,209 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mailbox reading over IMAP.
+//!
+//! Each call opens its own TLS connection and logs out when done. Mailboxes
+//! are opened with `EXAMINE` and messages fetched with `BODY.PEEK`, so
+//! reading never changes the mailbox (messages stay unread).
+
+use super::parse::{parse_message, summarize};
+use crate::models::email::{EmailAccount, EmailMessage, EmailSummary};
+use crate::tools::constants::email as email_const;
+use async_imap::types::{Fetch, Flag};
+use futures::TryStreamExt;
+use std::cmp::Reverse;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::{self, pki_types::ServerName};
+use tokio_rustls::TlsConnector;
+
+type ImapSession = async_imap::Session<TlsStream<TcpStream>>;
+
+/// Bytes of the message text fetched for the preview of a listed message
+const PREVIEW_FETCH_BYTES: usize = 4096;
+
+/// Fails when the server does not answer in time.
+async fn with_timeout<T>(future: impl Future<Output = Result<T, String>>) -> Result<T, String> {
+    tokio::time::timeout(Duration::from_secs(email_const::TIMEOUT_SECS), future)
+        .await
+        .map_err(|_| "The IMAP server did not answer in time".to_string())?
+}
+
+fn tls_connector() -> Result<TlsConnector, String> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| format!("TLS configuration failed: {}", e))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// Connects over TLS and logs in.
+async fn connect(account: &EmailAccount, password: &str) -> Result<ImapSession, String> {
+    let server_name = ServerName::try_from(account.imap_host.clone())
+        .map_err(|e| format!("Invalid IMAP host '{}': {}", account.imap_host, e))?;
+    let tcp = TcpStream::connect((account.imap_host.as_str(), account.imap_port))
+        .await
+        .map_err(|e| format!("Failed to connect to the IMAP server: {}", e))?;
+    let tls = tls_connector()?
+        .connect(server_name, tcp)
+        .await
+        .map_err(|e| format!("IMAP TLS handshake failed: {}", e))?;
+
+    let mut client = async_imap::Client::new(tls);
+    client
+        .read_response()
+        .await
+        .ok_or_else(|| "The IMAP server closed the connection".to_string())?
+        .map_err(|e| format!("Failed to read the IMAP greeting: {}", e))?;
+    client
+        .login(&account.username, password)
+        .await
+        .map_err(|(e, _)| format!("IMAP login failed: {}", e))
+}
+
+/// Logs out, ignoring failures (the connection is closed anyway).
+async fn logout(mut session: ImapSession) {
+    let _ = session.logout().await;
+}
+
+fn is_seen(fetch: &Fetch) -> bool {
+    fetch.flags().any(|flag| flag == Flag::Seen)
+}
+
+/// Checks that the account can log in to its IMAP server.
+pub async fn test_login(account: &EmailAccount, password: &str) -> Result<(), String> {
+    with_timeout(async {
+        logout(connect(account, password).await?).await;
+        Ok(())
+    })
+    .await
+}
+
+/// Lists the latest messages of a mailbox, newest first.
+pub async fn list_messages(
+    account: &EmailAccount,
+    password: &str,
+    mailbox: &str,
+    limit: usize,
+    unread_only: bool,
+) -> Result<Vec<EmailSummary>, String> {
+    with_timeout(async {
+        let mut session = connect(account, password).await?;
+        let result = fetch_summaries(&mut session, mailbox, limit, unread_only).await;
+        logout(session).await;
+        result
+    })
+    .await
+}
+
+async fn fetch_summaries(
+    session: &mut ImapSession,
+    mailbox: &str,
+    limit: usize,
+    unread_only: bool,
+) -> Result<Vec<EmailSummary>, String> {
+    session
+        .examine(mailbox)
+        .await
+        .map_err(|e| format!("Failed to open mailbox '{}': {}", mailbox, e))?;
+    let mut uids: Vec<u32> = session
+        .uid_search(if unread_only { "UNSEEN" } else { "ALL" })
+        .await
+        .map_err(|e| format!("Failed to search the mailbox: {}", e))?
+        .into_iter()
+        .collect();
+    uids.sort_unstable_by_key(|uid| Reverse(*uid));
+    uids.truncate(limit);
+    if uids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let uid_set = uids
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let fetches: Vec<Fetch> = session
+        .uid_fetch(
+            &uid_set,
+            format!(
+                "(UID FLAGS BODY.PEEK[HEADER] BODY.PEEK[TEXT]<0.{}>)",
+                PREVIEW_FETCH_BYTES
+            ),
+        )
+        .await
+        .map_err(|e| format!("Failed to fetch messages: {}", e))?
+        .try_collect()
+        .await
+        .map_err(|e| format!("Failed to fetch messages: {}", e))?;
+
+    let mut summaries: Vec<EmailSummary> = fetches
+        .iter()
+        .filter_map(|fetch| {
+            let mut raw = fetch.header()?.to_vec();
+            raw.extend_from_slice(fetch.text().unwrap_or_default());
+            Some(summarize(fetch.uid?, is_seen(fetch), &raw))
+        })
+        .collect();
+    summaries.sort_by_key(|summary| Reverse(summary.uid));
+    Ok(summaries)
+}
+
+/// Fetches a complete message by UID.
+pub async fn fetch_message(
+    account: &EmailAccount,
+    password: &str,
+    mailbox: &str,
+    uid: u32,
+) -> Result<Option<EmailMessage>, String> {
+    with_timeout(async {
+        let mut session = connect(account, password).await?;
+        let result = fetch_raw(&mut session, mailbox, uid).await;
+        logout(session).await;
+        result?.map(|raw| parse_message(uid, &raw)).transpose()
+    })
+    .await
+}
+
+async fn fetch_raw(
+    session: &mut ImapSession,
+    mailbox: &str,
+    uid: u32,
+) -> Result<Option<Vec<u8>>, String> {
+    session
+        .examine(mailbox)
+        .await
+        .map_err(|e| format!("Failed to open mailbox '{}': {}", mailbox, e))?;
+    let fetches: Vec<Fetch> = session
+        .uid_fetch(uid.to_string(), "(UID BODY.PEEK[])")
+        .await
+        .map_err(|e| format!("Failed to fetch message {}: {}", uid, e))?
+        .try_collect()
+        .await
+        .map_err(|e| format!("Failed to fetch message {}: {}", uid, e))?;
+    Ok(fetches
+        .iter()
+        .find(|fetch| fetch.uid == Some(uid))
+        .and_then(|fetch| fetch.body())
+        .map(<[u8]>::to_vec))
+}
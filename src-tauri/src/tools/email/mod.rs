@@ -0,0 +1,30 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Email Tool Module
+//!
+//! Provides the EmailTool, which lets agents use user-configured email
+//! accounts:
+//! - Mailbox reading over IMAP, read-only (messages stay unread)
+//! - Plain text sending over SMTP, always confirmed by the user
+//!
+//! Passwords are stored in SecureKeyStore, never in the database.
+
+pub mod config;
+pub mod imap;
+mod parse;
+pub mod smtp;
+mod tool;
+
+pub use tool::EmailTool;
@@ -0,0 +1,150 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversion of raw RFC 5322 messages to the summaries and messages
+//! returned to agents.
+
+use crate::models::email::{EmailMessage, EmailSummary};
+use crate::tools::constants::email as email_const;
+use crate::tools::validation_helper::safe_truncate;
+use mail_parser::{Addr, Address, MessageParser, MimeHeaders};
+
+/// Formats an address as `Name <address>`, or the bare address.
+fn format_addr(addr: &Addr) -> String {
+    match (addr.name(), addr.address()) {
+        (Some(name), Some(address)) => format!("{} <{}>", name, address),
+        (None, Some(address)) => address.to_string(),
+        (Some(name), None) => name.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+fn format_addresses(address: Option<&Address>) -> Vec<String> {
+    address
+        .map(|address| address.iter().map(format_addr).collect())
+        .unwrap_or_default()
+}
+
+/// Collapses the whitespace of a body excerpt to a single line.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Summarizes a message from its headers and the start of its text.
+pub fn summarize(uid: u32, seen: bool, raw: &[u8]) -> EmailSummary {
+    let message = MessageParser::default().parse(raw);
+    let from = message
+        .as_ref()
+        .and_then(|message| message.from())
+        .and_then(|address| address.first())
+        .map(format_addr)
+        .unwrap_or_default();
+    let preview = message
+        .as_ref()
+        .and_then(|message| message.body_text(0))
+        .map(|text| safe_truncate(&single_line(&text), email_const::PREVIEW_CHARS, true))
+        .unwrap_or_default();
+
+    EmailSummary {
+        uid,
+        from,
+        subject: message
+            .as_ref()
+            .and_then(|message| message.subject())
+            .unwrap_or_default()
+            .to_string(),
+        date: message
+            .as_ref()
+            .and_then(|message| message.date())
+            .map(|date| date.to_rfc3339()),
+        seen,
+        preview,
+    }
+}
+
+/// Parses a complete message, its body truncated to the read limit.
+pub fn parse_message(uid: u32, raw: &[u8]) -> Result<EmailMessage, String> {
+    let message = MessageParser::default()
+        .parse(raw)
+        .ok_or_else(|| format!("Message {} could not be parsed", uid))?;
+
+    let body = message.body_text(0).unwrap_or_default();
+    let truncated = body.chars().count() > email_const::MAX_BODY_CHARS;
+    let body = safe_truncate(&body, email_const::MAX_BODY_CHARS, false);
+
+    Ok(EmailMessage {
+        uid,
+        from: message
+            .from()
+            .and_then(|address| address.first())
+            .map(format_addr)
+            .unwrap_or_default(),
+        to: format_addresses(message.to()),
+        cc: format_addresses(message.cc()),
+        subject: message.subject().unwrap_or_default().to_string(),
+        date: message.date().map(|date| date.to_rfc3339()),
+        message_id: message.message_id().map(|id| format!("<{}>", id)),
+        body,
+        truncated,
+        attachments: message
+            .attachments()
+            .filter_map(|part| part.attachment_name())
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RAW: &str = "From: Alice Martin <alice@example.com>\r\n\
+        To: me@example.com, Bob <bob@example.com>\r\n\
+        Subject: Quarterly report\r\n\
+        Date: Mon, 6 Oct 2025 09:30:00 +0200\r\n\
+        Message-ID: <abc123@example.com>\r\n\
+        Content-Type: multipart/mixed; boundary=\"sep\"\r\n\
+        \r\n\
+        --sep\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        \r\n\
+        Hello,\r\n\r\nthe report is attached.\r\n\
+        --sep\r\n\
+        Content-Type: application/pdf\r\n\
+        Content-Disposition: attachment; filename=\"report.pdf\"\r\n\
+        \r\n\
+        JVBERi0=\r\n\
+        --sep--\r\n";
+
+    #[test]
+    fn test_summarize() {
+        let summary = summarize(42, false, RAW.as_bytes());
+        assert_eq!(summary.uid, 42);
+        assert_eq!(summary.from, "Alice Martin <alice@example.com>");
+        assert_eq!(summary.subject, "Quarterly report");
+        assert_eq!(summary.preview, "Hello, the report is attached.");
+        assert!(summary.date.unwrap().starts_with("2025-10-06T09:30:00"));
+    }
+
+    #[test]
+    fn test_parse_message() {
+        let message = parse_message(42, RAW.as_bytes()).unwrap();
+        assert_eq!(message.to, vec!["me@example.com", "Bob <bob@example.com>"]);
+        assert!(message.cc.is_empty());
+        assert_eq!(message.message_id.as_deref(), Some("<abc123@example.com>"));
+        assert!(message.body.contains("the report is attached"));
+        assert!(!message.truncated);
+        assert_eq!(message.attachments, vec!["report.pdf"]);
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Message sending over SMTP.
+//!
+//! Port 587 uses STARTTLS; any other port uses implicit TLS.
+
+use crate::models::email::{EmailAccount, EmailDraft};
+use crate::tools::constants::email as email_const;
+use lettre::message::{header::ContentType, Mailbox, Message};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::time::Duration;
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, String> {
+    address
+        .trim()
+        .parse::<Mailbox>()
+        .map_err(|e| format!("Invalid address '{}': {}", address, e))
+}
+
+/// Builds the message of a draft, sent from the account address.
+pub fn build_message(account: &EmailAccount, draft: &EmailDraft) -> Result<Message, String> {
+    let from = Mailbox::new(
+        account.display_name.clone(),
+        account
+            .address
+            .parse()
+            .map_err(|e| format!("Invalid account address '{}': {}", account.address, e))?,
+    );
+    let mut builder = Message::builder().from(from).subject(&draft.subject);
+    for to in &draft.to {
+        builder = builder.to(parse_mailbox(to)?);
+    }
+    for cc in &draft.cc {
+        builder = builder.cc(parse_mailbox(cc)?);
+    }
+    if let Some(message_id) = &draft.in_reply_to {
+        builder = builder
+            .in_reply_to(message_id.clone())
+            .references(message_id.clone());
+    }
+    builder
+        .header(ContentType::TEXT_PLAIN)
+        .body(draft.body.clone())
+        .map_err(|e| format!("Failed to build the message: {}", e))
+}
+
+fn transport(
+    account: &EmailAccount,
+    password: &str,
+) -> Result<AsyncSmtpTransport<Tokio1Executor>, String> {
+    let builder = if account.smtp_port == email_const::STARTTLS_SMTP_PORT {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&account.smtp_host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&account.smtp_host)
+    }
+    .map_err(|e| format!("Invalid SMTP host '{}': {}", account.smtp_host, e))?;
+
+    Ok(builder
+        .port(account.smtp_port)
+        .credentials(Credentials::new(
+            account.username.clone(),
+            password.to_string(),
+        ))
+        .timeout(Some(Duration::from_secs(email_const::TIMEOUT_SECS)))
+        .build())
+}
+
+/// Sends a message.
+pub async fn send(account: &EmailAccount, password: &str, message: Message) -> Result<(), String> {
+    transport(account, password)?
+        .send(message)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Failed to send the message: {}", e))
+}
+
+/// Checks that the account can connect to its SMTP server.
+pub async fn test_connection(account: &EmailAccount, password: &str) -> Result<(), String> {
+    let connected = transport(account, password)?
+        .test_connection()
+        .await
+        .map_err(|e| format!("SMTP connection failed: {}", e))?;
+    if connected {
+        Ok(())
+    } else {
+        Err("The SMTP server refused the connection".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account() -> EmailAccount {
+        EmailAccount {
+            name: "work".to_string(),
+            address: "me@example.com".to_string(),
+            display_name: Some("Me".to_string()),
+            imap_host: "imap.example.com".to_string(),
+            imap_port: 993,
+            smtp_host: "smtp.example.com".to_string(),
+            smtp_port: 465,
+            username: "me@example.com".to_string(),
+            read_only: false,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_build_message() {
+        let draft = EmailDraft {
+            to: vec!["Alice <alice@example.com>".to_string()],
+            cc: vec!["bob@example.com".to_string()],
+            subject: "Re: Quarterly report".to_string(),
+            body: "Thanks, received.".to_string(),
+            in_reply_to: Some("<abc123@example.com>".to_string()),
+        };
+        let formatted =
+            String::from_utf8(build_message(&account(), &draft).unwrap().formatted()).unwrap();
+        assert!(formatted.contains("From: Me <me@example.com>"));
+        assert!(formatted.contains("To: Alice <alice@example.com>"));
+        assert!(formatted.contains("Cc: bob@example.com"));
+        assert!(formatted.contains("In-Reply-To: <abc123@example.com>"));
+        assert!(formatted.contains("Thanks, received."));
+
+        let invalid = EmailDraft {
+            to: vec!["not an address".to_string()],
+            ..draft
+        };
+        assert!(build_message(&account(), &invalid).is_err());
+    }
+}
@@ -0,0 +1,577 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EmailTool implementation.
+//!
+//! Reading never modifies the mailbox; sending requires an account that is
+//! not read-only and always waits for the user's confirmation of the draft.
+
+use super::config::{list_accounts, load_account, password_key};
+use super::{imap, smtp};
+use crate::commands::SecureKeyStore;
+use crate::db::DBClient;
+use crate::models::email::{EmailAccount, EmailDraft};
+use crate::tools::constants::email as email_const;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{validate_enum_value, validate_length, validate_not_empty};
+use crate::tools::validation_helper::ValidationHelper;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tracing::{info, instrument};
+
+/// Tool reading mailboxes (IMAP) and sending confirmed drafts (SMTP).
+pub struct EmailTool {
+    /// Database client (email accounts, validations)
+    db: Arc<DBClient>,
+    /// Secure storage of the account passwords
+    keystore: Option<SecureKeyStore>,
+    /// Current workflow ID (scope)
+    workflow_id: String,
+    /// Agent ID using this tool
+    agent_id: String,
+    /// Tauri app handle for validation events
+    app_handle: Option<AppHandle>,
+}
+
+impl EmailTool {
+    /// Creates a new EmailTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `keystore` - Secure storage of the account passwords
+    /// * `workflow_id` - Workflow ID
+    /// * `agent_id` - Agent ID using the tool
+    /// * `app_handle` - Tauri app handle for validation events
+    pub fn new(
+        db: Arc<DBClient>,
+        keystore: Option<SecureKeyStore>,
+        workflow_id: String,
+        agent_id: String,
+        app_handle: Option<AppHandle>,
+    ) -> Self {
+        Self {
+            db,
+            keystore,
+            workflow_id,
+            agent_id,
+            app_handle,
+        }
+    }
+
+    /// Lists the configured accounts.
+    async fn list(&self) -> ToolResult<Value> {
+        let accounts: Vec<Value> = list_accounts(&self.db)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .into_iter()
+            .map(|account| {
+                json!({
+                    "name": account.name,
+                    "address": account.address,
+                    "read_only": account.read_only,
+                })
+            })
+            .collect();
+        let count = accounts.len();
+        Ok(ResponseBuilder::list(accounts, count))
+    }
+
+    /// Lists the latest messages of a mailbox.
+    #[instrument(skip(self), fields(agent_id = %self.agent_id))]
+    async fn read_inbox(
+        &self,
+        account: &str,
+        mailbox: &str,
+        limit: usize,
+        unread_only: bool,
+    ) -> ToolResult<Value> {
+        let account = self.require_account(account).await?;
+        let password = self.password(&account)?;
+        let messages = imap::list_messages(&account, &password, mailbox, limit, unread_only)
+            .await
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(
+            account = %account.name,
+            mailbox = %mailbox,
+            count = messages.len(),
+            "Mailbox read"
+        );
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("account", account.name)
+            .field("mailbox", mailbox)
+            .data("messages", &messages)
+            .count(messages.len())
+            .build())
+    }
+
+    /// Reads a complete message.
+    #[instrument(skip(self), fields(agent_id = %self.agent_id))]
+    async fn read_message(&self, account: &str, mailbox: &str, uid: u32) -> ToolResult<Value> {
+        let account = self.require_account(account).await?;
+        let password = self.password(&account)?;
+        let message = imap::fetch_message(&account, &password, mailbox, uid)
+            .await
+            .map_err(ToolError::ExecutionFailed)?
+            .ok_or_else(|| {
+                ToolError::NotFound(format!("Message {} not found in '{}'", uid, mailbox))
+            })?;
+
+        info!(account = %account.name, uid = uid, "Message read");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("account", account.name)
+            .data("message", &message)
+            .build())
+    }
+
+    /// Sends a draft once the user confirms it.
+    #[instrument(skip(self, draft), fields(agent_id = %self.agent_id))]
+    async fn send_draft(&self, account: &str, draft: EmailDraft) -> ToolResult<Value> {
+        let account = self.require_account(account).await?;
+        if account.read_only {
+            return Err(ToolError::PermissionDenied(format!(
+                "Account '{}' is read-only: the user must allow sending in its settings",
+                account.name
+            )));
+        }
+        let password = self.password(&account)?;
+        let message = smtp::build_message(&account, &draft).map_err(ToolError::InvalidInput)?;
+
+        ValidationHelper::new(self.db.clone(), self.app_handle.clone())
+            .request_email_send_validation(&self.workflow_id, &account, &draft)
+            .await?;
+
+        smtp::send(&account, &password, message)
+            .await
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(
+            account = %account.name,
+            recipients = draft.to.len() + draft.cc.len(),
+            "Email sent"
+        );
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("account", account.name)
+            .data("to", &draft.to)
+            .message("Email sent")
+            .build())
+    }
+
+    async fn require_account(&self, name: &str) -> ToolResult<EmailAccount> {
+        load_account(&self.db, name)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .ok_or_else(|| {
+                ToolError::NotFound(format!(
+                    "Email account '{}' is not configured (use 'list_accounts')",
+                    name
+                ))
+            })
+    }
+
+    fn password(&self, account: &EmailAccount) -> ToolResult<String> {
+        self.keystore
+            .as_ref()
+            .and_then(|keystore| keystore.get_key(&password_key(&account.name)))
+            .ok_or_else(|| {
+                ToolError::DependencyError(format!(
+                    "No password stored for email account '{}'",
+                    account.name
+                ))
+            })
+    }
+
+    /// Creates the tool with the keystore holding the account passwords.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let wf_id = ctx.workflow_id_or_default();
+            let keystore = ctx.keystore();
+            let tool = EmailTool::new(
+                ctx.factory.get_db(),
+                keystore,
+                wf_id,
+                ctx.agent_id,
+                ctx.app_handle,
+            );
+            info!("EmailTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Sends wait for the user's confirmation: no default timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "EmailTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: None,
+        constructor: EmailTool::build,
+    })
+}
+
+/// Reads a list of addresses (`to`, `cc`).
+fn parse_recipients(input: &Value, field: &str) -> ToolResult<Vec<String>> {
+    match input.get(field) {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| {
+                value
+                    .as_str()
+                    .map(|address| address.trim().to_string())
+                    .filter(|address| !address.is_empty())
+                    .ok_or_else(|| {
+                        ToolError::InvalidInput(format!("'{}' must contain addresses", field))
+                    })
+            })
+            .collect(),
+        Some(_) => Err(ToolError::InvalidInput(format!(
+            "'{}' must be an array of addresses",
+            field
+        ))),
+    }
+}
+
+/// Reads the draft of a send_draft operation.
+fn parse_draft(input: &Value) -> ToolResult<EmailDraft> {
+    Ok(EmailDraft {
+        to: parse_recipients(input, "to")?,
+        cc: parse_recipients(input, "cc")?,
+        subject: input["subject"].as_str().unwrap_or_default().to_string(),
+        body: input["body"].as_str().unwrap_or_default().to_string(),
+        in_reply_to: input["in_reply_to"]
+            .as_str()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string),
+    })
+}
+
+#[async_trait]
+impl Tool for EmailTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "EmailTool".to_string(),
+            name: "Email Tool".to_string(),
+            description: format!(
+                r#"Reads the user's mailboxes and sends emails from accounts they configured.
+
+USE THIS TOOL WHEN:
+- The user asks about their emails (new messages, a message from someone)
+- The user asks you to answer or write an email
+
+OPERATIONS:
+- list_accounts: List the configured accounts (name, address, read_only)
+- read_inbox: List the latest messages of a mailbox, newest first (uid, from, subject, date, seen, preview)
+- read_message: Read one message by "uid" (body, recipients, message_id, attachment names)
+- send_draft: Send an email; the user ALWAYS reviews and confirms the draft before it is sent
+
+IMPORTANT CONSTRAINTS:
+- Reading never marks messages as read
+- read_inbox returns at most {} messages ("limit", default {}); "mailbox" defaults to INBOX
+- Message bodies are truncated to {} characters
+- send_draft is plain text, at most {} recipients; read-only accounts cannot send
+- To answer a message, pass its "message_id" as "in_reply_to" and prefix the subject with "Re: "
+- Write the draft in the language of the conversation and never invent recipients
+
+EXAMPLES:
+1. {{"operation": "read_inbox", "account": "work", "unread_only": true, "limit": 5}}
+2. {{"operation": "read_message", "account": "work", "uid": 4812}}
+3. {{"operation": "send_draft", "account": "work", "to": ["alice@example.com"], "subject": "Re: Quarterly report", "body": "Hello Alice,\n\nThanks for the report...", "in_reply_to": "<abc123@example.com>"}}"#,
+                email_const::MAX_LIST_LIMIT,
+                email_const::DEFAULT_LIST_LIMIT,
+                email_const::MAX_BODY_CHARS,
+                email_const::MAX_RECIPIENTS
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": email_const::VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "account": {
+                        "type": "string",
+                        "description": "Account name (from list_accounts)"
+                    },
+                    "mailbox": {
+                        "type": "string",
+                        "description": "Mailbox to read (default: INBOX)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": email_const::MAX_LIST_LIMIT,
+                        "description": "Messages to list (read_inbox)"
+                    },
+                    "unread_only": {
+                        "type": "boolean",
+                        "description": "List unread messages only (read_inbox)"
+                    },
+                    "uid": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Message UID from read_inbox (read_message)"
+                    },
+                    "to": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Recipients (send_draft)"
+                    },
+                    "cc": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Copy recipients (send_draft)"
+                    },
+                    "subject": {
+                        "type": "string",
+                        "description": "Subject (send_draft)"
+                    },
+                    "body": {
+                        "type": "string",
+                        "description": "Plain text body (send_draft)"
+                    },
+                    "in_reply_to": {
+                        "type": "string",
+                        "description": "message_id of the answered message (send_draft)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "items": {"type": "array"},
+                    "messages": {"type": "array"},
+                    "message": {},
+                    "count": {"type": "integer"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        let operation = input["operation"].as_str().unwrap_or_default();
+
+        if operation == "list_accounts" {
+            return self.list().await;
+        }
+
+        let account = input["account"].as_str().unwrap_or_default();
+        let mailbox = input["mailbox"]
+            .as_str()
+            .map(str::trim)
+            .filter(|mailbox| !mailbox.is_empty())
+            .unwrap_or(email_const::DEFAULT_MAILBOX);
+        match operation {
+            "read_inbox" => {
+                let limit = input["limit"]
+                    .as_u64()
+                    .map_or(email_const::DEFAULT_LIST_LIMIT, |limit| limit as usize);
+                let unread_only = input["unread_only"].as_bool().unwrap_or(false);
+                self.read_inbox(account, mailbox, limit, unread_only).await
+            }
+            "read_message" => {
+                let uid = input["uid"].as_u64().unwrap_or_default() as u32;
+                self.read_message(account, mailbox, uid).await
+            }
+            _ => self.send_draft(account, parse_draft(&input)?).await,
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation field".to_string()))?;
+        validate_enum_value(operation, email_const::VALID_OPERATIONS, "operation")?;
+        if operation == "list_accounts" {
+            return Ok(());
+        }
+
+        let account = input["account"].as_str().ok_or_else(|| {
+            ToolError::InvalidInput(format!("Missing 'account' for {} operation", operation))
+        })?;
+        validate_not_empty(account, "account")?;
+
+        match operation {
+            "read_inbox" => {
+                if let Some(limit) = input.get("limit").filter(|limit| !limit.is_null()) {
+                    if limit.as_u64().is_none_or(|limit| {
+                        limit == 0 || limit as usize > email_const::MAX_LIST_LIMIT
+                    }) {
+                        return Err(ToolError::InvalidInput(format!(
+                            "'limit' must be 1-{}",
+                            email_const::MAX_LIST_LIMIT
+                        )));
+                    }
+                }
+            }
+            "read_message" => {
+                if input["uid"]
+                    .as_u64()
+                    .is_none_or(|uid| uid == 0 || uid > u64::from(u32::MAX))
+                {
+                    return Err(ToolError::InvalidInput(
+                        "Missing or invalid 'uid' for read_message operation".to_string(),
+                    ));
+                }
+            }
+            _ => {
+                let draft = parse_draft(input)?;
+                if draft.to.is_empty() {
+                    return Err(ToolError::InvalidInput(
+                        "A draft needs at least one recipient in 'to'".to_string(),
+                    ));
+                }
+                if draft.to.len() + draft.cc.len() > email_const::MAX_RECIPIENTS {
+                    return Err(ToolError::InvalidInput(format!(
+                        "At most {} recipients are allowed",
+                        email_const::MAX_RECIPIENTS
+                    )));
+                }
+                validate_not_empty(&draft.subject, "subject")?;
+                validate_length(&draft.subject, email_const::MAX_SUBJECT_LENGTH, "subject")?;
+                validate_not_empty(&draft.body, "body")?;
+                validate_length(&draft.body, email_const::MAX_BODY_LENGTH, "body")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::email::config::save_account;
+
+    async fn create_test_tool() -> (EmailTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let tool = EmailTool::new(
+            Arc::new(db),
+            None,
+            "wf_test".to_string(),
+            "test_agent".to_string(),
+            None,
+        );
+        (tool, dir)
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = create_test_tool().await;
+        assert!(tool
+            .validate_input(&json!({"operation": "list_accounts"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "read_inbox", "account": "work", "limit": 5}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "read_inbox", "account": "work", "limit": 500}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "read_message", "account": "work"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "read_inbox"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({
+                "operation": "send_draft", "account": "work",
+                "to": ["a@example.com"], "subject": "Hi", "body": "Hello"
+            }))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({
+                "operation": "send_draft", "account": "work",
+                "to": [], "subject": "Hi", "body": "Hello"
+            }))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({
+                "operation": "send_draft", "account": "work",
+                "to": "a@example.com", "subject": "Hi", "body": "Hello"
+            }))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "delete"}))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_rejected_on_read_only_account() {
+        let (tool, _dir) = create_test_tool().await;
+        let account = EmailAccount {
+            name: "work".to_string(),
+            address: "me@example.com".to_string(),
+            display_name: None,
+            imap_host: "imap.example.com".to_string(),
+            imap_port: 993,
+            smtp_host: "smtp.example.com".to_string(),
+            smtp_port: 465,
+            username: "me@example.com".to_string(),
+            read_only: true,
+            created_at: None,
+        };
+        save_account(&tool.db, &account).await.unwrap();
+
+        let listed = tool
+            .execute(json!({"operation": "list_accounts"}))
+            .await
+            .unwrap();
+        assert_eq!(listed["count"], 1);
+        assert_eq!(listed["items"][0]["read_only"], true);
+
+        let result = tool
+            .execute(json!({
+                "operation": "send_draft", "account": "work",
+                "to": ["a@example.com"], "subject": "Hi", "body": "Hello"
+            }))
+            .await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+
+        let missing = tool
+            .execute(json!({"operation": "read_inbox", "account": "other"}))
+            .await;
+        assert!(matches!(missing, Err(ToolError::NotFound(_))));
+
+        // No keystore: the password is missing
+        let no_password = tool
+            .execute(json!({"operation": "read_inbox", "account": "work"}))
+            .await;
+        assert!(matches!(no_password, Err(ToolError::DependencyError(_))));
+    }
+}
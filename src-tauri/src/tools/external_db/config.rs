@@ -0,0 +1,176 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External database configuration.
+//!
+//! Metadata lives in the `external_database` table; each connection string
+//! is stored in SecureKeyStore under [`connection_key`].
+
+use crate::db::DBClient;
+use crate::models::external_db::ExternalDatabase;
+use crate::tools::constants::external_db as sql_const;
+use serde_json::json;
+use tracing::warn;
+
+/// Fields selected for an external database
+const DATABASE_SELECT_FIELDS: &str = "name, kind, description, read_only, max_rows, \
+     time::format(created_at, '%Y-%m-%dT%H:%M:%SZ') AS created_at";
+
+/// SecureKeyStore entry of the connection string of a database
+pub fn connection_key(name: &str) -> String {
+    format!("external_db_{}", name)
+}
+
+/// Validates a database name (lowercase letters, digits, `-` and `_`).
+pub fn validate_database_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > sql_const::MAX_NAME_LENGTH {
+        return Err(format!(
+            "Database name must be 1-{} characters",
+            sql_const::MAX_NAME_LENGTH
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(
+            "Database name must contain only lowercase letters, numbers, '-' and '_'".to_string(),
+        );
+    }
+    Ok(name.to_string())
+}
+
+/// Lists the configured external databases, sorted by name.
+pub async fn list_databases(db: &DBClient) -> Result<Vec<ExternalDatabase>, String> {
+    let rows = db
+        .query_json(&format!(
+            "SELECT {} FROM external_database ORDER BY name",
+            DATABASE_SELECT_FIELDS
+        ))
+        .await
+        .map_err(|e| format!("Failed to list external databases: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let name = row["name"].as_str().unwrap_or_default().to_string();
+            match serde_json::from_value(row) {
+                Ok(database) => Some(database),
+                Err(e) => {
+                    warn!(name = %name, error = %e, "Skipping malformed external database record");
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Loads an external database by name.
+pub async fn load_database(db: &DBClient, name: &str) -> Result<Option<ExternalDatabase>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM external_database WHERE name = $name",
+                DATABASE_SELECT_FIELDS
+            ),
+            vec![("name".to_string(), json!(name))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load external database: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .next()
+        .and_then(|row| match serde_json::from_value(row) {
+            Ok(database) => Some(database),
+            Err(e) => {
+                warn!(name = %name, error = %e, "Skipping malformed external database record");
+                None
+            }
+        }))
+}
+
+/// Creates or updates an external database.
+pub async fn save_database(db: &DBClient, database: &ExternalDatabase) -> Result<(), String> {
+    db.execute_with_params(
+        "UPSERT type::thing('external_database', $name) SET name = $name, kind = $kind, \
+         description = $description, read_only = $read_only, max_rows = $max_rows, \
+         updated_at = time::now()",
+        vec![
+            ("name".to_string(), json!(database.name)),
+            ("kind".to_string(), json!(database.kind)),
+            ("description".to_string(), json!(database.description)),
+            ("read_only".to_string(), json!(database.read_only)),
+            ("max_rows".to_string(), json!(database.max_rows)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to save external database: {}", e))
+}
+
+/// Deletes an external database.
+pub async fn delete_database(db: &DBClient, name: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "DELETE type::thing('external_database', $name)",
+        vec![("name".to_string(), json!(name))],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete external database: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::external_db::ExternalDbKind;
+
+    #[test]
+    fn test_validate_database_name() {
+        assert_eq!(
+            validate_database_name(" crm_prod "),
+            Ok("crm_prod".to_string())
+        );
+        assert!(validate_database_name("").is_err());
+        assert!(validate_database_name("CRM").is_err());
+        assert!(validate_database_name("crm prod").is_err());
+        assert!(validate_database_name(&"a".repeat(65)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_save_load_delete_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let mut database = ExternalDatabase {
+            name: "crm".to_string(),
+            kind: ExternalDbKind::Postgres,
+            description: Some("Customer records".to_string()),
+            read_only: false,
+            max_rows: 100,
+            created_at: None,
+        };
+        save_database(&db, &database).await.unwrap();
+        database.read_only = true;
+        database.description = None;
+        save_database(&db, &database).await.unwrap();
+
+        let loaded = load_database(&db, "crm").await.unwrap().unwrap();
+        assert!(loaded.read_only);
+        assert_eq!(loaded.description, None);
+        assert!(loaded.created_at.is_some());
+        assert_eq!(list_databases(&db).await.unwrap().len(), 1);
+
+        delete_database(&db, "crm").await.unwrap();
+        assert!(load_database(&db, "crm").await.unwrap().is_none());
+    }
+}
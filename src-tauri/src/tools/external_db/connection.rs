@@ -0,0 +1,429 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statement execution on external databases.
+//!
+//! Each call opens its own connection, runs one statement and closes it.
+//! Read statements run inside a read-only transaction (`PRAGMA query_only`
+//! on SQLite), so a statement misclassified as a read cannot modify data.
+
+use crate::models::external_db::{ExternalDbKind, SqlQueryResult};
+use crate::tools::constants::external_db as sql_const;
+use crate::tools::validation_helper::safe_truncate;
+use futures::TryStreamExt;
+use serde_json::{json, Value};
+use sqlx::mysql::{MySqlConnection, MySqlRow};
+use sqlx::postgres::{PgConnection, PgRow};
+use sqlx::sqlite::{SqliteConnection, SqliteRow};
+use sqlx::{Column, Connection, Decode, Executor, Row, Type, TypeInfo};
+use std::future::Future;
+use std::time::Duration;
+
+/// Binds JSON parameters to a query, in order.
+macro_rules! bind_params {
+    ($query:expr, $params:expr) => {{
+        let mut query = $query;
+        for param in $params {
+            query = match param {
+                Value::Null => query.bind(None::<String>),
+                Value::Bool(value) => query.bind(*value),
+                Value::Number(number) => match number.as_i64() {
+                    Some(value) => query.bind(value),
+                    None => query.bind(number.as_f64()),
+                },
+                Value::String(value) => query.bind(value.clone()),
+                other => query.bind(other.to_string()),
+            };
+        }
+        query
+    }};
+}
+
+/// Fetches at most `max_rows` rows of a read statement and converts them.
+macro_rules! fetch_rows {
+    ($conn:expr, $sql:expr, $params:expr, $max_rows:expr, $convert:expr) => {{
+        let mut stream = Executor::fetch(&mut $conn, bind_params!(sqlx::query($sql), $params));
+        let mut rows = Vec::new();
+        let mut truncated = false;
+        while let Some(row) = stream.try_next().await.map_err(query_error)? {
+            if rows.len() == $max_rows {
+                truncated = true;
+                break;
+            }
+            rows.push(row);
+        }
+        drop(stream);
+        to_result(&rows, truncated, $convert)
+    }};
+}
+
+/// Runs a statement without parameters (simple query protocol).
+macro_rules! raw {
+    ($conn:expr, $sql:expr) => {
+        Executor::execute(&mut $conn, sqlx::raw_sql($sql))
+            .await
+            .map_err(query_error)
+    };
+}
+
+/// Converts the first compatible decoding of a column to JSON.
+macro_rules! decode_as {
+    ($row:expr, $index:expr, $($ty:ty => $convert:expr),+ $(,)?) => {
+        $(
+            if let Some(value) = try_decode::<_, $ty>($row, $index) {
+                return value.map($convert).unwrap_or(Value::Null);
+            }
+        )+
+    };
+}
+
+/// Runs a read statement and returns at most `max_rows` rows.
+pub async fn run_query(
+    kind: ExternalDbKind,
+    connection_string: &str,
+    sql: &str,
+    params: &[Value],
+    max_rows: usize,
+) -> Result<SqlQueryResult, String> {
+    with_timeout(async {
+        match kind {
+            ExternalDbKind::Postgres => {
+                let mut conn = connect::<PgConnection>(connection_string).await?;
+                raw!(conn, "START TRANSACTION READ ONLY")?;
+                let result = fetch_rows!(conn, sql, params, max_rows, pg_value);
+                raw!(conn, "ROLLBACK")?;
+                close(conn).await;
+                Ok(result)
+            }
+            ExternalDbKind::Mysql => {
+                let mut conn = connect::<MySqlConnection>(connection_string).await?;
+                raw!(conn, "START TRANSACTION READ ONLY")?;
+                let result = fetch_rows!(conn, sql, params, max_rows, mysql_value);
+                raw!(conn, "ROLLBACK")?;
+                close(conn).await;
+                Ok(result)
+            }
+            ExternalDbKind::Sqlite => {
+                let mut conn = connect::<SqliteConnection>(connection_string).await?;
+                raw!(conn, "PRAGMA query_only = ON")?;
+                let result = fetch_rows!(conn, sql, params, max_rows, sqlite_value);
+                close(conn).await;
+                Ok(result)
+            }
+        }
+    })
+    .await
+}
+
+/// Runs a write statement and returns the number of rows affected.
+pub async fn run_statement(
+    kind: ExternalDbKind,
+    connection_string: &str,
+    sql: &str,
+    params: &[Value],
+) -> Result<u64, String> {
+    with_timeout(async {
+        match kind {
+            ExternalDbKind::Postgres => {
+                let mut conn = connect::<PgConnection>(connection_string).await?;
+                let result = Executor::execute(&mut conn, bind_params!(sqlx::query(sql), params))
+                    .await
+                    .map_err(query_error)?;
+                close(conn).await;
+                Ok(result.rows_affected())
+            }
+            ExternalDbKind::Mysql => {
+                let mut conn = connect::<MySqlConnection>(connection_string).await?;
+                let result = Executor::execute(&mut conn, bind_params!(sqlx::query(sql), params))
+                    .await
+                    .map_err(query_error)?;
+                close(conn).await;
+                Ok(result.rows_affected())
+            }
+            ExternalDbKind::Sqlite => {
+                let mut conn = connect::<SqliteConnection>(connection_string).await?;
+                let result = Executor::execute(&mut conn, bind_params!(sqlx::query(sql), params))
+                    .await
+                    .map_err(query_error)?;
+                close(conn).await;
+                Ok(result.rows_affected())
+            }
+        }
+    })
+    .await
+}
+
+/// Checks that a database accepts connections.
+pub async fn test_connection(kind: ExternalDbKind, connection_string: &str) -> Result<(), String> {
+    run_query(kind, connection_string, "SELECT 1", &[], 1)
+        .await
+        .map(|_| ())
+}
+
+/// Bounds a whole statement (connection included) by the statement timeout.
+async fn with_timeout<T>(operation: impl Future<Output = Result<T, String>>) -> Result<T, String> {
+    tokio::time::timeout(
+        Duration::from_secs(sql_const::STATEMENT_TIMEOUT_SECS),
+        operation,
+    )
+    .await
+    .map_err(|_| {
+        format!(
+            "Statement timed out after {} seconds",
+            sql_const::STATEMENT_TIMEOUT_SECS
+        )
+    })?
+}
+
+/// Opens a connection, bounded by the connection timeout.
+async fn connect<C: Connection>(connection_string: &str) -> Result<C, String> {
+    tokio::time::timeout(
+        Duration::from_secs(sql_const::CONNECT_TIMEOUT_SECS),
+        C::connect(connection_string),
+    )
+    .await
+    .map_err(|_| {
+        format!(
+            "Connection timed out after {} seconds",
+            sql_const::CONNECT_TIMEOUT_SECS
+        )
+    })?
+    // The connection string may hold a password: never echo it
+    .map_err(|e| format!("Failed to connect: {}", connection_error(&e)))
+}
+
+/// Closes a connection; a failure only loses the graceful shutdown.
+async fn close<C: Connection>(conn: C) {
+    if let Err(e) = conn.close().await {
+        tracing::debug!(error = %e, "Failed to close external database connection");
+    }
+}
+
+fn query_error(e: sqlx::Error) -> String {
+    format!("Statement failed: {}", e)
+}
+
+/// Describes a connection error without the connection options.
+fn connection_error(e: &sqlx::Error) -> String {
+    match e {
+        sqlx::Error::Configuration(_) => "invalid connection string".to_string(),
+        sqlx::Error::Database(db_error) => db_error.message().to_string(),
+        sqlx::Error::Io(io_error) => io_error.to_string(),
+        sqlx::Error::Tls(_) => "TLS negotiation failed".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Builds the result of fetched rows.
+fn to_result<R: Row>(
+    rows: &[R],
+    truncated: bool,
+    convert: fn(&R, usize) -> Value,
+) -> SqlQueryResult {
+    let columns = rows
+        .first()
+        .map(|row| {
+            row.columns()
+                .iter()
+                .map(|column| column.name().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    let rows: Vec<Vec<Value>> = rows
+        .iter()
+        .map(|row| (0..row.len()).map(|i| convert(row, i)).collect())
+        .collect();
+    SqlQueryResult {
+        columns,
+        row_count: rows.len(),
+        rows,
+        truncated,
+    }
+}
+
+/// Decodes a column as `T`; None when `T` is not compatible with its type.
+fn try_decode<'r, R, T>(row: &'r R, index: usize) -> Option<Option<T>>
+where
+    R: Row,
+    T: Decode<'r, R::Database> + Type<R::Database>,
+    usize: sqlx::ColumnIndex<R>,
+{
+    row.try_get::<Option<T>, _>(index).ok()
+}
+
+fn text(value: String) -> Value {
+    Value::String(safe_truncate(&value, sql_const::MAX_CELL_CHARS, true))
+}
+
+fn bytes(value: Vec<u8>) -> Value {
+    Value::String(format!("<{} bytes>", value.len()))
+}
+
+/// Placeholder of a value whose type cannot be converted
+fn unsupported<R: Row>(row: &R, index: usize) -> Value {
+    let type_name = row.columns()[index].type_info().name().to_string();
+    Value::String(format!("<unsupported type {}>", type_name))
+}
+
+fn pg_value(row: &PgRow, index: usize) -> Value {
+    decode_as!(row, index,
+        bool => Value::Bool,
+        i16 => |v| json!(v),
+        i32 => |v| json!(v),
+        i64 => |v| json!(v),
+        f32 => |v| json!(v),
+        f64 => |v| json!(v),
+        String => text,
+        chrono::DateTime<chrono::Utc> => |v| json!(v.to_rfc3339()),
+        chrono::NaiveDateTime => |v| json!(v.to_string()),
+        chrono::NaiveDate => |v| json!(v.to_string()),
+        chrono::NaiveTime => |v| json!(v.to_string()),
+        uuid::Uuid => |v| json!(v.to_string()),
+        Value => |v| v,
+        Vec<u8> => bytes,
+    );
+    unsupported(row, index)
+}
+
+fn mysql_value(row: &MySqlRow, index: usize) -> Value {
+    decode_as!(row, index,
+        i64 => |v| json!(v),
+        u64 => |v| json!(v),
+        f64 => |v| json!(v),
+        f32 => |v| json!(v),
+        String => text,
+        chrono::DateTime<chrono::Utc> => |v| json!(v.to_rfc3339()),
+        chrono::NaiveDateTime => |v| json!(v.to_string()),
+        chrono::NaiveDate => |v| json!(v.to_string()),
+        chrono::NaiveTime => |v| json!(v.to_string()),
+        Value => |v| v,
+        Vec<u8> => bytes,
+    );
+    unsupported(row, index)
+}
+
+fn sqlite_value(row: &SqliteRow, index: usize) -> Value {
+    decode_as!(row, index,
+        i64 => |v| json!(v),
+        f64 => |v| json!(v),
+        String => text,
+        bool => Value::Bool,
+        Vec<u8> => bytes,
+    );
+    unsupported(row, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn sqlite_db() -> (String, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            dir.path().join("external.db").display()
+        );
+        run_statement(
+            ExternalDbKind::Sqlite,
+            &url,
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, score REAL, avatar BLOB)",
+            &[],
+        )
+        .await
+        .unwrap();
+        (url, dir)
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_query_and_statement() {
+        let (url, _dir) = sqlite_db().await;
+        for (name, score) in [("ada", 9.5), ("linus", 7.0), ("grace", 8.25)] {
+            let affected = run_statement(
+                ExternalDbKind::Sqlite,
+                &url,
+                "INSERT INTO users (name, score) VALUES (?, ?)",
+                &[json!(name), json!(score)],
+            )
+            .await
+            .unwrap();
+            assert_eq!(affected, 1);
+        }
+
+        let result = run_query(
+            ExternalDbKind::Sqlite,
+            &url,
+            "SELECT id, name, score, avatar FROM users WHERE score > ? ORDER BY id",
+            &[json!(7.5)],
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.columns, vec!["id", "name", "score", "avatar"]);
+        assert_eq!(result.row_count, 2);
+        assert!(!result.truncated);
+        assert_eq!(
+            result.rows[0],
+            vec![json!(1), json!("ada"), json!(9.5), Value::Null]
+        );
+
+        let limited = run_query(
+            ExternalDbKind::Sqlite,
+            &url,
+            "SELECT name FROM users",
+            &[],
+            2,
+        )
+        .await
+        .unwrap();
+        assert_eq!(limited.row_count, 2);
+        assert!(limited.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_query_is_read_only() {
+        let (url, _dir) = sqlite_db().await;
+        let result = run_query(
+            ExternalDbKind::Sqlite,
+            &url,
+            "INSERT INTO users (name) VALUES ('mallory')",
+            &[],
+            10,
+        )
+        .await;
+        assert!(result.is_err());
+
+        let count = run_query(
+            ExternalDbKind::Sqlite,
+            &url,
+            "SELECT count(*) AS total FROM users",
+            &[],
+            10,
+        )
+        .await
+        .unwrap();
+        assert_eq!(count.rows[0], vec![json!(0)]);
+    }
+
+    #[tokio::test]
+    async fn test_connection_error_hides_connection_string() {
+        let error = test_connection(
+            ExternalDbKind::Sqlite,
+            "sqlite:///nonexistent/dir/secret.db",
+        )
+        .await
+        .unwrap_err();
+        assert!(error.starts_with("Failed to connect"));
+        assert!(!error.contains("secret"));
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External Database Tool Module
+//!
+//! Provides the SqlTool, which lets agents query user-configured external
+//! databases (PostgreSQL, MySQL, SQLite):
+//! - Read statements with bound parameters and a per-database row limit
+//! - Write statements, always confirmed by the user
+//!
+//! Connection strings are stored in SecureKeyStore, never in the database.
+
+pub mod config;
+pub mod connection;
+pub mod sql;
+mod tool;
+
+pub use tool::SqlTool;
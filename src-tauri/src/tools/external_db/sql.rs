@@ -0,0 +1,203 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SQL statement classification.
+//!
+//! Splits a statement into keywords, skipping string literals, quoted
+//! identifiers and comments, to tell read statements from writes. The check
+//! is conservative: any statement that is not clearly a read is a write, and
+//! therefore needs the user's confirmation.
+
+/// Keywords a read statement may start with
+const READ_KEYWORDS: &[&str] = &["SELECT", "WITH", "VALUES", "SHOW", "DESCRIBE", "DESC"];
+
+/// Keywords that make a statement a write wherever they appear
+/// (`SELECT ... INTO`, `EXPLAIN ANALYZE`, data-modifying CTEs, ...)
+const WRITE_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "MERGE", "UPSERT", "REPLACE", "CREATE", "ALTER", "DROP",
+    "TRUNCATE", "RENAME", "GRANT", "REVOKE", "COPY", "CALL", "EXEC", "EXECUTE", "DO", "LOCK",
+    "VACUUM", "ANALYZE", "ATTACH", "DETACH", "PRAGMA", "SET", "RESET", "LOAD", "INTO", "REINDEX",
+    "CLUSTER", "COMMENT", "HANDLER", "PREPARE",
+];
+
+/// Kind of a SQL statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind {
+    /// Reads data only
+    Read,
+    /// May modify data or the schema
+    Write,
+}
+
+/// Classifies a single SQL statement.
+///
+/// # Errors
+/// The statement is empty, is not terminated (open literal or comment), or
+/// contains several statements.
+pub fn classify_statement(sql: &str) -> Result<StatementKind, String> {
+    let keywords = keywords(sql)?;
+    let first = keywords
+        .first()
+        .ok_or_else(|| "SQL statement is empty".to_string())?;
+
+    let is_read = READ_KEYWORDS.contains(&first.as_str())
+        && !keywords
+            .iter()
+            .any(|word| WRITE_KEYWORDS.contains(&word.as_str()));
+    Ok(if is_read {
+        StatementKind::Read
+    } else {
+        StatementKind::Write
+    })
+}
+
+/// Uppercase words of a statement, outside literals, identifiers and comments.
+fn keywords(sql: &str) -> Result<Vec<String>, String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut words = Vec::new();
+    let mut ended = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if ended && !c.is_whitespace() && c != ';' {
+            // Comments may follow the final semicolon
+            if !(c == '-' && next == Some('-') || c == '/' && next == Some('*')) {
+                return Err("Only one SQL statement is allowed per call".to_string());
+            }
+        }
+
+        match c {
+            '\'' | '"' | '`' => i = skip_quoted(&chars, i, c)?,
+            '-' if next == Some('-') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if next == Some('*') => {
+                let end = (i + 2..chars.len().saturating_sub(1))
+                    .find(|&j| chars[j] == '*' && chars[j + 1] == '/')
+                    .ok_or_else(|| "Unterminated comment in SQL statement".to_string())?;
+                i = end + 2;
+            }
+            '$' if dollar_tag(&chars, i).is_some() => {
+                i = skip_dollar_quoted(&chars, i)?;
+            }
+            ';' => {
+                ended = true;
+                i += 1;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                words.push(chars[start..i].iter().collect::<String>().to_uppercase());
+            }
+            _ => i += 1,
+        }
+    }
+    Ok(words)
+}
+
+/// Skips a quoted literal or identifier starting at `start`; a doubled quote
+/// is an escaped quote and a backslash escapes the next character (MySQL).
+fn skip_quoted(chars: &[char], start: usize, quote: char) -> Result<usize, String> {
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == '\\' && quote == '\'' {
+            i += 2;
+        } else if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return Ok(i + 1);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    Err("Unterminated quoted string in SQL statement".to_string())
+}
+
+/// Returns the PostgreSQL dollar-quote tag (`$$` or `$tag$`) starting at `start`.
+fn dollar_tag(chars: &[char], start: usize) -> Option<String> {
+    let mut i = start + 1;
+    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+        if i == start + 1 && chars[i].is_ascii_digit() {
+            // `$1` is a parameter, not a tag
+            return None;
+        }
+        i += 1;
+    }
+    (chars.get(i) == Some(&'$')).then(|| chars[start..=i].iter().collect())
+}
+
+/// Skips a dollar-quoted string starting at `start`.
+fn skip_dollar_quoted(chars: &[char], start: usize) -> Result<usize, String> {
+    let tag: Vec<char> = dollar_tag(chars, start)
+        .unwrap_or_default()
+        .chars()
+        .collect();
+    let body = start + tag.len();
+    (body..chars.len())
+        .find(|&j| chars[j..].starts_with(&tag))
+        .map(|j| j + tag.len())
+        .ok_or_else(|| "Unterminated dollar-quoted string in SQL statement".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_reads() {
+        for sql in [
+            "SELECT * FROM users WHERE id = $1",
+            "  select name from t limit 10;",
+            "WITH recent AS (SELECT * FROM orders) SELECT count(*) FROM recent",
+            "SELECT 'DELETE FROM users' AS text, \"update\" FROM t -- drop table t",
+            "SELECT /* INSERT */ 1; -- trailing comment",
+            "SELECT $$ DROP TABLE t $$",
+            "SHOW TABLES",
+        ] {
+            assert_eq!(classify_statement(sql), Ok(StatementKind::Read), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_classify_writes() {
+        for sql in [
+            "INSERT INTO users (name) VALUES ('a')",
+            "update users set name = 'b'",
+            "WITH gone AS (DELETE FROM users RETURNING *) SELECT * FROM gone",
+            "SELECT * INTO backup FROM users",
+            "EXPLAIN ANALYZE DELETE FROM users",
+            "PRAGMA journal_mode = WAL",
+            "SELECT * FROM users FOR UPDATE",
+        ] {
+            assert_eq!(classify_statement(sql), Ok(StatementKind::Write), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_classify_rejects_invalid_statements() {
+        assert!(classify_statement("   ").is_err());
+        assert!(classify_statement("-- only a comment").is_err());
+        assert!(classify_statement("SELECT 1; DROP TABLE users").is_err());
+        assert!(classify_statement("SELECT 'unterminated").is_err());
+        assert!(classify_statement("SELECT 1 /* open").is_err());
+    }
+}
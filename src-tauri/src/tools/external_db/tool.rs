@@ -0,0 +1,487 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SqlTool implementation.
+//!
+//! Read statements run directly (bounded by the row limit of the database);
+//! write statements always wait for the user's confirmation.
+
+use super::config::{connection_key, list_databases, load_database};
+use super::connection::{run_query, run_statement};
+use super::sql::{classify_statement, StatementKind};
+use crate::commands::SecureKeyStore;
+use crate::db::DBClient;
+use crate::models::external_db::ExternalDatabase;
+use crate::tools::constants::external_db as sql_const;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{validate_enum_value, validate_length, validate_not_empty};
+use crate::tools::validation_helper::ValidationHelper;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tracing::{info, instrument};
+
+/// Tool querying user-configured external databases (PostgreSQL, MySQL, SQLite).
+pub struct SqlTool {
+    /// Database client (external database configuration, validations)
+    db: Arc<DBClient>,
+    /// Secure storage of the connection strings
+    keystore: Option<SecureKeyStore>,
+    /// Current workflow ID (scope)
+    workflow_id: String,
+    /// Agent ID using this tool
+    agent_id: String,
+    /// Tauri app handle for validation events
+    app_handle: Option<AppHandle>,
+}
+
+impl SqlTool {
+    /// Creates a new SqlTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `keystore` - Secure storage of the connection strings
+    /// * `workflow_id` - Workflow ID
+    /// * `agent_id` - Agent ID using the tool
+    /// * `app_handle` - Tauri app handle for validation events
+    pub fn new(
+        db: Arc<DBClient>,
+        keystore: Option<SecureKeyStore>,
+        workflow_id: String,
+        agent_id: String,
+        app_handle: Option<AppHandle>,
+    ) -> Self {
+        Self {
+            db,
+            keystore,
+            workflow_id,
+            agent_id,
+            app_handle,
+        }
+    }
+
+    /// Lists the configured databases.
+    async fn list(&self) -> ToolResult<Value> {
+        let databases: Vec<Value> = list_databases(&self.db)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .into_iter()
+            .map(|database| {
+                json!({
+                    "name": database.name,
+                    "kind": database.kind,
+                    "description": database.description,
+                    "read_only": database.read_only,
+                    "max_rows": database.max_rows,
+                })
+            })
+            .collect();
+        let count = databases.len();
+        Ok(ResponseBuilder::list(databases, count))
+    }
+
+    /// Runs a read statement.
+    #[instrument(skip(self, sql, params), fields(agent_id = %self.agent_id))]
+    async fn query(
+        &self,
+        database: &str,
+        sql: &str,
+        params: &[Value],
+        limit: Option<usize>,
+    ) -> ToolResult<Value> {
+        if classify_statement(sql).map_err(ToolError::InvalidInput)? != StatementKind::Read {
+            return Err(ToolError::InvalidInput(
+                "The 'query' operation only runs read statements (SELECT, WITH, SHOW). \
+                 Use 'execute' for statements that modify data"
+                    .to_string(),
+            ));
+        }
+        let database = self.require_database(database).await?;
+        let connection_string = self.connection_string(&database)?;
+        let max_rows = limit.map_or(database.max_rows, |limit| limit.min(database.max_rows));
+
+        let result = run_query(database.kind, &connection_string, sql, params, max_rows)
+            .await
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(
+            database = %database.name,
+            rows = result.row_count,
+            truncated = result.truncated,
+            "External database query executed"
+        );
+        let message = if result.truncated {
+            format!(
+                "Returned the first {} rows; add a LIMIT or narrower filters to see the others",
+                result.row_count
+            )
+        } else {
+            format!("Returned {} rows", result.row_count)
+        };
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("database", database.name)
+            .data("columns", &result.columns)
+            .data("rows", &result.rows)
+            .field("row_count", result.row_count)
+            .field("truncated", result.truncated)
+            .message(message)
+            .build())
+    }
+
+    /// Runs a write statement once the user confirms it.
+    #[instrument(skip(self, sql, params), fields(agent_id = %self.agent_id))]
+    async fn execute_write(
+        &self,
+        database: &str,
+        sql: &str,
+        params: &[Value],
+    ) -> ToolResult<Value> {
+        if classify_statement(sql).map_err(ToolError::InvalidInput)? == StatementKind::Read {
+            return Err(ToolError::InvalidInput(
+                "Use the 'query' operation for read statements".to_string(),
+            ));
+        }
+        let database = self.require_database(database).await?;
+        if database.read_only {
+            return Err(ToolError::PermissionDenied(format!(
+                "Database '{}' is read-only",
+                database.name
+            )));
+        }
+        let connection_string = self.connection_string(&database)?;
+
+        ValidationHelper::new(self.db.clone(), self.app_handle.clone())
+            .request_sql_write_validation(&self.workflow_id, &database.name, sql, params)
+            .await?;
+
+        let rows_affected = run_statement(database.kind, &connection_string, sql, params)
+            .await
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(
+            database = %database.name,
+            rows_affected = rows_affected,
+            "External database statement executed"
+        );
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("database", database.name)
+            .field("rows_affected", rows_affected)
+            .message(format!(
+                "Statement executed, {} rows affected",
+                rows_affected
+            ))
+            .build())
+    }
+
+    async fn require_database(&self, name: &str) -> ToolResult<ExternalDatabase> {
+        load_database(&self.db, name)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .ok_or_else(|| {
+                ToolError::NotFound(format!(
+                    "Database '{}' is not configured (use 'list_databases')",
+                    name
+                ))
+            })
+    }
+
+    fn connection_string(&self, database: &ExternalDatabase) -> ToolResult<String> {
+        self.keystore
+            .as_ref()
+            .and_then(|keystore| keystore.get_key(&connection_key(&database.name)))
+            .ok_or_else(|| {
+                ToolError::DependencyError(format!(
+                    "No connection string stored for database '{}'",
+                    database.name
+                ))
+            })
+    }
+
+    /// Creates the tool with the keystore holding the connection secrets.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let wf_id = ctx.workflow_id_or_default();
+            let keystore = ctx.keystore();
+            let tool = SqlTool::new(
+                ctx.factory.get_db(),
+                keystore,
+                wf_id,
+                ctx.agent_id,
+                ctx.app_handle,
+            );
+            info!("SqlTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Statements are bounded by their own timeout; writes wait for the user
+    ToolRegistration::Tool(ToolMetadata {
+        name: "SqlTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: None,
+        constructor: SqlTool::build,
+    })
+}
+
+/// Reads the bound parameters of a statement.
+fn parse_params(input: &Value) -> ToolResult<Vec<Value>> {
+    match input.get("params") {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::Array(params)) => Ok(params.clone()),
+        Some(_) => Err(ToolError::InvalidInput(
+            "'params' must be an array of values".to_string(),
+        )),
+    }
+}
+
+#[async_trait]
+impl Tool for SqlTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "SqlTool".to_string(),
+            name: "SQL Tool".to_string(),
+            description: format!(
+                r#"Queries external databases (PostgreSQL, MySQL, SQLite) configured by the user.
+
+USE THIS TOOL WHEN:
+- You need data stored in one of the user's own databases
+- The user asks to inspect or change records in such a database
+
+OPERATIONS:
+- list_databases: List the configured databases (name, kind, description, read_only, max_rows)
+- query: Run ONE read statement (SELECT, WITH, SHOW) and get rows back
+- execute: Run ONE write statement (INSERT, UPDATE, DELETE, DDL); ALWAYS waits for the user's confirmation
+
+IMPORTANT CONSTRAINTS:
+- One statement per call, at most {} characters
+- Pass values through "params" (at most {}), never by concatenating them into the SQL:
+  PostgreSQL placeholders are $1, $2...; MySQL and SQLite use ?
+- Queries return at most max_rows rows of the database ("truncated": true when more matched)
+- Read-only databases reject 'execute'
+- Cast unsupported column types (numeric, intervals...) to text in the SELECT
+
+EXAMPLES:
+1. {{"operation": "list_databases"}}
+2. {{"operation": "query", "database": "crm", "sql": "SELECT id, name FROM customers WHERE country = $1 LIMIT 20", "params": ["FR"]}}
+3. {{"operation": "execute", "database": "crm", "sql": "UPDATE customers SET status = $1 WHERE id = $2", "params": ["inactive", 42]}}"#,
+                sql_const::MAX_STATEMENT_LENGTH,
+                sql_const::MAX_PARAMS
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": sql_const::VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "database": {
+                        "type": "string",
+                        "description": "Database name (from list_databases)"
+                    },
+                    "sql": {
+                        "type": "string",
+                        "description": "Single SQL statement"
+                    },
+                    "params": {
+                        "type": "array",
+                        "description": "Values bound to the statement placeholders, in order"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Maximum rows to return (query, capped by the database max_rows)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "items": {"type": "array"},
+                    "columns": {"type": "array", "items": {"type": "string"}},
+                    "rows": {"type": "array", "items": {"type": "array"}},
+                    "row_count": {"type": "integer"},
+                    "truncated": {"type": "boolean"},
+                    "rows_affected": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        let operation = input["operation"].as_str().unwrap_or_default();
+
+        if operation == "list_databases" {
+            return self.list().await;
+        }
+
+        let database = input["database"].as_str().unwrap_or_default();
+        let sql = input["sql"].as_str().unwrap_or_default();
+        let params = parse_params(&input)?;
+        match operation {
+            "query" => {
+                let limit = input["limit"].as_u64().map(|limit| limit as usize);
+                self.query(database, sql, &params, limit).await
+            }
+            _ => self.execute_write(database, sql, &params).await,
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation field".to_string()))?;
+        validate_enum_value(operation, sql_const::VALID_OPERATIONS, "operation")?;
+        if operation == "list_databases" {
+            return Ok(());
+        }
+
+        let database = input["database"].as_str().ok_or_else(|| {
+            ToolError::InvalidInput(format!("Missing 'database' for {} operation", operation))
+        })?;
+        validate_not_empty(database, "database")?;
+        let sql = input["sql"].as_str().ok_or_else(|| {
+            ToolError::InvalidInput(format!("Missing 'sql' for {} operation", operation))
+        })?;
+        validate_not_empty(sql, "sql")?;
+        validate_length(sql, sql_const::MAX_STATEMENT_LENGTH, "sql")?;
+
+        if parse_params(input)?.len() > sql_const::MAX_PARAMS {
+            return Err(ToolError::InvalidInput(format!(
+                "At most {} parameters are allowed",
+                sql_const::MAX_PARAMS
+            )));
+        }
+        if let Some(limit) = input.get("limit").filter(|limit| !limit.is_null()) {
+            if limit.as_u64().is_none_or(|limit| limit == 0) {
+                return Err(ToolError::InvalidInput(
+                    "'limit' must be a positive integer".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::external_db::ExternalDbKind;
+    use crate::tools::external_db::config::save_database;
+
+    async fn create_test_tool() -> (SqlTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let tool = SqlTool::new(
+            Arc::new(db),
+            None,
+            "wf_test".to_string(),
+            "test_agent".to_string(),
+            None,
+        );
+        (tool, dir)
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = create_test_tool().await;
+        assert!(tool
+            .validate_input(&json!({"operation": "list_databases"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "query", "database": "crm", "sql": "SELECT 1"}))
+            .is_ok());
+        assert!(tool.validate_input(&json!({"operation": "drop"})).is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "query", "sql": "SELECT 1"}))
+            .is_err());
+        assert!(tool
+            .validate_input(
+                &json!({"operation": "query", "database": "crm", "sql": "SELECT 1", "params": "x"})
+            )
+            .is_err());
+        assert!(tool
+            .validate_input(
+                &json!({"operation": "query", "database": "crm", "sql": "SELECT 1", "limit": 0})
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_query_rejects_write_statements() {
+        let (tool, _dir) = create_test_tool().await;
+        let result = tool
+            .execute(
+                json!({"operation": "query", "database": "crm", "sql": "DELETE FROM customers"}),
+            )
+            .await;
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_rejected_on_read_only_database() {
+        let (tool, _dir) = create_test_tool().await;
+        let database = ExternalDatabase {
+            name: "crm".to_string(),
+            kind: ExternalDbKind::Sqlite,
+            description: None,
+            read_only: true,
+            max_rows: 10,
+            created_at: None,
+        };
+        save_database(&tool.db, &database).await.unwrap();
+
+        let listed = tool
+            .execute(json!({"operation": "list_databases"}))
+            .await
+            .unwrap();
+        assert_eq!(listed["count"], 1);
+        assert_eq!(listed["items"][0]["read_only"], true);
+
+        let result = tool
+            .execute(
+                json!({"operation": "execute", "database": "crm", "sql": "DELETE FROM customers"}),
+            )
+            .await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+
+        let missing = tool
+            .execute(json!({"operation": "query", "database": "other", "sql": "SELECT 1"}))
+            .await;
+        assert!(matches!(missing, Err(ToolError::NotFound(_))));
+    }
+}
@@ -20,12 +20,19 @@
 //! This module provides a factory pattern for creating tool instances
 //! based on their string identifiers from agent configuration.
 //!
-//! # Supported Tools
+//! The factory holds the shared dependencies and calls the constructor each
+//! tool registered in the [`ToolRegistry`](crate::tools::registry::ToolRegistry)
+//! under its ID: supporting a new tool does not change this module.
 //!
-//! | Tool ID | Module | Description |
-//! |---------|--------|-------------|
-//! | `MemoryTool` | [`memory`] | Contextual memory with semantic search |
-//! | `TodoTool` | [`todo`] | Task management for workflows |
+//! `ResourceTool` needs the MCP manager and the agent's assigned servers:
+//! it is created by the agent at execution time, not by this factory.
+//!
+//! Plugin tools (`plugin__<id>`) are created from the WASM plugins enabled
+//! in the active profile (see [`crate::plugins`]), custom tools
+//! (`custom__<name>`) from the enabled Rhai scripts of the `custom_tool`
+//! table (see [`crate::tools::script`]) and macro tools (`macro__<name>`)
+//! from the enabled macros of the `macro_tool` table, with one tool per step
+//! (see [`crate::tools::macros`]).
 //!
 //! # Usage
 //!
@@ -43,17 +50,21 @@
 //!     Some("wf_001".to_string()),
 //!     "db_agent".to_string(),
 //! )?;
+//!
+//! // Apply the agent's per-tool defaults and restrictions
+//! let tools = factory.apply_tool_settings(tools, &agent_config.tool_settings);
 //! ```
 
 use crate::db::DBClient;
 use crate::llm::embedding::EmbeddingService;
+use crate::models::ToolSettings;
+use crate::tools::configured::ConfiguredTool;
 use crate::tools::context::AgentToolContext;
-use crate::tools::delegate_task::DelegateTaskTool;
-use crate::tools::parallel_tasks::ParallelTasksTool;
-use crate::tools::registry::TOOL_REGISTRY;
-use crate::tools::spawn_agent::SpawnAgentTool;
-use crate::tools::{CalculatorTool, MemoryTool, TodoTool, Tool, UserQuestionTool};
+use crate::tools::registry::{ToolBuildContext, TOOL_REGISTRY};
+use crate::tools::Tool;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, info, warn};
 
 /// Factory for creating tool instances.
@@ -112,7 +123,7 @@ impl ToolFactory {
     }
 
     /// Gets the current embedding service (reads from dynamic reference)
-    async fn get_embedding_service(&self) -> Option<Arc<EmbeddingService>> {
+    pub async fn get_embedding_service(&self) -> Option<Arc<EmbeddingService>> {
         self.embedding_service.read().await.clone()
     }
 
@@ -134,11 +145,8 @@ impl ToolFactory {
     ///
     /// # Returns
     /// * `Ok(Arc<dyn Tool>)` - Tool instance ready for use
-    /// * `Err(String)` - Error message if tool is unknown
-    ///
-    /// # Supported Tools
-    /// * `MemoryTool` - Contextual memory with semantic search
-    /// * `TodoTool` - Task management for workflow decomposition
+    /// * `Err(String)` - Error message if tool is unknown or requires
+    ///   `AgentToolContext` (see [`Self::create_tool_with_context`])
     ///
     /// # Example
     /// ```ignore
@@ -156,58 +164,30 @@ impl ToolFactory {
         agent_id: String,
         app_handle: Option<tauri::AppHandle>,
     ) -> Result<Arc<dyn Tool>, String> {
-        // Validate tool exists in registry
-        TOOL_REGISTRY.validate(tool_name)?;
+        self.build(ToolBuildContext {
+            factory: self,
+            tool_name,
+            workflow_id,
+            agent_id,
+            app_handle,
+            agent_context: None,
+            is_primary_agent: false,
+        })
+        .await
+    }
+
+    /// Creates a tool with the constructor registered under its name.
+    async fn build(&self, ctx: ToolBuildContext<'_>) -> Result<Arc<dyn Tool>, String> {
+        let metadata = TOOL_REGISTRY.validate(ctx.tool_name).inspect_err(|_| {
+            warn!(tool_name = %ctx.tool_name, "Unknown tool requested");
+        })?;
         debug!(
-            tool_name = %tool_name,
-            workflow_id = ?workflow_id,
-            agent_id = %agent_id,
+            tool_name = %ctx.tool_name,
+            workflow_id = ?ctx.workflow_id,
+            agent_id = %ctx.agent_id,
             "Creating tool instance"
         );
-
-        match tool_name {
-            "MemoryTool" => {
-                // Read current embedding service state (configured via Settings)
-                let embedding_service = self.get_embedding_service().await;
-                let has_embedding = embedding_service.is_some();
-                debug!(
-                    has_embedding = has_embedding,
-                    "Creating MemoryTool with current embedding state"
-                );
-                let tool =
-                    MemoryTool::new(self.db.clone(), embedding_service, workflow_id, agent_id);
-                info!(has_embedding = has_embedding, "MemoryTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            "TodoTool" => {
-                let wf_id = workflow_id.unwrap_or_else(|| "default".to_string());
-                let tool = TodoTool::new(self.db.clone(), wf_id, agent_id, app_handle);
-                info!("TodoTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            "CalculatorTool" => {
-                let tool = CalculatorTool::new();
-                info!("CalculatorTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            "UserQuestionTool" => {
-                let wf_id = workflow_id.unwrap_or_else(|| "default".to_string());
-                let tool = UserQuestionTool::new(self.db.clone(), wf_id, agent_id, app_handle);
-                info!("UserQuestionTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            _ => {
-                warn!(tool_name = %tool_name, "Unknown tool requested");
-                Err(format!(
-                    "Unknown tool: '{}'. Available tools: MemoryTool, TodoTool, CalculatorTool, UserQuestionTool",
-                    tool_name
-                ))
-            }
-        }
+        (metadata.constructor)(ctx).await
     }
 
     /// Creates multiple tools from a list of names.
@@ -261,6 +241,58 @@ impl ToolFactory {
         tools
     }
 
+    /// Applies per-agent tool settings to created tool instances.
+    ///
+    /// Tools with settings are wrapped in a [`ConfiguredTool`] which merges
+    /// parameter defaults, enforces fixed parameters and restricts operations.
+    /// Tools without settings are returned unchanged.
+    ///
+    /// # Arguments
+    /// * `tools` - Tool instances created for the agent
+    /// * `tool_settings` - Agent's settings keyed by tool name
+    pub fn apply_tool_settings(
+        &self,
+        tools: Vec<Arc<dyn Tool>>,
+        tool_settings: &HashMap<String, ToolSettings>,
+    ) -> Vec<Arc<dyn Tool>> {
+        if tool_settings.is_empty() {
+            return tools;
+        }
+
+        tools
+            .into_iter()
+            .map(|tool| {
+                let tool_id = tool.definition().id;
+                match tool_settings.get(&tool_id) {
+                    Some(settings) if !settings.is_empty() => {
+                        debug!(tool_name = %tool_id, "Applying agent tool settings");
+                        Arc::new(ConfiguredTool::new(tool, settings.clone())) as Arc<dyn Tool>
+                    }
+                    _ => tool,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the call timeout of a local tool for an agent.
+    ///
+    /// The agent's `timeout_secs` setting overrides the registry default.
+    /// None means the call is not bounded (tools with their own wait logic).
+    ///
+    /// # Arguments
+    /// * `tool_name` - Tool identifier
+    /// * `tool_settings` - Agent's settings keyed by tool name
+    pub fn tool_timeout(
+        tool_name: &str,
+        tool_settings: &HashMap<String, ToolSettings>,
+    ) -> Option<Duration> {
+        tool_settings
+            .get(tool_name)
+            .and_then(|settings| settings.timeout_secs)
+            .or_else(|| TOOL_REGISTRY.default_timeout_secs(tool_name))
+            .map(Duration::from_secs)
+    }
+
     /// Returns list of available tool names.
     ///
     /// This includes both basic tools (MemoryTool, TodoTool) and
@@ -357,64 +389,17 @@ impl ToolFactory {
             ));
         }
 
-        match tool_name {
-            // Basic tools (delegate to create_tool)
-            "MemoryTool" | "TodoTool" | "CalculatorTool" | "UserQuestionTool" => {
-                // Extract app_handle from context for basic tools
-                let app_handle = context.app_handle.clone();
-                self.create_tool(tool_name, workflow_id, agent_id, app_handle)
-                    .await
-            }
-
-            // Sub-agent tools (require context)
-            "SpawnAgentTool" => {
-                let wf_id = workflow_id.unwrap_or_else(|| "default".to_string());
-                let tool = SpawnAgentTool::new(
-                    self.db.clone(),
-                    context,
-                    agent_id,
-                    wf_id,
-                    is_primary_agent,
-                );
-                info!("SpawnAgentTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            "DelegateTaskTool" => {
-                let wf_id = workflow_id.unwrap_or_else(|| "default".to_string());
-                let tool = DelegateTaskTool::new(
-                    self.db.clone(),
-                    context,
-                    agent_id,
-                    wf_id,
-                    is_primary_agent,
-                );
-                info!("DelegateTaskTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            "ParallelTasksTool" => {
-                let wf_id = workflow_id.unwrap_or_else(|| "default".to_string());
-                let tool = ParallelTasksTool::new(
-                    self.db.clone(),
-                    context,
-                    agent_id,
-                    wf_id,
-                    is_primary_agent,
-                );
-                info!("ParallelTasksTool instance created");
-                Ok(Arc::new(tool))
-            }
-
-            _ => {
-                warn!(tool_name = %tool_name, "Unknown tool requested");
-                Err(format!(
-                    "Unknown tool: '{}'. Available tools: {:?}",
-                    tool_name,
-                    Self::available_tools()
-                ))
-            }
-        }
+        let app_handle = context.app_handle.clone();
+        self.build(ToolBuildContext {
+            factory: self,
+            tool_name,
+            workflow_id,
+            agent_id,
+            app_handle,
+            agent_context: Some(context),
+            is_primary_agent,
+        })
+        .await
     }
 
     /// Creates multiple tools, handling both basic and context-aware tools.
@@ -546,6 +531,7 @@ impl ToolFactory {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::{macros, script};
     use tempfile::tempdir;
 
     async fn create_test_factory() -> ToolFactory {
@@ -571,7 +557,19 @@ mod tests {
         assert!(tools.contains(&"SpawnAgentTool"));
         assert!(tools.contains(&"DelegateTaskTool"));
         assert!(tools.contains(&"ParallelTasksTool"));
-        assert_eq!(tools.len(), 7); // 4 basic + 3 sub-agent
+        assert!(tools.contains(&"ResourceTool"));
+        assert!(tools.contains(&"WorkflowTool"));
+        assert!(tools.contains(&"SqlTool"));
+        assert!(tools.contains(&"ChartTool"));
+        assert!(tools.contains(&"ClipboardTool"));
+        assert!(tools.contains(&"ScreenshotTool"));
+        assert!(tools.contains(&"EmailTool"));
+        assert!(tools.contains(&"RssTool"));
+        assert!(tools.contains(&"UnitsTool"));
+        assert!(tools.contains(&"TextTool"));
+        assert!(tools.contains(&"PdfTool"));
+        assert!(tools.contains(&"OcrTool"));
+        assert_eq!(tools.len(), 19); // 15 basic + 3 sub-agent + 1 composite
     }
 
     #[test]
@@ -581,8 +579,19 @@ mod tests {
         assert!(tools.contains(&"TodoTool"));
         assert!(tools.contains(&"CalculatorTool"));
         assert!(tools.contains(&"UserQuestionTool"));
+        assert!(tools.contains(&"ResourceTool"));
+        assert!(tools.contains(&"SqlTool"));
+        assert!(tools.contains(&"ChartTool"));
+        assert!(tools.contains(&"ClipboardTool"));
+        assert!(tools.contains(&"ScreenshotTool"));
+        assert!(tools.contains(&"EmailTool"));
+        assert!(tools.contains(&"RssTool"));
+        assert!(tools.contains(&"UnitsTool"));
+        assert!(tools.contains(&"TextTool"));
+        assert!(tools.contains(&"PdfTool"));
+        assert!(tools.contains(&"OcrTool"));
         assert!(!tools.contains(&"SpawnAgentTool"));
-        assert_eq!(tools.len(), 4);
+        assert_eq!(tools.len(), 15);
     }
 
     #[test]
@@ -688,6 +697,103 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_create_context_tool_without_context() {
+        let factory = create_test_factory().await;
+
+        let result = factory
+            .create_tool("SpawnAgentTool", None, "test_agent".to_string(), None)
+            .await;
+        match result {
+            Err(msg) => assert!(msg.contains("requires AgentToolContext")),
+            Ok(_) => panic!("Expected error without AgentToolContext"),
+        }
+
+        let result = factory
+            .create_tool("ResourceTool", None, "test_agent".to_string(), None)
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_disabled_plugin_tool() {
+        let factory = create_test_factory().await;
+
+        // Accepted by the registry, but not enabled in this profile
+        let result = factory
+            .create_tool("plugin__weather", None, "test_agent".to_string(), None)
+            .await;
+        match result {
+            Err(msg) => assert!(msg.contains("not enabled")),
+            Ok(_) => panic!("Expected error for a disabled plugin"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_custom_tool() {
+        let factory = create_test_factory().await;
+
+        let result = factory
+            .create_tool("custom__word_count", None, "test_agent".to_string(), None)
+            .await;
+        match result {
+            Err(msg) => assert!(msg.contains("not found")),
+            Ok(_) => panic!("Expected error for a missing custom tool"),
+        }
+
+        script::store::create_custom_tool(
+            &factory.db,
+            "tool-1",
+            &crate::models::custom_tool::CustomToolCreate {
+                name: "word_count".to_string(),
+                description: "Counts words".to_string(),
+                input_schema: serde_json::json!({ "type": "object" }),
+                script: "42".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        let tool = factory
+            .create_tool("custom__word_count", None, "test_agent".to_string(), None)
+            .await
+            .expect("Failed to create custom tool");
+        assert_eq!(tool.definition().id, "custom__word_count");
+    }
+
+    #[tokio::test]
+    async fn test_create_macro_tool() {
+        let factory = create_test_factory().await;
+
+        macros::store::create_tool_macro(
+            &factory.db,
+            "macro-1",
+            &crate::models::macro_tool::ToolMacroCreate {
+                name: "root".to_string(),
+                description: "Computes a square root".to_string(),
+                input_schema: serde_json::json!({ "type": "object" }),
+                steps: vec![crate::models::macro_tool::MacroStep {
+                    id: "calc".to_string(),
+                    tool: "CalculatorTool".to_string(),
+                    input: serde_json::json!({ "operation": "sqrt", "value": "{{input.value}}" }),
+                }],
+                output: None,
+            },
+        )
+        .await
+        .unwrap();
+        let tool = factory
+            .create_tool("macro__root", None, "test_agent".to_string(), None)
+            .await
+            .expect("Failed to create macro tool");
+        assert_eq!(tool.definition().id, "macro__root");
+
+        let result = tool
+            .execute(serde_json::json!({ "value": 4 }))
+            .await
+            .unwrap();
+        assert_eq!(result["result"]["result"], serde_json::json!(2.0));
+    }
+
     #[tokio::test]
     async fn test_create_tools_batch() {
         let factory = create_test_factory().await;
@@ -721,4 +827,66 @@ mod tests {
             .await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_apply_tool_settings() {
+        let factory = create_test_factory().await;
+
+        let tools = factory
+            .create_tools(
+                &["MemoryTool".to_string(), "CalculatorTool".to_string()],
+                Some("wf_settings".to_string()),
+                "settings_agent".to_string(),
+                None,
+            )
+            .await;
+
+        let mut settings = HashMap::new();
+        settings.insert(
+            "MemoryTool".to_string(),
+            ToolSettings {
+                allowed_operations: Some(vec!["describe".to_string(), "search".to_string()]),
+                ..Default::default()
+            },
+        );
+
+        let tools = factory.apply_tool_settings(tools, &settings);
+        assert_eq!(tools.len(), 2);
+
+        let memory = tools
+            .iter()
+            .find(|t| t.definition().id == "MemoryTool")
+            .unwrap();
+        let ops = memory.definition().input_schema["properties"]["operation"]["enum"].clone();
+        assert_eq!(ops, serde_json::json!(["describe", "search"]));
+
+        let result = memory
+            .execute(serde_json::json!({"operation": "delete", "memory_id": "mem_1"}))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_tool_timeout() {
+        let mut settings = HashMap::new();
+        settings.insert(
+            "MemoryTool".to_string(),
+            ToolSettings {
+                timeout_secs: Some(5),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            ToolFactory::tool_timeout("MemoryTool", &settings),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            ToolFactory::tool_timeout("TodoTool", &settings),
+            Some(Duration::from_secs(
+                crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS
+            ))
+        );
+        assert_eq!(ToolFactory::tool_timeout("SpawnAgentTool", &settings), None);
+    }
 }
@@ -0,0 +1,115 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timeout and cancellation wrapper for tool calls.
+//!
+//! The agent loop runs every tool call (local or MCP) through
+//! [`run_with_limits`], so a hung tool cannot block the iteration loop and a
+//! workflow cancellation aborts the call in flight.
+
+use crate::tools::{ToolError, ToolResult};
+use std::future::Future;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+/// Runs a tool call future, bounded by a timeout and a cancellation token.
+///
+/// The future is dropped when a limit is hit, which aborts the pending I/O.
+///
+/// # Arguments
+/// * `tool_name` - Tool name, for errors and logs
+/// * `timeout` - Maximum duration (None = unbounded)
+/// * `cancellation` - Workflow cancellation token (None = not cancellable)
+/// * `call` - The tool call future
+///
+/// # Errors
+/// * `Timeout` - The call did not complete in time
+/// * `ExecutionFailed` - The workflow was cancelled during the call
+pub async fn run_with_limits<F: Future>(
+    tool_name: &str,
+    timeout: Option<Duration>,
+    cancellation: Option<&CancellationToken>,
+    call: F,
+) -> ToolResult<F::Output> {
+    let deadline = async {
+        match timeout {
+            Some(duration) => tokio::time::sleep(duration).await,
+            None => std::future::pending::<()>().await,
+        }
+    };
+    let cancelled = async {
+        match cancellation {
+            Some(token) => token.cancelled().await,
+            None => std::future::pending::<()>().await,
+        }
+    };
+
+    tokio::select! {
+        output = call => Ok(output),
+        _ = deadline => {
+            let secs = timeout.unwrap_or_default().as_secs();
+            warn!(tool = %tool_name, timeout_secs = secs, "Tool call timed out");
+            Err(ToolError::Timeout(format!(
+                "Tool '{}' did not complete within {}s",
+                tool_name, secs
+            )))
+        }
+        _ = cancelled => {
+            warn!(tool = %tool_name, "Tool call aborted by workflow cancellation");
+            Err(ToolError::ExecutionFailed(format!(
+                "Tool '{}' was cancelled by the user",
+                tool_name
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_with_limits_completes() {
+        let result =
+            run_with_limits("FastTool", Some(Duration::from_secs(1)), None, async { 42 }).await;
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_limits_timeout() {
+        let result = run_with_limits(
+            "SlowTool",
+            Some(Duration::from_millis(20)),
+            None,
+            std::future::pending::<()>(),
+        )
+        .await;
+        assert!(matches!(result, Err(ToolError::Timeout(msg)) if msg.contains("SlowTool")));
+    }
+
+    #[tokio::test]
+    async fn test_run_with_limits_cancellation() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let result =
+            run_with_limits("HungTool", None, Some(&token), std::future::pending::<()>()).await;
+        assert!(matches!(result, Err(ToolError::ExecutionFailed(_))));
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Macro Tools
+//!
+//! Named sequences of tool calls defined by the user in Settings > Tools and
+//! stored in the `macro_tool` table. Agents call a macro like any other tool
+//! (`macro__<name>`) and get its result in one iteration instead of one
+//! iteration per call.
+//!
+//! # Templates
+//!
+//! The input of each step is a JSON template whose strings may hold
+//! placeholders:
+//!
+//! - `{{input.<param>}}`: a parameter of the macro call
+//! - `{{steps.<id>.<path>}}`: the result of an earlier step; numeric path
+//!   segments index arrays (`{{steps.search.results.0.url}}`)
+//!
+//! A string made of a single placeholder takes the value as is (number,
+//! object...), other strings get the value as text. Parameters whose lone
+//! placeholder refers to a missing value are left out of the call, so
+//! optional inputs keep the defaults of the tool.
+//!
+//! ```json
+//! [
+//!   { "id": "search", "tool": "plugin__web-search", "input": { "query": "{{input.topic}}" } },
+//!   { "id": "save", "tool": "MemoryTool", "input": {
+//!       "operation": "add", "type": "knowledge",
+//!       "content": "{{input.topic}}: {{steps.search.results.0.snippet}}" } }
+//! ]
+//! ```
+//!
+//! Steps may call the basic local tools, plugin tools and custom script
+//! tools, but not other macros, MCP tools or sub-agent tools.
+
+pub mod store;
+mod tool;
+
+pub use tool::MacroTool;
+
+use crate::models::macro_tool::MacroStep;
+use crate::tools::constants::macros as macro_const;
+use crate::tools::registry::{ToolCategory, TOOL_REGISTRY};
+use crate::tools::script;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Tool name of a macro.
+pub fn tool_name(name: &str) -> String {
+    format!("{}{}", macro_const::TOOL_PREFIX, name)
+}
+
+/// Macro name of a tool name, None for other tools.
+pub fn macro_name(tool_name: &str) -> Option<&str> {
+    tool_name
+        .strip_prefix(macro_const::TOOL_PREFIX)
+        .filter(|name| is_valid_name(name))
+}
+
+/// Whether a macro or step name is well-formed: lowercase letters, digits
+/// and `_`, starting with a letter.
+pub fn is_valid_name(name: &str) -> bool {
+    name.len() <= macro_const::MAX_NAME_LEN && script::is_valid_name(name)
+}
+
+/// Whether a macro step may call a tool.
+///
+/// ResourceTool is built by the agent from its MCP servers and sub-agent
+/// tools need the agent context; macros cannot call macros, which keeps the
+/// sequence flat.
+pub fn is_step_tool(tool: &str) -> bool {
+    tool != "ResourceTool"
+        && macro_name(tool).is_none()
+        && TOOL_REGISTRY
+            .get(tool)
+            .is_some_and(|metadata| metadata.category == ToolCategory::Basic)
+}
+
+/// Validates the steps and output template of a macro.
+///
+/// Step IDs must be well-formed and unique, step tools usable in a macro
+/// and step inputs objects. Placeholders must refer to the input or to an
+/// earlier step (any step for the output template).
+pub fn validate_steps(steps: &[MacroStep], output: Option<&Value>) -> Result<(), String> {
+    if steps.is_empty() {
+        return Err("A macro needs at least one step".to_string());
+    }
+    if steps.len() > macro_const::MAX_STEPS {
+        return Err(format!(
+            "A macro has at most {} steps",
+            macro_const::MAX_STEPS
+        ));
+    }
+    let size = serde_json::to_string(steps).map_or(0, |text| text.len())
+        + output.map_or(0, |output| output.to_string().len());
+    if size > macro_const::MAX_TEMPLATE_BYTES {
+        return Err(format!(
+            "The steps exceed {} bytes",
+            macro_const::MAX_TEMPLATE_BYTES
+        ));
+    }
+
+    let mut step_ids = HashSet::new();
+    for (index, step) in steps.iter().enumerate() {
+        let label = format!("Step {}", index + 1);
+        if !is_valid_name(&step.id) {
+            return Err(format!(
+                "{}: invalid ID '{}': use lowercase letters, digits and '_', starting with a letter",
+                label, step.id
+            ));
+        }
+        if !is_step_tool(&step.tool) {
+            return Err(format!(
+                "{} '{}': tool '{}' cannot be used in a macro",
+                label, step.id, step.tool
+            ));
+        }
+        if !step.input.is_object() {
+            return Err(format!(
+                "{} '{}': the input must be a JSON object",
+                label, step.id
+            ));
+        }
+        check_references(&step.input, &step_ids)
+            .map_err(|e| format!("{} '{}': {}", label, step.id, e))?;
+        if !step_ids.insert(step.id.as_str()) {
+            return Err(format!("{}: duplicate ID '{}'", label, step.id));
+        }
+    }
+    if let Some(output) = output {
+        check_references(output, &step_ids).map_err(|e| format!("Output: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Checks that the placeholders of a template refer to the input or to one
+/// of the given steps.
+fn check_references(template: &Value, step_ids: &HashSet<&str>) -> Result<(), String> {
+    match template {
+        Value::String(text) => {
+            for (_, path) in placeholders(text) {
+                let mut segments = path.split('.');
+                match (segments.next(), segments.next()) {
+                    (Some("input"), Some(param)) if !param.is_empty() => {}
+                    (Some("steps"), Some(id)) if step_ids.contains(id) => {}
+                    (Some("steps"), _) => {
+                        return Err(format!(
+                            "'{{{{{}}}}}' refers to an unknown or later step",
+                            path
+                        ))
+                    }
+                    _ => {
+                        return Err(format!(
+                            "'{{{{{}}}}}' must start with 'input.' or 'steps.'",
+                            path
+                        ))
+                    }
+                }
+            }
+            Ok(())
+        }
+        Value::Array(items) => items
+            .iter()
+            .try_for_each(|item| check_references(item, step_ids)),
+        Value::Object(fields) => fields
+            .values()
+            .try_for_each(|field| check_references(field, step_ids)),
+        _ => Ok(()),
+    }
+}
+
+/// Placeholders of a template string: their byte range and trimmed path
+/// (`{{ input.url }}` -> `input.url`).
+fn placeholders(text: &str) -> Vec<(Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("{{") {
+        let start = offset + start;
+        let Some(len) = text[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + len + 4;
+        found.push((start..end, text[start + 2..end - 2].trim()));
+        offset = end;
+    }
+    found
+}
+
+/// Value at a dotted path of the scope, None if missing.
+fn lookup<'a>(scope: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(scope, |value, key| match value {
+        Value::Object(fields) => fields.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => None,
+    })
+}
+
+/// Resolves the placeholders of a template.
+///
+/// # Arguments
+/// * `template` - Step input or output template
+/// * `scope` - `{ "input": ..., "steps": { "<id>": ... } }`
+pub fn resolve(template: &Value, scope: &Value) -> Value {
+    resolve_value(template, scope).unwrap_or(Value::Null)
+}
+
+/// Resolves a template value, None for a lone placeholder referring to a
+/// missing value.
+fn resolve_value(template: &Value, scope: &Value) -> Option<Value> {
+    match template {
+        Value::String(text) => resolve_text(text, scope),
+        Value::Array(items) => Some(Value::Array(
+            items.iter().map(|item| resolve(item, scope)).collect(),
+        )),
+        Value::Object(fields) => Some(Value::Object(
+            fields
+                .iter()
+                .filter_map(|(key, field)| {
+                    resolve_value(field, scope).map(|value| (key.clone(), value))
+                })
+                .collect::<Map<String, Value>>(),
+        )),
+        other => Some(other.clone()),
+    }
+}
+
+/// Resolves the placeholders of a template string.
+fn resolve_text(text: &str, scope: &Value) -> Option<Value> {
+    let found = placeholders(text);
+    // A lone placeholder keeps the type of the value it refers to
+    if let [(range, path)] = found.as_slice() {
+        if range.len() == text.len() {
+            return lookup(scope, path).cloned();
+        }
+    }
+
+    let mut resolved = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, path) in found {
+        resolved.push_str(&text[last..range.start]);
+        match lookup(scope, path) {
+            Some(Value::String(value)) => resolved.push_str(value),
+            Some(Value::Null) | None => {}
+            Some(value) => resolved.push_str(&value.to_string()),
+        }
+        last = range.end;
+    }
+    resolved.push_str(&text[last..]);
+    Some(Value::String(resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn step(id: &str, tool: &str, input: Value) -> MacroStep {
+        MacroStep {
+            id: id.to_string(),
+            tool: tool.to_string(),
+            input,
+        }
+    }
+
+    #[test]
+    fn test_tool_names() {
+        assert_eq!(tool_name("research"), "macro__research");
+        assert_eq!(macro_name("macro__research"), Some("research"));
+        assert_eq!(macro_name("macro__Research"), None);
+        assert_eq!(macro_name("custom__research"), None);
+        assert!(is_step_tool("MemoryTool"));
+        assert!(is_step_tool("plugin__web-search"));
+        assert!(is_step_tool("custom__word_count"));
+        assert!(!is_step_tool("macro__research"));
+        assert!(!is_step_tool("ResourceTool"));
+        assert!(!is_step_tool("SpawnAgentTool"));
+        assert!(!is_step_tool("WorkflowTool"));
+    }
+
+    #[test]
+    fn test_validate_steps() {
+        let steps = vec![
+            step(
+                "calc",
+                "CalculatorTool",
+                json!({ "operation": "sqrt", "value": "{{input.value}}" }),
+            ),
+            step(
+                "save",
+                "MemoryTool",
+                json!({ "operation": "add", "content": "{{ steps.calc.result }}" }),
+            ),
+        ];
+        assert!(validate_steps(&steps, None).is_ok());
+        assert!(validate_steps(&steps, Some(&json!("{{steps.save}}"))).is_ok());
+
+        assert!(validate_steps(&[], None).is_err());
+        let invalid = [
+            vec![step("Calc", "CalculatorTool", json!({}))],
+            vec![step("calc", "macro__other", json!({}))],
+            vec![step("calc", "CalculatorTool", json!("{{input.value}}"))],
+            vec![step(
+                "calc",
+                "CalculatorTool",
+                json!({ "value": "{{steps.calc}}" }),
+            )],
+            vec![step(
+                "calc",
+                "CalculatorTool",
+                json!({ "value": "{{value}}" }),
+            )],
+            vec![
+                step("calc", "CalculatorTool", json!({})),
+                step("calc", "CalculatorTool", json!({})),
+            ],
+        ];
+        for steps in invalid {
+            assert!(validate_steps(&steps, None).is_err(), "{:?}", steps);
+        }
+        assert!(validate_steps(&steps, Some(&json!({ "value": "{{steps.other}}" }))).is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let scope = json!({
+            "input": { "topic": "rust", "limit": 3 },
+            "steps": { "search": { "results": [{ "url": "https://a.test", "rank": 1 }] } }
+        });
+        let template = json!({
+            "url": "{{steps.search.results.0.url}}",
+            "limit": "{{ input.limit }}",
+            "title": "About {{input.topic}} (rank {{steps.search.results.0.rank}})",
+            "tags": ["{{input.topic}}", "{{input.missing}}"],
+            "optional": "{{input.missing}}",
+            "note": "{{input.missing}} only"
+        });
+        assert_eq!(
+            resolve(&template, &scope),
+            json!({
+                "url": "https://a.test",
+                "limit": 3,
+                "title": "About rust (rank 1)",
+                "tags": ["rust", null],
+                "note": " only"
+            })
+        );
+        assert_eq!(
+            resolve(&json!("{{steps.search}}"), &scope)["results"][0]["rank"],
+            1
+        );
+        assert_eq!(resolve(&json!("{{unclosed"), &scope), json!("{{unclosed"));
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Macro tool storage.
+//!
+//! Macros live in the `macro_tool` table, their input schema, steps and
+//! output template serialized as strings.
+
+use crate::db::DBClient;
+use crate::models::macro_tool::{ToolMacro, ToolMacroCreate, ToolMacroUpdate};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+/// Fields selected when loading macros
+const SELECT_FIELDS: &str = "meta::id(id) AS id, name, description, input_schema, steps, \
+     output, enabled, created_at, updated_at";
+
+/// Macro tool as stored
+#[derive(Deserialize)]
+struct ToolMacroRow {
+    id: String,
+    name: String,
+    description: String,
+    input_schema: String,
+    steps: String,
+    output: String,
+    enabled: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<ToolMacroRow> for ToolMacro {
+    fn from(row: ToolMacroRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            input_schema: serde_json::from_str(&row.input_schema)
+                .unwrap_or_else(|_| json!({ "type": "object" })),
+            steps: serde_json::from_str(&row.steps).unwrap_or_default(),
+            output: serde_json::from_str::<Value>(&row.output)
+                .ok()
+                .filter(|output| !output.is_null()),
+            enabled: row.enabled,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Serializes an output template (null = result of the last step).
+fn output_field(output: Option<&Value>) -> Value {
+    json!(output.unwrap_or(&Value::Null).to_string())
+}
+
+/// Converts query rows into macros.
+fn from_rows(rows: Vec<Value>) -> Vec<ToolMacro> {
+    rows.into_iter()
+        .filter_map(|row| serde_json::from_value::<ToolMacroRow>(row).ok())
+        .map(ToolMacro::from)
+        .collect()
+}
+
+/// Lists the macros, sorted by name.
+pub async fn list_tool_macros(db: &DBClient) -> Result<Vec<ToolMacro>, String> {
+    let rows = db
+        .query_json(&format!(
+            "SELECT {} FROM macro_tool ORDER BY name",
+            SELECT_FIELDS
+        ))
+        .await
+        .map_err(|e| format!("Failed to list macro tools: {}", e))?;
+    Ok(from_rows(rows))
+}
+
+/// Loads a macro by ID.
+pub async fn load_tool_macro(db: &DBClient, macro_id: &str) -> Result<Option<ToolMacro>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM macro_tool WHERE meta::id(id) = $id",
+                SELECT_FIELDS
+            ),
+            vec![("id".to_string(), json!(macro_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load macro tool: {}", e))?;
+    Ok(from_rows(rows).into_iter().next())
+}
+
+/// Loads a macro by name.
+pub async fn find_tool_macro(db: &DBClient, name: &str) -> Result<Option<ToolMacro>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM macro_tool WHERE name = $name",
+                SELECT_FIELDS
+            ),
+            vec![("name".to_string(), json!(name))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load macro tool: {}", e))?;
+    Ok(from_rows(rows).into_iter().next())
+}
+
+/// Creates a macro from a validated payload.
+pub async fn create_tool_macro(
+    db: &DBClient,
+    macro_id: &str,
+    tool_macro: &ToolMacroCreate,
+) -> Result<(), String> {
+    db.execute_with_params(
+        "CREATE type::thing('macro_tool', $id) CONTENT { \
+         name: $name, description: $description, input_schema: $input_schema, \
+         steps: $steps, output: $output, enabled: true, \
+         created_at: time::now(), updated_at: time::now() }",
+        vec![
+            ("id".to_string(), json!(macro_id)),
+            ("name".to_string(), json!(tool_macro.name)),
+            ("description".to_string(), json!(tool_macro.description)),
+            (
+                "input_schema".to_string(),
+                json!(tool_macro.input_schema.to_string()),
+            ),
+            (
+                "steps".to_string(),
+                json!(json!(tool_macro.steps).to_string()),
+            ),
+            (
+                "output".to_string(),
+                output_field(tool_macro.output.as_ref()),
+            ),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create macro tool: {}", e))
+}
+
+/// Updates the fields of a validated payload. The output template is
+/// written with the steps.
+pub async fn update_tool_macro(
+    db: &DBClient,
+    macro_id: &str,
+    update: &ToolMacroUpdate,
+) -> Result<(), String> {
+    let mut set_clauses = vec!["updated_at = time::now()"];
+    let mut params = vec![("id".to_string(), json!(macro_id))];
+    if let Some(description) = &update.description {
+        set_clauses.push("description = $description");
+        params.push(("description".to_string(), json!(description)));
+    }
+    if let Some(input_schema) = &update.input_schema {
+        set_clauses.push("input_schema = $input_schema");
+        params.push(("input_schema".to_string(), json!(input_schema.to_string())));
+    }
+    if let Some(steps) = &update.steps {
+        set_clauses.push("steps = $steps");
+        set_clauses.push("output = $output");
+        params.push(("steps".to_string(), json!(json!(steps).to_string())));
+        params.push(("output".to_string(), output_field(update.output.as_ref())));
+    }
+    if let Some(enabled) = update.enabled {
+        set_clauses.push("enabled = $enabled");
+        params.push(("enabled".to_string(), json!(enabled)));
+    }
+
+    db.execute_with_params(
+        &format!(
+            "UPDATE type::thing('macro_tool', $id) SET {}",
+            set_clauses.join(", ")
+        ),
+        params,
+    )
+    .await
+    .map_err(|e| format!("Failed to update macro tool: {}", e))
+}
+
+/// Deletes a macro.
+pub async fn delete_tool_macro(db: &DBClient, macro_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "DELETE type::thing('macro_tool', $id)",
+        vec![("id".to_string(), json!(macro_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete macro tool: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::macro_tool::MacroStep;
+
+    #[tokio::test]
+    async fn test_tool_macro_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let steps = vec![MacroStep {
+            id: "calc".to_string(),
+            tool: "CalculatorTool".to_string(),
+            input: json!({ "operation": "sqrt", "value": "{{input.value}}" }),
+        }];
+        create_tool_macro(
+            &db,
+            "macro-1",
+            &ToolMacroCreate {
+                name: "compute".to_string(),
+                description: "Computes a square root".to_string(),
+                input_schema: json!({ "type": "object" }),
+                steps: steps.clone(),
+                output: Some(json!("{{steps.calc.result}}")),
+            },
+        )
+        .await
+        .unwrap();
+
+        let tool_macro = find_tool_macro(&db, "compute").await.unwrap().unwrap();
+        assert_eq!(tool_macro.id, "macro-1");
+        assert_eq!(tool_macro.steps, steps);
+        assert_eq!(tool_macro.output, Some(json!("{{steps.calc.result}}")));
+        assert!(tool_macro.enabled);
+
+        // Replacing the steps without an output template clears it
+        update_tool_macro(
+            &db,
+            "macro-1",
+            &ToolMacroUpdate {
+                steps: Some(steps),
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let tool_macro = load_tool_macro(&db, "macro-1").await.unwrap().unwrap();
+        assert!(!tool_macro.enabled);
+        assert_eq!(tool_macro.output, None);
+        assert_eq!(list_tool_macros(&db).await.unwrap().len(), 1);
+
+        delete_tool_macro(&db, "macro-1").await.unwrap();
+        assert!(find_tool_macro(&db, "compute").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,284 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! MacroTool implementation.
+//!
+//! Exposes an enabled macro to agents: its definition comes from the
+//! `macro_tool` record and each call runs the step tools in order, each one
+//! bounded by its registry timeout.
+
+use super::{is_step_tool, macro_name, resolve, store, tool_name};
+use crate::db::DBClient;
+use crate::models::macro_tool::{MacroStep, ToolMacro};
+use crate::tools::limits::run_with_limits;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration, TOOL_REGISTRY,
+};
+use crate::tools::script::check_input;
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Tool running the steps of a macro.
+pub struct MacroTool {
+    /// Definition built from the macro
+    definition: ToolDefinition,
+    /// Steps of the macro
+    steps: Vec<MacroStep>,
+    /// Output template (None = result of the last step)
+    output: Option<Value>,
+    /// Tool of each step, in step order
+    tools: Vec<Arc<dyn Tool>>,
+}
+
+impl MacroTool {
+    /// Creates a tool from a macro and the tools of its steps.
+    ///
+    /// # Arguments
+    /// * `tool_macro` - Macro definition
+    /// * `tools` - Tool of each step, in step order
+    pub fn new(tool_macro: &ToolMacro, tools: Vec<Arc<dyn Tool>>) -> Self {
+        let sequence: Vec<&str> = tool_macro
+            .steps
+            .iter()
+            .map(|step| step.tool.as_str())
+            .collect();
+        Self {
+            definition: ToolDefinition {
+                id: tool_name(&tool_macro.name),
+                name: tool_macro.name.clone(),
+                description: format!(
+                    "{}\n\nRuns in one call: {}",
+                    tool_macro.description,
+                    sequence.join(" -> ")
+                ),
+                input_schema: tool_macro.input_schema.clone(),
+                output_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "success": { "type": "boolean" },
+                        "steps": { "type": "array", "items": { "type": "string" } },
+                        "result": { "description": "Output of the macro" }
+                    }
+                }),
+                requires_confirmation: tools.iter().any(|tool| tool.requires_confirmation()),
+            },
+            steps: tool_macro.steps.clone(),
+            output: tool_macro.output.clone(),
+            tools,
+        }
+    }
+
+    /// Loads an enabled macro.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `name` - Macro name (without the `macro__` prefix)
+    pub async fn load_macro(db: &DBClient, name: &str) -> Result<ToolMacro, String> {
+        let tool_macro = store::find_tool_macro(db, name)
+            .await?
+            .ok_or_else(|| format!("Macro tool '{}' not found", name))?;
+        if !tool_macro.enabled {
+            return Err(format!(
+                "Macro tool '{}' is disabled in Settings > Tools",
+                name
+            ));
+        }
+        Ok(tool_macro)
+    }
+
+    /// Creates the tool of an enabled macro, with one tool per step.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let name = macro_name(ctx.tool_name)
+                .ok_or_else(|| format!("Invalid macro tool name '{}'", ctx.tool_name))?;
+            let tool_macro = Self::load_macro(&ctx.factory.get_db(), name).await?;
+            let mut step_tools = Vec::with_capacity(tool_macro.steps.len());
+            for step in &tool_macro.steps {
+                // Checked when the macro is saved, this also rules out recursion
+                if !is_step_tool(&step.tool) {
+                    return Err(format!(
+                        "Macro tool '{}', step '{}': tool '{}' cannot be used in a macro",
+                        name, step.id, step.tool
+                    ));
+                }
+                let tool = ctx
+                    .factory
+                    .create_tool(
+                        &step.tool,
+                        ctx.workflow_id.clone(),
+                        ctx.agent_id.clone(),
+                        ctx.app_handle.clone(),
+                    )
+                    .await
+                    .map_err(|e| format!("Macro tool '{}', step '{}': {}", name, step.id, e))?;
+                step_tools.push(tool);
+            }
+            info!(macro_tool = %name, steps = step_tools.len(), "Macro tool instance created");
+            Ok(Arc::new(Self::new(&tool_macro, step_tools)) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Each step is bounded by the timeout of its tool
+    ToolRegistration::Family {
+        matches: |name| macro_name(name).is_some(),
+        metadata: ToolMetadata {
+            name: "MacroTool",
+            category: ToolCategory::Basic,
+            requires_context: false,
+            default_timeout_secs: None,
+            constructor: MacroTool::build,
+        },
+    }
+}
+
+#[async_trait]
+impl Tool for MacroTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+
+        let mut scope = json!({ "input": input, "steps": Map::new() });
+        let mut last = Value::Null;
+        for (index, (step, tool)) in self.steps.iter().zip(&self.tools).enumerate() {
+            let step_input = resolve(&step.input, &scope);
+            debug!(
+                macro_tool = %self.definition.name,
+                step = %step.id,
+                tool = %step.tool,
+                "Running macro step"
+            );
+
+            let timeout = TOOL_REGISTRY
+                .default_timeout_secs(&step.tool)
+                .map(Duration::from_secs);
+            let output = run_with_limits(&step.tool, timeout, None, tool.execute(step_input))
+                .await
+                .and_then(|result| result)
+                .map_err(|e| {
+                    ToolError::ExecutionFailed(format!(
+                        "Step {} '{}' ({}) failed: {}",
+                        index + 1,
+                        step.id,
+                        step.tool,
+                        e
+                    ))
+                })?;
+            scope["steps"][&step.id] = output.clone();
+            last = output;
+        }
+
+        let result = match &self.output {
+            Some(template) => resolve(template, &scope),
+            None => last,
+        };
+        Ok(json!({
+            "success": true,
+            "steps": self.steps.iter().map(|step| &step.id).collect::<Vec<_>>(),
+            "result": result
+        }))
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        check_input(&self.definition.input_schema, input).map_err(ToolError::InvalidInput)
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        self.definition.requires_confirmation
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::CalculatorTool;
+    use chrono::Utc;
+
+    fn tool_macro(output: Option<Value>) -> ToolMacro {
+        ToolMacro {
+            id: "macro-1".to_string(),
+            name: "hypotenuse".to_string(),
+            description: "Computes a square root, then doubles it".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": { "value": { "type": "number" } },
+                "required": ["value"]
+            }),
+            steps: vec![
+                MacroStep {
+                    id: "root".to_string(),
+                    tool: "CalculatorTool".to_string(),
+                    input: json!({ "operation": "sqrt", "value": "{{input.value}}" }),
+                },
+                MacroStep {
+                    id: "double".to_string(),
+                    tool: "CalculatorTool".to_string(),
+                    input: json!({
+                        "operation": "multiply",
+                        "a": "{{steps.root.result}}",
+                        "b": 2
+                    }),
+                },
+            ],
+            output,
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn macro_tool(output: Option<Value>) -> MacroTool {
+        let calculator: Arc<dyn Tool> = Arc::new(CalculatorTool::new());
+        MacroTool::new(&tool_macro(output), vec![calculator.clone(), calculator])
+    }
+
+    #[tokio::test]
+    async fn test_macro_tool() {
+        let tool = macro_tool(None);
+        let definition = tool.definition();
+        assert_eq!(definition.id, "macro__hypotenuse");
+        assert!(definition
+            .description
+            .ends_with("CalculatorTool -> CalculatorTool"));
+
+        let result = tool.execute(json!({ "value": 16 })).await.unwrap();
+        assert_eq!(result["steps"], json!(["root", "double"]));
+        assert_eq!(result["result"]["result"], json!(8.0));
+
+        let tool = macro_tool(Some(json!({
+            "root": "{{steps.root.result}}",
+            "text": "Twice the root: {{steps.double.result}}"
+        })));
+        let result = tool.execute(json!({ "value": 9 })).await.unwrap();
+        assert_eq!(
+            result["result"],
+            json!({ "root": 3.0, "text": "Twice the root: 6.0" })
+        );
+
+        assert!(matches!(
+            tool.execute(json!({})).await,
+            Err(ToolError::InvalidInput(_))
+        ));
+        let error = tool.execute(json!({ "value": -1 })).await.unwrap_err();
+        assert!(error.to_string().contains("Step 1 'root' (CalculatorTool)"));
+    }
+}
@@ -0,0 +1,582 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Background embedding of memories.
+//!
+//! Memories are stored immediately with `embedding_status = 'pending'` and
+//! embedded here, off the agent's critical path. Jobs run concurrently up to
+//! `EMBEDDING_MAX_CONCURRENT`, retry transient failures with exponential
+//! backoff, then store the vector (`ready`) or give up (`failed`).
+//!
+//! The embedding is only written if the memory content did not change in the
+//! meantime (an update queues a new job). Pending memories left by a previous
+//! session are queued again at startup by [`requeue_pending_embeddings`].
+//!
+//! [`regenerate_embeddings`] re-embeds existing memories in provider batches
+//! and stores its progress as a checkpoint, so an interrupted run resumes
+//! where it stopped.
+
+use crate::db::{queries::settings, DBClient};
+use crate::llm::embedding::EmbeddingService;
+use crate::models::embedding::RegenerateProgress;
+use crate::models::memory::MemoryEmbeddingStatus;
+use crate::security::workflow_vault::ENCRYPTED_PREFIX;
+use crate::tools::constants::memory as mem_constants;
+use futures::stream::{self, StreamExt};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Storage key of the checkpoint of an interrupted regeneration
+pub const REGENERATION_CHECKPOINT_KEY: &str = "settings:embedding_regeneration";
+
+/// Concurrency limit shared by all embedding jobs
+static EMBEDDING_SLOTS: Semaphore = Semaphore::const_new(mem_constants::EMBEDDING_MAX_CONCURRENT);
+
+/// Queues the background embedding of a memory.
+///
+/// Returns the job handle (awaited by tests; callers usually drop it).
+pub fn enqueue_embedding(
+    db: Arc<DBClient>,
+    service: Arc<EmbeddingService>,
+    memory_id: String,
+    content: String,
+) -> JoinHandle<MemoryEmbeddingStatus> {
+    tokio::spawn(async move {
+        let _permit = EMBEDDING_SLOTS.acquire().await;
+        embed_memory(&db, &service, &memory_id, &content).await
+    })
+}
+
+/// Embeds a memory with retries and stores the result.
+async fn embed_memory(
+    db: &DBClient,
+    service: &EmbeddingService,
+    memory_id: &str,
+    content: &str,
+) -> MemoryEmbeddingStatus {
+    let mut attempt = 1;
+    let status = loop {
+        match service.embed(content).await {
+            Ok(embedding) => match store_embedding(db, memory_id, content, &embedding).await {
+                Ok(()) => break MemoryEmbeddingStatus::Ready,
+                Err(e) => {
+                    warn!(memory_id = %memory_id, error = %e, "Failed to store memory embedding");
+                    break MemoryEmbeddingStatus::Failed;
+                }
+            },
+            Err(e) if attempt < mem_constants::EMBEDDING_MAX_ATTEMPTS => {
+                let delay = mem_constants::EMBEDDING_RETRY_BASE_MS * 2u64.pow(attempt - 1);
+                debug!(
+                    memory_id = %memory_id,
+                    attempt = attempt,
+                    delay_ms = delay,
+                    error = %e,
+                    "Memory embedding failed, retrying"
+                );
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                warn!(
+                    memory_id = %memory_id,
+                    attempts = attempt,
+                    error = %e,
+                    "Memory embedding failed, memory stays text-searchable only"
+                );
+                break MemoryEmbeddingStatus::Failed;
+            }
+        }
+    };
+
+    if status == MemoryEmbeddingStatus::Failed {
+        let query = "UPDATE type::thing('memory', $id) SET embedding_status = 'failed' \
+                     WHERE content = $content";
+        let params = vec![
+            ("id".to_string(), serde_json::json!(memory_id)),
+            ("content".to_string(), serde_json::json!(content)),
+        ];
+        if let Err(e) = db.execute_with_params(query, params).await {
+            warn!(memory_id = %memory_id, error = %e, "Failed to mark memory embedding as failed");
+        }
+    }
+    debug!(memory_id = %memory_id, status = %status, "Memory embedding job finished");
+    status
+}
+
+/// Stores the embedding if the memory still has the embedded content.
+async fn store_embedding(
+    db: &DBClient,
+    memory_id: &str,
+    content: &str,
+    embedding: &[f32],
+) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('memory', $id) SET embedding = $embedding, \
+         embedding_status = 'ready' WHERE content = $content",
+        vec![
+            ("id".to_string(), serde_json::json!(memory_id)),
+            ("embedding".to_string(), serde_json::json!(embedding)),
+            ("content".to_string(), serde_json::json!(content)),
+        ],
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Queues again the memories still pending from a previous session.
+///
+/// # Returns
+/// The number of queued memories.
+pub async fn requeue_pending_embeddings(
+    db: &Arc<DBClient>,
+    service: &Arc<EmbeddingService>,
+) -> Result<usize, String> {
+    let pending: Vec<serde_json::Value> = db
+        .query_json(
+            "SELECT meta::id(id) AS id, content FROM memory WHERE embedding_status = 'pending'",
+        )
+        .await
+        .map_err(|e| format!("Failed to load pending embeddings: {}", e))?;
+
+    let mut queued = 0;
+    for memory in &pending {
+        if let (Some(id), Some(content)) = (
+            memory.get("id").and_then(|v| v.as_str()),
+            memory.get("content").and_then(|v| v.as_str()),
+        ) {
+            enqueue_embedding(
+                db.clone(),
+                service.clone(),
+                id.to_string(),
+                content.to_string(),
+            );
+            queued += 1;
+        }
+    }
+
+    if queued > 0 {
+        info!(count = queued, "Pending memory embeddings queued again");
+    }
+    Ok(queued)
+}
+
+/// Memories selected by a regeneration.
+///
+/// Encrypted memories of sensitive workflows are never embedded.
+fn regeneration_filter(
+    progress: &RegenerateProgress,
+) -> (String, Vec<(String, serde_json::Value)>) {
+    let mut conditions = vec!["!string::starts_with(content, $encrypted_prefix)"];
+    if progress.type_filter.is_some() {
+        conditions.push("type = $type");
+    }
+    if progress.missing_only {
+        conditions.push("embedding IS NONE");
+    }
+    if progress.cursor.is_some() {
+        conditions.push("meta::id(id) > $cursor");
+    }
+    let params = vec![
+        ("encrypted_prefix".to_string(), json!(ENCRYPTED_PREFIX)),
+        ("type".to_string(), json!(progress.type_filter)),
+        ("cursor".to_string(), json!(progress.cursor)),
+    ];
+    (conditions.join(" AND "), params)
+}
+
+/// Creates the progress of a new regeneration, counting the memories to embed.
+///
+/// With `missing_only`, only the memories without embedding are selected.
+pub async fn start_regeneration(
+    db: &DBClient,
+    type_filter: Option<String>,
+    missing_only: bool,
+) -> Result<RegenerateProgress, String> {
+    let mut progress = RegenerateProgress {
+        type_filter,
+        missing_only,
+        ..Default::default()
+    };
+    let (filter, params) = regeneration_filter(&progress);
+    let rows: Vec<serde_json::Value> = db
+        .query_json_with_params(
+            &format!(
+                "SELECT count() AS count FROM memory WHERE {} GROUP ALL",
+                filter
+            ),
+            params,
+        )
+        .await
+        .map_err(|e| format!("Failed to count memories: {}", e))?;
+    progress.total = rows
+        .first()
+        .and_then(|row| row["count"].as_u64())
+        .unwrap_or(0) as usize;
+    Ok(progress)
+}
+
+/// Loads the checkpoint of an interrupted regeneration.
+pub async fn load_regeneration_checkpoint(
+    db: &DBClient,
+) -> Result<Option<RegenerateProgress>, String> {
+    settings::load_config(db, REGENERATION_CHECKPOINT_KEY)
+        .await
+        .map_err(|e| format!("Failed to load regeneration checkpoint: {}", e))?
+        .map(|config| {
+            serde_json::from_value(config)
+                .map_err(|e| format!("Failed to parse regeneration checkpoint: {}", e))
+        })
+        .transpose()
+}
+
+async fn save_regeneration_checkpoint(
+    db: &DBClient,
+    progress: Option<&RegenerateProgress>,
+) -> Result<(), String> {
+    settings::save_config(db, REGENERATION_CHECKPOINT_KEY, &json!(progress))
+        .await
+        .map_err(|e| format!("Failed to save regeneration checkpoint: {}", e))
+}
+
+/// Regenerates the embeddings of the memories after the progress cursor.
+///
+/// Memories are read in ID order by pages of `REGENERATION_CONCURRENCY`
+/// batches; the batches of a page are embedded concurrently. The progress is
+/// reported after each batch and stored as checkpoint after each page; the
+/// checkpoint is cleared once every memory was processed.
+pub async fn regenerate_embeddings<F>(
+    db: &DBClient,
+    service: &EmbeddingService,
+    mut progress: RegenerateProgress,
+    on_progress: F,
+) -> Result<RegenerateProgress, String>
+where
+    F: Fn(&RegenerateProgress),
+{
+    let page_size =
+        mem_constants::REGENERATION_BATCH_SIZE * mem_constants::REGENERATION_CONCURRENCY;
+    loop {
+        let (filter, mut params) = regeneration_filter(&progress);
+        params.push(("limit".to_string(), json!(page_size)));
+        let rows: Vec<serde_json::Value> = db
+            .query_json_with_params(
+                &format!(
+                    "SELECT meta::id(id) AS id, content FROM memory WHERE {} \
+                     ORDER BY id LIMIT $limit",
+                    filter
+                ),
+                params,
+            )
+            .await
+            .map_err(|e| format!("Failed to load memories: {}", e))?;
+        let Some(last_id) = rows
+            .last()
+            .and_then(|row| row["id"].as_str())
+            .map(String::from)
+        else {
+            break;
+        };
+
+        let memories: Vec<(String, String)> = rows
+            .iter()
+            .filter_map(|row| {
+                Some((
+                    row["id"].as_str()?.to_string(),
+                    row["content"].as_str()?.to_string(),
+                ))
+            })
+            .collect();
+        progress.failed += rows.len() - memories.len();
+        progress.processed += rows.len() - memories.len();
+
+        let batches: Vec<_> = memories
+            .chunks(mem_constants::REGENERATION_BATCH_SIZE)
+            .map(|batch| regenerate_batch(db, service, batch))
+            .collect();
+        let mut batches =
+            stream::iter(batches).buffer_unordered(mem_constants::REGENERATION_CONCURRENCY);
+        while let Some((success, failed)) = batches.next().await {
+            progress.processed += success + failed;
+            progress.success += success;
+            progress.failed += failed;
+            on_progress(&progress);
+        }
+
+        progress.cursor = Some(last_id);
+        save_regeneration_checkpoint(db, Some(&progress)).await?;
+        if rows.len() < page_size {
+            break;
+        }
+    }
+
+    save_regeneration_checkpoint(db, None).await?;
+    info!(
+        processed = progress.processed,
+        success = progress.success,
+        failed = progress.failed,
+        "Embedding regeneration completed"
+    );
+    Ok(progress)
+}
+
+/// Embeds a batch of memories and stores the vectors.
+///
+/// A failed batch request is retried text by text, so one invalid memory
+/// does not fail the others. Returns the success and failure counts.
+async fn regenerate_batch(
+    db: &DBClient,
+    service: &EmbeddingService,
+    batch: &[(String, String)],
+) -> (usize, usize) {
+    let texts: Vec<&str> = batch.iter().map(|(_, content)| content.as_str()).collect();
+    let embeddings = match service.embed_batch(&texts).await {
+        Ok(embeddings) if embeddings.len() == texts.len() => {
+            embeddings.into_iter().map(Ok).collect()
+        }
+        result => {
+            debug!(
+                batch_size = texts.len(),
+                error = ?result.err(),
+                "Batch embedding failed, embedding memories one by one"
+            );
+            let mut embeddings = Vec::with_capacity(texts.len());
+            for text in &texts {
+                embeddings.push(service.embed(text).await);
+            }
+            embeddings
+        }
+    };
+
+    let (mut success, mut failed) = (0, 0);
+    for ((memory_id, content), embedding) in batch.iter().zip(embeddings) {
+        let stored = match embedding {
+            Ok(embedding) => store_embedding(db, memory_id, content, &embedding).await,
+            Err(e) => Err(e.to_string()),
+        };
+        match stored {
+            Ok(()) => success += 1,
+            Err(e) => {
+                warn!(memory_id = %memory_id, error = %e, "Failed to regenerate embedding");
+                failed += 1;
+            }
+        }
+    }
+    (success, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::llm::embedding::EmbeddingProvider;
+    use crate::mcp::test_server::{http, json_reply, spawn_mock_server};
+    use crate::models::MemoryType;
+    use crate::tools::memory::{
+        add_memory_core, search_memories_core, AddMemoryParams, SearchParams,
+    };
+    use tempfile::tempdir;
+
+    async fn setup_db() -> (Arc<DBClient>, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("embedding_queue_db");
+        let db = DBClient::new(db_path.to_str().unwrap())
+            .await
+            .expect("Failed to create DB");
+        db.initialize_schema().await.expect("Failed to init schema");
+        (Arc::new(db), temp_dir)
+    }
+
+    /// Embedding service backed by a mock Ollama server
+    async fn mock_service(healthy: bool) -> Arc<EmbeddingService> {
+        let base = spawn_mock_server(move |_| {
+            if healthy {
+                json_reply(&[], serde_json::json!({"embedding": vec![0.1_f32; 1024]}))
+            } else {
+                http("500 Internal Server Error", &[], "model not loaded")
+            }
+        })
+        .await;
+        Arc::new(EmbeddingService::with_provider(
+            EmbeddingProvider::ollama_with_config(&base, "mxbai-embed-large"),
+        ))
+    }
+
+    fn params(content: &str) -> AddMemoryParams {
+        AddMemoryParams {
+            memory_type: MemoryType::Knowledge,
+            content: content.to_string(),
+            metadata: serde_json::json!({}),
+            workflow_id: None,
+            importance: 0.5,
+            expires_at: None,
+        }
+    }
+
+    async fn status_of(db: &DBClient, memory_id: &str) -> (Option<String>, bool) {
+        let rows: Vec<serde_json::Value> = db
+            .query_json_with_params(
+                "SELECT embedding_status, embedding IS NOT NONE AS embedded \
+                 FROM type::thing('memory', $id)",
+                vec![("id".to_string(), serde_json::json!(memory_id))],
+            )
+            .await
+            .unwrap();
+        let row = &rows[0];
+        (
+            row["embedding_status"].as_str().map(String::from),
+            row["embedded"].as_bool().unwrap_or(false),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_add_memory_embeds_in_background() {
+        let (db, _temp) = setup_db().await;
+        let service = mock_service(true).await;
+
+        let result = add_memory_core(params("SurrealDB has HNSW"), &db, Some(&service))
+            .await
+            .unwrap();
+        assert_eq!(
+            result.embedding_status,
+            Some(MemoryEmbeddingStatus::Pending)
+        );
+
+        let mut state = status_of(&db, &result.memory_id).await;
+        for _ in 0..50 {
+            if state.0.as_deref() == Some("ready") {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            state = status_of(&db, &result.memory_id).await;
+        }
+        assert_eq!(state, (Some("ready".to_string()), true));
+
+        // Without service: stored text-only, no status
+        let result = add_memory_core(params("Text only"), &db, None)
+            .await
+            .unwrap();
+        assert_eq!(result.embedding_status, None);
+        assert_eq!(status_of(&db, &result.memory_id).await, (None, false));
+    }
+
+    #[tokio::test]
+    async fn test_embedding_job_failure_and_stale_content() {
+        let (db, _temp) = setup_db().await;
+        let result = add_memory_core(params("Original content"), &db, None)
+            .await
+            .unwrap();
+        db.execute_with_params(
+            "UPDATE type::thing('memory', $id) SET embedding_status = 'pending'",
+            vec![("id".to_string(), serde_json::json!(result.memory_id))],
+        )
+        .await
+        .unwrap();
+
+        // Content changed since the job was queued: nothing is written
+        let status = enqueue_embedding(
+            db.clone(),
+            mock_service(true).await,
+            result.memory_id.clone(),
+            "Outdated content".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, MemoryEmbeddingStatus::Ready);
+        assert_eq!(
+            status_of(&db, &result.memory_id).await,
+            (Some("pending".to_string()), false)
+        );
+
+        // Provider keeps failing: marked failed after retries
+        let status = enqueue_embedding(
+            db.clone(),
+            mock_service(false).await,
+            result.memory_id.clone(),
+            "Original content".to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, MemoryEmbeddingStatus::Failed);
+        assert_eq!(
+            status_of(&db, &result.memory_id).await,
+            (Some("failed".to_string()), false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_vector_search_includes_unembedded_memories() {
+        let (db, _temp) = setup_db().await;
+        let service = mock_service(true).await;
+        add_memory_core(params("Queued fact about rust"), &db, None)
+            .await
+            .unwrap();
+
+        let (results, search_type) = search_memories_core(
+            SearchParams {
+                query_text: "rust".to_string(),
+                limit: 10,
+                type_filter: None,
+                workflow_id: None,
+                scope: "both".to_string(),
+                threshold: 0.7,
+            },
+            &db,
+            Some(&service),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(search_type, "hybrid");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["content"], "Queued fact about rust");
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_embeddings_resumes_after_cursor() {
+        let (db, _temp) = setup_db().await;
+        let mut ids = Vec::new();
+        for content in ["First fact", "Second fact", "Third fact"] {
+            let result = add_memory_core(params(content), &db, None).await.unwrap();
+            ids.push(result.memory_id);
+        }
+        ids.sort();
+
+        let mut progress = start_regeneration(&db, None, false).await.unwrap();
+        assert_eq!(progress.total, 3);
+
+        // Interrupted run: the first memory was already processed
+        progress.cursor = Some(ids[0].clone());
+        progress.processed = 1;
+        progress.success = 1;
+
+        let service = mock_service(true).await;
+        let reports = std::sync::Mutex::new(Vec::new());
+        let result = regenerate_embeddings(&db, &service, progress, |p| {
+            reports.lock().unwrap().push(p.processed)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!((result.processed, result.success, result.failed), (3, 3, 0));
+        assert_eq!(reports.into_inner().unwrap(), vec![3]);
+        assert_eq!(status_of(&db, &ids[0]).await, (None, false));
+        for id in &ids[1..] {
+            assert_eq!(status_of(&db, id).await, (Some("ready".to_string()), true));
+        }
+        assert_eq!(load_regeneration_checkpoint(&db).await.unwrap(), None);
+    }
+}
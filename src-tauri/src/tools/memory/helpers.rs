@@ -18,10 +18,13 @@
 //! and the memory Tauri commands. It eliminates code duplication for
 //! add_memory, search, and describe operations.
 
+use super::embedding_queue::enqueue_embedding;
 use crate::db::DBClient;
 use crate::llm::embedding::EmbeddingService;
-use crate::models::memory::MemoryDescribeResult;
-use crate::models::{Memory, MemoryCreate, MemoryCreateWithEmbedding, MemoryType};
+use crate::models::memory::{MemoryDescribeResult, MemoryEmbeddingStatus};
+use crate::models::{Memory, MemoryCreate, MemoryType};
+use crate::security::workflow_vault::{is_encrypted, ENCRYPTED_PREFIX};
+use crate::security::WORKFLOW_VAULT;
 use crate::tools::constants::memory as mem_constants;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
@@ -56,17 +59,19 @@ pub struct AddMemoryParams {
 pub struct AddMemoryResult {
     /// The UUID of the created memory
     pub memory_id: String,
-    /// Whether an embedding was successfully generated
-    pub embedding_generated: bool,
+    /// Embedding state (`Pending` when queued, None without embedding service)
+    pub embedding_status: Option<MemoryEmbeddingStatus>,
 }
 
 /// Core logic for adding a memory with optional embedding.
 ///
 /// This function handles the common pattern of:
 /// 1. Generating UUID
-/// 2. Attempting embedding generation (if service available)
-/// 3. Falling back to text-only storage on embedding failure
-/// 4. Creating the database record
+/// 2. Creating the database record immediately
+/// 3. Queuing the embedding in the background (if service available)
+///
+/// The memory is text-searchable right away and becomes vector-searchable
+/// once the queued embedding is stored (see [`super::embedding_queue`]).
 ///
 /// # Arguments
 /// * `params` - Pre-validated memory parameters
@@ -89,85 +94,69 @@ pub struct AddMemoryResult {
 /// ```
 pub async fn add_memory_core(
     params: AddMemoryParams,
-    db: &DBClient,
+    db: &Arc<DBClient>,
     embedding_service: Option<&Arc<EmbeddingService>>,
 ) -> Result<AddMemoryResult, String> {
     let memory_id = Uuid::new_v4().to_string();
 
-    let embedding_generated = if let Some(embed_svc) = embedding_service {
-        match embed_svc.embed(&params.content).await {
-            Ok(embedding) => {
-                // Create memory with embedding using unified builder
-                let memory = MemoryCreateWithEmbedding::build(
-                    params.memory_type.clone(),
-                    params.content.clone(),
-                    embedding,
-                    params.metadata.clone(),
-                    params.workflow_id.clone(),
-                    params.importance,
-                    params.expires_at,
-                );
-
-                db.create("memory", &memory_id, memory)
-                    .await
-                    .map_err(|e| format!("Failed to create memory with embedding: {}", e))?;
-
-                // Set expires_at separately (SurrealDB datetime cast)
-                set_expires_at_if_present(db, &memory_id, params.expires_at).await?;
-
-                true
-            }
-            Err(e) => {
-                // Fallback to text-only storage
-                warn!(error = %e, "Embedding generation failed, storing without embedding");
-
-                create_memory_without_embedding(db, &memory_id, &params).await?;
-                false
-            }
+    // Memories of a sensitive workflow are stored encrypted and never embedded
+    let mut content = params.content.clone();
+    let mut embedding_service = embedding_service;
+    if let Some(workflow_id) = &params.workflow_id {
+        if WORKFLOW_VAULT.is_sensitive(db, workflow_id).await? {
+            content = WORKFLOW_VAULT.encrypt(workflow_id, &content)?;
+            embedding_service = None;
         }
-    } else {
-        // No embedding service, store text only
-        create_memory_without_embedding(db, &memory_id, &params).await?;
-        false
-    };
-
-    info!(
-        memory_id = %memory_id,
-        memory_type = %params.memory_type,
-        embedding = embedding_generated,
-        workflow_id = ?params.workflow_id,
-        "Memory created via helper"
-    );
-
-    Ok(AddMemoryResult {
-        memory_id,
-        embedding_generated,
-    })
-}
+    }
+    let embedding_status = embedding_service.map(|_| MemoryEmbeddingStatus::Pending);
 
-/// Helper to create a memory record without embedding.
-async fn create_memory_without_embedding(
-    db: &DBClient,
-    memory_id: &str,
-    params: &AddMemoryParams,
-) -> Result<(), String> {
-    let memory = MemoryCreate::build(
+    let mut memory = MemoryCreate::build(
         params.memory_type.clone(),
-        params.content.clone(),
+        content,
         params.metadata.clone(),
         params.workflow_id.clone(),
         params.importance,
         params.expires_at,
     );
+    memory.embedding_status = embedding_status;
 
-    db.create("memory", memory_id, memory)
+    db.create("memory", &memory_id, memory)
         .await
         .map_err(|e| format!("Failed to create memory: {}", e))?;
 
     // Set expires_at separately using datetime cast (SurrealDB rejects ISO strings for datetime fields)
-    set_expires_at_if_present(db, memory_id, params.expires_at).await?;
+    set_expires_at_if_present(db, &memory_id, params.expires_at).await?;
 
-    Ok(())
+    if let Some(embed_svc) = embedding_service {
+        enqueue_embedding(
+            db.clone(),
+            embed_svc.clone(),
+            memory_id.clone(),
+            params.content.clone(),
+        );
+    }
+
+    info!(
+        memory_id = %memory_id,
+        memory_type = %params.memory_type,
+        embedding_status = ?embedding_status,
+        workflow_id = ?params.workflow_id,
+        "Memory created via helper"
+    );
+
+    Ok(AddMemoryResult {
+        memory_id,
+        embedding_status,
+    })
+}
+
+/// Decrypts the content of a memory of a sensitive workflow for display.
+///
+/// Memories of a closed sensitive workflow show a placeholder.
+pub fn reveal_memory(memory: &mut Memory) {
+    if let Some(workflow_id) = &memory.workflow_id {
+        memory.content = WORKFLOW_VAULT.reveal(workflow_id, &memory.content);
+    }
 }
 
 /// Sets expires_at on a memory record if a value is provided.
@@ -180,16 +169,15 @@ async fn set_expires_at_if_present(
     expires_at: Option<DateTime<Utc>>,
 ) -> Result<(), String> {
     if let Some(expires) = expires_at {
-        let query = format!(
-            "UPDATE memory:`{}` SET expires_at = <datetime>$expires_at",
-            memory_id
-        );
         db.execute_with_params(
-            &query,
-            vec![(
-                "expires_at".to_string(),
-                serde_json::json!(expires.to_rfc3339()),
-            )],
+            "UPDATE type::thing('memory', $id) SET expires_at = <datetime>$expires_at",
+            vec![
+                ("id".to_string(), serde_json::json!(memory_id)),
+                (
+                    "expires_at".to_string(),
+                    serde_json::json!(expires.to_rfc3339()),
+                ),
+            ],
         )
         .await
         .map_err(|e| format!("Failed to set expires_at: {}", e))?;
@@ -250,6 +238,8 @@ pub fn expiration_filter() -> String {
 ///
 /// If an EmbeddingService is available, attempts vector search first.
 /// Falls back to text search on embedding failure or unavailability.
+/// Memories whose embedding is still pending (or failed) are matched by
+/// text and appended after the vector results (`search_type = "hybrid"`).
 pub async fn search_memories_core(
     params: SearchParams,
     db: &DBClient,
@@ -272,7 +262,7 @@ pub async fn search_memories_core(
                     db,
                 )
                 .await?;
-                return Ok((results, "vector".to_string()));
+                return merge_unembedded_matches(results, &params, limit, db).await;
             }
             Err(e) => {
                 warn!(error = %e, "Query embedding failed, falling back to text search");
@@ -287,12 +277,48 @@ pub async fn search_memories_core(
         params.type_filter.as_deref(),
         &params.workflow_id,
         &params.scope,
+        false,
         db,
     )
     .await?;
     Ok((results, "text".to_string()))
 }
 
+/// Appends text matches among memories without embedding to vector results.
+///
+/// Keeps freshly added memories findable while their embedding is queued.
+async fn merge_unembedded_matches(
+    mut results: Vec<serde_json::Value>,
+    params: &SearchParams,
+    limit: usize,
+    db: &DBClient,
+) -> Result<(Vec<serde_json::Value>, String), String> {
+    if results.len() >= limit {
+        return Ok((results, "vector".to_string()));
+    }
+
+    let unembedded = text_search_core(
+        &params.query_text,
+        limit - results.len(),
+        params.type_filter.as_deref(),
+        &params.workflow_id,
+        &params.scope,
+        true,
+        db,
+    )
+    .await?;
+    if unembedded.is_empty() {
+        return Ok((results, "vector".to_string()));
+    }
+
+    debug!(
+        count = unembedded.len(),
+        "Unembedded memories matched by text"
+    );
+    results.extend(unembedded);
+    Ok((results, "hybrid".to_string()))
+}
+
 /// Performs vector similarity search using HNSW index with composite scoring.
 ///
 /// Scoring formula:
@@ -319,17 +345,9 @@ pub async fn vector_search_core(
     }
 
     let where_clause = conditions.join(" AND ");
-    let similarity_threshold = threshold;
-
-    // Pre-allocate embedding string
-    let mut embedding_str = String::with_capacity(query_embedding.len() * 12);
-    for (i, v) in query_embedding.iter().enumerate() {
-        if i > 0 {
-            embedding_str.push_str(", ");
-        }
-        use std::fmt::Write;
-        let _ = write!(embedding_str, "{}", v);
-    }
+    params.push(("embedding".to_string(), serde_json::json!(query_embedding)));
+    params.push(("threshold".to_string(), serde_json::json!(threshold)));
+    params.push(("limit".to_string(), serde_json::json!(limit)));
 
     // Composite scoring: cosine * 0.7 + importance * 0.15 + recency * 0.15
     let query = format!(
@@ -341,9 +359,10 @@ pub async fn vector_search_core(
             metadata,
             importance,
             expires_at,
+            embedding_status,
             created_at,
-            vector::similarity::cosine(embedding, [{embedding}]) AS cosine_score,
-            (vector::similarity::cosine(embedding, [{embedding}]) * {w_cosine}
+            vector::similarity::cosine(embedding, $embedding) AS cosine_score,
+            (vector::similarity::cosine(embedding, $embedding) * {w_cosine}
              + importance * {w_importance}
              + (1.0 - math::clamp(
                  duration::secs(time::now() - created_at) / ({decay_days} * 24.0 * 3600.0),
@@ -353,17 +372,14 @@ pub async fn vector_search_core(
             ) AS score
         FROM memory
         WHERE {where_clause}
-          AND vector::similarity::cosine(embedding, [{embedding}]) > {similarity}
+          AND vector::similarity::cosine(embedding, $embedding) > $threshold
         ORDER BY score DESC
-        LIMIT {limit}"#,
-        embedding = embedding_str,
+        LIMIT $limit"#,
         w_cosine = mem_constants::SCORE_WEIGHT_COSINE,
         w_importance = mem_constants::SCORE_WEIGHT_IMPORTANCE,
         w_recency = mem_constants::SCORE_WEIGHT_RECENCY,
         decay_days = mem_constants::RECENCY_DECAY_DAYS,
-        where_clause = where_clause,
-        similarity = similarity_threshold,
-        limit = limit
+        where_clause = where_clause
     );
 
     let results: Vec<serde_json::Value> =
@@ -385,23 +401,45 @@ pub async fn vector_search_core(
 }
 
 /// Performs text-based search as fallback when embeddings are unavailable.
+///
+/// With `unembedded_only`, only memories without embedding are matched.
+/// Encrypted memories are only matched while their workflow is open.
 pub async fn text_search_core(
     query_text: &str,
     limit: usize,
     type_filter: Option<&str>,
     workflow_id: &Option<String>,
     scope: &str,
+    unembedded_only: bool,
     db: &DBClient,
 ) -> Result<Vec<serde_json::Value>, String> {
     let mut conditions = Vec::new();
     let mut params: Vec<(String, serde_json::Value)> = Vec::new();
 
-    conditions
-        .push("string::lowercase(content) CONTAINS string::lowercase($query_text)".to_string());
+    // Encrypted memories cannot be matched by the database: those of the open
+    // sensitive workflows are loaded and matched after decryption, the others
+    // are excluded
+    let unlocked = WORKFLOW_VAULT.unlocked_workflows();
+    conditions.push(
+        "((string::lowercase(content) CONTAINS string::lowercase($query_text) \
+         AND !string::starts_with(content, $encrypted_prefix)) \
+         OR (string::starts_with(content, $encrypted_prefix) AND workflow_id IN $unlocked))"
+            .to_string(),
+    );
     params.push(("query_text".to_string(), serde_json::json!(query_text)));
+    params.push((
+        "encrypted_prefix".to_string(),
+        serde_json::json!(ENCRYPTED_PREFIX),
+    ));
+    params.push(("unlocked".to_string(), serde_json::json!(unlocked)));
+    params.push(("limit".to_string(), serde_json::json!(limit)));
 
     conditions.push(expiration_filter());
 
+    if unembedded_only {
+        conditions.push("embedding IS NONE".to_string());
+    }
+
     if let Some(scope_cond) = build_scope_condition(scope, workflow_id, &mut params) {
         conditions.push(scope_cond);
     }
@@ -422,12 +460,19 @@ pub async fn text_search_core(
             metadata,
             importance,
             expires_at,
+            embedding_status,
             created_at
         FROM memory
         WHERE {}
         ORDER BY created_at DESC
-        LIMIT {}"#,
-        where_clause, limit
+        {}"#,
+        where_clause,
+        // Decrypted memories are filtered below, so the limit is applied after
+        if unlocked.is_empty() {
+            "LIMIT $limit"
+        } else {
+            ""
+        }
     );
 
     let memories: Vec<Memory> = db.query_with_params(&query, params).await.map_err(|e| {
@@ -439,6 +484,21 @@ pub async fn text_search_core(
     let query_lower = query_text.to_lowercase();
     let results: Vec<serde_json::Value> = memories
         .into_iter()
+        .filter_map(|mut m| {
+            if is_encrypted(&m.content) {
+                let workflow_id = m.workflow_id.as_deref()?;
+                m.content = WORKFLOW_VAULT.decrypt(workflow_id, &m.content).ok()?;
+                if !m
+                    .content
+                    .to_lowercase()
+                    .contains(&query_text.to_lowercase())
+                {
+                    return None;
+                }
+            }
+            Some(m)
+        })
+        .take(limit)
         .map(|m| {
             let content_lower = m.content.to_lowercase();
             let occurrences = content_lower.matches(&query_lower).count();
@@ -456,6 +516,7 @@ pub async fn text_search_core(
                 "metadata": m.metadata,
                 "importance": m.importance,
                 "expires_at": m.expires_at,
+                "embedding_status": m.embedding_status,
                 "created_at": m.created_at,
                 "score": score
             })
@@ -476,8 +537,8 @@ pub async fn describe_memories_core(
     // Build scope filter
     let scope_filter = match scope {
         "workflow" => {
-            if let Some(wf_id) = workflow_id {
-                format!("AND workflow_id = '{}'", wf_id)
+            if workflow_id.is_some() {
+                "AND workflow_id = $workflow_id"
             } else {
                 return Ok(MemoryDescribeResult {
                     total: 0,
@@ -490,18 +551,19 @@ pub async fn describe_memories_core(
                 });
             }
         }
-        "general" => "AND workflow_id IS NONE".to_string(),
+        "general" => "AND workflow_id IS NONE",
         _ => {
             // "both" - workflow + general
-            if let Some(wf_id) = workflow_id {
-                format!("AND (workflow_id = '{}' OR workflow_id IS NONE)", wf_id)
+            if workflow_id.is_some() {
+                "AND (workflow_id = $workflow_id OR workflow_id IS NONE)"
             } else {
-                String::new() // No filter needed if no workflow
+                "" // No filter needed if no workflow
             }
         }
     };
 
     let expiry = expiration_filter();
+    let params = vec![("workflow_id".to_string(), serde_json::json!(workflow_id))];
 
     // Count by type
     let type_query = format!(
@@ -509,7 +571,7 @@ pub async fn describe_memories_core(
         expiry, scope_filter
     );
     let type_results: Vec<serde_json::Value> = db
-        .query_json(&type_query)
+        .query_json_with_params(&type_query, params.clone())
         .await
         .map_err(|e| format!("Failed to count by type: {}", e))?;
 
@@ -531,7 +593,7 @@ pub async fn describe_memories_core(
         expiry, scope_filter
     );
     let tags_results: Vec<serde_json::Value> = db
-        .query_json(&tags_query)
+        .query_json_with_params(&tags_query, params.clone())
         .await
         .map_err(|e| format!("Failed to get tags: {}", e))?;
 
@@ -552,7 +614,7 @@ pub async fn describe_memories_core(
         expiry, scope_filter
     );
     let date_results: Vec<serde_json::Value> = db
-        .query_json(&date_query)
+        .query_json_with_params(&date_query, params.clone())
         .await
         .map_err(|e| format!("Failed to get date range: {}", e))?;
 
@@ -576,7 +638,7 @@ pub async fn describe_memories_core(
         expiry, scope_filter
     );
     let wf_count_results: Vec<serde_json::Value> = db
-        .query_json(&wf_count_query)
+        .query_json_with_params(&wf_count_query, params)
         .await
         .map_err(|e| format!("Failed to count workflow memories: {}", e))?;
 
@@ -638,10 +700,13 @@ mod tests {
     fn test_add_memory_result_fields() {
         let result = AddMemoryResult {
             memory_id: "test-uuid".to_string(),
-            embedding_generated: true,
+            embedding_status: Some(MemoryEmbeddingStatus::Pending),
         };
 
         assert_eq!(result.memory_id, "test-uuid");
-        assert!(result.embedding_generated);
+        assert_eq!(
+            result.embedding_status,
+            Some(MemoryEmbeddingStatus::Pending)
+        );
     }
 }
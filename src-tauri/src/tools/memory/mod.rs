@@ -23,6 +23,7 @@
 //! - Semantic search using vector embeddings (HNSW index)
 //! - Workflow-scoped memory isolation
 //! - Multiple memory types (user_pref, context, knowledge, decision)
+//! - Background embedding queue (memories are stored before being embedded)
 //!
 //! ## Architecture
 //!
@@ -63,8 +64,16 @@
 //! })).await?;
 //! ```
 
+mod embedding_queue;
 mod helpers;
 mod tool;
 
-pub use helpers::{add_memory_core, search_memories_core, AddMemoryParams, SearchParams};
+pub use embedding_queue::{
+    enqueue_embedding, load_regeneration_checkpoint, regenerate_embeddings,
+    requeue_pending_embeddings, start_regeneration,
+};
+pub use helpers::{
+    add_memory_core, expiration_filter, reveal_memory, search_memories_core, AddMemoryParams,
+    SearchParams,
+};
 pub use tool::MemoryTool;
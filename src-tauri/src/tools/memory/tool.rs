@@ -16,11 +16,15 @@
 //!
 //! This tool allows agents to manage memories with semantic search capabilities
 //! using vector embeddings and SurrealDB's HNSW index.
+//!
+//! Agents with attached collections (knowledge packs) search those
+//! collections plus the workflow memory by default (`scope = "collections"`).
 
 use super::helpers::{
-    add_memory_core, build_scope_condition, describe_memories_core, search_memories_core,
-    AddMemoryParams, SearchParams,
+    add_memory_core, build_scope_condition, describe_memories_core, reveal_memory,
+    search_memories_core, AddMemoryParams, SearchParams,
 };
+use crate::agents::knowledge::{attached_packs, retrieve};
 use crate::db::DBClient;
 use crate::llm::embedding::EmbeddingService;
 use crate::models::memory::{Memory, MemoryType};
@@ -28,6 +32,10 @@ use crate::tools::constants::memory::{
     self as mem_constants, DEFAULT_LIMIT, DEFAULT_SIMILARITY_THRESHOLD, MAX_CONTENT_LENGTH,
     MAX_LIMIT, VALID_TYPES,
 };
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
 use crate::tools::response::ResponseBuilder;
 use crate::tools::utils::{
     db_error, delete_with_check, validate_enum_value, validate_length, validate_not_empty,
@@ -232,7 +240,7 @@ impl MemoryTool {
         info!(
             memory_id = %result.memory_id,
             memory_type = %memory_type,
-            embedding = result.embedding_generated,
+            embedding_status = ?result.embedding_status,
             scope = ?workflow_id,
             "Memory created"
         );
@@ -241,7 +249,10 @@ impl MemoryTool {
             .success(true)
             .id("memory_id", result.memory_id)
             .field("type", memory_type)
-            .field("embedding_generated", result.embedding_generated)
+            .field(
+                "embedding_status",
+                result.embedding_status.map(|s| s.to_string()),
+            )
             .field("workflow_id", workflow_id)
             .field("importance", importance)
             .message("Memory created successfully")
@@ -275,10 +286,13 @@ impl MemoryTool {
             .map_err(db_error)?;
 
         match results.into_iter().next() {
-            Some(memory) => Ok(serde_json::json!({
-                "success": true,
-                "memory": memory
-            })),
+            Some(mut memory) => {
+                reveal_memory(&mut memory);
+                Ok(serde_json::json!({
+                    "success": true,
+                    "memory": memory
+                }))
+            }
             None => Err(ToolError::NotFound(format!(
                 "Memory '{}' does not exist. Use 'list' to see available memories",
                 memory_id
@@ -353,15 +367,17 @@ impl MemoryTool {
             FROM memory
             {}
             ORDER BY created_at DESC
-            LIMIT {}"#,
-            where_clause, limit
+            LIMIT $limit"#,
+            where_clause
         );
+        params.push(("limit".to_string(), serde_json::json!(limit)));
 
-        let memories: Vec<Memory> = self
+        let mut memories: Vec<Memory> = self
             .db
             .query_with_params(&query, params)
             .await
             .map_err(db_error)?;
+        memories.iter_mut().for_each(reveal_memory);
 
         debug!(count = memories.len(), scope = %scope, mode = %mode, "Memories listed");
 
@@ -431,7 +447,8 @@ impl MemoryTool {
     /// * `limit` - Maximum results (default: 10)
     /// * `type_filter` - Optional type filter
     /// * `threshold` - Similarity threshold 0-1 (default: 0.7)
-    /// * `scope` - Scope filter: "workflow", "general", or "both" (default: "both")
+    /// * `scope` - Scope filter: "workflow", "general", "both" or "collections"
+    ///   (default: "collections" with attached collections, "both" otherwise)
     #[instrument(skip(self, input), fields(query_len = query_text.len(), limit = limit, scope = %scope))]
     async fn search_memories(
         &self,
@@ -449,6 +466,12 @@ impl MemoryTool {
             Self::parse_memory_type(mem_type)?;
         }
 
+        if scope == "collections" {
+            return self
+                .search_collections(query_text, limit, type_filter, threshold, workflow_id)
+                .await;
+        }
+
         let params = SearchParams {
             query_text: query_text.to_string(),
             limit,
@@ -474,6 +497,90 @@ impl MemoryTool {
         }))
     }
 
+    /// Searches the workflow memory and the collections attached to the agent.
+    ///
+    /// General memories are left out: the attached collections are the
+    /// agent's curated knowledge.
+    async fn search_collections(
+        &self,
+        query_text: &str,
+        limit: usize,
+        type_filter: Option<&str>,
+        threshold: f64,
+        workflow_id: Option<String>,
+    ) -> ToolResult<Value> {
+        let (results, search_type) = match &workflow_id {
+            Some(_) => {
+                let params = SearchParams {
+                    query_text: query_text.to_string(),
+                    limit,
+                    type_filter: type_filter.map(String::from),
+                    workflow_id: workflow_id.clone(),
+                    scope: "workflow".to_string(),
+                    threshold,
+                };
+                search_memories_core(params, &self.db, self.embedding_service.as_ref())
+                    .await
+                    .map_err(ToolError::DatabaseError)?
+            }
+            None => (Vec::new(), "none".to_string()),
+        };
+
+        let packs = attached_packs(&self.db, &self.agent_id)
+            .await
+            .map_err(ToolError::DatabaseError)?;
+        let chunks = retrieve(
+            &self.db,
+            self.embedding_service.as_ref(),
+            &packs,
+            query_text,
+            limit.min(MAX_LIMIT),
+        )
+        .await
+        .map_err(ToolError::DatabaseError)?;
+        let collection_results: Vec<Value> = chunks
+            .into_iter()
+            .map(|chunk| {
+                let collection = packs
+                    .iter()
+                    .find(|pack| pack.id == chunk.pack_id)
+                    .map(|pack| pack.name.clone());
+                serde_json::json!({
+                    "collection": collection,
+                    "document": chunk.document,
+                    "content": chunk.content,
+                    "score": chunk.score,
+                })
+            })
+            .collect();
+
+        debug!(
+            memories = results.len(),
+            chunks = collection_results.len(),
+            collections = packs.len(),
+            "Collections searched"
+        );
+        Ok(serde_json::json!({
+            "success": true,
+            "search_type": search_type,
+            "count": results.len() + collection_results.len(),
+            "threshold": threshold,
+            "scope": "collections",
+            "workflow_id": workflow_id,
+            "collections": packs.iter().map(|pack| pack.name.as_str()).collect::<Vec<_>>(),
+            "results": results,
+            "collection_results": collection_results
+        }))
+    }
+
+    /// Default search scope: the attached collections when there are some.
+    async fn default_search_scope(&self) -> &'static str {
+        match attached_packs(&self.db, &self.agent_id).await {
+            Ok(packs) if !packs.is_empty() => "collections",
+            _ => "both",
+        }
+    }
+
     /// Describes memory statistics (for agent discovery).
     #[instrument(skip(self, input), fields(scope = %scope))]
     async fn describe_memories(&self, input: &MemoryInput, scope: &str) -> ToolResult<Value> {
@@ -562,6 +669,36 @@ impl MemoryTool {
             "message": format!("All '{}' memories have been cleared", memory_type)
         }))
     }
+
+    /// Creates the tool with the current embedding service (configured via Settings).
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let embedding_service = ctx.factory.get_embedding_service().await;
+            let has_embedding = embedding_service.is_some();
+            debug!(
+                has_embedding = has_embedding,
+                "Creating MemoryTool with current embedding state"
+            );
+            let tool = MemoryTool::new(
+                ctx.factory.get_db(),
+                embedding_service,
+                ctx.workflow_id,
+                ctx.agent_id,
+            );
+            info!(has_embedding = has_embedding, "MemoryTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "MemoryTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: MemoryTool::build,
+    })
 }
 
 #[async_trait]
@@ -605,6 +742,8 @@ SCOPE PARAMETER (for list/search/describe):
 - "both" (default): Shows workflow-specific AND general memories
 - "workflow": Only memories from current workflow
 - "general": Only global memories (not tied to any workflow)
+- "collections" (search only, default when collections are attached to you): memories of the
+  current workflow plus the excerpts of your attached collections (in "collection_results")
 
 CONSTRAINTS:
 - Content length: max {} characters
@@ -708,9 +847,9 @@ EXAMPLES:
                     },
                     "scope": {
                         "type": "string",
-                        "enum": ["workflow", "general", "both"],
+                        "enum": ["workflow", "general", "both", "collections"],
                         "default": "both",
-                        "description": "For add: override auto-scoping ('general' forces cross-workflow, 'workflow' forces workflow-scoped). For list/search/describe: filter scope."
+                        "description": "For add: override auto-scoping ('general' forces cross-workflow, 'workflow' forces workflow-scoped). For list/search/describe: filter scope. 'collections' (search only, default with attached collections): workflow memories plus attached collections."
                     },
                     "mode": {
                         "type": "string",
@@ -741,7 +880,7 @@ EXAMPLES:
                     "count": {"type": "integer"},
                     "scope": {"type": "string"},
                     "workflow_id": {"type": "string"},
-                    "embedding_generated": {"type": "boolean"},
+                    "embedding_status": {"type": "string"},
                     "search_type": {"type": "string"}
                 }
             }),
@@ -798,7 +937,10 @@ EXAMPLES:
                 // SAFETY: validate_search() ensures query is Some
                 let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
                 let threshold = params.threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
-                let scope = params.scope.as_deref().unwrap_or("both");
+                let scope = match params.scope.as_deref() {
+                    Some(scope) => scope,
+                    None => self.default_search_scope().await,
+                };
                 self.search_memories(
                     &params,
                     params.query.as_deref().unwrap(),
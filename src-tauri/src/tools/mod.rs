@@ -30,6 +30,14 @@
 //! - `validate_input()` - Validates parameters before execution
 //! - `requires_confirmation()` - Whether human confirmation is needed
 //!
+//! The agent loop bounds each call with a timeout and the workflow
+//! cancellation token (see [`limits`]), and enforces the agent's per-tool call
+//! quotas (see [`quota`]). In a dry run workflow, calls with side effects
+//! return a simulated result instead of running (see [`dry_run`]).
+//!
+//! Each tool registers its metadata and constructor in the [`registry`],
+//! which [`ToolFactory`] uses to create the tools listed by an agent.
+//!
 //! # Available Tools
 //!
 //! | Module | Tool | Description |
@@ -37,6 +45,14 @@
 //! | [`memory`] | `MemoryTool` | Contextual memory with semantic search |
 //! | [`todo`] | `TodoTool` | Task management for workflow decomposition |
 //! | [`calculator`] | `CalculatorTool` | Scientific calculator for mathematical operations |
+//! | [`calculator`] | `UnitsTool` | Unit conversions, time zones, date and duration arithmetic |
+//! | [`text`] | `TextTool` | Regex, diff, JSON/YAML/CSV conversion, hashes and encodings |
+//! | [`pdf`] | `PdfTool` | PDF text, tables and metadata extraction; PDFs from Markdown |
+//! | [`ocr`] | `OcrTool` | Text recognition in images and scanned PDFs (tesseract or Ollama) |
+//! | [`resource`] | `ResourceTool` | Read resources exposed by the agent's MCP servers |
+//! | [`workflow`] | `WorkflowTool` | Run saved workflows as steps of a task |
+//! | [`script`] | `ScriptTool` | Custom tools written by the user as Rhai scripts |
+//! | [`macros`] | `MacroTool` | Sequences of tool calls defined by the user, run as one tool |
 //!
 //! # Example
 //!
@@ -53,21 +69,39 @@
 //! ```
 
 pub mod calculator;
+pub mod chart;
+pub mod configured;
 pub mod constants;
 pub mod context;
 pub mod delegate_task;
+pub mod desktop;
+pub mod dry_run;
+pub mod email;
+pub mod external_db;
 pub mod factory;
+pub mod limits;
+pub mod macros;
 pub mod memory;
+pub mod ocr;
 pub mod parallel_tasks;
+pub mod pdf;
+pub mod quota;
 pub mod registry;
+pub mod resource;
 pub mod response;
+pub mod rss;
+pub mod script;
 pub mod spawn_agent;
 pub mod sub_agent_circuit_breaker;
 pub mod sub_agent_executor;
+pub mod text;
 pub mod todo;
 pub mod user_question;
 pub mod utils;
 pub mod validation_helper;
+pub mod validation_notifier;
+pub mod validation_preview;
+pub mod workflow;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -78,25 +112,53 @@ use std::fmt;
 #[allow(unused_imports)]
 pub use calculator::CalculatorTool;
 #[allow(unused_imports)]
+pub use calculator::UnitsTool;
+#[allow(unused_imports)]
+pub use chart::ChartTool;
+#[allow(unused_imports)]
+pub use configured::ConfiguredTool;
+#[allow(unused_imports)]
 pub use context::AgentToolContext;
 #[allow(unused_imports)]
 pub use delegate_task::DelegateTaskTool;
 #[allow(unused_imports)]
+pub use desktop::{ClipboardTool, ScreenshotTool};
+#[allow(unused_imports)]
+pub use email::EmailTool;
+#[allow(unused_imports)]
+pub use external_db::SqlTool;
+#[allow(unused_imports)]
 pub use factory::ToolFactory;
 #[allow(unused_imports)]
+pub use macros::MacroTool;
+#[allow(unused_imports)]
 pub use memory::MemoryTool;
 #[allow(unused_imports)]
+pub use ocr::OcrTool;
+#[allow(unused_imports)]
 pub use parallel_tasks::ParallelTasksTool;
 #[allow(unused_imports)]
-pub use registry::TOOL_REGISTRY;
+pub use pdf::PdfTool;
+#[allow(unused_imports)]
+pub use registry::{KNOWN_TOOLS, TOOL_REGISTRY};
+#[allow(unused_imports)]
+pub use resource::ResourceTool;
+#[allow(unused_imports)]
+pub use rss::RssTool;
+#[allow(unused_imports)]
+pub use script::ScriptTool;
 #[allow(unused_imports)]
 pub use spawn_agent::SpawnAgentTool;
 #[allow(unused_imports)]
 pub use sub_agent_circuit_breaker::SubAgentCircuitBreaker;
 #[allow(unused_imports)]
+pub use text::TextTool;
+#[allow(unused_imports)]
 pub use todo::TodoTool;
 #[allow(unused_imports)]
 pub use user_question::UserQuestionTool;
+#[allow(unused_imports)]
+pub use workflow::WorkflowTool;
 
 /// Tool definition metadata for LLM understanding.
 ///
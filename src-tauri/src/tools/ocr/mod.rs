@@ -0,0 +1,26 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OCR Tool Module
+//!
+//! Provides the OcrTool, which recognizes the text of images (screenshots,
+//! photos) and scanned PDF pages with the engine of the OCR settings (see
+//! [`crate::documents::ocr`]): tesseract with its language packs, or a local
+//! vision model served by Ollama.
+//!
+//! Gated by the local documents toggle of the desktop access settings.
+
+mod tool;
+
+pub use tool::OcrTool;
@@ -0,0 +1,460 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OcrTool implementation.
+//!
+//! Images are recognized whole; PDFs are recognized page by page from the
+//! largest image of each page (the scan). The engine and the default
+//! language packs are read from the OCR settings on every call.
+
+use crate::commands::ocr::load_ocr_settings;
+use crate::db::DBClient;
+use crate::documents::{ocr, pdf, read_file};
+use crate::models::ocr::OcrEngine;
+use crate::tools::constants::ocr as ocr_const;
+use crate::tools::desktop::ensure_permission;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{resolve_path, validate_enum_value};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::{info, instrument};
+
+/// Lowercase extension of a path.
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default()
+}
+
+/// Keeps at most `remaining` characters of a text, updating the budget.
+fn take_chars(text: &str, remaining: &mut usize, truncated: &mut bool) -> String {
+    let kept: String = text.chars().take(*remaining).collect();
+    let kept_count = kept.chars().count();
+    *truncated |= text.chars().count() > kept_count;
+    *remaining -= kept_count;
+    kept
+}
+
+/// Tool recognizing the text of images and scanned PDFs.
+pub struct OcrTool {
+    /// Database client (OCR settings, desktop access permissions)
+    db: Arc<DBClient>,
+    /// Agent ID using this tool
+    agent_id: String,
+}
+
+impl OcrTool {
+    /// Creates a new OcrTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `agent_id` - Agent ID using the tool
+    pub fn new(db: Arc<DBClient>, agent_id: String) -> Self {
+        Self { db, agent_id }
+    }
+
+    /// Recognizes an image, or the scanned pages of a PDF.
+    #[instrument(skip(self, input), fields(agent_id = %self.agent_id))]
+    async fn recognize(&self, input: &Value) -> ToolResult<Value> {
+        let path = resolve_path(input["path"].as_str().unwrap_or_default())?;
+        let settings = load_ocr_settings(&self.db).await;
+        let languages: Vec<String> = match input["languages"].as_array() {
+            Some(languages) => languages
+                .iter()
+                .filter_map(|language| language.as_str().map(str::to_string))
+                .collect(),
+            None => settings.languages.clone(),
+        };
+
+        let read_path = path.clone();
+        let bytes = tokio::task::spawn_blocking(move || read_file(&read_path))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("File read failed: {}", e)))?
+            .map_err(ToolError::ExecutionFailed)?;
+
+        let mut remaining = ocr_const::MAX_TEXT_CHARS;
+        let mut truncated = false;
+        let mut response = ResponseBuilder::new().success(true).field(
+            "engine",
+            match settings.engine {
+                OcrEngine::Tesseract => "tesseract",
+                OcrEngine::Ollama => "ollama",
+            },
+        );
+
+        if extension(&path) != "pdf" {
+            if bytes.len() as u64 > ocr_const::MAX_IMAGE_BYTES {
+                return Err(ToolError::ValidationFailed(format!(
+                    "'{}' exceeds the maximum image size of {} MB",
+                    path.display(),
+                    ocr_const::MAX_IMAGE_BYTES / (1024 * 1024)
+                )));
+            }
+            let text = ocr::recognize(&settings, bytes, &languages)
+                .await
+                .map_err(ToolError::ExecutionFailed)?;
+            let text = take_chars(&text, &mut remaining, &mut truncated);
+            info!(path = %path.display(), chars = text.len(), "Image recognized");
+            if text.is_empty() {
+                response = response.message("No text was recognized in the image");
+            }
+            return Ok(response
+                .field("text", text)
+                .field("truncated", truncated)
+                .build());
+        }
+
+        let page = input["page"].as_u64().map(|page| page as usize);
+        let images = tokio::task::spawn_blocking(move || {
+            pdf::page_images(&bytes, page, ocr_const::MAX_PDF_PAGES)
+        })
+        .await
+        .map_err(|e| ToolError::ExecutionFailed(format!("PDF read failed: {}", e)))?
+        .map_err(ToolError::ExecutionFailed)?;
+        if images.is_empty() {
+            return Err(ToolError::ExecutionFailed(
+                "The PDF has no scanned pages: use PdfTool extract_text for its text layer"
+                    .to_string(),
+            ));
+        }
+
+        let mut pages = Vec::with_capacity(images.len());
+        let mut skipped = Vec::new();
+        for image in images {
+            let Some(data) = image.data else {
+                skipped.push(image.page);
+                continue;
+            };
+            if remaining == 0 {
+                truncated = true;
+                break;
+            }
+            let text = ocr::recognize(&settings, data, &languages)
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("Page {}: {}", image.page, e)))?;
+            let text = take_chars(&text, &mut remaining, &mut truncated);
+            pages.push(json!({"page": image.page, "text": text}));
+        }
+
+        info!(
+            path = %path.display(),
+            pages = pages.len(),
+            skipped = skipped.len(),
+            truncated,
+            "Scanned PDF recognized"
+        );
+        if !skipped.is_empty() {
+            response = response.message(format!(
+                "Pages {:?} use an image encoding that cannot be recognized",
+                skipped
+            ));
+        }
+        Ok(response
+            .count(pages.len())
+            .data("pages", pages)
+            .field("skipped_pages", skipped)
+            .field("truncated", truncated)
+            .build())
+    }
+
+    /// Lists the installed tesseract language packs.
+    #[instrument(skip(self), fields(agent_id = %self.agent_id))]
+    async fn list_languages(&self) -> ToolResult<Value> {
+        let settings = load_ocr_settings(&self.db).await;
+        let installed = ocr::list_languages(&settings)
+            .await
+            .map_err(ToolError::ExecutionFailed)?;
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .count(installed.len())
+            .data("languages", installed)
+            .field("default_languages", settings.languages)
+            .build())
+    }
+
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let tool = OcrTool::new(ctx.factory.get_db(), ctx.agent_id);
+            info!("OcrTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Each recognized image is bounded by its own timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "OcrTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: None,
+        constructor: OcrTool::build,
+    })
+}
+
+#[async_trait]
+impl Tool for OcrTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "OcrTool".to_string(),
+            name: "OCR Tool".to_string(),
+            description: format!(
+                r#"Recognizes the text of images and scanned PDFs on the user's computer (OCR).
+
+USE THIS TOOL WHEN:
+- The text of a screenshot (the "path" returned by ScreenshotTool), photo or scanned
+  document is needed
+- PdfTool extract_text returns empty pages (scanned PDF without text layer)
+
+OPERATIONS:
+- recognize: text of an image, or of each scanned page of a PDF ("path", optional "page"
+  for PDFs, optional "languages" to override the default language packs)
+- list_languages: tesseract language packs installed, and the defaults of the settings
+
+IMPORTANT CONSTRAINTS:
+- The user must enable local documents in the settings; a permission error means it is disabled
+- Paths are absolute or start with ~ (home directory); images up to {} MB
+- Images: {}
+- At most {} PDF pages per call: use "page" for long documents
+- Languages are tesseract codes ("eng", "fra", "deu", "chi_sim"...), combined for
+  multilingual documents; the Ollama engine ignores them
+- Recognized text may contain errors: check names and figures that matter
+
+EXAMPLES:
+1. {{"operation": "recognize", "path": "/tmp/zileo-screenshots/capture.png"}}
+2. {{"operation": "recognize", "path": "~/Scans/contract.pdf", "page": 2, "languages": ["fra", "eng"]}}
+3. {{"operation": "list_languages"}}"#,
+                ocr_const::MAX_IMAGE_BYTES / (1024 * 1024),
+                ocr_const::IMAGE_EXTENSIONS.join(", "),
+                ocr_const::MAX_PDF_PAGES
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": ocr_const::VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Image or PDF file to recognize (recognize)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Single PDF page to recognize (recognize)"
+                    },
+                    "languages": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "maxItems": ocr_const::MAX_LANGUAGES,
+                        "description": "Tesseract language packs, replacing the defaults (recognize)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "engine": {"type": "string"},
+                    "text": {"type": "string"},
+                    "pages": {"type": "array"},
+                    "skipped_pages": {"type": "array"},
+                    "languages": {"type": "array"},
+                    "default_languages": {"type": "array"},
+                    "truncated": {"type": "boolean"},
+                    "count": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        ensure_permission(&self.db, |settings| settings.documents, "OcrTool").await?;
+
+        match input["operation"].as_str().unwrap_or_default() {
+            "recognize" => self.recognize(&input).await,
+            _ => self.list_languages().await,
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation".to_string()))?;
+        validate_enum_value(operation, ocr_const::VALID_OPERATIONS, "operation")?;
+        if operation != "recognize" {
+            return Ok(());
+        }
+
+        let path = input["path"].as_str().unwrap_or_default().trim();
+        if path.is_empty() {
+            return Err(ToolError::InvalidInput(
+                "Missing 'path' of the image or PDF for recognize".to_string(),
+            ));
+        }
+        let extension = extension(Path::new(path));
+        if extension != "pdf" && !ocr_const::IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(ToolError::InvalidInput(format!(
+                "'{}' is not an image or a PDF ({}, pdf)",
+                path,
+                ocr_const::IMAGE_EXTENSIONS.join(", ")
+            )));
+        }
+        if !input["page"].is_null() && extension != "pdf" {
+            return Err(ToolError::InvalidInput(
+                "'page' only applies to PDF files".to_string(),
+            ));
+        }
+
+        if let Some(languages) = input.get("languages").filter(|value| !value.is_null()) {
+            let languages = languages.as_array().ok_or_else(|| {
+                ToolError::InvalidInput("'languages' must be an array".to_string())
+            })?;
+            if languages.is_empty() || languages.len() > ocr_const::MAX_LANGUAGES {
+                return Err(ToolError::ValidationFailed(format!(
+                    "'languages' must have 1-{} entries",
+                    ocr_const::MAX_LANGUAGES
+                )));
+            }
+            for language in languages {
+                let code = language.as_str().unwrap_or_default();
+                if !ocr::is_valid_language(code) {
+                    return Err(ToolError::ValidationFailed(format!(
+                        "Invalid language '{}': use tesseract codes like 'eng' or 'chi_sim'",
+                        language
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::desktop_access::DesktopAccessSettings;
+    use crate::models::ocr::OcrSettings;
+    use crate::tools::constants::desktop as desktop_const;
+
+    async fn setup(documents: bool) -> (OcrTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let settings = DesktopAccessSettings {
+            documents,
+            ..Default::default()
+        };
+        crate::db::queries::settings::save_config(
+            &db,
+            desktop_const::SETTINGS_KEY,
+            &serde_json::to_value(settings).unwrap(),
+        )
+        .await
+        .unwrap();
+        let ocr_settings = OcrSettings {
+            tesseract_path: Some(dir.path().join("no-tesseract").display().to_string()),
+            ..Default::default()
+        };
+        crate::db::queries::settings::save_config(
+            &db,
+            ocr_const::SETTINGS_KEY,
+            &serde_json::to_value(ocr_settings).unwrap(),
+        )
+        .await
+        .unwrap();
+        (OcrTool::new(Arc::new(db), "agent".to_string()), dir)
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = setup(false).await;
+        let valid = [
+            json!({"operation": "recognize", "path": "/tmp/capture.PNG"}),
+            json!({"operation": "recognize", "path": "~/scan.pdf", "page": 2}),
+            json!({"operation": "recognize", "path": "/tmp/a.jpg", "languages": ["fra", "eng"]}),
+            json!({"operation": "list_languages"}),
+        ];
+        for input in valid {
+            assert!(tool.validate_input(&input).is_ok(), "{}", input);
+        }
+
+        let invalid = [
+            json!({"operation": "recognize"}),
+            json!({"operation": "recognize", "path": "/tmp/notes.docx"}),
+            json!({"operation": "recognize", "path": "/tmp/a.png", "page": 1}),
+            json!({"operation": "recognize", "path": "/tmp/a.png", "languages": []}),
+            json!({"operation": "recognize", "path": "/tmp/a.png", "languages": ["eng+fra"]}),
+            json!({"operation": "recognize", "path": "/tmp/a.png", "languages": "eng"}),
+            json!({"operation": "translate"}),
+        ];
+        for input in invalid {
+            assert!(tool.validate_input(&input).is_err(), "{}", input);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_permission() {
+        let (tool, _dir) = setup(false).await;
+        let result = tool.execute(json!({"operation": "list_languages"})).await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_pdf_without_scans() {
+        let (tool, dir) = setup(true).await;
+        let path = dir.path().join("text.pdf");
+        let bytes = crate::documents::markdown_pdf::render("Text layer", None).unwrap();
+        std::fs::write(&path, bytes).unwrap();
+
+        let result = tool
+            .execute(json!({"operation": "recognize", "path": path}))
+            .await;
+        match result {
+            Err(ToolError::ExecutionFailed(message)) => assert!(message.contains("PdfTool")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_engine() {
+        let (tool, dir) = setup(true).await;
+        let path = dir.path().join("capture.png");
+        std::fs::write(&path, [0x89, b'P', b'N', b'G']).unwrap();
+
+        let result = tool
+            .execute(json!({"operation": "recognize", "path": path}))
+            .await;
+        match result {
+            Err(ToolError::ExecutionFailed(message)) => assert!(message.contains("not found")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
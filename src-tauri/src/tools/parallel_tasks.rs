@@ -49,6 +49,9 @@ use crate::models::sub_agent::{
     SubAgentMetrics,
 };
 use crate::tools::context::AgentToolContext;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
 use crate::tools::sub_agent_executor::{ExecutionResult, SubAgentExecutor};
 use crate::tools::utils::sub_agent_description_template;
 use crate::tools::validation_helper::ValidationHelper;
@@ -606,6 +609,34 @@ impl ParallelTasksTool {
 
     // NOTE: update_execution_record() removed as part of OPT-SA-5
     // Now using SubAgentExecutor::update_execution_record() for unified DB updates
+
+    /// Creates the tool with the agent system access of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let context = ctx.require_agent_context()?;
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = ParallelTasksTool::new(
+                ctx.factory.get_db(),
+                context,
+                ctx.agent_id,
+                wf_id,
+                ctx.is_primary_agent,
+            );
+            info!("ParallelTasksTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Bounded by the sub-agent inactivity timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "ParallelTasksTool",
+        category: ToolCategory::SubAgent,
+        requires_context: true,
+        default_timeout_secs: None,
+        constructor: ParallelTasksTool::build,
+    })
 }
 
 #[async_trait]
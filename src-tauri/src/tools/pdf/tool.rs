@@ -0,0 +1,515 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! PdfTool implementation.
+//!
+//! Paths are absolute or start with `~`. Generated PDFs are saved in the
+//! `Documents/Zileo` directory of the user unless a path is given; existing
+//! files are never overwritten.
+
+use crate::db::DBClient;
+use crate::documents::{markdown_pdf, pdf, read_file, tables::detect_tables};
+use crate::tools::constants::pdf as pdf_const;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::desktop::ensure_permission;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{home_dir, resolve_path, validate_enum_value, validate_length};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{info, instrument};
+use uuid::Uuid;
+
+/// File name of a generated PDF, from its title.
+fn file_name_for(title: Option<&str>) -> String {
+    let stem: String = title
+        .unwrap_or("document")
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(60)
+        .collect();
+    let stem = stem.trim_matches('_');
+    let stem = if stem.is_empty() { "document" } else { stem };
+    format!("{}-{}.pdf", stem, &Uuid::new_v4().simple().to_string()[..8])
+}
+
+/// Tool extracting text, tables and metadata from PDFs and generating PDFs
+/// from Markdown.
+pub struct PdfTool {
+    /// Database client (desktop access permissions)
+    db: Arc<DBClient>,
+    /// Agent ID using this tool
+    agent_id: String,
+    /// Directory of the generated PDFs without explicit path
+    output_dir: PathBuf,
+}
+
+impl PdfTool {
+    /// Creates a new PdfTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `agent_id` - Agent ID using the tool
+    pub fn new(db: Arc<DBClient>, agent_id: String) -> Self {
+        let output_dir = home_dir()
+            .map(|home| home.join("Documents"))
+            .unwrap_or_else(std::env::temp_dir)
+            .join(pdf_const::OUTPUT_DIR_NAME);
+        Self {
+            db,
+            agent_id,
+            output_dir,
+        }
+    }
+
+    /// Reads the PDF of the `path` field.
+    async fn read_pdf(input: &Value) -> ToolResult<(PathBuf, Vec<u8>)> {
+        let path = resolve_path(input["path"].as_str().unwrap_or_default())?;
+        let read_path = path.clone();
+        let bytes = tokio::task::spawn_blocking(move || read_file(&read_path))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("PDF read failed: {}", e)))?
+            .map_err(ToolError::ExecutionFailed)?;
+        Ok((path, bytes))
+    }
+
+    /// Extracts the pages of a PDF, or the page of the `page` field.
+    async fn pages(input: &Value, bytes: Vec<u8>) -> ToolResult<Vec<(usize, String)>> {
+        let pages = tokio::task::spawn_blocking(move || pdf::extract_pages(&bytes))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("PDF extraction failed: {}", e)))?
+            .map_err(ToolError::ExecutionFailed)?;
+        let total = pages.len();
+        let pages = pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, text)| (index + 1, text));
+
+        match input["page"].as_u64() {
+            None => Ok(pages.collect()),
+            Some(page) => {
+                let page = pages
+                    .filter(|(number, _)| *number as u64 == page)
+                    .collect::<Vec<_>>();
+                if page.is_empty() {
+                    return Err(ToolError::InvalidInput(format!(
+                        "'page' must be 1-{} (pages of the PDF)",
+                        total
+                    )));
+                }
+                Ok(page)
+            }
+        }
+    }
+
+    /// Extracts the text of the pages, truncated to the text limit.
+    #[instrument(skip(self, input), fields(agent_id = %self.agent_id))]
+    async fn extract_text(&self, input: &Value) -> ToolResult<Value> {
+        let (path, bytes) = Self::read_pdf(input).await?;
+        let pages = Self::pages(input, bytes).await?;
+
+        let mut remaining = pdf_const::MAX_TEXT_CHARS;
+        let mut truncated = false;
+        let mut results = Vec::with_capacity(pages.len());
+        for (number, text) in &pages {
+            if remaining == 0 {
+                truncated = true;
+                break;
+            }
+            let text = text.trim();
+            let length = text.chars().count();
+            let kept: String = text.chars().take(remaining).collect();
+            truncated |= length > remaining;
+            remaining -= kept.chars().count();
+            results.push(json!({"page": number, "text": kept}));
+        }
+        let empty = pages.iter().all(|(_, text)| text.trim().is_empty());
+
+        info!(path = %path.display(), pages = pages.len(), truncated, "PDF text extracted");
+        let mut response = ResponseBuilder::new()
+            .success(true)
+            .count(results.len())
+            .data("pages", results)
+            .field("truncated", truncated);
+        if empty {
+            response = response
+                .message("The PDF has no text layer (scanned document?): no text was extracted");
+        }
+        Ok(response.build())
+    }
+
+    /// Detects the tables of the pages.
+    #[instrument(skip(self, input), fields(agent_id = %self.agent_id))]
+    async fn extract_tables(&self, input: &Value) -> ToolResult<Value> {
+        let (path, bytes) = Self::read_pdf(input).await?;
+        let pages = Self::pages(input, bytes).await?;
+
+        let texts: Vec<String> = pages.iter().map(|(_, text)| text.clone()).collect();
+        let mut tables = detect_tables(&texts);
+        for table in &mut tables {
+            table.page = pages[table.page - 1].0;
+        }
+        let total = tables.len();
+        tables.truncate(pdf_const::MAX_TABLES);
+
+        info!(path = %path.display(), tables = total, "PDF tables extracted");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .count(tables.len())
+            .field("total", total)
+            .data("tables", tables)
+            .build())
+    }
+
+    /// Reads the metadata of a PDF.
+    #[instrument(skip(self, input), fields(agent_id = %self.agent_id))]
+    async fn metadata(&self, input: &Value) -> ToolResult<Value> {
+        let (path, bytes) = Self::read_pdf(input).await?;
+        let metadata = tokio::task::spawn_blocking(move || pdf::read_metadata(&bytes))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("PDF read failed: {}", e)))?
+            .map_err(ToolError::ExecutionFailed)?;
+
+        info!(path = %path.display(), pages = metadata.page_count, "PDF metadata read");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .data("metadata", metadata)
+            .build())
+    }
+
+    /// Generates a PDF from Markdown.
+    #[instrument(skip(self, input), fields(agent_id = %self.agent_id))]
+    async fn create(&self, input: &Value) -> ToolResult<Value> {
+        let markdown = input["markdown"].as_str().unwrap_or_default().to_string();
+        let title = input["title"].as_str().map(str::to_string);
+        let path = match input["output_path"].as_str() {
+            Some(raw) => {
+                let path = resolve_path(raw)?;
+                if !path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+                {
+                    return Err(ToolError::InvalidInput(
+                        "'output_path' must end with .pdf".to_string(),
+                    ));
+                }
+                path
+            }
+            None => self.output_dir.join(file_name_for(title.as_deref())),
+        };
+        if path.exists() {
+            return Err(ToolError::ValidationFailed(format!(
+                "'{}' already exists; choose another output_path",
+                path.display()
+            )));
+        }
+
+        let bytes =
+            tokio::task::spawn_blocking(move || markdown_pdf::render(&markdown, title.as_deref()))
+                .await
+                .map_err(|e| ToolError::ExecutionFailed(format!("PDF generation failed: {}", e)))?
+                .map_err(ToolError::ExecutionFailed)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await.map_err(|e| {
+                ToolError::ExecutionFailed(format!("Failed to create '{}': {}", dir.display(), e))
+            })?;
+        }
+        tokio::fs::write(&path, &bytes).await.map_err(|e| {
+            ToolError::ExecutionFailed(format!("Failed to write '{}': {}", path.display(), e))
+        })?;
+        let page_count = pdf::read_metadata(&bytes)
+            .map(|metadata| metadata.page_count)
+            .unwrap_or_default();
+
+        info!(path = %path.display(), size = bytes.len(), "PDF created");
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("path", path.to_string_lossy().into_owned())
+            .field("page_count", page_count)
+            .field("size_bytes", bytes.len())
+            .message(format!("PDF saved to {}", path.display()))
+            .build())
+    }
+
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let tool = PdfTool::new(ctx.factory.get_db(), ctx.agent_id);
+            info!("PdfTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "PdfTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: PdfTool::build,
+    })
+}
+
+#[async_trait]
+impl Tool for PdfTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "PdfTool".to_string(),
+            name: "PDF Tool".to_string(),
+            description: format!(
+                r##"Reads PDF files on the user's computer and generates PDFs from Markdown.
+
+USE THIS TOOL WHEN:
+- The user asks about the content of a PDF file (report, invoice, article)
+- Figures must be taken from the tables of a PDF
+- The user wants a document delivered as a PDF
+
+OPERATIONS:
+- extract_text: text of each page ("path", optional "page" number)
+- extract_tables: tables detected in the pages, as rows of cells ("path", optional "page")
+- metadata: title, author, dates, page count and encryption ("path")
+- create: generates a PDF from "markdown" (optional "title", optional "output_path");
+  saved in Documents/{} of the user by default
+
+IMPORTANT CONSTRAINTS:
+- The user must enable local documents in the settings; a permission error means it is disabled
+- Paths are absolute or start with ~ (home directory); files up to {} MB
+- Scanned PDFs have no text layer: extraction returns empty pages
+- Tables are detected from column spacing: check the cells of complex layouts
+- Extracted text is limited to {} characters: use "page" for long documents
+- Generated PDFs support headings, bold/italic, lists, quotes, code, tables and rules;
+  text is limited to Latin characters. Existing files are never overwritten
+
+EXAMPLES:
+1. {{"operation": "extract_text", "path": "~/Downloads/invoice.pdf"}}
+2. {{"operation": "extract_tables", "path": "/home/ann/report.pdf", "page": 3}}
+3. {{"operation": "create", "title": "Meeting notes", "markdown": "# Meeting notes\n\n- Budget approved"}}"##,
+                pdf_const::OUTPUT_DIR_NAME,
+                pdf_const::MAX_FILE_BYTES / (1024 * 1024),
+                pdf_const::MAX_TEXT_CHARS
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": pdf_const::VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "PDF file to read (extract_text, extract_tables, metadata)"
+                    },
+                    "page": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Single page to read (extract_text, extract_tables)"
+                    },
+                    "markdown": {
+                        "type": "string",
+                        "description": "Content of the PDF to create (create)"
+                    },
+                    "title": {
+                        "type": "string",
+                        "description": "Document title stored in the PDF metadata (create)"
+                    },
+                    "output_path": {
+                        "type": "string",
+                        "description": "Path of the PDF to create, ending with .pdf (create)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "pages": {"type": "array"},
+                    "tables": {"type": "array"},
+                    "metadata": {"type": "object"},
+                    "path": {"type": "string"},
+                    "page_count": {"type": "integer"},
+                    "size_bytes": {"type": "integer"},
+                    "truncated": {"type": "boolean"},
+                    "total": {"type": "integer"},
+                    "count": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        ensure_permission(&self.db, |settings| settings.documents, "PdfTool").await?;
+
+        match input["operation"].as_str().unwrap_or_default() {
+            "extract_text" => self.extract_text(&input).await,
+            "extract_tables" => self.extract_tables(&input).await,
+            "metadata" => self.metadata(&input).await,
+            _ => self.create(&input).await,
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation".to_string()))?;
+        validate_enum_value(operation, pdf_const::VALID_OPERATIONS, "operation")?;
+
+        if operation == "create" {
+            let markdown = input["markdown"].as_str().unwrap_or_default();
+            if markdown.trim().is_empty() {
+                return Err(ToolError::InvalidInput(
+                    "Missing 'markdown' content for create".to_string(),
+                ));
+            }
+            validate_length(markdown, pdf_const::MAX_MARKDOWN_LENGTH, "markdown")?;
+            if let Some(title) = input["title"].as_str() {
+                validate_length(title, pdf_const::MAX_TITLE_LENGTH, "title")?;
+            }
+        } else {
+            let path = input["path"].as_str().unwrap_or_default();
+            if path.trim().is_empty() {
+                return Err(ToolError::InvalidInput(format!(
+                    "Missing 'path' of the PDF for {}",
+                    operation
+                )));
+            }
+            if !Path::new(path.trim())
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"))
+            {
+                return Err(ToolError::InvalidInput(format!(
+                    "'{}' is not a PDF file",
+                    path
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::desktop_access::DesktopAccessSettings;
+    use crate::tools::constants::desktop as desktop_const;
+
+    async fn setup(documents: bool) -> (PdfTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        let settings = DesktopAccessSettings {
+            documents,
+            ..Default::default()
+        };
+        crate::db::queries::settings::save_config(
+            &db,
+            desktop_const::SETTINGS_KEY,
+            &serde_json::to_value(settings).unwrap(),
+        )
+        .await
+        .unwrap();
+        let tool = PdfTool {
+            output_dir: dir.path().join("out"),
+            ..PdfTool::new(Arc::new(db), "agent".to_string())
+        };
+        (tool, dir)
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = setup(false).await;
+        assert!(tool
+            .validate_input(&json!({"operation": "metadata", "path": "/tmp/a.PDF"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "create", "markdown": "# Hi"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "extract_text"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "extract_text", "path": "/tmp/a.docx"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "create", "markdown": "  "}))
+            .is_err());
+        assert!(tool.validate_input(&json!({"operation": "merge"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_requires_permission() {
+        let (tool, _dir) = setup(false).await;
+        let result = tool
+            .execute(json!({"operation": "create", "markdown": "# Hi"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_then_extract() {
+        let (tool, dir) = setup(true).await;
+        let created = tool
+            .execute(json!({
+                "operation": "create",
+                "title": "Sales / Q3",
+                "markdown": "# Sales\n\nThe North region grew by 12%."
+            }))
+            .await
+            .unwrap();
+        let path = created["path"].as_str().unwrap().to_string();
+        assert!(path.starts_with(dir.path().join("out").to_str().unwrap()));
+        assert!(path.contains("Sales___Q3-"));
+        assert_eq!(created["page_count"], 1);
+
+        let text = tool
+            .execute(json!({"operation": "extract_text", "path": path, "page": 1}))
+            .await
+            .unwrap();
+        assert_eq!(text["count"], 1);
+        assert!(text["pages"][0]["text"].as_str().unwrap().contains("Sales"));
+
+        let metadata = tool
+            .execute(json!({"operation": "metadata", "path": path}))
+            .await
+            .unwrap();
+        assert_eq!(metadata["metadata"]["title"], "Sales / Q3");
+
+        let out_of_range = tool
+            .execute(json!({"operation": "extract_text", "path": path, "page": 4}))
+            .await;
+        assert!(matches!(out_of_range, Err(ToolError::InvalidInput(_))));
+
+        let overwrite = tool
+            .execute(json!({"operation": "create", "markdown": "x", "output_path": path}))
+            .await;
+        assert!(matches!(overwrite, Err(ToolError::ValidationFailed(_))));
+    }
+}
@@ -0,0 +1,265 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-agent tool call quotas and rate limiting.
+//!
+//! An agent's [`ToolSettings`] may cap the calls of a tool per workflow
+//! (`max_calls_per_workflow`) and per minute (`max_calls_per_minute`). The
+//! agent loop checks every call with [`ToolQuotaTracker::acquire`] before it
+//! runs; a rejected call fails with `PermissionDenied` and a message telling
+//! the LLM its quota state, so it can adjust instead of retrying blindly.
+
+use crate::models::ToolSettings;
+use crate::tools::constants::tool_execution::MAX_QUOTA_TRACKED_WORKFLOWS;
+use crate::tools::{ToolError, ToolResult};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Window of the per-minute rate limit
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Tool call counts of one workflow
+struct WorkflowCalls {
+    /// Calls made, keyed by tool name
+    counts: HashMap<String, u32>,
+    /// Last call, used to evict idle workflows
+    last_used: Instant,
+}
+
+/// Mutable quota state
+#[derive(Default)]
+struct QuotaState {
+    /// Per-workflow call counts, keyed by workflow ID
+    workflows: HashMap<String, WorkflowCalls>,
+    /// Start times of the calls of the last minute, keyed by tool name
+    recent: HashMap<String, VecDeque<Instant>>,
+}
+
+/// Tracks the tool calls of an agent against its configured quotas.
+///
+/// Owned by the agent instance: per-minute limits apply across all workflows
+/// of the agent, per-workflow limits to each workflow separately.
+#[derive(Default)]
+pub struct ToolQuotaTracker {
+    state: Mutex<QuotaState>,
+}
+
+impl ToolQuotaTracker {
+    /// Creates an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks a call against the tool's quotas and records it if allowed.
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Workflow the call belongs to
+    /// * `tool_name` - Tool name
+    /// * `settings` - Agent settings of the tool (None = no quota)
+    ///
+    /// # Errors
+    /// * `PermissionDenied` - A quota is exhausted (the call is not recorded)
+    pub fn acquire(
+        &self,
+        workflow_id: &str,
+        tool_name: &str,
+        settings: Option<&ToolSettings>,
+    ) -> ToolResult<()> {
+        self.acquire_at(workflow_id, tool_name, settings, Instant::now())
+    }
+
+    fn acquire_at(
+        &self,
+        workflow_id: &str,
+        tool_name: &str,
+        settings: Option<&ToolSettings>,
+        now: Instant,
+    ) -> ToolResult<()> {
+        let Some(settings) = settings else {
+            return Ok(());
+        };
+        if settings.max_calls_per_workflow.is_none() && settings.max_calls_per_minute.is_none() {
+            return Ok(());
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(max) = settings.max_calls_per_workflow {
+            let used = state
+                .workflows
+                .get(workflow_id)
+                .and_then(|calls| calls.counts.get(tool_name))
+                .copied()
+                .unwrap_or(0);
+            if used >= max {
+                warn!(tool = %tool_name, workflow_id = %workflow_id, max, "Tool workflow quota exhausted");
+                return Err(ToolError::PermissionDenied(format!(
+                    "Quota exhausted for tool '{}': {}/{} calls allowed per workflow have been used. \
+                     Do not call this tool again in this workflow; continue with the results you already have",
+                    tool_name, used, max
+                )));
+            }
+        }
+
+        if let Some(max) = settings.max_calls_per_minute {
+            let recent = state.recent.entry(tool_name.to_string()).or_default();
+            while recent
+                .front()
+                .is_some_and(|start| now.duration_since(*start) >= RATE_WINDOW)
+            {
+                recent.pop_front();
+            }
+            if recent.len() >= max as usize {
+                let retry_after = recent
+                    .front()
+                    .map(|oldest| RATE_WINDOW.saturating_sub(now.duration_since(*oldest)))
+                    .unwrap_or(RATE_WINDOW);
+                warn!(tool = %tool_name, max, "Tool rate limit reached");
+                return Err(ToolError::PermissionDenied(format!(
+                    "Rate limit reached for tool '{}': {}/{} calls per minute used. \
+                     Retry in about {}s, or continue with other tools meanwhile",
+                    tool_name,
+                    recent.len(),
+                    max,
+                    retry_after.as_secs().max(1)
+                )));
+            }
+            recent.push_back(now);
+        }
+
+        if settings.max_calls_per_workflow.is_some() {
+            if !state.workflows.contains_key(workflow_id)
+                && state.workflows.len() >= MAX_QUOTA_TRACKED_WORKFLOWS
+            {
+                let idle = state
+                    .workflows
+                    .iter()
+                    .min_by_key(|(_, calls)| calls.last_used)
+                    .map(|(id, _)| id.clone());
+                if let Some(idle) = idle {
+                    state.workflows.remove(&idle);
+                }
+            }
+            let calls = state
+                .workflows
+                .entry(workflow_id.to_string())
+                .or_insert_with(|| WorkflowCalls {
+                    counts: HashMap::new(),
+                    last_used: now,
+                });
+            calls.last_used = now;
+            *calls.counts.entry(tool_name.to_string()).or_insert(0) += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(per_workflow: Option<u32>, per_minute: Option<u32>) -> ToolSettings {
+        ToolSettings {
+            max_calls_per_workflow: per_workflow,
+            max_calls_per_minute: per_minute,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_workflow_quota() {
+        let tracker = ToolQuotaTracker::new();
+        let quota = settings(Some(2), None);
+
+        assert!(tracker.acquire("wf-1", "MemoryTool", Some(&quota)).is_ok());
+        assert!(tracker.acquire("wf-1", "MemoryTool", Some(&quota)).is_ok());
+        let err = tracker
+            .acquire("wf-1", "MemoryTool", Some(&quota))
+            .unwrap_err();
+        assert!(
+            matches!(err, ToolError::PermissionDenied(ref msg) if msg.contains("2/2 calls allowed per workflow"))
+        );
+
+        // Other workflows and tools have their own counts
+        assert!(tracker.acquire("wf-2", "MemoryTool", Some(&quota)).is_ok());
+        assert!(tracker.acquire("wf-1", "TodoTool", Some(&quota)).is_ok());
+
+        // Tools without quota are never limited
+        for _ in 0..10 {
+            assert!(tracker.acquire("wf-1", "CalculatorTool", None).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_window() {
+        let tracker = ToolQuotaTracker::new();
+        let quota = settings(None, Some(2));
+        let start = Instant::now();
+
+        assert!(tracker
+            .acquire_at("wf-1", "MemoryTool", Some(&quota), start)
+            .is_ok());
+        assert!(tracker
+            .acquire_at(
+                "wf-2",
+                "MemoryTool",
+                Some(&quota),
+                start + Duration::from_secs(10)
+            )
+            .is_ok());
+
+        let err = tracker
+            .acquire_at(
+                "wf-1",
+                "MemoryTool",
+                Some(&quota),
+                start + Duration::from_secs(20),
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, ToolError::PermissionDenied(ref msg) if msg.contains("Retry in about 40s"))
+        );
+
+        // The first call leaves the window after one minute
+        assert!(tracker
+            .acquire_at(
+                "wf-1",
+                "MemoryTool",
+                Some(&quota),
+                start + Duration::from_secs(61)
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn test_rejected_call_is_not_counted() {
+        let tracker = ToolQuotaTracker::new();
+        let quota = settings(Some(1), Some(1));
+        let start = Instant::now();
+
+        assert!(tracker
+            .acquire_at("wf-1", "MemoryTool", Some(&quota), start)
+            .is_ok());
+        assert!(tracker
+            .acquire_at("wf-2", "MemoryTool", Some(&quota), start)
+            .is_err());
+        // wf-2 was rate limited, its workflow quota is still available
+        assert!(tracker
+            .acquire_at("wf-2", "MemoryTool", Some(&quota), start + RATE_WINDOW)
+            .is_ok());
+    }
+}
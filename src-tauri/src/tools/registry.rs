@@ -12,17 +12,102 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Centralized registry for tool discovery and validation.
+//! Centralized registry for tool discovery, validation and creation.
+//!
+//! Each tool submits its metadata and its constructor from its own module
+//! (see [`ToolConstructor`]); [`ToolRegistry::new`] collects them and
+//! [`ToolFactory`] only looks them up. Adding a tool means writing its module,
+//! the registry and the factory are left untouched.
 
+use crate::commands::SecureKeyStore;
+use crate::tools::context::AgentToolContext;
+use crate::tools::factory::ToolFactory;
+use crate::tools::Tool;
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tauri::Manager;
 
 /// Categories of tools.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToolCategory {
     Basic,
     SubAgent,
+    /// Tools composing saved workflows (primary agent only, opt-in)
+    Composite,
 }
 
+/// Dependencies and scope given to a tool constructor.
+pub struct ToolBuildContext<'a> {
+    /// Factory holding the shared dependencies (database, embedding service)
+    pub factory: &'a ToolFactory,
+    /// Requested tool name (e.g. `plugin__weather` for a plugin tool)
+    pub tool_name: &'a str,
+    /// Workflow ID for scoping
+    pub workflow_id: Option<String>,
+    /// Agent ID using the tool
+    pub agent_id: String,
+    /// Tauri app handle for event emission
+    pub app_handle: Option<tauri::AppHandle>,
+    /// Agent system access (None outside `create_tool_with_context`)
+    pub agent_context: Option<AgentToolContext>,
+    /// Whether the tool is created for the primary workflow agent
+    pub is_primary_agent: bool,
+}
+
+impl ToolBuildContext<'_> {
+    /// Workflow ID, `"default"` when the tool is not scoped to a workflow.
+    pub fn workflow_id_or_default(&self) -> String {
+        self.workflow_id
+            .clone()
+            .unwrap_or_else(|| "default".to_string())
+    }
+
+    /// Secure keystore of the app (None without app handle, e.g. in tests).
+    pub fn keystore(&self) -> Option<SecureKeyStore> {
+        self.app_handle.as_ref().and_then(|handle| {
+            handle
+                .try_state::<SecureKeyStore>()
+                .map(|keystore| keystore.inner().clone())
+        })
+    }
+
+    /// Agent system access, required by the sub-agent and composite tools.
+    pub fn require_agent_context(&self) -> Result<AgentToolContext, String> {
+        self.agent_context.clone().ok_or_else(|| {
+            format!(
+                "Tool '{}' requires AgentToolContext (use create_tool_with_context)",
+                self.tool_name
+            )
+        })
+    }
+}
+
+/// Future returned by a tool constructor.
+pub type ToolFuture<'a> = Pin<Box<dyn Future<Output = Result<Arc<dyn Tool>, String>> + Send + 'a>>;
+
+/// Creates a tool instance from its build context.
+///
+/// Tools register their constructor next to it, with a [`ToolRegistration`]
+/// submitted from their module:
+///
+/// ```ignore
+/// inventory::submit! {
+///     ToolRegistration::Tool(ToolMetadata {
+///         name: "MyTool",
+///         category: ToolCategory::Basic,
+///         requires_context: false,
+///         default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+///         constructor: MyTool::build,
+///     })
+/// }
+/// ```
+///
+/// Tools created from user data (plugins, custom tools, macros) submit a
+/// [`ToolRegistration::Family`] recognizing their names instead.
+pub type ToolConstructor = fn(ToolBuildContext<'_>) -> ToolFuture<'_>;
+
 /// Metadata for a registered tool.
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -30,103 +115,107 @@ pub struct ToolMetadata {
     pub name: &'static str,
     pub category: ToolCategory,
     pub requires_context: bool,
+    /// Default call timeout (None = the tool bounds its own waits)
+    pub default_timeout_secs: Option<u64>,
+    /// Creates an instance of the tool
+    pub constructor: ToolConstructor,
+}
+
+/// Registration of a tool, submitted from its module (see [`ToolConstructor`]).
+pub enum ToolRegistration {
+    /// Tool registered under its name
+    Tool(ToolMetadata),
+    /// Family of tools recognized by `matches`, sharing one metadata entry
+    Family {
+        matches: fn(&str) -> bool,
+        metadata: ToolMetadata,
+    },
+}
+
+inventory::collect!(ToolRegistration);
+
+/// Tools sharing one metadata entry, recognized by their name.
+struct ToolFamily {
+    /// Whether a tool name belongs to the family
+    matches: fn(&str) -> bool,
+    metadata: ToolMetadata,
 }
 
 /// Centralized registry for all available tools.
+///
+/// Tools of the WASM plugins (`plugin__<id>`) form a family sharing one
+/// metadata entry: any well-formed plugin tool name is accepted, whether the
+/// plugin is installed is checked when the tool is created. The same goes for
+/// the custom script tools (`custom__<name>`) and the macro tools
+/// (`macro__<name>`).
 pub struct ToolRegistry {
     tools: HashMap<&'static str, ToolMetadata>,
+    /// Families of tools created from user data (plugins, scripts, macros)
+    families: Vec<ToolFamily>,
 }
 
 impl ToolRegistry {
-    /// Creates a new registry with all tools registered.
+    /// Creates a new registry with all the tools submitted by their modules.
     pub fn new() -> Self {
-        let mut tools = HashMap::new();
-
-        // Basic tools
-        tools.insert(
-            "MemoryTool",
-            ToolMetadata {
-                name: "MemoryTool",
-                category: ToolCategory::Basic,
-                requires_context: false,
-            },
-        );
-        tools.insert(
-            "TodoTool",
-            ToolMetadata {
-                name: "TodoTool",
-                category: ToolCategory::Basic,
-                requires_context: false,
-            },
-        );
-        tools.insert(
-            "CalculatorTool",
-            ToolMetadata {
-                name: "CalculatorTool",
-                category: ToolCategory::Basic,
-                requires_context: false,
-            },
-        );
-        tools.insert(
-            "UserQuestionTool",
-            ToolMetadata {
-                name: "UserQuestionTool",
-                category: ToolCategory::Basic,
-                requires_context: false,
-            },
-        );
+        let mut registry = Self {
+            tools: HashMap::new(),
+            families: Vec::new(),
+        };
+        for registration in inventory::iter::<ToolRegistration> {
+            match registration {
+                ToolRegistration::Tool(metadata) => registry.register(metadata.clone()),
+                ToolRegistration::Family { matches, metadata } => {
+                    registry.register_family(*matches, metadata.clone())
+                }
+            }
+        }
+        registry
+    }
 
-        // Sub-agent tools
-        tools.insert(
-            "SpawnAgentTool",
-            ToolMetadata {
-                name: "SpawnAgentTool",
-                category: ToolCategory::SubAgent,
-                requires_context: true,
-            },
-        );
-        tools.insert(
-            "DelegateTaskTool",
-            ToolMetadata {
-                name: "DelegateTaskTool",
-                category: ToolCategory::SubAgent,
-                requires_context: true,
-            },
-        );
-        tools.insert(
-            "ParallelTasksTool",
-            ToolMetadata {
-                name: "ParallelTasksTool",
-                category: ToolCategory::SubAgent,
-                requires_context: true,
-            },
-        );
+    /// Registers a tool under its name.
+    pub fn register(&mut self, metadata: ToolMetadata) {
+        let previous = self.tools.insert(metadata.name, metadata);
+        debug_assert!(previous.is_none(), "Tool registered twice");
+    }
 
-        Self { tools }
+    /// Registers a family of tools, recognized by `matches`.
+    ///
+    /// The family's metadata name is not a tool name: it is not listed with
+    /// the registered tools.
+    pub fn register_family(&mut self, matches: fn(&str) -> bool, metadata: ToolMetadata) {
+        self.families.push(ToolFamily { matches, metadata });
     }
 
     /// Checks if a tool exists.
     pub fn has_tool(&self, name: &str) -> bool {
-        self.tools.contains_key(name)
+        self.get(name).is_some()
     }
 
     /// Returns metadata for a tool.
-    #[allow(dead_code)]
     pub fn get(&self, name: &str) -> Option<&ToolMetadata> {
-        self.tools.get(name)
+        self.tools.get(name).or_else(|| {
+            self.families
+                .iter()
+                .find(|family| (family.matches)(name))
+                .map(|family| &family.metadata)
+        })
+    }
+
+    /// Returns the default call timeout of a tool (None if unbounded or unknown).
+    pub fn default_timeout_secs(&self, name: &str) -> Option<u64> {
+        self.get(name).and_then(|m| m.default_timeout_secs)
     }
 
     /// Checks if a tool requires AgentToolContext.
     pub fn requires_context(&self, name: &str) -> bool {
-        self.tools
-            .get(name)
-            .map(|m| m.requires_context)
-            .unwrap_or(false)
+        self.get(name).map(|m| m.requires_context).unwrap_or(false)
     }
 
-    /// Returns all tool names.
+    /// Returns all tool names, sorted.
     pub fn available_tools(&self) -> Vec<&'static str> {
-        self.tools.keys().copied().collect()
+        let mut names: Vec<&'static str> = self.tools.keys().copied().collect();
+        names.sort_unstable();
+        names
     }
 
     /// Returns basic tools (no context needed).
@@ -149,7 +238,7 @@ impl ToolRegistry {
 
     /// Validates a tool name, returns error if unknown.
     pub fn validate(&self, name: &str) -> Result<&ToolMetadata, String> {
-        self.tools.get(name).ok_or_else(|| {
+        self.get(name).ok_or_else(|| {
             format!(
                 "Unknown tool: '{}'. Available tools: {:?}",
                 name,
@@ -169,9 +258,14 @@ impl Default for ToolRegistry {
 use once_cell::sync::Lazy;
 pub static TOOL_REGISTRY: Lazy<ToolRegistry> = Lazy::new(ToolRegistry::new);
 
+/// Names of the registered tools, sorted (plugin, custom and macro tools are
+/// accepted by [`ToolRegistry::has_tool`] but not listed).
+pub static KNOWN_TOOLS: Lazy<Vec<&'static str>> = Lazy::new(|| TOOL_REGISTRY.available_tools());
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
 
     #[test]
     fn test_registry_has_all_basic_tools() {
@@ -213,8 +307,19 @@ mod tests {
         assert!(basic.contains(&"TodoTool"));
         assert!(basic.contains(&"CalculatorTool"));
         assert!(basic.contains(&"UserQuestionTool"));
+        assert!(basic.contains(&"ResourceTool"));
+        assert!(basic.contains(&"SqlTool"));
+        assert!(basic.contains(&"ChartTool"));
+        assert!(basic.contains(&"ClipboardTool"));
+        assert!(basic.contains(&"ScreenshotTool"));
+        assert!(basic.contains(&"EmailTool"));
+        assert!(basic.contains(&"RssTool"));
+        assert!(basic.contains(&"UnitsTool"));
+        assert!(basic.contains(&"TextTool"));
+        assert!(basic.contains(&"PdfTool"));
+        assert!(basic.contains(&"OcrTool"));
         assert!(!basic.contains(&"SpawnAgentTool"));
-        assert_eq!(basic.len(), 4);
+        assert_eq!(basic.len(), 15);
 
         let sub_agent = TOOL_REGISTRY.sub_agent_tools();
         assert!(sub_agent.contains(&"SpawnAgentTool"));
@@ -232,12 +337,68 @@ mod tests {
         assert!(TOOL_REGISTRY.requires_context("SpawnAgentTool"));
         assert!(TOOL_REGISTRY.requires_context("DelegateTaskTool"));
         assert!(TOOL_REGISTRY.requires_context("ParallelTasksTool"));
+        assert!(TOOL_REGISTRY.requires_context("WorkflowTool"));
+    }
+
+    #[test]
+    fn test_registry_default_timeouts() {
+        assert_eq!(
+            TOOL_REGISTRY.default_timeout_secs("MemoryTool"),
+            Some(DEFAULT_TIMEOUT_SECS)
+        );
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("UserQuestionTool"), None);
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("SqlTool"), None);
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("EmailTool"), None);
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("RssTool"), None);
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("SpawnAgentTool"), None);
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("UnknownTool"), None);
     }
 
     #[test]
     fn test_registry_available_tools_count() {
         let all = TOOL_REGISTRY.available_tools();
-        assert_eq!(all.len(), 7); // 4 basic + 3 sub-agent
+        assert_eq!(all.len(), 19); // 15 basic + 3 sub-agent + 1 composite
+        assert!(all.contains(&"WorkflowTool"));
+    }
+
+    #[test]
+    fn test_known_tools() {
+        assert_eq!(KNOWN_TOOLS.len(), 19);
+        assert!(KNOWN_TOOLS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(KNOWN_TOOLS.contains(&"MemoryTool"));
+        assert!(!KNOWN_TOOLS.contains(&"PluginTool"));
+    }
+
+    #[test]
+    fn test_registry_register_tool() {
+        fn build(_ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+            Box::pin(async { Err("Not implemented".to_string()) })
+        }
+
+        let mut registry = ToolRegistry::new();
+        assert!(!registry.has_tool("EchoTool"));
+        registry.register(ToolMetadata {
+            name: "EchoTool",
+            category: ToolCategory::Basic,
+            requires_context: false,
+            default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+            constructor: build,
+        });
+        assert!(registry.has_tool("EchoTool"));
+        assert!(registry.basic_tools().contains(&"EchoTool"));
+
+        registry.register_family(
+            |name| name.starts_with("echo__"),
+            ToolMetadata {
+                name: "EchoFamily",
+                category: ToolCategory::Basic,
+                requires_context: false,
+                default_timeout_secs: None,
+                constructor: build,
+            },
+        );
+        assert_eq!(registry.get("echo__hello").unwrap().name, "EchoFamily");
+        assert!(!registry.available_tools().contains(&"EchoFamily"));
     }
 
     #[test]
@@ -248,4 +409,40 @@ mod tests {
         assert_eq!(metadata.category, ToolCategory::Basic);
         assert!(!metadata.requires_context);
     }
+
+    #[test]
+    fn test_registry_plugin_tools() {
+        assert!(TOOL_REGISTRY.has_tool("plugin__weather"));
+        assert!(TOOL_REGISTRY.validate("plugin__web-search").is_ok());
+        assert!(!TOOL_REGISTRY.requires_context("plugin__weather"));
+        assert_eq!(
+            TOOL_REGISTRY.default_timeout_secs("plugin__weather"),
+            Some(DEFAULT_TIMEOUT_SECS)
+        );
+        assert!(!TOOL_REGISTRY.has_tool("plugin__"));
+        assert!(!TOOL_REGISTRY.has_tool("plugin__../weather"));
+        // Plugins are not listed with the built-in tools
+        assert!(!TOOL_REGISTRY.basic_tools().contains(&"PluginTool"));
+    }
+
+    #[test]
+    fn test_registry_custom_tools() {
+        assert!(TOOL_REGISTRY.has_tool("custom__word_count"));
+        assert!(!TOOL_REGISTRY.requires_context("custom__word_count"));
+        assert_eq!(
+            TOOL_REGISTRY.default_timeout_secs("custom__word_count"),
+            None
+        );
+        assert!(!TOOL_REGISTRY.has_tool("custom__Word"));
+        assert!(!TOOL_REGISTRY.available_tools().contains(&"ScriptTool"));
+    }
+
+    #[test]
+    fn test_registry_macro_tools() {
+        assert!(TOOL_REGISTRY.has_tool("macro__research"));
+        assert!(!TOOL_REGISTRY.requires_context("macro__research"));
+        assert_eq!(TOOL_REGISTRY.default_timeout_secs("macro__research"), None);
+        assert!(!TOOL_REGISTRY.has_tool("macro__"));
+        assert!(!TOOL_REGISTRY.available_tools().contains(&"MacroTool"));
+    }
 }
@@ -0,0 +1,611 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ResourceTool implementation for reading MCP resources.
+
+use crate::mcp::MCPManager;
+use crate::models::mcp::MCPResourceContents;
+use crate::tools::constants::commands::MAX_MCP_RESOURCE_URI_LEN;
+use crate::tools::constants::resource::{MAX_READ_URIS, MAX_TEXT_CHARS, VALID_OPERATIONS};
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{validate_enum_value, validate_length, validate_not_empty};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+
+/// Tool for reading resources exposed by MCP servers.
+///
+/// # Scope
+///
+/// Each ResourceTool instance is scoped to the MCP servers assigned to the
+/// agent (`mcp_servers`); resources of other servers cannot be listed or read.
+pub struct ResourceTool {
+    /// MCP manager routing requests to the servers
+    mcp_manager: Arc<MCPManager>,
+    /// Server names the agent may access
+    allowed_servers: Vec<String>,
+}
+
+impl ResourceTool {
+    /// Creates a new ResourceTool scoped to the given servers.
+    ///
+    /// # Arguments
+    /// * `mcp_manager` - MCP manager
+    /// * `allowed_servers` - Names of the MCP servers assigned to the agent
+    pub fn new(mcp_manager: Arc<MCPManager>, allowed_servers: Vec<String>) -> Self {
+        Self {
+            mcp_manager,
+            allowed_servers,
+        }
+    }
+
+    /// Checks that a server is assigned to the agent.
+    fn check_server(&self, server: &str) -> ToolResult<()> {
+        if self.allowed_servers.iter().any(|s| s == server) {
+            Ok(())
+        } else {
+            Err(ToolError::PermissionDenied(format!(
+                "MCP server '{}' is not assigned to this agent. Available servers: {}",
+                server,
+                self.allowed_servers.join(", ")
+            )))
+        }
+    }
+
+    /// Lists resources of one server, or of all assigned servers.
+    #[instrument(skip(self))]
+    async fn list_resources(&self, server: Option<&str>) -> ToolResult<Value> {
+        let servers: Vec<&str> = match server {
+            Some(server) => {
+                self.check_server(server)?;
+                vec![server]
+            }
+            None => self.allowed_servers.iter().map(String::as_str).collect(),
+        };
+
+        let mut resources = Vec::new();
+        for server in servers {
+            for resource in self.mcp_manager.list_server_resources(server).await {
+                resources.push(json!({
+                    "server": server,
+                    "uri": resource.uri,
+                    "name": resource.name,
+                    "description": resource.description,
+                    "mime_type": resource.mime_type,
+                }));
+            }
+        }
+
+        debug!(count = resources.len(), "MCP resources listed");
+        Ok(ResponseBuilder::list(resources.clone(), resources.len()))
+    }
+
+    /// Reads a resource from a server.
+    #[instrument(skip(self))]
+    async fn read_resource(&self, server: &str, uri: &str) -> ToolResult<Value> {
+        self.check_server(server)?;
+
+        let contents = self
+            .mcp_manager
+            .read_resource(server, uri)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Failed to read resource: {}", e)))?;
+
+        if contents.is_empty() {
+            return Err(ToolError::NotFound(format!(
+                "Resource '{}' on server '{}' has no content",
+                uri, server
+            )));
+        }
+
+        info!(server = %server, uri = %uri, items = contents.len(), "MCP resource read");
+
+        let items: Vec<Value> = contents.iter().map(content_to_json).collect();
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("server", server)
+            .field("uri", uri)
+            .count(items.len())
+            .field("contents", items)
+            .build())
+    }
+
+    /// Reads several resources from a server.
+    ///
+    /// The reads are sent as one JSON-RPC batch when the server supports it;
+    /// a failed read is reported in its entry without failing the others.
+    #[instrument(skip(self))]
+    async fn read_resources(&self, server: &str, uris: &[String]) -> ToolResult<Value> {
+        self.check_server(server)?;
+
+        let results = self
+            .mcp_manager
+            .read_resources(server, uris)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Failed to read resources: {}", e)))?;
+
+        let resources: Vec<Value> = uris
+            .iter()
+            .zip(results)
+            .map(|(uri, result)| match result {
+                Ok(contents) => json!({
+                    "uri": uri,
+                    "success": true,
+                    "contents": contents.iter().map(content_to_json).collect::<Vec<_>>(),
+                }),
+                Err(e) => json!({
+                    "uri": uri,
+                    "success": false,
+                    "error": e.to_string(),
+                }),
+            })
+            .collect();
+
+        info!(server = %server, count = resources.len(), "MCP resources read");
+
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("server", server)
+            .count(resources.len())
+            .field("resources", resources)
+            .build())
+    }
+
+    /// Refuses: the tool needs the MCP manager and the agent's assigned
+    /// servers, it is created by the agent at execution time.
+    fn build(_ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async {
+            Err("ResourceTool is created by the agent from its assigned MCP servers".to_string())
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "ResourceTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: ResourceTool::build,
+    })
+}
+
+/// Converts a resource item to the tool output format.
+///
+/// Text is truncated to [`MAX_TEXT_CHARS`]; binary content is summarized
+/// (base64 data is not useful to the LLM and would flood the context).
+fn content_to_json(content: &MCPResourceContents) -> Value {
+    let mut item = json!({
+        "uri": content.uri,
+        "mime_type": content.mime_type,
+    });
+
+    if let Some(ref text) = content.text {
+        let total_chars = text.chars().count();
+        if total_chars > MAX_TEXT_CHARS {
+            item["text"] = json!(text.chars().take(MAX_TEXT_CHARS).collect::<String>());
+            item["truncated"] = json!(true);
+            item["total_chars"] = json!(total_chars);
+        } else {
+            item["text"] = json!(text);
+            item["truncated"] = json!(false);
+        }
+    } else if let Some(ref blob) = content.blob {
+        item["binary"] = json!(true);
+        item["size_bytes"] = json!(blob.trim_end_matches('=').len() * 3 / 4);
+    }
+
+    item
+}
+
+#[async_trait]
+impl Tool for ResourceTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "ResourceTool".to_string(),
+            name: "MCP Resources".to_string(),
+            description: format!(
+                r#"Reads resources (files, documents, schemas, records) exposed by your MCP servers, to use them as context.
+
+USE THIS TOOL WHEN:
+- You need the content of a document or file provided by an MCP server
+- You want to discover what data an MCP server exposes
+
+DO NOT USE THIS TOOL WHEN:
+- An MCP tool performs the action you need (call the tool directly)
+
+OPERATIONS:
+- list: List resources (optionally of one server)
+- read: Read a resource by server name and URI (from 'list'), or several at once with 'uris'
+
+CONSTRAINTS:
+- Only servers assigned to you are accessible: {}
+- Text content is truncated to {} characters; binary content is summarized
+
+EXAMPLES:
+1. List all resources:
+   {{"operation": "list"}}
+
+2. List resources of one server:
+   {{"operation": "list", "server": "Docs"}}
+
+3. Read a resource:
+   {{"operation": "read", "server": "Docs", "uri": "file:///docs/README.md"}}
+
+4. Read several resources of one server at once:
+   {{"operation": "read", "server": "Docs", "uris": ["file:///docs/README.md", "file:///docs/API.md"]}}"#,
+                if self.allowed_servers.is_empty() {
+                    "none".to_string()
+                } else {
+                    self.allowed_servers.join(", ")
+                },
+                MAX_TEXT_CHARS
+            ),
+
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": VALID_OPERATIONS,
+                        "description": "'list': list resources | 'read': read a resource"
+                    },
+                    "server": {
+                        "type": "string",
+                        "description": "MCP server name (required for 'read', optional filter for 'list')"
+                    },
+                    "uri": {
+                        "type": "string",
+                        "maxLength": MAX_MCP_RESOURCE_URI_LEN,
+                        "description": "Resource URI as returned by 'list' (required for 'read' unless 'uris' is given)"
+                    },
+                    "uris": {
+                        "type": "array",
+                        "items": {"type": "string", "maxLength": MAX_MCP_RESOURCE_URI_LEN},
+                        "minItems": 1,
+                        "maxItems": MAX_READ_URIS,
+                        "description": "Several resource URIs of the same server to read at once ('read')"
+                    }
+                },
+                "required": ["operation"]
+            }),
+
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "count": {"type": "integer"},
+                    "items": {
+                        "type": "array",
+                        "description": "Resources (list): server, uri, name, description, mime_type"
+                    },
+                    "contents": {
+                        "type": "array",
+                        "description": "Resource items (read): uri, mime_type, text, truncated or binary, size_bytes"
+                    },
+                    "resources": {
+                        "type": "array",
+                        "description": "Per-URI results (read with 'uris'): uri, success, contents or error"
+                    }
+                }
+            }),
+
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+
+        let operation = input["operation"].as_str().unwrap_or_default();
+        let server = input["server"].as_str();
+
+        debug!(operation = %operation, "Executing ResourceTool");
+
+        match operation {
+            "list" => self.list_resources(server).await,
+            "read" => {
+                let server = server.unwrap_or_default();
+                if let Some(uris) = input["uris"].as_array() {
+                    let uris: Vec<String> = uris
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(|uri| uri.trim().to_string())
+                        .collect();
+                    return self.read_resources(server, &uris).await;
+                }
+                let uri = input["uri"].as_str().unwrap_or_default().trim();
+                self.read_resource(server, uri).await
+            }
+            _ => Err(ToolError::InvalidInput(format!(
+                "Unknown operation: '{}'. Valid operations: {}",
+                operation,
+                VALID_OPERATIONS.join(", ")
+            ))),
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation field".to_string()))?;
+        validate_enum_value(operation, VALID_OPERATIONS, "operation")?;
+
+        if operation == "read" {
+            let server = input["server"].as_str().ok_or_else(|| {
+                ToolError::InvalidInput("Missing 'server' for read operation".to_string())
+            })?;
+            validate_not_empty(server, "server")?;
+
+            if let Some(uris) = input.get("uris") {
+                let uris = uris.as_array().ok_or_else(|| {
+                    ToolError::InvalidInput("'uris' must be an array of strings".to_string())
+                })?;
+                if uris.is_empty() || uris.len() > MAX_READ_URIS {
+                    return Err(ToolError::InvalidInput(format!(
+                        "'uris' must contain 1 to {} URIs",
+                        MAX_READ_URIS
+                    )));
+                }
+                for uri in uris {
+                    let uri = uri.as_str().ok_or_else(|| {
+                        ToolError::InvalidInput("'uris' must be an array of strings".to_string())
+                    })?;
+                    validate_not_empty(uri.trim(), "uris")?;
+                    validate_length(uri, MAX_MCP_RESOURCE_URI_LEN, "uris")?;
+                }
+            } else {
+                let uri = input["uri"].as_str().ok_or_else(|| {
+                    ToolError::InvalidInput("Missing 'uri' for read operation".to_string())
+                })?;
+                validate_not_empty(uri.trim(), "uri")?;
+                validate_length(uri, MAX_MCP_RESOURCE_URI_LEN, "uri")?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DBClient;
+    use crate::mcp::test_server::{accepted, json_reply, spawn_mock_server, MockRequest};
+    use crate::models::mcp::{MCPDeploymentMethod, MCPServerConfig};
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    fn result_for(request: &MockRequest, result: Value) -> Value {
+        json!({"jsonrpc": "2.0", "id": request.body["id"], "result": result})
+    }
+
+    async fn create_test_manager() -> (Arc<MCPManager>, tempfile::TempDir) {
+        let temp_dir = tempdir().expect("Failed to create temp dir");
+        let db_path = temp_dir.path().join("test_db");
+        let db = Arc::new(
+            DBClient::new(db_path.to_str().unwrap())
+                .await
+                .expect("Failed to create DB"),
+        );
+        db.initialize_schema().await.expect("Failed to init schema");
+        let manager = MCPManager::new(db).await.expect("Failed to create manager");
+        (Arc::new(manager), temp_dir)
+    }
+
+    async fn spawn_docs_server(manager: &MCPManager) {
+        let url = spawn_mock_server(|request| match request.rpc_method() {
+            "initialize" => json_reply(
+                &[],
+                result_for(
+                    &request,
+                    json!({
+                        "protocolVersion": "2025-06-18",
+                        "capabilities": {"resources": {}},
+                        "serverInfo": {"name": "docs", "version": "1.0"}
+                    }),
+                ),
+            ),
+            "tools/list" => json_reply(&[], result_for(&request, json!({"tools": []}))),
+            "resources/list" => json_reply(
+                &[],
+                result_for(
+                    &request,
+                    json!({"resources": [
+                        {"uri": "file:///README.md", "name": "README", "mimeType": "text/markdown"}
+                    ]}),
+                ),
+            ),
+            "resources/read" => {
+                let uri = request.body["params"]["uri"].as_str().unwrap_or_default();
+                if uri == "file:///README.md" {
+                    json_reply(
+                        &[],
+                        result_for(
+                            &request,
+                            json!({"contents": [
+                                {"uri": uri, "mimeType": "text/markdown", "text": "# Docs"}
+                            ]}),
+                        ),
+                    )
+                } else {
+                    json_reply(
+                        &[],
+                        json!({
+                            "jsonrpc": "2.0",
+                            "id": request.body["id"],
+                            "error": {"code": -32002, "message": "Resource not found"}
+                        }),
+                    )
+                }
+            }
+            _ => accepted(),
+        })
+        .await;
+
+        manager
+            .spawn_server(MCPServerConfig {
+                id: "docs".to_string(),
+                name: "Docs".to_string(),
+                enabled: true,
+                command: MCPDeploymentMethod::Http,
+                args: vec![url],
+                env: HashMap::new(),
+                description: None,
+            })
+            .await
+            .expect("Failed to spawn mock server");
+    }
+
+    #[test]
+    fn test_content_to_json_truncates_text() {
+        let long = MCPResourceContents {
+            uri: "file:///big.txt".to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: Some("é".repeat(MAX_TEXT_CHARS + 10)),
+            blob: None,
+        };
+        let item = content_to_json(&long);
+        assert_eq!(item["truncated"], true);
+        assert_eq!(item["total_chars"], MAX_TEXT_CHARS + 10);
+        assert_eq!(
+            item["text"].as_str().unwrap().chars().count(),
+            MAX_TEXT_CHARS
+        );
+
+        let binary = MCPResourceContents {
+            uri: "file:///logo.png".to_string(),
+            mime_type: Some("image/png".to_string()),
+            text: None,
+            blob: Some("AAAA".to_string()),
+        };
+        let item = content_to_json(&binary);
+        assert_eq!(item["binary"], true);
+        assert_eq!(item["size_bytes"], 3);
+        assert!(item.get("text").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (manager, _dir) = create_test_manager().await;
+        let tool = ResourceTool::new(manager, vec!["Docs".to_string()]);
+
+        assert!(tool.validate_input(&json!({"operation": "list"})).is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "read", "server": "Docs", "uri": "file:///a"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "read", "server": "Docs"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "read", "uri": "file:///a"}))
+            .is_err());
+        assert!(tool
+            .validate_input(
+                &json!({"operation": "read", "server": "Docs", "uris": ["file:///a", "file:///b"]})
+            )
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "read", "server": "Docs", "uris": []}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "read", "server": "Docs", "uris": [1]}))
+            .is_err());
+        assert!(tool.validate_input(&json!({"operation": "write"})).is_err());
+        assert!(tool.validate_input(&json!("list")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_and_read_resources() {
+        let (manager, _dir) = create_test_manager().await;
+        spawn_docs_server(&manager).await;
+        let tool = ResourceTool::new(manager, vec!["Docs".to_string()]);
+
+        let listed = tool.execute(json!({"operation": "list"})).await.unwrap();
+        assert_eq!(listed["count"], 1);
+        assert_eq!(listed["items"][0]["server"], "Docs");
+        assert_eq!(listed["items"][0]["uri"], "file:///README.md");
+
+        let read = tool
+            .execute(json!({"operation": "read", "server": "Docs", "uri": "file:///README.md"}))
+            .await
+            .unwrap();
+        assert_eq!(read["success"], true);
+        assert_eq!(read["contents"][0]["text"], "# Docs");
+        assert_eq!(read["contents"][0]["mime_type"], "text/markdown");
+
+        let missing = tool
+            .execute(json!({"operation": "read", "server": "Docs", "uri": "file:///missing"}))
+            .await;
+        assert!(
+            matches!(missing, Err(ToolError::ExecutionFailed(msg)) if msg.contains("Resource not found"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_several_resources() {
+        let (manager, _dir) = create_test_manager().await;
+        spawn_docs_server(&manager).await;
+        let tool = ResourceTool::new(manager, vec!["Docs".to_string()]);
+
+        let read = tool
+            .execute(json!({
+                "operation": "read",
+                "server": "Docs",
+                "uris": ["file:///README.md", "file:///missing"]
+            }))
+            .await
+            .unwrap();
+        assert_eq!(read["count"], 2);
+        assert_eq!(read["resources"][0]["success"], true);
+        assert_eq!(read["resources"][0]["contents"][0]["text"], "# Docs");
+        assert_eq!(read["resources"][1]["uri"], "file:///missing");
+        assert_eq!(read["resources"][1]["success"], false);
+        assert!(read["resources"][1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("Resource not found"));
+    }
+
+    #[tokio::test]
+    async fn test_unassigned_server_is_denied() {
+        let (manager, _dir) = create_test_manager().await;
+        spawn_docs_server(&manager).await;
+        let tool = ResourceTool::new(manager, vec!["Other".to_string()]);
+
+        let result = tool
+            .execute(json!({"operation": "read", "server": "Docs", "uri": "file:///README.md"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::PermissionDenied(_))));
+
+        let listed = tool.execute(json!({"operation": "list"})).await.unwrap();
+        assert_eq!(listed["count"], 0);
+    }
+}
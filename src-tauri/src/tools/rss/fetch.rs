@@ -0,0 +1,97 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Feed downloading.
+
+use super::parse::{parse_feed, ParsedFeed};
+use crate::tools::constants::rss as rss_const;
+use std::time::Duration;
+
+/// Validates a feed URL (http or https, bounded length).
+pub fn validate_feed_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    if url.is_empty() || url.len() > rss_const::MAX_URL_LENGTH {
+        return Err(format!(
+            "Feed URL must be 1-{} characters",
+            rss_const::MAX_URL_LENGTH
+        ));
+    }
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid feed URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err("Feed URL must be an http or https URL".to_string());
+    }
+    Ok(url.to_string())
+}
+
+/// Builds the HTTP client used to download feeds.
+pub fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(rss_const::TIMEOUT_SECS))
+        .user_agent(concat!("Zileo-Chat/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// Downloads and parses a feed (at most `MAX_FEED_BYTES`).
+pub async fn fetch_feed(client: &reqwest::Client, url: &str) -> Result<ParsedFeed, String> {
+    let mut response = client
+        .get(url)
+        .header(
+            reqwest::header::ACCEPT,
+            "application/rss+xml, application/atom+xml, application/xml, text/xml;q=0.9, */*;q=0.8",
+        )
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download feed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Feed request failed with status {}",
+            response.status()
+        ));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to download feed: {}", e))?
+    {
+        if body.len() + chunk.len() > rss_const::MAX_FEED_BYTES {
+            return Err(format!(
+                "Feed exceeds {} MB",
+                rss_const::MAX_FEED_BYTES / (1024 * 1024)
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    parse_feed(&String::from_utf8_lossy(&body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_feed_url() {
+        assert_eq!(
+            validate_feed_url(" https://example.com/feed.xml ").unwrap(),
+            "https://example.com/feed.xml"
+        );
+        assert!(validate_feed_url("http://example.com/rss").is_ok());
+        assert!(validate_feed_url("").is_err());
+        assert!(validate_feed_url("file:///etc/passwd").is_err());
+        assert!(validate_feed_url("example.com/feed").is_err());
+        assert!(validate_feed_url(&format!("https://example.com/{}", "a".repeat(2048))).is_err());
+    }
+}
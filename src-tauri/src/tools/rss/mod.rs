@@ -0,0 +1,30 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RSS Tool Module
+//!
+//! Provides the RssTool, which lets agents read the news feeds configured by
+//! the user:
+//! - RSS 0.9x/1.0/2.0 and Atom parsing
+//! - Deduplication: each item is returned once by `fetch_new`
+//!
+//! Scheduled digests (feed commands) run an agent with the RssTool daily and
+//! post its summaries into a dedicated workflow.
+
+pub mod fetch;
+mod parse;
+pub mod store;
+mod tool;
+
+pub use tool::RssTool;
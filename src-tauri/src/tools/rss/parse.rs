@@ -0,0 +1,372 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RSS and Atom parsing.
+//!
+//! Namespaces are ignored (`dc:date`, `content:encoded`), which covers RSS
+//! 0.9x/1.0/2.0 and Atom 1.0 with a single pass over the document.
+
+use crate::tools::constants::rss as rss_const;
+use crate::tools::validation_helper::safe_truncate;
+use chrono::{DateTime, Utc};
+use quick_xml::escape::{resolve_html5_entity, unescape_with};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// Feed read from a document
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedFeed {
+    /// Feed title
+    pub title: Option<String>,
+    /// Website of the feed
+    pub site_url: Option<String>,
+    /// Items in document order (usually newest first)
+    pub items: Vec<ParsedItem>,
+}
+
+/// Item read from a document
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedItem {
+    /// Stable identifier (guid, id or link)
+    pub guid: String,
+    /// Title (summary start when the item has none)
+    pub title: String,
+    /// Link to the article
+    pub link: Option<String>,
+    /// Plain text summary
+    pub summary: Option<String>,
+    /// Publication date (RFC 3339 when it could be parsed)
+    pub published_at: Option<String>,
+}
+
+/// Fields of the item being read
+#[derive(Default)]
+struct ItemFields {
+    guid: Option<String>,
+    title: Option<String>,
+    link: Option<String>,
+    summary: Option<String>,
+    content: Option<String>,
+    date: Option<String>,
+}
+
+impl ItemFields {
+    /// Builds the item, or None without any identifying field.
+    fn finish(self) -> Option<ParsedItem> {
+        let summary = self
+            .summary
+            .or(self.content)
+            .map(|html| html_to_text(&html))
+            .filter(|text| !text.is_empty())
+            .map(|text| safe_truncate(&text, rss_const::SUMMARY_CHARS, true));
+        let title = self
+            .title
+            .map(|title| html_to_text(&title))
+            .filter(|title| !title.is_empty())
+            .or_else(|| summary.clone())?;
+        let guid = self
+            .guid
+            .or_else(|| self.link.clone())
+            .unwrap_or_else(|| title.clone());
+        Some(ParsedItem {
+            guid,
+            title: safe_truncate(&title, rss_const::TITLE_CHARS, true),
+            link: self.link,
+            summary,
+            published_at: self.date.map(|date| normalize_date(&date)),
+        })
+    }
+}
+
+/// Elements whose text is read
+const TEXT_FIELDS: &[&str] = &[
+    "title",
+    "link",
+    "guid",
+    "id",
+    "description",
+    "summary",
+    "encoded",
+    "content",
+    "pubDate",
+    "published",
+    "updated",
+    "date",
+];
+
+/// Parses an RSS or Atom document.
+pub fn parse_feed(xml: &str) -> Result<ParsedFeed, String> {
+    // Text is not trimmed by the reader: entities split it into several
+    // events, and the spaces around them are significant
+    let mut reader = Reader::from_str(xml);
+
+    let mut feed = ParsedFeed::default();
+    let mut is_feed = false;
+    let mut stack: Vec<String> = Vec::new();
+    let mut item: Option<ItemFields> = None;
+    let mut text = String::new();
+
+    loop {
+        let event = reader.read_event().map_err(|e| {
+            format!(
+                "Invalid feed XML at position {}: {}",
+                reader.error_position(),
+                e
+            )
+        })?;
+        match event {
+            Event::Start(element) => {
+                let name = local_name(&element);
+                match name.as_str() {
+                    "rss" | "RDF" | "feed" if stack.is_empty() => is_feed = true,
+                    "item" | "entry" => item = Some(ItemFields::default()),
+                    "link" => read_atom_link(&element, stack.last(), &mut item, &mut feed),
+                    _ => {}
+                }
+                if TEXT_FIELDS.contains(&name.as_str()) {
+                    text.clear();
+                }
+                stack.push(name);
+            }
+            Event::Empty(element) if local_name(&element) == "link" => {
+                read_atom_link(&element, stack.last(), &mut item, &mut feed);
+            }
+            Event::Text(content) => text.push_str(&content.decode().unwrap_or_default()),
+            Event::CData(content) => text.push_str(&content.decode().unwrap_or_default()),
+            Event::GeneralRef(reference) => {
+                if let Ok(Some(ch)) = reference.resolve_char_ref() {
+                    text.push(ch);
+                } else {
+                    let name = reference.decode().unwrap_or_default();
+                    match resolve_html5_entity(&name) {
+                        Some(value) => text.push_str(value),
+                        None => {
+                            text.push('&');
+                            text.push_str(&name);
+                            text.push(';');
+                        }
+                    }
+                }
+            }
+            Event::End(_) => {
+                let Some(name) = stack.pop() else {
+                    continue;
+                };
+                if name == "item" || name == "entry" {
+                    if let Some(parsed) = item.take().and_then(ItemFields::finish) {
+                        feed.items.push(parsed);
+                    }
+                    continue;
+                }
+                if !TEXT_FIELDS.contains(&name.as_str()) {
+                    continue;
+                }
+                let value = std::mem::take(&mut text).trim().to_string();
+                if value.is_empty() {
+                    continue;
+                }
+                let parent = stack.last().map(String::as_str);
+                match (item.as_mut(), parent) {
+                    (Some(fields), Some("item" | "entry")) => {
+                        let slot = match name.as_str() {
+                            "title" => &mut fields.title,
+                            "link" => &mut fields.link,
+                            "guid" | "id" => &mut fields.guid,
+                            "description" | "summary" => &mut fields.summary,
+                            "encoded" | "content" => &mut fields.content,
+                            _ => &mut fields.date,
+                        };
+                        slot.get_or_insert(value);
+                    }
+                    (None, Some("channel" | "feed")) => match name.as_str() {
+                        "title" => {
+                            feed.title.get_or_insert(html_to_text(&value));
+                        }
+                        "link" => {
+                            feed.site_url.get_or_insert(value);
+                        }
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !is_feed {
+        return Err("The document is not an RSS or Atom feed".to_string());
+    }
+    Ok(feed)
+}
+
+/// Local name of an element (namespace prefix removed).
+fn local_name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.local_name().as_ref()).into_owned()
+}
+
+/// Reads an Atom `<link rel="alternate" href="...">` of an entry or feed.
+fn read_atom_link(
+    element: &BytesStart,
+    parent: Option<&String>,
+    item: &mut Option<ItemFields>,
+    feed: &mut ParsedFeed,
+) {
+    let mut href = None;
+    let mut alternate = true;
+    for attribute in element.attributes().flatten() {
+        let value = attribute.unescape_value().unwrap_or_default().into_owned();
+        match attribute.key.local_name().as_ref() {
+            b"href" => href = Some(value),
+            b"rel" => alternate = value == "alternate",
+            _ => {}
+        }
+    }
+    let Some(href) = href.filter(|href| !href.is_empty() && alternate) else {
+        return;
+    };
+    match (item.as_mut(), parent.map(String::as_str)) {
+        (Some(fields), Some("entry")) => {
+            fields.link.get_or_insert(href);
+        }
+        (None, Some("feed")) => {
+            feed.site_url.get_or_insert(href);
+        }
+        _ => {}
+    }
+}
+
+/// Converts an RFC 2822 (RSS) or RFC 3339 (Atom) date to RFC 3339 UTC,
+/// keeping unparseable dates as given.
+fn normalize_date(date: &str) -> String {
+    DateTime::parse_from_rfc2822(date)
+        .or_else(|_| DateTime::parse_from_rfc3339(date))
+        .map(|date| date.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// Converts an HTML fragment to plain text (tags removed, entities decoded,
+/// whitespace collapsed).
+pub fn html_to_text(html: &str) -> String {
+    let mut stripped = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => {
+                in_tag = true;
+                stripped.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => stripped.push(ch),
+            _ => {}
+        }
+    }
+    let decoded = unescape_with(&stripped, resolve_html5_entity)
+        .map(|text| text.into_owned())
+        .unwrap_or(stripped.clone());
+    decoded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rss() {
+        let xml = r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+  <channel>
+    <title>Example &amp; News</title>
+    <link>https://example.com/</link>
+    <image><title>Logo</title><url>https://example.com/logo.png</url></image>
+    <item>
+      <title>First</title>
+      <link>https://example.com/1</link>
+      <guid isPermaLink="false">item-1</guid>
+      <description>&lt;p&gt;Hello&amp;nbsp;&lt;b&gt;world&lt;/b&gt;&lt;/p&gt;</description>
+      <pubDate>Tue, 10 Jun 2025 04:00:00 GMT</pubDate>
+    </item>
+    <item>
+      <link>https://example.com/2</link>
+      <content:encoded><![CDATA[<p>Only content</p>]]></content:encoded>
+    </item>
+    <item><category>empty</category></item>
+  </channel>
+</rss>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Example & News"));
+        assert_eq!(feed.site_url.as_deref(), Some("https://example.com/"));
+        assert_eq!(feed.items.len(), 2);
+
+        let first = &feed.items[0];
+        assert_eq!(first.guid, "item-1");
+        assert_eq!(first.title, "First");
+        assert_eq!(first.link.as_deref(), Some("https://example.com/1"));
+        assert_eq!(first.summary.as_deref(), Some("Hello world"));
+        assert_eq!(
+            first.published_at.as_deref(),
+            Some("2025-06-10T04:00:00+00:00")
+        );
+
+        let second = &feed.items[1];
+        assert_eq!(second.guid, "https://example.com/2");
+        assert_eq!(second.title, "Only content");
+    }
+
+    #[test]
+    fn test_parse_atom() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="text">Atom Blog</title>
+  <link rel="self" href="https://blog.example/feed.xml"/>
+  <link href="https://blog.example/"/>
+  <entry>
+    <title>Post</title>
+    <link rel="alternate" href="https://blog.example/post"/>
+    <id>urn:uuid:1</id>
+    <updated>2025-06-10T06:00:00+02:00</updated>
+    <summary>Short</summary>
+    <content type="xhtml"><div>Long</div></content>
+  </entry>
+</feed>"#;
+        let feed = parse_feed(xml).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("Atom Blog"));
+        assert_eq!(feed.site_url.as_deref(), Some("https://blog.example/"));
+        assert_eq!(
+            feed.items,
+            vec![ParsedItem {
+                guid: "urn:uuid:1".to_string(),
+                title: "Post".to_string(),
+                link: Some("https://blog.example/post".to_string()),
+                summary: Some("Short".to_string()),
+                published_at: Some("2025-06-10T04:00:00+00:00".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_other_documents() {
+        assert!(parse_feed("<html><body>Not a feed</body></html>").is_err());
+        assert!(parse_feed("<rss><channel><title>x</rss>").is_err());
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        assert_eq!(
+            html_to_text("<p>A&amp;B</p>\n<p>caf&eacute; &#233;</p>"),
+            "A&B café é"
+        );
+        assert_eq!(html_to_text("plain  text"), "plain text");
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Feed storage.
+//!
+//! Feeds live in the `feed` table. Every item returned to an agent is
+//! recorded in `feed_item` under an ID derived from its feed and guid, so a
+//! feed fetched again only yields the items not seen yet.
+
+use super::parse::ParsedItem;
+use crate::db::DBClient;
+use crate::models::feed::{Feed, FeedItem};
+use crate::tools::constants::rss as rss_const;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use tracing::warn;
+
+/// Fields selected when loading feeds
+pub const FEED_SELECT_FIELDS: &str = "meta::id(id) AS id, url, title, site_url, \
+     last_fetched_at, last_error, created_at";
+
+/// Fields selected when loading items
+const ITEM_SELECT_FIELDS: &str = "feed_id, guid, title, link, summary, published_at";
+
+/// Record ID of an item of a feed.
+fn item_record_id(feed_id: &str, guid: &str) -> String {
+    let digest = Sha256::digest(format!("{}\n{}", feed_id, guid).as_bytes());
+    digest[..16].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Parses a feed record, logging and skipping a malformed one.
+fn parse_feed(row: serde_json::Value) -> Option<Feed> {
+    let id = row["id"].as_str().unwrap_or_default().to_string();
+    match serde_json::from_value(row) {
+        Ok(feed) => Some(feed),
+        Err(e) => {
+            warn!(feed_id = %id, error = %e, "Skipping malformed feed record");
+            None
+        }
+    }
+}
+
+/// Lists the feeds, sorted by title then URL.
+pub async fn list_feeds(db: &DBClient) -> Result<Vec<Feed>, String> {
+    let rows = db
+        .query_json(&format!(
+            "SELECT {} FROM feed ORDER BY title, url",
+            FEED_SELECT_FIELDS
+        ))
+        .await
+        .map_err(|e| format!("Failed to list feeds: {}", e))?;
+    Ok(rows.into_iter().filter_map(parse_feed).collect())
+}
+
+/// Loads a feed by ID.
+pub async fn load_feed(db: &DBClient, feed_id: &str) -> Result<Option<Feed>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM feed WHERE meta::id(id) = $id",
+                FEED_SELECT_FIELDS
+            ),
+            vec![("id".to_string(), json!(feed_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load feed: {}", e))?;
+    Ok(rows.into_iter().find_map(parse_feed))
+}
+
+/// Deletes a feed and its recorded items.
+pub async fn delete_feed(db: &DBClient, feed_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "DELETE feed_item WHERE feed_id = $id; DELETE type::thing('feed', $id)",
+        vec![("id".to_string(), json!(feed_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete feed: {}", e))
+}
+
+/// Records the outcome of a fetch of a feed.
+pub async fn set_fetch_result(
+    db: &DBClient,
+    feed_id: &str,
+    error: Option<&str>,
+) -> Result<(), String> {
+    db.execute_with_params(
+        "UPDATE type::thing('feed', $id) SET last_fetched_at = time::now(), last_error = $error",
+        vec![
+            ("id".to_string(), json!(feed_id)),
+            ("error".to_string(), json!(error)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to update feed: {}", e))
+}
+
+/// Records the items of a feed not seen yet and returns them.
+///
+/// At most `MAX_NEW_ITEMS_PER_FEED` items are recorded per call, in document
+/// order (newest first in most feeds); the others are returned by the next
+/// calls. The oldest records beyond `MAX_STORED_ITEMS_PER_FEED` are pruned.
+pub async fn record_new_items(
+    db: &DBClient,
+    feed_id: &str,
+    items: Vec<ParsedItem>,
+) -> Result<Vec<FeedItem>, String> {
+    let rows = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id FROM feed_item WHERE feed_id = $feed_id",
+            vec![("feed_id".to_string(), json!(feed_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load feed items: {}", e))?;
+    let mut seen: HashSet<String> = rows
+        .iter()
+        .filter_map(|row| row["id"].as_str().map(str::to_string))
+        .collect();
+
+    let mut new_items = Vec::new();
+    for item in items {
+        if new_items.len() >= rss_const::MAX_NEW_ITEMS_PER_FEED {
+            break;
+        }
+        let record_id = item_record_id(feed_id, &item.guid);
+        if !seen.insert(record_id.clone()) {
+            continue;
+        }
+        let item = FeedItem {
+            feed_id: feed_id.to_string(),
+            guid: item.guid,
+            title: item.title,
+            link: item.link,
+            summary: item.summary,
+            published_at: item.published_at,
+        };
+        db.create("feed_item", &record_id, item.clone())
+            .await
+            .map_err(|e| format!("Failed to record feed item: {}", e))?;
+        new_items.push(item);
+    }
+
+    if seen.len() > rss_const::MAX_STORED_ITEMS_PER_FEED {
+        db.execute_with_params(
+            "LET $kept = (SELECT VALUE id FROM feed_item WHERE feed_id = $feed_id \
+             ORDER BY seen_at DESC LIMIT $limit); \
+             DELETE feed_item WHERE feed_id = $feed_id AND id NOTINSIDE $kept",
+            vec![
+                ("feed_id".to_string(), json!(feed_id)),
+                (
+                    "limit".to_string(),
+                    json!(rss_const::MAX_STORED_ITEMS_PER_FEED),
+                ),
+            ],
+        )
+        .await
+        .map_err(|e| format!("Failed to prune feed items: {}", e))?;
+    }
+    Ok(new_items)
+}
+
+/// Lists the recorded items, most recently seen first.
+pub async fn recent_items(
+    db: &DBClient,
+    feed_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<FeedItem>, String> {
+    let (filter, mut params) = match feed_id {
+        Some(feed_id) => (
+            "WHERE feed_id = $feed_id ",
+            vec![("feed_id".to_string(), json!(feed_id))],
+        ),
+        None => ("", Vec::new()),
+    };
+    params.push(("limit".to_string(), json!(limit)));
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {}, seen_at FROM feed_item {}ORDER BY seen_at DESC LIMIT $limit",
+                ITEM_SELECT_FIELDS, filter
+            ),
+            params,
+        )
+        .await
+        .map_err(|e| format!("Failed to load feed items: {}", e))?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let feed_id = row["feed_id"].as_str().unwrap_or_default().to_string();
+            let guid = row["guid"].as_str().unwrap_or_default().to_string();
+            match serde_json::from_value(row) {
+                Ok(item) => Some(item),
+                Err(e) => {
+                    warn!(feed_id = %feed_id, guid = %guid, error = %e, "Skipping malformed feed item record");
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::feed::FeedCreate;
+
+    fn parsed(guid: &str) -> ParsedItem {
+        ParsedItem {
+            guid: guid.to_string(),
+            title: format!("Title {}", guid),
+            link: Some(format!("https://example.com/{}", guid)),
+            summary: None,
+            published_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_new_items_deduplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        db.create(
+            "feed",
+            "feed-1",
+            FeedCreate {
+                url: "https://example.com/feed.xml".to_string(),
+                title: Some("Example".to_string()),
+                site_url: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let first = record_new_items(&db, "feed-1", vec![parsed("a"), parsed("b"), parsed("a")])
+            .await
+            .unwrap();
+        assert_eq!(first.len(), 2);
+
+        let second = record_new_items(&db, "feed-1", vec![parsed("c"), parsed("b")])
+            .await
+            .unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].guid, "c");
+
+        // Same guid in another feed is a different item
+        let other = record_new_items(&db, "feed-2", vec![parsed("a")])
+            .await
+            .unwrap();
+        assert_eq!(other.len(), 1);
+
+        assert_eq!(
+            recent_items(&db, Some("feed-1"), 10).await.unwrap().len(),
+            3
+        );
+        assert_eq!(recent_items(&db, None, 2).await.unwrap().len(), 2);
+
+        set_fetch_result(&db, "feed-1", Some("timeout"))
+            .await
+            .unwrap();
+        let feed = load_feed(&db, "feed-1").await.unwrap().unwrap();
+        assert_eq!(feed.last_error.as_deref(), Some("timeout"));
+        assert!(feed.last_fetched_at.is_some());
+        assert_eq!(list_feeds(&db).await.unwrap().len(), 1);
+
+        delete_feed(&db, "feed-1").await.unwrap();
+        assert!(load_feed(&db, "feed-1").await.unwrap().is_none());
+        assert!(recent_items(&db, Some("feed-1"), 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,423 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RssTool implementation.
+//!
+//! Feeds are managed by the user (feed commands); the tool only reads them.
+//! `fetch_new` records the returned items, so each item is reported once.
+
+use super::fetch::{fetch_feed, http_client};
+use super::store::{list_feeds, load_feed, recent_items, record_new_items, set_fetch_result};
+use crate::db::DBClient;
+use crate::models::feed::{Feed, FeedItem};
+use crate::tools::constants::rss as rss_const;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{validate_enum_value, validate_not_empty};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, instrument, warn};
+
+/// Tool reading the new items of the user's news feeds.
+pub struct RssTool {
+    /// Database client (feeds and recorded items)
+    db: Arc<DBClient>,
+    /// Agent ID using this tool
+    agent_id: String,
+}
+
+/// JSON of an item with the title of its feed.
+fn item_json(item: &FeedItem, feed_titles: &HashMap<String, String>) -> Value {
+    json!({
+        "feed": feed_titles.get(&item.feed_id).cloned().unwrap_or_default(),
+        "title": item.title,
+        "link": item.link,
+        "summary": item.summary,
+        "published_at": item.published_at,
+    })
+}
+
+/// Display title of a feed (its URL when it has no title).
+fn feed_title(feed: &Feed) -> String {
+    feed.title.clone().unwrap_or_else(|| feed.url.clone())
+}
+
+impl RssTool {
+    /// Creates a new RssTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `agent_id` - Agent ID using the tool
+    pub fn new(db: Arc<DBClient>, agent_id: String) -> Self {
+        Self { db, agent_id }
+    }
+
+    /// Lists the configured feeds.
+    async fn list(&self) -> ToolResult<Value> {
+        let feeds: Vec<Value> = list_feeds(&self.db)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .into_iter()
+            .map(|feed| {
+                json!({
+                    "feed_id": feed.id,
+                    "title": feed_title(&feed),
+                    "url": feed.url,
+                    "last_fetched_at": feed.last_fetched_at,
+                    "last_error": feed.last_error,
+                })
+            })
+            .collect();
+        let count = feeds.len();
+        Ok(ResponseBuilder::list(feeds, count))
+    }
+
+    /// Loads one feed or all of them.
+    async fn feeds(&self, feed_id: Option<&str>) -> ToolResult<Vec<Feed>> {
+        match feed_id {
+            Some(feed_id) => load_feed(&self.db, feed_id)
+                .await
+                .map_err(ToolError::DatabaseError)?
+                .map(|feed| vec![feed])
+                .ok_or_else(|| ToolError::NotFound(format!("Feed '{}' not found", feed_id))),
+            None => list_feeds(&self.db).await.map_err(ToolError::DatabaseError),
+        }
+    }
+
+    /// Downloads a feed and records its new items.
+    async fn fetch_one(
+        &self,
+        client: &reqwest::Client,
+        feed: &Feed,
+    ) -> Result<Vec<FeedItem>, String> {
+        let result = match fetch_feed(client, &feed.url).await {
+            Ok(parsed) => record_new_items(&self.db, &feed.id, parsed.items).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = set_fetch_result(
+            &self.db,
+            &feed.id,
+            result.as_ref().err().map(String::as_str),
+        )
+        .await
+        {
+            warn!(feed_id = %feed.id, error = %e, "Failed to record feed fetch");
+        }
+        result
+    }
+
+    /// Fetches the feeds and returns the items not returned before.
+    #[instrument(skip(self), fields(agent_id = %self.agent_id))]
+    async fn fetch_new(&self, feed_id: Option<&str>) -> ToolResult<Value> {
+        let feeds = self.feeds(feed_id).await?;
+        if feeds.is_empty() {
+            return Ok(ResponseBuilder::new()
+                .success(true)
+                .data("items", Vec::<Value>::new())
+                .count(0)
+                .message("No feed configured: feeds are managed by the user")
+                .build());
+        }
+
+        let client = http_client().map_err(ToolError::ExecutionFailed)?;
+        let outcomes: Vec<(Feed, Result<Vec<FeedItem>, String>)> = stream::iter(feeds)
+            .map(|feed| {
+                let client = &client;
+                async move {
+                    let result = self.fetch_one(client, &feed).await;
+                    (feed, result)
+                }
+            })
+            .buffer_unordered(rss_const::MAX_CONCURRENT_FETCHES)
+            .collect()
+            .await;
+
+        let mut feed_titles = HashMap::new();
+        let mut items = Vec::new();
+        let mut failed = Vec::new();
+        for (feed, result) in &outcomes {
+            feed_titles.insert(feed.id.clone(), feed_title(feed));
+            match result {
+                Ok(new_items) => items.extend(new_items),
+                Err(error) => failed.push(json!({
+                    "feed_id": feed.id,
+                    "title": feed_title(feed),
+                    "error": error,
+                })),
+            }
+        }
+        let items: Vec<Value> = items
+            .into_iter()
+            .map(|item| item_json(item, &feed_titles))
+            .collect();
+
+        info!(
+            feeds = outcomes.len(),
+            new_items = items.len(),
+            failed = failed.len(),
+            "Feeds fetched"
+        );
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .data("items", &items)
+            .data("failed_feeds", &failed)
+            .count(items.len())
+            .message(format!(
+                "{} new item(s) from {} feed(s); these items will not be returned again",
+                items.len(),
+                outcomes.len() - failed.len()
+            ))
+            .build())
+    }
+
+    /// Lists the items already returned, most recent first.
+    async fn recent(&self, feed_id: Option<&str>, limit: usize) -> ToolResult<Value> {
+        let feed_titles: HashMap<String, String> = list_feeds(&self.db)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .iter()
+            .map(|feed| (feed.id.clone(), feed_title(feed)))
+            .collect();
+        let items: Vec<Value> = recent_items(&self.db, feed_id, limit)
+            .await
+            .map_err(ToolError::DatabaseError)?
+            .iter()
+            .map(|item| item_json(item, &feed_titles))
+            .collect();
+        let count = items.len();
+        Ok(ResponseBuilder::list(items, count))
+    }
+
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let tool = RssTool::new(ctx.factory.get_db(), ctx.agent_id);
+            info!("RssTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Each feed request is bounded by its own timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "RssTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: None,
+        constructor: RssTool::build,
+    })
+}
+
+#[async_trait]
+impl Tool for RssTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "RssTool".to_string(),
+            name: "RSS Tool".to_string(),
+            description: format!(
+                r#"Reads the RSS and Atom news feeds configured by the user.
+
+USE THIS TOOL WHEN:
+- The user asks for news, updates or a digest of their feeds
+- A scheduled digest asks you to summarize the new items
+
+OPERATIONS:
+- list_feeds: configured feeds with their last fetch outcome
+- fetch_new: downloads the feeds (all, or one with "feed_id") and returns only the items never returned before (at most {} per feed); they are then marked as seen
+- recent_items: items already returned, most recent first (for follow-up questions)
+
+IMPORTANT CONSTRAINTS:
+- Feeds are managed by the user; you cannot add or remove feeds
+- fetch_new marks items as seen: include every returned item you summarize, with its link
+- Feeds that could not be downloaded are listed in "failed_feeds"
+
+EXAMPLES:
+1. {{"operation": "fetch_new"}}
+2. {{"operation": "fetch_new", "feed_id": "<feed_id from list_feeds>"}}
+3. {{"operation": "recent_items", "limit": 10}}"#,
+                rss_const::MAX_NEW_ITEMS_PER_FEED
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": rss_const::VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "feed_id": {
+                        "type": "string",
+                        "description": "Feed ID from list_feeds (fetch_new, recent_items; default: all feeds)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "maximum": rss_const::MAX_RECENT_LIMIT,
+                        "description": "Items listed by recent_items (default: 20)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "items": {"type": "array"},
+                    "failed_feeds": {"type": "array"},
+                    "count": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        let feed_id = input["feed_id"].as_str().map(str::trim);
+        match input["operation"].as_str().unwrap_or_default() {
+            "list_feeds" => self.list().await,
+            "fetch_new" => self.fetch_new(feed_id).await,
+            _ => {
+                let limit = input["limit"]
+                    .as_u64()
+                    .map_or(rss_const::DEFAULT_RECENT_LIMIT, |limit| limit as usize);
+                self.recent(feed_id, limit).await
+            }
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation field".to_string()))?;
+        validate_enum_value(operation, rss_const::VALID_OPERATIONS, "operation")?;
+
+        if let Some(feed_id) = input.get("feed_id").filter(|feed_id| !feed_id.is_null()) {
+            let feed_id = feed_id
+                .as_str()
+                .ok_or_else(|| ToolError::InvalidInput("'feed_id' must be a string".to_string()))?;
+            validate_not_empty(feed_id.trim(), "feed_id")?;
+        }
+        if let Some(limit) = input.get("limit").filter(|limit| !limit.is_null()) {
+            if limit
+                .as_u64()
+                .is_none_or(|limit| limit == 0 || limit as usize > rss_const::MAX_RECENT_LIMIT)
+            {
+                return Err(ToolError::InvalidInput(format!(
+                    "'limit' must be 1-{}",
+                    rss_const::MAX_RECENT_LIMIT
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::feed::FeedCreate;
+
+    async fn setup() -> (RssTool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+        (RssTool::new(Arc::new(db), "agent".to_string()), dir)
+    }
+
+    #[tokio::test]
+    async fn test_validate_input() {
+        let (tool, _dir) = setup().await;
+        assert!(tool
+            .validate_input(&json!({"operation": "fetch_new"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "recent_items", "limit": 5, "feed_id": "f"}))
+            .is_ok());
+        assert!(tool
+            .validate_input(&json!({"operation": "add_feed"}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "recent_items", "limit": 0}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "fetch_new", "feed_id": " "}))
+            .is_err());
+        assert!(tool.validate_input(&json!("fetch_new")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_without_feeds() {
+        let (tool, _dir) = setup().await;
+        let result = tool
+            .execute(json!({"operation": "fetch_new"}))
+            .await
+            .unwrap();
+        assert_eq!(result["count"], 0);
+
+        let missing = tool
+            .execute(json!({"operation": "fetch_new", "feed_id": "missing"}))
+            .await;
+        assert!(matches!(missing, Err(ToolError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_records_failure() {
+        let (tool, _dir) = setup().await;
+        tool.db
+            .create(
+                "feed",
+                "feed-1",
+                FeedCreate {
+                    url: "http://127.0.0.1:1/feed.xml".to_string(),
+                    title: None,
+                    site_url: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let result = tool
+            .execute(json!({"operation": "fetch_new"}))
+            .await
+            .unwrap();
+        assert_eq!(result["count"], 0);
+        assert_eq!(result["failed_feeds"][0]["feed_id"], "feed-1");
+        assert_eq!(
+            result["failed_feeds"][0]["title"],
+            "http://127.0.0.1:1/feed.xml"
+        );
+
+        let feeds = tool
+            .execute(json!({"operation": "list_feeds"}))
+            .await
+            .unwrap();
+        assert_eq!(feeds["count"], 1);
+        assert!(feeds["items"][0]["last_error"].is_string());
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Custom Script Tools
+//!
+//! Lightweight tools defined by the user in Settings > Tools: a Rhai script
+//! and a JSON Schema of its input, stored in the `custom_tool` table. Agents
+//! use them like built-in tools under the name `custom__<name>`.
+//!
+//! The [`ScriptToolRunner`] runs a script in a sandboxed Rhai engine: no
+//! module imports, no `eval`, no file or network access, and limits on
+//! operations, call depth, value sizes and wall-clock time.
+//!
+//! ```rhai
+//! // Input: { "text": "..." }
+//! let words = input.text.split(" ").filter(|w| w != "");
+//! #{ count: words.len() }
+//! ```
+
+mod runner;
+pub mod store;
+mod tool;
+
+pub use runner::ScriptToolRunner;
+pub use tool::ScriptTool;
+
+use crate::tools::constants::script as script_const;
+use serde_json::{json, Value};
+
+/// JSON Schema types a property may declare
+const SCHEMA_TYPES: [&str; 7] = [
+    "string", "number", "integer", "boolean", "array", "object", "null",
+];
+
+/// Tool name of a custom tool.
+pub fn tool_name(name: &str) -> String {
+    format!("{}{}", script_const::TOOL_PREFIX, name)
+}
+
+/// Custom tool name of a tool name, None for other tools.
+pub fn custom_name(tool_name: &str) -> Option<&str> {
+    tool_name
+        .strip_prefix(script_const::TOOL_PREFIX)
+        .filter(|name| is_valid_name(name))
+}
+
+/// Whether a custom tool name is well-formed: lowercase letters, digits and
+/// `_`, starting with a letter.
+pub fn is_valid_name(name: &str) -> bool {
+    name.len() <= script_const::MAX_NAME_LEN
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Validates the input schema of a custom tool.
+///
+/// The schema must describe an object; `type` defaults to `object`. Each
+/// property must be an object whose `type`, if any, is a JSON Schema type,
+/// and `required` may only list declared properties.
+///
+/// # Returns
+/// The schema with its `type` set
+pub fn validate_schema(schema: &Value) -> Result<Value, String> {
+    let mut schema = match schema {
+        Value::Object(obj) => obj.clone(),
+        Value::Null => serde_json::Map::new(),
+        _ => return Err("The input schema must be a JSON object".to_string()),
+    };
+    if serde_json::to_string(&schema).map_or(0, |text| text.len()) > script_const::MAX_SCHEMA_BYTES
+    {
+        return Err(format!(
+            "The input schema exceeds {} bytes",
+            script_const::MAX_SCHEMA_BYTES
+        ));
+    }
+    match schema.get("type") {
+        None => {
+            schema.insert("type".to_string(), json!("object"));
+        }
+        Some(kind) if kind == "object" => {}
+        Some(_) => return Err("The input schema must have the type 'object'".to_string()),
+    }
+
+    let properties = match schema.get("properties") {
+        None => serde_json::Map::new(),
+        Some(Value::Object(properties)) => properties.clone(),
+        Some(_) => return Err("'properties' must be an object".to_string()),
+    };
+    for (name, property) in &properties {
+        let Some(property) = property.as_object() else {
+            return Err(format!("Property '{}' must be an object", name));
+        };
+        if let Some(kind) = property.get("type") {
+            if !kind
+                .as_str()
+                .is_some_and(|kind| SCHEMA_TYPES.contains(&kind))
+            {
+                return Err(format!(
+                    "Property '{}' has an invalid type: use one of {}",
+                    name,
+                    SCHEMA_TYPES.join(", ")
+                ));
+            }
+        }
+    }
+    if let Some(required) = schema.get("required") {
+        let Some(required) = required.as_array() else {
+            return Err("'required' must be an array of property names".to_string());
+        };
+        for name in required {
+            match name.as_str() {
+                Some(name) if properties.contains_key(name) => {}
+                _ => return Err(format!("'required' lists an undeclared property: {}", name)),
+            }
+        }
+    }
+    Ok(Value::Object(schema))
+}
+
+/// Checks an input against a schema accepted by [`validate_schema`]:
+/// required properties and declared property types.
+pub fn check_input(schema: &Value, input: &Value) -> Result<(), String> {
+    let Some(input) = input.as_object() else {
+        return Err("Input must be a JSON object".to_string());
+    };
+
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !input.contains_key(name) {
+                return Err(format!("Missing required parameter '{}'", name));
+            }
+        }
+    }
+
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Ok(());
+    };
+    for (name, value) in input {
+        let Some(kind) = properties
+            .get(name)
+            .and_then(|property| property.get("type"))
+            .and_then(Value::as_str)
+        else {
+            continue;
+        };
+        let matches = match kind {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        if !matches {
+            return Err(format!("Parameter '{}' must be of type {}", name, kind));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_names() {
+        assert_eq!(tool_name("word_count"), "custom__word_count");
+        assert_eq!(custom_name("custom__word_count"), Some("word_count"));
+        assert_eq!(custom_name("custom__Word"), None);
+        assert_eq!(custom_name("custom__"), None);
+        assert_eq!(custom_name("MemoryTool"), None);
+        assert!(!is_valid_name("2fa"));
+        assert!(!is_valid_name("word-count"));
+        assert!(!is_valid_name(&"a".repeat(script_const::MAX_NAME_LEN + 1)));
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        assert_eq!(
+            validate_schema(&Value::Null).unwrap(),
+            json!({ "type": "object" })
+        );
+        let schema = validate_schema(&json!({
+            "properties": { "text": { "type": "string" } },
+            "required": ["text"]
+        }))
+        .unwrap();
+        assert_eq!(schema["type"], "object");
+
+        let invalid = [
+            json!([]),
+            json!({ "type": "string" }),
+            json!({ "properties": { "text": "string" } }),
+            json!({ "properties": { "text": { "type": "text" } } }),
+            json!({ "properties": {}, "required": ["text"] }),
+        ];
+        for schema in invalid {
+            assert!(validate_schema(&schema).is_err(), "{}", schema);
+        }
+    }
+
+    #[test]
+    fn test_check_input() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "text": { "type": "string" },
+                "limit": { "type": "integer" }
+            },
+            "required": ["text"]
+        });
+        assert!(check_input(&schema, &json!({ "text": "a", "limit": 3 })).is_ok());
+        // Undeclared parameters are passed through
+        assert!(check_input(&schema, &json!({ "text": "a", "extra": true })).is_ok());
+        assert!(check_input(&schema, &json!({ "limit": 3 }))
+            .unwrap_err()
+            .contains("'text'"));
+        assert!(check_input(&schema, &json!({ "text": "a", "limit": 1.5 })).is_err());
+        assert!(check_input(&schema, &json!("a")).is_err());
+    }
+}
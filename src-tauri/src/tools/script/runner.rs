@@ -0,0 +1,192 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sandboxed Rhai interpreter running the custom tools.
+//!
+//! Rhai is built without module support, so scripts cannot import files;
+//! the engine has no file, network or process functions. `eval` is
+//! disabled and every call is bounded by operation, depth, size and time
+//! limits (see [`script_const`]).
+
+use crate::tools::constants::script as script_const;
+use crate::tools::{ToolError, ToolResult};
+use rhai::{Dynamic, Engine, EvalAltResult, Scope, AST};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Creates a sandboxed engine whose evaluation stops at a deadline.
+fn sandboxed_engine(deadline: Instant) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(script_const::MAX_OPERATIONS);
+    engine.set_max_call_levels(script_const::MAX_CALL_LEVELS);
+    engine.set_max_expr_depths(script_const::MAX_EXPR_DEPTH, script_const::MAX_EXPR_DEPTH);
+    engine.set_max_string_size(script_const::MAX_STRING_BYTES);
+    engine.set_max_array_size(script_const::MAX_ARRAY_LEN);
+    engine.set_max_map_size(script_const::MAX_MAP_LEN);
+    engine.disable_symbol("eval");
+    engine.on_print(|text| debug!(output = %text, "Custom tool print"));
+    engine.on_debug(
+        |text, _, position| debug!(output = %text, position = %position, "Custom tool debug"),
+    );
+    engine.on_progress(move |_| (Instant::now() >= deadline).then_some(Dynamic::UNIT));
+    engine
+}
+
+/// Compiled script of a custom tool.
+pub struct ScriptToolRunner {
+    /// Custom tool name (for error messages)
+    name: String,
+    /// Compiled script
+    ast: AST,
+}
+
+impl ScriptToolRunner {
+    /// Compiles the script of a custom tool.
+    ///
+    /// # Errors
+    /// The syntax error, with its position
+    pub fn compile(name: &str, script: &str) -> Result<Self, String> {
+        if script.trim().is_empty() {
+            return Err("The script is empty".to_string());
+        }
+        if script.len() > script_const::MAX_SCRIPT_BYTES {
+            return Err(format!(
+                "The script exceeds {} bytes",
+                script_const::MAX_SCRIPT_BYTES
+            ));
+        }
+        let ast =
+            sandboxed_engine(Instant::now() + Duration::from_secs(script_const::TIMEOUT_SECS))
+                .compile(script)
+                .map_err(|e| format!("Script error: {}", e))?;
+        Ok(Self {
+            name: name.to_string(),
+            ast,
+        })
+    }
+
+    /// Runs the script with an input (blocking).
+    ///
+    /// The input is the `input` constant of the script; the value of its
+    /// last expression (or of `return`) is converted to JSON, `()` giving
+    /// `null`. A script fails a call with `throw "message"`.
+    ///
+    /// # Errors
+    /// * `Timeout` - The script ran longer than `TIMEOUT_SECS`
+    /// * `ExecutionFailed` - Runtime error, exceeded limit or invalid result
+    pub fn run(&self, input: &Value) -> ToolResult<Value> {
+        let input = rhai::serde::to_dynamic(input)
+            .map_err(|e| ToolError::InvalidInput(format!("Unsupported input: {}", e)))?;
+        let mut scope = Scope::new();
+        scope.push_constant("input", input);
+
+        let engine =
+            sandboxed_engine(Instant::now() + Duration::from_secs(script_const::TIMEOUT_SECS));
+        let result = engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast)
+            .map_err(|e| self.error(*e))?;
+
+        let output: Value = rhai::serde::from_dynamic(&result).map_err(|e| {
+            ToolError::ExecutionFailed(format!(
+                "Custom tool '{}' returned a value that is not JSON: {}",
+                self.name, e
+            ))
+        })?;
+        if output.to_string().len() > script_const::MAX_OUTPUT_BYTES {
+            return Err(ToolError::ExecutionFailed(format!(
+                "Custom tool '{}' returned more than {} bytes",
+                self.name,
+                script_const::MAX_OUTPUT_BYTES
+            )));
+        }
+        Ok(output)
+    }
+
+    /// Maps an evaluation error to a tool error.
+    fn error(&self, error: EvalAltResult) -> ToolError {
+        match error {
+            EvalAltResult::ErrorTerminated(..) => ToolError::Timeout(format!(
+                "Custom tool '{}' ran longer than {} seconds",
+                self.name,
+                script_const::TIMEOUT_SECS
+            )),
+            EvalAltResult::ErrorRuntime(value, _) => {
+                ToolError::ExecutionFailed(format!("Custom tool '{}' failed: {}", self.name, value))
+            }
+            error => {
+                ToolError::ExecutionFailed(format!("Custom tool '{}' failed: {}", self.name, error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_run_script() {
+        let runner = ScriptToolRunner::compile(
+            "word_count",
+            r#"
+            let words = input.text.split(" ").filter(|w| w != "");
+            #{ count: words.len(), first: words[0], ratio: 0.5 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            runner.run(&json!({ "text": "hello  world" })).unwrap(),
+            json!({ "count": 2, "first": "hello", "ratio": 0.5 })
+        );
+
+        let runner = ScriptToolRunner::compile("noop", "let x = 1;").unwrap();
+        assert_eq!(runner.run(&json!({})).unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn test_script_errors() {
+        assert!(ScriptToolRunner::compile("broken", "let x = ;")
+            .err()
+            .unwrap()
+            .starts_with("Script error"));
+        assert!(ScriptToolRunner::compile("empty", "  ").is_err());
+
+        let runner = ScriptToolRunner::compile("fails", r#"throw "no city""#).unwrap();
+        match runner.run(&json!({})) {
+            Err(ToolError::ExecutionFailed(msg)) => assert!(msg.ends_with("no city")),
+            other => panic!("Expected a script error, got {:?}", other),
+        }
+
+        // The input is read-only
+        let runner = ScriptToolRunner::compile("mutates", "input.text = 1;").unwrap();
+        assert!(runner.run(&json!({ "text": "a" })).is_err());
+    }
+
+    #[test]
+    fn test_sandbox_limits() {
+        // Infinite loop: stopped by the operation limit or the deadline
+        let runner = ScriptToolRunner::compile("spin", "loop {}").unwrap();
+        assert!(runner.run(&json!({})).is_err());
+
+        // No eval, no module imports
+        assert!(ScriptToolRunner::compile("eval", r#"eval("1")"#).is_err());
+        assert!(ScriptToolRunner::compile("import", r#"import "file" as f;"#).is_err());
+
+        // Size limits
+        let runner = ScriptToolRunner::compile("grow", r#"let s = "x"; loop { s += s; }"#).unwrap();
+        assert!(runner.run(&json!({})).is_err());
+    }
+}
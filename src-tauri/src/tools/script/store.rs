@@ -0,0 +1,233 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Custom tool storage.
+//!
+//! Custom tools live in the `custom_tool` table, their input schema
+//! serialized as a string.
+
+use crate::db::DBClient;
+use crate::models::custom_tool::{CustomTool, CustomToolCreate, CustomToolUpdate};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Fields selected when loading custom tools
+const SELECT_FIELDS: &str = "meta::id(id) AS id, name, description, input_schema, script, \
+     enabled, created_at, updated_at";
+
+/// Custom tool as stored
+#[derive(Deserialize)]
+struct CustomToolRow {
+    id: String,
+    name: String,
+    description: String,
+    input_schema: String,
+    script: String,
+    enabled: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CustomToolRow> for CustomTool {
+    fn from(row: CustomToolRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            input_schema: serde_json::from_str(&row.input_schema)
+                .unwrap_or_else(|_| json!({ "type": "object" })),
+            script: row.script,
+            enabled: row.enabled,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Converts query rows into custom tools.
+fn from_rows(rows: Vec<serde_json::Value>) -> Vec<CustomTool> {
+    rows.into_iter()
+        .filter_map(|row| serde_json::from_value::<CustomToolRow>(row).ok())
+        .map(CustomTool::from)
+        .collect()
+}
+
+/// Lists the custom tools, sorted by name.
+pub async fn list_custom_tools(db: &DBClient) -> Result<Vec<CustomTool>, String> {
+    let rows = db
+        .query_json(&format!(
+            "SELECT {} FROM custom_tool ORDER BY name",
+            SELECT_FIELDS
+        ))
+        .await
+        .map_err(|e| format!("Failed to list custom tools: {}", e))?;
+    Ok(from_rows(rows))
+}
+
+/// Loads a custom tool by ID.
+pub async fn load_custom_tool(db: &DBClient, tool_id: &str) -> Result<Option<CustomTool>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM custom_tool WHERE meta::id(id) = $id",
+                SELECT_FIELDS
+            ),
+            vec![("id".to_string(), json!(tool_id))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load custom tool: {}", e))?;
+    Ok(from_rows(rows).into_iter().next())
+}
+
+/// Loads a custom tool by name.
+pub async fn find_custom_tool(db: &DBClient, name: &str) -> Result<Option<CustomTool>, String> {
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT {} FROM custom_tool WHERE name = $name",
+                SELECT_FIELDS
+            ),
+            vec![("name".to_string(), json!(name))],
+        )
+        .await
+        .map_err(|e| format!("Failed to load custom tool: {}", e))?;
+    Ok(from_rows(rows).into_iter().next())
+}
+
+/// Creates a custom tool from a validated payload.
+pub async fn create_custom_tool(
+    db: &DBClient,
+    tool_id: &str,
+    tool: &CustomToolCreate,
+) -> Result<(), String> {
+    db.execute_with_params(
+        "CREATE type::thing('custom_tool', $id) CONTENT { \
+         name: $name, description: $description, input_schema: $input_schema, \
+         script: $script, enabled: true, created_at: time::now(), updated_at: time::now() }",
+        vec![
+            ("id".to_string(), json!(tool_id)),
+            ("name".to_string(), json!(tool.name)),
+            ("description".to_string(), json!(tool.description)),
+            (
+                "input_schema".to_string(),
+                json!(tool.input_schema.to_string()),
+            ),
+            ("script".to_string(), json!(tool.script)),
+        ],
+    )
+    .await
+    .map_err(|e| format!("Failed to create custom tool: {}", e))
+}
+
+/// Updates the fields of a validated payload.
+pub async fn update_custom_tool(
+    db: &DBClient,
+    tool_id: &str,
+    update: &CustomToolUpdate,
+) -> Result<(), String> {
+    let mut set_clauses = vec!["updated_at = time::now()"];
+    let mut params = vec![("id".to_string(), json!(tool_id))];
+    if let Some(description) = &update.description {
+        set_clauses.push("description = $description");
+        params.push(("description".to_string(), json!(description)));
+    }
+    if let Some(input_schema) = &update.input_schema {
+        set_clauses.push("input_schema = $input_schema");
+        params.push(("input_schema".to_string(), json!(input_schema.to_string())));
+    }
+    if let Some(script) = &update.script {
+        set_clauses.push("script = $script");
+        params.push(("script".to_string(), json!(script)));
+    }
+    if let Some(enabled) = update.enabled {
+        set_clauses.push("enabled = $enabled");
+        params.push(("enabled".to_string(), json!(enabled)));
+    }
+
+    db.execute_with_params(
+        &format!(
+            "UPDATE type::thing('custom_tool', $id) SET {}",
+            set_clauses.join(", ")
+        ),
+        params,
+    )
+    .await
+    .map_err(|e| format!("Failed to update custom tool: {}", e))
+}
+
+/// Deletes a custom tool.
+pub async fn delete_custom_tool(db: &DBClient, tool_id: &str) -> Result<(), String> {
+    db.execute_with_params(
+        "DELETE type::thing('custom_tool', $id)",
+        vec![("id".to_string(), json!(tool_id))],
+    )
+    .await
+    .map_err(|e| format!("Failed to delete custom tool: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_custom_tool_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = DBClient::new(dir.path().join("db").to_str().unwrap())
+            .await
+            .unwrap();
+        db.initialize_schema().await.unwrap();
+
+        let schema = json!({
+            "type": "object",
+            "properties": { "text": { "type": "string" } },
+            "required": ["text"]
+        });
+        create_custom_tool(
+            &db,
+            "tool-1",
+            &CustomToolCreate {
+                name: "word_count".to_string(),
+                description: "Counts words".to_string(),
+                input_schema: schema.clone(),
+                script: "input.text.len()".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+        let tool = find_custom_tool(&db, "word_count").await.unwrap().unwrap();
+        assert_eq!(tool.id, "tool-1");
+        assert_eq!(tool.input_schema, schema);
+        assert!(tool.enabled);
+
+        update_custom_tool(
+            &db,
+            "tool-1",
+            &CustomToolUpdate {
+                enabled: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let tool = load_custom_tool(&db, "tool-1").await.unwrap().unwrap();
+        assert!(!tool.enabled);
+        assert_eq!(tool.description, "Counts words");
+        assert_eq!(list_custom_tools(&db).await.unwrap().len(), 1);
+
+        delete_custom_tool(&db, "tool-1").await.unwrap();
+        assert!(find_custom_tool(&db, "word_count").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ScriptTool implementation.
+//!
+//! Exposes an enabled custom tool to agents: its definition comes from the
+//! `custom_tool` record and each call runs its compiled script.
+
+use super::runner::ScriptToolRunner;
+use super::{check_input, custom_name, store, tool_name};
+use crate::db::DBClient;
+use crate::models::custom_tool::CustomTool;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::info;
+
+/// Tool running the script of a custom tool.
+pub struct ScriptTool {
+    /// Definition built from the custom tool
+    definition: ToolDefinition,
+    /// Compiled script
+    runner: Arc<ScriptToolRunner>,
+}
+
+impl ScriptTool {
+    /// Creates a tool from a custom tool, compiling its script.
+    pub fn new(custom_tool: &CustomTool) -> Result<Self, String> {
+        let runner = ScriptToolRunner::compile(&custom_tool.name, &custom_tool.script)?;
+        Ok(Self {
+            definition: ToolDefinition {
+                id: tool_name(&custom_tool.name),
+                name: custom_tool.name.clone(),
+                description: custom_tool.description.clone(),
+                input_schema: custom_tool.input_schema.clone(),
+                output_schema: json!({ "description": "Value returned by the script" }),
+                requires_confirmation: false,
+            },
+            runner: Arc::new(runner),
+        })
+    }
+
+    /// Creates the tool of an enabled custom tool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `name` - Custom tool name (without the `custom__` prefix)
+    pub async fn load(db: &DBClient, name: &str) -> Result<Self, String> {
+        let custom_tool = store::find_custom_tool(db, name)
+            .await?
+            .ok_or_else(|| format!("Custom tool '{}' not found", name))?;
+        if !custom_tool.enabled {
+            return Err(format!(
+                "Custom tool '{}' is disabled in Settings > Tools",
+                name
+            ));
+        }
+        Self::new(&custom_tool)
+    }
+
+    /// Creates the tool of an enabled custom tool.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let name = custom_name(ctx.tool_name)
+                .ok_or_else(|| format!("Invalid custom tool name '{}'", ctx.tool_name))?;
+            let tool = Self::load(&ctx.factory.get_db(), name).await?;
+            info!(custom_tool = %name, "Custom tool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Bounded by the runner's own time limit
+    ToolRegistration::Family {
+        matches: |name| custom_name(name).is_some(),
+        metadata: ToolMetadata {
+            name: "ScriptTool",
+            category: ToolCategory::Basic,
+            requires_context: false,
+            default_timeout_secs: None,
+            constructor: ScriptTool::build,
+        },
+    }
+}
+
+#[async_trait]
+impl Tool for ScriptTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+
+        let runner = self.runner.clone();
+        tokio::task::spawn_blocking(move || runner.run(&input))
+            .await
+            .map_err(|e| ToolError::ExecutionFailed(format!("Script task failed: {}", e)))?
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        check_input(&self.definition.input_schema, input).map_err(ToolError::InvalidInput)
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn custom_tool(script: &str) -> CustomTool {
+        CustomTool {
+            id: "tool-1".to_string(),
+            name: "word_count".to_string(),
+            description: "Counts the words of a text".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } },
+                "required": ["text"]
+            }),
+            script: script.to_string(),
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_script_tool() {
+        let tool = ScriptTool::new(&custom_tool(r#"input.text.split(" ").len()"#)).unwrap();
+        let definition = tool.definition();
+        assert_eq!(definition.id, "custom__word_count");
+        assert_eq!(definition.input_schema["required"], json!(["text"]));
+
+        assert_eq!(
+            tool.execute(json!({ "text": "one two three" }))
+                .await
+                .unwrap(),
+            json!(3)
+        );
+        assert!(matches!(
+            tool.execute(json!({ "text": 3 })).await,
+            Err(ToolError::InvalidInput(_))
+        ));
+        assert!(ScriptTool::new(&custom_tool("let = 1")).is_err());
+    }
+}
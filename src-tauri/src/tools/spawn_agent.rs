@@ -41,6 +41,9 @@ use crate::mcp::MCPManager;
 use crate::models::streaming::SubAgentOperationType;
 use crate::models::sub_agent::{constants::MAX_SUB_AGENTS, SubAgentSpawnResult, SubAgentStatus};
 use crate::models::{AgentConfig, LLMConfig, Lifecycle};
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
 use crate::tools::{
     constants::sub_agent::TASK_DESC_TRUNCATE_CHARS,
     context::AgentToolContext,
@@ -338,9 +341,15 @@ impl SpawnAgentTool {
             system_prompt: system_prompt
                 .unwrap_or(DEFAULT_SUB_AGENT_SYSTEM_PROMPT)
                 .to_string(),
-            // Sub-agents inherit parent's max_tool_iterations and enable_thinking
+            // Sub-agents inherit parent's max_tool_iterations, enable_thinking
+            // and tool restrictions
             max_tool_iterations: parent_config.max_tool_iterations,
             enable_thinking: parent_config.enable_thinking,
+            tool_settings: parent_config.tool_settings.clone(),
+            mcp_tool_policy: parent_config.mcp_tool_policy.clone(),
+            // Regression suites target the parent prompt, not the sub-agent
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         // 10. Create execution record in database (status: running)
@@ -511,15 +520,13 @@ impl SpawnAgentTool {
         child.status = SubAgentStatus::Cancelled;
 
         // Update database record
-        let update_query = format!(
-            "UPDATE sub_agent_execution:`{}` SET \
+        let update_query = "UPDATE type::thing('sub_agent_execution', $id) SET \
              status = 'cancelled', \
              error_message = 'Terminated by parent agent', \
-             completed_at = time::now()",
-            child.execution_id
-        );
+             completed_at = time::now()";
+        let params = vec![("id".to_string(), serde_json::json!(child.execution_id))];
 
-        if let Err(e) = self.db.execute(&update_query).await {
+        if let Err(e) = self.db.execute_with_params(update_query, params).await {
             warn!(
                 execution_id = %child.execution_id,
                 error = %e,
@@ -544,6 +551,34 @@ impl SpawnAgentTool {
             "message": format!("Sub-agent '{}' has been terminated", child_id)
         }))
     }
+
+    /// Creates the tool with the agent system access of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let context = ctx.require_agent_context()?;
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = SpawnAgentTool::new(
+                ctx.factory.get_db(),
+                context,
+                ctx.agent_id,
+                wf_id,
+                ctx.is_primary_agent,
+            );
+            info!("SpawnAgentTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Bounded by the sub-agent inactivity timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "SpawnAgentTool",
+        category: ToolCategory::SubAgent,
+        requires_context: true,
+        default_timeout_secs: None,
+        constructor: SpawnAgentTool::build,
+    })
 }
 
 #[async_trait]
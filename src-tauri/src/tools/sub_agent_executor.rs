@@ -79,6 +79,13 @@ use crate::tools::sub_agent_circuit_breaker::SubAgentCircuitBreaker;
 use crate::tools::validation_helper::{safe_truncate, ValidationHelper};
 use crate::tools::{ToolError, ToolResult};
 
+/// Records the outcome of a sub-agent execution (`$id` is the execution record ID).
+pub(crate) const SUB_AGENT_EXECUTION_COMPLETE: &str =
+    "UPDATE type::thing('sub_agent_execution', $id) SET status = $status, \
+     duration_ms = $duration_ms, tokens_input = $tokens_input, tokens_output = $tokens_output, \
+     result_summary = $result_summary, error_message = $error_message, \
+     completed_at = time::now()";
+
 /// Result of sub-agent execution with metrics.
 #[derive(Debug)]
 pub struct ExecutionResult {
@@ -594,33 +601,36 @@ impl SubAgentExecutor {
         };
         let result_summary = safe_truncate(&result.report, 200, true);
 
-        let result_summary_json =
-            serde_json::to_string(&result_summary).unwrap_or_else(|_| "null".to_string());
-        let error_message_json = result
-            .error_message
-            .as_ref()
-            .map(|s| serde_json::to_string(s).unwrap_or_else(|_| "null".to_string()))
-            .unwrap_or_else(|| "null".to_string());
-
-        let update_query = format!(
-            "UPDATE sub_agent_execution:`{}` SET \
-             status = '{}', \
-             duration_ms = {}, \
-             tokens_input = {}, \
-             tokens_output = {}, \
-             result_summary = {}, \
-             error_message = {}, \
-             completed_at = time::now()",
-            execution_id,
-            status,
-            result.metrics.duration_ms,
-            result.metrics.tokens_input,
-            result.metrics.tokens_output,
-            result_summary_json,
-            error_message_json,
-        );
+        let params = vec![
+            ("id".to_string(), serde_json::json!(execution_id)),
+            ("status".to_string(), serde_json::json!(status)),
+            (
+                "duration_ms".to_string(),
+                serde_json::json!(result.metrics.duration_ms),
+            ),
+            (
+                "tokens_input".to_string(),
+                serde_json::json!(result.metrics.tokens_input),
+            ),
+            (
+                "tokens_output".to_string(),
+                serde_json::json!(result.metrics.tokens_output),
+            ),
+            (
+                "result_summary".to_string(),
+                serde_json::json!(result_summary),
+            ),
+            (
+                "error_message".to_string(),
+                serde_json::json!(result.error_message),
+            ),
+        ];
 
-        if let Err(e) = self.db.execute(&update_query).await {
+        if let Err(e) = self
+            .db
+            .execute_with_params(SUB_AGENT_EXECUTION_COMPLETE, params)
+            .await
+        {
             warn!(
                 execution_id = %execution_id,
                 error = %e,
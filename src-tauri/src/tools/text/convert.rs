@@ -0,0 +1,197 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON, YAML and CSV conversion.
+//!
+//! Documents are converted through a JSON value. CSV documents are read as an
+//! array of objects keyed by the header row (cells stay strings) and written
+//! from an array of objects (columns in order of first appearance) or of
+//! arrays.
+
+use serde_json::{Map, Value};
+
+/// Structured text format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Yaml,
+    Csv,
+}
+
+impl Format {
+    /// Parses a format name.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a document into a JSON value.
+pub fn parse(text: &str, format: Format, delimiter: u8) -> Result<Value, String> {
+    match format {
+        Format::Json => serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e)),
+        Format::Yaml => serde_norway::from_str(text).map_err(|e| format!("Invalid YAML: {}", e)),
+        Format::Csv => parse_csv(text, delimiter),
+    }
+}
+
+/// Renders a JSON value in a format.
+pub fn render(value: &Value, format: Format, delimiter: u8) -> Result<String, String> {
+    match format {
+        Format::Json => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+        Format::Yaml => serde_norway::to_string(value).map_err(|e| e.to_string()),
+        Format::Csv => render_csv(value, delimiter),
+    }
+}
+
+/// Reads a CSV document with a header row.
+fn parse_csv(text: &str, delimiter: u8) -> Result<Value, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| format!("Invalid CSV header: {}", e))?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| format!("Invalid CSV: {}", e))?;
+        let row: Map<String, Value> = headers
+            .iter()
+            .enumerate()
+            .map(|(index, header)| {
+                let cell = record.get(index).unwrap_or_default();
+                (header.clone(), Value::String(cell.to_string()))
+            })
+            .collect();
+        rows.push(Value::Object(row));
+    }
+    Ok(Value::Array(rows))
+}
+
+/// Text of a CSV cell (nested values are written as JSON).
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Writes an array of objects or of arrays as CSV.
+fn render_csv(value: &Value, delimiter: u8) -> Result<String, String> {
+    let rows = value
+        .as_array()
+        .ok_or("CSV output needs an array of objects or of arrays")?;
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_writer(Vec::new());
+
+    if rows.iter().all(Value::is_object) {
+        let mut columns: Vec<&String> = Vec::new();
+        for row in rows.iter().filter_map(Value::as_object) {
+            for key in row.keys() {
+                if !columns.contains(&key) {
+                    columns.push(key);
+                }
+            }
+        }
+        writer.write_record(&columns).map_err(|e| e.to_string())?;
+        for row in rows.iter().filter_map(Value::as_object) {
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|column| row.get(*column).map(cell_text).unwrap_or_default())
+                .collect();
+            writer.write_record(&cells).map_err(|e| e.to_string())?;
+        }
+    } else if rows.iter().all(Value::is_array) {
+        for row in rows.iter().filter_map(Value::as_array) {
+            let cells: Vec<String> = row.iter().map(cell_text).collect();
+            writer.write_record(&cells).map_err(|e| e.to_string())?;
+        }
+    } else {
+        return Err("CSV output needs an array of objects or of arrays".to_string());
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_parse() {
+        assert_eq!(Format::parse("YML"), Some(Format::Yaml));
+        assert_eq!(Format::parse("xml"), None);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let value = parse(
+            "name: Zileo\ntags:\n  - chat\n  - agents\n",
+            Format::Yaml,
+            b',',
+        )
+        .unwrap();
+        assert_eq!(value, json!({"name": "Zileo", "tags": ["chat", "agents"]}));
+        let yaml = render(&value, Format::Yaml, b',').unwrap();
+        assert_eq!(parse(&yaml, Format::Yaml, b',').unwrap(), value);
+    }
+
+    #[test]
+    fn test_csv_to_json() {
+        let value = parse("name,age\n\"Doe, Jane\",42\nBob\n", Format::Csv, b',').unwrap();
+        assert_eq!(
+            value,
+            json!([{"name": "Doe, Jane", "age": "42"}, {"name": "Bob", "age": ""}])
+        );
+        let value = parse("a;b\n1;2\n", Format::Csv, b';').unwrap();
+        assert_eq!(value, json!([{"a": "1", "b": "2"}]));
+    }
+
+    #[test]
+    fn test_json_to_csv() {
+        let value = json!([
+            {"name": "Ann", "age": 31},
+            {"name": "Bob", "tags": ["x"], "age": null}
+        ]);
+        assert_eq!(
+            render(&value, Format::Csv, b',').unwrap(),
+            "name,age,tags\nAnn,31,\nBob,,\"[\"\"x\"\"]\"\n"
+        );
+        let rows = json!([["a", 1], ["b", true]]);
+        assert_eq!(render(&rows, Format::Csv, b',').unwrap(), "a,1\nb,true\n");
+        assert!(render(&json!({"a": 1}), Format::Csv, b',').is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(parse("{", Format::Json, b',').unwrap_err().contains("JSON"));
+        assert!(parse("a: [", Format::Yaml, b',')
+            .unwrap_err()
+            .contains("YAML"));
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Line diff of two texts.
+
+use similar::{ChangeTag, TextDiff};
+
+/// Unified diff of two texts with its line counts
+#[derive(Debug)]
+pub struct LineDiff {
+    /// Unified diff (empty when the texts are identical)
+    pub unified: String,
+    /// Lines only in the new text
+    pub insertions: usize,
+    /// Lines only in the old text
+    pub deletions: usize,
+}
+
+/// Computes the unified diff of two texts.
+pub fn line_diff(old: &str, new: &str, context_lines: usize) -> LineDiff {
+    let diff = TextDiff::from_lines(old, new);
+    let mut insertions = 0;
+    let mut deletions = 0;
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => insertions += 1,
+            ChangeTag::Delete => deletions += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let unified = diff
+        .unified_diff()
+        .context_radius(context_lines)
+        .header("old", "new")
+        .to_string();
+    LineDiff {
+        unified,
+        insertions,
+        deletions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("a\nb\nc\n", "a\nB\nc\nd\n", 3);
+        assert_eq!(diff.insertions, 2);
+        assert_eq!(diff.deletions, 1);
+        assert!(diff.unified.starts_with("--- old\n+++ new\n"));
+        assert!(diff.unified.contains("-b\n+B\n"));
+    }
+
+    #[test]
+    fn test_line_diff_identical() {
+        let diff = line_diff("same\n", "same\n", 3);
+        assert_eq!(diff.insertions + diff.deletions, 0);
+        assert!(diff.unified.is_empty());
+    }
+}
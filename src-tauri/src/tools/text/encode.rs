@@ -0,0 +1,214 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hashes and encodings of UTF-8 text.
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use md5::Md5;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Lowercase hexadecimal of bytes.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Digest of a text with an algorithm (`md5`, `sha1`, `sha256`, `sha512`).
+pub fn hash(text: &str, algorithm: &str) -> Result<Vec<u8>, String> {
+    let bytes = text.as_bytes();
+    match algorithm.to_ascii_lowercase().replace('-', "").as_str() {
+        "md5" => Ok(Md5::digest(bytes).to_vec()),
+        "sha1" => Ok(Sha1::digest(bytes).to_vec()),
+        "sha256" => Ok(Sha256::digest(bytes).to_vec()),
+        "sha512" => Ok(Sha512::digest(bytes).to_vec()),
+        other => Err(format!(
+            "Unknown hash algorithm '{}': use md5, sha1, sha256 or sha512",
+            other
+        )),
+    }
+}
+
+/// Encodes a digest as `hex` or `base64`.
+pub fn encode_digest(digest: &[u8], encoding: &str) -> Result<String, String> {
+    match encoding {
+        "hex" => Ok(to_hex(digest)),
+        "base64" => Ok(STANDARD.encode(digest)),
+        other => Err(format!(
+            "Unknown digest encoding '{}': use hex or base64",
+            other
+        )),
+    }
+}
+
+/// Escapes the HTML special characters.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Unescapes the HTML special characters and numeric references.
+fn unescape_html(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end]);
+        let decoded = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{A0}'),
+            _ => {
+                let code = match entity.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16),
+                    Some(decimal) => decimal.parse(),
+                    None => return None,
+                };
+                code.ok().and_then(char::from_u32)
+            }
+        });
+        match (entity, decoded) {
+            (Some(entity), Some(c)) => {
+                unescaped.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Encodes a text (`base64`, `base64url`, `hex`, `url`, `html`).
+pub fn encode(text: &str, encoding: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    match encoding {
+        "base64" => Ok(STANDARD.encode(bytes)),
+        "base64url" => Ok(URL_SAFE_NO_PAD.encode(bytes)),
+        "hex" => Ok(to_hex(bytes)),
+        "url" => Ok(utf8_percent_encode(text, NON_ALPHANUMERIC).to_string()),
+        "html" => Ok(escape_html(text)),
+        other => Err(unknown_encoding(other)),
+    }
+}
+
+/// Decodes a text encoded with [`encode`]; the result must be UTF-8.
+pub fn decode(text: &str, encoding: &str) -> Result<String, String> {
+    let text = text.trim();
+    let bytes = match encoding {
+        "base64" => STANDARD
+            .decode(text)
+            .map_err(|e| format!("Invalid Base64: {}", e))?,
+        "base64url" => URL_SAFE_NO_PAD
+            .decode(text.trim_end_matches('='))
+            .map_err(|e| format!("Invalid Base64url: {}", e))?,
+        "hex" => {
+            if !text.len().is_multiple_of(2) || !text.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err("Invalid hex: expected an even number of hex digits".to_string());
+            }
+            (0..text.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&text[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|e| format!("Invalid hex: {}", e))?
+        }
+        "url" => percent_decode_str(&text.replace('+', " ")).collect(),
+        "html" => return Ok(unescape_html(text)),
+        other => return Err(unknown_encoding(other)),
+    };
+    String::from_utf8(bytes)
+        .map_err(|_| "Decoded bytes are not UTF-8 text (binary data is not supported)".to_string())
+}
+
+fn unknown_encoding(encoding: &str) -> String {
+    format!(
+        "Unknown encoding '{}': use base64, base64url, hex, url or html",
+        encoding
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        let digest = hash("abc", "sha256").unwrap();
+        assert_eq!(
+            encode_digest(&digest, "hex").unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let digest = hash("abc", "MD5").unwrap();
+        assert_eq!(
+            encode_digest(&digest, "hex").unwrap(),
+            "900150983cd24fb0d6963f7d28e17f72"
+        );
+        assert_eq!(hash("abc", "sha-1").unwrap().len(), 20);
+        assert_eq!(hash("abc", "sha512").unwrap().len(), 64);
+        assert!(hash("abc", "crc32").is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let text = "Zileo <chat> & \"agents\" é?";
+        for encoding in ["base64", "base64url", "hex", "url", "html"] {
+            let encoded = encode(text, encoding).unwrap();
+            assert_eq!(decode(&encoded, encoding).unwrap(), text, "{}", encoding);
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode("hi", "base64").unwrap(), "aGk=");
+        assert_eq!(encode("hi", "hex").unwrap(), "6869");
+        assert_eq!(encode("a b/c", "url").unwrap(), "a%20b%2Fc");
+        assert_eq!(
+            encode("<a href='x'>", "html").unwrap(),
+            "&lt;a href=&#39;x&#39;&gt;"
+        );
+        assert!(encode("hi", "rot13").is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("a+b%21", "url").unwrap(), "a b!");
+        assert_eq!(
+            decode("&#x41;&#66; &copy; & ok", "html").unwrap(),
+            "AB &copy; & ok"
+        );
+        assert!(decode("abc", "hex").is_err());
+        assert!(decode("/w==", "base64").unwrap_err().contains("UTF-8"));
+    }
+}
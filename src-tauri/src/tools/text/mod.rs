@@ -0,0 +1,30 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Text Tool Module
+//!
+//! Provides the TextTool, which applies deterministic transformations to the
+//! strings given by agents:
+//! - Regex match and replace (linear-time `regex` engine)
+//! - Unified line diff
+//! - JSON, YAML and CSV conversion
+//! - Hashing (MD5, SHA-1, SHA-256, SHA-512) and encodings (Base64, hex, URL, HTML)
+
+mod convert;
+mod diff;
+mod encode;
+mod pattern;
+mod tool;
+
+pub use tool::TextTool;
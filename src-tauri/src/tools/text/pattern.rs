@@ -0,0 +1,134 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Regex match and replace.
+//!
+//! Patterns use the `regex` crate syntax (no look-around or backreferences),
+//! which matches in linear time: an agent pattern cannot hang the tool.
+
+use crate::tools::constants::text as text_const;
+use regex::{Regex, RegexBuilder};
+use serde_json::{json, Map, Value};
+
+/// Compiles a pattern with flags (`i` case-insensitive, `m` multi-line,
+/// `s` dot matches newline, `x` verbose).
+pub fn compile(pattern: &str, flags: &str) -> Result<Regex, String> {
+    let mut builder = RegexBuilder::new(pattern);
+    builder.size_limit(text_const::REGEX_SIZE_LIMIT);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            'g' => &mut builder, // Global is the default
+            other => return Err(format!("Unknown regex flag '{}': use i, m, s or x", other)),
+        };
+    }
+    builder.build().map_err(|e| format!("Invalid regex: {}", e))
+}
+
+/// Character offset of a byte offset (agents count characters, not bytes).
+fn char_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].chars().count()
+}
+
+/// Finds the matches of a regex, with their capture groups.
+///
+/// Returns the matches (at most `max_matches`) and the total number found.
+pub fn find_matches(regex: &Regex, text: &str, max_matches: usize) -> (Vec<Value>, usize) {
+    let names: Vec<Option<&str>> = regex.capture_names().collect();
+    let mut matches = Vec::new();
+    let mut total = 0;
+    for captures in regex.captures_iter(text) {
+        total += 1;
+        if matches.len() >= max_matches {
+            continue;
+        }
+        let whole = captures.get(0).expect("group 0 is the whole match");
+        let mut groups = Map::new();
+        for (index, name) in names.iter().enumerate().skip(1) {
+            let key = name.map_or_else(|| index.to_string(), str::to_string);
+            let value = captures
+                .get(index)
+                .map_or(Value::Null, |group| json!(group.as_str()));
+            groups.insert(key, value);
+        }
+        matches.push(json!({
+            "match": whole.as_str(),
+            "start": char_offset(text, whole.start()),
+            "end": char_offset(text, whole.end()),
+            "groups": groups,
+        }));
+    }
+    (matches, total)
+}
+
+/// Replaces the matches of a regex (`limit` 0 replaces all of them).
+///
+/// The replacement may reference groups with `$1` or `${name}`. Returns the
+/// new text and the number of replacements.
+pub fn replace(regex: &Regex, text: &str, replacement: &str, limit: usize) -> (String, usize) {
+    let found = regex.find_iter(text).count();
+    let count = if limit == 0 { found } else { found.min(limit) };
+    let result = regex.replacen(text, limit, replacement).into_owned();
+    (result, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_flags() {
+        assert!(compile("abc", "i").unwrap().is_match("ABC"));
+        assert!(compile("^b", "m").unwrap().is_match("a\nb"));
+        assert!(compile("a.b", "s").unwrap().is_match("a\nb"));
+        assert!(compile("abc", "q").is_err());
+        assert!(compile("(unclosed", "").is_err());
+        // Backreferences are not supported by the linear-time engine
+        assert!(compile(r"(a)\1", "").is_err());
+    }
+
+    #[test]
+    fn test_find_matches_groups() {
+        let regex = compile(r"(?P<user>\w+)@(\w+)\.com", "").unwrap();
+        let (matches, total) = find_matches(&regex, "é bob@mail.com, ann@corp.com", 10);
+        assert_eq!(total, 2);
+        assert_eq!(matches[0]["match"], "bob@mail.com");
+        assert_eq!(matches[0]["start"], 2);
+        assert_eq!(matches[0]["groups"]["user"], "bob");
+        assert_eq!(matches[0]["groups"]["2"], "mail");
+    }
+
+    #[test]
+    fn test_find_matches_limit() {
+        let regex = compile(r"\d", "").unwrap();
+        let (matches, total) = find_matches(&regex, "1 2 3 4", 2);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(total, 4);
+    }
+
+    #[test]
+    fn test_replace() {
+        let regex = compile(r"(\d{4})-(\d{2})-(\d{2})", "").unwrap();
+        let (result, count) = replace(&regex, "2025-06-10 and 2025-07-01", "$3/$2/$1", 0);
+        assert_eq!(result, "10/06/2025 and 01/07/2025");
+        assert_eq!(count, 2);
+
+        let (result, count) = replace(&regex, "2025-06-10 and 2025-07-01", "<date>", 1);
+        assert_eq!(result, "<date> and 2025-07-01");
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,531 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TextTool implementation.
+//!
+//! Stateless: every operation works on the strings given in the input.
+
+use super::convert::{self, Format};
+use super::diff::line_diff;
+use super::encode::{decode, encode, encode_digest, hash};
+use super::pattern::{compile, find_matches, replace};
+use crate::tools::constants::text as text_const;
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::response::ResponseBuilder;
+use crate::tools::utils::{validate_enum_value, validate_length};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+/// Tool applying regex, diff, conversion, hash and encoding operations to text.
+pub struct TextTool;
+
+/// Reads a required string field.
+fn required_str<'a>(input: &'a Value, field: &str) -> ToolResult<&'a str> {
+    input[field]
+        .as_str()
+        .ok_or_else(|| ToolError::InvalidInput(format!("Missing '{}' field (string)", field)))
+}
+
+/// Reads an optional count field, bounded by `max`.
+fn optional_count(input: &Value, field: &str, default: usize, max: usize) -> ToolResult<usize> {
+    match input.get(field).filter(|value| !value.is_null()) {
+        None => Ok(default),
+        Some(value) => value
+            .as_u64()
+            .map(|value| value as usize)
+            .filter(|value| *value <= max)
+            .ok_or_else(|| ToolError::InvalidInput(format!("'{}' must be 0-{}", field, max))),
+    }
+}
+
+impl TextTool {
+    /// Creates a new TextTool.
+    pub fn new() -> Self {
+        debug!("TextTool instance created");
+        Self
+    }
+
+    /// Finds the matches of a pattern.
+    fn regex_match(&self, input: &Value) -> ToolResult<Value> {
+        let text = required_str(input, "text")?;
+        let regex = compile(
+            required_str(input, "pattern")?,
+            input["flags"].as_str().unwrap_or_default(),
+        )
+        .map_err(ToolError::InvalidInput)?;
+        let max_matches = optional_count(
+            input,
+            "max_matches",
+            text_const::DEFAULT_MAX_MATCHES,
+            text_const::MAX_MATCHES,
+        )?;
+
+        let (matches, total) = find_matches(&regex, text, max_matches);
+        let returned = matches.len();
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("matches", matches)
+            .field("total", json!(total))
+            .field("truncated", json!(returned < total))
+            .count(returned)
+            .message(format!("{} match(es) found", total))
+            .build())
+    }
+
+    /// Replaces the matches of a pattern.
+    fn regex_replace(&self, input: &Value) -> ToolResult<Value> {
+        let text = required_str(input, "text")?;
+        let replacement = required_str(input, "replacement")?;
+        let regex = compile(
+            required_str(input, "pattern")?,
+            input["flags"].as_str().unwrap_or_default(),
+        )
+        .map_err(ToolError::InvalidInput)?;
+        let limit = optional_count(input, "limit", 0, usize::MAX)?;
+
+        let (result, count) = replace(&regex, text, replacement, limit);
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("result", json!(result))
+            .field("replacements", json!(count))
+            .message(format!("{} replacement(s)", count))
+            .build())
+    }
+
+    /// Computes the unified diff of two texts.
+    fn diff(&self, input: &Value) -> ToolResult<Value> {
+        let old = required_str(input, "old")?;
+        let new = required_str(input, "new")?;
+        let context_lines = optional_count(
+            input,
+            "context_lines",
+            text_const::DEFAULT_CONTEXT_LINES,
+            text_const::MAX_CONTEXT_LINES,
+        )?;
+
+        let diff = line_diff(old, new, context_lines);
+        let identical = diff.insertions == 0 && diff.deletions == 0;
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("diff", json!(diff.unified))
+            .field("insertions", json!(diff.insertions))
+            .field("deletions", json!(diff.deletions))
+            .field("identical", json!(identical))
+            .message(if identical {
+                "The texts are identical".to_string()
+            } else {
+                format!("+{} -{} line(s)", diff.insertions, diff.deletions)
+            })
+            .build())
+    }
+
+    /// Converts a document between JSON, YAML and CSV.
+    fn convert(&self, input: &Value) -> ToolResult<Value> {
+        let text = required_str(input, "text")?;
+        let format = |field: &str| {
+            let name = required_str(input, field)?;
+            Format::parse(name).ok_or_else(|| {
+                ToolError::InvalidInput(format!(
+                    "Unknown format '{}' for '{}': use json, yaml or csv",
+                    name, field
+                ))
+            })
+        };
+        let from = format("from")?;
+        let to = format("to")?;
+        let delimiter = match input["delimiter"].as_str() {
+            None => b',',
+            Some(delimiter) if delimiter.len() == 1 => delimiter.as_bytes()[0],
+            Some(_) => {
+                return Err(ToolError::InvalidInput(
+                    "'delimiter' must be a single ASCII character".to_string(),
+                ))
+            }
+        };
+
+        let value = convert::parse(text, from, delimiter).map_err(ToolError::InvalidInput)?;
+        let result = convert::render(&value, to, delimiter).map_err(ToolError::ExecutionFailed)?;
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("result", json!(result))
+            .message("Document converted")
+            .build())
+    }
+
+    /// Hashes a text.
+    fn hash(&self, input: &Value) -> ToolResult<Value> {
+        let text = required_str(input, "text")?;
+        let algorithm = required_str(input, "algorithm")?;
+        let output = input["output"].as_str().unwrap_or("hex");
+
+        let digest = hash(text, algorithm).map_err(ToolError::InvalidInput)?;
+        let digest = encode_digest(&digest, output).map_err(ToolError::InvalidInput)?;
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("algorithm", json!(algorithm.to_ascii_lowercase()))
+            .field("result", json!(digest))
+            .message("Hash of the UTF-8 bytes of the text")
+            .build())
+    }
+
+    /// Encodes or decodes a text.
+    fn transcode(&self, input: &Value, decoding: bool) -> ToolResult<Value> {
+        let text = required_str(input, "text")?;
+        let encoding = required_str(input, "encoding")?;
+        validate_enum_value(encoding, text_const::VALID_ENCODINGS, "encoding")?;
+
+        let result = if decoding {
+            decode(text, encoding).map_err(ToolError::InvalidInput)?
+        } else {
+            encode(text, encoding).map_err(ToolError::InvalidInput)?
+        };
+        Ok(ResponseBuilder::new()
+            .success(true)
+            .field("encoding", json!(encoding))
+            .field("result", json!(result))
+            .message(if decoding {
+                "Text decoded"
+            } else {
+                "Text encoded"
+            })
+            .build())
+    }
+
+    fn build(_ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async {
+            info!("TextTool instance created");
+            Ok(Arc::new(TextTool::new()) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "TextTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: TextTool::build,
+    })
+}
+
+impl Default for TextTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for TextTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "TextTool".to_string(),
+            name: "Text Tool".to_string(),
+            description: format!(
+                r#"Applies exact text transformations to the strings you provide.
+
+USE THIS TOOL WHEN:
+- You need the exact matches of a regex, or a regex replacement
+- You need the differences between two texts
+- You need to convert a document between JSON, YAML and CSV
+- You need a hash (md5, sha1, sha256, sha512) or an encoding (base64, base64url, hex, url, html)
+Never compute these results yourself: they must be exact.
+
+OPERATIONS:
+- regex_match: matches of "pattern" in "text" with positions (characters) and capture groups (at most "max_matches", default {})
+- regex_replace: replaces the matches of "pattern" in "text" with "replacement" ($1, ${{name}} reference groups; "limit" 0 = all)
+- diff: unified line diff from "old" to "new" ("context_lines", default {})
+- convert: converts "text" "from" json/yaml/csv "to" json/yaml/csv ("delimiter" for CSV, default ","). CSV has a header row; CSV cells are read as strings
+- hash: digest of "text" with "algorithm" ("output": hex (default) or base64)
+- encode / decode: "text" with "encoding" (base64, base64url, hex, url, html)
+
+REGEX SYNTAX:
+- Rust regex syntax: no look-around and no backreferences
+- "flags": i (case-insensitive), m (^ and $ match at lines), s (. matches newline), x (verbose)
+
+EXAMPLES:
+1. {{"operation": "regex_match", "text": "Call 555-1234 or 555-9876", "pattern": "(\\d{{3}})-(\\d{{4}})"}}
+2. {{"operation": "regex_replace", "text": "2025-06-10", "pattern": "(\\d+)-(\\d+)-(\\d+)", "replacement": "$3/$2/$1"}}
+3. {{"operation": "diff", "old": "a\nb\n", "new": "a\nc\n"}}
+4. {{"operation": "convert", "text": "name,age\nAnn,31", "from": "csv", "to": "json"}}
+5. {{"operation": "hash", "text": "hello", "algorithm": "sha256"}}
+6. {{"operation": "decode", "text": "aGVsbG8=", "encoding": "base64"}}
+
+LIMITS:
+- Texts up to {} bytes, patterns up to {} characters
+- Text is UTF-8: binary data cannot be decoded"#,
+                text_const::DEFAULT_MAX_MATCHES,
+                text_const::DEFAULT_CONTEXT_LINES,
+                text_const::MAX_TEXT_LENGTH,
+                text_const::MAX_PATTERN_LENGTH
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": text_const::VALID_OPERATIONS,
+                        "description": "Operation to perform"
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "Input text (all operations except diff)"
+                    },
+                    "pattern": {
+                        "type": "string",
+                        "description": "Regex pattern (regex_match, regex_replace)"
+                    },
+                    "flags": {
+                        "type": "string",
+                        "description": "Regex flags: i, m, s, x (e.g. \"im\")"
+                    },
+                    "replacement": {
+                        "type": "string",
+                        "description": "Replacement text, $1 or ${name} for groups (regex_replace)"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "description": "Maximum replacements, 0 for all (regex_replace)"
+                    },
+                    "max_matches": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": text_const::MAX_MATCHES,
+                        "description": "Maximum matches returned (regex_match)"
+                    },
+                    "old": {
+                        "type": "string",
+                        "description": "Original text (diff)"
+                    },
+                    "new": {
+                        "type": "string",
+                        "description": "Modified text (diff)"
+                    },
+                    "context_lines": {
+                        "type": "integer",
+                        "minimum": 0,
+                        "maximum": text_const::MAX_CONTEXT_LINES,
+                        "description": "Unchanged lines around each change (diff)"
+                    },
+                    "from": {
+                        "type": "string",
+                        "enum": ["json", "yaml", "csv"],
+                        "description": "Input format (convert)"
+                    },
+                    "to": {
+                        "type": "string",
+                        "enum": ["json", "yaml", "csv"],
+                        "description": "Output format (convert)"
+                    },
+                    "delimiter": {
+                        "type": "string",
+                        "description": "CSV delimiter, one character (convert)"
+                    },
+                    "algorithm": {
+                        "type": "string",
+                        "enum": text_const::VALID_ALGORITHMS,
+                        "description": "Hash algorithm (hash)"
+                    },
+                    "output": {
+                        "type": "string",
+                        "enum": ["hex", "base64"],
+                        "description": "Digest encoding (hash, default hex)"
+                    },
+                    "encoding": {
+                        "type": "string",
+                        "enum": text_const::VALID_ENCODINGS,
+                        "description": "Encoding (encode, decode)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "result": {"type": "string"},
+                    "matches": {"type": "array"},
+                    "total": {"type": "integer"},
+                    "replacements": {"type": "integer"},
+                    "diff": {"type": "string"},
+                    "insertions": {"type": "integer"},
+                    "deletions": {"type": "integer"},
+                    "message": {"type": "string"}
+                }
+            }),
+            requires_confirmation: false,
+        }
+    }
+
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+        let operation = input["operation"].as_str().unwrap_or_default();
+        debug!(operation = %operation, "Executing text operation");
+        match operation {
+            "regex_match" => self.regex_match(&input),
+            "regex_replace" => self.regex_replace(&input),
+            "diff" => self.diff(&input),
+            "convert" => self.convert(&input),
+            "hash" => self.hash(&input),
+            "encode" => self.transcode(&input, false),
+            _ => self.transcode(&input, true),
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing operation field".to_string()))?;
+        validate_enum_value(operation, text_const::VALID_OPERATIONS, "operation")?;
+
+        for field in ["text", "old", "new", "replacement"] {
+            if let Some(value) = input[field].as_str() {
+                validate_length(value, text_const::MAX_TEXT_LENGTH, field)?;
+            }
+        }
+        if let Some(pattern) = input["pattern"].as_str() {
+            validate_length(pattern, text_const::MAX_PATTERN_LENGTH, "pattern")?;
+        }
+        Ok(())
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false // Operations only transform the given strings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_regex_match() {
+        let result = TextTool::new()
+            .execute(json!({
+                "operation": "regex_match",
+                "text": "Call 555-1234 or 555-9876",
+                "pattern": r"(\d{3})-(\d{4})",
+                "max_matches": 1
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result["total"], 2);
+        assert_eq!(result["count"], 1);
+        assert_eq!(result["truncated"], true);
+        assert_eq!(result["matches"][0]["groups"]["2"], "1234");
+    }
+
+    #[tokio::test]
+    async fn test_regex_replace() {
+        let result = TextTool::new()
+            .execute(json!({
+                "operation": "regex_replace",
+                "text": "Hello World",
+                "pattern": "o",
+                "flags": "i",
+                "replacement": "0"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result["result"], "Hell0 W0rld");
+        assert_eq!(result["replacements"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_diff() {
+        let result = TextTool::new()
+            .execute(json!({"operation": "diff", "old": "a\nb\n", "new": "a\nc\n"}))
+            .await
+            .unwrap();
+        assert_eq!(result["identical"], false);
+        assert!(result["diff"].as_str().unwrap().contains("-b\n+c\n"));
+    }
+
+    #[tokio::test]
+    async fn test_convert_csv_to_yaml() {
+        let result = TextTool::new()
+            .execute(json!({
+                "operation": "convert",
+                "text": "name;age\nAnn;31\n",
+                "from": "csv",
+                "to": "yaml",
+                "delimiter": ";"
+            }))
+            .await
+            .unwrap();
+        assert_eq!(result["result"], "- name: Ann\n  age: '31'\n");
+    }
+
+    #[tokio::test]
+    async fn test_hash_and_encode() {
+        let tool = TextTool::new();
+        let result = tool
+            .execute(json!({"operation": "hash", "text": "", "algorithm": "sha1"}))
+            .await
+            .unwrap();
+        assert_eq!(result["result"], "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+
+        let result = tool
+            .execute(json!({"operation": "encode", "text": "hello", "encoding": "base64"}))
+            .await
+            .unwrap();
+        assert_eq!(result["result"], "aGVsbG8=");
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let tool = TextTool::new();
+        let result = tool
+            .execute(json!({"operation": "regex_match", "text": "a", "pattern": "("}))
+            .await;
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+
+        let result = tool
+            .execute(json!({"operation": "encode", "text": "a", "encoding": "rot13"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::ValidationFailed(_))));
+
+        let result = tool
+            .execute(json!({"operation": "convert", "text": "{}", "from": "json", "to": "xml"}))
+            .await;
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_validate_input() {
+        let tool = TextTool::new();
+        assert!(tool
+            .validate_input(&json!({"operation": "translate"}))
+            .is_err());
+        let long = "a".repeat(text_const::MAX_PATTERN_LENGTH + 1);
+        assert!(tool
+            .validate_input(&json!({"operation": "regex_match", "text": "", "pattern": long}))
+            .is_err());
+        assert!(tool
+            .validate_input(&json!({"operation": "hash", "text": "x", "algorithm": "md5"}))
+            .is_ok());
+    }
+}
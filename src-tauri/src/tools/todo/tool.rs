@@ -24,6 +24,10 @@ use crate::tools::constants::todo::{
     MAX_DESCRIPTION_LENGTH, MAX_NAME_LENGTH, PRIORITY_MAX, PRIORITY_MIN, TASK_SELECT_FIELDS,
     VALID_STATUSES,
 };
+use crate::tools::constants::tool_execution::DEFAULT_TIMEOUT_SECS;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
 use crate::tools::response::ResponseBuilder;
 use crate::tools::utils::{
     db_error, delete_with_check, validate_enum_value, validate_length, validate_not_empty,
@@ -369,6 +373,26 @@ impl TodoTool {
             "Task deleted successfully",
         ))
     }
+
+    /// Creates the tool for the workflow of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = TodoTool::new(ctx.factory.get_db(), wf_id, ctx.agent_id, ctx.app_handle);
+            info!("TodoTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    ToolRegistration::Tool(ToolMetadata {
+        name: "TodoTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+        constructor: TodoTool::build,
+    })
 }
 
 #[async_trait]
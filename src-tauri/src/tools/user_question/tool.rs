@@ -17,8 +17,13 @@
 //! This tool allows agents to ask questions to users through a modal interface.
 
 use crate::db::{sanitize_for_surrealdb, DBClient};
+use crate::inbox;
+use crate::models::pending_item::{PendingItemCreate, PendingItemKind};
 use crate::models::{QuestionOption, UserQuestionCreate, UserQuestionStreamPayload};
 use crate::tools::constants::user_question as uq_const;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
 use crate::tools::user_question::circuit_breaker::UserQuestionCircuitBreaker;
 use crate::tools::utils::{validate_length, validate_not_empty};
 use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
@@ -212,19 +217,27 @@ impl UserQuestionTool {
             .map_err(|e| ToolError::ExecutionFailed(format!("Failed to encode JSON: {}", e)))?;
         // Sanitize to remove null characters that cause SurrealDB panics
         let json_data = sanitize_for_surrealdb(json_data);
-        let query = format!("CREATE user_question:`{}` CONTENT $data", question_id);
-
         info!(question_id = %question_id, "Creating user question in DB");
 
         self.db
-            .execute_with_params(&query, vec![("data".to_string(), json_data)])
+            .execute_with_params(
+                "CREATE type::thing('user_question', $id) CONTENT $data",
+                vec![
+                    ("id".to_string(), serde_json::json!(question_id)),
+                    ("data".to_string(), json_data),
+                ],
+            )
             .await
             .map_err(|e| ToolError::ExecutionFailed(format!("Failed to create question: {}", e)))?;
 
         // Verify the question was created
-        let verify_query = format!("SELECT status FROM user_question:`{}`", question_id);
-        let verify_result: Vec<serde_json::Value> =
-            self.db.query_json(&verify_query).await.map_err(|e| {
+        let verify_query = "SELECT status FROM type::thing('user_question', $id)";
+        let params = vec![("id".to_string(), serde_json::json!(question_id))];
+        let verify_result: Vec<serde_json::Value> = self
+            .db
+            .query_json_with_params(verify_query, params)
+            .await
+            .map_err(|e| {
                 ToolError::ExecutionFailed(format!("Failed to verify question creation: {}", e))
             })?;
 
@@ -332,10 +345,16 @@ impl UserQuestionTool {
 
         let question_id = Uuid::new_v4().to_string();
         self.create_question_record(&question_id, &input).await?;
+        inbox::track_pending_item(
+            &self.db,
+            PendingItemCreate::user_question(&question_id, &self.workflow_id, &input.question),
+        )
+        .await;
         self.emit_question_event(&question_id, &input);
 
         // Wait for response and update circuit breaker based on result
         let response = self.wait_for_response(&question_id).await;
+        inbox::resolve_pending_item(&self.db, PendingItemKind::UserQuestion, &question_id).await;
 
         // OPT-UQ-12: Update circuit breaker based on response
         match &response {
@@ -394,11 +413,10 @@ impl UserQuestionTool {
                 );
 
                 // Update DB status to timeout
-                let update_query = format!(
-                    "UPDATE user_question:`{}` SET status = 'timeout'",
-                    question_id
-                );
-                if let Err(e) = self.db.execute(&update_query).await {
+                let update_query =
+                    "UPDATE type::thing('user_question', $id) SET status = 'timeout'";
+                let params = vec![("id".to_string(), serde_json::json!(question_id))];
+                if let Err(e) = self.db.execute_with_params(update_query, params).await {
                     warn!(error = %e, "Failed to update question status to timeout");
                 }
 
@@ -409,14 +427,12 @@ impl UserQuestionTool {
             }
 
             // Query question status
-            let query = format!(
-                "SELECT status, selected_options, text_response FROM user_question:`{}`",
-                question_id
-            );
+            let query = "SELECT status, selected_options, text_response FROM type::thing('user_question', $id)";
+            let params = vec![("id".to_string(), serde_json::json!(question_id))];
 
             let result: Vec<serde_json::Value> = self
                 .db
-                .query_json(&query)
+                .query_json_with_params(query, params)
                 .await
                 .map_err(|e| ToolError::ExecutionFailed(format!("DB query failed: {}", e)))?;
 
@@ -500,6 +516,28 @@ impl UserQuestionTool {
             }
         }
     }
+
+    /// Creates the tool for the workflow of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let wf_id = ctx.workflow_id_or_default();
+            let tool =
+                UserQuestionTool::new(ctx.factory.get_db(), wf_id, ctx.agent_id, ctx.app_handle);
+            info!("UserQuestionTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Waits for the user with its own timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "UserQuestionTool",
+        category: ToolCategory::Basic,
+        requires_context: false,
+        default_timeout_secs: None,
+        constructor: UserQuestionTool::build,
+    })
 }
 
 #[async_trait]
@@ -14,9 +14,17 @@
 
 //! Database and validation utilities for tools.
 
-use crate::db::DBClient;
+use crate::db::{queries::settings, DBClient};
+use crate::models::locale::LocaleSettings;
+use crate::models::WorkflowToolExecution;
+use crate::tools::constants::attachments as attachments_const;
 use crate::tools::{ToolError, ToolResult};
+use std::path::PathBuf;
 use std::sync::Arc;
+use tracing::debug;
+
+/// Storage key of the format locale settings
+pub const LOCALE_SETTINGS_KEY: &str = "settings:locale";
 
 /// Verifies a record exists in the database.
 ///
@@ -58,10 +66,15 @@ pub async fn delete_with_check(
 ) -> ToolResult<()> {
     ensure_record_exists(db, table, id, resource_name).await?;
 
-    let delete_query = format!("DELETE {}:`{}`", table, id);
-    db.execute(&delete_query)
-        .await
-        .map_err(|e| ToolError::DatabaseError(e.to_string()))?;
+    db.execute_with_params(
+        "DELETE type::thing($table, $id)",
+        vec![
+            ("table".to_string(), serde_json::json!(table)),
+            ("id".to_string(), serde_json::json!(id)),
+        ],
+    )
+    .await
+    .map_err(|e| ToolError::DatabaseError(e.to_string()))?;
     Ok(())
 }
 
@@ -71,6 +84,19 @@ pub fn db_error(e: impl std::fmt::Display) -> ToolError {
     ToolError::DatabaseError(e.to_string())
 }
 
+/// Loads locale settings from the database, falling back to defaults.
+pub async fn load_locale_settings(db: &DBClient) -> LocaleSettings {
+    match settings::load_config(db, LOCALE_SETTINGS_KEY).await {
+        Ok(stored) => stored
+            .and_then(|config| serde_json::from_value(config).ok())
+            .unwrap_or_default(),
+        Err(e) => {
+            debug!(error = %e, "No locale settings found, using defaults");
+            LocaleSettings::default()
+        }
+    }
+}
+
 /// Validates that a string is not empty.
 #[inline]
 pub fn validate_not_empty(value: &str, field_name: &str) -> ToolResult<()> {
@@ -126,6 +152,39 @@ pub fn validate_enum_value(value: &str, valid_values: &[&str], field_name: &str)
     Ok(())
 }
 
+/// Home directory of the user.
+pub fn home_dir() -> Option<PathBuf> {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()
+        .map(PathBuf::from)
+}
+
+/// Data directory of the application (`~/.zileo`).
+pub fn data_dir() -> PathBuf {
+    home_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join(".zileo")
+}
+
+/// Resolves a local file path given to a tool: absolute or starting with `~`.
+pub fn resolve_path(raw: &str) -> ToolResult<PathBuf> {
+    let raw = raw.trim();
+    let path = match raw.strip_prefix('~') {
+        Some(rest) => home_dir()
+            .ok_or_else(|| ToolError::ExecutionFailed("Home directory not found".to_string()))?
+            .join(rest.trim_start_matches(['/', '\\'])),
+        None => PathBuf::from(raw),
+    };
+    if !path.is_absolute() {
+        return Err(ToolError::InvalidInput(format!(
+            "'{}' is not an absolute path",
+            raw
+        )));
+    }
+    Ok(path)
+}
+
 /// Fluent builder for SurrealDB queries.
 #[allow(dead_code)]
 pub struct QueryBuilder {
@@ -343,6 +402,22 @@ Sub-agents return structured JSON with:
     )
 }
 
+/// Image paths produced during a run (charts, screenshots), in order.
+///
+/// Capped to the attachments a message can hold: later images are dropped.
+pub fn tool_image_paths(executions: &[WorkflowToolExecution]) -> Vec<String> {
+    executions
+        .iter()
+        .filter(|execution| {
+            execution.success
+                && attachments_const::IMAGE_TOOLS.contains(&execution.tool_name.as_str())
+        })
+        .filter_map(|execution| execution.output_result["path"].as_str())
+        .map(str::to_string)
+        .take(attachments_const::MAX_PER_MESSAGE)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -457,4 +532,61 @@ mod tests {
         assert!(query.contains("workflow_id IS NONE AND type = $mem_type"));
         assert_eq!(params.len(), 1);
     }
+
+    fn execution(
+        tool_name: &str,
+        success: bool,
+        output: serde_json::Value,
+    ) -> WorkflowToolExecution {
+        WorkflowToolExecution {
+            tool_type: "local".to_string(),
+            tool_name: tool_name.to_string(),
+            server_name: None,
+            input_params: serde_json::json!({}),
+            output_result: output,
+            success,
+            error_message: None,
+            duration_ms: 1,
+            iteration: 0,
+        }
+    }
+
+    #[test]
+    fn test_tool_image_paths() {
+        use serde_json::json;
+
+        let mut executions = vec![
+            execution("ChartTool", true, json!({"path": "/tmp/a.png"})),
+            execution("ChartTool", false, json!({"path": "/tmp/b.png"})),
+            execution("SqlTool", true, json!({"path": "/tmp/c.png"})),
+            execution("ChartTool", true, json!({"success": true})),
+            execution("ScreenshotTool", true, json!({"path": "/tmp/d.png"})),
+        ];
+        assert_eq!(
+            tool_image_paths(&executions),
+            vec!["/tmp/a.png", "/tmp/d.png"]
+        );
+
+        for i in 0..attachments_const::MAX_PER_MESSAGE {
+            executions.push(execution(
+                "ChartTool",
+                true,
+                json!({"path": format!("/tmp/{}.svg", i)}),
+            ));
+        }
+        assert_eq!(
+            tool_image_paths(&executions).len(),
+            attachments_const::MAX_PER_MESSAGE
+        );
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        assert!(resolve_path("relative/file.pdf").is_err());
+        assert!(resolve_path("~/file.pdf").unwrap().is_absolute());
+        assert_eq!(
+            resolve_path("/tmp/file.pdf").unwrap(),
+            PathBuf::from("/tmp/file.pdf")
+        );
+    }
 }
@@ -23,8 +23,10 @@
 //!
 //! 1. Tool calls `request_validation()` before executing operation
 //! 2. Helper creates a `ValidationRequest` in the database
-//! 3. Helper emits `validation_required` Tauri event
-//! 4. Helper waits for approval/rejection (polling with timeout)
+//! 3. Helper emits `validation_required` Tauri event and forwards the request
+//!    to the external notification channels (see [`super::validation_notifier`])
+//! 4. Helper waits for approval/rejection (polling with timeout); on timeout
+//!    the request is rejected, approved or asked again per `TimeoutBehavior`
 //! 5. Helper returns result to tool
 //!
 //! # Events
@@ -32,14 +34,26 @@
 //! - `validation_required`: Emitted when validation is needed
 //! - `validation_response`: Listened for approval/rejection from frontend
 
-use crate::db::DBClient;
+use crate::agents::tool_acknowledgment;
+use crate::commands::validation::VALIDATION_SETTINGS_KEY;
+use crate::db::{queries::settings, DBClient};
+use crate::inbox;
+use crate::models::audit::{AuditAction, AuditLogCreate};
+use crate::models::email::{EmailAccount, EmailDraft};
+use crate::models::pending_item::PendingItemCreate;
 use crate::models::streaming::{events, SubAgentOperationType, ValidationRequiredEvent};
+use crate::models::tool_acknowledgment::{classify_powerful_tool, PowerfulToolKind};
+use crate::models::validation::{classify_tool_call, ToolCallRisk};
 use crate::models::{
-    RiskLevel, ValidationMode, ValidationRequestCreate, ValidationSettings, ValidationStatus,
-    ValidationType,
+    RiskLevel, TimeoutBehavior, ValidationMode, ValidationRequestCreate, ValidationSettings,
+    ValidationStatus, ValidationType,
 };
-use crate::tools::constants::sub_agent::{VALIDATION_POLL_MS, VALIDATION_TIMEOUT_SECS};
+use crate::security::audit;
+use crate::tools::constants::sub_agent::{VALIDATION_MAX_ASKS, VALIDATION_POLL_MS};
+use crate::tools::validation_notifier::{self, ValidationNotification};
+use crate::tools::validation_preview;
 use crate::tools::ToolError;
+use chrono::Utc;
 use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
@@ -100,24 +114,17 @@ impl ValidationHelper {
     /// Loads validation settings from database.
     /// Returns default settings if not configured.
     async fn load_validation_settings(&self) -> ValidationSettings {
-        let query = "SELECT config FROM settings:`settings:validation`";
-        let results: Vec<Value> = match self.db.query(query).await {
-            Ok(r) => r,
+        let stored = match settings::load_config(&self.db, VALIDATION_SETTINGS_KEY).await {
+            Ok(stored) => stored,
             Err(e) => {
                 debug!(error = %e, "No validation settings found, using defaults");
                 return ValidationSettings::default();
             }
         };
 
-        if let Some(first) = results.first() {
-            if let Some(config) = first.get("config") {
-                if !config.is_null() {
-                    if let Ok(settings) =
-                        serde_json::from_value::<ValidationSettings>(config.clone())
-                    {
-                        return settings;
-                    }
-                }
+        if let Some(config) = stored {
+            if let Ok(settings) = serde_json::from_value::<ValidationSettings>(config) {
+                return settings;
             }
         }
 
@@ -183,6 +190,10 @@ impl ValidationHelper {
             ValidationType::Mcp => settings.selective_config.mcp,
             ValidationType::FileOp => settings.selective_config.file_ops,
             ValidationType::DbOp => settings.selective_config.db_ops,
+            // Token anomaly pauses are opted into through their own settings
+            ValidationType::TokenAnomaly => true,
+            // First-use acknowledgments are required whatever the settings
+            ValidationType::ToolFirstUse => true,
         };
 
         if !type_requires_validation {
@@ -268,31 +279,47 @@ impl ValidationHelper {
                 error!(error = %e, "Failed to create validation request in database");
                 ToolError::DatabaseError(format!("Failed to create validation request: {}", e))
             })?;
+        inbox::track_pending_item(
+            &self.db,
+            PendingItemCreate::validation(
+                &validation_id,
+                workflow_id,
+                &ValidationType::SubAgent,
+                operation_description,
+                &risk_level,
+            ),
+        )
+        .await;
 
         // 3. Emit validation_required event to frontend
-        if let Some(ref app_handle) = self.app_handle {
-            let event = ValidationRequiredEvent {
+        let reminder = serde_json::to_value(ValidationRequiredEvent {
+            validation_id: validation_id.clone(),
+            workflow_id: workflow_id.to_string(),
+            operation_type: operation_type.clone(),
+            operation: operation_description.to_string(),
+            risk_level: risk_level.to_string(),
+            details,
+        })
+        .unwrap_or_default();
+        self.emit_validation_required(&validation_id, &reminder);
+
+        self.forward_to_channels(
+            &settings,
+            ValidationNotification {
                 validation_id: validation_id.clone(),
                 workflow_id: workflow_id.to_string(),
-                operation_type: operation_type.clone(),
+                validation_type: ValidationType::SubAgent.to_string(),
                 operation: operation_description.to_string(),
-                risk_level: risk_level.to_string(),
-                details,
-            };
-
-            if let Err(e) = app_handle.emit(events::VALIDATION_REQUIRED, &event) {
-                warn!(error = %e, "Failed to emit validation_required event");
-            } else {
-                debug!(validation_id = %validation_id, "Emitted validation_required event");
-            }
-        } else {
-            warn!("No app handle available, skipping event emission");
-        }
+                risk_level: risk_level.clone(),
+            },
+        )
+        .await;
 
         // 4. Wait for validation response (polling with timeout)
         let result = self
-            .wait_for_validation(&validation_id, Duration::from_secs(VALIDATION_TIMEOUT_SECS))
+            .await_decision(&settings, &validation_id, reminder)
             .await;
+        inbox::resolve_validation(&self.db, &validation_id).await;
 
         // 5. Return result
         match result {
@@ -311,6 +338,121 @@ impl ValidationHelper {
         }
     }
 
+    /// Forwards a pending request to the external channels, if configured.
+    async fn forward_to_channels(
+        &self,
+        settings: &ValidationSettings,
+        notification: ValidationNotification,
+    ) {
+        // Links stay valid as long as the request waits for a decision
+        let asks = match settings.timeout_behavior {
+            TimeoutBehavior::AskAgain => VALIDATION_MAX_ASKS,
+            _ => 1,
+        };
+        let pending_secs = settings.timeout().as_secs() * asks as u64;
+        validation_notifier::notify_validation_required(
+            self.db.clone(),
+            self.app_handle.clone(),
+            &settings.notifications,
+            notification,
+            Utc::now() + chrono::Duration::seconds(pending_secs as i64),
+        )
+        .await;
+    }
+
+    /// Emits the `validation_required` event of a pending request to the frontend.
+    fn emit_validation_required(&self, validation_id: &str, event: &Value) {
+        if let Some(ref app_handle) = self.app_handle {
+            if let Err(e) = app_handle.emit(events::VALIDATION_REQUIRED, event) {
+                warn!(error = %e, "Failed to emit validation_required event");
+            } else {
+                debug!(validation_id = %validation_id, "Emitted validation_required event");
+            }
+        } else {
+            warn!("No app handle available, skipping event emission");
+        }
+    }
+
+    /// Waits for the user's decision, applying the configured timeout behavior.
+    ///
+    /// On timeout, the request is rejected, approved, or asked again (event
+    /// emitted again, at most `VALIDATION_MAX_ASKS` times in total, then
+    /// rejected) according to `settings.timeout_behavior`.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If approved (by the user or on timeout)
+    /// * `Ok(false)` - If rejected by the user
+    /// * `Err(ToolError::Timeout)` - If timed out and rejected
+    async fn await_decision(
+        &self,
+        settings: &ValidationSettings,
+        validation_id: &str,
+        event: Value,
+    ) -> Result<bool, ToolError> {
+        let timeout = settings.timeout();
+        let mut asks = 1;
+
+        loop {
+            if let Some(approved) = self.wait_for_validation(validation_id, timeout).await? {
+                return Ok(approved);
+            }
+
+            match settings.timeout_behavior {
+                TimeoutBehavior::AskAgain if asks < VALIDATION_MAX_ASKS => {
+                    asks += 1;
+                    info!(validation_id = %validation_id, asks, "Validation timed out, asking again");
+                    self.emit_validation_required(validation_id, &event);
+                }
+                TimeoutBehavior::Approve => {
+                    info!(validation_id = %validation_id, "Validation timed out, approving");
+                    self.close_timed_out(validation_id, ValidationStatus::Approved)
+                        .await;
+                    return Ok(true);
+                }
+                TimeoutBehavior::Reject | TimeoutBehavior::AskAgain => {
+                    self.close_timed_out(validation_id, ValidationStatus::Rejected)
+                        .await;
+                    return Err(ToolError::Timeout(format!(
+                        "Validation request '{}' timed out after {} seconds. \
+                         User did not respond in time.",
+                        validation_id,
+                        timeout.as_secs() * asks as u64
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Records the outcome of a validation request nobody answered.
+    async fn close_timed_out(&self, validation_id: &str, status: ValidationStatus) {
+        let reason = match status {
+            ValidationStatus::Approved => "approval_reason",
+            _ => "rejection_reason",
+        };
+        let _ = self
+            .db
+            .execute_with_params(
+                &format!(
+                    "UPDATE type::thing('validation_request', $id) SET status = $status, \
+                     details.{} = 'Validation timed out'",
+                    reason
+                ),
+                vec![
+                    ("id".to_string(), serde_json::json!(validation_id)),
+                    ("status".to_string(), serde_json::json!(status.to_string())),
+                ],
+            )
+            .await;
+        audit::record(
+            &self.db,
+            AuditLogCreate {
+                details: serde_json::json!({ "status": status.to_string(), "reason": "timeout" }),
+                ..AuditLogCreate::new(AuditAction::ValidationDecision, "system", validation_id)
+            },
+        )
+        .await;
+    }
+
     /// Waits for validation response by polling the database.
     ///
     /// # Arguments
@@ -318,49 +460,41 @@ impl ValidationHelper {
     /// * `timeout` - Maximum time to wait for response
     ///
     /// # Returns
-    /// * `Ok(true)` - If approved
-    /// * `Ok(false)` - If rejected
-    /// * `Err(ToolError::Timeout)` - If timed out
+    /// * `Ok(Some(true))` - If approved
+    /// * `Ok(Some(false))` - If rejected
+    /// * `Ok(None)` - If timed out (the request is still pending)
     async fn wait_for_validation(
         &self,
         validation_id: &str,
         timeout: Duration,
-    ) -> Result<bool, ToolError> {
+    ) -> Result<Option<bool>, ToolError> {
         let poll_interval = Duration::from_millis(VALIDATION_POLL_MS);
         let start_time = std::time::Instant::now();
 
         loop {
             // Check if timeout exceeded
             if start_time.elapsed() >= timeout {
-                // Update validation status to rejected (timeout)
-                let update_query = format!(
-                    "UPDATE validation_request:`{}` SET status = 'rejected', \
-                     details.rejection_reason = 'Validation timed out'",
-                    validation_id
-                );
-                let _ = self.db.execute(&update_query).await;
-
-                return Err(ToolError::Timeout(format!(
-                    "Validation request '{}' timed out after {} seconds. \
-                     User did not respond in time.",
-                    validation_id,
-                    timeout.as_secs()
-                )));
+                return Ok(None);
             }
 
             // Query validation status
-            let query = format!("SELECT status FROM validation_request:`{}`", validation_id);
-
-            let result: Vec<Value> = self.db.query(&query).await.map_err(|e| {
-                ToolError::DatabaseError(format!("Failed to query validation status: {}", e))
-            })?;
+            let result: Vec<Value> = self
+                .db
+                .query_with_params(
+                    "SELECT status FROM type::thing('validation_request', $id)",
+                    vec![("id".to_string(), serde_json::json!(validation_id))],
+                )
+                .await
+                .map_err(|e| {
+                    ToolError::DatabaseError(format!("Failed to query validation status: {}", e))
+                })?;
 
             if let Some(first) = result.first() {
                 let status = first["status"].as_str().unwrap_or("pending");
 
                 match status {
-                    "approved" => return Ok(true),
-                    "rejected" => return Ok(false),
+                    "approved" => return Ok(Some(true)),
+                    "rejected" => return Ok(Some(false)),
                     "pending" => {
                         // Continue waiting
                         debug!(
@@ -465,9 +599,12 @@ impl ValidationHelper {
         arguments: Value,
     ) -> Result<(), ToolError> {
         let settings = self.load_validation_settings().await;
-        let risk_level = RiskLevel::Low; // Local tools are generally low risk
+        let ToolCallRisk {
+            validation_type,
+            risk_level,
+        } = classify_tool_call(tool_name, false, &arguments);
 
-        if !self.needs_validation_for_type(&settings, &ValidationType::Tool, &risk_level) {
+        if !self.needs_validation_for_type(&settings, &validation_type, &risk_level) {
             info!(
                 workflow_id = %workflow_id,
                 tool_name = %tool_name,
@@ -478,7 +615,9 @@ impl ValidationHelper {
         }
 
         let validation_id = uuid::Uuid::new_v4().to_string();
-        let details = Self::tool_details(tool_name, operation, &arguments);
+        let mut details = Self::tool_details(tool_name, operation, &arguments);
+        self.attach_preview(&mut details, workflow_id, tool_name, false, &arguments)
+            .await;
         let description = format!("Execute {} tool: {}", tool_name, operation);
 
         info!(
@@ -491,7 +630,7 @@ impl ValidationHelper {
         self.create_and_wait_validation(
             &validation_id,
             workflow_id,
-            ValidationType::Tool,
+            validation_type,
             &description,
             details,
             risk_level,
@@ -508,6 +647,114 @@ impl ValidationHelper {
         })
     }
 
+    /// Requests confirmation of a write statement on an external database.
+    ///
+    /// Always creates a validation request, whatever the validation mode:
+    /// writes to user databases are never auto-approved.
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Associated workflow ID
+    /// * `database` - External database name
+    /// * `statement` - SQL statement
+    /// * `params` - Bound parameters
+    pub async fn request_sql_write_validation(
+        &self,
+        workflow_id: &str,
+        database: &str,
+        statement: &str,
+        params: &[Value],
+    ) -> Result<(), ToolError> {
+        let validation_id = uuid::Uuid::new_v4().to_string();
+        let details = Self::sql_write_details(database, statement, params);
+        let description = format!("Execute write statement on database '{}'", database);
+
+        info!(
+            validation_id = %validation_id,
+            workflow_id = %workflow_id,
+            database = %database,
+            "Creating validation request for SQL write statement"
+        );
+
+        self.create_and_wait_validation(
+            &validation_id,
+            workflow_id,
+            ValidationType::DbOp,
+            &description,
+            details,
+            RiskLevel::High,
+        )
+        .await
+    }
+
+    /// Creates operation details JSON for a SQL write statement.
+    pub fn sql_write_details(database: &str, statement: &str, params: &[Value]) -> Value {
+        serde_json::json!({
+            "tool_name": "SqlTool",
+            "operation": "execute",
+            "database": database,
+            "statement": safe_truncate(statement, 2000, true),
+            "params_preview": safe_truncate(&Value::from(params.to_vec()).to_string(), 200, true)
+        })
+    }
+
+    /// Requests confirmation of an email before it is sent.
+    ///
+    /// Always creates a validation request, whatever the validation mode:
+    /// emails are never sent without the user's approval.
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Associated workflow ID
+    /// * `account` - Email account sending the message
+    /// * `draft` - Message to send
+    pub async fn request_email_send_validation(
+        &self,
+        workflow_id: &str,
+        account: &EmailAccount,
+        draft: &EmailDraft,
+    ) -> Result<(), ToolError> {
+        let validation_id = uuid::Uuid::new_v4().to_string();
+        let details = Self::email_send_details(account, draft);
+        let description = format!(
+            "Send email '{}' from {} to {}",
+            safe_truncate(&draft.subject, 80, true),
+            account.address,
+            draft.to.join(", ")
+        );
+
+        info!(
+            validation_id = %validation_id,
+            workflow_id = %workflow_id,
+            account = %account.name,
+            recipients = draft.to.len() + draft.cc.len(),
+            "Creating validation request for email sending"
+        );
+
+        self.create_and_wait_validation(
+            &validation_id,
+            workflow_id,
+            ValidationType::Tool,
+            &description,
+            details,
+            RiskLevel::High,
+        )
+        .await
+    }
+
+    /// Creates operation details JSON for an email to send.
+    pub fn email_send_details(account: &EmailAccount, draft: &EmailDraft) -> Value {
+        serde_json::json!({
+            "tool_name": "EmailTool",
+            "operation": "send_draft",
+            "account": account.name,
+            "from": account.address,
+            "to": draft.to,
+            "cc": draft.cc,
+            "subject": draft.subject,
+            "body": safe_truncate(&draft.body, 2000, true),
+            "in_reply_to": draft.in_reply_to
+        })
+    }
+
     // =========================================================================
     // MCP Tool Validation
     // =========================================================================
@@ -531,9 +778,12 @@ impl ValidationHelper {
         arguments: Value,
     ) -> Result<(), ToolError> {
         let settings = self.load_validation_settings().await;
-        let risk_level = RiskLevel::Medium; // MCP calls are medium risk (external system)
+        let ToolCallRisk {
+            validation_type,
+            risk_level,
+        } = classify_tool_call(tool_name, true, &arguments);
 
-        if !self.needs_validation_for_type(&settings, &ValidationType::Mcp, &risk_level) {
+        if !self.needs_validation_for_type(&settings, &validation_type, &risk_level) {
             info!(
                 workflow_id = %workflow_id,
                 server_name = %server_name,
@@ -545,7 +795,9 @@ impl ValidationHelper {
         }
 
         let validation_id = uuid::Uuid::new_v4().to_string();
-        let details = Self::mcp_details(server_name, tool_name, &arguments);
+        let mut details = Self::mcp_details(server_name, tool_name, &arguments);
+        self.attach_preview(&mut details, workflow_id, tool_name, true, &arguments)
+            .await;
         let description = format!("Call MCP server '{}': {}", server_name, tool_name);
 
         info!(
@@ -559,7 +811,7 @@ impl ValidationHelper {
         self.create_and_wait_validation(
             &validation_id,
             workflow_id,
-            ValidationType::Mcp,
+            validation_type,
             &description,
             details,
             risk_level,
@@ -567,6 +819,25 @@ impl ValidationHelper {
         .await
     }
 
+    /// Adds the change preview of a tool call to its details (`preview` field).
+    ///
+    /// See [`validation_preview`] for the previewed operations.
+    async fn attach_preview(
+        &self,
+        details: &mut Value,
+        workflow_id: &str,
+        tool_name: &str,
+        is_mcp: bool,
+        arguments: &Value,
+    ) {
+        let preview =
+            validation_preview::build_preview(&self.db, workflow_id, tool_name, is_mcp, arguments)
+                .await;
+        if let Some(preview) = preview.and_then(|p| serde_json::to_value(p).ok()) {
+            details["preview"] = preview;
+        }
+    }
+
     /// Creates operation details JSON for MCP tool call.
     pub fn mcp_details(server_name: &str, tool_name: &str, arguments: &Value) -> Value {
         serde_json::json!({
@@ -576,6 +847,162 @@ impl ValidationHelper {
         })
     }
 
+    // =========================================================================
+    // First-Use Acknowledgment
+    // =========================================================================
+
+    /// Gates the first use of a powerful tool by an agent.
+    ///
+    /// Shell, file system write and SQL tools (see
+    /// [`classify_powerful_tool`]) must be acknowledged once per (agent, tool)
+    /// pair, whatever the validation mode. The acknowledgment is then recorded
+    /// and later calls follow the normal risk-based validation.
+    ///
+    /// # Arguments
+    /// * `workflow_id` - Associated workflow ID
+    /// * `agent_id` - Agent calling the tool
+    /// * `call_name` - Tool name as called by the agent (`mcp__server__tool`)
+    /// * `tool_name` - Tool name without the MCP server prefix
+    /// * `arguments` - Tool arguments (JSON)
+    ///
+    /// # Returns
+    /// * `Ok(true)` - If the tool was acknowledged by this call
+    /// * `Ok(false)` - If the tool is not powerful or was already acknowledged
+    /// * `Err(ToolError)` - If rejected, timed out or the check failed
+    pub async fn acknowledge_first_use(
+        &self,
+        workflow_id: &str,
+        agent_id: &str,
+        call_name: &str,
+        tool_name: &str,
+        arguments: &Value,
+    ) -> Result<bool, ToolError> {
+        let kind = match classify_powerful_tool(tool_name) {
+            Some(kind) => kind,
+            None => return Ok(false),
+        };
+
+        let acknowledged = tool_acknowledgment::is_tool_acknowledged(&self.db, agent_id, call_name)
+            .await
+            .map_err(|e| {
+                ToolError::DatabaseError(format!("Failed to check tool acknowledgment: {}", e))
+            })?;
+        if acknowledged {
+            return Ok(false);
+        }
+
+        self.request_first_use_acknowledgment(
+            workflow_id,
+            agent_id,
+            call_name,
+            kind,
+            arguments.clone(),
+        )
+        .await?;
+
+        if let Err(e) =
+            tool_acknowledgment::record_tool_acknowledgment(&self.db, agent_id, call_name, kind)
+                .await
+        {
+            // The call was acknowledged; the next one asks again
+            warn!(error = %e, tool_name = %call_name, "Failed to record tool acknowledgment");
+        }
+        Ok(true)
+    }
+
+    /// Requests the one-time acknowledgment of a powerful tool for an agent.
+    ///
+    /// Always creates a validation request, whatever the validation mode.
+    async fn request_first_use_acknowledgment(
+        &self,
+        workflow_id: &str,
+        agent_id: &str,
+        tool_name: &str,
+        kind: PowerfulToolKind,
+        arguments: Value,
+    ) -> Result<(), ToolError> {
+        let validation_id = uuid::Uuid::new_v4().to_string();
+        let details = serde_json::json!({
+            "agent_id": agent_id,
+            "tool_name": tool_name,
+            "kind": kind,
+            "first_use": true,
+            "arguments_preview": safe_truncate(&arguments.to_string(), 200, true)
+        });
+        let description = format!(
+            "Allow agent '{}' to use {} tool '{}' (first use)",
+            agent_id, kind, tool_name
+        );
+
+        info!(
+            validation_id = %validation_id,
+            workflow_id = %workflow_id,
+            agent_id = %agent_id,
+            tool_name = %tool_name,
+            kind = %kind,
+            "Creating first-use acknowledgment request"
+        );
+
+        self.create_and_wait_validation(
+            &validation_id,
+            workflow_id,
+            ValidationType::ToolFirstUse,
+            &description,
+            details,
+            RiskLevel::High,
+        )
+        .await
+    }
+
+    // =========================================================================
+    // Token Anomaly Validation
+    // =========================================================================
+
+    /// Requests confirmation to continue a run whose token usage is anomalous.
+    ///
+    /// Always creates a validation request: the pause is enabled explicitly through
+    /// `TokenAnomalySettings::pause_for_confirmation`, not the validation mode.
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the user allowed the run to continue
+    /// * `Err(ToolError)` - If rejected or timed out
+    pub async fn request_token_anomaly_validation(
+        &self,
+        workflow_id: &str,
+        tokens_used: usize,
+        baseline_tokens: usize,
+        ratio: f64,
+    ) -> Result<(), ToolError> {
+        let validation_id = uuid::Uuid::new_v4().to_string();
+        let details = serde_json::json!({
+            "tokens_used": tokens_used,
+            "baseline_tokens": baseline_tokens,
+            "ratio": ratio
+        });
+        let description = format!(
+            "Continue run using {} tokens ({:.1}x the usual {} tokens)",
+            tokens_used, ratio, baseline_tokens
+        );
+
+        info!(
+            validation_id = %validation_id,
+            workflow_id = %workflow_id,
+            tokens_used,
+            baseline_tokens,
+            "Creating validation request for token anomaly"
+        );
+
+        self.create_and_wait_validation(
+            &validation_id,
+            workflow_id,
+            ValidationType::TokenAnomaly,
+            &description,
+            details,
+            RiskLevel::High,
+        )
+        .await
+    }
+
     // =========================================================================
     // Common Validation Logic
     // =========================================================================
@@ -608,32 +1035,45 @@ impl ValidationHelper {
                 error!(error = %e, "Failed to create validation request in database");
                 ToolError::DatabaseError(format!("Failed to create validation request: {}", e))
             })?;
+        inbox::track_pending_item(
+            &self.db,
+            PendingItemCreate::validation(
+                validation_id,
+                workflow_id,
+                &validation_type,
+                description,
+                &risk_level,
+            ),
+        )
+        .await;
+
+        // Emit validation_required event to frontend (generic validation event)
+        let event = serde_json::json!({
+            "validation_id": validation_id,
+            "workflow_id": workflow_id,
+            "validation_type": validation_type.to_string(),
+            "operation": description,
+            "risk_level": risk_level.to_string(),
+            "details": details
+        });
+        self.emit_validation_required(validation_id, &event);
 
-        // Emit validation_required event to frontend
-        if let Some(ref app_handle) = self.app_handle {
-            // Create a generic validation event
-            let event = serde_json::json!({
-                "validation_id": validation_id,
-                "workflow_id": workflow_id,
-                "validation_type": validation_type.to_string(),
-                "operation": description,
-                "risk_level": risk_level.to_string(),
-                "details": details
-            });
-
-            if let Err(e) = app_handle.emit(events::VALIDATION_REQUIRED, &event) {
-                warn!(error = %e, "Failed to emit validation_required event");
-            } else {
-                debug!(validation_id = %validation_id, "Emitted validation_required event");
-            }
-        } else {
-            warn!("No app handle available, skipping event emission");
-        }
+        let settings = self.load_validation_settings().await;
+        self.forward_to_channels(
+            &settings,
+            ValidationNotification {
+                validation_id: validation_id.to_string(),
+                workflow_id: workflow_id.to_string(),
+                validation_type: validation_type.to_string(),
+                operation: description.to_string(),
+                risk_level,
+            },
+        )
+        .await;
 
         // Wait for validation response
-        let result = self
-            .wait_for_validation(validation_id, Duration::from_secs(VALIDATION_TIMEOUT_SECS))
-            .await;
+        let result = self.await_decision(&settings, validation_id, event).await;
+        inbox::resolve_validation(&self.db, validation_id).await;
 
         match result {
             Ok(true) => {
@@ -689,6 +1129,45 @@ mod tests {
         assert_eq!(details["tools"].as_array().unwrap().len(), 2);
     }
 
+    #[test]
+    fn test_sql_write_details() {
+        let details = ValidationHelper::sql_write_details(
+            "crm",
+            "DELETE FROM leads WHERE id = $1",
+            &[serde_json::json!(42)],
+        );
+        assert_eq!(details["database"], "crm");
+        assert_eq!(details["statement"], "DELETE FROM leads WHERE id = $1");
+        assert_eq!(details["params_preview"], "[42]");
+    }
+
+    #[test]
+    fn test_email_send_details() {
+        let account = EmailAccount {
+            name: "work".to_string(),
+            address: "me@example.com".to_string(),
+            display_name: None,
+            imap_host: "imap.example.com".to_string(),
+            imap_port: 993,
+            smtp_host: "smtp.example.com".to_string(),
+            smtp_port: 465,
+            username: "me@example.com".to_string(),
+            read_only: false,
+            created_at: None,
+        };
+        let draft = EmailDraft {
+            to: vec!["alice@example.com".to_string()],
+            cc: Vec::new(),
+            subject: "Report".to_string(),
+            body: "B".repeat(3000),
+            in_reply_to: None,
+        };
+        let details = ValidationHelper::email_send_details(&account, &draft);
+        assert_eq!(details["from"], "me@example.com");
+        assert_eq!(details["to"], serde_json::json!(["alice@example.com"]));
+        assert!(details["body"].as_str().unwrap().ends_with("..."));
+    }
+
     #[test]
     fn test_spawn_details_long_prompt() {
         let long_prompt = "A".repeat(300);
@@ -0,0 +1,886 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! External notifications for pending validation requests.
+//!
+//! When `ValidationSettings::notifications` is enabled, every pending request is
+//! forwarded to the configured channels (webhook, ntfy, Gotify) with signed
+//! approve/reject links. The links point to a lightweight callback listener
+//! which applies the decision to the `validation_request` record; the waiting
+//! [`ValidationHelper`](super::validation_helper::ValidationHelper) picks it up
+//! on its next poll.
+//!
+//! # Links
+//!
+//! A link token is `{validation_id}.{action}.{expires_at}.{signature}`, the
+//! signature being an HMAC-SHA256 of the first three parts keyed with a secret
+//! generated at startup (links do not survive a restart). Opening a link shows
+//! a confirmation page: the decision is only applied on `POST`, so link
+//! previews cannot answer a request. A decision is applied only while the
+//! request is pending, which makes each link single-use.
+
+use crate::db::DBClient;
+use crate::models::audit::{AuditAction, AuditLogCreate};
+use crate::models::streaming::events;
+use crate::models::workflow_snapshot::escape_html;
+use crate::models::{
+    NotificationChannel, NotificationChannelType, RiskLevel, ValidationNotificationConfig,
+};
+use crate::security::audit;
+use crate::tools::constants::validation_notification::{
+    MAX_OPERATION_CHARS, MAX_REQUEST_HEAD_BYTES, REQUEST_READ_TIMEOUT_SECS, SEND_TIMEOUT_SECS,
+};
+use crate::tools::validation_helper::safe_truncate;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Path prefix of the links served by the callback listener
+const LINK_PATH: &str = "/validation/";
+
+/// Secret signing the links of this process
+static SIGNING_KEY: Lazy<Vec<u8>> = Lazy::new(|| {
+    let mut key = Uuid::new_v4().as_bytes().to_vec();
+    key.extend_from_slice(Uuid::new_v4().as_bytes());
+    key
+});
+
+/// Running callback listener (at most one)
+static CALLBACK_LISTENER: Lazy<Mutex<Option<CallbackListener>>> = Lazy::new(|| Mutex::new(None));
+
+/// Callback listener state
+struct CallbackListener {
+    /// Requested bind address (port 0 = random)
+    requested: SocketAddr,
+    /// Base URL of the bound listener
+    base_url: String,
+    /// Token stopping the listener
+    cancel: CancellationToken,
+}
+
+/// Decision carried by a link
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkAction {
+    Approve,
+    Reject,
+}
+
+impl LinkAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            LinkAction::Approve => "approve",
+            LinkAction::Reject => "reject",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "approve" => Some(LinkAction::Approve),
+            "reject" => Some(LinkAction::Reject),
+            _ => None,
+        }
+    }
+}
+
+/// Pending validation request forwarded to the channels
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationNotification {
+    /// Validation request ID
+    pub validation_id: String,
+    /// Associated workflow ID
+    pub workflow_id: String,
+    /// Type of validation (`tool`, `sub_agent`, `mcp`, ...)
+    pub validation_type: String,
+    /// Operation description
+    pub operation: String,
+    /// Risk level of the operation
+    pub risk_level: RiskLevel,
+}
+
+/// Signed links answering one notification
+#[derive(Debug, Clone)]
+struct NotificationLinks {
+    approve_url: String,
+    reject_url: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Verified content of a link token
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LinkClaim {
+    validation_id: String,
+    action: LinkAction,
+}
+
+/// Signs a link token for a validation request decision.
+fn sign_link_token(key: &[u8], validation_id: &str, action: LinkAction, expires_at: i64) -> String {
+    let payload = format!("{}.{}.{}", validation_id, action.as_str(), expires_at);
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+    format!("{}.{}", payload, signature)
+}
+
+/// Verifies the signature and expiry of a link token.
+fn verify_link_token(key: &[u8], token: &str, now: i64) -> Result<LinkClaim, &'static str> {
+    let (payload, signature) = token.rsplit_once('.').ok_or("malformed token")?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| "malformed signature")?;
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature)
+        .map_err(|_| "invalid signature")?;
+
+    let mut parts = payload.split('.');
+    let (Some(validation_id), Some(action), Some(expires_at), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("malformed token");
+    };
+    let action = LinkAction::parse(action).ok_or("unknown action")?;
+    let expires_at: i64 = expires_at.parse().map_err(|_| "malformed expiry")?;
+    if now > expires_at {
+        return Err("link expired");
+    }
+
+    Ok(LinkClaim {
+        validation_id: validation_id.to_string(),
+        action,
+    })
+}
+
+/// Validates notification settings before they are saved.
+pub fn validate_notification_config(config: &ValidationNotificationConfig) -> Result<(), String> {
+    config
+        .callback_bind_address
+        .parse::<IpAddr>()
+        .map_err(|_| {
+            format!(
+                "Invalid callback bind address '{}'",
+                config.callback_bind_address
+            )
+        })?;
+
+    if let Some(ref base) = config.public_base_url {
+        if !is_http_url(base) {
+            return Err("Public base URL must start with http:// or https://".to_string());
+        }
+    }
+
+    for channel in &config.channels {
+        if !is_http_url(&channel.url) {
+            return Err(format!(
+                "Invalid {} channel URL '{}': must start with http:// or https://",
+                channel.channel_type, channel.url
+            ));
+        }
+        if channel.channel_type == NotificationChannelType::Gotify
+            && channel.token.as_deref().is_none_or(|t| t.trim().is_empty())
+        {
+            return Err("Gotify channels require an application token".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn is_http_url(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|u| matches!(u.scheme(), "http" | "https"))
+}
+
+/// Forwards a pending validation request to the enabled channels.
+///
+/// Starts the callback listener if needed. Delivery runs in the background
+/// and failures are only logged: the desktop validation flow is unaffected.
+/// The links expire at `expires_at`, when the request stops waiting for a
+/// decision.
+pub async fn notify_validation_required(
+    db: Arc<DBClient>,
+    app_handle: Option<AppHandle>,
+    config: &ValidationNotificationConfig,
+    notification: ValidationNotification,
+    expires_at: DateTime<Utc>,
+) {
+    let channels: Vec<NotificationChannel> = config
+        .channels
+        .iter()
+        .filter(|c| c.enabled)
+        .cloned()
+        .collect();
+    if !config.enabled || channels.is_empty() {
+        return;
+    }
+
+    let base_url = match ensure_callback_listener(db, app_handle, config).await {
+        Ok(base_url) => base_url,
+        Err(e) => {
+            warn!(error = %e, "Failed to start validation callback listener");
+            return;
+        }
+    };
+
+    let link = |action| {
+        format!(
+            "{}{}{}",
+            base_url,
+            LINK_PATH,
+            sign_link_token(
+                &SIGNING_KEY,
+                &notification.validation_id,
+                action,
+                expires_at.timestamp()
+            )
+        )
+    };
+    let links = NotificationLinks {
+        approve_url: link(LinkAction::Approve),
+        reject_url: link(LinkAction::Reject),
+        expires_at,
+    };
+
+    let client = match reqwest::Client::builder()
+        .timeout(Duration::from_secs(SEND_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            warn!(error = %e, "Failed to build notification HTTP client");
+            return;
+        }
+    };
+
+    let notification = Arc::new(notification);
+    let links = Arc::new(links);
+    for channel in channels {
+        let client = client.clone();
+        let notification = notification.clone();
+        let links = links.clone();
+        tokio::spawn(async move {
+            match send_to_channel(&client, &channel, &notification, &links).await {
+                Ok(()) => debug!(
+                    channel = %channel.channel_type,
+                    validation_id = %notification.validation_id,
+                    "Validation notification sent"
+                ),
+                Err(e) => warn!(
+                    channel = %channel.channel_type,
+                    validation_id = %notification.validation_id,
+                    error = %e,
+                    "Failed to send validation notification"
+                ),
+            }
+        });
+    }
+}
+
+/// Sends one notification to a channel.
+async fn send_to_channel(
+    client: &reqwest::Client,
+    channel: &NotificationChannel,
+    notification: &ValidationNotification,
+    links: &NotificationLinks,
+) -> Result<(), String> {
+    let operation = safe_truncate(&notification.operation, MAX_OPERATION_CHARS, true);
+    let title = format!("Validation required ({} risk)", notification.risk_level);
+
+    let request = match channel.channel_type {
+        NotificationChannelType::Webhook => client.post(&channel.url).json(&serde_json::json!({
+            "event": events::VALIDATION_REQUIRED,
+            "validation_id": notification.validation_id,
+            "workflow_id": notification.workflow_id,
+            "validation_type": notification.validation_type,
+            "operation": operation,
+            "risk_level": notification.risk_level,
+            "approve_url": links.approve_url,
+            "reject_url": links.reject_url,
+            "expires_at": links.expires_at.to_rfc3339(),
+        })),
+        NotificationChannelType::Ntfy => {
+            let priority = match notification.risk_level {
+                RiskLevel::High => "high",
+                RiskLevel::Medium => "default",
+                RiskLevel::Low => "low",
+            };
+            client
+                .post(&channel.url)
+                .header("Title", title)
+                .header("Priority", priority)
+                .header("Tags", "warning")
+                .header(
+                    "Actions",
+                    format!(
+                        "http, Approve, {}, method=POST, clear=true; \
+                         http, Reject, {}, method=POST, clear=true",
+                        links.approve_url, links.reject_url
+                    ),
+                )
+                .body(operation)
+        }
+        NotificationChannelType::Gotify => {
+            let priority = match notification.risk_level {
+                RiskLevel::High => 8,
+                RiskLevel::Medium => 5,
+                RiskLevel::Low => 2,
+            };
+            client
+                .post(format!("{}/message", channel.url.trim_end_matches('/')))
+                .json(&serde_json::json!({
+                    "title": title,
+                    "message": format!(
+                        "{}\n\n[Approve]({}) · [Reject]({})",
+                        operation, links.approve_url, links.reject_url
+                    ),
+                    "priority": priority,
+                    "extras": { "client::display": { "contentType": "text/markdown" } }
+                }))
+        }
+    };
+
+    let request = match (&channel.channel_type, channel.token.as_deref()) {
+        (NotificationChannelType::Gotify, Some(token)) => request.header("X-Gotify-Key", token),
+        (_, Some(token)) if !token.is_empty() => request.bearer_auth(token),
+        _ => request,
+    };
+
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Starts the callback listener, or reuses it when the bind address is unchanged.
+///
+/// # Returns
+/// The base URL used in links
+async fn ensure_callback_listener(
+    db: Arc<DBClient>,
+    app_handle: Option<AppHandle>,
+    config: &ValidationNotificationConfig,
+) -> std::io::Result<String> {
+    let ip: IpAddr = config
+        .callback_bind_address
+        .parse()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let requested = SocketAddr::new(ip, config.callback_port);
+
+    let mut listener_slot = CALLBACK_LISTENER.lock().await;
+    let base_url = match listener_slot.as_ref() {
+        Some(running) if running.requested == requested && !running.cancel.is_cancelled() => {
+            running.base_url.clone()
+        }
+        _ => {
+            if let Some(previous) = listener_slot.take() {
+                previous.cancel.cancel();
+            }
+            let listener = TcpListener::bind(requested).await?;
+            let base_url = format!("http://{}", listener.local_addr()?);
+            let cancel = CancellationToken::new();
+            tokio::spawn(run_callback_listener(
+                listener,
+                db,
+                app_handle,
+                cancel.clone(),
+            ));
+            info!(base_url = %base_url, "Validation callback listener started");
+            *listener_slot = Some(CallbackListener {
+                requested,
+                base_url: base_url.clone(),
+                cancel,
+            });
+            base_url
+        }
+    };
+
+    Ok(config
+        .public_base_url
+        .as_deref()
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or(base_url))
+}
+
+/// Stops the callback listener.
+///
+/// # Returns
+/// `true` if a listener was running
+pub async fn stop_callback_listener() -> bool {
+    match CALLBACK_LISTENER.lock().await.take() {
+        Some(listener) => {
+            listener.cancel.cancel();
+            info!("Validation callback listener stopped");
+            true
+        }
+        None => false,
+    }
+}
+
+async fn run_callback_listener(
+    listener: TcpListener,
+    db: Arc<DBClient>,
+    app_handle: Option<AppHandle>,
+    cancel: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(handle_callback_request(stream, db.clone(), app_handle.clone()));
+                }
+                Err(e) => warn!(error = %e, "Validation callback listener accept failed"),
+            },
+        }
+    }
+}
+
+/// Answers a single request on the callback listener.
+async fn handle_callback_request(
+    mut stream: TcpStream,
+    db: Arc<DBClient>,
+    app_handle: Option<AppHandle>,
+) {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    let read = tokio::time::timeout(Duration::from_secs(REQUEST_READ_TIMEOUT_SECS), async {
+        while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+            if head.len() > MAX_REQUEST_HEAD_BYTES {
+                return false;
+            }
+            match stream.read(&mut buf).await {
+                Ok(0) | Err(_) => return false,
+                Ok(n) => head.extend_from_slice(&buf[..n]),
+            }
+        }
+        true
+    })
+    .await;
+    if !matches!(read, Ok(true)) {
+        return;
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+    let token = target
+        .strip_prefix(LINK_PATH)
+        .map(|rest| rest.split('?').next().unwrap_or_default());
+
+    let (status, body) = match (method, token) {
+        ("GET" | "HEAD" | "POST", None) => ("404 Not Found", render_page("Not found", None)),
+        ("GET" | "HEAD" | "POST", Some(token)) => {
+            match verify_link_token(&SIGNING_KEY, token, Utc::now().timestamp()) {
+                Err(reason) => {
+                    debug!(reason, "Rejected validation link");
+                    (
+                        "403 Forbidden",
+                        render_page("This link is invalid or has expired.", None),
+                    )
+                }
+                Ok(claim) if method == "POST" => {
+                    answer_validation(&db, app_handle.as_ref(), &claim).await
+                }
+                Ok(claim) => {
+                    let question = match claim.action {
+                        LinkAction::Approve => "Approve this validation request?",
+                        LinkAction::Reject => "Reject this validation request?",
+                    };
+                    ("200 OK", render_page(question, Some(claim.action)))
+                }
+            }
+        }
+        _ => (
+            "405 Method Not Allowed",
+            render_page("Method not allowed", None),
+        ),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\n\
+         Cache-Control: no-store\r\nX-Content-Type-Options: nosniff\r\n\
+         Referrer-Policy: no-referrer\r\nConnection: close\r\n\r\n",
+        status,
+        body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        debug!(error = %e, "Failed to write validation callback response");
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// Applies the decision of a verified link and notifies the frontend.
+async fn answer_validation(
+    db: &DBClient,
+    app_handle: Option<&AppHandle>,
+    claim: &LinkClaim,
+) -> (&'static str, String) {
+    match apply_link_decision(db, claim).await {
+        Ok(true) => {
+            let approved = claim.action == LinkAction::Approve;
+            audit::record(
+                db,
+                AuditLogCreate {
+                    details: serde_json::json!({
+                        "status": if approved { "approved" } else { "rejected" }
+                    }),
+                    ..AuditLogCreate::new(
+                        AuditAction::ValidationDecision,
+                        "notification_link",
+                        &claim.validation_id,
+                    )
+                },
+            )
+            .await;
+            info!(
+                validation_id = %claim.validation_id,
+                approved,
+                "Validation answered from notification link"
+            );
+            if let Some(app_handle) = app_handle {
+                let event = serde_json::json!({
+                    "validation_id": claim.validation_id,
+                    "approved": approved
+                });
+                if let Err(e) = app_handle.emit(events::VALIDATION_RESPONSE, &event) {
+                    warn!(error = %e, "Failed to emit validation_response event");
+                }
+            }
+            let message = if approved {
+                "Request approved."
+            } else {
+                "Request rejected."
+            };
+            ("200 OK", render_page(message, None))
+        }
+        Ok(false) => (
+            "409 Conflict",
+            render_page("This request was already answered.", None),
+        ),
+        Err(e) => {
+            warn!(error = %e, "Failed to apply validation link decision");
+            (
+                "500 Internal Server Error",
+                render_page("The decision could not be saved.", None),
+            )
+        }
+    }
+}
+
+/// Sets the status of a pending validation request.
+///
+/// # Returns
+/// `false` if the request is no longer pending (or does not exist)
+async fn apply_link_decision(db: &DBClient, claim: &LinkClaim) -> anyhow::Result<bool> {
+    let id = crate::security::Validator::validate_uuid(&claim.validation_id)?;
+    let update = match claim.action {
+        LinkAction::Approve => "status = 'approved'",
+        LinkAction::Reject => {
+            "status = 'rejected', details.rejection_reason = 'Rejected from notification link'"
+        }
+    };
+    let updated = db
+        .query_json_with_params(
+            &format!(
+                "UPDATE type::thing('validation_request', $id) SET {} \
+                 WHERE status = 'pending' RETURN status",
+                update
+            ),
+            vec![("id".to_string(), serde_json::json!(id))],
+        )
+        .await?;
+    Ok(!updated.is_empty())
+}
+
+/// Renders a minimal page, with a confirmation form when an action is given.
+fn render_page(message: &str, action: Option<LinkAction>) -> String {
+    let form = match action {
+        Some(action) => {
+            let label = match action {
+                LinkAction::Approve => "Approve",
+                LinkAction::Reject => "Reject",
+            };
+            format!(
+                "<form method=\"post\"><button type=\"submit\">{}</button></form>",
+                label
+            )
+        }
+        None => String::new(),
+    };
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+         <meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'; \
+         style-src 'unsafe-inline'; form-action 'self'\">\
+         <title>Zileo Chat validation</title>\
+         <style>body{{font-family:system-ui,sans-serif;max-width:32rem;margin:3rem auto;\
+         padding:0 1rem}}button{{font-size:1rem;padding:.5rem 1.5rem}}</style></head>\
+         <body><h1>Zileo Chat</h1><p>{}</p>{}</body></html>",
+        escape_html(message),
+        form
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::test_server::{http, spawn_mock_server, MockRequest};
+    use crate::models::{ValidationRequestCreate, ValidationStatus, ValidationType};
+    use std::sync::Mutex as StdMutex;
+    use tempfile::tempdir;
+
+    const KEY: &[u8] = b"test-key";
+    const ID: &str = "550e8400-e29b-41d4-a716-446655440000";
+
+    #[test]
+    fn test_link_token_roundtrip() {
+        let token = sign_link_token(KEY, ID, LinkAction::Reject, 1_000);
+        let claim = verify_link_token(KEY, &token, 999).unwrap();
+        assert_eq!(claim.validation_id, ID);
+        assert_eq!(claim.action, LinkAction::Reject);
+
+        assert_eq!(verify_link_token(KEY, &token, 1_001), Err("link expired"));
+        assert_eq!(
+            verify_link_token(b"other-key", &token, 999),
+            Err("invalid signature")
+        );
+
+        // Changing the action invalidates the signature
+        let tampered = token.replacen("reject", "approve", 1);
+        assert_eq!(
+            verify_link_token(KEY, &tampered, 999),
+            Err("invalid signature")
+        );
+        assert!(verify_link_token(KEY, "garbage", 999).is_err());
+    }
+
+    #[test]
+    fn test_validate_notification_config() {
+        let channel = |channel_type, url: &str, token: Option<&str>| NotificationChannel {
+            channel_type,
+            url: url.to_string(),
+            token: token.map(String::from),
+            enabled: true,
+        };
+        let mut config = ValidationNotificationConfig {
+            enabled: true,
+            channels: vec![
+                channel(
+                    NotificationChannelType::Webhook,
+                    "https://hooks.example.com/x",
+                    None,
+                ),
+                channel(
+                    NotificationChannelType::Ntfy,
+                    "https://ntfy.sh/approvals",
+                    None,
+                ),
+                channel(
+                    NotificationChannelType::Gotify,
+                    "https://gotify.local",
+                    Some("t"),
+                ),
+            ],
+            ..Default::default()
+        };
+        assert!(validate_notification_config(&config).is_ok());
+
+        config.channels[2].token = None;
+        assert!(validate_notification_config(&config).is_err());
+        config.channels[2].token = Some("t".to_string());
+
+        config.channels[0].url = "ftp://example.com".to_string();
+        assert!(validate_notification_config(&config).is_err());
+        config.channels[0].url = "https://hooks.example.com/x".to_string();
+
+        config.callback_bind_address = "localhost".to_string();
+        assert!(validate_notification_config(&config).is_err());
+        config.callback_bind_address = "0.0.0.0".to_string();
+        config.public_base_url = Some("approvals.example.com".to_string());
+        assert!(validate_notification_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_to_channels() {
+        let received: Arc<StdMutex<Vec<MockRequest>>> = Arc::new(StdMutex::new(Vec::new()));
+        let sink = received.clone();
+        let base = spawn_mock_server(move |req| {
+            sink.lock().unwrap().push(req);
+            http("200 OK", &[], "")
+        })
+        .await;
+
+        let client = reqwest::Client::new();
+        let notification = ValidationNotification {
+            validation_id: ID.to_string(),
+            workflow_id: "wf1".to_string(),
+            validation_type: "mcp".to_string(),
+            operation: "Call search".to_string(),
+            risk_level: RiskLevel::High,
+        };
+        let links = NotificationLinks {
+            approve_url: "http://127.0.0.1:1/validation/a".to_string(),
+            reject_url: "http://127.0.0.1:1/validation/r".to_string(),
+            expires_at: Utc::now(),
+        };
+        let channel = |channel_type, path: &str, token: Option<&str>| NotificationChannel {
+            channel_type,
+            url: format!("{}{}", base, path),
+            token: token.map(String::from),
+            enabled: true,
+        };
+
+        for channel in [
+            channel(NotificationChannelType::Webhook, "/hook", Some("secret")),
+            channel(NotificationChannelType::Ntfy, "/approvals", None),
+            channel(NotificationChannelType::Gotify, "/", Some("app-token")),
+        ] {
+            send_to_channel(&client, &channel, &notification, &links)
+                .await
+                .unwrap();
+        }
+
+        let received = received.lock().unwrap();
+        let webhook = &received[0];
+        assert_eq!(webhook.path, "/hook");
+        assert_eq!(webhook.header("authorization"), Some("Bearer secret"));
+        assert_eq!(webhook.body["validation_id"], ID);
+        assert_eq!(webhook.body["risk_level"], "high");
+        assert_eq!(
+            webhook.body["approve_url"],
+            "http://127.0.0.1:1/validation/a"
+        );
+
+        let ntfy = &received[1];
+        assert_eq!(ntfy.path, "/approvals");
+        assert_eq!(ntfy.raw_body, "Call search");
+        assert_eq!(ntfy.header("priority"), Some("high"));
+        assert!(ntfy
+            .header("actions")
+            .unwrap()
+            .contains("http, Reject, http://127.0.0.1:1/validation/r, method=POST"));
+
+        let gotify = &received[2];
+        assert_eq!(gotify.path, "/message");
+        assert_eq!(gotify.header("x-gotify-key"), Some("app-token"));
+        assert_eq!(gotify.body["priority"], 8);
+        assert!(gotify.body["message"]
+            .as_str()
+            .unwrap()
+            .contains("[Approve](http://127.0.0.1:1/validation/a)"));
+    }
+
+    #[tokio::test]
+    async fn test_callback_listener_answers_once() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("notify_db");
+        let db = Arc::new(DBClient::new(db_path.to_str().unwrap()).await.unwrap());
+        db.initialize_schema().await.unwrap();
+        db.create(
+            "validation_request",
+            ID,
+            ValidationRequestCreate::new(
+                "wf1".to_string(),
+                ValidationType::Mcp,
+                "Call search".to_string(),
+                serde_json::json!({}),
+                RiskLevel::Medium,
+                ValidationStatus::Pending,
+            ),
+        )
+        .await
+        .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let cancel = CancellationToken::new();
+        tokio::spawn(run_callback_listener(
+            listener,
+            db.clone(),
+            None,
+            cancel.clone(),
+        ));
+
+        let expires_at = Utc::now().timestamp() + 60;
+        let url = |action| {
+            format!(
+                "{}{}{}",
+                base,
+                LINK_PATH,
+                sign_link_token(&SIGNING_KEY, ID, action, expires_at)
+            )
+        };
+        let client = reqwest::Client::new();
+
+        // Opening the link only shows the confirmation form
+        let response = client.get(url(LinkAction::Approve)).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response
+            .text()
+            .await
+            .unwrap()
+            .contains("<form method=\"post\">"));
+        let status: Vec<serde_json::Value> = db
+            .query_json_with_params(
+                "SELECT status FROM type::thing('validation_request', $id)",
+                vec![("id".to_string(), serde_json::json!(ID))],
+            )
+            .await
+            .unwrap();
+        assert_eq!(status[0]["status"], "pending");
+
+        let response = client.post(url(LinkAction::Approve)).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        let status: Vec<serde_json::Value> = db
+            .query_json_with_params(
+                "SELECT status FROM type::thing('validation_request', $id)",
+                vec![("id".to_string(), serde_json::json!(ID))],
+            )
+            .await
+            .unwrap();
+        assert_eq!(status[0]["status"], "approved");
+
+        // The request is answered: the other link no longer applies
+        let response = client.post(url(LinkAction::Reject)).send().await.unwrap();
+        assert_eq!(response.status(), 409);
+
+        let forged = format!("{}{}{}.approve.{}.AAAA", base, LINK_PATH, ID, expires_at);
+        let response = client.post(forged).send().await.unwrap();
+        assert_eq!(response.status(), 403);
+
+        let response = client.get(format!("{}/other", base)).send().await.unwrap();
+        assert_eq!(response.status(), 404);
+
+        cancel.cancel();
+    }
+}
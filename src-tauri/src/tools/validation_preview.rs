@@ -0,0 +1,552 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Change previews for validation requests.
+//!
+//! Before a tool call that modifies data is submitted to the user, the
+//! [`ValidationHelper`](super::validation_helper::ValidationHelper) attaches a
+//! [`ChangePreview`] to the request details so it can be approved with full
+//! context rather than from its raw arguments:
+//!
+//! - `MemoryTool` `delete` / `clear_by_type`: the memories that will be deleted
+//! - `TodoTool` `update_status` / `complete`: the status before and after;
+//!   `delete`: the task that will be deleted
+//! - MCP file writes (`write_file`, `edit_file`...): a line diff between the
+//!   current file and the written content
+//! - MCP SQL tools: the statement, as affected rows are only known once it runs
+//!
+//! A preview is best effort: when it cannot be computed (unknown record,
+//! relative path, unreadable file) the request is sent without one.
+
+use crate::db::DBClient;
+use crate::models::tool_acknowledgment::{classify_powerful_tool, PowerfulToolKind};
+use crate::models::tool_effects::STATEMENT_ARGS;
+use crate::models::validation::{ChangePreview, FieldChange, PreviewRow};
+use crate::tools::constants::validation_preview::{
+    DIFF_CONTEXT_LINES, MAX_DIFF_CELLS, MAX_DIFF_LINES, MAX_FILE_BYTES, MAX_PREVIEW_ROWS,
+    MAX_STATEMENT_CHARS, MAX_SUMMARY_CHARS,
+};
+use crate::tools::validation_helper::safe_truncate;
+use serde_json::{json, Value};
+use std::path::Path;
+use tracing::debug;
+
+/// Argument names holding the path of a written file
+const PATH_ARGS: &[&str] = &["path", "file_path", "filepath", "filename"];
+/// Argument names holding the content of a written file
+const CONTENT_ARGS: &[&str] = &["content", "contents", "text", "data"];
+
+/// Builds the preview of a tool call, if it modifies data in a previewable way.
+///
+/// # Arguments
+/// * `db` - Database client (local tool records)
+/// * `workflow_id` - Workflow of the call (default scope of memory operations)
+/// * `tool_name` - Local tool name, or MCP tool name without server prefix
+/// * `is_mcp` - Whether the tool is served by an MCP server
+/// * `arguments` - Tool call arguments
+pub async fn build_preview(
+    db: &DBClient,
+    workflow_id: &str,
+    tool_name: &str,
+    is_mcp: bool,
+    arguments: &Value,
+) -> Option<ChangePreview> {
+    let result =
+        if is_mcp {
+            match classify_powerful_tool(tool_name) {
+                Some(PowerfulToolKind::FileSystemWrite) => Ok(file_write_preview(arguments)),
+                Some(PowerfulToolKind::ExternalSql) => Ok(string_arg(arguments, STATEMENT_ARGS)
+                    .map(|statement| ChangePreview::Statement {
+                        statement: safe_truncate(statement, MAX_STATEMENT_CHARS, true),
+                    })),
+                _ => Ok(None),
+            }
+        } else {
+            let operation = arguments["operation"].as_str().unwrap_or_default();
+            match (tool_name, operation) {
+                ("MemoryTool", "delete" | "clear_by_type") => {
+                    memory_preview(db, workflow_id, operation, arguments).await
+                }
+                ("TodoTool", "update_status" | "complete" | "delete") => {
+                    task_preview(db, operation, arguments).await
+                }
+                _ => Ok(None),
+            }
+        };
+
+    result.unwrap_or_else(|e| {
+        debug!(tool_name = %tool_name, error = %e, "Change preview unavailable");
+        None
+    })
+}
+
+/// Returns the first string argument among `names`.
+pub(crate) fn string_arg<'a>(arguments: &'a Value, names: &[&str]) -> Option<&'a str> {
+    names.iter().find_map(|name| arguments[*name].as_str())
+}
+
+/// Lists the memories deleted by `delete` or `clear_by_type`.
+async fn memory_preview(
+    db: &DBClient,
+    workflow_id: &str,
+    operation: &str,
+    arguments: &Value,
+) -> anyhow::Result<Option<ChangePreview>> {
+    let (filter, params) = if operation == "delete" {
+        let Some(memory_id) = arguments["memory_id"].as_str() else {
+            return Ok(None);
+        };
+        (
+            "meta::id(id) = $memory_id",
+            vec![("memory_id".to_string(), json!(memory_id))],
+        )
+    } else {
+        let Some(memory_type) = arguments["type"].as_str() else {
+            return Ok(None);
+        };
+        // Same scope as MemoryTool::clear_by_type
+        let scope = arguments["workflow_id"].as_str().unwrap_or(workflow_id);
+        (
+            "type = $memory_type AND workflow_id = $workflow_id",
+            vec![
+                ("memory_type".to_string(), json!(memory_type)),
+                ("workflow_id".to_string(), json!(scope)),
+            ],
+        )
+    };
+
+    let total = db
+        .query_json_with_params(
+            &format!(
+                "SELECT count() AS total FROM memory WHERE {} GROUP ALL",
+                filter
+            ),
+            params.clone(),
+        )
+        .await?
+        .first()
+        .and_then(|row| row["total"].as_u64())
+        .unwrap_or(0);
+    let rows = db
+        .query_json_with_params(
+            &format!(
+                "SELECT meta::id(id) AS id, type, content, created_at FROM memory \
+                 WHERE {} ORDER BY created_at DESC LIMIT {}",
+                filter, MAX_PREVIEW_ROWS
+            ),
+            params,
+        )
+        .await?
+        .iter()
+        .map(|row| PreviewRow {
+            id: row["id"].as_str().unwrap_or_default().to_string(),
+            summary: format!(
+                "[{}] {}",
+                row["type"].as_str().unwrap_or_default(),
+                safe_truncate(
+                    row["content"].as_str().unwrap_or_default(),
+                    MAX_SUMMARY_CHARS,
+                    true
+                )
+            ),
+        })
+        .collect();
+
+    Ok(Some(ChangePreview::Rows {
+        table: "memory".to_string(),
+        total,
+        rows,
+    }))
+}
+
+/// Shows the status change or the deletion of a task.
+async fn task_preview(
+    db: &DBClient,
+    operation: &str,
+    arguments: &Value,
+) -> anyhow::Result<Option<ChangePreview>> {
+    let Some(task_id) = arguments["task_id"].as_str() else {
+        return Ok(None);
+    };
+    let Some(task) = db
+        .query_json_with_params(
+            "SELECT meta::id(id) AS id, name, status FROM task WHERE meta::id(id) = $task_id",
+            vec![("task_id".to_string(), json!(task_id))],
+        )
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+    let name = task["name"].as_str().unwrap_or_default().to_string();
+
+    let new_status = match operation {
+        "update_status" => arguments["status"].clone(),
+        "complete" => json!("completed"),
+        _ => {
+            return Ok(Some(ChangePreview::Rows {
+                table: "task".to_string(),
+                total: 1,
+                rows: vec![PreviewRow {
+                    id: task_id.to_string(),
+                    summary: format!("{} ({})", name, task["status"].as_str().unwrap_or_default()),
+                }],
+            }))
+        }
+    };
+
+    Ok(Some(ChangePreview::Change {
+        table: "task".to_string(),
+        id: task_id.to_string(),
+        label: name,
+        fields: vec![FieldChange {
+            field: "status".to_string(),
+            before: task["status"].clone(),
+            after: new_status,
+        }],
+    }))
+}
+
+/// Diffs the content an MCP tool will write against the current file.
+///
+/// Supports full writes (`content` argument) and search/replace edits
+/// (`edits: [{ oldText, newText }]`). The path must be absolute: relative
+/// paths are resolved by the MCP server.
+fn file_write_preview(arguments: &Value) -> Option<ChangePreview> {
+    let path = string_arg(arguments, PATH_ARGS)?;
+    if !Path::new(path).is_absolute() {
+        return None;
+    }
+
+    let current = match std::fs::metadata(path) {
+        Ok(meta) if meta.len() > MAX_FILE_BYTES => return None,
+        Ok(_) => Some(std::fs::read_to_string(path).ok()?),
+        Err(_) => None,
+    };
+
+    let written = match string_arg(arguments, CONTENT_ARGS) {
+        Some(content) => content.to_string(),
+        None => apply_edits(current.as_deref()?, arguments["edits"].as_array()?)?,
+    };
+
+    let diff = line_diff(current.as_deref().unwrap_or_default(), &written);
+    Some(ChangePreview::Diff {
+        path: path.to_string(),
+        new_file: current.is_none(),
+        additions: diff.additions,
+        deletions: diff.deletions,
+        diff: diff.text,
+        truncated: diff.truncated,
+    })
+}
+
+/// Applies search/replace edits, `None` when an edit does not match.
+fn apply_edits(content: &str, edits: &[Value]) -> Option<String> {
+    edits.iter().try_fold(content.to_string(), |text, edit| {
+        let old = edit["oldText"].as_str().or(edit["old_text"].as_str())?;
+        let new = edit["newText"].as_str().or(edit["new_text"].as_str())?;
+        text.contains(old).then(|| text.replacen(old, new, 1))
+    })
+}
+
+/// Line diff in unified format
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct LineDiff {
+    /// Hunks (`@@ -a,b +c,d @@` headers, ` `/`-`/`+` prefixed lines)
+    pub text: String,
+    /// Number of added lines
+    pub additions: usize,
+    /// Number of deleted lines
+    pub deletions: usize,
+    /// Whether the hunks were cut to `MAX_DIFF_LINES`
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Delete(&'a str),
+    Insert(&'a str),
+}
+
+/// Computes the line diff between two texts.
+pub(crate) fn line_diff(before: &str, after: &str) -> LineDiff {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let ops = diff_ops(&old, &new);
+
+    let mut diff = LineDiff {
+        additions: ops
+            .iter()
+            .filter(|op| matches!(op, DiffOp::Insert(_)))
+            .count(),
+        deletions: ops
+            .iter()
+            .filter(|op| matches!(op, DiffOp::Delete(_)))
+            .count(),
+        ..Default::default()
+    };
+    let mut lines = render_hunks(&ops);
+    if lines.len() > MAX_DIFF_LINES {
+        lines.truncate(MAX_DIFF_LINES);
+        diff.truncated = true;
+    }
+    diff.text = lines.join("\n");
+    diff
+}
+
+/// Edit script between two line lists (longest common subsequence).
+///
+/// The common prefix and suffix are skipped; when the remaining table would
+/// exceed `MAX_DIFF_CELLS`, the middle is shown as deleted then inserted.
+fn diff_ops<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+
+    let mut ops: Vec<DiffOp> = old[..prefix].iter().map(|l| DiffOp::Equal(l)).collect();
+    if a.len().saturating_mul(b.len()) > MAX_DIFF_CELLS {
+        ops.extend(a.iter().map(|l| DiffOp::Delete(l)));
+        ops.extend(b.iter().map(|l| DiffOp::Insert(l)));
+    } else {
+        let (n, m) = (a.len(), b.len());
+        let at = |i: usize, j: usize| i * (m + 1) + j;
+        // lcs[at(i, j)]: length of the LCS of a[i..] and b[j..]
+        let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[at(i, j)] = if a[i] == b[j] {
+                    lcs[at(i + 1, j + 1)] + 1
+                } else {
+                    lcs[at(i + 1, j)].max(lcs[at(i, j + 1)])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n || j < m {
+            if i < n && j < m && a[i] == b[j] {
+                ops.push(DiffOp::Equal(a[i]));
+                i += 1;
+                j += 1;
+            } else if i < n && (j == m || lcs[at(i + 1, j)] >= lcs[at(i, j + 1)]) {
+                ops.push(DiffOp::Delete(a[i]));
+                i += 1;
+            } else {
+                ops.push(DiffOp::Insert(b[j]));
+                j += 1;
+            }
+        }
+    }
+    ops.extend(old[old.len() - suffix..].iter().map(|l| DiffOp::Equal(l)));
+    ops
+}
+
+/// Renders the changes with `DIFF_CONTEXT_LINES` of context as unified hunks.
+fn render_hunks(ops: &[DiffOp]) -> Vec<String> {
+    let mut shown = vec![false; ops.len()];
+    for (k, _) in ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, DiffOp::Equal(_)))
+    {
+        let end = (k + DIFF_CONTEXT_LINES).min(ops.len() - 1);
+        shown[k.saturating_sub(DIFF_CONTEXT_LINES)..=end].fill(true);
+    }
+
+    let mut lines = Vec::new();
+    let (mut old_line, mut new_line) = (1, 1);
+    let mut k = 0;
+    while k < ops.len() {
+        if !shown[k] {
+            old_line += 1;
+            new_line += 1;
+            k += 1;
+            continue;
+        }
+
+        let (old_start, new_start) = (old_line, new_line);
+        let mut body = Vec::new();
+        while k < ops.len() && shown[k] {
+            match ops[k] {
+                DiffOp::Equal(line) => {
+                    body.push(format!(" {}", line));
+                    old_line += 1;
+                    new_line += 1;
+                }
+                DiffOp::Delete(line) => {
+                    body.push(format!("-{}", line));
+                    old_line += 1;
+                }
+                DiffOp::Insert(line) => {
+                    body.push(format!("+{}", line));
+                    new_line += 1;
+                }
+            }
+            k += 1;
+        }
+
+        let (old_len, new_len) = (old_line - old_start, new_line - new_start);
+        // Unified format: an empty range starts at the line before it
+        let start = |start: usize, len: usize| if len == 0 { start - 1 } else { start };
+        lines.push(format!(
+            "@@ -{},{} +{},{} @@",
+            start(old_start, old_len),
+            old_len,
+            start(new_start, new_len),
+            new_len
+        ));
+        lines.extend(body);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_line_diff() {
+        let before = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let after = "a\nb\nc\nd\nE\nf\ng\nh\ni\nj\nk";
+        let diff = line_diff(before, after);
+        assert_eq!((diff.additions, diff.deletions), (2, 1));
+        assert_eq!(
+            diff.text,
+            "@@ -2,9 +2,10 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n i\n j\n+k"
+        );
+        assert!(!diff.truncated);
+
+        let created = line_diff("", "one\ntwo");
+        assert_eq!(created.text, "@@ -0,0 +1,2 @@\n+one\n+two");
+        assert_eq!(line_diff("same", "same"), LineDiff::default());
+    }
+
+    #[test]
+    fn test_file_write_preview() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("notes.txt");
+        std::fs::write(&path, "alpha\nbeta\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let edit = json!({ "path": path, "edits": [{ "oldText": "beta", "newText": "gamma" }] });
+        let Some(ChangePreview::Diff {
+            new_file,
+            diff,
+            additions,
+            deletions,
+            ..
+        }) = file_write_preview(&edit)
+        else {
+            panic!("expected a diff preview");
+        };
+        assert!(!new_file);
+        assert_eq!((additions, deletions), (1, 1));
+        assert_eq!(diff, "@@ -1,2 +1,2 @@\n alpha\n-beta\n+gamma");
+
+        let missing_edit =
+            json!({ "path": path, "edits": [{ "oldText": "delta", "newText": "" }] });
+        assert!(file_write_preview(&missing_edit).is_none());
+        assert!(file_write_preview(&json!({ "path": "notes.txt", "content": "x" })).is_none());
+
+        let created = dir.path().join("new.txt");
+        let write = json!({ "path": created.to_str().unwrap(), "content": "hello" });
+        assert!(matches!(
+            file_write_preview(&write),
+            Some(ChangePreview::Diff {
+                new_file: true,
+                additions: 1,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_build_preview_for_local_tools() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("preview_db");
+        let db = DBClient::new(db_path.to_str().unwrap()).await.unwrap();
+        db.initialize_schema().await.unwrap();
+
+        for (id, memory_type, workflow_id) in [
+            ("m1", "context", "wf1"),
+            ("m2", "context", "wf1"),
+            ("m3", "context", "wf2"),
+        ] {
+            db.create(
+                "memory",
+                id,
+                json!({
+                    "id": id,
+                    "type": memory_type,
+                    "content": format!("memory {}", id),
+                    "workflow_id": workflow_id,
+                    "metadata": {}
+                }),
+            )
+            .await
+            .unwrap();
+        }
+        db.create(
+            "task",
+            "t1",
+            json!({ "id": "t1", "workflow_id": "wf1", "name": "Ship", "description": "", "dependencies": [] }),
+        )
+        .await
+        .unwrap();
+
+        let clear = json!({ "operation": "clear_by_type", "type": "context" });
+        let Some(ChangePreview::Rows { total, rows, .. }) =
+            build_preview(&db, "wf1", "MemoryTool", false, &clear).await
+        else {
+            panic!("expected a rows preview");
+        };
+        assert_eq!(total, 2);
+        assert!(rows.iter().all(|r| r.id != "m3"));
+        assert!(rows[0].summary.starts_with("[context] memory"));
+
+        let complete = json!({ "operation": "complete", "task_id": "t1" });
+        assert_eq!(
+            build_preview(&db, "wf1", "TodoTool", false, &complete).await,
+            Some(ChangePreview::Change {
+                table: "task".to_string(),
+                id: "t1".to_string(),
+                label: "Ship".to_string(),
+                fields: vec![FieldChange {
+                    field: "status".to_string(),
+                    before: json!("pending"),
+                    after: json!("completed"),
+                }],
+            })
+        );
+
+        let list = json!({ "operation": "list" });
+        assert!(build_preview(&db, "wf1", "TodoTool", false, &list)
+            .await
+            .is_none());
+        let sql = json!({ "sql": "DELETE FROM users" });
+        assert!(matches!(
+            build_preview(&db, "wf1", "execute_sql", true, &sql).await,
+            Some(ChangePreview::Statement { .. })
+        ));
+    }
+}
@@ -0,0 +1,686 @@
+// Copyright 2025 Assistance Micro Design
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WorkflowTool - Saved workflows invoked as callable steps
+//!
+//! This tool lets the primary agent of a workflow run another saved workflow
+//! as one step of its own task, building higher-order automations from
+//! existing workflows.
+//!
+//! # Workflow Templates
+//!
+//! The first user message of a saved workflow is its template. `{{name}}`
+//! placeholders of the template are filled from the `arguments` of the call;
+//! arguments without a placeholder are appended as additional inputs.
+//!
+//! # Execution
+//!
+//! The template is executed by the agent of the invoked workflow as a
+//! sub-agent of the calling workflow (same events, execution records,
+//! validation and cancellation as `DelegateTaskTool`). The invoked workflow
+//! itself is not modified. Long results are summarized with the invoked
+//! agent's model before being returned to the caller.
+//!
+//! Invoked agents run as sub-agents: they cannot invoke workflows themselves,
+//! which bounds the composition depth to one level.
+
+use crate::agents::core::agent::Task;
+use crate::agents::core::{AgentOrchestrator, AgentRegistry};
+use crate::db::DBClient;
+use crate::llm::{ProviderManager, ProviderType};
+use crate::mcp::MCPManager;
+use crate::models::streaming::SubAgentOperationType;
+use crate::models::Prompt;
+use crate::security::WORKFLOW_VAULT;
+use crate::tools::constants::workflow_tool::{
+    MAX_LISTED_WORKFLOWS, MAX_RESULT_CHARS, MAX_RUNS_PER_TASK, SUMMARY_MAX_TOKENS,
+    SUMMARY_THRESHOLD_CHARS, TEMPLATE_PREVIEW_CHARS,
+};
+use crate::tools::context::AgentToolContext;
+use crate::tools::registry::{
+    ToolBuildContext, ToolCategory, ToolFuture, ToolMetadata, ToolRegistration,
+};
+use crate::tools::sub_agent_executor::SubAgentExecutor;
+use crate::tools::validation_helper::{safe_truncate, ValidationHelper};
+use crate::tools::{Tool, ToolDefinition, ToolError, ToolResult};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tauri::AppHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Saved workflow that can be invoked
+#[derive(Debug, Clone, Deserialize)]
+struct InvocableWorkflow {
+    id: String,
+    name: String,
+    agent_id: String,
+}
+
+/// Tool running saved workflows as steps of the current task.
+///
+/// # Operations
+///
+/// - `list_workflows`: List the saved workflows with their template parameters
+/// - `run`: Execute the template of a workflow with arguments
+pub struct WorkflowTool {
+    /// Database client
+    db: Arc<DBClient>,
+    /// Agent registry (agents of the invoked workflows)
+    registry: Arc<AgentRegistry>,
+    /// Agent orchestrator for execution
+    orchestrator: Arc<AgentOrchestrator>,
+    /// LLM provider manager (result summaries)
+    llm_manager: Arc<ProviderManager>,
+    /// MCP manager for tool routing (optional)
+    mcp_manager: Option<Arc<MCPManager>>,
+    /// Tauri app handle for event emission (optional, for validation)
+    app_handle: Option<AppHandle>,
+    /// Cancellation token of the calling workflow
+    cancellation_token: Option<CancellationToken>,
+    /// Calling agent ID
+    current_agent_id: String,
+    /// Calling workflow ID
+    workflow_id: String,
+    /// Whether this tool is for the primary agent (true) or a sub-agent (false)
+    is_primary_agent: bool,
+    /// Workflows invoked during this task
+    runs: AtomicUsize,
+}
+
+impl WorkflowTool {
+    /// Creates a new WorkflowTool.
+    ///
+    /// # Arguments
+    /// * `db` - Database client
+    /// * `context` - Agent tool context with system dependencies
+    /// * `current_agent_id` - ID of the agent using this tool
+    /// * `workflow_id` - Calling workflow ID
+    /// * `is_primary_agent` - Whether this is the primary workflow agent
+    pub fn new(
+        db: Arc<DBClient>,
+        context: AgentToolContext,
+        current_agent_id: String,
+        workflow_id: String,
+        is_primary_agent: bool,
+    ) -> Self {
+        Self {
+            db,
+            registry: context.registry,
+            orchestrator: context.orchestrator,
+            llm_manager: context.llm_manager,
+            mcp_manager: context.mcp_manager,
+            app_handle: context.app_handle,
+            cancellation_token: context.cancellation_token,
+            current_agent_id,
+            workflow_id,
+            is_primary_agent,
+            runs: AtomicUsize::new(0),
+        }
+    }
+
+    /// Loads the saved workflows other than the calling one, most recent first.
+    async fn load_workflows(&self) -> ToolResult<Vec<InvocableWorkflow>> {
+        let rows = self
+            .db
+            .query_json_with_params(
+                &format!(
+                    "SELECT meta::id(id) AS id, name, agent_id, updated_at FROM workflow \
+                     WHERE meta::id(id) != $workflow_id ORDER BY updated_at DESC LIMIT {}",
+                    MAX_LISTED_WORKFLOWS
+                ),
+                vec![("workflow_id".to_string(), json!(self.workflow_id))],
+            )
+            .await
+            .map_err(|e| ToolError::DatabaseError(format!("Failed to load workflows: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| serde_json::from_value(row).ok())
+            .collect())
+    }
+
+    /// Loads a saved workflow other than the calling one.
+    async fn load_workflow(&self, workflow_id: &str) -> ToolResult<InvocableWorkflow> {
+        if workflow_id == self.workflow_id {
+            return Err(ToolError::ValidationFailed(
+                "A workflow cannot invoke itself. Use 'list_workflows' to find other workflows."
+                    .to_string(),
+            ));
+        }
+        let rows = self
+            .db
+            .query_json_with_params(
+                "SELECT meta::id(id) AS id, name, agent_id FROM workflow \
+                 WHERE meta::id(id) = $workflow_id",
+                vec![("workflow_id".to_string(), json!(workflow_id))],
+            )
+            .await
+            .map_err(|e| ToolError::DatabaseError(format!("Failed to load workflow: {}", e)))?;
+
+        rows.into_iter()
+            .find_map(|row| serde_json::from_value(row).ok())
+            .ok_or_else(|| {
+                ToolError::NotFound(format!(
+                    "Workflow '{}' not found. Use 'list_workflows' to see available workflows.",
+                    workflow_id
+                ))
+            })
+    }
+
+    /// Loads the template of a workflow: its first user message.
+    ///
+    /// Returns None when the workflow has no user message, or is sensitive
+    /// and locked.
+    async fn load_template(&self, workflow_id: &str) -> ToolResult<Option<String>> {
+        let sensitive = WORKFLOW_VAULT
+            .is_sensitive(&self.db, workflow_id)
+            .await
+            .map_err(ToolError::DatabaseError)?;
+        if sensitive && !WORKFLOW_VAULT.is_unlocked(workflow_id) {
+            return Ok(None);
+        }
+
+        let rows = self
+            .db
+            .query_json_with_params(
+                "SELECT content, timestamp FROM message \
+                 WHERE workflow_id = $workflow_id AND role = 'user' \
+                 ORDER BY timestamp ASC LIMIT 1",
+                vec![("workflow_id".to_string(), json!(workflow_id))],
+            )
+            .await
+            .map_err(|e| ToolError::DatabaseError(format!("Failed to load template: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .next()
+            .and_then(|row| {
+                row.get("content")
+                    .and_then(|c| c.as_str())
+                    .map(String::from)
+            })
+            .and_then(|content| WORKFLOW_VAULT.decrypt(workflow_id, &content).ok()))
+    }
+
+    /// Lists the invocable workflows with their template parameters.
+    #[instrument(skip(self), fields(workflow_id = %self.workflow_id))]
+    async fn list_workflows(&self) -> ToolResult<Value> {
+        let mut workflows = Vec::new();
+        for workflow in self.load_workflows().await? {
+            let Some(template) = self.load_template(&workflow.id).await? else {
+                continue;
+            };
+            let parameters: Vec<String> = Prompt::detect_variables(&template)
+                .into_iter()
+                .map(|v| v.name)
+                .collect();
+            workflows.push(json!({
+                "workflow_id": workflow.id,
+                "name": workflow.name,
+                "agent_id": workflow.agent_id,
+                "parameters": parameters,
+                "template_preview": safe_truncate(&template, TEMPLATE_PREVIEW_CHARS, true)
+            }));
+        }
+
+        let runs = self.runs.load(Ordering::SeqCst);
+        debug!(count = workflows.len(), runs, "Listed invocable workflows");
+        Ok(json!({
+            "success": true,
+            "count": workflows.len(),
+            "workflows": workflows,
+            "remaining_runs": MAX_RUNS_PER_TASK.saturating_sub(runs)
+        }))
+    }
+
+    /// Runs the template of a saved workflow with arguments.
+    ///
+    /// # Arguments
+    /// * `target_id` - ID of the invoked workflow
+    /// * `arguments` - Values of the template parameters (and additional inputs)
+    /// * `summarize` - Summarize results longer than `SUMMARY_THRESHOLD_CHARS`
+    #[instrument(skip(self, arguments), fields(
+        workflow_id = %self.workflow_id,
+        target_workflow_id = %target_id
+    ))]
+    async fn run(
+        &self,
+        target_id: &str,
+        arguments: &Map<String, Value>,
+        summarize: bool,
+    ) -> ToolResult<Value> {
+        if !self.is_primary_agent {
+            return Err(ToolError::PermissionDenied(
+                "Only the primary workflow agent can invoke workflows.".to_string(),
+            ));
+        }
+        if self.runs.load(Ordering::SeqCst) >= MAX_RUNS_PER_TASK {
+            return Err(ToolError::ValidationFailed(format!(
+                "Maximum {} workflow invocations reached for this task.",
+                MAX_RUNS_PER_TASK
+            )));
+        }
+
+        let target = self.load_workflow(target_id).await?;
+        let template = self.load_template(&target.id).await?.ok_or_else(|| {
+            ToolError::DependencyError(format!(
+                "Workflow '{}' has no message to use as template, or is sensitive and locked.",
+                target.name
+            ))
+        })?;
+        let prompt = map_arguments(&template, arguments)?;
+
+        let agent = self.registry.get(&target.agent_id).await.ok_or_else(|| {
+            ToolError::DependencyError(format!(
+                "Agent '{}' of workflow '{}' is not available.",
+                target.agent_id, target.name
+            ))
+        })?;
+        let agent_config = agent.config().clone();
+
+        let executor = SubAgentExecutor::with_cancellation(
+            self.db.clone(),
+            self.orchestrator.clone(),
+            self.mcp_manager.clone(),
+            self.app_handle.clone(),
+            self.workflow_id.clone(),
+            self.current_agent_id.clone(),
+            self.cancellation_token.clone(),
+        );
+
+        let mut details =
+            ValidationHelper::delegate_details(&target.agent_id, &agent_config.name, &prompt);
+        details["workflow_name"] = json!(target.name);
+        executor
+            .request_validation(
+                SubAgentOperationType::Delegate,
+                &format!("Run workflow '{}'", target.name),
+                details,
+            )
+            .await?;
+
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        info!(
+            target_workflow = %target.name,
+            agent_id = %target.agent_id,
+            "Invoking workflow as a step"
+        );
+
+        let execution_id = executor
+            .create_execution_record(&target.agent_id, &agent_config.name, &prompt)
+            .await?;
+        executor.emit_start_event(&target.agent_id, &agent_config.name, &prompt);
+
+        let task = Task {
+            id: format!("workflow_step_{}", Uuid::new_v4()),
+            description: prompt,
+            context: json!({
+                "workflow_id": self.workflow_id,
+                "invoked_workflow_id": target.id,
+                "invoker_agent_id": self.current_agent_id,
+                "is_workflow_step": true
+            }),
+        };
+        let result = executor
+            .execute_with_retry(&target.agent_id, task, None)
+            .await;
+        executor.emit_complete_event(&target.agent_id, &agent_config.name, &result);
+        executor
+            .update_execution_record(&execution_id, &result)
+            .await;
+
+        if !result.success {
+            return Ok(json!({
+                "success": false,
+                "workflow_id": target.id,
+                "workflow_name": target.name,
+                "error": result.error_message.unwrap_or_else(|| "Unknown error".to_string()),
+                "metrics": result.metrics
+            }));
+        }
+
+        let (output, summarized) =
+            if summarize && result.report.chars().count() > SUMMARY_THRESHOLD_CHARS {
+                match self
+                    .summarize(&agent_config.llm, &target.name, &result.report)
+                    .await
+                {
+                    Some(summary) => (summary, true),
+                    None => (safe_truncate(&result.report, MAX_RESULT_CHARS, true), false),
+                }
+            } else {
+                (safe_truncate(&result.report, MAX_RESULT_CHARS, true), false)
+            };
+
+        Ok(json!({
+            "success": true,
+            "workflow_id": target.id,
+            "workflow_name": target.name,
+            "result": output,
+            "summarized": summarized,
+            "metrics": result.metrics
+        }))
+    }
+
+    /// Summarizes the result of an invoked workflow with its agent's model.
+    ///
+    /// Returns None if the summary fails (the caller falls back to the
+    /// truncated result).
+    async fn summarize(
+        &self,
+        llm: &crate::models::LLMConfig,
+        workflow_name: &str,
+        report: &str,
+    ) -> Option<String> {
+        let provider: ProviderType = llm.provider.parse().ok()?;
+        match self
+            .llm_manager
+            .complete_with_provider(
+                provider,
+                &summary_prompt(workflow_name, report),
+                Some(
+                    "You summarize the results of automated workflow steps for another agent. \
+                     Keep every fact, figure, identifier and conclusion it needs; drop narration.",
+                ),
+                Some(&llm.model),
+                0.2,
+                SUMMARY_MAX_TOKENS,
+                None,
+            )
+            .await
+        {
+            Ok(response) if !response.content.trim().is_empty() => Some(response.content),
+            Ok(_) => None,
+            Err(e) => {
+                warn!(error = %e, "Failed to summarize workflow result");
+                None
+            }
+        }
+    }
+
+    /// Creates the tool with the agent system access of the context.
+    fn build(ctx: ToolBuildContext<'_>) -> ToolFuture<'_> {
+        Box::pin(async move {
+            let context = ctx.require_agent_context()?;
+            let wf_id = ctx.workflow_id_or_default();
+            let tool = WorkflowTool::new(
+                ctx.factory.get_db(),
+                context,
+                ctx.agent_id,
+                wf_id,
+                ctx.is_primary_agent,
+            );
+            info!("WorkflowTool instance created");
+            Ok(Arc::new(tool) as Arc<dyn Tool>)
+        })
+    }
+}
+
+inventory::submit! {
+    // Opt-in, bounded by the sub-agent inactivity timeout
+    ToolRegistration::Tool(ToolMetadata {
+        name: "WorkflowTool",
+        category: ToolCategory::Composite,
+        requires_context: true,
+        default_timeout_secs: None,
+        constructor: WorkflowTool::build,
+    })
+}
+
+/// Builds the prompt of a workflow step from its template and arguments.
+///
+/// `{{name}}` placeholders are replaced by the arguments of the same name
+/// (strings as is, other values as JSON); arguments without a placeholder are
+/// appended as additional inputs.
+///
+/// # Errors
+/// A placeholder of the template has no argument.
+fn map_arguments(template: &str, arguments: &Map<String, Value>) -> ToolResult<String> {
+    let parameters: Vec<String> = Prompt::detect_variables(template)
+        .into_iter()
+        .map(|v| v.name)
+        .collect();
+    let missing: Vec<&str> = parameters
+        .iter()
+        .filter(|name| !arguments.contains_key(name.as_str()))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(ToolError::InvalidInput(format!(
+            "Missing arguments for the workflow template: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let values: HashMap<String, String> = arguments
+        .iter()
+        .map(|(name, value)| {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (name.clone(), text)
+        })
+        .collect();
+    let mut prompt = Prompt::interpolate(template, &values);
+
+    let extra: Vec<String> = arguments
+        .keys()
+        .filter(|name| !parameters.contains(name))
+        .map(|name| format!("- {}: {}", name, values[name]))
+        .collect();
+    if !extra.is_empty() {
+        prompt.push_str("\n\nAdditional inputs:\n");
+        prompt.push_str(&extra.join("\n"));
+    }
+    Ok(prompt)
+}
+
+/// Builds the summary request of a workflow result.
+fn summary_prompt(workflow_name: &str, report: &str) -> String {
+    format!(
+        "Summarize the result of the workflow \"{}\" below in at most 300 words.\n\n\
+         --- RESULT ---\n{}\n--- END OF RESULT ---",
+        workflow_name,
+        safe_truncate(report, MAX_RESULT_CHARS, true)
+    )
+}
+
+#[async_trait]
+impl Tool for WorkflowTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            id: "WorkflowTool".to_string(),
+            name: "Workflow".to_string(),
+            description: r#"Runs another saved workflow as one step of your task.
+
+The first user message of a saved workflow is its template; {{name}} placeholders are filled from "arguments". Arguments without a placeholder are appended as additional inputs. The workflow runs with its own agent, which only receives the filled template: include every input it needs in the arguments.
+
+USE THIS TOOL WHEN:
+- A saved workflow already automates a step of your task (report, analysis, formatting...)
+
+OPERATIONS:
+- list_workflows: Show the saved workflows with their template parameters
+- run: Execute a workflow template
+  Required: workflow_id, arguments (values of all parameters)
+  Optional: summarize (default true) - long results are summarized
+
+EXAMPLE:
+{"operation": "list_workflows"}
+{"operation": "run", "workflow_id": "3f2a...", "arguments": {"customer": "ACME", "period": "Q3"}}"#
+                .to_string(),
+
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "operation": {
+                        "type": "string",
+                        "enum": ["list_workflows", "run"],
+                        "description": "Operation: 'list_workflows' shows invocable workflows, 'run' executes one"
+                    },
+                    "workflow_id": {
+                        "type": "string",
+                        "description": "Workflow to run (for run). Use list_workflows to find it."
+                    },
+                    "arguments": {
+                        "type": "object",
+                        "description": "Values of the template parameters, plus any additional input (for run)"
+                    },
+                    "summarize": {
+                        "type": "boolean",
+                        "description": "Summarize long results (default true)"
+                    }
+                },
+                "required": ["operation"]
+            }),
+
+            output_schema: json!({
+                "type": "object",
+                "properties": {
+                    "success": {"type": "boolean"},
+                    "workflow_id": {"type": "string"},
+                    "workflow_name": {"type": "string"},
+                    "result": {"type": "string"},
+                    "summarized": {"type": "boolean"},
+                    "error": {"type": "string"},
+                    "metrics": {"type": "object"},
+                    "workflows": {"type": "array"},
+                    "remaining_runs": {"type": "integer"}
+                }
+            }),
+
+            requires_confirmation: false,
+        }
+    }
+
+    #[instrument(skip(self, input), fields(workflow_id = %self.workflow_id))]
+    async fn execute(&self, input: Value) -> ToolResult<Value> {
+        self.validate_input(&input)?;
+
+        match input["operation"].as_str() {
+            Some("list_workflows") => self.list_workflows().await,
+            Some("run") => {
+                let workflow_id = input["workflow_id"].as_str().unwrap_or_default();
+                let arguments = input
+                    .get("arguments")
+                    .and_then(|a| a.as_object())
+                    .cloned()
+                    .unwrap_or_default();
+                let summarize = input
+                    .get("summarize")
+                    .and_then(|s| s.as_bool())
+                    .unwrap_or(true);
+                self.run(workflow_id, &arguments, summarize).await
+            }
+            _ => Err(ToolError::InvalidInput(
+                "Unknown operation. Valid operations: list_workflows, run".to_string(),
+            )),
+        }
+    }
+
+    fn validate_input(&self, input: &Value) -> ToolResult<()> {
+        if !input.is_object() {
+            return Err(ToolError::InvalidInput(
+                "Input must be an object".to_string(),
+            ));
+        }
+
+        let operation = input["operation"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput("Missing 'operation' field".to_string()))?;
+
+        match operation {
+            "list_workflows" => Ok(()),
+            "run" => {
+                let workflow_id = input["workflow_id"].as_str().unwrap_or_default();
+                if workflow_id.trim().is_empty() {
+                    return Err(ToolError::InvalidInput(
+                        "Missing 'workflow_id' for run operation. \
+                         Use 'list_workflows' to find available workflows."
+                            .to_string(),
+                    ));
+                }
+                match input.get("arguments") {
+                    None | Some(Value::Object(_)) => Ok(()),
+                    Some(_) => Err(ToolError::InvalidInput(
+                        "'arguments' must be an object of parameter values".to_string(),
+                    )),
+                }
+            }
+            _ => Err(ToolError::InvalidInput(format!(
+                "Unknown operation: '{}'. Valid operations: list_workflows, run",
+                operation
+            ))),
+        }
+    }
+
+    fn requires_confirmation(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arguments(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_map_arguments_fills_placeholders() {
+        let prompt = map_arguments(
+            "Write the {{period}} report for {{customer}}",
+            &arguments(json!({ "customer": "ACME", "period": "Q3" })),
+        )
+        .unwrap();
+        assert_eq!(prompt, "Write the Q3 report for ACME");
+    }
+
+    #[test]
+    fn test_map_arguments_appends_extra_inputs() {
+        let prompt = map_arguments(
+            "Summarize the incidents of {{team}}",
+            &arguments(json!({ "team": "infra", "max_items": 5, "tags": ["p1"] })),
+        )
+        .unwrap();
+        assert_eq!(
+            prompt,
+            "Summarize the incidents of infra\n\nAdditional inputs:\n- max_items: 5\n- tags: [\"p1\"]"
+        );
+
+        // A template without placeholders receives all arguments as inputs
+        let prompt =
+            map_arguments("Clean up the data", &arguments(json!({ "file": "a.csv" }))).unwrap();
+        assert_eq!(
+            prompt,
+            "Clean up the data\n\nAdditional inputs:\n- file: a.csv"
+        );
+    }
+
+    #[test]
+    fn test_map_arguments_missing_parameter() {
+        let err =
+            map_arguments("Compare {{a}} with {{b}}", &arguments(json!({ "a": "x" }))).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Missing arguments for the workflow template: b"));
+    }
+}
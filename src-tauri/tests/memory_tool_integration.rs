@@ -312,6 +312,10 @@ mod agent_config_tests {
             system_prompt: "You are an agent with memory capabilities.".to_string(),
             max_tool_iterations: 50,
             enable_thinking: true,
+            tool_settings: Default::default(),
+            mcp_tool_policy: Default::default(),
+            regressions: Default::default(),
+            moderation: Default::default(),
         };
 
         assert!(config.has_valid_tools());
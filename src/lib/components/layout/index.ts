@@ -26,3 +26,4 @@ export { default as AppContainer } from './AppContainer.svelte';
 export { default as FloatingMenu } from './FloatingMenu.svelte';
 export { default as Sidebar } from './Sidebar.svelte';
 export { default as RightSidebar } from './RightSidebar.svelte';
+export { default as StartupSplash } from './StartupSplash.svelte';
@@ -30,3 +30,4 @@
 export { default as MCPServerCard } from './MCPServerCard.svelte';
 export { default as MCPServerForm } from './MCPServerForm.svelte';
 export { default as MCPServerTester } from './MCPServerTester.svelte';
+export { default as MCPServerImport } from './MCPServerImport.svelte';
@@ -18,3 +18,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 export { default as ValidationSettings } from './ValidationSettings.svelte';
+export { default as DesktopAccessSettings } from './DesktopAccessSettings.svelte';
+export { default as OcrSettings } from './OcrSettings.svelte';
+export { default as PluginSettings } from './PluginSettings.svelte';
@@ -14,17 +14,43 @@ export const AVAILABLE_TOOLS = [
   'MemoryTool',
   'TodoTool',
   'CalculatorTool',
+  'UnitsTool',
+  'TextTool',
   'UserQuestionTool',
+  'SqlTool',
+  'EmailTool',
+  'ChartTool',
+  'ClipboardTool',
+  'ScreenshotTool',
+  'RssTool',
+  'PdfTool',
+  'OcrTool',
   'SpawnAgentTool',
   'DelegateTaskTool',
-  'ParallelTasksTool'
+  'ParallelTasksTool',
+  'WorkflowTool'
 ] as const;
 
 /**
  * Basic tools for memory and task management.
  * These tools do not require AgentToolContext.
  */
-export const BASIC_TOOLS = ['MemoryTool', 'TodoTool', 'CalculatorTool', 'UserQuestionTool'] as const;
+export const BASIC_TOOLS = [
+  'MemoryTool',
+  'TodoTool',
+  'CalculatorTool',
+  'UnitsTool',
+  'TextTool',
+  'UserQuestionTool',
+  'SqlTool',
+  'EmailTool',
+  'ChartTool',
+  'ClipboardTool',
+  'ScreenshotTool',
+  'RssTool',
+  'PdfTool',
+  'OcrTool'
+] as const;
 
 /**
  * Sub-agent orchestration tools.
@@ -32,6 +58,12 @@ export const BASIC_TOOLS = ['MemoryTool', 'TodoTool', 'CalculatorTool', 'UserQue
  */
 export const SUB_AGENT_TOOLS = ['SpawnAgentTool', 'DelegateTaskTool', 'ParallelTasksTool'] as const;
 
+/**
+ * Composite tools running saved workflows as steps.
+ * These tools require AgentToolContext, only available to primary agents (opt-in).
+ */
+export const COMPOSITE_TOOLS = ['WorkflowTool'] as const;
+
 /**
  * Type for available tool names
  */
@@ -46,3 +78,8 @@ export type BasicToolName = (typeof BASIC_TOOLS)[number];
  * Type for sub-agent tool names
  */
 export type SubAgentToolName = (typeof SUB_AGENT_TOOLS)[number];
+
+/**
+ * Type for composite tool names
+ */
+export type CompositeToolName = (typeof COMPOSITE_TOOLS)[number];
@@ -24,8 +24,8 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { ToolExecution } from '$types/tool';
-import type { ThinkingStep } from '$types/thinking';
+import type { ExecutionTrace, ToolExecution, ToolExecutionPage } from '$types/tool';
+import type { ThinkingStep, ThinkingStepPage } from '$types/thinking';
 import type { SubAgentExecution } from '$types/sub-agent';
 import type { Task } from '$types/task';
 import type { WorkflowActivityEvent } from '$types/activity';
@@ -61,17 +61,82 @@ export const ActivityService = {
 	 * Load tool executions for a workflow.
 	 *
 	 * @param workflowId - Workflow ID
+	 * @param compact - Omit the output of executions that have a result diff
 	 * @returns Array of tool executions
 	 */
-	async loadToolExecutions(workflowId: string): Promise<ToolExecution[]> {
+	async loadToolExecutions(workflowId: string, compact = false): Promise<ToolExecution[]> {
 		try {
-			return await invoke<ToolExecution[]>('load_workflow_tool_executions', { workflowId });
+			return await invoke<ToolExecution[]>('load_workflow_tool_executions', {
+				workflowId,
+				compact
+			});
 		} catch (e) {
 			console.error('Failed to load tool executions:', e);
 			return [];
 		}
 	},
 
+	/**
+	 * Load a page of the tool executions of a workflow (oldest first).
+	 *
+	 * @param workflowId - Workflow ID
+	 * @param cursor - `next_cursor` of the previous page (null for the first page)
+	 * @param limit - Page size (default 50, capped at 500)
+	 * @param summary - Omit the payloads (load them with `loadToolExecution`)
+	 * @returns Page of tool executions
+	 */
+	async loadToolExecutionsPage(
+		workflowId: string,
+		cursor: string | null = null,
+		limit?: number,
+		summary = false
+	): Promise<ToolExecutionPage> {
+		return invoke<ToolExecutionPage>('load_workflow_tool_executions_page', {
+			workflowId,
+			cursor,
+			limit,
+			summary
+		});
+	},
+
+	/**
+	 * Load a single tool execution with its payloads.
+	 *
+	 * @param executionId - Tool execution ID
+	 * @returns Full tool execution
+	 */
+	async loadToolExecution(executionId: string): Promise<ToolExecution> {
+		return invoke<ToolExecution>('get_tool_execution', { executionId });
+	},
+
+	/**
+	 * Load the execution trace (tool loop graph) of an assistant message.
+	 *
+	 * @param workflowId - Workflow ID
+	 * @param messageId - Assistant message ID
+	 * @returns Execution trace, or null if loading failed
+	 */
+	async loadExecutionTrace(workflowId: string, messageId: string): Promise<ExecutionTrace | null> {
+		try {
+			return await invoke<ExecutionTrace>('get_execution_trace', { workflowId, messageId });
+		} catch (e) {
+			console.error('Failed to load execution trace:', e);
+			return null;
+		}
+	},
+
+	/**
+	 * Export the timeline of a workflow (agent runs, iterations, LLM and tool
+	 * calls) as a Jaeger JSON trace file.
+	 *
+	 * @param workflowId - Workflow ID
+	 * @param path - Destination file
+	 * @returns Number of exported spans
+	 */
+	async exportWorkflowTrace(workflowId: string, path: string): Promise<number> {
+		return invoke<number>('export_workflow_trace', { workflowId, path });
+	},
+
 	/**
 	 * Load thinking steps for a workflow.
 	 *
@@ -87,6 +152,39 @@ export const ActivityService = {
 		}
 	},
 
+	/**
+	 * Load a page of the thinking steps of a workflow (oldest first).
+	 *
+	 * @param workflowId - Workflow ID
+	 * @param cursor - `next_cursor` of the previous page (null for the first page)
+	 * @param limit - Page size (default 50, capped at 500)
+	 * @param summary - Shorten the contents to a preview (load a step with `loadThinkingStep`)
+	 * @returns Page of thinking steps
+	 */
+	async loadThinkingStepsPage(
+		workflowId: string,
+		cursor: string | null = null,
+		limit?: number,
+		summary = false
+	): Promise<ThinkingStepPage> {
+		return invoke<ThinkingStepPage>('load_workflow_thinking_steps_page', {
+			workflowId,
+			cursor,
+			limit,
+			summary
+		});
+	},
+
+	/**
+	 * Load a single thinking step with its full content.
+	 *
+	 * @param stepId - Thinking step ID
+	 * @returns Full thinking step
+	 */
+	async loadThinkingStep(stepId: string): Promise<ThinkingStep> {
+		return invoke<ThinkingStep>('get_thinking_step', { stepId });
+	},
+
 	/**
 	 * Load sub-agent executions for a workflow.
 	 *
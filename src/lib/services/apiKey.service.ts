@@ -0,0 +1,42 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview API key service: key metadata, rotation and expiry status.
+ *
+ * @module lib/services/apiKey
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ApiKeyInfo } from '$types/security';
+
+export const ApiKeyService = {
+	/**
+	 * List the stored provider API keys with their age and expiry status.
+	 */
+	async listMetadata(): Promise<ApiKeyInfo[]> {
+		return invoke<ApiKeyInfo[]>('list_api_key_metadata');
+	},
+
+	/**
+	 * Replace the API key of a provider; providers are re-initialized with the new key.
+	 */
+	async rotate(provider: string, apiKey: string, expiresAt?: string): Promise<ApiKeyInfo> {
+		return invoke<ApiKeyInfo>('rotate_api_key', { provider, apiKey, expiresAt });
+	}
+};
@@ -0,0 +1,46 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Audit log service: query and export the log of sensitive actions.
+ *
+ * @module lib/services/audit
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { AuditExportFormat, AuditLogEntry, AuditLogFilter } from '$types/audit';
+
+export const AuditService = {
+	/**
+	 * Load audit log entries, newest first.
+	 */
+	async query(filter?: AuditLogFilter): Promise<AuditLogEntry[]> {
+		return invoke<AuditLogEntry[]>('query_audit_log', { filter });
+	},
+
+	/**
+	 * Export audit log entries to a file, returning the number of entries written.
+	 */
+	async export(
+		path: string,
+		filter?: AuditLogFilter,
+		format: AuditExportFormat = 'jsonl'
+	): Promise<number> {
+		return invoke<number>('export_audit_log', { path, filter, format });
+	}
+};
@@ -0,0 +1,57 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Behavior changelog service: changes affecting agent behavior.
+ *
+ * @module lib/services/behaviorChangelog
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { BehaviorChange, BehaviorChangeQuery } from '$types/behaviorChangelog';
+
+export const BehaviorChangelogService = {
+	/**
+	 * List behavior changes, newest first.
+	 *
+	 * @param query - Optional agent and position filters
+	 */
+	async list(query: BehaviorChangeQuery = {}): Promise<BehaviorChange[]> {
+		return invoke<BehaviorChange[]>('list_behavior_changes', {
+			agentId: query.agentId ?? null,
+			afterPosition: query.afterPosition ?? null,
+			untilPosition: query.untilPosition ?? null,
+			limit: query.limit ?? null
+		});
+	},
+
+	/**
+	 * List the changes made between two runs of an agent.
+	 *
+	 * @param agentId - Agent of the runs
+	 * @param fromPosition - `behavior_position` of the earlier run
+	 * @param toPosition - `behavior_position` of the later run
+	 */
+	async between(
+		agentId: string,
+		fromPosition: number,
+		toPosition: number
+	): Promise<BehaviorChange[]> {
+		return this.list({ agentId, afterPosition: fromPosition, untilPosition: toPosition });
+	}
+};
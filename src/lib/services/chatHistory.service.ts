@@ -0,0 +1,40 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Chat history service: import conversations exported from
+ * ChatGPT or Claude as workflows.
+ *
+ * @module lib/services/chatHistory
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ChatHistoryImportOptions, ChatHistoryImportResult } from '$types/importExport';
+
+export const ChatHistoryService = {
+	/**
+	 * Import the `conversations.json` file at `path`. Conversations already
+	 * imported are skipped.
+	 */
+	async importChatHistory(
+		path: string,
+		options: ChatHistoryImportOptions
+	): Promise<ChatHistoryImportResult> {
+		return invoke<ChatHistoryImportResult>('import_chat_history', { path, options });
+	}
+};
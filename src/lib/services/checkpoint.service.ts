@@ -0,0 +1,56 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Checkpoint service: resume workflow executions interrupted by a crash.
+ *
+ * @module lib/services/checkpoint
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ResumableExecution } from '$types/checkpoint';
+
+export const CheckpointService = {
+	/**
+	 * List the executions interrupted by a crash, most recent first.
+	 */
+	async listResumable(): Promise<ResumableExecution[]> {
+		return invoke<ResumableExecution[]>('list_resumable_executions');
+	},
+
+	/**
+	 * Resume an interrupted execution from its last checkpoint.
+	 *
+	 * Progress is emitted on `workflow_stream` and the end on `workflow_complete`.
+	 *
+	 * @param workflowId - Workflow of the interrupted execution
+	 * @returns ID of the assistant message
+	 */
+	async resume(workflowId: string): Promise<string> {
+		return invoke<string>('resume_workflow_execution', { workflowId });
+	},
+
+	/**
+	 * Discard an interrupted execution (it will not be resumed).
+	 *
+	 * @param workflowId - Workflow of the interrupted execution
+	 */
+	async discard(workflowId: string): Promise<void> {
+		return invoke<void>('discard_workflow_checkpoint', { workflowId });
+	}
+};
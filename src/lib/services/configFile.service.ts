@@ -0,0 +1,35 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Config file service: settings overridden by `~/.zileo/config.toml`.
+ *
+ * @module lib/services/configFile
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { EffectiveConfig } from '$types/configFile';
+
+export const ConfigFileService = {
+	/**
+	 * Get the settings in effect and where they come from.
+	 */
+	async getEffective(): Promise<EffectiveConfig> {
+		return invoke<EffectiveConfig>('get_effective_config');
+	}
+};
@@ -0,0 +1,78 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Custom tool service: tools written as Rhai scripts.
+ *
+ * @module lib/services/customTool
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	CustomTool,
+	CustomToolCreate,
+	CustomToolSchema,
+	CustomToolUpdate
+} from '$types/custom-tool';
+
+/** Prefix of the tool names given to agents */
+export const CUSTOM_TOOL_PREFIX = 'custom__';
+
+export const CustomToolService = {
+	/**
+	 * List the custom tools, sorted by name.
+	 */
+	async list(): Promise<CustomTool[]> {
+		return invoke<CustomTool[]>('list_custom_tools');
+	},
+
+	/**
+	 * Create a custom tool (enabled). The script must compile.
+	 */
+	async create(config: CustomToolCreate): Promise<CustomTool> {
+		return invoke<CustomTool>('create_custom_tool', { config });
+	},
+
+	/**
+	 * Update the description, schema or script of a custom tool, or enable/disable it.
+	 */
+	async update(toolId: string, config: CustomToolUpdate): Promise<CustomTool> {
+		return invoke<CustomTool>('update_custom_tool', { toolId, config });
+	},
+
+	/**
+	 * Delete a custom tool.
+	 */
+	async delete(toolId: string): Promise<void> {
+		return invoke<void>('delete_custom_tool', { toolId });
+	},
+
+	/**
+	 * Run a script on a sample input, as an agent call would.
+	 */
+	async test(script: string, inputSchema: CustomToolSchema, input: unknown): Promise<unknown> {
+		return invoke<unknown>('test_custom_tool', { script, inputSchema, input });
+	},
+
+	/**
+	 * Tool name given to agents for a custom tool.
+	 */
+	toolName(name: string): string {
+		return `${CUSTOM_TOOL_PREFIX}${name}`;
+	}
+};
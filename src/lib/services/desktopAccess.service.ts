@@ -0,0 +1,42 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Desktop access service: permissions of the ClipboardTool, ScreenshotTool, PdfTool and OcrTool.
+ *
+ * @module lib/services/desktopAccess
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { DesktopAccessSettings } from '$types/desktop-access';
+
+export const DesktopAccessService = {
+	/**
+	 * Get the desktop access permissions (all disabled when never saved).
+	 */
+	async getSettings(): Promise<DesktopAccessSettings> {
+		return invoke<DesktopAccessSettings>('get_desktop_access_settings');
+	},
+
+	/**
+	 * Save the desktop access permissions; running workflows use them on their next tool call.
+	 */
+	async updateSettings(settings: DesktopAccessSettings): Promise<DesktopAccessSettings> {
+		return invoke<DesktopAccessSettings>('update_desktop_access_settings', { settings });
+	}
+};
@@ -0,0 +1,35 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Diagnostics service: bundle attached to bug reports.
+ *
+ * @module lib/services/diagnostics
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { DiagnosticsBundle } from '$types/diagnostics';
+
+export const DiagnosticsService = {
+	/**
+	 * Write the diagnostics bundle (zip, secrets stripped) at the given path.
+	 */
+	async generate(path: string): Promise<DiagnosticsBundle> {
+		return invoke<DiagnosticsBundle>('generate_diagnostics', { path });
+	}
+};
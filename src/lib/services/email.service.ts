@@ -0,0 +1,66 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Email service: accounts read and sent from by the EmailTool.
+ *
+ * @module lib/services/email
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { EmailAccount, EmailAccountInput } from '$types/email';
+
+export const EmailService = {
+	/**
+	 * List the configured email accounts.
+	 */
+	async list(): Promise<EmailAccount[]> {
+		return invoke<EmailAccount[]>('list_email_accounts');
+	},
+
+	/**
+	 * Create or update an email account.
+	 *
+	 * @param account - Address, servers, login and read-only flag
+	 * @param password - Password (required on creation, kept when omitted)
+	 */
+	async save(account: EmailAccountInput, password?: string): Promise<EmailAccount> {
+		return invoke<EmailAccount>('save_email_account', {
+			account,
+			password: password ?? null
+		});
+	},
+
+	/**
+	 * Delete an email account and its password.
+	 *
+	 * @param name - Account name
+	 */
+	async delete(name: string): Promise<void> {
+		return invoke<void>('delete_email_account', { name });
+	},
+
+	/**
+	 * Check that an account can log in to its IMAP server (and SMTP server unless read-only).
+	 *
+	 * @param name - Account name
+	 */
+	async test(name: string): Promise<void> {
+		return invoke<void>('test_email_account', { name });
+	}
+};
@@ -0,0 +1,69 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview External database service: databases queried by the SqlTool.
+ *
+ * @module lib/services/externalDb
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ExternalDatabase, ExternalDatabaseInput } from '$types/external-db';
+
+export const ExternalDbService = {
+	/**
+	 * List the configured external databases.
+	 */
+	async list(): Promise<ExternalDatabase[]> {
+		return invoke<ExternalDatabase[]>('list_external_databases');
+	},
+
+	/**
+	 * Create or update an external database.
+	 *
+	 * @param database - Name, description, read-only flag and row limit
+	 * @param connectionString - Connection URL (required on creation, kept when omitted)
+	 */
+	async save(
+		database: ExternalDatabaseInput,
+		connectionString?: string
+	): Promise<ExternalDatabase> {
+		return invoke<ExternalDatabase>('save_external_database', {
+			database,
+			connectionString: connectionString ?? null
+		});
+	},
+
+	/**
+	 * Delete an external database and its connection string.
+	 *
+	 * @param name - Database name
+	 */
+	async delete(name: string): Promise<void> {
+		return invoke<void>('delete_external_database', { name });
+	},
+
+	/**
+	 * Check that an external database accepts connections.
+	 *
+	 * @param name - Database name
+	 */
+	async test(name: string): Promise<void> {
+		return invoke<void>('test_external_database', { name });
+	}
+};
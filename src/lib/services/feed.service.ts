@@ -0,0 +1,69 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Feed service: news feeds read by the RssTool and scheduled digests.
+ *
+ * @module lib/services/feed
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { Feed } from '$types/feed';
+import type { Schedule } from '$types/schedule';
+
+export const FeedService = {
+	/**
+	 * List the news feeds.
+	 */
+	async list(): Promise<Feed[]> {
+		return invoke<Feed[]>('list_feeds');
+	},
+
+	/**
+	 * Add a feed (downloaded once to check it and read its title).
+	 *
+	 * @param url - Feed URL (http or https)
+	 */
+	async add(url: string): Promise<Feed> {
+		return invoke<Feed>('add_feed', { url });
+	},
+
+	/**
+	 * Delete a feed and its recorded items.
+	 *
+	 * @param feedId - Feed ID
+	 */
+	async delete(feedId: string): Promise<void> {
+		return invoke<void>('delete_feed', { feedId });
+	},
+
+	/**
+	 * Schedule a digest of the new feed items, posted into a dedicated workflow.
+	 *
+	 * @param agentId - Agent writing the digests (must have the RssTool)
+	 * @param name - Schedule and workflow name (default: "News digest")
+	 * @param cron - Cron expression in local time (default: every day at 8:00)
+	 */
+	async createDigest(agentId: string, name?: string, cron?: string): Promise<Schedule> {
+		return invoke<Schedule>('create_feed_digest', {
+			agentId,
+			name: name ?? null,
+			cron: cron ?? null
+		});
+	}
+};
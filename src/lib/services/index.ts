@@ -28,3 +28,31 @@ export * from './message.service';
 export * from './activity.service';
 export * from './localStorage.service';
 export * from './workflowExecutor.service';
+export * from './speech.service';
+export * from './sync.service';
+export * from './knowledge.service';
+export * from './schedule.service';
+export * from './job.service';
+export * from './checkpoint.service';
+export * from './usageStats.service';
+export * from './behaviorChangelog.service';
+export * from './inbox.service';
+export * from './search.service';
+export * from './workspaceArchive.service';
+export * from './transcript.service';
+export * from './chatHistory.service';
+export * from './audit.service';
+export * from './apiKey.service';
+export * from './profile.service';
+export * from './configFile.service';
+export * from './proxy.service';
+export * from './externalDb.service';
+export * from './metrics.service';
+export * from './diagnostics.service';
+export * from './desktopAccess.service';
+export * from './email.service';
+export * from './feed.service';
+export * from './ocr.service';
+export * from './plugin.service';
+export * from './customTool.service';
+export * from './macroTool.service';
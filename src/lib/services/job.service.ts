@@ -0,0 +1,62 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Job service: background workflow executions.
+ *
+ * @module lib/services/job
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { JOB_STATUS_EVENT, type Job, type JobStatusEvent } from '$types/job';
+
+export const JobService = {
+	/**
+	 * Execute a workflow in the background.
+	 *
+	 * The user message is stored right away; the response is stored when the
+	 * job is done, even after an app restart.
+	 *
+	 * @param workflowId - Workflow receiving the messages
+	 * @param message - User message
+	 * @param agentId - Agent executing the message
+	 */
+	async enqueue(workflowId: string, message: string, agentId: string): Promise<Job> {
+		return invoke<Job>('enqueue_workflow', { workflowId, message, agentId });
+	},
+
+	/**
+	 * Get the state of a job.
+	 *
+	 * @param jobId - Job ID
+	 */
+	async getStatus(jobId: string): Promise<Job> {
+		return invoke<Job>('get_job_status', { jobId });
+	},
+
+	/**
+	 * Listen for job state changes.
+	 *
+	 * @param handler - Called on each state change
+	 */
+	async onStatus(handler: (event: JobStatusEvent) => void): Promise<UnlistenFn> {
+		return listen<JobStatusEvent>(JOB_STATUS_EVENT, (event) => handler(event.payload));
+	}
+};
@@ -0,0 +1,175 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Knowledge pack service: packs, documents and agent attachment.
+ *
+ * @module lib/services/knowledge
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { KnowledgeChunk, KnowledgeDocument, KnowledgePack } from '$types/knowledge';
+
+export const KnowledgeService = {
+	/**
+	 * List the knowledge packs with their documents.
+	 */
+	async listPacks(): Promise<KnowledgePack[]> {
+		return invoke<KnowledgePack[]>('list_knowledge_packs');
+	},
+
+	/**
+	 * Create an empty knowledge pack.
+	 *
+	 * @param name - Pack name (unique)
+	 * @param description - Description of the pack content
+	 */
+	async createPack(name: string, description?: string): Promise<KnowledgePack> {
+		return invoke<KnowledgePack>('create_knowledge_pack', {
+			name,
+			description: description ?? null
+		});
+	},
+
+	/**
+	 * Rename a knowledge pack or change its description.
+	 *
+	 * @param packId - Pack ID
+	 * @param name - New name (unchanged if omitted)
+	 * @param description - New description (unchanged if omitted)
+	 */
+	async updatePack(packId: string, name?: string, description?: string): Promise<KnowledgePack> {
+		return invoke<KnowledgePack>('update_knowledge_pack', {
+			packId,
+			name: name ?? null,
+			description: description ?? null
+		});
+	},
+
+	/**
+	 * Delete a knowledge pack and its documents (curated memories are kept).
+	 *
+	 * @param packId - Pack ID
+	 */
+	async deletePack(packId: string): Promise<void> {
+		return invoke<void>('delete_knowledge_pack', { packId });
+	},
+
+	/**
+	 * Add a document to a pack (replaces the document with the same title).
+	 *
+	 * @param packId - Pack ID
+	 * @param title - Document title
+	 * @param content - Document text
+	 * @returns The stored document with its chunk counts
+	 */
+	async addDocument(packId: string, title: string, content: string): Promise<KnowledgeDocument> {
+		return invoke<KnowledgeDocument>('add_knowledge_document', { packId, title, content });
+	},
+
+	/**
+	 * Import a PDF, Markdown or text file into a pack.
+	 *
+	 * @param packId - Pack ID
+	 * @param path - Absolute path of the file
+	 * @param title - Document title (defaults to the PDF title or the file name)
+	 * @returns The stored document with its chunk counts
+	 */
+	async importDocument(packId: string, path: string, title?: string): Promise<KnowledgeDocument> {
+		return invoke<KnowledgeDocument>('import_knowledge_document', { packId, path, title });
+	},
+
+	/**
+	 * Remove a document from a pack.
+	 *
+	 * @param packId - Pack ID
+	 * @param title - Document title
+	 */
+	async removeDocument(packId: string, title: string): Promise<void> {
+		return invoke<void>('remove_knowledge_document', { packId, title });
+	},
+
+	/**
+	 * Set the memories curated into a pack.
+	 *
+	 * @param packId - Pack ID
+	 * @param memoryIds - Memory IDs (replaces the current list)
+	 */
+	async setMemories(packId: string, memoryIds: string[]): Promise<KnowledgePack> {
+		return invoke<KnowledgePack>('set_knowledge_pack_memories', { packId, memoryIds });
+	},
+
+	/**
+	 * Attach a pack to an agent.
+	 *
+	 * @param packId - Pack ID
+	 * @param agentId - Agent ID
+	 */
+	async attach(packId: string, agentId: string): Promise<KnowledgePack> {
+		return invoke<KnowledgePack>('attach_knowledge_pack', { packId, agentId });
+	},
+
+	/**
+	 * Detach a pack from an agent.
+	 *
+	 * @param packId - Pack ID
+	 * @param agentId - Agent ID
+	 */
+	async detach(packId: string, agentId: string): Promise<KnowledgePack> {
+		return invoke<KnowledgePack>('detach_knowledge_pack', { packId, agentId });
+	},
+
+	/**
+	 * List the packs (collections) attached to an agent.
+	 *
+	 * @param agentId - Agent ID
+	 */
+	async listForAgent(agentId: string): Promise<KnowledgePack[]> {
+		return invoke<KnowledgePack[]>('list_agent_knowledge_packs', { agentId });
+	},
+
+	/**
+	 * Set the packs (collections) attached to an agent.
+	 *
+	 * @param agentId - Agent ID
+	 * @param packIds - Pack IDs (replaces the current list)
+	 */
+	async setForAgent(agentId: string, packIds: string[]): Promise<KnowledgePack[]> {
+		return invoke<KnowledgePack[]>('set_agent_knowledge_packs', { agentId, packIds });
+	},
+
+	/**
+	 * Preview the chunks an agent retrieves from its attached packs for a query.
+	 *
+	 * @param agentId - Agent ID
+	 * @param query - Query text
+	 * @param limit - Maximum number of chunks
+	 */
+	async searchAgentKnowledge(
+		agentId: string,
+		query: string,
+		limit?: number
+	): Promise<KnowledgeChunk[]> {
+		return invoke<KnowledgeChunk[]>('search_agent_knowledge', {
+			agentId,
+			query,
+			limit: limit ?? null
+		});
+	}
+};
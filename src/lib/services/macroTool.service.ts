@@ -0,0 +1,66 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Macro tool service: sequences of tool calls run as one tool.
+ *
+ * @module lib/services/macroTool
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ToolMacro, ToolMacroCreate, ToolMacroUpdate } from '$types/macro-tool';
+
+/** Prefix of the tool names given to agents */
+export const MACRO_TOOL_PREFIX = 'macro__';
+
+export const MacroToolService = {
+	/**
+	 * List the macros, sorted by name.
+	 */
+	async list(): Promise<ToolMacro[]> {
+		return invoke<ToolMacro[]>('list_macro_tools');
+	},
+
+	/**
+	 * Create a macro (enabled). Step tools and placeholders are validated.
+	 */
+	async create(config: ToolMacroCreate): Promise<ToolMacro> {
+		return invoke<ToolMacro>('create_macro_tool', { config });
+	},
+
+	/**
+	 * Update the description, schema or steps of a macro, or enable/disable it.
+	 */
+	async update(macroId: string, config: ToolMacroUpdate): Promise<ToolMacro> {
+		return invoke<ToolMacro>('update_macro_tool', { macroId, config });
+	},
+
+	/**
+	 * Delete a macro.
+	 */
+	async delete(macroId: string): Promise<void> {
+		return invoke<void>('delete_macro_tool', { macroId });
+	},
+
+	/**
+	 * Tool name given to agents for a macro.
+	 */
+	toolName(name: string): string {
+		return `${MACRO_TOOL_PREFIX}${name}`;
+	}
+};
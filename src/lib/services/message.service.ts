@@ -24,7 +24,8 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { Message, SubAgentSummary } from '$types/message';
+import type { AttachmentInput, ContextUsage, Message, SubAgentSummary } from '$types/message';
+import type { GenerationParams } from '$types/generation';
 import type { SubAgentExecution } from '$types/sub-agent';
 import type { WorkflowMetrics } from '$types/workflow';
 import { getErrorMessage } from '$lib/utils/error';
@@ -38,6 +39,10 @@ interface MessageCreate {
 	role: 'user' | 'assistant' | 'system';
 	content: string;
 	metrics?: WorkflowMetrics;
+	contextUsage?: ContextUsage;
+	behaviorPosition?: number;
+	generation?: GenerationParams;
+	attachments?: AttachmentInput[];
 }
 
 /**
@@ -148,7 +153,11 @@ export const MessageService = {
 			tokensOutput: params.metrics?.tokens_output ?? null,
 			model: params.metrics?.model ?? null,
 			provider: params.metrics?.provider ?? null,
-			durationMs: params.metrics?.duration_ms ?? null
+			durationMs: params.metrics?.duration_ms ?? null,
+			contextUsage: params.contextUsage ?? null,
+			behaviorPosition: params.behaviorPosition ?? null,
+			generation: params.generation ?? null,
+			attachments: params.attachments ?? null
 		});
 	},
 
@@ -157,10 +166,25 @@ export const MessageService = {
 	 *
 	 * @param workflowId - Workflow ID
 	 * @param content - Message content
+	 * @param attachments - Optional images attached to the message
 	 * @returns ID of the saved message
 	 */
-	async saveUser(workflowId: string, content: string): Promise<string> {
-		return this.save({ workflowId, role: 'user', content });
+	async saveUser(
+		workflowId: string,
+		content: string,
+		attachments?: AttachmentInput[]
+	): Promise<string> {
+		return this.save({ workflowId, role: 'user', content, attachments });
+	},
+
+	/**
+	 * Load the content of an attachment for display.
+	 *
+	 * @param attachmentId - Attachment ID
+	 * @returns Data URL of the image
+	 */
+	async getAttachmentData(attachmentId: string): Promise<string> {
+		return invoke<string>('get_attachment_data', { attachmentId });
 	},
 
 	/**
@@ -169,10 +193,31 @@ export const MessageService = {
 	 * @param workflowId - Workflow ID
 	 * @param content - Message content
 	 * @param metrics - Optional workflow execution metrics
+	 * @param contextUsage - Optional context window utilization of the run
+	 * @param behaviorPosition - Optional behavior changelog position of the run
+	 * @param generation - Optional generation parameters used for the response
+	 * @param attachments - Optional images attached to the response (rendered charts)
 	 * @returns ID of the saved message
 	 */
-	async saveAssistant(workflowId: string, content: string, metrics?: WorkflowMetrics): Promise<string> {
-		return this.save({ workflowId, role: 'assistant', content, metrics });
+	async saveAssistant(
+		workflowId: string,
+		content: string,
+		metrics?: WorkflowMetrics,
+		contextUsage?: ContextUsage,
+		behaviorPosition?: number,
+		generation?: GenerationParams,
+		attachments?: AttachmentInput[]
+	): Promise<string> {
+		return this.save({
+			workflowId,
+			role: 'assistant',
+			content,
+			metrics,
+			contextUsage,
+			behaviorPosition,
+			generation,
+			attachments
+		});
 	},
 
 	/**
@@ -193,5 +238,54 @@ export const MessageService = {
 	 */
 	async clear(workflowId: string): Promise<void> {
 		return invoke<void>('clear_workflow_messages', { workflowId });
+	},
+
+	/**
+	 * Pin or unpin a message (pinned messages are always sent to the agent).
+	 *
+	 * @param messageId - Message ID
+	 * @param pinned - Whether the message is pinned
+	 */
+	async pin(messageId: string, pinned: boolean): Promise<void> {
+		return invoke<void>('pin_message', { messageId, pinned });
+	},
+
+	/**
+	 * List the pinned messages of a workflow (oldest first).
+	 *
+	 * @param workflowId - Workflow ID
+	 */
+	async listPinned(workflowId: string): Promise<Message[]> {
+		return invoke<Message[]>('list_pinned_messages', { workflowId });
+	},
+
+	/**
+	 * Bookmark or unbookmark a message.
+	 *
+	 * @param messageId - Message ID
+	 * @param bookmarked - Whether the message is bookmarked
+	 */
+	async bookmark(messageId: string, bookmarked: boolean): Promise<void> {
+		return invoke<void>('bookmark_message', { messageId, bookmarked });
+	},
+
+	/**
+	 * List the bookmarked messages (oldest first).
+	 *
+	 * @param workflowId - Only the bookmarks of this workflow (all workflows if omitted)
+	 */
+	async listBookmarked(workflowId?: string): Promise<Message[]> {
+		return invoke<Message[]>('list_bookmarked_messages', { workflowId: workflowId ?? null });
+	},
+
+	/**
+	 * Add a reaction to a message, or remove it if already present.
+	 *
+	 * @param messageId - Message ID
+	 * @param reaction - Reaction (emoji)
+	 * @returns The reactions of the message
+	 */
+	async toggleReaction(messageId: string, reaction: string): Promise<string[]> {
+		return invoke<string[]>('toggle_message_reaction', { messageId, reaction });
 	}
 };
@@ -0,0 +1,49 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Metrics service: Prometheus endpoint and OTLP push settings.
+ *
+ * @module lib/services/metrics
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { MetricsExportStatus, MetricsSettings } from '$types/metrics';
+
+export const MetricsService = {
+	/**
+	 * Get the metrics export settings.
+	 */
+	async getSettings(): Promise<MetricsSettings> {
+		return invoke<MetricsSettings>('get_metrics_settings');
+	},
+
+	/**
+	 * Save the export settings and (re)start the exporters.
+	 */
+	async updateSettings(settings: MetricsSettings): Promise<MetricsExportStatus> {
+		return invoke<MetricsExportStatus>('update_metrics_settings', { settings });
+	},
+
+	/**
+	 * Get the running exporters.
+	 */
+	async getStatus(): Promise<MetricsExportStatus> {
+		return invoke<MetricsExportStatus>('get_metrics_export_status');
+	}
+};
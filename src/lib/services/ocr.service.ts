@@ -0,0 +1,49 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview OCR service: engine and language pack settings of the OcrTool.
+ *
+ * @module lib/services/ocr
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { OcrSettings } from '$types/ocr';
+
+export const OcrService = {
+	/**
+	 * Get the OCR settings (tesseract with English when never saved).
+	 */
+	async getSettings(): Promise<OcrSettings> {
+		return invoke<OcrSettings>('get_ocr_settings');
+	},
+
+	/**
+	 * Save the OCR settings; returns them trimmed and deduplicated.
+	 */
+	async updateSettings(settings: OcrSettings): Promise<OcrSettings> {
+		return invoke<OcrSettings>('update_ocr_settings', { settings });
+	},
+
+	/**
+	 * List the language packs installed for tesseract (fails when tesseract cannot run).
+	 */
+	async listLanguages(): Promise<string[]> {
+		return invoke<string[]>('list_ocr_languages');
+	}
+};
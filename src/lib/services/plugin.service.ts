@@ -0,0 +1,62 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Plugin service: WASM tool plugins and their capability grants.
+ *
+ * @module lib/services/plugin
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { PluginCapability, PluginInfo } from '$types/plugin';
+
+export const PluginService = {
+	/**
+	 * List the installed plugins with their grants in the active profile.
+	 */
+	async list(): Promise<PluginInfo[]> {
+		return invoke<PluginInfo[]>('list_plugins');
+	},
+
+	/**
+	 * Install a plugin from a directory holding plugin.toml and plugin.wasm,
+	 * granting it the given capabilities. Enables it in the active profile.
+	 */
+	async install(path: string, capabilities: PluginCapability[]): Promise<PluginInfo> {
+		return invoke<PluginInfo>('install_plugin', { path, capabilities });
+	},
+
+	/**
+	 * Enable or disable a plugin and set its granted capabilities; approves the
+	 * plugin as currently installed.
+	 */
+	async setPermissions(
+		id: string,
+		enabled: boolean,
+		capabilities: PluginCapability[]
+	): Promise<PluginInfo> {
+		return invoke<PluginInfo>('set_plugin_permissions', { id, enabled, capabilities });
+	},
+
+	/**
+	 * Remove a plugin.
+	 */
+	async uninstall(id: string): Promise<void> {
+		return invoke<void>('uninstall_plugin', { id });
+	}
+};
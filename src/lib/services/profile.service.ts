@@ -0,0 +1,49 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Profile service: list, create and switch profiles (isolated databases).
+ *
+ * @module lib/services/profile
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { Profile, ProfileRegistry } from '$types/profile';
+
+export const ProfileService = {
+	/**
+	 * List the profiles and the active one.
+	 */
+	async list(): Promise<ProfileRegistry> {
+		return invoke<ProfileRegistry>('list_profiles');
+	},
+
+	/**
+	 * Create a profile with an empty database.
+	 */
+	async create(name: string): Promise<Profile> {
+		return invoke<Profile>('create_profile', { name });
+	},
+
+	/**
+	 * Switch to another profile; the app reloads on the `profile_switched` event.
+	 */
+	async switch(profileId: string): Promise<ProfileRegistry> {
+		return invoke<ProfileRegistry>('switch_profile', { profileId });
+	}
+};
@@ -0,0 +1,42 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Proxy service: proxy of the provider, embedding and MCP requests.
+ *
+ * @module lib/services/proxy
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ProxySettings } from '$types/proxy';
+
+export const ProxyService = {
+	/**
+	 * Get the saved proxy settings (the config file may replace them, see ConfigFileService).
+	 */
+	async getSettings(): Promise<ProxySettings> {
+		return invoke<ProxySettings>('get_proxy_settings');
+	},
+
+	/**
+	 * Save the proxy settings; they apply to the next requests.
+	 */
+	async updateSettings(settings: ProxySettings): Promise<ProxySettings> {
+		return invoke<ProxySettings>('update_proxy_settings', { settings });
+	}
+};
@@ -0,0 +1,98 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Schedule service: cron-triggered workflow runs.
+ *
+ * @module lib/services/schedule
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { SCHEDULE_RUN_EVENT, type Schedule, type ScheduleRunEvent } from '$types/schedule';
+
+export const ScheduleService = {
+	/**
+	 * List the schedules.
+	 */
+	async list(): Promise<Schedule[]> {
+		return invoke<Schedule[]>('list_schedules');
+	},
+
+	/**
+	 * Create a schedule.
+	 *
+	 * @param name - Schedule name
+	 * @param cron - Cron expression (5 fields or @hourly/@daily/@weekly/@monthly/@yearly)
+	 * @param agentId - Agent executing the prompt
+	 * @param prompt - Prompt sent at each run
+	 * @param workflowId - Workflow receiving the runs (a new one is created if omitted)
+	 */
+	async create(
+		name: string,
+		cron: string,
+		agentId: string,
+		prompt: string,
+		workflowId?: string
+	): Promise<Schedule> {
+		return invoke<Schedule>('create_schedule', {
+			name,
+			cron,
+			agentId,
+			prompt,
+			workflowId: workflowId ?? null
+		});
+	},
+
+	/**
+	 * Pause a schedule.
+	 *
+	 * @param scheduleId - Schedule ID
+	 */
+	async pause(scheduleId: string): Promise<Schedule> {
+		return invoke<Schedule>('pause_schedule', { scheduleId });
+	},
+
+	/**
+	 * Resume a paused schedule (the next run is computed from now).
+	 *
+	 * @param scheduleId - Schedule ID
+	 */
+	async resume(scheduleId: string): Promise<Schedule> {
+		return invoke<Schedule>('resume_schedule', { scheduleId });
+	},
+
+	/**
+	 * Delete a schedule (its workflow and messages are kept).
+	 *
+	 * @param scheduleId - Schedule ID
+	 */
+	async delete(scheduleId: string): Promise<void> {
+		return invoke('delete_schedule', { scheduleId });
+	},
+
+	/**
+	 * Listen for finished scheduled runs.
+	 *
+	 * @param handler - Called with the outcome of each run
+	 */
+	async onRun(handler: (event: ScheduleRunEvent) => void): Promise<UnlistenFn> {
+		return listen<ScheduleRunEvent>(SCHEDULE_RUN_EVENT, (event) => handler(event.payload));
+	}
+};
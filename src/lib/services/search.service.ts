@@ -0,0 +1,53 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Full-text search service: find past conversations by keyword.
+ *
+ * @module lib/services/search
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	MessageSearchHit,
+	SearchQuery,
+	SearchResults,
+	WorkflowSearchHit
+} from '$types/search';
+
+export const SearchService = {
+	/**
+	 * Search message contents, optionally within one workflow.
+	 */
+	async searchMessages(
+		query: SearchQuery,
+		workflowId?: string
+	): Promise<SearchResults<MessageSearchHit>> {
+		return invoke<SearchResults<MessageSearchHit>>('search_messages', {
+			query,
+			workflowId
+		});
+	},
+
+	/**
+	 * Search workflow names.
+	 */
+	async searchWorkflows(query: SearchQuery): Promise<SearchResults<WorkflowSearchHit>> {
+		return invoke<SearchResults<WorkflowSearchHit>>('search_workflows', { query });
+	}
+};
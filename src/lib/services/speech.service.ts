@@ -0,0 +1,371 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Speech service: voice recording and local transcription.
+ *
+ * @module lib/services/speech
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type {
+	ReadAloud,
+	SpeechAudioChunk,
+	SpeechSettings,
+	SpeechSynthesis,
+	Transcription,
+	TtsSettings,
+	VoiceSettings
+} from '$types/speech';
+
+/** Sample rate expected by Whisper models */
+const WHISPER_SAMPLE_RATE = 16000;
+
+/**
+ * Records a voice message from the microphone.
+ *
+ * @example
+ * const recorder = new VoiceRecorder();
+ * await recorder.start();
+ * const audio = await recorder.stop();
+ * const { text } = await SpeechService.transcribe(audio);
+ */
+export class VoiceRecorder {
+	private recorder: MediaRecorder | null = null;
+	private chunks: Blob[] = [];
+
+	/** Whether a recording is in progress */
+	get recording(): boolean {
+		return this.recorder?.state === 'recording';
+	}
+
+	/**
+	 * Start recording (asks for microphone permission on first use).
+	 */
+	async start(): Promise<void> {
+		const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
+		this.chunks = [];
+		this.recorder = new MediaRecorder(stream);
+		this.recorder.ondataavailable = (event) => {
+			if (event.data.size > 0) this.chunks.push(event.data);
+		};
+		this.recorder.start();
+	}
+
+	/**
+	 * Stop recording and release the microphone.
+	 *
+	 * @returns The recording, as a 16 kHz mono WAV file
+	 */
+	async stop(): Promise<Blob> {
+		const recorder = this.recorder;
+		if (!recorder) throw new Error('No recording in progress');
+
+		const stopped = new Promise<void>((resolve) => {
+			recorder.onstop = () => resolve();
+		});
+		recorder.stop();
+		await stopped;
+		recorder.stream.getTracks().forEach((track) => track.stop());
+		this.recorder = null;
+
+		const recording = new Blob(this.chunks, { type: recorder.mimeType });
+		this.chunks = [];
+		return toWav(recording);
+	}
+
+	/**
+	 * Stop recording and discard it.
+	 */
+	cancel(): void {
+		if (!this.recorder) return;
+		this.recorder.onstop = null;
+		this.recorder.stop();
+		this.recorder.stream.getTracks().forEach((track) => track.stop());
+		this.recorder = null;
+		this.chunks = [];
+	}
+}
+
+/**
+ * Plays a reply while it is synthesized.
+ *
+ * Audio chunks of `speech_audio` events are collected per segment; each
+ * segment is played as soon as it is complete, in order.
+ *
+ * @example
+ * const player = new SpeechPlayer();
+ * await player.speak(message.content, message.workflow_id);
+ * player.stop();
+ */
+export class SpeechPlayer {
+	private requestId: string | null = null;
+	private unlisten: UnlistenFn | null = null;
+	private chunks = new Map<number, Uint8Array[]>();
+	private queue: Blob[] = [];
+	private audio: HTMLAudioElement | null = null;
+	private synthesisDone = false;
+	private finished: (() => void) | null = null;
+
+	/** Whether a reply is being synthesized or played */
+	get speaking(): boolean {
+		return this.requestId !== null;
+	}
+
+	/**
+	 * Speak a text and resolve when playback ends (or is stopped).
+	 *
+	 * @param text - Text to speak (Markdown accepted)
+	 * @param workflowId - Workflow whose voice applies
+	 */
+	async speak(text: string, workflowId?: string): Promise<void> {
+		this.stop();
+		const requestId = crypto.randomUUID();
+		this.requestId = requestId;
+		this.synthesisDone = false;
+		const played = new Promise<void>((resolve) => {
+			this.finished = resolve;
+		});
+
+		this.unlisten = await listen<SpeechAudioChunk>('speech_audio', (event) => {
+			if (event.payload.request_id === requestId) this.handleChunk(event.payload);
+		});
+		try {
+			await invoke<SpeechSynthesis>('synthesize_speech', {
+				requestId,
+				text,
+				workflowId: workflowId ?? null
+			});
+		} catch (error) {
+			this.stop();
+			throw error;
+		}
+		await played;
+	}
+
+	/**
+	 * Stop synthesis and playback.
+	 */
+	stop(): void {
+		if (this.requestId) {
+			invoke('stop_speech', { requestId: this.requestId }).catch(() => {});
+		}
+		this.unlisten?.();
+		this.unlisten = null;
+		this.requestId = null;
+		this.chunks.clear();
+		this.queue = [];
+		if (this.audio) {
+			this.audio.pause();
+			URL.revokeObjectURL(this.audio.src);
+			this.audio = null;
+		}
+		this.finished?.();
+		this.finished = null;
+	}
+
+	private handleChunk(chunk: SpeechAudioChunk): void {
+		switch (chunk.kind) {
+			case 'audio': {
+				const bytes = Uint8Array.from(atob(chunk.data ?? ''), (c) => c.charCodeAt(0));
+				const segment = this.chunks.get(chunk.segment) ?? [];
+				segment.push(bytes);
+				this.chunks.set(chunk.segment, segment);
+				break;
+			}
+			case 'segment_end':
+				this.queue.push(
+					new Blob((this.chunks.get(chunk.segment) ?? []) as BlobPart[], {
+						type: chunk.mime_type
+					})
+				);
+				this.chunks.delete(chunk.segment);
+				if (!this.audio) this.playNext();
+				break;
+			case 'done':
+			case 'error':
+				this.synthesisDone = true;
+				if (!this.audio) this.playNext();
+				break;
+		}
+	}
+
+	private playNext(): void {
+		const next = this.queue.shift();
+		if (!next) {
+			if (this.synthesisDone) this.stop();
+			return;
+		}
+		const audio = new Audio(URL.createObjectURL(next));
+		this.audio = audio;
+		const advance = () => {
+			URL.revokeObjectURL(audio.src);
+			if (this.audio !== audio) return;
+			this.audio = null;
+			this.playNext();
+		};
+		audio.onended = advance;
+		audio.onerror = advance;
+		audio.play().catch(advance);
+	}
+}
+
+/**
+ * Convert a recording to a 16 kHz mono 16-bit WAV file.
+ *
+ * MediaRecorder produces WebM/Ogg, which the whisper.cpp server only
+ * accepts when built with ffmpeg: WAV works with every server.
+ */
+async function toWav(recording: Blob): Promise<Blob> {
+	const context = new AudioContext();
+	let decoded: AudioBuffer;
+	try {
+		decoded = await context.decodeAudioData(await recording.arrayBuffer());
+	} finally {
+		await context.close();
+	}
+
+	const length = Math.ceil(decoded.duration * WHISPER_SAMPLE_RATE);
+	const offline = new OfflineAudioContext(1, Math.max(length, 1), WHISPER_SAMPLE_RATE);
+	const source = offline.createBufferSource();
+	source.buffer = decoded;
+	source.connect(offline.destination);
+	source.start();
+	const samples = (await offline.startRendering()).getChannelData(0);
+
+	const buffer = new ArrayBuffer(44 + samples.length * 2);
+	const view = new DataView(buffer);
+	const writeString = (offset: number, value: string) => {
+		for (let i = 0; i < value.length; i++) view.setUint8(offset + i, value.charCodeAt(i));
+	};
+	writeString(0, 'RIFF');
+	view.setUint32(4, 36 + samples.length * 2, true);
+	writeString(8, 'WAVE');
+	writeString(12, 'fmt ');
+	view.setUint32(16, 16, true);
+	view.setUint16(20, 1, true); // PCM
+	view.setUint16(22, 1, true); // mono
+	view.setUint32(24, WHISPER_SAMPLE_RATE, true);
+	view.setUint32(28, WHISPER_SAMPLE_RATE * 2, true);
+	view.setUint16(32, 2, true);
+	view.setUint16(34, 16, true);
+	writeString(36, 'data');
+	view.setUint32(40, samples.length * 2, true);
+	samples.forEach((sample, i) => {
+		const clamped = Math.max(-1, Math.min(1, sample));
+		view.setInt16(44 + i * 2, clamped < 0 ? clamped * 0x8000 : clamped * 0x7fff, true);
+	});
+
+	return new Blob([buffer], { type: 'audio/wav' });
+}
+
+/**
+ * Encode a blob as base64 (without data URL prefix).
+ */
+async function toBase64(blob: Blob): Promise<string> {
+	const bytes = new Uint8Array(await blob.arrayBuffer());
+	let binary = '';
+	for (let i = 0; i < bytes.length; i += 0x8000) {
+		binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000));
+	}
+	return btoa(binary);
+}
+
+/**
+ * Service for voice message transcription.
+ */
+export const SpeechService = {
+	/**
+	 * Transcribe a voice message with the local Whisper server.
+	 *
+	 * @param audio - Recording (WAV, WebM, Ogg, MP3, M4A or FLAC)
+	 * @param language - Spoken language (ISO 639-1 code), defaults to the settings
+	 * @returns The transcription, to send as a user message
+	 */
+	async transcribe(audio: Blob, language?: string): Promise<Transcription> {
+		return invoke<Transcription>('transcribe_audio', {
+			audio: await toBase64(audio),
+			language: language ?? null
+		});
+	},
+
+	/**
+	 * Get the read-aloud version of a message (plain text and SSML) for text-to-speech.
+	 *
+	 * @param messageId - Message ID
+	 * @returns The read-aloud text, prepared on first request
+	 */
+	async getReadAloud(messageId: string): Promise<ReadAloud> {
+		return invoke<ReadAloud>('get_read_aloud', { messageId });
+	},
+
+	/**
+	 * Get the transcription server settings.
+	 */
+	async getSettings(): Promise<SpeechSettings> {
+		return invoke<SpeechSettings>('get_speech_settings');
+	},
+
+	/**
+	 * Update the transcription server settings.
+	 *
+	 * @param settings - Server API, endpoint, model and default language
+	 * @returns The stored settings
+	 */
+	async updateSettings(settings: SpeechSettings): Promise<SpeechSettings> {
+		return invoke<SpeechSettings>('update_speech_settings', { settings });
+	},
+
+	/**
+	 * Get the text-to-speech settings.
+	 */
+	async getTtsSettings(): Promise<TtsSettings> {
+		return invoke<TtsSettings>('get_tts_settings');
+	},
+
+	/**
+	 * Update the text-to-speech settings.
+	 *
+	 * @param settings - Backend, endpoint, model, default voice and speaking rate
+	 * @returns The stored settings
+	 */
+	async updateTtsSettings(settings: TtsSettings): Promise<TtsSettings> {
+		return invoke<TtsSettings>('update_tts_settings', { settings });
+	},
+
+	/**
+	 * Get the voice settings of a workflow.
+	 *
+	 * @param workflowId - Workflow ID
+	 */
+	async getWorkflowVoice(workflowId: string): Promise<VoiceSettings> {
+		return invoke<VoiceSettings>('get_workflow_voice', { workflowId });
+	},
+
+	/**
+	 * Set the voice of a workflow (null restores the defaults).
+	 *
+	 * @param workflowId - Workflow ID
+	 * @param voice - Voice and speaking rate
+	 */
+	async setWorkflowVoice(workflowId: string, voice: VoiceSettings | null): Promise<void> {
+		return invoke<void>('set_workflow_voice', { workflowId, voice });
+	}
+};
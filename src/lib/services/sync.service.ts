@@ -0,0 +1,85 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Settings sync service: pairing and syncing with another machine.
+ *
+ * @module lib/services/sync
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { SyncPairing, SyncPairingOffer, SyncResult, SyncTransport } from '$types/sync';
+
+export const SyncService = {
+	/**
+	 * Create a pairing and its code, to enter on the other machine.
+	 *
+	 * @param deviceName - Name of this machine
+	 * @param transport - Mailbox relay or this machine on the local network
+	 * @param relayUrl - Relay server URL (relay transport only)
+	 * @returns The pairing with its code and QR code
+	 */
+	async createPairing(
+		deviceName: string,
+		transport: SyncTransport,
+		relayUrl?: string
+	): Promise<SyncPairingOffer> {
+		return invoke<SyncPairingOffer>('create_sync_pairing', {
+			deviceName,
+			transport,
+			relayUrl: relayUrl ?? null
+		});
+	},
+
+	/**
+	 * Accept a pairing code created on the other machine.
+	 *
+	 * @param code - Pairing code
+	 * @param deviceName - Name of this machine
+	 */
+	async acceptPairing(code: string, deviceName: string): Promise<SyncPairing> {
+		return invoke<SyncPairing>('accept_sync_pairing', { code, deviceName });
+	},
+
+	/**
+	 * List the pairings of this machine.
+	 */
+	async listPairings(): Promise<SyncPairing[]> {
+		return invoke<SyncPairing[]>('list_sync_pairings');
+	},
+
+	/**
+	 * Sync settings with the peer of a pairing.
+	 *
+	 * @param pairingId - Pairing ID
+	 * @returns Entities sent and received
+	 */
+	async syncNow(pairingId: string): Promise<SyncResult> {
+		return invoke<SyncResult>('sync_settings_now', { pairingId });
+	},
+
+	/**
+	 * Delete a pairing and its key.
+	 *
+	 * @param pairingId - Pairing ID
+	 */
+	async deletePairing(pairingId: string): Promise<void> {
+		return invoke<void>('delete_sync_pairing', { pairingId });
+	}
+};
@@ -0,0 +1,39 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Transcript service: export a workflow as Markdown, HTML or PDF.
+ *
+ * @module lib/services/transcript
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { TranscriptOptions } from '$types/transcript';
+
+export const TranscriptService = {
+	/**
+	 * Write the transcript of a workflow to `path`. Returns the file size in bytes.
+	 */
+	async exportWorkflowTranscript(
+		workflowId: string,
+		path: string,
+		options: TranscriptOptions
+	): Promise<number> {
+		return invoke<number>('export_workflow_transcript', { workflowId, path, options });
+	}
+};
@@ -0,0 +1,42 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Team usage statistics service.
+ *
+ * @module lib/services/usageStats
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { TeamUsageReport } from '$types/usageStats';
+
+export const UsageStatsService = {
+	/**
+	 * Generate the team usage report (noised weekly aggregates, generated locally).
+	 *
+	 * @param days - Number of days covered (default 30, max 365)
+	 * @param epsilon - Privacy budget of each bucket (default 1.0, lower is noisier)
+	 */
+	async exportTeamUsage(days?: number, epsilon?: number): Promise<TeamUsageReport> {
+		return invoke<TeamUsageReport>('export_team_usage', {
+			days: days ?? null,
+			epsilon: epsilon ?? null
+		});
+	}
+};
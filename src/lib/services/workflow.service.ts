@@ -24,7 +24,20 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
-import type { Workflow, WorkflowResult, WorkflowFullState } from '$types/workflow';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import {
+	WORKFLOW_TITLE_UPDATED_EVENT,
+	type BulkTagResult,
+	type PaginatedWorkflows,
+	type Workflow,
+	type WorkflowListQuery,
+	type WorkflowResult,
+	type WorkflowFullState,
+	type WorkflowSnapshot,
+	type WorkflowTag,
+	type WorkflowTitleUpdatedEvent
+} from '$types/workflow';
+import type { GenerationParams } from '$types/generation';
 import type { RestorationResult } from '$types/services';
 
 /**
@@ -42,6 +55,16 @@ export const WorkflowService = {
 		return invoke<Workflow[]>('load_workflows');
 	},
 
+	/**
+	 * Load a page of workflows, sorted and filtered by the backend.
+	 *
+	 * @param query - Sort key, filters and pagination
+	 * @returns Workflows of the page with pagination metadata
+	 */
+	async loadPage(query: WorkflowListQuery = {}): Promise<PaginatedWorkflows> {
+		return invoke<PaginatedWorkflows>('load_workflows_paginated', { query });
+	},
+
 	/**
 	 * Create a new workflow.
 	 *
@@ -64,6 +87,168 @@ export const WorkflowService = {
 		return invoke<Workflow>('rename_workflow', { workflowId, name });
 	},
 
+	/**
+	 * Generate the workflow name again from its first exchange.
+	 *
+	 * @param workflowId - Workflow ID
+	 * @returns The new workflow name
+	 */
+	async regenerateTitle(workflowId: string): Promise<string> {
+		return invoke<string>('regenerate_title', { workflowId });
+	},
+
+	/**
+	 * Listen for generated workflow titles.
+	 *
+	 * @param handler - Called with each generated title
+	 */
+	async onTitleUpdated(handler: (event: WorkflowTitleUpdatedEvent) => void): Promise<UnlistenFn> {
+		return listen<WorkflowTitleUpdatedEvent>(WORKFLOW_TITLE_UPDATED_EVENT, (event) =>
+			handler(event.payload)
+		);
+	},
+
+	/**
+	 * Pin the response language of a workflow.
+	 *
+	 * Overrides the language detected from the user messages.
+	 *
+	 * @param workflowId - Workflow ID to update
+	 * @param language - Language code (e.g., "en", "fr"), or null to detect it again
+	 */
+	async setLanguage(workflowId: string, language: string | null): Promise<void> {
+		return invoke<void>('set_workflow_language', { workflowId, language });
+	},
+
+	/**
+	 * Set the tags of a workflow.
+	 *
+	 * @param workflowId - Workflow ID to update
+	 * @param tags - Tags (trimmed, lowercased and deduplicated by the backend)
+	 * @returns The stored tags
+	 */
+	async setTags(workflowId: string, tags: string[]): Promise<string[]> {
+		return invoke<string[]>('set_workflow_tags', { workflowId, tags });
+	},
+
+	/**
+	 * List the tag catalog with the number of workflows per tag.
+	 *
+	 * @returns Tags sorted by name
+	 */
+	async listTags(): Promise<WorkflowTag[]> {
+		return invoke<WorkflowTag[]>('list_workflow_tags');
+	},
+
+	/**
+	 * Create a tag in the catalog.
+	 *
+	 * @param name - Tag name
+	 * @param color - Optional display color (`#rrggbb`)
+	 * @returns The created tag
+	 */
+	async createTag(name: string, color?: string | null): Promise<WorkflowTag> {
+		return invoke<WorkflowTag>('create_workflow_tag', { name, color: color ?? null });
+	},
+
+	/**
+	 * Rename a tag in the catalog and in every workflow carrying it.
+	 * Renaming onto an existing tag merges both.
+	 *
+	 * @param name - Current tag name
+	 * @param newName - New tag name
+	 * @returns The renamed tag
+	 */
+	async renameTag(name: string, newName: string): Promise<WorkflowTag> {
+		return invoke<WorkflowTag>('rename_workflow_tag', { name, newName });
+	},
+
+	/**
+	 * Set or clear the display color of a tag.
+	 *
+	 * @param name - Tag name
+	 * @param color - Display color, or null to clear it
+	 * @returns The updated tag
+	 */
+	async setTagColor(name: string, color: string | null): Promise<WorkflowTag> {
+		return invoke<WorkflowTag>('set_workflow_tag_color', { name, color });
+	},
+
+	/**
+	 * Delete a tag from the catalog and from every workflow carrying it.
+	 *
+	 * @param name - Tag name
+	 * @returns Number of workflows untagged
+	 */
+	async deleteTag(name: string): Promise<number> {
+		return invoke<number>('delete_workflow_tag', { name });
+	},
+
+	/**
+	 * Add tags to several workflows at once.
+	 *
+	 * @param workflowIds - Workflows to tag
+	 * @param tags - Tags to add
+	 * @returns Number of workflows updated and the IDs skipped
+	 */
+	async addTags(workflowIds: string[], tags: string[]): Promise<BulkTagResult> {
+		return invoke<BulkTagResult>('add_workflow_tags', { workflowIds, tags });
+	},
+
+	/**
+	 * Remove tags from several workflows at once.
+	 *
+	 * @param workflowIds - Workflows to untag
+	 * @param tags - Tags to remove
+	 * @returns Number of workflows updated and the IDs skipped
+	 */
+	async removeTags(workflowIds: string[], tags: string[]): Promise<BulkTagResult> {
+		return invoke<BulkTagResult>('remove_workflow_tags', { workflowIds, tags });
+	},
+
+	/**
+	 * Mark a workflow as sensitive (encrypted contents) or regular.
+	 *
+	 * @param workflowId - Workflow ID to update
+	 * @param sensitive - Whether the workflow is sensitive
+	 */
+	async setSensitive(workflowId: string, sensitive: boolean): Promise<void> {
+		return invoke<void>('set_workflow_sensitive', { workflowId, sensitive });
+	},
+
+	/**
+	 * Enable or disable the dry run mode of a workflow.
+	 *
+	 * In dry run, tools with side effects return a simulated result describing
+	 * what they would have done instead of executing.
+	 *
+	 * @param workflowId - Workflow ID to update
+	 * @param dryRun - Whether the workflow runs in dry run mode
+	 */
+	async setDryRun(workflowId: string, dryRun: boolean): Promise<void> {
+		return invoke<void>('set_workflow_dry_run', { workflowId, dryRun });
+	},
+
+	/**
+	 * Open a sensitive workflow (loads its encryption key).
+	 *
+	 * @param workflowId - Workflow ID to open
+	 * @returns Whether the workflow is sensitive
+	 */
+	async unlock(workflowId: string): Promise<boolean> {
+		return invoke<boolean>('unlock_workflow', { workflowId });
+	},
+
+	/**
+	 * Close a sensitive workflow (drops its encryption key from memory).
+	 *
+	 * @param workflowId - Workflow ID to close
+	 * @returns Whether the workflow is locked (a running workflow stays open)
+	 */
+	async lock(workflowId: string): Promise<boolean> {
+		return invoke<boolean>('lock_workflow', { workflowId });
+	},
+
 	/**
 	 * Delete a workflow.
 	 *
@@ -80,19 +265,22 @@ export const WorkflowService = {
 	 * @param message - User message to process
 	 * @param agentId - Agent ID to use for execution
 	 * @param locale - User's selected language (e.g., "en", "fr")
+	 * @param generation - Optional generation parameters overriding the agent's
 	 * @returns Workflow result with metrics and report
 	 */
 	async executeStreaming(
 		workflowId: string,
 		message: string,
 		agentId: string,
-		locale: string
+		locale: string,
+		generation?: GenerationParams
 	): Promise<WorkflowResult> {
 		return invoke<WorkflowResult>('execute_workflow_streaming', {
 			workflowId,
 			message,
 			agentId,
-			locale
+			locale,
+			generation: generation ?? null
 		});
 	},
 
@@ -115,6 +303,27 @@ export const WorkflowService = {
 		return invoke<WorkflowFullState>('get_workflow_full_state', { workflowId });
 	},
 
+	/**
+	 * Create a read-only HTML snapshot of a workflow.
+	 *
+	 * @param workflowId - Workflow ID to render
+	 * @param serve - Also serve the page on a temporary localhost URL
+	 * @returns Snapshot with the HTML page (and URL when served)
+	 */
+	async createSnapshot(workflowId: string, serve = false): Promise<WorkflowSnapshot> {
+		return invoke<WorkflowSnapshot>('create_workflow_snapshot', { workflowId, serve });
+	},
+
+	/**
+	 * Stop the temporary snapshot server of a workflow.
+	 *
+	 * @param workflowId - Workflow ID
+	 * @returns True if a server was running
+	 */
+	async stopSnapshotServer(workflowId: string): Promise<boolean> {
+		return invoke<boolean>('stop_workflow_snapshot_server', { workflowId });
+	},
+
 	/**
 	 * Restore a workflow state from database.
 	 *
@@ -36,7 +36,8 @@
  * @module lib/services/workflowExecutor
  */
 
-import type { Message, SubAgentSummary } from '$types/message';
+import type { GenerationParams } from '$types/generation';
+import type { AttachmentInput, Message, SubAgentSummary } from '$types/message';
 import type { Workflow, WorkflowMetrics, WorkflowResult } from '$types/workflow';
 import { MessageService } from './message.service';
 import { WorkflowService } from './workflow.service';
@@ -61,6 +62,10 @@ export interface ExecutionParams {
 	agentId: string;
 	/** User's selected locale (e.g., "en", "fr") */
 	locale: string;
+	/** Images attached to the message */
+	attachments?: AttachmentInput[];
+	/** Generation parameters overriding the agent's for this message */
+	generation?: GenerationParams;
 }
 
 /**
@@ -134,6 +139,9 @@ function createAssistantMessage(workflowId: string, result: WorkflowResult): Mes
 		model: result.metrics.model,
 		provider: result.metrics.provider,
 		duration_ms: result.metrics.duration_ms,
+		context_usage: result.context_usage,
+		behavior_position: result.behavior_position,
+		generation: result.generation,
 		timestamp: new Date()
 	};
 }
@@ -202,7 +210,7 @@ export const WorkflowExecutorService = {
 	 * ```
 	 */
 	async execute(params: ExecutionParams, callbacks?: ExecutionCallbacks): Promise<ExecutionResult> {
-		const { workflowId, message, agentId, locale } = params;
+		const { workflowId, message, agentId, locale, attachments, generation } = params;
 
 		// Check concurrent limit before starting
 		if (!backgroundWorkflowsStore.canStart()) {
@@ -226,7 +234,7 @@ export const WorkflowExecutorService = {
 
 		try {
 			// Step 1: Save user message
-			const userMessageId = await MessageService.saveUser(workflowId, message);
+			const userMessageId = await MessageService.saveUser(workflowId, message, attachments);
 			const userMessage = createUserMessage(workflowId, message);
 			callbacks?.onUserMessage?.(userMessage);
 
@@ -247,7 +255,8 @@ export const WorkflowExecutorService = {
 				workflowId,
 				message,
 				agentId,
-				locale
+				locale,
+				generation
 			);
 
 			// Post-execution: user may have switched to a different workflow.
@@ -261,11 +270,29 @@ export const WorkflowExecutorService = {
 			}
 			callbacks?.onTokenUpdate?.(workflowResult.metrics);
 
-			// Step 5: Save assistant response (always persist to DB)
+			// Step 4.5: Surface a flagged response kept by the agent's moderation policy
+			const moderation = workflowResult.moderation;
+			if (moderation?.flagged && moderation.action === 'warn') {
+				toastStore.add({
+					type: 'warning',
+					title: t('toast_moderation_flagged_title'),
+					message: t('toast_moderation_flagged_message', {
+						categories: moderation.categories.join(', ')
+					}),
+					persistent: false,
+					duration: 8000
+				});
+			}
+
+			// Step 5: Save assistant response with its tool images (always persist to DB)
 			const assistantMessageId = await MessageService.saveAssistant(
 				workflowId,
 				workflowResult.response,
-				workflowResult.metrics
+				workflowResult.metrics,
+				workflowResult.context_usage,
+				workflowResult.behavior_position,
+				workflowResult.generation,
+				workflowResult.images?.map((path) => ({ path }))
 			);
 			// Only push to UI if still viewing this workflow
 			if (isStillViewed()) {
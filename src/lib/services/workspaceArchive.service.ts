@@ -0,0 +1,50 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * @fileoverview Workspace archive service: export and import conversations
+ * and memories as a single file.
+ *
+ * @module lib/services/workspaceArchive
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type {
+	WorkspaceArchiveManifest,
+	WorkspaceExportOptions,
+	WorkspaceImportResult
+} from '$types/importExport';
+
+export const WorkspaceArchiveService = {
+	/**
+	 * Write a workspace archive to `path`. Sensitive workflows are left out.
+	 */
+	async exportWorkspace(
+		path: string,
+		options: WorkspaceExportOptions
+	): Promise<WorkspaceArchiveManifest> {
+		return invoke<WorkspaceArchiveManifest>('export_workspace', { path, options });
+	},
+
+	/**
+	 * Import the workspace archive at `path`. Existing records are never overwritten.
+	 */
+	async importWorkspace(path: string): Promise<WorkspaceImportResult> {
+		return invoke<WorkspaceImportResult>('import_workspace', { path });
+	}
+};
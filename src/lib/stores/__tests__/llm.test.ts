@@ -72,6 +72,10 @@ describe('LLM Store', () => {
 		is_reasoning: false,
 		input_price_per_mtok: 0,
 		output_price_per_mtok: 0,
+		supports_vision: null,
+		supports_tools: null,
+		metadata_drift: [],
+		metadata_refreshed_at: null,
 		created_at: new Date().toISOString(),
 		updated_at: new Date().toISOString()
 	});
@@ -115,6 +115,22 @@ describe('streamingStore', () => {
 			expect(completed[0].name).toBe('MemoryTool');
 		});
 
+		it('should track MCP tool progress', () => {
+			streamingStore.addToolStart('mcp__docs__index');
+			streamingStore.processChunkDirect({
+				workflow_id: 'wf-1',
+				chunk_type: 'tool_progress',
+				tool: 'mcp__docs__index',
+				progress: 40,
+				content: 'Indexing files'
+			});
+
+			const tools = get(activeTools);
+			expect(tools[0].status).toBe('running');
+			expect(tools[0].progress).toBe(40);
+			expect(tools[0].statusMessage).toBe('Indexing files');
+		});
+
 		it('should fail tool with error', () => {
 			streamingStore.addToolStart('MemoryTool');
 			streamingStore.failTool('MemoryTool', 'Connection failed');
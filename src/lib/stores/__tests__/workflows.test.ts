@@ -28,9 +28,11 @@ import {
 	workflowSearchFilter,
 	selectedWorkflow,
 	filteredWorkflows,
+	workflowListFilters,
+	workflowsHasMore,
 	type WorkflowState
 } from '../workflows';
-import type { Workflow } from '$types/workflow';
+import type { PaginatedWorkflows, Workflow } from '$types/workflow';
 
 // Mock @tauri-apps/api/core
 vi.mock('@tauri-apps/api/core', () => ({
@@ -54,6 +56,14 @@ describe('Workflow Store', () => {
 		current_context_tokens: 0
 	});
 
+	const pageOf = (items: Workflow[], total = items.length, offset = 0): PaginatedWorkflows => ({
+		workflows: items,
+		total,
+		offset,
+		limit: 50,
+		has_more: offset + items.length < total
+	});
+
 	beforeEach(() => {
 		workflowStore.reset();
 		vi.resetAllMocks();
@@ -62,7 +72,7 @@ describe('Workflow Store', () => {
 	describe('workflowStore.loadWorkflows', () => {
 		it('should load workflows from backend', async () => {
 			const mockWorkflows = [createMockWorkflow('wf1', 'Test Workflow')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 
 			await workflowStore.loadWorkflows();
 
@@ -77,7 +87,7 @@ describe('Workflow Store', () => {
 				() =>
 					new Promise((resolve) => {
 						expect(get(workflowsLoading)).toBe(true);
-						resolve(mockWorkflows);
+						resolve(pageOf(mockWorkflows));
 					})
 			);
 
@@ -113,7 +123,7 @@ describe('Workflow Store', () => {
 
 			// Second load succeeds
 			const mockWorkflows = [createMockWorkflow('wf1', 'Test')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			expect(get(workflowsError)).toBeNull();
@@ -121,6 +131,60 @@ describe('Workflow Store', () => {
 		});
 	});
 
+	describe('workflowStore pagination', () => {
+		it('should load the first page with the list filters', async () => {
+			vi.mocked(invoke).mockResolvedValue(pageOf([createMockWorkflow('wf1', 'A')], 120));
+
+			await workflowStore.loadWorkflows();
+
+			expect(invoke).toHaveBeenCalledWith('load_workflows_paginated', {
+				query: { sort_by: 'last_activity', ascending: false, limit: 50, offset: 0 }
+			});
+			expect(get(workflowsHasMore)).toBe(true);
+		});
+
+		it('should append the next page', async () => {
+			vi.mocked(invoke).mockResolvedValue(pageOf([createMockWorkflow('wf1', 'A')], 2));
+			await workflowStore.loadWorkflows();
+
+			vi.mocked(invoke).mockResolvedValue(
+				pageOf([createMockWorkflow('wf1', 'A'), createMockWorkflow('wf2', 'B')], 2, 1)
+			);
+			await workflowStore.loadMore();
+
+			expect(invoke).toHaveBeenLastCalledWith('load_workflows_paginated', {
+				query: { sort_by: 'last_activity', ascending: false, limit: 50, offset: 1 }
+			});
+			// Duplicates shifted into the page are skipped
+			expect(get(workflows).map((w) => w.id)).toEqual(['wf1', 'wf2']);
+			expect(get(workflowsHasMore)).toBe(false);
+		});
+
+		it('should not load more when all workflows are loaded', async () => {
+			vi.mocked(invoke).mockResolvedValue(pageOf([createMockWorkflow('wf1', 'A')]));
+			await workflowStore.loadWorkflows();
+
+			await workflowStore.loadMore();
+
+			expect(invoke).toHaveBeenCalledTimes(1);
+		});
+
+		it('should reload the first page when filters change', async () => {
+			vi.mocked(invoke).mockResolvedValue(pageOf([createMockWorkflow('wf1', 'A')]));
+
+			await workflowStore.setListFilters({ sort_by: 'cost', status: 'completed' });
+
+			expect(get(workflowListFilters)).toEqual({
+				sort_by: 'cost',
+				ascending: false,
+				status: 'completed'
+			});
+			expect(invoke).toHaveBeenLastCalledWith('load_workflows_paginated', {
+				query: { sort_by: 'cost', ascending: false, status: 'completed', limit: 50, offset: 0 }
+			});
+		});
+	});
+
 	describe('workflowStore.select', () => {
 		it('should select a workflow by ID', () => {
 			workflowStore.select('wf1');
@@ -175,7 +239,7 @@ describe('Workflow Store', () => {
 				createMockWorkflow('wf1', 'Workflow 1'),
 				createMockWorkflow('wf2', 'Workflow 2')
 			];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			workflowStore.select('wf1');
@@ -187,7 +251,7 @@ describe('Workflow Store', () => {
 
 		it('should return undefined when nothing selected', async () => {
 			const mockWorkflows = [createMockWorkflow('wf1', 'Workflow 1')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			const selected = workflowStore.getSelected();
@@ -196,7 +260,7 @@ describe('Workflow Store', () => {
 
 		it('should return undefined when selected workflow does not exist', async () => {
 			const mockWorkflows = [createMockWorkflow('wf1', 'Workflow 1')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			workflowStore.select('nonexistent');
@@ -210,7 +274,7 @@ describe('Workflow Store', () => {
 		it('should reset to initial state', async () => {
 			// Load some workflows
 			const mockWorkflows = [createMockWorkflow('wf1', 'Test')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			// Select a workflow and set filter
@@ -244,7 +308,7 @@ describe('Workflow Store', () => {
 				createMockWorkflow('wf1', 'Workflow 1'),
 				createMockWorkflow('wf2', 'Workflow 2')
 			];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			workflowStore.select('wf2');
@@ -256,7 +320,7 @@ describe('Workflow Store', () => {
 
 		it('should return null when nothing selected', async () => {
 			const mockWorkflows = [createMockWorkflow('wf1', 'Workflow 1')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			const selected = get(selectedWorkflow);
@@ -265,7 +329,7 @@ describe('Workflow Store', () => {
 
 		it('should return null when selected workflow does not exist', async () => {
 			const mockWorkflows = [createMockWorkflow('wf1', 'Workflow 1')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			workflowStore.select('nonexistent');
@@ -283,7 +347,7 @@ describe('Workflow Store', () => {
 		];
 
 		beforeEach(async () => {
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 		});
 
@@ -338,7 +402,7 @@ describe('Workflow Store', () => {
 	describe('derived stores: basic getters', () => {
 		it('should expose workflows array', async () => {
 			const mockWorkflows = [createMockWorkflow('wf1', 'Test')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			expect(get(workflows)).toEqual(mockWorkflows);
@@ -385,7 +449,7 @@ describe('Workflow Store', () => {
 
 			// Load workflows
 			const mockWorkflows = [createMockWorkflow('wf1', 'Test')];
-			vi.mocked(invoke).mockResolvedValue(mockWorkflows);
+			vi.mocked(invoke).mockResolvedValue(pageOf(mockWorkflows));
 			await workflowStore.loadWorkflows();
 
 			// Should have captured state changes
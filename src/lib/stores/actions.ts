@@ -0,0 +1,41 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Command palette actions.
+ *
+ * The list of invocable actions comes from the backend registry, so the
+ * palette stays in sync with the features and user data (prompt templates,
+ * agents) that actually exist.
+ * @module stores/actions
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import type { ActionMatch } from '$types/action';
+
+/**
+ * Searches the command palette actions
+ * @param query - Fuzzy query (empty returns every action)
+ * @param limit - Maximum number of results (backend default: 20)
+ * @returns Promise resolving to the matching actions, best first
+ */
+export async function searchActions(query: string, limit?: number): Promise<ActionMatch[]> {
+	return invoke<ActionMatch[]>('search_actions', { query, limit: limit ?? null });
+}
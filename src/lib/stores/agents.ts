@@ -20,6 +20,7 @@
  * @module stores/agents
  */
 
+import { invoke } from '@tauri-apps/api/core';
 import {
 	createCRUDStore,
 	createDerivedStores
@@ -28,7 +29,12 @@ import type {
 	AgentConfig,
 	AgentSummary,
 	AgentConfigCreate,
-	AgentConfigUpdate
+	AgentConfigUpdate,
+	AgentHealthReport,
+	AgentToolUsage,
+	RegressionReport,
+	ToolAcknowledgment,
+	WorkspaceDefaults
 } from '$types/agent';
 
 // ============================================================================
@@ -138,6 +144,67 @@ export const agentStore = {
 	 */
 	getAgentConfig: (agentId: string) => baseCrudStore.getItem(agentId),
 
+	/**
+	 * Runs the regression cases bundled with an agent.
+	 * @param agentId - ID of the agent
+	 * @returns Regression report with per-case results
+	 * @throws Error if the agent has no regression cases
+	 */
+	runRegressions: (agentId: string) =>
+		invoke<RegressionReport>('run_agent_regressions', { agentId }),
+
+	/**
+	 * Probes an agent's provider, model, tools and MCP servers before execution.
+	 * @param agentId - ID of the agent
+	 * @returns Health report with one check per dependency
+	 */
+	probe: (agentId: string) => invoke<AgentHealthReport>('probe_agent', { agentId }),
+
+	/**
+	 * Gets the tool usage statistics injected in the agent's system prompt.
+	 * @param agentId - ID of the agent
+	 * @returns Statistics per tool, sorted by tool name
+	 */
+	getToolUsage: (agentId: string) =>
+		invoke<AgentToolUsage[]>('get_agent_tool_usage', { agentId }),
+
+	/**
+	 * Clears the tool usage statistics of an agent.
+	 * @param agentId - ID of the agent
+	 */
+	resetToolUsage: (agentId: string) => invoke<void>('reset_agent_tool_usage', { agentId }),
+
+	/**
+	 * Gets the powerful tools (shell, file writes, SQL) acknowledged for an agent.
+	 * @param agentId - ID of the agent
+	 * @returns Acknowledged tools, sorted by tool name
+	 */
+	getToolAcknowledgments: (agentId: string) =>
+		invoke<ToolAcknowledgment[]>('get_agent_tool_acknowledgments', { agentId }),
+
+	/**
+	 * Revokes acknowledged tools, so their next use is acknowledged again.
+	 * @param agentId - ID of the agent
+	 * @param toolName - Tool to revoke (all tools when omitted)
+	 */
+	revokeToolAcknowledgment: (agentId: string, toolName?: string) =>
+		invoke<void>('revoke_agent_tool_acknowledgment', { agentId, toolName: toolName ?? null }),
+
+	/**
+	 * Gets the default tools and MCP servers inherited by new agents.
+	 * @returns Workspace defaults (empty lists if not configured)
+	 */
+	getWorkspaceDefaults: () => invoke<WorkspaceDefaults>('get_workspace_defaults'),
+
+	/**
+	 * Updates the default tools and MCP servers inherited by new agents.
+	 * Existing agents are not modified.
+	 * @param defaults - Complete defaults to store
+	 * @returns Stored defaults (validated, deduplicated)
+	 */
+	updateWorkspaceDefaults: (defaults: WorkspaceDefaults) =>
+		invoke<WorkspaceDefaults>('update_workspace_defaults', { defaults }),
+
 	/**
 	 * Selects an agent by ID.
 	 * @param agentId - ID to select (or null to deselect)
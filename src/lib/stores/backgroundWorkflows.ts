@@ -178,6 +178,18 @@ function updateExecutionFromChunk(
 			);
 			break;
 
+		case 'tool_progress':
+			updated.tools = updated.tools.map((t: ActiveTool) =>
+				t.name === chunk.tool && t.status === 'running'
+					? {
+							...t,
+							progress: chunk.progress ?? t.progress,
+							statusMessage: chunk.content ?? t.statusMessage
+						}
+					: t
+			);
+			break;
+
 		case 'reasoning':
 			updated.reasoning = [
 				...updated.reasoning,
@@ -33,6 +33,7 @@ export * from './activity';
 export * from './tokens';
 export * from './locale';
 export * from './onboarding';
+export * from './startup';
 export * from './userQuestion';
 export * from './validation';
 export * from './factory';
@@ -31,9 +31,14 @@ import type {
 	UpdateModelRequest,
 	ProviderSettings,
 	ConnectionTestResult,
-	ProviderType
+	ModelRefreshReport,
+	LLMRetrySettings,
+	GeminiSafetySetting,
+	ProviderType,
+	UtilityModelSettings
 } from '$types/llm';
 import type { ProviderInfo } from '$types/customProvider';
+import type { LocalLlmStatus, ModelFitCheck } from '$types/local-llm';
 
 // ============================================================================
 // Cache Management (OPT-9)
@@ -582,6 +587,94 @@ export async function testConnection(provider: ProviderType): Promise<Connection
 	return invoke<ConnectionTestResult>('test_provider_connection', { provider });
 }
 
+/**
+ * Refreshes models from the provider model list APIs.
+ * Creates discovered models, updates their capabilities and flags builtin
+ * models whose metadata drifted.
+ * @param provider - Provider to refresh (all configured providers if omitted)
+ * @returns Promise resolving to one report per provider
+ */
+export async function refreshProviderModels(provider?: ProviderType): Promise<ModelRefreshReport[]> {
+	const reports = await invoke<ModelRefreshReport[]>('refresh_provider_models', {
+		provider: provider ?? null
+	});
+	invalidateLLMCache();
+	return reports;
+}
+
+/**
+ * Gets the models loaded by Ollama, their VRAM usage and the host memory.
+ * @returns Promise resolving to the local runtime status
+ */
+export async function getLocalLlmStatus(): Promise<LocalLlmStatus> {
+	return invoke<LocalLlmStatus>('get_local_llm_status');
+}
+
+/**
+ * Checks whether an Ollama model fits in the available memory.
+ * Call before selecting a local model and show the returned warning.
+ * @param model - Ollama model name
+ * @returns Promise resolving to the fit check
+ */
+export async function checkLocalModelFit(model: string): Promise<ModelFitCheck> {
+	return invoke<ModelFitCheck>('check_local_model_fit', { model });
+}
+
+/**
+ * Loads the retry policy for transient LLM failures.
+ * @returns Promise resolving to the retry settings
+ */
+export async function loadRetrySettings(): Promise<LLMRetrySettings> {
+	return invoke<LLMRetrySettings>('get_llm_retry_settings');
+}
+
+/**
+ * Updates the retry policy for transient LLM failures.
+ * @param settings - Complete retry settings
+ * @returns Promise resolving to the stored settings
+ */
+export async function updateRetrySettings(settings: LLMRetrySettings): Promise<LLMRetrySettings> {
+	return invoke<LLMRetrySettings>('update_llm_retry_settings', { settings });
+}
+
+/**
+ * Loads the safety settings sent with every Gemini request.
+ * @returns Promise resolving to the safety settings (empty for API defaults)
+ */
+export async function loadGeminiSafetySettings(): Promise<GeminiSafetySetting[]> {
+	return invoke<GeminiSafetySetting[]>('get_gemini_safety_settings');
+}
+
+/**
+ * Updates the safety settings sent with every Gemini request.
+ * @param settings - One threshold per harm category
+ * @returns Promise resolving to the stored settings
+ */
+export async function updateGeminiSafetySettings(
+	settings: GeminiSafetySetting[]
+): Promise<GeminiSafetySetting[]> {
+	return invoke<GeminiSafetySetting[]>('update_gemini_safety_settings', { settings });
+}
+
+/**
+ * Loads the model used by internal summarization passes.
+ * @returns Promise resolving to the utility model (null when agents' models are used)
+ */
+export async function loadUtilityModelSettings(): Promise<UtilityModelSettings | null> {
+	return invoke<UtilityModelSettings | null>('get_utility_model_settings');
+}
+
+/**
+ * Updates the model used by internal summarization passes.
+ * @param settings - Utility model, or null to use the calling agent's model
+ * @returns Promise resolving to the stored settings
+ */
+export async function updateUtilityModelSettings(
+	settings: UtilityModelSettings | null
+): Promise<UtilityModelSettings | null> {
+	return invoke<UtilityModelSettings | null>('update_utility_model_settings', { settings });
+}
+
 /**
  * Seeds the database with builtin models.
  * @returns Promise resolving to number of models inserted
@@ -658,6 +751,15 @@ export async function deleteCustomProvider(name: string): Promise<void> {
 	invalidateLLMCache();
 }
 
+/**
+ * Lists the models served by a custom provider (`GET {baseUrl}/models`).
+ * @param name - Provider identifier
+ * @returns Promise resolving to sorted model IDs
+ */
+export async function listCustomProviderModels(name: string): Promise<string[]> {
+	return invoke<string[]>('list_custom_provider_models', { name });
+}
+
 /**
  * Loads all provider settings and models.
  * Uses cache with 30s TTL to avoid duplicate API calls.
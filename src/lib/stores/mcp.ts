@@ -24,15 +24,23 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import type {
+	MCPOAuthAuthorizationStart,
+	MCPResource,
+	MCPResourceContents,
 	MCPServer,
 	MCPServerConfig,
+	MCPServerImportReport,
 	MCPServerStatus,
+	MCPServerStatusChange,
 	MCPTestResult,
 	MCPTool,
 	MCPToolCallRequest,
 	MCPToolCallResult
 } from '$types/mcp';
+import type { Prompt } from '$types/prompt';
+import type { ConflictResolution } from '$types/importExport';
 
 // ============================================================================
 // Cache Management (OPT-9)
@@ -407,6 +415,30 @@ export async function deleteServer(id: string): Promise<void> {
 	invalidateMCPCache();
 }
 
+/**
+ * Imports MCP servers from another client's `mcpServers` JSON configuration.
+ * Imported servers are created disabled.
+ * @param json - Configuration file content (Claude Desktop, Cursor, VS Code...)
+ * @param conflict - What to do when a server name is already taken
+ * @param dryRun - Only report what would be imported
+ * @returns Promise resolving to the import report
+ */
+export async function importServersFromJson(
+	json: string,
+	conflict: ConflictResolution = 'skip',
+	dryRun = false
+): Promise<MCPServerImportReport> {
+	const report = await invoke<MCPServerImportReport>('import_mcp_servers_from_json', {
+		json,
+		conflict,
+		dryRun
+	});
+	if (!dryRun) {
+		invalidateMCPCache();
+	}
+	return report;
+}
+
 /**
  * Tests an MCP server connection
  * @param config - Server configuration to test
@@ -451,3 +483,102 @@ export async function callTool(request: MCPToolCallRequest): Promise<MCPToolCall
 export async function listServerTools(serverName: string): Promise<MCPTool[]> {
 	return invoke<MCPTool[]>('list_mcp_tools', { serverName });
 }
+
+/**
+ * Lists resources exposed by a specific server
+ * @param serverName - Name of the server
+ * @param refresh - Re-fetch the resource list from the server
+ * @returns Promise resolving to array of resources
+ */
+export async function listServerResources(
+	serverName: string,
+	refresh = false
+): Promise<MCPResource[]> {
+	return invoke<MCPResource[]>('list_mcp_resources', { serverName, refresh });
+}
+
+/**
+ * Lists prompts exposed by MCP servers as library prompts
+ * @param serverName - Only list prompts of this server (all servers if omitted)
+ * @param refresh - Re-fetch the prompt list from the server (requires serverName)
+ * @returns Promise resolving to prompts with `mcp_server` set and empty content
+ */
+export async function listMCPPrompts(serverName?: string, refresh = false): Promise<Prompt[]> {
+	return invoke<Prompt[]>('list_mcp_prompts', { serverName, refresh });
+}
+
+/**
+ * Renders an MCP prompt with argument values
+ * @param serverName - Name of the server
+ * @param promptName - Name of the prompt
+ * @param args - Argument values by argument name
+ * @returns Promise resolving to the prompt with its rendered content
+ */
+export async function getMCPPrompt(
+	serverName: string,
+	promptName: string,
+	args: Record<string, string>
+): Promise<Prompt> {
+	return invoke<Prompt>('get_mcp_prompt', { serverName, promptName, arguments: args });
+}
+
+/**
+ * Reads the contents of a resource from a specific server
+ * @param serverName - Name of the server
+ * @param uri - Resource URI
+ * @returns Promise resolving to the resource contents
+ */
+export async function readServerResource(
+	serverName: string,
+	uri: string
+): Promise<MCPResourceContents[]> {
+	return invoke<MCPResourceContents[]>('read_mcp_resource', { serverName, uri });
+}
+
+/**
+ * Gets the status transitions of an MCP server (oldest first)
+ * @param serverName - Name of the server
+ * @returns Promise resolving to the recorded status changes
+ */
+export async function getServerStatusHistory(serverName: string): Promise<MCPServerStatusChange[]> {
+	return invoke<MCPServerStatusChange[]>('get_mcp_server_status_history', { serverName });
+}
+
+/**
+ * Listens to server status changes (health check failures, crashes, restarts)
+ * @param onChange - Called with each status change
+ * @returns Promise resolving to the function removing the listener
+ */
+export async function listenServerStatusChanges(
+	onChange: (change: MCPServerStatusChange) => void
+): Promise<UnlistenFn> {
+	return listen<MCPServerStatusChange>('mcp_server_status_changed', (event) => {
+		invalidateMCPCache();
+		onChange(event.payload);
+	});
+}
+
+/**
+ * Starts the OAuth authorization of an HTTP MCP server
+ * @param id - Server ID
+ * @param redirectUri - Loopback redirect URI receiving the authorization code
+ * @returns Promise resolving to the authorization URL and state
+ */
+export async function beginServerOAuth(
+	id: string,
+	redirectUri: string
+): Promise<MCPOAuthAuthorizationStart> {
+	return invoke<MCPOAuthAuthorizationStart>('begin_mcp_oauth', { id, redirectUri });
+}
+
+/**
+ * Completes the OAuth authorization of an HTTP MCP server
+ * @param oauthState - State returned by beginServerOAuth
+ * @param code - Authorization code received on the redirect URI
+ * @returns Promise resolving to the restarted server
+ */
+export async function completeServerOAuth(oauthState: string, code: string): Promise<MCPServer> {
+	const server = await invoke<MCPServer>('complete_mcp_oauth', { oauthState, code });
+	invalidateMCPCache();
+	return server;
+}
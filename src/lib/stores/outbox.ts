@@ -0,0 +1,58 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Persistence outbox notifications.
+ *
+ * When a database write fails during a workflow execution, the backend queues
+ * the record in a durable outbox and emits `persistence_warning`. The record
+ * is retried after the next execution and at startup, or on demand with
+ * `flush_outbox`.
+ * @module stores/outbox
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { toastStore } from '$lib/stores/toast';
+import { t } from '$lib/i18n';
+import type { OutboxFlushResult, PersistenceWarning } from '$types/outbox';
+
+/**
+ * Writes the records queued in the outbox to the database
+ * @returns Promise resolving to the number of records written and still waiting
+ */
+export async function flushOutbox(): Promise<OutboxFlushResult> {
+	return invoke<OutboxFlushResult>('flush_outbox');
+}
+
+/**
+ * Shows a warning toast for each record queued in the outbox
+ * @returns Promise resolving to the function removing the listener
+ */
+export async function listenPersistenceWarnings(): Promise<UnlistenFn> {
+	return listen<PersistenceWarning>('persistence_warning', (event) => {
+		toastStore.add({
+			type: 'warning',
+			title: t('toast_persistence_warning_title'),
+			message: t('toast_persistence_warning_message', { pending: event.payload.pending }),
+			persistent: false,
+			duration: 8000
+		});
+	});
+}
@@ -36,8 +36,12 @@ import type {
 	PromptUpdate,
 	PromptSummary,
 	PromptStoreState,
-	PromptCategory
+	PromptCategory,
+	RenderedPrompt,
+	PromptVersion,
+	PromptComparison
 } from '$types/prompt';
+import type { Job } from '$types/job';
 
 // ============================================================================
 // Base CRUD Store
@@ -147,6 +151,59 @@ export const promptStore = {
 		}
 	},
 
+	/**
+	 * Render a prompt with variable values (defaults applied)
+	 */
+	async renderPrompt(id: string, values: Record<string, string>): Promise<RenderedPrompt> {
+		return invoke<RenderedPrompt>('render_prompt', { promptId: id, values });
+	},
+
+	/**
+	 * Render a prompt and queue it as a workflow job.
+	 * A new workflow is created when workflowId is omitted.
+	 */
+	async launchPrompt(
+		id: string,
+		values: Record<string, string>,
+		agentId: string,
+		workflowId?: string
+	): Promise<Job> {
+		const job = await invoke<Job>('launch_prompt', {
+			promptId: id,
+			values,
+			agentId,
+			workflowId: workflowId ?? null
+		});
+		await baseCrudStore.loadItems();
+		return job;
+	},
+
+	/**
+	 * List the versions of a prompt (newest first)
+	 */
+	async listVersions(id: string): Promise<PromptVersion[]> {
+		return invoke<PromptVersion[]>('list_prompt_versions', { promptId: id });
+	},
+
+	/**
+	 * Run two versions of a prompt with the same values and agent
+	 */
+	async compareVersions(
+		id: string,
+		versionA: number,
+		versionB: number,
+		values: Record<string, string>,
+		agentId: string
+	): Promise<PromptComparison> {
+		return invoke<PromptComparison>('compare_prompt_versions', {
+			promptId: id,
+			versionA,
+			versionB,
+			values,
+			agentId
+		});
+	},
+
 	// ===== UI State Management =====
 
 	/**
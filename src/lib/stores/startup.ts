@@ -0,0 +1,112 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * @fileoverview Startup store tracking the deferred backend initialization.
+ *
+ * The backend opens the database before the window shows, then restores
+ * providers, MCP servers and agents concurrently. This store follows the
+ * `startup_progress` events to drive the splash screen.
+ * @module stores/startup
+ */
+
+import { writable, derived } from 'svelte/store';
+import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { StartupPhase, StartupPhaseStatus, StartupStatus } from '$types/startup';
+
+const STATUS_RANK: Record<StartupPhaseStatus, number> = {
+	pending: 0,
+	running: 1,
+	completed: 2,
+	failed: 2
+};
+
+/**
+ * Sums the phase ranks of a status: phases only move forward, so the status
+ * with the highest score is the most recent one.
+ */
+function advancement(status: StartupStatus | null): number {
+	return status?.phases.reduce((sum, p) => sum + STATUS_RANK[p.status], 0) ?? -1;
+}
+
+/**
+ * Keeps the most recent of two statuses (an event and the initial snapshot
+ * may arrive in any order)
+ */
+function mostRecent(current: StartupStatus | null, next: StartupStatus): StartupStatus {
+	if (next.ready) return next;
+	return advancement(next) >= advancement(current) ? next : (current as StartupStatus);
+}
+
+/**
+ * Creates the startup store
+ */
+function createStartupStore() {
+	const { subscribe, set, update } = writable<StartupStatus | null>(null);
+
+	return {
+		subscribe,
+
+		/**
+		 * Loads the current status and listens to progress events.
+		 * Events emitted before the listener was registered are covered by the
+		 * initial `get_startup_status` call.
+		 * @returns Promise resolving to the function removing the listener
+		 */
+		init: async (): Promise<UnlistenFn> => {
+			const unlisten = await listen<StartupStatus>('startup_progress', (event) => {
+				update((current) => mostRecent(current, event.payload));
+			});
+			try {
+				const status = await invoke<StartupStatus>('get_startup_status');
+				update((current) => mostRecent(current, status));
+			} catch (e) {
+				console.error('Failed to load startup status:', e);
+				// Do not block the UI on a status error
+				set({ phases: [], ready: true });
+			}
+			return unlisten;
+		}
+	};
+}
+
+/**
+ * Startup store instance
+ */
+export const startupStore = createStartupStore();
+
+/**
+ * Phases the splash screen does not wait for: MCP servers may take long to
+ * spawn, their status is reported by the MCP supervision events
+ */
+const BACKGROUND_PHASES: StartupPhase[] = ['mcp_servers'];
+
+/**
+ * True once the phases needed to use the application have finished
+ */
+export const startupReady = derived(
+	startupStore,
+	($s) =>
+		$s !== null &&
+		($s.ready ||
+			$s.phases
+				.filter((p) => !BACKGROUND_PHASES.includes(p.phase))
+				.every((p) => p.status === 'completed' || p.status === 'failed'))
+);
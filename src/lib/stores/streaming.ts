@@ -51,6 +51,10 @@ export interface ActiveTool {
 	duration?: number;
 	/** Error message if failed */
 	error?: string;
+	/** Progress percentage (0-100) reported by the MCP server */
+	progress?: number;
+	/** Progress message reported by the MCP server */
+	statusMessage?: string;
 }
 
 /**
@@ -65,6 +69,32 @@ export interface ActiveReasoningStep {
 	stepNumber: number;
 }
 
+/**
+ * LLM request being retried after a transient failure
+ */
+export interface ActiveRetry {
+	/** Retry number (1-indexed) */
+	attempt: number;
+	/** Maximum number of retries */
+	maxRetries: number;
+	/** Delay before the retry in milliseconds */
+	delayMs: number;
+	/** Error of the failed attempt */
+	error: string;
+}
+
+/**
+ * Run reported as possibly stuck by the backend watchdog
+ */
+export interface ActiveStall {
+	/** Phase of the run when the silence was detected */
+	phase: string;
+	/** Tool being executed, if a single tool runs */
+	tool?: string;
+	/** Silence duration in milliseconds */
+	silentMs: number;
+}
+
 /**
  * Sub-agent status during streaming
  */
@@ -148,6 +178,10 @@ export interface StreamingState {
 	error: string | null;
 	/** Whether workflow was cancelled */
 	cancelled: boolean;
+	/** LLM request being retried (cleared when tokens arrive) */
+	retry: ActiveRetry | null;
+	/** Possibly stuck run (cleared by the next chunk); the UI offers cancellation */
+	stall: ActiveStall | null;
 }
 
 // ============================================================================
@@ -168,7 +202,9 @@ const initialState: StreamingState = {
 	completed: false,
 	tokensReceived: 0,
 	error: null,
-	cancelled: false
+	cancelled: false,
+	retry: null,
+	stall: null
 };
 
 // ============================================================================
@@ -205,7 +241,8 @@ function handleToken(s: StreamingState, c: StreamChunk): StreamingState {
 	return {
 		...s,
 		content: s.content + (c.content ?? ''),
-		tokensReceived: newTokensReceived
+		tokensReceived: newTokensReceived,
+		retry: null
 	};
 }
 
@@ -240,6 +277,24 @@ function handleToolEnd(s: StreamingState, c: StreamChunk): StreamingState {
 	};
 }
 
+/**
+ * Handle tool_progress chunk - update progress of the running tool
+ */
+function handleToolProgress(s: StreamingState, c: StreamChunk): StreamingState {
+	return {
+		...s,
+		tools: s.tools.map((t) =>
+			t.name === c.tool && t.status === 'running'
+				? {
+						...t,
+						progress: c.progress ?? t.progress,
+						statusMessage: c.content ?? t.statusMessage
+					}
+				: t
+		)
+	};
+}
+
 /**
  * Handle reasoning chunk - add new reasoning step
  */
@@ -257,6 +312,35 @@ function handleReasoning(s: StreamingState, c: StreamChunk): StreamingState {
 	};
 }
 
+/**
+ * Handle llm_retry chunk - record the retried LLM request
+ */
+function handleLlmRetry(s: StreamingState, c: StreamChunk): StreamingState {
+	return {
+		...s,
+		retry: {
+			attempt: c.retry_attempt ?? 1,
+			maxRetries: c.max_retries ?? 0,
+			delayMs: c.duration ?? 0,
+			error: c.content ?? ''
+		}
+	};
+}
+
+/**
+ * Handle possibly_stuck chunk - record the silence reported by the watchdog
+ */
+function handlePossiblyStuck(s: StreamingState, c: StreamChunk): StreamingState {
+	return {
+		...s,
+		stall: {
+			phase: c.content ?? '',
+			tool: c.tool,
+			silentMs: c.duration ?? 0
+		}
+	};
+}
+
 /**
  * Handle error chunk - set error message and stop streaming
  */
@@ -400,6 +484,7 @@ const chunkHandlers: Record<string, ChunkHandler> = {
 	token: handleToken,
 	tool_start: handleToolStart,
 	tool_end: handleToolEnd,
+	tool_progress: handleToolProgress,
 	reasoning: handleReasoning,
 	error: handleError,
 	sub_agent_start: handleSubAgentStart,
@@ -408,7 +493,9 @@ const chunkHandlers: Record<string, ChunkHandler> = {
 	sub_agent_error: handleSubAgentError,
 	task_create: handleTaskCreate,
 	task_update: handleTaskUpdate,
-	task_complete: handleTaskComplete
+	task_complete: handleTaskComplete,
+	llm_retry: handleLlmRetry,
+	possibly_stuck: handlePossiblyStuck
 };
 
 /**
@@ -446,7 +533,9 @@ export const streamingStore = {
 	processChunkDirect(chunk: StreamChunk): void {
 		store.update((s) => {
 			const handler = chunkHandlers[chunk.chunk_type];
-			return handler ? handler(s, chunk) : s;
+			const next = handler ? handler(s, chunk) : s;
+			// Any other chunk means the run is making progress again
+			return chunk.chunk_type !== 'possibly_stuck' && next.stall ? { ...next, stall: null } : next;
 		});
 	},
 
@@ -701,6 +790,16 @@ export const reasoningSteps = derived(store, (s) => s.reasoning);
  */
 export const streamError = derived(store, (s) => s.error);
 
+/**
+ * Derived store: LLM request being retried
+ */
+export const llmRetry = derived(store, (s) => s.retry);
+
+/**
+ * Derived store: run reported as possibly stuck (offer cancellation)
+ */
+export const possiblyStuck = derived(store, (s) => s.stall);
+
 /**
  * Derived store: whether workflow was cancelled
  */
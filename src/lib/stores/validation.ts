@@ -93,6 +93,11 @@ const store = writable<ValidationState>(initialState);
  */
 let unlistener: UnlistenFn | null = null;
 
+/**
+ * Cleanup function of the validation_response listener
+ */
+let responseUnlistener: UnlistenFn | null = null;
+
 /**
  * Tracks whether the store has been initialized with event listeners
  */
@@ -160,6 +165,18 @@ export const validationStore = {
 			}
 		);
 
+		// Requests answered outside the app (notification links) close the modal
+		responseUnlistener = await listen<{ validation_id: string; approved: boolean }>(
+			EVENTS.VALIDATION_RESPONSE,
+			(event) => {
+				store.update((s) =>
+					s.pending?.event.validation_id === event.payload.validation_id
+						? { ...s, pending: null, totalProcessed: s.totalProcessed + 1 }
+						: s
+				);
+			}
+		);
+
 		isInitialized = true;
 	},
 
@@ -256,6 +273,10 @@ export const validationStore = {
 			unlistener();
 			unlistener = null;
 		}
+		if (responseUnlistener) {
+			responseUnlistener();
+			responseUnlistener = null;
+		}
 		isInitialized = false;
 	},
 
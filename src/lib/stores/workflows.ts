@@ -23,16 +23,31 @@
  * @module stores/workflows
  */
 
-import type { Workflow } from '$types/workflow';
+import type { PaginatedWorkflows, Workflow, WorkflowListQuery } from '$types/workflow';
 
 import { writable, derived, get } from 'svelte/store';
 import { invoke } from '@tauri-apps/api/core';
 
+/**
+ * Number of workflows loaded per page
+ */
+export const WORKFLOW_PAGE_SIZE = 50;
+
+/**
+ * Maximum page size accepted by the backend
+ */
+const MAX_WORKFLOW_PAGE_SIZE = 200;
+
+/**
+ * Sorting and filters of the workflow list (pagination is managed by the store)
+ */
+export type WorkflowListFilters = Omit<WorkflowListQuery, 'limit' | 'offset'>;
+
 /**
  * State interface for the reactive workflow store
  */
 export interface WorkflowState {
-	/** List of all workflows */
+	/** Loaded workflows (pages loaded so far, in list order) */
 	workflows: Workflow[];
 	/** Currently selected workflow ID */
 	selectedId: string | null;
@@ -42,6 +57,14 @@ export interface WorkflowState {
 	error: string | null;
 	/** Search filter text */
 	searchFilter: string;
+	/** Sorting and filters applied by the backend */
+	listFilters: WorkflowListFilters;
+	/** Total number of workflows matching the filters */
+	total: number;
+	/** Whether more workflows can be loaded */
+	hasMore: boolean;
+	/** Loading state of the next page */
+	loadingMore: boolean;
 }
 
 /**
@@ -52,7 +75,11 @@ const initialStoreState: WorkflowState = {
 	selectedId: null,
 	loading: false,
 	error: null,
-	searchFilter: ''
+	searchFilter: '',
+	listFilters: { sort_by: 'last_activity', ascending: false },
+	total: 0,
+	hasMore: false,
+	loadingMore: false
 };
 
 /**
@@ -71,19 +98,81 @@ export const workflowStore = {
 	subscribe: workflowWritable.subscribe,
 
 	/**
-	 * Load all workflows from backend.
+	 * Load the first page of workflows from backend.
+	 * Reloads as many workflows as already loaded (up to the backend page
+	 * limit), so refreshing after a rename or delete keeps the visible list.
 	 */
 	async loadWorkflows(): Promise<void> {
+		const state = get(workflowWritable);
+		const limit = Math.min(
+			Math.max(state.workflows.length, WORKFLOW_PAGE_SIZE),
+			MAX_WORKFLOW_PAGE_SIZE
+		);
 		workflowWritable.update((s) => ({ ...s, loading: true, error: null }));
 		try {
-			const workflows = await invoke<Workflow[]>('load_workflows');
-			workflowWritable.update((s) => ({ ...s, workflows, loading: false }));
+			const page = await invoke<PaginatedWorkflows>('load_workflows_paginated', {
+				query: { ...state.listFilters, limit, offset: 0 }
+			});
+			workflowWritable.update((s) => ({
+				...s,
+				workflows: page.workflows,
+				total: page.total,
+				hasMore: page.has_more,
+				loading: false
+			}));
 		} catch (e) {
 			const error = e instanceof Error ? e.message : String(e);
 			workflowWritable.update((s) => ({ ...s, error, loading: false }));
 		}
 	},
 
+	/**
+	 * Load the next page of workflows (no-op while loading or when all are loaded).
+	 */
+	async loadMore(): Promise<void> {
+		const state = get(workflowWritable);
+		if (!state.hasMore || state.loading || state.loadingMore) return;
+
+		workflowWritable.update((s) => ({ ...s, loadingMore: true, error: null }));
+		try {
+			const page = await invoke<PaginatedWorkflows>('load_workflows_paginated', {
+				query: {
+					...state.listFilters,
+					limit: WORKFLOW_PAGE_SIZE,
+					offset: state.workflows.length
+				}
+			});
+			workflowWritable.update((s) => {
+				// Skip workflows shifted into the page by concurrent updates
+				const loadedIds = new Set(s.workflows.map((w) => w.id));
+				return {
+					...s,
+					workflows: [...s.workflows, ...page.workflows.filter((w) => !loadedIds.has(w.id))],
+					total: page.total,
+					hasMore: page.has_more,
+					loadingMore: false
+				};
+			});
+		} catch (e) {
+			const error = e instanceof Error ? e.message : String(e);
+			workflowWritable.update((s) => ({ ...s, error, loadingMore: false }));
+		}
+	},
+
+	/**
+	 * Change the sorting or filters and reload the first page.
+	 *
+	 * @param filters - Filters to merge into the current ones
+	 */
+	async setListFilters(filters: Partial<WorkflowListFilters>): Promise<void> {
+		workflowWritable.update((s) => ({
+			...s,
+			listFilters: { ...s.listFilters, ...filters },
+			workflows: []
+		}));
+		await workflowStore.loadWorkflows();
+	},
+
 	/**
 	 * Select a workflow by ID.
 	 *
@@ -102,6 +191,19 @@ export const workflowStore = {
 		workflowWritable.update((s) => ({ ...s, searchFilter: filter }));
 	},
 
+	/**
+	 * Update the name of a loaded workflow (generated title).
+	 *
+	 * @param workflowId - Workflow ID
+	 * @param name - New workflow name
+	 */
+	setName(workflowId: string, name: string): void {
+		workflowWritable.update((s) => ({
+			...s,
+			workflows: s.workflows.map((w) => (w.id === workflowId ? { ...w, name } : w))
+		}));
+	},
+
 	/**
 	 * Get the currently selected workflow (synchronous).
 	 *
@@ -140,6 +242,16 @@ export const workflowsLoading = derived(workflowWritable, ($s) => $s.loading);
  */
 export const workflowsError = derived(workflowWritable, ($s) => $s.error);
 
+/**
+ * Derived store: sorting and filters of the list
+ */
+export const workflowListFilters = derived(workflowWritable, ($s) => $s.listFilters);
+
+/**
+ * Derived store: whether more workflows can be loaded
+ */
+export const workflowsHasMore = derived(workflowWritable, ($s) => $s.hasMore);
+
 /**
  * Derived store: search filter text
  */
@@ -47,10 +47,12 @@ export function activeToolToActivity(tool: ActiveTool, index: number): WorkflowA
 		timestamp: tool.startedAt,
 		type,
 		title: tool.name,
+		description: tool.statusMessage,
 		status,
 		duration: tool.duration,
 		metadata: {
 			toolName: tool.name,
+			progress: tool.progress,
 			error: tool.error
 		}
 	};
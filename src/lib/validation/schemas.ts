@@ -35,6 +35,16 @@ export const LLMModelSchema = z.object({
 	is_reasoning: z.boolean(),
 	input_price_per_mtok: z.number().nonnegative(),
 	output_price_per_mtok: z.number().nonnegative(),
+	supports_vision: z.boolean().nullable(),
+	supports_tools: z.boolean().nullable(),
+	metadata_drift: z.array(
+		z.object({
+			field: z.enum(['context_window', 'max_output_tokens']),
+			stored: z.number(),
+			reported: z.number()
+		})
+	),
+	metadata_refreshed_at: z.string().nullable(),
 	created_at: z.string(),
 	updated_at: z.string()
 });
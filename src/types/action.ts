@@ -0,0 +1,65 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Command palette type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/action.rs`. The backend owns the
+ * registry of invocable actions and ranks them for a palette query.
+ *
+ * @module types/action
+ */
+
+/**
+ * Palette group of an action
+ */
+export type ActionCategory = 'create' | 'workflow' | 'navigate';
+
+/**
+ * Invocable action listed in the command palette
+ */
+export interface PaletteAction {
+	/** Stable identifier (e.g. `agent.create`, `settings.open:mcp`) */
+	id: string;
+	/** Palette group */
+	category: ActionCategory;
+	/** Displayed title (English fallback) */
+	title: string;
+	/** i18n key of the title, for static actions */
+	label_key?: string;
+	/** Short description */
+	description: string;
+	/** Additional search terms */
+	keywords: string[];
+	/** Frontend route to open */
+	route: string;
+	/** Parameters passed to the page (e.g. `{ prompt_id }`) */
+	params?: Record<string, unknown>;
+}
+
+/**
+ * Action matching a palette query (result of `search_actions`)
+ */
+export interface ActionMatch {
+	/** Matched action */
+	action: PaletteAction;
+	/** Relevance score (higher is better, 0 for an empty query) */
+	score: number;
+}
@@ -69,6 +69,230 @@ export interface AgentConfig {
   max_tool_iterations: number;
   /** Enable thinking mode for supported models (default: true) */
   enable_thinking?: boolean;
+  /** Per-tool parameter defaults and restrictions, keyed by tool name */
+  tool_settings?: Record<string, ToolSettings>;
+  /** MCP tools the agent may use, keyed by MCP server name (servers without a policy expose all tools) */
+  mcp_tool_policy?: Record<string, MCPToolPolicy>;
+  /** Regression test cases bundled with the agent */
+  regressions?: RegressionSuite;
+  /** Output moderation policy */
+  moderation?: ModerationPolicy;
+}
+
+/**
+ * Per-agent parameter defaults and restrictions for a single tool
+ */
+export interface ToolSettings {
+  /** Parameter values used when the LLM omits them */
+  defaults?: Record<string, unknown>;
+  /** Parameter values enforced on every call (the LLM cannot override them) */
+  fixed?: Record<string, unknown>;
+  /** Operations the agent may invoke (undefined = all operations allowed) */
+  allowed_operations?: string[];
+  /** Call timeout in seconds (undefined = tool default, max 3600) */
+  timeout_secs?: number;
+  /** Maximum calls of the tool per workflow (undefined = unlimited, max 10000) */
+  max_calls_per_workflow?: number;
+  /** Maximum calls of the tool per minute across workflows (undefined = unlimited, max 600) */
+  max_calls_per_minute?: number;
+}
+
+/**
+ * Per-agent allow/deny lists for the tools of one MCP server
+ */
+export interface MCPToolPolicy {
+  /** Tools the agent may use (undefined = all tools of the server) */
+  allow?: string[];
+  /** Tools the agent may not use (takes precedence over allow) */
+  deny?: string[];
+}
+
+/**
+ * Action taken when an agent response is flagged by moderation
+ */
+export type ModerationAction = 'block' | 'warn' | 'annotate';
+
+/**
+ * Per-agent output moderation policy (checked before the response is persisted)
+ */
+export interface ModerationPolicy {
+  /** Check responses before they are persisted */
+  enabled: boolean;
+  /** Action taken when a response is flagged (default: warn) */
+  action: ModerationAction;
+  /** Provider moderation endpoint (local fallback) or local classifier only */
+  source: 'provider' | 'local';
+  /** Additional terms flagged by the local classifier (case-insensitive, whole words) */
+  blocked_terms: string[];
+}
+
+/**
+ * Result of a moderation check, returned with the workflow result
+ */
+export interface ModerationOutcome {
+  /** Whether the response was flagged */
+  flagged: boolean;
+  /** Flagged categories (e.g. "hate", "pii", "blocked_term") */
+  categories: string[];
+  /** Action applied */
+  action: ModerationAction;
+  /** Classifier that produced the verdict */
+  source: string;
+}
+
+/**
+ * Regression test cases bundled with an agent
+ */
+export interface RegressionSuite {
+  /** Block system prompt updates when a regression fails */
+  strict: boolean;
+  /** Test cases executed in order */
+  cases: RegressionCase[];
+}
+
+/**
+ * Single regression test case
+ */
+export interface RegressionCase {
+  /** Case name (unique within the suite) */
+  name: string;
+  /** User input sent to the agent */
+  input: string;
+  /** Assertions evaluated against the agent's response */
+  assertions: RegressionAssertion[];
+  /** Mocked tool outputs keyed by function name (MemoryTool, mcp__server__tool) */
+  mock_tools?: Record<string, unknown>;
+}
+
+/**
+ * Assertion evaluated against a regression case result
+ */
+export type RegressionAssertion =
+  | { type: 'contains'; value: string }
+  | { type: 'not_contains'; value: string }
+  | { type: 'regex'; pattern: string }
+  | { type: 'tool_called'; tool: string }
+  | { type: 'tool_not_called'; tool: string };
+
+/**
+ * Result of a single regression case
+ */
+export interface RegressionCaseResult {
+  /** Case name */
+  name: string;
+  /** Whether all assertions held */
+  passed: boolean;
+  /** Failure descriptions (assertion failures or execution error) */
+  failures: string[];
+  /** Final agent response */
+  response: string;
+  /** Tools called during the case */
+  tools_called: string[];
+  /** Execution duration in milliseconds */
+  duration_ms: number;
+}
+
+/**
+ * Result of a regression suite run (run_agent_regressions)
+ */
+export interface RegressionReport {
+  /** Agent the suite belongs to */
+  agent_id: string;
+  /** Whether every case passed */
+  passed: boolean;
+  /** Number of cases executed */
+  total: number;
+  /** Number of failed cases */
+  failed: number;
+  /** Per-case results */
+  results: RegressionCaseResult[];
+}
+
+/**
+ * Outcome of a single health check (ordered by severity)
+ */
+export type HealthStatus = 'ok' | 'warning' | 'error';
+
+/**
+ * Dependency verified by a health check
+ */
+export type HealthCheckKind = 'provider' | 'model' | 'tool' | 'mcp_server';
+
+/**
+ * Single health check result
+ */
+export interface HealthCheck {
+  /** Dependency kind */
+  kind: HealthCheckKind;
+  /** Dependency name (provider, model, tool or server name) */
+  target: string;
+  /** Check outcome */
+  status: HealthStatus;
+  /** Human-readable detail */
+  message: string;
+}
+
+/**
+ * Health report of an agent (probe_agent)
+ */
+export interface AgentHealthReport {
+  /** Agent ID */
+  agent_id: string;
+  /** Worst status among all checks */
+  status: HealthStatus;
+  /** True if no check reported an error */
+  healthy: boolean;
+  /** Individual check results */
+  checks: HealthCheck[];
+  /** Probe timestamp (ISO 8601) */
+  checked_at: string;
+}
+
+/**
+ * Tool usage statistics of an agent (get_agent_tool_usage)
+ */
+export interface AgentToolUsage {
+  /** Agent ID */
+  agent_id: string;
+  /** Tool name as called by the agent (mcp__server__tool for MCP tools) */
+  tool_name: string;
+  /** Number of successful calls */
+  success_count: number;
+  /** Number of failed calls */
+  failure_count: number;
+  /** Keywords of recent tasks the tool succeeded on (most recent first) */
+  task_keywords: string[];
+  /** Last call timestamp (ISO 8601) */
+  last_used_at: string;
+}
+
+/**
+ * Kind of powerful tool requiring a first-use acknowledgment
+ */
+export type PowerfulToolKind = 'shell' | 'file_system_write' | 'external_sql';
+
+/**
+ * Powerful tool acknowledged for an agent (get_agent_tool_acknowledgments)
+ */
+export interface ToolAcknowledgment {
+  /** Agent ID */
+  agent_id: string;
+  /** Tool name as called by the agent (mcp__server__tool for MCP tools) */
+  tool_name: string;
+  /** Kind of powerful tool */
+  kind: PowerfulToolKind;
+  /** Acknowledgment timestamp (ISO 8601) */
+  acknowledged_at: string;
+}
+
+/**
+ * Baseline tools and MCP servers for new agents (get/update_workspace_defaults)
+ */
+export interface WorkspaceDefaults {
+  /** Local tools assigned to every new agent */
+  tools: string[];
+  /** MCP server names assigned to every new agent */
+  mcp_servers: string[];
 }
 
 /**
@@ -105,6 +329,16 @@ export interface AgentConfigCreate {
   max_tool_iterations?: number;
   /** Enable thinking mode for supported models (default: true) */
   enable_thinking?: boolean;
+  /** Per-tool parameter defaults and restrictions, keyed by tool name */
+  tool_settings?: Record<string, ToolSettings>;
+  /** MCP tools the agent may use, keyed by MCP server name (servers without a policy expose all tools) */
+  mcp_tool_policy?: Record<string, MCPToolPolicy>;
+  /** Regression test cases bundled with the agent */
+  regressions?: RegressionSuite;
+  /** Output moderation policy */
+  moderation?: ModerationPolicy;
+  /** Add the workspace default tools and MCP servers (default: true) */
+  inherit_workspace_defaults?: boolean;
 }
 
 /**
@@ -125,6 +359,14 @@ export interface AgentConfigUpdate {
   max_tool_iterations?: number;
   /** Enable thinking mode for supported models */
   enable_thinking?: boolean;
+  /** Per-tool parameter defaults and restrictions (replaces existing settings) */
+  tool_settings?: Record<string, ToolSettings>;
+  /** MCP tool policies (replaces existing policies) */
+  mcp_tool_policy?: Record<string, MCPToolPolicy>;
+  /** Regression test cases (replaces existing suite) */
+  regressions?: RegressionSuite;
+  /** Output moderation policy (replaces existing policy) */
+  moderation?: ModerationPolicy;
 }
 
 /**
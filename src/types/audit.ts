@@ -0,0 +1,84 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Audit log type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/audit.rs`. Entries are written by the
+ * backend for sensitive actions when audit logging is enabled in the
+ * validation settings; they cannot be modified.
+ *
+ * @module types/audit
+ */
+
+/**
+ * Kind of audited action
+ */
+export type AuditAction =
+	| 'api_key_access'
+	| 'tool_execution'
+	| 'validation_decision'
+	| 'agent_config_change'
+	| 'mcp_server_start'
+	| 'mcp_server_stop'
+	| 'moderation';
+
+/**
+ * Audit log entry
+ */
+export interface AuditLogEntry {
+	/** Entry ID */
+	id: string;
+	/** Audited action */
+	action: AuditAction;
+	/** Who performed the action ("user", "system", an agent ID...) */
+	actor: string;
+	/** Object of the action (provider, tool, validation request, agent, server) */
+	target: string;
+	/** Associated workflow ID */
+	workflow_id: string | null;
+	/** Whether the action succeeded */
+	success: boolean;
+	/** Additional context */
+	details: Record<string, unknown>;
+	/** Action timestamp (ISO 8601) */
+	created_at: string;
+}
+
+/**
+ * Filter of audit log queries and exports (all criteria optional)
+ */
+export interface AuditLogFilter {
+	/** Restrict to one action */
+	action?: AuditAction;
+	/** Restrict to one actor */
+	actor?: string;
+	/** Restrict to one workflow */
+	workflow_id?: string;
+	/** Entries at or after this date (ISO 8601) */
+	since?: string;
+	/** Entries before this date (ISO 8601) */
+	until?: string;
+	/** Maximum number of entries, newest first (default 500) */
+	limit?: number;
+}
+
+/**
+ * File format of an audit log export
+ */
+export type AuditExportFormat = 'jsonl' | 'csv';
@@ -0,0 +1,91 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Behavior changelog type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/behavior_changelog.rs`. Changes
+ * affecting agent behavior are recorded in one ordered log; assistant messages
+ * carry the `behavior_position` of their run.
+ *
+ * @module types/behaviorChangelog
+ */
+
+/**
+ * Kind of behavior change
+ */
+export type BehaviorChangeKind =
+	| 'agent_created'
+	| 'agent_deleted'
+	| 'prompt_changed'
+	| 'model_changed'
+	| 'tools_changed'
+	| 'mcp_servers_changed'
+	| 'settings_changed'
+	| 'mcp_server_added'
+	| 'mcp_server_updated'
+	| 'mcp_server_removed'
+	| 'mcp_tools_changed';
+
+/**
+ * Value of one field before and after a change
+ */
+export interface BehaviorFieldChange {
+	/** Changed field (e.g. "system_prompt", "llm.model") */
+	field: string;
+	/** Previous value (absent when the field did not exist) */
+	before?: string;
+	/** New value (absent when the field was removed) */
+	after?: string;
+}
+
+/**
+ * Recorded behavior change
+ */
+export interface BehaviorChange {
+	/** Unique identifier */
+	id: string;
+	/** Position in the changelog (increasing, starts at 1) */
+	position: number;
+	/** Kind of change */
+	kind: BehaviorChangeKind;
+	/** Agent affected (null for MCP server changes) */
+	agent_id: string | null;
+	/** Name of the changed agent or MCP server */
+	subject: string;
+	/** One-line description of the change */
+	summary: string;
+	/** Changed fields */
+	changes: BehaviorFieldChange[];
+	/** Time of the change */
+	created_at: string;
+}
+
+/**
+ * Filters of the behavior changelog
+ */
+export interface BehaviorChangeQuery {
+	/** Only changes of this agent and MCP server changes */
+	agentId?: string;
+	/** Only changes after this position (exclusive) */
+	afterPosition?: number;
+	/** Only changes up to this position (inclusive) */
+	untilPosition?: number;
+	/** Maximum number of changes (default: 100) */
+	limit?: number;
+}
@@ -0,0 +1,43 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Workflow execution checkpoint type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/checkpoint.rs`. The primary agent of
+ * a workflow checkpoints its tool loop after each step; a checkpoint left
+ * behind by a crash lets the execution resume instead of starting over.
+ *
+ * @module types/checkpoint
+ */
+
+/**
+ * Interrupted execution that can be resumed
+ */
+export interface ResumableExecution {
+	/** Workflow of the execution */
+	workflow_id: string;
+	/** Agent executing the task */
+	agent_id: string;
+	/** Last tool loop iteration started */
+	iteration: number;
+	/** Tools requested and not executed yet when the execution stopped */
+	pending_tools: string[];
+	/** Time of the last checkpoint */
+	updated_at: string;
+}
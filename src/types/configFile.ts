@@ -0,0 +1,58 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Config file type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/config_file.rs`. The optional
+ * `~/.zileo/config.toml` overrides startup settings (database path, log level,
+ * default provider, proxy, feature flags).
+ *
+ * @module types/configFile
+ */
+
+/**
+ * Where an effective setting comes from
+ */
+export type ConfigSource = 'default' | 'config_file' | 'environment';
+
+/**
+ * Setting in effect
+ */
+export interface EffectiveSetting {
+	/** Setting key, as written in config.toml (e.g. "proxy.url", "features.scheduler") */
+	key: string;
+	/** Value in effect */
+	value: unknown;
+	/** Where the value comes from */
+	source: ConfigSource;
+}
+
+/**
+ * Effective startup configuration
+ */
+export interface EffectiveConfig {
+	/** Path of the config file */
+	path: string;
+	/** Whether the file exists and was loaded */
+	loaded: boolean;
+	/** Error reading or parsing the file (the file is then ignored) */
+	error: string | null;
+	/** Settings in effect */
+	settings: EffectiveSetting[];
+}
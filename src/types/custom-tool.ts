@@ -0,0 +1,74 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Custom tool type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/custom_tool.rs`. Custom tools are
+ * Rhai scripts written in Settings > Tools; agents use them as the tool
+ * `custom__<name>`.
+ *
+ * @module types/custom-tool
+ */
+
+/**
+ * JSON Schema of the input of a custom tool (an object)
+ */
+export type CustomToolSchema = Record<string, unknown>;
+
+/**
+ * Custom tool stored in the database
+ */
+export interface CustomTool {
+	/** Custom tool ID (UUID) */
+	id: string;
+	/** Tool name (lowercase letters, digits and `_`), fixed at creation */
+	name: string;
+	/** Description for the LLM */
+	description: string;
+	/** JSON Schema of the input */
+	input_schema: CustomToolSchema;
+	/** Rhai script: the input is `input`, the last expression is the result */
+	script: string;
+	/** Whether agents can use the tool */
+	enabled: boolean;
+	/** Creation timestamp (ISO 8601) */
+	created_at: string;
+	/** Last update timestamp (ISO 8601) */
+	updated_at: string;
+}
+
+/**
+ * Payload for creating a custom tool
+ */
+export interface CustomToolCreate {
+	name: string;
+	description: string;
+	input_schema: CustomToolSchema;
+	script: string;
+}
+
+/**
+ * Payload for updating a custom tool (the name cannot change)
+ */
+export interface CustomToolUpdate {
+	description?: string;
+	input_schema?: CustomToolSchema;
+	script?: string;
+	enabled?: boolean;
+}
@@ -0,0 +1,38 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Desktop access permission type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/desktop_access.rs`. Each toggle
+ * gates a desktop tool; the tools check it on every call.
+ *
+ * @module types/desktop-access
+ */
+
+/**
+ * Permissions of the desktop access tools (all disabled by default)
+ */
+export interface DesktopAccessSettings {
+	/** Agents may read and write the system clipboard (ClipboardTool) */
+	clipboard: boolean;
+	/** Agents may capture the screen and windows (ScreenshotTool) */
+	screenshot: boolean;
+	/** Agents may read local PDF files and images and save generated PDFs (PdfTool, OcrTool) */
+	documents: boolean;
+}
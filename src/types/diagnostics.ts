@@ -0,0 +1,39 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Diagnostics bundle type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/diagnostics.rs`. The bundle is a zip
+ * of recent logs, schema version, provider/MCP health, database statistics and
+ * configuration, with secrets stripped, to attach to bug reports.
+ *
+ * @module types/diagnostics
+ */
+
+/**
+ * Diagnostics bundle written by `generate_diagnostics`
+ */
+export interface DiagnosticsBundle {
+	/** Path of the zip archive */
+	path: string;
+	/** Size of the archive in bytes */
+	size_bytes: number;
+	/** Files of the archive */
+	files: string[];
+}
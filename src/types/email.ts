@@ -0,0 +1,70 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Email account types.
+ *
+ * Synchronized with `src-tauri/src/models/email.rs`. Accounts are read and
+ * sent from by agents through the EmailTool; their passwords are kept in
+ * secure storage and never returned.
+ *
+ * @module types/email
+ */
+
+/**
+ * A configured email account.
+ */
+export interface EmailAccount {
+	/** Identifier used by agents (lowercase letters, digits, `-` and `_`) */
+	name: string;
+	/** Address messages are sent from */
+	address: string;
+	/** Name shown to recipients */
+	display_name?: string | null;
+	/** IMAP server (implicit TLS) */
+	imap_host: string;
+	imap_port: number;
+	/** SMTP server (implicit TLS, or STARTTLS on port 587) */
+	smtp_host: string;
+	smtp_port: number;
+	/** Login of both servers */
+	username: string;
+	/** Rejects sending, even confirmed drafts */
+	read_only: boolean;
+	/** Creation timestamp */
+	created_at?: string;
+}
+
+/**
+ * Input to create or update an email account.
+ */
+export interface EmailAccountInput {
+	name: string;
+	address: string;
+	display_name?: string | null;
+	imap_host: string;
+	/** Default (993) when omitted */
+	imap_port?: number | null;
+	smtp_host: string;
+	/** Default (465) when omitted */
+	smtp_port?: number | null;
+	/** The address when omitted */
+	username?: string | null;
+	/** Defaults to true: agents can only read */
+	read_only?: boolean;
+}
@@ -125,6 +125,8 @@ export interface ImportResult {
 export interface RegenerateEmbeddingsParams {
   /** Optional type filter (regenerate only this type) */
   type_filter?: string;
+  /** Continue the interrupted run instead of starting over */
+  resume?: boolean;
 }
 
 /**
@@ -139,6 +141,29 @@ export interface RegenerateResult {
   failed: number;
 }
 
+/**
+ * Progress of an embedding regeneration.
+ *
+ * Emitted as the `embedding_regeneration_progress` event and stored as the
+ * checkpoint of an interrupted run.
+ */
+export interface RegenerateProgress {
+  /** Memory type filter of the run */
+  type_filter: string | null;
+  /** Only memories without embedding (dimension migration) */
+  missing_only: boolean;
+  /** ID of the last memory of the completed pages */
+  cursor: string | null;
+  /** Number of memories to regenerate */
+  total: number;
+  /** Number of memories processed */
+  processed: number;
+  /** Number of embeddings successfully generated */
+  success: number;
+  /** Number of failures */
+  failed: number;
+}
+
 /**
  * Result of embedding test operation
  */
@@ -216,3 +241,30 @@ export const DEFAULT_EMBEDDING_CONFIG: EmbeddingConfig = {
   chunk_overlap: 50,
   strategy: 'fixed'
 };
+
+/**
+ * Embedding dimension status of the memory vector index.
+ * Synchronized with `EmbeddingDimensionStatus` in `src-tauri/src/commands/migration.rs`.
+ */
+export interface EmbeddingDimensionStatus {
+  /** Dimension of the HNSW index (null if missing) */
+  index_dimension: number | null;
+  /** Dimension of the configured embedding service */
+  service_dimension: number | null;
+  /** Stored embeddings whose length differs from the service dimension */
+  mismatched_embeddings: number;
+  /** Whether the index must be rebuilt */
+  needs_migration: boolean;
+}
+
+/**
+ * Result of a database migration
+ */
+export interface MigrationResult {
+  /** Whether the migration was successful */
+  success: boolean;
+  /** Human-readable message describing the result */
+  message: string;
+  /** Number of records affected */
+  records_affected: number;
+}
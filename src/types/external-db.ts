@@ -0,0 +1,59 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * External database types.
+ *
+ * Synchronized with `src-tauri/src/models/external_db.rs`. External
+ * databases are queried by agents through the SqlTool; their connection
+ * strings are kept in secure storage and never returned.
+ *
+ * @module types/external-db
+ */
+
+/** Database engine, detected from the connection string */
+export type ExternalDbKind = 'postgres' | 'mysql' | 'sqlite';
+
+/**
+ * A configured external database.
+ */
+export interface ExternalDatabase {
+	/** Identifier used by agents (lowercase letters, digits, `-` and `_`) */
+	name: string;
+	/** Database engine */
+	kind: ExternalDbKind;
+	/** What the database contains (shown to agents) */
+	description?: string | null;
+	/** Rejects write statements, even confirmed ones */
+	read_only: boolean;
+	/** Maximum rows returned by a query */
+	max_rows: number;
+	/** Creation timestamp */
+	created_at?: string;
+}
+
+/**
+ * Input to create or update an external database.
+ */
+export interface ExternalDatabaseInput {
+	name: string;
+	description?: string | null;
+	read_only: boolean;
+	/** Default (100) when omitted */
+	max_rows?: number | null;
+}
@@ -0,0 +1,46 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * News feed types.
+ *
+ * Synchronized with `src-tauri/src/models/feed.rs`. Feeds are read by agents
+ * through the RssTool, which returns each item once.
+ *
+ * @module types/feed
+ */
+
+/**
+ * An RSS or Atom feed.
+ */
+export interface Feed {
+	/** Feed ID (UUID) */
+	id: string;
+	/** Feed URL (http or https) */
+	url: string;
+	/** Feed title, read when the feed was added */
+	title?: string | null;
+	/** Website of the feed */
+	site_url?: string | null;
+	/** Last fetch by the RssTool (ISO 8601) */
+	last_fetched_at?: string | null;
+	/** Error of the last fetch, if it failed */
+	last_error?: string | null;
+	/** Creation timestamp (ISO 8601) */
+	created_at: string;
+}
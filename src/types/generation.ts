@@ -0,0 +1,44 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Per-request generation parameter types.
+ *
+ * Synchronized with `src-tauri/src/models/generation.rs`. A message may
+ * override the agent's sampling settings; the values actually used are
+ * recorded on the assistant message.
+ *
+ * @module types/generation
+ */
+
+/**
+ * Generation parameters of a single request.
+ *
+ * Unset fields fall back to the agent configuration (temperature, max tokens)
+ * or to the provider default (top_p, stop sequences).
+ */
+export interface GenerationParams {
+  /** Sampling temperature (0-2) */
+  temperature?: number;
+  /** Nucleus sampling probability mass (0-1, exclusive of 0) */
+  top_p?: number;
+  /** Maximum tokens to generate (256-128000) */
+  max_tokens?: number;
+  /** Sequences that stop generation (at most 4, 64 characters each) */
+  stop?: string[];
+}
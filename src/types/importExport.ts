@@ -361,11 +361,115 @@ export interface ImportError {
 	error: string;
 }
 
+// ============ WORKSPACE ARCHIVE TYPES ============
+
+/**
+ * Options of a workspace archive export.
+ */
+export interface WorkspaceExportOptions {
+	/** Workflows to export (all non-sensitive workflows if omitted) */
+	workflowIds?: string[];
+	/** Whether memory embeddings are included (recomputed after import otherwise) */
+	includeEmbeddings: boolean;
+}
+
+/**
+ * Record counts of a workspace archive, per table.
+ */
+export interface WorkspaceCounts {
+	workflows: number;
+	messages: number;
+	toolExecutions: number;
+	thinkingSteps: number;
+	memories: number;
+}
+
+/**
+ * Manifest of a workspace archive.
+ */
+export interface WorkspaceArchiveManifest {
+	/** Archive format version */
+	version: string;
+	/** Version of the application that produced the archive */
+	appVersion: string;
+	/** Export timestamp (ISO 8601) */
+	exportedAt: string;
+	/** Number of records per table */
+	counts: WorkspaceCounts;
+	/** Number of sensitive workflows left out of the archive */
+	skippedSensitive: number;
+	/** Whether memories carry their embeddings */
+	includeEmbeddings: boolean;
+}
+
+/**
+ * Individual record import error of a workspace archive.
+ */
+export interface WorkspaceImportError {
+	entityType: 'workflow' | 'message' | 'tool_execution' | 'thinking_step' | 'memory';
+	entityId: string;
+	error: string;
+}
+
+/**
+ * Result of a workspace archive import.
+ */
+export interface WorkspaceImportResult {
+	/** Number of records imported per table */
+	imported: WorkspaceCounts;
+	/** Number of records skipped per table (already present) */
+	skipped: WorkspaceCounts;
+	/** Non-blocking issues (e.g. agents missing in this installation) */
+	warnings: string[];
+	/** Records that could not be imported */
+	errors: WorkspaceImportError[];
+}
+
+// ============ CHAT HISTORY IMPORT TYPES ============
+
+/**
+ * Assistant that produced a chat history export.
+ */
+export type ChatHistorySource = 'chatgpt' | 'claude';
+
+/**
+ * Options of a chat history import (ChatGPT or Claude `conversations.json`).
+ */
+export interface ChatHistoryImportOptions {
+	/** Assistant that produced the file (detected if omitted) */
+	source?: ChatHistorySource;
+	/** Agent of the imported workflows */
+	agentId: string;
+	/** Also store each exchange as a memory, recalled in new conversations */
+	createMemories: boolean;
+}
+
+/**
+ * Result of a chat history import.
+ */
+export interface ChatHistoryImportResult {
+	/** Assistant that produced the file */
+	source: ChatHistorySource;
+	/** Number of conversations imported as workflows */
+	workflows: number;
+	/** Number of messages imported */
+	messages: number;
+	/** Number of memories created */
+	memories: number;
+	/** Number of conversations skipped (already imported or without text) */
+	skipped: number;
+	/** Conversations that could not be imported */
+	errors: Array<{ entityType: 'workflow' | 'memory'; entityId: string; error: string }>;
+}
+
 // ============ CONSTANTS ============
 
 /** Current schema version for export packages */
 export const EXPORT_SCHEMA_VERSION = '1.0';
 
+/** Current format version of workspace archives */
+export const WORKSPACE_ARCHIVE_VERSION = '1.0';
+
 /** Maximum import file size in bytes (10MB) */
 export const MAX_IMPORT_FILE_SIZE = 10 * 1024 * 1024;
 
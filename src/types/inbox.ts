@@ -0,0 +1,81 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Priority inbox type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/pending_item.rs`. Each subsystem
+ * records the items awaiting the user and resolves them when they are handled.
+ *
+ * @module types/inbox
+ */
+
+/**
+ * Kind of pending item
+ */
+export type PendingItemKind =
+	| 'validation'
+	| 'user_question'
+	| 'paused_workflow'
+	| 'interrupted_execution'
+	| 'failed_schedule'
+	| 'expiring_credential'
+	| 'expiring_api_key';
+
+/**
+ * Priority of a pending item
+ */
+export type PendingPriority = 'low' | 'normal' | 'high' | 'urgent';
+
+/**
+ * Where a pending item is handled in the app
+ */
+export interface PendingItemLink {
+	/** Frontend route (e.g. "/agent", "/settings/mcp") */
+	route: string;
+	/** Workflow to open on the route */
+	workflow_id?: string;
+	/** Record to focus (validation, question, schedule or MCP server ID) */
+	target_id: string;
+}
+
+/**
+ * Item awaiting the user
+ */
+export interface PendingItem {
+	/** Item ID */
+	id: string;
+	/** Kind of item */
+	kind: PendingItemKind;
+	/** ID of the record the item stands for */
+	source_id: string;
+	/** Workflow concerned */
+	workflow_id: string | null;
+	/** One-line description */
+	title: string;
+	/** Additional information (error, operation details) */
+	detail: string | null;
+	/** Priority, raised as the deadline approaches */
+	priority: PendingPriority;
+	/** Deadline (ISO 8601, e.g. token expiry) */
+	due_at: string | null;
+	/** Where the item is handled */
+	link: PendingItemLink;
+	/** Time the item appeared (ISO 8601) */
+	created_at: string;
+}
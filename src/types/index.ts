@@ -55,3 +55,33 @@ export * from './prompt';
 export * from './importExport';
 export * from './function_calling';
 export * from './onboarding';
+export * from './startup';
+export * from './outbox';
+export * from './speech';
+export * from './sync';
+export * from './knowledge';
+export * from './schedule';
+export * from './job';
+export * from './checkpoint';
+export * from './usageStats';
+export * from './behaviorChangelog';
+export * from './inbox';
+export * from './action';
+export * from './search';
+export * from './transcript';
+export * from './audit';
+export * from './profile';
+export * from './configFile';
+export * from './proxy';
+export * from './metrics';
+export * from './diagnostics';
+export * from './generation';
+export * from './local-llm';
+export * from './external-db';
+export * from './desktop-access';
+export * from './email';
+export * from './feed';
+export * from './ocr';
+export * from './plugin';
+export * from './custom-tool';
+export * from './macro-tool';
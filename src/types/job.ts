@@ -0,0 +1,78 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Background job type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/job.rs`. A job executes a message
+ * with an agent in a workflow independently of the IPC call that enqueued it;
+ * interrupted jobs resume after an app restart.
+ *
+ * @module types/job
+ */
+
+/**
+ * State of a job
+ */
+export type JobStatus = 'queued' | 'running' | 'done' | 'failed';
+
+/**
+ * Background workflow execution
+ */
+export interface Job {
+	/** Job ID (UUID) */
+	id: string;
+	/** Workflow receiving the response */
+	workflow_id: string;
+	/** Agent executing the message */
+	agent_id: string;
+	/** User message (already stored in the workflow) */
+	message: string;
+	/** Current state */
+	status: JobStatus;
+	/** Number of executions started (an interrupted job is retried) */
+	attempts: number;
+	/** ID of the assistant message, once done */
+	response_message_id: string | null;
+	/** Error, if the job failed */
+	error: string | null;
+	/** Enqueue timestamp */
+	created_at: string;
+	/** Start of the last execution */
+	started_at: string | null;
+	/** End of the execution */
+	finished_at: string | null;
+}
+
+/**
+ * Payload of the `job_status` event, emitted when a job changes state
+ */
+export interface JobStatusEvent {
+	/** Job ID */
+	job_id: string;
+	/** Workflow of the job */
+	workflow_id: string;
+	/** New state */
+	status: JobStatus;
+	/** Error, if the job failed */
+	error: string | null;
+}
+
+/** Event emitted when a job changes state */
+export const JOB_STATUS_EVENT = 'job_status';
@@ -0,0 +1,76 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Knowledge pack type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/knowledge_pack.rs`. A knowledge pack
+ * bundles documents (split into chunks) and curated memories; agents only
+ * retrieve context from the packs attached to them.
+ *
+ * @module types/knowledge
+ */
+
+/**
+ * Document of a knowledge pack
+ */
+export interface KnowledgeDocument {
+	/** Document title (unique within the pack) */
+	title: string;
+	/** Number of chunks the document was split into */
+	chunk_count: number;
+	/** Number of chunks stored with an embedding */
+	embedded_count: number;
+}
+
+/**
+ * Knowledge pack with its documents
+ */
+export interface KnowledgePack {
+	/** Pack ID (UUID) */
+	id: string;
+	/** Pack name (unique) */
+	name: string;
+	/** Description of the pack content */
+	description: string;
+	/** IDs of the agents the pack is attached to */
+	agent_ids: string[];
+	/** IDs of the memories curated into the pack */
+	memory_ids: string[];
+	/** Documents of the pack */
+	documents: KnowledgeDocument[];
+	/** Creation timestamp (ISO 8601) */
+	created_at: string;
+	/** Last update timestamp (ISO 8601) */
+	updated_at: string;
+}
+
+/**
+ * Chunk retrieved from an attached pack for a query
+ */
+export interface KnowledgeChunk {
+	/** Pack ID */
+	pack_id: string;
+	/** Title of the source document (null for a curated memory) */
+	document: string | null;
+	/** Chunk text */
+	content: string;
+	/** Relevance score (cosine similarity, or keyword overlap without embeddings) */
+	score: number;
+}
@@ -20,7 +20,7 @@
 /**
  * Builtin provider identifiers.
  */
-export type BuiltinProvider = 'mistral' | 'ollama';
+export type BuiltinProvider = 'mistral' | 'ollama' | 'gemini';
 
 /**
  * LLM provider types supported by the application.
@@ -72,6 +72,8 @@ export interface LLMConfigResponse {
   mistral: ProviderStatus;
   /** Ollama configuration status */
   ollama: ProviderStatus;
+  /** Gemini configuration status */
+  gemini: ProviderStatus;
   /** Ollama server URL */
   ollama_url: string;
 }
@@ -114,12 +116,50 @@ export interface LLMModel {
   input_price_per_mtok: number;
   /** Price per million output tokens (USD) - user configurable */
   output_price_per_mtok: number;
+  /** Whether the model accepts images (reported by the provider API, null if unknown) */
+  supports_vision: boolean | null;
+  /** Whether the model supports function calling (reported by the provider API, null if unknown) */
+  supports_tools: boolean | null;
+  /** Builtin values that differ from the provider API at the last refresh */
+  metadata_drift: ModelMetadataDrift[];
+  /** ISO 8601 timestamp of the last refresh from the provider API */
+  metadata_refreshed_at: string | null;
   /** ISO 8601 creation timestamp */
   created_at: string;
   /** ISO 8601 last update timestamp */
   updated_at: string;
 }
 
+/**
+ * Builtin model value that differs from the provider API.
+ */
+export interface ModelMetadataDrift {
+  /** Drifted field */
+  field: 'context_window' | 'max_output_tokens';
+  /** Value stored on the builtin model */
+  stored: number;
+  /** Value reported by the provider API */
+  reported: number;
+}
+
+/**
+ * Outcome of refreshing the models of one provider (`refresh_provider_models`).
+ */
+export interface ModelRefreshReport {
+  /** Refreshed provider */
+  provider: ProviderType;
+  /** Models reported by the provider API */
+  discovered: number;
+  /** Models created from the API */
+  created: number;
+  /** Existing models whose metadata changed */
+  updated: number;
+  /** IDs of the builtin models whose metadata drifted from the API */
+  drifted: string[];
+  /** Error when the model list could not be fetched */
+  error: string | null;
+}
+
 /**
  * Request payload for creating a new custom model.
  *
@@ -192,6 +232,56 @@ export interface ProviderSettings {
   updated_at: string;
 }
 
+/**
+ * Retry policy for transient LLM failures (get/update_llm_retry_settings).
+ *
+ * Rate limits (429), server errors (5xx) and connection errors are retried
+ * with jittered exponential backoff.
+ */
+export interface LLMRetrySettings {
+  /** Maximum number of retries, not including the first attempt (0-10) */
+  max_retries: number;
+  /** Delay before the first retry in milliseconds (100-60000) */
+  initial_delay_ms: number;
+  /** Maximum delay between retries in milliseconds */
+  max_delay_ms: number;
+  /** Delay multiplier between retries (1-10) */
+  backoff_multiplier: number;
+  /** Fraction of each delay that is randomized (0-1) */
+  jitter: number;
+}
+
+/**
+ * Safety setting sent with every Gemini request
+ * (get/update_gemini_safety_settings).
+ *
+ * Harm categories without a setting use the API default threshold.
+ */
+export interface GeminiSafetySetting {
+  /** Harm category (e.g., "HARM_CATEGORY_HARASSMENT") */
+  category: string;
+  /** Block threshold (e.g., "BLOCK_ONLY_HIGH", "BLOCK_NONE") */
+  threshold: string;
+}
+
+/**
+ * Model used by internal summarization and extraction passes
+ * (get/update_utility_model_settings).
+ *
+ * When unset, or when its provider is not configured, passes use the model
+ * of the agent that triggered them.
+ */
+export interface UtilityModelSettings {
+  /** Provider name (builtin or custom, e.g., "ollama") */
+  provider: string;
+  /** Model identifier used in API calls */
+  model: string;
+  /** Sampling temperature (0-2) */
+  temperature: number;
+  /** Maximum output tokens (256-128000) */
+  max_tokens: number;
+}
+
 /**
  * Result of a provider connection test.
  *
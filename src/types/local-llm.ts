@@ -0,0 +1,85 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Local model runtime status types.
+ *
+ * Synchronized with `src-tauri/src/models/local_llm.rs`. Used by the
+ * settings screen and to warn before selecting an Ollama model that does
+ * not fit in memory.
+ *
+ * @module types/local-llm
+ */
+
+/**
+ * A model currently loaded by Ollama.
+ */
+export interface LoadedModel {
+  /** Model name (e.g. "qwen2.5:7b") */
+  name: string;
+  /** Total memory used by the model (bytes) */
+  size_bytes: number;
+  /** Part of the model held in VRAM (bytes) */
+  vram_bytes: number;
+  /** When Ollama unloads the model if it stays idle */
+  expires_at: string | null;
+}
+
+/**
+ * Host memory (bytes).
+ */
+export interface SystemMemory {
+  total_bytes: number;
+  available_bytes: number;
+}
+
+/**
+ * Runtime status of the local Ollama server.
+ */
+export interface LocalLlmStatus {
+  /** Ollama server URL */
+  server_url: string;
+  /** Whether the server answered */
+  reachable: boolean;
+  /** Models currently loaded */
+  loaded_models: LoadedModel[];
+  /** VRAM used by the loaded models (bytes) */
+  vram_used_bytes: number;
+  /** Host memory, null when it cannot be read on this platform */
+  system_memory: SystemMemory | null;
+  /** Error of the Ollama request when unreachable */
+  error: string | null;
+}
+
+/**
+ * Whether a local model fits in the available memory.
+ */
+export interface ModelFitCheck {
+  /** Model name */
+  model: string;
+  /** Size of the model on disk (bytes), null when not installed */
+  model_size_bytes: number | null;
+  /** Memory the model needs once loaded (bytes) */
+  required_bytes: number | null;
+  /** Memory usable by the model (bytes) */
+  available_bytes: number | null;
+  /** null when the size or the available memory is unknown */
+  fits: boolean | null;
+  /** Message shown before selecting the model */
+  warning: string | null;
+}
@@ -0,0 +1,88 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Macro tool type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/macro_tool.rs`. Macros are
+ * sequences of tool calls defined in Settings > Tools; agents call them as
+ * the single tool `macro__<name>`.
+ *
+ * @module types/macro-tool
+ */
+
+import type { CustomToolSchema } from './custom-tool';
+
+/**
+ * One tool call of a macro
+ */
+export interface MacroStep {
+	/** Step ID, referenced by later steps as `{{steps.<id>...}}` */
+	id: string;
+	/** Tool called (e.g. "MemoryTool", "plugin__search", "custom__summary") */
+	tool: string;
+	/** Input template: `{{input.<param>}}` and `{{steps.<id>.<path>}}` are replaced */
+	input: Record<string, unknown>;
+}
+
+/**
+ * Macro tool stored in the database
+ */
+export interface ToolMacro {
+	/** Macro ID (UUID) */
+	id: string;
+	/** Macro name (lowercase letters, digits and `_`), fixed at creation */
+	name: string;
+	/** Description for the LLM */
+	description: string;
+	/** JSON Schema of the input */
+	input_schema: CustomToolSchema;
+	/** Tool calls, run in order */
+	steps: MacroStep[];
+	/** Output template (null = result of the last step) */
+	output: unknown | null;
+	/** Whether agents can use the macro */
+	enabled: boolean;
+	/** Creation timestamp (ISO 8601) */
+	created_at: string;
+	/** Last update timestamp (ISO 8601) */
+	updated_at: string;
+}
+
+/**
+ * Payload for creating a macro tool
+ */
+export interface ToolMacroCreate {
+	name: string;
+	description: string;
+	input_schema: CustomToolSchema;
+	steps: MacroStep[];
+	output?: unknown | null;
+}
+
+/**
+ * Payload for updating a macro tool (the name cannot change; the output
+ * template is replaced along with the steps)
+ */
+export interface ToolMacroUpdate {
+	description?: string;
+	input_schema?: CustomToolSchema;
+	steps?: MacroStep[];
+	output?: unknown | null;
+	enabled?: boolean;
+}
@@ -102,6 +102,25 @@ export interface MCPServer extends MCPServerConfig {
 	updated_at: string;
 }
 
+/**
+ * MCP server status transition recorded by the server supervision.
+ * Also the payload of the `mcp_server_status_changed` event.
+ */
+export interface MCPServerStatusChange {
+	/** Server ID */
+	server_id: string;
+	/** Server name */
+	server_name: string;
+	/** New status */
+	status: MCPServerStatus;
+	/** Status before the transition (null for the first recorded status) */
+	previous_status: MCPServerStatus | null;
+	/** Reason of the transition (error message, restart attempt...) */
+	message?: string;
+	/** ISO 8601 timestamp of the transition */
+	timestamp: string;
+}
+
 /**
  * MCP tool definition as discovered from a server.
  */
@@ -128,6 +147,46 @@ export interface MCPResource {
 	mime_type?: string;
 }
 
+/**
+ * Argument of an MCP prompt.
+ */
+export interface MCPPromptArgument {
+	/** Argument name */
+	name: string;
+	/** Optional description of the argument */
+	description?: string;
+	/** Whether the argument must be provided */
+	required: boolean;
+}
+
+/**
+ * MCP prompt template as discovered from a server.
+ */
+export interface MCPPrompt {
+	/** Prompt name (unique within the server) */
+	name: string;
+	/** Optional description of the prompt */
+	description?: string;
+	/** Arguments accepted by the prompt */
+	arguments: MCPPromptArgument[];
+}
+
+/**
+ * Contents of an MCP resource returned by `read_mcp_resource`.
+ *
+ * Text resources carry `text`; binary resources carry base64 `blob`.
+ */
+export interface MCPResourceContents {
+	/** Resource URI */
+	uri: string;
+	/** MIME type of the content */
+	mime_type?: string;
+	/** Text content */
+	text?: string;
+	/** Base64-encoded binary content */
+	blob?: string;
+}
+
 /**
  * Result from testing an MCP server connection.
  */
@@ -144,6 +203,45 @@ export interface MCPTestResult {
 	latency_ms: number;
 }
 
+/**
+ * Outcome of importing one server from an `mcpServers` JSON block.
+ */
+export type MCPServerImportAction = 'created' | 'renamed' | 'overwritten' | 'skipped' | 'failed';
+
+/**
+ * Import result of one server of an `mcpServers` JSON block.
+ */
+export interface MCPServerImportResult {
+	/** Server key in the imported JSON */
+	source_name: string;
+	/** Name of the server in Zileo (differs from source_name when renamed) */
+	name: string;
+	/** ID of the created or overwritten server */
+	server_id?: string;
+	/** Deployment method detected from the entry */
+	command?: MCPDeploymentMethod;
+	/** What happened to the entry */
+	action: MCPServerImportAction;
+	/** Reason of a skip or failure */
+	message?: string;
+}
+
+/**
+ * Report of an `mcpServers` JSON import (Claude Desktop, Cursor, VS Code...).
+ */
+export interface MCPServerImportReport {
+	/** One result per server entry */
+	results: MCPServerImportResult[];
+	/** Number of servers created (including renamed) or overwritten */
+	imported: number;
+	/** Number of servers skipped because of a name conflict */
+	skipped: number;
+	/** Number of entries that could not be imported */
+	failed: number;
+	/** True if nothing was written (preview) */
+	dry_run: boolean;
+}
+
 /**
  * Request to call an MCP tool.
  */
@@ -187,6 +285,21 @@ export interface MCPLatencyMetrics {
 	total_calls: number;
 }
 
+/**
+ * OAuth authorization started for an HTTP MCP server.
+ *
+ * The authorization URL is opened in the browser; the code received on the
+ * redirect URI is passed back with the state to `complete_mcp_oauth`.
+ */
+export interface MCPOAuthAuthorizationStart {
+	/** MCP server ID */
+	server_id: string;
+	/** URL to open in the browser */
+	authorization_url: string;
+	/** State to pass back with the authorization code */
+	state: string;
+}
+
 /**
  * Default values for MCP server configuration.
  */
@@ -31,6 +31,11 @@
  */
 export type MemoryType = 'user_pref' | 'context' | 'knowledge' | 'decision';
 
+/**
+ * Background embedding state (absent = stored without embedding service)
+ */
+export type MemoryEmbeddingStatus = 'pending' | 'ready' | 'failed';
+
 /**
  * Metadata associated with a memory entry.
  *
@@ -68,6 +73,8 @@ export interface Memory {
   importance: number;
   /** Optional expiration timestamp (ISO string, absent = permanent) */
   expires_at?: string;
+  /** Embedding state; pending/failed memories are found by text search only */
+  embedding_status?: MemoryEmbeddingStatus;
   /** Creation timestamp (ISO string from backend) */
   created_at: string;
 }
@@ -17,6 +17,8 @@
 // Copyright 2025 Zileo-Chat-3 Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+import type { GenerationParams } from './generation';
+
 /**
  * Message role in the conversation
  */
@@ -39,6 +41,60 @@ export interface SubAgentSummary {
   tokens_output?: number;
 }
 
+/**
+ * Context window utilization of the LLM calls behind an assistant message.
+ * Explains which earlier messages the model did not see.
+ */
+export interface ContextUsage {
+  /** Context window of the model in tokens (absent if the model is unknown) */
+  context_window?: number;
+  /** Largest prompt sent to the model during the run, in tokens */
+  peak_context_tokens: number;
+  /** peak_context_tokens / context_window (absent if the window is unknown) */
+  utilization?: number;
+  /** Messages stored in the workflow when the run started */
+  history_messages: number;
+  /** Messages sent to the model as conversation history */
+  history_sent: number;
+  /** Earlier messages left out of the prompt */
+  history_truncated: number;
+  /** Timestamp of the oldest message sent, when earlier messages were left out */
+  truncated_before?: string;
+}
+
+/**
+ * Image attached to a user message (metadata; the content is loaded with
+ * get_attachment_data).
+ */
+export interface Attachment {
+  /** Unique identifier (UUID) */
+  id: string;
+  /** Associated workflow ID */
+  workflow_id: string;
+  /** Message the attachment belongs to */
+  message_id: string;
+  /** MIME type (image/png, image/jpeg, image/gif or image/webp) */
+  mime_type: string;
+  /** Original file name, if known */
+  file_name?: string;
+  /** Size in bytes */
+  size_bytes: number;
+  /** Creation timestamp */
+  created_at: string;
+}
+
+/**
+ * Image sent with save_message: base64 data (or data URL) or a local file path.
+ */
+export interface AttachmentInput {
+  /** Base64 content, optionally as a data URL */
+  data?: string;
+  /** Path of a local image file */
+  path?: string;
+  /** Original file name */
+  file_name?: string;
+}
+
 /**
  * Message entity representing a conversation message with optional metrics.
  *
@@ -68,6 +124,20 @@ export interface Message {
   cost_usd?: number;
   /** Generation duration in milliseconds */
   duration_ms?: number;
+  /** Context window utilization of the run (assistant messages) */
+  context_usage?: ContextUsage;
+  /** Behavior changelog position in effect when the run started (assistant messages) */
+  behavior_position?: number;
+  /** Generation parameters used for the response (assistant messages) */
+  generation?: GenerationParams;
+  /** Image attachments (user messages) */
+  attachments?: Attachment[];
+  /** Pinned: always sent to the agent, even when left out of the history */
+  pinned?: boolean;
+  /** Bookmarked by the user */
+  bookmarked?: boolean;
+  /** Reactions of the user (emoji) */
+  reactions?: string[];
   /** Message timestamp */
   timestamp: Date;
   /** Sub-agent summaries (transient, captured from StreamingState) */
@@ -97,6 +167,12 @@ export interface MessageCreate {
   cost_usd?: number;
   /** Duration in milliseconds */
   duration_ms?: number;
+  /** Context window utilization */
+  context_usage?: ContextUsage;
+  /** Behavior changelog position of the run */
+  behavior_position?: number;
+  /** Generation parameters used for the response */
+  generation?: GenerationParams;
 }
 
 /**
@@ -0,0 +1,54 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Metrics export type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/metrics.rs`. Workflow executions,
+ * tool calls, LLM latency, token usage and MCP errors are counted in memory
+ * and exposed on a local Prometheus endpoint and/or pushed to an OTLP/HTTP
+ * collector.
+ *
+ * @module types/metrics
+ */
+
+/**
+ * Metrics export settings
+ */
+export interface MetricsSettings {
+	/** Serve the Prometheus endpoint on 127.0.0.1:<prometheus_port>/metrics */
+	prometheus_enabled: boolean;
+	/** Port of the Prometheus endpoint */
+	prometheus_port: number;
+	/** Push the metrics to an OTLP/HTTP collector */
+	otlp_enabled: boolean;
+	/** OTLP/HTTP metrics endpoint of the collector */
+	otlp_endpoint: string;
+	/** Interval between two pushes (seconds) */
+	otlp_interval_secs: number;
+}
+
+/**
+ * Running metrics exporters
+ */
+export interface MetricsExportStatus {
+	/** URL of the Prometheus endpoint, when served */
+	prometheus_url: string | null;
+	/** Whether the metrics are pushed to the OTLP collector */
+	otlp_active: boolean;
+}
@@ -0,0 +1,47 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * OCR settings type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/ocr.rs`. The OcrTool recognizes
+ * the text of images and scanned PDFs with the engine configured here.
+ *
+ * @module types/ocr
+ */
+
+/**
+ * Text recognition engine
+ */
+export type OcrEngine = 'tesseract' | 'ollama';
+
+/**
+ * OCR engine settings (`settings:ocr`)
+ */
+export interface OcrSettings {
+	/** Recognition engine: tesseract command line or an Ollama vision model */
+	engine: OcrEngine;
+	/** Tesseract language packs used by default (e.g., ["eng", "fra"]) */
+	languages: string[];
+	/** Path of the tesseract executable, null to find it in the PATH */
+	tesseract_path: string | null;
+	/** Base URL of the Ollama server (e.g., "http://localhost:11434") */
+	ollama_url: string;
+	/** Vision model transcribing the images (e.g., "llama3.2-vision") */
+	ollama_model: string;
+}
@@ -0,0 +1,54 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Persistence outbox type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/outbox.rs`. Records whose database
+ * write failed during a workflow execution are queued in a durable outbox
+ * and written again later.
+ *
+ * @module types/outbox
+ */
+
+/**
+ * Payload of the `persistence_warning` event (a record was queued in the outbox)
+ */
+export interface PersistenceWarning {
+	/** Target table */
+	table: string;
+	/** Record ID */
+	record_id: string;
+	/** Database error */
+	error: string;
+	/** Records waiting in the outbox, including this one */
+	pending: number;
+}
+
+/**
+ * Result of the `flush_outbox` command
+ */
+export interface OutboxFlushResult {
+	/** Records written to the database */
+	flushed: number;
+	/** Records still waiting in the outbox */
+	remaining: number;
+	/** Error of the last failed record, if any */
+	last_error?: string;
+}
@@ -0,0 +1,62 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Tool plugin type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/plugin.rs`. Plugins are
+ * WebAssembly components installed in `~/.zileo/plugins`; agents use them
+ * as the tool `plugin__<id>` once they are enabled in the active profile.
+ *
+ * @module types/plugin
+ */
+
+/**
+ * Host function a plugin can only use once granted
+ * - network: HTTP requests to the hosts listed in the manifest
+ * - read_files: reading local text files in the directories listed in the manifest
+ */
+export type PluginCapability = 'network' | 'read_files';
+
+/**
+ * Installed plugin with its grants in the active profile
+ */
+export interface PluginInfo {
+	/** Plugin ID (directory name in ~/.zileo/plugins) */
+	id: string;
+	/** Tool name given to agents (e.g., "plugin__weather") */
+	tool_name: string;
+	/** Display name from the manifest */
+	name: string;
+	/** Version from the manifest */
+	version: string;
+	/** Description from the manifest */
+	description: string;
+	/** Capabilities requested by the manifest */
+	requested_capabilities: PluginCapability[];
+	/** Hosts the plugin requests network access to */
+	network_hosts: string[];
+	/** Directories the plugin requests read access to */
+	read_dirs: string[];
+	/** Capabilities granted in the active profile */
+	granted_capabilities: PluginCapability[];
+	/** Whether agents of the active profile can use the plugin */
+	enabled: boolean;
+	/** Why the plugin cannot be used (invalid manifest, modified since approved...) */
+	error: string | null;
+}
@@ -0,0 +1,59 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Profile type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/profile.rs`. A profile (e.g. work,
+ * personal) is an isolated data set with its own database.
+ *
+ * @module types/profile
+ */
+
+/**
+ * Profile
+ */
+export interface Profile {
+	/** Profile ID (derived from the name) */
+	id: string;
+	/** Display name */
+	name: string;
+	/** Creation timestamp (ISO 8601) */
+	created_at: string;
+}
+
+/**
+ * Profiles and the active one
+ */
+export interface ProfileRegistry {
+	/** ID of the active profile */
+	active: string;
+	/** Profiles, in creation order */
+	profiles: Profile[];
+}
+
+/**
+ * Payload of the `profile_switched` event
+ */
+export interface ProfileSwitchedEvent {
+	/** Profile now active */
+	profile: Profile;
+}
+
+/** Event emitted when the active profile changed */
+export const PROFILE_SWITCHED_EVENT = 'profile_switched';
@@ -35,8 +35,11 @@ export interface Prompt {
   category: PromptCategory;
   content: string;           // max 50000 chars, with {{variable}} placeholders
   variables: PromptVariable[]; // Auto-detected from content
+  usage_count: number;       // Times launched via launch_prompt
+  last_used_at?: string;     // ISO 8601, absent if never launched
   created_at: string;        // ISO 8601
   updated_at: string;        // ISO 8601
+  mcp_server?: string;       // MCP server providing the prompt (list_mcp_prompts)
 }
 
 // For list display (lightweight)
@@ -46,7 +49,9 @@ export interface PromptSummary {
   description: string;
   category: PromptCategory;
   variables_count: number;
+  usage_count: number;
   updated_at: string;
+  mcp_server?: string;       // MCP server providing the prompt
 }
 
 // For creation (no id, no timestamps)
@@ -65,8 +70,47 @@ export interface PromptUpdate {
   content?: string;
 }
 
+// Saved state of a prompt (one per create/update)
+export interface PromptVersion {
+  prompt_id: string;
+  version: number;           // Starts at 1
+  name: string;
+  description: string;
+  category: PromptCategory;
+  content: string;
+  created_at: string;        // ISO 8601
+}
+
+// Output of one version in compare_prompt_versions
+export interface PromptVersionRun {
+  version: number;
+  input: string;             // Rendered prompt sent to the agent
+  response: string;
+  success: boolean;
+  error?: string;
+  tokens_input: number;
+  tokens_output: number;
+  cost_usd: number;
+  duration_ms: number;
+}
+
+// Side-by-side outputs of two prompt versions
+export interface PromptComparison {
+  prompt_id: string;
+  agent_id: string;
+  a: PromptVersionRun;
+  b: PromptVersionRun;
+}
+
 // ===== Utility Types =====
 
+// Result of render_prompt
+export interface RenderedPrompt {
+  prompt_id: string;
+  content: string;             // Placeholders without a value are left as-is
+  missing_variables: string[]; // Variables with no value and no default
+}
+
 export interface PromptPreviewParams {
   content: string;
   variables: Record<string, string>;
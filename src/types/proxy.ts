@@ -0,0 +1,48 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Proxy settings type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/proxy.rs`. The proxy applies to the
+ * requests of the LLM providers, the embedding service and the MCP HTTP
+ * transports; loopback hosts (e.g. a local Ollama server) are never proxied.
+ *
+ * @module types/proxy
+ */
+
+/**
+ * Proxy of one target, overriding the global proxy
+ */
+export type ProxyOverride =
+	/** Direct connection (global proxy bypassed) */
+	| { mode: 'direct' }
+	/** Dedicated proxy (http, https, socks5 or socks5h URL) */
+	| { mode: 'custom'; url: string };
+
+/**
+ * Proxy settings of the outgoing requests
+ */
+export interface ProxySettings {
+	/** Global proxy URL (http, https, socks5 or socks5h); null = direct connection */
+	url: string | null;
+	/** Hosts reached without the global proxy ("example.com" also matches its subdomains) */
+	no_proxy: string[];
+	/** Per-target overrides, keyed by provider name, "embedding" or "mcp" */
+	overrides: Record<string, ProxyOverride>;
+}
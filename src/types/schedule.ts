@@ -0,0 +1,80 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Scheduled run type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/schedule.rs`. A schedule runs a
+ * prompt with an agent in a workflow on a cron expression; the runs are
+ * stored as regular messages of the workflow.
+ *
+ * @module types/schedule
+ */
+
+/**
+ * Outcome of the last run of a schedule
+ */
+export type ScheduleRunStatus = 'success' | 'failed';
+
+/**
+ * Scheduled workflow run
+ */
+export interface Schedule {
+	/** Schedule ID (UUID) */
+	id: string;
+	/** Schedule name */
+	name: string;
+	/** Cron expression (local time), e.g. `0 9 * * mon-fri` or `@daily` */
+	cron: string;
+	/** Agent executing the prompt */
+	agent_id: string;
+	/** Workflow receiving the messages of the runs */
+	workflow_id: string;
+	/** Prompt sent at each run */
+	prompt: string;
+	/** Paused schedules do not run */
+	paused: boolean;
+	/** Next run (null when paused) */
+	next_run_at: string | null;
+	/** Start of the last run */
+	last_run_at: string | null;
+	/** Outcome of the last run */
+	last_status: ScheduleRunStatus | null;
+	/** Error of the last run, if it failed */
+	last_error: string | null;
+	/** Creation timestamp */
+	created_at: string;
+}
+
+/**
+ * Payload of the `schedule_run` event, emitted when a run finishes
+ */
+export interface ScheduleRunEvent {
+	/** Schedule ID */
+	schedule_id: string;
+	/** Workflow that received the messages */
+	workflow_id: string;
+	/** Outcome of the run */
+	status: ScheduleRunStatus;
+	/** Error, if the run failed */
+	error: string | null;
+}
+
+/** Event emitted when a scheduled run finishes */
+export const SCHEDULE_RUN_EVENT = 'schedule_run';
@@ -0,0 +1,100 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Full-text search type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/search.rs`. Matches come back as
+ * segments (never as markup), so stored content is never rendered as HTML.
+ *
+ * @module types/search
+ */
+
+/**
+ * Search terms and pagination
+ */
+export interface SearchQuery {
+	/** Keywords (all must match) */
+	text: string;
+	/** Maximum number of hits (default 20, max 100) */
+	limit?: number;
+	/** Number of hits to skip */
+	offset?: number;
+}
+
+/**
+ * Part of a highlighted text
+ */
+export interface HighlightSegment {
+	/** Text of the segment */
+	text: string;
+	/** Whether the segment matches a search keyword */
+	matched: boolean;
+}
+
+/**
+ * Message matching a search
+ */
+export interface MessageSearchHit {
+	/** Message ID */
+	message_id: string;
+	/** Workflow of the message */
+	workflow_id: string;
+	/** Name of the workflow (null if it no longer exists) */
+	workflow_name: string | null;
+	/** Message role */
+	role: 'user' | 'assistant' | 'system';
+	/** Message timestamp (ISO 8601) */
+	timestamp: string;
+	/** Relevance, higher is better */
+	score: number;
+	/** Excerpt of the content around the first match */
+	snippet: HighlightSegment[];
+}
+
+/**
+ * Workflow whose name matches a search
+ */
+export interface WorkflowSearchHit {
+	/** Workflow ID */
+	workflow_id: string;
+	/** Workflow name, with the matches highlighted */
+	name: HighlightSegment[];
+	/** Agent of the workflow */
+	agent_id: string;
+	/** Last activity (ISO 8601) */
+	updated_at: string;
+	/** Relevance, higher is better */
+	score: number;
+}
+
+/**
+ * Page of search hits, most relevant first
+ */
+export interface SearchResults<T> {
+	/** Hits in the current page */
+	hits: T[];
+	/** Total number of hits */
+	total: number;
+	/** Current offset (number of hits skipped) */
+	offset: number;
+	/** Page size limit */
+	limit: number;
+	/** Whether more hits are available after this page */
+	has_more: boolean;
+}
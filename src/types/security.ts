@@ -35,6 +35,7 @@ export type LLMProvider =
 	| 'OpenAI'
 	| 'Anthropic'
 	| 'Google'
+	| 'Gemini'
 	| 'Cohere'
 	| 'HuggingFace';
 
@@ -55,3 +56,29 @@ export interface SecuritySettings {
 	/** List of providers with stored API keys */
 	configuredProviders: string[];
 }
+
+/**
+ * Age and expiry status of a stored API key.
+ *
+ * Synchronized with `ApiKeyStatus` in `src-tauri/src/models/api_key.rs`.
+ */
+export type ApiKeyAgeStatus = 'ok' | 'stale' | 'expiring_soon' | 'expired';
+
+/**
+ * Metadata of a stored API key, as returned by `list_api_key_metadata`
+ * and `rotate_api_key`.
+ */
+export interface ApiKeyInfo {
+	/** The provider name */
+	provider: string;
+	/** Date the current key was saved (ISO 8601) */
+	created_at: string;
+	/** Date the key was last used, at hour resolution (ISO 8601) */
+	last_used: string | null;
+	/** Expiry date set by the user (ISO 8601) */
+	expires_at: string | null;
+	/** Date the key was last rotated (ISO 8601) */
+	rotated_at: string | null;
+	/** Status at the time of the request */
+	status: ApiKeyAgeStatus;
+}
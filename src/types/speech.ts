@@ -0,0 +1,140 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Speech transcription type definitions.
+ *
+ * Synchronized with `src-tauri/src/speech/mod.rs`. Voice messages are
+ * transcribed by a local Whisper server and sent as user messages.
+ *
+ * @module types/speech
+ */
+
+/**
+ * API of the transcription server
+ */
+export type SpeechBackend = 'whisper_cpp' | 'open_ai_compatible';
+
+/**
+ * Transcription server settings (`settings:speech`)
+ */
+export interface SpeechSettings {
+	/** Server API: whisper.cpp server (`/inference`) or OpenAI-compatible (`/v1/audio/transcriptions`) */
+	backend: SpeechBackend;
+	/** Base URL of the server (e.g., "http://127.0.0.1:8080") */
+	endpoint: string;
+	/** Model name sent to OpenAI-compatible servers */
+	model?: string | null;
+	/** Default spoken language (ISO 639-1 code), null for auto-detection */
+	language?: string | null;
+	/** Prepare a read-aloud version of each assistant response when it is saved */
+	read_aloud?: boolean;
+}
+
+/**
+ * Result of a transcription
+ */
+export interface Transcription {
+	/** Transcribed text */
+	text: string;
+	/** Language detected by the server, if reported */
+	language?: string;
+	/** Audio duration in seconds, if reported */
+	duration_secs?: number;
+}
+
+/**
+ * Read-aloud version of a message, stored next to its Markdown content
+ */
+export interface ReadAloud {
+	/** Plain text without Markdown, code blocks or abbreviations */
+	text: string;
+	/** Same content as an SSML document */
+	ssml: string;
+}
+
+/**
+ * Text-to-speech backend
+ */
+export type TtsBackend = 'piper' | 'open_ai';
+
+/**
+ * Text-to-speech settings (`settings:tts`)
+ */
+export interface TtsSettings {
+	/** Backend: local piper HTTP server or OpenAI speech API (or a compatible server) */
+	backend: TtsBackend;
+	/** Base URL of the server (e.g., "http://127.0.0.1:5000") */
+	endpoint: string;
+	/** Model sent to OpenAI (e.g., "tts-1") */
+	model?: string | null;
+	/** Default voice (piper voice name or OpenAI voice) */
+	voice?: string | null;
+	/** Default speaking rate (1.0 is the natural rate) */
+	speed?: number | null;
+}
+
+/**
+ * Voice settings of a workflow, override the text-to-speech settings
+ */
+export interface VoiceSettings {
+	/** Voice */
+	voice?: string | null;
+	/** Speaking rate (0.25 to 4.0) */
+	speed?: number | null;
+}
+
+/**
+ * Kind of a `speech_audio` event
+ */
+export type SpeechAudioKind = 'audio' | 'segment_end' | 'done' | 'error';
+
+/**
+ * Audio streamed by `synthesize_speech` (`speech_audio` event)
+ */
+export interface SpeechAudioChunk {
+	/** Synthesis request ID */
+	request_id: string;
+	/** Event kind */
+	kind: SpeechAudioKind;
+	/** Segment index */
+	segment: number;
+	/** Audio type of the segment */
+	mime_type: string;
+	/** Audio bytes as base64 (audio events) */
+	data?: string;
+	/** Error message (error events) */
+	error?: string;
+}
+
+/**
+ * Outcome of a synthesis
+ */
+export interface SpeechSynthesis {
+	/** Synthesis request ID */
+	request_id: string;
+	/** Segments synthesized */
+	segments: number;
+	/** Audio bytes streamed */
+	bytes: number;
+	/** Audio type of the segments */
+	mime_type: string;
+	/** Whether synthesis was stopped before the end */
+	stopped: boolean;
+}
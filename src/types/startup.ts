@@ -0,0 +1,67 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Startup progress type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/startup.rs`. The status is the
+ * payload of the `startup_progress` event and of the `get_startup_status` command.
+ *
+ * @module types/startup
+ */
+
+/**
+ * Initialization phase of the application
+ */
+export type StartupPhase =
+	| 'database'
+	| 'providers'
+	| 'embedding'
+	| 'models'
+	| 'mcp_servers'
+	| 'agents';
+
+/**
+ * State of a startup phase
+ */
+export type StartupPhaseStatus = 'pending' | 'running' | 'completed' | 'failed';
+
+/**
+ * Progress of a single startup phase
+ */
+export interface StartupPhaseProgress {
+	/** Phase */
+	phase: StartupPhase;
+	/** Current state */
+	status: StartupPhaseStatus;
+	/** Duration in milliseconds (set once finished) */
+	duration_ms?: number;
+	/** Error message (set when failed) */
+	error?: string;
+}
+
+/**
+ * Startup progress of the application
+ */
+export interface StartupStatus {
+	/** Progress of each phase, in display order */
+	phases: StartupPhaseProgress[];
+	/** True once every phase has finished (completed or failed) */
+	ready: boolean;
+}
@@ -37,6 +37,7 @@ export type ChunkType =
   | 'token'
   | 'tool_start'
   | 'tool_end'
+  | 'tool_progress'
   | 'reasoning'
   | 'error'
   | 'sub_agent_start'
@@ -46,6 +47,8 @@ export type ChunkType =
   | 'task_create'
   | 'task_update'
   | 'task_complete'
+  | 'llm_retry'
+  | 'possibly_stuck'
   | 'user_question_start'
   | 'user_question_complete';
 
@@ -73,11 +76,11 @@ export interface StreamChunk {
   workflow_id: string;
   /** Type of chunk content */
   chunk_type: ChunkType;
-  /** Text content (for token/reasoning/error/sub_agent chunks) */
+  /** Text content (for token/reasoning/error/sub_agent chunks, current phase for possibly_stuck chunks) */
   content?: string;
   /** Tool name (for tool_start/tool_end chunks) */
   tool?: string;
-  /** Duration in milliseconds (for tool_end/sub_agent_complete/sub_agent_error/task_complete chunks) */
+  /** Duration in milliseconds (for tool_end/sub_agent_complete/sub_agent_error/task_complete chunks, delay before the retry for llm_retry chunks, silence for possibly_stuck chunks) */
   duration?: number;
   /** Sub-agent ID (for sub_agent_* chunks) */
   sub_agent_id?: string;
@@ -87,7 +90,7 @@ export interface StreamChunk {
   parent_agent_id?: string;
   /** Sub-agent metrics (for sub_agent_complete chunks) */
   metrics?: SubAgentStreamMetrics;
-  /** Progress percentage 0-100 (for sub_agent_progress chunks) */
+  /** Progress percentage 0-100 (for sub_agent_progress/tool_progress chunks) */
   progress?: number;
   /** Task ID (for task_* chunks) */
   task_id?: string;
@@ -103,6 +106,10 @@ export interface StreamChunk {
   tokens_delta?: number;
   /** Cumulative token count (running total) */
   tokens_total?: number;
+  /** Retry number, 1-indexed (for llm_retry chunks) */
+  retry_attempt?: number;
+  /** Maximum number of retries (for llm_retry chunks) */
+  max_retries?: number;
 }
 
 /**
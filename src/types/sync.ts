@@ -0,0 +1,96 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Settings sync type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/settings_sync.rs`. Two machines of
+ * the user are paired with a code (or QR code) and exchange their agents,
+ * prompts and MCP servers end-to-end encrypted through a mailbox server.
+ *
+ * @module types/sync
+ */
+
+import type { ImportCounts, ImportError } from './importExport';
+
+/**
+ * Side of a pairing
+ */
+export type SyncRole = 'initiator' | 'responder';
+
+/**
+ * How sealed payloads travel between the machines
+ */
+export type SyncTransport = 'relay' | 'lan';
+
+/**
+ * Pairing with another machine
+ */
+export interface SyncPairing {
+	/** Pairing ID (shared by both machines) */
+	id: string;
+	/** Name of this machine */
+	device_name: string;
+	/** Name of the other machine, once known */
+	peer_name?: string | null;
+	/** Side of this machine */
+	role: SyncRole;
+	/** Payload transport */
+	transport: SyncTransport;
+	/** Base URL of the mailbox server */
+	mailbox_url: string;
+	/** Creation timestamp (ISO 8601) */
+	created_at: string;
+	/** Last successful sync (ISO 8601) */
+	last_synced_at?: string | null;
+	/** Creation time of the last payload applied from the peer (ISO 8601) */
+	last_received_at?: string | null;
+}
+
+/**
+ * New pairing with its code, to enter on the other machine
+ */
+export interface SyncPairingOffer {
+	/** The stored pairing */
+	pairing: SyncPairing;
+	/** Pairing code (contains the pairing key: never log it) */
+	code: string;
+	/** Pairing code as an SVG QR code */
+	qr_svg: string;
+}
+
+/**
+ * Outcome of a sync
+ */
+export interface SyncResult {
+	/** Pairing ID */
+	pairing_id: string;
+	/** Entities sent to the peer */
+	pushed: ImportCounts;
+	/** Whether a new payload of the peer was applied */
+	received: boolean;
+	/** Entities created or updated from the peer payload */
+	imported: ImportCounts;
+	/** Entities of the peer payload left unchanged */
+	skipped: ImportCounts;
+	/** Entity import errors */
+	errors: ImportError[];
+	/** Sync timestamp (ISO 8601) */
+	synced_at: string;
+}
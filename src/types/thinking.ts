@@ -55,6 +55,20 @@ export interface ThinkingStep {
 	created_at: string;
 }
 
+/**
+ * Page of the thinking steps of a workflow (matches Rust ThinkingStepPage)
+ */
+export interface ThinkingStepPage {
+  /** Steps in the current page (chronological order) */
+  steps: ThinkingStep[];
+  /** Cursor of the next page (null on the last page) */
+  next_cursor: string | null;
+  /** Whether more steps are available after this page */
+  has_more: boolean;
+  /** Whether the contents were shortened to a preview */
+  summary: boolean;
+}
+
 /**
  * Active thinking step for real-time display during streaming
  *
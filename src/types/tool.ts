@@ -23,6 +23,7 @@
  * These types are synchronized with Rust backend types:
  * - src-tauri/src/models/tool_execution.rs (ToolExecution, ToolExecutionCreate)
  * - src-tauri/src/models/workflow.rs (WorkflowToolExecution)
+ * - src-tauri/src/models/execution_trace.rs (ExecutionTrace)
  *
  * Phase 3: Tool Execution Persistence
  *
@@ -104,10 +105,45 @@ export interface ToolExecution {
 	duration_ms: number;
 	/** Iteration number in tool loop (0-indexed) */
 	iteration: number;
+	/** Previous execution of the same call (same tool and input) in the workflow */
+	previous_execution_id?: string;
+	/** Output changes since the previous execution (empty = unchanged) */
+	result_diff?: ToolResultDiffOp[];
+	/** Unix time (ms) at which the execution started (absent for older records) */
+	started_at_ms?: number;
 	/** Timestamp when recorded */
 	created_at: string;
 }
 
+/**
+ * Page of the tool executions of a workflow (matches Rust ToolExecutionPage)
+ */
+export interface ToolExecutionPage {
+  /** Executions in the current page (chronological order) */
+  executions: ToolExecution[];
+  /** Cursor of the next page (null on the last page) */
+  next_cursor: string | null;
+  /** Whether more executions are available after this page */
+  has_more: boolean;
+  /** Whether the payloads (input, output, result diff) were omitted */
+  summary: boolean;
+}
+
+/**
+ * Change of a tool output relative to the previous execution of the same call
+ * (JSON pointer paths, arrays compared by index)
+ */
+export interface ToolResultDiffOp {
+	/** Operation kind */
+	op: 'add' | 'remove' | 'replace';
+	/** JSON pointer of the changed value */
+	path: string;
+	/** New value (add, replace) */
+	value?: unknown;
+	/** Previous value (remove, replace) */
+	old?: unknown;
+}
+
 /**
  * Tool execution data from workflow result (IPC-friendly version)
  * Matches Rust WorkflowToolExecution
@@ -230,3 +266,64 @@ export function getToolIdentifier(execution: ToolExecution | WorkflowToolExecuti
 	}
 	return execution.tool_name;
 }
+
+/**
+ * Kind of node in an execution trace
+ */
+export type TraceNodeKind = 'llm_turn' | 'tool_call' | 'response';
+
+/**
+ * Node of an execution trace graph
+ */
+export interface TraceNode {
+  /** Node ID (unique within the trace) */
+  id: string;
+  /** Node kind */
+  kind: TraceNodeKind;
+  /** Display label (turn number, tool name) */
+  label: string;
+  /** Tool loop iteration the node belongs to */
+  iteration: number;
+  /** Duration in milliseconds (absent when not recorded) */
+  duration_ms?: number;
+  /** Input tokens (LLM turns only) */
+  tokens_input?: number;
+  /** Output tokens (LLM turns only) */
+  tokens_output?: number;
+  /** Whether the tool call succeeded (tool calls only) */
+  success?: boolean;
+  /** Tool execution record ID (tool calls only) */
+  tool_execution_id?: string;
+}
+
+/**
+ * Directed edge between two trace nodes
+ */
+export interface TraceEdge {
+  /** Source node ID */
+  from: string;
+  /** Target node ID */
+  to: string;
+}
+
+/**
+ * Execution flow graph of a single assistant message (get_execution_trace)
+ */
+export interface ExecutionTrace {
+  /** Workflow ID */
+  workflow_id: string;
+  /** Assistant message ID */
+  message_id: string;
+  /** Graph nodes in execution order */
+  nodes: TraceNode[];
+  /** Graph edges */
+  edges: TraceEdge[];
+  /** Number of tool loop iterations */
+  iterations: number;
+  /** Sum of node durations in milliseconds */
+  total_duration_ms: number;
+  /** Sum of LLM turn input tokens */
+  tokens_input: number;
+  /** Sum of LLM turn output tokens */
+  tokens_output: number;
+}
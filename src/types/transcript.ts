@@ -0,0 +1,42 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+/**
+ * Workflow transcript export type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/transcript.rs`.
+ *
+ * @module types/transcript
+ */
+
+/**
+ * Output format of a transcript (PDF needs a Chromium-based browser)
+ */
+export type TranscriptFormat = 'markdown' | 'html' | 'pdf';
+
+/**
+ * What a transcript contains
+ */
+export interface TranscriptOptions {
+	/** Output format */
+	format: TranscriptFormat;
+	/** Include the thinking steps of assistant messages */
+	include_thinking: boolean;
+	/** Include tool inputs and outputs (tool names and outcomes are always listed) */
+	include_tool_payloads: boolean;
+}
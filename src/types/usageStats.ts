@@ -0,0 +1,88 @@
+/**
+ * Copyright 2025 Assistance Micro Design
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+// Copyright 2025 Zileo-Chat-3 Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+/**
+ * Team usage statistics type definitions.
+ *
+ * Synchronized with `src-tauri/src/models/usage_stats.rs`. The report is
+ * generated locally: usage is grouped in weekly buckets whose counts are
+ * clipped per conversation and noised, without any per-conversation detail.
+ *
+ * @module types/usageStats
+ */
+
+/**
+ * LLM usage of a week for a provider and model
+ */
+export interface UsageBucket {
+	/** Monday of the week (UTC, YYYY-MM-DD) */
+	week: string;
+	/** Provider name */
+	provider: string;
+	/** Model name */
+	model: string;
+	/** Conversations using the model (noised) */
+	conversations: number;
+	/** Assistant responses (noised) */
+	messages: number;
+	/** Input and output tokens (noised, rounded to the thousand) */
+	tokens: number;
+	/** Estimated spend in USD (noised, rounded to the cent) */
+	spend_usd: number;
+}
+
+/**
+ * Tool usage of a week
+ */
+export interface ToolUsageBucket {
+	/** Monday of the week (UTC, YYYY-MM-DD) */
+	week: string;
+	/** Tool type */
+	tool_type: 'local' | 'mcp';
+	/** Tool name */
+	tool_name: string;
+	/** Conversations calling the tool (noised) */
+	conversations: number;
+	/** Tool calls (noised) */
+	calls: number;
+	/** Failed tool calls (noised) */
+	failures: number;
+}
+
+/**
+ * Aggregated usage report for a team, generated locally
+ */
+export interface TeamUsageReport {
+	/** Report format version */
+	format_version: number;
+	/** Generation timestamp */
+	generated_at: string;
+	/** First day covered (YYYY-MM-DD) */
+	period_start: string;
+	/** Last day covered (YYYY-MM-DD) */
+	period_end: string;
+	/** Privacy budget of each bucket (lower is noisier) */
+	epsilon: number;
+	/** LLM usage buckets */
+	usage: UsageBucket[];
+	/** Tool usage buckets */
+	tools: ToolUsageBucket[];
+	/** Buckets left out because too few conversations contributed to them */
+	suppressed_buckets: number;
+}
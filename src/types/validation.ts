@@ -25,7 +25,14 @@ export type ValidationMode = 'auto' | 'manual' | 'selective';
 /**
  * Type of operation requiring validation
  */
-export type ValidationType = 'tool' | 'sub_agent' | 'mcp' | 'file_op' | 'db_op';
+export type ValidationType =
+  | 'tool'
+  | 'sub_agent'
+  | 'mcp'
+  | 'file_op'
+  | 'db_op'
+  | 'token_anomaly'
+  | 'tool_first_use';
 
 /**
  * Risk level of the operation
@@ -93,12 +100,90 @@ export interface ParallelValidationDetails {
   [key: string]: unknown;
 }
 
+/**
+ * Record listed in a deletion preview
+ */
+export interface PreviewRow {
+  /** Record ID */
+  id: string;
+  /** Short description of the record */
+  summary: string;
+}
+
+/**
+ * Field updated in a change preview
+ */
+export interface FieldChange {
+  /** Field name */
+  field: string;
+  /** Current value */
+  before: unknown;
+  /** Value after the call */
+  after: unknown;
+}
+
+/**
+ * Preview of the changes a tool call will make (`details.preview`)
+ */
+export type ChangePreview =
+  | {
+      kind: 'rows';
+      /** Table of the deleted records */
+      table: string;
+      /** Number of affected records */
+      total: number;
+      /** First affected records */
+      rows: PreviewRow[];
+    }
+  | {
+      kind: 'change';
+      /** Table of the updated record */
+      table: string;
+      /** Record ID */
+      id: string;
+      /** Readable name of the record */
+      label: string;
+      /** Updated fields */
+      fields: FieldChange[];
+    }
+  | {
+      kind: 'diff';
+      /** Written file path */
+      path: string;
+      /** Whether the file does not exist yet */
+      new_file: boolean;
+      /** Number of added lines */
+      additions: number;
+      /** Number of deleted lines */
+      deletions: number;
+      /** Unified diff hunks */
+      diff: string;
+      /** Whether the diff was cut to the preview limit */
+      truncated: boolean;
+    }
+  | {
+      kind: 'statement';
+      /** Statement that will be run */
+      statement: string;
+    }
+  | {
+      kind: 'request';
+      /** HTTP method */
+      method: string;
+      /** Request URL */
+      url: string;
+      /** Request body, if any */
+      body: string | null;
+    };
+
 /**
  * Generic validation details for operations without specific structure
  */
 export interface GenericValidationDetails {
   /** Optional file path for file operations */
   path?: string;
+  /** Preview of the changes (destructive tool calls) */
+  preview?: ChangePreview;
   /** Rejection reason (added when status changes to rejected) */
   rejection_reason?: string;
   /** Allow additional custom fields */
@@ -181,12 +266,47 @@ export interface RiskThresholdConfig {
  * Audit/logging configuration
  */
 export interface AuditConfig {
-  /** Enable validation decision logging */
+  /** Record sensitive actions in the audit log */
   enableLogging: boolean;
   /** Log retention in days (7-90) */
   retentionDays: number;
 }
 
+/**
+ * External channel receiving pending validation requests
+ */
+export type NotificationChannelType = 'webhook' | 'ntfy' | 'gotify';
+
+/**
+ * Notification channel configuration
+ */
+export interface NotificationChannel {
+  /** Channel type */
+  type: NotificationChannelType;
+  /** Webhook endpoint, ntfy topic URL or Gotify server URL */
+  url: string;
+  /** Bearer token (webhook/ntfy) or application token (Gotify, required) */
+  token?: string;
+  /** Whether requests are forwarded to this channel */
+  enabled: boolean;
+}
+
+/**
+ * Forwarding of pending validation requests with signed approve/reject links
+ */
+export interface ValidationNotificationConfig {
+  /** Forward pending requests to the channels */
+  enabled: boolean;
+  /** Configured channels */
+  channels: NotificationChannel[];
+  /** Address the callback listener binds to (default: 127.0.0.1) */
+  callbackBindAddress: string;
+  /** Port of the callback listener (0 = random) */
+  callbackPort: number;
+  /** Base URL used in links when the listener is behind a proxy or tunnel */
+  publicBaseUrl?: string;
+}
+
 /**
  * Main validation settings configuration
  */
@@ -209,6 +329,8 @@ export interface ValidationSettingsConfig {
  * Validation settings with metadata
  */
 export interface ValidationSettings extends ValidationSettingsConfig {
+  /** External notification channels */
+  notifications: ValidationNotificationConfig;
   /** Last update timestamp (ISO 8601) */
   updatedAt: string;
 }
@@ -223,6 +345,8 @@ export interface UpdateValidationSettingsRequest {
   timeoutSeconds?: number;
   timeoutBehavior?: TimeoutBehavior;
   audit?: Partial<AuditConfig>;
+  /** Replaces the notification settings */
+  notifications?: ValidationNotificationConfig;
 }
 
 /**
@@ -260,3 +384,60 @@ export const DEFAULT_VALIDATION_SETTINGS: ValidationSettingsConfig = {
     retentionDays: 30,
   },
 };
+
+/**
+ * Format locale for dates and numbers in system prompts and tool outputs
+ */
+export type FormatLocale = 'en' | 'fr';
+
+/**
+ * Format locale settings (get/update_locale_settings)
+ */
+export interface LocaleSettings {
+  /** Locale used for dates and numbers (default: 'en') */
+  locale: FormatLocale;
+}
+
+/**
+ * Token usage anomaly detection settings (get/update_token_anomaly_settings)
+ */
+export interface TokenAnomalySettings {
+  /** Enable anomaly detection */
+  enabled: boolean;
+  /** Alert when a run uses more than this multiple of the baseline (1.5-100) */
+  threshold_multiplier: number;
+  /** Number of recent runs used to compute the baseline (1-200) */
+  baseline_window: number;
+  /** Minimum number of runs before alerts are raised */
+  min_baseline_runs: number;
+  /** Pause the run and request user confirmation when an anomaly is detected */
+  pause_for_confirmation: boolean;
+}
+
+/**
+ * Stuck tool loop watchdog settings (get/update_watchdog_settings)
+ */
+export interface WatchdogSettings {
+  /** Enable the watchdog */
+  enabled: boolean;
+  /** Seconds without any stream event before the run is reported as possibly stuck (10-3600) */
+  stall_threshold_secs: number;
+}
+
+/**
+ * Payload of the `token_anomaly` event
+ */
+export interface TokenAnomalyEvent {
+  workflow_id: string;
+  agent_id: string;
+  /** Tokens used by the current run so far */
+  tokens_used: number;
+  /** Baseline tokens per run (median of recent runs) */
+  baseline_tokens: number;
+  /** tokens_used / baseline_tokens */
+  ratio: number;
+  /** Configured threshold multiplier */
+  threshold_multiplier: number;
+  /** Whether the run is paused waiting for confirmation */
+  paused: boolean;
+}
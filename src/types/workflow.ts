@@ -17,7 +17,9 @@
 // Copyright 2025 Zileo-Chat-3 Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-import type { Message } from './message';
+import type { ModerationOutcome } from './agent';
+import type { GenerationParams } from './generation';
+import type { ContextUsage, Message } from './message';
 import type { ThinkingStep } from './thinking';
 import type { ToolExecution, WorkflowToolExecution } from './tool';
 
@@ -54,6 +56,94 @@ export interface Workflow {
   model_id?: string;
   /** Current context size (last API call context window usage) */
   current_context_tokens: number;
+  /** Pinned response language (ISO 639-1 code), detected from the first messages or set by the user */
+  response_language?: string | null;
+  /** User-defined tags (used to filter the workflow list) */
+  tags?: string[];
+  /** Sensitive workflow: contents encrypted, readable only while the workflow is open */
+  sensitive?: boolean;
+  /** Dry run: tools with side effects return a simulated result instead of executing */
+  dry_run?: boolean;
+}
+
+/**
+ * Payload of the `workflow_title_updated` event (title generated from the first exchange)
+ */
+export interface WorkflowTitleUpdatedEvent {
+  /** Workflow ID */
+  workflow_id: string;
+  /** New workflow name */
+  title: string;
+}
+
+/** Event emitted when a workflow title is generated */
+export const WORKFLOW_TITLE_UPDATED_EVENT = 'workflow_title_updated';
+
+/**
+ * Sort key of the workflow list
+ */
+export type WorkflowSortBy = 'last_activity' | 'cost' | 'name';
+
+/**
+ * Query of a workflow list page (sorting, filters and pagination)
+ */
+export interface WorkflowListQuery {
+  /** Sort key (default: last_activity) */
+  sort_by?: WorkflowSortBy;
+  /** Ascending order (descending by default) */
+  ascending?: boolean;
+  /** Only workflows with this status */
+  status?: WorkflowStatus | null;
+  /** Only workflows with this tag */
+  tag?: string | null;
+  /** Only workflows of this agent */
+  agent_id?: string | null;
+  /** Only workflows created at or after this date (ISO 8601) */
+  created_after?: string | null;
+  /** Only workflows created before this date (ISO 8601) */
+  created_before?: string | null;
+  /** Page size (default 50, capped at 200) */
+  limit?: number;
+  /** Number of workflows to skip */
+  offset?: number;
+}
+
+/**
+ * Tag of the workflow catalog, with the number of workflows using it
+ */
+export interface WorkflowTag {
+  /** Tag name (trimmed, lowercase) */
+  name: string;
+  /** Display color (e.g. `#3b82f6`) */
+  color?: string | null;
+  /** Number of workflows carrying the tag */
+  workflow_count: number;
+}
+
+/**
+ * Outcome of a bulk tag operation
+ */
+export interface BulkTagResult {
+  /** Number of workflows updated */
+  updated: number;
+  /** Workflows left untouched (not found, or the tag limit would be exceeded) */
+  skipped: string[];
+}
+
+/**
+ * Page of the workflow list
+ */
+export interface PaginatedWorkflows {
+  /** Workflows in the current page */
+  workflows: Workflow[];
+  /** Total number of workflows matching the filters */
+  total: number;
+  /** Current offset (number of workflows skipped) */
+  offset: number;
+  /** Page size limit */
+  limit: number;
+  /** Whether more workflows are available after this page */
+  has_more: boolean;
 }
 
 /**
@@ -72,6 +162,16 @@ export interface WorkflowResult {
   mcp_calls: string[];
   /** Detailed tool execution data for persistence and display */
   tool_executions: WorkflowToolExecution[];
+  /** Moderation check result (absent when the agent has no enabled policy) */
+  moderation?: ModerationOutcome;
+  /** Context window utilization of the run (stored with the assistant message) */
+  context_usage?: ContextUsage;
+  /** Behavior changelog position in effect when the run started (stored with the assistant message) */
+  behavior_position?: number;
+  /** Generation parameters used for the run (stored with the assistant message) */
+  generation?: GenerationParams;
+  /** Image paths produced by tools: ChartTool charts and ScreenshotTool captures (attached to the assistant message) */
+  images?: string[];
 }
 
 /**
@@ -114,6 +214,29 @@ export interface WorkflowFullState {
   thinking_steps: ThinkingStep[];
 }
 
+/**
+ * Read-only HTML snapshot of a workflow.
+ *
+ * Returned by `create_workflow_snapshot`. The page is self-contained and
+ * has secrets redacted; `url` and `expires_at` are set when it is served.
+ */
+export interface WorkflowSnapshot {
+  /** Workflow ID */
+  workflow_id: string;
+  /** Workflow name */
+  workflow_name: string;
+  /** Self-contained HTML page */
+  html: string;
+  /** Number of messages in the transcript */
+  message_count: number;
+  /** Number of tool executions in the transcript */
+  tool_execution_count: number;
+  /** Temporary localhost URL serving the page */
+  url?: string;
+  /** Time at which the temporary server stops (ISO 8601) */
+  expires_at?: string;
+}
+
 /**
  * Token display data for real-time monitoring.
  *